@@ -0,0 +1,36 @@
+//! Feeds arbitrary text to the diff parser.
+//!
+//! Run with `cargo +nightly fuzz run diff_parser` from the repository root.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use octorus::diff::{
+    get_line_info, line_number_to_position, nearest_new_line_number, new_line_content,
+    parse_unified_diff, patch_warnings, validate_multiline_range,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(patch) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let line_count = patch.lines().count();
+    for index in 0..=line_count {
+        let info = get_line_info(patch, index);
+        assert_eq!(info.is_some(), index < line_count);
+        let _ = nearest_new_line_number(patch, index);
+        let _ = validate_multiline_range(patch, index, index + 2);
+
+        // Every commentable line must round-trip through its new-file line number
+        if let Some(line) = info.and_then(|info| info.new_line_number) {
+            assert!(new_line_content(patch, line).is_some());
+            let _ = line_number_to_position(patch, line);
+        }
+    }
+
+    let _ = patch_warnings(patch);
+    for (_, file_patch) in parse_unified_diff(patch) {
+        let _ = patch_warnings(&file_patch);
+    }
+});
@@ -0,0 +1,282 @@
+//! オフラインの脆弱性データベース（OSV 形式）の照会
+//!
+//! `[dependencies] advisory_db` に指定したディレクトリ配下の OSV JSON
+//! （<https://osv.dev> の一括ダウンロードや RustSec の OSV エクスポート）を読み込み、
+//! 依存の追加・更新で入るバージョンに既知の脆弱性がないかを調べる。
+
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use crate::manifest_diff::{base_version, compare_versions, Ecosystem};
+
+/// 読み込むファイル数の上限（巨大なディレクトリを誤って指定したときの保険）
+const MAX_FILES: usize = 200_000;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advisory {
+    pub id: String,
+    pub summary: String,
+}
+
+#[derive(Debug, Clone)]
+struct AffectedRange {
+    introduced: Option<String>,
+    /// このバージョン以降は修正済み
+    fixed: Option<String>,
+    /// このバージョンまでが影響を受ける
+    last_affected: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+struct Entry {
+    advisory: Advisory,
+    versions: Vec<String>,
+    ranges: Vec<AffectedRange>,
+}
+
+#[derive(Debug, Default)]
+pub struct AdvisoryDb {
+    /// (OSV のエコシステム名, パッケージ名) → 該当するアドバイザリ
+    entries: HashMap<(String, String), Vec<Entry>>,
+}
+
+impl AdvisoryDb {
+    /// ディレクトリ配下の `*.json` をすべて読み込む。読めないファイルは飛ばす
+    pub fn load(dir: &Path) -> Result<Self> {
+        let mut db = Self::default();
+        let mut pending = vec![dir.to_path_buf()];
+        let mut files = 0;
+        while let Some(dir) = pending.pop() {
+            let entries = std::fs::read_dir(&dir)
+                .with_context(|| format!("Failed to read advisory database {}", dir.display()))?;
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.is_dir() {
+                    pending.push(path);
+                } else if path.extension().is_some_and(|e| e == "json") && files < MAX_FILES {
+                    files += 1;
+                    if let Ok(text) = std::fs::read_to_string(&path) {
+                        db.add_json(&text);
+                    }
+                }
+            }
+        }
+        Ok(db)
+    }
+
+    /// OSV JSON 1 件を追加する。形式が違えば何もしない
+    pub fn add_json(&mut self, text: &str) {
+        let Ok(osv) = serde_json::from_str::<OsvRecord>(text) else {
+            return;
+        };
+        let advisory = Advisory {
+            summary: osv
+                .summary
+                .or(osv
+                    .details
+                    .map(|d| d.lines().next().unwrap_or_default().to_string()))
+                .unwrap_or_default(),
+            id: osv.id,
+        };
+        for affected in osv.affected {
+            let Some(package) = affected.package else {
+                continue;
+            };
+            let ranges = affected
+                .ranges
+                .iter()
+                .filter(|r| r.kind != "GIT")
+                .flat_map(|r| collect_ranges(&r.events))
+                .collect();
+            self.entries
+                .entry((package.ecosystem, package.name))
+                .or_default()
+                .push(Entry {
+                    advisory: advisory.clone(),
+                    versions: affected.versions,
+                    ranges,
+                });
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// パッケージのバージョンに該当するアドバイザリ。
+    /// バージョン要求（`^1.2` など）は下限のバージョンで照会する
+    pub fn lookup(&self, ecosystem: Ecosystem, name: &str, version: &str) -> Vec<&Advisory> {
+        let Some(entries) = self
+            .entries
+            .get(&(ecosystem.osv_name().to_string(), name.to_string()))
+        else {
+            return Vec::new();
+        };
+        let version = base_version(version);
+        entries
+            .iter()
+            .filter(|entry| {
+                entry.versions.iter().any(|v| base_version(v) == version)
+                    || entry.ranges.iter().any(|r| r.contains(version))
+            })
+            .map(|entry| &entry.advisory)
+            .collect()
+    }
+}
+
+impl AffectedRange {
+    fn contains(&self, version: &str) -> bool {
+        let after_start = self
+            .introduced
+            .as_deref()
+            .is_none_or(|v| v == "0" || compare_versions(version, v).is_ge());
+        let before_end = match (&self.fixed, &self.last_affected) {
+            (Some(fixed), _) => compare_versions(version, fixed).is_lt(),
+            (None, Some(last)) => compare_versions(version, last).is_le(),
+            (None, None) => true,
+        };
+        after_start && before_end
+    }
+}
+
+/// `introduced` から次の `fixed` / `last_affected` までを 1 つの範囲にする
+fn collect_ranges(events: &[OsvEvent]) -> Vec<AffectedRange> {
+    let mut ranges = Vec::new();
+    let mut current: Option<AffectedRange> = None;
+    for event in events {
+        if let Some(ref introduced) = event.introduced {
+            if let Some(open) = current.take() {
+                ranges.push(open);
+            }
+            current = Some(AffectedRange {
+                introduced: Some(introduced.clone()),
+                fixed: None,
+                last_affected: None,
+            });
+        } else if event.fixed.is_some() || event.last_affected.is_some() {
+            let mut range = current.take().unwrap_or(AffectedRange {
+                introduced: None,
+                fixed: None,
+                last_affected: None,
+            });
+            range.fixed = event.fixed.clone();
+            range.last_affected = event.last_affected.clone();
+            ranges.push(range);
+        }
+    }
+    ranges.extend(current);
+    ranges
+}
+
+#[derive(Deserialize)]
+struct OsvRecord {
+    id: String,
+    summary: Option<String>,
+    details: Option<String>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+}
+
+#[derive(Deserialize)]
+struct OsvAffected {
+    package: Option<OsvPackage>,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<OsvEvent>,
+}
+
+#[derive(Deserialize)]
+struct OsvEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RUSTSEC: &str = r#"{
+        "id": "RUSTSEC-2024-0001",
+        "summary": "Use-after-free in foo",
+        "affected": [{
+            "package": {"ecosystem": "crates.io", "name": "foo"},
+            "ranges": [{"type": "SEMVER", "events": [
+                {"introduced": "0"}, {"fixed": "1.2.3"},
+                {"introduced": "2.0.0"}, {"last_affected": "2.1.0"}
+            ]}]
+        }]
+    }"#;
+
+    #[test]
+    fn test_lookup_by_ranges() {
+        let mut db = AdvisoryDb::default();
+        db.add_json(RUSTSEC);
+
+        let ids = |version: &str| -> Vec<String> {
+            db.lookup(Ecosystem::Cargo, "foo", version)
+                .into_iter()
+                .map(|a| a.id.clone())
+                .collect()
+        };
+        assert_eq!(ids("1.2.2"), vec!["RUSTSEC-2024-0001"]);
+        assert!(ids("1.2.3").is_empty());
+        assert_eq!(ids("2.1.0"), vec!["RUSTSEC-2024-0001"]);
+        assert!(ids("2.1.1").is_empty());
+        // バージョン要求は下限で照会する
+        assert_eq!(ids("^1.0"), vec!["RUSTSEC-2024-0001"]);
+        // エコシステムが違えば該当しない
+        assert!(db.lookup(Ecosystem::Npm, "foo", "1.0.0").is_empty());
+    }
+
+    #[test]
+    fn test_lookup_by_versions_and_details_fallback() {
+        let mut db = AdvisoryDb::default();
+        db.add_json(
+            r#"{"id": "GHSA-xxxx", "details": "Prototype pollution\nmore text",
+                "affected": [{"package": {"ecosystem": "npm", "name": "lodash"},
+                              "versions": ["4.17.20"]}]}"#,
+        );
+        db.add_json("not json");
+
+        let found = db.lookup(Ecosystem::Npm, "lodash", "4.17.20");
+        assert_eq!(
+            found,
+            vec![&Advisory {
+                id: "GHSA-xxxx".to_string(),
+                summary: "Prototype pollution".to_string(),
+            }]
+        );
+        assert!(db.lookup(Ecosystem::Npm, "lodash", "4.17.21").is_empty());
+    }
+
+    #[test]
+    fn test_load_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("crates.io")).unwrap();
+        std::fs::write(dir.path().join("crates.io/RUSTSEC-2024-0001.json"), RUSTSEC).unwrap();
+        std::fs::write(dir.path().join("README.md"), "ignored").unwrap();
+
+        let db = AdvisoryDb::load(dir.path()).unwrap();
+        assert_eq!(db.lookup(Ecosystem::Cargo, "foo", "1.0.0").len(), 1);
+        assert!(AdvisoryDb::load(&dir.path().join("missing")).is_err());
+    }
+}
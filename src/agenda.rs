@@ -0,0 +1,346 @@
+//! ペアレビュー用のレビューアジェンダ
+//!
+//! 変更ファイルを AI Rally のコンテキスト計画と同じ基準（ファイルの種類と変更量）で
+//! 並べ、見るべき関数（ハンクヘッダーの関数名）・所要時間の目安・リスクの印を付けた
+//! 一覧を作る。ロックファイル・生成物・vendored 依存は「流し見でよいもの」にまとめる。
+//! Markdown にしてコメントとして投稿したり、ローカルに保存したりする。
+
+use crate::ai::context_planner::FileKind;
+use crate::github::ChangedFile;
+use crate::{manifest_diff, vendored};
+
+/// この行数以上の変更は「大きな変更」として印を付ける
+const LARGE_CHANGE_LINES: u32 = 300;
+
+/// 1 ファイルに挙げる関数の最大数
+const MAX_FUNCTIONS: usize = 5;
+
+/// パスに含まれていたらセキュリティ上注意が必要とみなす語
+const SECURITY_WORDS: &[&str] = &[
+    "auth",
+    "crypt",
+    "password",
+    "permission",
+    "secret",
+    "session",
+    "token",
+    "credential",
+];
+
+/// レビュー時に注意したい点
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiskFlag {
+    /// 認証・暗号・秘密情報まわり
+    Security,
+    /// 依存の追加・更新
+    Dependencies,
+    /// DB のマイグレーション・スキーマ
+    Migration,
+    /// CI・ビルドの設定
+    Build,
+    /// ライセンスファイル
+    License,
+    /// 削除されたファイル
+    Removed,
+    /// patch がない（バイナリや大きすぎるファイル）
+    NoDiff,
+    /// 変更行が多い
+    Large,
+}
+
+impl RiskFlag {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Security => "security-sensitive",
+            Self::Dependencies => "dependencies",
+            Self::Migration => "migration",
+            Self::Build => "build/CI",
+            Self::License => "licence",
+            Self::Removed => "removed",
+            Self::NoDiff => "no diff",
+            Self::Large => "large change",
+        }
+    }
+}
+
+/// アジェンダの 1 項目（1 ファイル）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AgendaItem {
+    pub path: String,
+    pub kind: FileKind,
+    pub additions: u32,
+    pub deletions: u32,
+    /// 所要時間の目安（分）
+    pub minutes: u32,
+    /// 変更のあった関数（ハンクヘッダーの関数名、出現順）
+    pub functions: Vec<String>,
+    pub flags: Vec<RiskFlag>,
+}
+
+/// レビューアジェンダ
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Agenda {
+    /// 見る順に並べたファイル
+    pub items: Vec<AgendaItem>,
+    /// 流し見でよいファイル（ロックファイル・生成物・vendored 依存）
+    pub skim: Vec<String>,
+    /// ソースコードを変更しているのにテストの変更がない
+    pub missing_tests: bool,
+}
+
+impl Agenda {
+    /// 所要時間の目安の合計（分）
+    pub fn total_minutes(&self) -> u32 {
+        self.items.iter().map(|item| item.minutes).sum()
+    }
+}
+
+/// 変更ファイルからアジェンダを作る
+pub fn build(files: &[ChangedFile]) -> Agenda {
+    let mut agenda = Agenda::default();
+    for file in files {
+        let patch = file.patch.as_deref().unwrap_or("");
+        let kind = FileKind::classify(&file.filename, patch);
+        if matches!(kind, FileKind::Lockfile | FileKind::Generated)
+            || (vendored::is_vendored(&file.filename) && !vendored::is_license_file(&file.filename))
+        {
+            agenda.skim.push(file.filename.clone());
+            continue;
+        }
+        agenda.items.push(AgendaItem {
+            path: file.filename.clone(),
+            kind,
+            additions: file.additions,
+            deletions: file.deletions,
+            minutes: estimate_minutes(kind, file.additions + file.deletions),
+            functions: changed_functions(patch),
+            flags: risk_flags(file),
+        });
+    }
+    agenda.missing_tests = agenda.items.iter().any(|i| i.kind == FileKind::Source)
+        && !agenda.items.iter().any(|i| i.kind == FileKind::Test);
+
+    // 注意点の多いもの → ソース・テスト・ドキュメントの順 → 変更の大きいもの
+    agenda.items.sort_by(|a, b| {
+        b.flags
+            .len()
+            .cmp(&a.flags.len())
+            .then(b.kind.weight().cmp(&a.kind.weight()))
+            .then((b.additions + b.deletions).cmp(&(a.additions + a.deletions)))
+            .then(a.path.cmp(&b.path))
+    });
+    agenda
+}
+
+/// 変更行数から所要時間の目安（分）を出す。ソースは 1 分あたり 20 行ほどで読む想定
+fn estimate_minutes(kind: FileKind, changed_lines: u32) -> u32 {
+    let lines_per_minute = match kind {
+        FileKind::Source => 20,
+        FileKind::Test => 40,
+        _ => 60,
+    };
+    changed_lines.div_ceil(lines_per_minute).max(1)
+}
+
+/// ハンクヘッダー（`@@ -1,2 +1,3 @@ fn foo()`）の関数名を重複なく集める
+fn changed_functions(patch: &str) -> Vec<String> {
+    let mut functions: Vec<String> = Vec::new();
+    for line in patch.lines() {
+        let Some(rest) = line.strip_prefix("@@") else {
+            continue;
+        };
+        let Some((_, context)) = rest.split_once("@@") else {
+            continue;
+        };
+        let context = context.trim().trim_end_matches('{').trim_end();
+        if context.is_empty() || functions.iter().any(|f| f == context) {
+            continue;
+        }
+        functions.push(context.to_string());
+        if functions.len() == MAX_FUNCTIONS {
+            break;
+        }
+    }
+    functions
+}
+
+fn risk_flags(file: &ChangedFile) -> Vec<RiskFlag> {
+    let path = file.filename.to_ascii_lowercase();
+    let name = path.rsplit('/').next().unwrap_or(&path);
+    let mut flags = Vec::new();
+    if SECURITY_WORDS.iter().any(|word| path.contains(word)) {
+        flags.push(RiskFlag::Security);
+    }
+    if manifest_diff::is_manifest(&file.filename) {
+        flags.push(RiskFlag::Dependencies);
+    }
+    if path.contains("migration") || path.ends_with(".sql") {
+        flags.push(RiskFlag::Migration);
+    }
+    if path.starts_with(".github/workflows/")
+        || name.starts_with("dockerfile")
+        || name == "makefile"
+        || name == "build.rs"
+    {
+        flags.push(RiskFlag::Build);
+    }
+    if vendored::is_license_file(&file.filename) {
+        flags.push(RiskFlag::License);
+    }
+    if file.status == "removed" {
+        flags.push(RiskFlag::Removed);
+    } else if file.patch.is_none() {
+        flags.push(RiskFlag::NoDiff);
+    }
+    if file.additions + file.deletions >= LARGE_CHANGE_LINES {
+        flags.push(RiskFlag::Large);
+    }
+    flags
+}
+
+/// コメントとして投稿・保存する Markdown
+pub fn to_markdown(agenda: &Agenda, heading: &str) -> String {
+    let mut out = format!("## Review agenda: {}\n\n", heading);
+    out.push_str(&format!(
+        "{} file(s) to review, about {} min in total.\n",
+        agenda.items.len(),
+        agenda.total_minutes()
+    ));
+    if agenda.missing_tests {
+        out.push_str("\n> ⚠ Source files changed without any test changes.\n");
+    }
+    if !agenda.items.is_empty() {
+        out.push('\n');
+    }
+    for (i, item) in agenda.items.iter().enumerate() {
+        out.push_str(&format!(
+            "{}. [ ] `{}` (+{} −{}, ~{} min)",
+            i + 1,
+            item.path,
+            item.additions,
+            item.deletions,
+            item.minutes
+        ));
+        if !item.flags.is_empty() {
+            let flags: Vec<&str> = item.flags.iter().map(|f| f.label()).collect();
+            out.push_str(&format!(" ⚠ {}", flags.join(", ")));
+        }
+        out.push('\n');
+        for function in &item.functions {
+            out.push_str(&format!("   - `{}`\n", function));
+        }
+    }
+    if !agenda.skim.is_empty() {
+        out.push_str(&format!(
+            "\n<details><summary>Skim only ({} lockfile/generated/vendored file(s))</summary>\n\n",
+            agenda.skim.len()
+        ));
+        for path in &agenda.skim {
+            out.push_str(&format!("- `{}`\n", path));
+        }
+        out.push_str("\n</details>\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, additions: u32, patch: Option<&str>) -> ChangedFile {
+        ChangedFile {
+            filename: name.to_string(),
+            status: "modified".to_string(),
+            additions,
+            deletions: 0,
+            patch: patch.map(String::from),
+            viewed: false,
+        }
+    }
+
+    #[test]
+    fn test_build_orders_by_risk_then_kind_and_size() {
+        let files = vec![
+            file("README.md", 10, Some("@@ -1 +1 @@\n+docs")),
+            file("src/lib.rs", 30, Some("@@ -1 +1 @@ pub mod a;\n+x")),
+            file(
+                "src/auth/token.rs",
+                5,
+                Some("@@ -10,2 +10,3 @@ fn refresh() {\n+x"),
+            ),
+            file("Cargo.lock", 200, Some("@@ -1 +1 @@\n+x")),
+            file("vendor/foo/lib.c", 500, Some("@@ -1 +1 @@\n+x")),
+            file("tests/parse.rs", 50, Some("@@ -1 +1 @@\n+x")),
+        ];
+        let agenda = build(&files);
+        let paths: Vec<&str> = agenda.items.iter().map(|i| i.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            [
+                "src/auth/token.rs",
+                "src/lib.rs",
+                "tests/parse.rs",
+                "README.md"
+            ]
+        );
+        assert_eq!(agenda.items[0].flags, [RiskFlag::Security]);
+        assert_eq!(agenda.items[0].functions, ["fn refresh()"]);
+        assert_eq!(agenda.skim, ["Cargo.lock", "vendor/foo/lib.c"]);
+        assert!(!agenda.missing_tests);
+        // 30 行のソースは 2 分、50 行のテストも 2 分
+        assert_eq!(agenda.items[1].minutes, 2);
+        assert_eq!(agenda.items[2].minutes, 2);
+    }
+
+    #[test]
+    fn test_risk_flags() {
+        let mut removed = file("db/migrations/001_init.sql", 400, None);
+        removed.status = "removed".to_string();
+        assert_eq!(
+            risk_flags(&removed),
+            [RiskFlag::Migration, RiskFlag::Removed, RiskFlag::Large]
+        );
+        assert_eq!(
+            risk_flags(&file(".github/workflows/ci.yml", 1, Some(""))),
+            [RiskFlag::Build]
+        );
+        assert_eq!(
+            risk_flags(&file("package.json", 1, Some(""))),
+            [RiskFlag::Dependencies]
+        );
+        assert_eq!(
+            risk_flags(&file("assets/logo.png", 0, None)),
+            [RiskFlag::NoDiff]
+        );
+    }
+
+    #[test]
+    fn test_changed_functions_are_unique_and_capped() {
+        let patch = "@@ -1 +1 @@ fn a() {\n+x\n@@ -5 +5 @@ fn a() {\n+y\n@@ -9 +9 @@\n+z";
+        assert_eq!(changed_functions(patch), ["fn a()"]);
+        let many: String = (0..10)
+            .map(|i| format!("@@ -{0} +{0} @@ fn f{0}()\n+x\n", i))
+            .collect();
+        assert_eq!(changed_functions(&many).len(), MAX_FUNCTIONS);
+    }
+
+    #[test]
+    fn test_to_markdown() {
+        let files = vec![
+            file("src/main.rs", 25, Some("@@ -1 +1 @@ fn main() {\n+x")),
+            file("yarn.lock", 3, Some("@@ -1 +1 @@\n+x")),
+        ];
+        let markdown = to_markdown(&build(&files), "#12 Add feature");
+        assert_eq!(
+            markdown,
+            "## Review agenda: #12 Add feature\n\n\
+             1 file(s) to review, about 2 min in total.\n\n\
+             > ⚠ Source files changed without any test changes.\n\n\
+             1. [ ] `src/main.rs` (+25 −0, ~2 min)\n\
+             \x20  - `fn main()`\n\
+             \n<details><summary>Skim only (1 lockfile/generated/vendored file(s))</summary>\n\n\
+             - `yarn.lock`\n\
+             \n</details>\n"
+        );
+    }
+}
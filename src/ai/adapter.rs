@@ -4,6 +4,7 @@ use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use super::orchestrator::RallyEvent;
+use crate::spawn_env::SpawnEnv;
 
 /// Context information passed to agents
 #[derive(Debug, Clone)]
@@ -24,6 +25,29 @@ pub struct Context {
     pub local_mode: bool,
     /// ファイル名 → patch のペア（position 変換用）
     pub file_patches: Vec<(String, String)>,
+    /// エージェントの起動時に追加する環境変数（`[env]`）
+    pub env: SpawnEnv,
+    /// Files the rally is limited to (empty: the whole PR)
+    pub scope: Vec<String>,
+}
+
+impl Context {
+    /// Whether `path` is reviewed in this rally
+    pub fn in_scope(&self, path: &str) -> bool {
+        self.scope.is_empty() || self.scope.iter().any(|p| p == path)
+    }
+
+    /// Drop the files outside `scope` from a unified diff (as-is when not scoped)
+    pub fn scoped_diff(&self, diff: String) -> String {
+        if self.scope.is_empty() {
+            return diff;
+        }
+        super::context_planner::split_git_diff(&diff)
+            .into_iter()
+            .filter(|(path, _)| self.in_scope(path))
+            .map(|(_, patch)| patch)
+            .collect()
+    }
 }
 
 /// Comment from external tools (bots)
@@ -55,6 +79,9 @@ pub struct ReviewComment {
     pub line: u32,
     pub body: String,
     pub severity: CommentSeverity,
+    /// Reviewers that reported this finding, set when several reviewers run in parallel
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sources: Vec<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -106,6 +133,66 @@ pub struct RevieweeOutput {
     pub error_details: Option<String>,
 }
 
+/// Token counts (and cost, when the agent reports it) for one or more agent calls
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct TokenUsage {
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    /// Cost in USD as reported by the agent. `None` when the agent does not report it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cost_usd: Option<f64>,
+}
+
+impl TokenUsage {
+    pub fn total_tokens(&self) -> u64 {
+        self.input_tokens + self.output_tokens
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.total_tokens() == 0 && self.cost_usd.is_none()
+    }
+
+    /// Accumulate another usage into this one (costs are summed when either side has one)
+    pub fn add(&mut self, other: &TokenUsage) {
+        self.input_tokens += other.input_tokens;
+        self.output_tokens += other.output_tokens;
+        self.cost_usd = match (self.cost_usd, other.cost_usd) {
+            (None, None) => None,
+            (a, b) => Some(a.unwrap_or(0.0) + b.unwrap_or(0.0)),
+        };
+    }
+
+    /// Fill in the cost from per-million-token prices when the agent did not report one
+    pub fn with_estimated_cost(self, input_per_mtok: f64, output_per_mtok: f64) -> Self {
+        if self.cost_usd.is_some() || (input_per_mtok <= 0.0 && output_per_mtok <= 0.0) {
+            return self;
+        }
+        let cost = (self.input_tokens as f64 * input_per_mtok
+            + self.output_tokens as f64 * output_per_mtok)
+            / 1_000_000.0;
+        Self {
+            cost_usd: Some(cost),
+            ..self
+        }
+    }
+}
+
+impl std::fmt::Display for TokenUsage {
+    /// Compact form for logs and headers, e.g. `12.3k tokens ($0.42)`
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let total = self.total_tokens();
+        match total {
+            0..=999 => write!(f, "{} tokens", total)?,
+            1_000..=999_999 => write!(f, "{:.1}k tokens", total as f64 / 1_000.0)?,
+            _ => write!(f, "{:.2}M tokens", total as f64 / 1_000_000.0)?,
+        }
+        if let Some(cost) = self.cost_usd {
+            write!(f, " (${:.2})", cost)?;
+        }
+        Ok(())
+    }
+}
+
 /// Trait for agent adapters
 ///
 /// NOTE: async-trait is required because native async fn in traits are not dyn-compatible
@@ -155,6 +242,36 @@ pub trait AgentAdapter: Send + Sync {
     /// are blocked to prevent unintended changes to the working tree.
     /// This is propagated from Context via Orchestrator::set_context().
     fn set_local_mode(&mut self, local_mode: bool);
+
+    /// Set dry-run flag
+    ///
+    /// When dry_run is true, the reviewee must leave the repository untouched:
+    /// file-writing tools and git write operations are blocked or stubbed out.
+    /// This is propagated from AiConfig via Orchestrator::new().
+    fn set_dry_run(&mut self, dry_run: bool);
+
+    /// Set extra environment variables for the agent CLI process
+    ///
+    /// Secret values are redacted from streamed events before they reach the log view.
+    /// This is propagated from Context via Orchestrator::set_context().
+    fn set_env(&mut self, env: SpawnEnv);
+
+    /// Take the token usage accumulated since the last call
+    ///
+    /// Adapters that cannot read usage from their agent's output report nothing.
+    fn take_usage(&mut self) -> TokenUsage {
+        TokenUsage::default()
+    }
+
+    /// Agent session IDs of the (reviewer, reviewee) conversations, saved for resuming
+    ///
+    /// Adapters that keep the conversation in memory have nothing to save.
+    fn session_ids(&self) -> (Option<String>, Option<String>) {
+        (None, None)
+    }
+
+    /// Restore session IDs saved from `session_ids` when a rally is resumed
+    fn restore_session_ids(&mut self, _reviewer: Option<String>, _reviewee: Option<String>) {}
 }
 
 /// Supported agent types
@@ -162,7 +279,9 @@ pub trait AgentAdapter: Send + Sync {
 pub enum SupportedAgent {
     Claude,
     Codex,
-    // Gemini, // Future
+    Gemini,
+    /// Any OpenAI-compatible chat endpoint (`[ai.openai]`)
+    OpenAi,
 }
 
 impl SupportedAgent {
@@ -170,7 +289,8 @@ impl SupportedAgent {
         match name.to_lowercase().as_str() {
             "claude" => Some(Self::Claude),
             "codex" => Some(Self::Codex),
-            // "gemini" => Some(Self::Gemini),
+            "gemini" => Some(Self::Gemini),
+            "openai" => Some(Self::OpenAi),
             _ => None,
         }
     }
@@ -181,7 +301,8 @@ impl SupportedAgent {
         match self {
             Self::Claude => "claude",
             Self::Codex => "codex",
-            // Self::Gemini => "gemini",
+            Self::Gemini => "gemini",
+            Self::OpenAi => "openai",
         }
     }
 }
@@ -6,9 +6,10 @@ use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use tokio::sync::mpsc;
 
-use crate::ai::adapter::{AgentAdapter, Context, RevieweeOutput, ReviewerOutput};
+use crate::ai::adapter::{AgentAdapter, Context, RevieweeOutput, ReviewerOutput, TokenUsage};
 use crate::ai::orchestrator::RallyEvent;
 use crate::config::AiConfig;
+use crate::spawn_env::SpawnEnv;
 
 const REVIEWER_SCHEMA: &str = include_str!("../schemas/reviewer.json");
 const REVIEWEE_SCHEMA: &str = include_str!("../schemas/reviewee.json");
@@ -23,6 +24,9 @@ const GIT_DISALLOWED_TOOLS: &str = concat!(
     "Bash(git restore:*),Bash(git tag:*),Bash(git rm:*),Bash(git clean:*)"
 );
 
+/// File-writing tools to disallow in dry-run mode, in addition to the git write commands.
+const DRY_RUN_DISALLOWED_TOOLS: &str = "Edit,MultiEdit,Write,NotebookEdit,Bash(cargo fmt:*)";
+
 /// Claude Code adapter
 pub struct ClaudeAdapter {
     /// Cached allowed tools string for reviewer (built once at initialization)
@@ -34,6 +38,12 @@ pub struct ClaudeAdapter {
     event_sender: Option<mpsc::Sender<RallyEvent>>,
     /// When true, git write operations are blocked via --disallowedTools
     local_mode: bool,
+    /// When true, file-writing tools and git write operations are blocked via --disallowedTools
+    dry_run: bool,
+    /// Extra environment variables for the claude process (`[env]`)
+    env: SpawnEnv,
+    /// Token usage reported by result events since the last `take_usage`
+    usage: TokenUsage,
 }
 
 impl ClaudeAdapter {
@@ -45,6 +55,9 @@ impl ClaudeAdapter {
             reviewee_session_id: None,
             event_sender: None,
             local_mode: false,
+            dry_run: false,
+            env: SpawnEnv::default(),
+            usage: TokenUsage::default(),
         }
     }
 
@@ -99,7 +112,20 @@ impl ClaudeAdapter {
 
     async fn send_event(&self, event: RallyEvent) {
         if let Some(ref sender) = self.event_sender {
-            let _ = sender.send(event).await;
+            let _ = sender.send(event.redact(&self.env)).await;
+        }
+    }
+
+    /// Tools blocked via --disallowedTools: git writes in local mode, and also the
+    /// file-writing tools in dry-run mode
+    pub(crate) fn disallowed_tools(&self) -> Option<String> {
+        match (self.local_mode, self.dry_run) {
+            (_, true) => Some(format!(
+                "{},{}",
+                DRY_RUN_DISALLOWED_TOOLS, GIT_DISALLOWED_TOOLS
+            )),
+            (true, false) => Some(GIT_DISALLOWED_TOOLS.to_string()),
+            (false, false) => None,
         }
     }
 
@@ -115,6 +141,7 @@ impl ClaudeAdapter {
         let mut cmd = Command::new("claude");
         // Prevent nested session detection when octorus is run inside Claude Code
         cmd.env_remove("CLAUDECODE");
+        cmd.envs(self.env.vars());
         // Use -p without prompt arg; prompt is piped via stdin to avoid OS ARG_MAX limit
         cmd.arg("-p");
         cmd.arg("--output-format").arg("stream-json");
@@ -126,8 +153,8 @@ impl ClaudeAdapter {
 
         // In local mode, block git write operations via --disallowedTools.
         // --disallowedTools takes precedence over --allowedTools and settings.local.json.
-        if self.local_mode {
-            cmd.arg("--disallowedTools").arg(GIT_DISALLOWED_TOOLS);
+        if let Some(tools) = self.disallowed_tools() {
+            cmd.arg("--disallowedTools").arg(tools);
         }
 
         if let Some(session) = session_id {
@@ -190,9 +217,9 @@ impl ClaudeAdapter {
                                         .or_else(|| event.result.clone());
                                     if let Some(result) = result_value {
                                         final_response = Some(ClaudeResponse {
+                                            usage: event.token_usage(),
                                             session_id: event.session_id.unwrap_or_default(),
                                             result: Some(result),
-                                            cost_usd: event.cost_usd,
                                             duration_ms: event.duration_ms,
                                         });
                                     }
@@ -367,6 +394,7 @@ impl AgentAdapter for ClaudeAdapter {
             )
             .await?;
 
+        self.usage.add(&response.usage);
         self.reviewer_session_id = Some(response.session_id.clone());
 
         parse_reviewer_output(response.result.as_ref(), "claude")
@@ -406,6 +434,7 @@ impl AgentAdapter for ClaudeAdapter {
             )
             .await?;
 
+        self.usage.add(&response.usage);
         self.reviewee_session_id = Some(response.session_id.clone());
 
         parse_reviewee_output(response.result.as_ref(), "claude")
@@ -427,6 +456,7 @@ impl AgentAdapter for ClaudeAdapter {
                 Some(&session_id),
             )
             .await?;
+        self.usage.add(&response.usage);
         parse_reviewer_output(response.result.as_ref(), "claude")
     }
 
@@ -446,6 +476,7 @@ impl AgentAdapter for ClaudeAdapter {
                 Some(&session_id),
             )
             .await?;
+        self.usage.add(&response.usage);
         parse_reviewee_output(response.result.as_ref(), "claude")
     }
 
@@ -462,6 +493,30 @@ impl AgentAdapter for ClaudeAdapter {
     fn set_local_mode(&mut self, local_mode: bool) {
         self.local_mode = local_mode;
     }
+
+    fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    fn set_env(&mut self, env: SpawnEnv) {
+        self.env = env;
+    }
+
+    fn take_usage(&mut self) -> TokenUsage {
+        std::mem::take(&mut self.usage)
+    }
+
+    fn session_ids(&self) -> (Option<String>, Option<String>) {
+        (
+            self.reviewer_session_id.clone(),
+            self.reviewee_session_id.clone(),
+        )
+    }
+
+    fn restore_session_ids(&mut self, reviewer: Option<String>, reviewee: Option<String>) {
+        self.reviewer_session_id = reviewer;
+        self.reviewee_session_id = reviewee;
+    }
 }
 
 /// Stream event from Claude CLI stream-json output
@@ -490,10 +545,41 @@ struct StreamEvent {
     structured_output: Option<serde_json::Value>,
     #[serde(default)]
     cost_usd: Option<f64>,
+    /// Reported on result events by newer CLI versions (older ones use `cost_usd`)
+    #[serde(default)]
+    total_cost_usd: Option<f64>,
+    #[serde(default)]
+    usage: Option<StreamUsage>,
     #[serde(default)]
     duration_ms: Option<u64>,
 }
 
+impl StreamEvent {
+    /// Token usage of a result event. Cache reads/writes count as input tokens
+    fn token_usage(&self) -> TokenUsage {
+        let usage = self.usage.as_ref();
+        TokenUsage {
+            input_tokens: usage.map_or(0, |u| {
+                u.input_tokens + u.cache_creation_input_tokens + u.cache_read_input_tokens
+            }),
+            output_tokens: usage.map_or(0, |u| u.output_tokens),
+            cost_usd: self.total_cost_usd.or(self.cost_usd),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct StreamUsage {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    cache_creation_input_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
 #[derive(Debug, Deserialize)]
 struct StreamMessage {
     #[serde(default)]
@@ -534,10 +620,8 @@ struct ClaudeResponse {
     session_id: String,
     #[serde(default)]
     result: Option<serde_json::Value>,
-    // For monitoring and cost analysis (future feature)
-    #[serde(default)]
-    #[allow(dead_code)]
-    cost_usd: Option<f64>,
+    #[serde(skip)]
+    usage: TokenUsage,
     // For performance monitoring (future feature)
     #[serde(default)]
     #[allow(dead_code)]
@@ -578,6 +662,25 @@ mod tests {
         assert_snapshot!(tools, @"Read,Glob,Grep,Bash(gh pr view:*),Bash(gh pr diff:*),Bash(gh pr checks:*),Bash(gh api --method GET:*),Bash(gh api -X GET:*),Skill,WebSearch");
     }
 
+    #[test]
+    fn test_disallowed_tools() {
+        let mut adapter = ClaudeAdapter::new(&AiConfig::default());
+        assert_eq!(adapter.disallowed_tools(), None);
+
+        adapter.set_local_mode(true);
+        assert_eq!(
+            adapter.disallowed_tools().as_deref(),
+            Some(GIT_DISALLOWED_TOOLS)
+        );
+
+        adapter.set_local_mode(false);
+        adapter.set_dry_run(true);
+        let tools = adapter.disallowed_tools().unwrap();
+        assert!(tools.starts_with("Edit,MultiEdit,Write,NotebookEdit,"));
+        assert!(tools.contains("Bash(git commit:*)"));
+        assert!(tools.contains("Bash(git push:*)"));
+    }
+
     #[test]
     fn test_reviewee_default_no_git_push() {
         let config = AiConfig::default();
@@ -623,6 +726,23 @@ mod tests {
         assert!(tools.contains("Bash(gh api --method POST:*)"));
     }
 
+    #[test]
+    fn test_result_event_token_usage() {
+        let event: StreamEvent = serde_json::from_str(
+            r#"{"type":"result","total_cost_usd":0.25,"usage":{"input_tokens":10,"cache_creation_input_tokens":100,"cache_read_input_tokens":1000,"output_tokens":42}}"#,
+        )
+        .unwrap();
+        let usage = event.token_usage();
+        assert_eq!(usage.input_tokens, 1110);
+        assert_eq!(usage.output_tokens, 42);
+        assert_eq!(usage.cost_usd, Some(0.25));
+
+        let legacy: StreamEvent =
+            serde_json::from_str(r#"{"type":"result","cost_usd":0.5}"#).unwrap();
+        assert_eq!(legacy.token_usage().total_tokens(), 0);
+        assert_eq!(legacy.token_usage().cost_usd, Some(0.5));
+    }
+
     #[test]
     fn test_add_reviewee_allowed_tool() {
         use crate::ai::adapter::AgentAdapter;
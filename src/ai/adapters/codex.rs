@@ -9,8 +9,9 @@ use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
 use tokio::sync::mpsc;
 
-use crate::ai::adapter::{AgentAdapter, Context, RevieweeOutput, ReviewerOutput};
+use crate::ai::adapter::{AgentAdapter, Context, RevieweeOutput, ReviewerOutput, TokenUsage};
 use crate::ai::orchestrator::RallyEvent;
+use crate::spawn_env::SpawnEnv;
 
 // Codex requires additionalProperties: false for all objects in the schema
 const REVIEWER_SCHEMA: &str = r#"{
@@ -117,6 +118,12 @@ pub struct CodexAdapter {
     event_sender: Option<mpsc::Sender<RallyEvent>>,
     /// When true, git write prohibition is prepended to prompts (best-effort)
     local_mode: bool,
+    /// When true, the reviewee runs in the read-only sandbox so it cannot write files
+    dry_run: bool,
+    /// Extra environment variables for the codex process (`[env]`)
+    env: SpawnEnv,
+    /// Token usage reported by turn.completed events since the last `take_usage`
+    usage: TokenUsage,
 }
 
 impl CodexAdapter {
@@ -126,6 +133,9 @@ impl CodexAdapter {
             reviewee_session_id: None,
             event_sender: None,
             local_mode: false,
+            dry_run: false,
+            env: SpawnEnv::default(),
+            usage: TokenUsage::default(),
         }
     }
 
@@ -144,7 +154,7 @@ impl CodexAdapter {
 
     async fn send_event(&self, event: RallyEvent) {
         if let Some(ref sender) = self.event_sender {
-            let _ = sender.send(event).await;
+            let _ = sender.send(event.redact(&self.env)).await;
         }
     }
 
@@ -170,6 +180,7 @@ impl CodexAdapter {
         let mut cmd = Command::new("codex");
         // Prevent nested session detection when octorus is run inside Claude Code
         cmd.env_remove("CLAUDECODE");
+        cmd.envs(self.env.vars());
 
         // Handle session resume
         // Usage: codex exec resume <SESSION_ID> [PROMPT]
@@ -228,6 +239,7 @@ impl CodexAdapter {
         // This ensures we don't lose the session if Codex doesn't emit thread.started
         let mut thread_id: Option<String> = session_id.map(|s| s.to_string());
         let mut stream_error: Option<anyhow::Error> = None;
+        let mut usage = TokenUsage::default();
 
         // Process NDJSON stream
         loop {
@@ -241,6 +253,9 @@ impl CodexAdapter {
                             // Parse Codex event
                             match serde_json::from_str::<CodexEvent>(&l) {
                                 Ok(event) => {
+                                    if let CodexEvent::TurnCompleted { usage: Some(u) } = &event {
+                                        usage.add(&u.token_usage());
+                                    }
                                     match self.handle_codex_event(&event, &mut thread_id).await {
                                         Ok(Some(result)) => {
                                             final_response = Some(result);
@@ -312,7 +327,9 @@ impl CodexAdapter {
             ));
         }
 
-        final_response.ok_or_else(|| anyhow!("No result received from codex"))
+        final_response
+            .map(|response| CodexResponse { usage, ..response })
+            .ok_or_else(|| anyhow!("No result received from codex"))
     }
 
     /// Handle Codex streaming event and convert to RallyEvent
@@ -400,6 +417,7 @@ impl CodexAdapter {
                             return Ok(Some(CodexResponse {
                                 session_id,
                                 result: Some(result),
+                                usage: TokenUsage::default(),
                             }));
                         }
                         // If not JSON, just show as text
@@ -486,13 +504,14 @@ impl AgentAdapter for CodexAdapter {
             )
             .await?;
 
+        self.usage.add(&response.usage);
         self.reviewer_session_id = Some(response.session_id.clone());
 
         parse_reviewer_output(response.result.as_ref(), "codex")
     }
 
     async fn run_reviewee(&mut self, prompt: &str, context: &Context) -> Result<RevieweeOutput> {
-        // Reviewee runs in full-auto mode (workspace-write)
+        // Reviewee runs in full-auto mode (workspace-write), or read-only in dry-run mode
         // NOTE: full-auto allows git push, but the prompt explicitly prohibits it
         // In local mode, prepend git write prohibition (best-effort for Codex)
         let effective_prompt = if self.local_mode {
@@ -504,12 +523,13 @@ impl AgentAdapter for CodexAdapter {
             .run_codex_streaming(
                 &effective_prompt,
                 REVIEWEE_SCHEMA,
-                true, // full-auto sandbox for reviewee
+                !self.dry_run, // full-auto sandbox for reviewee
                 context.working_dir.as_deref(),
                 None,
             )
             .await?;
 
+        self.usage.add(&response.usage);
         self.reviewee_session_id = Some(response.session_id.clone());
 
         parse_reviewee_output(response.result.as_ref(), "codex")
@@ -526,6 +546,7 @@ impl AgentAdapter for CodexAdapter {
             .run_codex_streaming(message, REVIEWER_SCHEMA, false, None, Some(&session_id))
             .await?;
 
+        self.usage.add(&response.usage);
         parse_reviewer_output(response.result.as_ref(), "codex")
     }
 
@@ -537,9 +558,16 @@ impl AgentAdapter for CodexAdapter {
             .clone();
 
         let response = self
-            .run_codex_streaming(message, REVIEWEE_SCHEMA, true, None, Some(&session_id))
+            .run_codex_streaming(
+                message,
+                REVIEWEE_SCHEMA,
+                !self.dry_run,
+                None,
+                Some(&session_id),
+            )
             .await?;
 
+        self.usage.add(&response.usage);
         parse_reviewee_output(response.result.as_ref(), "codex")
     }
 
@@ -552,6 +580,30 @@ impl AgentAdapter for CodexAdapter {
     fn set_local_mode(&mut self, local_mode: bool) {
         self.local_mode = local_mode;
     }
+
+    fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    fn set_env(&mut self, env: SpawnEnv) {
+        self.env = env;
+    }
+
+    fn take_usage(&mut self) -> TokenUsage {
+        std::mem::take(&mut self.usage)
+    }
+
+    fn session_ids(&self) -> (Option<String>, Option<String>) {
+        (
+            self.reviewer_session_id.clone(),
+            self.reviewee_session_id.clone(),
+        )
+    }
+
+    fn restore_session_ids(&mut self, reviewer: Option<String>, reviewee: Option<String>) {
+        self.reviewer_session_id = reviewer;
+        self.reviewee_session_id = reviewee;
+    }
 }
 
 // Codex event types based on actual CLI output
@@ -565,8 +617,7 @@ pub enum CodexEvent {
     #[serde(rename = "turn.completed")]
     TurnCompleted {
         #[serde(default)]
-        #[allow(dead_code)]
-        usage: Option<serde_json::Value>,
+        usage: Option<CodexUsage>,
     },
     #[serde(rename = "turn.failed")]
     TurnFailed {
@@ -585,6 +636,25 @@ pub enum CodexEvent {
     Unknown,
 }
 
+/// Usage info in turn.completed event (`input_tokens` already includes cached input)
+#[derive(Debug, Deserialize)]
+pub struct CodexUsage {
+    #[serde(default)]
+    pub input_tokens: u64,
+    #[serde(default)]
+    pub output_tokens: u64,
+}
+
+impl CodexUsage {
+    fn token_usage(&self) -> TokenUsage {
+        TokenUsage {
+            input_tokens: self.input_tokens,
+            output_tokens: self.output_tokens,
+            cost_usd: None,
+        }
+    }
+}
+
 /// Error info in turn.failed event
 #[derive(Debug, Deserialize)]
 pub struct CodexErrorInfo {
@@ -622,6 +692,7 @@ pub struct CodexItem {
 struct CodexResponse {
     session_id: String,
     result: Option<serde_json::Value>,
+    usage: TokenUsage,
 }
 
 use super::common::{parse_reviewee_output, parse_reviewer_output};
@@ -656,6 +727,20 @@ mod tests {
         assert!(matches!(event, CodexEvent::TurnCompleted { .. }));
     }
 
+    #[test]
+    fn test_turn_completed_token_usage() {
+        let json = r#"{"type": "turn.completed", "usage": {"input_tokens": 100, "cached_input_tokens": 80, "output_tokens": 7}}"#;
+        let CodexEvent::TurnCompleted { usage: Some(usage) } =
+            serde_json::from_str::<CodexEvent>(json).unwrap()
+        else {
+            panic!("expected turn.completed with usage");
+        };
+        let usage = usage.token_usage();
+        assert_eq!(usage.input_tokens, 100);
+        assert_eq!(usage.output_tokens, 7);
+        assert_eq!(usage.cost_usd, None);
+    }
+
     #[test]
     fn test_parse_turn_failed_event() {
         let json = r#"{"type": "turn.failed", "error": {"message": "Something went wrong"}}"#;
@@ -1,13 +1,41 @@
 //! Common types and parsing functions shared between AI adapters (Claude, Codex, etc.)
 
-use anyhow::{anyhow, Context as AnyhowContext, Result};
+use anyhow::Result;
 use serde::Deserialize;
+use thiserror::Error;
 
 use crate::ai::adapter::{
     CommentSeverity, PermissionRequest, ReviewAction, ReviewComment, RevieweeOutput,
     RevieweeStatus, ReviewerOutput,
 };
 
+/// JSON schemas the final answers are validated against
+const REVIEWER_SCHEMA: &str = include_str!("../schemas/reviewer.json");
+const REVIEWEE_SCHEMA: &str = include_str!("../schemas/reviewee.json");
+
+/// The agent's final answer does not match its output schema.
+///
+/// The orchestrator sends the problems back to the agent and asks for a corrected answer
+/// instead of failing the rally right away.
+#[derive(Debug, Error)]
+#[error("Invalid {role} output from {agent}: {}", .problems.join("; "))]
+pub(crate) struct OutputFormatError {
+    /// "reviewer" or "reviewee"
+    pub role: &'static str,
+    pub agent: String,
+    pub problems: Vec<String>,
+}
+
+impl OutputFormatError {
+    fn new(role: &'static str, agent: &str, problems: Vec<String>) -> Self {
+        Self {
+            role,
+            agent: agent.to_string(),
+            problems,
+        }
+    }
+}
+
 /// Raw reviewer output structure shared by all adapters.
 #[derive(Debug, Deserialize)]
 pub(crate) struct RawReviewerOutput {
@@ -50,20 +78,27 @@ pub(crate) struct RawPermissionRequest {
 /// Parse reviewer output from a JSON result value.
 ///
 /// `agent_name` is used in error messages (e.g., "claude", "codex").
+/// Fails with [`OutputFormatError`] when the value does not match the reviewer schema.
 pub(crate) fn parse_reviewer_output(
     result: Option<&serde_json::Value>,
     agent_name: &str,
 ) -> Result<ReviewerOutput> {
-    let result = result.ok_or_else(|| anyhow!("No result in {} response", agent_name))?;
+    let invalid = |problems| OutputFormatError::new("reviewer", agent_name, problems);
+    let result =
+        result.ok_or_else(|| invalid(vec![format!("No result in {} response", agent_name)]))?;
+    let problems = validate_schema(result, REVIEWER_SCHEMA);
+    if !problems.is_empty() {
+        return Err(invalid(problems).into());
+    }
 
-    let raw: RawReviewerOutput =
-        serde_json::from_value(result.clone()).context("Failed to parse reviewer output")?;
+    let raw: RawReviewerOutput = serde_json::from_value(result.clone())
+        .map_err(|e| invalid(vec![format!("Failed to parse reviewer output: {}", e)]))?;
 
     let action = match raw.action.as_str() {
         "approve" => ReviewAction::Approve,
         "request_changes" => ReviewAction::RequestChanges,
         "comment" => ReviewAction::Comment,
-        _ => return Err(anyhow!("Unknown review action: {}", raw.action)),
+        _ => return Err(invalid(vec![format!("Unknown review action: {}", raw.action)]).into()),
     };
 
     let comments = raw
@@ -82,6 +117,7 @@ pub(crate) fn parse_reviewer_output(
                 line: c.line,
                 body: c.body,
                 severity,
+                sources: Vec::new(),
             }
         })
         .collect();
@@ -97,21 +133,28 @@ pub(crate) fn parse_reviewer_output(
 /// Parse reviewee output from a JSON result value.
 ///
 /// `agent_name` is used in error messages (e.g., "claude", "codex").
+/// Fails with [`OutputFormatError`] when the value does not match the reviewee schema.
 pub(crate) fn parse_reviewee_output(
     result: Option<&serde_json::Value>,
     agent_name: &str,
 ) -> Result<RevieweeOutput> {
-    let result = result.ok_or_else(|| anyhow!("No result in {} response", agent_name))?;
+    let invalid = |problems| OutputFormatError::new("reviewee", agent_name, problems);
+    let result =
+        result.ok_or_else(|| invalid(vec![format!("No result in {} response", agent_name)]))?;
+    let problems = validate_schema(result, REVIEWEE_SCHEMA);
+    if !problems.is_empty() {
+        return Err(invalid(problems).into());
+    }
 
-    let raw: RawRevieweeOutput =
-        serde_json::from_value(result.clone()).context("Failed to parse reviewee output")?;
+    let raw: RawRevieweeOutput = serde_json::from_value(result.clone())
+        .map_err(|e| invalid(vec![format!("Failed to parse reviewee output: {}", e)]))?;
 
     let status = match raw.status.as_str() {
         "completed" => RevieweeStatus::Completed,
         "needs_clarification" => RevieweeStatus::NeedsClarification,
         "needs_permission" => RevieweeStatus::NeedsPermission,
         "error" => RevieweeStatus::Error,
-        _ => return Err(anyhow!("Unknown reviewee status: {}", raw.status)),
+        _ => return Err(invalid(vec![format!("Unknown reviewee status: {}", raw.status)]).into()),
     };
 
     let permission_request = raw.permission_request.map(|p| PermissionRequest {
@@ -129,6 +172,86 @@ pub(crate) fn parse_reviewee_output(
     })
 }
 
+/// Check a value against the subset of JSON Schema used by the output schemas:
+/// `type`, `properties`, `required` and `items`.
+///
+/// `enum` is left to the parsers, which name the unknown value (and map unknown
+/// severities to minor instead of rejecting the whole review).
+/// Returns one message per problem, prefixed with the path of the value.
+pub(crate) fn validate_schema(value: &serde_json::Value, schema: &str) -> Vec<String> {
+    let schema: serde_json::Value =
+        serde_json::from_str(schema).expect("output schemas are valid JSON");
+    let mut problems = Vec::new();
+    validate_value(value, &schema, "$", &mut problems);
+    problems
+}
+
+fn validate_value(
+    value: &serde_json::Value,
+    schema: &serde_json::Value,
+    path: &str,
+    problems: &mut Vec<String>,
+) {
+    use serde_json::Value;
+
+    if let Some(expected) = schema.get("type").and_then(Value::as_str) {
+        let matches = match expected {
+            "object" => value.is_object(),
+            "array" => value.is_array(),
+            "string" => value.is_string(),
+            "integer" => value.is_i64() || value.is_u64(),
+            "number" => value.is_number(),
+            "boolean" => value.is_boolean(),
+            "null" => value.is_null(),
+            _ => true,
+        };
+        if !matches {
+            problems.push(format!(
+                "{}: expected {}, got {}",
+                path,
+                expected,
+                json_type(value)
+            ));
+            return;
+        }
+    }
+
+    if let Value::Object(map) = value {
+        if let Some(required) = schema.get("required").and_then(Value::as_array) {
+            for key in required.iter().filter_map(Value::as_str) {
+                if !map.contains_key(key) {
+                    problems.push(format!("{}: missing required field \"{}\"", path, key));
+                }
+            }
+        }
+        if let Some(properties) = schema.get("properties").and_then(Value::as_object) {
+            for (key, property) in properties {
+                if let Some(child) = map.get(key) {
+                    validate_value(child, property, &format!("{}.{}", path, key), problems);
+                }
+            }
+        }
+    }
+
+    if let (Value::Array(items), Some(item_schema)) = (value, schema.get("items")) {
+        for (index, item) in items.iter().enumerate() {
+            validate_value(item, item_schema, &format!("{}[{}]", path, index), problems);
+        }
+    }
+}
+
+fn json_type(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "boolean",
+        serde_json::Value::Number(n) if n.is_f64() => "number",
+        serde_json::Value::Number(_) => "integer",
+        serde_json::Value::String(_) => "string",
+        serde_json::Value::Array(_) => "array",
+        serde_json::Value::Object(_) => "object",
+    }
+}
+
 /// Summarize JSON value for display
 pub(super) fn summarize_json(value: &serde_json::Value) -> String {
     match value {
@@ -158,6 +281,40 @@ pub(super) fn summarize_text(s: &str) -> String {
     }
 }
 
+/// Append the output schema to the prompt, for agents without structured output.
+pub(super) fn with_output_schema(prompt: &str, schema: &str) -> String {
+    format!(
+        "{}\n\nWhen you are done, reply with a single JSON object that matches the \
+following JSON schema and nothing else:\n{}",
+        prompt, schema
+    )
+}
+
+/// Find the JSON object in the final answer of an agent without structured output.
+/// Models often wrap it in a ```json fence or add a sentence around it.
+pub(super) fn extract_json(text: &str) -> Option<serde_json::Value> {
+    let parse = |s: &str| {
+        serde_json::from_str::<serde_json::Value>(s.trim())
+            .ok()
+            .filter(serde_json::Value::is_object)
+    };
+    if let Some(value) = parse(text) {
+        return Some(value);
+    }
+    // The last fenced block wins
+    let blocks: Vec<&str> = text.split("```").skip(1).step_by(2).collect();
+    if let Some(value) = blocks
+        .iter()
+        .rev()
+        .find_map(|block| parse(block.strip_prefix("json").unwrap_or(block)))
+    {
+        return Some(value);
+    }
+    let start = text.find('{')?;
+    let end = text.rfind('}')?;
+    parse(text.get(start..=end)?)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,6 +461,53 @@ mod tests {
         assert!(err.to_string().contains("Unknown reviewee status: pending"));
     }
 
+    #[test]
+    fn test_parse_errors_are_output_format_errors() {
+        let err = parse_reviewer_output(None, "test").unwrap_err();
+        let format_error = err.downcast_ref::<OutputFormatError>().unwrap();
+        assert_eq!(format_error.role, "reviewer");
+        assert_eq!(format_error.agent, "test");
+
+        let result = serde_json::json!({"status": "completed", "summary": 1});
+        let err = parse_reviewee_output(Some(&result), "test").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Invalid reviewee output from test: $: missing required field \"files_modified\"; \
+$.summary: expected string, got integer"
+        );
+        assert!(err.downcast_ref::<OutputFormatError>().is_some());
+    }
+
+    #[test]
+    fn test_validate_schema() {
+        let valid = serde_json::json!({
+            "action": "comment",
+            "summary": "Ok",
+            "comments": [{"path": "a.rs", "line": 1, "body": "x", "severity": "minor"}],
+            "blocking_issues": []
+        });
+        assert!(validate_schema(&valid, REVIEWER_SCHEMA).is_empty());
+
+        let invalid = serde_json::json!({
+            "action": "comment",
+            "summary": "Ok",
+            "comments": [{"path": "a.rs", "line": "1", "body": "x"}],
+            "blocking_issues": "none"
+        });
+        assert_eq!(
+            validate_schema(&invalid, REVIEWER_SCHEMA),
+            vec![
+                "$.blocking_issues: expected array, got string",
+                "$.comments[0]: missing required field \"severity\"",
+                "$.comments[0].line: expected integer, got string",
+            ]
+        );
+        assert_eq!(
+            validate_schema(&serde_json::json!([]), REVIEWEE_SCHEMA),
+            vec!["$: expected object, got array"]
+        );
+    }
+
     #[test]
     fn test_parse_reviewer_output_unknown_severity_fallback() {
         let result = serde_json::json!({
@@ -327,6 +531,25 @@ mod tests {
 
     // --- Utility tests ---
 
+    #[test]
+    fn test_extract_json() {
+        let expected = serde_json::json!({"status": "completed"});
+        assert_eq!(
+            extract_json(r#"{"status": "completed"}"#),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            extract_json("Done.\n```json\n{\"status\": \"completed\"}\n```\n"),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            extract_json("Here is the result: {\"status\": \"completed\"} Thanks."),
+            Some(expected)
+        );
+        assert_eq!(extract_json("no json here"), None);
+        assert_eq!(extract_json("[1, 2]"), None);
+    }
+
     #[test]
     fn test_summarize_json_object() {
         let value = serde_json::json!({"key1": "val1", "key2": "val2"});
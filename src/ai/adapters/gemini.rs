@@ -0,0 +1,766 @@
+use std::collections::HashMap;
+use std::process::Stdio;
+
+use anyhow::{anyhow, Context as AnyhowContext, Result};
+use async_trait::async_trait;
+use serde::Deserialize;
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::ai::adapter::{AgentAdapter, Context, RevieweeOutput, ReviewerOutput, TokenUsage};
+use crate::ai::orchestrator::RallyEvent;
+use crate::config::GeminiConfig;
+use crate::spawn_env::SpawnEnv;
+
+use super::common::{
+    extract_json, parse_reviewee_output, parse_reviewer_output, summarize_json, summarize_text,
+    with_output_schema,
+};
+
+const REVIEWER_SCHEMA: &str = include_str!("../schemas/reviewer.json");
+const REVIEWEE_SCHEMA: &str = include_str!("../schemas/reviewee.json");
+
+/// Shell commands the reviewer may run (read-only).
+/// `gh api` is not listed because a prefix match cannot restrict it to GET requests.
+const REVIEWER_SHELL_COMMANDS: &[&str] = &[
+    "gh pr view",
+    "gh pr diff",
+    "gh pr checks",
+    "git status",
+    "git diff",
+    "git log",
+    "git show",
+];
+
+/// Shell commands the reviewee may run.
+/// NOTE: git push is NOT included; it is only added when the user grants it.
+const REVIEWEE_SHELL_COMMANDS: &[&str] = &[
+    "git status",
+    "git diff",
+    "git add",
+    "git commit",
+    "git log",
+    "git show",
+    "git branch",
+    "git switch",
+    "git stash",
+    "gh pr view",
+    "gh pr diff",
+    "gh pr checks",
+    "cargo build",
+    "cargo test",
+    "cargo check",
+    "cargo clippy",
+    "cargo fmt",
+    "cargo run",
+    "npm install",
+    "npm test",
+    "npm run",
+    "npm ci",
+    "pnpm install",
+    "pnpm test",
+    "pnpm run",
+    "bun install",
+    "bun test",
+    "bun run",
+];
+
+/// Git commands removed from the reviewee's shell commands in local mode
+const GIT_WRITE_COMMANDS: &[&str] = &[
+    "git add",
+    "git commit",
+    "git branch",
+    "git switch",
+    "git stash",
+];
+
+/// Gemini-specific errors
+#[derive(Debug, Error)]
+pub enum GeminiError {
+    #[error(
+        "Gemini authentication failed. Set GEMINI_API_KEY or [ai.gemini] api_key, \
+         or run 'gemini' once to sign in"
+    )]
+    AuthenticationFailed,
+    #[error("Turn failed: {reason}")]
+    TurnFailed { reason: String },
+}
+
+/// Google Gemini CLI adapter
+pub struct GeminiAdapter {
+    /// Model passed to --model (None uses the CLI's default)
+    model: Option<String>,
+    /// API key passed as GEMINI_API_KEY
+    api_key: Option<String>,
+    /// Tools granted to the reviewee through permission requests
+    reviewee_granted_tools: Vec<String>,
+    reviewer_session_id: Option<String>,
+    reviewee_session_id: Option<String>,
+    event_sender: Option<mpsc::Sender<RallyEvent>>,
+    /// When true, git write commands are removed from the reviewee's allowed tools
+    local_mode: bool,
+    /// When true, the reviewee cannot edit files, write with git or use granted tools
+    dry_run: bool,
+    /// Extra environment variables for the gemini process (`[env]` and the API key)
+    env: SpawnEnv,
+    /// Token usage reported by result events since the last `take_usage`
+    usage: TokenUsage,
+}
+
+impl GeminiAdapter {
+    pub fn new(config: &GeminiConfig) -> Self {
+        let mut adapter = Self {
+            model: config.model.clone(),
+            api_key: config.api_key.clone(),
+            reviewee_granted_tools: Vec::new(),
+            reviewer_session_id: None,
+            reviewee_session_id: None,
+            event_sender: None,
+            local_mode: false,
+            dry_run: false,
+            env: SpawnEnv::default(),
+            usage: TokenUsage::default(),
+        };
+        adapter.set_env(SpawnEnv::default());
+        adapter
+    }
+
+    /// Allowed tools for the reviewer. Read-only file tools need no permission.
+    pub(crate) fn reviewer_allowed_tools() -> String {
+        REVIEWER_SHELL_COMMANDS
+            .iter()
+            .map(|command| shell_tool(command))
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    /// Allowed tools for the reviewee, including tools granted during the rally
+    /// (except in dry-run mode)
+    pub(crate) fn reviewee_allowed_tools(&self) -> String {
+        let read_only = self.local_mode || self.dry_run;
+        let granted: &[String] = if self.dry_run {
+            &[]
+        } else {
+            &self.reviewee_granted_tools
+        };
+        REVIEWEE_SHELL_COMMANDS
+            .iter()
+            .filter(|command| !(read_only && GIT_WRITE_COMMANDS.contains(command)))
+            .map(|command| shell_tool(command))
+            .chain(granted.iter().cloned())
+            .collect::<Vec<_>>()
+            .join(",")
+    }
+
+    async fn send_event(&self, event: RallyEvent) {
+        if let Some(ref sender) = self.event_sender {
+            let _ = sender.send(event.redact(&self.env)).await;
+        }
+    }
+
+    /// Run Gemini CLI with stream-json output
+    ///
+    /// The reviewer runs in the default approval mode (read-only tools and the listed
+    /// shell commands); the reviewee runs in auto_edit mode so it can edit files,
+    /// except in dry-run mode.
+    async fn run_gemini_streaming(
+        &self,
+        prompt: &str,
+        schema: &str,
+        reviewee: bool,
+        working_dir: Option<&str>,
+        session_id: Option<&str>,
+    ) -> Result<GeminiResponse> {
+        let mut cmd = Command::new("gemini");
+        // Prevent nested session detection when octorus is run inside Claude Code
+        cmd.env_remove("CLAUDECODE");
+        cmd.envs(self.env.vars());
+        // Without --prompt, gemini reads the prompt from stdin and runs non-interactively.
+        // This avoids the OS ARG_MAX limit for large diffs.
+        cmd.arg("--output-format").arg("stream-json");
+        if let Some(ref model) = self.model {
+            cmd.arg("--model").arg(model);
+        }
+        let allowed_tools = if reviewee {
+            if !self.dry_run {
+                cmd.arg("--approval-mode").arg("auto_edit");
+            }
+            self.reviewee_allowed_tools()
+        } else {
+            Self::reviewer_allowed_tools()
+        };
+        cmd.arg("--allowed-tools").arg(allowed_tools);
+
+        if let Some(session) = session_id {
+            cmd.arg("--resume").arg(session);
+        }
+
+        if let Some(dir) = working_dir {
+            cmd.current_dir(dir);
+        }
+
+        cmd.stdin(Stdio::piped());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+
+        let mut child = cmd.spawn().with_context(|| {
+            format!(
+                "Failed to spawn gemini process (command: {:?})",
+                cmd.as_std()
+            )
+        })?;
+
+        if let Some(mut stdin) = child.stdin.take() {
+            use tokio::io::AsyncWriteExt;
+            // Gemini CLI has no structured output option, so the schema goes in the prompt
+            let input = with_output_schema(prompt, schema);
+            stdin
+                .write_all(input.as_bytes())
+                .await
+                .context("Failed to write prompt to gemini stdin")?;
+            drop(stdin); // Close stdin to signal EOF
+        }
+
+        let stdout = child.stdout.take().expect("stdout should be available");
+        let stderr = child.stderr.take().expect("stderr should be available");
+
+        let mut stdout_reader = BufReader::new(stdout).lines();
+        let mut stderr_reader = BufReader::new(stderr).lines();
+
+        let mut state = StreamState::default();
+        let mut error_lines = Vec::new();
+        let mut stream_error: Option<anyhow::Error> = None;
+
+        // Process NDJSON stream
+        loop {
+            tokio::select! {
+                line = stdout_reader.next_line() => {
+                    match line {
+                        Ok(Some(l)) => {
+                            if l.trim().is_empty() {
+                                continue;
+                            }
+                            // Lines that are not events (e.g. CLI notices) are ignored
+                            if let Ok(event) = serde_json::from_str::<GeminiEvent>(&l) {
+                                if let Err(e) = self.handle_gemini_event(event, &mut state).await {
+                                    // Capture error but continue to wait for process
+                                    stream_error = Some(e);
+                                    break;
+                                }
+                            }
+                        }
+                        Ok(None) => break,
+                        Err(e) => {
+                            stream_error = Some(anyhow!("Error reading stdout: {}", e));
+                            break;
+                        }
+                    }
+                }
+                line = stderr_reader.next_line() => {
+                    match line {
+                        Ok(Some(l)) => error_lines.push(l),
+                        Ok(None) => {},
+                        Err(e) => {
+                            stream_error = Some(anyhow!("Error reading stderr: {}", e));
+                            break;
+                        }
+                    }
+                }
+            }
+        }
+
+        // Always wait for the child process to terminate before returning
+        let status = match child.wait().await {
+            Ok(s) => s,
+            Err(e) => {
+                let _ = child.kill().await;
+                return Err(anyhow!("Failed to wait for gemini process: {}", e));
+            }
+        };
+
+        if let Some(e) = stream_error {
+            return Err(e);
+        }
+
+        if !status.success() {
+            let stderr_output = error_lines.join("\n");
+
+            if stderr_output.contains("GEMINI_API_KEY")
+                || stderr_output.contains("auth")
+                || stderr_output.contains("API key not valid")
+            {
+                return Err(GeminiError::AuthenticationFailed.into());
+            }
+
+            return Err(anyhow!(
+                "Gemini process failed with status {}: {}",
+                status,
+                stderr_output
+            ));
+        }
+
+        if !state.finished {
+            return Err(anyhow!("No result received from gemini"));
+        }
+        // Without a JSON object the response is still returned so that the session is kept
+        // and the orchestrator can ask for a corrected answer
+        let result = extract_json(&state.text);
+        if result.is_none() {
+            tracing::warn!(
+                "Gemini did not reply with a JSON object: {}",
+                summarize_text(&state.text)
+            );
+        }
+
+        Ok(GeminiResponse {
+            // Keep the resumed session if gemini did not report a new one
+            session_id: state.session_id.or_else(|| session_id.map(str::to_string)),
+            result,
+            usage: state.usage,
+        })
+    }
+
+    /// Handle a stream-json event and convert it to RallyEvent
+    async fn handle_gemini_event(&self, event: GeminiEvent, state: &mut StreamState) -> Result<()> {
+        match event {
+            GeminiEvent::Init { session_id } => {
+                state.session_id = session_id;
+                self.send_event(RallyEvent::AgentThinking("Starting...".to_string()))
+                    .await;
+            }
+            GeminiEvent::Message { role, content } => {
+                // The prompt is echoed back as a user message
+                if role == "assistant" {
+                    state.text.push_str(&content);
+                    self.send_event(RallyEvent::AgentText(content)).await;
+                }
+            }
+            GeminiEvent::ToolUse {
+                tool_name,
+                tool_id,
+                parameters,
+            } => {
+                // The answer is the text after the last tool call
+                state.text.clear();
+                let input_summary = parameters.as_ref().map(summarize_json).unwrap_or_default();
+                state.tools.insert(tool_id, tool_name.clone());
+                self.send_event(RallyEvent::AgentToolUse(tool_name, input_summary))
+                    .await;
+            }
+            GeminiEvent::ToolResult {
+                tool_id,
+                status,
+                output,
+                error,
+            } => {
+                let tool_name = state
+                    .tools
+                    .remove(&tool_id)
+                    .unwrap_or_else(|| "tool".to_string());
+                let result_summary = match (error, output) {
+                    (Some(error), _) if status == "error" => {
+                        format!("error: {}", summarize_text(&error.message))
+                    }
+                    (_, Some(output)) if !output.trim().is_empty() => summarize_text(&output),
+                    _ => status,
+                };
+                self.send_event(RallyEvent::AgentToolResult(tool_name, result_summary))
+                    .await;
+            }
+            GeminiEvent::Error { severity, message } => {
+                if severity == "error" {
+                    return Err(GeminiError::TurnFailed { reason: message }.into());
+                }
+                self.send_event(RallyEvent::Log(format!("gemini: {}", message)))
+                    .await;
+            }
+            GeminiEvent::Finished {
+                status,
+                error,
+                stats,
+            } => {
+                if let Some(stats) = stats {
+                    state.usage.input_tokens += stats.input_tokens;
+                    state.usage.output_tokens += stats.output_tokens;
+                }
+                if status != "success" {
+                    let reason = error
+                        .map(|e| e.message)
+                        .unwrap_or_else(|| format!("status {}", status));
+                    return Err(GeminiError::TurnFailed { reason }.into());
+                }
+                state.finished = true;
+            }
+            GeminiEvent::Unknown => {}
+        }
+        Ok(())
+    }
+}
+
+impl Default for GeminiAdapter {
+    fn default() -> Self {
+        Self::new(&GeminiConfig::default())
+    }
+}
+
+#[async_trait]
+impl AgentAdapter for GeminiAdapter {
+    fn name(&self) -> &str {
+        "gemini"
+    }
+
+    fn set_event_sender(&mut self, sender: mpsc::Sender<RallyEvent>) {
+        self.event_sender = Some(sender);
+    }
+
+    async fn run_reviewer(&mut self, prompt: &str, context: &Context) -> Result<ReviewerOutput> {
+        let response = self
+            .run_gemini_streaming(
+                prompt,
+                REVIEWER_SCHEMA,
+                false,
+                context.working_dir.as_deref(),
+                None,
+            )
+            .await?;
+
+        self.usage.add(&response.usage);
+        self.reviewer_session_id = response.session_id;
+
+        parse_reviewer_output(response.result.as_ref(), "gemini")
+    }
+
+    async fn run_reviewee(&mut self, prompt: &str, context: &Context) -> Result<RevieweeOutput> {
+        let response = self
+            .run_gemini_streaming(
+                prompt,
+                REVIEWEE_SCHEMA,
+                true,
+                context.working_dir.as_deref(),
+                None,
+            )
+            .await?;
+
+        self.usage.add(&response.usage);
+        self.reviewee_session_id = response.session_id;
+
+        parse_reviewee_output(response.result.as_ref(), "gemini")
+    }
+
+    async fn continue_reviewer(&mut self, message: &str) -> Result<ReviewerOutput> {
+        let session_id = self
+            .reviewer_session_id
+            .clone()
+            .ok_or_else(|| anyhow!("No reviewer session to continue"))?;
+
+        let response = self
+            .run_gemini_streaming(message, REVIEWER_SCHEMA, false, None, Some(&session_id))
+            .await?;
+        self.usage.add(&response.usage);
+        parse_reviewer_output(response.result.as_ref(), "gemini")
+    }
+
+    async fn continue_reviewee(&mut self, message: &str) -> Result<RevieweeOutput> {
+        let session_id = self
+            .reviewee_session_id
+            .clone()
+            .ok_or_else(|| anyhow!("No reviewee session to continue"))?;
+
+        let response = self
+            .run_gemini_streaming(message, REVIEWEE_SCHEMA, true, None, Some(&session_id))
+            .await?;
+        self.usage.add(&response.usage);
+        parse_reviewee_output(response.result.as_ref(), "gemini")
+    }
+
+    fn add_reviewee_allowed_tool(&mut self, tool: &str) {
+        let tool = gemini_tool(tool);
+        if !self.reviewee_granted_tools.contains(&tool) {
+            self.reviewee_granted_tools.push(tool);
+        }
+    }
+
+    fn set_local_mode(&mut self, local_mode: bool) {
+        self.local_mode = local_mode;
+    }
+
+    fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    fn set_env(&mut self, env: SpawnEnv) {
+        self.env = match self.api_key {
+            Some(ref key) => env.with_var("GEMINI_API_KEY", key),
+            None => env,
+        };
+    }
+
+    fn take_usage(&mut self) -> TokenUsage {
+        std::mem::take(&mut self.usage)
+    }
+
+    fn session_ids(&self) -> (Option<String>, Option<String>) {
+        (
+            self.reviewer_session_id.clone(),
+            self.reviewee_session_id.clone(),
+        )
+    }
+
+    fn restore_session_ids(&mut self, reviewer: Option<String>, reviewee: Option<String>) {
+        self.reviewer_session_id = reviewer;
+        self.reviewee_session_id = reviewee;
+    }
+}
+
+fn shell_tool(command: &str) -> String {
+    format!("run_shell_command({})", command)
+}
+
+/// Convert a granted tool to Gemini's format.
+/// Claude-style `Bash(git push:*)` becomes `run_shell_command(git push)`.
+fn gemini_tool(tool: &str) -> String {
+    match tool
+        .strip_prefix("Bash(")
+        .and_then(|rest| rest.strip_suffix(')'))
+    {
+        Some(command) => shell_tool(command.strip_suffix(":*").unwrap_or(command)),
+        None => tool.to_string(),
+    }
+}
+
+/// State collected while reading the stream
+#[derive(Debug, Default)]
+struct StreamState {
+    session_id: Option<String>,
+    /// Assistant text since the last tool call
+    text: String,
+    /// tool_id → tool name (tool_result events only carry the ID)
+    tools: HashMap<String, String>,
+    /// A successful result event was received
+    finished: bool,
+    /// Token counts from the result event's stats
+    usage: TokenUsage,
+}
+
+// Gemini CLI stream-json event types
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+enum GeminiEvent {
+    Init {
+        #[serde(default)]
+        session_id: Option<String>,
+    },
+    Message {
+        role: String,
+        #[serde(default)]
+        content: String,
+    },
+    ToolUse {
+        tool_name: String,
+        #[serde(default)]
+        tool_id: String,
+        #[serde(default)]
+        parameters: Option<serde_json::Value>,
+    },
+    ToolResult {
+        #[serde(default)]
+        tool_id: String,
+        #[serde(default)]
+        status: String,
+        #[serde(default)]
+        output: Option<String>,
+        #[serde(default)]
+        error: Option<GeminiErrorInfo>,
+    },
+    Error {
+        #[serde(default)]
+        severity: String,
+        message: String,
+    },
+    #[serde(rename = "result")]
+    Finished {
+        status: String,
+        #[serde(default)]
+        error: Option<GeminiErrorInfo>,
+        #[serde(default)]
+        stats: Option<GeminiStats>,
+    },
+    #[serde(other)]
+    Unknown,
+}
+
+/// Error info in tool_result and result events
+#[derive(Debug, Deserialize)]
+struct GeminiErrorInfo {
+    #[serde(default)]
+    message: String,
+}
+
+/// Token counts in the result event
+#[derive(Debug, Deserialize)]
+struct GeminiStats {
+    #[serde(default)]
+    input_tokens: u64,
+    #[serde(default)]
+    output_tokens: u64,
+}
+
+/// Gemini response structure
+#[derive(Debug)]
+struct GeminiResponse {
+    session_id: Option<String>,
+    result: Option<serde_json::Value>,
+    usage: TokenUsage,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_stream_events() {
+        let event: GeminiEvent = serde_json::from_str(
+            r#"{"type":"init","timestamp":"2025-01-01T00:00:00Z","session_id":"abc","model":"gemini-2.5-pro"}"#,
+        )
+        .unwrap();
+        assert!(matches!(event, GeminiEvent::Init { session_id: Some(ref id) } if id == "abc"));
+
+        let event: GeminiEvent = serde_json::from_str(
+            r#"{"type":"tool_use","tool_name":"read_file","tool_id":"t1","parameters":{"file_path":"src/lib.rs"}}"#,
+        )
+        .unwrap();
+        assert!(
+            matches!(event, GeminiEvent::ToolUse { ref tool_name, .. } if tool_name == "read_file")
+        );
+
+        let event: GeminiEvent = serde_json::from_str(
+            r#"{"type":"result","status":"success","stats":{"total_tokens":10}}"#,
+        )
+        .unwrap();
+        assert!(matches!(event, GeminiEvent::Finished { ref status, .. } if status == "success"));
+
+        let event: GeminiEvent = serde_json::from_str(r#"{"type":"something_new"}"#).unwrap();
+        assert!(matches!(event, GeminiEvent::Unknown));
+    }
+
+    #[tokio::test]
+    async fn test_handle_events_keeps_text_after_last_tool() {
+        let (tx, mut rx) = mpsc::channel(16);
+        let mut adapter = GeminiAdapter::default();
+        adapter.set_event_sender(tx);
+        let mut state = StreamState::default();
+        let lines = [
+            r#"{"type":"init","session_id":"s1"}"#,
+            r#"{"type":"message","role":"user","content":"Review this PR"}"#,
+            r#"{"type":"message","role":"assistant","content":"Let me read it.","delta":true}"#,
+            r#"{"type":"tool_use","tool_name":"read_file","tool_id":"t1","parameters":{"file_path":"a.rs"}}"#,
+            r#"{"type":"tool_result","tool_id":"t1","status":"success","output":"fn main() {}"}"#,
+            r#"{"type":"message","role":"assistant","content":"{\"action\":","delta":true}"#,
+            r#"{"type":"message","role":"assistant","content":"\"approve\"}","delta":true}"#,
+            r#"{"type":"result","status":"success","stats":{"total_tokens":15,"input_tokens":12,"output_tokens":3}}"#,
+        ];
+        for line in lines {
+            let event = serde_json::from_str(line).unwrap();
+            adapter
+                .handle_gemini_event(event, &mut state)
+                .await
+                .unwrap();
+        }
+        assert_eq!(state.session_id.as_deref(), Some("s1"));
+        assert_eq!(state.text, r#"{"action":"approve"}"#);
+        assert!(state.finished);
+        assert_eq!(state.usage.input_tokens, 12);
+        assert_eq!(state.usage.output_tokens, 3);
+
+        let mut tool_result = None;
+        while let Ok(event) = rx.try_recv() {
+            if let RallyEvent::AgentToolResult(name, summary) = event {
+                tool_result = Some((name, summary));
+            }
+        }
+        assert_eq!(
+            tool_result,
+            Some(("read_file".to_string(), "fn main() {}".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_handle_error_events() {
+        let adapter = GeminiAdapter::default();
+        let mut state = StreamState::default();
+        let warning = serde_json::from_str(
+            r#"{"type":"error","severity":"warning","message":"Loop detected"}"#,
+        )
+        .unwrap();
+        assert!(adapter
+            .handle_gemini_event(warning, &mut state)
+            .await
+            .is_ok());
+
+        let failed = serde_json::from_str(
+            r#"{"type":"result","status":"error","error":{"type":"FatalError","message":"Quota exceeded"}}"#,
+        )
+        .unwrap();
+        let err = adapter
+            .handle_gemini_event(failed, &mut state)
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Turn failed: Quota exceeded");
+    }
+
+    #[test]
+    fn test_reviewee_allowed_tools() {
+        let mut adapter = GeminiAdapter::default();
+        let tools = adapter.reviewee_allowed_tools();
+        assert!(tools.contains("run_shell_command(git commit)"));
+        assert!(!tools.contains("git push"));
+
+        // Granted tools in Claude's format are converted
+        adapter.add_reviewee_allowed_tool("Bash(git push:*)");
+        adapter.add_reviewee_allowed_tool("Bash(git push:*)");
+        let tools = adapter.reviewee_allowed_tools();
+        assert_eq!(tools.matches("run_shell_command(git push)").count(), 1);
+
+        // Local mode blocks git write commands
+        adapter.set_local_mode(true);
+        let tools = adapter.reviewee_allowed_tools();
+        assert!(!tools.contains("run_shell_command(git commit)"));
+        assert!(tools.contains("run_shell_command(git diff)"));
+
+        // Dry run also drops the granted tools
+        adapter.set_local_mode(false);
+        adapter.set_dry_run(true);
+        let tools = adapter.reviewee_allowed_tools();
+        assert!(!tools.contains("run_shell_command(git commit)"));
+        assert!(!tools.contains("run_shell_command(git push)"));
+        assert!(tools.contains("run_shell_command(git diff)"));
+    }
+
+    #[test]
+    fn test_reviewer_allowed_tools_are_read_only() {
+        let tools = GeminiAdapter::reviewer_allowed_tools();
+        assert!(tools.contains("run_shell_command(gh pr diff)"));
+        assert!(!tools.contains("gh api"));
+        assert!(!tools.contains("git commit"));
+    }
+
+    #[test]
+    fn test_api_key_is_passed_and_redacted() {
+        let config = GeminiConfig {
+            model: None,
+            api_key: Some("gemini-secret-key".to_string()),
+        };
+        let mut adapter = GeminiAdapter::new(&config);
+        adapter.set_env(SpawnEnv::default());
+        assert!(adapter
+            .env
+            .vars()
+            .any(|var| var == ("GEMINI_API_KEY", "gemini-secret-key")));
+        assert_eq!(
+            adapter.env.redact("key gemini-secret-key"),
+            "key [redacted]"
+        );
+    }
+}
@@ -1,9 +1,15 @@
 mod claude;
 mod codex;
 mod common;
+mod gemini;
+mod openai;
 
 pub use claude::ClaudeAdapter;
 pub use codex::CodexAdapter;
+pub use gemini::GeminiAdapter;
+pub use openai::OpenAiAdapter;
+
+pub(crate) use common::OutputFormatError;
 
 use anyhow::{anyhow, Result};
 
@@ -13,17 +19,25 @@ use crate::config::AiConfig;
 /// Create an adapter from agent name.
 ///
 /// # Arguments
-/// * `name` - Agent name ("claude" or "codex")
-/// * `config` - AI configuration (used by Claude adapter for additional tools, ignored by Codex)
+/// * `name` - Agent name ("claude", "codex", "gemini" or "openai")
+/// * `config` - AI configuration (additional tools for Claude, model and API key for Gemini,
+///   endpoint for OpenAI-compatible servers, ignored by Codex)
 pub fn create_adapter(name: &str, config: &AiConfig) -> Result<Box<dyn AgentAdapter>> {
-    let agent = SupportedAgent::from_name(name)
-        .ok_or_else(|| anyhow!("Unsupported agent: {}. Supported: claude, codex", name))?;
+    let agent = SupportedAgent::from_name(name).ok_or_else(|| {
+        anyhow!(
+            "Unsupported agent: {}. Supported: claude, codex, gemini, openai",
+            name
+        )
+    })?;
 
     match agent {
         // Claude adapter uses config for additional tools
         SupportedAgent::Claude => Ok(Box::new(ClaudeAdapter::new(config))),
         // Codex adapter does not support fine-grained tool control
         SupportedAgent::Codex => Ok(Box::new(CodexAdapter::new())),
-        // SupportedAgent::Gemini => Ok(Box::new(GeminiAdapter::new())),
+        // Gemini adapter uses config for model and API key
+        SupportedAgent::Gemini => Ok(Box::new(GeminiAdapter::new(&config.gemini))),
+        // OpenAI-compatible adapter talks to the configured endpoint over HTTP
+        SupportedAgent::OpenAi => Ok(Box::new(OpenAiAdapter::new(&config.openai)?)),
     }
 }
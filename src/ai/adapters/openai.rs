@@ -0,0 +1,917 @@
+use std::path::{Component, Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context as AnyhowContext, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tokio::sync::mpsc;
+
+use crate::ai::adapter::{AgentAdapter, Context, RevieweeOutput, ReviewerOutput, TokenUsage};
+use crate::ai::orchestrator::RallyEvent;
+use crate::config::OpenAiConfig;
+use crate::spawn_env::SpawnEnv;
+
+use super::common::{
+    extract_json, parse_reviewee_output, parse_reviewer_output, summarize_text, with_output_schema,
+    OutputFormatError,
+};
+
+const REVIEWER_SCHEMA: &str = include_str!("../schemas/reviewer.json");
+const REVIEWEE_SCHEMA: &str = include_str!("../schemas/reviewee.json");
+
+/// Rounds of tool calls allowed before giving up on a reply
+const MAX_TOOL_ROUNDS: usize = 30;
+/// Files larger than this are truncated when read by the model
+const MAX_FILE_BYTES: usize = 200_000;
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Adapter for any OpenAI-compatible chat endpoint (ollama, vLLM, LM Studio, ...)
+///
+/// Unlike the CLI adapters there is no agent process: the conversation is kept here and
+/// the model gets file tools through function calling. It cannot run shell commands,
+/// so git and build commands are never executed.
+pub struct OpenAiAdapter {
+    /// `{base_url}/chat/completions`
+    url: String,
+    model: String,
+    api_key: Option<String>,
+    http: reqwest::Client,
+    reviewer: Conversation,
+    reviewee: Conversation,
+    event_sender: Option<mpsc::Sender<RallyEvent>>,
+    /// When true, write_file calls are answered without writing anything
+    dry_run: bool,
+    /// Used only to redact `[env]` secrets from streamed events
+    env: SpawnEnv,
+}
+
+/// Messages of one role, kept for continue_reviewer / continue_reviewee
+#[derive(Default)]
+struct Conversation {
+    messages: Vec<ChatMessage>,
+    /// Directory the file tools work in
+    root: PathBuf,
+    /// Token usage reported since the last `take_usage`
+    usage: TokenUsage,
+}
+
+impl OpenAiAdapter {
+    pub fn new(config: &OpenAiConfig) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .user_agent(concat!("octorus/", env!("CARGO_PKG_VERSION")))
+            .connect_timeout(CONNECT_TIMEOUT)
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self {
+            url: format!("{}/chat/completions", config.base_url.trim_end_matches('/')),
+            model: config.model.clone(),
+            api_key: config.api_key.clone(),
+            http,
+            reviewer: Conversation::default(),
+            reviewee: Conversation::default(),
+            event_sender: None,
+            dry_run: false,
+            env: SpawnEnv::default(),
+        })
+    }
+
+    async fn send_event(&self, event: RallyEvent) {
+        if let Some(ref sender) = self.event_sender {
+            let _ = sender.send(event.redact(&self.env)).await;
+        }
+    }
+
+    /// Run the conversation until the model answers without tool calls,
+    /// and return the JSON object in that answer
+    async fn run_chat(
+        &self,
+        conversation: &mut Conversation,
+        write: bool,
+    ) -> Result<serde_json::Value> {
+        let tools = tool_definitions(write);
+        for _ in 0..MAX_TOOL_ROUNDS {
+            let completion = self
+                .stream_completion(&conversation.messages, &tools)
+                .await?;
+            conversation.usage.add(&completion.usage);
+            conversation.messages.push(ChatMessage::assistant(
+                completion.content.clone(),
+                completion.tool_calls.clone(),
+            ));
+            if completion.tool_calls.is_empty() {
+                // The answer stays in the conversation, so the orchestrator can ask for a fix
+                return extract_json(&completion.content).ok_or_else(|| {
+                    OutputFormatError {
+                        role: if write { "reviewee" } else { "reviewer" },
+                        agent: "openai".to_string(),
+                        problems: vec![format!(
+                            "The model did not reply with a JSON object: {}",
+                            summarize_text(&completion.content)
+                        )],
+                    }
+                    .into()
+                });
+            }
+            for call in completion.tool_calls {
+                let name = call.function.name;
+                self.send_event(RallyEvent::AgentToolUse(
+                    name.clone(),
+                    summarize_text(&call.function.arguments),
+                ))
+                .await;
+                let result = if self.dry_run && write && name == "write_file" {
+                    dry_run_write(&call.function.arguments)
+                } else {
+                    execute_tool(&conversation.root, &name, &call.function.arguments, write)
+                };
+                self.send_event(RallyEvent::AgentToolResult(name, summarize_text(&result)))
+                    .await;
+                conversation
+                    .messages
+                    .push(ChatMessage::tool(call.id, result));
+            }
+        }
+        Err(anyhow!(
+            "The model kept calling tools for {} rounds without answering",
+            MAX_TOOL_ROUNDS
+        ))
+    }
+
+    /// Send one chat completion request and read its SSE stream
+    async fn stream_completion(
+        &self,
+        messages: &[ChatMessage],
+        tools: &[serde_json::Value],
+    ) -> Result<Completion> {
+        if self.model.is_empty() {
+            return Err(anyhow!("Set [ai.openai] model to use the openai agent"));
+        }
+        let body = json!({
+            "model": self.model,
+            "messages": messages,
+            "tools": tools,
+            "stream": true,
+            // Servers that support it send token counts in the last chunk
+            "stream_options": {"include_usage": true},
+        });
+        let mut request = self.http.post(&self.url).json(&body);
+        if let Some(ref key) = self.api_key {
+            request = request.bearer_auth(key);
+        }
+        let mut response = request
+            .send()
+            .await
+            .with_context(|| format!("Failed to connect to {}", self.url))?;
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(anyhow!("{} returned {}: {}", self.url, status, text.trim()));
+        }
+
+        let mut parser = SseParser::default();
+        let mut completion = Completion::default();
+        while let Some(bytes) = response
+            .chunk()
+            .await
+            .context("Failed to read the response stream")?
+        {
+            for data in parser.push(&bytes) {
+                if data == "[DONE]" {
+                    return Ok(completion);
+                }
+                let chunk: ChatChunk = serde_json::from_str(&data)
+                    .with_context(|| format!("Invalid stream chunk: {}", summarize_text(&data)))?;
+                self.apply_chunk(chunk, &mut completion).await?;
+            }
+        }
+        Ok(completion)
+    }
+
+    /// Add a stream chunk to the completion and stream its text to the log
+    async fn apply_chunk(&self, chunk: ChatChunk, completion: &mut Completion) -> Result<()> {
+        if let Some(error) = chunk.error {
+            return Err(anyhow!("The model returned an error: {}", error.message));
+        }
+        if let Some(usage) = chunk.usage {
+            completion.usage.input_tokens += usage.prompt_tokens;
+            completion.usage.output_tokens += usage.completion_tokens;
+        }
+        for choice in chunk.choices {
+            let delta = choice.delta;
+            if let Some(thinking) = delta.reasoning_content.or(delta.reasoning) {
+                if !thinking.is_empty() {
+                    self.send_event(RallyEvent::AgentThinking(thinking)).await;
+                }
+            }
+            if let Some(text) = delta.content {
+                if !text.is_empty() {
+                    completion.content.push_str(&text);
+                    self.send_event(RallyEvent::AgentText(text)).await;
+                }
+            }
+            for call in delta.tool_calls {
+                // Some servers (ollama) send each tool call whole, without an index
+                let index = call.index.unwrap_or(completion.tool_calls.len());
+                if completion.tool_calls.len() <= index {
+                    completion
+                        .tool_calls
+                        .resize_with(index + 1, ToolCall::default);
+                }
+                let target = &mut completion.tool_calls[index];
+                if let Some(id) = call.id {
+                    target.id = id;
+                }
+                if let Some(function) = call.function {
+                    if let Some(name) = function.name {
+                        target.function.name.push_str(&name);
+                    }
+                    if let Some(arguments) = function.arguments {
+                        target.function.arguments.push_str(&arguments);
+                    }
+                }
+            }
+        }
+        // Tool results must refer to an ID, which some servers leave out
+        for (index, call) in completion.tool_calls.iter_mut().enumerate() {
+            if call.id.is_empty() {
+                call.id = format!("call_{}", index);
+            }
+        }
+        Ok(())
+    }
+
+    fn start(
+        conversation: &mut Conversation,
+        prompt: &str,
+        schema: &str,
+        context: &Context,
+        write: bool,
+    ) {
+        conversation.root = PathBuf::from(context.working_dir.as_deref().unwrap_or("."));
+        conversation.messages = vec![
+            ChatMessage::system(system_prompt(write)),
+            ChatMessage::user(with_output_schema(prompt, schema)),
+        ];
+    }
+}
+
+#[async_trait]
+impl AgentAdapter for OpenAiAdapter {
+    fn name(&self) -> &str {
+        "openai"
+    }
+
+    fn set_event_sender(&mut self, sender: mpsc::Sender<RallyEvent>) {
+        self.event_sender = Some(sender);
+    }
+
+    async fn run_reviewer(&mut self, prompt: &str, context: &Context) -> Result<ReviewerOutput> {
+        let mut conversation = std::mem::take(&mut self.reviewer);
+        Self::start(&mut conversation, prompt, REVIEWER_SCHEMA, context, false);
+        let result = self.run_chat(&mut conversation, false).await;
+        self.reviewer = conversation;
+        parse_reviewer_output(Some(&result?), "openai")
+    }
+
+    async fn run_reviewee(&mut self, prompt: &str, context: &Context) -> Result<RevieweeOutput> {
+        let mut conversation = std::mem::take(&mut self.reviewee);
+        Self::start(&mut conversation, prompt, REVIEWEE_SCHEMA, context, true);
+        let result = self.run_chat(&mut conversation, true).await;
+        self.reviewee = conversation;
+        parse_reviewee_output(Some(&result?), "openai")
+    }
+
+    async fn continue_reviewer(&mut self, message: &str) -> Result<ReviewerOutput> {
+        if self.reviewer.messages.is_empty() {
+            return Err(anyhow!("No reviewer session to continue"));
+        }
+        let mut conversation = std::mem::take(&mut self.reviewer);
+        conversation
+            .messages
+            .push(ChatMessage::user(with_output_schema(
+                message,
+                REVIEWER_SCHEMA,
+            )));
+        let result = self.run_chat(&mut conversation, false).await;
+        self.reviewer = conversation;
+        parse_reviewer_output(Some(&result?), "openai")
+    }
+
+    async fn continue_reviewee(&mut self, message: &str) -> Result<RevieweeOutput> {
+        if self.reviewee.messages.is_empty() {
+            return Err(anyhow!("No reviewee session to continue"));
+        }
+        let mut conversation = std::mem::take(&mut self.reviewee);
+        conversation
+            .messages
+            .push(ChatMessage::user(with_output_schema(
+                message,
+                REVIEWEE_SCHEMA,
+            )));
+        let result = self.run_chat(&mut conversation, true).await;
+        self.reviewee = conversation;
+        parse_reviewee_output(Some(&result?), "openai")
+    }
+
+    fn add_reviewee_allowed_tool(&mut self, _tool: &str) {
+        // The model only has file tools and cannot run commands,
+        // so there is nothing to grant. This is a no-op.
+    }
+
+    fn set_local_mode(&mut self, _local_mode: bool) {
+        // No git commands are ever run, so local mode needs no extra restriction.
+    }
+
+    fn set_dry_run(&mut self, dry_run: bool) {
+        self.dry_run = dry_run;
+    }
+
+    fn set_env(&mut self, env: SpawnEnv) {
+        self.env = env;
+    }
+
+    fn take_usage(&mut self) -> TokenUsage {
+        let mut usage = std::mem::take(&mut self.reviewer.usage);
+        usage.add(&std::mem::take(&mut self.reviewee.usage));
+        usage
+    }
+}
+
+fn system_prompt(write: bool) -> String {
+    let tools = if write {
+        "read_file, list_files and write_file"
+    } else {
+        "read_file and list_files"
+    };
+    format!(
+        "You are working in a git repository. Use the {} tools with paths relative to the \
+repository root to inspect{} files. You cannot run shell commands.",
+        tools,
+        if write { " and edit" } else { "" }
+    )
+}
+
+/// Function definitions sent with each request
+fn tool_definitions(write: bool) -> Vec<serde_json::Value> {
+    let function =
+        |name: &str, description: &str, properties: serde_json::Value, required: &[&str]| {
+            json!({
+                "type": "function",
+                "function": {
+                    "name": name,
+                    "description": description,
+                    "parameters": {
+                        "type": "object",
+                        "properties": properties,
+                        "required": required,
+                    },
+                },
+            })
+        };
+    let mut tools = vec![
+        function(
+            "read_file",
+            "Read a file in the repository",
+            json!({"path": {"type": "string", "description": "Path relative to the repository root"}}),
+            &["path"],
+        ),
+        function(
+            "list_files",
+            "List the entries of a directory in the repository",
+            json!({"path": {"type": "string", "description": "Directory relative to the repository root (default: the root)"}}),
+            &[],
+        ),
+    ];
+    if write {
+        tools.push(function(
+            "write_file",
+            "Replace the contents of a file in the repository, creating it if needed",
+            json!({
+                "path": {"type": "string", "description": "Path relative to the repository root"},
+                "content": {"type": "string", "description": "The complete new contents"},
+            }),
+            &["path", "content"],
+        ));
+    }
+    tools
+}
+
+/// Run a tool call. Failures are returned as text so the model can react to them.
+fn execute_tool(root: &Path, name: &str, arguments: &str, write: bool) -> String {
+    match run_tool(root, name, arguments, write) {
+        Ok(output) => output,
+        Err(e) => format!("Error: {:#}", e),
+    }
+}
+
+/// Answer a write_file call in dry-run mode without touching the file
+fn dry_run_write(arguments: &str) -> String {
+    let path = serde_json::from_str::<serde_json::Value>(arguments)
+        .ok()
+        .and_then(|arguments| Some(arguments.get("path")?.as_str()?.to_string()))
+        .unwrap_or_default();
+    format!(
+        "Dry run: {} was not written. Describe the change in your summary instead.",
+        path
+    )
+}
+
+fn run_tool(root: &Path, name: &str, arguments: &str, write: bool) -> Result<String> {
+    let arguments: serde_json::Value = if arguments.trim().is_empty() {
+        json!({})
+    } else {
+        serde_json::from_str(arguments).context("Arguments are not valid JSON")?
+    };
+    let argument = |key: &str| arguments.get(key).and_then(serde_json::Value::as_str);
+    match name {
+        "read_file" => {
+            let path = resolve_path(root, argument("path").unwrap_or_default())?;
+            let bytes = std::fs::read(&path).context("Failed to read the file")?;
+            let mut text =
+                String::from_utf8_lossy(&bytes[..bytes.len().min(MAX_FILE_BYTES)]).into_owned();
+            if bytes.len() > MAX_FILE_BYTES {
+                text.push_str("\n... (truncated)");
+            }
+            Ok(text)
+        }
+        "list_files" => {
+            let path = resolve_path(root, argument("path").unwrap_or("."))?;
+            let mut entries: Vec<String> = std::fs::read_dir(&path)
+                .context("Failed to list the directory")?
+                .filter_map(|entry| {
+                    let entry = entry.ok()?;
+                    let mut name = entry.file_name().to_string_lossy().into_owned();
+                    if entry.file_type().ok()?.is_dir() {
+                        name.push('/');
+                    }
+                    Some(name)
+                })
+                .filter(|name| name != ".git/")
+                .collect();
+            entries.sort();
+            Ok(entries.join("\n"))
+        }
+        "write_file" if write => {
+            let path = resolve_path(root, argument("path").unwrap_or_default())?;
+            let content = argument("content").ok_or_else(|| anyhow!("Missing content"))?;
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent).context("Failed to create the directory")?;
+            }
+            std::fs::write(&path, content).context("Failed to write the file")?;
+            Ok(format!("Wrote {} bytes", content.len()))
+        }
+        _ => Err(anyhow!("Unknown tool: {}", name)),
+    }
+}
+
+/// Resolve a path from the model inside `root`. Absolute paths, `..` and `.git` are rejected.
+fn resolve_path(root: &Path, path: &str) -> Result<PathBuf> {
+    let relative = Path::new(path);
+    let mut resolved = root.to_path_buf();
+    for component in relative.components() {
+        match component {
+            Component::Normal(part) if part == ".git" => {
+                return Err(anyhow!("{} is inside .git", path));
+            }
+            Component::Normal(part) => resolved.push(part),
+            Component::CurDir => {}
+            _ => return Err(anyhow!("{} is outside the repository", path)),
+        }
+    }
+    Ok(resolved)
+}
+
+/// Splits a Server-Sent Events stream into `data:` payloads.
+/// Bytes are buffered until a full line arrives, so UTF-8 split across chunks is kept intact.
+#[derive(Debug, Default)]
+struct SseParser {
+    buffer: Vec<u8>,
+}
+
+impl SseParser {
+    fn push(&mut self, bytes: &[u8]) -> Vec<String> {
+        self.buffer.extend_from_slice(bytes);
+        let mut payloads = Vec::new();
+        while let Some(end) = self.buffer.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.buffer.drain(..=end).collect();
+            let line = String::from_utf8_lossy(&line);
+            if let Some(data) = line.trim_end().strip_prefix("data:") {
+                payloads.push(data.trim_start().to_string());
+            }
+        }
+        payloads
+    }
+}
+
+/// A reply read from the stream
+#[derive(Debug, Default)]
+struct Completion {
+    content: String,
+    tool_calls: Vec<ToolCall>,
+    usage: TokenUsage,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ChatMessage {
+    role: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    tool_calls: Vec<ToolCall>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tool_call_id: Option<String>,
+}
+
+impl ChatMessage {
+    fn new(role: &'static str, content: String) -> Self {
+        Self {
+            role,
+            content: Some(content),
+            tool_calls: Vec::new(),
+            tool_call_id: None,
+        }
+    }
+
+    fn system(content: String) -> Self {
+        Self::new("system", content)
+    }
+
+    fn user(content: String) -> Self {
+        Self::new("user", content)
+    }
+
+    fn assistant(content: String, tool_calls: Vec<ToolCall>) -> Self {
+        Self {
+            tool_calls,
+            ..Self::new("assistant", content)
+        }
+    }
+
+    fn tool(tool_call_id: String, content: String) -> Self {
+        Self {
+            tool_call_id: Some(tool_call_id),
+            ..Self::new("tool", content)
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ToolCall {
+    id: String,
+    #[serde(rename = "type")]
+    call_type: &'static str,
+    function: FunctionCall,
+}
+
+impl Default for ToolCall {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            call_type: "function",
+            function: FunctionCall::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+struct FunctionCall {
+    name: String,
+    /// JSON-encoded arguments
+    arguments: String,
+}
+
+/// One `data:` payload of the stream
+#[derive(Debug, Deserialize)]
+struct ChatChunk {
+    #[serde(default)]
+    choices: Vec<ChunkChoice>,
+    #[serde(default)]
+    error: Option<ApiError>,
+    /// Sent in the last chunk when `stream_options.include_usage` is honoured
+    #[serde(default)]
+    usage: Option<ChunkUsage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkUsage {
+    #[serde(default)]
+    prompt_tokens: u64,
+    #[serde(default)]
+    completion_tokens: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct ChunkChoice {
+    #[serde(default)]
+    delta: ChunkDelta,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ChunkDelta {
+    #[serde(default)]
+    content: Option<String>,
+    /// Thinking output of reasoning models (vLLM / DeepSeek)
+    #[serde(default)]
+    reasoning_content: Option<String>,
+    /// Thinking output of reasoning models (ollama)
+    #[serde(default)]
+    reasoning: Option<String>,
+    #[serde(default)]
+    tool_calls: Vec<ToolCallDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolCallDelta {
+    #[serde(default)]
+    index: Option<usize>,
+    #[serde(default)]
+    id: Option<String>,
+    #[serde(default)]
+    function: Option<FunctionDelta>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionDelta {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    arguments: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ApiError {
+    #[serde(default)]
+    message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+
+    fn context(working_dir: &Path) -> Context {
+        Context {
+            repo: "owner/repo".to_string(),
+            pr_number: 1,
+            pr_title: "Test".to_string(),
+            pr_body: None,
+            diff: String::new(),
+            working_dir: Some(working_dir.to_string_lossy().into_owned()),
+            head_sha: "abc".to_string(),
+            base_branch: "main".to_string(),
+            external_comments: Vec::new(),
+            local_mode: false,
+            file_patches: Vec::new(),
+            env: SpawnEnv::default(),
+            scope: Vec::new(),
+        }
+    }
+
+    /// Serve each response body as an SSE stream, one connection per response.
+    /// Returns the base URL and the received request bodies.
+    async fn serve(responses: Vec<String>) -> (String, tokio::task::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/v1", listener.local_addr().unwrap());
+        let handle = tokio::spawn(async move {
+            let mut requests = Vec::new();
+            for body in responses {
+                let (mut socket, _) = listener.accept().await.unwrap();
+                let mut received = Vec::new();
+                let mut buf = [0u8; 8192];
+                // Read the headers, then the body by Content-Length
+                loop {
+                    let n = socket.read(&mut buf).await.unwrap();
+                    received.extend_from_slice(&buf[..n]);
+                    let text = String::from_utf8_lossy(&received).to_string();
+                    if let Some(header_end) = text.find("\r\n\r\n") {
+                        let length = text[..header_end]
+                            .lines()
+                            .find_map(|l| {
+                                l.to_ascii_lowercase()
+                                    .strip_prefix("content-length:")
+                                    .map(|v| v.trim().parse::<usize>().unwrap())
+                            })
+                            .unwrap_or(0);
+                        if received.len() >= header_end + 4 + length {
+                            requests.push(text[header_end + 4..].to_string());
+                            break;
+                        }
+                    }
+                }
+                let response = format!(
+                    "HTTP/1.1 200 OK\r\ncontent-type: text/event-stream\r\nconnection: close\r\n\r\n{}",
+                    body
+                );
+                socket.write_all(response.as_bytes()).await.unwrap();
+                socket.shutdown().await.unwrap();
+            }
+            requests
+        });
+        (url, handle)
+    }
+
+    fn sse(chunks: &[serde_json::Value]) -> String {
+        let mut body: String = chunks
+            .iter()
+            .map(|chunk| format!("data: {}\n\n", chunk))
+            .collect();
+        body.push_str("data: [DONE]\n\n");
+        body
+    }
+
+    #[test]
+    fn test_sse_parser_buffers_partial_lines() {
+        let mut parser = SseParser::default();
+        let text = "data: {\"a\":\"日本\"}\n\n: keep-alive\ndata: [DONE]\n";
+        let bytes = text.as_bytes();
+        // Split inside the multi-byte character
+        let split = text.find('本').unwrap() + 1;
+        assert!(parser.push(&bytes[..split]).is_empty());
+        assert_eq!(
+            parser.push(&bytes[split..]),
+            vec!["{\"a\":\"日本\"}".to_string(), "[DONE]".to_string()]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_apply_chunk_joins_tool_call_deltas() {
+        let adapter = OpenAiAdapter::new(&OpenAiConfig::default()).unwrap();
+        let mut completion = Completion::default();
+        let chunks = [
+            json!({"choices": [{"delta": {"content": "Let me look."}}]}),
+            json!({"choices": [{"delta": {"tool_calls": [{"index": 0, "id": "c1", "function": {"name": "read_file", "arguments": "{\"pa"}}]}}]}),
+            json!({"choices": [{"delta": {"tool_calls": [{"index": 0, "function": {"arguments": "th\": \"a.rs\"}"}}]}}]}),
+            // ollama sends whole calls without index or ID
+            json!({"choices": [{"delta": {"tool_calls": [{"function": {"name": "list_files", "arguments": "{}"}}]}}]}),
+        ];
+        for chunk in chunks {
+            let chunk: ChatChunk = serde_json::from_value(chunk).unwrap();
+            adapter.apply_chunk(chunk, &mut completion).await.unwrap();
+        }
+        assert_eq!(completion.content, "Let me look.");
+        assert_eq!(completion.tool_calls.len(), 2);
+        assert_eq!(completion.tool_calls[0].id, "c1");
+        assert_eq!(completion.tool_calls[0].function.name, "read_file");
+        assert_eq!(
+            completion.tool_calls[0].function.arguments,
+            r#"{"path": "a.rs"}"#
+        );
+        assert_eq!(completion.tool_calls[1].id, "call_1");
+        assert_eq!(completion.tool_calls[1].function.name, "list_files");
+
+        let error: ChatChunk =
+            serde_json::from_value(json!({"error": {"message": "model not found"}})).unwrap();
+        let err = adapter
+            .apply_chunk(error, &mut Completion::default())
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "The model returned an error: model not found"
+        );
+    }
+
+    #[test]
+    fn test_resolve_path_stays_in_root() {
+        let root = Path::new("/repo");
+        assert_eq!(
+            resolve_path(root, "./src/lib.rs").unwrap(),
+            PathBuf::from("/repo/src/lib.rs")
+        );
+        assert!(resolve_path(root, "../etc/passwd").is_err());
+        assert!(resolve_path(root, "/etc/passwd").is_err());
+        assert!(resolve_path(root, ".git/config").is_err());
+    }
+
+    #[test]
+    fn test_tools_read_list_and_write() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/lib.rs"), "fn a() {}").unwrap();
+
+        let read = execute_tool(dir.path(), "read_file", r#"{"path": "src/lib.rs"}"#, false);
+        assert_eq!(read, "fn a() {}");
+        assert_eq!(execute_tool(dir.path(), "list_files", "", false), "src/");
+
+        // The reviewer cannot write
+        let args = r#"{"path": "src/new.rs", "content": "fn b() {}"}"#;
+        assert_eq!(
+            execute_tool(dir.path(), "write_file", args, false),
+            "Error: Unknown tool: write_file"
+        );
+        assert_eq!(
+            execute_tool(dir.path(), "write_file", args, true),
+            "Wrote 9 bytes"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("src/new.rs")).unwrap(),
+            "fn b() {}"
+        );
+        assert!(
+            execute_tool(dir.path(), "read_file", r#"{"path": "../x"}"#, true)
+                .starts_with("Error:")
+        );
+    }
+
+    #[test]
+    fn test_dry_run_write_does_not_write() {
+        let args = r#"{"path": "src/new.rs", "content": "fn b() {}"}"#;
+        assert_eq!(
+            dry_run_write(args),
+            "Dry run: src/new.rs was not written. Describe the change in your summary instead."
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_reviewer_with_tool_call() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.rs"), "fn main() {}").unwrap();
+        let review = json!({
+            "action": "comment",
+            "summary": "Looks fine",
+            "comments": [],
+            "blocking_issues": []
+        });
+        let (url, server) = serve(vec![
+            sse(&[json!({"choices": [{"delta": {"tool_calls": [{"index": 0, "id": "c1", "function": {"name": "read_file", "arguments": "{\"path\": \"a.rs\"}"}}]}}]})]),
+            sse(&[
+                json!({"choices": [{"delta": {"content": format!("```json\n{}\n```", review)}}]}),
+                json!({"choices": [], "usage": {"prompt_tokens": 120, "completion_tokens": 30, "total_tokens": 150}}),
+            ]),
+        ])
+        .await;
+
+        let config = OpenAiConfig {
+            base_url: url,
+            model: "local-model".to_string(),
+            api_key: None,
+        };
+        let mut adapter = OpenAiAdapter::new(&config).unwrap();
+        let (tx, mut rx) = mpsc::channel(16);
+        adapter.set_event_sender(tx);
+        let output = adapter
+            .run_reviewer("Review this", &context(dir.path()))
+            .await
+            .unwrap();
+        assert_eq!(output.summary, "Looks fine");
+        let usage = adapter.take_usage();
+        assert_eq!((usage.input_tokens, usage.output_tokens), (120, 30));
+        assert!(adapter.take_usage().is_empty());
+
+        // The tool result is sent back with the call's ID
+        let requests = server.await.unwrap();
+        let second: serde_json::Value = serde_json::from_str(&requests[1]).unwrap();
+        assert_eq!(second["model"], "local-model");
+        let tool_message = &second["messages"][3];
+        assert_eq!(tool_message["role"], "tool");
+        assert_eq!(tool_message["tool_call_id"], "c1");
+        assert_eq!(tool_message["content"], "fn main() {}");
+
+        let mut tool_uses = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let RallyEvent::AgentToolUse(name, _) = event {
+                tool_uses.push(name);
+            }
+        }
+        assert_eq!(tool_uses, vec!["read_file"]);
+    }
+
+    #[tokio::test]
+    async fn test_answer_without_json_can_be_repaired() {
+        let dir = tempfile::tempdir().unwrap();
+        let review = json!({
+            "action": "approve",
+            "summary": "LGTM",
+            "comments": [],
+            "blocking_issues": []
+        });
+        let (url, server) = serve(vec![
+            sse(&[json!({"choices": [{"delta": {"content": "Looks good to me."}}]})]),
+            sse(&[json!({"choices": [{"delta": {"content": review.to_string()}}]})]),
+        ])
+        .await;
+
+        let config = OpenAiConfig {
+            base_url: url,
+            model: "local-model".to_string(),
+            api_key: None,
+        };
+        let mut adapter = OpenAiAdapter::new(&config).unwrap();
+        let err = adapter
+            .run_reviewer("Review this", &context(dir.path()))
+            .await
+            .unwrap_err();
+        let format_error = err.downcast_ref::<OutputFormatError>().unwrap();
+        assert_eq!(format_error.role, "reviewer");
+        assert!(format_error.problems[0].contains("Looks good to me."));
+
+        // The rejected answer stays in the conversation for the follow-up
+        let output = adapter.continue_reviewer("Fix the format").await.unwrap();
+        assert_eq!(output.summary, "LGTM");
+        let requests = server.await.unwrap();
+        let second: serde_json::Value = serde_json::from_str(&requests[1]).unwrap();
+        assert_eq!(second["messages"][2]["content"], "Looks good to me.");
+    }
+}
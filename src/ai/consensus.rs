@@ -0,0 +1,263 @@
+//! Merging the reviews of reviewer agents that ran in parallel on the same diff.
+//!
+//! Findings that point at the same place and say the same thing are combined, and every
+//! finding keeps the names of the agents that reported it.
+
+use std::collections::HashSet;
+
+use super::adapter::{CommentSeverity, ReviewComment, ReviewerOutput};
+use super::context_planner::stricter;
+
+/// Comments further apart than this many lines are never the same finding
+const LINE_TOLERANCE: u32 = 3;
+
+/// Merge the reviews of several agents, given as `(agent name, review)` pairs.
+///
+/// The strictest action wins, so the PR is only approved when every reviewer approves.
+/// Duplicate comments and blocking issues are merged and attributed to all of their agents.
+pub fn merge(reviews: Vec<(String, ReviewerOutput)>) -> Option<ReviewerOutput> {
+    let mut reviews = reviews.into_iter();
+    let (first_agent, first) = reviews.next()?;
+    let mut merged = ReviewerOutput {
+        action: first.action,
+        summary: format!("{}: {}", first_agent, first.summary),
+        comments: Vec::new(),
+        blocking_issues: Vec::new(),
+    };
+    let mut blocking: Vec<(String, Vec<String>)> = Vec::new();
+    add_findings(&mut merged.comments, &mut blocking, &first_agent, first);
+
+    for (agent, review) in reviews {
+        merged.action = stricter(merged.action, review.action);
+        merged
+            .summary
+            .push_str(&format!("\n\n{}: {}", agent, review.summary));
+        add_findings(&mut merged.comments, &mut blocking, &agent, review);
+    }
+
+    merged.blocking_issues = blocking
+        .into_iter()
+        .map(|(issue, agents)| format!("{} ({})", issue, agents.join(", ")))
+        .collect();
+    Some(merged)
+}
+
+/// Number of findings that more than one agent reported
+pub fn agreed_count(review: &ReviewerOutput) -> usize {
+    review
+        .comments
+        .iter()
+        .filter(|comment| comment.sources.len() > 1)
+        .count()
+}
+
+fn add_findings(
+    comments: &mut Vec<ReviewComment>,
+    blocking: &mut Vec<(String, Vec<String>)>,
+    agent: &str,
+    review: ReviewerOutput,
+) {
+    for comment in review.comments {
+        match comments
+            .iter_mut()
+            .find(|existing| same_finding(existing, &comment))
+        {
+            Some(existing) => {
+                add_source(&mut existing.sources, agent);
+                if severity_rank(comment.severity) > severity_rank(existing.severity) {
+                    existing.severity = comment.severity;
+                }
+            }
+            None => comments.push(ReviewComment {
+                sources: vec![agent.to_string()],
+                ..comment
+            }),
+        }
+    }
+
+    for issue in review.blocking_issues {
+        match blocking
+            .iter_mut()
+            .find(|(existing, _)| same_issue(existing, &issue))
+        {
+            Some((_, agents)) => add_source(agents, agent),
+            None => blocking.push((issue, vec![agent.to_string()])),
+        }
+    }
+}
+
+fn add_source(sources: &mut Vec<String>, agent: &str) {
+    if !sources.iter().any(|source| source == agent) {
+        sources.push(agent.to_string());
+    }
+}
+
+/// Comments on the same line are the same finding; comments on nearby lines only when
+/// they are worded alike
+fn same_finding(a: &ReviewComment, b: &ReviewComment) -> bool {
+    if a.path != b.path || a.line.abs_diff(b.line) > LINE_TOLERANCE {
+        return false;
+    }
+    a.line == b.line || similarity(&a.body, &b.body) >= 0.3
+}
+
+fn same_issue(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim()) || similarity(a, b) >= 0.5
+}
+
+/// Share of distinct words (three characters or more) the two texts have in common
+fn similarity(a: &str, b: &str) -> f64 {
+    fn words(text: &str) -> HashSet<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| word.chars().count() >= 3)
+            .map(str::to_lowercase)
+            .collect()
+    }
+    let (a, b) = (words(a), words(b));
+    let union = a.union(&b).count();
+    if union == 0 {
+        return 0.0;
+    }
+    a.intersection(&b).count() as f64 / union as f64
+}
+
+fn severity_rank(severity: CommentSeverity) -> u8 {
+    match severity {
+        CommentSeverity::Suggestion => 0,
+        CommentSeverity::Minor => 1,
+        CommentSeverity::Major => 2,
+        CommentSeverity::Critical => 3,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::adapter::ReviewAction;
+
+    fn comment(path: &str, line: u32, body: &str, severity: CommentSeverity) -> ReviewComment {
+        ReviewComment {
+            path: path.to_string(),
+            line,
+            body: body.to_string(),
+            severity,
+            sources: Vec::new(),
+        }
+    }
+
+    fn review(
+        action: ReviewAction,
+        comments: Vec<ReviewComment>,
+        blocking_issues: &[&str],
+    ) -> ReviewerOutput {
+        ReviewerOutput {
+            action,
+            summary: "looked at it".to_string(),
+            comments,
+            blocking_issues: blocking_issues.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_merge_combines_duplicate_findings() {
+        let claude = review(
+            ReviewAction::Comment,
+            vec![
+                comment(
+                    "src/a.rs",
+                    10,
+                    "unwrap may panic on empty input",
+                    CommentSeverity::Minor,
+                ),
+                comment(
+                    "src/b.rs",
+                    3,
+                    "typo in the doc comment",
+                    CommentSeverity::Suggestion,
+                ),
+            ],
+            &["Handle the empty input case"],
+        );
+        let codex = review(
+            ReviewAction::RequestChanges,
+            vec![
+                // Same line, different wording
+                comment("src/a.rs", 10, "This will crash", CommentSeverity::Major),
+                // Nearby line, same finding
+                comment(
+                    "src/b.rs",
+                    5,
+                    "Typo in doc comment",
+                    CommentSeverity::Suggestion,
+                ),
+                // Nearby line, unrelated finding
+                comment(
+                    "src/a.rs",
+                    12,
+                    "Prefer a borrowed slice here",
+                    CommentSeverity::Minor,
+                ),
+            ],
+            &["handle the empty input case", "Add a test"],
+        );
+
+        let merged = merge(vec![
+            ("claude".to_string(), claude),
+            ("codex".to_string(), codex),
+        ])
+        .unwrap();
+
+        assert_eq!(merged.action, ReviewAction::RequestChanges);
+        assert_eq!(
+            merged.summary,
+            "claude: looked at it\n\ncodex: looked at it"
+        );
+        assert_eq!(merged.comments.len(), 3);
+        assert_eq!(merged.comments[0].body, "unwrap may panic on empty input");
+        assert_eq!(merged.comments[0].severity, CommentSeverity::Major);
+        assert_eq!(merged.comments[0].sources, vec!["claude", "codex"]);
+        assert_eq!(merged.comments[1].sources, vec!["claude", "codex"]);
+        assert_eq!(merged.comments[2].sources, vec!["codex"]);
+        assert_eq!(agreed_count(&merged), 2);
+        assert_eq!(
+            merged.blocking_issues,
+            vec![
+                "Handle the empty input case (claude, codex)",
+                "Add a test (codex)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_approves_only_when_all_approve() {
+        let approve = || review(ReviewAction::Approve, vec![], &[]);
+        let merged = merge(vec![
+            ("claude".to_string(), approve()),
+            ("gemini".to_string(), approve()),
+        ])
+        .unwrap();
+        assert_eq!(merged.action, ReviewAction::Approve);
+        assert!(merged.blocking_issues.is_empty());
+
+        let merged = merge(vec![
+            ("claude".to_string(), approve()),
+            (
+                "gemini".to_string(),
+                review(ReviewAction::Comment, vec![], &[]),
+            ),
+        ])
+        .unwrap();
+        assert_eq!(merged.action, ReviewAction::Comment);
+        assert!(merge(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_similarity() {
+        assert_eq!(similarity("", "anything"), 0.0);
+        assert_eq!(
+            similarity("Missing error handling", "missing ERROR handling!"),
+            1.0
+        );
+        assert!(similarity("Missing error handling", "Rename the variable") < 0.3);
+    }
+}
@@ -0,0 +1,555 @@
+//! Context budgeting for AI Rally reviews.
+//!
+//! Large PRs can exceed what a reviewer agent can take in at once. The planner
+//! summarizes lockfiles and generated code, orders the remaining files by risk,
+//! and packs them into chunks that each fit within the configured budget so the
+//! orchestrator can review them in separate invocations.
+
+use std::path::Path;
+
+use super::adapter::{ReviewAction, ReviewerOutput};
+
+/// Rough characters-per-token ratio used to turn a token budget into characters
+pub const CHARS_PER_TOKEN: usize = 4;
+
+/// Upper bound on reviewer invocations for a single review pass
+pub const MAX_CHUNKS: usize = 8;
+
+/// Lockfiles whose diffs are summarized instead of sent verbatim
+const LOCKFILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "npm-shrinkwrap.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lockb",
+    "poetry.lock",
+    "Pipfile.lock",
+    "uv.lock",
+    "Gemfile.lock",
+    "composer.lock",
+    "go.sum",
+    "flake.lock",
+    "mix.lock",
+    "Podfile.lock",
+    "packages.lock.json",
+];
+
+/// File name suffixes that indicate generated or minified output
+const GENERATED_SUFFIXES: &[&str] = &[
+    ".min.js", ".min.css", ".map", ".pb.go", "_pb2.py", ".g.dart", ".snap", ".svg",
+];
+
+/// Directory components that typically hold generated output
+const GENERATED_DIRS: &[&str] = &["dist", "generated", "__generated__", "node_modules"];
+
+/// Markers that code generators put near the top of their output
+const GENERATED_MARKERS: &[&str] = &["@generated", "Code generated", "DO NOT EDIT"];
+
+/// How a file is represented in the planned context
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileKind {
+    Source,
+    Test,
+    Docs,
+    Lockfile,
+    Generated,
+}
+
+impl FileKind {
+    pub fn classify(path: &str, patch: &str) -> Self {
+        let name = Path::new(path)
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or(path);
+        if LOCKFILES.contains(&name) {
+            return Self::Lockfile;
+        }
+        if GENERATED_SUFFIXES.iter().any(|s| name.ends_with(s))
+            || path
+                .split('/')
+                .rev()
+                .skip(1)
+                .any(|dir| GENERATED_DIRS.contains(&dir))
+            || patch
+                .lines()
+                .take(10)
+                .any(|l| GENERATED_MARKERS.iter().any(|m| l.contains(m)))
+        {
+            return Self::Generated;
+        }
+        let lower = path.to_ascii_lowercase();
+        if lower.ends_with(".md")
+            || lower.ends_with(".txt")
+            || lower.ends_with(".rst")
+            || lower.starts_with("docs/")
+        {
+            return Self::Docs;
+        }
+        if lower.contains("test") || lower.contains("spec") || lower.contains("fixtures/") {
+            return Self::Test;
+        }
+        Self::Source
+    }
+
+    fn is_summarized(self) -> bool {
+        matches!(self, Self::Lockfile | Self::Generated)
+    }
+
+    /// Review priority; riskier kinds have a higher weight
+    pub fn weight(self) -> usize {
+        match self {
+            Self::Source => 3,
+            Self::Test => 2,
+            Self::Docs => 1,
+            Self::Lockfile | Self::Generated => 0,
+        }
+    }
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Lockfile => "lockfile",
+            Self::Generated => "generated file",
+            Self::Source | Self::Test | Self::Docs => "file",
+        }
+    }
+}
+
+/// One reviewer invocation worth of diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextChunk {
+    /// Diff text sent to the reviewer for this chunk
+    pub diff: String,
+    /// Files whose patches are (at least partially) included
+    pub files: Vec<String>,
+}
+
+/// Result of budgeting a diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextPlan {
+    pub chunks: Vec<ContextChunk>,
+    /// One-line summaries for lockfiles, generated code and files that did not fit
+    pub summaries: Vec<String>,
+}
+
+impl ContextPlan {
+    pub fn is_chunked(&self) -> bool {
+        self.chunks.len() > 1
+    }
+
+    /// Note appended to each chunk's diff so the reviewer knows what it is not seeing
+    pub fn chunk_note(&self, index: usize) -> String {
+        let mut note = String::new();
+        if self.is_chunked() {
+            let others: Vec<&str> = self
+                .chunks
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| *i != index)
+                .flat_map(|(_, c)| c.files.iter().map(String::as_str))
+                .collect();
+            note.push_str(&format!(
+                "\n# [octorus] This is part {} of {} of a large PR. \
+                 Review only the files in this part.\n",
+                index + 1,
+                self.chunks.len()
+            ));
+            if !others.is_empty() {
+                note.push_str(&format!(
+                    "# Files reviewed in other parts: {}\n",
+                    others.join(", ")
+                ));
+            }
+        }
+        for summary in &self.summaries {
+            note.push_str(&format!("# [octorus] {}\n", summary));
+        }
+        note
+    }
+}
+
+/// Split a budget-exceeding diff into prioritized chunks.
+///
+/// Returns a single chunk with the untouched diff when everything fits.
+pub fn plan(file_patches: &[(String, String)], budget_tokens: usize) -> ContextPlan {
+    let budget = budget_tokens.saturating_mul(CHARS_PER_TOKEN);
+    let rendered: Vec<(String, FileKind, String)> = file_patches
+        .iter()
+        .map(|(path, patch)| {
+            (
+                path.clone(),
+                FileKind::classify(path, patch),
+                render_file(path, patch),
+            )
+        })
+        .collect();
+
+    let total: usize = rendered.iter().map(|(_, _, d)| d.len()).sum();
+    if budget == 0 || total <= budget {
+        return ContextPlan {
+            chunks: vec![ContextChunk {
+                diff: rendered
+                    .iter()
+                    .map(|(_, _, d)| d.as_str())
+                    .collect::<Vec<_>>()
+                    .join(""),
+                files: rendered.into_iter().map(|(p, _, _)| p).collect(),
+            }],
+            summaries: Vec::new(),
+        };
+    }
+
+    let mut summaries = Vec::new();
+    let mut candidates = Vec::new();
+    for (path, kind, diff) in rendered {
+        if kind.is_summarized() {
+            summaries.push(summarize(&path, kind, &diff));
+        } else {
+            candidates.push((path, kind, diff));
+        }
+    }
+
+    // Riskier kinds first; within a kind, larger changes first since they
+    // are the most likely to hide problems.
+    candidates.sort_by(|a, b| {
+        b.1.weight()
+            .cmp(&a.1.weight())
+            .then(b.2.len().cmp(&a.2.len()))
+            .then(a.0.cmp(&b.0))
+    });
+
+    let mut chunks: Vec<ContextChunk> = Vec::new();
+    for (path, kind, diff) in candidates {
+        let diff = if diff.len() > budget {
+            truncate_at_hunk(&diff, budget)
+        } else {
+            diff
+        };
+
+        // First fit keeps the number of reviewer invocations low
+        if let Some(chunk) = chunks
+            .iter_mut()
+            .find(|c| c.diff.len() + diff.len() <= budget)
+        {
+            chunk.diff.push_str(&diff);
+            chunk.files.push(path);
+        } else if chunks.len() < MAX_CHUNKS {
+            chunks.push(ContextChunk {
+                diff,
+                files: vec![path],
+            });
+        } else {
+            summaries.push(format!(
+                "{} {} omitted to stay within the context budget",
+                kind.label(),
+                path
+            ));
+        }
+    }
+
+    if chunks.is_empty() {
+        chunks.push(ContextChunk {
+            diff: String::new(),
+            files: Vec::new(),
+        });
+    }
+
+    ContextPlan { chunks, summaries }
+}
+
+/// Split a `git diff` output into `(path, patch)` pairs
+pub fn split_git_diff(diff: &str) -> Vec<(String, String)> {
+    let mut files: Vec<(String, String)> = Vec::new();
+    for line in diff.split_inclusive('\n') {
+        if let Some(rest) = line.strip_prefix("diff --git ") {
+            let path = rest
+                .trim_end()
+                .rsplit_once(" b/")
+                .map(|(_, b)| b)
+                .unwrap_or(rest.trim_end())
+                .to_string();
+            files.push((path, line.to_string()));
+        } else if let Some((_, patch)) = files.last_mut() {
+            patch.push_str(line);
+        } else if !line.trim().is_empty() {
+            files.push((String::new(), line.to_string()));
+        }
+    }
+    files
+}
+
+/// Merge per-chunk reviews into a single review.
+///
+/// The strictest action wins, and comments and blocking issues are concatenated.
+pub fn merge_reviews(outputs: Vec<ReviewerOutput>) -> Option<ReviewerOutput> {
+    if outputs.len() <= 1 {
+        return outputs.into_iter().next();
+    }
+    let total = outputs.len();
+    let mut merged = ReviewerOutput {
+        action: ReviewAction::Approve,
+        summary: String::new(),
+        comments: Vec::new(),
+        blocking_issues: Vec::new(),
+    };
+    let mut summaries = Vec::new();
+    for (i, output) in outputs.into_iter().enumerate() {
+        merged.action = stricter(merged.action, output.action);
+        summaries.push(format!("Part {}/{}: {}", i + 1, total, output.summary));
+        merged.comments.extend(output.comments);
+        merged.blocking_issues.extend(output.blocking_issues);
+    }
+    merged.summary = summaries.join("\n\n");
+    Some(merged)
+}
+
+pub(crate) fn stricter(a: ReviewAction, b: ReviewAction) -> ReviewAction {
+    fn rank(action: ReviewAction) -> u8 {
+        match action {
+            ReviewAction::Approve => 0,
+            ReviewAction::Comment => 1,
+            ReviewAction::RequestChanges => 2,
+        }
+    }
+    if rank(b) > rank(a) {
+        b
+    } else {
+        a
+    }
+}
+
+/// Give each file a `diff --git` header so the reviewer can tell files apart
+fn render_file(path: &str, patch: &str) -> String {
+    let mut out = if patch.starts_with("diff --git ") || path.is_empty() {
+        patch.to_string()
+    } else {
+        format!("diff --git a/{} b/{}\n{}", path, path, patch)
+    };
+    if !out.ends_with('\n') {
+        out.push('\n');
+    }
+    out
+}
+
+fn summarize(path: &str, kind: FileKind, diff: &str) -> String {
+    let (added, removed) = diff.lines().fold((0, 0), |(a, r), line| {
+        if line.starts_with('+') && !line.starts_with("+++") {
+            (a + 1, r)
+        } else if line.starts_with('-') && !line.starts_with("---") {
+            (a, r + 1)
+        } else {
+            (a, r)
+        }
+    });
+    format!(
+        "{} {} changed (+{} -{} lines, diff omitted)",
+        kind.label(),
+        path,
+        added,
+        removed
+    )
+}
+
+/// Cut a single oversized file diff at the last hunk boundary that fits
+fn truncate_at_hunk(diff: &str, budget: usize) -> String {
+    let mut out = String::new();
+    let mut hunk = String::new();
+    let mut dropped = 0usize;
+    for line in diff.split_inclusive('\n') {
+        if line.starts_with("@@") {
+            if out.len() + hunk.len() <= budget {
+                out.push_str(&hunk);
+            } else if !hunk.is_empty() {
+                dropped += 1;
+            }
+            hunk.clear();
+        }
+        hunk.push_str(line);
+    }
+    if !hunk.is_empty() {
+        if out.len() + hunk.len() <= budget {
+            out.push_str(&hunk);
+        } else {
+            dropped += 1;
+        }
+    }
+    if dropped > 0 {
+        out.push_str(&format!(
+            "# [octorus] {} more hunk(s) truncated to fit the context budget\n",
+            dropped
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::adapter::{CommentSeverity, ReviewComment};
+
+    fn patch(lines: usize) -> String {
+        let mut p = String::from("@@ -1,1 +1,1 @@\n");
+        for i in 0..lines {
+            p.push_str(&format!("+line {}\n", i));
+        }
+        p
+    }
+
+    fn review(action: ReviewAction, summary: &str, paths: &[&str]) -> ReviewerOutput {
+        ReviewerOutput {
+            action,
+            summary: summary.to_string(),
+            comments: paths
+                .iter()
+                .map(|p| ReviewComment {
+                    path: p.to_string(),
+                    line: 1,
+                    body: "x".to_string(),
+                    severity: CommentSeverity::Minor,
+                    sources: Vec::new(),
+                })
+                .collect(),
+            blocking_issues: vec![format!("{} issue", summary)],
+        }
+    }
+
+    #[test]
+    fn test_classify() {
+        assert_eq!(FileKind::classify("Cargo.lock", ""), FileKind::Lockfile);
+        assert_eq!(
+            FileKind::classify("web/package-lock.json", ""),
+            FileKind::Lockfile
+        );
+        assert_eq!(
+            FileKind::classify("static/app.min.js", ""),
+            FileKind::Generated
+        );
+        assert_eq!(
+            FileKind::classify("src/generated/api.rs", ""),
+            FileKind::Generated
+        );
+        assert_eq!(
+            FileKind::classify("src/api.rs", "@@ -0,0 +1 @@\n+// @generated by tool\n"),
+            FileKind::Generated
+        );
+        assert_eq!(FileKind::classify("README.md", ""), FileKind::Docs);
+        assert_eq!(FileKind::classify("tests/e2e.rs", ""), FileKind::Test);
+        assert_eq!(FileKind::classify("src/main.rs", ""), FileKind::Source);
+    }
+
+    #[test]
+    fn test_plan_within_budget_keeps_everything() {
+        let files = vec![
+            ("Cargo.lock".to_string(), patch(3)),
+            ("src/lib.rs".to_string(), patch(3)),
+        ];
+        let plan = plan(&files, 10_000);
+        assert!(!plan.is_chunked());
+        assert!(plan.summaries.is_empty());
+        assert_eq!(plan.chunks[0].files, vec!["Cargo.lock", "src/lib.rs"]);
+        assert!(plan.chunks[0].diff.contains("diff --git a/Cargo.lock"));
+        assert!(plan.chunk_note(0).is_empty());
+    }
+
+    #[test]
+    fn test_plan_summarizes_lockfiles_and_chunks_by_priority() {
+        let files = vec![
+            ("docs/guide.md".to_string(), patch(40)),
+            ("Cargo.lock".to_string(), patch(200)),
+            ("src/small.rs".to_string(), patch(10)),
+            ("src/big.rs".to_string(), patch(60)),
+        ];
+        // ~ 600 chars per chunk
+        let plan = plan(&files, 150);
+
+        assert!(plan.is_chunked());
+        assert_eq!(
+            plan.summaries,
+            vec!["lockfile Cargo.lock changed (+200 -0 lines, diff omitted)"]
+        );
+        assert_eq!(plan.chunks[0].files[0], "src/big.rs");
+        let all: Vec<&str> = plan
+            .chunks
+            .iter()
+            .flat_map(|c| c.files.iter().map(String::as_str))
+            .collect();
+        assert!(!all.contains(&"Cargo.lock"));
+        assert!(all.contains(&"src/small.rs"));
+        assert!(all.contains(&"docs/guide.md"));
+        for chunk in &plan.chunks {
+            assert!(chunk.diff.len() <= 600);
+        }
+
+        let note = plan.chunk_note(0);
+        assert!(note.contains(&format!("part 1 of {}", plan.chunks.len())));
+        assert!(note.contains("Cargo.lock changed"));
+    }
+
+    #[test]
+    fn test_plan_truncates_oversized_file_at_hunk_boundary() {
+        let mut big = patch(20);
+        big.push_str(&patch(200));
+        let plan = plan(&[("src/a.rs".to_string(), big)], 100);
+        let diff = &plan.chunks[0].diff;
+        assert!(diff.len() < 500);
+        assert!(diff.contains("+line 19\n"));
+        assert!(diff.contains("1 more hunk(s) truncated"));
+    }
+
+    #[test]
+    fn test_plan_limits_chunk_count() {
+        let files: Vec<(String, String)> = (0..MAX_CHUNKS + 2)
+            .map(|i| (format!("src/f{}.rs", i), patch(30)))
+            .collect();
+        let plan = plan(&files, 80);
+        assert_eq!(plan.chunks.len(), MAX_CHUNKS);
+        assert_eq!(
+            plan.summaries
+                .iter()
+                .filter(|s| s.contains("omitted to stay within"))
+                .count(),
+            2
+        );
+    }
+
+    #[test]
+    fn test_split_git_diff() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n--- a/src/a.rs\n+++ b/src/a.rs\n@@ -1 +1 @@\n-a\n+b\n\
+                    diff --git a/b.txt b/b.txt\n@@ -0,0 +1 @@\n+x\n";
+        let files = split_git_diff(diff);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].0, "src/a.rs");
+        assert!(files[0].1.ends_with("+b\n"));
+        assert_eq!(files[1].0, "b.txt");
+        assert_eq!(
+            files.iter().map(|(_, p)| p.as_str()).collect::<String>(),
+            diff
+        );
+    }
+
+    #[test]
+    fn test_merge_reviews() {
+        assert!(merge_reviews(Vec::new()).is_none());
+
+        let merged = merge_reviews(vec![
+            review(ReviewAction::Approve, "ok", &["a.rs"]),
+            review(ReviewAction::RequestChanges, "bad", &["b.rs"]),
+            review(ReviewAction::Comment, "meh", &[]),
+        ])
+        .unwrap();
+        assert_eq!(merged.action, ReviewAction::RequestChanges);
+        assert_eq!(merged.comments.len(), 2);
+        assert_eq!(merged.blocking_issues.len(), 3);
+        assert_eq!(
+            merged.summary,
+            "Part 1/3: ok\n\nPart 2/3: bad\n\nPart 3/3: meh"
+        );
+
+        let merged = merge_reviews(vec![
+            review(ReviewAction::Approve, "a", &[]),
+            review(ReviewAction::Comment, "b", &[]),
+        ])
+        .unwrap();
+        assert_eq!(merged.action, ReviewAction::Comment);
+    }
+}
@@ -1,9 +1,13 @@
 pub mod adapter;
 pub mod adapters;
+pub mod consensus;
+pub mod context_planner;
+pub mod oneshot;
 pub mod orchestrator;
 pub mod prompt_loader;
 pub mod prompts;
 pub mod session;
+pub mod transcript;
 
 pub use adapter::{Context, ReviewAction, RevieweeOutput, RevieweeStatus, ReviewerOutput};
 pub use orchestrator::{Orchestrator, RallyState};
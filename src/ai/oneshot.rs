@@ -0,0 +1,195 @@
+//! One-shot agent calls: a single prompt in, plain text out.
+//!
+//! Unlike the rally adapters these calls keep no session, stream no events and
+//! grant no tools. They are used for small side tasks such as translating a
+//! comment or explaining a single file's change.
+
+use std::process::Stdio;
+
+use anyhow::{anyhow, bail, Context, Result};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use super::adapter::SupportedAgent;
+use crate::config::Config;
+use crate::spawn_env::SpawnEnv;
+
+/// The agent for one-shot side tasks, with the environment of the repository.
+pub struct OneshotAgent {
+    agent: SupportedAgent,
+    env: SpawnEnv,
+}
+
+impl OneshotAgent {
+    /// Use the `ai.reviewer` agent. `repo` selects the `[env.repos]` variables and
+    /// `purpose` (e.g. "explanations") names the task in the error for an unsupported agent.
+    pub fn from_config(config: &Config, repo: &str, purpose: &str) -> Result<Self> {
+        let agent = SupportedAgent::from_name(&config.ai.reviewer)
+            .ok_or_else(|| anyhow!("Unsupported agent for {}: {}", purpose, config.ai.reviewer))?;
+        Ok(Self {
+            agent,
+            env: SpawnEnv::for_repo(&config.env, repo),
+        })
+    }
+
+    /// Name shown in the status line.
+    pub fn name(&self) -> &'static str {
+        self.agent.name()
+    }
+
+    pub async fn run(&self, prompt: &str) -> Result<String> {
+        run_prompt(self.agent, prompt, &self.env).await
+    }
+}
+
+/// The first `max_chars` characters of `patch`, marked when the rest is cut off.
+pub fn truncate_patch(patch: &str, max_chars: usize) -> String {
+    let mut truncated: String = patch.chars().take(max_chars).collect();
+    if truncated.len() < patch.len() {
+        truncated.push_str("\n... (patch truncated)");
+    }
+    truncated
+}
+
+/// Send `prompt` to `agent` and return its final answer.
+pub async fn run_prompt(agent: SupportedAgent, prompt: &str, env: &SpawnEnv) -> Result<String> {
+    match agent {
+        SupportedAgent::Claude => {
+            let mut cmd = Command::new("claude");
+            // Prevent nested session detection when octorus is run inside Claude Code
+            cmd.env_remove("CLAUDECODE");
+            cmd.arg("-p").arg("--output-format").arg("text");
+            run_with_stdin(cmd, prompt, env).await
+        }
+        SupportedAgent::Codex => {
+            // codex exec also prints progress to stdout, so read the last message from a file
+            let output =
+                tempfile::NamedTempFile::new().context("Failed to create temporary file")?;
+            let mut cmd = Command::new("codex");
+            cmd.env_remove("CLAUDECODE");
+            cmd.arg("exec")
+                .arg("-")
+                .arg("--output-last-message")
+                .arg(output.path());
+            run_with_stdin(cmd, prompt, env).await?;
+            let message =
+                std::fs::read_to_string(output.path()).context("Failed to read codex output")?;
+            non_empty(message)
+        }
+        SupportedAgent::Gemini => {
+            // gemini runs non-interactively when the prompt comes from stdin
+            let mut cmd = Command::new("gemini");
+            cmd.env_remove("CLAUDECODE");
+            cmd.arg("--output-format").arg("text");
+            run_with_stdin(cmd, prompt, env).await
+        }
+        SupportedAgent::OpenAi => {
+            bail!("The \"openai\" agent is only available in AI Rally; use claude, codex or gemini")
+        }
+    }
+}
+
+/// Run `cmd` with `input` on stdin and return its trimmed stdout.
+///
+/// Extra environment variables from `env` are added, and their secret values are
+/// redacted from the error when the command fails.
+pub(crate) async fn run_with_stdin(
+    mut cmd: Command,
+    input: &str,
+    env: &SpawnEnv,
+) -> Result<String> {
+    cmd.envs(env.vars());
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let program = cmd.as_std().get_program().to_string_lossy().into_owned();
+    let mut child = cmd
+        .spawn()
+        .with_context(|| format!("Failed to spawn {:?}", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        // A command that exits without reading stdin is judged by its exit status
+        match stdin.write_all(input.as_bytes()).await {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => {
+                return Err(e).with_context(|| format!("Failed to write to {} stdin", program));
+            }
+            _ => {}
+        }
+    }
+
+    let output = child
+        .wait_with_output()
+        .await
+        .with_context(|| format!("Failed to wait for {}", program))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!(
+            "{} exited with {}: {}",
+            program,
+            output.status,
+            env.redact(stderr.trim())
+        );
+    }
+    non_empty(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+fn non_empty(text: String) -> Result<String> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        bail!("The command returned empty output");
+    }
+    Ok(trimmed.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_truncate_patch() {
+        assert_eq!(truncate_patch("+a\n+b", 10), "+a\n+b");
+        assert_eq!(truncate_patch("+a\n+b", 3), "+a\n\n... (patch truncated)");
+    }
+
+    #[test]
+    fn test_oneshot_agent_from_config() {
+        let mut config: Config = toml::from_str("").unwrap();
+        let agent = OneshotAgent::from_config(&config, "owner/repo", "explanations").unwrap();
+        assert_eq!(agent.name(), "claude");
+        config.ai.reviewer = "codex".to_string();
+        let agent = OneshotAgent::from_config(&config, "owner/repo", "explanations").unwrap();
+        assert_eq!(agent.name(), "codex");
+        config.ai.reviewer = "unknown".to_string();
+        let err = OneshotAgent::from_config(&config, "owner/repo", "explanations")
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "Unsupported agent for explanations: unknown"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_run_with_stdin_trims_output_and_reports_failures() {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("tr a-z A-Z");
+        let output = run_with_stdin(cmd, "  hello\n", &SpawnEnv::default()).await;
+        assert_eq!(output.unwrap(), "HELLO");
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("echo broken >&2; exit 2");
+        let err = run_with_stdin(cmd, "x", &SpawnEnv::default())
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(err.starts_with("sh exited with"), "{}", err);
+        assert!(err.contains("broken"), "{}", err);
+
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg("cat >/dev/null");
+        assert!(run_with_stdin(cmd, "x", &SpawnEnv::default())
+            .await
+            .is_err());
+    }
+}
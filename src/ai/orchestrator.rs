@@ -6,21 +6,28 @@ use tokio::sync::mpsc;
 use tokio::time::timeout;
 use tracing::warn;
 
+use crate::audit::{self, AuditAction, AuditEntry};
 use crate::config::AiConfig;
 use crate::github;
 use crate::github::comment::{fetch_discussion_comments, fetch_review_comments};
+use crate::spawn_env::SpawnEnv;
 
 use super::adapter::{
     AgentAdapter, Context, ExternalComment, ReviewAction, RevieweeOutput, RevieweeStatus,
-    ReviewerOutput,
+    ReviewerOutput, TokenUsage,
 };
-use super::adapters::create_adapter;
+use super::adapters::{create_adapter, OutputFormatError};
+use super::consensus;
+use super::context_planner;
 use super::prompt_loader::PromptLoader;
 use super::prompts::{
-    build_clarification_prompt, build_clarification_skipped_prompt, build_permission_denied_prompt,
-    build_permission_granted_prompt,
+    build_clarification_prompt, build_clarification_skipped_prompt, build_output_repair_prompt,
+    build_permission_denied_prompt, build_permission_granted_prompt,
+};
+use super::session::{
+    append_event, clear_rally_state, events_path, read_history, read_session, resume_point,
+    write_history_entry, write_session, HistoryEntryType, RallySession,
 };
-use super::session::{write_history_entry, write_session, HistoryEntryType, RallySession};
 
 /// Bot suffixes to identify bot users
 const BOT_SUFFIXES: &[&str] = &["[bot]"];
@@ -73,7 +80,7 @@ impl RallyState {
 /// Event emitted during rally for TUI updates
 ///
 /// Variants are used by TUI handlers (ui/ai_rally.rs) via mpsc channel
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 #[allow(dead_code)]
 pub enum RallyEvent {
     StateChanged(RallyState),
@@ -92,6 +99,27 @@ pub enum RallyEvent {
     AgentToolUse(String, String),    // tool_name, input_summary
     AgentToolResult(String, String), // tool_name, result_summary
     AgentText(String),               // text output
+    /// Tokens (and cost) used by the agent calls since the last update
+    UsageRecorded(TokenUsage),
+}
+
+impl RallyEvent {
+    /// Replace secret values from `[env]` in free-form text before it reaches the log view
+    pub(crate) fn redact(self, env: &SpawnEnv) -> Self {
+        let r = |s: String| env.redact(&s).into_owned();
+        match self {
+            RallyEvent::ClarificationNeeded(s) => RallyEvent::ClarificationNeeded(r(s)),
+            RallyEvent::Error(s) => RallyEvent::Error(r(s)),
+            RallyEvent::Log(s) => RallyEvent::Log(r(s)),
+            RallyEvent::AgentThinking(s) => RallyEvent::AgentThinking(r(s)),
+            RallyEvent::AgentToolUse(name, input) => RallyEvent::AgentToolUse(name, r(input)),
+            RallyEvent::AgentToolResult(name, result) => {
+                RallyEvent::AgentToolResult(name, r(result))
+            }
+            RallyEvent::AgentText(s) => RallyEvent::AgentText(r(s)),
+            other => other,
+        }
+    }
 }
 
 /// Result of the rally process
@@ -107,7 +135,7 @@ pub enum RallyResult {
 }
 
 /// Lightweight DTO for review post confirmation (sent via RallyEvent)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ReviewPostInfo {
     pub action: String,
     pub summary: String,
@@ -115,12 +143,21 @@ pub struct ReviewPostInfo {
 }
 
 /// Lightweight DTO for fix post confirmation (sent via RallyEvent)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FixPostInfo {
     pub summary: String,
     pub files_modified: Vec<String>,
 }
 
+/// How many times an agent is asked to resend an answer that does not match its output schema
+const MAX_OUTPUT_REPAIRS: u32 = 2;
+
+/// Prepended to the reviewee prompt in dry-run mode. The adapters also block file writes.
+const DRY_RUN_REVIEWEE_NOTE: &str = "\
+DRY RUN: Do NOT modify, create or delete any files and do NOT run git commands that change \
+the repository. Instead, describe the exact changes you would make (file, location and new \
+code) in your summary, and list the files you would modify in files_modified.\n\n";
+
 /// Command sent from TUI to Orchestrator
 #[derive(Debug)]
 pub enum OrchestratorCommand {
@@ -143,6 +180,8 @@ pub struct Orchestrator {
     config: AiConfig,
     reviewer_adapter: Box<dyn AgentAdapter>,
     reviewee_adapter: Box<dyn AgentAdapter>,
+    /// Reviewer run in parallel with `reviewer_adapter` (`ai.co_reviewer`)
+    co_reviewer_adapter: Option<Box<dyn AgentAdapter>>,
     session: RallySession,
     context: Option<Context>,
     last_review: Option<ReviewerOutput>,
@@ -151,6 +190,13 @@ pub struct Orchestrator {
     prompt_loader: PromptLoader,
     /// Command receiver for TUI commands
     command_receiver: Option<mpsc::Receiver<OrchestratorCommand>>,
+    /// Tokens (and cost) used by both agents so far, checked against the budget
+    total_usage: TokenUsage,
+    /// Events from the adapters and the orchestrator, written to the event log by `run`
+    /// before they are forwarded to the TUI
+    event_log: Option<(mpsc::Receiver<RallyEvent>, mpsc::Sender<RallyEvent>)>,
+    /// Continuing a rally saved on disk instead of starting over
+    resumed: bool,
 }
 
 impl Orchestrator {
@@ -164,10 +210,25 @@ impl Orchestrator {
     ) -> Result<Self> {
         let mut reviewer_adapter = create_adapter(&config.reviewer, &config)?;
         let mut reviewee_adapter = create_adapter(&config.reviewee, &config)?;
+        let mut co_reviewer_adapter = config
+            .co_reviewer
+            .as_deref()
+            .filter(|name| !name.trim().is_empty())
+            .map(|name| create_adapter(name, &config))
+            .transpose()?;
+
+        // All events pass through the event log on their way to the TUI
+        let (log_sender, log_receiver) = mpsc::channel(100);
 
         // Set event sender for streaming events
-        reviewer_adapter.set_event_sender(event_sender.clone());
-        reviewee_adapter.set_event_sender(event_sender.clone());
+        reviewer_adapter.set_event_sender(log_sender.clone());
+        reviewee_adapter.set_event_sender(log_sender.clone());
+        reviewer_adapter.set_dry_run(config.dry_run);
+        reviewee_adapter.set_dry_run(config.dry_run);
+        if let Some(adapter) = co_reviewer_adapter.as_mut() {
+            adapter.set_event_sender(log_sender.clone());
+            adapter.set_dry_run(config.dry_run);
+        }
 
         let session = RallySession::new(repo, pr_number);
         let prompt_loader = PromptLoader::new(&config, project_root);
@@ -178,13 +239,17 @@ impl Orchestrator {
             config,
             reviewer_adapter,
             reviewee_adapter,
+            co_reviewer_adapter,
             session,
             context: None,
             last_review: None,
             last_fix: None,
-            event_sender,
+            event_sender: log_sender,
             prompt_loader,
             command_receiver,
+            total_usage: TokenUsage::default(),
+            event_log: Some((log_receiver, event_sender)),
+            resumed: false,
         })
     }
 
@@ -194,9 +259,64 @@ impl Orchestrator {
         // git write restrictions at the tool level
         self.reviewer_adapter.set_local_mode(context.local_mode);
         self.reviewee_adapter.set_local_mode(context.local_mode);
+        self.reviewer_adapter.set_env(context.env.clone());
+        self.reviewee_adapter.set_env(context.env.clone());
+        if let Some(adapter) = self.co_reviewer_adapter.as_mut() {
+            adapter.set_local_mode(context.local_mode);
+            adapter.set_env(context.env.clone());
+        }
+        self.session.scope = context.scope.clone();
         self.context = Some(context);
     }
 
+    /// Continue the rally saved on disk for this PR instead of starting over.
+    ///
+    /// Restores the iteration count, the last review and fix, token usage and the agent
+    /// sessions. Returns false when there is no unfinished rally to resume.
+    pub fn resume(&mut self) -> Result<bool> {
+        let Some(saved) = read_session(&self.repo, self.pr_number)? else {
+            return Ok(false);
+        };
+        if !saved.is_resumable() {
+            return Ok(false);
+        }
+        let point = resume_point(&read_history(&self.repo, self.pr_number)?);
+
+        // A scoped rally resumes with the same files
+        if let Some(context) = self.context.as_mut() {
+            if context.scope.is_empty() && !saved.scope.is_empty() {
+                let scope = saved.scope.clone();
+                context
+                    .file_patches
+                    .retain(|(path, _)| scope.contains(path));
+                if !context.file_patches.is_empty() {
+                    context.diff = context
+                        .file_patches
+                        .iter()
+                        .map(|(_, patch)| patch.as_str())
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                }
+                context.scope = scope;
+            }
+        }
+
+        self.reviewer_adapter
+            .restore_session_ids(saved.reviewer_session_id.clone(), None);
+        self.reviewee_adapter
+            .restore_session_ids(None, saved.reviewee_session_id.clone());
+        self.total_usage = saved.usage;
+        self.last_review = point.last_review;
+        self.last_fix = point.last_fix;
+        self.session = RallySession {
+            iteration: point.iteration,
+            ..saved
+        };
+        self.session.update_state(RallyState::Initializing);
+        self.resumed = true;
+        Ok(true)
+    }
+
     /// Run the rally process
     pub async fn run(&mut self) -> Result<RallyResult> {
         let context = self
@@ -205,11 +325,39 @@ impl Orchestrator {
             .ok_or_else(|| anyhow!("Context not set"))?
             .clone();
 
+        if self.resumed {
+            // Keep the event log so it still holds the whole rally
+            self.start_event_log();
+            self.send_event(RallyEvent::Log(format!(
+                "Resuming rally after iteration {}",
+                self.session.iteration
+            )))
+            .await;
+        } else {
+            if let Err(e) = clear_rally_state(&self.repo, self.pr_number) {
+                warn!("Failed to clear previous rally: {}", e);
+            }
+            self.start_event_log();
+        }
+
         self.send_event(RallyEvent::StateChanged(RallyState::Initializing))
             .await;
+        if self.config.dry_run {
+            self.send_event(RallyEvent::Log(
+                "Dry run: nothing will be posted to the PR and no files will be written"
+                    .to_string(),
+            ))
+            .await;
+        }
 
         // Main loop
         while self.session.iteration < self.config.max_iterations {
+            // Follow-up calls (clarification / permission) of the previous iteration
+            self.record_usage().await;
+            if let Some(reason) = budget_exceeded(&self.config, &self.total_usage) {
+                return Ok(self.abort_over_budget(reason).await);
+            }
+
             self.session.increment_iteration();
             let iteration = self.session.iteration;
 
@@ -239,7 +387,9 @@ impl Orchestrator {
                 .await;
             }
 
-            let review_result = match self.run_reviewer_with_timeout(&context, iteration).await {
+            let review_result = self.run_reviewer_with_timeout(&context, iteration).await;
+            self.record_usage().await;
+            let review_result = match review_result {
                 Ok(result) => result,
                 Err(e) => {
                     self.session.update_state(RallyState::Error);
@@ -311,6 +461,11 @@ impl Orchestrator {
                 });
             }
 
+            // Stop before the reviewee starts another round of work
+            if let Some(reason) = budget_exceeded(&self.config, &self.total_usage) {
+                return Ok(self.abort_over_budget(reason).await);
+            }
+
             // Run reviewee to fix issues
             self.session.update_state(RallyState::RevieweeFix);
             self.send_event(RallyEvent::StateChanged(RallyState::RevieweeFix))
@@ -344,10 +499,11 @@ impl Orchestrator {
                 .ok_or_else(|| anyhow!("Context not set"))?
                 .clone();
 
-            let fix_result = match self
+            let fix_result = self
                 .run_reviewee_with_timeout(&context, &review_result, iteration)
-                .await
-            {
+                .await;
+            self.record_usage().await;
+            let fix_result = match fix_result {
                 Ok(result) => result,
                 Err(e) => {
                     self.session.update_state(RallyState::Error);
@@ -449,7 +605,7 @@ impl Orchestrator {
                                     .await;
 
                                     let prompt = build_clarification_skipped_prompt(question);
-                                    match self.reviewee_adapter.continue_reviewee(&prompt).await {
+                                    match self.follow_up_reviewee(&prompt).await {
                                         Ok(output) => {
                                             // Write history entry for the follow-up fix
                                             if let Err(e) = write_history_entry(
@@ -577,8 +733,7 @@ impl Orchestrator {
                                             &perm.action,
                                             &perm.reason,
                                         );
-                                        match self.reviewee_adapter.continue_reviewee(&prompt).await
-                                        {
+                                        match self.follow_up_reviewee(&prompt).await {
                                             Ok(output) => {
                                                 // Write history entry for the follow-up fix
                                                 if let Err(e) = write_history_entry(
@@ -709,7 +864,7 @@ impl Orchestrator {
 
         // Ask reviewer for clarification and log the response
         let prompt = build_clarification_prompt(answer);
-        let reviewer_response = self.reviewer_adapter.continue_reviewer(&prompt).await?;
+        let reviewer_response = self.follow_up_reviewer(&prompt).await?;
 
         // Log the reviewer's response for debugging/audit purposes
         self.send_event(RallyEvent::Log(format!(
@@ -719,7 +874,7 @@ impl Orchestrator {
         .await;
 
         // Continue reviewee with the answer
-        self.reviewee_adapter.continue_reviewee(answer).await?;
+        self.follow_up_reviewee(answer).await?;
 
         self.session.update_state(RallyState::RevieweeFix);
         self.send_event(RallyEvent::StateChanged(RallyState::RevieweeFix))
@@ -745,7 +900,7 @@ impl Orchestrator {
 
                 // Route to denied flow instead of returning error
                 let prompt = build_permission_denied_prompt(action, &reason);
-                self.reviewee_adapter.continue_reviewee(&prompt).await?;
+                self.follow_up_reviewee(&prompt).await?;
 
                 self.session.update_state(RallyState::RevieweeFix);
                 self.send_event(RallyEvent::StateChanged(RallyState::RevieweeFix))
@@ -767,7 +922,7 @@ impl Orchestrator {
         self.reviewee_adapter.add_reviewee_allowed_tool(action);
 
         let prompt = build_permission_granted_prompt(action);
-        self.reviewee_adapter.continue_reviewee(&prompt).await?;
+        self.follow_up_reviewee(&prompt).await?;
 
         self.session.update_state(RallyState::RevieweeFix);
         self.send_event(RallyEvent::StateChanged(RallyState::RevieweeFix))
@@ -794,14 +949,18 @@ impl Orchestrator {
         context: &Context,
         iteration: u32,
     ) -> Result<ReviewerOutput> {
-        let prompt = if iteration == 1 {
-            self.prompt_loader.load_reviewer_prompt(context, iteration)
+        // Re-review after fixes - fetch updated diff and include fix summary
+        let rereview = if iteration == 1 {
+            None
         } else {
-            // Re-review after fixes - fetch updated diff and include fix summary
-            let updated_diff = self.fetch_current_diff().await.unwrap_or_else(|e| {
-                warn!("Failed to fetch updated diff: {}", e);
-                context.diff.clone()
-            });
+            let updated_diff = match self.fetch_current_diff().await {
+                // A scoped rally re-reviews only its own files
+                Ok(diff) => context.scoped_diff(diff),
+                Err(e) => {
+                    warn!("Failed to fetch updated diff: {}", e);
+                    context.diff.clone()
+                }
+            };
 
             let changes_summary = self
                 .last_fix
@@ -815,27 +974,142 @@ impl Orchestrator {
                     format!("{}\n\nFiles modified: {}", f.summary, files)
                 })
                 .unwrap_or_else(|| "No changes recorded".to_string());
-            self.prompt_loader.load_rereview_prompt(
-                context,
-                iteration,
-                &changes_summary,
-                &updated_diff,
-            )
+            Some((changes_summary, updated_diff))
+        };
+
+        let file_patches = match rereview {
+            Some((_, ref updated_diff)) => context_planner::split_git_diff(updated_diff),
+            None if context.file_patches.is_empty() => {
+                context_planner::split_git_diff(&context.diff)
+            }
+            None => context.file_patches.clone(),
         };
+        let plan = context_planner::plan(&file_patches, self.config.context_budget_tokens);
+
+        // Fits in the budget: send the diff as-is
+        if !plan.is_chunked() && plan.summaries.is_empty() {
+            let prompt = match rereview {
+                Some((ref changes_summary, ref updated_diff)) => self
+                    .prompt_loader
+                    .load_rereview_prompt(context, iteration, changes_summary, updated_diff),
+                None => self.prompt_loader.load_reviewer_prompt(context, iteration),
+            };
+            return self.run_reviewer_prompt(&prompt, context).await;
+        }
+
+        let total = plan.chunks.len();
+        let mut outputs = Vec::with_capacity(total);
+        for (index, chunk) in plan.chunks.iter().enumerate() {
+            self.send_event(RallyEvent::Log(format!(
+                "Large diff: reviewing part {}/{} ({} files, {} omitted or summarized)",
+                index + 1,
+                total,
+                chunk.files.len(),
+                plan.summaries.len()
+            )))
+            .await;
 
+            let diff = format!("{}{}", chunk.diff, plan.chunk_note(index));
+            let mut chunk_context = context.clone();
+            chunk_context.diff = diff.clone();
+            let prompt = match rereview {
+                Some((ref changes_summary, _)) => self.prompt_loader.load_rereview_prompt(
+                    context,
+                    iteration,
+                    changes_summary,
+                    &diff,
+                ),
+                None => self
+                    .prompt_loader
+                    .load_reviewer_prompt(&chunk_context, iteration),
+            };
+            outputs.push(self.run_reviewer_prompt(&prompt, &chunk_context).await?);
+        }
+
+        context_planner::merge_reviews(outputs)
+            .ok_or_else(|| anyhow!("Reviewer produced no output"))
+    }
+
+    async fn run_reviewer_prompt(
+        &mut self,
+        prompt: &str,
+        context: &Context,
+    ) -> Result<ReviewerOutput> {
         let duration = Duration::from_secs(self.config.timeout_secs);
+        let timeout_secs = self.config.timeout_secs;
+        let timed_out = |role: &str| anyhow!("{} timeout after {} seconds", role, timeout_secs);
 
-        timeout(
-            duration,
-            self.reviewer_adapter.run_reviewer(&prompt, context),
-        )
-        .await
-        .map_err(|_| {
-            anyhow!(
-                "Reviewer timeout after {} seconds",
-                self.config.timeout_secs
+        let Some(co_reviewer) = self.co_reviewer_adapter.as_mut() else {
+            let result = timeout(
+                duration,
+                self.reviewer_adapter.run_reviewer(prompt, context),
             )
-        })?
+            .await
+            .map_err(|_| timed_out("Reviewer"))
+            .and_then(|r| r);
+            return self.repair_review(false, result).await;
+        };
+
+        // Both reviewers look at the same diff at the same time
+        let (primary, secondary) = tokio::join!(
+            timeout(
+                duration,
+                self.reviewer_adapter.run_reviewer(prompt, context)
+            ),
+            timeout(duration, co_reviewer.run_reviewer(prompt, context)),
+        );
+        let primary = primary.map_err(|_| timed_out("Reviewer")).and_then(|r| r);
+        let secondary = secondary
+            .map_err(|_| timed_out("Co-reviewer"))
+            .and_then(|r| r);
+        let primary_name = self.reviewer_adapter.name().to_string();
+        let secondary_name = co_reviewer.name().to_string();
+        let primary = self.repair_review(false, primary).await;
+        let secondary = self.repair_review(true, secondary).await;
+
+        match (primary, secondary) {
+            (Ok(primary), Ok(secondary)) => {
+                let merged = consensus::merge(vec![
+                    (primary_name.clone(), primary),
+                    (secondary_name.clone(), secondary),
+                ])
+                .ok_or_else(|| anyhow!("Reviewer produced no output"))?;
+                self.log_consensus(&merged, &primary_name, &secondary_name)
+                    .await;
+                Ok(merged)
+            }
+            (Ok(review), Err(e)) | (Err(e), Ok(review)) => {
+                self.send_event(RallyEvent::Log(format!(
+                    "One reviewer failed, using the other review only: {}",
+                    e
+                )))
+                .await;
+                Ok(review)
+            }
+            (Err(e), Err(_)) => Err(e),
+        }
+    }
+
+    /// Log the merged findings of parallel reviewers with the agents that reported them
+    async fn log_consensus(&self, review: &ReviewerOutput, primary: &str, secondary: &str) {
+        self.send_event(RallyEvent::Log(format!(
+            "Reviewers {} and {}: {} findings, {} reported by both",
+            primary,
+            secondary,
+            review.comments.len(),
+            consensus::agreed_count(review)
+        )))
+        .await;
+        for comment in &review.comments {
+            self.send_event(RallyEvent::Log(format!(
+                "[{}] {}:{} {}",
+                comment.sources.join(", "),
+                comment.path,
+                comment.line,
+                comment.body
+            )))
+            .await;
+        }
     }
 
     async fn run_reviewee_with_timeout(
@@ -844,12 +1118,15 @@ impl Orchestrator {
         review: &ReviewerOutput,
         iteration: u32,
     ) -> Result<RevieweeOutput> {
-        let prompt = self
+        let mut prompt = self
             .prompt_loader
             .load_reviewee_prompt(context, review, iteration);
+        if self.config.dry_run {
+            prompt.insert_str(0, DRY_RUN_REVIEWEE_NOTE);
+        }
         let duration = Duration::from_secs(self.config.timeout_secs);
 
-        timeout(
+        let result = timeout(
             duration,
             self.reviewee_adapter.run_reviewee(&prompt, context),
         )
@@ -859,18 +1136,158 @@ impl Orchestrator {
                 "Reviewee timeout after {} seconds",
                 self.config.timeout_secs
             )
-        })?
+        })
+        .and_then(|r| r);
+        self.repair_fix(result).await
+    }
+
+    /// Send a follow-up message to the reviewer, repairing an answer in the wrong format
+    async fn follow_up_reviewer(&mut self, message: &str) -> Result<ReviewerOutput> {
+        let result = self.reviewer_adapter.continue_reviewer(message).await;
+        self.repair_review(false, result).await
+    }
+
+    /// Send a follow-up message to the reviewee, repairing an answer in the wrong format
+    async fn follow_up_reviewee(&mut self, message: &str) -> Result<RevieweeOutput> {
+        let result = self.reviewee_adapter.continue_reviewee(message).await;
+        self.repair_fix(result).await
+    }
+
+    /// While the review does not match the output schema, send the problems back to the
+    /// reviewer (or the co-reviewer) and ask for a corrected answer, up to `MAX_OUTPUT_REPAIRS`
+    /// times. Other errors are returned as they are.
+    async fn repair_review(
+        &mut self,
+        co_reviewer: bool,
+        mut result: Result<ReviewerOutput>,
+    ) -> Result<ReviewerOutput> {
+        let timeout_secs = self.config.timeout_secs;
+        let duration = Duration::from_secs(timeout_secs);
+        for attempt in 1..=MAX_OUTPUT_REPAIRS {
+            let Some((error, prompt)) = output_repair(&result) else {
+                break;
+            };
+            self.log_output_repair(&error, attempt).await;
+            let adapter = if co_reviewer {
+                self.co_reviewer_adapter.as_mut()
+            } else {
+                Some(&mut self.reviewer_adapter)
+            };
+            let Some(adapter) = adapter else {
+                break;
+            };
+            result = timeout(duration, adapter.continue_reviewer(&prompt))
+                .await
+                .map_err(|_| anyhow!("Output repair timeout after {} seconds", timeout_secs))
+                .and_then(|r| r);
+        }
+        result
+    }
+
+    /// Reviewee counterpart of [`Self::repair_review`]
+    async fn repair_fix(&mut self, mut result: Result<RevieweeOutput>) -> Result<RevieweeOutput> {
+        let timeout_secs = self.config.timeout_secs;
+        let duration = Duration::from_secs(timeout_secs);
+        for attempt in 1..=MAX_OUTPUT_REPAIRS {
+            let Some((error, prompt)) = output_repair(&result) else {
+                break;
+            };
+            self.log_output_repair(&error, attempt).await;
+            result = timeout(duration, self.reviewee_adapter.continue_reviewee(&prompt))
+                .await
+                .map_err(|_| anyhow!("Output repair timeout after {} seconds", timeout_secs))
+                .and_then(|r| r);
+        }
+        result
+    }
+
+    async fn log_output_repair(&self, error: &str, attempt: u32) {
+        warn!("{}", error);
+        self.send_event(RallyEvent::Log(format!(
+            "{}. Asking for a corrected answer ({}/{})",
+            error, attempt, MAX_OUTPUT_REPAIRS
+        )))
+        .await;
+    }
+
+    /// Collect the usage reported by both adapters, add it to the total and notify the TUI.
+    /// Also keeps the agent sessions in the rally session so it can be resumed.
+    async fn record_usage(&mut self) {
+        self.session.reviewer_session_id = self.reviewer_adapter.session_ids().0;
+        self.session.reviewee_session_id = self.reviewee_adapter.session_ids().1;
+
+        let mut usage = self.reviewer_adapter.take_usage();
+        usage.add(&self.reviewee_adapter.take_usage());
+        if let Some(adapter) = self.co_reviewer_adapter.as_mut() {
+            usage.add(&adapter.take_usage());
+        }
+        if usage.is_empty() {
+            return;
+        }
+        let usage = usage.with_estimated_cost(
+            self.config.input_cost_per_mtok,
+            self.config.output_cost_per_mtok,
+        );
+        self.total_usage.add(&usage);
+        self.session.usage = self.total_usage;
+        self.send_event(RallyEvent::UsageRecorded(usage)).await;
+    }
+
+    /// Start writing events to the rally's event log and forwarding them to the TUI
+    fn start_event_log(&mut self) {
+        let Some((mut receiver, sender)) = self.event_log.take() else {
+            return;
+        };
+        let path = events_path(&self.repo, self.pr_number)
+            .map_err(|e| warn!("Rally events will not be saved: {}", e))
+            .ok();
+        tokio::spawn(async move {
+            while let Some(event) = receiver.recv().await {
+                if let Some(ref path) = path {
+                    if let Err(e) = append_event(path, &event) {
+                        warn!("Failed to save rally event: {}", e);
+                    }
+                }
+                let _ = sender.send(event).await;
+            }
+        });
+    }
+
+    /// Abort the rally because the token or cost budget ran out
+    async fn abort_over_budget(&mut self, reason: String) -> RallyResult {
+        self.session.update_state(RallyState::Aborted);
+        let _ = write_session(&self.session);
+        self.send_event(RallyEvent::Error(reason.clone())).await;
+        self.send_event(RallyEvent::StateChanged(RallyState::Aborted))
+            .await;
+        RallyResult::Aborted {
+            iteration: self.session.iteration,
+            reason,
+        }
     }
 
     async fn send_event(&self, event: RallyEvent) {
+        let event = match self.context.as_ref() {
+            Some(context) => event.redact(&context.env),
+            None => event,
+        };
         let _ = self.event_sender.send(event).await;
     }
 
     /// Wrapper that optionally asks for user confirmation before posting review.
+    /// - dry_run: only log that the review was not posted
     /// - local_mode: skip posting entirely
     /// - auto_post: post directly without confirmation
     /// - otherwise: send confirmation event and wait for user response
     async fn maybe_post_review_to_pr(&mut self, review: &ReviewerOutput) -> Result<()> {
+        if self.config.dry_run {
+            self.send_event(RallyEvent::Log(
+                "Dry run: not posting the review to the PR".to_string(),
+            ))
+            .await;
+            return Ok(());
+        }
+
         // local_mode is handled inside post_review_to_pr
         if self.context.as_ref().is_some_and(|c| c.local_mode) {
             return self.post_review_to_pr(review).await;
@@ -928,6 +1345,14 @@ impl Orchestrator {
 
     /// Wrapper that optionally asks for user confirmation before posting fix comment.
     async fn maybe_post_fix_comment(&mut self, fix: &RevieweeOutput) -> Result<()> {
+        if self.config.dry_run {
+            self.send_event(RallyEvent::Log(
+                "Dry run: not posting the fix comment to the PR".to_string(),
+            ))
+            .await;
+            return Ok(());
+        }
+
         // local_mode is handled inside post_fix_comment
         if self.context.as_ref().is_some_and(|c| c.local_mode) {
             return self.post_fix_comment(fix).await;
@@ -1003,9 +1428,9 @@ impl Orchestrator {
 
         // Map AI ReviewAction to App ReviewAction
         let app_action = match review.action {
-            ReviewAction::Approve => crate::app::ReviewAction::Approve,
-            ReviewAction::RequestChanges => crate::app::ReviewAction::RequestChanges,
-            ReviewAction::Comment => crate::app::ReviewAction::Comment,
+            ReviewAction::Approve => crate::github::ReviewAction::Approve,
+            ReviewAction::RequestChanges => crate::github::ReviewAction::RequestChanges,
+            ReviewAction::Comment => crate::github::ReviewAction::Comment,
         };
 
         // Copy for potential fallback use (app_action is moved into submit_review)
@@ -1016,22 +1441,33 @@ impl Orchestrator {
 
         // Post summary comment using gh pr review
         // If approve fails (e.g., can't approve own PR), fall back to comment
-        let result =
+        let mut result =
             github::submit_review(&self.repo, self.pr_number, app_action, &summary_with_prefix)
                 .await;
 
-        if result.is_err() && matches!(app_action_for_fallback, crate::app::ReviewAction::Approve) {
+        if result.is_err()
+            && matches!(
+                app_action_for_fallback,
+                crate::github::ReviewAction::Approve
+            )
+        {
             warn!("Approve failed, falling back to comment");
-            github::submit_review(
+            result = github::submit_review(
                 &self.repo,
                 self.pr_number,
-                crate::app::ReviewAction::Comment,
+                crate::github::ReviewAction::Comment,
                 &summary_with_prefix,
             )
-            .await?;
-        } else {
-            result?;
+            .await;
         }
+        audit::record(&AuditEntry::new(
+            &self.repo,
+            self.pr_number,
+            AuditAction::RallyReview,
+            None,
+            &result,
+        ));
+        result?;
 
         // Post inline comments with rate limit handling
         for comment in &review.comments {
@@ -1055,9 +1491,17 @@ impl Orchestrator {
                 continue;
             };
 
-            // Add prefix to inline comment
-            let body_with_prefix = format!("[AI Rally - Reviewer]\n\n{}", comment.body);
-            if let Err(e) = github::create_review_comment(
+            // Add prefix to inline comment, naming the reviewers that found it
+            let body_with_prefix = if comment.sources.is_empty() {
+                format!("[AI Rally - Reviewer]\n\n{}", comment.body)
+            } else {
+                format!(
+                    "[AI Rally - Reviewer ({})]\n\n{}",
+                    comment.sources.join(", "),
+                    comment.body
+                )
+            };
+            let result = github::create_review_comment(
                 &self.repo,
                 self.pr_number,
                 &context.head_sha,
@@ -1065,8 +1509,15 @@ impl Orchestrator {
                 position,
                 &body_with_prefix,
             )
-            .await
-            {
+            .await;
+            audit::record(&AuditEntry::new(
+                &self.repo,
+                self.pr_number,
+                AuditAction::RallyComment,
+                Some(format!("{}:{}", comment.path, comment.line)),
+                &result,
+            ));
+            if let Err(e) = result {
                 warn!(
                     "Failed to post inline comment on {}:{} (position {}): {}",
                     comment.path, comment.line, position, e
@@ -1106,13 +1557,21 @@ impl Orchestrator {
         );
 
         // Post as a comment (not a review)
-        github::submit_review(
+        let result = github::submit_review(
             &self.repo,
             self.pr_number,
-            crate::app::ReviewAction::Comment,
+            crate::github::ReviewAction::Comment,
             &comment_body,
         )
-        .await?;
+        .await;
+        audit::record(&AuditEntry::new(
+            &self.repo,
+            self.pr_number,
+            AuditAction::RallyFix,
+            None,
+            &result,
+        ));
+        result?;
 
         Ok(())
     }
@@ -1336,6 +1795,34 @@ impl Orchestrator {
 }
 
 /// Check if a user is a bot
+/// Reason to stop the rally when `total` has reached `budget_tokens` or `budget_usd`
+fn budget_exceeded(config: &AiConfig, total: &TokenUsage) -> Option<String> {
+    if config.budget_tokens > 0 && total.total_tokens() >= config.budget_tokens {
+        return Some(format!(
+            "Token budget exceeded: {} tokens used (budget {}). Aborting rally.",
+            total.total_tokens(),
+            config.budget_tokens
+        ));
+    }
+    match total.cost_usd {
+        Some(cost) if config.budget_usd > 0.0 && cost >= config.budget_usd => Some(format!(
+            "Cost budget exceeded: ${:.2} spent (budget ${:.2}). Aborting rally.",
+            cost, config.budget_usd
+        )),
+        _ => None,
+    }
+}
+
+/// The error message and the follow-up prompt when `result` failed because the answer does
+/// not match the output schema
+fn output_repair<T>(result: &Result<T>) -> Option<(String, String)> {
+    let error = result.as_ref().err()?.downcast_ref::<OutputFormatError>()?;
+    Some((
+        error.to_string(),
+        build_output_repair_prompt(&error.problems),
+    ))
+}
+
 fn is_bot_user(login: &str) -> bool {
     BOT_SUFFIXES.iter().any(|suffix| login.ends_with(suffix)) || BOT_EXACT_MATCHES.contains(&login)
 }
@@ -1458,6 +1945,26 @@ mod tests {
     use super::*;
     use tokio::sync::mpsc;
 
+    #[test]
+    fn test_output_repair_only_for_format_errors() {
+        let format_error: Result<()> = Err(OutputFormatError {
+            role: "reviewer",
+            agent: "claude".to_string(),
+            problems: vec!["$: missing required field \"summary\"".to_string()],
+        }
+        .into());
+        let (error, prompt) = output_repair(&format_error).unwrap();
+        assert_eq!(
+            error,
+            "Invalid reviewer output from claude: $: missing required field \"summary\""
+        );
+        assert!(prompt.contains("- $: missing required field \"summary\""));
+
+        let timeout: Result<()> = Err(anyhow!("Reviewer timeout after 600 seconds"));
+        assert!(output_repair(&timeout).is_none());
+        assert!(output_repair(&Ok(())).is_none());
+    }
+
     #[test]
     fn test_orchestrator_command_variants() {
         // Test ClarificationResponse
@@ -1869,4 +2376,96 @@ mod tests {
         assert!(check_blocked_git_operation("Bash(widget build:*)").is_none());
         assert!(check_blocked_git_operation("Bash(cargo test --features digit:*)").is_none());
     }
+
+    #[test]
+    fn test_rally_event_redacts_env_secrets() {
+        let config: crate::config::Config = toml::from_str(
+            r#"
+            [env.vars]
+            NPM_TOKEN = "npm_abcdef"
+            "#,
+        )
+        .unwrap();
+        let env = SpawnEnv::for_repo(&config.env, "owner/repo");
+
+        match RallyEvent::AgentToolResult("Bash".into(), "npm_abcdef ok".into()).redact(&env) {
+            RallyEvent::AgentToolResult(name, result) => {
+                assert_eq!(name, "Bash");
+                assert_eq!(result, "[redacted] ok");
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+        match RallyEvent::Error("auth npm_abcdef".into()).redact(&env) {
+            RallyEvent::Error(message) => assert_eq!(message, "auth [redacted]"),
+            other => panic!("Unexpected event: {:?}", other),
+        }
+        assert!(matches!(
+            RallyEvent::IterationStarted(2).redact(&env),
+            RallyEvent::IterationStarted(2)
+        ));
+    }
+
+    #[test]
+    fn test_token_usage_add_and_estimate() {
+        let mut total = TokenUsage::default();
+        assert!(total.is_empty());
+        total.add(&TokenUsage {
+            input_tokens: 1_000_000,
+            output_tokens: 200_000,
+            cost_usd: None,
+        });
+        assert_eq!(total.cost_usd, None);
+
+        // Estimated only when the agent reported no cost and a price is set
+        assert_eq!(total.with_estimated_cost(0.0, 0.0).cost_usd, None);
+        let estimated = total.with_estimated_cost(3.0, 15.0);
+        assert_eq!(estimated.cost_usd, Some(6.0));
+
+        total.add(&TokenUsage {
+            input_tokens: 10,
+            output_tokens: 5,
+            cost_usd: Some(0.5),
+        });
+        assert_eq!(total.total_tokens(), 1_200_015);
+        assert_eq!(total.cost_usd, Some(0.5));
+        assert_eq!(total.with_estimated_cost(3.0, 15.0).cost_usd, Some(0.5));
+        assert_eq!(total.to_string(), "1.20M tokens ($0.50)");
+        assert_eq!(TokenUsage::default().to_string(), "0 tokens");
+    }
+
+    #[test]
+    fn test_budget_exceeded() {
+        let usage = TokenUsage {
+            input_tokens: 9_000,
+            output_tokens: 1_000,
+            cost_usd: Some(1.25),
+        };
+        assert!(budget_exceeded(&AiConfig::default(), &usage).is_none());
+
+        let config = AiConfig {
+            budget_tokens: 10_000,
+            ..Default::default()
+        };
+        assert_eq!(
+            budget_exceeded(&config, &usage).as_deref(),
+            Some("Token budget exceeded: 10000 tokens used (budget 10000). Aborting rally.")
+        );
+
+        let config = AiConfig {
+            budget_tokens: 20_000,
+            budget_usd: 1.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            budget_exceeded(&config, &usage).as_deref(),
+            Some("Cost budget exceeded: $1.25 spent (budget $1.00). Aborting rally.")
+        );
+
+        // Without a reported or estimated cost only the token budget applies
+        let unpriced = TokenUsage {
+            cost_usd: None,
+            ..usage
+        };
+        assert!(budget_exceeded(&config, &unpriced).is_none());
+    }
 }
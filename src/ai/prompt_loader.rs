@@ -26,6 +26,9 @@ mod defaults {
     pub const REREVIEW: &str = include_str!("defaults/rereview.md");
 }
 
+/// Project-local coding guidelines used when `ai.guidelines_file` is not set
+const LOCAL_GUIDELINES_FILE: &str = ".octorus/guidelines.md";
+
 /// Prompt loader that reads templates from files or uses defaults.
 ///
 /// Resolution order (highest priority first):
@@ -37,6 +40,8 @@ pub struct PromptLoader {
     prompt_dir: Option<PathBuf>,
     local_prompts_dir: Option<PathBuf>,
     global_prompts_dir: Option<PathBuf>,
+    /// Contents of the coding guidelines file, rendered into `{{guidelines}}`
+    guidelines: Option<String>,
 }
 
 impl PromptLoader {
@@ -69,6 +74,34 @@ impl PromptLoader {
             prompt_dir,
             local_prompts_dir,
             global_prompts_dir,
+            guidelines: Self::load_guidelines(config, project_root),
+        }
+    }
+
+    /// Path of the coding guidelines file: `config.guidelines_file` (relative to the
+    /// project root), or `.octorus/guidelines.md` when it exists
+    pub fn guidelines_path(config: &AiConfig, project_root: &Path) -> Option<PathBuf> {
+        match config.guidelines_file.as_ref() {
+            Some(file) => Some(project_root.join(file)),
+            None => Some(project_root.join(LOCAL_GUIDELINES_FILE)).filter(|path| path.is_file()),
+        }
+    }
+
+    /// Read the coding guidelines. A configured file that cannot be read is reported
+    /// and skipped, like a prompt template.
+    fn load_guidelines(config: &AiConfig, project_root: &Path) -> Option<String> {
+        let path = Self::guidelines_path(config, project_root)?;
+        match fs::read_to_string(&path) {
+            Ok(content) if content.trim().is_empty() => None,
+            Ok(content) => Some(content.trim().to_string()),
+            Err(e) => {
+                eprintln!(
+                    "Warning: Failed to read coding guidelines from {}: {}",
+                    path.display(),
+                    e
+                );
+                None
+            }
         }
     }
 
@@ -111,24 +144,49 @@ impl PromptLoader {
             .collect()
     }
 
-    /// Load the reviewer prompt with variable substitution
-    pub fn load_reviewer_prompt(&self, context: &Context, iteration: u32) -> String {
-        let template = self.load_template("reviewer.md", defaults::REVIEWER);
-
+    /// Variables available in every template: PR metadata, the iteration and the
+    /// coding guidelines section
+    fn common_vars(&self, context: &Context, iteration: u32) -> HashMap<&'static str, String> {
         let pr_body = context
             .pr_body
             .as_deref()
             .unwrap_or("(No description provided)");
 
+        let guidelines_section = self
+            .guidelines
+            .as_ref()
+            .map(|text| {
+                format!(
+                    "\n## Coding Guidelines\n\n\
+                     This repository's review standards. Follow them and point out violations:\n\n\
+                     {}\n",
+                    text
+                )
+            })
+            .unwrap_or_default();
+
         let mut vars = HashMap::new();
         vars.insert("repo", context.repo.clone());
         vars.insert("pr_number", context.pr_number.to_string());
         vars.insert("pr_title", context.pr_title.clone());
         vars.insert("pr_body", pr_body.to_string());
-        vars.insert("diff", context.diff.clone());
+        vars.insert("base_branch", context.base_branch.clone());
+        vars.insert("head_sha", context.head_sha.clone());
         vars.insert("iteration", iteration.to_string());
+        vars.insert("guidelines", guidelines_section);
+        vars
+    }
 
-        render_template(&template, &vars)
+    /// Load the reviewer prompt with variable substitution
+    pub fn load_reviewer_prompt(&self, context: &Context, iteration: u32) -> String {
+        let template = self.load_template("reviewer.md", defaults::REVIEWER);
+
+        let mut vars = self.common_vars(context, iteration);
+        vars.insert("diff", context.diff.clone());
+
+        let mut prompt = render_template(&template, &vars);
+        prompt.push_str(&scope_section(context));
+        prompt
     }
 
     /// Load the reviewee prompt with variable substitution
@@ -224,11 +282,7 @@ Note: Address these comments if they are relevant and valid. Don't wait for more
              If git push is needed and allowed, it will be explicitly permitted via config."
         };
 
-        let mut vars = HashMap::new();
-        vars.insert("repo", context.repo.clone());
-        vars.insert("pr_number", context.pr_number.to_string());
-        vars.insert("pr_title", context.pr_title.clone());
-        vars.insert("iteration", iteration.to_string());
+        let mut vars = self.common_vars(context, iteration);
         vars.insert("review_summary", review.summary.clone());
         vars.insert("review_action", review_action.to_string());
         vars.insert("review_comments", comments_text);
@@ -249,15 +303,13 @@ Note: Address these comments if they are relevant and valid. Don't wait for more
     ) -> String {
         let template = self.load_template("rereview.md", defaults::REREVIEW);
 
-        let mut vars = HashMap::new();
-        vars.insert("repo", context.repo.clone());
-        vars.insert("pr_number", context.pr_number.to_string());
-        vars.insert("pr_title", context.pr_title.clone());
-        vars.insert("iteration", iteration.to_string());
+        let mut vars = self.common_vars(context, iteration);
         vars.insert("changes_summary", changes_summary.to_string());
         vars.insert("updated_diff", updated_diff.to_string());
 
-        render_template(&template, &vars)
+        let mut prompt = render_template(&template, &vars);
+        prompt.push_str(&scope_section(context));
+        prompt
     }
 
     /// Load a template with multi-level resolution.
@@ -302,8 +354,27 @@ Note: Address these comments if they are relevant and valid. Don't wait for more
     }
 }
 
+/// Appended to reviewer prompts when the rally is limited to some files.
+/// Added after rendering so that custom templates get it too.
+fn scope_section(context: &Context) -> String {
+    if context.scope.is_empty() {
+        return String::new();
+    }
+    let files = context
+        .scope
+        .iter()
+        .map(|path| format!("- {}", path))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "\n\n## Scope\n\nThis review is limited to the following files of the PR. \
+Only review and comment on these files; the rest of the PR is out of scope:\n\n{}\n",
+        files
+    )
+}
+
 /// Render a template by replacing {{key}} with values from vars
-fn render_template(template: &str, vars: &HashMap<&str, String>) -> String {
+pub(crate) fn render_template(template: &str, vars: &HashMap<&str, String>) -> String {
     let mut result = template.to_string();
     for (key, value) in vars {
         let placeholder = format!("{{{{{}}}}}", key);
@@ -344,6 +415,8 @@ mod tests {
             external_comments: Vec::new(),
             local_mode: false,
             file_patches: Vec::new(),
+            env: crate::spawn_env::SpawnEnv::default(),
+            scope: Vec::new(),
         }
     }
 
@@ -382,6 +455,35 @@ mod tests {
         assert!(prompt.contains("This adds a new feature"));
         assert!(prompt.contains("+added line"));
         assert!(prompt.contains("iteration 1"));
+        assert!(!prompt.contains("## Scope"));
+    }
+
+    #[test]
+    fn test_scoped_prompts_list_the_files() {
+        let loader = create_default_loader();
+        let mut context = create_test_context();
+        context.scope = vec!["src/a.rs".to_string(), "src/b.rs".to_string()];
+
+        let prompt = loader.load_reviewer_prompt(&context, 1);
+        assert!(prompt.ends_with("the rest of the PR is out of scope:\n\n- src/a.rs\n- src/b.rs\n"));
+        let prompt = loader.load_rereview_prompt(&context, 2, "Fixed", "+new code");
+        assert!(prompt.contains("## Scope"));
+    }
+
+    #[test]
+    fn test_context_scoped_diff() {
+        let diff = "diff --git a/src/a.rs b/src/a.rs\n@@ -1 +1 @@\n-a\n+b\n\
+                    diff --git a/src/c.rs b/src/c.rs\n@@ -1 +1 @@\n-c\n+d\n";
+        let mut context = create_test_context();
+        assert_eq!(context.scoped_diff(diff.to_string()), diff);
+
+        context.scope = vec!["src/a.rs".to_string()];
+        assert!(context.in_scope("src/a.rs"));
+        assert!(!context.in_scope("src/c.rs"));
+        assert_eq!(
+            context.scoped_diff(diff.to_string()),
+            "diff --git a/src/a.rs b/src/a.rs\n@@ -1 +1 @@\n-a\n+b\n"
+        );
     }
 
     #[test]
@@ -398,6 +500,7 @@ mod tests {
                 line: 10,
                 body: "Missing error handling".to_string(),
                 severity: CommentSeverity::Major,
+                sources: Vec::new(),
             }],
             blocking_issues: vec!["Fix error handling".to_string()],
         };
@@ -474,7 +577,92 @@ mod tests {
             prompt_dir: None,
             local_prompts_dir: None,
             global_prompts_dir: None,
+            guidelines: None,
+        }
+    }
+
+    #[test]
+    fn test_guidelines_are_rendered_into_every_prompt() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("REVIEWING.md"),
+            "- No unwrap in library code\n",
+        )
+        .unwrap();
+        let config = AiConfig {
+            guidelines_file: Some("REVIEWING.md".to_string()),
+            ..AiConfig::default()
+        };
+        let loader = PromptLoader {
+            guidelines: PromptLoader::load_guidelines(&config, dir.path()),
+            ..create_default_loader()
+        };
+        let context = create_test_context();
+        let review = ReviewerOutput {
+            action: ReviewAction::RequestChanges,
+            summary: "Fix issues".to_string(),
+            comments: vec![],
+            blocking_issues: vec![],
+        };
+
+        for prompt in [
+            loader.load_reviewer_prompt(&context, 1),
+            loader.load_reviewee_prompt(&context, &review, 1),
+            loader.load_rereview_prompt(&context, 2, "Fixed", "+new code"),
+        ] {
+            assert!(prompt.contains("## Coding Guidelines"));
+            assert!(prompt.contains("- No unwrap in library code"));
+            assert!(!prompt.contains("{{guidelines}}"));
         }
+
+        // Without guidelines the section is left out
+        let prompt = create_default_loader().load_reviewer_prompt(&context, 1);
+        assert!(!prompt.contains("## Coding Guidelines"));
+        assert!(!prompt.contains("{{guidelines}}"));
+    }
+
+    #[test]
+    fn test_guidelines_path_defaults_to_local_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AiConfig::default();
+        assert_eq!(PromptLoader::guidelines_path(&config, dir.path()), None);
+
+        std::fs::create_dir_all(dir.path().join(".octorus")).unwrap();
+        std::fs::write(dir.path().join(LOCAL_GUIDELINES_FILE), "Be kind").unwrap();
+        assert_eq!(
+            PromptLoader::guidelines_path(&config, dir.path()),
+            Some(dir.path().join(LOCAL_GUIDELINES_FILE))
+        );
+        assert_eq!(
+            PromptLoader::load_guidelines(&config, dir.path()).as_deref(),
+            Some("Be kind")
+        );
+    }
+
+    #[test]
+    fn test_custom_template_gets_pr_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("reviewee.md"),
+            "{{repo}}#{{pr_number}} {{base_branch}}..{{head_sha}}: {{pr_body}}",
+        )
+        .unwrap();
+        let loader = PromptLoader {
+            local_prompts_dir: Some(dir.path().to_path_buf()),
+            ..create_default_loader()
+        };
+        let review = ReviewerOutput {
+            action: ReviewAction::Comment,
+            summary: String::new(),
+            comments: vec![],
+            blocking_issues: vec![],
+        };
+
+        let prompt = loader.load_reviewee_prompt(&create_test_context(), &review, 1);
+        assert_eq!(
+            prompt,
+            "owner/repo#123 main..abc123: This adds a new feature"
+        );
     }
 
     #[test]
@@ -539,6 +727,7 @@ mod tests {
             prompt_dir: None,
             local_prompts_dir: Some(local_dir.clone()),
             global_prompts_dir: None,
+            guidelines: None,
         };
         let source = loader.resolve_source("reviewer.md");
         assert_eq!(source, PromptSource::Local(local_dir.join("reviewer.md")));
@@ -555,6 +744,7 @@ mod tests {
             prompt_dir: Some(prompt_dir.clone()),
             local_prompts_dir: None,
             global_prompts_dir: None,
+            guidelines: None,
         };
         let source = loader.resolve_source("reviewer.md");
         assert_eq!(
@@ -574,6 +764,7 @@ mod tests {
             prompt_dir: None,
             local_prompts_dir: None,
             global_prompts_dir: Some(global_dir.clone()),
+            guidelines: None,
         };
         let source = loader.resolve_source("reviewer.md");
         assert_eq!(
@@ -72,6 +72,23 @@ If you're completely uncertain, make minimal changes and document your assumptio
     )
 }
 
+/// Prompt for an answer that does not match the output schema
+pub fn build_output_repair_prompt(problems: &[String]) -> String {
+    let problems: Vec<String> = problems.iter().map(|p| format!("- {}", p)).collect();
+    format!(
+        r#"Your previous answer could not be used because it does not match the required output format:
+
+## Problems
+{problems}
+
+## Your Task
+
+Reply again with ONLY a single JSON object that matches the output schema.
+Keep the content of your previous answer and fix only its format. Do not redo the work or make any changes."#,
+        problems = problems.join("\n"),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +162,24 @@ mod tests {
         If you're completely uncertain, make minimal changes and document your assumptions in the summary.
         "#);
     }
+
+    #[test]
+    fn test_build_output_repair_prompt() {
+        let result = build_output_repair_prompt(&[
+            "$: missing required field \"summary\"".to_string(),
+            "$.comments[0].line: expected integer, got string".to_string(),
+        ]);
+        assert_snapshot!(result, @r#"
+        Your previous answer could not be used because it does not match the required output format:
+
+        ## Problems
+        - $: missing required field "summary"
+        - $.comments[0].line: expected integer, got string
+
+        ## Your Task
+
+        Reply again with ONLY a single JSON object that matches the output schema.
+        Keep the content of your previous answer and fix only its format. Do not redo the work or make any changes.
+        "#);
+    }
 }
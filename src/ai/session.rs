@@ -1,9 +1,12 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::fs;
-use std::path::PathBuf;
+use std::fs::{self, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
 use xdg::BaseDirectories;
 
+use super::adapter::TokenUsage;
+use super::orchestrator::RallyEvent;
 use super::{RallyState, RevieweeOutput, ReviewerOutput};
 use crate::cache::sanitize_repo_name;
 
@@ -15,6 +18,18 @@ pub struct RallySession {
     pub state: RallyState,
     pub started_at: String,
     pub updated_at: String,
+    /// Agent session of the reviewer, restored when the rally is resumed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewer_session_id: Option<String>,
+    /// Agent session of the reviewee, restored when the rally is resumed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reviewee_session_id: Option<String>,
+    /// Files a scoped rally is limited to (empty: the whole PR)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub scope: Vec<String>,
+    /// Tokens used so far, so a resumed rally keeps counting toward the budget
+    #[serde(default, skip_serializing_if = "TokenUsage::is_empty")]
+    pub usage: TokenUsage,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,7 +45,7 @@ pub enum HistoryEntryType {
     Fix(RevieweeOutput),
 }
 
-fn rally_dir(repo: &str, pr_number: u32) -> Result<PathBuf> {
+pub(crate) fn rally_dir(repo: &str, pr_number: u32) -> Result<PathBuf> {
     let safe_repo = sanitize_repo_name(repo)?;
     let dir = BaseDirectories::with_prefix("octorus")
         .map(|dirs| {
@@ -52,8 +67,11 @@ pub fn history_dir(repo: &str, pr_number: u32) -> Result<PathBuf> {
     Ok(rally_dir(repo, pr_number)?.join("history"))
 }
 
-// For --resume-rally feature (not yet implemented)
-#[allow(dead_code)]
+/// Event log of the current rally (one JSON event per line), replayed into the view on resume
+pub fn events_path(repo: &str, pr_number: u32) -> Result<PathBuf> {
+    Ok(rally_dir(repo, pr_number)?.join("events.jsonl"))
+}
+
 pub fn read_session(repo: &str, pr_number: u32) -> Result<Option<RallySession>> {
     let path = session_path(repo, pr_number)?;
     if !path.exists() {
@@ -113,8 +131,52 @@ pub fn write_history_entry(
     Ok(())
 }
 
-// For --resume-rally feature (not yet implemented)
-#[allow(dead_code)]
+pub fn append_event(path: &Path, event: &RallyEvent) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).context("Failed to create rally directory")?;
+    }
+    let mut line = serde_json::to_string(event).context("Failed to serialize rally event")?;
+    line.push('\n');
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context("Failed to open rally event log")?;
+    file.write_all(line.as_bytes())
+        .context("Failed to append rally event")
+}
+
+/// Read the event log, skipping lines that cannot be parsed (e.g. a write cut short)
+pub fn read_events(path: &Path) -> Vec<RallyEvent> {
+    let Ok(file) = fs::File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<RallyEvent>(&line).ok())
+        .collect()
+}
+
+/// Remove the session, history and event log of the previous rally before a new one starts.
+/// Exported transcripts in the same directory are kept.
+pub fn clear_rally_state(repo: &str, pr_number: u32) -> Result<()> {
+    for path in [
+        session_path(repo, pr_number)?,
+        events_path(repo, pr_number)?,
+    ] {
+        if path.exists() {
+            fs::remove_file(&path)
+                .with_context(|| format!("Failed to remove {}", path.display()))?;
+        }
+    }
+    let history = history_dir(repo, pr_number)?;
+    if history.exists() {
+        fs::remove_dir_all(&history).context("Failed to remove rally history")?;
+    }
+    Ok(())
+}
+
 pub fn read_history(repo: &str, pr_number: u32) -> Result<Vec<RallyHistoryEntry>> {
     let dir = history_dir(repo, pr_number)?;
     if !dir.exists() {
@@ -137,6 +199,38 @@ pub fn read_history(repo: &str, pr_number: u32) -> Result<Vec<RallyHistoryEntry>
     Ok(entries)
 }
 
+/// Where a resumed rally picks up
+#[derive(Debug, Default)]
+pub struct ResumePoint {
+    /// Last iteration whose fix was recorded (0: start from the first review)
+    pub iteration: u32,
+    pub last_review: Option<ReviewerOutput>,
+    pub last_fix: Option<RevieweeOutput>,
+}
+
+/// An iteration interrupted before its fix was recorded is run again from the review.
+pub fn resume_point(history: &[RallyHistoryEntry]) -> ResumePoint {
+    let Some(iteration) = history
+        .iter()
+        .filter(|e| matches!(e.entry_type, HistoryEntryType::Fix(_)))
+        .map(|e| e.iteration)
+        .max()
+    else {
+        return ResumePoint::default();
+    };
+    let mut point = ResumePoint {
+        iteration,
+        ..Default::default()
+    };
+    for entry in history.iter().filter(|e| e.iteration == iteration) {
+        match &entry.entry_type {
+            HistoryEntryType::Review(review) => point.last_review = Some(review.clone()),
+            HistoryEntryType::Fix(fix) => point.last_fix = Some(fix.clone()),
+        }
+    }
+    point
+}
+
 // For session cleanup after rally completion
 #[allow(dead_code)]
 pub fn cleanup_session(repo: &str, pr_number: u32) -> Result<()> {
@@ -161,9 +255,18 @@ impl RallySession {
             state: RallyState::Initializing,
             started_at: now.clone(),
             updated_at: now,
+            reviewer_session_id: None,
+            reviewee_session_id: None,
+            scope: Vec::new(),
+            usage: TokenUsage::default(),
         }
     }
 
+    /// A rally that stopped before it finished (aborted, failed or interrupted by closing octorus)
+    pub fn is_resumable(&self) -> bool {
+        self.iteration > 0 && self.state != RallyState::Completed
+    }
+
     pub fn update_state(&mut self, state: RallyState) {
         self.state = state;
         self.updated_at = chrono_now();
@@ -231,6 +334,79 @@ mod tests {
         assert_eq!(session.iteration, 2);
     }
 
+    #[test]
+    fn test_rally_session_without_resume_fields() {
+        // Sessions written before resume support still load
+        let session: RallySession = serde_json::from_str(
+            r#"{"repo":"owner/repo","pr_number":1,"iteration":3,"state":"RevieweeFix","started_at":"t","updated_at":"t"}"#,
+        )
+        .unwrap();
+        assert!(session.is_resumable());
+        assert!(session.reviewer_session_id.is_none());
+        assert!(session.scope.is_empty());
+        assert!(session.usage.is_empty());
+
+        let mut finished = session.clone();
+        finished.update_state(RallyState::Completed);
+        assert!(!finished.is_resumable());
+        assert!(!RallySession::new("owner/repo", 1).is_resumable());
+    }
+
+    #[test]
+    fn test_event_log_round_trip_skips_broken_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("rally").join("events.jsonl");
+        append_event(&path, &RallyEvent::IterationStarted(1)).unwrap();
+        append_event(&path, &RallyEvent::Log("hello".to_string())).unwrap();
+        let mut file = OpenOptions::new().append(true).open(&path).unwrap();
+        file.write_all(b"{\"Log\": \"cut sh").unwrap();
+
+        let events = read_events(&path);
+        assert_eq!(events.len(), 2);
+        assert!(matches!(events[0], RallyEvent::IterationStarted(1)));
+        assert!(matches!(events[1], RallyEvent::Log(ref msg) if msg == "hello"));
+        assert!(read_events(&dir.path().join("missing.jsonl")).is_empty());
+    }
+
+    #[test]
+    fn test_resume_point() {
+        let review = |summary: &str| ReviewerOutput {
+            action: ReviewAction::RequestChanges,
+            summary: summary.to_string(),
+            comments: vec![],
+            blocking_issues: vec![],
+        };
+        let fix = |summary: &str| RevieweeOutput {
+            status: RevieweeStatus::Completed,
+            summary: summary.to_string(),
+            files_modified: vec![],
+            question: None,
+            permission_request: None,
+            error_details: None,
+        };
+        let entry = |iteration, entry_type| RallyHistoryEntry {
+            iteration,
+            entry_type,
+            timestamp: String::new(),
+        };
+
+        assert_eq!(resume_point(&[]).iteration, 0);
+        // Interrupted during the first fix: start over from the first review
+        let point = resume_point(&[entry(1, HistoryEntryType::Review(review("r1")))]);
+        assert_eq!(point.iteration, 0);
+        assert!(point.last_review.is_none());
+
+        // Interrupted during the second fix: iteration 2 runs again
+        let point = resume_point(&[
+            entry(1, HistoryEntryType::Review(review("r1"))),
+            entry(1, HistoryEntryType::Fix(fix("f1"))),
+            entry(2, HistoryEntryType::Review(review("r2"))),
+        ]);
+        assert_eq!(point.iteration, 1);
+        assert_eq!(point.last_review.unwrap().summary, "r1");
+        assert_eq!(point.last_fix.unwrap().summary, "f1");
+    }
+
     #[test]
     fn test_history_entry_review_serialization() {
         let entry = RallyHistoryEntry {
@@ -243,6 +419,7 @@ mod tests {
                     line: 10,
                     body: "Fix this".to_string(),
                     severity: CommentSeverity::Major,
+                    sources: Vec::new(),
                 }],
                 blocking_issues: vec!["Error handling".to_string()],
             }),
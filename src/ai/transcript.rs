@@ -0,0 +1,394 @@
+//! Rally transcripts: the event stream of a rally saved as JSON so it can be
+//! shared and replayed in the AI Rally view without re-running the agents,
+//! or rendered as Markdown for archiving or posting on the PR.
+
+use anyhow::{bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::adapter::{CommentSeverity, ReviewAction, RevieweeOutput, ReviewerOutput, TokenUsage};
+use super::orchestrator::RallyEvent;
+use super::session::rally_dir;
+
+/// Current transcript format version
+pub const TRANSCRIPT_VERSION: u32 = 1;
+
+/// Tool results longer than this are cut in the Markdown transcript
+const MAX_TOOL_RESULT_CHARS: usize = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transcript {
+    pub version: u32,
+    pub repo: String,
+    pub pr_number: u32,
+    pub exported_at: String,
+    pub events: Vec<RallyEvent>,
+}
+
+/// Accepts either a full transcript or a bare array of events
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum TranscriptFile {
+    Full(Transcript),
+    Events(Vec<RallyEvent>),
+}
+
+impl Transcript {
+    pub fn new(repo: &str, pr_number: u32, events: Vec<RallyEvent>) -> Self {
+        Self {
+            version: TRANSCRIPT_VERSION,
+            repo: repo.to_string(),
+            pr_number,
+            exported_at: chrono::Utc::now().to_rfc3339(),
+            events,
+        }
+    }
+
+    pub fn from_json(content: &str) -> Result<Self> {
+        let transcript = match serde_json::from_str::<TranscriptFile>(content)
+            .context("Failed to parse rally transcript")?
+        {
+            TranscriptFile::Full(t) => t,
+            TranscriptFile::Events(events) => Self {
+                version: TRANSCRIPT_VERSION,
+                repo: String::new(),
+                pr_number: 0,
+                exported_at: String::new(),
+                events,
+            },
+        };
+        if transcript.version > TRANSCRIPT_VERSION {
+            bail!(
+                "Unsupported transcript version {} (this build reads up to {})",
+                transcript.version,
+                TRANSCRIPT_VERSION
+            );
+        }
+        Ok(transcript)
+    }
+
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read transcript {}", path.display()))?;
+        Self::from_json(&content)
+    }
+
+    /// Write the transcript next to the rally session data and return its path
+    pub fn export(&self) -> Result<PathBuf> {
+        let content =
+            serde_json::to_string_pretty(self).context("Failed to serialize transcript")?;
+        self.write_to_rally_dir("json", &content)
+    }
+
+    /// Write the Markdown transcript next to the rally session data and return its path
+    pub fn export_markdown(&self) -> Result<PathBuf> {
+        self.write_to_rally_dir("md", &self.to_markdown())
+    }
+
+    fn write_to_rally_dir(&self, extension: &str, content: &str) -> Result<PathBuf> {
+        let dir = rally_dir(&self.repo, self.pr_number)?;
+        fs::create_dir_all(&dir).context("Failed to create rally directory")?;
+        let path = dir.join(format!(
+            "transcript-{}.{}",
+            chrono::Utc::now().format("%Y%m%d%H%M%S"),
+            extension
+        ));
+        fs::write(&path, content).context("Failed to write transcript")?;
+        Ok(path)
+    }
+
+    /// Render the rally as Markdown: each iteration's review and fix, the decisions
+    /// asked of the user, and the agents' tool calls folded into `<details>` blocks.
+    /// Thinking and streamed text are left out.
+    pub fn to_markdown(&self) -> String {
+        let mut out = String::from("# AI Rally");
+        if !self.repo.is_empty() {
+            if self.pr_number > 0 {
+                out.push_str(&format!(": {}#{}", self.repo, self.pr_number));
+            } else {
+                out.push_str(&format!(": {} (local)", self.repo));
+            }
+        }
+        out.push('\n');
+        if !self.exported_at.is_empty() {
+            out.push_str(&format!("\nExported at {}\n", self.exported_at));
+        }
+
+        let mut activity: Vec<String> = Vec::new();
+        let mut usage = TokenUsage::default();
+        for event in &self.events {
+            match event {
+                RallyEvent::IterationStarted(iteration) => {
+                    flush_activity(&mut out, &mut activity);
+                    out.push_str(&format!("\n## Iteration {}\n", iteration));
+                }
+                RallyEvent::ReviewCompleted(review) => {
+                    flush_activity(&mut out, &mut activity);
+                    push_review(&mut out, review);
+                }
+                RallyEvent::FixCompleted(fix) => {
+                    flush_activity(&mut out, &mut activity);
+                    push_fix(&mut out, fix);
+                }
+                RallyEvent::ClarificationNeeded(question) => {
+                    flush_activity(&mut out, &mut activity);
+                    out.push_str(&format!(
+                        "\n**Clarification needed:**\n\n{}\n",
+                        quote(question)
+                    ));
+                }
+                RallyEvent::PermissionNeeded(action, reason) => {
+                    flush_activity(&mut out, &mut activity);
+                    out.push_str(&format!(
+                        "\n**Permission requested:** `{}`\n\n{}\n",
+                        action,
+                        quote(reason)
+                    ));
+                }
+                RallyEvent::Approved(summary) => {
+                    flush_activity(&mut out, &mut activity);
+                    out.push_str(&format!("\n## Approved\n\n{}\n", summary.trim()));
+                }
+                RallyEvent::Error(error) => {
+                    flush_activity(&mut out, &mut activity);
+                    out.push_str(&format!("\n**Error:** {}\n", one_line(error)));
+                }
+                RallyEvent::Log(message) => activity.push(format!("- {}", one_line(message))),
+                RallyEvent::AgentToolUse(name, input) => {
+                    activity.push(format!("- `{}` {}", name, one_line(input)))
+                }
+                RallyEvent::AgentToolResult(name, result) => {
+                    let mut result = one_line(result);
+                    if result.chars().count() > MAX_TOOL_RESULT_CHARS {
+                        result = result.chars().take(MAX_TOOL_RESULT_CHARS).collect();
+                        result.push_str("...");
+                    }
+                    activity.push(format!("  - `{}` → {}", name, result))
+                }
+                RallyEvent::UsageRecorded(recorded) => usage.add(recorded),
+                RallyEvent::StateChanged(_)
+                | RallyEvent::ReviewPostConfirmNeeded(_)
+                | RallyEvent::FixPostConfirmNeeded(_)
+                | RallyEvent::AgentThinking(_)
+                | RallyEvent::AgentText(_) => {}
+            }
+        }
+        flush_activity(&mut out, &mut activity);
+        if !usage.is_empty() {
+            out.push_str(&format!("\n---\n\nUsage: {}\n", usage));
+        }
+        out
+    }
+}
+
+/// Fold the tool calls and log lines collected since the last section
+fn flush_activity(out: &mut String, activity: &mut Vec<String>) {
+    if activity.is_empty() {
+        return;
+    }
+    out.push_str(&format!(
+        "\n<details>\n<summary>Agent activity ({} entries)</summary>\n\n{}\n\n</details>\n",
+        activity.len(),
+        activity.join("\n")
+    ));
+    activity.clear();
+}
+
+fn push_review(out: &mut String, review: &ReviewerOutput) {
+    let action = match review.action {
+        ReviewAction::Approve => "approve",
+        ReviewAction::RequestChanges => "request changes",
+        ReviewAction::Comment => "comment",
+    };
+    out.push_str(&format!(
+        "\n### Review ({})\n\n{}\n",
+        action,
+        review.summary.trim()
+    ));
+    if !review.blocking_issues.is_empty() {
+        out.push_str("\n**Blocking issues**\n\n");
+        for issue in &review.blocking_issues {
+            out.push_str(&format!("- {}\n", indent(issue)));
+        }
+    }
+    if !review.comments.is_empty() {
+        out.push_str("\n**Comments**\n\n");
+        for comment in &review.comments {
+            let severity = match comment.severity {
+                CommentSeverity::Critical => "critical",
+                CommentSeverity::Major => "major",
+                CommentSeverity::Minor => "minor",
+                CommentSeverity::Suggestion => "suggestion",
+            };
+            let sources = if comment.sources.is_empty() {
+                String::new()
+            } else {
+                format!(" [{}]", comment.sources.join(", "))
+            };
+            out.push_str(&format!(
+                "- `{}:{}` ({}){}: {}\n",
+                comment.path,
+                comment.line,
+                severity,
+                sources,
+                indent(&comment.body)
+            ));
+        }
+    }
+}
+
+fn push_fix(out: &mut String, fix: &RevieweeOutput) {
+    let status = serde_json::to_value(fix.status)
+        .ok()
+        .and_then(|value| value.as_str().map(|s| s.replace('_', " ")))
+        .unwrap_or_default();
+    out.push_str(&format!(
+        "\n### Fix ({})\n\n{}\n",
+        status,
+        fix.summary.trim()
+    ));
+    if !fix.files_modified.is_empty() {
+        out.push_str("\n**Files modified**\n\n");
+        for file in &fix.files_modified {
+            out.push_str(&format!("- `{}`\n", file));
+        }
+    }
+    if let Some(ref details) = fix.error_details {
+        out.push_str(&format!("\n**Error:** {}\n", one_line(details)));
+    }
+}
+
+/// Markdown blockquote of `text`
+fn quote(text: &str) -> String {
+    text.trim()
+        .lines()
+        .map(|line| format!("> {}", line).trim_end().to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Indent the continuation lines of a list item
+fn indent(text: &str) -> String {
+    text.trim().replace('\n', "\n  ")
+}
+
+fn one_line(text: &str) -> String {
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ai::{RallyState, ReviewAction, ReviewerOutput};
+
+    fn sample_events() -> Vec<RallyEvent> {
+        vec![
+            RallyEvent::StateChanged(RallyState::ReviewerReviewing),
+            RallyEvent::IterationStarted(1),
+            RallyEvent::AgentToolUse("Read".to_string(), "src/lib.rs".to_string()),
+            RallyEvent::ReviewCompleted(ReviewerOutput {
+                action: ReviewAction::Approve,
+                summary: "LGTM".to_string(),
+                comments: Vec::new(),
+                blocking_issues: Vec::new(),
+            }),
+            RallyEvent::StateChanged(RallyState::Completed),
+        ]
+    }
+
+    #[test]
+    fn test_transcript_roundtrip() {
+        let transcript = Transcript::new("owner/repo", 42, sample_events());
+        let json = serde_json::to_string(&transcript).unwrap();
+        let loaded = Transcript::from_json(&json).unwrap();
+
+        assert_eq!(loaded.repo, "owner/repo");
+        assert_eq!(loaded.pr_number, 42);
+        assert_eq!(loaded.events.len(), 5);
+        assert!(matches!(
+            loaded.events[3],
+            RallyEvent::ReviewCompleted(ReviewerOutput {
+                action: ReviewAction::Approve,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_transcript_to_markdown() {
+        let mut events = sample_events();
+        events.insert(
+            3,
+            RallyEvent::AgentToolResult("Read".to_string(), "fn main() {\n}".to_string()),
+        );
+        events.extend([
+            RallyEvent::IterationStarted(2),
+            RallyEvent::ReviewCompleted(ReviewerOutput {
+                action: ReviewAction::RequestChanges,
+                summary: "One problem".to_string(),
+                comments: vec![crate::ai::adapter::ReviewComment {
+                    path: "src/lib.rs".to_string(),
+                    line: 3,
+                    body: "Handle the error\ninstead of unwrapping".to_string(),
+                    severity: CommentSeverity::Major,
+                    sources: Vec::new(),
+                }],
+                blocking_issues: vec!["unwrap on user input".to_string()],
+            }),
+            RallyEvent::PermissionNeeded("cargo test".to_string(), "Run the tests".to_string()),
+            RallyEvent::FixCompleted(RevieweeOutput {
+                status: crate::ai::RevieweeStatus::NeedsPermission,
+                summary: "Replaced the unwrap".to_string(),
+                files_modified: vec!["src/lib.rs".to_string()],
+                question: None,
+                permission_request: None,
+                error_details: None,
+            }),
+            RallyEvent::UsageRecorded(TokenUsage {
+                input_tokens: 1_000,
+                output_tokens: 500,
+                cost_usd: None,
+            }),
+        ]);
+        let mut transcript = Transcript::new("owner/repo", 42, events);
+        transcript.exported_at = "2026-01-01T00:00:00+00:00".to_string();
+
+        assert_eq!(
+            transcript.to_markdown(),
+            "# AI Rally: owner/repo#42\n\
+\nExported at 2026-01-01T00:00:00+00:00\n\
+\n## Iteration 1\n\
+\n<details>\n<summary>Agent activity (2 entries)</summary>\n\n\
+- `Read` src/lib.rs\n  - `Read` → fn main() { }\n\n</details>\n\
+\n### Review (approve)\n\nLGTM\n\
+\n## Iteration 2\n\
+\n### Review (request changes)\n\nOne problem\n\
+\n**Blocking issues**\n\n- unwrap on user input\n\
+\n**Comments**\n\n- `src/lib.rs:3` (major): Handle the error\n  instead of unwrapping\n\
+\n**Permission requested:** `cargo test`\n\n> Run the tests\n\
+\n### Fix (needs permission)\n\nReplaced the unwrap\n\
+\n**Files modified**\n\n- `src/lib.rs`\n\
+\n---\n\nUsage: 1.5k tokens\n"
+        );
+    }
+
+    #[test]
+    fn test_transcript_accepts_bare_event_list() {
+        let json = r#"[{"IterationStarted": 2}, {"Log": "hello"}]"#;
+        let loaded = Transcript::from_json(json).unwrap();
+        assert_eq!(loaded.events.len(), 2);
+        assert!(matches!(loaded.events[0], RallyEvent::IterationStarted(2)));
+    }
+
+    #[test]
+    fn test_transcript_rejects_newer_version_and_garbage() {
+        let json =
+            r#"{"version": 99, "repo": "a/b", "pr_number": 1, "exported_at": "", "events": []}"#;
+        assert!(Transcript::from_json(json)
+            .unwrap_err()
+            .to_string()
+            .contains("Unsupported transcript version"));
+        assert!(Transcript::from_json("not json").is_err());
+    }
+}
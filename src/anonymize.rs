@@ -0,0 +1,236 @@
+//! 画面録画用の匿名化（`--anonymize`）
+//!
+//! 実際の PR を映したデモを録画しても社内の情報が漏れないよう、描画時にユーザー名を
+//! `user1` のような仮名に置き換える。`--anonymize-repo` を付けるとリポジトリ名と
+//! オーナー（組織）名も伏せる。保持しているデータ自体は書き換えないので、
+//! 投稿するコメントや API 呼び出しには影響しない。
+
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+/// 伏せたオーナー名の代わりに表示する名前
+const HIDDEN_OWNER: &str = "acme";
+/// 伏せたリポジトリ名の代わりに表示する名前
+const HIDDEN_NAME: &str = "project";
+
+/// 描画時の匿名化（App が保持する。既定では何もしない）
+#[derive(Debug, Clone, Default)]
+pub struct Anonymizer {
+    enabled: bool,
+    /// リポジトリ名も伏せるときの (owner, name)
+    hidden_repo: Option<(String, String)>,
+    /// ログイン名（小文字）→ 仮名。セッション中は同じ人に同じ仮名を使う
+    aliases: RefCell<HashMap<String, String>>,
+}
+
+impl Anonymizer {
+    /// `hide_repo` が true なら `owner/name` 形式の `repo` も伏せる
+    pub fn new(repo: &str, hide_repo: bool) -> Self {
+        let hidden_repo = repo
+            .split_once('/')
+            .filter(|_| hide_repo)
+            .map(|(owner, name)| (owner.to_string(), name.to_string()));
+        Self {
+            enabled: true,
+            hidden_repo,
+            aliases: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// 表示中のリポジトリを切り替えたときに、伏せる名前を差し替える。仮名はそのまま引き継ぐ
+    pub fn set_repo(&mut self, repo: &str) {
+        if self.hidden_repo.is_some() {
+            if let Some((owner, name)) = repo.split_once('/') {
+                self.hidden_repo = Some((owner.to_string(), name.to_string()));
+            }
+        }
+    }
+
+    /// 匿名化しているか（実名や所属など、仮名に置き換えられない情報を伏せるのに使う）
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// ユーザー（`org/team` 形式ならチーム）の表示名
+    pub fn user<'a>(&self, login: &'a str) -> Cow<'a, str> {
+        if !self.enabled {
+            return Cow::Borrowed(login);
+        }
+        let mut aliases = self.aliases.borrow_mut();
+        let prefix = if login.contains('/') { "team" } else { "user" };
+        let count = aliases.values().filter(|a| a.starts_with(prefix)).count();
+        let alias = aliases
+            .entry(login.to_ascii_lowercase())
+            .or_insert_with(|| format!("{}{}", prefix, count + 1));
+        Cow::Owned(alias.clone())
+    }
+
+    /// リポジトリの表示名
+    pub fn repo<'a>(&self, repo: &'a str) -> Cow<'a, str> {
+        match self.hidden_repo {
+            Some(_) if self.enabled => Cow::Owned(format!("{}/{}", HIDDEN_OWNER, HIDDEN_NAME)),
+            _ => Cow::Borrowed(repo),
+        }
+    }
+
+    /// コメント本文などの自由記述。`@mention` を仮名に置き換え、
+    /// リポジトリを伏せるときはオーナー名・リポジトリ名も置き換える
+    pub fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if !self.enabled {
+            return Cow::Borrowed(text);
+        }
+        let mut result = self.replace_mentions(text);
+        if let Some((ref owner, ref name)) = self.hidden_repo {
+            let full = format!("{}/{}", owner, name);
+            let hidden = format!("{}/{}", HIDDEN_OWNER, HIDDEN_NAME);
+            result = replace_word(&result, &full, &hidden);
+            result = replace_word(&result, owner, HIDDEN_OWNER);
+            result = replace_word(&result, name, HIDDEN_NAME);
+        }
+        if result == text {
+            Cow::Borrowed(text)
+        } else {
+            Cow::Owned(result)
+        }
+    }
+
+    fn replace_mentions(&self, text: &str) -> String {
+        let mut result = String::with_capacity(text.len());
+        let mut rest = text;
+        let mut prev: Option<char> = None;
+        while let Some(at) = rest.find('@') {
+            let before = &rest[..at];
+            result.push_str(before);
+            prev = before.chars().next_back().or(prev);
+            let after = &rest[at + 1..];
+            let len = mention_len(after);
+            // メールアドレス（`a@example.com`）の `@` はメンションではない
+            if len == 0 || prev.is_some_and(is_word_char) {
+                result.push('@');
+                rest = after;
+                prev = Some('@');
+                continue;
+            }
+            result.push('@');
+            result.push_str(&self.user(&after[..len]));
+            rest = &after[len..];
+            prev = after[..len].chars().next_back();
+        }
+        result.push_str(rest);
+        result
+    }
+}
+
+fn is_word_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_'
+}
+
+/// `@` の直後から続くログイン名（`org/team` を含む）のバイト長
+fn mention_len(s: &str) -> usize {
+    let login = s
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '-'))
+        .unwrap_or(s.len());
+    if login == 0 {
+        return 0;
+    }
+    // チームメンション `@org/team`
+    match s[login..].strip_prefix('/') {
+        Some(team) => {
+            let slug = team.find(|c: char| !is_word_char(c)).unwrap_or(team.len());
+            if slug == 0 {
+                login
+            } else {
+                login + 1 + slug
+            }
+        }
+        None => login,
+    }
+}
+
+/// 単語として現れる `word` を大文字小文字を区別せずに置き換える
+fn replace_word(text: &str, word: &str, replacement: &str) -> String {
+    if word.is_empty() {
+        return text.to_string();
+    }
+    // ASCII の小文字化ではバイト位置が変わらない
+    let lower = text.to_ascii_lowercase();
+    let needle = word.to_ascii_lowercase();
+    let mut result = String::with_capacity(text.len());
+    let mut last = 0;
+    for (start, _) in lower.match_indices(&needle) {
+        if start < last {
+            continue;
+        }
+        let end = start + needle.len();
+        let bounded_before = !text[..start].chars().next_back().is_some_and(is_word_char);
+        let bounded_after = !text[end..].chars().next().is_some_and(is_word_char);
+        if bounded_before && bounded_after {
+            result.push_str(&text[last..start]);
+            result.push_str(replacement);
+            last = end;
+        }
+    }
+    result.push_str(&text[last..]);
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disabled_keeps_everything() {
+        let anonymizer = Anonymizer::default();
+        assert_eq!(anonymizer.user("alice"), "alice");
+        assert_eq!(anonymizer.repo("owner/repo"), "owner/repo");
+        assert_eq!(anonymizer.text("thanks @alice"), "thanks @alice");
+    }
+
+    #[test]
+    fn test_user_aliases_are_stable() {
+        let anonymizer = Anonymizer::new("owner/repo", false);
+        assert_eq!(anonymizer.user("alice"), "user1");
+        assert_eq!(anonymizer.user("bob"), "user2");
+        assert_eq!(anonymizer.user("Alice"), "user1");
+        assert_eq!(anonymizer.user("acme/backend"), "team1");
+        // リポジトリ名は伏せない
+        assert_eq!(anonymizer.repo("owner/repo"), "owner/repo");
+    }
+
+    #[test]
+    fn test_text_replaces_mentions_but_not_emails() {
+        let anonymizer = Anonymizer::new("owner/repo", false);
+        assert_eq!(anonymizer.user("bob"), "user1");
+        assert_eq!(
+            anonymizer.text("@alice, @bob and @org/reviewers: mail me@example.com"),
+            "@user2, @user1 and @team1: mail me@example.com"
+        );
+        assert_eq!(anonymizer.text("no mentions @ all"), "no mentions @ all");
+    }
+
+    #[test]
+    fn test_hide_repo_replaces_owner_and_name() {
+        let anonymizer = Anonymizer::new("Contoso/billing", true);
+        assert_eq!(anonymizer.repo("Contoso/billing"), "acme/project");
+        assert_eq!(
+            anonymizer
+                .text("See https://github.com/contoso/billing/pull/1 and contoso-tools, billing."),
+            "See https://github.com/acme/project/pull/1 and contoso-tools, project."
+        );
+    }
+
+    #[test]
+    fn test_set_repo_hides_switched_repo() {
+        let mut anonymizer = Anonymizer::new("contoso/billing", true);
+        assert_eq!(anonymizer.user("alice"), "user1");
+        anonymizer.set_repo("contoso/search");
+        assert_eq!(anonymizer.text("search is down"), "project is down");
+        assert_eq!(anonymizer.user("alice"), "user1");
+
+        // リポジトリ名を伏せない設定では何もしない
+        let mut anonymizer = Anonymizer::new("contoso/billing", false);
+        anonymizer.set_repo("contoso/search");
+        assert_eq!(anonymizer.text("search is down"), "search is down");
+    }
+}
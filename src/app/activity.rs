@@ -0,0 +1,33 @@
+use crossterm::event;
+
+use crate::ui::popup::PopupKey;
+
+use super::types::*;
+use super::{App, PopupId};
+
+impl App {
+    /// このセッションの操作履歴パネルを開く（監査ログから読み込み）
+    pub(crate) fn open_session_activity(&mut self) {
+        let entries = crate::audit::read_session();
+        // 最新のエントリが見えるよう末尾へスクロール
+        let scroll = entries.len().saturating_sub(1);
+        self.session_activity = Some(SessionActivityState { entries, scroll });
+        self.push_popup(PopupId::SessionActivity);
+    }
+
+    pub(crate) fn handle_session_activity_input(&mut self, key: event::KeyEvent) {
+        let kb = &self.config.keybindings;
+        let action = PopupKey::from_key(&key, kb);
+        let close =
+            action == PopupKey::Close || self.matches_single_key(&key, &kb.session_activity);
+
+        let Some(panel) = self.session_activity.as_mut() else {
+            return;
+        };
+        if close {
+            self.close_popup(PopupId::SessionActivity);
+        } else if let Some(scroll) = action.navigate(panel.scroll, panel.entries.len()) {
+            panel.scroll = scroll;
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::agenda;
+use crate::audit::{self, AuditAction, AuditEntry};
+use crate::cache;
+use crate::github;
+use crate::ui::popup::PopupKey;
+
+use super::types::InputMode;
+use super::{App, AppState, PopupId};
+
+/// レビューアジェンダのパネル
+#[derive(Debug, Clone)]
+pub struct AgendaPanel {
+    /// 投稿・保存する Markdown
+    pub markdown: String,
+    pub scroll: usize,
+}
+
+impl App {
+    fn set_agenda_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 変更ファイルからレビューアジェンダを作ってパネルで開く
+    pub(crate) fn open_review_agenda(&mut self) {
+        if self.files().is_empty() {
+            return;
+        }
+        let heading = match (self.pr_number, self.pr()) {
+            (Some(number), Some(pr)) => format!("#{} {}", number, pr.title),
+            _ => "local changes".to_string(),
+        };
+        let markdown = agenda::to_markdown(&agenda::build(self.files()), &heading);
+        self.review_agenda = Some(AgendaPanel {
+            markdown,
+            scroll: 0,
+        });
+        self.push_popup(PopupId::ReviewAgenda);
+    }
+
+    /// 保存先（`~/.cache/octorus/agenda/<owner>_<repo>-<PR 番号>.md`）
+    fn agenda_path(&self) -> Result<PathBuf> {
+        let name = match self.pr_number {
+            Some(number) if !self.local_mode => {
+                format!("{}-{}.md", cache::sanitize_repo_name(&self.repo)?, number)
+            }
+            _ => "local.md".to_string(),
+        };
+        Ok(cache::cache_dir().join("agenda").join(name))
+    }
+
+    fn save_review_agenda(&mut self) {
+        let Some(ref panel) = self.review_agenda else {
+            return;
+        };
+        let markdown = panel.markdown.clone();
+        let result = self.agenda_path().and_then(|path| {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)?;
+            }
+            std::fs::write(&path, markdown)?;
+            Ok(path)
+        });
+        match result {
+            Ok(path) => self.set_agenda_message(true, format!("Saved to {}", path.display())),
+            Err(e) => {
+                tracing::warn!(error = %e, "failed to save review agenda");
+                self.set_agenda_message(false, format!("Failed to save agenda: {}", e));
+            }
+        }
+    }
+
+    /// 入力画面でアジェンダを編集してから PR のコメントとして投稿する
+    fn compose_review_agenda(&mut self) {
+        let Some(pr_number) = self.pr_number.filter(|_| !self.local_mode) else {
+            self.set_agenda_message(false, "Posting is only available for PRs");
+            return;
+        };
+        let Some(panel) = self.review_agenda.take() else {
+            return;
+        };
+        self.input_mode = Some(InputMode::ReviewAgenda { pr_number });
+        self.input_text_area.set_content(&panel.markdown);
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+
+    pub(crate) fn submit_review_agenda(&mut self, pr_number: u32, body: String) {
+        let (tx, rx) = mpsc::channel(1);
+        self.agenda_submit_receiver = Some(rx);
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = github::create_discussion_comment(&repo, pr_number, &body).await;
+            audit::record(&AuditEntry::new(
+                &repo,
+                pr_number,
+                AuditAction::ReviewAgenda,
+                None,
+                &result,
+            ));
+            let _ = tx
+                .send((pr_number, result.map(|_| ()).map_err(|e| e.to_string())))
+                .await;
+        });
+    }
+
+    pub(crate) fn poll_agenda_updates(&mut self) {
+        let Some(ref mut rx) = self.agenda_submit_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok((pr_number, result)) => {
+                self.agenda_submit_receiver = None;
+                match result {
+                    Ok(()) => self.set_agenda_message(
+                        true,
+                        format!("Review agenda posted on PR #{}", pr_number),
+                    ),
+                    Err(e) => self.set_agenda_message(false, format!("Failed to post: {}", e)),
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.agenda_submit_receiver = None;
+            }
+        }
+    }
+
+    pub(crate) fn handle_review_agenda_input(&mut self, key: &KeyEvent) {
+        let action = PopupKey::from_key(key, &self.config.keybindings);
+        if action == PopupKey::Close {
+            self.close_popup(PopupId::ReviewAgenda);
+            return;
+        }
+        match key.code {
+            KeyCode::Char('c') => {
+                self.compose_review_agenda();
+                return;
+            }
+            KeyCode::Char('w') => {
+                self.save_review_agenda();
+                return;
+            }
+            _ => {}
+        }
+        let Some(ref mut panel) = self.review_agenda else {
+            return;
+        };
+        if let Some(scroll) = action.navigate(panel.scroll, panel.markdown.lines().count()) {
+            panel.scroll = scroll;
+        }
+    }
+}
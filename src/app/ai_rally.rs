@@ -1,12 +1,14 @@
+use std::time::Instant;
+
 use anyhow::Result;
 use crossterm::event::{self, KeyCode};
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::Stdout;
 use tokio::sync::mpsc;
 
 use crate::ai::orchestrator::{OrchestratorCommand, RallyEvent};
-use crate::ai::{Context, Orchestrator, RallyState};
-use crate::ui;
+use crate::ai::{session, Context, Orchestrator, RallyState};
+use crate::config::KeybindingView;
+use crate::spawn_env::SpawnEnv;
+use crate::ui::TuiTerminal;
 
 use super::types::*;
 use super::{App, AppState};
@@ -15,260 +17,288 @@ impl App {
     pub(crate) async fn handle_ai_rally_input(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
+        let kb = self.config.keybindings.for_view(KeybindingView::AiRally);
+
         // Handle modal state first
-        if let Some(ref mut rally_state) = self.ai_rally_state {
-            if rally_state.showing_log_detail {
-                match key.code {
-                    KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
-                        rally_state.showing_log_detail = false;
-                    }
-                    _ => {}
+        if self
+            .ai_rally_state
+            .as_ref()
+            .is_some_and(|rally_state| rally_state.showing_log_detail)
+        {
+            if key.code == KeyCode::Esc
+                || self.matches_single_key(&key, &kb.open_panel)
+                || self.matches_single_key(&key, &kb.quit)
+            {
+                if let Some(ref mut rally_state) = self.ai_rally_state {
+                    rally_state.showing_log_detail = false;
                 }
+            }
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_time_format) {
+            self.time_format.toggle_style();
+            return Ok(());
+        }
+
+        // Replay: playback controls instead of orchestrator commands
+        if self.is_replaying_rally() {
+            if self.matches_single_key(&key, &kb.quit) || key.code == KeyCode::Esc {
+                self.stop_rally_replay();
+                return Ok(());
+            }
+            if self.matches_single_key(&key, &kb.rally_replay_pause) {
+                self.toggle_rally_replay();
+                return Ok(());
+            }
+            if self.matches_single_key(&key, &kb.move_right) || key.code == KeyCode::Right {
+                self.step_rally_replay();
+                return Ok(());
+            }
+            if [
+                &kb.rally_background,
+                &kb.rally_approve,
+                &kb.rally_deny,
+                &kb.rally_retry,
+            ]
+            .into_iter()
+            .any(|seq| self.matches_single_key(&key, seq))
+            {
                 return Ok(());
             }
         }
 
-        match key.code {
-            KeyCode::Char('b') => {
-                // バックグラウンドで実行を継続したままFileListに戻る
-                // abort()を呼ばない、状態も保持したまま
-                self.state = AppState::FileList;
+        if self.matches_single_key(&key, &kb.rally_export) {
+            self.export_rally_transcript(false);
+        } else if self.matches_single_key(&key, &kb.rally_export_markdown) {
+            self.export_rally_transcript(true);
+        } else if self.matches_single_key(&key, &kb.rally_background) {
+            // バックグラウンドで実行を継続したままFileListに戻る
+            // abort()を呼ばない、状態も保持したまま
+            self.state = AppState::FileList;
+        } else if self.matches_single_key(&key, &kb.quit) || key.code == KeyCode::Esc {
+            // Send abort command to orchestrator if in waiting state
+            if let Some(ref state) = self.ai_rally_state {
+                if matches!(
+                    state.state,
+                    RallyState::WaitingForClarification
+                        | RallyState::WaitingForPermission
+                        | RallyState::WaitingForPostConfirmation
+                ) {
+                    self.send_rally_command(OrchestratorCommand::Abort);
+                }
+            }
+            // Abort the orchestrator task if running
+            if let Some(handle) = self.rally_abort_handle.take() {
+                handle.abort();
             }
-            KeyCode::Char('q') | KeyCode::Esc => {
-                // Send abort command to orchestrator if in waiting state
-                if let Some(ref state) = self.ai_rally_state {
-                    if matches!(
-                        state.state,
-                        RallyState::WaitingForClarification
-                            | RallyState::WaitingForPermission
-                            | RallyState::WaitingForPostConfirmation
-                    ) {
-                        self.send_rally_command(OrchestratorCommand::Abort);
+            // Abort rally and return to file list
+            self.cleanup_rally_state();
+            self.state = AppState::FileList;
+        } else if self.matches_single_key(&key, &kb.rally_approve) {
+            // Grant permission or open clarification editor
+            let current_state = self
+                .ai_rally_state
+                .as_ref()
+                .map(|s| s.state)
+                .unwrap_or(RallyState::Error);
+
+            match current_state {
+                RallyState::WaitingForPermission => {
+                    // Send permission granted
+                    self.send_rally_command(OrchestratorCommand::PermissionResponse(true));
+                    // Clear pending permission and update state to prevent duplicate sends
+                    if let Some(ref mut rally_state) = self.ai_rally_state {
+                        rally_state.pending_permission = None;
+                        rally_state.state = RallyState::RevieweeFix;
+                        rally_state.push_log(LogEntry::new(
+                            LogEventType::Info,
+                            "Permission granted, continuing...".to_string(),
+                        ));
                     }
                 }
-                // Abort the orchestrator task if running
-                if let Some(handle) = self.rally_abort_handle.take() {
-                    handle.abort();
+                RallyState::WaitingForClarification => {
+                    // Get the question for the editor
+                    let question = self
+                        .ai_rally_state
+                        .as_ref()
+                        .and_then(|s| s.pending_question.clone())
+                        .unwrap_or_default();
+
+                    // Open editor synchronously (restore terminal first)
+                    self.open_clarification_editor_sync(&question, terminal)?;
                 }
-                // Abort rally and return to file list
-                self.cleanup_rally_state();
-                self.state = AppState::FileList;
-            }
-            KeyCode::Char('y') => {
-                // Grant permission or open clarification editor
-                let current_state = self
-                    .ai_rally_state
-                    .as_ref()
-                    .map(|s| s.state)
-                    .unwrap_or(RallyState::Error);
-
-                match current_state {
-                    RallyState::WaitingForPermission => {
-                        // Send permission granted
-                        self.send_rally_command(OrchestratorCommand::PermissionResponse(true));
-                        // Clear pending permission and update state to prevent duplicate sends
-                        if let Some(ref mut rally_state) = self.ai_rally_state {
-                            rally_state.pending_permission = None;
-                            rally_state.state = RallyState::RevieweeFix;
-                            rally_state.push_log(LogEntry::new(
-                                LogEventType::Info,
-                                "Permission granted, continuing...".to_string(),
-                            ));
-                        }
-                    }
-                    RallyState::WaitingForClarification => {
-                        // Get the question for the editor
-                        let question = self
-                            .ai_rally_state
-                            .as_ref()
-                            .and_then(|s| s.pending_question.clone())
-                            .unwrap_or_default();
-
-                        // Open editor synchronously (restore terminal first)
-                        self.open_clarification_editor_sync(&question, terminal)?;
+                RallyState::WaitingForPostConfirmation => {
+                    // Approve posting
+                    self.send_rally_command(OrchestratorCommand::PostConfirmResponse(true));
+                    if let Some(ref mut rally_state) = self.ai_rally_state {
+                        rally_state.pending_review_post = None;
+                        rally_state.pending_fix_post = None;
+                        // Transition state immediately to prevent duplicate sends
+                        rally_state.state = RallyState::RevieweeFix;
+                        rally_state.push_log(LogEntry::new(
+                            LogEventType::Info,
+                            "Post approved, posting to PR...".to_string(),
+                        ));
                     }
-                    RallyState::WaitingForPostConfirmation => {
-                        // Approve posting
-                        self.send_rally_command(OrchestratorCommand::PostConfirmResponse(true));
-                        if let Some(ref mut rally_state) = self.ai_rally_state {
-                            rally_state.pending_review_post = None;
-                            rally_state.pending_fix_post = None;
-                            // Transition state immediately to prevent duplicate sends
-                            rally_state.state = RallyState::RevieweeFix;
-                            rally_state.push_log(LogEntry::new(
-                                LogEventType::Info,
-                                "Post approved, posting to PR...".to_string(),
-                            ));
-                        }
-                    }
-                    _ => {}
                 }
+                _ => {}
             }
-            KeyCode::Char('n') => {
-                // Deny permission or skip clarification
-                let current_state = self
-                    .ai_rally_state
-                    .as_ref()
-                    .map(|s| s.state)
-                    .unwrap_or(RallyState::Error);
-
-                match current_state {
-                    RallyState::WaitingForPermission => {
-                        // Send permission denied
-                        self.send_rally_command(OrchestratorCommand::PermissionResponse(false));
-                        // Clear pending permission - state change is delegated to Orchestrator's StateChanged event
-                        if let Some(ref mut rally_state) = self.ai_rally_state {
-                            rally_state.pending_permission = None;
-                            // Do NOT change rally_state.state here - let Orchestrator's StateChanged event handle it
-                            rally_state.push_log(LogEntry::new(
-                                LogEventType::Info,
-                                "Permission denied, continuing without it...".to_string(),
-                            ));
-                        }
+        } else if self.matches_single_key(&key, &kb.rally_deny) {
+            // Deny permission or skip clarification
+            let current_state = self
+                .ai_rally_state
+                .as_ref()
+                .map(|s| s.state)
+                .unwrap_or(RallyState::Error);
+
+            match current_state {
+                RallyState::WaitingForPermission => {
+                    // Send permission denied
+                    self.send_rally_command(OrchestratorCommand::PermissionResponse(false));
+                    // Clear pending permission - state change is delegated to Orchestrator's StateChanged event
+                    if let Some(ref mut rally_state) = self.ai_rally_state {
+                        rally_state.pending_permission = None;
+                        // Do NOT change rally_state.state here - let Orchestrator's StateChanged event handle it
+                        rally_state.push_log(LogEntry::new(
+                            LogEventType::Info,
+                            "Permission denied, continuing without it...".to_string(),
+                        ));
                     }
-                    RallyState::WaitingForClarification => {
-                        // Send skip clarification (continue with best judgment)
-                        self.send_rally_command(OrchestratorCommand::SkipClarification);
-                        // Clear pending question - state change is delegated to Orchestrator's StateChanged event
-                        if let Some(ref mut rally_state) = self.ai_rally_state {
-                            rally_state.pending_question = None;
-                            // Do NOT change rally_state.state here - let Orchestrator's StateChanged event handle it
-                            rally_state.push_log(LogEntry::new(
-                                LogEventType::Info,
-                                "Clarification skipped, continuing with best judgment..."
-                                    .to_string(),
-                            ));
-                        }
+                }
+                RallyState::WaitingForClarification => {
+                    // Send skip clarification (continue with best judgment)
+                    self.send_rally_command(OrchestratorCommand::SkipClarification);
+                    // Clear pending question - state change is delegated to Orchestrator's StateChanged event
+                    if let Some(ref mut rally_state) = self.ai_rally_state {
+                        rally_state.pending_question = None;
+                        // Do NOT change rally_state.state here - let Orchestrator's StateChanged event handle it
+                        rally_state.push_log(LogEntry::new(
+                            LogEventType::Info,
+                            "Clarification skipped, continuing with best judgment...".to_string(),
+                        ));
                     }
-                    RallyState::WaitingForPostConfirmation => {
-                        // Skip posting
-                        self.send_rally_command(OrchestratorCommand::PostConfirmResponse(false));
-                        if let Some(ref mut rally_state) = self.ai_rally_state {
-                            rally_state.pending_review_post = None;
-                            rally_state.pending_fix_post = None;
-                            // Transition state immediately to prevent duplicate sends
-                            rally_state.state = RallyState::RevieweeFix;
-                            rally_state.push_log(LogEntry::new(
-                                LogEventType::Info,
-                                "Post skipped, continuing...".to_string(),
-                            ));
-                        }
+                }
+                RallyState::WaitingForPostConfirmation => {
+                    // Skip posting
+                    self.send_rally_command(OrchestratorCommand::PostConfirmResponse(false));
+                    if let Some(ref mut rally_state) = self.ai_rally_state {
+                        rally_state.pending_review_post = None;
+                        rally_state.pending_fix_post = None;
+                        // Transition state immediately to prevent duplicate sends
+                        rally_state.state = RallyState::RevieweeFix;
+                        rally_state.push_log(LogEntry::new(
+                            LogEventType::Info,
+                            "Post skipped, continuing...".to_string(),
+                        ));
                     }
-                    _ => {}
                 }
+                _ => {}
             }
-            KeyCode::Char('r') => {
-                // Retry on error state
-                if let Some(ref state) = self.ai_rally_state {
-                    if state.state == RallyState::Error {
-                        // Abort current handle if any
-                        if let Some(handle) = self.rally_abort_handle.take() {
-                            handle.abort();
-                        }
-                        // Clear state and restart
-                        self.ai_rally_state = None;
-                        self.rally_event_receiver = None;
-                        self.state = AppState::FileList;
-                        // Restart the rally
-                        self.start_ai_rally();
+        } else if self.matches_single_key(&key, &kb.rally_retry) {
+            // Retry on error state
+            if let Some(ref state) = self.ai_rally_state {
+                if state.state == RallyState::Error {
+                    // Abort current handle if any
+                    if let Some(handle) = self.rally_abort_handle.take() {
+                        handle.abort();
                     }
+                    // Clear state and restart
+                    self.ai_rally_state = None;
+                    self.rally_event_receiver = None;
+                    self.state = AppState::FileList;
+                    // Restart the rally on the same files
+                    let scope = std::mem::take(&mut self.rally_scope);
+                    self.start_ai_rally_with_scope(scope);
                 }
             }
+        } else if self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down {
             // Log selection and scrolling
-            KeyCode::Char('j') | KeyCode::Down => {
-                if let Some(ref mut rally_state) = self.ai_rally_state {
-                    let total_logs = rally_state.logs.len();
-                    if total_logs == 0 {
-                        return Ok(());
-                    }
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                let total_logs = rally_state.logs.len();
+                if total_logs == 0 {
+                    return Ok(());
+                }
 
-                    // Initialize selection if not set
-                    let current = rally_state.selected_log_index.unwrap_or(0);
-                    let new_index = (current + 1).min(total_logs.saturating_sub(1));
-                    rally_state.selected_log_index = Some(new_index);
+                // Initialize selection if not set
+                let current = rally_state.selected_log_index.unwrap_or(0);
+                let new_index = (current + 1).min(total_logs.saturating_sub(1));
+                rally_state.selected_log_index = Some(new_index);
 
-                    // Auto-scroll to keep selection visible
-                    self.adjust_log_scroll_to_selection();
-                }
+                // Auto-scroll to keep selection visible
+                self.adjust_log_scroll_to_selection();
             }
-            KeyCode::Char('k') | KeyCode::Up => {
-                if let Some(ref mut rally_state) = self.ai_rally_state {
-                    let total_logs = rally_state.logs.len();
-                    if total_logs == 0 {
-                        return Ok(());
-                    }
+        } else if self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up {
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                let total_logs = rally_state.logs.len();
+                if total_logs == 0 {
+                    return Ok(());
+                }
 
-                    // Initialize selection if not set (start from last)
-                    let current = rally_state
-                        .selected_log_index
-                        .unwrap_or(total_logs.saturating_sub(1));
-                    let new_index = current.saturating_sub(1);
-                    rally_state.selected_log_index = Some(new_index);
+                // Initialize selection if not set (start from last)
+                let current = rally_state
+                    .selected_log_index
+                    .unwrap_or(total_logs.saturating_sub(1));
+                let new_index = current.saturating_sub(1);
+                rally_state.selected_log_index = Some(new_index);
 
-                    // Auto-scroll to keep selection visible
-                    self.adjust_log_scroll_to_selection();
-                }
+                // Auto-scroll to keep selection visible
+                self.adjust_log_scroll_to_selection();
             }
-            KeyCode::Char('J') => {
-                if let Some(ref mut rally_state) = self.ai_rally_state {
-                    let total_logs = rally_state.logs.len();
-                    if total_logs == 0 {
-                        return Ok(());
-                    }
-
-                    let page_step = rally_state.last_visible_log_height.saturating_sub(1).max(1);
-                    let current = rally_state.selected_log_index.unwrap_or(0);
-                    let new_index = (current + page_step).min(total_logs.saturating_sub(1));
-                    rally_state.selected_log_index = Some(new_index);
-                    self.adjust_log_scroll_to_selection();
+        } else if self.matches_single_key(&key, &kb.rally_log_page_down) {
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                let total_logs = rally_state.logs.len();
+                if total_logs == 0 {
+                    return Ok(());
                 }
-            }
-            KeyCode::Char('K') => {
-                if let Some(ref mut rally_state) = self.ai_rally_state {
-                    let total_logs = rally_state.logs.len();
-                    if total_logs == 0 {
-                        return Ok(());
-                    }
 
-                    let page_step = rally_state.last_visible_log_height.saturating_sub(1).max(1);
-                    let current = rally_state
-                        .selected_log_index
-                        .unwrap_or(total_logs.saturating_sub(1));
-                    let new_index = current.saturating_sub(page_step);
-                    rally_state.selected_log_index = Some(new_index);
-                    self.adjust_log_scroll_to_selection();
+                let page_step = rally_state.last_visible_log_height.saturating_sub(1).max(1);
+                let current = rally_state.selected_log_index.unwrap_or(0);
+                let new_index = (current + page_step).min(total_logs.saturating_sub(1));
+                rally_state.selected_log_index = Some(new_index);
+                self.adjust_log_scroll_to_selection();
+            }
+        } else if self.matches_single_key(&key, &kb.rally_log_page_up) {
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                let total_logs = rally_state.logs.len();
+                if total_logs == 0 {
+                    return Ok(());
                 }
+
+                let page_step = rally_state.last_visible_log_height.saturating_sub(1).max(1);
+                let current = rally_state
+                    .selected_log_index
+                    .unwrap_or(total_logs.saturating_sub(1));
+                let new_index = current.saturating_sub(page_step);
+                rally_state.selected_log_index = Some(new_index);
+                self.adjust_log_scroll_to_selection();
             }
-            KeyCode::Enter => {
-                // Show log detail modal
-                if let Some(ref mut rally_state) = self.ai_rally_state {
-                    if rally_state.selected_log_index.is_some() && !rally_state.logs.is_empty() {
-                        rally_state.showing_log_detail = true;
-                    }
+        } else if self.matches_single_key(&key, &kb.open_panel) {
+            // Show log detail modal
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                if rally_state.selected_log_index.is_some() && !rally_state.logs.is_empty() {
+                    rally_state.showing_log_detail = true;
                 }
             }
-            KeyCode::Char('G') => {
-                // Jump to bottom
-                if let Some(ref mut rally_state) = self.ai_rally_state {
-                    let total_logs = rally_state.logs.len();
-                    if total_logs > 0 {
-                        rally_state.selected_log_index = Some(total_logs.saturating_sub(1));
-                        rally_state.log_scroll_offset = 0; // 0 means auto-scroll to bottom
-                    }
+        } else if self.matches_single_key(&key, &kb.rally_log_bottom) {
+            // Jump to bottom
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                let total_logs = rally_state.logs.len();
+                if total_logs > 0 {
+                    rally_state.selected_log_index = Some(total_logs.saturating_sub(1));
+                    rally_state.log_scroll_offset = 0; // 0 means auto-scroll to bottom
                 }
             }
-            KeyCode::Char('g') => {
-                // Jump to top
-                if let Some(ref mut rally_state) = self.ai_rally_state {
-                    if !rally_state.logs.is_empty() {
-                        rally_state.selected_log_index = Some(0);
-                        rally_state.log_scroll_offset = 1; // 1 is minimum (not 0 which means auto-scroll)
-                    }
+        } else if self.matches_single_key(&key, &kb.rally_log_top) {
+            // Jump to top
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                if !rally_state.logs.is_empty() {
+                    rally_state.selected_log_index = Some(0);
+                    rally_state.log_scroll_offset = 1; // 1 is minimum (not 0 which means auto-scroll)
                 }
             }
-            _ => {}
         }
         Ok(())
     }
@@ -311,6 +341,7 @@ impl App {
     /// Clean up rally state when orchestrator terminates or user aborts
     pub(crate) fn cleanup_rally_state(&mut self) {
         self.ai_rally_state = None;
+        self.rally_scope.clear();
         self.rally_command_sender = None;
         self.rally_event_receiver = None;
         if let Some(handle) = self.rally_abort_handle.take() {
@@ -320,17 +351,17 @@ impl App {
     pub(crate) fn open_clarification_editor_sync(
         &mut self,
         question: &str,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         // Restore terminal before opening editor
-        ui::restore_terminal(terminal)?;
+        terminal.suspend()?;
 
         // Open editor (blocking)
         let answer =
             crate::editor::open_clarification_editor(self.config.editor.as_deref(), question)?;
 
         // Re-setup terminal after editor closes
-        *terminal = ui::setup_terminal()?;
+        terminal.resume()?;
 
         // Process result
         if let Some(ref mut rally_state) = self.ai_rally_state {
@@ -400,6 +431,46 @@ impl App {
     }
 
     pub(crate) fn start_ai_rally(&mut self) {
+        if std::mem::take(&mut self.resume_rally_on_load) {
+            self.resume_ai_rally();
+            return;
+        }
+        self.start_ai_rally_with_scope(Vec::new());
+    }
+
+    /// 前回途中で終わった Rally をディスクの記録から再開する。
+    /// ログを復元し、最後に完了したイテレーションの続きから進める
+    pub(crate) fn resume_ai_rally(&mut self) {
+        if self
+            .ai_rally_state
+            .as_ref()
+            .is_some_and(|state| state.state.is_active())
+        {
+            self.state = AppState::AiRally;
+            return;
+        }
+        let saved = session::read_session(&self.repo, self.pr_number())
+            .ok()
+            .flatten()
+            .filter(|saved| saved.is_resumable());
+        let Some(saved) = saved else {
+            self.submission_result = Some((
+                false,
+                "No unfinished AI Rally to resume for this PR".to_string(),
+            ));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        };
+        self.cleanup_rally_state();
+        self.launch_ai_rally(saved.scope, true);
+    }
+
+    /// `scope` のファイルだけを対象に Rally を始める（空なら PR 全体）
+    pub(crate) fn start_ai_rally_with_scope(&mut self, scope: Vec<String>) {
+        self.launch_ai_rally(scope, false);
+    }
+
+    fn launch_ai_rally(&mut self, scope: Vec<String>, resume: bool) {
         // Get PR data for context
         let Some(pr) = self.pr() else {
             return;
@@ -408,6 +479,7 @@ impl App {
         let file_patches: Vec<(String, String)> = self
             .files()
             .iter()
+            .filter(|f| scope.is_empty() || scope.contains(&f.filename))
             .filter_map(|f| f.patch.as_ref().map(|p| (f.filename.clone(), p.clone())))
             .collect();
 
@@ -436,7 +508,10 @@ impl App {
             external_comments: Vec::new(),
             local_mode: self.local_mode,
             file_patches,
+            env: SpawnEnv::for_repo(&self.config.env, &self.repo),
+            scope: scope.clone(),
         };
+        self.rally_scope = scope;
 
         let (event_tx, event_rx) = mpsc::channel(100);
         let (cmd_tx, cmd_rx) = mpsc::channel(10);
@@ -460,9 +535,20 @@ impl App {
             pending_review_post: None,
             pending_fix_post: None,
             last_visible_log_height: 10,
+            usage: RallyUsage::default(),
         });
 
+        if resume {
+            self.restore_rally_log();
+        }
+
         self.state = AppState::AiRally;
+        if !self.rally_scope.is_empty() {
+            let message = format!("Reviewing only: {}", self.rally_scope.join(", "));
+            if let Some(ref mut rally_state) = self.ai_rally_state {
+                rally_state.push_log(LogEntry::new(LogEventType::Info, message));
+            }
+        }
 
         // Spawn the orchestrator and store the abort handle
         let config = self.config.ai.clone();
@@ -482,6 +568,14 @@ impl App {
             match orchestrator_result {
                 Ok(mut orchestrator) => {
                     orchestrator.set_context(context);
+                    if resume {
+                        if let Err(e) = orchestrator.resume() {
+                            let _ = event_tx
+                                .send(RallyEvent::Error(format!("Failed to resume rally: {}", e)))
+                                .await;
+                            return;
+                        }
+                    }
                     // Note: orchestrator.run() already emits RallyEvent::Error and
                     // StateChanged(Error) when it fails, so we don't emit them again here
                     // to avoid duplicate error logs in the UI
@@ -502,4 +596,32 @@ impl App {
         // Store the abort handle so we can cancel the task when user presses 'q'
         self.rally_abort_handle = Some(handle.abort_handle());
     }
+
+    /// 前回の Rally のイベントログを読み直して、履歴・ログ・使用量を画面に戻す
+    fn restore_rally_log(&mut self) {
+        let Ok(path) = session::events_path(&self.repo, self.pr_number()) else {
+            return;
+        };
+        let events = session::read_events(&path);
+        let (tx, rx) = mpsc::channel(events.len().max(1));
+        for event in events {
+            let _ = tx.try_send(event);
+        }
+        let mut receiver = Some(rx);
+        Self::drain_rally_events(&mut receiver, &mut self.ai_rally_state);
+        // tx を drain の後まで生かして、切断扱いのエラーにしない
+        drop(tx);
+
+        if let Some(ref mut rally_state) = self.ai_rally_state {
+            rally_state.state = RallyState::Initializing;
+            rally_state.pending_question = None;
+            rally_state.pending_permission = None;
+            rally_state.pending_review_post = None;
+            rally_state.pending_fix_post = None;
+            rally_state.push_log(LogEntry::new(
+                LogEventType::Info,
+                "Resuming previous rally".to_string(),
+            ));
+        }
+    }
 }
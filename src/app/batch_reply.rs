@@ -0,0 +1,164 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use crate::audit::{self, AuditAction, AuditEntry};
+use crate::cache::PrCacheKey;
+use crate::github;
+
+use super::types::*;
+use super::{App, AppState};
+
+/// 返信の間隔。短時間に大量のコメントを作ると GitHub の secondary rate limit に当たる
+const BATCH_REPLY_INTERVAL: Duration = Duration::from_millis(1000);
+
+impl App {
+    /// コメント一覧（Review タブ）で選択中のコメントをマーク/解除して次へ進む
+    pub(crate) fn toggle_review_comment_mark(&mut self) {
+        let Some(ref comments) = self.review_comments else {
+            return;
+        };
+        let Some(comment) = comments.get(self.selected_comment) else {
+            return;
+        };
+        if !self.marked_review_comments.remove(&comment.id) {
+            self.marked_review_comments.insert(comment.id);
+        }
+        self.selected_comment = (self.selected_comment + 1).min(comments.len().saturating_sub(1));
+    }
+
+    /// マークしたスレッド（なければ選択中の 1 件）への返信を入力する
+    pub(crate) fn enter_batch_reply_input(&mut self) {
+        let Some(ref comments) = self.review_comments else {
+            return;
+        };
+        let targets: Vec<BatchReplyTarget> = comments
+            .iter()
+            .enumerate()
+            .filter(|(i, c)| {
+                if self.marked_review_comments.is_empty() {
+                    *i == self.selected_comment
+                } else {
+                    self.marked_review_comments.contains(&c.id)
+                }
+            })
+            .map(|(_, c)| BatchReplyTarget {
+                comment_id: c.id,
+                user: c.user.login.clone(),
+                location: match c.line {
+                    Some(line) => format!("{}:{}", c.path, line),
+                    None => c.path.clone(),
+                },
+            })
+            .collect();
+        if targets.is_empty() {
+            return;
+        }
+
+        self.input_mode = Some(InputMode::BatchReply { targets });
+        self.input_text_area.clear();
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+
+    /// 各スレッドに順番に返信し、スレッドごとの結果をまとめて返す
+    pub(crate) fn submit_batch_reply(&mut self, targets: Vec<BatchReplyTarget>, body: String) {
+        let repo = self.repo.clone();
+        let pr_number = self.pr_number();
+
+        let (tx, rx) = mpsc::channel(1);
+        self.batch_reply_receiver = Some((pr_number, rx));
+        self.comment_submitting = true;
+
+        tokio::spawn(async move {
+            let mut outcomes = Vec::with_capacity(targets.len());
+            for (i, target) in targets.into_iter().enumerate() {
+                if i > 0 {
+                    tokio::time::sleep(BATCH_REPLY_INTERVAL).await;
+                }
+                let result =
+                    github::create_reply_comment(&repo, pr_number, target.comment_id, &body).await;
+                audit::record(&AuditEntry::new(
+                    &repo,
+                    pr_number,
+                    AuditAction::Reply,
+                    Some(format!("#{}", target.comment_id)),
+                    &result,
+                ));
+                outcomes.push(BatchReplyOutcome {
+                    target,
+                    error: result.err().map(|e| e.to_string()),
+                });
+            }
+            let _ = tx.send(outcomes).await;
+        });
+    }
+
+    pub(crate) fn poll_batch_reply_updates(&mut self) {
+        let Some((origin_pr, ref mut rx)) = self.batch_reply_receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok(outcomes) => {
+                self.batch_reply_receiver = None;
+                self.comment_submitting = false;
+                if self.pr_number == Some(origin_pr) {
+                    self.apply_batch_reply_outcomes(&outcomes);
+                }
+
+                let cache_key = PrCacheKey {
+                    repo: self.repo.clone(),
+                    pr_number: origin_pr,
+                };
+                self.session_cache.remove_review_comments(&cache_key);
+                if self.pr_number == Some(origin_pr) {
+                    self.review_comments = None;
+                    self.load_review_comments();
+                    self.update_file_comment_positions();
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.batch_reply_receiver = None;
+                self.comment_submitting = false;
+            }
+        }
+    }
+
+    /// 成功したスレッドはマークを外し、失敗したスレッドはマークを残してエラーを記録する
+    pub(crate) fn apply_batch_reply_outcomes(&mut self, outcomes: &[BatchReplyOutcome]) {
+        self.batch_reply_errors.clear();
+        for outcome in outcomes {
+            match outcome.error {
+                None => {
+                    self.marked_review_comments
+                        .remove(&outcome.target.comment_id);
+                }
+                Some(ref e) => {
+                    self.marked_review_comments
+                        .insert(outcome.target.comment_id);
+                    self.batch_reply_errors
+                        .insert(outcome.target.comment_id, e.clone());
+                }
+            }
+        }
+
+        let failed: Vec<&BatchReplyOutcome> =
+            outcomes.iter().filter(|o| o.error.is_some()).collect();
+        let total = outcomes.len();
+        let message = match failed.first() {
+            None => format!("Replied to {} thread(s)", total),
+            Some(first) => format!(
+                "Replied to {}/{} thread(s); {} failed (still marked), e.g. {}: {}",
+                total - failed.len(),
+                total,
+                failed.len(),
+                first.target.location,
+                first.error.as_deref().unwrap_or_default()
+            ),
+        };
+        self.submission_result = Some((failed.is_empty(), message));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
@@ -0,0 +1,116 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::blame::FileBlame;
+
+use super::{App, AppState};
+
+/// blame のキャッシュのキー。blame したリビジョン（ローカルモードの作業ツリーは None）とファイル名
+pub(crate) type BlameKey = (Option<String>, String);
+
+impl App {
+    fn set_blame_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// diff のガターの blame の表示を切り替える。消すと取得済みの blame も捨て、次は取り直す
+    pub(crate) fn toggle_blame(&mut self) {
+        self.blame_enabled = !self.blame_enabled;
+        if self.blame_enabled {
+            self.set_blame_message(true, "Showing blame for unchanged lines");
+            self.request_file_blame();
+        } else {
+            self.file_blames.clear();
+            self.blame_receiver = None;
+        }
+    }
+
+    /// 選択中のファイルの blame のキー。コミット単体の diff・削除されたファイル・
+    /// LFS のプレビューは head の行番号と対応しないので blame しない
+    fn blame_key(&self) -> Option<BlameKey> {
+        if self.commit_view.is_some() || self.is_lfs_preview(self.selected_file) {
+            return None;
+        }
+        let file = self.files().get(self.selected_file)?;
+        if file.status == "removed" {
+            return None;
+        }
+        let rev = if self.local_mode {
+            None
+        } else {
+            Some(self.pr()?.head.sha.clone())
+        };
+        Some((rev, file.filename.clone()))
+    }
+
+    /// ガターに表示する選択中のファイルの blame
+    pub fn current_file_blame(&self) -> Option<&FileBlame> {
+        if !self.blame_enabled {
+            return None;
+        }
+        self.file_blames.get(&self.blame_key()?)?.as_ref()
+    }
+
+    fn request_file_blame(&mut self) {
+        if self.blame_receiver.is_some() {
+            return;
+        }
+        let Some(key) = self.blame_key() else {
+            return;
+        };
+        if self.file_blames.contains_key(&key) {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel(1);
+        self.blame_receiver = Some(rx);
+        let repo = self.repo.clone();
+        let working_dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let (rev, filename) = &key;
+            let result = match rev {
+                Some(rev) => {
+                    crate::blame::fetch_blame(&repo, working_dir.as_deref(), rev, filename).await
+                }
+                None => crate::blame::blame_from_git(working_dir.as_deref(), None, filename).await,
+            };
+            let _ = tx.send((key, result.map_err(|e| format!("{:#}", e)))).await;
+        });
+    }
+
+    /// blame の取得結果を取り込み、表示中なら diff を開いているファイルの blame を取得する
+    pub(crate) fn poll_blame_updates(&mut self) {
+        if let Some(ref mut rx) = self.blame_receiver {
+            match rx.try_recv() {
+                Ok((key, result)) => {
+                    self.blame_receiver = None;
+                    match result {
+                        Ok(blame) => {
+                            self.file_blames.insert(key, Some(blame));
+                        }
+                        Err(e) => {
+                            // 取得できなかったファイルは blame を切り替え直すまで取り直さない
+                            self.set_blame_message(false, format!("Blame unavailable: {}", e));
+                            self.file_blames.insert(key, None);
+                        }
+                    }
+                }
+                Err(mpsc::error::TryRecvError::Empty) => return,
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.blame_receiver = None;
+                }
+            }
+        }
+
+        if self.blame_enabled
+            && matches!(
+                self.state,
+                AppState::DiffView | AppState::SplitViewFileList | AppState::SplitViewDiff
+            )
+        {
+            self.request_file_blame();
+        }
+    }
+}
@@ -0,0 +1,73 @@
+use crate::bot_author;
+
+use super::types::CommentTab;
+use super::App;
+
+impl App {
+    /// コメント一覧の表示順（コメントのインデックス）と bot のコメント数
+    pub fn comment_list_order(&self, tab: CommentTab) -> (Vec<usize>, usize) {
+        let patterns = &self.config.comments.bot_authors;
+        let expanded = self.comment_list_bots_expanded;
+        match tab {
+            CommentTab::Review => {
+                // 未解決のスレッドだけに絞り込んでいるときは、解決済みのスレッドのコメントを
+                // bot のコメントとしても数えない
+                let comments = self.review_comments.as_deref().unwrap_or_default();
+                let shown: Vec<usize> = (0..comments.len())
+                    .filter(|&i| {
+                        !self.comment_list_unresolved_only
+                            || self.is_in_unresolved_thread(comments[i].id)
+                    })
+                    .collect();
+                let (order, bot_count) = bot_author::fold_order(
+                    shown.iter().map(|&i| comments[i].user.login.as_str()),
+                    patterns,
+                    expanded,
+                );
+                (order.into_iter().map(|pos| shown[pos]).collect(), bot_count)
+            }
+            CommentTab::Discussion => bot_author::fold_order(
+                self.discussion_comments
+                    .iter()
+                    .flatten()
+                    .map(|c| c.user.login.as_str()),
+                patterns,
+                expanded,
+            ),
+        }
+    }
+
+    fn comment_list_selection(&mut self, tab: CommentTab) -> &mut usize {
+        match tab {
+            CommentTab::Review => &mut self.selected_comment,
+            CommentTab::Discussion => &mut self.selected_discussion_comment,
+        }
+    }
+
+    /// 表示順で `delta` 行だけ選択を動かす。
+    /// 折りたたんだ bot のコメントを選んでいたら先頭の行に戻す
+    pub(crate) fn move_comment_list_selection(&mut self, delta: isize) {
+        let tab = self.comment_tab;
+        let (order, _) = self.comment_list_order(tab);
+        let Some(last) = order.len().checked_sub(1) else {
+            return;
+        };
+        let selected = self.comment_list_selection(tab);
+        let next = match order.iter().position(|&i| i == *selected) {
+            Some(pos) => pos.saturating_add_signed(delta).min(last),
+            None => 0,
+        };
+        *selected = order[next];
+    }
+
+    /// 選択中のコメントが表示されていなければ先頭の行を選ぶ
+    pub(crate) fn normalize_comment_list_selection(&mut self) {
+        self.move_comment_list_selection(0);
+    }
+
+    /// bot のコメントの折りたたみを切り替える
+    pub(crate) fn toggle_bot_comments(&mut self) {
+        self.comment_list_bots_expanded = !self.comment_list_bots_expanded;
+        self.normalize_comment_list_selection();
+    }
+}
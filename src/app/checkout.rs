@@ -0,0 +1,157 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::checkout::{self, CheckoutResult, CheckoutTarget};
+use crate::ui::popup::PopupKey;
+
+use super::{App, PopupId};
+
+/// チェックアウトの進み具合
+#[derive(Debug, Clone)]
+pub enum CheckoutPhase {
+    /// 実行前の確認
+    Confirm,
+    Running,
+    Done(Result<CheckoutResult, String>),
+}
+
+/// PR のブランチのチェックアウトのポップアップ
+#[derive(Debug, Clone)]
+pub struct CheckoutState {
+    pub target: CheckoutTarget,
+    /// 未コミットの変更があるファイル（確認を開いた時点）
+    pub dirty: Vec<String>,
+    pub phase: CheckoutPhase,
+}
+
+impl App {
+    fn set_checkout_status(&mut self, success: bool, message: String) {
+        self.submission_result = Some((success, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// go: 表示中の PR のブランチをチェックアウトする前に確認する
+    pub(crate) fn open_checkout(&mut self) {
+        if self.local_mode {
+            self.set_checkout_status(false, "Checkout is only available for a PR".to_string());
+            return;
+        }
+        if self.checkout_receiver.is_some() {
+            self.set_checkout_status(false, "A checkout is already in progress".to_string());
+            return;
+        }
+        let Some(pr) = self.pr() else {
+            return;
+        };
+        let target = CheckoutTarget {
+            repo: self.repo.clone(),
+            pr_number: pr.number,
+            head_ref: pr.head.ref_name.clone(),
+            base_ref: pr.base.ref_name.clone(),
+        };
+        let dirty = match checkout::dirty_files(self.working_dir.as_deref()) {
+            Ok(dirty) => dirty,
+            Err(_) => {
+                self.set_checkout_status(
+                    false,
+                    "Not inside a local checkout of the repository".to_string(),
+                );
+                return;
+            }
+        };
+        self.checkout = Some(CheckoutState {
+            target,
+            dirty,
+            phase: CheckoutPhase::Confirm,
+        });
+        self.push_popup(PopupId::Checkout);
+    }
+
+    fn start_checkout(&mut self, stash: bool) {
+        let Some(ref mut state) = self.checkout else {
+            return;
+        };
+        state.phase = CheckoutPhase::Running;
+        let target = state.target.clone();
+        let working_dir = self.working_dir.clone();
+        let (tx, rx) = mpsc::channel(1);
+        self.checkout_receiver = Some(rx);
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                checkout::checkout_pr(working_dir.as_deref(), &target, stash)
+            })
+            .await
+            .map_err(|e| format!("Checkout task failed: {}", e))
+            .and_then(|result| result.map_err(|e| format!("{:#}", e)));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn poll_checkout_updates(&mut self) {
+        let Some(ref mut rx) = self.checkout_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.checkout_receiver = None;
+                match result {
+                    Ok(ref done) => self.set_checkout_status(true, done.summary()),
+                    Err(ref e) => {
+                        self.set_checkout_status(false, format!("Checkout failed: {}", e))
+                    }
+                }
+                // 実行中にポップアップを閉じていても結果はフッターに出る
+                if let Some(ref mut state) = self.checkout {
+                    state.phase = CheckoutPhase::Done(result);
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.checkout_receiver = None;
+                if let Some(ref mut state) = self.checkout {
+                    state.phase = CheckoutPhase::Done(Err("Checkout was interrupted".to_string()));
+                }
+            }
+        }
+    }
+
+    /// 確認中は y / Enter で実行し、未コミットの変更があれば s で stash してから実行する。
+    /// 完了後は L でチェックアウトしたブランチのローカルモードに切り替える
+    pub(crate) fn handle_checkout_input(&mut self, key: &KeyEvent) {
+        let Some(ref state) = self.checkout else {
+            return;
+        };
+        let action = PopupKey::from_key(key, &self.config.keybindings);
+        match state.phase {
+            CheckoutPhase::Confirm => {
+                let clean = state.dirty.is_empty();
+                match (action, key.code) {
+                    (PopupKey::Close, _) => self.close_popup(PopupId::Checkout),
+                    (PopupKey::Confirm, _) | (_, KeyCode::Char('y')) if clean => {
+                        self.start_checkout(false)
+                    }
+                    (_, KeyCode::Char('s')) if !clean => self.start_checkout(true),
+                    _ => {}
+                }
+            }
+            // git の実行中は取り消せないので、閉じても結果はフッターに出す
+            CheckoutPhase::Running => {
+                if action == PopupKey::Close {
+                    self.close_popup(PopupId::Checkout);
+                }
+            }
+            CheckoutPhase::Done(ref result) => {
+                if result.is_ok() && key.code == KeyCode::Char('L') {
+                    self.close_popup(PopupId::Checkout);
+                    if !self.local_mode {
+                        self.toggle_local_mode();
+                    }
+                } else if matches!(action, PopupKey::Close | PopupKey::Confirm) {
+                    self.close_popup(PopupId::Checkout);
+                }
+            }
+        }
+    }
+}
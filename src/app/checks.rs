@@ -0,0 +1,254 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::ci_log::{self, CiLog, CiLogLine};
+use crate::github::CheckRun;
+
+use super::{App, AppState};
+
+/// 失敗した行の前に残す行数（ログを開いたときの位置）
+const ERROR_CONTEXT_LINES: usize = 3;
+
+/// PR の head コミットの CI チェック一覧の状態
+#[derive(Debug)]
+pub struct ChecksState {
+    /// 読み込み中は None
+    pub runs: Option<Vec<CheckRun>>,
+    pub error: Option<String>,
+    pub selected: usize,
+    /// 開いている失敗ジョブのログ
+    pub log: Option<CheckLogState>,
+    /// 一覧を閉じたときに戻る画面
+    return_state: AppState,
+}
+
+/// 失敗ジョブのログのモーダル
+#[derive(Debug)]
+pub struct CheckLogState {
+    pub check_id: u64,
+    pub name: String,
+    /// 読み込み中は None
+    pub log: Option<CiLog>,
+    pub error: Option<String>,
+    pub scroll: usize,
+}
+
+impl App {
+    fn set_checks_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// PR の head コミットの CI チェック一覧を開く
+    pub(crate) fn open_checks(&mut self) {
+        if self.local_mode {
+            self.set_checks_message(false, "Checks are only available for PRs");
+            return;
+        }
+        let (Some(pr_number), Some(head_sha)) =
+            (self.pr_number, self.pr().map(|pr| pr.head.sha.clone()))
+        else {
+            return;
+        };
+
+        self.checks = Some(ChecksState {
+            runs: None,
+            error: None,
+            selected: 0,
+            log: None,
+            return_state: self.state,
+        });
+        self.state = AppState::Checks;
+
+        let (tx, rx) = mpsc::channel(1);
+        self.checks_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = crate::github::fetch_check_runs(&repo, &head_sha)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn poll_checks_updates(&mut self) {
+        self.poll_check_runs();
+        self.poll_check_log();
+    }
+
+    fn poll_check_runs(&mut self) {
+        let Some((origin_pr, ref mut rx)) = self.checks_receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.checks_receiver = None;
+                return;
+            }
+        };
+        self.checks_receiver = None;
+        if self.pr_number != Some(origin_pr) {
+            return;
+        }
+        let Some(ref mut checks) = self.checks else {
+            return;
+        };
+        match result {
+            Ok(runs) => checks.runs = Some(runs),
+            Err(e) => checks.error = Some(e),
+        }
+    }
+
+    fn poll_check_log(&mut self) {
+        let Some((origin_pr, ref mut rx)) = self.check_log_receiver else {
+            return;
+        };
+        let (check_id, result) = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.check_log_receiver = None;
+                return;
+            }
+        };
+        self.check_log_receiver = None;
+        if self.pr_number != Some(origin_pr) {
+            return;
+        }
+        // 取得中に閉じた・別のジョブを開いた場合は捨てる
+        let Some(log_state) = self
+            .checks
+            .as_mut()
+            .and_then(|c| c.log.as_mut())
+            .filter(|l| l.check_id == check_id)
+        else {
+            return;
+        };
+        match result {
+            Ok(log) => {
+                log_state.scroll = log
+                    .lines
+                    .iter()
+                    .position(|line| matches!(line, CiLogLine::Error(_)))
+                    .map_or(0, |i| i.saturating_sub(ERROR_CONTEXT_LINES));
+                log_state.log = Some(log);
+            }
+            Err(e) => log_state.error = Some(e),
+        }
+    }
+
+    fn close_checks(&mut self) {
+        if let Some(checks) = self.checks.take() {
+            self.state = checks.return_state;
+        }
+        self.checks_receiver = None;
+        self.check_log_receiver = None;
+    }
+
+    /// 選択中の失敗ジョブのログを取得してモーダルで開く
+    fn open_check_log(&mut self) {
+        let Some(run) = self
+            .checks
+            .as_ref()
+            .and_then(|c| c.runs.as_ref()?.get(c.selected))
+            .cloned()
+        else {
+            return;
+        };
+        if !run.is_failing() {
+            self.set_checks_message(false, "Logs are only shown for failed jobs");
+            return;
+        }
+        if run.actions_run_id().is_none() {
+            self.set_checks_message(false, "Logs are only available for GitHub Actions jobs");
+            return;
+        }
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+        if let Some(ref mut checks) = self.checks {
+            checks.log = Some(CheckLogState {
+                check_id: run.id,
+                name: run.name.clone(),
+                log: None,
+                error: None,
+                scroll: 0,
+            });
+        }
+
+        let (tx, rx) = mpsc::channel(1);
+        self.check_log_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = crate::github::fetch_job_log(&repo, run.id)
+                .await
+                .map(|raw| ci_log::parse(&raw))
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((run.id, result)).await;
+        });
+    }
+
+    pub(crate) fn handle_checks_input(&mut self, key: KeyEvent, visible_rows: usize) {
+        let kb = &self.config.keybindings;
+        let close = key.code == KeyCode::Esc || self.matches_single_key(&key, &kb.quit);
+        let down = self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down;
+        let up = self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up;
+        let page_down = self.matches_single_key(&key, &kb.page_down);
+        let page_up = self.matches_single_key(&key, &kb.page_up);
+        let last = self.matches_single_key(&key, &kb.jump_to_last);
+        let open = self.matches_single_key(&key, &kb.open_panel);
+
+        let Some(ref mut checks) = self.checks else {
+            return;
+        };
+
+        if let Some(ref mut log_state) = checks.log {
+            if close {
+                checks.log = None;
+                self.check_log_receiver = None;
+                return;
+            }
+            let max_scroll = log_state
+                .log
+                .as_ref()
+                .map_or(0, |l| l.lines.len().saturating_sub(visible_rows.max(1)));
+            let page = visible_rows.max(1);
+            if down {
+                log_state.scroll = (log_state.scroll + 1).min(max_scroll);
+            } else if up {
+                log_state.scroll = log_state.scroll.saturating_sub(1);
+            } else if page_down {
+                log_state.scroll = (log_state.scroll + page).min(max_scroll);
+            } else if page_up {
+                log_state.scroll = log_state.scroll.saturating_sub(page);
+            } else if last {
+                log_state.scroll = max_scroll;
+            }
+            return;
+        }
+
+        if close {
+            self.close_checks();
+            return;
+        }
+        if open {
+            self.open_check_log();
+            return;
+        }
+        let max = checks
+            .runs
+            .as_ref()
+            .map_or(0, |r| r.len().saturating_sub(1));
+        if down {
+            checks.selected = (checks.selected + 1).min(max);
+        } else if up {
+            checks.selected = checks.selected.saturating_sub(1);
+        } else if last {
+            checks.selected = max;
+        }
+    }
+}
@@ -0,0 +1,141 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::comment_draft::{CommentDrafter, CommentTarget};
+use crate::diff::LineType;
+
+use super::types::{InputMode, LineInputContext};
+use super::{App, AppState};
+
+/// 下書きの結果（コメントの位置とファイル名を添える）
+pub(crate) type CommentDraftResult = (LineInputContext, String, Result<String, String>);
+
+impl App {
+    /// E: 選択中の行（複数行選択中はその範囲）へのコメントの下書きをエージェントに頼む。
+    /// 下書きが届くとコメント入力に入るので、編集してから送信する
+    pub(crate) fn draft_comment_with_ai(&mut self) {
+        if self.local_mode {
+            return;
+        }
+        let ctx = if self.multiline_selection.is_some() {
+            self.multiline_comment_context()
+        } else {
+            self.line_comment_context()
+        };
+        let Some(ctx) = ctx else {
+            self.set_comment_draft_status(false, "Cannot comment on this line".to_string());
+            return;
+        };
+        if self.comment_draft_receiver.is_some() {
+            self.set_comment_draft_status(false, "Another draft is in progress".to_string());
+            return;
+        }
+        let drafter = match CommentDrafter::from_config(&self.config, &self.repo) {
+            Ok(drafter) => drafter,
+            Err(e) => {
+                self.set_comment_draft_status(false, format!("Comment draft unavailable: {}", e));
+                return;
+            }
+        };
+        let Some(file) = self.files().get(ctx.file_index) else {
+            return;
+        };
+        let patch = file.patch.clone().unwrap_or_default();
+        let (start, end) = match self.multiline_selection {
+            Some(ref selection) => (selection.start(), selection.end()),
+            None => (self.selected_line, self.selected_line),
+        };
+        let selected = (start..=end)
+            .filter_map(|i| crate::diff::get_line_info(&patch, i))
+            .filter_map(|info| match info.line_type {
+                LineType::Added => Some(format!("+{}", info.line_content)),
+                LineType::Context => Some(format!(" {}", info.line_content)),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        let target = CommentTarget {
+            pr_title: self.pr().map(|pr| pr.title.clone()),
+            filename: file.filename.clone(),
+            start_line: ctx.start_line_number,
+            line: ctx.line_number,
+            selected,
+            patch,
+        };
+
+        self.multiline_selection = None;
+        self.set_comment_draft_status(true, format!("Drafting comment with {}...", drafter.name()));
+        let (tx, rx) = mpsc::channel(1);
+        self.comment_draft_receiver = Some(rx);
+        tokio::spawn(async move {
+            let result = drafter
+                .draft(&target)
+                .await
+                .map(|draft| draft.trim().to_string())
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((ctx, target.filename, result)).await;
+        });
+    }
+
+    pub(crate) fn poll_comment_draft_updates(&mut self) {
+        let Some(ref mut rx) = self.comment_draft_receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok(result) => {
+                self.comment_draft_receiver = None;
+                self.apply_comment_draft(result);
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.comment_draft_receiver = None;
+            }
+        }
+    }
+
+    /// 下書きをコメント入力に入れる。
+    /// 待っている間に別のファイルや画面へ移っていれば、入力を奪わずに知らせるだけにする
+    pub(crate) fn apply_comment_draft(&mut self, (ctx, filename, result): CommentDraftResult) {
+        let draft = match result {
+            Ok(draft) if !draft.is_empty() => draft,
+            Ok(_) => {
+                self.set_comment_draft_status(
+                    false,
+                    "The agent returned an empty draft".to_string(),
+                );
+                return;
+            }
+            Err(e) => {
+                self.set_comment_draft_status(false, format!("Comment draft failed: {}", e));
+                return;
+            }
+        };
+        let same_file = self
+            .files()
+            .get(ctx.file_index)
+            .is_some_and(|file| file.filename == filename);
+        if !same_file
+            || ctx.file_index != self.selected_file
+            || !matches!(self.state, AppState::DiffView | AppState::SplitViewDiff)
+        {
+            self.set_comment_draft_status(
+                false,
+                format!("Comment draft for {} discarded (view changed)", filename),
+            );
+            return;
+        }
+
+        self.submission_result = None;
+        self.input_mode = Some(InputMode::Comment(ctx));
+        self.input_text_area.set_content(&draft);
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+
+    fn set_comment_draft_status(&mut self, ok: bool, message: String) {
+        self.submission_result = Some((ok, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
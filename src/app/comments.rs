@@ -1,73 +1,77 @@
 use anyhow::Result;
-use crossterm::event::{self, KeyCode, KeyModifiers};
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::Stdout;
+use crossterm::event::{self, KeyCode};
 use std::time::Instant;
 use tokio::sync::mpsc;
 
-use crate::cache::PrCacheKey;
-use crate::github::{self, comment::ReviewComment};
-use crate::ui;
+use crate::cache::{self, OutboxPayload, PrCacheKey};
+use crate::config::KeybindingView;
+use crate::github;
+use crate::loader;
+use crate::gutter_filter;
+use crate::review_template;
+use crate::ui::{self, TuiTerminal};
 
 use super::types::*;
 use super::{App, AppState};
 
 impl App {
     pub(crate) fn enter_comment_input(&mut self) {
-        if self.local_mode {
-            return;
-        }
-        let Some(file) = self.files().get(self.selected_file) else {
-            return;
-        };
-        let Some(patch) = file.patch.as_ref() else {
+        let Some(ctx) = self.line_comment_context() else {
             return;
         };
+        self.input_mode = Some(InputMode::Comment(ctx));
+        self.input_text_area.clear();
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+    /// 選択中の行へのコメントの位置（コメントできない行なら None）
+    pub(crate) fn line_comment_context(&self) -> Option<LineInputContext> {
+        // LFS 実体のプレビュー行は PR の diff に存在しないためコメント不可
+        if self.local_mode || self.is_file_preview(self.selected_file) {
+            return None;
+        }
+        let file = self.files().get(self.selected_file)?;
+        let patch = file.patch.as_ref()?;
 
         // Get actual line number from diff
-        let Some(line_info) = crate::diff::get_line_info(patch, self.selected_line) else {
-            return;
-        };
+        let line_info = crate::diff::get_line_info(patch, self.selected_line)?;
 
         // Only allow comments on Added or Context lines (not Removed/Header/Meta)
         if !matches!(
             line_info.line_type,
             crate::diff::LineType::Added | crate::diff::LineType::Context
         ) {
-            return;
+            return None;
         }
 
-        let Some(line_number) = line_info.new_line_number else {
-            return;
-        };
-
-        let Some(diff_position) = line_info.diff_position else {
-            return;
-        };
-
-        self.input_mode = Some(InputMode::Comment(LineInputContext {
+        Some(LineInputContext {
             file_index: self.selected_file,
-            line_number,
-            diff_position,
+            line_number: line_info.new_line_number?,
+            diff_position: line_info.diff_position?,
             start_line_number: None,
-        }));
-        self.input_text_area.clear();
-        self.preview_return_state = self.state;
-        self.state = AppState::TextInput;
+        })
     }
     pub(crate) async fn submit_review(
         &mut self,
         action: ReviewAction,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         tracing::debug!(?action, "submit_review: start");
-        ui::restore_terminal(terminal)?;
-
-        let editor_result = crate::editor::open_review_editor(self.config.editor.as_deref());
+        terminal.suspend()?;
+
+        // ラベルや変更されたパスに一致するテンプレートを本文の初期値にする
+        let labels: Vec<&str> = self
+            .pr()
+            .map(|pr| pr.labels.iter().map(|label| label.name.as_str()).collect())
+            .unwrap_or_default();
+        let paths: Vec<&str> = self.files().iter().map(|file| file.filename.as_str()).collect();
+        let template = review_template::matching(&self.config.review_templates, &labels, &paths);
+        let editor_result =
+            crate::editor::open_review_editor(self.config.editor.as_deref(), template.as_ref());
         tracing::debug!(?editor_result, "submit_review: editor returned");
 
         // エディタの成否に関わらずターミナルを再セットアップ
-        *terminal = ui::setup_terminal()?;
+        terminal.resume()?;
 
         let body = match editor_result {
             Ok(body) => body,
@@ -84,6 +88,9 @@ impl App {
             if action == ReviewAction::Approve {
                 // Empty comment → show approve confirmation UI
                 self.pending_approve_body = Some(String::new());
+            } else if action == ReviewAction::Comment && !self.review_drafts().is_empty() {
+                // 下書きのインラインコメントだけのレビューは本文なしで送信できる
+                return self.submit_review_with_body(action, "").await;
             } else {
                 self.submission_result = Some((false, "Review cancelled".to_string()));
                 self.submission_result_time = Some(Instant::now());
@@ -96,8 +103,20 @@ impl App {
     }
 
     pub(crate) async fn submit_review_with_body(&mut self, action: ReviewAction, body: &str) -> Result<()> {
+        // スコープ指定時は本文にレビュー対象のパスを列挙する
+        let body = self.path_scope.review_body(body, self.files());
         tracing::debug!(body_len = body.len(), "submit_review: calling GitHub API");
-        match github::submit_review(&self.repo, self.pr_number(), action, body).await {
+        let pr_number = self.pr_number();
+        let drafts = self.review_drafts().to_vec();
+        // 下書きがあればインラインコメントと一緒に 1 つのレビューとして送信する
+        let payload = OutboxPayload::Review {
+            commit_id: self.pr().map(|pr| pr.head.sha.clone()).unwrap_or_default(),
+            action,
+            body,
+            comments: drafts.clone(),
+        };
+        let result = loader::submit_payload(&self.repo, pr_number, &payload).await;
+        match result {
             Ok(()) => {
                 let action_str = match action {
                     ReviewAction::Approve => "approved",
@@ -105,8 +124,43 @@ impl App {
                     ReviewAction::Comment => "commented",
                 };
                 tracing::debug!(action_str, "submit_review: success");
-                self.submission_result = Some((true, format!("Review submitted ({})", action_str)));
+                let message = if drafts.is_empty() {
+                    format!("Review submitted ({})", action_str)
+                } else {
+                    format!(
+                        "Review submitted ({}, {} comment(s))",
+                        action_str,
+                        drafts.len()
+                    )
+                };
+                self.submission_result = Some((true, message));
                 self.submission_result_time = Some(Instant::now());
+                if !drafts.is_empty() {
+                    self.pending_review.clear(pr_number);
+                    self.review_drafts_open = false;
+                    // 投稿されたコメントを反映するため再取得
+                    let cache_key = PrCacheKey {
+                        repo: self.repo.clone(),
+                        pr_number,
+                    };
+                    self.session_cache.remove_review_comments(&cache_key);
+                    self.review_comments = None;
+                    self.load_review_comments();
+                    self.update_file_comment_positions();
+                }
+                self.record_review_outcome(action);
+                if action == ReviewAction::Approve {
+                    self.advance_review_queue_after_approve();
+                }
+            }
+            Err(e) if cache::is_retryable(&e.to_string()) => {
+                tracing::debug!(%e, "submit_review: API failed, queued for retry");
+                // 下書きは送信待ちのレビューに含めたので、二重に送らないよう消す
+                if !drafts.is_empty() {
+                    self.pending_review.clear(pr_number);
+                    self.review_drafts_open = false;
+                }
+                self.queue_for_retry(pr_number, payload, &e.to_string());
             }
             Err(e) => {
                 tracing::debug!(%e, "submit_review: API failed");
@@ -117,8 +171,16 @@ impl App {
         self.pending_approve_body = None;
         Ok(())
     }
+    /// 送信に成功したレビューを記録する。`--wait-for-review` ではここで終了する
+    pub(crate) fn record_review_outcome(&mut self, action: ReviewAction) {
+        self.review_outcome = Some(action);
+        if self.wait_for_review {
+            self.should_quit = true;
+        }
+    }
     pub(crate) fn enter_suggestion_input(&mut self) {
-        if self.local_mode {
+        // LFS 実体のプレビュー行は PR の diff に存在しないためコメント不可
+        if self.local_mode || self.is_file_preview(self.selected_file) {
             return;
         }
         let Some(file) = self.files().get(self.selected_file) else {
@@ -149,6 +211,13 @@ impl App {
             return;
         };
 
+        // 元のバイト列を再現できないファイルには suggestion を付けない
+        if let Some(error) = crate::encoding::suggestion_error(patch) {
+            self.submission_result = Some((false, error));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+
         let original_code = line_info.line_content.clone();
 
         self.input_mode = Some(InputMode::Suggestion {
@@ -167,7 +236,8 @@ impl App {
     }
     /// 複数行選択モードを開始する（Shift+Enter）
     pub(crate) fn enter_multiline_selection(&mut self) {
-        if self.local_mode {
+        // LFS 実体のプレビュー行は PR の diff に存在しないためコメント不可
+        if self.local_mode || self.is_file_preview(self.selected_file) {
             return;
         }
         // 現在の行がコメント可能な行であることを確認
@@ -192,51 +262,48 @@ impl App {
         });
     }
     pub(crate) fn enter_multiline_comment_input(&mut self) {
-        if self.local_mode {
-            return;
-        }
-        let Some(ref selection) = self.multiline_selection else {
+        let Some(ctx) = self.multiline_comment_context() else {
             return;
         };
+
+        // バリデーション成功後にのみ選択状態をクリア
+        self.multiline_selection = None;
+
+        self.input_mode = Some(InputMode::Comment(ctx));
+        self.input_text_area.clear();
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+    /// 複数行選択へのコメントの位置（範囲がコメントできなければ None）
+    pub(crate) fn multiline_comment_context(&self) -> Option<LineInputContext> {
+        if self.local_mode {
+            return None;
+        }
+        let selection = self.multiline_selection.as_ref()?;
         let start = selection.start();
         let end = selection.end();
 
-        let Some(file) = self.files().get(self.selected_file) else {
-            return;
-        };
-        let Some(patch) = file.patch.as_ref() else {
-            return;
-        };
+        let file = self.files().get(self.selected_file)?;
+        let patch = file.patch.as_ref()?;
 
         // 範囲内の全行が同一ハンク内の new-side 行であることを検証
         if !crate::diff::validate_multiline_range(patch, start, end) {
-            return;
+            return None;
         }
 
         // 終了行の情報を取得（GitHub API の line パラメータ）
-        let Some(end_info) = crate::diff::get_line_info(patch, end) else {
-            return;
-        };
+        let end_info = crate::diff::get_line_info(patch, end)?;
         if !matches!(
             end_info.line_type,
             crate::diff::LineType::Added | crate::diff::LineType::Context
         ) {
-            return;
+            return None;
         }
-        let Some(end_line_number) = end_info.new_line_number else {
-            return;
-        };
-        let Some(diff_position) = end_info.diff_position else {
-            return;
-        };
+        let end_line_number = end_info.new_line_number?;
+        let diff_position = end_info.diff_position?;
 
         // 開始行の情報を取得（GitHub API の start_line パラメータ）
-        let Some(start_info) = crate::diff::get_line_info(patch, start) else {
-            return;
-        };
-        let Some(start_line_number) = start_info.new_line_number else {
-            return;
-        };
+        let start_line_number = crate::diff::get_line_info(patch, start)?.new_line_number?;
 
         // 単一行の場合は start_line_number を None にする
         let start_line = if start_line_number < end_line_number {
@@ -245,18 +312,12 @@ impl App {
             None
         };
 
-        // バリデーション成功後にのみ選択状態をクリア
-        self.multiline_selection = None;
-
-        self.input_mode = Some(InputMode::Comment(LineInputContext {
+        Some(LineInputContext {
             file_index: self.selected_file,
             line_number: end_line_number,
             diff_position,
             start_line_number: start_line,
-        }));
-        self.input_text_area.clear();
-        self.preview_return_state = self.state;
-        self.state = AppState::TextInput;
+        })
     }
     pub(crate) fn enter_multiline_suggestion_input(&mut self) {
         if self.local_mode {
@@ -279,6 +340,12 @@ impl App {
         if !crate::diff::validate_multiline_range(patch, start, end) {
             return;
         }
+        // 元のバイト列を再現できないファイルには suggestion を付けない（選択は残す）
+        if let Some(error) = crate::encoding::suggestion_error(patch) {
+            self.submission_result = Some((false, error));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
 
         // 終了行の情報を取得
         let Some(end_info) = crate::diff::get_line_info(patch, end) else {
@@ -348,6 +415,8 @@ impl App {
         self.state = AppState::CommentList;
         self.discussion_comment_detail_mode = false;
         self.discussion_comment_detail_scroll = 0;
+        self.marked_review_comments.clear();
+        self.batch_reply_errors.clear();
 
         // Load review comments
         self.load_review_comments();
@@ -367,10 +436,12 @@ impl App {
             self.selected_comment = 0;
             self.comment_list_scroll_offset = 0;
             self.comments_loading = false;
+            self.load_review_thread_states(false);
             return;
         }
 
         // キャッシュミス: API取得
+        self.load_review_thread_states(true);
         self.comments_loading = true;
         let (tx, rx) = mpsc::channel(1);
         let pr_number = self.pr_number();
@@ -379,41 +450,32 @@ impl App {
         let repo = self.repo.clone();
 
         tokio::spawn(async move {
-            // Fetch both review comments and reviews
-            let review_comments_result =
-                github::comment::fetch_review_comments(&repo, pr_number).await;
-            let reviews_result = github::comment::fetch_reviews(&repo, pr_number).await;
-
-            // Combine results
-            let mut all_comments: Vec<ReviewComment> = Vec::new();
+            let comments = crate::loader::fetch_all_review_comments(&repo, pr_number).await;
+            let _ = tx.send(Ok(comments)).await;
+        });
+    }
 
-            // Add review comments (inline comments)
-            if let Ok(comments) = review_comments_result {
-                all_comments.extend(comments);
-            }
+    /// 未解決のスレッド数とガターの絞り込みに使うスレッドの状態（resolved / outdated）を取得する
+    fn load_review_thread_states(&mut self, refresh: bool) {
+        if self.local_mode {
+            return;
+        }
+        let pr_number = self.pr_number();
+        if !refresh && self.thread_states_pr == Some(pr_number) {
+            return;
+        }
+        self.thread_states_pr = Some(pr_number);
+        let (tx, rx) = mpsc::channel(1);
+        self.thread_state_receiver = Some(rx);
+        let repo = self.repo.clone();
 
-            // Convert reviews to ReviewComment format (only those with body)
-            if let Ok(reviews) = reviews_result {
-                for review in reviews {
-                    if let Some(body) = review.body {
-                        if !body.trim().is_empty() {
-                            all_comments.push(ReviewComment {
-                                id: review.id,
-                                path: "[PR Review]".to_string(),
-                                line: None,
-                                body,
-                                user: review.user,
-                                created_at: review.submitted_at.unwrap_or_default(),
-                            });
-                        }
-                    }
+        tokio::spawn(async move {
+            match github::fetch_review_thread_states(&repo, pr_number).await {
+                Ok(states) => {
+                    let _ = tx.send(states).await;
                 }
+                Err(e) => tracing::warn!(error = %e, "Failed to fetch review thread states"),
             }
-
-            // Sort by created_at
-            all_comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
-
-            let _ = tx.send(Ok(all_comments)).await;
         });
     }
 
@@ -453,94 +515,98 @@ impl App {
     pub(crate) async fn handle_comment_list_input(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         let visible_lines = terminal.size()?.height.saturating_sub(8) as usize;
 
-        // Handle detail mode input separately
-        if self.discussion_comment_detail_mode {
-            return self.handle_discussion_detail_input(key, visible_lines);
+        // 削除の確認中は y 以外のキーで取り消す
+        if self.is_pending_discussion_delete() {
+            self.confirm_discussion_delete(&key).await;
+            return Ok(());
         }
+        if self.is_pending_suggestion_apply() {
+            self.confirm_suggestion_apply(&key);
+            return Ok(());
+        }
+
+        let kb = self
+            .config
+            .keybindings
+            .for_view(KeybindingView::CommentList);
 
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc => {
-                self.state = self.previous_state;
+        // Handle detail mode input separately
+        if self.discussion_comment_detail_mode {
+            if self.matches_single_key(&key, &kb.comments_user_info) {
+                self.open_user_info();
+                return Ok(());
             }
-            KeyCode::Char('[') => {
-                self.comment_tab = match self.comment_tab {
-                    CommentTab::Review => CommentTab::Discussion,
-                    CommentTab::Discussion => CommentTab::Review,
-                };
+            if self.matches_single_key(&key, &kb.comments_edit) {
+                return self.edit_discussion_comment(terminal).await;
             }
-            KeyCode::Char(']') => {
-                self.comment_tab = match self.comment_tab {
-                    CommentTab::Review => CommentTab::Discussion,
-                    CommentTab::Discussion => CommentTab::Review,
-                };
-            }
-            KeyCode::Char('j') | KeyCode::Down => match self.comment_tab {
-                CommentTab::Review => {
-                    if let Some(ref comments) = self.review_comments {
-                        if !comments.is_empty() {
-                            self.selected_comment =
-                                (self.selected_comment + 1).min(comments.len().saturating_sub(1));
-                        }
-                    }
-                }
-                CommentTab::Discussion => {
-                    if let Some(ref comments) = self.discussion_comments {
-                        if !comments.is_empty() {
-                            self.selected_discussion_comment = (self.selected_discussion_comment
-                                + 1)
-                            .min(comments.len().saturating_sub(1));
-                        }
-                    }
-                }
-            },
-            KeyCode::Char('k') | KeyCode::Up => match self.comment_tab {
-                CommentTab::Review => {
-                    self.selected_comment = self.selected_comment.saturating_sub(1);
-                }
-                CommentTab::Discussion => {
-                    self.selected_discussion_comment =
-                        self.selected_discussion_comment.saturating_sub(1);
-                }
-            },
-            KeyCode::Char('J') => {
-                let step = visible_lines.max(1);
-                match self.comment_tab {
-                    CommentTab::Review => {
-                        if let Some(ref comments) = self.review_comments {
-                            if !comments.is_empty() {
-                                self.selected_comment =
-                                    (self.selected_comment + step).min(comments.len() - 1);
-                            }
-                        }
-                    }
-                    CommentTab::Discussion => {
-                        if let Some(ref comments) = self.discussion_comments {
-                            if !comments.is_empty() {
-                                self.selected_discussion_comment =
-                                    (self.selected_discussion_comment + step)
-                                        .min(comments.len() - 1);
-                            }
-                        }
-                    }
-                }
-            }
-            KeyCode::Char('K') => {
-                let step = visible_lines.max(1);
-                match self.comment_tab {
-                    CommentTab::Review => {
-                        self.selected_comment = self.selected_comment.saturating_sub(step);
-                    }
-                    CommentTab::Discussion => {
-                        self.selected_discussion_comment =
-                            self.selected_discussion_comment.saturating_sub(step);
-                    }
-                }
+            if self.matches_single_key(&key, &kb.comments_delete) {
+                self.request_discussion_delete().await;
+                return Ok(());
             }
-            KeyCode::Enter => match self.comment_tab {
+            return self.handle_discussion_detail_input(key, visible_lines);
+        }
+
+        if self.matches_single_key(&key, &kb.toggle_time_format) {
+            self.time_format.toggle_style();
+            return Ok(());
+        }
+
+        // 折りたたんだ bot のコメントを選んだまま操作しない
+        self.normalize_comment_list_selection();
+
+        let review_tab = self.comment_tab == CommentTab::Review;
+        if self.matches_single_key(&key, &kb.quit) || key.code == KeyCode::Esc {
+            self.state = self.previous_state;
+        } else if self.matches_single_key(&key, &kb.comments_prev_tab)
+            || self.matches_single_key(&key, &kb.comments_next_tab)
+        {
+            self.comment_tab = match self.comment_tab {
+                CommentTab::Review => CommentTab::Discussion,
+                CommentTab::Discussion => CommentTab::Review,
+            };
+        } else if self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down {
+            self.move_comment_list_selection(1);
+        } else if self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up {
+            self.move_comment_list_selection(-1);
+        } else if self.matches_single_key(&key, &kb.comments_page_down) {
+            let step = visible_lines.max(1) as isize;
+            self.move_comment_list_selection(step);
+        } else if self.matches_single_key(&key, &kb.comments_page_up) {
+            let step = visible_lines.max(1) as isize;
+            self.move_comment_list_selection(-step);
+        } else if self.matches_single_key(&key, &kb.comments_toggle_bots) {
+            // bot のコメントの折りたたみ
+            self.toggle_bot_comments();
+        } else if self.matches_single_key(&key, &kb.comments_user_info) {
+            // 選択中のコメントの作成者の情報
+            self.open_user_info();
+        } else if (self.matches_single_key(&key, &kb.open_panel)
+            || self.matches_single_key(&key, &kb.comments_mark))
+            && self.comment_list_order(self.comment_tab).0.is_empty()
+        {
+            // 表示中のコメントがない（すべて折りたたんだ bot のコメント）ときは何もしない
+        } else if review_tab && self.matches_single_key(&key, &kb.comments_mark) {
+            // Review タブ：マークしたスレッドにまとめて返信する
+            self.toggle_review_comment_mark();
+        } else if review_tab && self.matches_single_key(&key, &kb.comments_batch_reply) {
+            self.enter_batch_reply_input();
+        } else if review_tab && self.matches_single_key(&key, &kb.comments_unresolved_only) {
+            // Review タブ：未解決のスレッドだけに絞り込む
+            self.toggle_unresolved_only();
+        } else if review_tab && self.matches_single_key(&key, &kb.comments_apply_suggestion) {
+            // Review タブ：選択中のコメントの suggestion を作業ツリーに適用
+            self.request_suggestion_apply().await;
+        } else if !review_tab && self.matches_single_key(&key, &kb.comments_edit) {
+            // Discussion タブ：自分のコメントの編集・削除
+            self.edit_discussion_comment(terminal).await?;
+        } else if !review_tab && self.matches_single_key(&key, &kb.comments_delete) {
+            self.request_discussion_delete().await;
+        } else if self.matches_single_key(&key, &kb.open_panel) {
+            match self.comment_tab {
                 CommentTab::Review => {
                     self.jump_to_comment();
                 }
@@ -554,10 +620,10 @@ impl App {
                     {
                         self.discussion_comment_detail_mode = true;
                         self.discussion_comment_detail_scroll = 0;
+                        self.discussion_task_cursor = 0;
                     }
                 }
-            },
-            _ => {}
+            }
         }
         Ok(())
     }
@@ -567,40 +633,46 @@ impl App {
         key: event::KeyEvent,
         visible_lines: usize,
     ) -> Result<()> {
-        match key.code {
-            KeyCode::Char('q') | KeyCode::Esc | KeyCode::Enter => {
-                self.discussion_comment_detail_mode = false;
-                self.discussion_comment_detail_scroll = 0;
-            }
-            KeyCode::Char('j') | KeyCode::Down => {
-                self.discussion_comment_detail_scroll =
-                    self.discussion_comment_detail_scroll.saturating_add(1);
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                self.discussion_comment_detail_scroll =
-                    self.discussion_comment_detail_scroll.saturating_sub(1);
-            }
-            KeyCode::Char('J') => {
-                self.discussion_comment_detail_scroll = self
-                    .discussion_comment_detail_scroll
-                    .saturating_add(visible_lines.max(1));
-            }
-            KeyCode::Char('K') => {
-                self.discussion_comment_detail_scroll = self
-                    .discussion_comment_detail_scroll
-                    .saturating_sub(visible_lines.max(1));
-            }
-            KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.discussion_comment_detail_scroll = self
-                    .discussion_comment_detail_scroll
-                    .saturating_add(visible_lines / 2);
-            }
-            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
-                self.discussion_comment_detail_scroll = self
-                    .discussion_comment_detail_scroll
-                    .saturating_sub(visible_lines / 2);
-            }
-            _ => {}
+        let kb = self
+            .config
+            .keybindings
+            .for_view(KeybindingView::CommentList);
+
+        if self.matches_single_key(&key, &kb.quit)
+            || self.matches_single_key(&key, &kb.open_panel)
+            || key.code == KeyCode::Esc
+        {
+            self.discussion_comment_detail_mode = false;
+            self.discussion_comment_detail_scroll = 0;
+        } else if self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down {
+            self.discussion_comment_detail_scroll =
+                self.discussion_comment_detail_scroll.saturating_add(1);
+        } else if self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up {
+            self.discussion_comment_detail_scroll =
+                self.discussion_comment_detail_scroll.saturating_sub(1);
+        } else if self.matches_single_key(&key, &kb.comments_page_down) {
+            self.discussion_comment_detail_scroll = self
+                .discussion_comment_detail_scroll
+                .saturating_add(visible_lines.max(1));
+        } else if self.matches_single_key(&key, &kb.comments_page_up) {
+            self.discussion_comment_detail_scroll = self
+                .discussion_comment_detail_scroll
+                .saturating_sub(visible_lines.max(1));
+        } else if key.code == KeyCode::Tab {
+            // タスクリスト：Tab/Shift-Tab で選択、x でチェックを切り替え
+            self.move_discussion_task_cursor(true);
+        } else if key.code == KeyCode::BackTab {
+            self.move_discussion_task_cursor(false);
+        } else if self.matches_single_key(&key, &kb.comments_toggle_task) {
+            self.toggle_discussion_task();
+        } else if self.matches_single_key(&key, &kb.page_down) {
+            self.discussion_comment_detail_scroll = self
+                .discussion_comment_detail_scroll
+                .saturating_add(visible_lines / 2);
+        } else if self.matches_single_key(&key, &kb.page_up) {
+            self.discussion_comment_detail_scroll = self
+                .discussion_comment_detail_scroll
+                .saturating_sub(visible_lines / 2);
         }
         Ok(())
     }
@@ -613,6 +685,7 @@ impl App {
         };
 
         let target_path = &comment.path;
+        let target_line = comment.line;
 
         // Find file index by path
         let file_index = self.files().iter().position(|f| &f.filename == target_path);
@@ -632,7 +705,12 @@ impl App {
                 .file_comment_positions
                 .iter()
                 .find(|pos| pos.comment_index == self.selected_comment)
-                .map(|pos| pos.diff_line_index);
+                .map(|pos| pos.diff_line_index)
+                // ガターから外したコメントは位置の一覧にないため行番号から探す
+                .or_else(|| {
+                    let patch = self.files().get(idx)?.patch.as_deref()?;
+                    Self::find_diff_line_index(patch, target_line?)
+                });
 
             if let Some(line_idx) = diff_line_index {
                 self.selected_line = line_idx;
@@ -646,6 +724,11 @@ impl App {
         self.file_comment_positions.clear();
         self.file_comment_lines.clear();
 
+        // コミット単体の diff の行は PR の diff の行と対応しない
+        if self.commit_view.is_some() {
+            return;
+        }
+
         let Some(file) = self.files().get(self.selected_file) else {
             return;
         };
@@ -667,6 +750,10 @@ impl App {
             let Some(line_num) = comment.line else {
                 continue;
             };
+            // 設定でガターから外したコメント（次/前のコメントへの移動でも飛ばす）
+            if gutter_filter::is_hidden(&self.config.diff, comment, &self.review_thread_states) {
+                continue;
+            }
             if let Some(diff_index) = Self::find_diff_line_index(&patch, line_num) {
                 self.file_comment_positions.push(CommentPosition {
                     diff_line_index: diff_index,
@@ -724,23 +811,29 @@ impl App {
     }
 
     /// テキスト行がパネル幅内で折り返される表示行数を計算
+    /// （CJK・絵文字は 2 カラムとして数え、描画側の `text_width::wrap` と一致させる）
     pub(crate) fn wrapped_line_count(text: &str, panel_width: usize) -> usize {
-        if panel_width == 0 {
-            return 1;
-        }
-        let char_count = text.chars().count();
-        if char_count == 0 {
-            return 1;
-        }
-        char_count.div_ceil(panel_width)
+        crate::text_width::wrapped_line_count(text, panel_width)
     }
 
     /// コメント本文の折り返しを考慮した表示行数を計算
     pub(crate) fn comment_body_wrapped_lines(body: &str, panel_width: usize) -> usize {
-        body.lines()
-            .map(|line| Self::wrapped_line_count(line, panel_width))
-            .sum::<usize>()
-            .max(1) // 空の本文でも最低1行
+        // アラートやテーブルの整形後の行数（空の本文でも最低1行）
+        ui::markdown::render_body(body, panel_width, None).len()
+    }
+
+    /// 原文の下に表示する翻訳ブロックの行数（見出し行を含む）
+    pub(crate) fn comment_translation_lines(&self, comment_id: u64, panel_width: usize) -> usize {
+        match self.comment_translations.get(&comment_id) {
+            None => 0,
+            Some(CommentTranslation::Pending) => 1,
+            Some(CommentTranslation::Failed(e)) => {
+                Self::wrapped_line_count(&format!("Translation failed: {}", e), panel_width)
+            }
+            Some(CommentTranslation::Done(text)) => {
+                1 + Self::comment_body_wrapped_lines(text, panel_width)
+            }
+        }
     }
 
     /// コメントパネルのコンテンツ行数を計算（スクロール上限算出用）
@@ -762,6 +855,7 @@ impl App {
             }
             count += 1; // header
             count += Self::comment_body_wrapped_lines(&comment.body, panel_inner_width);
+            count += self.comment_translation_lines(comment.id, panel_inner_width);
             count += 1; // spacing
         }
         count
@@ -786,6 +880,7 @@ impl App {
             }
             offset += 1; // header
             offset += Self::comment_body_wrapped_lines(&comment.body, panel_inner_width);
+            offset += self.comment_translation_lines(comment.id, panel_inner_width);
             offset += 1; // spacing
         }
         if target > 0 {
@@ -0,0 +1,281 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::github::{ChangedFile, PrCommit};
+
+use super::{App, AppState, DataState};
+
+/// PR のコミット一覧画面の状態
+#[derive(Debug)]
+pub struct CommitListState {
+    /// 読み込み中は None
+    pub commits: Option<Vec<PrCommit>>,
+    pub error: Option<String>,
+    /// 0 は「全コミット」（PR 全体の diff）、1 以降が commits のインデックス + 1
+    pub selected: usize,
+    /// diff を読み込み中のコミット
+    pub loading_sha: Option<String>,
+    /// 一覧を閉じたときに戻る画面
+    return_state: AppState,
+}
+
+impl CommitListState {
+    /// 「全コミット」行を含めた行数
+    pub fn row_count(&self) -> usize {
+        self.commits.as_ref().map_or(0, |c| c.len()) + 1
+    }
+}
+
+/// 1 コミットの diff を表示中の状態
+#[derive(Debug)]
+pub struct CommitView {
+    pub commit: PrCommit,
+    /// PR 全体のファイル一覧（コミット表示を抜けるときに戻す）
+    pr_files: Vec<ChangedFile>,
+    pr_selected_file: usize,
+}
+
+impl CommitView {
+    /// PR 全体のファイル一覧
+    pub(crate) fn pr_files(&self) -> &[ChangedFile] {
+        &self.pr_files
+    }
+}
+
+impl App {
+    /// PR のコミット一覧を開く
+    pub(crate) fn open_commit_list(&mut self) {
+        if self.local_mode {
+            self.submission_result =
+                Some((false, "Commit list is only available for PRs".to_string()));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+
+        // 表示中のコミットがあれば、取得後にその行を選択する
+        self.commit_list = Some(CommitListState {
+            commits: None,
+            error: None,
+            selected: 0,
+            loading_sha: None,
+            return_state: self.state,
+        });
+        self.state = AppState::CommitList;
+
+        let (tx, rx) = mpsc::channel(1);
+        self.commit_list_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = crate::github::fetch_pr_commits(&repo, pr_number)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn poll_commit_list_updates(&mut self) {
+        self.poll_commits();
+        self.poll_commit_diff();
+    }
+
+    fn poll_commits(&mut self) {
+        let Some((origin_pr, ref mut rx)) = self.commit_list_receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.commit_list_receiver = None;
+                return;
+            }
+        };
+        self.commit_list_receiver = None;
+        if self.pr_number != Some(origin_pr) {
+            return;
+        }
+        let current_sha = self.commit_view.as_ref().map(|v| v.commit.sha.clone());
+        let Some(ref mut list) = self.commit_list else {
+            return;
+        };
+        match result {
+            Ok(commits) => {
+                list.selected = current_sha
+                    .and_then(|sha| commits.iter().position(|c| c.sha == sha))
+                    .map_or(0, |i| i + 1);
+                list.commits = Some(commits);
+            }
+            Err(e) => list.error = Some(e),
+        }
+    }
+
+    fn poll_commit_diff(&mut self) {
+        let Some((origin_pr, ref mut rx)) = self.commit_diff_receiver else {
+            return;
+        };
+        let (commit, result) = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.commit_diff_receiver = None;
+                return;
+            }
+        };
+        self.commit_diff_receiver = None;
+        if let Some(ref mut list) = self.commit_list {
+            list.loading_sha = None;
+        }
+        if self.pr_number != Some(origin_pr) {
+            return;
+        }
+        match result {
+            Ok(mut files) => {
+                self.path_scope.retain(&mut files, |f| &f.filename);
+                self.enter_commit_view(commit, files);
+            }
+            Err(e) => {
+                self.submission_result = Some((false, format!("Failed to load commit: {}", e)));
+                self.submission_result_time = Some(Instant::now());
+            }
+        }
+    }
+
+    /// ファイル一覧をコミットの変更ファイルに差し替える
+    pub(crate) fn enter_commit_view(&mut self, commit: PrCommit, files: Vec<ChangedFile>) {
+        let DataState::Loaded {
+            files: ref mut current,
+            ..
+        } = self.data_state
+        else {
+            return;
+        };
+        let previous = std::mem::replace(current, files);
+        // 別のコミットから切り替えた場合は PR 全体のファイル一覧を保持し続ける
+        let (pr_files, pr_selected_file) = match self.commit_view.take() {
+            Some(view) => (view.pr_files, view.pr_selected_file),
+            None => (previous, self.selected_file),
+        };
+        self.commit_view = Some(CommitView {
+            commit,
+            pr_files,
+            pr_selected_file,
+        });
+        self.reset_view_for_file_swap(0);
+        self.commit_list = None;
+        self.state = AppState::FileList;
+    }
+
+    /// コミット表示を抜けて PR 全体の diff に戻る
+    pub(crate) fn exit_commit_view(&mut self) {
+        let Some(view) = self.commit_view.take() else {
+            return;
+        };
+        if let DataState::Loaded { ref mut files, .. } = self.data_state {
+            *files = view.pr_files;
+        }
+        self.reset_view_for_file_swap(view.pr_selected_file);
+    }
+
+    /// ファイル一覧の入れ替えに合わせて diff 関連の状態を破棄する
+    fn reset_view_for_file_swap(&mut self, selected_file: usize) {
+        self.selected_file = selected_file.min(self.files().len().saturating_sub(1));
+        self.file_list_scroll_offset = 0;
+        self.file_list_filter = None;
+        self.selected_line = 0;
+        self.scroll_offset = 0;
+        self.multiline_selection = None;
+        self.comment_panel_open = false;
+        self.comment_panel_scroll = 0;
+        self.diff_cache = None;
+        self.diff_cache_receiver = None;
+        self.prefetch_receiver = None;
+        self.highlighted_cache_store.clear();
+        self.file_view_states.clear();
+        self.base_file_patches.clear();
+        self.lfs_pointer_patches.clear();
+        self.vendored_expanded.clear();
+        self.update_diff_line_count();
+        self.update_file_comment_positions();
+    }
+
+    fn close_commit_list(&mut self) {
+        if let Some(list) = self.commit_list.take() {
+            self.state = list.return_state;
+        }
+        self.commit_list_receiver = None;
+        self.commit_diff_receiver = None;
+    }
+
+    /// 選択した行のコミットを表示する（0 行目は PR 全体）
+    fn select_commit(&mut self) {
+        let Some(ref list) = self.commit_list else {
+            return;
+        };
+        if list.loading_sha.is_some() {
+            return;
+        }
+        if list.selected == 0 {
+            self.exit_commit_view();
+            self.close_commit_list();
+            self.state = AppState::FileList;
+            return;
+        }
+        let Some(commit) = list
+            .commits
+            .as_ref()
+            .and_then(|c| c.get(list.selected - 1))
+            .cloned()
+        else {
+            return;
+        };
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+        if let Some(ref mut list) = self.commit_list {
+            list.loading_sha = Some(commit.sha.clone());
+        }
+
+        let (tx, rx) = mpsc::channel(1);
+        self.commit_diff_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        let working_dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let result = crate::loader::fetch_commit_diff(&repo, &commit, working_dir.as_deref())
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((commit, result)).await;
+        });
+    }
+
+    pub(crate) fn handle_commit_list_input(&mut self, key: KeyEvent) {
+        let kb = &self.config.keybindings;
+        if key.code == KeyCode::Esc || self.matches_single_key(&key, &kb.quit) {
+            self.close_commit_list();
+            return;
+        }
+        if self.matches_single_key(&key, &kb.open_panel) {
+            self.select_commit();
+            return;
+        }
+        let down = self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down;
+        let up = self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up;
+        let last = self.matches_single_key(&key, &kb.jump_to_last);
+
+        let Some(ref mut list) = self.commit_list else {
+            return;
+        };
+        let max = list.row_count() - 1;
+        if down {
+            list.selected = (list.selected + 1).min(max);
+        } else if up {
+            list.selected = list.selected.saturating_sub(1);
+        } else if last {
+            list.selected = max;
+        }
+    }
+}
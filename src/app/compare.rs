@@ -0,0 +1,151 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::compare::Comparison;
+
+use super::{App, AppState};
+
+/// PR の head 版と作業ツリー版の比較画面の状態
+#[derive(Debug)]
+pub struct LocalCompareState {
+    pub path: String,
+    /// 読み込み中は None
+    pub comparison: Option<Comparison>,
+    pub error: Option<String>,
+    pub scroll: usize,
+    /// 比較画面を閉じたときに戻る画面
+    return_state: AppState,
+}
+
+impl App {
+    /// 選択中のファイルについて、PR の head 版と作業ツリー版の比較画面を開く
+    pub(crate) fn open_local_compare(&mut self) {
+        if self.local_mode {
+            self.submission_result = Some((
+                false,
+                "Compare with local is only available for PRs".to_string(),
+            ));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+        let Some(path) = self
+            .files()
+            .get(self.selected_file)
+            .map(|f| f.filename.clone())
+        else {
+            return;
+        };
+        let Some(head_sha) = self.pr().map(|pr| pr.head.sha.clone()) else {
+            return;
+        };
+
+        self.local_compare = Some(LocalCompareState {
+            path: path.clone(),
+            comparison: None,
+            error: None,
+            scroll: 0,
+            return_state: self.state,
+        });
+        self.state = AppState::LocalCompare;
+
+        let (tx, rx) = mpsc::channel(1);
+        self.local_compare_receiver = Some(rx);
+        let repo = self.repo.clone();
+        let working_dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let result = crate::compare::load(&repo, &head_sha, working_dir.as_deref(), &path)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn poll_local_compare_updates(&mut self) {
+        let Some(ref mut rx) = self.local_compare_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.local_compare_receiver = None;
+                let Some(ref mut view) = self.local_compare else {
+                    return;
+                };
+                match result {
+                    Ok(comparison) => {
+                        // 最初の食い違いから表示する
+                        view.scroll = comparison
+                            .divergent_blocks()
+                            .first()
+                            .map(|&row| row.saturating_sub(3))
+                            .unwrap_or(0);
+                        view.comparison = Some(comparison);
+                    }
+                    Err(e) => view.error = Some(e),
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.local_compare_receiver = None;
+            }
+        }
+    }
+
+    pub(crate) fn close_local_compare(&mut self) {
+        if let Some(view) = self.local_compare.take() {
+            self.state = view.return_state;
+        }
+        self.local_compare_receiver = None;
+    }
+
+    pub(crate) fn handle_local_compare_input(&mut self, key: KeyEvent, visible_rows: usize) {
+        let kb = &self.config.keybindings;
+        if key.code == KeyCode::Esc || self.matches_single_key(&key, &kb.quit) {
+            self.close_local_compare();
+            return;
+        }
+        let down = self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down;
+        let up = self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up;
+        let page_down = self.matches_single_key(&key, &kb.page_down);
+        let page_up = self.matches_single_key(&key, &kb.page_up);
+        let last = self.matches_single_key(&key, &kb.jump_to_last);
+        let next = self.matches_single_key(&key, &kb.next_comment);
+        let prev = self.matches_single_key(&key, &kb.prev_comment);
+
+        let Some(ref mut view) = self.local_compare else {
+            return;
+        };
+        let Some((row_count, blocks)) = view
+            .comparison
+            .as_ref()
+            .map(|c| (c.rows.len(), c.divergent_blocks()))
+        else {
+            return;
+        };
+        let max_scroll = row_count.saturating_sub(visible_rows.max(1));
+        let page = (visible_rows / 2).max(1);
+        // n / N で食い違っている箇所を移動する（前に 3 行残して表示）
+        let current = view.scroll + 3;
+
+        if down {
+            view.scroll = (view.scroll + 1).min(max_scroll);
+        } else if up {
+            view.scroll = view.scroll.saturating_sub(1);
+        } else if page_down {
+            view.scroll = (view.scroll + page).min(max_scroll);
+        } else if page_up {
+            view.scroll = view.scroll.saturating_sub(page);
+        } else if last {
+            view.scroll = max_scroll;
+        } else if next {
+            if let Some(&row) = blocks.iter().find(|&&row| row > current) {
+                view.scroll = row.saturating_sub(3).min(max_scroll);
+            }
+        } else if prev {
+            if let Some(&row) = blocks.iter().rev().find(|&&row| row < current) {
+                view.scroll = row.saturating_sub(3);
+            }
+        }
+    }
+}
@@ -0,0 +1,250 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::conflict::{ConflictFile, Resolution};
+
+use super::{App, AppState};
+
+/// コンフリクト解消画面の状態
+#[derive(Debug)]
+pub struct ConflictResolveState {
+    pub path: String,
+    /// 読み込み中は None
+    pub file: Option<ConflictFile>,
+    pub error: Option<String>,
+    /// 表示中のコンフリクト
+    pub selected: usize,
+    /// コンフリクト内のスクロール位置
+    pub scroll: usize,
+    /// 書き戻し中
+    pub writing: bool,
+    /// 解消画面を閉じたときに戻る画面
+    return_state: AppState,
+}
+
+impl ConflictResolveState {
+    /// 表示中のコンフリクトの ours / base / theirs のうち最も長い行数
+    fn block_height(&self) -> usize {
+        self.file
+            .as_ref()
+            .and_then(|f| f.conflicts().nth(self.selected))
+            .map_or(0, |b| {
+                let base = b.base.as_ref().map_or(0, |base| base.len());
+                b.ours.len().max(b.theirs.len()).max(base)
+            })
+    }
+}
+
+impl App {
+    /// 選択中のファイルのコンフリクト解消画面を開く（Local Mode のみ）
+    pub(crate) fn open_conflict_resolve(&mut self) {
+        if !self.local_mode {
+            self.submission_result = Some((
+                false,
+                "Conflict resolution is only available in local mode".to_string(),
+            ));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+        let Some(path) = self
+            .files()
+            .get(self.selected_file)
+            .map(|f| f.filename.clone())
+        else {
+            return;
+        };
+
+        self.conflict_resolve = Some(ConflictResolveState {
+            path: path.clone(),
+            file: None,
+            error: None,
+            selected: 0,
+            scroll: 0,
+            writing: false,
+            return_state: self.state,
+        });
+        self.state = AppState::ConflictResolve;
+
+        let (tx, rx) = mpsc::channel(1);
+        self.conflict_receiver = Some(rx);
+        let working_dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let result = crate::conflict::load(working_dir.as_deref(), &path)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn poll_conflict_updates(&mut self) {
+        if let Some(ref mut rx) = self.conflict_receiver {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.conflict_receiver = None;
+                    if let Some(ref mut view) = self.conflict_resolve {
+                        match result {
+                            Ok(file) => view.file = Some(file),
+                            Err(e) => view.error = Some(e),
+                        }
+                    }
+                }
+                Err(mpsc::error::TryRecvError::Empty) => {}
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.conflict_receiver = None;
+                }
+            }
+        }
+
+        let Some(ref mut rx) = self.conflict_write_receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.conflict_write_receiver = None;
+                return;
+            }
+        };
+        self.conflict_write_receiver = None;
+        match result {
+            Ok(message) => {
+                self.close_conflict_resolve();
+                self.submission_result = Some((true, message));
+                // 書き戻した内容で diff を取り直す
+                self.retry_load();
+            }
+            Err(e) => {
+                if let Some(ref mut view) = self.conflict_resolve {
+                    view.writing = false;
+                }
+                self.submission_result = Some((false, format!("Failed to write: {}", e)));
+            }
+        }
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    pub(crate) fn close_conflict_resolve(&mut self) {
+        if let Some(view) = self.conflict_resolve.take() {
+            self.state = view.return_state;
+        }
+        self.conflict_receiver = None;
+        self.conflict_write_receiver = None;
+    }
+
+    /// 全コンフリクトを解消していれば作業ツリーに書き戻す
+    fn write_conflict_resolution(&mut self) {
+        let Some(ref mut view) = self.conflict_resolve else {
+            return;
+        };
+        let Some(ref file) = view.file else {
+            return;
+        };
+        if view.writing {
+            return;
+        }
+        let remaining = file.conflict_count() - file.resolved_count();
+        if remaining > 0 {
+            self.submission_result =
+                Some((false, format!("{} conflict(s) left to resolve", remaining)));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+        view.writing = true;
+        let file = file.clone();
+
+        let (tx, rx) = mpsc::channel(1);
+        self.conflict_write_receiver = Some(rx);
+        let working_dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let result = crate::conflict::write_back(working_dir.as_deref(), &file)
+                .await
+                .map(|()| {
+                    format!(
+                        "Resolved {} conflict(s) in {}",
+                        file.conflict_count(),
+                        file.path
+                    )
+                })
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn handle_conflict_resolve_input(&mut self, key: KeyEvent, visible_rows: usize) {
+        let kb = &self.config.keybindings;
+        if key.code == KeyCode::Esc || self.matches_single_key(&key, &kb.quit) {
+            self.close_conflict_resolve();
+            return;
+        }
+        if key.code == KeyCode::Char('w') {
+            self.write_conflict_resolution();
+            return;
+        }
+        let down = self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down;
+        let up = self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up;
+        let next = self.matches_single_key(&key, &kb.next_comment);
+        let prev = self.matches_single_key(&key, &kb.prev_comment);
+
+        let Some(ref mut view) = self.conflict_resolve else {
+            return;
+        };
+        if view.writing {
+            return;
+        }
+        let Some(count) = view.file.as_ref().map(|f| f.conflict_count()) else {
+            return;
+        };
+        let max_scroll = view.block_height().saturating_sub(visible_rows.max(1));
+
+        let resolution = match key.code {
+            KeyCode::Char('o') => Some(Some(Resolution::Ours)),
+            KeyCode::Char('t') => Some(Some(Resolution::Theirs)),
+            KeyCode::Char('u') => Some(None),
+            _ => None,
+        };
+        if let Some(resolution) = resolution {
+            let selected = view.selected;
+            if let Some(block) = view.file.as_mut().and_then(|f| f.conflict_mut(selected)) {
+                block.resolution = resolution;
+            }
+            // 選んだら次の未解消のコンフリクトへ進む
+            if resolution.is_some() {
+                let next_unresolved = view.file.as_ref().and_then(|f| {
+                    let unresolved: Vec<usize> = f
+                        .conflicts()
+                        .enumerate()
+                        .filter(|(_, b)| b.resolution.is_none())
+                        .map(|(i, _)| i)
+                        .collect();
+                    unresolved
+                        .iter()
+                        .find(|&&i| i > selected)
+                        .or_else(|| unresolved.first())
+                        .copied()
+                });
+                if let Some(i) = next_unresolved {
+                    view.selected = i;
+                    view.scroll = 0;
+                }
+            }
+            return;
+        }
+
+        if down {
+            view.scroll = (view.scroll + 1).min(max_scroll);
+        } else if up {
+            view.scroll = view.scroll.saturating_sub(1);
+        } else if next {
+            if view.selected + 1 < count {
+                view.selected += 1;
+                view.scroll = 0;
+            }
+        } else if prev && view.selected > 0 {
+            view.selected -= 1;
+            view.scroll = 0;
+        }
+    }
+}
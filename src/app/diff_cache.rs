@@ -2,6 +2,7 @@ use tokio::sync::mpsc;
 
 use crate::github::{ChangedFile, PullRequest};
 use crate::syntax::ParserPool;
+use crate::ui::diff_view::LAZY_HIGHLIGHT_CHUNK;
 
 use super::types::*;
 use super::{App, DataState, MAX_HIGHLIGHTED_CACHE_ENTRIES};
@@ -51,6 +52,7 @@ impl App {
             self.load_review_comments();
         }
         self.update_file_comment_positions();
+        self.restore_file_view_state();
         self.request_lazy_diff();
         self.ensure_diff_cache();
     }
@@ -109,9 +111,14 @@ impl App {
         }
 
         // 3. キャッシュミス: プレーンキャッシュを即座に構築（~1ms）
+        // LFS ポインタはメタデータ表示に、Latin-1 のバイトは文字に置き換える
+        // （行数は変わらないがハッシュは元の patch で持つ）
+        let patch_hash = hash_string(&patch);
+        let patch = crate::encoding::display_patch(&crate::lfs::display_patch(&patch)).into_owned();
         let tab_width = self.config.diff.tab_width;
         let mut plain_cache = crate::ui::diff_view::build_plain_diff_cache(&patch, tab_width);
         plain_cache.file_index = file_index;
+        plain_cache.patch_hash = patch_hash;
         self.diff_cache = Some(plain_cache);
 
         // 完全版キャッシュをバックグラウンドで構築
@@ -131,7 +138,57 @@ impl App {
                 tab_width,
             );
             cache.file_index = file_index;
+            cache.patch_hash = patch_hash;
             let _ = tx.try_send(cache);
         });
     }
+
+    /// 大きな diff のプレーンキャッシュで、表示位置のチャンクと前後のチャンクだけを先にハイライトする
+    ///
+    /// 残りはバックグラウンドで構築中の完全版キャッシュが届いたときに差し替わる。
+    /// 5 万行の patch でも開いてすぐにスクロールでき、見ている場所には色が付く
+    pub(crate) fn highlight_visible_lines(&mut self) {
+        let Some(ref cache) = self.diff_cache else {
+            return;
+        };
+        if cache.highlighted {
+            return;
+        }
+        let current = self.scroll_offset / LAZY_HIGHLIGHT_CHUNK;
+        let chunks: Vec<usize> = (current.saturating_sub(1)..=current + 1)
+            .filter(|&chunk| cache.highlighted_chunks.get(chunk) == Some(&false))
+            .collect();
+        if chunks.is_empty() {
+            return;
+        }
+        let Some(file) = self.files().get(cache.file_index) else {
+            return;
+        };
+        let Some(ref patch) = file.patch else {
+            return;
+        };
+        if hash_string(patch) != cache.patch_hash {
+            return;
+        }
+        // プレーンキャッシュと同じく表示用に置き換えた patch をハイライトする
+        let patch = crate::encoding::display_patch(&crate::lfs::display_patch(patch)).into_owned();
+        let filename = file.filename.clone();
+        let theme = self.config.diff.theme.clone();
+        let tab_width = self.config.diff.tab_width;
+
+        let Some(cache) = self.diff_cache.as_mut() else {
+            return;
+        };
+        for chunk in chunks {
+            crate::ui::diff_view::highlight_cache_chunk(
+                cache,
+                &patch,
+                chunk,
+                &filename,
+                &theme,
+                &mut self.viewport_parser_pool,
+                tab_width,
+            );
+        }
+    }
 }
@@ -0,0 +1,187 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+use std::time::Instant;
+
+use crate::audit::{self, AuditAction, AuditEntry};
+use crate::cache::PrCacheKey;
+use crate::github::{self, comment::DiscussionComment};
+use crate::ui::TuiTerminal;
+
+use super::App;
+
+impl App {
+    fn set_discussion_edit_status(&mut self, ok: bool, message: String) {
+        self.submission_result = Some((ok, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 選択中の Discussion コメントが自分のものなら複製して返す。
+    /// 認証ユーザーをまだ取得していなければここで取得する
+    async fn own_selected_discussion(&mut self, verb: &str) -> Option<DiscussionComment> {
+        let comment = self
+            .discussion_comments
+            .as_ref()?
+            .get(self.selected_discussion_comment)?
+            .clone();
+        if self.viewer_login.is_none() {
+            match github::comment::fetch_viewer_login().await {
+                Ok(login) => self.viewer_login = Some(login),
+                Err(e) => {
+                    self.set_discussion_edit_status(false, format!("Failed: {:#}", e));
+                    return None;
+                }
+            }
+        }
+        if self.viewer_login.as_deref() != Some(comment.user.login.as_str()) {
+            self.set_discussion_edit_status(
+                false,
+                format!("Only your own comments can be {}", verb),
+            );
+            return None;
+        }
+        if comment.node_id.is_empty() {
+            // 古いキャッシュから読んだコメントには GraphQL の ID がない
+            self.set_discussion_edit_status(
+                false,
+                "Comment ID unavailable, reopen the PR and try again".to_string(),
+            );
+            return None;
+        }
+        Some(comment)
+    }
+
+    /// Discussion コメントを書き換え、開き直したときに古い内容が出ないようキャッシュも更新する
+    pub(crate) fn update_discussion_comments(
+        &mut self,
+        update: impl FnOnce(&mut Vec<DiscussionComment>),
+    ) {
+        let Some(ref mut comments) = self.discussion_comments else {
+            return;
+        };
+        update(comments);
+        if let Some(pr_number) = self.pr_number {
+            let cache_key = PrCacheKey {
+                repo: self.repo.clone(),
+                pr_number,
+            };
+            self.session_cache
+                .put_discussion_comments(cache_key, comments.clone());
+        }
+    }
+
+    /// e: 自分のコメントを $EDITOR で編集する
+    pub(crate) async fn edit_discussion_comment(
+        &mut self,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        let Some(comment) = self.own_selected_discussion("edited").await else {
+            return Ok(());
+        };
+
+        terminal.suspend()?;
+        let editor_result =
+            crate::editor::open_comment_edit_editor(self.config.editor.as_deref(), &comment.body);
+        // エディタの成否に関わらずターミナルを再セットアップ
+        terminal.resume()?;
+
+        let body = match editor_result {
+            Ok(Some(body)) => body.trim().to_string(),
+            Ok(None) => {
+                self.set_discussion_edit_status(false, "Edit cancelled".to_string());
+                return Ok(());
+            }
+            Err(e) => {
+                self.set_discussion_edit_status(false, format!("Editor failed: {}", e));
+                return Ok(());
+            }
+        };
+        if body == comment.body.trim() {
+            self.set_discussion_edit_status(true, "No changes".to_string());
+            return Ok(());
+        }
+
+        let result = github::comment::edit_discussion_comment(&comment.node_id, &body).await;
+        audit::record(&AuditEntry::new(
+            &self.repo,
+            self.pr_number(),
+            AuditAction::EditComment,
+            Some(format!("comment {}", comment.id)),
+            &result,
+        ));
+        match result {
+            Ok(edited) => {
+                self.update_discussion_comments(|comments| {
+                    if let Some(target) = comments.iter_mut().find(|c| c.id == comment.id) {
+                        target.body = edited.body;
+                        if edited.last_edited_at.is_some() {
+                            target.updated_at = edited.last_edited_at;
+                        }
+                    }
+                });
+                self.discussion_task_cursor = 0;
+                self.set_discussion_edit_status(true, "Comment updated".to_string());
+            }
+            Err(e) => {
+                self.set_discussion_edit_status(false, format!("Failed to edit comment: {:#}", e))
+            }
+        }
+        Ok(())
+    }
+
+    /// D: 自分のコメントの削除を確認する
+    pub(crate) async fn request_discussion_delete(&mut self) {
+        if let Some(comment) = self.own_selected_discussion("deleted").await {
+            self.pending_discussion_delete = Some(comment.id);
+        }
+    }
+
+    /// 削除の確認中か
+    pub fn is_pending_discussion_delete(&self) -> bool {
+        self.pending_discussion_delete.is_some()
+    }
+
+    /// 削除の確認中のキー入力。y で削除し、それ以外のキーで取り消す
+    pub(crate) async fn confirm_discussion_delete(&mut self, key: &KeyEvent) {
+        let Some(comment_id) = self.pending_discussion_delete.take() else {
+            return;
+        };
+        if key.code != KeyCode::Char('y') {
+            self.set_discussion_edit_status(false, "Delete cancelled".to_string());
+            return;
+        }
+        let Some(node_id) = self
+            .discussion_comments
+            .as_ref()
+            .and_then(|comments| comments.iter().find(|c| c.id == comment_id))
+            .map(|comment| comment.node_id.clone())
+        else {
+            return;
+        };
+
+        let result = github::comment::delete_discussion_comment(&node_id).await;
+        audit::record(&AuditEntry::new(
+            &self.repo,
+            self.pr_number(),
+            AuditAction::DeleteComment,
+            Some(format!("comment {}", comment_id)),
+            &result,
+        ));
+        match result {
+            Ok(()) => {
+                self.update_discussion_comments(|comments| {
+                    comments.retain(|c| c.id != comment_id);
+                });
+                let remaining = self.discussion_comments.as_ref().map_or(0, Vec::len);
+                self.selected_discussion_comment = self
+                    .selected_discussion_comment
+                    .min(remaining.saturating_sub(1));
+                self.discussion_comment_detail_mode = false;
+                self.discussion_comment_detail_scroll = 0;
+                self.set_discussion_edit_status(true, "Comment deleted".to_string());
+            }
+            Err(e) => {
+                self.set_discussion_edit_status(false, format!("Failed to delete comment: {:#}", e))
+            }
+        }
+    }
+}
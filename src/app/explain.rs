@@ -0,0 +1,121 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::explain::{Explainer, FileChange};
+
+use super::App;
+
+/// ファイルの変更の説明
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FileExplanation {
+    Pending,
+    Done(String),
+    Failed(String),
+}
+
+/// 説明のキャッシュのキー。patch が変わらない限り同じ説明を使う
+fn explanation_key(filename: &str, patch: &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    filename.hash(&mut hasher);
+    patch.hash(&mut hasher);
+    hasher.finish()
+}
+
+impl App {
+    pub(crate) fn selected_explanation_key(&self) -> Option<u64> {
+        let file = self.files().get(self.selected_file)?;
+        Some(explanation_key(&file.filename, file.patch.as_deref()?))
+    }
+
+    /// 選択中のファイルの説明（取得を始めていなければ None）
+    pub fn current_file_explanation(&self) -> Option<&FileExplanation> {
+        self.file_explanations
+            .get(&self.selected_explanation_key()?)
+    }
+
+    /// ge: 選択中のファイルの変更の説明をサイドパネルに表示する。
+    /// 説明を表示中なら閉じ、まだなければエージェントに頼む（失敗していればやり直す）
+    pub(crate) fn toggle_file_explanation(&mut self) {
+        let Some(key) = self.selected_explanation_key() else {
+            self.set_explain_status(false, "No diff available for this file".to_string());
+            return;
+        };
+        let existing = self.file_explanations.get(&key);
+        if self.explain_panel_open && existing.is_some() {
+            self.explain_panel_open = false;
+            return;
+        }
+        self.explain_panel_open = true;
+        if matches!(
+            existing,
+            Some(FileExplanation::Pending | FileExplanation::Done(_))
+        ) {
+            return;
+        }
+        if self.explain_receiver.is_some() {
+            self.set_explain_status(false, "Another explanation is in progress".to_string());
+            return;
+        }
+        let explainer = match Explainer::from_config(&self.config, &self.repo) {
+            Ok(explainer) => explainer,
+            Err(e) => {
+                self.set_explain_status(false, format!("Explanation unavailable: {}", e));
+                return;
+            }
+        };
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        let change = FileChange {
+            pr_title: self
+                .pr()
+                .filter(|_| !self.local_mode)
+                .map(|pr| pr.title.clone()),
+            filename: file.filename.clone(),
+            status: file.status.clone(),
+            patch: file.patch.clone().unwrap_or_default(),
+        };
+
+        self.file_explanations.insert(key, FileExplanation::Pending);
+        let (tx, rx) = mpsc::channel(1);
+        self.explain_receiver = Some(rx);
+        tokio::spawn(async move {
+            let result = explainer
+                .explain(&change)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((key, result)).await;
+        });
+    }
+
+    pub(crate) fn poll_explain_updates(&mut self) {
+        let Some(ref mut rx) = self.explain_receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok((key, result)) => {
+                self.explain_receiver = None;
+                let explanation = match result {
+                    Ok(text) => FileExplanation::Done(text),
+                    Err(e) => FileExplanation::Failed(e),
+                };
+                self.file_explanations.insert(key, explanation);
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.explain_receiver = None;
+                self.file_explanations
+                    .retain(|_, e| *e != FileExplanation::Pending);
+            }
+        }
+    }
+
+    fn set_explain_status(&mut self, ok: bool, message: String) {
+        self.submission_result = Some((ok, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
@@ -0,0 +1,179 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use super::{App, DataState};
+
+impl App {
+    /// 選択中のファイルが patch を差し替えたプレビュー（LFS 実体 / base 版）か、
+    /// 折りたたんだ vendored 依存やコミット単体の diff を表示中か。
+    /// いずれも表示中の行が PR の diff の行と対応しないためコメントできない
+    pub(crate) fn is_file_preview(&self, file_index: usize) -> bool {
+        self.commit_view.is_some()
+            || self.is_lfs_preview(file_index)
+            || self.is_base_file_view(file_index)
+            || self.is_vendored_collapsed(file_index)
+    }
+
+    pub(crate) fn is_base_file_view(&self, file_index: usize) -> bool {
+        self.files()
+            .get(file_index)
+            .is_some_and(|f| self.base_file_patches.contains_key(&f.filename))
+    }
+
+    /// diff ヘッダーに添える表示（ファイル全体の表示 / LFS / vendored）
+    pub fn preview_label(&self, file_index: usize) -> Option<&'static str> {
+        if self.is_base_file_view(file_index) {
+            let removed = self.files()[file_index].status == "removed";
+            return Some(if removed { "base version" } else { "full file" });
+        }
+        self.lfs_label(file_index)
+            .or_else(|| self.vendored_label(file_index))
+    }
+
+    /// ファイル全体を表示する。削除されたファイルは base 版を、それ以外は head 版に
+    /// diff の追加・削除行を差し込んだものを表示する。表示中なら元の patch に戻す
+    pub(crate) fn toggle_base_file_view(&mut self) {
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        let filename = file.filename.clone();
+        let status = file.status.clone();
+        let patch = file.patch.clone();
+
+        if let Some(original) = self.base_file_patches.remove(&filename) {
+            // リロードで元の patch に戻っていればそのまま取得し直す
+            if patch != original {
+                self.replace_file_patch(&filename, original);
+                return;
+            }
+        }
+
+        let removed = status == "removed";
+        // 削除以外は diff の行を差し込むので、patch が省略された大きなファイルには使えない
+        let diff_patch = match patch {
+            Some(patch) => patch,
+            None if removed => String::new(),
+            None => {
+                self.set_preview_status(false, "No diff available for this file".to_string());
+                return;
+            }
+        };
+        if self.base_file_receiver.is_some() {
+            self.set_preview_status(false, "File is already being fetched".to_string());
+            return;
+        }
+
+        // ローカルモードは作業ツリーと HEAD の diff なので、削除前の内容は HEAD に、
+        // 変更後の内容は作業ツリーにある
+        let rev = match (self.local_mode, removed) {
+            (true, true) => Some("HEAD".to_string()),
+            (true, false) => None,
+            (false, true) => self.pr().map(|pr| pr.base.sha.clone()),
+            (false, false) => self.pr().map(|pr| pr.head.sha.clone()),
+        };
+        if rev.is_none() && !self.local_mode {
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel(1);
+        self.base_file_receiver = Some(rx);
+        let side = if removed { "base" } else { "head" };
+        self.set_preview_status(true, format!("Fetching {} at {}...", filename, side));
+        let repo = self.repo.clone();
+        let working_dir = self.working_dir.clone();
+        let local_mode = self.local_mode;
+        tokio::spawn(async move {
+            let result = match rev {
+                Some(rev) if local_mode => {
+                    crate::file_content::fetch_from_git(working_dir.as_deref(), &rev, &filename)
+                        .await
+                }
+                Some(rev) => crate::file_content::fetch_from_github(&repo, &rev, &filename).await,
+                None => crate::file_content::read_worktree(working_dir.as_deref(), &filename).await,
+            };
+            let result = result
+                .and_then(|content| {
+                    if removed {
+                        return Ok(crate::file_content::full_file_patch(&content));
+                    }
+                    crate::file_content::full_file_diff_patch(&content, &diff_patch)
+                        .ok_or_else(|| anyhow::anyhow!("Cannot show the full file for this diff"))
+                })
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((filename, result)).await;
+        });
+    }
+
+    pub(crate) fn poll_base_file_updates(&mut self) {
+        let Some(ref mut rx) = self.base_file_receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok((filename, result)) => {
+                self.base_file_receiver = None;
+                match result {
+                    Ok(patch) => {
+                        // 大きなファイルは patch 自体が省略されていることがある
+                        let Some((original, removed)) = self
+                            .files()
+                            .iter()
+                            .find(|f| f.filename == filename)
+                            .map(|f| (f.patch.clone(), f.status == "removed"))
+                        else {
+                            return;
+                        };
+                        let side = if removed { "at base" } else { "in full" };
+                        self.base_file_patches.insert(filename.clone(), original);
+                        self.replace_file_patch(&filename, Some(patch));
+                        self.set_preview_status(
+                            true,
+                            format!("Showing {} {} (read-only)", filename, side),
+                        );
+                    }
+                    Err(e) => self.set_preview_status(false, e),
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.base_file_receiver = None;
+            }
+        }
+    }
+
+    /// ファイルの patch を差し替え、diff キャッシュを作り直す
+    pub(super) fn replace_file_patch(&mut self, filename: &str, patch: Option<String>) {
+        let mut replaced_index = None;
+        if let DataState::Loaded { ref mut files, .. } = self.data_state {
+            if let Some((index, file)) = files
+                .iter_mut()
+                .enumerate()
+                .find(|(_, f)| f.filename == filename)
+            {
+                file.patch = patch;
+                replaced_index = Some(index);
+            }
+        }
+        let Some(index) = replaced_index else {
+            return;
+        };
+
+        self.highlighted_cache_store.remove(&index);
+        if index == self.selected_file {
+            self.diff_cache = None;
+            self.diff_cache_receiver = None;
+            self.selected_line = 0;
+            self.scroll_offset = 0;
+            self.multiline_selection = None;
+            self.update_diff_line_count();
+            self.update_file_comment_positions();
+            self.ensure_diff_cache();
+        }
+    }
+
+    fn set_preview_status(&mut self, ok: bool, message: String) {
+        self.submission_result = Some((ok, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
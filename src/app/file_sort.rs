@@ -0,0 +1,139 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::PathBuf;
+use std::time::{Instant, SystemTime};
+
+use crate::file_sort::{self, FileSort};
+use crate::github::ChangedFile;
+
+use super::App;
+
+/// ファイル一覧の表示順。選択の移動や描画はこの順で行い、
+/// `selected_file` などは元のファイルのインデックスのまま扱う
+#[derive(Debug, Clone, Default)]
+pub struct FileOrder {
+    pub sort: FileSort,
+    /// 表示順に並べたファイルのインデックス
+    indices: Vec<usize>,
+    /// ファイルのインデックス → 表示位置
+    positions: Vec<usize>,
+    /// 並べ替えたときのファイル一覧のシグネチャ（変わったら並べ直す）
+    signature: Option<u64>,
+    /// 作業ツリーのルート（Recent で更新日時を調べるのに使う）
+    local_root: Option<PathBuf>,
+}
+
+/// 並び順に影響する内容のシグネチャ
+fn signature(files: &[ChangedFile], sort: FileSort) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    sort.hash(&mut hasher);
+    for file in files {
+        file.filename.hash(&mut hasher);
+        file.status.hash(&mut hasher);
+        file.additions.hash(&mut hasher);
+        file.deletions.hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+impl App {
+    /// ファイル一覧が変わっていれば表示順を計算し直す。描画の前と選択の移動の前に呼ぶ
+    pub(crate) fn sync_file_order(&mut self) {
+        let sort = self.file_order.sort;
+        let signature = signature(self.files(), sort);
+        if self.file_order.signature == Some(signature) {
+            return;
+        }
+        let modified = if sort == FileSort::Recent {
+            self.local_modified_times()
+        } else {
+            Vec::new()
+        };
+        let indices = file_sort::sorted_indices(self.files(), sort, &modified);
+        let mut positions = vec![0; indices.len()];
+        for (position, &index) in indices.iter().enumerate() {
+            positions[index] = position;
+        }
+        self.file_order.indices = indices;
+        self.file_order.positions = positions;
+        self.file_order.signature = Some(signature);
+        // ファイルが減った場合は選択を範囲内に収める（読み込み中の空の一覧では動かさない）
+        let count = self.file_order.positions.len();
+        if count > 0 && self.selected_file >= count {
+            self.selected_file = count - 1;
+        }
+    }
+
+    /// 表示順に並べたファイルのインデックス
+    pub fn file_order(&self) -> &[usize] {
+        &self.file_order.indices
+    }
+
+    /// 現在の並び順
+    pub fn file_sort(&self) -> FileSort {
+        self.file_order.sort
+    }
+
+    /// ファイルの表示位置
+    pub fn file_display_position(&self, file_index: usize) -> usize {
+        self.file_order
+            .positions
+            .get(file_index)
+            .copied()
+            .unwrap_or(file_index)
+    }
+
+    /// 表示順で選択を `delta` だけ動かす（フィルタなしのとき）
+    pub(crate) fn move_file_selection(&mut self, delta: isize) {
+        self.sync_file_order();
+        let order = &self.file_order.indices;
+        if order.is_empty() {
+            return;
+        }
+        let position = self.file_display_position(self.selected_file);
+        let target = position.saturating_add_signed(delta).min(order.len() - 1);
+        self.selected_file = order[target];
+    }
+
+    /// Space s: 並び順を切り替える（path → additions → status → recently changed）
+    pub(crate) fn cycle_file_sort(&mut self) {
+        self.file_order.sort = self.file_order.sort.next(self.local_mode);
+        self.file_order.signature = None;
+        self.sync_file_order();
+        if self.file_list_filter.is_some() {
+            self.reapply_filter("file");
+        }
+        self.submission_result = Some((
+            true,
+            format!("Files sorted by {}", self.file_order.sort.label()),
+        ));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 作業ツリーのファイルの更新日時（ローカルモードの Recent 用）
+    fn local_modified_times(&mut self) -> Vec<Option<SystemTime>> {
+        if self.file_order.local_root.is_none() {
+            let output = std::process::Command::new("git")
+                .args(["rev-parse", "--show-toplevel"])
+                .current_dir(self.working_dir.as_deref().unwrap_or("."))
+                .output();
+            self.file_order.local_root = match output {
+                Ok(o) if o.status.success() => Some(PathBuf::from(
+                    String::from_utf8_lossy(&o.stdout).trim().to_string(),
+                )),
+                _ => None,
+            };
+        }
+        let Some(ref root) = self.file_order.local_root else {
+            return Vec::new();
+        };
+        self.files()
+            .iter()
+            .map(|file| {
+                std::fs::metadata(root.join(&file.filename))
+                    .and_then(|metadata| metadata.modified())
+                    .ok()
+            })
+            .collect()
+    }
+}
@@ -148,6 +148,7 @@ impl App {
                 self.pr_list_filter = Some(filter);
             }
             "file" => {
+                self.sync_file_order();
                 // file_list_filter と data_state を同時に借用するため、一時的に取り出す
                 let mut filter = match self.file_list_filter.take() {
                     Some(f) => f,
@@ -158,6 +159,10 @@ impl App {
                     _ => &[],
                 };
                 filter.apply(files, |file, q| file.filename.to_lowercase().contains(q));
+                // 一覧と同じ並び順にする
+                filter
+                    .matched_indices
+                    .sort_by_key(|&i| self.file_display_position(i));
                 if let Some(idx) = filter.sync_selection() {
                     self.selected_file = idx;
                 }
@@ -0,0 +1,207 @@
+use crossterm::event::{self, KeyCode, KeyModifiers};
+
+use crate::fuzzy::fuzzy_match;
+use crate::symbol::collect_patch_definitions;
+
+use super::types::*;
+use super::{App, PopupId};
+
+/// 記憶しておく最近開いたファイル数
+const MAX_RECENT_FILES: usize = 50;
+/// 最も最近開いたファイルに与える加点（古くなるほど減る）
+const RECENCY_BONUS: i64 = 64;
+const RECENCY_DECAY: i64 = 8;
+/// 表示する候補の上限
+const MAX_RESULTS: usize = 200;
+
+/// クエリで候補を絞り込み、スコア順（同点は元の順）に並べる。
+///
+/// クエリが空のときはファイルのみを最近開いた順に返す。
+pub(crate) fn rank_finder_items(
+    items: &[FinderItem],
+    files: &[crate::github::ChangedFile],
+    query: &str,
+    recent: &[String],
+) -> Vec<(usize, Vec<usize>)> {
+    let query_empty = query.trim().is_empty();
+    let recency_bonus = |file_index: usize| -> i64 {
+        let Some(path) = files.get(file_index).map(|f| &f.filename) else {
+            return 0;
+        };
+        recent
+            .iter()
+            .rev()
+            .position(|p| p == path)
+            .map(|rank| (RECENCY_BONUS - rank as i64 * RECENCY_DECAY).max(0))
+            .unwrap_or(0)
+    };
+
+    let mut scored: Vec<(i64, usize, Vec<usize>)> = items
+        .iter()
+        .enumerate()
+        .filter(|(_, item)| !(query_empty && item.diff_line.is_some()))
+        .filter_map(|(i, item)| {
+            let m = fuzzy_match(query, &item.label)?;
+            Some((m.score + recency_bonus(item.file_index), i, m.positions))
+        })
+        .collect();
+    scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    scored.truncate(MAX_RESULTS);
+    scored.into_iter().map(|(_, i, pos)| (i, pos)).collect()
+}
+
+impl App {
+    /// ファジーファインダーを開く（変更ファイルと、その定義シンボルを候補にする）
+    pub(crate) fn open_file_finder(&mut self) {
+        let mut items: Vec<FinderItem> = Vec::new();
+        for (file_index, file) in self.files().iter().enumerate() {
+            items.push(FinderItem {
+                label: file.filename.clone(),
+                file_index,
+                diff_line: None,
+            });
+            if let Some(ref patch) = file.patch {
+                items.extend(
+                    collect_patch_definitions(&file.filename, patch)
+                        .into_iter()
+                        .map(|(name, diff_line)| FinderItem {
+                            label: name,
+                            file_index,
+                            diff_line: Some(diff_line),
+                        }),
+                );
+            }
+        }
+        if items.is_empty() {
+            return;
+        }
+
+        let matches = rank_finder_items(&items, self.files(), "", &self.recent_files);
+        self.file_finder = Some(FileFinderState {
+            query: String::new(),
+            items,
+            matches,
+            selected: 0,
+        });
+        self.push_popup(PopupId::Finder);
+    }
+
+    pub(crate) fn handle_file_finder_input(&mut self, key: event::KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.close_popup(PopupId::Finder);
+                return;
+            }
+            KeyCode::Char('c') if ctrl => {
+                self.close_popup(PopupId::Finder);
+                return;
+            }
+            KeyCode::Enter => {
+                self.open_finder_selection();
+                return;
+            }
+            _ => {}
+        }
+
+        let Some(finder) = self.file_finder.as_mut() else {
+            return;
+        };
+        let last = finder.matches.len().saturating_sub(1);
+        let mut query_changed = false;
+        match key.code {
+            KeyCode::Down | KeyCode::Tab => {
+                finder.selected = (finder.selected + 1).min(last);
+            }
+            KeyCode::Char('n' | 'j') if ctrl => {
+                finder.selected = (finder.selected + 1).min(last);
+            }
+            KeyCode::Up | KeyCode::BackTab => {
+                finder.selected = finder.selected.saturating_sub(1);
+            }
+            KeyCode::Char('p' | 'k') if ctrl => {
+                finder.selected = finder.selected.saturating_sub(1);
+            }
+            KeyCode::Char('u') if ctrl => {
+                finder.query.clear();
+                query_changed = true;
+            }
+            KeyCode::Backspace => {
+                query_changed = finder.query.pop().is_some();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                finder.query.push(c);
+                query_changed = true;
+            }
+            _ => {}
+        }
+
+        if query_changed {
+            let Some(mut finder) = self.file_finder.take() else {
+                return;
+            };
+            finder.matches = rank_finder_items(
+                &finder.items,
+                self.files(),
+                &finder.query,
+                &self.recent_files,
+            );
+            finder.selected = 0;
+            self.file_finder = Some(finder);
+        }
+    }
+
+    /// 選択中の候補の diff を開く（シンボルなら定義行へ移動）
+    fn open_finder_selection(&mut self) {
+        let Some(finder) = self.file_finder.take() else {
+            return;
+        };
+        let Some(item) = finder
+            .matches
+            .get(finder.selected)
+            .and_then(|(i, _)| finder.items.get(*i))
+        else {
+            return;
+        };
+        let FinderItem {
+            file_index,
+            diff_line,
+            ..
+        } = *item;
+
+        // ファイル一覧のフィルタで対象が隠れている場合はフィルタを解除
+        if let Some(ref mut filter) = self.file_list_filter {
+            match filter.matched_indices.iter().position(|&i| i == file_index) {
+                Some(pos) => filter.selected = Some(pos),
+                None => self.file_list_filter = None,
+            }
+        }
+
+        self.selected_file = file_index;
+        self.state = AppState::SplitViewDiff;
+        self.sync_diff_to_selected_file();
+        if let Some(idx) = diff_line {
+            self.selected_line = idx;
+            self.scroll_offset = idx;
+        }
+        self.record_recent_file();
+    }
+
+    /// 選択中のファイルを「最近開いたファイル」に記録
+    pub(crate) fn record_recent_file(&mut self) {
+        let Some(path) = self
+            .files()
+            .get(self.selected_file)
+            .map(|f| f.filename.clone())
+        else {
+            return;
+        };
+        self.opened_files
+            .insert((self.pr_number.unwrap_or(0), path.clone()));
+        self.recent_files.retain(|p| *p != path);
+        self.recent_files.push(path);
+        if self.recent_files.len() > MAX_RECENT_FILES {
+            self.recent_files.remove(0);
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use anyhow::Result;
+use crossterm::event::{self, KeyCode};
+use std::time::Instant;
+use tokio::sync::mpsc;
+
+use crate::cache::PrCacheKey;
+use crate::github::comment::ReviewComment;
+use crate::ui::popup::PopupKey;
+use crate::ui::TuiTerminal;
+
+use super::types::*;
+use super::{App, PopupId};
+
+impl App {
+    /// ローカルモードから参照する PR 番号（PR モードのスナップショット → CLI 指定の順）
+    fn fixup_source_pr_number(&self) -> Option<u32> {
+        self.saved_pr_snapshot
+            .as_ref()
+            .and_then(|s| s.pr_number)
+            .filter(|&n| n != 0)
+            .or(self.original_pr_number)
+    }
+
+    /// PR モードで読み込んだレビューコメントを取得
+    fn fixup_review_comments(&self) -> Option<Vec<ReviewComment>> {
+        if let Some(comments) = self
+            .saved_pr_snapshot
+            .as_ref()
+            .and_then(|s| s.review_comments.as_ref())
+        {
+            return Some(comments.clone());
+        }
+        let pr_number = self.fixup_source_pr_number()?;
+        let key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number,
+        };
+        self.session_cache
+            .get_review_comments(&key)
+            .map(|c| c.to_vec())
+    }
+
+    /// PR のベースブランチ（キャッシュ済み PR データ → upstream 検出の順）
+    fn fixup_base_branch(&mut self) -> Option<String> {
+        if let Some(pr_number) = self.fixup_source_pr_number() {
+            let key = PrCacheKey {
+                repo: self.repo.clone(),
+                pr_number,
+            };
+            if let Some(cached) = self.session_cache.get_pr_data(&key) {
+                return Some(cached.pr.base.ref_name.clone());
+            }
+        }
+        Self::detect_local_base_branch(self.working_dir.as_deref())
+    }
+
+    fn set_fixup_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 未コミットの変更をレビューコメントごとにまとめたポップアップを開く
+    pub(crate) fn open_fixup_helper(&mut self) {
+        if !self.local_mode {
+            return;
+        }
+        if self.fixup_receiver.is_some() {
+            self.set_fixup_message(false, "Fixup commits are already being created");
+            return;
+        }
+        let Some(comments) = self.fixup_review_comments() else {
+            self.set_fixup_message(false, "No PR review comments available for local changes");
+            return;
+        };
+        let Some(base_branch) = self.fixup_base_branch() else {
+            self.set_fixup_message(false, "Could not determine PR base branch");
+            return;
+        };
+
+        let plan = crate::fixup::build_fixup_plan(self.files(), &comments);
+        if plan.groups.is_empty() {
+            self.set_fixup_message(false, "No local changes near review comments");
+            return;
+        }
+
+        self.fixup_popup = Some(FixupPopupState {
+            checked: vec![true; plan.groups.len()],
+            groups: plan.groups,
+            selected: 0,
+            unassigned: plan.unassigned.len(),
+            base_branch,
+        });
+        self.push_popup(PopupId::Fixup);
+    }
+
+    pub(crate) fn handle_fixup_popup_input(
+        &mut self,
+        key: event::KeyEvent,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        let action = PopupKey::from_key(&key, &self.config.keybindings);
+        let Some(popup) = self.fixup_popup.as_mut() else {
+            return Ok(());
+        };
+        if let Some(selected) = action.navigate(popup.selected, popup.groups.len()) {
+            popup.selected = selected;
+            return Ok(());
+        }
+
+        match key.code {
+            KeyCode::Char(' ') => {
+                if let Some(checked) = popup.checked.get_mut(popup.selected) {
+                    *checked = !*checked;
+                }
+            }
+            KeyCode::Char('r') => {
+                self.run_fixup_rebase(terminal)?;
+            }
+            _ => match action {
+                PopupKey::Confirm => self.start_fixup_commits(),
+                PopupKey::Close => self.close_popup(PopupId::Fixup),
+                _ => {}
+            },
+        }
+        Ok(())
+    }
+
+    /// チェックされたグループの fixup コミットをバックグラウンドで作成
+    fn start_fixup_commits(&mut self) {
+        let Some(popup) = self.fixup_popup.take() else {
+            return;
+        };
+        let groups: Vec<_> = popup
+            .groups
+            .into_iter()
+            .zip(popup.checked)
+            .filter_map(|(group, checked)| checked.then_some(group))
+            .collect();
+        if groups.is_empty() {
+            self.set_fixup_message(false, "No comments selected");
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel(1);
+        self.fixup_receiver = Some(rx);
+        let working_dir = self.working_dir.clone();
+        let base_branch = popup.base_branch;
+
+        tokio::spawn(async move {
+            let result = tokio::task::spawn_blocking(move || {
+                crate::fixup::create_fixup_commits(working_dir.as_deref(), &base_branch, &groups)
+            })
+            .await
+            .map_err(|e| e.to_string())
+            .and_then(|r| r.map_err(|e| e.to_string()));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    /// TUI を一時停止して `git rebase -i --autosquash` を実行
+    fn run_fixup_rebase(&mut self, terminal: &mut dyn TuiTerminal) -> Result<()> {
+        let Some(popup) = self.fixup_popup.take() else {
+            return Ok(());
+        };
+
+        terminal.suspend()?;
+        let result =
+            crate::fixup::run_autosquash_rebase(self.working_dir.as_deref(), &popup.base_branch);
+        // rebase の成否に関わらずターミナルを再セットアップ
+        terminal.resume()?;
+
+        match result {
+            Ok(true) => self.set_fixup_message(true, "Rebase with --autosquash completed"),
+            Ok(false) => self.set_fixup_message(
+                false,
+                "Rebase stopped (resolve conflicts and run git rebase --continue)",
+            ),
+            Err(e) => self.set_fixup_message(false, format!("Rebase failed: {}", e)),
+        }
+        self.retry_load();
+        Ok(())
+    }
+}
@@ -0,0 +1,58 @@
+use crate::diff;
+use crate::follow::{FollowEmitter, JumpEvent};
+
+use super::types::*;
+use super::App;
+
+impl App {
+    /// `[follow] path` が設定されていればジャンプイベントの送信を開始
+    pub fn enable_follow_events(&mut self) {
+        self.follow_emitter = FollowEmitter::from_config(&self.config.follow);
+    }
+
+    /// 現在のカーソル位置（ファイル + 新ファイル側の行）
+    pub(crate) fn current_jump_event(&self) -> Option<JumpEvent> {
+        if !matches!(
+            self.state,
+            AppState::DiffView | AppState::SplitViewDiff | AppState::SplitViewFileList
+        ) {
+            return None;
+        }
+        let file = self.files().get(self.selected_file)?;
+        let patch = file.patch.as_deref()?;
+        // ファイル一覧上では diff 先頭の hunk を指す
+        let line_index = if self.state == AppState::SplitViewFileList {
+            0
+        } else {
+            self.selected_line
+        };
+        let line = (line_index..patch.lines().count())
+            .find_map(|i| diff::nearest_new_line_number(patch, i))?;
+        Some(JumpEvent {
+            path: file.filename.clone(),
+            line,
+        })
+    }
+
+    /// カーソル位置をジャンプイベントとして送信（毎 tick 呼ぶ）
+    pub(crate) fn emit_follow_event(&mut self) {
+        if self.follow_emitter.is_none() {
+            return;
+        }
+        // カーソルが動いたときだけ patch を走査する
+        let cursor = (self.state, self.selected_file, self.selected_line);
+        let event = if self.follow_cursor != Some(cursor) {
+            self.follow_cursor = Some(cursor);
+            self.current_jump_event()
+        } else {
+            None
+        };
+        let Some(emitter) = self.follow_emitter.as_mut() else {
+            return;
+        };
+        if let Some(event) = event {
+            emitter.emit(event);
+        }
+        emitter.flush();
+    }
+}
@@ -0,0 +1,189 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::diff_search::{self, GrepMatch, SearchQuery};
+
+use super::types::*;
+use super::{App, DiffSearchState, PopupId};
+
+/// 1 回の描画の間に検索する patch の行数の目安。大きな PR でも入力が止まらないようにする
+const LINES_PER_TICK: usize = 20_000;
+/// 結果の上限
+const MAX_RESULTS: usize = 1000;
+
+/// 全ファイルの grep（Space f）の状態
+#[derive(Debug, Clone, Default)]
+pub struct GrepState {
+    pub input: String,
+    pub results: Vec<GrepMatch>,
+    pub selected: usize,
+    /// 検索済みのファイル数
+    pub scanned: usize,
+    pub total: usize,
+    /// diff を読み込んでいないため検索できなかったファイル数
+    pub skipped: usize,
+    /// 結果が上限に達して検索を打ち切ったか
+    pub truncated: bool,
+    query: Option<SearchQuery>,
+}
+
+impl GrepState {
+    /// まだ検索していないファイルが残っているか
+    pub fn is_searching(&self) -> bool {
+        self.query.is_some() && self.scanned < self.total
+    }
+
+    /// 一致したファイル数
+    pub fn file_count(&self) -> usize {
+        let mut count = 0;
+        let mut last = None;
+        for result in &self.results {
+            if last != Some(result.hit.file_index) {
+                count += 1;
+                last = Some(result.hit.file_index);
+            }
+        }
+        count
+    }
+}
+
+impl App {
+    /// 全ファイルの grep を開く
+    pub(crate) fn open_grep(&mut self) {
+        if self.files().is_empty() {
+            return;
+        }
+        self.grep = Some(GrepState::default());
+        self.push_popup(PopupId::Grep);
+    }
+
+    pub(crate) fn handle_grep_input(&mut self, key: &KeyEvent) {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => {
+                self.close_popup(PopupId::Grep);
+                return;
+            }
+            KeyCode::Char('c') if ctrl => {
+                self.close_popup(PopupId::Grep);
+                return;
+            }
+            KeyCode::Enter => {
+                self.open_grep_selection();
+                return;
+            }
+            _ => {}
+        }
+        let Some(ref mut grep) = self.grep else {
+            return;
+        };
+        let last = grep.results.len().saturating_sub(1);
+        let mut query_changed = false;
+        match key.code {
+            KeyCode::Down | KeyCode::Tab => grep.selected = (grep.selected + 1).min(last),
+            KeyCode::Char('n' | 'j') if ctrl => grep.selected = (grep.selected + 1).min(last),
+            KeyCode::Up | KeyCode::BackTab => grep.selected = grep.selected.saturating_sub(1),
+            KeyCode::Char('p' | 'k') if ctrl => grep.selected = grep.selected.saturating_sub(1),
+            KeyCode::PageDown => grep.selected = (grep.selected + 10).min(last),
+            KeyCode::PageUp => grep.selected = grep.selected.saturating_sub(10),
+            KeyCode::Char('u') if ctrl => {
+                grep.input.clear();
+                query_changed = true;
+            }
+            KeyCode::Backspace => query_changed = grep.input.pop().is_some(),
+            KeyCode::Char(c) if !ctrl => {
+                grep.input.push(c);
+                query_changed = true;
+            }
+            _ => {}
+        }
+        if query_changed {
+            self.restart_grep();
+        }
+    }
+
+    /// クエリが変わったら最初のファイルから検索し直す
+    fn restart_grep(&mut self) {
+        let total = self.files().len();
+        let Some(ref mut grep) = self.grep else {
+            return;
+        };
+        grep.query = SearchQuery::new(&grep.input);
+        grep.results.clear();
+        grep.selected = 0;
+        grep.scanned = 0;
+        grep.skipped = 0;
+        grep.truncated = false;
+        grep.total = total;
+        self.advance_grep();
+    }
+
+    /// 検索を少しずつ進める。描画ループから毎回呼ぶ
+    pub(crate) fn advance_grep(&mut self) {
+        let Some(mut grep) = self.grep.take() else {
+            return;
+        };
+        if let Some(query) = grep.query.clone() {
+            let mut budget = LINES_PER_TICK;
+            while budget > 0 && grep.scanned < grep.total {
+                let file_index = grep.scanned;
+                grep.scanned += 1;
+                match self
+                    .files()
+                    .get(file_index)
+                    .and_then(|f| f.patch.as_deref())
+                {
+                    Some(patch) => {
+                        budget = budget.saturating_sub(patch.lines().count().max(1));
+                        grep.results
+                            .extend(diff_search::grep_patch(&query, file_index, patch));
+                    }
+                    None => grep.skipped += 1,
+                }
+                if grep.results.len() >= MAX_RESULTS {
+                    grep.results.truncate(MAX_RESULTS);
+                    grep.truncated = true;
+                    grep.scanned = grep.total;
+                }
+            }
+        }
+        self.grep = Some(grep);
+    }
+
+    /// 選択中の一致の diff を開く。以降は n/N でほかの一致にも移動できる
+    fn open_grep_selection(&mut self) {
+        let Some(grep) = self.grep.take() else {
+            return;
+        };
+        self.close_popup(PopupId::Grep);
+        let (Some(result), Some(query)) = (grep.results.get(grep.selected), grep.query) else {
+            return;
+        };
+        let hit = result.hit;
+
+        // ファイル一覧のフィルタで対象が隠れている場合はフィルタを解除
+        if let Some(ref mut filter) = self.file_list_filter {
+            match filter
+                .matched_indices
+                .iter()
+                .position(|&i| i == hit.file_index)
+            {
+                Some(pos) => filter.selected = Some(pos),
+                None => self.file_list_filter = None,
+            }
+        }
+
+        self.selected_file = hit.file_index;
+        self.state = AppState::SplitViewDiff;
+        self.sync_diff_to_selected_file();
+        self.selected_line = hit.line;
+        self.scroll_offset = hit.line;
+        self.record_recent_file();
+        self.diff_search = Some(DiffSearchState {
+            input: query.text().to_string(),
+            editing: false,
+            all_files: true,
+            query: Some(query),
+            position: None,
+        });
+    }
+}
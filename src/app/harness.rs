@@ -0,0 +1,569 @@
+//! 端末なしで App を動かすテスト用のハーネス。
+//!
+//! GitHub は `MockBackend` に差し替え、キー入力を `handle_key_event` に直接渡して
+//! `TestBackend` に描画した画面を文字列として検証する。
+
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::backend::TestBackend;
+use ratatui::Terminal;
+use tokio::sync::mpsc;
+
+use crate::config::Config;
+use crate::keybinding::NamedKey;
+use crate::loader::{self, FetchMode};
+use crate::ui;
+
+use super::{App, DataState, RefreshRequest};
+
+const WIDTH: u16 = 120;
+const HEIGHT: u16 = 40;
+const WAIT_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub(crate) struct Harness {
+    pub app: App,
+    terminal: Terminal<TestBackend>,
+}
+
+impl Harness {
+    /// `repo` の PR を読み込んだ状態まで進める。
+    /// エディタは `true` にして、レビュー本文の入力を空のまま閉じる
+    pub(crate) async fn open_pr(repo: &str, pr_number: u32) -> Self {
+        let config = Config {
+            editor: Some("true".to_string()),
+            ..Config::default()
+        };
+        let (mut app, tx) = App::new_loading(repo, pr_number, config);
+        let (retry_tx, mut retry_rx) = mpsc::channel::<RefreshRequest>(1);
+        app.set_retry_sender(retry_tx);
+
+        let repo = repo.to_string();
+        tokio::spawn(async move {
+            loader::fetch_pr_data(repo.clone(), pr_number, FetchMode::Fresh, tx.clone()).await;
+            while let Some(request) = retry_rx.recv().await {
+                match request {
+                    RefreshRequest::PrRefresh { repo, pr_number } => {
+                        loader::fetch_pr_data(repo, pr_number, FetchMode::Fresh, tx.clone()).await;
+                    }
+                    RefreshRequest::LocalRefresh { working_dir } => {
+                        loader::fetch_local_diff(repo.clone(), working_dir, tx.clone()).await;
+                    }
+                }
+            }
+        });
+
+        let mut harness = Self {
+            app,
+            terminal: Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap(),
+        };
+        harness
+            .wait_until(|app| matches!(app.data_state, DataState::Loaded { .. }))
+            .await;
+        harness
+    }
+
+    /// `repo` の PR 一覧から受信箱を開き、通知を読み込んだ状態まで進める
+    pub(crate) async fn open_inbox(repo: &str) -> Self {
+        let mut app = App::new_pr_list(repo, Config::default());
+        let (data_tx, data_rx) = mpsc::channel(2);
+        app.set_data_receiver(0, data_rx);
+        let (retry_tx, mut retry_rx) = mpsc::channel::<RefreshRequest>(1);
+        app.set_retry_sender(retry_tx);
+        tokio::spawn(async move {
+            while let Some(request) = retry_rx.recv().await {
+                if let RefreshRequest::PrRefresh { repo, pr_number } = request {
+                    loader::fetch_pr_data(repo, pr_number, FetchMode::Fresh, data_tx.clone()).await;
+                }
+            }
+        });
+        app.open_inbox();
+
+        let mut harness = Self {
+            app,
+            terminal: Terminal::new(TestBackend::new(WIDTH, HEIGHT)).unwrap(),
+        };
+        harness
+            .wait_until(|app| {
+                app.inbox
+                    .as_ref()
+                    .is_some_and(|inbox| inbox.notifications.is_some())
+            })
+            .await;
+        harness
+    }
+
+    /// 空白区切りのキーを順に押す。`<Enter>` のような名前付きキーと `<C-s>` を受け付ける
+    pub(crate) async fn press(&mut self, keys: &str) {
+        for token in keys.split_whitespace() {
+            for key in parse_keys(token) {
+                self.app
+                    .handle_key_event(key, &mut self.terminal)
+                    .await
+                    .unwrap();
+                self.tick().await;
+            }
+        }
+    }
+
+    /// 文字列をそのまま入力する（テキスト入力欄向け）
+    pub(crate) async fn type_text(&mut self, text: &str) {
+        for c in text.chars() {
+            let key = KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+            self.app
+                .handle_key_event(key, &mut self.terminal)
+                .await
+                .unwrap();
+        }
+        self.tick().await;
+    }
+
+    /// バックグラウンドのタスクを進めてから結果を取り込み、1 フレーム描画する
+    pub(crate) async fn tick(&mut self) {
+        tokio::task::yield_now().await;
+        self.app.poll_background_updates();
+        let app = &mut self.app;
+        self.terminal.draw(|frame| ui::render(frame, app)).unwrap();
+    }
+
+    /// 条件を満たすまでフレームを進める。時間内に満たなければ画面を添えて失敗させる
+    pub(crate) async fn wait_until(&mut self, condition: impl Fn(&App) -> bool) {
+        let started = Instant::now();
+        loop {
+            self.tick().await;
+            if condition(&self.app) {
+                return;
+            }
+            if started.elapsed() > WAIT_TIMEOUT {
+                panic!(
+                    "timed out waiting for condition; screen:\n{}",
+                    self.screen()
+                );
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+
+    /// 最後に描画した画面（行ごとに末尾の空白を除く）
+    pub(crate) fn screen(&self) -> String {
+        let buffer = self.terminal.backend().buffer();
+        let area = buffer.area;
+        (area.top()..area.bottom())
+            .map(|y| {
+                let line: String = (area.left()..area.right())
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect();
+                line.trim_end().to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    pub(crate) fn selected_filename(&self) -> Option<&str> {
+        self.app
+            .files()
+            .get(self.app.selected_file)
+            .map(|file| file.filename.as_str())
+    }
+}
+
+fn parse_keys(token: &str) -> Vec<KeyEvent> {
+    let Some(name) = token.strip_prefix('<').and_then(|t| t.strip_suffix('>')) else {
+        return token
+            .chars()
+            .map(|c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE))
+            .collect();
+    };
+    if let Some(c) = name.strip_prefix("C-").and_then(|rest| rest.chars().next()) {
+        return vec![KeyEvent::new(KeyCode::Char(c), KeyModifiers::CONTROL)];
+    }
+    let named = NamedKey::parse(name).unwrap_or_else(|| panic!("unknown key: {}", token));
+    vec![KeyEvent::new(named.to_keycode(), KeyModifiers::NONE)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::app::AppState;
+    use crate::github::mock::{self, MockBackend};
+
+    /// ファイル一覧で `filename` を選ぶ
+    async fn select_file(harness: &mut Harness, filename: &str) {
+        for _ in 0..harness.app.files().len() {
+            if harness.selected_filename() == Some(filename) {
+                return;
+            }
+            harness.press("j").await;
+        }
+        panic!("{} not found; screen:\n{}", filename, harness.screen());
+    }
+
+    #[tokio::test]
+    async fn test_flow_inline_comment_then_approve() {
+        let mock = MockBackend::install();
+        mock.add_pr(&mock::small_pr("flow/comment", 7));
+
+        let mut harness = Harness::open_pr("flow/comment", 7).await;
+        assert!(harness.screen().contains("Add greeting"));
+
+        // 分割ビューから全画面の diff に入り、追加行にコメントする
+        select_file(&mut harness, "src/lib.rs").await;
+        harness.press("<Enter> l j j c").await;
+        assert_eq!(harness.app.state, AppState::TextInput);
+        harness.type_text("Looks good").await;
+        harness.press("<C-s>").await;
+        harness
+            .wait_until(|app| app.comment_submit_receiver.is_none() && !app.comment_submitting)
+            .await;
+
+        // ファイル一覧に戻って承認する。未確認のファイルがあるのでまとめを出し、
+        // 本文が空なので確認の後にもう一度 a で送信される
+        harness.press("<Esc> <Esc>").await;
+        assert_eq!(harness.app.state, AppState::FileList);
+        harness.press("a").await;
+        assert!(harness.screen().contains("Unviewed files"));
+        harness.press("a").await;
+        assert!(harness.app.pending_approve_body.is_some());
+        harness.press("a").await;
+
+        let writes = mock.requests_to("repos/flow/comment/");
+        assert_eq!(writes.len(), 2, "{:?}", writes);
+        assert_eq!(writes[0].method, "POST");
+        assert_eq!(writes[0].endpoint, "repos/flow/comment/pulls/7/comments");
+        assert_eq!(writes[0].body["path"], "src/lib.rs");
+        assert_eq!(writes[0].body["position"], 2);
+        assert_eq!(writes[0].body["body"], "Looks good");
+        assert_eq!(writes[1].endpoint, "repos/flow/comment/pulls/7/reviews");
+        assert_eq!(writes[1].body["event"], "APPROVE");
+    }
+
+    #[tokio::test]
+    async fn test_flow_switch_to_local_mode_and_back() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .env("GIT_AUTHOR_NAME", "octorus-test")
+                .env("GIT_AUTHOR_EMAIL", "octorus-test@example.com")
+                .env("GIT_COMMITTER_NAME", "octorus-test")
+                .env("GIT_COMMITTER_EMAIL", "octorus-test@example.com")
+                .output()
+                .unwrap();
+        };
+        git(&["init", "-q"]);
+        std::fs::write(dir.path().join("notes.txt"), "a\n").unwrap();
+        git(&["add", "notes.txt"]);
+        git(&["commit", "-q", "-m", "init"]);
+        std::fs::write(dir.path().join("notes.txt"), "changed\n").unwrap();
+
+        MockBackend::install().add_pr(&mock::small_pr("flow/local", 8));
+        let mut harness = Harness::open_pr("flow/local", 8).await;
+        harness
+            .app
+            .set_working_dir(Some(dir.path().display().to_string()));
+
+        harness.press("L").await;
+        assert!(harness.app.local_mode);
+        harness
+            .wait_until(|app| app.files().iter().any(|file| file.filename == "notes.txt"))
+            .await;
+        assert!(harness.screen().contains("notes.txt"));
+
+        // PR モードに戻るとセッションキャッシュから PR の一覧が復元される
+        harness.press("L").await;
+        assert!(!harness.app.local_mode);
+        harness
+            .wait_until(|app| app.files().iter().any(|file| file.filename == "src/lib.rs"))
+            .await;
+        assert!(harness.screen().contains("Add greeting"));
+    }
+
+    #[tokio::test]
+    async fn test_flow_large_pr_shows_patch_filled_from_diff() {
+        MockBackend::install().add_pr(&mock::large_pr("flow/large", 9, 60));
+        let mut harness = Harness::open_pr("flow/large", 9).await;
+        assert_eq!(harness.app.files().len(), 60);
+
+        select_file(&mut harness, "src/generated/module_025.rs").await;
+        harness.press("<Enter> l G").await;
+        assert_eq!(harness.app.state, AppState::DiffView);
+        assert!(harness.screen().contains("line 400"));
+    }
+
+    #[tokio::test]
+    async fn test_flow_huge_pr_streams_files_and_loads_patches_on_demand() {
+        MockBackend::install().add_pr(&mock::large_pr("flow/huge", 15, 350));
+        let mut harness = Harness::open_pr("flow/huge", 15).await;
+
+        // 最初のページで開き、残りのファイルは届いたページから一覧に足す
+        harness.wait_until(|app| app.files().len() == 350).await;
+        assert!(harness.app.file_page_progress.is_none());
+
+        // diff は取得できないので、開いたファイルを含むページの patch だけを後から取得する
+        select_file(&mut harness, "src/generated/module_301.rs").await;
+        harness.press("<Enter>").await;
+        harness
+            .wait_until(|app| app.files()[301].patch.is_some())
+            .await;
+        let loaded = &harness.app.lazy_patch_pages[&15].loaded;
+        assert_eq!(loaded.len(), 2, "{:?}", loaded);
+        assert!(harness.app.files()[150].patch.is_none());
+        harness.press("l").await;
+        assert_eq!(harness.app.state, AppState::DiffView);
+        assert!(harness.screen().contains("line 5"));
+    }
+
+    #[tokio::test]
+    async fn test_flow_multi_key_sequence_in_view_override() {
+        use crate::keybinding::{KeyBinding, KeySequence};
+
+        MockBackend::install().add_pr(&mock::large_pr("flow/keys", 16, 5));
+        let mut harness = Harness::open_pr("flow/keys", 16).await;
+        harness.app.config.keybindings.views.file_list.insert(
+            "move_down".to_string(),
+            KeySequence(vec![
+                KeyBinding::char('g'),
+                KeyBinding::char('j'),
+                KeyBinding::char('j'),
+            ]),
+        );
+
+        // ファイル一覧では j 単独では動かず、3 キーのシーケンスで下へ移動する
+        harness.press("j").await;
+        assert_eq!(harness.app.selected_file, 0);
+        harness.press("gjj").await;
+        assert_eq!(harness.app.selected_file, 1);
+        assert!(harness.app.pending_keys.is_empty());
+        assert!(harness.app.completed_sequence.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_flow_renamed_and_outdated_files_render() {
+        let mock = MockBackend::install();
+        mock.add_pr(&mock::renamed_pr("flow/renamed", 10));
+        let mut harness = Harness::open_pr("flow/renamed", 10).await;
+        let screen = harness.screen();
+        assert!(screen.contains("src/new_name.rs"));
+        assert!(screen.contains("src/edited_name.rs"));
+
+        // patch のないリネームを開いても落ちない
+        select_file(&mut harness, "src/new_name.rs").await;
+        harness.press("<Enter> l").await;
+        assert_eq!(harness.app.state, AppState::DiffView);
+
+        mock.add_pr(&mock::outdated_comments_pr("flow/outdated", 11));
+        let mut harness = Harness::open_pr("flow/outdated", 11).await;
+        harness.press("C").await;
+        assert_eq!(harness.app.state, AppState::CommentList);
+        harness
+            .wait_until(|app| app.review_comments.as_ref().is_some_and(|c| c.len() == 3))
+            .await;
+        assert!(harness.screen().contains("Why greet here?"));
+    }
+
+    #[tokio::test]
+    async fn test_flow_comments_arrive_with_the_pr() {
+        MockBackend::install().add_pr(&mock::outdated_comments_pr("flow/bundle", 14));
+        let mut harness = Harness::open_pr("flow/bundle", 14).await;
+
+        // PR と同じ問い合わせで取得したコメントが届き、一覧を開いても取り直さない
+        harness
+            .wait_until(|app| app.review_comments.as_ref().is_some_and(|c| c.len() == 3))
+            .await;
+        assert!(harness.app.review_thread_states[&103].resolved);
+        harness.press("C").await;
+        assert!(harness.app.comment_receiver.is_none());
+        assert!(harness.app.discussion_comment_receiver.is_none());
+        assert!(harness.screen().contains("Why greet here?"));
+    }
+
+    #[tokio::test]
+    async fn test_flow_approve_wraps_up_unresolved_threads() {
+        let mock = MockBackend::install();
+        mock.add_pr(&mock::outdated_comments_pr("flow/wrapup", 12));
+        let mut harness = Harness::open_pr("flow/wrapup", 12).await;
+
+        // 未解決のスレッドが 2 つあるので、承認の前にまとめを出す
+        harness.press("a").await;
+        assert!(harness.screen().contains("2 unresolved threads"));
+        harness.press("<Esc>").await;
+        assert!(harness.app.review_summary.is_none());
+
+        harness.press("a r").await;
+        assert_eq!(harness.app.state, AppState::CommentList);
+        harness.press("<Esc>").await;
+
+        // そのまま承認すると、本文が空なので確認の後に送信される
+        harness.press("a a a").await;
+        let writes = mock.requests_to("repos/flow/wrapup/");
+        assert_eq!(writes.len(), 1, "{:?}", writes);
+        assert_eq!(writes[0].body["event"], "APPROVE");
+    }
+
+    #[tokio::test]
+    async fn test_flow_request_changes_lists_unviewed_files() {
+        let mock = MockBackend::install();
+        mock.add_pr(&mock::small_pr("flow/summary", 15));
+        let mut harness = Harness::open_pr("flow/summary", 15).await;
+
+        // 変更要求の前にも、まだ見ていないファイルを一覧して確認する
+        harness.press("r").await;
+        let screen = harness.screen();
+        assert!(screen.contains("Request changes on this PR?"));
+        assert!(screen.contains("Unviewed files (2)"));
+        harness.press("<Esc>").await;
+        assert!(harness.app.review_summary.is_none());
+
+        // r で最初の未確認のファイルの diff を開く
+        harness.press("r r").await;
+        assert_eq!(harness.app.state, AppState::DiffView);
+        assert_eq!(
+            harness.app.files()[harness.app.selected_file].filename,
+            "src/lib.rs"
+        );
+        assert!(mock
+            .requests_to("repos/flow/summary/pulls/15/reviews")
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_flow_failed_comment_is_queued_and_retried() {
+        let mock = MockBackend::install();
+        mock.add_pr(&mock::small_pr("flow/outbox", 13));
+        let endpoint = "repos/flow/outbox/pulls/13/comments";
+        let mut harness = Harness::open_pr("flow/outbox", 13).await;
+
+        // 接続できずに失敗したコメントは送信待ちに入る
+        mock.fail_once("POST", endpoint, "error connecting to api.github.com");
+        select_file(&mut harness, "src/lib.rs").await;
+        harness.press("<Enter> l j j c").await;
+        harness.type_text("Looks good").await;
+        harness.press("<C-s>").await;
+        harness.wait_until(|app| app.outbox.len() == 1).await;
+        assert!(mock.requests_to(endpoint).is_empty());
+
+        harness.press("<Esc> <Esc> U").await;
+        assert!(harness.screen().contains("comment flow/outbox#13 src/lib.rs"));
+
+        // r ですぐに送り直す
+        harness.press("r").await;
+        harness.wait_until(|app| app.outbox.is_empty()).await;
+        let writes = mock.requests_to(endpoint);
+        assert_eq!(writes.len(), 1, "{:?}", writes);
+        assert_eq!(writes[0].body["body"], "Looks good");
+    }
+
+    #[tokio::test]
+    async fn test_flow_queued_review_ends_wait_for_review() {
+        let mock = MockBackend::install();
+        mock.add_pr(&mock::small_pr("flow/queued-review", 17));
+        let endpoint = "repos/flow/queued-review/pulls/17/reviews";
+        let mut harness = Harness::open_pr("flow/queued-review", 17).await;
+        harness.app.set_wait_for_review(true);
+
+        // 送れなかった承認は送信待ちに入り、まだ終了しない
+        mock.fail_once("POST", endpoint, "error connecting to api.github.com");
+        harness.press("a a a").await;
+        harness.wait_until(|app| app.outbox.len() == 1).await;
+        assert_eq!(harness.app.review_outcome(), None);
+        assert!(!harness.app.should_quit);
+
+        // 送り直しで届いたら、その場で送れたときと同じく終了する
+        harness.press("U r").await;
+        harness.wait_until(|app| app.outbox.is_empty()).await;
+        assert_eq!(mock.requests_to(endpoint).len(), 1);
+        assert_eq!(
+            harness.app.review_outcome(),
+            Some(crate::github::ReviewAction::Approve)
+        );
+        assert!(harness.app.should_quit);
+    }
+
+    #[tokio::test]
+    async fn test_flow_mark_draft_ready_for_review() {
+        let mock = MockBackend::install();
+        let mut fixture = mock::small_pr("flow/draft", 14);
+        fixture.pr.draft = true;
+        mock.add_pr(&fixture);
+        mock.add_graphql(|query, _| {
+            query.contains("markPullRequestReadyForReview").then(|| {
+                serde_json::json!({ "data": { "markPullRequestReadyForReview": {
+                    "clientMutationId": null,
+                } } })
+            })
+        });
+        let mut harness = Harness::open_pr("flow/draft", 14).await;
+        assert!(harness.screen().contains("[DRAFT] PR #14"));
+
+        harness.press("g w").await;
+        assert!(harness
+            .screen()
+            .contains("Mark this PR as ready for review?"));
+        harness.press("y").await;
+
+        let writes = mock.requests_to("graphql");
+        let mutation = writes.iter().find(|request| {
+            request.body["query"]
+                .as_str()
+                .is_some_and(|query| query.contains("markPullRequestReadyForReview"))
+        });
+        let mutation = mutation.unwrap_or_else(|| panic!("{:?}", writes));
+        assert_eq!(
+            mutation.body["variables"]["pullRequestId"],
+            "PR_flow/draft_14"
+        );
+        assert!(!harness.app.pr().unwrap().draft);
+        assert!(!harness.screen().contains("[DRAFT]"));
+        assert!(harness.screen().contains("Marked as ready for review"));
+    }
+
+    #[tokio::test]
+    async fn test_flow_inbox_opens_review_request_and_returns() {
+        let mock = MockBackend::install();
+        mock.add_pr(&mock::small_pr("flow/inbox-other", 16));
+        let thread = |id: &str, repo: &str, number: u32, reason: &str| {
+            serde_json::json!({
+                "id": id,
+                "reason": reason,
+                "unread": true,
+                "updated_at": "2024-01-01T00:00:00Z",
+                "subject": {
+                    "title": format!("PR {}", number),
+                    "url": format!("https://api.github.com/repos/{}/pulls/{}", repo, number),
+                    "type": "PullRequest",
+                },
+                "repository": { "full_name": repo },
+            })
+        };
+        mock.add_json(
+            "notifications",
+            serde_json::json!([
+                thread("101", "flow/inbox-other", 16, "review_requested"),
+                thread("102", "flow/inbox", 3, "mention"),
+            ]),
+        );
+        let mut harness = Harness::open_inbox("flow/inbox").await;
+
+        // 既定ではレビュー依頼だけ、Tab で PR の通知すべてを表示する
+        assert!(harness.screen().contains("flow/inbox-other#16"));
+        assert!(!harness.screen().contains("flow/inbox#3"));
+        harness.press("<Tab>").await;
+        assert!(harness.screen().contains("flow/inbox#3"));
+        harness.press("<Tab>").await;
+
+        // Enter で既読にして、別のリポジトリの PR を開く
+        harness.press("<Enter>").await;
+        assert_eq!(mock.requests_to("notifications/threads/101").len(), 1);
+        assert!(!harness.app.inbox.as_ref().unwrap().visible()[0].unread);
+        assert_eq!(harness.app.repo, "flow/inbox-other");
+        harness
+            .wait_until(|app| matches!(app.data_state, DataState::Loaded { .. }))
+            .await;
+        assert_eq!(harness.app.state, AppState::FileList);
+
+        // PR から戻ると受信箱に戻る
+        harness.press("q").await;
+        assert_eq!(harness.app.state, AppState::Inbox);
+    }
+}
@@ -0,0 +1,260 @@
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::github::{self, Notification};
+
+use super::{App, AppState};
+
+/// 受信箱を表示している間に通知を取り直す間隔（GitHub の既定の X-Poll-Interval）。
+/// アイドル中は PR 監視と同じく延ばす
+const INBOX_POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// 自分宛ての PR の通知の受信箱の状態
+#[derive(Debug)]
+pub struct InboxState {
+    /// 読み込み中は None
+    pub notifications: Option<Vec<Notification>>,
+    pub error: Option<String>,
+    /// 表示中の通知のうち選択中のもの
+    pub selected: usize,
+    /// レビュー依頼以外の PR の通知も表示する
+    pub show_all: bool,
+    /// 閉じたときに戻る画面。`or inbox` で起動したときは None で、閉じると終了する
+    return_state: Option<AppState>,
+    /// 受信箱から PR を開いた。PR から戻ると受信箱に戻る
+    opened_pr: bool,
+    last_fetch: Option<Instant>,
+}
+
+impl InboxState {
+    fn new(return_state: Option<AppState>) -> Self {
+        Self {
+            notifications: None,
+            error: None,
+            selected: 0,
+            show_all: false,
+            return_state,
+            opened_pr: false,
+            last_fetch: None,
+        }
+    }
+
+    /// 表示する通知。既定ではレビュー依頼だけ
+    pub fn visible(&self) -> Vec<&Notification> {
+        self.notifications
+            .iter()
+            .flatten()
+            .filter(|n| self.show_all || n.is_review_request())
+            .collect()
+    }
+
+    fn selected_notification(&self) -> Option<&Notification> {
+        self.visible().get(self.selected).copied()
+    }
+}
+
+impl App {
+    fn set_inbox_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// `or inbox` で起動したとき、PR 一覧の代わりに受信箱を最初に表示する
+    pub fn open_inbox_on_start(&mut self) {
+        self.inbox = Some(InboxState::new(None));
+        self.state = AppState::Inbox;
+        self.fetch_inbox();
+    }
+
+    /// PR 一覧から受信箱を開く
+    pub(crate) fn open_inbox(&mut self) {
+        self.inbox = Some(InboxState::new(Some(self.state)));
+        self.state = AppState::Inbox;
+        self.fetch_inbox();
+    }
+
+    fn fetch_inbox(&mut self) {
+        if let Some(ref mut inbox) = self.inbox {
+            inbox.last_fetch = Some(Instant::now());
+        }
+        let (tx, rx) = mpsc::channel(1);
+        self.inbox_receiver = Some(rx);
+        tokio::spawn(async move {
+            let result = github::fetch_pr_notifications()
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    /// 通知の取得結果を取り込み、受信箱の表示中は一定の間隔で取り直す
+    pub(crate) fn poll_inbox_updates(&mut self) {
+        if let Some(ref mut rx) = self.inbox_receiver {
+            let result = match rx.try_recv() {
+                Ok(result) => Some(result),
+                Err(mpsc::error::TryRecvError::Empty) => None,
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.inbox_receiver = None;
+                    None
+                }
+            };
+            if let Some(result) = result {
+                self.inbox_receiver = None;
+                if let Some(ref mut inbox) = self.inbox {
+                    match result {
+                        Ok(notifications) => {
+                            inbox.notifications = Some(notifications);
+                            inbox.error = None;
+                            inbox.selected =
+                                inbox.selected.min(inbox.visible().len().saturating_sub(1));
+                        }
+                        Err(e) => inbox.error = Some(e),
+                    }
+                }
+            }
+        }
+
+        if self.state != AppState::Inbox || self.inbox_receiver.is_some() {
+            return;
+        }
+        let interval = self.poll_tier().watch_interval(INBOX_POLL_INTERVAL);
+        let due = self
+            .inbox
+            .as_ref()
+            .is_some_and(|inbox| inbox.last_fetch.is_none_or(|at| at.elapsed() >= interval));
+        if due {
+            self.fetch_inbox();
+        }
+    }
+
+    /// 受信箱から開いた PR から戻るときに、受信箱に戻るか。
+    /// 戻るなら通知は次のフレームで取り直す
+    pub(crate) fn take_inbox_return(&mut self) -> bool {
+        let Some(ref mut inbox) = self.inbox else {
+            return false;
+        };
+        if !std::mem::take(&mut inbox.opened_pr) {
+            return false;
+        }
+        inbox.last_fetch = None;
+        true
+    }
+
+    fn close_inbox(&mut self) {
+        let Some(inbox) = self.inbox.take() else {
+            return;
+        };
+        self.inbox_receiver = None;
+        match inbox.return_state {
+            Some(state) => self.state = state,
+            None => self.request_quit(),
+        }
+    }
+
+    /// 通知を既読にする。受信箱では取り直すまで既読の表示で残す
+    async fn mark_inbox_read(&mut self, notification: &Notification) {
+        if !notification.unread {
+            return;
+        }
+        match github::mark_notification_read(&notification.id).await {
+            Ok(()) => {
+                if let Some(n) = self
+                    .inbox
+                    .as_mut()
+                    .and_then(|inbox| inbox.notifications.as_mut())
+                    .and_then(|list| list.iter_mut().find(|n| n.id == notification.id))
+                {
+                    n.unread = false;
+                }
+            }
+            Err(e) => self.set_inbox_message(false, format!("Failed to mark as read: {:#}", e)),
+        }
+    }
+
+    /// 選択中の通知を既読にして、その PR のレビューを始める。
+    /// 別のリポジトリの PR なら PR 一覧もそのリポジトリに切り替える
+    async fn open_inbox_pr(&mut self) {
+        let Some(notification) = self
+            .inbox
+            .as_ref()
+            .and_then(|inbox| inbox.selected_notification())
+            .cloned()
+        else {
+            return;
+        };
+        self.mark_inbox_read(&notification).await;
+
+        if notification.repo != self.repo {
+            self.state = AppState::PullRequestList;
+            self.switch_repo(notification.repo.clone());
+        }
+        if let Some(ref mut inbox) = self.inbox {
+            inbox.opened_pr = true;
+        }
+        self.open_pr_tab(notification.pr_number, false);
+    }
+
+    pub(crate) async fn handle_inbox_input(&mut self, key: KeyEvent) {
+        let kb = &self.config.keybindings;
+        let close = key.code == KeyCode::Esc || self.matches_single_key(&key, &kb.quit);
+        let down = self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down;
+        let up = self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up;
+        let last = self.matches_single_key(&key, &kb.jump_to_last);
+        let open = self.matches_single_key(&key, &kb.open_panel);
+        let refresh = self.matches_single_key(&key, &kb.refresh);
+        let browser = self.matches_single_key(&key, &kb.open_in_browser);
+
+        if close {
+            self.close_inbox();
+            return;
+        }
+        if open {
+            self.open_inbox_pr().await;
+            return;
+        }
+        if refresh {
+            self.fetch_inbox();
+            return;
+        }
+        let Some(ref mut inbox) = self.inbox else {
+            return;
+        };
+        let selected = inbox.selected_notification().cloned();
+        if browser {
+            if let Some(n) = selected {
+                tokio::spawn(async move {
+                    let _ = github::open_pr_in_browser(&n.repo, n.pr_number).await;
+                });
+            }
+            return;
+        }
+        match key.code {
+            // m: 既読にする
+            KeyCode::Char('m') => {
+                if let Some(n) = selected {
+                    self.mark_inbox_read(&n).await;
+                }
+                return;
+            }
+            // Tab: レビュー依頼だけ / PR の通知すべての切り替え
+            KeyCode::Tab => {
+                inbox.show_all = !inbox.show_all;
+                inbox.selected = 0;
+                return;
+            }
+            _ => {}
+        }
+        let max = inbox.visible().len().saturating_sub(1);
+        if down {
+            inbox.selected = (inbox.selected + 1).min(max);
+        } else if up {
+            inbox.selected = inbox.selected.saturating_sub(1);
+        } else if last {
+            inbox.selected = max;
+        } else if key.code == KeyCode::Char('g') {
+            inbox.selected = 0;
+        }
+    }
+}
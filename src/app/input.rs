@@ -1,84 +1,153 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::Stdout;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use std::time::Instant;
 use tokio::sync::mpsc;
 
+use crate::config::KeybindingView;
 use crate::filter::ListFilter;
 use crate::github::{self, ChangedFile};
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
+use crate::progress::Progress;
+use crate::ui::TuiTerminal;
 
 use super::types::*;
-use super::{App, AppState, DataState};
+use super::{App, AppState, DataState, PopupId};
 
 impl App {
-    pub(crate) async fn handle_input(
-        &mut self,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    ) -> Result<()> {
-        if event::poll(std::time::Duration::from_millis(100))? {
-            if let Event::Key(key) = event::read()? {
+    pub(crate) async fn handle_input(&mut self, terminal: &mut dyn TuiTerminal) -> Result<()> {
+        if event::poll(self.poll_tier().event_timeout())? {
+            let event = event::read()?;
+            self.activity.record_input(std::time::Instant::now());
+            if let Event::Key(key) = event {
                 // Kitty keyboard protocol が有効な場合、Release/Repeat イベントも
                 // 報告されるため、Press のみ処理して二重実行を防止する。
                 if key.kind != KeyEventKind::Press {
                     return Ok(());
                 }
+                self.handle_key_event(key, terminal).await?;
+            }
+        }
+        Ok(())
+    }
 
-                // PR一覧画面は独自のLoading処理があるためスキップ
-                // Help画面はデータ状態に依存しないためスキップ
-                if self.state != AppState::PullRequestList && self.state != AppState::Help {
-                    // Error状態でのリトライ処理
-                    if let DataState::Error(_) = &self.data_state {
-                        match key.code {
-                            KeyCode::Char('q') => self.should_quit = true,
-                            KeyCode::Char('r') => self.retry_load(),
-                            _ => {}
-                        }
-                        return Ok(());
-                    }
-
-                    // Loading状態ではqのみ受け付け
-                    if matches!(self.data_state, DataState::Loading) {
-                        if key.code == KeyCode::Char('q') {
-                            self.should_quit = true;
-                        }
-                        return Ok(());
-                    }
+    /// 押されたキーを最前面のポップアップか現在の画面の入力処理に渡す
+    pub(crate) async fn handle_key_event(
+        &mut self,
+        key: event::KeyEvent,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        // ポップアップ表示中は最前面のポップアップが入力を受け取る
+        if self.handle_popup_input(key, terminal).await? {
+            return Ok(());
+        }
+        self.record_key_usage(&key);
+
+        // PR一覧画面は独自のLoading処理があるためスキップ
+        // Help画面・受信箱はデータ状態に依存しないためスキップ
+        if !matches!(
+            self.state,
+            AppState::PullRequestList | AppState::Help | AppState::Inbox
+        ) {
+            // Error状態でのリトライ処理
+            if let DataState::Error(_) = &self.data_state {
+                match key.code {
+                    KeyCode::Char('q') => self.request_quit(),
+                    KeyCode::Char('r') => self.retry_load(),
+                    _ => {}
+                }
+                return Ok(());
+            }
 
-                    if self.pending_approve_body.is_some() {
-                        match self.handle_pending_approve_choice(&key) {
-                            PendingApproveChoice::Submit => {
-                                let body = self.pending_approve_body.take().unwrap_or_default();
-                                self.submit_review_with_body(ReviewAction::Approve, &body)
-                                    .await?;
-                            }
-                            PendingApproveChoice::Cancel | PendingApproveChoice::Ignore => {}
-                        }
-                        return Ok(());
-                    }
+            // Loading状態ではqのみ受け付け
+            if matches!(self.data_state, DataState::Loading) {
+                if key.code == KeyCode::Char('q') {
+                    self.request_quit();
                 }
+                return Ok(());
+            }
 
-                match self.state {
-                    AppState::PullRequestList => self.handle_pr_list_input(key).await?,
-                    AppState::FileList => self.handle_file_list_input(key, terminal).await?,
-                    AppState::DiffView => self.handle_diff_view_input(key, terminal).await?,
-                    AppState::TextInput => self.handle_text_input(key)?,
-                    AppState::CommentList => self.handle_comment_list_input(key, terminal).await?,
-                    AppState::Help => self.handle_help_input(key, terminal)?,
-                    AppState::AiRally => self.handle_ai_rally_input(key, terminal).await?,
-                    AppState::SplitViewFileList => {
-                        self.handle_split_view_file_list_input(key, terminal)
-                            .await?
-                    }
-                    AppState::SplitViewDiff => {
-                        self.handle_split_view_diff_input(key, terminal).await?
+            if self.pending_approve_body.is_some() {
+                match self.handle_pending_approve_choice(&key) {
+                    PendingApproveChoice::Submit => {
+                        let body = self.pending_approve_body.take().unwrap_or_default();
+                        self.submit_review_with_body(ReviewAction::Approve, &body)
+                            .await?;
+                        // 送信に成功していれば次の PR を開き終えている
+                        self.approve_then_next = false;
                     }
+                    PendingApproveChoice::Cancel => self.approve_then_next = false,
+                    PendingApproveChoice::Ignore => {}
                 }
+                return Ok(());
+            }
+
+            if self.pending_draft_toggle.is_some() {
+                self.confirm_draft_toggle(&key).await;
+                return Ok(());
+            }
+        }
+
+        self.handle_state_key(key, terminal).await?;
+        // 保留中のキーで他の操作のシーケンスが揃ったら、その操作として同じキーをもう一度処理する
+        if self.completed_sequence.is_some() {
+            let result = self.handle_state_key(key, terminal).await;
+            self.completed_sequence = None;
+            result?;
+        }
+        Ok(())
+    }
+
+    /// 現在の画面の入力処理
+    async fn handle_state_key(
+        &mut self,
+        key: event::KeyEvent,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        match self.state {
+            AppState::PullRequestList => self.handle_pr_list_input(key).await?,
+            AppState::FileList => self.handle_file_list_input(key, terminal).await?,
+            AppState::DiffView => self.handle_diff_view_input(key, terminal).await?,
+            AppState::TextInput => self.handle_text_input(key)?,
+            AppState::CommentList => self.handle_comment_list_input(key, terminal).await?,
+            AppState::Help => self.handle_help_input(key, terminal)?,
+            AppState::AiRally => self.handle_ai_rally_input(key, terminal).await?,
+            AppState::SplitViewFileList => {
+                self.handle_split_view_file_list_input(key, terminal)
+                    .await?
+            }
+            AppState::SplitViewDiff => self.handle_split_view_diff_input(key, terminal).await?,
+            AppState::LocalCompare => {
+                // Header(3) + Footer(3) + borders(2) を除いた表示行数
+                let visible_rows = (terminal.size()?.height as usize).saturating_sub(8);
+                self.handle_local_compare_input(key, visible_rows)
+            }
+            AppState::LocalData => self.handle_local_data_input(key),
+            AppState::CommitList => self.handle_commit_list_input(key),
+            AppState::Checks => {
+                // Header(3) + Footer(3) + borders(2) を除いた表示行数
+                let visible_rows = (terminal.size()?.height as usize).saturating_sub(8);
+                self.handle_checks_input(key, visible_rows)
             }
+            AppState::ConflictResolve => {
+                // Header(3) + Footer(3) + borders(2) を除いた表示行数
+                let visible_rows = (terminal.size()?.height as usize).saturating_sub(8);
+                self.handle_conflict_resolve_input(key, visible_rows)
+            }
+            AppState::PrOverview => {
+                // Header(3) + Footer(3) + borders(2) を除いた表示行数
+                let visible_rows = (terminal.size()?.height as usize).saturating_sub(8);
+                self.handle_pr_overview_input(key, visible_rows)
+            }
+            AppState::PrSummary => {
+                // Header(3) + Footer(3) + borders(2) を除いた表示行数
+                let visible_rows = (terminal.size()?.height as usize).saturating_sub(8);
+                self.handle_pr_summary_input(key, visible_rows)
+            }
+            AppState::Inbox => self.handle_inbox_input(key).await,
         }
         Ok(())
     }
+
     pub(crate) fn retry_load(&mut self) {
         if let Some(ref tx) = self.retry_sender {
             // 既にデータがある場合は Loading に戻さない（バックグラウンド更新のみ）
@@ -86,9 +155,12 @@ impl App {
                 self.data_state = DataState::Loading;
             }
             let request = if self.local_mode {
-                RefreshRequest::LocalRefresh
+                RefreshRequest::LocalRefresh {
+                    working_dir: self.working_dir.clone(),
+                }
             } else {
                 RefreshRequest::PrRefresh {
+                    repo: self.repo.clone(),
                     pr_number: self.pr_number.unwrap_or(0),
                 }
             };
@@ -98,19 +170,30 @@ impl App {
     pub(crate) async fn handle_file_list_input(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         // フィルタ入力中はフィルタ処理を優先
         if self.handle_filter_input(&key, "file") {
             return Ok(());
         }
 
+        let kb = self.config.keybindings.for_view(KeybindingView::FileList);
+
+        if self.matches_single_key(&key, &kb.file_finder) {
+            self.open_file_finder();
+            return Ok(());
+        }
+
+        // PR のタブの切り替え
+        if self.handle_pr_tab_key(&key, &kb) {
+            return Ok(());
+        }
+
         // フィルタ結果が空の場合、ファイル操作を無効化（stale selection 防止）
         if !self.is_filter_selection_empty("file") && self.handle_mark_viewed_key(key) {
             return Ok(());
         }
 
-        let kb = self.config.keybindings.clone();
         let has_filter = self.file_list_filter.is_some();
 
         // Quit or back to PR list
@@ -118,7 +201,7 @@ impl App {
             if self.started_from_pr_list {
                 self.back_to_pr_list();
             } else {
-                self.should_quit = true;
+                self.request_quit();
             }
             return Ok(());
         }
@@ -133,8 +216,7 @@ impl App {
             if has_filter {
                 self.handle_filter_navigation("file", true);
             } else if !self.files().is_empty() {
-                self.selected_file =
-                    (self.selected_file + 1).min(self.files().len().saturating_sub(1));
+                self.move_file_selection(1);
             }
             return Ok(());
         }
@@ -144,7 +226,7 @@ impl App {
             if has_filter {
                 self.handle_filter_navigation("file", false);
             } else {
-                self.selected_file = self.selected_file.saturating_sub(1);
+                self.move_file_selection(-1);
             }
             return Ok(());
         }
@@ -154,8 +236,7 @@ impl App {
             if !self.files().is_empty() && !has_filter {
                 let page_step = terminal.size()?.height.saturating_sub(8) as usize;
                 let step = page_step.max(1);
-                self.selected_file =
-                    (self.selected_file + step).min(self.files().len().saturating_sub(1));
+                self.move_file_selection(step as isize);
             }
             return Ok(());
         }
@@ -165,7 +246,7 @@ impl App {
             if !has_filter {
                 let page_step = terminal.size()?.height.saturating_sub(8) as usize;
                 let step = page_step.max(1);
-                self.selected_file = self.selected_file.saturating_sub(step);
+                self.move_file_selection(-(step as isize));
             }
             return Ok(());
         }
@@ -194,11 +275,149 @@ impl App {
                     return Ok(());
                 }
 
+                // Space+f: 全ファイルの grep
+                if self.try_match_sequence(&kb.grep) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_grep();
+                    return Ok(());
+                }
+
+                // Space+s: ファイル一覧の並び順を切り替え
+                if self.try_match_sequence(&kb.sort_files) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.cycle_file_sort();
+                    return Ok(());
+                }
+
+                // gv: vendored 依存とライセンス変更の要約
+                if self.try_match_sequence(&kb.toggle_vendored) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.vendored_summary_open = true;
+                    self.push_popup(PopupId::VendoredSummary);
+                    return Ok(());
+                }
+
+                // gp: レビュー下書きモードの切り替え
+                if self.try_match_sequence(&kb.toggle_review_draft) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_review_draft_mode();
+                    return Ok(());
+                }
+
+                // gh: PR のコミット一覧
+                if self.try_match_sequence(&kb.commit_list) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_commit_list();
+                    return Ok(());
+                }
+
+                // ga: レビューアジェンダ
+                if self.try_match_sequence(&kb.review_agenda) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_review_agenda();
+                    return Ok(());
+                }
+
+                // gr: レビュー依頼・アサイン
+                if self.try_match_sequence(&kb.request_reviewers) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_people_picker();
+                    return Ok(());
+                }
+
+                // gt: ラベルの編集
+                if self.try_match_sequence(&kb.edit_labels) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_label_picker();
+                    return Ok(());
+                }
+
+                // gw: Draft とレビュー可能を切り替える
+                if self.try_match_sequence(&kb.toggle_pr_draft) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.request_draft_toggle();
+                    return Ok(());
+                }
+
+                // gs: CI チェック一覧
+                if self.try_match_sequence(&kb.checks) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_checks();
+                    return Ok(());
+                }
+
+                // gi: PR の概要（本文・レビュアー・リンクされた Issue）
+                if self.try_match_sequence(&kb.pr_overview) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_pr_overview();
+                    return Ok(());
+                }
+
+                // gS: AI による PR の要約
+                if self.try_match_sequence(&kb.pr_summary) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_pr_summary();
+                    return Ok(());
+                }
+
+                // go: PR のブランチをチェックアウト
+                if self.try_match_sequence(&kb.checkout_pr) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_checkout();
+                    return Ok(());
+                }
+
+                // gu: ブランチから PR を作成（Local Mode）
+                if self.try_match_sequence(&kb.create_pr) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_pr_create();
+                    return Ok(());
+                }
+
+                // gm: コンフリクト解消（Local Mode）
+                if self.try_match_sequence(&kb.resolve_conflicts) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_conflict_resolve();
+                    return Ok(());
+                }
+
+                // gn: ファイルのメモ
+                if self.try_match_sequence(&kb.file_note) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    if !self.is_filter_selection_empty("file") {
+                        self.open_file_note();
+                    }
+                    return Ok(());
+                }
+
+                // より長いシーケンスの途中か、他の操作のシーケンスが揃った
+                if self.resolve_pending_keys(&kb) {
+                    return Ok(());
+                }
+
                 // マッチしなければペンディングをクリア
                 self.clear_pending_keys();
             } else {
                 // シーケンス開始チェック
-                if self.key_could_match_sequence(&key, &kb.filter) {
+                if self.starts_key_sequence(&key, &kb)
+                    || self.key_could_match_sequence(&key, &kb.filter)
+                    || self.key_could_match_sequence(&key, &kb.grep)
+                    || self.key_could_match_sequence(&key, &kb.sort_files)
+                    || self.key_could_match_sequence(&key, &kb.toggle_vendored)
+                    || self.key_could_match_sequence(&key, &kb.toggle_review_draft)
+                    || self.key_could_match_sequence(&key, &kb.commit_list)
+                    || self.key_could_match_sequence(&key, &kb.checks)
+                    || self.key_could_match_sequence(&key, &kb.pr_overview)
+                    || self.key_could_match_sequence(&key, &kb.pr_summary)
+                    || self.key_could_match_sequence(&key, &kb.review_agenda)
+                    || self.key_could_match_sequence(&key, &kb.request_reviewers)
+                    || self.key_could_match_sequence(&key, &kb.edit_labels)
+                    || self.key_could_match_sequence(&key, &kb.toggle_pr_draft)
+                    || self.key_could_match_sequence(&key, &kb.checkout_pr)
+                    || self.key_could_match_sequence(&key, &kb.create_pr)
+                    || self.key_could_match_sequence(&key, &kb.resolve_conflicts)
+                    || self.key_could_match_sequence(&key, &kb.file_note)
+                {
                     self.push_pending_key(kb_event);
                     return Ok(());
                 }
@@ -216,22 +435,29 @@ impl App {
             if !self.files().is_empty() {
                 self.state = AppState::SplitViewDiff;
                 self.sync_diff_to_selected_file();
+                self.record_recent_file();
             }
             return Ok(());
         }
 
         // Actions (disabled in local mode - no PR to submit reviews to)
         if !self.local_mode && self.matches_single_key(&key, &kb.approve) {
-            self.submit_review(ReviewAction::Approve, terminal).await?;
+            self.start_review(ReviewAction::Approve, terminal).await?;
             return Ok(());
         }
 
         if !self.local_mode && self.matches_single_key(&key, &kb.request_changes) {
-            self.submit_review(ReviewAction::RequestChanges, terminal)
+            self.start_review(ReviewAction::RequestChanges, terminal)
                 .await?;
             return Ok(());
         }
 
+        // 承認してキューの次の PR へ
+        if !self.local_mode && self.matches_single_key(&key, &kb.approve_next) {
+            self.approve_and_open_next(terminal).await?;
+            return Ok(());
+        }
+
         // Note: In FileList, 'comment' key triggers review comment (not inline comment)
         // Using separate check for review comment in FileList context
         if !self.local_mode && self.matches_single_key(&key, &kb.comment) {
@@ -246,6 +472,20 @@ impl App {
             return Ok(());
         }
 
+        // Pending review comments
+        if !self.local_mode && self.matches_single_key(&key, &kb.review_drafts) {
+            self.open_review_drafts();
+            return Ok(());
+        }
+
+        // ローカルのレビュー進捗に見終えた印を付ける
+        if !self.local_mode && self.matches_single_key(&key, &kb.mark_reviewed) {
+            if !self.is_filter_selection_empty("file") {
+                self.toggle_file_reviewed();
+            }
+            return Ok(());
+        }
+
         // Refresh
         if self.matches_single_key(&key, &kb.refresh) {
             self.refresh_all();
@@ -259,6 +499,25 @@ impl App {
             return Ok(());
         }
 
+        // マークしたファイル（なければ選択中のファイル）だけで AI Rally
+        if self.matches_single_key(&key, &kb.ai_rally_files) {
+            self.start_scoped_ai_rally();
+            return Ok(());
+        }
+
+        // 前回途中で終わった AI Rally を再開
+        if self.matches_single_key(&key, &kb.resume_ai_rally) {
+            self.resume_ai_rally();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.mark_rally_file) {
+            if !self.is_filter_selection_empty("file") {
+                self.toggle_rally_scope_mark();
+            }
+            return Ok(());
+        }
+
         // Open in browser (disabled in local mode)
         if !self.local_mode && self.matches_single_key(&key, &kb.open_in_browser) {
             if let Some(pr_number) = self.pr_number {
@@ -281,12 +540,46 @@ impl App {
             return Ok(());
         }
 
+        // W: 別のディレクトリのリポジトリへ切り替え（local mode only）
+        if self.local_mode && self.matches_single_key(&key, &kb.switch_repo) {
+            self.open_repo_switcher();
+            return Ok(());
+        }
+
+        // Fixup commits for review comments (local mode only)
+        if self.local_mode && self.matches_single_key(&key, &kb.fixup) {
+            self.open_fixup_helper();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.session_activity) {
+            self.open_session_activity();
+            return Ok(());
+        }
+
+        if self.config.learning.enabled && self.matches_single_key(&key, &kb.key_stats) {
+            self.key_stats_open = true;
+            self.push_popup(PopupId::KeyStats);
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.local_data) {
+            self.open_local_data();
+            return Ok(());
+        }
+
+        if self.matches_single_key(&key, &kb.outbox) {
+            self.open_outbox();
+            return Ok(());
+        }
+
         // Help
         if self.matches_single_key(&key, &kb.help) {
             self.previous_state = AppState::FileList;
             self.state = AppState::Help;
             self.help_scroll_offset = 0;
             self.config_scroll_offset = 0;
+            self.help_filter = None;
             return Ok(());
         }
 
@@ -295,7 +588,7 @@ impl App {
     pub(crate) async fn handle_common_file_list_keys(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<bool> {
         // フィルタ結果が空の場合、ファイル操作を無効化（stale selection 防止）
         if !self.is_filter_selection_empty("file") && self.handle_mark_viewed_key(key) {
@@ -306,21 +599,33 @@ impl App {
 
         // Review actions (disabled in local mode)
         if !self.local_mode && self.matches_single_key(&key, &kb.approve) {
-            self.submit_review(ReviewAction::Approve, terminal).await?;
+            self.start_review(ReviewAction::Approve, terminal).await?;
             return Ok(true);
         }
 
         if !self.local_mode && self.matches_single_key(&key, &kb.request_changes) {
-            self.submit_review(ReviewAction::RequestChanges, terminal)
+            self.start_review(ReviewAction::RequestChanges, terminal)
                 .await?;
             return Ok(true);
         }
 
+        if !self.local_mode && self.matches_single_key(&key, &kb.approve_next) {
+            self.approve_and_open_next(terminal).await?;
+            return Ok(true);
+        }
+
         if !self.local_mode && self.matches_single_key(&key, &kb.comment) {
             self.submit_review(ReviewAction::Comment, terminal).await?;
             return Ok(true);
         }
 
+        if !self.local_mode && self.matches_single_key(&key, &kb.mark_reviewed) {
+            if !self.is_filter_selection_empty("file") {
+                self.toggle_file_reviewed();
+            }
+            return Ok(true);
+        }
+
         if self.matches_single_key(&key, &kb.refresh) {
             self.refresh_all();
             return Ok(true);
@@ -332,6 +637,23 @@ impl App {
             return Ok(true);
         }
 
+        if self.matches_single_key(&key, &kb.ai_rally_files) {
+            self.start_scoped_ai_rally();
+            return Ok(true);
+        }
+
+        if self.matches_single_key(&key, &kb.resume_ai_rally) {
+            self.resume_ai_rally();
+            return Ok(true);
+        }
+
+        if self.matches_single_key(&key, &kb.mark_rally_file) {
+            if !self.is_filter_selection_empty("file") {
+                self.toggle_rally_scope_mark();
+            }
+            return Ok(true);
+        }
+
         if !self.local_mode && self.matches_single_key(&key, &kb.open_in_browser) {
             if let Some(pr_number) = self.pr_number {
                 self.open_pr_in_browser(pr_number);
@@ -353,6 +675,12 @@ impl App {
             return Ok(true);
         }
 
+        // W: 別のディレクトリのリポジトリへ切り替え（local mode only）
+        if self.local_mode && self.matches_single_key(&key, &kb.switch_repo) {
+            self.open_repo_switcher();
+            return Ok(true);
+        }
+
         Ok(false)
     }
     pub(crate) fn handle_mark_viewed_key(&mut self, key: event::KeyEvent) -> bool {
@@ -360,16 +688,12 @@ impl App {
             return false;
         }
 
-        let is_mark_file = key.code == KeyCode::Char('v')
-            && !key.modifiers.contains(KeyModifiers::SHIFT)
-            && !key.modifiers.contains(KeyModifiers::CONTROL)
-            && !key.modifiers.contains(KeyModifiers::ALT);
-        let is_mark_directory = key.code == KeyCode::Char('V')
-            || (key.code == KeyCode::Char('v') && key.modifiers.contains(KeyModifiers::SHIFT));
-        let has_unexpected_modifiers = key.modifiers.contains(KeyModifiers::CONTROL)
-            || key.modifiers.contains(KeyModifiers::ALT);
+        let kb = self.config.keybindings.for_view(KeybindingView::FileList);
+        // Shift-v は v にもマッチするため、ディレクトリを先に判定する
+        let is_mark_directory = self.matches_single_key(&key, &kb.mark_viewed_directory);
+        let is_mark_file = !is_mark_directory && self.matches_single_key(&key, &kb.mark_viewed);
 
-        if has_unexpected_modifiers || (!is_mark_file && !is_mark_directory) {
+        if !is_mark_file && !is_mark_directory {
             return false;
         }
 
@@ -434,12 +758,12 @@ impl App {
         let repo = self.repo.clone();
         let (tx, rx) = mpsc::channel(1);
         self.mark_viewed_receiver = Some((pr_number, rx));
-        let action_label = if set_viewed { "viewed" } else { "unviewed" };
-        self.submission_result = Some((
-            true,
-            format!("Marking {} file(s) as {}...", total_targets, action_label),
-        ));
-        self.submission_result_time = Some(Instant::now());
+        let label = if set_viewed {
+            "Marking viewed"
+        } else {
+            "Unmarking viewed"
+        };
+        self.mark_viewed_progress = Some(Progress::new(label, total_targets));
 
         tokio::spawn(async move {
             let mut marked_paths = Vec::with_capacity(total_targets);
@@ -452,7 +776,13 @@ impl App {
                     github::unmark_file_as_viewed(&repo, &pr_node_id, &path).await
                 };
                 match result {
-                    Ok(()) => marked_paths.push(path),
+                    Ok(()) => {
+                        marked_paths.push(path);
+                        // 進捗は取りこぼしても完了通知で確定するので待たない
+                        let _ = tx.try_send(MarkViewedResult::Progress {
+                            done: marked_paths.len(),
+                        });
+                    }
                     Err(e) => {
                         error = Some(format!("{}: {}", path, e));
                         break;
@@ -516,9 +846,7 @@ impl App {
     pub(crate) fn open_pr_in_browser(&self, pr_number: u32) {
         let repo = self.repo.clone();
         tokio::spawn(async move {
-            let _ =
-                github::gh_command(&["pr", "view", &pr_number.to_string(), "-R", &repo, "--web"])
-                    .await;
+            let _ = github::open_pr_in_browser(&repo, pr_number).await;
         });
     }
 }
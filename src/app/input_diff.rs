@@ -1,10 +1,10 @@
 use anyhow::Result;
 use crossterm::event::{self, KeyCode, KeyModifiers};
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::Stdout;
 
+use crate::config::KeybindingView;
 use crate::filter::ListFilter;
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
+use crate::ui::TuiTerminal;
 
 use super::types::*;
 use super::{App, AppState};
@@ -13,7 +13,7 @@ impl App {
     pub(crate) async fn handle_split_view_file_list_input(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         // フィルタ入力中はフィルタ処理を優先
         if self.handle_filter_input(&key, "file") {
@@ -22,7 +22,18 @@ impl App {
             return Ok(());
         }
 
-        let kb = self.config.keybindings.clone();
+        let kb = self.config.keybindings.for_view(KeybindingView::FileList);
+
+        if self.matches_single_key(&key, &kb.file_finder) {
+            self.open_file_finder();
+            return Ok(());
+        }
+
+        // PR のタブの切り替え
+        if self.handle_pr_tab_key(&key, &kb) {
+            return Ok(());
+        }
+
         let has_filter = self.file_list_filter.is_some();
 
         // Move down
@@ -30,8 +41,7 @@ impl App {
             if has_filter {
                 self.handle_filter_navigation("file", true);
             } else if !self.files().is_empty() {
-                self.selected_file =
-                    (self.selected_file + 1).min(self.files().len().saturating_sub(1));
+                self.move_file_selection(1);
             }
             self.sync_diff_to_selected_file();
             return Ok(());
@@ -41,8 +51,8 @@ impl App {
         if self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up {
             if has_filter {
                 self.handle_filter_navigation("file", false);
-            } else if self.selected_file > 0 {
-                self.selected_file = self.selected_file.saturating_sub(1);
+            } else {
+                self.move_file_selection(-1);
             }
             self.sync_diff_to_selected_file();
             return Ok(());
@@ -53,8 +63,7 @@ impl App {
             if !self.files().is_empty() && !has_filter {
                 let page_step = terminal.size()?.height.saturating_sub(8) as usize;
                 let step = page_step.max(1);
-                self.selected_file =
-                    (self.selected_file + step).min(self.files().len().saturating_sub(1));
+                self.move_file_selection(step as isize);
                 self.sync_diff_to_selected_file();
             }
             return Ok(());
@@ -65,7 +74,7 @@ impl App {
             if !has_filter {
                 let page_step = terminal.size()?.height.saturating_sub(8) as usize;
                 let step = page_step.max(1);
-                self.selected_file = self.selected_file.saturating_sub(step);
+                self.move_file_selection(-(step as isize));
                 self.sync_diff_to_selected_file();
             }
             return Ok(());
@@ -104,11 +113,34 @@ impl App {
                     return Ok(());
                 }
 
+                // Space+f: 全ファイルの grep
+                if self.try_match_sequence(&kb.grep) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_grep();
+                    return Ok(());
+                }
+
+                // Space+s: ファイル一覧の並び順を切り替え
+                if self.try_match_sequence(&kb.sort_files) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.cycle_file_sort();
+                    return Ok(());
+                }
+
+                // より長いシーケンスの途中か、他の操作のシーケンスが揃った
+                if self.resolve_pending_keys(&kb) {
+                    return Ok(());
+                }
+
                 // マッチしなければペンディングをクリア
                 self.clear_pending_keys();
             } else {
                 // シーケンス開始チェック
-                if self.key_could_match_sequence(&key, &kb.filter) {
+                if self.starts_key_sequence(&key, &kb)
+                    || self.key_could_match_sequence(&key, &kb.filter)
+                    || self.key_could_match_sequence(&key, &kb.grep)
+                    || self.key_could_match_sequence(&key, &kb.sort_files)
+                {
                     self.push_pending_key(kb_event);
                     return Ok(());
                 }
@@ -125,6 +157,7 @@ impl App {
             }
             if !self.files().is_empty() {
                 self.state = AppState::SplitViewDiff;
+                self.record_recent_file();
             }
             return Ok(());
         }
@@ -151,6 +184,7 @@ impl App {
             self.state = AppState::Help;
             self.help_scroll_offset = 0;
             self.config_scroll_offset = 0;
+            self.help_filter = None;
             return Ok(());
         }
 
@@ -162,15 +196,9 @@ impl App {
     pub(super) async fn handle_diff_input_common(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
         variant: DiffViewVariant,
     ) -> Result<()> {
-        // シンボルポップアップ表示中
-        if self.symbol_popup.is_some() {
-            self.handle_symbol_popup_input(key, terminal).await?;
-            return Ok(());
-        }
-
         let term_size = terminal.size()?;
         let term_h = term_size.height as usize;
         let term_w = term_size.width as usize;
@@ -183,10 +211,18 @@ impl App {
             }
             DiffViewVariant::Fullscreen => term_h.saturating_sub(8),
         };
+        // 依存マニフェストの要約が diff 領域の上部を占める分を除く
+        let visible_lines = visible_lines
+            .saturating_sub(self.manifest_summary_rows((visible_lines + 2) as u16) as usize);
         let panel_inner_width = self.comment_panel_inner_width(term_w);
 
         // Clone keybindings to avoid borrow issues with self
-        let kb = self.config.keybindings.clone();
+        let kb = self.config.keybindings.for_view(KeybindingView::DiffView);
+
+        // 検索クエリの入力中
+        if self.handle_diff_search_input(&key, visible_lines) {
+            return Ok(());
+        }
 
         // 複数行選択モード中
         if self.multiline_selection.is_some() {
@@ -228,6 +264,12 @@ impl App {
                 return Ok(());
             }
 
+            // E: 選択範囲へのコメントの下書きをエージェントに頼む
+            if self.matches_single_key(&key, &kb.draft_comment) {
+                self.draft_comment_with_ai();
+                return Ok(());
+            }
+
             // Esc / q: 選択モードをキャンセル
             if self.matches_single_key(&key, &kb.quit) || key.code == KeyCode::Esc {
                 self.multiline_selection = None;
@@ -238,6 +280,11 @@ impl App {
             return Ok(());
         }
 
+        // PR のタブの切り替え
+        if self.handle_pr_tab_key(&key, &kb) {
+            return Ok(());
+        }
+
         // コメントパネルフォーカス中
         if self.comment_panel_open {
             // Move down in panel
@@ -290,6 +337,12 @@ impl App {
                 return Ok(());
             }
 
+            // Draft a comment with the AI agent
+            if self.matches_single_key(&key, &kb.draft_comment) {
+                self.draft_comment_with_ai();
+                return Ok(());
+            }
+
             // Reply
             if self.matches_single_key(&key, &kb.reply) {
                 if self.has_comment_at_current_line() {
@@ -298,6 +351,12 @@ impl App {
                 return Ok(());
             }
 
+            // Translate selected comment
+            if self.matches_single_key(&key, &kb.translate_comment) {
+                self.toggle_comment_translation();
+                return Ok(());
+            }
+
             // Tab - select next inline comment
             if key.code == KeyCode::Tab {
                 if self.has_comment_at_current_line() {
@@ -375,7 +434,7 @@ impl App {
         // Get KeyBinding for current event
         let current_kb = event_to_keybinding(&key);
 
-        // Try to match two-key sequences (gd, gf, gg)
+        // Try to match two-key sequences (gd, gf, gl, gb, gc, gg)
         if let Some(kb_event) = current_kb {
             // Check if this key continues a pending sequence
             if !self.pending_keys.is_empty() {
@@ -395,6 +454,147 @@ impl App {
                     return Ok(());
                 }
 
+                // Check for lfs_preview (gl)
+                if self.try_match_sequence(&kb.lfs_preview) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_lfs_preview();
+                    return Ok(());
+                }
+
+                // Check for view_base_file (gb)
+                if self.try_match_sequence(&kb.view_base_file) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_base_file_view();
+                    return Ok(());
+                }
+
+                // Check for toggle_blame (gB)
+                if self.try_match_sequence(&kb.toggle_blame) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_blame();
+                    return Ok(());
+                }
+
+                // Check for next_unresolved_thread (gU)
+                if self.try_match_sequence(&kb.next_unresolved_thread) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.jump_to_next_unresolved_thread();
+                    self.adjust_scroll(visible_lines);
+                    return Ok(());
+                }
+
+                // Check for line_history (gL)
+                if self.try_match_sequence(&kb.line_history) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_line_history();
+                    return Ok(());
+                }
+
+                // Check for toggle_vendored (gv)
+                if self.try_match_sequence(&kb.toggle_vendored) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_vendored_expand();
+                    return Ok(());
+                }
+
+                // Check for toggle_review_draft (gp)
+                if self.try_match_sequence(&kb.toggle_review_draft) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_review_draft_mode();
+                    return Ok(());
+                }
+
+                // Check for compare_local (gc)
+                if self.try_match_sequence(&kb.compare_local) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_local_compare();
+                    return Ok(());
+                }
+
+                // Check for commit_list (gh)
+                if self.try_match_sequence(&kb.commit_list) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_commit_list();
+                    return Ok(());
+                }
+
+                // Check for review_agenda (ga)
+                if self.try_match_sequence(&kb.review_agenda) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_review_agenda();
+                    return Ok(());
+                }
+
+                // Check for request_reviewers (gr)
+                if self.try_match_sequence(&kb.request_reviewers) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_people_picker();
+                    return Ok(());
+                }
+
+                // Check for edit_labels (gt)
+                if self.try_match_sequence(&kb.edit_labels) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_label_picker();
+                    return Ok(());
+                }
+
+                // Check for toggle_pr_draft (gw)
+                if self.try_match_sequence(&kb.toggle_pr_draft) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.request_draft_toggle();
+                    return Ok(());
+                }
+
+                // Check for explain_file (ge)
+                if self.try_match_sequence(&kb.explain_file) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.toggle_file_explanation();
+                    return Ok(());
+                }
+
+                // Check for ai_rally_files (gA)
+                if self.try_match_sequence(&kb.ai_rally_files) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.start_scoped_ai_rally();
+                    return Ok(());
+                }
+
+                // Check for resume_ai_rally (gR)
+                if self.try_match_sequence(&kb.resume_ai_rally) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.resume_ai_rally();
+                    return Ok(());
+                }
+
+                // Check for checkout_pr (go)
+                if self.try_match_sequence(&kb.checkout_pr) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_checkout();
+                    return Ok(());
+                }
+
+                // Check for checks (gs)
+                if self.try_match_sequence(&kb.checks) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_checks();
+                    return Ok(());
+                }
+
+                // Check for resolve_conflicts (gm)
+                if self.try_match_sequence(&kb.resolve_conflicts) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_conflict_resolve();
+                    return Ok(());
+                }
+
+                // Check for grep (Space f)
+                if self.try_match_sequence(&kb.grep) == SequenceMatch::Full {
+                    self.clear_pending_keys();
+                    self.open_grep();
+                    return Ok(());
+                }
+
                 // Check for jump_to_first (gg)
                 if self.try_match_sequence(&kb.jump_to_first) == SequenceMatch::Full {
                     self.clear_pending_keys();
@@ -403,21 +603,77 @@ impl App {
                     return Ok(());
                 }
 
+                // Still a prefix of a longer sequence, or completed another action's sequence
+                if self.resolve_pending_keys(&kb) {
+                    return Ok(());
+                }
+
                 // No match - clear pending keys and fall through
                 self.clear_pending_keys();
             } else {
                 // Check if this key could start a sequence
                 let could_start_gd = self.key_could_match_sequence(&key, &kb.go_to_definition);
                 let could_start_gf = self.key_could_match_sequence(&key, &kb.go_to_file);
+                let could_start_gl = self.key_could_match_sequence(&key, &kb.lfs_preview);
+                let could_start_gb = self.key_could_match_sequence(&key, &kb.view_base_file);
+                let could_start_blame = self.key_could_match_sequence(&key, &kb.toggle_blame);
+                let could_start_history = self.key_could_match_sequence(&key, &kb.line_history);
+                let could_start_unresolved =
+                    self.key_could_match_sequence(&key, &kb.next_unresolved_thread);
+                let could_start_gc = self.key_could_match_sequence(&key, &kb.compare_local);
+                let could_start_gv = self.key_could_match_sequence(&key, &kb.toggle_vendored);
+                let could_start_gp = self.key_could_match_sequence(&key, &kb.toggle_review_draft);
+                let could_start_gh = self.key_could_match_sequence(&key, &kb.commit_list);
+                let could_start_gs = self.key_could_match_sequence(&key, &kb.checks);
+                let could_start_ga = self.key_could_match_sequence(&key, &kb.review_agenda);
+                let could_start_gr = self.key_could_match_sequence(&key, &kb.request_reviewers);
+                let could_start_gt = self.key_could_match_sequence(&key, &kb.edit_labels);
+                let could_start_gw = self.key_could_match_sequence(&key, &kb.toggle_pr_draft);
+                let could_start_ge = self.key_could_match_sequence(&key, &kb.explain_file);
+                let could_start_rally = self.key_could_match_sequence(&key, &kb.ai_rally_files);
+                let could_start_resume = self.key_could_match_sequence(&key, &kb.resume_ai_rally);
+                let could_start_go = self.key_could_match_sequence(&key, &kb.checkout_pr);
+                let could_start_gm = self.key_could_match_sequence(&key, &kb.resolve_conflicts);
                 let could_start_gg = self.key_could_match_sequence(&key, &kb.jump_to_first);
-
-                if could_start_gd || could_start_gf || could_start_gg {
+                let could_start_grep = self.key_could_match_sequence(&key, &kb.grep);
+
+                if self.starts_key_sequence(&key, &kb)
+                    || could_start_gd
+                    || could_start_gf
+                    || could_start_gl
+                    || could_start_gb
+                    || could_start_blame
+                    || could_start_history
+                    || could_start_unresolved
+                    || could_start_gc
+                    || could_start_gv
+                    || could_start_gp
+                    || could_start_gh
+                    || could_start_gs
+                    || could_start_ga
+                    || could_start_gr
+                    || could_start_gt
+                    || could_start_gw
+                    || could_start_ge
+                    || could_start_rally
+                    || could_start_resume
+                    || could_start_go
+                    || could_start_gm
+                    || could_start_gg
+                    || could_start_grep
+                {
                     self.push_pending_key(kb_event);
                     return Ok(());
                 }
             }
         }
 
+        // Esc: 検索のハイライトを消す
+        if key.code == KeyCode::Esc && self.diff_search.is_some() {
+            self.clear_diff_search();
+            return Ok(());
+        }
+
         // Variant-specific quit/back handling (outside panel)
         match variant {
             DiffViewVariant::SplitPane => {
@@ -485,6 +741,12 @@ impl App {
             return Ok(());
         }
 
+        // E: 選択中の行へのコメントの下書きをエージェントに頼む
+        if self.matches_single_key(&key, &kb.draft_comment) {
+            self.draft_comment_with_ai();
+            return Ok(());
+        }
+
         // Move down
         if self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down {
             if self.diff_line_count > 0 {
@@ -534,6 +796,24 @@ impl App {
             return Ok(());
         }
 
+        // Search in the diff
+        if self.matches_single_key(&key, &kb.search) {
+            self.start_diff_search();
+            return Ok(());
+        }
+
+        // 検索中は n/N で一致箇所へ移動
+        if self.has_diff_search() {
+            if self.matches_single_key(&key, &kb.next_comment) {
+                self.jump_to_search_match(true, visible_lines);
+                return Ok(());
+            }
+            if self.matches_single_key(&key, &kb.prev_comment) {
+                self.jump_to_search_match(false, visible_lines);
+                return Ok(());
+            }
+        }
+
         // Next comment
         if self.matches_single_key(&key, &kb.next_comment) {
             self.jump_to_next_comment();
@@ -553,6 +833,18 @@ impl App {
             return Ok(());
         }
 
+        // Toggle relative/absolute timestamps
+        if self.matches_single_key(&key, &kb.toggle_time_format) {
+            self.time_format.toggle_style();
+            return Ok(());
+        }
+
+        // Pending review comments
+        if !self.local_mode && self.matches_single_key(&key, &kb.review_drafts) {
+            self.open_review_drafts();
+            return Ok(());
+        }
+
         // Open panel (local mode ではコメント対象の PR がないため無効)
         if !self.local_mode && self.matches_single_key(&key, &kb.open_panel) {
             self.comment_panel_open = true;
@@ -580,7 +872,7 @@ impl App {
     pub(crate) async fn handle_split_view_diff_input(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         self.handle_diff_input_common(key, terminal, DiffViewVariant::SplitPane)
             .await
@@ -588,7 +880,7 @@ impl App {
     pub(crate) async fn handle_diff_view_input(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         self.handle_diff_input_common(key, terminal, DiffViewVariant::Fullscreen)
             .await
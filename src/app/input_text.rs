@@ -2,8 +2,8 @@ use anyhow::Result;
 use crossterm::event::{self, KeyCode, KeyEvent};
 use tokio::sync::mpsc;
 
-use crate::github;
-use crate::loader::CommentSubmitResult;
+use crate::cache::OutboxPayload;
+use crate::loader::{self, CommentSubmitResult};
 use crate::ui::text_area::TextAreaAction;
 
 use super::types::*;
@@ -19,7 +19,9 @@ impl App {
         match self.input_text_area.input(key) {
             TextAreaAction::Submit => {
                 let content = self.input_text_area.content();
-                if content.trim().is_empty() {
+                // メモは空で保存すると削除になるため、キャンセル扱いにしない
+                let is_note = matches!(self.input_mode, Some(InputMode::FileNote { .. }));
+                if content.trim().is_empty() && !is_note {
                     // 空の場合はキャンセル扱い
                     self.cancel_input();
                     return Ok(());
@@ -27,17 +29,37 @@ impl App {
 
                 match self.input_mode.take() {
                     Some(InputMode::Comment(ctx)) => {
-                        self.submit_comment(ctx, content);
+                        if self.review_draft_mode {
+                            self.queue_review_draft(ctx, content);
+                        } else {
+                            self.submit_comment(ctx, content);
+                        }
                     }
                     Some(InputMode::Suggestion {
                         context,
                         original_code: _,
                     }) => {
-                        self.submit_suggestion(context, content);
+                        if self.review_draft_mode {
+                            self.queue_review_draft(context, suggestion_body(&content));
+                        } else {
+                            self.submit_suggestion(context, content);
+                        }
                     }
                     Some(InputMode::Reply { comment_id, .. }) => {
                         self.submit_reply(comment_id, content);
                     }
+                    Some(InputMode::BatchReply { targets }) => {
+                        self.submit_batch_reply(targets, content);
+                    }
+                    Some(InputMode::Nudge { pr_number, .. }) => {
+                        self.submit_nudge(pr_number, content);
+                    }
+                    Some(InputMode::ReviewAgenda { pr_number }) => {
+                        self.submit_review_agenda(pr_number, content);
+                    }
+                    Some(InputMode::FileNote { filename }) => {
+                        self.save_file_note(&filename, &content);
+                    }
                     None => {}
                 }
                 self.state = self.preview_return_state;
@@ -58,91 +80,38 @@ impl App {
         self.state = self.preview_return_state;
     }
     pub(crate) fn submit_comment(&mut self, ctx: LineInputContext, body: String) {
-        let Some(file) = self.files().get(ctx.file_index) else {
-            return;
-        };
-        let Some(pr) = self.pr() else {
-            return;
-        };
-
-        let commit_id = pr.head.sha.clone();
-        let filename = file.filename.clone();
-        let repo = self.repo.clone();
-        let pr_number = self.pr_number();
-        let position = ctx.diff_position;
-        let start_line = ctx.start_line_number;
-        let end_line = ctx.line_number;
-
-        let (tx, rx) = mpsc::channel(1);
-        self.comment_submit_receiver = Some((pr_number, rx));
-        self.comment_submitting = true;
-
-        tokio::spawn(async move {
-            let result = if let Some(start) = start_line {
-                github::create_multiline_review_comment(
-                    &repo, pr_number, &commit_id, &filename, start, end_line, "RIGHT", &body,
-                )
-                .await
-            } else {
-                github::create_review_comment(
-                    &repo, pr_number, &commit_id, &filename, position, &body,
-                )
-                .await
-            };
-
-            let _ = tx
-                .send(match result {
-                    Ok(_) => CommentSubmitResult::Success,
-                    Err(e) => CommentSubmitResult::Error(e.to_string()),
-                })
-                .await;
-        });
+        self.submit_line_comment(ctx, body, false);
     }
 
     pub(crate) fn submit_suggestion(&mut self, ctx: LineInputContext, suggested_code: String) {
+        self.submit_line_comment(ctx, suggestion_body(&suggested_code), true);
+    }
+
+    fn submit_line_comment(&mut self, ctx: LineInputContext, body: String, suggestion: bool) {
         let Some(file) = self.files().get(ctx.file_index) else {
             return;
         };
         let Some(pr) = self.pr() else {
             return;
         };
-
-        let commit_id = pr.head.sha.clone();
-        let filename = file.filename.clone();
-        let body = format!("```suggestion\n{}\n```", suggested_code.trim_end());
-        let repo = self.repo.clone();
-        let pr_number = self.pr_number();
-        let position = ctx.diff_position;
-        let start_line = ctx.start_line_number;
-        let end_line = ctx.line_number;
-
-        let (tx, rx) = mpsc::channel(1);
-        self.comment_submit_receiver = Some((pr_number, rx));
-        self.comment_submitting = true;
-
-        tokio::spawn(async move {
-            let result = if let Some(start) = start_line {
-                github::create_multiline_review_comment(
-                    &repo, pr_number, &commit_id, &filename, start, end_line, "RIGHT", &body,
-                )
-                .await
-            } else {
-                github::create_review_comment(
-                    &repo, pr_number, &commit_id, &filename, position, &body,
-                )
-                .await
-            };
-
-            let _ = tx
-                .send(match result {
-                    Ok(_) => CommentSubmitResult::Success,
-                    Err(e) => CommentSubmitResult::Error(e.to_string()),
-                })
-                .await;
-        });
+        let payload = OutboxPayload::Comment {
+            commit_id: pr.head.sha.clone(),
+            path: file.filename.clone(),
+            position: ctx.diff_position,
+            start_line: ctx.start_line_number,
+            line: ctx.line_number,
+            body,
+            suggestion,
+        };
+        self.spawn_comment_submit(payload);
     }
 
     pub(crate) fn submit_reply(&mut self, comment_id: u64, body: String) {
+        self.spawn_comment_submit(OutboxPayload::Reply { comment_id, body });
+    }
+
+    /// バックグラウンドで送信する。一時的な失敗は `poll_comment_submit_updates` で送信待ちに入る
+    fn spawn_comment_submit(&mut self, payload: OutboxPayload) {
         let repo = self.repo.clone();
         let pr_number = self.pr_number();
 
@@ -151,16 +120,11 @@ impl App {
         self.comment_submitting = true;
 
         tokio::spawn(async move {
-            let result = github::create_reply_comment(&repo, pr_number, comment_id, &body).await;
-
-            let _ = tx
-                .send(match result {
-                    Ok(_) => CommentSubmitResult::Success,
-                    Err(e) => CommentSubmitResult::Error(e.to_string()),
-                })
-                .await;
+            let result = loader::submit_payload(&repo, pr_number, &payload).await;
+            let _ = tx.send(CommentSubmitResult::new(result, payload)).await;
         });
     }
+
     pub(super) fn handle_pending_approve_choice(&mut self, key: &KeyEvent) -> PendingApproveChoice {
         if self.pending_approve_body.is_none() {
             return PendingApproveChoice::Ignore;
@@ -179,3 +143,8 @@ impl App {
         }
     }
 }
+
+/// サジェスチョンの入力内容をコメント本文にする
+fn suggestion_body(suggested_code: &str) -> String {
+    format!("```suggestion\n{}\n```", suggested_code.trim_end())
+}
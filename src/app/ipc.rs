@@ -0,0 +1,117 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::diff;
+use crate::ipc::IpcCommand;
+
+use super::types::*;
+use super::App;
+
+impl App {
+    /// IPC で受信したコマンドをすべて処理
+    pub(crate) fn poll_ipc_commands(&mut self) {
+        loop {
+            let Some(ref mut rx) = self.ipc_receiver else {
+                return;
+            };
+            match rx.try_recv() {
+                Ok(command) => self.handle_ipc_command(command),
+                Err(mpsc::error::TryRecvError::Empty) => return,
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.ipc_receiver = None;
+                    return;
+                }
+            }
+        }
+    }
+
+    fn set_ipc_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    pub(crate) fn handle_ipc_command(&mut self, command: IpcCommand) {
+        // 入力中のテキストを失わないよう、入力モード中は画面遷移しない
+        if self.state == AppState::TextInput {
+            self.set_ipc_message(false, "IPC: ignored while editing a comment");
+            return;
+        }
+        if !matches!(self.data_state, DataState::Loaded { .. }) {
+            self.set_ipc_message(false, "IPC: no diff loaded");
+            return;
+        }
+
+        match command {
+            IpcCommand::Open { path, line } => self.ipc_open_file(&path, line),
+            IpcCommand::Refresh => self.refresh_all(),
+            IpcCommand::Comment { path, line, body } => self.ipc_post_comment(&path, line, body),
+        }
+    }
+
+    fn ipc_open_file(&mut self, path: &str, line: Option<u32>) {
+        let Some(file_index) = self.files().iter().position(|f| f.filename == path) else {
+            self.set_ipc_message(false, format!("IPC: {} is not in the diff", path));
+            return;
+        };
+
+        self.selected_file = file_index;
+        self.diff_view_return_state = AppState::FileList;
+        self.state = AppState::DiffView;
+        self.selected_line = 0;
+        self.scroll_offset = 0;
+        self.multiline_selection = None;
+        self.update_diff_line_count();
+        self.update_file_comment_positions();
+        self.ensure_diff_cache();
+
+        let diff_line_index = line.and_then(|line| {
+            let patch = self.files().get(file_index)?.patch.as_deref()?;
+            Self::find_diff_line_index(patch, line)
+        });
+        if let Some(idx) = diff_line_index {
+            self.selected_line = idx;
+            self.scroll_offset = idx;
+        }
+    }
+
+    fn ipc_post_comment(&mut self, path: &str, line: u32, body: String) {
+        if self.local_mode {
+            self.set_ipc_message(false, "IPC: comments are not available in local mode");
+            return;
+        }
+        if self.comment_submitting {
+            self.set_ipc_message(false, "IPC: another comment is being submitted");
+            return;
+        }
+        if body.trim().is_empty() {
+            self.set_ipc_message(false, "IPC: comment body is empty");
+            return;
+        }
+
+        let Some((file_index, patch)) = self
+            .files()
+            .iter()
+            .enumerate()
+            .find(|(_, f)| f.filename == path)
+            .and_then(|(i, f)| f.patch.clone().map(|p| (i, p)))
+        else {
+            self.set_ipc_message(false, format!("IPC: {} is not in the diff", path));
+            return;
+        };
+        let Some(diff_position) = diff::line_number_to_position(&patch, line) else {
+            self.set_ipc_message(false, format!("IPC: {}:{} is not in the diff", path, line));
+            return;
+        };
+
+        self.submit_comment(
+            LineInputContext {
+                file_index,
+                line_number: line,
+                diff_position,
+                start_line_number: None,
+            },
+            body,
+        );
+    }
+}
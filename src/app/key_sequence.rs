@@ -1,9 +1,10 @@
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::time::Instant;
 
+use crate::config::{KeybindingView, KeybindingsConfig};
 use crate::keybinding::{event_to_keybinding, KeyBinding, KeySequence, SequenceMatch, SEQUENCE_TIMEOUT};
 
-use super::App;
+use super::{App, AppState};
 
 impl App {
     pub(crate) fn check_sequence_timeout(&mut self) {
@@ -30,7 +31,12 @@ impl App {
     }
 
     /// Check if a KeyEvent matches a KeySequence (single-key sequences only)
+    ///
+    /// While a completed sequence is being handled, only that sequence matches.
     pub(crate) fn matches_single_key(&self, event: &KeyEvent, seq: &KeySequence) -> bool {
+        if let Some(ref completed) = self.completed_sequence {
+            return completed == seq;
+        }
         if !seq.is_single() {
             return false;
         }
@@ -87,6 +93,9 @@ impl App {
 
     /// Check if current key event starts or continues a sequence that could match the given sequence
     pub(crate) fn key_could_match_sequence(&self, event: &KeyEvent, seq: &KeySequence) -> bool {
+        if self.completed_sequence.is_some() {
+            return false;
+        }
         let Some(kb) = event_to_keybinding(event) else {
             return false;
         };
@@ -117,4 +126,53 @@ impl App {
             .map(|expected| *expected == kb)
             .unwrap_or(false)
     }
+
+    /// Check if the key starts any multi-key sequence in `kb` (including user-defined ones)
+    pub(crate) fn starts_key_sequence(&self, event: &KeyEvent, kb: &KeybindingsConfig) -> bool {
+        if self.completed_sequence.is_some() || !self.pending_keys.is_empty() {
+            return false;
+        }
+        let Some(key) = event_to_keybinding(event) else {
+            return false;
+        };
+        kb.bindings()
+            .into_iter()
+            .any(|(_, seq)| !seq.is_single() && seq.first() == Some(&key))
+    }
+
+    /// Handle pending keys that no sequence of the current view matched.
+    ///
+    /// Returns true if the key was consumed: the pending keys are a prefix of a longer
+    /// sequence (keep waiting), or they complete a sequence bound to an action that is
+    /// otherwise checked as a single key. In the latter case the sequence is stored in
+    /// `completed_sequence` and the key is dispatched once more, so that
+    /// [`App::matches_single_key`] matches that action.
+    pub(crate) fn resolve_pending_keys(&mut self, kb: &KeybindingsConfig) -> bool {
+        let mut partial = false;
+        for (_, seq) in kb.bindings() {
+            match self.try_match_sequence(seq) {
+                SequenceMatch::Full if !seq.is_single() => {
+                    self.completed_sequence = Some(seq.clone());
+                    self.clear_pending_keys();
+                    return true;
+                }
+                SequenceMatch::Partial => partial = true,
+                _ => {}
+            }
+        }
+        partial
+    }
+
+    /// Keybindings of the current screen, with its `[keybindings.views.<view>]` overrides applied
+    pub(crate) fn current_keybindings(&self) -> KeybindingsConfig {
+        let view = match self.state {
+            AppState::PullRequestList => KeybindingView::PrList,
+            AppState::FileList | AppState::SplitViewFileList => KeybindingView::FileList,
+            AppState::DiffView | AppState::SplitViewDiff => KeybindingView::DiffView,
+            AppState::CommentList => KeybindingView::CommentList,
+            AppState::AiRally => KeybindingView::AiRally,
+            _ => return self.config.keybindings.clone(),
+        };
+        self.config.keybindings.for_view(view)
+    }
 }
@@ -0,0 +1,274 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::audit::{self, AuditAction, AuditEntry};
+use crate::github::{self, Label, RepoLabel};
+use crate::ui::picker::{Picker, PickerAction, PickerItem};
+
+use super::{App, DataState, PopupId};
+
+/// ラベルの変更結果（変更後のラベル）
+pub(crate) type LabelUpdateResult = Result<Vec<String>, String>;
+
+/// ラベル編集のポップアップ
+#[derive(Debug, Clone)]
+pub struct LabelPickerState {
+    /// リポジトリのラベル（読み込み中は None）
+    pub labels: Option<Vec<RepoLabel>>,
+    pub error: Option<String>,
+    /// 開いたときに PR に付いていたラベル
+    original: Vec<String>,
+    /// 変更を反映中
+    pub applying: bool,
+    pub picker: Picker,
+}
+
+impl LabelPickerState {
+    /// 付いているラベルを先頭に、リポジトリのラベルを並べる
+    fn items(&self) -> Vec<PickerItem> {
+        let repo_labels = self.labels.as_deref().unwrap_or_default();
+        let mut items: Vec<PickerItem> = self
+            .original
+            .iter()
+            .map(|name| PickerItem {
+                label: name.clone(),
+                detail: repo_labels
+                    .iter()
+                    .find(|l| l.name == *name)
+                    .and_then(|l| l.description.clone()),
+                checked: true,
+            })
+            .collect();
+        items.extend(
+            repo_labels
+                .iter()
+                .filter(|l| !self.original.contains(&l.name))
+                .map(|l| PickerItem {
+                    label: l.name.clone(),
+                    detail: l.description.clone(),
+                    checked: false,
+                }),
+        );
+        items
+    }
+
+    /// 読み込んだリポジトリのラベルで候補を作り直す。読み込み中に付け外しした印は保つ
+    fn set_repo_labels(&mut self, result: Result<Vec<RepoLabel>, String>) {
+        match result {
+            Ok(labels) => self.labels = Some(labels),
+            Err(e) => self.error = Some(e),
+        }
+        let toggled: Vec<String> = self
+            .picker
+            .items()
+            .iter()
+            .filter(|item| item.checked != self.original.contains(&item.label))
+            .map(|item| item.label.clone())
+            .collect();
+        let items = self
+            .items()
+            .into_iter()
+            .map(|mut item| {
+                if toggled.contains(&item.label) {
+                    item.checked = !item.checked;
+                }
+                item
+            })
+            .collect();
+        self.picker.set_items(items);
+    }
+
+    /// (追加するラベル, 外すラベル)
+    fn changes(&self) -> (Vec<String>, Vec<String>) {
+        let checked: Vec<&String> = self
+            .picker
+            .items()
+            .iter()
+            .filter(|item| item.checked)
+            .map(|item| &item.label)
+            .collect();
+        let added = checked
+            .iter()
+            .filter(|&&name| !self.original.contains(name))
+            .map(|&name| name.clone())
+            .collect();
+        let removed = self
+            .original
+            .iter()
+            .filter(|name| !checked.contains(name))
+            .cloned()
+            .collect();
+        (added, removed)
+    }
+}
+
+impl App {
+    fn set_label_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// ラベル編集のポップアップを開き、リポジトリのラベルを読み込む
+    pub(crate) fn open_label_picker(&mut self) {
+        if self.local_mode {
+            self.set_label_message(false, "Labels can only be edited on PRs");
+            return;
+        }
+        let (Some(pr_number), Some(pr)) = (self.pr_number, self.pr()) else {
+            return;
+        };
+        let original = pr.labels.iter().map(|l| l.name.clone()).collect();
+        let mut state = LabelPickerState {
+            labels: None,
+            error: None,
+            original,
+            applying: false,
+            picker: Picker::default(),
+        };
+        state.picker.set_items(state.items());
+        self.label_picker = Some(state);
+        self.push_popup(PopupId::Labels);
+
+        let (tx, rx) = mpsc::channel(1);
+        self.label_list_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = github::fetch_repo_labels(&repo)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn poll_label_updates(&mut self) {
+        if let Some((origin_pr, ref mut rx)) = self.label_list_receiver {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.label_list_receiver = None;
+                    if self.pr_number == Some(origin_pr) {
+                        if let Some(ref mut state) = self.label_picker {
+                            state.set_repo_labels(result);
+                        }
+                    }
+                }
+                Err(mpsc::error::TryRecvError::Empty) => {}
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.label_list_receiver = None;
+                }
+            }
+        }
+
+        let Some((origin_pr, ref mut rx)) = self.label_update_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(result) => {
+                self.label_update_receiver = None;
+                if self.pr_number == Some(origin_pr) {
+                    self.apply_label_update(result);
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.label_update_receiver = None;
+                if let Some(ref mut state) = self.label_picker {
+                    state.applying = false;
+                }
+            }
+        }
+    }
+
+    fn apply_label_update(&mut self, result: LabelUpdateResult) {
+        match result {
+            Ok(names) => {
+                if let DataState::Loaded { ref mut pr, .. } = self.data_state {
+                    pr.labels = names.into_iter().map(|name| Label { name }).collect();
+                }
+                self.label_picker = None;
+                self.set_label_message(true, "Labels updated");
+            }
+            Err(e) => {
+                if let Some(ref mut state) = self.label_picker {
+                    state.applying = false;
+                }
+                self.set_label_message(false, format!("Failed to update labels: {}", e));
+            }
+        }
+    }
+
+    /// 付け外しした印を PR に反映する
+    fn submit_label_changes(&mut self) {
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+        let Some(ref mut state) = self.label_picker else {
+            return;
+        };
+        if state.applying {
+            return;
+        }
+        let (added, removed) = state.changes();
+        if added.is_empty() && removed.is_empty() {
+            self.label_picker = None;
+            return;
+        }
+        state.applying = true;
+        let mut labels: Vec<String> = state
+            .original
+            .iter()
+            .filter(|name| !removed.contains(name))
+            .cloned()
+            .collect();
+        labels.extend(added.iter().cloned());
+
+        let (tx, rx) = mpsc::channel(1);
+        self.label_update_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let mut result = github::add_labels(&repo, pr_number, &added).await;
+            for name in &removed {
+                if result.is_err() {
+                    break;
+                }
+                result = github::remove_label(&repo, pr_number, name).await;
+            }
+            let target = added
+                .iter()
+                .map(|name| format!("+{}", name))
+                .chain(removed.iter().map(|name| format!("-{}", name)))
+                .collect::<Vec<_>>()
+                .join(" ");
+            audit::record(&AuditEntry::new(
+                &repo,
+                pr_number,
+                AuditAction::EditLabels,
+                Some(target),
+                &result,
+            ));
+            let _ = tx
+                .send(result.map(|()| labels).map_err(|e| format!("{:#}", e)))
+                .await;
+        });
+    }
+
+    pub(crate) fn handle_label_picker_input(&mut self, key: &KeyEvent) {
+        let Some(ref mut state) = self.label_picker else {
+            return;
+        };
+        if state.applying {
+            return;
+        }
+        // Tab で選択中のラベルの付け外しを切り替え、Enter でまとめて反映する
+        if key.code == KeyCode::Tab {
+            state.picker.toggle_checked();
+            return;
+        }
+        match state.picker.handle_key(key) {
+            PickerAction::Continue => {}
+            PickerAction::Close => self.close_popup(PopupId::Labels),
+            PickerAction::Select(_) => self.submit_label_changes(),
+        }
+    }
+}
@@ -0,0 +1,134 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::keybinding::{event_to_keybinding, KeySequence};
+use crate::ui::popup::PopupKey;
+
+use super::{App, AppState, PopupId};
+
+/// フォールバックキーを押したときにキーバインドを表示しておく時間
+const HINT_DURATION: Duration = Duration::from_millis(1500);
+
+/// 学習モードで集計するキーごとの使用回数
+#[derive(Debug, Default)]
+pub struct KeyUsageStats {
+    counts: HashMap<String, usize>,
+    /// フォールバックキー → 同じ操作のキーバインド
+    home_row: HashMap<String, String>,
+}
+
+impl KeyUsageStats {
+    pub fn record(&mut self, key: String, home_row: Option<String>) {
+        if let Some(binding) = home_row {
+            self.home_row.insert(key.clone(), binding);
+        }
+        *self.counts.entry(key).or_insert(0) += 1;
+    }
+
+    pub fn total(&self) -> usize {
+        self.counts.values().sum()
+    }
+
+    /// 使用回数の多い順（同数ならキー名順）。フォールバックキーには対応するキーバインドを添える
+    pub fn ranked(&self) -> Vec<(&str, usize, Option<&str>)> {
+        let mut ranked: Vec<_> = self
+            .counts
+            .iter()
+            .map(|(key, count)| {
+                (
+                    key.as_str(),
+                    *count,
+                    self.home_row.get(key).map(String::as_str),
+                )
+            })
+            .collect();
+        ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+        ranked
+    }
+}
+
+impl App {
+    /// 入力のたびに呼ばれる。学習モードならキーを集計し、
+    /// 矢印キーや Enter で操作したときは対応するキーバインドを表示する
+    pub(crate) fn record_key_usage(&mut self, key: &KeyEvent) {
+        if !self.config.learning.enabled {
+            return;
+        }
+        let Some(pressed) = event_to_keybinding(key).map(|b| b.display()) else {
+            return;
+        };
+        let hint = self.fallback_hint(key);
+        self.key_usage
+            .record(pressed.clone(), hint.as_ref().map(|(b, _)| b.clone()));
+        if let Some((binding, action)) = hint {
+            self.key_hint = Some((
+                format!("{} → {}  ({})", pressed, binding, action),
+                Instant::now(),
+            ));
+        }
+    }
+
+    /// フォールバックキーに対応するキーバインドと操作名。
+    /// ポップアップや入力中など、キーの意味が変わる場面では表示しない
+    fn fallback_hint(&self, key: &KeyEvent) -> Option<(String, &'static str)> {
+        if self.focused_popup().is_some()
+            || [&self.pr_list_filter, &self.file_list_filter]
+                .into_iter()
+                .flatten()
+                .any(|f| f.input_active)
+        {
+            return None;
+        }
+        let kb = self.current_keybindings();
+        let in_list = matches!(
+            self.state,
+            AppState::FileList | AppState::SplitViewFileList | AppState::SplitViewDiff
+        );
+        let navigable =
+            in_list || matches!(self.state, AppState::DiffView | AppState::PullRequestList);
+        let (seq, action): (&KeySequence, &'static str) = match key.code {
+            KeyCode::Down if navigable => (&kb.move_down, "move down"),
+            KeyCode::Up if navigable => (&kb.move_up, "move up"),
+            KeyCode::Right if in_list => (&kb.move_right, "move right"),
+            KeyCode::Left
+                if matches!(
+                    self.state,
+                    AppState::SplitViewFileList | AppState::SplitViewDiff
+                ) =>
+            {
+                (&kb.move_left, "move left")
+            }
+            KeyCode::Enter
+                if matches!(self.state, AppState::FileList | AppState::SplitViewFileList) =>
+            {
+                (&kb.move_right, "open diff")
+            }
+            _ => return None,
+        };
+        let binding = seq.display();
+        // キーバインド自体がそのキーに設定されていれば教えることはない
+        if event_to_keybinding(key).is_some_and(|b| b.display() == binding) {
+            return None;
+        }
+        Some((binding, action))
+    }
+
+    /// 表示期間中のキーバインド表示
+    pub fn key_hint_text(&self) -> Option<&str> {
+        self.key_hint
+            .as_ref()
+            .filter(|(_, shown_at)| shown_at.elapsed() < HINT_DURATION)
+            .map(|(text, _)| text.as_str())
+    }
+
+    pub(crate) fn handle_key_stats_input(&mut self, key: &KeyEvent) {
+        let kb = self.current_keybindings();
+        if PopupKey::from_key(key, &kb) == PopupKey::Close
+            || self.matches_single_key(key, &kb.key_stats)
+        {
+            self.close_popup(PopupId::KeyStats);
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use super::App;
+
+impl App {
+    /// 選択中のファイルが LFS 実体のプレビュー表示中か
+    pub(crate) fn is_lfs_preview(&self, file_index: usize) -> bool {
+        self.files()
+            .get(file_index)
+            .is_some_and(|f| self.lfs_pointer_patches.contains_key(&f.filename))
+    }
+
+    /// diff ヘッダーに添える LFS 表示（ポインタ / 実体プレビュー）
+    pub fn lfs_label(&self, file_index: usize) -> Option<&'static str> {
+        if self.is_lfs_preview(file_index) {
+            return Some("LFS contents");
+        }
+        self.files()
+            .get(file_index)
+            .and_then(|f| f.patch.as_deref())
+            .and_then(crate::lfs::parse_patch)
+            .map(|_| "LFS object")
+    }
+
+    /// LFS ポインタの実体を取得して内容の diff を表示する。表示中なら元に戻す
+    pub(crate) fn toggle_lfs_preview(&mut self) {
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        let filename = file.filename.clone();
+        let patch = file.patch.clone();
+
+        if let Some(original) = self.lfs_pointer_patches.remove(&filename) {
+            // リロードでポインタ patch に戻っていればそのまま取得し直す
+            if patch.as_deref().and_then(crate::lfs::parse_patch).is_none() {
+                self.replace_file_patch(&filename, Some(original));
+                return;
+            }
+        }
+
+        let Some(pointers) = patch.as_deref().and_then(crate::lfs::parse_patch) else {
+            self.set_lfs_status(false, "Not a Git LFS pointer".to_string());
+            return;
+        };
+        if self.lfs_receiver.is_some() {
+            self.set_lfs_status(false, "LFS object is already being fetched".to_string());
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel(1);
+        self.lfs_receiver = Some(rx);
+        self.set_lfs_status(true, format!("Fetching LFS object for {}...", filename));
+        let working_dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let result = crate::lfs::fetch_content_diff(working_dir, filename.clone(), pointers)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((filename, result)).await;
+        });
+    }
+
+    pub(crate) fn poll_lfs_updates(&mut self) {
+        let Some(ref mut rx) = self.lfs_receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok((filename, result)) => {
+                self.lfs_receiver = None;
+                match result {
+                    Ok(diff) => {
+                        let Some(original) = self
+                            .files()
+                            .iter()
+                            .find(|f| f.filename == filename)
+                            .and_then(|f| f.patch.clone())
+                        else {
+                            return;
+                        };
+                        self.lfs_pointer_patches.insert(filename.clone(), original);
+                        self.replace_file_patch(&filename, Some(diff));
+                        self.set_lfs_status(true, format!("Showing LFS contents of {}", filename));
+                    }
+                    Err(e) => self.set_lfs_status(false, e),
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.lfs_receiver = None;
+            }
+        }
+    }
+
+    fn set_lfs_status(&mut self, ok: bool, message: String) {
+        self.submission_result = Some((ok, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
@@ -0,0 +1,231 @@
+use std::time::Instant;
+
+use crossterm::event::KeyEvent;
+use tokio::sync::mpsc;
+
+use crate::github::ChangedFile;
+use crate::line_history::{LineChange, LineHistory};
+use crate::ui::popup::PopupKey;
+
+use super::{App, AppState, PopupId};
+
+/// 選択した行の変更履歴のポップアップ
+#[derive(Debug)]
+pub struct LineHistoryState {
+    pub filename: String,
+    /// 調べた行の範囲（新しい側の行番号）
+    pub start: u32,
+    pub end: u32,
+    /// 読み込み中は None
+    pub history: Option<LineHistory>,
+    pub error: Option<String>,
+    pub selected: usize,
+    /// diff を読み込み中のコミット
+    pub loading_sha: Option<String>,
+}
+
+impl App {
+    fn set_line_history_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 選択中の行（複数行選択中はその範囲）を変更したコミットの一覧を開く
+    pub(crate) fn open_line_history(&mut self) {
+        if self.commit_view.is_some() {
+            self.set_line_history_message(false, "Line history is only available on the PR diff");
+            return;
+        }
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        let Some(patch) = file.patch.as_deref() else {
+            return;
+        };
+        let (first, last) = self
+            .multiline_selection
+            .as_ref()
+            .map_or((self.selected_line, self.selected_line), |s| {
+                (s.start(), s.end())
+            });
+        let new_line = |index| crate::diff::get_line_info(patch, index)?.new_line_number;
+        let (Some(start), Some(end)) = (new_line(first), new_line(last)) else {
+            self.set_line_history_message(false, "Select an added or unchanged line");
+            return;
+        };
+        let filename = file.filename.clone();
+        // ローカルモードは HEAD から辿る
+        let rev = if self.local_mode {
+            None
+        } else {
+            match self.pr() {
+                Some(pr) => Some(pr.head.sha.clone()),
+                None => return,
+            }
+        };
+
+        self.line_history = Some(LineHistoryState {
+            filename: filename.clone(),
+            start,
+            end,
+            history: None,
+            error: None,
+            selected: 0,
+            loading_sha: None,
+        });
+        self.line_history_diff_receiver = None;
+        self.push_popup(PopupId::LineHistory);
+
+        let (tx, rx) = mpsc::channel(1);
+        self.line_history_receiver = Some(rx);
+        let repo = self.repo.clone();
+        let working_dir = self.working_dir.clone();
+        tokio::spawn(async move {
+            let result = crate::line_history::fetch_line_history(
+                &repo,
+                working_dir.as_deref(),
+                rev.as_deref(),
+                &filename,
+                start,
+                end,
+            )
+            .await
+            .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn poll_line_history_updates(&mut self) {
+        if let Some(ref mut rx) = self.line_history_receiver {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.line_history_receiver = None;
+                    if let Some(ref mut state) = self.line_history {
+                        match result {
+                            Ok(history) => state.history = Some(history),
+                            Err(e) => state.error = Some(e),
+                        }
+                    }
+                }
+                Err(mpsc::error::TryRecvError::Empty) => {}
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.line_history_receiver = None;
+                }
+            }
+        }
+
+        let Some(ref mut rx) = self.line_history_diff_receiver else {
+            return;
+        };
+        let (change, result) = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.line_history_diff_receiver = None;
+                return;
+            }
+        };
+        self.line_history_diff_receiver = None;
+        // 読み込み中にポップアップを閉じた場合は表示しない
+        let Some(ref mut state) = self.line_history else {
+            return;
+        };
+        if state.loading_sha.take().as_deref() != Some(change.commit.sha.as_str()) {
+            return;
+        }
+        match result {
+            Ok(files) => self.show_line_change(change, files),
+            Err(e) => self.set_line_history_message(false, format!("Failed to load commit: {}", e)),
+        }
+    }
+
+    /// 選択したコミットの diff を読み込む
+    fn open_line_change(&mut self) {
+        let Some(ref mut state) = self.line_history else {
+            return;
+        };
+        if state.loading_sha.is_some() {
+            return;
+        }
+        let Some(change) = state
+            .history
+            .as_ref()
+            .and_then(|h| h.changes.get(state.selected))
+            .cloned()
+        else {
+            return;
+        };
+        state.loading_sha = Some(change.commit.sha.clone());
+
+        let (tx, rx) = mpsc::channel(1);
+        self.line_history_diff_receiver = Some(rx);
+        let repo = self.repo.clone();
+        let working_dir = self.working_dir.clone();
+        let local_mode = self.local_mode;
+        tokio::spawn(async move {
+            let result = if local_mode {
+                crate::loader::fetch_local_commit_diff(working_dir.as_deref(), &change.commit.sha)
+                    .await
+            } else {
+                crate::loader::fetch_commit_diff(&repo, &change.commit, working_dir.as_deref())
+                    .await
+            };
+            let _ = tx
+                .send((change, result.map_err(|e| format!("{:#}", e))))
+                .await;
+        });
+    }
+
+    /// コミットの diff に切り替えて、行を変更した位置を表示する。
+    /// 切り替える前の位置をジャンプスタックに積むので、jump_back で元の diff に戻れる
+    pub(crate) fn show_line_change(&mut self, change: LineChange, files: Vec<ChangedFile>) {
+        self.close_popup(PopupId::LineHistory);
+        self.push_jump_location();
+        let short_sha = change.commit.short_sha().to_string();
+        self.enter_commit_view(change.commit, files);
+
+        let Some(file_index) = self.files().iter().position(|f| f.filename == change.path) else {
+            self.set_line_history_message(
+                false,
+                format!("{} is not in the diff of {}", change.path, short_sha),
+            );
+            return;
+        };
+        self.selected_file = file_index;
+        self.diff_view_return_state = AppState::FileList;
+        self.state = AppState::DiffView;
+        self.update_diff_line_count();
+        self.update_file_comment_positions();
+        self.ensure_diff_cache();
+
+        let line_index = change.line.and_then(|line| {
+            let patch = self.files().get(file_index)?.patch.as_deref()?;
+            Self::find_diff_line_index(patch, line)
+        });
+        if let Some(index) = line_index {
+            self.selected_line = index;
+            self.scroll_offset = index;
+        }
+    }
+
+    pub(crate) fn handle_line_history_input(&mut self, key: &KeyEvent) {
+        let action = PopupKey::from_key(key, &self.config.keybindings);
+        match action {
+            PopupKey::Close => {
+                self.close_popup(PopupId::LineHistory);
+                self.line_history_receiver = None;
+                self.line_history_diff_receiver = None;
+            }
+            PopupKey::Confirm => self.open_line_change(),
+            _ => {
+                let Some(ref mut state) = self.line_history else {
+                    return;
+                };
+                let len = state.history.as_ref().map_or(0, |h| h.changes.len());
+                if let Some(next) = action.navigate(state.selected, len) {
+                    state.selected = next;
+                }
+            }
+        }
+    }
+}
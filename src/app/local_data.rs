@@ -0,0 +1,206 @@
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::local_store::{self, Artifact};
+
+use super::{App, AppState};
+
+/// ローカル保存データの管理画面の状態
+#[derive(Debug)]
+pub struct LocalDataState {
+    /// キャッシュディレクトリ
+    pub root: PathBuf,
+    pub artifacts: Vec<Artifact>,
+    pub selected: usize,
+    /// 一括操作の対象（artifacts のインデックス）
+    pub marked: HashSet<usize>,
+    /// PR がクローズ済みか（取得できたものだけ）
+    pub closed: HashMap<(String, u32), bool>,
+    pub states_loading: bool,
+    /// 管理画面を閉じたときに戻る画面
+    return_state: AppState,
+}
+
+impl LocalDataState {
+    /// 操作対象：マークがあればマークしたもの、なければ選択中の 1 件
+    fn targets(&self) -> Vec<usize> {
+        if self.marked.is_empty() {
+            (self.selected < self.artifacts.len())
+                .then_some(self.selected)
+                .into_iter()
+                .collect()
+        } else {
+            let mut targets: Vec<usize> = self.marked.iter().copied().collect();
+            targets.sort();
+            targets
+        }
+    }
+
+    pub fn is_closed(&self, artifact: &Artifact) -> Option<bool> {
+        artifact
+            .pr
+            .as_ref()
+            .and_then(|pr| self.closed.get(pr).copied())
+    }
+}
+
+impl App {
+    pub(crate) fn open_local_data(&mut self) {
+        self.open_local_data_at(crate::cache::cache_dir());
+    }
+
+    pub(crate) fn open_local_data_at(&mut self, root: PathBuf) {
+        let artifacts = local_store::scan(&root);
+        let prs = local_store::active_prs(&artifacts);
+        self.local_data = Some(LocalDataState {
+            root,
+            artifacts,
+            selected: 0,
+            marked: HashSet::new(),
+            closed: HashMap::new(),
+            states_loading: !prs.is_empty(),
+            return_state: self.state,
+        });
+        self.state = AppState::LocalData;
+
+        // PR の状態（クローズ済みか）はバックグラウンドで取得する
+        self.local_data_receiver = None;
+        if !prs.is_empty() {
+            let (tx, rx) = mpsc::channel(1);
+            self.local_data_receiver = Some(rx);
+            tokio::spawn(async move {
+                let states = local_store::fetch_closed_states(&prs).await;
+                let _ = tx.send(states).await;
+            });
+        }
+    }
+
+    pub(crate) fn poll_local_data_updates(&mut self) {
+        let Some(ref mut rx) = self.local_data_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(states) => {
+                self.local_data_receiver = None;
+                if let Some(ref mut view) = self.local_data {
+                    view.closed = states;
+                    view.states_loading = false;
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.local_data_receiver = None;
+                if let Some(ref mut view) = self.local_data {
+                    view.states_loading = false;
+                }
+            }
+        }
+    }
+
+    fn close_local_data(&mut self) {
+        if let Some(view) = self.local_data.take() {
+            self.state = view.return_state;
+        }
+        self.local_data_receiver = None;
+    }
+
+    pub(crate) fn handle_local_data_input(&mut self, key: KeyEvent) {
+        let kb = &self.config.keybindings;
+        if key.code == KeyCode::Esc || self.matches_single_key(&key, &kb.quit) {
+            self.close_local_data();
+            return;
+        }
+        let down = self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down;
+        let up = self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up;
+
+        let Some(ref mut view) = self.local_data else {
+            return;
+        };
+        let count = view.artifacts.len();
+        if down {
+            view.selected = (view.selected + 1).min(count.saturating_sub(1));
+            return;
+        }
+        if up {
+            view.selected = view.selected.saturating_sub(1);
+            return;
+        }
+
+        let message = match key.code {
+            // Space: 一括操作の対象に追加/解除
+            KeyCode::Char(' ') => {
+                if view.selected < count && !view.marked.remove(&view.selected) {
+                    view.marked.insert(view.selected);
+                }
+                view.selected = (view.selected + 1).min(count.saturating_sub(1));
+                None
+            }
+            KeyCode::Char('d') => Some(apply(view, "Archived", |root, a| {
+                local_store::archive(root, a).map(|_| ())
+            })),
+            KeyCode::Char('u') => Some(apply(view, "Restored", |root, a| {
+                local_store::restore(root, a).map(|_| ())
+            })),
+            KeyCode::Char('x') => Some(apply(view, "Deleted", |_, a| local_store::purge(a))),
+            // p: クローズ済み PR のデータをまとめてアーカイブ
+            KeyCode::Char('p') => {
+                if view.states_loading {
+                    Some((false, "Still checking PR states...".to_string()))
+                } else {
+                    view.marked = local_store::prunable(&view.artifacts, &view.closed)
+                        .into_iter()
+                        .collect();
+                    if view.marked.is_empty() {
+                        Some((true, "Nothing to prune".to_string()))
+                    } else {
+                        Some(apply(view, "Archived", |root, a| {
+                            local_store::archive(root, a).map(|_| ())
+                        }))
+                    }
+                }
+            }
+            _ => None,
+        };
+        if let Some(result) = message {
+            self.submission_result = Some(result);
+            self.submission_result_time = Some(Instant::now());
+        }
+    }
+}
+
+/// 対象に操作を適用して一覧を読み直し、結果メッセージを返す
+fn apply(
+    view: &mut LocalDataState,
+    verb: &str,
+    op: impl Fn(&std::path::Path, &Artifact) -> anyhow::Result<()>,
+) -> (bool, String) {
+    let mut done = 0;
+    let mut errors = Vec::new();
+    for i in view.targets() {
+        match op(&view.root, &view.artifacts[i]) {
+            Ok(()) => done += 1,
+            Err(e) => errors.push(format!("{:#}", e)),
+        }
+    }
+    view.artifacts = local_store::scan(&view.root);
+    view.marked.clear();
+    view.selected = view.selected.min(view.artifacts.len().saturating_sub(1));
+
+    match errors.first() {
+        None => (true, format!("{} {} item(s)", verb, done)),
+        Some(first) => (
+            false,
+            format!(
+                "{} {} item(s), {} failed: {}",
+                verb,
+                done,
+                errors.len(),
+                first
+            ),
+        ),
+    }
+}
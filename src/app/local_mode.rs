@@ -1,3 +1,4 @@
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
@@ -55,6 +56,10 @@ impl App {
             return;
         }
 
+        // ファイル一覧が入れ替わるため、記録した表示位置も破棄
+        self.file_view_states.clear();
+        self.base_file_patches.clear();
+        self.vendored_expanded.clear();
         // PR モードの in-flight viewed mutation を破棄
         self.mark_viewed_receiver = None;
         // Local モードの in-flight バッチ/lazy diff を破棄（クロスPRキャッシュ汚染防止）
@@ -179,6 +184,7 @@ impl App {
             repo: self.repo.clone(),
             pr_number,
         };
+        self.commit_view = None;
         if let Some(cached) = self.session_cache.get_pr_data(&cache_key) {
             self.data_state = DataState::Loaded {
                 pr: cached.pr.clone(),
@@ -301,6 +307,7 @@ impl App {
         let active = Arc::new(AtomicBool::new(true));
         let active_clone = active.clone();
         let refresh_tx = retry_sender.clone();
+        let refresh_dir = watch_dir.clone();
 
         let thread = std::thread::spawn(move || {
             let callback = move |result: notify::Result<notify::Event>| {
@@ -312,15 +319,12 @@ impl App {
                     return;
                 };
 
-                let dominated_by_git = event
-                    .paths
-                    .iter()
-                    .all(|p| p.components().any(|c| c.as_os_str() == ".git"));
-                let is_access = matches!(event.kind, notify::EventKind::Access(_));
-
-                if !is_access && !dominated_by_git && !refresh_pending.swap(true, Ordering::AcqRel)
+                if should_refresh_local_change(&event.paths, &event.kind)
+                    && !refresh_pending.swap(true, Ordering::AcqRel)
                 {
-                    let _ = refresh_tx.try_send(RefreshRequest::LocalRefresh);
+                    let _ = refresh_tx.try_send(RefreshRequest::LocalRefresh {
+                        working_dir: Some(refresh_dir.clone()),
+                    });
                 }
             };
 
@@ -364,3 +368,22 @@ impl App {
         self.submission_result_time = Some(Instant::now());
     }
 }
+
+/// ファイルの変更でローカルの diff を読み直すか。
+/// アクセスだけのイベントと、`.git/`・`.octorus/` の中だけの変更は無視する
+pub(crate) fn should_refresh_local_change(paths: &[PathBuf], kind: &notify::EventKind) -> bool {
+    !matches!(kind, notify::EventKind::Access(_))
+        && paths
+            .iter()
+            .any(|path| !is_git_file(path) && !is_octorus_config_file(path))
+}
+
+pub(crate) fn is_git_file(path: &Path) -> bool {
+    path.components()
+        .any(|component| component.as_os_str() == ".git")
+}
+
+pub(crate) fn is_octorus_config_file(path: &Path) -> bool {
+    path.components()
+        .any(|component| component.as_os_str() == ".octorus")
+}
@@ -0,0 +1,135 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::advisory::{Advisory, AdvisoryDb};
+use crate::manifest_diff::{self, DependencyChange, ManifestSummary};
+
+use super::App;
+
+/// 選択中ファイルの依存マニフェスト要約（patch が変わるまで使い回す）
+pub(crate) struct ManifestSummaryCache {
+    filename: String,
+    patch_len: usize,
+    summary: Option<ManifestSummary>,
+}
+
+impl App {
+    /// 選択中のファイルが依存マニフェストなら要約を作り直す。
+    /// 脆弱性データベースが設定されていれば、初めて必要になった時点で読み込む
+    pub(crate) fn refresh_manifest_summary(&mut self) {
+        let Some(file) = self.files().get(self.selected_file) else {
+            self.manifest_summary = None;
+            return;
+        };
+        if !manifest_diff::is_manifest(&file.filename) || self.is_file_preview(self.selected_file) {
+            self.manifest_summary = None;
+            return;
+        }
+        let patch = file.patch.as_deref().unwrap_or_default();
+        if self
+            .manifest_summary
+            .as_ref()
+            .is_some_and(|c| c.filename == file.filename && c.patch_len == patch.len())
+        {
+            return;
+        }
+        let summary = manifest_diff::summarize(&file.filename, patch);
+        let has_changes = summary.is_some();
+        self.manifest_summary = Some(ManifestSummaryCache {
+            filename: file.filename.clone(),
+            patch_len: patch.len(),
+            summary,
+        });
+        if has_changes {
+            self.start_advisory_db_load();
+        }
+    }
+
+    /// 選択中ファイルの依存の変更
+    pub fn manifest_summary(&self) -> Option<&ManifestSummary> {
+        let cache = self.manifest_summary.as_ref()?;
+        let file = self.files().get(self.selected_file)?;
+        if cache.filename != file.filename {
+            return None;
+        }
+        cache.summary.as_ref()
+    }
+
+    /// diff の上に表示する要約の高さ（枠を含む）。diff 領域の 1/3 までに抑える
+    pub(crate) fn manifest_summary_rows(&self, area_height: u16) -> u16 {
+        let Some(summary) = self.manifest_summary() else {
+            return 0;
+        };
+        // ヘッダー 1 行 + 変更ごとに 1 行（アドバイザリは同じ行に続ける）+ 枠 2 行
+        let wanted = summary.changes.len().saturating_add(3);
+        (wanted.min(u16::MAX as usize) as u16).min(area_height / 3)
+    }
+
+    /// 依存の変更で入るバージョンの既知の脆弱性。削除された依存は照会しない
+    pub fn dependency_advisories(&self, change: &DependencyChange) -> Vec<&Advisory> {
+        let (Some(db), Some(summary), Some(version)) = (
+            self.advisory_db.as_ref(),
+            self.manifest_summary(),
+            change.new_version.as_deref(),
+        ) else {
+            return Vec::new();
+        };
+        db.lookup(summary.ecosystem, &change.name, version)
+    }
+
+    /// `[dependencies] advisory_db` の解決済みパス。相対パスはプロジェクトルートから
+    fn advisory_db_path(&self) -> Option<PathBuf> {
+        let configured = self.config.dependencies.advisory_db.as_deref()?;
+        let path = match configured.strip_prefix("~/") {
+            Some(rest) => PathBuf::from(std::env::var_os("HOME")?).join(rest),
+            None => PathBuf::from(configured),
+        };
+        if path.is_absolute() {
+            Some(path)
+        } else {
+            Some(self.config.project_root.join(path))
+        }
+    }
+
+    fn start_advisory_db_load(&mut self) {
+        if self.advisory_db.is_some() || self.advisory_db_receiver.is_some() {
+            return;
+        }
+        let Some(path) = self.advisory_db_path() else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel(1);
+        self.advisory_db_receiver = Some(rx);
+        tokio::task::spawn_blocking(move || {
+            let result = AdvisoryDb::load(&path).map_err(|e| format!("{:#}", e));
+            let _ = tx.blocking_send(result);
+        });
+    }
+
+    pub(crate) fn poll_advisory_db_updates(&mut self) {
+        let Some(ref mut rx) = self.advisory_db_receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok(result) => {
+                self.advisory_db_receiver = None;
+                match result {
+                    Ok(db) => self.advisory_db = Some(db),
+                    Err(e) => {
+                        // 読み込みに失敗しても繰り返し試さないよう、空のデータベースにしておく
+                        self.advisory_db = Some(AdvisoryDb::default());
+                        self.submission_result = Some((false, e));
+                        self.submission_result_time = Some(Instant::now());
+                    }
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.advisory_db_receiver = None;
+            }
+        }
+    }
+}
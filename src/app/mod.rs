@@ -7,27 +7,57 @@ use std::sync::Arc;
 use tokio::sync::mpsc;
 use tokio::task::AbortHandle;
 
+use crate::advisory::AdvisoryDb;
 use crate::ai::orchestrator::{OrchestratorCommand, RallyEvent};
-use crate::cache::SessionCache;
+use crate::anonymize::Anonymizer;
+use crate::cache::{DiskCache, SessionCache};
 use crate::config::Config;
 use crate::filter::ListFilter;
 use crate::github::comment::{DiscussionComment, ReviewComment};
-use crate::github::{self, PrStateFilter, PullRequestSummary};
-use crate::keybinding::KeyBinding;
-use crate::loader::{CommentSubmitResult, DataLoadResult, SingleFileDiffResult};
+use crate::github::{self, PrStateFilter, PullRequestSummary, ReviewThreadState};
+use crate::keybinding::{KeyBinding, KeySequence};
+use crate::loader::{CommentSubmitResult, DataLoadResult, PatchPageResult, SingleFileDiffResult};
+use crate::poll_rate::{ActivityTracker, PollTier};
+use crate::progress::Progress;
+use crate::scope::PathScope;
+use crate::time_format::TimeFormatter;
 use crate::ui;
 use crate::ui::text_area::TextArea;
 use std::time::Instant;
 
 mod types;
 pub use types::{
-    AiRallyState, AppState, CachedDiffLine, CommentPosition, CommentTab, DataState, DiffCache,
-    HelpTab, InternedSpan, InputMode, JumpLocation, LineInputContext, LogEntry, LogEventType,
-    MultilineSelection, PermissionInfo, RefreshRequest, ReviewAction, SymbolPopupState,
-    ViewSnapshot, WatcherHandle, hash_string,
+    AiRallyState, AppState, BatchReplyOutcome, BatchReplyTarget, CachedDiffLine, CommentPosition,
+    CommentTab, CommentTranslation, DataState, DiffCache, FileFinderState, FileViewState,
+    FinderItem, FixupPopupState, HelpTab, InternedSpan, InputMode, JumpLocation,
+    LineInputContext, LogEntry, LogEventType, MultilineSelection, PermissionInfo, RallyUsage,
+    RefreshRequest, ReviewAction, SessionActivityState, SymbolPopupState, ViewSnapshot,
+    WatcherHandle, hash_string,
 };
 // Internal-only types (not re-exported from crate::app)
-use types::MarkViewedResult;
+use types::{LazyPatchPages, MarkViewedResult, TaskToggleResult};
+pub use agenda::AgendaPanel;
+pub use checks::{CheckLogState, ChecksState};
+pub use pr_overview::PrOverviewState;
+pub use pr_summary::PrSummaryState;
+pub use inbox::InboxState;
+pub use pr_tabs::PrTabs;
+pub use commits::{CommitListState, CommitView};
+pub use compare::LocalCompareState;
+pub use conflict::ConflictResolveState;
+pub use labels::LabelPickerState;
+pub use local_data::LocalDataState;
+pub use popup::PopupId;
+pub use reviewers::{PeoplePickerState, PeopleTarget};
+pub use review_draft::PendingReview;
+pub use grep::GrepState;
+pub use line_history::LineHistoryState;
+pub use user_info::UserInfoState;
+pub use explain::FileExplanation;
+pub use checkout::{CheckoutPhase, CheckoutState};
+pub use pr_create::PrCreateState;
+pub use search::DiffSearchState;
+pub use wrap_up::ReviewSummary;
 
 mod polling;
 mod input;
@@ -41,10 +71,64 @@ mod filter;
 mod pr_list;
 mod local_mode;
 mod symbol;
+mod task_list;
+mod batch_reply;
+mod bot_comments;
+mod unresolved;
+mod vendored;
+mod manifest;
+mod review_draft;
+mod activity;
+mod agenda;
+mod reviewers;
+mod labels;
+mod popup;
+mod translate;
+mod finder;
+mod lfs;
+mod file_preview;
+mod blame;
+mod line_history;
+mod learning;
+mod compare;
+mod commits;
+mod checks;
+mod pr_overview;
+mod pr_summary;
+mod inbox;
+mod conflict;
+mod local_data;
+mod nudge;
+mod rally_replay;
+mod rally_scope;
+mod pr_watch;
+mod view_state;
+mod fixup;
+mod follow;
+mod ipc;
+mod workspace;
+mod search;
+mod grep;
+mod discussion_edit;
+mod file_sort;
+mod quit;
+mod user_info;
+mod suggestion_apply;
+mod explain;
+mod comment_draft;
+mod checkout;
+mod pr_create;
+mod pr_draft;
+mod pr_tabs;
+mod review_progress;
+mod review_queue;
+mod wrap_up;
+mod outbox;
+#[cfg(test)]
+mod harness;
 #[cfg(test)]
 mod tests;
 
-const SPINNER_FRAMES: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
 /// ハイライトキャッシュストアの最大エントリ数（メモリ上限）
 ///
@@ -56,9 +140,23 @@ const MAX_HIGHLIGHTED_CACHE_ENTRIES: usize = 50;
 /// 大規模PRで全ファイルをクローンしないよう制限。
 const MAX_PREFETCH_FILES: usize = 50;
 
+/// スピナーを進める間隔
+///
+/// ポーリングの速さでループの周期が変わっても、アニメーションの速さは一定にする。
+const SPINNER_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
 /// PR番号と紐づいたレシーバー（発信元PRを追跡してクロスPRキャッシュ汚染を防止）
 type PrReceiver<T> = Option<(u32, mpsc::Receiver<T>)>;
 
+/// 変更履歴で選んだコミットの変更ファイルの取得結果
+type LineChangeFilesResult = (
+    crate::line_history::LineChange,
+    Result<Vec<github::ChangedFile>, String>,
+);
+
+/// リマインド対象の PR とレビュー依頼先の取得結果
+type NudgeReviewersResult = (crate::nudge::NudgeContext, Result<Vec<String>, String>);
+
 pub struct App {
     pub repo: String,
     /// 選択されたPR番号（PR一覧から選択した場合は後から設定）
@@ -121,6 +219,11 @@ pub struct App {
     pub file_comment_positions: Vec<CommentPosition>,
     // Set of diff line indices with comments (for fast lookup in render)
     pub file_comment_lines: HashSet<usize>,
+    /// レビューコメントの ID → スレッドの状態（未解決のスレッド数と `[diff] hide_*_comments` 用）
+    pub review_thread_states: HashMap<u64, ReviewThreadState>,
+    /// スレッドの状態を取得済み（取得中）の PR
+    thread_states_pr: Option<u32>,
+    thread_state_receiver: Option<mpsc::Receiver<HashMap<u64, ReviewThreadState>>>,
     /// インラインコメントパネルが開いているか（= フォーカス中）
     pub comment_panel_open: bool,
     /// インラインコメントパネルのスクロールオフセット（行単位）
@@ -129,6 +232,8 @@ pub struct App {
     pub diff_cache: Option<DiffCache>,
     // Store for highlighted diff caches (file_index -> DiffCache)
     highlighted_cache_store: HashMap<usize, DiffCache>,
+    /// 大きな diff の表示位置の周りをその場でハイライトするためのパーサー
+    viewport_parser_pool: crate::syntax::ParserPool,
     // Discussion comments (PR conversation)
     pub discussion_comments: Option<Vec<DiscussionComment>>,
     pub selected_discussion_comment: usize,
@@ -136,14 +241,22 @@ pub struct App {
     pub discussion_comments_loading: bool,
     pub discussion_comment_detail_mode: bool,
     pub discussion_comment_detail_scroll: usize,
+    /// 詳細表示で選択中のタスク項目（`- [ ]`）
+    pub discussion_task_cursor: usize,
     /// ヘルプ画面のスクロールオフセット（行単位）
     pub help_scroll_offset: usize,
     /// ヘルプ画面の現在のタブ
     pub help_tab: HelpTab,
     /// Config タブのスクロールオフセット（行単位）
     pub config_scroll_offset: usize,
+    /// キーバインドタブの検索（入力中または確定済みのクエリ）
+    pub help_filter: Option<ListFilter>,
     // Comment tab state
     pub comment_tab: CommentTab,
+    /// コメント一覧で bot のコメントを展開しているか
+    pub comment_list_bots_expanded: bool,
+    /// コメント一覧の Review タブを未解決のスレッドだけに絞り込んでいるか
+    pub comment_list_unresolved_only: bool,
     // AI Rally state
     pub ai_rally_state: Option<AiRallyState>,
     pub working_dir: Option<String>,
@@ -161,8 +274,14 @@ pub struct App {
     rally_abort_handle: Option<AbortHandle>,
     // Command sender to communicate with the orchestrator
     rally_command_sender: Option<mpsc::Sender<OrchestratorCommand>>,
+    // Files the current rally is limited to (empty: the whole PR)
+    pub(crate) rally_scope: Vec<String>,
+    /// ファイル一覧で AI Rally の対象にマークしたファイル
+    pub(crate) rally_scope_marks: HashSet<String>,
     // Flag to start AI Rally when data is loaded (set by --ai-rally CLI flag)
     start_ai_rally_on_load: bool,
+    // Resume the saved rally instead of starting a new one (set by --resume-rally)
+    resume_rally_on_load: bool,
     // Pending AI Rally flag (set when --ai-rally is passed with PR list mode)
     pending_ai_rally: bool,
     // Comment submission state
@@ -178,6 +297,12 @@ pub struct App {
     pending_approve_body: Option<String>,
     /// Spinner animation frame counter (incremented each tick)
     pub spinner_frame: usize,
+    /// スピナーを最後に進めた時刻（ループの速さに関係なく 100ms ごとに進める）
+    spinner_tick: Instant,
+    /// 最後の入力からの経過時間（ポーリング間隔の調整用）
+    activity: ActivityTracker,
+    /// 現在のポーリングの速さ（PR 監視タスクへ伝える）
+    poll_tier: tokio::sync::watch::Sender<PollTier>,
     /// インラインコメントパネル内の選択インデックス
     pub selected_inline_comment: usize,
     /// ジャンプ履歴スタック（Go to Definition / Jump Back 用）
@@ -186,6 +311,9 @@ pub struct App {
     pub pending_keys: SmallVec<[KeyBinding; 4]>,
     /// Timestamp when pending keys started (for timeout)
     pub pending_since: Option<Instant>,
+    /// Sequence completed by the pending keys whose action is handled on a second pass
+    /// of the same key (see [`App::resolve_pending_keys`])
+    pub completed_sequence: Option<KeySequence>,
     /// シンボル選択ポップアップの状態
     pub symbol_popup: Option<SymbolPopupState>,
     /// インメモリセッションキャッシュ
@@ -202,6 +330,223 @@ pub struct App {
     lazy_diff_receiver: Option<mpsc::Receiver<SingleFileDiffResult>>,
     /// 現在オンデマンドロード要求中のファイル名（重複リクエスト防止）
     lazy_diff_pending_file: Option<String>,
+    /// patch を後から取得する PR（ファイルの多い PR）ごとの取得状況
+    lazy_patch_pages: HashMap<u32, LazyPatchPages>,
+    /// files API のページごとの patch の受信チャネル
+    lazy_patch_receivers: Vec<mpsc::Receiver<PatchPageResult>>,
+    /// fixup コミット支援ポップアップ（ローカルモード）
+    pub fixup_popup: Option<FixupPopupState>,
+    /// fixup コミット作成結果の受信チャネル（作成数 or エラー）
+    fixup_receiver: Option<mpsc::Receiver<Result<usize, String>>>,
+    /// 外部ツールからの IPC コマンド受信
+    ipc_receiver: Option<mpsc::Receiver<crate::ipc::IpcCommand>>,
+    /// エディタ追従用ジャンプイベントの送信元（`[follow] path` 設定時のみ）
+    follow_emitter: Option<crate::follow::FollowEmitter>,
+    follow_cursor: Option<(AppState, usize, usize)>,
+    /// セッション操作履歴パネル
+    pub session_activity: Option<SessionActivityState>,
+    /// コメント ID → 翻訳結果（コメントパネルで原文の下に表示）
+    pub comment_translations: HashMap<u64, CommentTranslation>,
+    translate_receiver: Option<mpsc::Receiver<(u64, Result<String, String>)>>,
+    /// ファジーファインダー
+    pub file_finder: Option<FileFinderState>,
+    /// 最近開いたファイル（古い順、ファインダーの並び替え用）
+    pub recent_files: Vec<String>,
+    /// 日時表示（`t` で相対/絶対を切り替え）
+    pub time_format: TimeFormatter,
+    /// `--anonymize` 指定時の描画用匿名化
+    pub anonymizer: Anonymizer,
+    /// LFS 実体プレビュー中のファイル → 元のポインタ patch
+    pub lfs_pointer_patches: HashMap<String, String>,
+    lfs_receiver: Option<mpsc::Receiver<(String, Result<String, String>)>>,
+    /// `--paths` / `[scope] paths` で絞り込んだパス（空なら全ファイル）
+    pub path_scope: PathScope,
+    /// Rally トランスクリプトの再生状態
+    pub rally_replay: Option<rally_replay::RallyReplay>,
+    /// `[watch]` による PR 監視タスク（監視中の PR 番号）
+    pr_watch: Option<(u32, AbortHandle)>,
+    pr_watch_receiver: Option<mpsc::Receiver<(u32, Vec<crate::pr_watch::PrChange>)>>,
+    /// ファイル名 → 最後に見ていた diff の位置
+    pub file_view_states: HashMap<String, FileViewState>,
+    /// base 版の全体表示中のファイル → 元の patch
+    pub base_file_patches: HashMap<String, Option<String>>,
+    base_file_receiver: Option<mpsc::Receiver<(String, Result<String, String>)>>,
+    /// diff のガターに blame を表示する
+    pub blame_enabled: bool,
+    /// 取得済みの blame。取得できなかったファイルは None
+    file_blames: HashMap<blame::BlameKey, Option<crate::blame::FileBlame>>,
+    blame_receiver:
+        Option<mpsc::Receiver<(blame::BlameKey, Result<crate::blame::FileBlame, String>)>>,
+    /// 選択した行の変更履歴のポップアップ
+    pub line_history: Option<LineHistoryState>,
+    line_history_receiver: Option<mpsc::Receiver<Result<crate::line_history::LineHistory, String>>>,
+    line_history_diff_receiver: Option<mpsc::Receiver<LineChangeFilesResult>>,
+    /// 学習モードで集計したキーの使用回数
+    pub key_usage: learning::KeyUsageStats,
+    /// 学習モードのキーバインド表示（表示開始時刻付き）
+    pub key_hint: Option<(String, Instant)>,
+    /// キー使用統計パネルを表示中か
+    pub key_stats_open: bool,
+    /// PR と作業ツリーの比較画面
+    pub local_compare: Option<compare::LocalCompareState>,
+    local_compare_receiver: Option<mpsc::Receiver<Result<crate::compare::Comparison, String>>>,
+    /// 作業ツリーのコンフリクト解消
+    pub conflict_resolve: Option<ConflictResolveState>,
+    conflict_receiver: Option<mpsc::Receiver<Result<crate::conflict::ConflictFile, String>>>,
+    conflict_write_receiver: Option<mpsc::Receiver<Result<String, String>>>,
+    /// PR 一覧の各 PR のレビュー済み割合（PR 番号 → viewed 済みファイル数）
+    pub pr_review_coverage: HashMap<u32, github::ReviewCoverage>,
+    /// 取得済みまたは取得中の PR 番号（同じ PR を繰り返し問い合わせない）
+    pr_coverage_requested: HashSet<u32>,
+    /// レビューを送信したら終了する（`--wait-for-review`）
+    wait_for_review: bool,
+    /// このセッションで最後に送信したレビュー
+    review_outcome: Option<ReviewAction>,
+    pub local_data: Option<local_data::LocalDataState>,
+    local_data_receiver: Option<mpsc::Receiver<HashMap<(String, u32), bool>>>,
+    /// リマインド対象の PR のレビュー依頼先の取得
+    nudge_receiver: Option<mpsc::Receiver<NudgeReviewersResult>>,
+    nudge_submit_receiver: Option<mpsc::Receiver<(u32, Result<(), String>)>>,
+    /// PR のコミット一覧
+    pub commit_list: Option<CommitListState>,
+    commit_list_receiver: PrReceiver<Result<Vec<github::PrCommit>, String>>,
+    /// 1 コミットの diff を表示中（ファイル一覧をコミットの変更に差し替えている）
+    pub commit_view: Option<CommitView>,
+    commit_diff_receiver: PrReceiver<(github::PrCommit, Result<Vec<github::ChangedFile>, String>)>,
+    /// head コミットの CI チェック一覧
+    pub checks: Option<ChecksState>,
+    checks_receiver: PrReceiver<Result<Vec<github::CheckRun>, String>>,
+    /// PR の概要画面（開いていなければ None）
+    pub pr_overview: Option<PrOverviewState>,
+    pr_overview_receiver: PrReceiver<Result<github::PrOverview, String>>,
+    /// AI による PR の要約の画面（gS）
+    pub pr_summary: Option<PrSummaryState>,
+    /// 要約した head コミットと結果
+    pr_summary_receiver: PrReceiver<(String, Result<crate::pr_summary::PrSummary, String>)>,
+    /// PR 番号と head コミットごとの要約
+    pr_summaries: HashMap<(u32, String), crate::pr_summary::PrSummary>,
+    /// 自分宛ての PR の通知の受信箱（開いていなければ None）
+    pub inbox: Option<InboxState>,
+    inbox_receiver: Option<mpsc::Receiver<Result<Vec<github::Notification>, String>>>,
+    check_log_receiver: PrReceiver<(u64, Result<crate::ci_log::CiLog, String>)>,
+    pr_coverage_receiver:
+        Option<mpsc::Receiver<Result<HashMap<u32, github::ReviewCoverage>, String>>>,
+    /// 認証中のユーザー（自分のコメントかの判定用。初回の編集時に取得）
+    viewer_login: Option<String>,
+    task_toggle_receiver: Option<mpsc::Receiver<TaskToggleResult>>,
+    /// 削除を確認中の Discussion コメントの ID
+    pending_discussion_delete: Option<u64>,
+    /// 確認中の Draft の切り替え（true なら下書きに戻す、false ならレビュー可能にする）
+    pending_draft_toggle: Option<bool>,
+    /// コメント一覧で一括返信の対象にしたコメント ID
+    pub marked_review_comments: HashSet<u64>,
+    /// 直前の一括返信で失敗したスレッド（コメント ID → エラー）
+    pub batch_reply_errors: HashMap<u64, String>,
+    batch_reply_receiver: PrReceiver<Vec<BatchReplyOutcome>>,
+    batch_diff_progress: Option<Progress>,
+    /// ファイルの多い PR の変更ファイルの続きの読み込み（PR 番号と進捗）
+    file_page_progress: Option<(u32, Progress)>,
+    prefetch_progress: Option<Progress>,
+    mark_viewed_progress: Option<Progress>,
+    /// 折りたたみを解除した vendored 依存のファイル
+    pub vendored_expanded: HashSet<String>,
+    /// vendored 依存とライセンス変更の要約パネルを表示中か
+    pub vendored_summary_open: bool,
+    /// 選択中ファイルの依存マニフェスト要約
+    manifest_summary: Option<manifest::ManifestSummaryCache>,
+    /// `[dependencies] advisory_db` から読み込んだ脆弱性データベース
+    advisory_db: Option<AdvisoryDb>,
+    advisory_db_receiver: Option<mpsc::Receiver<Result<AdvisoryDb, String>>>,
+    /// 送信前のレビュー下書き（PR ごと）
+    pending_review: PendingReview,
+    /// 有効な間はインラインコメントを下書きに貯める
+    pub review_draft_mode: bool,
+    pub review_drafts_open: bool,
+    pub selected_review_draft: usize,
+    /// ペアレビュー用のレビューアジェンダ
+    pub review_agenda: Option<AgendaPanel>,
+    agenda_submit_receiver: Option<mpsc::Receiver<(u32, Result<(), String>)>>,
+    /// レビュー依頼・アサインのピッカー
+    pub people_picker: Option<PeoplePickerState>,
+    people_receiver: PrReceiver<reviewers::PeopleLoadResult>,
+    people_update_receiver: PrReceiver<reviewers::PeopleUpdateResult>,
+    /// ラベル編集のポップアップ
+    pub label_picker: Option<LabelPickerState>,
+    label_list_receiver: PrReceiver<Result<Vec<github::RepoLabel>, String>>,
+    label_update_receiver: PrReceiver<labels::LabelUpdateResult>,
+    /// 開いた順のポップアップ（最後が最前面）
+    popup_stack: Vec<PopupId>,
+    /// 切り替えて見るリポジトリ（1 つだけなら切り替えない）
+    pub workspace_repos: Vec<String>,
+    /// リポジトリ切り替えのポップアップ
+    pub repo_switcher: Option<crate::ui::picker::Picker>,
+    /// 表示していないリポジトリのレビュー下書き
+    parked_reviews: HashMap<String, PendingReview>,
+    /// PR 一覧から開いた PR のタブ
+    pub pr_tabs: PrTabs,
+    /// 表示していないリポジトリのタブ
+    parked_pr_tabs: HashMap<String, PrTabs>,
+    /// diff 内検索（`/`）
+    pub diff_search: Option<DiffSearchState>,
+    /// 全ファイルの grep（Space f）
+    pub grep: Option<GrepState>,
+    /// ファイル一覧の表示順（Space s で並び順を切り替え）
+    file_order: file_sort::FileOrder,
+    /// このセッションで diff を開いたファイル（PR 番号とパス）
+    opened_files: HashSet<(u32, String)>,
+    /// 終了前の確認（未送信の下書きがあるとき）
+    pub quit_summary: Option<crate::session_summary::SessionSummary>,
+    /// 承認・変更要求の前のまとめ（未解決のスレッド・下書き・未確認のファイル）
+    pub review_summary: Option<ReviewSummary>,
+    /// コメントの作成者の情報（u）
+    pub user_info: Option<UserInfoState>,
+    user_info_receiver: Option<mpsc::Receiver<user_info::UserInfoLoadResult>>,
+    /// 取得したユーザー情報（リポジトリ、小文字のログイン名）
+    user_info_cache: HashMap<(String, String), crate::github::UserInfo>,
+    /// リポジトリごとの CODEOWNERS（ないことが分かっていれば None）
+    codeowners_cache: HashMap<String, Option<crate::codeowners::CodeOwners>>,
+    /// 作業ツリーへの適用を確認中の suggestion
+    pending_suggestion_apply: Option<suggestion_apply::PendingSuggestionApply>,
+    /// diff の右にファイルの変更の説明を表示する（ge）
+    pub explain_panel_open: bool,
+    /// ファイル名と patch のハッシュごとの説明
+    file_explanations: HashMap<u64, FileExplanation>,
+    explain_receiver: Option<mpsc::Receiver<(u64, Result<String, String>)>>,
+    /// エージェントに頼んだコメントの下書き（E）
+    comment_draft_receiver: Option<mpsc::Receiver<comment_draft::CommentDraftResult>>,
+    /// PR のブランチのチェックアウト（go）
+    pub checkout: Option<CheckoutState>,
+    checkout_receiver: Option<mpsc::Receiver<Result<crate::checkout::CheckoutResult, String>>>,
+    /// ローカルブランチからの PR 作成のベースブランチ選択（gu）
+    pub pr_create: Option<PrCreateState>,
+    pr_create_receiver: Option<mpsc::Receiver<Result<pr_create::CreatedPr, String>>>,
+    /// ローカルモードで切り替えるディレクトリ（W）
+    local_repos: Option<crate::local_repos::LocalRepoRegistry>,
+    /// 切り替えて離れたディレクトリごとの状態
+    parked_local_repos: HashMap<String, workspace::LocalRepoSession>,
+    /// ローカルに保存する見終えたファイルとメモ（PR モードのみ）
+    review_progress: Option<crate::review_progress::ReviewProgress>,
+    /// 読み込んだ進捗の (リポジトリ, PR 番号, head SHA)
+    review_progress_key: Option<(String, u32, String)>,
+    /// PR 一覧から開いたときの続けてレビューする PR（一覧の並び）
+    review_queue: Vec<u32>,
+    /// approve & next の承認の送信待ち
+    approve_then_next: bool,
+    /// レビュー進捗の同期先（`[sync]`）
+    progress_sync: Option<Arc<dyn crate::progress_sync::ProgressSyncBackend>>,
+    /// 同期の結果（進捗の名前と、同期先とまとめた進捗）
+    progress_sync_receiver:
+        Option<mpsc::Receiver<(String, Result<crate::review_progress::ReviewProgress, String>)>>,
+    /// 同期中に進捗が変わったら、終わった後にもう一度同期する
+    progress_sync_pending: bool,
+    /// 送信に失敗したコメント・レビューの送信待ち
+    pub outbox: crate::cache::Outbox,
+    /// 送信待ちの一覧（U）
+    pub outbox_open: bool,
+    pub selected_outbox_entry: usize,
+    outbox_receiver: Option<mpsc::Receiver<crate::loader::OutboxSendResult>>,
+    /// API のレート制限の残り（フッター表示用。まだ分からなければ None）
+    pub rate_limit: Option<crate::github::RateLimit>,
 }
 
 impl App {
@@ -246,6 +591,12 @@ impl App {
             multiline_selection: None,
             input_mode: None,
             input_text_area: TextArea::with_submit_key(config.keybindings.submit.clone()),
+            time_format: TimeFormatter::from_config(&config.time),
+            spinner_tick: Instant::now(),
+            activity: ActivityTracker::new(&config.polling, Instant::now()),
+            poll_tier: tokio::sync::watch::channel(PollTier::Active).0,
+            anonymizer: Anonymizer::default(),
+            path_scope: PathScope::new(&config.scope.paths),
             config,
             should_quit: false,
             review_comments: None,
@@ -254,10 +605,14 @@ impl App {
             comments_loading: false,
             file_comment_positions: vec![],
             file_comment_lines: HashSet::new(),
+            review_thread_states: HashMap::new(),
+            thread_states_pr: None,
+            thread_state_receiver: None,
             comment_panel_open: false,
             comment_panel_scroll: 0,
             diff_cache: None,
             highlighted_cache_store: HashMap::new(),
+            viewport_parser_pool: crate::syntax::ParserPool::new(),
             discussion_comments: None,
             selected_discussion_comment: 0,
             discussion_comment_list_scroll_offset: 0,
@@ -267,7 +622,10 @@ impl App {
             help_scroll_offset: 0,
             help_tab: HelpTab::default(),
             config_scroll_offset: 0,
+            help_filter: None,
             comment_tab: CommentTab::default(),
+            comment_list_bots_expanded: false,
+            comment_list_unresolved_only: false,
             ai_rally_state: None,
             working_dir: None,
             data_receiver: Some((pr_number, rx)),
@@ -279,7 +637,10 @@ impl App {
             rally_event_receiver: None,
             rally_abort_handle: None,
             rally_command_sender: None,
+            rally_scope: Vec::new(),
+            rally_scope_marks: HashSet::new(),
             start_ai_rally_on_load: false,
+            resume_rally_on_load: false,
             pending_ai_rally: false,
             comment_submit_receiver: None,
             mark_viewed_receiver: None,
@@ -292,6 +653,7 @@ impl App {
             jump_stack: Vec::new(),
             pending_keys: SmallVec::new(),
             pending_since: None,
+            completed_sequence: None,
             symbol_popup: None,
             session_cache: SessionCache::new(),
             markdown_rich: false,
@@ -300,6 +662,131 @@ impl App {
             batch_diff_receiver: None,
             lazy_diff_receiver: None,
             lazy_diff_pending_file: None,
+            lazy_patch_pages: HashMap::new(),
+            lazy_patch_receivers: Vec::new(),
+            fixup_popup: None,
+            fixup_receiver: None,
+            ipc_receiver: None,
+            follow_emitter: None,
+            follow_cursor: None,
+            session_activity: None,
+            comment_translations: HashMap::new(),
+            translate_receiver: None,
+            file_finder: None,
+            recent_files: Vec::new(),
+            lfs_pointer_patches: HashMap::new(),
+            lfs_receiver: None,
+            rally_replay: None,
+            pr_watch: None,
+            pr_watch_receiver: None,
+            file_view_states: HashMap::new(),
+            base_file_patches: HashMap::new(),
+            base_file_receiver: None,
+            blame_enabled: false,
+            file_blames: HashMap::new(),
+            blame_receiver: None,
+            line_history: None,
+            line_history_receiver: None,
+            line_history_diff_receiver: None,
+            key_usage: learning::KeyUsageStats::default(),
+            key_hint: None,
+            key_stats_open: false,
+            local_compare: None,
+            local_compare_receiver: None,
+            conflict_resolve: None,
+            conflict_receiver: None,
+            conflict_write_receiver: None,
+            pr_review_coverage: HashMap::new(),
+            pr_coverage_requested: HashSet::new(),
+            pr_coverage_receiver: None,
+            local_data: None,
+            local_data_receiver: None,
+            nudge_receiver: None,
+            nudge_submit_receiver: None,
+            commit_list: None,
+            commit_list_receiver: None,
+            checks: None,
+            checks_receiver: None,
+            pr_overview: None,
+            pr_overview_receiver: None,
+            pr_summary: None,
+            pr_summary_receiver: None,
+            pr_summaries: HashMap::new(),
+            inbox: None,
+            inbox_receiver: None,
+            check_log_receiver: None,
+            commit_view: None,
+            commit_diff_receiver: None,
+            wait_for_review: false,
+            review_outcome: None,
+            discussion_task_cursor: 0,
+            viewer_login: None,
+            task_toggle_receiver: None,
+            pending_discussion_delete: None,
+            pending_draft_toggle: None,
+            marked_review_comments: HashSet::new(),
+            batch_reply_errors: HashMap::new(),
+            batch_reply_receiver: None,
+            batch_diff_progress: None,
+            file_page_progress: None,
+            prefetch_progress: None,
+            mark_viewed_progress: None,
+            vendored_expanded: HashSet::new(),
+            vendored_summary_open: false,
+            manifest_summary: None,
+            advisory_db: None,
+            advisory_db_receiver: None,
+            pending_review: PendingReview::default(),
+            review_draft_mode: false,
+            review_drafts_open: false,
+            review_agenda: None,
+            agenda_submit_receiver: None,
+            people_picker: None,
+            people_receiver: None,
+            people_update_receiver: None,
+            label_picker: None,
+            label_list_receiver: None,
+            label_update_receiver: None,
+            popup_stack: Vec::new(),
+            workspace_repos: Vec::new(),
+            repo_switcher: None,
+            parked_reviews: HashMap::new(),
+            pr_tabs: PrTabs::default(),
+            parked_pr_tabs: HashMap::new(),
+            diff_search: None,
+            grep: None,
+            file_order: file_sort::FileOrder::default(),
+            opened_files: HashSet::new(),
+            quit_summary: None,
+            review_summary: None,
+            user_info: None,
+            user_info_receiver: None,
+            user_info_cache: HashMap::new(),
+            codeowners_cache: HashMap::new(),
+            pending_suggestion_apply: None,
+            explain_panel_open: false,
+            file_explanations: HashMap::new(),
+            explain_receiver: None,
+            comment_draft_receiver: None,
+            checkout: None,
+            checkout_receiver: None,
+            pr_create: None,
+            pr_create_receiver: None,
+            local_repos: None,
+            parked_local_repos: HashMap::new(),
+            review_progress: None,
+            review_progress_key: None,
+            review_queue: Vec::new(),
+            approve_then_next: false,
+            progress_sync: None,
+            progress_sync_receiver: None,
+            progress_sync_pending: false,
+            outbox: crate::cache::Outbox::default(),
+            outbox_open: false,
+            selected_outbox_entry: 0,
+            outbox_receiver: None,
+            rate_limit: None,
+            selected_review_draft: 0,
         };
 
         (app, tx)
@@ -331,6 +818,12 @@ impl App {
             multiline_selection: None,
             input_mode: None,
             input_text_area: TextArea::with_submit_key(config.keybindings.submit.clone()),
+            time_format: TimeFormatter::from_config(&config.time),
+            spinner_tick: Instant::now(),
+            activity: ActivityTracker::new(&config.polling, Instant::now()),
+            poll_tier: tokio::sync::watch::channel(PollTier::Active).0,
+            anonymizer: Anonymizer::default(),
+            path_scope: PathScope::new(&config.scope.paths),
             config,
             should_quit: false,
             review_comments: None,
@@ -339,10 +832,14 @@ impl App {
             comments_loading: false,
             file_comment_positions: vec![],
             file_comment_lines: HashSet::new(),
+            review_thread_states: HashMap::new(),
+            thread_states_pr: None,
+            thread_state_receiver: None,
             comment_panel_open: false,
             comment_panel_scroll: 0,
             diff_cache: None,
             highlighted_cache_store: HashMap::new(),
+            viewport_parser_pool: crate::syntax::ParserPool::new(),
             discussion_comments: None,
             selected_discussion_comment: 0,
             discussion_comment_list_scroll_offset: 0,
@@ -352,7 +849,10 @@ impl App {
             help_scroll_offset: 0,
             help_tab: HelpTab::default(),
             config_scroll_offset: 0,
+            help_filter: None,
             comment_tab: CommentTab::default(),
+            comment_list_bots_expanded: false,
+            comment_list_unresolved_only: false,
             ai_rally_state: None,
             working_dir: None,
             data_receiver: None,
@@ -364,7 +864,10 @@ impl App {
             rally_event_receiver: None,
             rally_abort_handle: None,
             rally_command_sender: None,
+            rally_scope: Vec::new(),
+            rally_scope_marks: HashSet::new(),
             start_ai_rally_on_load: false,
+            resume_rally_on_load: false,
             pending_ai_rally: false,
             comment_submit_receiver: None,
             mark_viewed_receiver: None,
@@ -377,6 +880,7 @@ impl App {
             jump_stack: Vec::new(),
             pending_keys: SmallVec::new(),
             pending_since: None,
+            completed_sequence: None,
             symbol_popup: None,
             local_mode: false,
             local_auto_focus: false,
@@ -394,6 +898,131 @@ impl App {
             batch_diff_receiver: None,
             lazy_diff_receiver: None,
             lazy_diff_pending_file: None,
+            lazy_patch_pages: HashMap::new(),
+            lazy_patch_receivers: Vec::new(),
+            fixup_popup: None,
+            fixup_receiver: None,
+            ipc_receiver: None,
+            follow_emitter: None,
+            follow_cursor: None,
+            session_activity: None,
+            comment_translations: HashMap::new(),
+            translate_receiver: None,
+            file_finder: None,
+            recent_files: Vec::new(),
+            lfs_pointer_patches: HashMap::new(),
+            lfs_receiver: None,
+            rally_replay: None,
+            pr_watch: None,
+            pr_watch_receiver: None,
+            file_view_states: HashMap::new(),
+            base_file_patches: HashMap::new(),
+            base_file_receiver: None,
+            blame_enabled: false,
+            file_blames: HashMap::new(),
+            blame_receiver: None,
+            line_history: None,
+            line_history_receiver: None,
+            line_history_diff_receiver: None,
+            key_usage: learning::KeyUsageStats::default(),
+            key_hint: None,
+            key_stats_open: false,
+            local_compare: None,
+            local_compare_receiver: None,
+            conflict_resolve: None,
+            conflict_receiver: None,
+            conflict_write_receiver: None,
+            pr_review_coverage: HashMap::new(),
+            pr_coverage_requested: HashSet::new(),
+            pr_coverage_receiver: None,
+            local_data: None,
+            local_data_receiver: None,
+            nudge_receiver: None,
+            nudge_submit_receiver: None,
+            commit_list: None,
+            commit_list_receiver: None,
+            checks: None,
+            checks_receiver: None,
+            pr_overview: None,
+            pr_overview_receiver: None,
+            pr_summary: None,
+            pr_summary_receiver: None,
+            pr_summaries: HashMap::new(),
+            inbox: None,
+            inbox_receiver: None,
+            check_log_receiver: None,
+            commit_view: None,
+            commit_diff_receiver: None,
+            wait_for_review: false,
+            review_outcome: None,
+            discussion_task_cursor: 0,
+            viewer_login: None,
+            task_toggle_receiver: None,
+            pending_discussion_delete: None,
+            pending_draft_toggle: None,
+            marked_review_comments: HashSet::new(),
+            batch_reply_errors: HashMap::new(),
+            batch_reply_receiver: None,
+            batch_diff_progress: None,
+            file_page_progress: None,
+            prefetch_progress: None,
+            mark_viewed_progress: None,
+            vendored_expanded: HashSet::new(),
+            vendored_summary_open: false,
+            manifest_summary: None,
+            advisory_db: None,
+            advisory_db_receiver: None,
+            pending_review: PendingReview::default(),
+            review_draft_mode: false,
+            review_drafts_open: false,
+            review_agenda: None,
+            agenda_submit_receiver: None,
+            people_picker: None,
+            people_receiver: None,
+            people_update_receiver: None,
+            label_picker: None,
+            label_list_receiver: None,
+            label_update_receiver: None,
+            popup_stack: Vec::new(),
+            workspace_repos: Vec::new(),
+            repo_switcher: None,
+            parked_reviews: HashMap::new(),
+            pr_tabs: PrTabs::default(),
+            parked_pr_tabs: HashMap::new(),
+            diff_search: None,
+            grep: None,
+            file_order: file_sort::FileOrder::default(),
+            opened_files: HashSet::new(),
+            quit_summary: None,
+            review_summary: None,
+            user_info: None,
+            user_info_receiver: None,
+            user_info_cache: HashMap::new(),
+            codeowners_cache: HashMap::new(),
+            pending_suggestion_apply: None,
+            explain_panel_open: false,
+            file_explanations: HashMap::new(),
+            explain_receiver: None,
+            comment_draft_receiver: None,
+            checkout: None,
+            checkout_receiver: None,
+            pr_create: None,
+            pr_create_receiver: None,
+            local_repos: None,
+            parked_local_repos: HashMap::new(),
+            review_progress: None,
+            review_progress_key: None,
+            review_queue: Vec::new(),
+            approve_then_next: false,
+            progress_sync: None,
+            progress_sync_receiver: None,
+            progress_sync_pending: false,
+            outbox: crate::cache::Outbox::default(),
+            outbox_open: false,
+            selected_outbox_entry: 0,
+            outbox_receiver: None,
+            rate_limit: None,
+            selected_review_draft: 0,
         }
     }
 
@@ -411,6 +1040,24 @@ impl App {
         self.retry_sender = Some(tx);
     }
 
+    /// `[cache]` が有効ならディスクキャッシュを使い、前回保存した PR を取得を待たずに表示する
+    pub fn enable_disk_cache(&mut self) {
+        let Some(disk) = DiskCache::from_config(&self.config.cache) else {
+            return;
+        };
+        self.session_cache = SessionCache::with_disk(disk);
+        if let Some(pr_number) = self.pr_number.filter(|&n| n != 0) {
+            if !self.local_mode && matches!(self.data_state, DataState::Loading) {
+                self.show_cached_pr(pr_number);
+            }
+        }
+    }
+
+    /// IPC コマンド受信チャンネルを設定
+    pub fn set_ipc_receiver(&mut self, rx: mpsc::Receiver<crate::ipc::IpcCommand>) {
+        self.ipc_receiver = Some(rx);
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         let mut terminal = ui::setup_terminal()?;
 
@@ -426,19 +1073,10 @@ impl App {
         }
 
         while !self.should_quit {
-            self.spinner_frame = self.spinner_frame.wrapping_add(1);
-            self.poll_pr_list_updates();
-            self.poll_data_updates();
-            self.poll_comment_updates();
-            self.poll_diff_cache_updates();
-            self.poll_prefetch_updates();
-            self.poll_batch_diff_updates();
-            self.poll_lazy_diff_updates();
-            self.poll_discussion_comment_updates();
-            self.poll_comment_submit_updates();
-            self.poll_mark_viewed_updates();
-            self.poll_rally_events();
+            self.poll_background_updates();
             terminal.draw(|frame| ui::render(frame, self))?;
+            self.remember_file_view_state();
+            self.request_visible_pr_coverage(terminal.size()?.height.saturating_sub(8) as usize);
             self.handle_input(&mut terminal).await?;
         }
 
@@ -446,14 +1084,157 @@ impl App {
         if let Some(handle) = self.rally_abort_handle.take() {
             handle.abort();
         }
+        self.abort_parked_rallies();
+        self.stop_pr_watch();
 
         ui::restore_terminal(&mut terminal)?;
+        self.print_session_summary();
         Ok(())
     }
 
+    /// 1 フレームごとにスピナーを進め、バックグラウンドの処理の結果を取り込む
+    pub(crate) fn poll_background_updates(&mut self) {
+        if self.spinner_tick.elapsed() >= SPINNER_INTERVAL {
+            self.spinner_tick = Instant::now();
+            self.spinner_frame = self.spinner_frame.wrapping_add(1);
+        }
+        self.poll_pr_list_updates();
+        self.poll_pr_coverage_updates();
+        self.poll_data_updates();
+        self.poll_comment_updates();
+        self.poll_thread_state_updates();
+        self.poll_diff_cache_updates();
+        self.highlight_visible_lines();
+        self.poll_prefetch_updates();
+        self.poll_batch_diff_updates();
+        self.poll_lazy_diff_updates();
+        self.poll_lazy_patch_updates();
+        self.poll_discussion_comment_updates();
+        self.poll_comment_submit_updates();
+        self.poll_mark_viewed_updates();
+        self.poll_fixup_updates();
+        self.poll_translate_updates();
+        self.poll_task_toggle_updates();
+        self.poll_batch_reply_updates();
+        self.poll_lfs_updates();
+        self.poll_base_file_updates();
+        self.poll_blame_updates();
+        self.poll_line_history_updates();
+        self.poll_advisory_db_updates();
+        self.refresh_manifest_summary();
+        self.poll_local_compare_updates();
+        self.poll_conflict_updates();
+        self.poll_local_data_updates();
+        self.poll_nudge_updates();
+        self.poll_agenda_updates();
+        self.poll_people_updates();
+        self.poll_label_updates();
+        self.poll_user_info_updates();
+        self.poll_explain_updates();
+        self.poll_comment_draft_updates();
+        self.poll_checkout_updates();
+        self.poll_pr_create_updates();
+        self.poll_commit_list_updates();
+        self.poll_checks_updates();
+        self.poll_pr_overview_updates();
+        self.poll_pr_summary_updates();
+        self.poll_inbox_updates();
+        self.poll_pr_watch_updates();
+        self.poll_ipc_commands();
+        self.emit_follow_event();
+        self.poll_rally_replay();
+        self.poll_rally_events();
+        self.poll_parked_rallies();
+        self.advance_grep();
+        self.sync_file_order();
+        self.sync_review_progress();
+        self.poll_progress_sync();
+        self.poll_outbox_updates();
+        self.rate_limit = crate::github::current_rate_limit();
+        self.update_poll_tier();
+    }
+
+    /// 実行中の処理と最後の入力からポーリングの速さを決め、PR 監視タスクにも伝える
+    fn update_poll_tier(&mut self) {
+        let tier = self.activity.tier(self.has_work_in_flight(), Instant::now());
+        self.poll_tier.send_if_modified(|current| {
+            let changed = *current != tier;
+            *current = tier;
+            changed
+        });
+    }
+
+    pub(crate) fn poll_tier(&self) -> PollTier {
+        *self.poll_tier.borrow()
+    }
+
+    /// 読み込み・投稿・Rally などの結果を待っているか
+    fn has_work_in_flight(&self) -> bool {
+        matches!(self.data_state, DataState::Loading)
+            || self.comment_submitting
+            || self.rally_event_receiver.is_some()
+            || self.rally_replay.is_some()
+            || self.comment_receiver.is_some()
+            || self.discussion_comment_receiver.is_some()
+            || self.diff_cache_receiver.is_some()
+            || self.prefetch_receiver.is_some()
+            || self.batch_diff_receiver.is_some()
+            || self.lazy_diff_receiver.is_some()
+            || !self.lazy_patch_receivers.is_empty()
+            || self.file_page_progress.is_some()
+            || self.comment_submit_receiver.is_some()
+            || self.mark_viewed_receiver.is_some()
+            || self.translate_receiver.is_some()
+            || self.checks_receiver.is_some()
+            || self.pr_overview_receiver.is_some()
+            || self.pr_summary_receiver.is_some()
+            || self.inbox_receiver.is_some()
+            || self.check_log_receiver.is_some()
+            || self.commit_list_receiver.is_some()
+            || self.commit_diff_receiver.is_some()
+            || self.pr_list_receiver.is_some()
+            || self.people_receiver.is_some()
+            || self.people_update_receiver.is_some()
+            || self.label_list_receiver.is_some()
+            || self.label_update_receiver.is_some()
+            || self.user_info_receiver.is_some()
+            || self.explain_receiver.is_some()
+            || self.comment_draft_receiver.is_some()
+            || self.checkout_receiver.is_some()
+            || self.pr_create_receiver.is_some()
+            || self.grep.as_ref().is_some_and(GrepState::is_searching)
+    }
+
     /// Get the current spinner character for loading animations
     pub fn spinner_char(&self) -> &str {
-        SPINNER_FRAMES[self.spinner_frame % SPINNER_FRAMES.len()]
+        let frames = self.config.ui.spinner.frames();
+        frames[self.spinner_frame % frames.len()]
+    }
+
+    /// 実行中の一括 viewed の進捗
+    pub fn mark_viewed_progress(&self) -> Option<&Progress> {
+        self.mark_viewed_progress
+            .as_ref()
+            .filter(|_| self.mark_viewed_receiver.is_some())
+    }
+
+    /// バックグラウンドで進んでいる読み込みの進捗（変更ファイルの続き・diff の一括取得・ハイライトの事前構築）
+    pub fn background_progress(&self) -> Vec<&Progress> {
+        [
+            self.file_page_progress
+                .as_ref()
+                .filter(|(pr_number, _)| self.pr_number == Some(*pr_number))
+                .map(|(_, progress)| progress),
+            self.batch_diff_progress
+                .as_ref()
+                .filter(|_| self.batch_diff_receiver.is_some()),
+            self.prefetch_progress
+                .as_ref()
+                .filter(|_| self.prefetch_receiver.is_some()),
+        ]
+        .into_iter()
+        .flatten()
+        .collect()
     }
 
     pub fn set_working_dir(&mut self, dir: Option<String>) {
@@ -464,10 +1245,29 @@ impl App {
         self.local_mode = local;
     }
 
+    /// `--local` で起動したときのファイルウォッチャーを開始する。
+    /// retry sender と working_dir を設定してから呼ぶ
+    pub fn start_local_watcher(&mut self) {
+        self.activate_watcher();
+    }
+
     pub fn set_local_auto_focus(&mut self, enable: bool) {
         self.local_auto_focus = enable;
     }
 
+    pub fn set_wait_for_review(&mut self, enable: bool) {
+        self.wait_for_review = enable;
+    }
+
+    pub fn set_anonymizer(&mut self, anonymizer: Anonymizer) {
+        self.anonymizer = anonymizer;
+    }
+
+    /// このセッションで最後に送信したレビュー（終了コードの判定用）
+    pub fn review_outcome(&self) -> Option<ReviewAction> {
+        self.review_outcome
+    }
+
     pub fn is_local_mode(&self) -> bool {
         self.local_mode
     }
@@ -485,6 +1285,11 @@ impl App {
         self.start_ai_rally_on_load = start;
     }
 
+    /// Resume the previous unfinished rally instead of starting a new one (--resume-rally)
+    pub fn set_resume_rally(&mut self, resume: bool) {
+        self.resume_rally_on_load = resume;
+    }
+
     /// Set pending AI Rally flag (for PR list mode with --ai-rally)
     pub fn set_pending_ai_rally(&mut self, pending: bool) {
         self.pending_ai_rally = pending;
@@ -543,6 +1348,12 @@ impl App {
             multiline_selection: None,
             input_mode: None,
             input_text_area: TextArea::with_submit_key(config.keybindings.submit.clone()),
+            time_format: TimeFormatter::from_config(&config.time),
+            spinner_tick: Instant::now(),
+            activity: ActivityTracker::new(&config.polling, Instant::now()),
+            poll_tier: tokio::sync::watch::channel(PollTier::Active).0,
+            anonymizer: Anonymizer::default(),
+            path_scope: PathScope::new(&config.scope.paths),
             config,
             should_quit: false,
             review_comments: None,
@@ -551,10 +1362,14 @@ impl App {
             comments_loading: false,
             file_comment_positions: vec![],
             file_comment_lines: HashSet::new(),
+            review_thread_states: HashMap::new(),
+            thread_states_pr: None,
+            thread_state_receiver: None,
             comment_panel_open: false,
             comment_panel_scroll: 0,
             diff_cache: None,
             highlighted_cache_store: HashMap::new(),
+            viewport_parser_pool: crate::syntax::ParserPool::new(),
             discussion_comments: None,
             selected_discussion_comment: 0,
             discussion_comment_list_scroll_offset: 0,
@@ -564,7 +1379,10 @@ impl App {
             help_scroll_offset: 0,
             help_tab: HelpTab::default(),
             config_scroll_offset: 0,
+            help_filter: None,
             comment_tab: CommentTab::default(),
+            comment_list_bots_expanded: false,
+            comment_list_unresolved_only: false,
             ai_rally_state: None,
             working_dir: None,
             data_receiver: None,
@@ -576,7 +1394,10 @@ impl App {
             rally_event_receiver: None,
             rally_abort_handle: None,
             rally_command_sender: None,
+            rally_scope: Vec::new(),
+            rally_scope_marks: HashSet::new(),
             start_ai_rally_on_load: false,
+            resume_rally_on_load: false,
             pending_ai_rally: false,
             comment_submit_receiver: None,
             mark_viewed_receiver: None,
@@ -589,6 +1410,7 @@ impl App {
             jump_stack: Vec::new(),
             pending_keys: SmallVec::new(),
             pending_since: None,
+            completed_sequence: None,
             symbol_popup: None,
             session_cache: SessionCache::new(),
             local_mode: false,
@@ -606,6 +1428,131 @@ impl App {
             batch_diff_receiver: None,
             lazy_diff_receiver: None,
             lazy_diff_pending_file: None,
+            lazy_patch_pages: HashMap::new(),
+            lazy_patch_receivers: Vec::new(),
+            fixup_popup: None,
+            fixup_receiver: None,
+            ipc_receiver: None,
+            follow_emitter: None,
+            follow_cursor: None,
+            session_activity: None,
+            comment_translations: HashMap::new(),
+            translate_receiver: None,
+            file_finder: None,
+            recent_files: Vec::new(),
+            lfs_pointer_patches: HashMap::new(),
+            lfs_receiver: None,
+            rally_replay: None,
+            pr_watch: None,
+            pr_watch_receiver: None,
+            file_view_states: HashMap::new(),
+            base_file_patches: HashMap::new(),
+            base_file_receiver: None,
+            blame_enabled: false,
+            file_blames: HashMap::new(),
+            blame_receiver: None,
+            line_history: None,
+            line_history_receiver: None,
+            line_history_diff_receiver: None,
+            key_usage: learning::KeyUsageStats::default(),
+            key_hint: None,
+            key_stats_open: false,
+            local_compare: None,
+            local_compare_receiver: None,
+            conflict_resolve: None,
+            conflict_receiver: None,
+            conflict_write_receiver: None,
+            pr_review_coverage: HashMap::new(),
+            pr_coverage_requested: HashSet::new(),
+            pr_coverage_receiver: None,
+            local_data: None,
+            local_data_receiver: None,
+            nudge_receiver: None,
+            nudge_submit_receiver: None,
+            commit_list: None,
+            commit_list_receiver: None,
+            checks: None,
+            checks_receiver: None,
+            pr_overview: None,
+            pr_overview_receiver: None,
+            pr_summary: None,
+            pr_summary_receiver: None,
+            pr_summaries: HashMap::new(),
+            inbox: None,
+            inbox_receiver: None,
+            check_log_receiver: None,
+            commit_view: None,
+            commit_diff_receiver: None,
+            wait_for_review: false,
+            review_outcome: None,
+            discussion_task_cursor: 0,
+            viewer_login: None,
+            task_toggle_receiver: None,
+            pending_discussion_delete: None,
+            pending_draft_toggle: None,
+            marked_review_comments: HashSet::new(),
+            batch_reply_errors: HashMap::new(),
+            batch_reply_receiver: None,
+            batch_diff_progress: None,
+            file_page_progress: None,
+            prefetch_progress: None,
+            mark_viewed_progress: None,
+            vendored_expanded: HashSet::new(),
+            vendored_summary_open: false,
+            manifest_summary: None,
+            advisory_db: None,
+            advisory_db_receiver: None,
+            pending_review: PendingReview::default(),
+            review_draft_mode: false,
+            review_drafts_open: false,
+            review_agenda: None,
+            agenda_submit_receiver: None,
+            people_picker: None,
+            people_receiver: None,
+            people_update_receiver: None,
+            label_picker: None,
+            label_list_receiver: None,
+            label_update_receiver: None,
+            popup_stack: Vec::new(),
+            workspace_repos: Vec::new(),
+            repo_switcher: None,
+            parked_reviews: HashMap::new(),
+            pr_tabs: PrTabs::default(),
+            parked_pr_tabs: HashMap::new(),
+            diff_search: None,
+            grep: None,
+            file_order: file_sort::FileOrder::default(),
+            opened_files: HashSet::new(),
+            quit_summary: None,
+            review_summary: None,
+            user_info: None,
+            user_info_receiver: None,
+            user_info_cache: HashMap::new(),
+            codeowners_cache: HashMap::new(),
+            pending_suggestion_apply: None,
+            explain_panel_open: false,
+            file_explanations: HashMap::new(),
+            explain_receiver: None,
+            comment_draft_receiver: None,
+            checkout: None,
+            checkout_receiver: None,
+            pr_create: None,
+            pr_create_receiver: None,
+            local_repos: None,
+            parked_local_repos: HashMap::new(),
+            review_progress: None,
+            review_progress_key: None,
+            review_queue: Vec::new(),
+            approve_then_next: false,
+            progress_sync: None,
+            progress_sync_receiver: None,
+            progress_sync_pending: false,
+            outbox: crate::cache::Outbox::default(),
+            outbox_open: false,
+            selected_outbox_entry: 0,
+            outbox_receiver: None,
+            rate_limit: None,
+            selected_review_draft: 0,
         }
     }
 
@@ -619,4 +1566,12 @@ impl App {
     pub fn set_pending_approve_body_for_test(&mut self, body: Option<String>) {
         self.pending_approve_body = body;
     }
+
+    /// Start a fake prefetch with the given progress for testing.
+    #[cfg(test)]
+    pub fn set_prefetch_progress_for_test(&mut self, progress: Progress) {
+        let (_tx, rx) = mpsc::channel(1);
+        self.prefetch_receiver = Some(rx);
+        self.prefetch_progress = Some(progress);
+    }
 }
@@ -0,0 +1,146 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::audit::{self, AuditAction, AuditEntry};
+use crate::github;
+use crate::nudge::{self, NudgeContext};
+
+use super::types::InputMode;
+use super::{App, AppState};
+
+impl App {
+    fn set_nudge_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// PR 一覧で選択中の PR へのリマインドコメントを作成する。
+    /// レビュー依頼中の相手を取得してからテンプレートを展開し、入力画面で編集してから投稿する
+    pub(crate) fn start_nudge(&mut self) {
+        if self.is_filter_selection_empty("pr") || self.nudge_receiver.is_some() {
+            return;
+        }
+        let Some(pr) = self
+            .pr_list
+            .as_ref()
+            .and_then(|prs| prs.get(self.selected_pr))
+        else {
+            return;
+        };
+        if !pr.state.eq_ignore_ascii_case("open") {
+            let message = format!("PR #{} is not open", pr.number);
+            self.set_nudge_message(false, message);
+            return;
+        }
+        let stale_days = self.config.nudge.stale_days;
+        let Some(days) = nudge::days_stale(&pr.updated_at, chrono::Utc::now()) else {
+            return;
+        };
+        if days < i64::from(stale_days) {
+            let message = format!(
+                "PR #{} was updated {} day(s) ago (nudge after {} days)",
+                pr.number, days, stale_days
+            );
+            self.set_nudge_message(false, message);
+            return;
+        }
+
+        let context = NudgeContext {
+            number: pr.number,
+            title: pr.title.clone(),
+            author: pr.author.login.clone(),
+            days,
+            reviewers: Vec::new(),
+        };
+        let (tx, rx) = mpsc::channel(1);
+        self.nudge_receiver = Some(rx);
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = github::fetch_requested_reviewers(&repo, context.number)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((context, result)).await;
+        });
+    }
+
+    pub(crate) fn poll_nudge_updates(&mut self) {
+        if let Some(ref mut rx) = self.nudge_receiver {
+            match rx.try_recv() {
+                Ok((context, result)) => {
+                    self.nudge_receiver = None;
+                    self.open_nudge_composer(context, result);
+                }
+                Err(mpsc::error::TryRecvError::Empty) => {}
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.nudge_receiver = None;
+                }
+            }
+        }
+
+        let Some(ref mut rx) = self.nudge_submit_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok((pr_number, result)) => {
+                self.nudge_submit_receiver = None;
+                match result {
+                    Ok(()) => self
+                        .set_nudge_message(true, format!("Reminder posted on PR #{}", pr_number)),
+                    Err(e) => self.set_nudge_message(false, format!("Failed to post: {}", e)),
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.nudge_submit_receiver = None;
+            }
+        }
+    }
+
+    /// 展開したテンプレートを入力欄に入れてコメント入力画面を開く
+    fn open_nudge_composer(
+        &mut self,
+        mut context: NudgeContext,
+        reviewers: Result<Vec<String>, String>,
+    ) {
+        // 取得待ちの間に別の画面へ移っていたら開かない
+        if self.state != AppState::PullRequestList {
+            return;
+        }
+        match reviewers {
+            Ok(reviewers) => context.reviewers = reviewers,
+            Err(e) => {
+                self.set_nudge_message(false, format!("Failed to load reviewers: {}", e));
+                return;
+            }
+        }
+        let body = nudge::render(&self.config.nudge.template, &context);
+        self.input_mode = Some(InputMode::Nudge {
+            pr_number: context.number,
+            title: context.title,
+            days: context.days,
+        });
+        self.input_text_area.set_content(&body);
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+
+    pub(crate) fn submit_nudge(&mut self, pr_number: u32, body: String) {
+        let (tx, rx) = mpsc::channel(1);
+        self.nudge_submit_receiver = Some(rx);
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = github::create_discussion_comment(&repo, pr_number, &body).await;
+            audit::record(&AuditEntry::new(
+                &repo,
+                pr_number,
+                AuditAction::Nudge,
+                None,
+                &result,
+            ));
+            let _ = tx
+                .send((pr_number, result.map(|_| ()).map_err(|e| e.to_string())))
+                .await;
+        });
+    }
+}
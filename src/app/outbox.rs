@@ -0,0 +1,169 @@
+use std::time::Instant;
+
+use chrono::Utc;
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::cache::{Outbox, OutboxPayload, PrCacheKey};
+use crate::loader::{self, OutboxSendResult};
+use crate::ui::popup::PopupKey;
+
+use super::{App, PopupId};
+
+impl App {
+    fn set_outbox_message(&mut self, success: bool, message: String) {
+        self.submission_result = Some((success, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 前回までに送れなかった送信待ちを読み込み、以後の送信待ちを保存する
+    pub fn enable_outbox(&mut self) {
+        self.outbox = Outbox::load();
+    }
+
+    /// 一時的な失敗で送れなかったコメント・レビューを送信待ちに入れる
+    pub(crate) fn queue_for_retry(&mut self, pr_number: u32, payload: OutboxPayload, error: &str) {
+        let kind = payload.kind_label();
+        let queued = self.outbox.push(
+            &self.repo,
+            pr_number,
+            payload,
+            error,
+            Utc::now().timestamp_millis(),
+        );
+        let message = match queued {
+            Ok(_) => format!(
+                "Failed: {} ({} queued, {}: outbox)",
+                error,
+                kind,
+                self.config.keybindings.outbox.display()
+            ),
+            Err(e) => format!("Failed: {} (could not queue: {})", error, e),
+        };
+        self.set_outbox_message(false, message);
+    }
+
+    /// 送り直しの結果を受け取り、送る時刻になった送信待ちがあれば送り直す
+    pub(crate) fn poll_outbox_updates(&mut self) {
+        if let Some(ref mut rx) = self.outbox_receiver {
+            match rx.try_recv() {
+                Ok(sent) => self.handle_outbox_result(sent),
+                Err(mpsc::error::TryRecvError::Empty) => {}
+                Err(mpsc::error::TryRecvError::Disconnected) => self.outbox_receiver = None,
+            }
+            return;
+        }
+        self.start_outbox_retry();
+    }
+
+    fn start_outbox_retry(&mut self) {
+        if self.outbox_receiver.is_some() {
+            return;
+        }
+        let due = self.outbox.due(Utc::now().timestamp_millis());
+        if due.is_empty() {
+            return;
+        }
+        let (tx, rx) = mpsc::channel(1);
+        self.outbox_receiver = Some(rx);
+        tokio::spawn(loader::retry_outbox(due, tx));
+    }
+
+    fn handle_outbox_result(&mut self, sent: OutboxSendResult) {
+        let updated = match sent.result {
+            Ok(()) => {
+                let removed = self.outbox.remove(sent.id);
+                if let Ok(Some(ref entry)) = removed {
+                    let message = format!(
+                        "Sent queued {} on PR #{}",
+                        entry.payload.kind_label(),
+                        sent.pr_number
+                    );
+                    self.set_outbox_message(true, message);
+                    // 送り直したレビューも、その場で送れたレビューと同じく結果として扱う
+                    if let OutboxPayload::Review { action, .. } = entry.payload {
+                        if sent.repo == self.repo && self.pr_number == Some(sent.pr_number) {
+                            self.record_review_outcome(action);
+                        }
+                    }
+                }
+                self.refresh_review_comments_after_send(&sent.repo, sent.pr_number);
+                removed.map(|_| ())
+            }
+            Err(e) => self
+                .outbox
+                .record_failure(sent.id, &e, Utc::now().timestamp_millis()),
+        };
+        if let Err(e) = updated {
+            tracing::warn!(error = %e, "failed to save outbox");
+        }
+        if self.outbox.is_empty() {
+            self.close_popup(PopupId::Outbox);
+        }
+        self.selected_outbox_entry = self
+            .selected_outbox_entry
+            .min(self.outbox.len().saturating_sub(1));
+    }
+
+    /// 送れたコメントを反映するため再取得する
+    fn refresh_review_comments_after_send(&mut self, repo: &str, pr_number: u32) {
+        let cache_key = PrCacheKey {
+            repo: repo.to_string(),
+            pr_number,
+        };
+        self.session_cache.remove_review_comments(&cache_key);
+        if repo == self.repo && self.pr_number == Some(pr_number) {
+            self.review_comments = None;
+            self.load_review_comments();
+            self.update_file_comment_positions();
+        }
+    }
+
+    /// 送信待ちの一覧を開く
+    pub(crate) fn open_outbox(&mut self) {
+        if self.outbox.is_empty() {
+            self.set_outbox_message(true, "Outbox is empty".to_string());
+            return;
+        }
+        self.outbox_open = true;
+        self.selected_outbox_entry = 0;
+        self.push_popup(PopupId::Outbox);
+    }
+
+    /// r: すべて今すぐ送り直す、d: 選択中の送信待ちを捨てる
+    pub(crate) fn handle_outbox_input(&mut self, key: &KeyEvent) {
+        let kb = self.config.keybindings.clone();
+        let action = PopupKey::from_key(key, &kb);
+        if action == PopupKey::Close || self.matches_single_key(key, &kb.outbox) {
+            self.close_popup(PopupId::Outbox);
+        } else if let Some(selected) =
+            action.navigate(self.selected_outbox_entry, self.outbox.len())
+        {
+            self.selected_outbox_entry = selected;
+        } else if key.code == KeyCode::Char('r') {
+            if let Err(e) = self.outbox.retry_all_now(Utc::now().timestamp_millis()) {
+                tracing::warn!(error = %e, "failed to save outbox");
+            }
+            self.start_outbox_retry();
+            self.set_outbox_message(true, "Retrying queued submissions".to_string());
+        } else if key.code == KeyCode::Char('d') {
+            let Some(id) = self
+                .outbox
+                .entries()
+                .get(self.selected_outbox_entry)
+                .map(|entry| entry.id)
+            else {
+                return;
+            };
+            if let Err(e) = self.outbox.remove(id) {
+                tracing::warn!(error = %e, "failed to save outbox");
+            }
+            if self.outbox.is_empty() {
+                self.close_popup(PopupId::Outbox);
+            }
+            self.selected_outbox_entry = self
+                .selected_outbox_entry
+                .min(self.outbox.len().saturating_sub(1));
+        }
+    }
+}
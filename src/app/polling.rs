@@ -5,9 +5,13 @@ use tokio::sync::mpsc;
 
 use crate::ai::orchestrator::RallyEvent;
 use crate::ai::RallyState;
+use crate::audit::{self, AuditAction, AuditEntry};
 use crate::cache::{PrCacheKey, PrData};
-use crate::github::ChangedFile;
-use crate::loader::{CommentSubmitResult, DataLoadResult};
+use crate::github::{ChangedFile, PrComments};
+use crate::loader::{
+    CommentSubmitResult, DataLoadResult, FilePage, PatchPageResult, PATCH_PAGE_SIZE,
+};
+use crate::progress::Progress;
 use crate::syntax::ParserPool;
 
 use super::types::*;
@@ -68,20 +72,31 @@ impl App {
         };
 
         match rx.try_recv() {
+            Ok(DataLoadResult::Comments {
+                pr_number,
+                comments,
+            }) => {
+                self.apply_bundled_comments(pr_number, *comments);
+            }
+            Ok(DataLoadResult::FilePage(page)) => self.apply_file_page(page),
             Ok(result) => {
                 // メッセージ自体から発信元PR番号を取得（mutable な origin_pr に依存しない）
                 let source_pr = match &result {
                     DataLoadResult::Success { pr, .. } => Some(pr.number),
-                    DataLoadResult::Error(_) => None,
+                    DataLoadResult::Error(_)
+                    | DataLoadResult::Comments { .. }
+                    | DataLoadResult::FilePage(_) => None,
                 };
 
                 if source_pr == self.pr_number || source_pr.is_none() {
                     // 現在のPR/モードに一致 → UI状態に反映
                     let pr_number = self.pr_number.unwrap_or(0);
                     self.handle_data_result(pr_number, result);
-                } else if let DataLoadResult::Success { pr, files } = result {
+                } else if let DataLoadResult::Success { pr, mut files } = result {
                     // 異なるPRのデータ: セッションキャッシュにのみ格納
                     // receiver は破棄しない（永続チャンネルを維持）
+                    self.path_scope.retain(&mut files, |f| &f.filename);
+                    self.lazy_patch_pages.remove(&pr.number);
                     let cache_key = PrCacheKey {
                         repo: self.repo.clone(),
                         pr_number: pr.number,
@@ -104,6 +119,102 @@ impl App {
         }
     }
 
+    /// PR と一緒に取得したコメント類をセッションキャッシュに入れ、表示中の PR なら反映する
+    pub(crate) fn apply_bundled_comments(&mut self, pr_number: u32, comments: PrComments) {
+        let PrComments {
+            mut review_comments,
+            discussion_comments,
+            thread_states,
+        } = comments;
+        self.path_scope.retain(&mut review_comments, |c| &c.path);
+        let cache_key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number,
+        };
+        self.session_cache
+            .put_review_comments(cache_key.clone(), review_comments.clone());
+        self.session_cache
+            .put_discussion_comments(cache_key, discussion_comments.clone());
+        if self.pr_number != Some(pr_number) {
+            return;
+        }
+        // すでに REST で取得中なら、その結果で上書きされても内容は同じ
+        self.review_comments = Some(review_comments);
+        self.discussion_comments = Some(discussion_comments);
+        self.review_thread_states.extend(thread_states);
+        self.thread_states_pr = Some(pr_number);
+        if matches!(
+            self.state,
+            AppState::DiffView | AppState::SplitViewDiff | AppState::SplitViewFileList
+        ) {
+            self.update_file_comment_positions();
+        }
+    }
+
+    /// ファイルの多い PR の変更ファイルの続きをセッションキャッシュに足し、表示中の PR なら一覧にも足す
+    pub(crate) fn apply_file_page(&mut self, page: FilePage) {
+        let FilePage {
+            pr_number,
+            mut files,
+            loaded,
+            total,
+            patches_pending,
+            done,
+        } = page;
+        self.path_scope.retain(&mut files, |f| &f.filename);
+        let cache_key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number,
+        };
+        // 途中で取得に失敗した一覧はディスクに残さない
+        self.session_cache
+            .extend_files(&cache_key, files.clone(), done && loaded >= total);
+
+        if done {
+            if matches!(self.file_page_progress, Some((n, _)) if n == pr_number) {
+                self.file_page_progress = None;
+            }
+        } else {
+            match self.file_page_progress {
+                Some((n, ref mut progress)) if n == pr_number => {
+                    progress.done = loaded.min(progress.total);
+                }
+                _ => {
+                    let mut progress = Progress::new("Loading files", total);
+                    progress.advance(loaded);
+                    self.file_page_progress = Some((pr_number, progress));
+                }
+            }
+        }
+        if patches_pending {
+            self.lazy_patch_pages
+                .entry(pr_number)
+                .or_insert_with(|| LazyPatchPages {
+                    page_count: total.div_ceil(PATCH_PAGE_SIZE),
+                    ..LazyPatchPages::default()
+                });
+        }
+
+        if self.pr_number != Some(pr_number) {
+            return;
+        }
+        if let DataState::Loaded {
+            files: ref mut loaded_files,
+            ..
+        } = self.data_state
+        {
+            loaded_files.extend(files);
+        }
+        if self.file_list_filter.is_some() {
+            self.reapply_filter("file");
+        }
+        // 最初の通知で、選択中のファイルの patch の取得を始める
+        self.request_lazy_diff();
+        if done && self.prefetch_receiver.is_none() {
+            self.start_prefetch_all_files();
+        }
+    }
+
     /// コメント取得のポーリング
     pub(crate) fn poll_comment_updates(&mut self) {
         let Some((origin_pr, rx)) = self.comment_receiver.as_mut() else {
@@ -112,7 +223,9 @@ impl App {
         let origin_pr = *origin_pr;
 
         match rx.try_recv() {
-            Ok(Ok(comments)) => {
+            Ok(Ok(mut comments)) => {
+                // スコープ外のファイルへのコメントは扱わない
+                self.path_scope.retain(&mut comments, |c| &c.path);
                 // セッションキャッシュに格納（発信元PRのキーで保存）
                 let cache_key = PrCacheKey {
                     repo: self.repo.clone(),
@@ -162,6 +275,29 @@ impl App {
         }
     }
 
+    /// スレッドの状態取得のポーリング（ガターの絞り込み用）
+    pub(crate) fn poll_thread_state_updates(&mut self) {
+        let Some(ref mut rx) = self.thread_state_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(states) => {
+                self.thread_state_receiver = None;
+                self.review_thread_states.extend(states);
+                if matches!(
+                    self.state,
+                    AppState::DiffView | AppState::SplitViewDiff | AppState::SplitViewFileList
+                ) {
+                    self.update_file_comment_positions();
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.thread_state_receiver = None;
+            }
+        }
+    }
+
     /// バックグラウンドdiffキャッシュ構築のポーリング
     pub(crate) fn poll_diff_cache_updates(&mut self) {
         let Some(ref mut rx) = self.diff_cache_receiver else {
@@ -241,13 +377,14 @@ impl App {
         let channel_size = files.len().min(MAX_PREFETCH_FILES);
         let (tx, rx) = mpsc::channel(channel_size);
         self.prefetch_receiver = Some(rx);
+        self.prefetch_progress = Some(Progress::new("Highlighting", files.len()));
 
         tokio::task::spawn_blocking(move || {
             let mut parser_pool = ParserPool::new();
 
             for (index, filename, patch) in &files {
                 let mut cache = crate::ui::diff_view::build_diff_cache(
-                    patch,
+                    &crate::encoding::display_patch(&crate::lfs::display_patch(patch)),
                     filename,
                     &theme,
                     &mut parser_pool,
@@ -255,6 +392,7 @@ impl App {
                     tab_width,
                 );
                 cache.file_index = *index;
+                cache.patch_hash = hash_string(patch);
                 if tx.blocking_send(cache).is_err() {
                     break; // receiver がドロップされた
                 }
@@ -271,6 +409,9 @@ impl App {
         loop {
             match rx.try_recv() {
                 Ok(cache) => {
+                    if let Some(ref mut progress) = self.prefetch_progress {
+                        progress.advance(1);
+                    }
                     let file_index = cache.file_index;
                     // 現在表示中でハイライト済みならスキップ
                     if self
@@ -344,9 +485,11 @@ impl App {
             return;
         }
 
-        let total_batches = (tracked_filenames.len() + untracked_filenames.len()).div_ceil(20) + 1;
+        let total_files = tracked_filenames.len() + untracked_filenames.len();
+        let total_batches = total_files.div_ceil(20) + 1;
         let (tx, rx) = mpsc::channel(total_batches);
         self.batch_diff_receiver = Some(rx);
+        self.batch_diff_progress = Some(Progress::new("Loading diffs", total_files));
 
         let working_dir = self.working_dir.clone();
         tokio::spawn(async move {
@@ -392,6 +535,9 @@ impl App {
             match rx.try_recv() {
                 Ok(results) => {
                     any_received = true;
+                    if let Some(ref mut progress) = self.batch_diff_progress {
+                        progress.advance(results.len());
+                    }
 
                     if let DataState::Loaded { ref mut files, .. } = self.data_state {
                         if let Some(ref index_map) = index_map {
@@ -453,6 +599,7 @@ impl App {
     /// 選択中ファイルの patch が None なら BG で単一 diff を即時取得
     pub(crate) fn request_lazy_diff(&mut self) {
         if !self.local_mode {
+            self.request_lazy_patch_page();
             return;
         }
         let file = self.files().get(self.selected_file);
@@ -522,9 +669,133 @@ impl App {
         }
     }
 
+    /// ファイルの多い PR で、選択中のファイルを含む files API のページの patch を BG で取得
+    ///
+    /// パスのスコープでファイルを除いていると位置が前にずれるため、
+    /// 見つかるまで後ろのページを順に取得する
+    fn request_lazy_patch_page(&mut self) {
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        if file.patch.is_some() {
+            return;
+        }
+        let filename = file.filename.clone();
+        let first_page = self.selected_file / PATCH_PAGE_SIZE + 1;
+        let Some(pages) = self.lazy_patch_pages.get_mut(&pr_number) else {
+            return;
+        };
+        if pages.fetched_files.contains(&filename) {
+            return;
+        }
+        // 取得中のページがあればその結果を待つ
+        let Some(page) = (first_page..=pages.page_count)
+            .find(|page| !pages.loaded.contains(page))
+            .filter(|page| !pages.requested.contains(page))
+        else {
+            return;
+        };
+        pages.requested.insert(page);
+
+        let (tx, rx) = mpsc::channel(1);
+        self.lazy_patch_receivers.push(rx);
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            crate::loader::fetch_pr_patch_page(repo, pr_number, page, tx).await;
+        });
+    }
+
+    /// files API のページごとの patch をポーリングして適用
+    pub(crate) fn poll_lazy_patch_updates(&mut self) {
+        if self.lazy_patch_receivers.is_empty() {
+            return;
+        }
+        let mut results = Vec::new();
+        self.lazy_patch_receivers
+            .retain_mut(|rx| match rx.try_recv() {
+                Ok(result) => {
+                    results.push(result);
+                    false
+                }
+                Err(mpsc::error::TryRecvError::Empty) => true,
+                Err(mpsc::error::TryRecvError::Disconnected) => false,
+            });
+        for result in results {
+            self.apply_patch_page(result);
+        }
+    }
+
+    fn apply_patch_page(&mut self, result: PatchPageResult) {
+        let PatchPageResult {
+            pr_number,
+            page,
+            patches,
+        } = result;
+        let Some(pages) = self.lazy_patch_pages.get_mut(&pr_number) else {
+            return;
+        };
+        let Some(patches) = patches else {
+            // 失敗したページは、次にファイルを選択したときに取り直す
+            pages.requested.remove(&page);
+            return;
+        };
+        pages.loaded.insert(page);
+        pages
+            .fetched_files
+            .extend(patches.iter().map(|result| result.filename.clone()));
+
+        let cache_key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number,
+        };
+        for result in &patches {
+            self.session_cache.update_file_patch(
+                &cache_key,
+                &result.filename,
+                result.patch.clone(),
+            );
+        }
+        if self.pr_number != Some(pr_number) {
+            return;
+        }
+
+        let selected = self
+            .files()
+            .get(self.selected_file)
+            .map(|file| file.filename.clone());
+        let selected_updated = patches
+            .iter()
+            .any(|result| Some(&result.filename) == selected.as_ref());
+        let patches: HashMap<String, Option<String>> = patches
+            .into_iter()
+            .map(|result| (result.filename, result.patch))
+            .collect();
+        if let DataState::Loaded { ref mut files, .. } = self.data_state {
+            for file in files.iter_mut().filter(|file| file.patch.is_none()) {
+                if let Some(patch) = patches.get(&file.filename) {
+                    file.patch = patch.clone();
+                }
+            }
+        }
+
+        if selected_updated {
+            self.diff_cache = None;
+            self.diff_cache_receiver = None;
+            self.update_diff_line_count();
+            self.ensure_diff_cache();
+        } else {
+            // 選択中のファイルがこのページになければ次のページを探す
+            self.request_lazy_diff();
+        }
+    }
+
     /// UI 用: lazy diff がロード中かどうか
     pub fn is_lazy_diff_loading(&self) -> bool {
-        self.lazy_diff_pending_file.is_some()
+        self.is_lazy_patch_loading()
+            || self.lazy_diff_pending_file.is_some()
             || (self.local_mode
                 && self
                     .files()
@@ -533,6 +804,21 @@ impl App {
                 && self.batch_diff_receiver.is_some())
     }
 
+    /// 選択中のファイルの patch を files API から取得中か
+    fn is_lazy_patch_loading(&self) -> bool {
+        let Some(pages) = self
+            .pr_number
+            .and_then(|pr_number| self.lazy_patch_pages.get(&pr_number))
+        else {
+            return false;
+        };
+        pages.requested.len() > pages.loaded.len()
+            && self
+                .files()
+                .get(self.selected_file)
+                .is_some_and(|f| f.patch.is_none() && !pages.fetched_files.contains(&f.filename))
+    }
+
     /// Discussion コメント取得のポーリング
     pub(crate) fn poll_discussion_comment_updates(&mut self) {
         let Some((origin_pr, rx)) = self.discussion_comment_receiver.as_mut() else {
@@ -620,6 +906,11 @@ impl App {
                 self.submission_result = Some((false, format!("Failed: {}", e)));
                 self.submission_result_time = Some(Instant::now());
             }
+            Ok(CommentSubmitResult::Retryable { error, payload }) => {
+                self.comment_submitting = false;
+                self.comment_submit_receiver = None;
+                self.queue_for_retry(origin_pr, payload, &error);
+            }
             Err(mpsc::error::TryRecvError::Empty) => {}
             Err(mpsc::error::TryRecvError::Disconnected) => {
                 self.comment_submitting = false;
@@ -634,6 +925,11 @@ impl App {
         };
 
         match rx.try_recv() {
+            Ok(MarkViewedResult::Progress { done }) => {
+                if let Some(ref mut progress) = self.mark_viewed_progress {
+                    progress.done = done;
+                }
+            }
             Ok(MarkViewedResult::Completed {
                 marked_paths,
                 total_targets,
@@ -642,6 +938,22 @@ impl App {
             }) => {
                 self.mark_viewed_receiver = None;
 
+                let audit_result = match error {
+                    Some(ref err) => Err(err),
+                    None => Ok(()),
+                };
+                audit::record(&AuditEntry::new(
+                    &self.repo,
+                    origin_pr,
+                    if set_viewed {
+                        AuditAction::MarkViewed
+                    } else {
+                        AuditAction::UnmarkViewed
+                    },
+                    Some(format!("{}/{} file(s)", marked_paths.len(), total_targets)),
+                    &audit_result,
+                ));
+
                 if self.pr_number == Some(origin_pr) {
                     self.apply_viewed_state_to_files(&marked_paths, set_viewed);
                 }
@@ -681,6 +993,34 @@ impl App {
         }
     }
 
+    pub(crate) fn poll_fixup_updates(&mut self) {
+        let Some(ref mut rx) = self.fixup_receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok(result) => {
+                self.fixup_receiver = None;
+                match result {
+                    Ok(created) => {
+                        self.submission_result =
+                            Some((true, format!("Created {} fixup commit(s)", created)));
+                        // コミットで HEAD が進むため差分を再取得
+                        self.retry_load();
+                    }
+                    Err(e) => {
+                        self.submission_result = Some((false, format!("Fixup failed: {}", e)));
+                    }
+                }
+                self.submission_result_time = Some(Instant::now());
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.fixup_receiver = None;
+            }
+        }
+    }
+
     pub(crate) fn apply_viewed_state_to_files(&mut self, marked_paths: &[String], set_viewed: bool) {
         if marked_paths.is_empty() {
             return;
@@ -721,7 +1061,15 @@ impl App {
     }
 
     pub(crate) fn poll_rally_events(&mut self) {
-        let Some(ref mut rx) = self.rally_event_receiver else {
+        Self::drain_rally_events(&mut self.rally_event_receiver, &mut self.ai_rally_state);
+    }
+
+    /// Rally のイベントを状態に反映する。表示していないタブの Rally にも使う
+    pub(crate) fn drain_rally_events(
+        receiver: &mut Option<mpsc::Receiver<RallyEvent>>,
+        ai_rally_state: &mut Option<AiRallyState>,
+    ) {
+        let Some(rx) = receiver.as_mut() else {
             return;
         };
 
@@ -729,7 +1077,7 @@ impl App {
         loop {
             match rx.try_recv() {
                 Ok(event) => {
-                    if let Some(ref mut rally_state) = self.ai_rally_state {
+                    if let Some(rally_state) = ai_rally_state.as_mut() {
                         match &event {
                             RallyEvent::StateChanged(state) => {
                                 rally_state.state = *state;
@@ -753,6 +1101,10 @@ impl App {
                                 rally_state
                                     .push_log(LogEntry::new(LogEventType::Info, msg.clone()));
                             }
+                            RallyEvent::UsageRecorded(usage) => {
+                                let iteration = rally_state.iteration;
+                                rally_state.usage.record(iteration, usage);
+                            }
                             RallyEvent::AgentThinking(content) => {
                                 // Store full content; truncation happens at display time
                                 rally_state.push_log(LogEntry::new(
@@ -839,8 +1191,8 @@ impl App {
                 }
                 Err(mpsc::error::TryRecvError::Empty) => break,
                 Err(mpsc::error::TryRecvError::Disconnected) => {
-                    self.rally_event_receiver = None;
-                    if let Some(ref mut rally_state) = self.ai_rally_state {
+                    *receiver = None;
+                    if let Some(rally_state) = ai_rally_state.as_mut() {
                         if rally_state.state.is_active() {
                             rally_state.state = RallyState::Error;
                             rally_state.push_log(LogEntry::new(
@@ -861,7 +1213,18 @@ impl App {
         self.lazy_diff_pending_file = None;
 
         match result {
-            DataLoadResult::Success { pr, files } => {
+            DataLoadResult::Success { pr, mut files } => {
+                self.path_scope.retain(&mut files, |f| &f.filename);
+                // ファイルの多い PR なら、続きのページと patch の取得状況は後から届く
+                self.lazy_patch_pages.remove(&origin_pr);
+                if matches!(self.file_page_progress, Some((n, _)) if n == origin_pr) {
+                    self.file_page_progress = None;
+                }
+                // 再取得したデータは PR 全体のものなのでコミット表示を抜ける
+                if self.commit_view.take().is_some() {
+                    self.diff_cache = None;
+                    self.diff_cache_receiver = None;
+                }
                 let changed_file_index = if self.local_mode && self.local_auto_focus {
                     self.find_changed_local_file_index(&files, self.selected_file)
                 } else {
@@ -986,6 +1349,11 @@ impl App {
                 // split view 側の「Loading diff...」が発生しないようにする
                 self.ensure_diff_cache();
             }
+            DataLoadResult::Comments {
+                pr_number,
+                comments,
+            } => self.apply_bundled_comments(pr_number, *comments),
+            DataLoadResult::FilePage(page) => self.apply_file_page(page),
             DataLoadResult::Error(msg) => {
                 // Loading状態の場合のみエラー表示（既にデータがある場合は無視）
                 if matches!(self.data_state, DataState::Loading) {
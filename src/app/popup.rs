@@ -0,0 +1,172 @@
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+
+use crate::ui::TuiTerminal;
+
+use super::App;
+
+/// 画面に重ねて表示するポップアップ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupId {
+    Symbol,
+    Fixup,
+    SessionActivity,
+    Finder,
+    VendoredSummary,
+    ReviewDrafts,
+    ReviewAgenda,
+    People,
+    Labels,
+    KeyStats,
+    Repos,
+    Grep,
+    QuitSummary,
+    UserInfo,
+    Checkout,
+    ReviewSummary,
+    Outbox,
+    CreatePr,
+    LineHistory,
+}
+
+impl PopupId {
+    /// 開いた順が分からないポップアップを重ねる順（奥から）
+    const ALL: [PopupId; 19] = [
+        Self::Symbol,
+        Self::Fixup,
+        Self::SessionActivity,
+        Self::Finder,
+        Self::VendoredSummary,
+        Self::ReviewDrafts,
+        Self::ReviewAgenda,
+        Self::People,
+        Self::Labels,
+        Self::KeyStats,
+        Self::Repos,
+        Self::Grep,
+        Self::QuitSummary,
+        Self::UserInfo,
+        Self::Checkout,
+        Self::ReviewSummary,
+        Self::Outbox,
+        Self::CreatePr,
+        Self::LineHistory,
+    ];
+}
+
+impl App {
+    pub(crate) fn is_popup_open(&self, id: PopupId) -> bool {
+        match id {
+            PopupId::Symbol => self.symbol_popup.is_some(),
+            PopupId::Fixup => self.fixup_popup.is_some(),
+            PopupId::SessionActivity => self.session_activity.is_some(),
+            PopupId::Finder => self.file_finder.is_some(),
+            PopupId::VendoredSummary => self.vendored_summary_open,
+            PopupId::ReviewDrafts => self.review_drafts_open,
+            PopupId::ReviewAgenda => self.review_agenda.is_some(),
+            PopupId::People => self.people_picker.is_some(),
+            PopupId::Labels => self.label_picker.is_some(),
+            PopupId::KeyStats => self.key_stats_open,
+            PopupId::Repos => self.repo_switcher.is_some(),
+            PopupId::Grep => self.grep.is_some(),
+            PopupId::QuitSummary => self.quit_summary.is_some(),
+            PopupId::UserInfo => self.user_info.is_some(),
+            PopupId::Checkout => self.checkout.is_some(),
+            PopupId::ReviewSummary => self.review_summary.is_some(),
+            PopupId::Outbox => self.outbox_open,
+            PopupId::CreatePr => self.pr_create.is_some(),
+            PopupId::LineHistory => self.line_history.is_some(),
+        }
+    }
+
+    /// 開いたポップアップを最前面に積む（既に開いていれば最前面へ移す）
+    pub(crate) fn push_popup(&mut self, id: PopupId) {
+        let mut stack: Vec<PopupId> = self
+            .open_popups()
+            .into_iter()
+            .filter(|&open| open != id)
+            .collect();
+        stack.push(id);
+        self.popup_stack = stack;
+    }
+
+    /// ポップアップを閉じる
+    pub(crate) fn close_popup(&mut self, id: PopupId) {
+        match id {
+            PopupId::Symbol => self.symbol_popup = None,
+            PopupId::Fixup => self.fixup_popup = None,
+            PopupId::SessionActivity => self.session_activity = None,
+            PopupId::Finder => self.file_finder = None,
+            PopupId::VendoredSummary => self.vendored_summary_open = false,
+            PopupId::ReviewDrafts => self.review_drafts_open = false,
+            PopupId::ReviewAgenda => self.review_agenda = None,
+            PopupId::People => self.people_picker = None,
+            PopupId::Labels => self.label_picker = None,
+            PopupId::KeyStats => self.key_stats_open = false,
+            PopupId::Repos => self.repo_switcher = None,
+            PopupId::Grep => self.grep = None,
+            PopupId::QuitSummary => self.quit_summary = None,
+            PopupId::UserInfo => self.user_info = None,
+            PopupId::Checkout => self.checkout = None,
+            PopupId::ReviewSummary => self.review_summary = None,
+            PopupId::Outbox => self.outbox_open = false,
+            PopupId::CreatePr => self.pr_create = None,
+            PopupId::LineHistory => self.line_history = None,
+        }
+        self.popup_stack.retain(|&open| open != id);
+    }
+
+    /// 開いているポップアップ（奥から手前の順）。
+    /// 状態を直接閉じたものは除き、`push_popup` を通さずに開いたものは一番奥に置く
+    pub fn open_popups(&self) -> Vec<PopupId> {
+        let mut open: Vec<PopupId> = PopupId::ALL
+            .into_iter()
+            .filter(|id| self.is_popup_open(*id) && !self.popup_stack.contains(id))
+            .collect();
+        open.extend(
+            self.popup_stack
+                .iter()
+                .copied()
+                .filter(|&id| self.is_popup_open(id)),
+        );
+        open
+    }
+
+    /// キー入力を受け取る最前面のポップアップ
+    pub fn focused_popup(&self) -> Option<PopupId> {
+        self.open_popups().last().copied()
+    }
+
+    /// 最前面のポップアップにキー入力を渡す。ポップアップが開いていなければ false
+    pub(crate) async fn handle_popup_input(
+        &mut self,
+        key: KeyEvent,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<bool> {
+        let Some(id) = self.focused_popup() else {
+            return Ok(false);
+        };
+        match id {
+            PopupId::Symbol => self.handle_symbol_popup_input(key, terminal).await?,
+            PopupId::Fixup => self.handle_fixup_popup_input(key, terminal)?,
+            PopupId::SessionActivity => self.handle_session_activity_input(key),
+            PopupId::Finder => self.handle_file_finder_input(key),
+            PopupId::VendoredSummary => self.handle_vendored_summary_input(&key),
+            PopupId::ReviewDrafts => self.handle_review_drafts_input(&key),
+            PopupId::ReviewAgenda => self.handle_review_agenda_input(&key),
+            PopupId::People => self.handle_people_picker_input(&key),
+            PopupId::Labels => self.handle_label_picker_input(&key),
+            PopupId::KeyStats => self.handle_key_stats_input(&key),
+            PopupId::Repos => self.handle_repo_switcher_input(&key),
+            PopupId::Grep => self.handle_grep_input(&key),
+            PopupId::QuitSummary => self.handle_quit_summary_input(&key).await?,
+            PopupId::UserInfo => self.handle_user_info_input(&key),
+            PopupId::Checkout => self.handle_checkout_input(&key),
+            PopupId::ReviewSummary => self.handle_review_summary_input(&key, terminal).await?,
+            PopupId::Outbox => self.handle_outbox_input(&key),
+            PopupId::CreatePr => self.handle_pr_create_input(&key, terminal)?,
+            PopupId::LineHistory => self.handle_line_history_input(&key),
+        }
+        Ok(true)
+    }
+}
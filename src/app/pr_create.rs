@@ -0,0 +1,217 @@
+use std::time::Instant;
+
+use anyhow::Result;
+use crossterm::event::KeyEvent;
+use tokio::sync::mpsc;
+
+use crate::github;
+use crate::pr_create::{self, LocalBranch, PrDraft};
+use crate::ui::picker::{Picker, PickerAction, PickerItem};
+use crate::ui::TuiTerminal;
+
+use super::{App, PopupId};
+
+/// ローカルブランチからの PR 作成のベースブランチ選択
+pub struct PrCreateState {
+    pub branch: LocalBranch,
+    pub picker: Picker,
+}
+
+/// PR 作成の結果
+#[derive(Debug)]
+pub(crate) struct CreatedPr {
+    pub number: u32,
+    /// ブランチに開いている PR が既にあり、作成しなかった
+    pub existed: bool,
+}
+
+impl App {
+    fn set_pr_create_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// ローカルモードで見ているブランチから PR を作るため、ベースブランチの選択を開く
+    pub(crate) fn open_pr_create(&mut self) {
+        if !self.local_mode {
+            self.set_pr_create_message(false, "Creating a PR is only available in local mode");
+            return;
+        }
+        if self.pr_create_receiver.is_some() {
+            self.set_pr_create_message(false, "A PR is already being created");
+            return;
+        }
+        let branch = match pr_create::inspect(self.working_dir.as_deref(), &self.repo) {
+            Ok(branch) => branch,
+            Err(e) => {
+                self.set_pr_create_message(false, format!("Cannot create a PR: {:#}", e));
+                return;
+            }
+        };
+        if branch.bases.is_empty() {
+            self.set_pr_create_message(
+                false,
+                format!("No branches on {} to open a PR against", branch.remote),
+            );
+            return;
+        }
+        let items = branch
+            .bases
+            .iter()
+            .enumerate()
+            .map(|(i, base)| PickerItem {
+                label: base.clone(),
+                detail: (i == 0).then(|| "default".to_string()),
+                checked: false,
+            })
+            .collect();
+        self.pr_create = Some(PrCreateState {
+            branch,
+            picker: Picker::new(items),
+        });
+        self.push_popup(PopupId::CreatePr);
+    }
+
+    pub(crate) fn handle_pr_create_input(
+        &mut self,
+        key: &KeyEvent,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        let Some(ref mut state) = self.pr_create else {
+            return Ok(());
+        };
+        match state.picker.handle_key(key) {
+            PickerAction::Continue => {}
+            PickerAction::Close => self.close_popup(PopupId::CreatePr),
+            PickerAction::Select(index) => {
+                let branch = state.branch.clone();
+                let base = branch.bases.get(index).cloned();
+                self.close_popup(PopupId::CreatePr);
+                if let Some(base) = base {
+                    self.write_and_create_pr(branch, base, terminal)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// コミットから下書きしたタイトルと本文を $EDITOR で編集し、push と PR 作成を始める
+    fn write_and_create_pr(
+        &mut self,
+        branch: LocalBranch,
+        base: String,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        let working_dir = self.working_dir.clone();
+        let draft = pr_create::draft(working_dir.as_deref(), &branch, &base);
+
+        terminal.suspend()?;
+        let editor_result = crate::editor::open_pr_create_editor(
+            self.config.editor.as_deref(),
+            &branch.name,
+            &base,
+            &draft.to_editor_text(),
+        );
+        // エディタの成否に関わらずターミナルを再セットアップ
+        terminal.resume()?;
+
+        let text = match editor_result {
+            Ok(Some(text)) => text,
+            Ok(None) => {
+                self.set_pr_create_message(false, "PR creation cancelled");
+                return Ok(());
+            }
+            Err(e) => {
+                self.set_pr_create_message(false, format!("Editor failed: {}", e));
+                return Ok(());
+            }
+        };
+        let Some(draft) = pr_create::parse_draft(&text) else {
+            self.set_pr_create_message(false, "A PR needs a title");
+            return Ok(());
+        };
+
+        self.set_pr_create_message(
+            true,
+            format!("Pushing {} and creating a PR...", branch.name),
+        );
+        let (tx, rx) = mpsc::channel(1);
+        self.pr_create_receiver = Some(rx);
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = push_and_create(&repo, working_dir, branch, &base, &draft)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+        Ok(())
+    }
+
+    pub(crate) fn poll_pr_create_updates(&mut self) {
+        let Some(ref mut rx) = self.pr_create_receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.pr_create_receiver = None;
+                return;
+            }
+        };
+        self.pr_create_receiver = None;
+        match result {
+            Ok(created) => {
+                // 作成中に PR モードへ戻っていたら、作った PR は開かずに知らせるだけにする
+                if self.local_mode {
+                    self.switch_to_created_pr(created.number);
+                }
+                let message = if created.existed {
+                    format!("The branch already has PR #{}", created.number)
+                } else {
+                    format!("Created PR #{}", created.number)
+                };
+                self.set_pr_create_message(true, message);
+            }
+            Err(e) => self.set_pr_create_message(false, format!("Failed to create the PR: {}", e)),
+        }
+    }
+
+    /// ローカルモードを抜けて、作成した PR を開く。
+    /// ローカルの表示状態は残すので、ローカルモードに戻れば続きから見られる
+    fn switch_to_created_pr(&mut self, pr_number: u32) {
+        self.deactivate_watcher();
+        self.saved_local_snapshot = Some(self.save_view_snapshot());
+        self.local_mode = false;
+        // 以前の PR の表示状態には戻らない
+        self.saved_pr_snapshot = None;
+        self.original_pr_number = Some(pr_number);
+        self.selected_line = 0;
+        self.scroll_offset = 0;
+        self.select_pr(pr_number);
+    }
+}
+
+/// ブランチを push して PR を作る。ブランチに開いている PR が既にあれば、それを返す
+async fn push_and_create(
+    repo: &str,
+    working_dir: Option<String>,
+    branch: LocalBranch,
+    base: &str,
+    draft: &PrDraft,
+) -> Result<CreatedPr> {
+    let pushed = branch.clone();
+    tokio::task::spawn_blocking(move || pr_create::push(working_dir.as_deref(), &pushed)).await??;
+    if let Some(number) = github::find_open_pr_for_branch(repo, &branch.name).await? {
+        return Ok(CreatedPr {
+            number,
+            existed: true,
+        });
+    }
+    let number =
+        github::create_pull_request(repo, &branch.name, base, &draft.title, &draft.body).await?;
+    Ok(CreatedPr {
+        number,
+        existed: false,
+    })
+}
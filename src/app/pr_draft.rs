@@ -0,0 +1,97 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::audit::{self, AuditAction, AuditEntry};
+use crate::github;
+
+use super::{App, DataState};
+
+impl App {
+    fn set_draft_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 開いている PR の Draft の切り替えを確認する。
+    /// 下書きならレビュー可能に、そうでなければ下書きに戻す
+    pub(crate) fn request_draft_toggle(&mut self) {
+        if self.local_mode {
+            self.set_draft_message(false, "Draft status can only be changed on PRs");
+            return;
+        }
+        let Some(pr) = self.pr() else {
+            return;
+        };
+        if pr.state != "open" {
+            self.set_draft_message(false, "Only open PRs can change their draft status");
+            return;
+        }
+        self.pending_draft_toggle = Some(!pr.draft);
+    }
+
+    /// Draft の切り替えの確認中か
+    pub fn is_pending_draft_toggle(&self) -> bool {
+        self.pending_draft_toggle.is_some()
+    }
+
+    /// 確認中のフッターの文言
+    pub fn draft_toggle_prompt(&self) -> Option<String> {
+        let draft = self.pending_draft_toggle?;
+        let action = if draft {
+            "Convert this PR back to a draft"
+        } else {
+            "Mark this PR as ready for review"
+        };
+        Some(format!("{}? y: confirm | any other key: cancel", action))
+    }
+
+    /// 確認中のキー入力。y で切り替え、それ以外のキーで取り消す
+    pub(crate) async fn confirm_draft_toggle(&mut self, key: &KeyEvent) {
+        let Some(draft) = self.pending_draft_toggle.take() else {
+            return;
+        };
+        if key.code != KeyCode::Char('y') {
+            self.set_draft_message(false, "Draft status unchanged");
+            return;
+        }
+        let Some(node_id) = self.pr().and_then(|pr| pr.node_id.clone()) else {
+            self.set_draft_message(false, "The PR's node ID is unknown; refresh and try again");
+            return;
+        };
+        let pr_number = self.pr_number();
+
+        let result = github::set_pr_draft(&node_id, draft).await;
+        let action = if draft {
+            AuditAction::ConvertToDraft
+        } else {
+            AuditAction::MarkReadyForReview
+        };
+        audit::record(&AuditEntry::new(
+            &self.repo, pr_number, action, None, &result,
+        ));
+        match result {
+            Ok(()) => {
+                if let DataState::Loaded { ref mut pr, .. } = self.data_state {
+                    pr.draft = draft;
+                }
+                if let Some(summary) = self
+                    .pr_list
+                    .as_mut()
+                    .and_then(|list| list.iter_mut().find(|s| s.number == pr_number))
+                {
+                    summary.is_draft = draft;
+                }
+                let message = if draft {
+                    "Converted to draft"
+                } else {
+                    "Marked as ready for review"
+                };
+                self.set_draft_message(true, message);
+            }
+            Err(e) => {
+                self.set_draft_message(false, format!("Failed to change draft status: {:#}", e))
+            }
+        }
+    }
+}
@@ -3,6 +3,7 @@ use crossterm::event::{self, KeyCode};
 use tokio::sync::mpsc;
 
 use crate::cache::PrCacheKey;
+use crate::config::KeybindingView;
 use crate::filter::ListFilter;
 use crate::github::{self, PrStateFilter};
 use crate::keybinding::{event_to_keybinding, SequenceMatch};
@@ -12,7 +13,7 @@ use super::{App, AppState, DataState};
 impl App {
     pub(crate) async fn handle_pr_list_input(&mut self, key: event::KeyEvent) -> Result<()> {
         // Clone keybindings to avoid borrow conflicts
-        let kb = self.config.keybindings.clone();
+        let kb = self.config.keybindings.for_view(KeybindingView::PrList);
 
         // フィルタ入力中はフィルタ処理を優先
         if self.handle_filter_input(&key, "pr") {
@@ -21,7 +22,7 @@ impl App {
 
         // Quit
         if self.matches_single_key(&key, &kb.quit) {
-            self.should_quit = true;
+            self.request_quit();
             return Ok(());
         }
 
@@ -124,11 +125,17 @@ impl App {
                     return Ok(());
                 }
 
+                // より長いシーケンスの途中か、他の操作のシーケンスが揃った
+                if self.resolve_pending_keys(&kb) {
+                    return Ok(());
+                }
+
                 // マッチしなければペンディングをクリア
                 self.clear_pending_keys();
             } else {
                 // シーケンス開始チェック
-                if self.key_could_match_sequence(&key, &kb.jump_to_first)
+                if self.starts_key_sequence(&key, &kb)
+                    || self.key_could_match_sequence(&key, &kb.jump_to_first)
                     || self.key_could_match_sequence(&key, &kb.filter)
                 {
                     self.push_pending_key(kb_event);
@@ -150,14 +157,39 @@ impl App {
             if self.is_filter_selection_empty("pr") {
                 return Ok(());
             }
-            if let Some(ref prs) = self.pr_list {
-                if let Some(pr) = prs.get(self.selected_pr) {
-                    self.select_pr(pr.number);
-                }
+            let selected = self
+                .pr_list
+                .as_ref()
+                .and_then(|prs| prs.get(self.selected_pr))
+                .map(|pr| pr.number);
+            if let Some(pr_number) = selected {
+                self.set_review_queue_from_pr_list();
+                self.open_pr_tab(pr_number, false);
             }
             return Ok(());
         }
 
+        // Tab: 新しいタブで開く
+        if self.matches_single_key(&key, &kb.open_pr_tab) {
+            if self.is_filter_selection_empty("pr") {
+                return Ok(());
+            }
+            let selected = self
+                .pr_list
+                .as_ref()
+                .and_then(|prs| prs.get(self.selected_pr))
+                .map(|pr| pr.number);
+            if let Some(pr_number) = selected {
+                self.open_pr_tab(pr_number, true);
+            }
+            return Ok(());
+        }
+
+        // 1〜9: 開いているタブへ
+        if self.handle_pr_tab_key(&key, &kb) {
+            return Ok(());
+        }
+
         // ブラウザで開く（configurable、フィルターキーより先に評価）
         if self.matches_single_key(&key, &kb.open_in_browser) {
             if self.is_filter_selection_empty("pr") {
@@ -172,7 +204,7 @@ impl App {
         }
 
         // o: open PRのみ
-        if key.code == KeyCode::Char('o') {
+        if self.matches_single_key(&key, &kb.pr_state_open) {
             if self.pr_list_state_filter != PrStateFilter::Open {
                 self.pr_list_state_filter = PrStateFilter::Open;
                 self.reload_pr_list();
@@ -181,7 +213,7 @@ impl App {
         }
 
         // c: closed PRのみ
-        if key.code == KeyCode::Char('c') {
+        if self.matches_single_key(&key, &kb.pr_state_closed) {
             if self.pr_list_state_filter != PrStateFilter::Closed {
                 self.pr_list_state_filter = PrStateFilter::Closed;
                 self.reload_pr_list();
@@ -190,7 +222,7 @@ impl App {
         }
 
         // a: all PRs
-        if key.code == KeyCode::Char('a') {
+        if self.matches_single_key(&key, &kb.pr_state_all) {
             if self.pr_list_state_filter != PrStateFilter::All {
                 self.pr_list_state_filter = PrStateFilter::All;
                 self.reload_pr_list();
@@ -210,12 +242,49 @@ impl App {
             return Ok(());
         }
 
+        // t: 相対/絶対時刻の切り替え
+        if self.matches_single_key(&key, &kb.toggle_time_format) {
+            self.time_format.toggle_style();
+            return Ok(());
+        }
+
+        // D: ローカル保存データの管理
+        if self.matches_single_key(&key, &kb.local_data) {
+            self.open_local_data();
+            return Ok(());
+        }
+
+        // U: 送信待ちの一覧
+        if self.matches_single_key(&key, &kb.outbox) {
+            self.open_outbox();
+            return Ok(());
+        }
+
+        // I: 自分宛ての PR の通知の受信箱
+        if self.matches_single_key(&key, &kb.inbox) {
+            self.open_inbox();
+            return Ok(());
+        }
+
+        // W: 別のリポジトリの PR 一覧へ切り替え
+        if self.matches_single_key(&key, &kb.switch_repo) {
+            self.open_repo_switcher();
+            return Ok(());
+        }
+
+        // B: 放置されている PR へのリマインド
+        if self.matches_single_key(&key, &kb.nudge) {
+            self.start_nudge();
+            return Ok(());
+        }
+
         // ?: ヘルプ
         if self.matches_single_key(&key, &kb.help) {
             self.previous_state = AppState::PullRequestList;
             self.state = AppState::Help;
             self.help_scroll_offset = 0;
             self.config_scroll_offset = 0;
+            self.help_filter = None;
             return Ok(());
         }
 
@@ -229,6 +298,10 @@ impl App {
         self.pr_list_loading = true;
         self.pr_list_has_more = false;
         self.pr_list_filter = None;
+        // レビュー済み割合も取り直す
+        self.pr_review_coverage.clear();
+        self.pr_coverage_requested.clear();
+        self.pr_coverage_receiver = None;
 
         let (tx, rx) = mpsc::channel(2);
         self.pr_list_receiver = Some(rx);
@@ -268,6 +341,7 @@ impl App {
         self.state = AppState::FileList;
         self.file_list_filter = None;
         self.pending_approve_body = None;
+        self.diff_search = None;
 
         // PR遷移時にバックグラウンドキャッシュをクリア（staleキャッシュ防止）
         self.diff_cache_receiver = None;
@@ -278,6 +352,10 @@ impl App {
         self.lazy_diff_pending_file = None;
         self.highlighted_cache_store.clear();
         self.diff_cache = None;
+        self.file_view_states.clear();
+        self.base_file_patches.clear();
+        self.vendored_expanded.clear();
+        self.rally_scope_marks.clear();
         self.selected_file = 0;
         self.file_list_scroll_offset = 0;
 
@@ -290,30 +368,37 @@ impl App {
         self.update_data_receiver_origin(pr_number);
 
         // インメモリキャッシュを確認し、Hit/Missに応じて分岐
-        let cache_key = PrCacheKey {
-            repo: self.repo.clone(),
-            pr_number,
-        };
-        if let Some(cached) = self.session_cache.get_pr_data(&cache_key) {
-            let diff_line_count = Self::calc_diff_line_count(&cached.files, 0);
-            self.data_state = DataState::Loaded {
-                pr: cached.pr.clone(),
-                files: cached.files.clone(),
-            };
-            self.diff_line_count = diff_line_count;
-            self.start_prefetch_all_files();
-            // キャッシュHit時はhandle_data_resultを経由しないため、ここでRally起動
-            if self.start_ai_rally_on_load {
-                self.start_ai_rally_on_load = false;
-                self.start_ai_rally();
-            }
-        } else {
+        if !self.show_cached_pr(pr_number) {
             self.data_state = DataState::Loading;
         }
 
         // 永続リトライループ経由で fetch 開始
         self.retry_load();
     }
+
+    /// セッションキャッシュ（ディスクキャッシュを含む）に PR があれば取得を待たずに表示する
+    pub(crate) fn show_cached_pr(&mut self, pr_number: u32) -> bool {
+        let cache_key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number,
+        };
+        let Some(cached) = self.session_cache.get_pr_data(&cache_key) else {
+            return false;
+        };
+        let diff_line_count = Self::calc_diff_line_count(&cached.files, 0);
+        self.data_state = DataState::Loaded {
+            pr: cached.pr.clone(),
+            files: cached.files.clone(),
+        };
+        self.diff_line_count = diff_line_count;
+        self.start_prefetch_all_files();
+        // キャッシュHit時はhandle_data_resultを経由しないため、ここでRally起動
+        if self.start_ai_rally_on_load {
+            self.start_ai_rally_on_load = false;
+            self.start_ai_rally();
+        }
+        true
+    }
     pub fn back_to_pr_list(&mut self) {
         if self.started_from_pr_list {
             // Local モードから戻る場合はスナップショット保存 + watcher 停止
@@ -323,8 +408,10 @@ impl App {
                 self.local_mode = false;
             }
 
-            // PR固有の状態をリセット
-            self.pr_number = None;
+            // タブのビューは退避し、PR 一覧から戻ったときに続きから表示する
+            self.park_tab_view();
+
+            // PR固有の状態をリセット（pr_number は下でレビュー済み割合の再取得に使う）
             self.data_state = DataState::Loading;
             self.review_comments = None;
             self.discussion_comments = None;
@@ -350,8 +437,81 @@ impl App {
             self.selected_line = 0;
             self.scroll_offset = 0;
             self.file_list_filter = None;
+            self.diff_search = None;
+
+            // レビューしていた PR のレビュー済み割合を取り直す
+            if let Some(pr_number) = self.pr_number.take() {
+                self.pr_review_coverage.remove(&pr_number);
+                self.pr_coverage_requested.remove(&pr_number);
+            }
+
+            // 受信箱から開いた PR なら受信箱に戻る
+            self.state = if self.take_inbox_return() {
+                AppState::Inbox
+            } else {
+                AppState::PullRequestList
+            };
+        }
+    }
 
-            self.state = AppState::PullRequestList;
+    /// 画面に表示されている PR のうち、レビュー済み割合が未取得のものをまとめて取得する
+    pub(crate) fn request_visible_pr_coverage(&mut self, visible_rows: usize) {
+        if self.state != AppState::PullRequestList
+            || self.local_mode
+            || self.pr_coverage_receiver.is_some()
+        {
+            return;
+        }
+        let Some(ref prs) = self.pr_list else {
+            return;
+        };
+        let display: Vec<u32> = match self.pr_list_filter {
+            Some(ref filter) => filter
+                .matched_indices
+                .iter()
+                .filter_map(|&i| prs.get(i).map(|pr| pr.number))
+                .collect(),
+            None => prs.iter().map(|pr| pr.number).collect(),
+        };
+        let numbers: Vec<u32> = display
+            .into_iter()
+            .skip(self.pr_list_scroll_offset)
+            .take(visible_rows)
+            .filter(|number| !self.pr_coverage_requested.contains(number))
+            .take(github::COVERAGE_BATCH_SIZE)
+            .collect();
+        if numbers.is_empty() {
+            return;
+        }
+        self.pr_coverage_requested.extend(numbers.iter().copied());
+
+        let (tx, rx) = mpsc::channel(1);
+        self.pr_coverage_receiver = Some(rx);
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = github::fetch_review_coverage(&repo, &numbers).await;
+            let _ = tx.send(result.map_err(|e| e.to_string())).await;
+        });
+    }
+
+    pub(crate) fn poll_pr_coverage_updates(&mut self) {
+        let Some(ref mut rx) = self.pr_coverage_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(Ok(coverage)) => {
+                self.pr_review_coverage.extend(coverage);
+                self.pr_coverage_receiver = None;
+            }
+            Ok(Err(e)) => {
+                // バッジは補助情報なので、失敗した PR は再取得せず空欄のままにする
+                tracing::warn!(error = %e, "Failed to fetch review coverage");
+                self.pr_coverage_receiver = None;
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.pr_coverage_receiver = None;
+            }
         }
     }
 }
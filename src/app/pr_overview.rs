@@ -0,0 +1,128 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::github::{self, PrOverview};
+
+use super::{App, AppState};
+
+/// PR の概要（タイトル・本文・ラベル・レビュアー・リンクされた Issue）の画面の状態
+#[derive(Debug)]
+pub struct PrOverviewState {
+    /// レビュアーとリンクされた Issue。読み込み中は None
+    pub details: Option<PrOverview>,
+    pub error: Option<String>,
+    /// 本文を含む内容全体のスクロール位置（行単位、描画時に上限へ丸める）
+    pub scroll: usize,
+    /// 閉じたときに戻る画面
+    return_state: AppState,
+}
+
+impl App {
+    fn set_overview_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// PR の概要を開き、レビュアーとリンクされた Issue を読み込む。
+    /// タイトル・本文・ラベルは読み込み済みの PR から表示する
+    pub(crate) fn open_pr_overview(&mut self) {
+        if self.local_mode {
+            self.set_overview_message(false, "The overview is only available for PRs");
+            return;
+        }
+        let Some(pr_number) = self.pr_number.filter(|_| self.pr().is_some()) else {
+            return;
+        };
+
+        self.pr_overview = Some(PrOverviewState {
+            details: None,
+            error: None,
+            scroll: 0,
+            return_state: self.state,
+        });
+        self.state = AppState::PrOverview;
+
+        let (tx, rx) = mpsc::channel(1);
+        self.pr_overview_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let result = github::fetch_pr_overview(&repo, pr_number)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send(result).await;
+        });
+    }
+
+    pub(crate) fn poll_pr_overview_updates(&mut self) {
+        let Some((origin_pr, ref mut rx)) = self.pr_overview_receiver else {
+            return;
+        };
+        let result = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.pr_overview_receiver = None;
+                return;
+            }
+        };
+        self.pr_overview_receiver = None;
+        if self.pr_number != Some(origin_pr) {
+            return;
+        }
+        let Some(ref mut overview) = self.pr_overview else {
+            return;
+        };
+        match result {
+            Ok(details) => overview.details = Some(details),
+            Err(e) => overview.error = Some(e),
+        }
+    }
+
+    fn close_pr_overview(&mut self) {
+        if let Some(overview) = self.pr_overview.take() {
+            self.state = overview.return_state;
+        }
+        self.pr_overview_receiver = None;
+    }
+
+    pub(crate) fn handle_pr_overview_input(&mut self, key: KeyEvent, visible_rows: usize) {
+        let kb = &self.config.keybindings;
+        let close = key.code == KeyCode::Esc || self.matches_single_key(&key, &kb.quit);
+        let down = self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down;
+        let up = self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up;
+        let page_down = self.matches_single_key(&key, &kb.page_down);
+        let page_up = self.matches_single_key(&key, &kb.page_up);
+        let last = self.matches_single_key(&key, &kb.jump_to_last);
+        let browser = self.matches_single_key(&key, &kb.open_in_browser);
+
+        if close {
+            self.close_pr_overview();
+            return;
+        }
+        if browser {
+            if let Some(pr_number) = self.pr_number {
+                self.open_pr_in_browser(pr_number);
+            }
+            return;
+        }
+        let Some(ref mut overview) = self.pr_overview else {
+            return;
+        };
+        let page = visible_rows.max(1);
+        if down {
+            overview.scroll = overview.scroll.saturating_add(1);
+        } else if up {
+            overview.scroll = overview.scroll.saturating_sub(1);
+        } else if page_down {
+            overview.scroll = overview.scroll.saturating_add(page);
+        } else if page_up {
+            overview.scroll = overview.scroll.saturating_sub(page);
+        } else if last {
+            overview.scroll = usize::MAX;
+        } else if key.code == KeyCode::Char('g') {
+            overview.scroll = 0;
+        }
+    }
+}
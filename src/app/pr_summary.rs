@@ -0,0 +1,198 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::pr_summary::{PrSummarizer, PrSummary, SummaryCache, SummaryRequest};
+
+use super::{App, AppState};
+
+/// AI による PR の要約の画面の状態
+#[derive(Debug)]
+pub struct PrSummaryState {
+    /// 要約した head コミット
+    pub head_sha: String,
+    /// 要約。エージェントの応答待ちの間は None
+    pub summary: Option<PrSummary>,
+    pub error: Option<String>,
+    /// 要約を頼んだエージェントの名前（読み込み中の表示用）
+    pub agent: &'static str,
+    /// 内容全体のスクロール位置（行単位、描画時に上限へ丸める）
+    pub scroll: usize,
+    /// 閉じたときに戻る画面
+    return_state: AppState,
+}
+
+impl App {
+    fn set_summary_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// gS: PR の要約を開く。head が同じ要約がキャッシュにあればそれを表示し、
+    /// なければエージェントに頼む
+    pub(crate) fn open_pr_summary(&mut self) {
+        if self.local_mode {
+            self.set_summary_message(false, "The summary is only available for PRs");
+            return;
+        }
+        let Some(head_sha) = self.pr().map(|pr| pr.head.sha.clone()) else {
+            return;
+        };
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+        let cached = self
+            .pr_summaries
+            .get(&(pr_number, head_sha.clone()))
+            .cloned()
+            .or_else(|| {
+                SummaryCache::from_config(&self.config.cache)?
+                    .load(&self.repo, pr_number, &head_sha)
+            });
+
+        self.pr_summary = Some(PrSummaryState {
+            head_sha: head_sha.clone(),
+            summary: None,
+            error: None,
+            agent: "",
+            scroll: 0,
+            return_state: self.state,
+        });
+        self.state = AppState::PrSummary;
+        match cached {
+            Some(summary) => {
+                self.pr_summaries
+                    .insert((pr_number, head_sha), summary.clone());
+                if let Some(ref mut state) = self.pr_summary {
+                    state.summary = Some(summary);
+                }
+            }
+            None => self.request_pr_summary(),
+        }
+    }
+
+    /// 表示中の head の要約をエージェントに頼む（キャッシュは使わない）
+    fn request_pr_summary(&mut self) {
+        let (Some(pr_number), Some(pr)) = (self.pr_number, self.pr()) else {
+            return;
+        };
+        let request = SummaryRequest {
+            title: pr.title.clone(),
+            body: pr.body.clone().unwrap_or_default(),
+            files: self.files().to_vec(),
+        };
+        let summarizer = match PrSummarizer::from_config(&self.config, &self.repo) {
+            Ok(summarizer) => summarizer,
+            Err(e) => {
+                if let Some(ref mut state) = self.pr_summary {
+                    state.error = Some(format!("{:#}", e));
+                }
+                return;
+            }
+        };
+        let Some(ref mut state) = self.pr_summary else {
+            return;
+        };
+        state.summary = None;
+        state.error = None;
+        state.agent = summarizer.name();
+        state.scroll = 0;
+
+        let head_sha = state.head_sha.clone();
+        let repo = self.repo.clone();
+        let cache = SummaryCache::from_config(&self.config.cache);
+        let (tx, rx) = mpsc::channel(1);
+        self.pr_summary_receiver = Some((pr_number, rx));
+        tokio::spawn(async move {
+            let result = summarizer
+                .summarize(&request)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            if let (Ok(summary), Some(cache)) = (&result, cache) {
+                if let Err(e) = cache.save(&repo, pr_number, &head_sha, summary) {
+                    tracing::warn!("Failed to save the PR summary: {:#}", e);
+                }
+            }
+            let _ = tx.send((head_sha, result)).await;
+        });
+    }
+
+    pub(crate) fn poll_pr_summary_updates(&mut self) {
+        let Some((origin_pr, ref mut rx)) = self.pr_summary_receiver else {
+            return;
+        };
+        let (head_sha, result) = match rx.try_recv() {
+            Ok(result) => result,
+            Err(mpsc::error::TryRecvError::Empty) => return,
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.pr_summary_receiver = None;
+                return;
+            }
+        };
+        self.pr_summary_receiver = None;
+        if let Ok(ref summary) = result {
+            self.pr_summaries
+                .insert((origin_pr, head_sha.clone()), summary.clone());
+        }
+        if self.pr_number != Some(origin_pr) {
+            return;
+        }
+        let Some(ref mut state) = self.pr_summary else {
+            return;
+        };
+        if state.head_sha != head_sha {
+            return;
+        }
+        match result {
+            Ok(summary) => state.summary = Some(summary),
+            Err(e) => state.error = Some(e),
+        }
+    }
+
+    fn close_pr_summary(&mut self) {
+        if let Some(state) = self.pr_summary.take() {
+            self.state = state.return_state;
+        }
+        self.pr_summary_receiver = None;
+    }
+
+    pub(crate) fn handle_pr_summary_input(&mut self, key: KeyEvent, visible_rows: usize) {
+        let kb = &self.config.keybindings;
+        let close = key.code == KeyCode::Esc || self.matches_single_key(&key, &kb.quit);
+        let down = self.matches_single_key(&key, &kb.move_down) || key.code == KeyCode::Down;
+        let up = self.matches_single_key(&key, &kb.move_up) || key.code == KeyCode::Up;
+        let page_down = self.matches_single_key(&key, &kb.page_down);
+        let page_up = self.matches_single_key(&key, &kb.page_up);
+        let last = self.matches_single_key(&key, &kb.jump_to_last);
+        let regenerate = self.matches_single_key(&key, &kb.refresh);
+
+        if close {
+            self.close_pr_summary();
+            return;
+        }
+        if regenerate {
+            if self.pr_summary_receiver.is_none() {
+                self.request_pr_summary();
+            }
+            return;
+        }
+        let Some(ref mut state) = self.pr_summary else {
+            return;
+        };
+        let page = visible_rows.max(1);
+        if down {
+            state.scroll = state.scroll.saturating_add(1);
+        } else if up {
+            state.scroll = state.scroll.saturating_sub(1);
+        } else if page_down {
+            state.scroll = state.scroll.saturating_add(page);
+        } else if page_up {
+            state.scroll = state.scroll.saturating_sub(page);
+        } else if last {
+            state.scroll = usize::MAX;
+        } else if key.code == KeyCode::Char('g') {
+            state.scroll = 0;
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+use tokio::task::AbortHandle;
+
+use crate::ai::orchestrator::{OrchestratorCommand, RallyEvent};
+use crate::config::KeybindingsConfig;
+
+use super::types::{FileViewState, ViewSnapshot};
+use super::{AiRallyState, App, AppState};
+
+/// 表示していないタブで動いている AI Rally
+struct ParkedRally {
+    state: Option<AiRallyState>,
+    event_receiver: Option<mpsc::Receiver<RallyEvent>>,
+    command_sender: Option<mpsc::Sender<OrchestratorCommand>>,
+    abort_handle: Option<AbortHandle>,
+}
+
+/// 表示していないタブのビュー
+struct ParkedView {
+    snapshot: ViewSnapshot,
+    file_view_states: HashMap<String, FileViewState>,
+    /// 表示していた画面（ファイル一覧・diff・AI Rally）
+    state: AppState,
+}
+
+/// 表示していないタブの状態。データ本体は `SessionCache` にあるので持たない
+#[derive(Default)]
+struct ParkedTab {
+    view: Option<ParkedView>,
+    rally: Option<ParkedRally>,
+}
+
+/// PR 一覧から開いた PR のタブ（リポジトリごと）
+#[derive(Default)]
+pub struct PrTabs {
+    /// 開いた順の PR 番号
+    prs: Vec<u32>,
+    /// 表示中（PR 一覧にいるときは最後に表示した）タブ
+    active: usize,
+    parked: HashMap<u32, ParkedTab>,
+}
+
+impl PrTabs {
+    pub fn prs(&self) -> &[u32] {
+        &self.prs
+    }
+
+    /// 表示中のタブの PR
+    pub fn active(&self) -> Option<u32> {
+        self.prs.get(self.active).copied()
+    }
+
+    /// 表示していないタブで AI Rally が動いているか
+    pub fn has_parked_rally(&self, pr_number: u32) -> bool {
+        self.parked
+            .get(&pr_number)
+            .is_some_and(|tab| tab.rally.is_some())
+    }
+
+    fn position(&self, pr_number: u32) -> Option<usize> {
+        self.prs.iter().position(|&n| n == pr_number)
+    }
+
+    /// 表示中のタブのビューを退避済みか（PR 一覧に戻っている）
+    fn is_view_parked(&self) -> bool {
+        self.active()
+            .and_then(|n| self.parked.get(&n))
+            .is_some_and(|tab| tab.view.is_some())
+    }
+
+    /// 表示していないタブの Rally のイベントを取り込む
+    fn poll_rallies(&mut self) {
+        for rally in self
+            .parked
+            .values_mut()
+            .filter_map(|tab| tab.rally.as_mut())
+        {
+            App::drain_rally_events(&mut rally.event_receiver, &mut rally.state);
+        }
+    }
+
+    fn abort_rallies(&mut self) {
+        for rally in self
+            .parked
+            .values_mut()
+            .filter_map(|tab| tab.rally.as_mut())
+        {
+            if let Some(handle) = rally.abort_handle.take() {
+                handle.abort();
+            }
+        }
+    }
+}
+
+impl App {
+    fn set_tab_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// PR 一覧から PR を開く。その PR のタブがあれば切り替え、`new_tab` なら新しいタブで、
+    /// そうでなければ表示中のタブを置き換えて開く。AI Rally のあるタブは置き換えない
+    pub(crate) fn open_pr_tab(&mut self, pr_number: u32, new_tab: bool) {
+        if let Some(index) = self.pr_tabs.position(pr_number) {
+            self.switch_pr_tab(index);
+            return;
+        }
+        if !self.pr_tabs.prs.is_empty() && !new_tab && self.ai_rally_state.is_none() {
+            let active = self.pr_tabs.active;
+            let replaced = std::mem::replace(&mut self.pr_tabs.prs[active], pr_number);
+            self.pr_tabs.parked.remove(&replaced);
+        } else {
+            self.park_active_tab();
+            self.pr_tabs.prs.push(pr_number);
+            self.pr_tabs.active = self.pr_tabs.prs.len() - 1;
+        }
+        self.select_pr(pr_number);
+    }
+
+    /// 表示中のタブのビューと AI Rally を退避する。PR 一覧に戻って退避済みのビューはそのまま
+    fn park_active_tab(&mut self) {
+        let Some(pr_number) = self.pr_tabs.active() else {
+            return;
+        };
+        let view = (!self.pr_tabs.is_view_parked()).then(|| self.take_tab_view());
+        let rally = self.ai_rally_state.take().map(|state| ParkedRally {
+            state: Some(state),
+            event_receiver: self.rally_event_receiver.take(),
+            command_sender: self.rally_command_sender.take(),
+            abort_handle: self.rally_abort_handle.take(),
+        });
+        let tab = self.pr_tabs.parked.entry(pr_number).or_default();
+        if view.is_some() {
+            tab.view = view;
+        }
+        tab.rally = rally;
+    }
+
+    fn take_tab_view(&mut self) -> ParkedView {
+        let state = match self.state {
+            AppState::DiffView
+            | AppState::SplitViewFileList
+            | AppState::SplitViewDiff
+            | AppState::AiRally => self.state,
+            _ => AppState::FileList,
+        };
+        ParkedView {
+            snapshot: self.save_view_snapshot(),
+            file_view_states: std::mem::take(&mut self.file_view_states),
+            state,
+        }
+    }
+
+    /// PR 一覧に戻るときに表示中のタブのビューを退避する。AI Rally は動かしたままにする
+    pub(crate) fn park_tab_view(&mut self) {
+        if self.local_mode || self.pr_tabs.is_view_parked() {
+            return;
+        }
+        let Some(pr_number) = self.pr_tabs.active() else {
+            return;
+        };
+        if self.pr_number != Some(pr_number) {
+            return;
+        }
+        let view = self.take_tab_view();
+        self.pr_tabs.parked.entry(pr_number).or_default().view = Some(view);
+    }
+
+    /// `index` 番目のタブに切り替える。表示位置・AI Rally はタブごとに残る
+    pub(crate) fn switch_pr_tab(&mut self, index: usize) {
+        if index >= self.pr_tabs.prs.len() {
+            return;
+        }
+        if index == self.pr_tabs.active && !self.pr_tabs.is_view_parked() {
+            return;
+        }
+        if index != self.pr_tabs.active {
+            self.park_active_tab();
+            self.pr_tabs.active = index;
+        }
+        self.show_active_tab();
+    }
+
+    /// 表示中のタブを退避した状態から表示する。データは `SessionCache` から戻し、裏で取り直す
+    fn show_active_tab(&mut self) {
+        let Some(pr_number) = self.pr_tabs.active() else {
+            return;
+        };
+        let tab = self.pr_tabs.parked.remove(&pr_number).unwrap_or_default();
+        let Some(view) = tab.view else {
+            self.select_pr(pr_number);
+            self.restore_tab_rally(tab.rally);
+            return;
+        };
+
+        self.file_list_filter = None;
+        self.pending_approve_body = None;
+        self.diff_search = None;
+        self.commit_view = None;
+        self.mark_viewed_receiver = None;
+        self.batch_diff_receiver = None;
+        self.lazy_diff_receiver = None;
+        self.lazy_diff_pending_file = None;
+        self.base_file_patches.clear();
+        self.vendored_expanded.clear();
+        self.restore_view_snapshot(view.snapshot);
+        self.file_view_states = view.file_view_states;
+        self.pr_number = Some(pr_number);
+        self.state = view.state;
+        self.restore_tab_rally(tab.rally);
+        if self.state == AppState::AiRally && self.ai_rally_state.is_none() {
+            self.state = AppState::FileList;
+        }
+
+        self.update_data_receiver_origin(pr_number);
+        self.restore_data_from_cache();
+    }
+
+    fn restore_tab_rally(&mut self, rally: Option<ParkedRally>) {
+        let Some(rally) = rally else {
+            return;
+        };
+        self.ai_rally_state = rally.state;
+        self.rally_event_receiver = rally.event_receiver;
+        self.rally_command_sender = rally.command_sender;
+        self.rally_abort_handle = rally.abort_handle;
+    }
+
+    /// 表示中のタブを閉じて隣のタブを表示する。最後のタブなら PR 一覧に戻る。
+    /// 動いている AI Rally は止めずに残すため、そのタブは閉じない
+    pub(crate) fn close_pr_tab(&mut self) {
+        let Some(pr_number) = self.pr_tabs.active() else {
+            return;
+        };
+        if self
+            .ai_rally_state
+            .as_ref()
+            .is_some_and(|rally| rally.state.is_active())
+        {
+            self.set_tab_message(false, "Abort the AI Rally before closing its tab");
+            return;
+        }
+        self.cleanup_rally_state();
+        let tabs = &mut self.pr_tabs;
+        tabs.prs.remove(tabs.active);
+        tabs.parked.remove(&pr_number);
+        if tabs.prs.is_empty() {
+            tabs.active = 0;
+            self.back_to_pr_list();
+            return;
+        }
+        tabs.active = tabs.active.min(tabs.prs.len() - 1);
+        self.show_active_tab();
+    }
+
+    /// 表示していないタブの AI Rally のイベントを取り込む（止めずに進める）
+    pub(crate) fn poll_parked_rallies(&mut self) {
+        self.pr_tabs.poll_rallies();
+        for tabs in self.parked_pr_tabs.values_mut() {
+            tabs.poll_rallies();
+        }
+    }
+
+    /// 終了時に表示していないタブの AI Rally も止める
+    pub(crate) fn abort_parked_rallies(&mut self) {
+        self.pr_tabs.abort_rallies();
+        for tabs in self.parked_pr_tabs.values_mut() {
+            tabs.abort_rallies();
+        }
+    }
+
+    /// リポジトリを切り替えるときにタブを退避し、切り替え先のタブを戻す
+    pub(crate) fn swap_pr_tabs(&mut self, previous_repo: String) {
+        self.park_active_tab();
+        let tabs = std::mem::take(&mut self.pr_tabs);
+        self.parked_pr_tabs.insert(previous_repo, tabs);
+        self.pr_tabs = self.parked_pr_tabs.remove(&self.repo).unwrap_or_default();
+    }
+
+    /// タブの操作キー。`1`〜`9` でそのタブへ、次・前のタブへの切り替え、タブを閉じる
+    pub(crate) fn handle_pr_tab_key(&mut self, key: &KeyEvent, kb: &KeybindingsConfig) -> bool {
+        if self.local_mode || self.pr_tabs.prs.is_empty() || !self.pending_keys.is_empty() {
+            return false;
+        }
+        let in_pr_list = self.state == AppState::PullRequestList;
+        let count = self.pr_tabs.prs.len();
+        if let KeyCode::Char(c @ '1'..='9') = key.code {
+            let index = c as usize - '1' as usize;
+            if index < count {
+                self.switch_pr_tab(index);
+            }
+            return true;
+        }
+        if in_pr_list {
+            return false;
+        }
+        if self.matches_single_key(key, &kb.next_pr_tab) {
+            self.switch_pr_tab((self.pr_tabs.active + 1) % count);
+            return true;
+        }
+        if self.matches_single_key(key, &kb.prev_pr_tab) {
+            self.switch_pr_tab((self.pr_tabs.active + count - 1) % count);
+            return true;
+        }
+        if self.matches_single_key(key, &kb.close_pr_tab) {
+            self.close_pr_tab();
+            return true;
+        }
+        false
+    }
+}
@@ -0,0 +1,102 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::cache::PrCacheKey;
+use crate::pr_watch::PrChange;
+
+use super::{App, AppState, DataState};
+
+impl App {
+    /// `[watch] enabled` のとき、表示中の PR の監視タスクを起動・切り替えする
+    pub(crate) fn ensure_pr_watch(&mut self) {
+        let target = self
+            .pr_number
+            .filter(|_| self.config.watch.enabled && !self.local_mode)
+            .filter(|_| matches!(self.data_state, DataState::Loaded { .. }))
+            .filter(|_| self.state != AppState::PullRequestList);
+
+        if self.pr_watch.as_ref().map(|(pr, _)| *pr) == target {
+            return;
+        }
+        self.stop_pr_watch();
+        let Some(pr_number) = target else {
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel(4);
+        let handle = tokio::spawn(crate::pr_watch::watch(
+            self.repo.clone(),
+            pr_number,
+            self.config.watch.interval_secs,
+            tx,
+            self.poll_tier.subscribe(),
+        ));
+        self.pr_watch = Some((pr_number, handle.abort_handle()));
+        self.pr_watch_receiver = Some(rx);
+    }
+
+    pub(crate) fn stop_pr_watch(&mut self) {
+        if let Some((_, handle)) = self.pr_watch.take() {
+            handle.abort();
+        }
+        self.pr_watch_receiver = None;
+    }
+
+    pub(crate) fn poll_pr_watch_updates(&mut self) {
+        self.ensure_pr_watch();
+        let Some(ref mut rx) = self.pr_watch_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok((pr_number, changes)) => {
+                if self.pr_number == Some(pr_number) {
+                    self.apply_pr_changes(&changes);
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.pr_watch_receiver = None;
+            }
+        }
+    }
+
+    /// 変化の種類に応じて必要なデータだけを再取得する
+    pub(crate) fn apply_pr_changes(&mut self, changes: &[PrChange]) {
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+        let cache_key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number,
+        };
+        let mut updated = Vec::new();
+        for change in changes {
+            match change {
+                PrChange::Files => {
+                    // 読み込み済みなら Loading に戻さずファイル一覧だけ差し替わる
+                    self.retry_load();
+                    updated.push("new commits");
+                }
+                PrChange::ReviewComments => {
+                    self.session_cache.remove_review_comments(&cache_key);
+                    if self.review_comments.is_some() || self.comments_loading {
+                        self.load_review_comments();
+                    }
+                    updated.push("review comments");
+                }
+                PrChange::Discussion => {
+                    self.session_cache.remove_discussion_comments(&cache_key);
+                    if self.discussion_comments.is_some() || self.discussion_comments_loading {
+                        self.load_discussion_comments();
+                    }
+                    updated.push("conversation");
+                }
+            }
+        }
+        if !updated.is_empty() {
+            self.submission_result = Some((true, format!("PR updated: {}", updated.join(", "))));
+            self.submission_result_time = Some(Instant::now());
+        }
+    }
+}
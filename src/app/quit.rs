@@ -0,0 +1,86 @@
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::audit;
+use crate::session_summary::SessionSummary;
+
+use super::types::*;
+use super::{App, PopupId};
+
+impl App {
+    /// このセッションのまとめ（監査ログと開いたファイル、未送信の下書きから作る）
+    pub fn session_summary(&self) -> SessionSummary {
+        let unsent_drafts = self.pending_review.total()
+            + self
+                .parked_reviews
+                .values()
+                .map(|review| review.total())
+                .sum::<usize>();
+        SessionSummary::from_entries(
+            &audit::read_session(),
+            self.opened_files.len(),
+            unsent_drafts,
+        )
+    }
+
+    /// q: 終了する。未送信の下書きが残っていれば、まとめを見せて送信するか確認する
+    pub(crate) fn request_quit(&mut self) {
+        let summary = self.session_summary();
+        if !summary.has_unsent_work() {
+            self.should_quit = true;
+            return;
+        }
+        self.quit_summary = Some(summary);
+        self.push_popup(PopupId::QuitSummary);
+    }
+
+    /// 終了確認から送信できる下書き数（PR を開いている間の、その PR の下書き）
+    pub fn quit_submittable_drafts(&self) -> usize {
+        if self.pr().is_some() {
+            self.review_drafts().len()
+        } else {
+            0
+        }
+    }
+
+    /// 終了確認のキー入力。s で現在の PR の下書きを送信して終了、q / y でそのまま終了、
+    /// Esc / n で取り消す
+    pub(crate) async fn handle_quit_summary_input(&mut self, key: &KeyEvent) -> Result<()> {
+        match key.code {
+            KeyCode::Char('s') if self.quit_submittable_drafts() > 0 => {
+                self.close_popup(PopupId::QuitSummary);
+                self.submit_review_with_body(ReviewAction::Comment, "")
+                    .await?;
+                // 送信に失敗した場合は下書きが残るので、エラーを表示したまま留まる
+                if self.review_drafts().is_empty() {
+                    self.request_quit();
+                }
+            }
+            KeyCode::Char('q' | 'y') => {
+                self.close_popup(PopupId::QuitSummary);
+                self.should_quit = true;
+            }
+            KeyCode::Esc | KeyCode::Char('n') => self.close_popup(PopupId::QuitSummary),
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// 終了後、ターミナルにセッションのまとめを出力する（何もしていなければ出さない）
+    pub(crate) fn print_session_summary(&self) {
+        let summary = self.session_summary();
+        if summary.is_empty() {
+            return;
+        }
+        let text = format!(
+            "octorus session summary\n  {}",
+            summary.lines().join("\n  ")
+        );
+        // --wait-for-review では標準出力を呼び出し元が読むことがあるため標準エラーに出す
+        if self.wait_for_review {
+            eprintln!("{}", text);
+        } else {
+            println!("{}", text);
+        }
+    }
+}
@@ -0,0 +1,175 @@
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use tokio::sync::mpsc;
+
+use crate::ai::orchestrator::RallyEvent;
+use crate::ai::transcript::Transcript;
+use crate::ai::RallyState;
+
+use super::types::{AiRallyState, LogEntry, LogEventType, RallyUsage};
+use super::{App, AppState};
+
+/// 再生中に次のイベントを流すまでの間隔
+const REPLAY_STEP_INTERVAL: Duration = Duration::from_millis(250);
+
+/// 書き出した Rally トランスクリプトを AI Rally 画面で再生する
+#[derive(Debug)]
+pub struct RallyReplay {
+    pending: VecDeque<RallyEvent>,
+    total: usize,
+    /// 通常の Rally と同じく poll_rally_events() 経由で処理させるための送信側
+    sender: mpsc::Sender<RallyEvent>,
+    pub playing: bool,
+    last_step: Instant,
+    /// 再生終了時に戻る画面
+    return_state: AppState,
+}
+
+impl RallyReplay {
+    pub fn position(&self) -> usize {
+        self.total - self.pending.len()
+    }
+
+    pub fn total(&self) -> usize {
+        self.total
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+impl App {
+    pub fn is_replaying_rally(&self) -> bool {
+        self.rally_replay.is_some()
+    }
+
+    /// トランスクリプトの再生を開始する（実行中の Rally がある場合は開始しない）
+    pub fn start_rally_replay(&mut self, transcript: Transcript) {
+        if self.ai_rally_state.is_some() {
+            self.submission_result = Some((
+                false,
+                "Close the current AI Rally before replaying a transcript".to_string(),
+            ));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+
+        let total = transcript.events.len();
+        let (tx, rx) = mpsc::channel(total.max(1));
+        self.rally_event_receiver = Some(rx);
+        let mut rally_state = AiRallyState {
+            iteration: 0,
+            max_iterations: self.config.ai.max_iterations,
+            state: RallyState::Initializing,
+            history: Vec::new(),
+            logs: Vec::new(),
+            log_scroll_offset: 0,
+            selected_log_index: None,
+            showing_log_detail: false,
+            pending_question: None,
+            pending_permission: None,
+            pending_review_post: None,
+            pending_fix_post: None,
+            last_visible_log_height: 10,
+            usage: RallyUsage::default(),
+        };
+        let source = if transcript.repo.is_empty() {
+            "transcript".to_string()
+        } else {
+            format!("{}#{}", transcript.repo, transcript.pr_number)
+        };
+        rally_state.push_log(LogEntry::new(
+            LogEventType::Info,
+            format!("Replaying {} ({} events)", source, total),
+        ));
+        self.ai_rally_state = Some(rally_state);
+        self.rally_replay = Some(RallyReplay {
+            pending: transcript.events.into(),
+            total,
+            sender: tx,
+            playing: true,
+            last_step: Instant::now(),
+            return_state: self.state,
+        });
+        self.state = AppState::AiRally;
+    }
+
+    /// 再生中なら一定間隔でイベントを 1 つずつ流す
+    pub(crate) fn poll_rally_replay(&mut self) {
+        let Some(ref replay) = self.rally_replay else {
+            return;
+        };
+        if replay.playing && replay.last_step.elapsed() >= REPLAY_STEP_INTERVAL {
+            self.step_rally_replay();
+        }
+    }
+
+    /// 次のイベントを 1 つ流す
+    pub(crate) fn step_rally_replay(&mut self) {
+        let Some(ref mut replay) = self.rally_replay else {
+            return;
+        };
+        replay.last_step = Instant::now();
+        match replay.pending.pop_front() {
+            Some(event) => {
+                // チャネル容量はイベント総数なので溢れない
+                let _ = replay.sender.try_send(event);
+            }
+            None => replay.playing = false,
+        }
+        if replay.is_finished() {
+            replay.playing = false;
+        }
+    }
+
+    pub(crate) fn toggle_rally_replay(&mut self) {
+        if let Some(ref mut replay) = self.rally_replay {
+            if !replay.is_finished() {
+                replay.playing = !replay.playing;
+                replay.last_step = Instant::now();
+            }
+        }
+    }
+
+    /// 再生を終了して元の画面に戻る
+    pub(crate) fn stop_rally_replay(&mut self) {
+        if let Some(replay) = self.rally_replay.take() {
+            self.ai_rally_state = None;
+            self.rally_event_receiver = None;
+            self.state = replay.return_state;
+        }
+    }
+
+    /// 現在の Rally のイベント履歴をトランスクリプトとして書き出す。
+    /// `markdown` なら再生用の JSON ではなく、PR に貼れる Markdown にする
+    pub(crate) fn export_rally_transcript(&mut self, markdown: bool) {
+        let Some(ref rally_state) = self.ai_rally_state else {
+            return;
+        };
+        let transcript = Transcript::new(
+            &self.repo,
+            self.pr_number.unwrap_or(0),
+            rally_state.history.clone(),
+        );
+        let result = if markdown {
+            transcript.export_markdown()
+        } else {
+            transcript.export()
+        };
+        let message = match result {
+            Ok(path) => LogEntry::new(
+                LogEventType::Info,
+                format!("Transcript saved: {}", path.display()),
+            ),
+            Err(e) => LogEntry::new(
+                LogEventType::Error,
+                format!("Failed to save transcript: {:#}", e),
+            ),
+        };
+        if let Some(ref mut rally_state) = self.ai_rally_state {
+            rally_state.push_log(message);
+        }
+    }
+}
@@ -0,0 +1,69 @@
+use std::time::Instant;
+
+use super::App;
+
+impl App {
+    fn set_rally_scope_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// ファイル一覧で選択中のファイルを AI Rally の対象にマーク/解除して次へ進む
+    pub(crate) fn toggle_rally_scope_mark(&mut self) {
+        let Some(filename) = self
+            .files()
+            .get(self.selected_file)
+            .map(|file| file.filename.clone())
+        else {
+            return;
+        };
+        if !self.rally_scope_marks.remove(&filename) {
+            self.rally_scope_marks.insert(filename);
+        }
+        if self.file_list_filter.is_some() {
+            self.handle_filter_navigation("file", true);
+        } else {
+            self.move_file_selection(1);
+        }
+    }
+
+    /// 範囲を絞った AI Rally の対象。マークしたファイル（PR のファイル順）、なければ選択中のファイル
+    pub(crate) fn rally_scope_files(&self) -> Vec<String> {
+        let files = self.files();
+        let marked: Vec<String> = files
+            .iter()
+            .filter(|file| self.rally_scope_marks.contains(&file.filename))
+            .map(|file| file.filename.clone())
+            .collect();
+        if !marked.is_empty() || self.is_filter_selection_empty("file") {
+            return marked;
+        }
+        files
+            .get(self.selected_file)
+            .map(|file| vec![file.filename.clone()])
+            .unwrap_or_default()
+    }
+
+    /// マークしたファイル（なければ選択中のファイル）だけを対象に AI Rally を始める。
+    /// 終わった Rally が残っていれば置き換える
+    pub(crate) fn start_scoped_ai_rally(&mut self) {
+        if self
+            .ai_rally_state
+            .as_ref()
+            .is_some_and(|state| state.state.is_active())
+        {
+            self.set_rally_scope_message(
+                false,
+                "An AI Rally is already running; quit it before starting another",
+            );
+            return;
+        }
+        let scope = self.rally_scope_files();
+        if scope.is_empty() {
+            return;
+        }
+        self.cleanup_rally_state();
+        self.rally_scope_marks.clear();
+        self.start_ai_rally_with_scope(scope);
+    }
+}
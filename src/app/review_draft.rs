@@ -0,0 +1,157 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::github::DraftReviewComment;
+use crate::ui::popup::PopupKey;
+
+use super::types::LineInputContext;
+use super::{App, AppState, PopupId};
+
+/// 送信前のレビュー下書き。PR ごとにインラインコメントを貯めておき、
+/// レビュー送信（approve / request changes / comment）でまとめて送る
+#[derive(Debug, Default)]
+pub struct PendingReview {
+    comments: HashMap<u32, Vec<DraftReviewComment>>,
+}
+
+impl PendingReview {
+    pub fn add(&mut self, pr_number: u32, comment: DraftReviewComment) {
+        self.comments.entry(pr_number).or_default().push(comment);
+    }
+
+    pub fn comments(&self, pr_number: u32) -> &[DraftReviewComment] {
+        self.comments.get(&pr_number).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn remove(&mut self, pr_number: u32, index: usize) -> Option<DraftReviewComment> {
+        let comments = self.comments.get_mut(&pr_number)?;
+        (index < comments.len()).then(|| comments.remove(index))
+    }
+
+    pub fn clear(&mut self, pr_number: u32) {
+        self.comments.remove(&pr_number);
+    }
+
+    /// すべての PR の下書きコメント数
+    pub fn total(&self) -> usize {
+        self.comments.values().map(Vec::len).sum()
+    }
+}
+
+impl App {
+    /// 現在の PR に貯まっている下書きコメント
+    pub fn review_drafts(&self) -> &[DraftReviewComment] {
+        match self.pr_number {
+            Some(pr_number) => self.pending_review.comments(pr_number),
+            None => &[],
+        }
+    }
+
+    /// 下書きモードを切り替える。有効な間はインラインコメントを即時に投稿せず下書きに貯める
+    pub(crate) fn toggle_review_draft_mode(&mut self) {
+        if self.local_mode {
+            self.set_review_draft_status(false, "Review drafts are not available in local mode");
+            return;
+        }
+        self.review_draft_mode = !self.review_draft_mode;
+        if self.review_draft_mode {
+            self.set_review_draft_status(
+                true,
+                "Draft mode on: comments are queued until you submit a review",
+            );
+        } else {
+            let message = match self.review_drafts().len() {
+                0 => "Draft mode off".to_string(),
+                n => format!("Draft mode off ({} pending comment(s) kept)", n),
+            };
+            self.set_review_draft_status(true, &message);
+        }
+    }
+
+    /// 入力したコメントを下書きに追加する
+    pub(crate) fn queue_review_draft(&mut self, ctx: LineInputContext, body: String) {
+        let Some(file) = self.files().get(ctx.file_index) else {
+            return;
+        };
+        let comment = DraftReviewComment {
+            path: file.filename.clone(),
+            body,
+            position: ctx.diff_position,
+            start_line: ctx.start_line_number,
+            line: ctx.line_number,
+        };
+        let pr_number = self.pr_number();
+        self.pending_review.add(pr_number, comment);
+        let count = self.review_drafts().len();
+        self.set_review_draft_status(true, &format!("Added to pending review ({})", count));
+    }
+
+    /// 下書き一覧を開く
+    pub(crate) fn open_review_drafts(&mut self) {
+        if self.review_drafts().is_empty() {
+            self.set_review_draft_status(false, "No pending review comments");
+            return;
+        }
+        self.review_drafts_open = true;
+        self.selected_review_draft = 0;
+        self.push_popup(PopupId::ReviewDrafts);
+    }
+
+    pub(crate) fn handle_review_drafts_input(&mut self, key: &KeyEvent) {
+        let kb = self.config.keybindings.clone();
+        let count = self.review_drafts().len();
+        let action = PopupKey::from_key(key, &kb);
+        if action == PopupKey::Close || self.matches_single_key(key, &kb.review_drafts) {
+            self.close_popup(PopupId::ReviewDrafts);
+        } else if let Some(selected) = action.navigate(self.selected_review_draft, count) {
+            self.selected_review_draft = selected;
+        } else if key.code == KeyCode::Char('d') {
+            let pr_number = self.pr_number();
+            self.pending_review
+                .remove(pr_number, self.selected_review_draft);
+            let remaining = self.review_drafts().len();
+            if remaining == 0 {
+                self.close_popup(PopupId::ReviewDrafts);
+            }
+            self.selected_review_draft =
+                self.selected_review_draft.min(remaining.saturating_sub(1));
+        } else if action == PopupKey::Confirm || self.matches_single_key(key, &kb.open_panel) {
+            self.close_popup(PopupId::ReviewDrafts);
+            self.jump_to_review_draft(self.selected_review_draft);
+        }
+    }
+
+    /// 下書きのコメント位置を diff ビューで開く
+    fn jump_to_review_draft(&mut self, index: usize) {
+        let Some(draft) = self.review_drafts().get(index) else {
+            return;
+        };
+        let line = draft.line;
+        let Some(file_index) = self.files().iter().position(|f| f.filename == draft.path) else {
+            return;
+        };
+        if self.state != AppState::DiffView {
+            self.diff_view_return_state = AppState::FileList;
+        }
+        self.selected_file = file_index;
+        self.state = AppState::DiffView;
+        self.selected_line = 0;
+        self.scroll_offset = 0;
+        self.update_diff_line_count();
+        self.update_file_comment_positions();
+        self.ensure_diff_cache();
+
+        let patch = self.files()[file_index].patch.clone().unwrap_or_default();
+        if let Some(line_idx) = Self::find_diff_line_index(&patch, line) {
+            self.selected_line = line_idx;
+            self.scroll_offset = line_idx;
+        }
+    }
+
+    fn set_review_draft_status(&mut self, ok: bool, message: &str) {
+        self.submission_result = Some((ok, message.to_string()));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
@@ -0,0 +1,190 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::progress_sync;
+use crate::review_progress::ReviewProgress;
+
+use super::types::InputMode;
+use super::{App, AppState};
+
+impl App {
+    fn set_review_progress_message(&mut self, success: bool, message: String) {
+        self.submission_result = Some((success, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 表示中の PR の head が変わったら進捗を読み直す（ローカルモードでは持たない）
+    pub(crate) fn sync_review_progress(&mut self) {
+        let current = match (self.local_mode, self.pr()) {
+            (false, Some(pr)) if pr.number != 0 => {
+                Some((self.repo.as_str(), pr.number, pr.head.sha.as_str()))
+            }
+            _ => None,
+        };
+        let loaded = self
+            .review_progress_key
+            .as_ref()
+            .map(|(repo, number, sha)| (repo.as_str(), *number, sha.as_str()));
+        // キーなしで持っている進捗（ローカルモードへの切り替え前など）も捨てる
+        if current == loaded && (current.is_some() || self.review_progress.is_none()) {
+            return;
+        }
+        let key = current.map(|(repo, number, sha)| (repo.to_string(), number, sha.to_string()));
+        self.review_progress = key.as_ref().and_then(|(repo, number, sha)| {
+            ReviewProgress::load(repo, *number, sha)
+                .map_err(|e| tracing::warn!(error = %e, "failed to load review progress"))
+                .ok()
+        });
+        self.review_progress_key = key;
+        self.start_progress_sync();
+    }
+
+    /// `[sync]` の同期先を用意する
+    pub fn enable_progress_sync(&mut self) {
+        self.progress_sync =
+            progress_sync::backend_from_config(&self.config.sync, self.working_dir.as_deref());
+    }
+
+    /// 表示中の進捗を同期先とまとめる。同期中なら終わった後にもう一度行う
+    fn start_progress_sync(&mut self) {
+        let Some(backend) = self.progress_sync.clone() else {
+            return;
+        };
+        if self.progress_sync_receiver.is_some() {
+            self.progress_sync_pending = true;
+            return;
+        }
+        let Some((key, local)) = self
+            .review_progress
+            .as_ref()
+            .and_then(|progress| Some((progress.sync_key()?, progress.clone())))
+        else {
+            return;
+        };
+        let (tx, rx) = mpsc::channel(1);
+        self.progress_sync_receiver = Some(rx);
+        tokio::spawn(async move {
+            let result = progress_sync::sync_progress(backend.as_ref(), &key, &local).await;
+            let _ = tx.send((key, result.map_err(|e| e.to_string()))).await;
+        });
+    }
+
+    /// 同期の結果を表示中の進捗に取り込んで保存する
+    pub(crate) fn poll_progress_sync(&mut self) {
+        let Some(ref mut rx) = self.progress_sync_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok((key, result)) => {
+                self.progress_sync_receiver = None;
+                match result {
+                    Ok(synced) => self.apply_synced_progress(&key, &synced),
+                    Err(e) => tracing::warn!(error = %e, "failed to sync review progress"),
+                }
+                if std::mem::take(&mut self.progress_sync_pending) {
+                    self.start_progress_sync();
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.progress_sync_receiver = None;
+            }
+        }
+    }
+
+    fn apply_synced_progress(&mut self, key: &str, synced: &ReviewProgress) {
+        let Some(ref mut progress) = self.review_progress else {
+            return;
+        };
+        // 同期中に別の PR や head に移っていたら捨てる
+        if progress.sync_key().as_deref() != Some(key) || !progress.merge(synced) {
+            return;
+        }
+        if let Err(e) = progress.save() {
+            tracing::warn!(error = %e, "failed to save synced review progress");
+        }
+        self.set_review_progress_message(true, "Review progress synced".to_string());
+    }
+
+    pub(crate) fn review_progress(&self) -> Option<&ReviewProgress> {
+        self.review_progress.as_ref()
+    }
+
+    /// ヘッダーに出す (レビュー済みのファイル数, ファイル数)
+    pub(crate) fn review_progress_counts(&self) -> Option<(usize, usize)> {
+        let progress = self.review_progress.as_ref()?;
+        let files = self.files();
+        Some((progress.reviewed_count(files), files.len()))
+    }
+
+    /// m: 選択中のファイルに見終えた印を付ける・外す
+    pub(crate) fn toggle_file_reviewed(&mut self) {
+        let Some(filename) = self
+            .files()
+            .get(self.selected_file)
+            .map(|file| file.filename.clone())
+        else {
+            return;
+        };
+        let Some(ref mut progress) = self.review_progress else {
+            self.set_review_progress_message(
+                false,
+                "Review progress is only tracked for PRs".to_string(),
+            );
+            return;
+        };
+        match progress.toggle_reviewed(&filename) {
+            Ok(reviewed) => {
+                let verb = if reviewed { "Reviewed" } else { "Unmarked" };
+                self.set_review_progress_message(true, format!("{} {}", verb, filename));
+                self.start_progress_sync();
+            }
+            Err(e) => self.set_review_progress_message(
+                false,
+                format!("Failed to save review progress: {}", e),
+            ),
+        }
+    }
+
+    /// gn: 選択中のファイルのメモを入力画面で編集する
+    pub(crate) fn open_file_note(&mut self) {
+        let Some(filename) = self
+            .files()
+            .get(self.selected_file)
+            .map(|file| file.filename.clone())
+        else {
+            return;
+        };
+        let Some(ref progress) = self.review_progress else {
+            self.set_review_progress_message(false, "Notes are only available for PRs".to_string());
+            return;
+        };
+        let note = progress.note(&filename).unwrap_or_default().to_string();
+        self.input_text_area.set_content(&note);
+        self.input_mode = Some(InputMode::FileNote { filename });
+        self.preview_return_state = self.state;
+        self.state = AppState::TextInput;
+    }
+
+    /// メモを保存する。空で保存するとメモを消す
+    pub(crate) fn save_file_note(&mut self, filename: &str, note: &str) {
+        let Some(ref mut progress) = self.review_progress else {
+            return;
+        };
+        let message = if note.trim().is_empty() {
+            "Note removed"
+        } else {
+            "Note saved"
+        };
+        match progress.set_note(filename, note) {
+            Ok(()) => {
+                self.set_review_progress_message(true, message.to_string());
+                self.start_progress_sync();
+            }
+            Err(e) => {
+                self.set_review_progress_message(false, format!("Failed to save note: {}", e))
+            }
+        }
+    }
+}
@@ -0,0 +1,99 @@
+use std::time::Instant;
+
+use anyhow::Result;
+
+use crate::github::ReviewAction;
+use crate::ui::TuiTerminal;
+
+use super::App;
+
+impl App {
+    fn set_review_queue_message(&mut self, success: bool, message: String) {
+        self.submission_result = Some((success, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// PR 一覧から PR を開くとき、一覧の並び（フィルタ適用中は絞り込んだ PR だけ）をキューにする
+    pub(crate) fn set_review_queue_from_pr_list(&mut self) {
+        let Some(ref prs) = self.pr_list else {
+            self.review_queue.clear();
+            return;
+        };
+        self.review_queue = match self.pr_list_filter {
+            Some(ref filter) => filter
+                .matched_indices
+                .iter()
+                .filter_map(|&index| prs.get(index))
+                .map(|pr| pr.number)
+                .collect(),
+            None => prs.iter().map(|pr| pr.number).collect(),
+        };
+    }
+
+    /// キューの中での表示中の PR の位置（1 始まり）とキューの長さ
+    pub(crate) fn review_queue_position(&self) -> Option<(usize, usize)> {
+        if self.local_mode || !self.started_from_pr_list {
+            return None;
+        }
+        let pr_number = self.pr_number?;
+        let index = self.review_queue.iter().position(|&n| n == pr_number)?;
+        Some((index + 1, self.review_queue.len()))
+    }
+
+    /// 承認を送信してキューの次の PR を開く。本文が空なら承認の確認の後に、
+    /// 未解決のスレッドなどがあればレビュー前のまとめで承認した後に進む
+    pub(crate) async fn approve_and_open_next(
+        &mut self,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        if self.review_queue_position().is_none() {
+            self.set_review_queue_message(
+                false,
+                "Open a PR from the PR list to review the queue".to_string(),
+            );
+            return Ok(());
+        }
+        self.approve_then_next = true;
+        self.start_review(ReviewAction::Approve, terminal).await?;
+        if self.pending_approve_body.is_none() && self.review_summary.is_none() {
+            // 送信に失敗したかキャンセルしたときは次へ進まない
+            self.approve_then_next = false;
+        }
+        Ok(())
+    }
+
+    /// 承認の送信に成功したときに呼ぶ。approve & next の途中ならキューの次の PR を開く
+    pub(crate) fn advance_review_queue_after_approve(&mut self) {
+        if !std::mem::take(&mut self.approve_then_next) {
+            return;
+        }
+        let (Some((position, total)), Some(approved)) =
+            (self.review_queue_position(), self.pr_number)
+        else {
+            return;
+        };
+        // position は 1 始まりなので、次の PR の添字と同じ
+        match self.review_queue.get(position).copied() {
+            Some(next) => {
+                self.open_pr_tab(next, false);
+                self.set_review_queue_message(
+                    true,
+                    format!(
+                        "Approved #{}, opened #{} ({}/{})",
+                        approved,
+                        next,
+                        position + 1,
+                        total
+                    ),
+                );
+            }
+            None => {
+                self.back_to_pr_list();
+                self.set_review_queue_message(
+                    true,
+                    format!("Approved #{}, review queue finished", approved),
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,309 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::anonymize::Anonymizer;
+use crate::audit::{self, AuditAction, AuditEntry};
+use crate::github::{self, PrPeople};
+use crate::ui::picker::{Picker, PickerAction, PickerItem};
+
+use super::{App, PopupId};
+
+/// ピッカーで変更する対象
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeopleTarget {
+    Reviewers,
+    Assignees,
+}
+
+impl PeopleTarget {
+    pub fn title(self) -> &'static str {
+        match self {
+            Self::Reviewers => "Request review",
+            Self::Assignees => "Assign",
+        }
+    }
+
+    pub fn other(self) -> Self {
+        match self {
+            Self::Reviewers => Self::Assignees,
+            Self::Assignees => Self::Reviewers,
+        }
+    }
+}
+
+/// 候補の読み込み結果（PR の現状とアサインできるユーザー）
+pub(crate) type PeopleLoadResult = Result<(PrPeople, Vec<String>), String>;
+
+/// レビュー依頼・アサインの変更結果（対象、ユーザー、追加したか、結果）
+pub(crate) type PeopleUpdateResult = (PeopleTarget, String, bool, Result<(), String>);
+
+/// レビュアー・アサイニーのピッカー
+#[derive(Debug, Clone)]
+pub struct PeoplePickerState {
+    pub target: PeopleTarget,
+    /// 読み込み中は None
+    pub people: Option<PrPeople>,
+    /// アサインできるユーザー（提案されたレビュアー以外の候補）
+    pub assignable: Vec<String>,
+    pub error: Option<String>,
+    /// 変更中のユーザー
+    pub pending: Option<String>,
+    /// ピッカーの候補と同じ順のログイン名（候補の表示は匿名化されることがある）
+    logins: Vec<String>,
+    pub picker: Picker,
+}
+
+impl PeoplePickerState {
+    /// 現在の対象の候補のログイン名。レビュアーは提案された人を先頭にし、PR の作成者は除く
+    fn candidates(&self, author: Option<&str>) -> Vec<String> {
+        let Some(ref people) = self.people else {
+            return Vec::new();
+        };
+        let (current, suggested) = self.current_and_suggested(people);
+        let mut logins: Vec<String> = Vec::new();
+        for login in suggested.iter().chain(current).chain(&self.assignable) {
+            if !logins.contains(login)
+                && (self.target == PeopleTarget::Assignees || Some(login.as_str()) != author)
+            {
+                logins.push(login.clone());
+            }
+        }
+        logins
+    }
+
+    /// (依頼中・アサイン済みのユーザー, 提案されたユーザー)
+    fn current_and_suggested<'a>(&self, people: &'a PrPeople) -> (&'a [String], &'a [String]) {
+        match self.target {
+            PeopleTarget::Reviewers => (&people.requested_reviewers, &people.suggested_reviewers),
+            PeopleTarget::Assignees => (&people.assignees, &[]),
+        }
+    }
+
+    /// 候補を作り直す
+    fn refresh(&mut self, author: Option<&str>, anonymizer: &Anonymizer) {
+        self.logins = self.candidates(author);
+        let items = match self.people {
+            Some(ref people) => {
+                let (current, suggested) = self.current_and_suggested(people);
+                self.logins
+                    .iter()
+                    .map(|login| PickerItem {
+                        label: anonymizer.user(login).into_owned(),
+                        detail: if self.pending.as_ref() == Some(login) {
+                            Some("updating...".to_string())
+                        } else if suggested.contains(login) {
+                            Some("suggested".to_string())
+                        } else {
+                            None
+                        },
+                        checked: current.contains(login),
+                    })
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+        self.picker.set_items(items);
+    }
+}
+
+impl App {
+    fn set_people_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// レビュー依頼・アサインのピッカーを開き、候補を読み込む
+    pub(crate) fn open_people_picker(&mut self) {
+        let Some(pr_number) = self.pr_number.filter(|_| !self.local_mode) else {
+            self.set_people_message(false, "Reviewers can only be requested on PRs");
+            return;
+        };
+        self.people_picker = Some(PeoplePickerState {
+            target: PeopleTarget::Reviewers,
+            people: None,
+            assignable: Vec::new(),
+            error: None,
+            pending: None,
+            logins: Vec::new(),
+            picker: Picker::default(),
+        });
+        self.push_popup(PopupId::People);
+        let (tx, rx) = mpsc::channel(1);
+        self.people_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let (people, assignable) = tokio::join!(
+                github::fetch_pr_people(&repo, pr_number),
+                github::fetch_assignable_users(&repo)
+            );
+            let result = match (people, assignable) {
+                (Ok(people), Ok(assignable)) => Ok((people, assignable)),
+                (Err(e), _) | (_, Err(e)) => Err(format!("{:#}", e)),
+            };
+            let _ = tx.send(result).await;
+        });
+    }
+
+    /// ピッカーの候補を現在の状態から作り直す
+    fn refresh_people_items(&mut self) {
+        let author = self.pr().map(|pr| pr.user.login.clone());
+        if let Some(ref mut state) = self.people_picker {
+            state.refresh(author.as_deref(), &self.anonymizer);
+        }
+    }
+
+    pub(crate) fn poll_people_updates(&mut self) {
+        if let Some((origin_pr, ref mut rx)) = self.people_receiver {
+            match rx.try_recv() {
+                Ok(result) => {
+                    self.people_receiver = None;
+                    if self.pr_number == Some(origin_pr) {
+                        self.apply_people_load(result);
+                    }
+                }
+                Err(mpsc::error::TryRecvError::Empty) => {}
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    self.people_receiver = None;
+                }
+            }
+        }
+
+        let Some((origin_pr, ref mut rx)) = self.people_update_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(update) => {
+                self.people_update_receiver = None;
+                if self.pr_number == Some(origin_pr) {
+                    self.apply_people_update(update);
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.people_update_receiver = None;
+                if let Some(ref mut state) = self.people_picker {
+                    state.pending = None;
+                }
+            }
+        }
+    }
+
+    fn apply_people_load(&mut self, result: PeopleLoadResult) {
+        let Some(ref mut state) = self.people_picker else {
+            return;
+        };
+        match result {
+            Ok((people, assignable)) => {
+                state.people = Some(people);
+                state.assignable = assignable;
+            }
+            Err(e) => state.error = Some(e),
+        }
+        self.refresh_people_items();
+    }
+
+    fn apply_people_update(&mut self, (target, login, added, result): PeopleUpdateResult) {
+        if let Some(ref mut state) = self.people_picker {
+            state.pending = None;
+            if let (Ok(()), Some(people)) = (&result, state.people.as_mut()) {
+                let list = match target {
+                    PeopleTarget::Reviewers => &mut people.requested_reviewers,
+                    PeopleTarget::Assignees => &mut people.assignees,
+                };
+                list.retain(|l| *l != login);
+                if added {
+                    list.push(login.clone());
+                }
+            }
+        }
+        self.refresh_people_items();
+
+        let login = self.anonymizer.user(&login).into_owned();
+        let message = match (target, added) {
+            (PeopleTarget::Reviewers, true) => format!("Requested review from {}", login),
+            (PeopleTarget::Reviewers, false) => format!("Removed review request for {}", login),
+            (PeopleTarget::Assignees, true) => format!("Assigned {}", login),
+            (PeopleTarget::Assignees, false) => format!("Unassigned {}", login),
+        };
+        match result {
+            Ok(()) => self.set_people_message(true, message),
+            Err(e) => self.set_people_message(false, format!("Failed: {}", e)),
+        }
+    }
+
+    /// 選んだユーザーのレビュー依頼・アサインを切り替える
+    fn toggle_person(&mut self, index: usize) {
+        let Some(pr_number) = self.pr_number else {
+            return;
+        };
+        if self.people_update_receiver.is_some() {
+            return;
+        }
+        let Some(ref mut state) = self.people_picker else {
+            return;
+        };
+        let (Some(item), Some(login)) = (state.picker.items().get(index), state.logins.get(index))
+        else {
+            return;
+        };
+        let login = login.clone();
+        let add = !item.checked;
+        let target = state.target;
+        state.pending = Some(login.clone());
+        self.refresh_people_items();
+
+        let (tx, rx) = mpsc::channel(1);
+        self.people_update_receiver = Some((pr_number, rx));
+        let repo = self.repo.clone();
+        tokio::spawn(async move {
+            let (result, action) = match (target, add) {
+                (PeopleTarget::Reviewers, true) => (
+                    github::request_reviewer(&repo, pr_number, &login).await,
+                    AuditAction::RequestReviewer,
+                ),
+                (PeopleTarget::Reviewers, false) => (
+                    github::remove_requested_reviewer(&repo, pr_number, &login).await,
+                    AuditAction::RemoveReviewer,
+                ),
+                (PeopleTarget::Assignees, true) => (
+                    github::add_assignee(&repo, pr_number, &login).await,
+                    AuditAction::Assign,
+                ),
+                (PeopleTarget::Assignees, false) => (
+                    github::remove_assignee(&repo, pr_number, &login).await,
+                    AuditAction::Unassign,
+                ),
+            };
+            audit::record(&AuditEntry::new(
+                &repo,
+                pr_number,
+                action,
+                Some(login.clone()),
+                &result,
+            ));
+            let _ = tx
+                .send((target, login, add, result.map_err(|e| format!("{:#}", e))))
+                .await;
+        });
+    }
+
+    pub(crate) fn handle_people_picker_input(&mut self, key: &KeyEvent) {
+        let Some(ref mut state) = self.people_picker else {
+            return;
+        };
+        // Tab でレビュアーとアサイニーを切り替える
+        if key.code == KeyCode::Tab {
+            state.target = state.target.other();
+            state.picker = Picker::default();
+            self.refresh_people_items();
+            return;
+        }
+        match state.picker.handle_key(key) {
+            PickerAction::Continue => {}
+            PickerAction::Close => self.close_popup(PopupId::People),
+            PickerAction::Select(index) => self.toggle_person(index),
+        }
+    }
+}
@@ -0,0 +1,176 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+use crate::diff_search::{self, SearchHit, SearchQuery};
+
+use super::App;
+
+/// diff 内検索
+#[derive(Debug, Clone, Default)]
+pub struct DiffSearchState {
+    /// 入力中のクエリ
+    pub input: String,
+    /// クエリを入力中か
+    pub editing: bool,
+    /// 全ファイルを対象にするか（入力中に Tab で切り替え）
+    pub all_files: bool,
+    /// 確定したクエリ
+    pub query: Option<SearchQuery>,
+    /// 最後に移動した一致の (位置, 件数)
+    pub position: Option<(usize, usize)>,
+}
+
+impl DiffSearchState {
+    /// 一致箇所のハイライトに使うクエリ。入力中は入力中の文字列で絞り込む
+    pub fn highlight_query(&self) -> Option<SearchQuery> {
+        if self.editing {
+            SearchQuery::new(&self.input)
+        } else {
+            self.query.clone()
+        }
+    }
+}
+
+impl App {
+    fn set_search_message(&mut self, success: bool, message: String) {
+        self.submission_result = Some((success, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// `/`: クエリの入力を始める。確定するまでは前回の検索結果を残す
+    pub(crate) fn start_diff_search(&mut self) {
+        let search = self
+            .diff_search
+            .get_or_insert_with(DiffSearchState::default);
+        search.input.clear();
+        search.editing = true;
+    }
+
+    /// 検索をやめてハイライトを消す
+    pub(crate) fn clear_diff_search(&mut self) {
+        self.diff_search = None;
+    }
+
+    /// 検索が確定していて n/N で移動できるか
+    pub(crate) fn has_diff_search(&self) -> bool {
+        self.diff_search
+            .as_ref()
+            .is_some_and(|search| !search.editing && search.query.is_some())
+    }
+
+    /// クエリ入力中のキー入力。入力中でなければ false
+    pub(crate) fn handle_diff_search_input(
+        &mut self,
+        key: &KeyEvent,
+        visible_lines: usize,
+    ) -> bool {
+        let Some(ref mut search) = self.diff_search else {
+            return false;
+        };
+        if !search.editing {
+            return false;
+        }
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        match key.code {
+            KeyCode::Esc => self.cancel_diff_search_input(),
+            KeyCode::Char('c') if ctrl => self.cancel_diff_search_input(),
+            KeyCode::Enter => self.confirm_diff_search(visible_lines),
+            KeyCode::Tab => search.all_files = !search.all_files,
+            KeyCode::Backspace => {
+                search.input.pop();
+            }
+            KeyCode::Char('u') if ctrl => search.input.clear(),
+            KeyCode::Char(c) if !ctrl => search.input.push(c),
+            _ => {}
+        }
+        true
+    }
+
+    /// 入力を取り消す。前回の検索があればそれに戻す
+    fn cancel_diff_search_input(&mut self) {
+        match self.diff_search {
+            Some(ref mut search) if search.query.is_some() => search.editing = false,
+            _ => self.diff_search = None,
+        }
+    }
+
+    fn confirm_diff_search(&mut self, visible_lines: usize) {
+        let Some(ref mut search) = self.diff_search else {
+            return;
+        };
+        search.editing = false;
+        match SearchQuery::new(&search.input) {
+            Some(query) => {
+                search.query = Some(query);
+                search.position = None;
+                self.jump_to_search_match(true, visible_lines);
+            }
+            None => self.diff_search = None,
+        }
+    }
+
+    /// 検索対象のファイルでクエリに一致する行（読み込み済みの diff のみ）
+    fn diff_search_hits(&self, query: &SearchQuery, all_files: bool) -> Vec<SearchHit> {
+        self.files()
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| all_files || *index == self.selected_file)
+            .filter_map(|(index, file)| file.patch.as_deref().map(|patch| (index, patch)))
+            .flat_map(|(file_index, patch)| {
+                query
+                    .matching_lines(patch)
+                    .into_iter()
+                    .map(move |line| SearchHit { file_index, line })
+            })
+            .collect()
+    }
+
+    /// n/N: 次（前）の一致へ移動する。全ファイル検索なら別のファイルの diff も開く
+    pub(crate) fn jump_to_search_match(&mut self, forward: bool, visible_lines: usize) {
+        let Some((query, all_files)) = self
+            .diff_search
+            .as_ref()
+            .and_then(|search| Some((search.query.clone()?, search.all_files)))
+        else {
+            return;
+        };
+        let hits = self.diff_search_hits(&query, all_files);
+        let current = SearchHit {
+            file_index: self.selected_file,
+            line: self.selected_line,
+        };
+        let Some((index, wrapped)) = diff_search::step(&hits, current, forward) else {
+            if let Some(ref mut search) = self.diff_search {
+                search.position = None;
+            }
+            self.set_search_message(false, format!("Pattern not found: {}", query.text()));
+            return;
+        };
+        let hit = hits[index];
+        if hit.file_index != self.selected_file {
+            // ファイル一覧のフィルタで対象が隠れている場合はフィルタを解除
+            if let Some(ref mut filter) = self.file_list_filter {
+                match filter
+                    .matched_indices
+                    .iter()
+                    .position(|&i| i == hit.file_index)
+                {
+                    Some(pos) => filter.selected = Some(pos),
+                    None => self.file_list_filter = None,
+                }
+            }
+            self.selected_file = hit.file_index;
+            self.sync_diff_to_selected_file();
+        }
+        self.selected_line = hit.line;
+        self.adjust_scroll(visible_lines);
+        if let Some(ref mut search) = self.diff_search {
+            search.position = Some((index, hits.len()));
+        }
+        if wrapped {
+            let edge = if forward { "BOTTOM" } else { "TOP" };
+            self.set_search_message(true, format!("Search hit {}, continuing", edge));
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::suggestion::{self, Applied, Suggestion};
+
+use super::App;
+
+/// 作業ツリーへの適用を確認中の suggestion
+#[derive(Debug, Clone)]
+pub(crate) struct PendingSuggestionApply {
+    suggestion: Suggestion,
+    local_path: PathBuf,
+    /// 確認を求めた時点のファイルの内容（書き込む前に変わっていないか確かめる）
+    before: String,
+    after: String,
+    /// 作業ツリーで置き換える開始行（ローカルの変更でずれていれば PR の行と異なる）
+    local_start_line: u32,
+}
+
+impl App {
+    fn set_suggestion_apply_status(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// a: 選択中のコメントの suggestion を作業ツリーのファイルに適用する前に確認する
+    pub(crate) async fn request_suggestion_apply(&mut self) {
+        if self.local_mode {
+            self.set_suggestion_apply_status(false, "Suggestions can only be applied from a PR");
+            return;
+        }
+        let Some(comment) = self
+            .review_comments
+            .as_ref()
+            .and_then(|c| c.get(self.selected_comment))
+            .cloned()
+        else {
+            return;
+        };
+        let patch = self
+            .files()
+            .iter()
+            .find(|f| f.filename == comment.path)
+            .and_then(|f| f.patch.clone())
+            .unwrap_or_default();
+        let suggestion = match Suggestion::from_comment(&comment, &patch) {
+            Ok(suggestion) => suggestion,
+            Err(e) => return self.set_suggestion_apply_status(false, e),
+        };
+        let (local_path, before) =
+            match suggestion::read_local(self.working_dir.as_deref(), &suggestion.path).await {
+                Ok(local) => local,
+                Err(e) => return self.set_suggestion_apply_status(false, format!("{:#}", e)),
+            };
+
+        match suggestion::apply(&before, &suggestion) {
+            Ok(Applied::Changed {
+                content,
+                start_line,
+            }) => {
+                self.pending_suggestion_apply = Some(PendingSuggestionApply {
+                    suggestion,
+                    local_path,
+                    before,
+                    after: content,
+                    local_start_line: start_line,
+                });
+            }
+            Ok(Applied::AlreadyApplied) => {
+                self.set_suggestion_apply_status(true, "Suggestion is already applied locally")
+            }
+            Err(e) => self.set_suggestion_apply_status(false, e),
+        }
+    }
+
+    /// suggestion の適用の確認中か
+    pub fn is_pending_suggestion_apply(&self) -> bool {
+        self.pending_suggestion_apply.is_some()
+    }
+
+    /// 適用の確認のフッター
+    pub fn suggestion_apply_prompt(&self) -> Option<String> {
+        let pending = self.pending_suggestion_apply.as_ref()?;
+        let moved = if pending.local_start_line != pending.suggestion.start_line {
+            format!(" (line {} locally)", pending.local_start_line)
+        } else {
+            String::new()
+        };
+        Some(format!(
+            "Apply suggestion to {}{}? y: apply | any other key: cancel",
+            pending.suggestion.location(),
+            moved
+        ))
+    }
+
+    /// 適用の確認中のキー入力。y で書き込み、それ以外のキーで取り消す
+    pub(crate) fn confirm_suggestion_apply(&mut self, key: &KeyEvent) {
+        let Some(pending) = self.pending_suggestion_apply.take() else {
+            return;
+        };
+        if key.code != KeyCode::Char('y') {
+            self.set_suggestion_apply_status(false, "Apply cancelled");
+            return;
+        }
+        match suggestion::write_local(&pending.local_path, &pending.before, &pending.after) {
+            Ok(()) => self.set_suggestion_apply_status(
+                true,
+                format!("Applied suggestion to {}", pending.suggestion.location()),
+            ),
+            Err(e) => self
+                .set_suggestion_apply_status(false, format!("Failed to apply suggestion: {:#}", e)),
+        }
+    }
+}
@@ -1,10 +1,12 @@
 use anyhow::Result;
 use crossterm::event::{self, KeyCode, KeyModifiers};
-use ratatui::{backend::CrosstermBackend, Terminal};
-use std::io::Stdout;
+
+use crate::filter::ListFilter;
+use crate::ui::popup::PopupKey;
+use crate::ui::TuiTerminal;
 
 use super::types::*;
-use super::App;
+use super::{App, PopupId};
 
 impl App {
     pub(crate) fn push_jump_location(&mut self) {
@@ -12,6 +14,7 @@ impl App {
             file_index: self.selected_file,
             line_index: self.selected_line,
             scroll_offset: self.scroll_offset,
+            commit_sha: self.commit_view.as_ref().map(|v| v.commit.sha.clone()),
         };
         self.jump_stack.push(loc);
         // 上限 100 件
@@ -26,7 +29,18 @@ impl App {
             return;
         };
 
-        let file_changed = self.selected_file != loc.file_index;
+        // 行の履歴から開いたコミットの diff を表示中なら、記録したときの PR 全体の diff に戻す。
+        // 表示していない別のコミットの diff の位置は戻せないので捨てる
+        let current_sha = self.commit_view.as_ref().map(|v| v.commit.sha.as_str());
+        if loc.commit_sha.is_some() && loc.commit_sha.as_deref() != current_sha {
+            return;
+        }
+        let left_commit = loc.commit_sha.is_none() && current_sha.is_some();
+        if left_commit {
+            self.exit_commit_view();
+        }
+
+        let file_changed = left_commit || self.selected_file != loc.file_index;
         self.selected_file = loc.file_index;
         self.selected_line = loc.line_index;
         self.scroll_offset = loc.scroll_offset;
@@ -39,10 +53,7 @@ impl App {
     }
 
     /// シンボル選択ポップアップを開く
-    pub(crate) async fn open_symbol_popup(
-        &mut self,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
-    ) -> Result<()> {
+    pub(crate) async fn open_symbol_popup(&mut self, terminal: &mut dyn TuiTerminal) -> Result<()> {
         let file = match self.files().get(self.selected_file) {
             Some(f) => f,
             None => return Ok(()),
@@ -73,6 +84,7 @@ impl App {
             symbols,
             selected: 0,
         });
+        self.push_popup(PopupId::Symbol);
         Ok(())
     }
 
@@ -80,29 +92,26 @@ impl App {
     pub(crate) async fn handle_symbol_popup_input(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
+        let action = PopupKey::from_key(&key, &self.config.keybindings);
         let popup = match self.symbol_popup.as_mut() {
             Some(p) => p,
             None => return Ok(()),
         };
+        if let Some(selected) = action.navigate(popup.selected, popup.symbols.len()) {
+            popup.selected = selected;
+            return Ok(());
+        }
 
-        match key.code {
-            KeyCode::Char('j') | KeyCode::Down => {
-                popup.selected = (popup.selected + 1).min(popup.symbols.len().saturating_sub(1));
-            }
-            KeyCode::Char('k') | KeyCode::Up => {
-                popup.selected = popup.selected.saturating_sub(1);
-            }
-            KeyCode::Enter => {
+        match action {
+            PopupKey::Confirm => {
                 let symbol_name = popup.symbols[popup.selected].0.clone();
-                self.symbol_popup = None;
+                self.close_popup(PopupId::Symbol);
                 self.jump_to_symbol_definition_async(&symbol_name, terminal)
                     .await?;
             }
-            KeyCode::Esc | KeyCode::Char('q') => {
-                self.symbol_popup = None;
-            }
+            PopupKey::Close => self.close_popup(PopupId::Symbol),
             _ => {}
         }
         Ok(())
@@ -112,7 +121,7 @@ impl App {
     pub(crate) async fn jump_to_symbol_definition_async(
         &mut self,
         symbol: &str,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         // Phase 1: diff パッチ内を検索
         let files: Vec<crate::github::ChangedFile> = self.files().to_vec();
@@ -158,13 +167,13 @@ impl App {
             let path_str = full_path.to_string_lossy().to_string();
 
             // ターミナルを一時停止して外部エディタを開く
-            crate::ui::restore_terminal(terminal)?;
+            terminal.suspend()?;
             let _ = crate::editor::open_file_at_line(
                 self.config.editor.as_deref(),
                 &path_str,
                 line_number,
             );
-            *terminal = crate::ui::setup_terminal()?;
+            terminal.resume()?;
         }
 
         Ok(())
@@ -173,7 +182,7 @@ impl App {
     /// 現在のファイルを外部エディタで開く（gf キー）
     pub(crate) async fn open_current_file_in_editor(
         &mut self,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         let file = match self.files().get(self.selected_file) {
             Some(f) => f.clone(),
@@ -209,13 +218,13 @@ impl App {
         };
 
         // TUI 一時停止 → エディタ → TUI 復帰
-        crate::ui::restore_terminal(terminal)?;
+        terminal.suspend()?;
         let _ = crate::editor::open_file_at_line(
             self.config.editor.as_deref(),
             &full_path,
             line_number.unwrap_or(1) as usize,
         );
-        *terminal = crate::ui::setup_terminal()?;
+        terminal.resume()?;
 
         Ok(())
     }
@@ -223,7 +232,7 @@ impl App {
     pub(crate) fn handle_help_input(
         &mut self,
         key: event::KeyEvent,
-        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        terminal: &mut dyn TuiTerminal,
     ) -> Result<()> {
         let terminal_height = terminal.size()?.height;
         self.apply_help_scroll(key, terminal_height);
@@ -234,6 +243,10 @@ impl App {
     pub(crate) const HELP_VIEWPORT_OVERHEAD: u16 = 6;
 
     pub(crate) fn apply_help_scroll(&mut self, key: event::KeyEvent, terminal_height: u16) {
+        if self.apply_help_search(&key) {
+            return;
+        }
+
         // Tab switching ([ / ])
         if matches!(key.code, KeyCode::Char('[') | KeyCode::Char(']')) {
             self.help_tab = match self.help_tab {
@@ -259,6 +272,13 @@ impl App {
         {
             self.state = self.previous_state;
             return;
+        } else if self.help_tab == HelpTab::Keybindings && self.matches_single_key(&key, &kb.search)
+        {
+            // Search the keybindings (edit the current query if one is applied)
+            self.help_filter
+                .get_or_insert_with(ListFilter::new)
+                .input_active = true;
+            offset = 0;
         } else if Self::is_shift_char_shortcut(&key, 'j') {
             // Page down (J / Shift+j)
             offset = offset.saturating_add(visible_lines.max(1));
@@ -287,4 +307,46 @@ impl App {
             HelpTab::Config => self.config_scroll_offset = offset,
         };
     }
+
+    /// Keybindings search input. Returns true if the key was consumed.
+    ///
+    /// While typing, every key edits the query; once applied, Esc clears it
+    /// instead of closing the help.
+    fn apply_help_search(&mut self, key: &event::KeyEvent) -> bool {
+        if self.help_tab != HelpTab::Keybindings {
+            return false;
+        }
+        let Some(filter) = self.help_filter.as_mut() else {
+            return false;
+        };
+        if !filter.input_active {
+            if key.code != KeyCode::Esc {
+                return false;
+            }
+            self.help_filter = None;
+            self.help_scroll_offset = 0;
+            return true;
+        }
+
+        match key.code {
+            KeyCode::Esc => self.help_filter = None,
+            KeyCode::Enter => {
+                if filter.has_query() {
+                    filter.input_active = false;
+                } else {
+                    self.help_filter = None;
+                }
+            }
+            KeyCode::Backspace => filter.delete_char(),
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                filter.clear_query()
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                filter.insert_char(c)
+            }
+            _ => return true,
+        }
+        self.help_scroll_offset = 0;
+        true
+    }
 }
@@ -0,0 +1,137 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use crate::github;
+use crate::ui::markdown;
+
+use super::types::*;
+use super::App;
+
+impl App {
+    /// 詳細表示中の Discussion コメントのタスク項目数
+    pub(crate) fn discussion_task_count(&self) -> usize {
+        self.discussion_comments
+            .as_ref()
+            .and_then(|comments| comments.get(self.selected_discussion_comment))
+            .map_or(0, |comment| markdown::task_count(&comment.body))
+    }
+
+    /// 選択中のタスク項目を次（`forward = false` なら前）へ移す
+    pub(crate) fn move_discussion_task_cursor(&mut self, forward: bool) {
+        let count = self.discussion_task_count();
+        if count == 0 {
+            return;
+        }
+        self.discussion_task_cursor = if forward {
+            (self.discussion_task_cursor + 1) % count
+        } else {
+            (self.discussion_task_cursor + count - 1) % count
+        };
+    }
+
+    /// 選択中のタスク項目のチェックを切り替え、コメント本文を GitHub 上で書き換える。
+    /// 編集できるのは自分のコメントだけ
+    pub(crate) fn toggle_discussion_task(&mut self) {
+        if self.task_toggle_receiver.is_some() {
+            return;
+        }
+        let Some(comment) = self
+            .discussion_comments
+            .as_ref()
+            .and_then(|comments| comments.get(self.selected_discussion_comment))
+        else {
+            return;
+        };
+        let Some((body, checked)) =
+            markdown::toggle_task(&comment.body, self.discussion_task_cursor)
+        else {
+            self.set_task_toggle_status(false, "No task list item to toggle".to_string());
+            return;
+        };
+        if let Some(ref login) = self.viewer_login {
+            if *login != comment.user.login {
+                self.set_task_toggle_status(
+                    false,
+                    "Only your own comments can be edited".to_string(),
+                );
+                return;
+            }
+        }
+
+        let repo = self.repo.clone();
+        let comment_id = comment.id;
+        let author = comment.user.login.clone();
+        let cached_login = self.viewer_login.clone();
+
+        let (tx, rx) = mpsc::channel(1);
+        self.task_toggle_receiver = Some(rx);
+        tokio::spawn(async move {
+            let login = match cached_login {
+                Some(login) => Ok(login),
+                None => github::comment::fetch_viewer_login().await,
+            };
+            let (viewer_login, result) = match login {
+                Err(e) => (None, Err(format!("{:#}", e))),
+                Ok(login) if login != author => (
+                    Some(login),
+                    Err("Only your own comments can be edited".to_string()),
+                ),
+                Ok(login) => {
+                    let result =
+                        github::comment::update_discussion_comment(&repo, comment_id, &body)
+                            .await
+                            .map(|updated| (updated, checked))
+                            .map_err(|e| format!("{:#}", e));
+                    (Some(login), result)
+                }
+            };
+            let _ = tx
+                .send(TaskToggleResult {
+                    viewer_login,
+                    result,
+                })
+                .await;
+        });
+    }
+
+    pub(crate) fn poll_task_toggle_updates(&mut self) {
+        let Some(ref mut rx) = self.task_toggle_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(update) => {
+                self.task_toggle_receiver = None;
+                if update.viewer_login.is_some() {
+                    self.viewer_login = update.viewer_login;
+                }
+                match update.result {
+                    Ok((updated, checked)) => {
+                        self.update_discussion_comments(|comments| {
+                            if let Some(comment) = comments.iter_mut().find(|c| c.id == updated.id)
+                            {
+                                *comment = updated;
+                            }
+                        });
+                        let message = if checked {
+                            "Task checked"
+                        } else {
+                            "Task unchecked"
+                        };
+                        self.set_task_toggle_status(true, message.to_string());
+                    }
+                    Err(e) => self.set_task_toggle_status(false, format!("Failed: {}", e)),
+                }
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.task_toggle_receiver = None;
+            }
+        }
+    }
+
+    fn set_task_toggle_status(&mut self, ok: bool, message: String) {
+        self.submission_result = Some((ok, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
@@ -6,6 +6,11 @@ use lasso::Rodeo;
 use crate::cache::{PrCacheKey, PrData};
 use crate::github::{ChangedFile, PullRequest};
 use crate::loader::DataLoadResult;
+use notify::event::{AccessKind, AccessMode, CreateKind};
+use notify::EventKind;
+use std::path::{Path, PathBuf};
+
+use super::local_mode::{is_git_file, is_octorus_config_file, should_refresh_local_change};
 
 #[test]
 fn test_find_diff_line_index_basic() {
@@ -349,6 +354,8 @@ async fn test_pr_list_local_toggle_round_trip() {
             login: "local".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     };
     let local_files = vec![ChangedFile {
         filename: "src/main.rs".to_string(),
@@ -421,6 +428,8 @@ async fn test_poll_data_updates_discards_stale_pr_data() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     };
     tx.send(DataLoadResult::Success {
         pr: Box::new(pr),
@@ -513,6 +522,8 @@ async fn test_handle_data_result_clamps_selected_file_when_files_shrink() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     // Set initial loaded state with 5 files
@@ -577,6 +588,8 @@ async fn test_handle_data_result_resyncs_diff_state_when_selected_file_changes()
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     // Set initial loaded state with 5 files
@@ -596,6 +609,8 @@ async fn test_handle_data_result_resyncs_diff_state_when_selected_file_changes()
         interner: Rodeo::default(),
         highlighted: false,
         markdown_rich: false,
+        parse_warnings: vec![],
+        highlighted_chunks: vec![],
     });
 
     // Refresh with only 2 files (selected_file will be clamped from 4 to 1)
@@ -660,6 +675,8 @@ async fn test_handle_data_result_resyncs_comment_positions_when_selected_file_ch
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     // Set initial loaded state with 5 files, selected_file = 4
@@ -674,6 +691,7 @@ async fn test_handle_data_result_resyncs_comment_positions_when_selected_file_ch
         id: 1,
         path: "file_4.rs".to_string(),
         line: Some(1),
+        start_line: None,
         body: "comment on old file".to_string(),
         user: crate::github::User {
             login: "reviewer".to_string(),
@@ -763,6 +781,8 @@ async fn test_handle_data_result_preserves_diff_state_when_selected_file_unchang
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     // Set initial loaded state
@@ -782,6 +802,8 @@ async fn test_handle_data_result_preserves_diff_state_when_selected_file_unchang
         interner: Rodeo::default(),
         highlighted: false,
         markdown_rich: false,
+        parse_warnings: vec![],
+        highlighted_chunks: vec![],
     });
 
     // Refresh with same or more files (selected_file stays at 1)
@@ -849,6 +871,8 @@ async fn test_handle_data_result_keeps_selected_file_by_filename() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     app.data_state = DataState::Loaded {
@@ -914,6 +938,8 @@ async fn test_handle_data_result_auto_focus_selects_next_changed_file() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     app.data_state = DataState::Loaded {
@@ -984,6 +1010,8 @@ async fn test_handle_data_result_auto_focus_prefers_nearest_changed_file() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     app.data_state = DataState::Loaded {
@@ -1055,6 +1083,8 @@ async fn test_handle_data_result_auto_focus_prefers_next_when_distances_are_tie(
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     app.data_state = DataState::Loaded {
@@ -1118,6 +1148,8 @@ async fn test_handle_data_result_auto_focus_transitions_to_split_view_diff() {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
 
     app.handle_data_result(
@@ -1213,6 +1245,7 @@ fn test_save_snapshot_captures_all_fields() {
         id: 10,
         path: "test.rs".to_string(),
         line: Some(5),
+        start_line: None,
         body: "snapshot test".to_string(),
         user: crate::github::User {
             login: "reviewer".to_string(),
@@ -1223,11 +1256,13 @@ fn test_save_snapshot_captures_all_fields() {
     // discussion_comments
     app.discussion_comments = Some(vec![crate::github::comment::DiscussionComment {
         id: 20,
+        node_id: String::new(),
         body: "discussion".to_string(),
         user: crate::github::User {
             login: "user".to_string(),
         },
         created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: None,
     }]);
 
     // local_file_signatures
@@ -1271,6 +1306,7 @@ fn test_save_snapshot_takes_from_app() {
         id: 1,
         path: "f.rs".to_string(),
         line: Some(1),
+        start_line: None,
         body: "c".to_string(),
         user: crate::github::User {
             login: "u".to_string(),
@@ -1279,11 +1315,13 @@ fn test_save_snapshot_takes_from_app() {
     }]);
     app.discussion_comments = Some(vec![crate::github::comment::DiscussionComment {
         id: 1,
+        node_id: String::new(),
         body: "d".to_string(),
         user: crate::github::User {
             login: "u".to_string(),
         },
         created_at: "".to_string(),
+        updated_at: None,
     }]);
     app.local_file_signatures.insert("b.rs".to_string(), 1);
     app.local_file_patch_signatures
@@ -1336,6 +1374,7 @@ fn test_restore_snapshot_all_fields() {
             id: 50,
             path: "r.rs".to_string(),
             line: Some(10),
+            start_line: None,
             body: "restored comment".to_string(),
             user: crate::github::User {
                 login: "r".to_string(),
@@ -1344,11 +1383,13 @@ fn test_restore_snapshot_all_fields() {
         }]),
         discussion_comments: Some(vec![crate::github::comment::DiscussionComment {
             id: 60,
+            node_id: String::new(),
             body: "restored discussion".to_string(),
             user: crate::github::User {
                 login: "d".to_string(),
             },
             created_at: "".to_string(),
+            updated_at: None,
         }]),
         local_file_signatures: sigs,
         local_file_patch_signatures: patch_sigs,
@@ -1438,6 +1479,7 @@ fn test_save_restore_roundtrip_preserves_data() {
         id: 77,
         path: "x.rs".to_string(),
         line: Some(3),
+        start_line: None,
         body: "roundtrip".to_string(),
         user: crate::github::User {
             login: "u".to_string(),
@@ -1688,14 +1730,14 @@ fn test_retry_load_sends_correct_request_type() {
     app.pr_number = Some(42);
     app.retry_load();
     let req = rx.try_recv().unwrap();
-    assert!(matches!(req, RefreshRequest::PrRefresh { pr_number: 42 }));
+    assert!(matches!(req, RefreshRequest::PrRefresh { pr_number: 42, .. }));
 
     // Local mode
     app.local_mode = true;
     app.data_state = DataState::Loading; // reset from retry_load
     app.retry_load();
     let req = rx.try_recv().unwrap();
-    assert!(matches!(req, RefreshRequest::LocalRefresh));
+    assert!(matches!(req, RefreshRequest::LocalRefresh { .. }));
 }
 
 #[test]
@@ -1828,6 +1870,8 @@ async fn test_poll_mark_viewed_applies_unmark() {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: Vec::new(),
+            draft: false,
         }),
         files: vec![
             ChangedFile {
@@ -1898,6 +1942,8 @@ async fn test_poll_mark_viewed_skips_apply_on_pr_mismatch() {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: Vec::new(),
+            draft: false,
         }),
         files: vec![ChangedFile {
             filename: "src/main.rs".to_string(),
@@ -1957,6 +2003,7 @@ async fn test_handle_data_result_auto_focus_skips_state_transition_during_bg_ral
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 0,
+        usage: RallyUsage::default(),
     });
 
     let pr = Box::new(make_local_pr());
@@ -1996,6 +2043,8 @@ fn make_local_pr() -> PullRequest {
             login: "local".to_string(),
         },
         updated_at: "".to_string(),
+        labels: Vec::new(),
+        draft: false,
     }
 }
 
@@ -2161,6 +2210,8 @@ fn make_app_with_patch(patch: &str) -> App {
             login: "user".to_string(),
         },
         updated_at: "2024-01-01T00:00:00Z".to_string(),
+        labels: Vec::new(),
+        draft: false,
     });
     app.data_state = DataState::Loaded {
         pr,
@@ -2177,6 +2228,31 @@ fn make_app_with_patch(patch: &str) -> App {
     app
 }
 
+/// `(filename, status, patch)` のファイルを読み込んだ App。追加・削除の行数は patch から数える
+fn make_app_with_files(files: &[(&str, &str, &str)]) -> App {
+    let count = |patch: &str, line_type: crate::diff::LineType| {
+        crate::diff::line_infos(patch)
+            .filter(|info| info.line_type == line_type)
+            .count() as u32
+    };
+    let mut app = App::new_for_test();
+    app.data_state = DataState::Loaded {
+        pr: Box::new(make_local_pr()),
+        files: files
+            .iter()
+            .map(|&(filename, status, patch)| ChangedFile {
+                filename: filename.to_string(),
+                status: status.to_string(),
+                additions: count(patch, crate::diff::LineType::Added),
+                deletions: count(patch, crate::diff::LineType::Removed),
+                patch: Some(patch.to_string()),
+                viewed: false,
+            })
+            .collect(),
+    };
+    app
+}
+
 #[test]
 fn test_enter_multiline_selection_sets_anchor() {
     let mut app = make_app_with_patch("@@ -1,3 +1,4 @@\n context\n+added\n more context");
@@ -2551,6 +2627,8 @@ async fn test_patch_signature_detects_same_numstat_different_patch() {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: Vec::new(),
+            draft: false,
         }),
         files: initial_files,
     };
@@ -2584,6 +2662,8 @@ async fn test_patch_signature_detects_same_numstat_different_patch() {
                 login: "user".to_string(),
             },
             updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: Vec::new(),
+            draft: false,
         }),
         files: updated_files,
     };
@@ -2868,6 +2948,7 @@ fn test_ai_rally_state_push_log_auto_follow() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     };
 
     // No selection = tail, should auto-follow
@@ -2899,6 +2980,7 @@ fn test_ai_rally_state_push_log_no_auto_follow() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     };
 
     // User is NOT at tail, should not auto-follow
@@ -2926,6 +3008,7 @@ fn test_ai_rally_state_is_selection_at_tail() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     };
 
     // At tail: selected_log_index == logs.len() - 1
@@ -3089,6 +3172,46 @@ fn test_key_could_match_sequence_no_match() {
     assert!(!app.key_could_match_sequence(&key, &seq));
 }
 
+#[test]
+fn test_resolve_pending_keys_completes_user_sequence() {
+    use crate::keybinding::{KeyBinding, KeySequence};
+
+    let mut app = App::new_for_test();
+    let mut kb = app.config.keybindings.clone();
+    kb.quit = KeySequence(vec![
+        KeyBinding::char(' '),
+        KeyBinding::char('q'),
+        KeyBinding::char('q'),
+    ]);
+
+    // 3 キー目を待つ
+    app.push_pending_key(KeyBinding::char(' '));
+    app.push_pending_key(KeyBinding::char('q'));
+    assert!(app.resolve_pending_keys(&kb));
+    assert!(app.completed_sequence.is_none());
+
+    app.push_pending_key(KeyBinding::char('q'));
+    assert!(app.resolve_pending_keys(&kb));
+    assert_eq!(app.completed_sequence.as_ref(), Some(&kb.quit));
+    assert!(app.pending_keys.is_empty());
+
+    // 揃ったシーケンスの操作だけがマッチする
+    let key = make_key(KeyCode::Char('q'));
+    assert!(app.matches_single_key(&key, &kb.quit));
+    assert!(!app.matches_single_key(&key, &kb.move_down));
+    assert!(!app.key_could_match_sequence(&key, &kb.jump_to_first));
+
+    // どのシーケンスにも続かないキー
+    app.completed_sequence = None;
+    app.push_pending_key(KeyBinding::char(' '));
+    app.push_pending_key(KeyBinding::char('x'));
+    assert!(!app.resolve_pending_keys(&kb));
+
+    app.clear_pending_keys();
+    assert!(app.starts_key_sequence(&make_key(KeyCode::Char(' ')), &kb));
+    assert!(!app.starts_key_sequence(&make_key(KeyCode::Char('x')), &kb));
+}
+
 // ===================================================================
 // 5. filter.rs tests
 // ===================================================================
@@ -3523,6 +3646,8 @@ async fn test_handle_mark_viewed_key_v_returns_true() {
                 login: "u".to_string(),
             },
             updated_at: "".to_string(),
+            labels: Vec::new(),
+            draft: false,
         }),
         files: vec![ChangedFile {
             filename: "test.rs".to_string(),
@@ -3716,6 +3841,7 @@ fn test_cleanup_rally_state() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     });
     let (cmd_tx, _cmd_rx) = mpsc::channel(10);
     app.rally_command_sender = Some(cmd_tx);
@@ -3747,6 +3873,7 @@ fn test_is_rally_running_in_background_not_in_rally() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     });
     assert!(app.is_rally_running_in_background());
 }
@@ -3769,6 +3896,7 @@ fn test_is_rally_running_in_background_in_rally() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     });
     assert!(!app.is_rally_running_in_background());
 }
@@ -3799,6 +3927,7 @@ fn test_is_rally_running_in_background_finished() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     });
     assert!(!app.is_rally_running_in_background());
 }
@@ -3821,6 +3950,7 @@ fn test_has_background_rally_true() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     });
     assert!(app.has_background_rally());
 }
@@ -3843,6 +3973,7 @@ fn test_has_background_rally_false_in_rally() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     });
     assert!(!app.has_background_rally());
 }
@@ -3873,6 +4004,7 @@ fn test_is_background_rally_finished_completed() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     });
     assert!(app.is_background_rally_finished());
 }
@@ -3895,6 +4027,7 @@ fn test_is_background_rally_finished_running() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 10,
+        usage: RallyUsage::default(),
     });
     assert!(!app.is_background_rally_finished());
 }
@@ -3918,6 +4051,7 @@ fn test_adjust_log_scroll_selection_above() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 5,
+        usage: RallyUsage::default(),
     });
 
     app.adjust_log_scroll_to_selection();
@@ -3945,6 +4079,7 @@ fn test_adjust_log_scroll_selection_below() {
         pending_review_post: None,
         pending_fix_post: None,
         last_visible_log_height: 5,
+        usage: RallyUsage::default(),
     });
 
     app.adjust_log_scroll_to_selection();
@@ -4100,6 +4235,84 @@ fn test_enter_comment_input_no_patch() {
     assert!(app.input_mode.is_none());
 }
 
+#[test]
+fn test_multiline_comment_context_spans_selection() {
+    let patch = "@@ -1,3 +1,4 @@\n context\n+added\n more context";
+    let mut app = make_app_with_patch(patch);
+    app.selected_line = 3;
+    app.multiline_selection = Some(MultilineSelection {
+        anchor_line: 1,
+        cursor_line: 3,
+    });
+
+    let ctx = app.multiline_comment_context().unwrap();
+    assert_eq!(ctx.start_line_number, Some(1));
+    assert_eq!(ctx.line_number, 3);
+    // 位置を求めるだけで選択は残す
+    assert!(app.multiline_selection.is_some());
+}
+
+#[test]
+fn test_apply_comment_draft_opens_comment_input() {
+    let patch = "@@ -1,3 +1,4 @@\n context\n+added\n more context";
+    let mut app = make_app_with_patch(patch);
+    app.selected_line = 2;
+    app.state = AppState::DiffView;
+    let ctx = app.line_comment_context().unwrap();
+
+    app.apply_comment_draft((
+        ctx,
+        "test.rs".to_string(),
+        Ok("Handle the error".to_string()),
+    ));
+
+    assert!(matches!(
+        app.input_mode,
+        Some(InputMode::Comment(LineInputContext { line_number: 2, .. }))
+    ));
+    assert_eq!(app.input_text_area.content(), "Handle the error");
+    assert_eq!(app.state, AppState::TextInput);
+    assert_eq!(app.preview_return_state, AppState::DiffView);
+}
+
+#[test]
+fn test_apply_comment_draft_discarded_after_leaving_diff() {
+    let patch = "@@ -1,3 +1,4 @@\n context\n+added\n more context";
+    let mut app = make_app_with_patch(patch);
+    app.selected_line = 2;
+    app.state = AppState::DiffView;
+    let ctx = app.line_comment_context().unwrap();
+    app.state = AppState::FileList;
+
+    app.apply_comment_draft((
+        ctx,
+        "test.rs".to_string(),
+        Ok("Handle the error".to_string()),
+    ));
+
+    assert!(app.input_mode.is_none());
+    assert_eq!(app.state, AppState::FileList);
+    assert!(matches!(app.submission_result, Some((false, ref msg)) if msg.contains("discarded")));
+}
+
+#[test]
+fn test_apply_comment_draft_failure_shows_error() {
+    let patch = "@@ -1,3 +1,4 @@\n context\n+added\n more context";
+    let mut app = make_app_with_patch(patch);
+    app.selected_line = 2;
+    app.state = AppState::DiffView;
+    let ctx = app.line_comment_context().unwrap();
+
+    app.apply_comment_draft((ctx, "test.rs".to_string(), Err("timed out".to_string())));
+
+    assert!(app.input_mode.is_none());
+    assert_eq!(app.state, AppState::DiffView);
+    assert_eq!(
+        app.submission_result,
+        Some((false, "Comment draft failed: timed out".to_string()))
+    );
+}
+
 #[test]
 fn test_enter_suggestion_input_sets_mode() {
     let patch = "@@ -1,3 +1,4 @@\n context\n+added line\n more context";
@@ -4113,6 +4326,26 @@ fn test_enter_suggestion_input_sets_mode() {
     assert_eq!(app.state, AppState::TextInput);
 }
 
+#[test]
+fn test_suggestion_refused_on_non_utf8_patch() {
+    let patch = crate::encoding::decode_lossless(b"@@ -1,2 +1,2 @@\n caf\xE9\n+added line");
+    let mut app = make_app_with_patch(&patch);
+    app.selected_line = 2;
+    app.state = AppState::DiffView;
+
+    app.enter_suggestion_input();
+
+    assert!(app.input_mode.is_none());
+    assert_eq!(app.state, AppState::DiffView);
+    assert_eq!(
+        app.submission_result,
+        Some((
+            false,
+            "Cannot suggest on a non-UTF-8 file (Latin-1)".to_string()
+        ))
+    );
+}
+
 #[tokio::test]
 async fn test_open_comment_list_transitions_state() {
     let mut app = App::new_for_test();
@@ -4140,6 +4373,8 @@ async fn test_open_comment_list_transitions_state() {
                 login: "u".to_string(),
             },
             updated_at: "".to_string(),
+            labels: Vec::new(),
+            draft: false,
         }),
         files: vec![],
     };
@@ -4179,6 +4414,7 @@ fn test_update_file_comment_positions_with_comments() {
             id: 1,
             path: "test.rs".to_string(),
             line: Some(1),
+            start_line: None,
             body: "comment at line 1".to_string(),
             user: crate::github::User {
                 login: "reviewer".to_string(),
@@ -4199,6 +4435,7 @@ fn test_update_file_comment_positions_stale_comment() {
             id: 1,
             path: "other_file.rs".to_string(), // different file
             line: Some(1),
+            start_line: None,
             body: "wrong file".to_string(),
             user: crate::github::User {
                 login: "reviewer".to_string(),
@@ -4210,6 +4447,294 @@ fn test_update_file_comment_positions_stale_comment() {
     assert!(app.file_comment_positions.is_empty());
 }
 
+#[test]
+fn test_update_file_comment_positions_skips_hidden_threads() {
+    use crate::github::comment::ReviewComment;
+    use crate::github::ReviewThreadState;
+
+    let patch = "@@ -1,3 +1,4 @@\n context\n+added\n more context";
+    let mut app = make_app_with_patch(patch);
+    let comment = |id: u64, line: u32, login: &str| ReviewComment {
+        id,
+        path: "test.rs".to_string(),
+        line: Some(line),
+        start_line: None,
+        body: "comment".to_string(),
+        user: crate::github::User {
+            login: login.to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+    };
+    app.review_comments = Some(vec![
+        comment(1, 1, "reviewer"),
+        comment(2, 2, "ci[bot]"),
+        comment(3, 3, "reviewer"),
+    ]);
+    app.config.diff.hide_resolved_comments = true;
+    app.config.diff.hide_comment_authors = vec!["*[bot]".to_string()];
+    app.review_thread_states.insert(
+        3,
+        ReviewThreadState {
+            resolved: true,
+            outdated: false,
+            ..ReviewThreadState::default()
+        },
+    );
+
+    app.update_file_comment_positions();
+    let indices: Vec<usize> = app
+        .file_comment_positions
+        .iter()
+        .map(|pos| pos.comment_index)
+        .collect();
+    assert_eq!(indices, vec![0]);
+    assert_eq!(app.file_comment_lines.len(), 1);
+
+    // 外したコメントは次のコメントへの移動でも止まらない
+    app.selected_line = 1;
+    app.jump_to_next_comment();
+    assert_eq!(app.selected_line, 1);
+}
+
+#[test]
+fn test_comment_list_folds_bot_comments() {
+    use crate::github::comment::ReviewComment;
+
+    let mut app = App::new_for_test();
+    let comment = |id: u64, login: &str| ReviewComment {
+        id,
+        path: "src/main.rs".to_string(),
+        line: Some(1),
+        start_line: None,
+        body: "comment".to_string(),
+        user: crate::github::User {
+            login: login.to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+    };
+    app.review_comments = Some(vec![
+        comment(1, "codecov[bot]"),
+        comment(2, "alice"),
+        comment(3, "github-actions"),
+        comment(4, "bob"),
+    ]);
+    app.comment_tab = CommentTab::Review;
+
+    // 先頭の bot のコメントは折りたたまれているので人のコメントを選ぶ
+    app.normalize_comment_list_selection();
+    assert_eq!(app.selected_comment, 1);
+    app.move_comment_list_selection(1);
+    assert_eq!(app.selected_comment, 3);
+    app.move_comment_list_selection(1);
+    assert_eq!(app.selected_comment, 3);
+
+    // 展開すると人のコメントの後ろに bot のコメントが並ぶ
+    app.toggle_bot_comments();
+    assert_eq!(
+        app.comment_list_order(CommentTab::Review),
+        (vec![1, 3, 0, 2], 2)
+    );
+    app.move_comment_list_selection(1);
+    assert_eq!(app.selected_comment, 0);
+
+    // 折りたたむと選択は表示中の行に戻る
+    app.toggle_bot_comments();
+    assert_eq!(app.selected_comment, 1);
+}
+
+fn make_unresolved_thread_app() -> App {
+    use crate::github::comment::ReviewComment;
+    use crate::github::ReviewThreadState;
+
+    let mut app = make_ipc_app();
+    let comment = |id: u64, path: &str, line: u32| ReviewComment {
+        id,
+        path: path.to_string(),
+        line: Some(line),
+        start_line: None,
+        body: "comment".to_string(),
+        user: crate::github::User {
+            login: "reviewer".to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+    };
+    app.review_comments = Some(vec![
+        comment(1, "src/a.rs", 1),
+        comment(2, "src/a.rs", 1),
+        comment(3, "src/b.rs", 12),
+        comment(4, "src/b.rs", 11),
+    ]);
+    // 1 と返信の 2 が未解決、3 は解決済み、4 は未解決
+    for (id, thread_id, resolved) in [(1, 1, false), (2, 1, false), (3, 3, true), (4, 4, false)] {
+        app.review_thread_states.insert(
+            id,
+            ReviewThreadState {
+                thread_id,
+                resolved,
+                ..ReviewThreadState::default()
+            },
+        );
+    }
+    app
+}
+
+#[test]
+fn test_unresolved_thread_count() {
+    let mut app = make_unresolved_thread_app();
+    assert_eq!(app.unresolved_thread_heads(), Some(vec![0, 3]));
+    assert_eq!(app.unresolved_thread_count(), Some(2));
+
+    // スレッドの状態を取得するまでは数えない
+    app.review_thread_states.clear();
+    assert_eq!(app.unresolved_thread_count(), None);
+}
+
+#[test]
+fn test_comment_list_shows_only_unresolved_threads() {
+    let mut app = make_unresolved_thread_app();
+    app.comment_tab = CommentTab::Review;
+    app.selected_comment = 2;
+
+    app.toggle_unresolved_only();
+    assert_eq!(app.comment_list_order(CommentTab::Review).0, vec![0, 1, 3]);
+    assert_ne!(app.selected_comment, 2);
+
+    app.toggle_unresolved_only();
+    assert_eq!(
+        app.comment_list_order(CommentTab::Review).0,
+        vec![0, 1, 2, 3]
+    );
+}
+
+#[tokio::test]
+async fn test_comment_list_keys_follow_view_overrides() {
+    use crate::keybinding::{KeyBinding, KeySequence};
+
+    let mut app = make_unresolved_thread_app();
+    app.state = AppState::CommentList;
+    app.comment_tab = CommentTab::Review;
+    app.config.keybindings.views.comment_list.insert(
+        "comments_unresolved_only".to_string(),
+        KeySequence::single(KeyBinding::char('U')),
+    );
+    let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 24)).unwrap();
+
+    // f は上書きされたので何もしない
+    app.handle_comment_list_input(make_key(KeyCode::Char('f')), &mut terminal)
+        .await
+        .unwrap();
+    assert_eq!(
+        app.comment_list_order(CommentTab::Review).0,
+        vec![0, 1, 2, 3]
+    );
+
+    app.handle_comment_list_input(make_key(KeyCode::Char('U')), &mut terminal)
+        .await
+        .unwrap();
+    assert_eq!(app.comment_list_order(CommentTab::Review).0, vec![0, 1, 3]);
+
+    app.handle_comment_list_input(make_key(KeyCode::Char(']')), &mut terminal)
+        .await
+        .unwrap();
+    assert_eq!(app.comment_tab, CommentTab::Discussion);
+}
+
+#[test]
+fn test_rally_scope_is_marked_files_or_selected_file() {
+    let mut app = make_ipc_app();
+    app.selected_file = 1;
+    assert_eq!(app.rally_scope_files(), vec!["src/b.rs".to_string()]);
+
+    // マークすると次のファイルへ進み、対象は PR のファイル順に並ぶ
+    app.toggle_rally_scope_mark();
+    app.selected_file = 0;
+    app.toggle_rally_scope_mark();
+    assert_eq!(
+        app.rally_scope_files(),
+        vec!["src/a.rs".to_string(), "src/b.rs".to_string()]
+    );
+
+    app.selected_file = 1;
+    app.toggle_rally_scope_mark();
+    assert_eq!(app.rally_scope_files(), vec!["src/a.rs".to_string()]);
+}
+
+#[test]
+fn test_scoped_rally_is_refused_while_a_rally_runs() {
+    use crate::ai::RallyState;
+    let mut app = make_ipc_app();
+    app.ai_rally_state = Some(AiRallyState {
+        iteration: 1,
+        max_iterations: 10,
+        state: RallyState::ReviewerReviewing,
+        history: vec![],
+        logs: vec![],
+        log_scroll_offset: 0,
+        selected_log_index: None,
+        showing_log_detail: false,
+        pending_question: None,
+        pending_permission: None,
+        pending_review_post: None,
+        pending_fix_post: None,
+        last_visible_log_height: 10,
+        usage: RallyUsage::default(),
+    });
+    app.rally_scope_marks.insert("src/a.rs".to_string());
+
+    app.start_scoped_ai_rally();
+    assert!(matches!(app.submission_result, Some((false, _))));
+    assert!(app.rally_scope.is_empty());
+    // マークは次の Rally のために残す
+    assert!(app.rally_scope_marks.contains("src/a.rs"));
+}
+
+#[test]
+fn test_resume_rally_shows_the_running_rally() {
+    use crate::ai::RallyState;
+    let mut app = make_ipc_app();
+    app.ai_rally_state = Some(AiRallyState {
+        iteration: 2,
+        max_iterations: 10,
+        state: RallyState::RevieweeFix,
+        history: vec![],
+        logs: vec![],
+        log_scroll_offset: 0,
+        selected_log_index: None,
+        showing_log_detail: false,
+        pending_question: None,
+        pending_permission: None,
+        pending_review_post: None,
+        pending_fix_post: None,
+        last_visible_log_height: 10,
+        usage: RallyUsage::default(),
+    });
+    app.state = AppState::FileList;
+
+    // 実行中の Rally は置き換えずに画面だけ切り替える
+    app.resume_ai_rally();
+    assert_eq!(app.state, AppState::AiRally);
+    assert_eq!(app.ai_rally_state.as_ref().unwrap().iteration, 2);
+    assert!(app.rally_abort_handle.is_none());
+}
+
+#[tokio::test]
+async fn test_jump_to_next_unresolved_thread_wraps_across_files() {
+    let mut app = make_unresolved_thread_app();
+    app.state = AppState::DiffView;
+
+    // src/a.rs の 1 行目 → src/b.rs の 11 行目 → 先頭に戻る
+    app.jump_to_next_unresolved_thread();
+    assert_eq!((app.selected_file, app.selected_line), (0, 1));
+    app.jump_to_next_unresolved_thread();
+    assert_eq!((app.selected_file, app.selected_line), (1, 3));
+    assert_eq!(
+        app.submission_result,
+        Some((true, "Unresolved thread 2/2".to_string()))
+    );
+    app.jump_to_next_unresolved_thread();
+    assert_eq!((app.selected_file, app.selected_line), (0, 1));
+}
+
 #[test]
 fn test_wrapped_line_count_short() {
     assert_eq!(App::wrapped_line_count("hello", 80), 1);
@@ -4227,6 +4752,25 @@ fn test_wrapped_line_count_empty() {
     assert_eq!(App::wrapped_line_count("", 80), 1);
 }
 
+#[test]
+fn test_wrapped_line_count_japanese_uses_display_width() {
+    // 20 文字だが表示幅は 40 カラム
+    let text = "日本語のレビューコメントは二倍の幅で表示";
+    assert_eq!(text.chars().count(), 20);
+    assert_eq!(App::wrapped_line_count(text, 20), 2);
+    assert_eq!(App::wrapped_line_count(text, 40), 1);
+    // 奇数幅では全角文字が行をまたがないため 1 行多くなる
+    assert_eq!(App::wrapped_line_count(text, 39), 2);
+    assert_eq!(App::wrapped_line_count("👍👍👍", 5), 2);
+}
+
+#[test]
+fn test_comment_body_wrapped_lines_japanese() {
+    let body = "修正ありがとうございます。\nこの関数のエラー処理についても確認をお願いします";
+    // 1 行目: 26 カラム → 1 行、2 行目: 48 カラム → 2 行
+    assert_eq!(App::comment_body_wrapped_lines(body, 30), 3);
+}
+
 #[test]
 fn test_comment_body_wrapped_lines() {
     let body = "short line\na longer line that has more characters";
@@ -4268,6 +4812,7 @@ fn test_enter_reply_input_sets_mode() {
             id: 42,
             path: "test.rs".to_string(),
             line: Some(1),
+            start_line: None,
             body: "original comment".to_string(),
             user: crate::github::User {
                 login: "reviewer".to_string(),
@@ -4309,41 +4854,152 @@ fn test_handle_discussion_detail_scroll_k() {
     assert_eq!(app.discussion_comment_detail_scroll, 4);
 }
 
-#[tokio::test]
-async fn test_jump_to_comment_sets_file_and_line() {
+fn make_task_list_app(author: &str) -> App {
     let mut app = App::new_for_test();
-    app.data_state = DataState::Loaded {
-        pr: Box::new(PullRequest {
-            number: 1,
-            node_id: None,
-            title: "Test".to_string(),
-            body: None,
-            state: "open".to_string(),
-            head: crate::github::Branch {
-                ref_name: "f".to_string(),
-                sha: "a".to_string(),
-            },
-            base: crate::github::Branch {
-                ref_name: "m".to_string(),
-                sha: "b".to_string(),
-            },
-            user: crate::github::User {
-                login: "u".to_string(),
-            },
-            updated_at: "".to_string(),
-        }),
-        files: vec![
-            ChangedFile {
-                filename: "first.rs".to_string(),
-                status: "modified".to_string(),
-                additions: 1,
-                deletions: 0,
-                patch: Some("@@ -1,1 +1,2 @@\n line1\n+line2".to_string()),
-                viewed: false,
-            },
-            ChangedFile {
-                filename: "second.rs".to_string(),
-                status: "modified".to_string(),
+    app.discussion_comments = Some(vec![DiscussionComment {
+        id: 7,
+        node_id: String::new(),
+        body: "- [ ] first\n- [x] second\n- [ ] third".to_string(),
+        user: crate::github::User {
+            login: author.to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+        updated_at: None,
+    }]);
+    app.discussion_comment_detail_mode = true;
+    app
+}
+
+#[test]
+fn test_discussion_detail_tab_cycles_task_items() {
+    let mut app = make_task_list_app("me");
+
+    app.handle_discussion_detail_input(make_key(KeyCode::Tab), 20)
+        .unwrap();
+    app.handle_discussion_detail_input(make_key(KeyCode::Tab), 20)
+        .unwrap();
+    assert_eq!(app.discussion_task_cursor, 2);
+    app.handle_discussion_detail_input(make_key(KeyCode::Tab), 20)
+        .unwrap();
+    assert_eq!(app.discussion_task_cursor, 0);
+    app.handle_discussion_detail_input(make_key(KeyCode::BackTab), 20)
+        .unwrap();
+    assert_eq!(app.discussion_task_cursor, 2);
+}
+
+#[test]
+fn test_toggle_task_rejects_other_users_comment() {
+    let mut app = make_task_list_app("someone-else");
+    app.viewer_login = Some("me".to_string());
+
+    app.handle_discussion_detail_input(make_key(KeyCode::Char('x')), 20)
+        .unwrap();
+
+    assert!(app.task_toggle_receiver.is_none());
+    assert_eq!(
+        app.submission_result,
+        Some((false, "Only your own comments can be edited".to_string()))
+    );
+    let body = &app.discussion_comments.as_ref().unwrap()[0].body;
+    assert!(body.starts_with("- [ ] first"));
+}
+
+#[tokio::test]
+async fn test_poll_task_toggle_updates_replaces_comment_body() {
+    let mut app = make_task_list_app("me");
+    let (tx, rx) = mpsc::channel(1);
+    app.task_toggle_receiver = Some(rx);
+    let mut updated = app.discussion_comments.as_ref().unwrap()[0].clone();
+    updated.body = "- [x] first\n- [x] second\n- [ ] third".to_string();
+    tx.try_send(TaskToggleResult {
+        viewer_login: Some("me".to_string()),
+        result: Ok((updated, true)),
+    })
+    .unwrap();
+
+    app.poll_task_toggle_updates();
+
+    assert!(app.task_toggle_receiver.is_none());
+    assert_eq!(app.viewer_login.as_deref(), Some("me"));
+    assert!(app.discussion_comments.as_ref().unwrap()[0]
+        .body
+        .starts_with("- [x] first"));
+    assert_eq!(
+        app.submission_result,
+        Some((true, "Task checked".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_discussion_delete_rejects_other_users_comment() {
+    let mut app = make_task_list_app("someone-else");
+    app.viewer_login = Some("me".to_string());
+
+    app.request_discussion_delete().await;
+
+    assert!(!app.is_pending_discussion_delete());
+    assert_eq!(
+        app.submission_result,
+        Some((false, "Only your own comments can be deleted".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_discussion_delete_cancelled_by_other_key() {
+    let mut app = make_task_list_app("me");
+    app.viewer_login = Some("me".to_string());
+    app.discussion_comments.as_mut().unwrap()[0].node_id = "IC_1".to_string();
+
+    app.request_discussion_delete().await;
+    assert_eq!(app.pending_discussion_delete, Some(7));
+
+    app.confirm_discussion_delete(&make_key(KeyCode::Char('n')))
+        .await;
+    assert!(!app.is_pending_discussion_delete());
+    assert_eq!(app.discussion_comments.as_ref().unwrap().len(), 1);
+    assert_eq!(
+        app.submission_result,
+        Some((false, "Delete cancelled".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_jump_to_comment_sets_file_and_line() {
+    let mut app = App::new_for_test();
+    app.data_state = DataState::Loaded {
+        pr: Box::new(PullRequest {
+            number: 1,
+            node_id: None,
+            title: "Test".to_string(),
+            body: None,
+            state: "open".to_string(),
+            head: crate::github::Branch {
+                ref_name: "f".to_string(),
+                sha: "a".to_string(),
+            },
+            base: crate::github::Branch {
+                ref_name: "m".to_string(),
+                sha: "b".to_string(),
+            },
+            user: crate::github::User {
+                login: "u".to_string(),
+            },
+            updated_at: "".to_string(),
+            labels: Vec::new(),
+            draft: false,
+        }),
+        files: vec![
+            ChangedFile {
+                filename: "first.rs".to_string(),
+                status: "modified".to_string(),
+                additions: 1,
+                deletions: 0,
+                patch: Some("@@ -1,1 +1,2 @@\n line1\n+line2".to_string()),
+                viewed: false,
+            },
+            ChangedFile {
+                filename: "second.rs".to_string(),
+                status: "modified".to_string(),
                 additions: 1,
                 deletions: 0,
                 patch: Some("@@ -1,1 +1,2 @@\n line1\n+line2".to_string()),
@@ -4356,6 +5012,7 @@ async fn test_jump_to_comment_sets_file_and_line() {
             id: 1,
             path: "second.rs".to_string(),
             line: Some(2),
+            start_line: None,
             body: "check this".to_string(),
             user: crate::github::User {
                 login: "r".to_string(),
@@ -4977,3 +5634,2765 @@ fn test_config_tab_scroll_with_jk() {
     app.apply_help_scroll(make_key(KeyCode::Char('k')), 30);
     assert_eq!(app.config_scroll_offset, 1);
 }
+
+#[test]
+fn test_help_search_edits_query_and_esc_clears_before_closing() {
+    let config = Config::default();
+    let (mut app, _) = App::new_loading("owner/repo", 1, config);
+    app.previous_state = AppState::FileList;
+    app.state = AppState::Help;
+    app.help_scroll_offset = 5;
+
+    app.apply_help_scroll(make_key(KeyCode::Char('/')), 30);
+    assert!(app.help_filter.as_ref().unwrap().input_active);
+    assert_eq!(app.help_scroll_offset, 0);
+
+    // While typing, q / [ / j are part of the query
+    for c in ['q', '[', 'j'] {
+        app.apply_help_scroll(make_key(KeyCode::Char(c)), 30);
+    }
+    assert_eq!(app.help_filter.as_ref().unwrap().query, "q[j");
+    assert_eq!(app.state, AppState::Help);
+    assert_eq!(app.help_tab, HelpTab::Keybindings);
+
+    app.apply_help_scroll(make_key(KeyCode::Backspace), 30);
+    app.apply_help_scroll(make_key(KeyCode::Enter), 30);
+    let filter = app.help_filter.as_ref().unwrap();
+    assert_eq!(filter.query, "q[");
+    assert!(!filter.input_active);
+
+    // Applied query: keys scroll again
+    app.apply_help_scroll(make_key(KeyCode::Char('j')), 30);
+    assert_eq!(app.help_scroll_offset, 1);
+
+    // Esc clears the search first, then closes the help
+    app.apply_help_scroll(make_key(KeyCode::Esc), 30);
+    assert!(app.help_filter.is_none());
+    assert_eq!(app.state, AppState::Help);
+    app.apply_help_scroll(make_key(KeyCode::Esc), 30);
+    assert_eq!(app.state, AppState::FileList);
+}
+
+fn make_fixup_comment(id: u64, path: &str, line: u32) -> ReviewComment {
+    ReviewComment {
+        id,
+        path: path.to_string(),
+        line: Some(line),
+        start_line: None,
+        body: "Please rename this".to_string(),
+        user: crate::github::User {
+            login: "reviewer".to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+    }
+}
+
+fn make_fixup_app() -> App {
+    let mut app = App::new_for_test();
+    let mut pr = make_local_pr();
+    pr.number = 1;
+    pr.base.ref_name = "main".to_string();
+    app.session_cache.put_pr_data(
+        PrCacheKey {
+            repo: app.repo.clone(),
+            pr_number: 1,
+        },
+        PrData {
+            pr: Box::new(pr),
+            files: vec![],
+            pr_updated_at: String::new(),
+        },
+    );
+    app.review_comments = Some(vec![make_fixup_comment(5, "src/lib.rs", 2)]);
+    app.saved_pr_snapshot = Some(app.save_view_snapshot());
+    app.local_mode = true;
+    app.data_state = DataState::Loaded {
+        pr: Box::new(make_local_pr()),
+        files: vec![ChangedFile {
+            filename: "src/lib.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 1,
+            patch: Some(
+                "diff --git a/src/lib.rs b/src/lib.rs\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,3 +1,3 @@\n a\n-b\n+c\n d"
+                    .to_string(),
+            ),
+            viewed: false,
+        }],
+    };
+    app
+}
+
+#[test]
+fn test_open_fixup_helper_ignored_outside_local_mode() {
+    let mut app = make_fixup_app();
+    app.local_mode = false;
+    app.open_fixup_helper();
+    assert!(app.fixup_popup.is_none());
+    assert!(app.submission_result.is_none());
+}
+
+#[test]
+fn test_open_fixup_helper_without_comments_shows_error() {
+    let mut app = App::new_for_test();
+    app.local_mode = true;
+    app.original_pr_number = None;
+    app.open_fixup_helper();
+    assert!(app.fixup_popup.is_none());
+    let (success, message) = app.submission_result.as_ref().unwrap();
+    assert!(!success);
+    assert!(message.contains("No PR review comments"));
+}
+
+#[test]
+fn test_open_fixup_helper_groups_hunks_by_comment() {
+    let mut app = make_fixup_app();
+    app.open_fixup_helper();
+    let popup = app.fixup_popup.as_ref().expect("popup should open");
+    assert_eq!(popup.groups.len(), 1);
+    assert_eq!(popup.groups[0].comment_id, 5);
+    assert_eq!(popup.checked, vec![true]);
+    assert_eq!(popup.base_branch, "main");
+    assert_eq!(popup.unassigned, 0);
+}
+
+fn make_ipc_app() -> App {
+    make_app_with_files(&[
+        ("src/a.rs", "modified", "@@ -1,1 +1,2 @@\n a"),
+        ("src/b.rs", "modified", "@@ -10,3 +10,4 @@\n x\n-y\n+y2\n+y3\n z"),
+    ])
+}
+
+#[tokio::test]
+async fn test_ipc_open_selects_file_and_line() {
+    let mut app = make_ipc_app();
+    app.handle_ipc_command(crate::ipc::IpcCommand::Open {
+        path: "src/b.rs".to_string(),
+        line: Some(12),
+    });
+    assert_eq!(app.selected_file, 1);
+    assert_eq!(app.state, AppState::DiffView);
+    // "@@" (0), " x" (1), "-y" (2), "+y2" (3) → line 11, "+y3" (4) → line 12
+    assert_eq!(app.selected_line, 4);
+}
+
+#[test]
+fn test_ipc_open_unknown_file_reports_error() {
+    let mut app = make_ipc_app();
+    app.state = AppState::FileList;
+    app.handle_ipc_command(crate::ipc::IpcCommand::Open {
+        path: "missing.rs".to_string(),
+        line: None,
+    });
+    assert_eq!(app.state, AppState::FileList);
+    assert!(matches!(app.submission_result, Some((false, _))));
+}
+
+#[test]
+fn test_ipc_ignored_while_editing() {
+    let mut app = make_ipc_app();
+    app.state = AppState::TextInput;
+    app.handle_ipc_command(crate::ipc::IpcCommand::Open {
+        path: "src/a.rs".to_string(),
+        line: None,
+    });
+    assert_eq!(app.state, AppState::TextInput);
+}
+
+#[test]
+fn test_ipc_comment_rejected_in_local_mode() {
+    let mut app = make_ipc_app();
+    app.local_mode = true;
+    app.handle_ipc_command(crate::ipc::IpcCommand::Comment {
+        path: "src/b.rs".to_string(),
+        line: 11,
+        body: "nit".to_string(),
+    });
+    assert!(!app.is_submitting_comment());
+    assert!(matches!(app.submission_result, Some((false, _))));
+}
+
+#[test]
+fn test_current_jump_event_follows_diff_cursor() {
+    let mut app = make_ipc_app();
+    app.selected_file = 1;
+    app.state = AppState::DiffView;
+    // "-y" (index 2) は直前の新ファイル行 10 を指す
+    app.selected_line = 2;
+    let event = app.current_jump_event().unwrap();
+    assert_eq!(event.path, "src/b.rs");
+    assert_eq!(event.line, 10);
+
+    app.selected_line = 4;
+    assert_eq!(app.current_jump_event().unwrap().line, 12);
+}
+
+#[test]
+fn test_current_jump_event_only_in_diff_states() {
+    let mut app = make_ipc_app();
+    app.state = AppState::FileList;
+    assert!(app.current_jump_event().is_none());
+
+    app.state = AppState::SplitViewFileList;
+    app.selected_file = 1;
+    app.selected_line = 3;
+    assert_eq!(app.current_jump_event().unwrap().line, 10);
+}
+
+fn activity_entry(action: crate::audit::AuditAction) -> crate::audit::AuditEntry {
+    crate::audit::AuditEntry::new::<(), String>("owner/repo", 1, action, None, &Ok(()))
+}
+
+#[test]
+fn test_session_activity_panel_scroll_and_close() {
+    use crate::audit::AuditAction;
+
+    let mut app = make_ipc_app();
+    app.session_activity = Some(SessionActivityState {
+        entries: vec![
+            activity_entry(AuditAction::Comment),
+            activity_entry(AuditAction::Reply),
+            activity_entry(AuditAction::Approve),
+        ],
+        scroll: 2,
+    });
+
+    let press = |code| KeyEvent::new(code, KeyModifiers::NONE);
+
+    app.handle_session_activity_input(press(KeyCode::Char('j')));
+    assert_eq!(app.session_activity.as_ref().unwrap().scroll, 2);
+
+    app.handle_session_activity_input(press(KeyCode::Char('k')));
+    app.handle_session_activity_input(press(KeyCode::Up));
+    app.handle_session_activity_input(press(KeyCode::Up));
+    assert_eq!(app.session_activity.as_ref().unwrap().scroll, 0);
+
+    app.handle_session_activity_input(press(KeyCode::Char('H')));
+    assert!(app.session_activity.is_none());
+}
+
+#[test]
+fn test_session_activity_panel_closes_on_esc() {
+    let mut app = make_ipc_app();
+    app.session_activity = Some(SessionActivityState {
+        entries: Vec::new(),
+        scroll: 0,
+    });
+    app.handle_session_activity_input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.session_activity.is_none());
+}
+
+#[test]
+fn test_popups_stack_in_open_order() {
+    let mut app = make_ipc_app();
+    // push_popup を通さずに開いたものは一番奥に置く
+    app.symbol_popup = Some(SymbolPopupState {
+        symbols: vec![("foo".to_string(), 0, 3), ("bar".to_string(), 4, 7)],
+        selected: 0,
+    });
+    app.session_activity = Some(SessionActivityState {
+        entries: Vec::new(),
+        scroll: 0,
+    });
+    app.push_popup(PopupId::SessionActivity);
+    app.key_stats_open = true;
+    app.push_popup(PopupId::KeyStats);
+    assert_eq!(
+        app.open_popups(),
+        vec![PopupId::Symbol, PopupId::SessionActivity, PopupId::KeyStats]
+    );
+    assert_eq!(app.focused_popup(), Some(PopupId::KeyStats));
+
+    // 閉じると下のポップアップにフォーカスが戻る
+    app.handle_key_stats_input(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(!app.key_stats_open);
+    assert_eq!(app.focused_popup(), Some(PopupId::SessionActivity));
+
+    // 開き直すと最前面に移る
+    app.push_popup(PopupId::Symbol);
+    assert_eq!(app.focused_popup(), Some(PopupId::Symbol));
+
+    // 状態を直接閉じたものは重なりから外れる
+    app.symbol_popup = None;
+    assert_eq!(app.open_popups(), vec![PopupId::SessionActivity]);
+}
+
+fn make_translate_app() -> App {
+    let mut app = make_ipc_app();
+    app.review_comments = Some(vec![crate::github::comment::ReviewComment {
+        id: 7,
+        path: "src/b.rs".to_string(),
+        line: Some(11),
+        start_line: None,
+        body: "please rename".to_string(),
+        user: crate::github::User {
+            login: "reviewer".to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+    }]);
+    app.selected_file = 1;
+    app.selected_line = 3;
+    app.file_comment_positions = vec![CommentPosition {
+        diff_line_index: 3,
+        comment_index: 0,
+    }];
+    app.comment_panel_open = true;
+    app
+}
+
+#[tokio::test]
+async fn test_apply_suggestion_to_worktree() {
+    let dir = tempfile::tempdir().unwrap();
+    std::process::Command::new("git")
+        .args(["init", "-q"])
+        .current_dir(dir.path())
+        .output()
+        .unwrap();
+    std::fs::create_dir(dir.path().join("src")).unwrap();
+    // ローカルで先頭に行が増え、PR の 11 行目は 3 行目にずれている
+    let file = dir.path().join("src/b.rs");
+    std::fs::write(&file, "new\nx\ny2\ny3\nz\n").unwrap();
+
+    let mut app = make_translate_app();
+    app.working_dir = Some(dir.path().display().to_string());
+    app.review_comments.as_mut().unwrap()[0].body = "nit\n```suggestion\ny = 2\n```".to_string();
+
+    app.request_suggestion_apply().await;
+    assert_eq!(
+        app.suggestion_apply_prompt().as_deref(),
+        Some("Apply suggestion to src/b.rs:11 (line 3 locally)? y: apply | any other key: cancel")
+    );
+    app.confirm_suggestion_apply(&make_key(KeyCode::Char('n')));
+    assert!(!app.is_pending_suggestion_apply());
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "new\nx\ny2\ny3\nz\n");
+
+    app.request_suggestion_apply().await;
+    app.confirm_suggestion_apply(&make_key(KeyCode::Char('y')));
+    assert_eq!(std::fs::read_to_string(&file).unwrap(), "new\nx\ny = 2\ny3\nz\n");
+    assert_eq!(
+        app.submission_result,
+        Some((true, "Applied suggestion to src/b.rs:11".to_string()))
+    );
+
+    // 適用済みなら確認せずにそう伝える
+    app.request_suggestion_apply().await;
+    assert!(!app.is_pending_suggestion_apply());
+    assert_eq!(
+        app.submission_result,
+        Some((true, "Suggestion is already applied locally".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_file_explanation_is_cached_per_patch() {
+    let mut app = make_ipc_app();
+    app.config.ai.reviewer = "unknown".to_string();
+    app.toggle_file_explanation();
+    assert!(app.explain_panel_open);
+    assert!(app.current_file_explanation().is_none());
+    assert!(matches!(app.submission_result, Some((false, ref msg))
+        if msg.starts_with("Explanation unavailable")));
+
+    let key = app.selected_explanation_key().unwrap();
+    let (tx, rx) = mpsc::channel(1);
+    app.explain_receiver = Some(rx);
+    app.file_explanations.insert(key, FileExplanation::Pending);
+    tx.send((key, Ok("**Adds** a line".to_string()))).await.unwrap();
+    app.poll_explain_updates();
+    assert!(app.explain_receiver.is_none());
+    assert_eq!(
+        app.current_file_explanation(),
+        Some(&FileExplanation::Done("**Adds** a line".to_string()))
+    );
+
+    // 説明済みのファイルでは閉じる
+    app.toggle_file_explanation();
+    assert!(!app.explain_panel_open);
+
+    // 別のファイルや patch が変わったファイルは説明し直す
+    app.selected_file = 1;
+    assert!(app.current_file_explanation().is_none());
+    app.selected_file = 0;
+    if let DataState::Loaded { ref mut files, .. } = app.data_state {
+        files[0].patch = Some("@@ -1,1 +1,2 @@\n a\n+b".to_string());
+    }
+    assert!(app.current_file_explanation().is_none());
+}
+
+#[test]
+fn test_translate_comment_requires_language() {
+    let mut app = make_translate_app();
+    app.toggle_comment_translation();
+    assert!(app.comment_translations.is_empty());
+    assert!(matches!(app.submission_result, Some((false, ref msg)) if msg.contains("[translate]")));
+}
+
+#[cfg(unix)]
+#[tokio::test]
+async fn test_translate_comment_shows_and_toggles_translation() {
+    let mut app = make_translate_app();
+    app.config.translate.language = Some("ja".to_string());
+    app.config.translate.command = Some("tr a-z A-Z".to_string());
+    let lines_before = app.comment_panel_content_lines(80);
+
+    app.toggle_comment_translation();
+    assert_eq!(
+        app.comment_translations.get(&7),
+        Some(&CommentTranslation::Pending)
+    );
+
+    for _ in 0..200 {
+        app.poll_translate_updates();
+        if app.translate_receiver.is_none() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert_eq!(
+        app.comment_translations.get(&7),
+        Some(&CommentTranslation::Done("PLEASE RENAME".to_string()))
+    );
+    // 見出し + 翻訳本文の 2 行が増える
+    assert_eq!(app.comment_panel_content_lines(80), lines_before + 2);
+
+    // もう一度で非表示
+    app.toggle_comment_translation();
+    assert!(app.comment_translations.is_empty());
+    assert_eq!(app.comment_panel_content_lines(80), lines_before);
+}
+
+fn make_finder_app() -> App {
+    let mut app = make_app_with_files(&[
+        ("src/app/mod.rs", "modified", "@@ -1,1 +1,2 @@\n mod a;\n+mod b;"),
+        (
+            "src/ui/render.rs",
+            "modified",
+            "@@ -1,1 +1,3 @@\n use x;\n+\n+pub fn render_helper() {}",
+        ),
+        ("README.md", "modified", "@@ -1,1 +1,2 @@\n # t\n+fn not_code"),
+    ]);
+    app.state = AppState::FileList;
+    app
+}
+
+fn type_into_finder(app: &mut App, text: &str) {
+    for c in text.chars() {
+        app.handle_file_finder_input(KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE));
+    }
+}
+
+#[test]
+fn test_file_finder_indexes_files_and_symbols() {
+    let mut app = make_finder_app();
+    app.open_file_finder();
+    let finder = app.file_finder.as_ref().unwrap();
+
+    // mod a / mod b (Rust), render_helper。README.md はインデックスしない
+    let symbols: Vec<&str> = finder
+        .items
+        .iter()
+        .filter(|i| i.diff_line.is_some())
+        .map(|i| i.label.as_str())
+        .collect();
+    assert_eq!(symbols, vec!["a", "b", "render_helper"]);
+
+    // 空クエリではファイルのみ
+    assert_eq!(finder.matches.len(), 3);
+}
+
+#[test]
+fn test_file_finder_ranks_recent_files_first() {
+    let mut app = make_finder_app();
+    app.selected_file = 2;
+    app.record_recent_file();
+    app.selected_file = 1;
+    app.record_recent_file();
+
+    app.open_file_finder();
+    let finder = app.file_finder.as_ref().unwrap();
+    let order: Vec<usize> = finder
+        .matches
+        .iter()
+        .map(|(i, _)| finder.items[*i].file_index)
+        .collect();
+    assert_eq!(order, vec![1, 2, 0]);
+}
+
+#[tokio::test]
+async fn test_file_finder_opens_symbol_definition() {
+    let mut app = make_finder_app();
+    app.open_file_finder();
+    type_into_finder(&mut app, "rhelp");
+
+    let finder = app.file_finder.as_ref().unwrap();
+    let (top, positions) = &finder.matches[0];
+    assert_eq!(finder.items[*top].label, "render_helper");
+    assert_eq!(positions.len(), 5);
+
+    app.handle_file_finder_input(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(app.file_finder.is_none());
+    assert_eq!(app.state, AppState::SplitViewDiff);
+    assert_eq!(app.selected_file, 1);
+    assert_eq!(app.selected_line, 3);
+    assert_eq!(app.recent_files, vec!["src/ui/render.rs".to_string()]);
+}
+
+#[test]
+fn test_file_finder_backspace_and_escape() {
+    let mut app = make_finder_app();
+    app.open_file_finder();
+    type_into_finder(&mut app, "zzz");
+    assert!(app.file_finder.as_ref().unwrap().matches.is_empty());
+
+    for _ in 0..3 {
+        app.handle_file_finder_input(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    }
+    assert_eq!(app.file_finder.as_ref().unwrap().matches.len(), 3);
+
+    app.handle_file_finder_input(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.file_finder.is_none());
+    assert_eq!(app.state, AppState::FileList);
+}
+
+#[tokio::test]
+async fn test_toggle_time_format_in_pr_list() {
+    use crate::config::TimeStyle;
+
+    let mut app = App::new_for_test();
+    app.state = AppState::PullRequestList;
+    app.pr_list_loading = false;
+    app.pr_list = Some(vec![]);
+    assert_eq!(app.time_format.style, TimeStyle::Relative);
+
+    app.handle_pr_list_input(make_key(KeyCode::Char('t')))
+        .await
+        .unwrap();
+    assert_eq!(app.time_format.style, TimeStyle::Absolute);
+
+    app.handle_pr_list_input(make_key(KeyCode::Char('t')))
+        .await
+        .unwrap();
+    assert_eq!(app.time_format.style, TimeStyle::Relative);
+}
+
+fn make_lfs_app() -> App {
+    let mut app = make_app_with_files(&[(
+        "assets/data.csv",
+        "modified",
+        "@@ -1,3 +1,3 @@\n version https://git-lfs.github.com/spec/v1\n-oid sha256:aaaa\n-size 10\n+oid sha256:bbbb\n+size 12",
+    )]);
+    app.state = AppState::DiffView;
+    app
+}
+
+#[tokio::test]
+async fn test_lfs_preview_replaces_and_restores_patch() {
+    let mut app = make_lfs_app();
+    assert_eq!(app.lfs_label(0), Some("LFS object"));
+    let pointer_patch = app.files()[0].patch.clone().unwrap();
+
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.lfs_receiver = Some(rx);
+    tx.send((
+        "assets/data.csv".to_string(),
+        Ok("@@ -1 +1 @@\n-a,b\n+a,c".to_string()),
+    ))
+    .await
+    .unwrap();
+    app.poll_lfs_updates();
+
+    assert!(app.lfs_receiver.is_none());
+    assert!(app.is_lfs_preview(0));
+    assert_eq!(app.lfs_label(0), Some("LFS contents"));
+    assert_eq!(
+        app.files()[0].patch.as_deref(),
+        Some("@@ -1 +1 @@\n-a,b\n+a,c")
+    );
+    assert_eq!(app.diff_line_count, 3);
+
+    // プレビュー行にはコメントできない
+    app.selected_line = 2;
+    app.enter_comment_input();
+    assert!(app.input_mode.is_none());
+
+    app.toggle_lfs_preview();
+    assert!(!app.is_lfs_preview(0));
+    assert_eq!(
+        app.files()[0].patch.as_deref(),
+        Some(pointer_patch.as_str())
+    );
+}
+
+#[tokio::test]
+async fn test_lfs_preview_reports_errors() {
+    let mut app = make_lfs_app();
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.lfs_receiver = Some(rx);
+    tx.send((
+        "assets/data.csv".to_string(),
+        Err("LFS object is binary".to_string()),
+    ))
+    .await
+    .unwrap();
+    app.poll_lfs_updates();
+
+    assert!(!app.is_lfs_preview(0));
+    assert_eq!(
+        app.submission_result,
+        Some((false, "LFS object is binary".to_string()))
+    );
+}
+
+#[test]
+fn test_lfs_preview_ignores_regular_files() {
+    let mut app = make_ipc_app();
+    assert_eq!(app.lfs_label(0), None);
+    app.toggle_lfs_preview();
+    assert!(app.lfs_receiver.is_none());
+    assert_eq!(
+        app.submission_result,
+        Some((false, "Not a Git LFS pointer".to_string()))
+    );
+}
+
+fn make_deleted_file_app() -> App {
+    let mut app =
+        make_app_with_files(&[("src/old.rs", "removed", "@@ -1,2 +0,0 @@\n-fn old() {\n-}")]);
+    app.state = AppState::DiffView;
+    app
+}
+
+#[tokio::test]
+async fn test_base_file_view_replaces_and_restores_patch() {
+    let mut app = make_deleted_file_app();
+    assert_eq!(app.preview_label(0), None);
+    let deleted_patch = app.files()[0].patch.clone();
+
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.base_file_receiver = Some(rx);
+    tx.send((
+        "src/old.rs".to_string(),
+        Ok(crate::file_content::full_file_patch(
+            "// kept for reference\nfn old() {\n}\n",
+        )),
+    ))
+    .await
+    .unwrap();
+    app.poll_base_file_updates();
+
+    assert!(app.base_file_receiver.is_none());
+    assert!(app.is_file_preview(0));
+    assert_eq!(app.preview_label(0), Some("base version"));
+    assert_eq!(app.diff_line_count, 4);
+
+    // 読み取り専用の表示なのでコメントできない
+    app.selected_line = 1;
+    app.enter_comment_input();
+    assert!(app.input_mode.is_none());
+
+    app.toggle_base_file_view();
+    assert!(!app.is_file_preview(0));
+    assert_eq!(app.files()[0].patch, deleted_patch);
+}
+
+#[tokio::test]
+async fn test_full_file_view_for_changed_files() {
+    let mut app = make_ipc_app();
+    app.selected_file = 1;
+    let diff_patch = app.files()[1].patch.clone().unwrap();
+
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.base_file_receiver = Some(rx);
+    let content = (1..=12)
+        .map(|n| match n {
+            11 => "y2\ny3".to_string(),
+            12 => "z".to_string(),
+            10 => "x".to_string(),
+            n => format!("line {}", n),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    tx.send((
+        "src/b.rs".to_string(),
+        Ok(crate::file_content::full_file_diff_patch(&content, &diff_patch).unwrap()),
+    ))
+    .await
+    .unwrap();
+    app.poll_base_file_updates();
+
+    assert_eq!(app.preview_label(1), Some("full file"));
+    assert!(app.is_file_preview(1));
+    // 9 行の前置き + hunk の 5 行 + ヘッダー
+    assert_eq!(app.diff_line_count, 15);
+    assert_eq!(
+        app.submission_result,
+        Some((true, "Showing src/b.rs in full (read-only)".to_string()))
+    );
+
+    app.toggle_base_file_view();
+    assert!(!app.is_file_preview(1));
+    assert_eq!(app.files()[1].patch.as_deref(), Some(diff_patch.as_str()));
+
+    // patch が省略されたファイルは差し込む diff がない
+    app.selected_file = 0;
+    app.replace_file_patch("src/a.rs", None);
+    app.toggle_base_file_view();
+    assert!(app.base_file_receiver.is_none());
+    assert_eq!(
+        app.submission_result,
+        Some((false, "No diff available for this file".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_blame_is_fetched_once_per_file() {
+    let mut app = make_ipc_app();
+    app.selected_file = 1;
+    app.state = AppState::DiffView;
+    app.blame_enabled = true;
+    let key = (Some(String::new()), "src/b.rs".to_string());
+
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.blame_receiver = Some(rx);
+    let porcelain = format!(
+        "{sha} 10 10 1\nauthor Alice\nauthor-time 1704067200\n\tx\n",
+        sha = "1".repeat(40)
+    );
+    tx.send((
+        key.clone(),
+        Ok(crate::blame::FileBlame::parse_porcelain(&porcelain)),
+    ))
+    .await
+    .unwrap();
+    app.poll_blame_updates();
+
+    // 取得済みなので取り直さない
+    assert!(app.blame_receiver.is_none());
+    let blame = app.current_file_blame().unwrap();
+    assert_eq!(blame.get(10).unwrap().author, "Alice");
+
+    // 別のファイルの blame はまだない
+    app.selected_file = 0;
+    assert!(app.current_file_blame().is_none());
+    app.file_blames
+        .insert((Some(String::new()), "src/a.rs".to_string()), None);
+    app.poll_blame_updates();
+    assert!(app.blame_receiver.is_none());
+
+    // 消すと取得済みの blame も捨てる
+    app.toggle_blame();
+    assert!(!app.blame_enabled);
+    assert!(app.file_blames.is_empty());
+}
+
+#[test]
+fn test_blame_gutter_uses_anonymized_authors() {
+    let mut app = make_ipc_app();
+    app.selected_file = 1;
+    app.blame_enabled = true;
+    app.anonymizer = crate::anonymize::Anonymizer::new("owner/repo", false);
+    let porcelain = format!(
+        "{sha} 10 10 1\nauthor Alice\nauthor-time 1704067200\n\tx\n",
+        sha = "1".repeat(40)
+    );
+    app.file_blames.insert(
+        (Some(String::new()), "src/b.rs".to_string()),
+        Some(crate::blame::FileBlame::parse_porcelain(&porcelain)),
+    );
+
+    let lines = vec![
+        ratatui::text::Line::from("@@"),
+        ratatui::text::Line::from(" x"),
+    ];
+    let lines = crate::ui::diff_view::prepend_blame_gutter(&app, lines, 0);
+    let gutter = lines[1].spans[0].content.to_string();
+    assert!(!gutter.contains("Alice"));
+    assert!(gutter.starts_with(app.anonymizer.user("Alice").as_ref()));
+}
+
+#[tokio::test]
+async fn test_line_history_opens_commit_and_jumps_back() {
+    let mut app = make_ipc_app();
+    app.selected_file = 1;
+    app.state = AppState::DiffView;
+    app.selected_line = 4;
+    app.scroll_offset = 1;
+
+    let change = crate::line_history::LineChange {
+        commit: crate::github::PrCommit {
+            sha: "abcdef1234567890".to_string(),
+            headline: "Add y3".to_string(),
+            author: "alice".to_string(),
+            date: "2024-01-01T00:00:00Z".to_string(),
+            is_merge: false,
+        },
+        path: "src/b.rs".to_string(),
+        line: Some(11),
+    };
+    let commit_files = vec![
+        ChangedFile {
+            filename: "README.md".to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("@@ -1 +1,2 @@\n a\n+b".to_string()),
+            viewed: false,
+        },
+        ChangedFile {
+            filename: "src/b.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("@@ -10,2 +10,3 @@\n x\n+y3\n z".to_string()),
+            viewed: false,
+        },
+    ];
+    app.show_line_change(change, commit_files);
+
+    // コミットの diff の変更した行を開く
+    assert!(app.commit_view.is_some());
+    assert_eq!(app.state, AppState::DiffView);
+    assert_eq!(app.selected_file, 1);
+    assert_eq!(app.files()[0].filename, "README.md");
+    assert_eq!(app.selected_line, 2);
+
+    // PR 全体の diff の元の位置に戻る
+    app.jump_back();
+    assert!(app.commit_view.is_none());
+    assert_eq!(app.files().len(), 2);
+    assert_eq!(app.files()[0].filename, "src/a.rs");
+    assert_eq!(app.selected_file, 1);
+    assert_eq!(app.selected_line, 4);
+    assert_eq!(app.scroll_offset, 1);
+}
+
+#[test]
+fn test_line_history_requires_new_side_line() {
+    let mut app = make_ipc_app();
+    app.selected_file = 1;
+    app.state = AppState::DiffView;
+    // "-y" は削除行なので履歴を調べられない
+    app.selected_line = 2;
+    app.open_line_history();
+    assert!(app.line_history.is_none());
+    assert!(matches!(app.submission_result, Some((false, _))));
+}
+
+fn make_vendored_app() -> App {
+    let patch = "@@ -1 +1 @@\n-a\n+b";
+    let mut app = make_app_with_files(&[
+        ("src/main.rs", "modified", patch),
+        ("vendor/github.com/foo/bar/bar.go", "modified", patch),
+        ("vendor/github.com/foo/bar/LICENSE", "modified", patch),
+    ]);
+    app.state = AppState::DiffView;
+    app
+}
+
+#[test]
+fn test_vendored_files_collapsed_until_expanded() {
+    let mut app = make_vendored_app();
+    assert!(!app.is_vendored_collapsed(0));
+    assert_eq!(app.preview_label(0), None);
+
+    // vendored ファイルは折りたたまれ、コメントもできない
+    app.selected_file = 1;
+    assert!(app.is_vendored_collapsed(1));
+    assert_eq!(app.preview_label(1), Some("vendored, collapsed"));
+    app.selected_line = 1;
+    app.enter_comment_input();
+    assert!(app.input_mode.is_none());
+
+    app.toggle_vendored_expand();
+    assert!(!app.is_vendored_collapsed(1));
+    assert_eq!(app.preview_label(1), Some("vendored"));
+    app.toggle_vendored_expand();
+    assert!(app.is_vendored_collapsed(1));
+}
+
+#[test]
+fn test_vendored_licence_is_never_collapsed() {
+    let mut app = make_vendored_app();
+    app.selected_file = 2;
+    assert!(!app.is_vendored_collapsed(2));
+    assert_eq!(app.preview_label(2), Some("vendored licence"));
+
+    app.toggle_vendored_expand();
+    assert!(app.vendored_expanded.is_empty());
+}
+
+#[test]
+fn test_vendored_summary_closes_on_esc() {
+    let mut app = make_vendored_app();
+    app.vendored_summary_open = true;
+    app.handle_vendored_summary_input(&make_key(KeyCode::Char('j')));
+    assert!(app.vendored_summary_open);
+    app.handle_vendored_summary_input(&make_key(KeyCode::Esc));
+    assert!(!app.vendored_summary_open);
+}
+
+#[test]
+fn test_review_agenda_lists_files_and_skims_vendored() {
+    let mut app = make_vendored_app();
+    app.open_review_agenda();
+    let markdown = app.review_agenda.as_ref().unwrap().markdown.clone();
+    assert!(markdown.contains("1. [ ] `vendor/github.com/foo/bar/LICENSE`"));
+    assert!(markdown.contains("2. [ ] `src/main.rs`"));
+    assert!(markdown.contains("- `vendor/github.com/foo/bar/bar.go`"));
+
+    app.handle_review_agenda_input(&make_key(KeyCode::Esc));
+    assert!(app.review_agenda.is_none());
+
+    // 投稿前に入力画面で編集できる
+    app.open_review_agenda();
+    app.handle_review_agenda_input(&make_key(KeyCode::Char('c')));
+    assert!(app.review_agenda.is_none());
+    assert_eq!(app.state, AppState::TextInput);
+    assert!(matches!(
+        app.input_mode,
+        Some(InputMode::ReviewAgenda { pr_number: 1 })
+    ));
+    assert_eq!(app.input_text_area.content(), markdown.trim_end());
+}
+
+#[tokio::test]
+async fn test_pr_overview_loads_details_and_returns_to_previous_view() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    app.state = AppState::FileList;
+    app.open_pr_overview();
+    assert_eq!(app.state, AppState::PrOverview);
+    assert!(app.pr_overview.as_ref().unwrap().details.is_none());
+
+    let (tx, rx) = mpsc::channel(1);
+    let overview = github::PrOverview {
+        reviewers: vec![github::ReviewerStatus {
+            name: "bob".to_string(),
+            state: "APPROVED".to_string(),
+        }],
+        linked_issues: Vec::new(),
+    };
+    tx.send(Ok(overview.clone())).await.unwrap();
+    app.pr_overview_receiver = Some((1, rx));
+    app.poll_pr_overview_updates();
+    assert_eq!(app.pr_overview.as_ref().unwrap().details, Some(overview));
+
+    let scroll = |app: &App| app.pr_overview.as_ref().unwrap().scroll;
+    app.handle_pr_overview_input(make_key(KeyCode::Char('j')), 10);
+    app.handle_pr_overview_input(make_key(KeyCode::Char('j')), 10);
+    app.handle_pr_overview_input(make_key(KeyCode::Char('k')), 10);
+    assert_eq!(scroll(&app), 1);
+    app.handle_pr_overview_input(make_ctrl_key('d'), 10);
+    assert_eq!(scroll(&app), 11);
+    app.handle_pr_overview_input(make_key(KeyCode::Char('g')), 10);
+    assert_eq!(scroll(&app), 0);
+
+    app.handle_pr_overview_input(make_key(KeyCode::Esc), 10);
+    assert_eq!(app.state, AppState::FileList);
+    assert!(app.pr_overview.is_none());
+}
+
+#[tokio::test]
+async fn test_pr_summary_uses_cached_summary_of_head() {
+    use crate::pr_summary::{PrSummary, SummaryItem};
+
+    let summary = |overview: &str| PrSummary {
+        overview: overview.to_string(),
+        risk_areas: Vec::new(),
+        review_order: vec![SummaryItem {
+            path: "test.rs".to_string(),
+            reason: "the only file".to_string(),
+        }],
+        breaking_changes: Vec::new(),
+    };
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    app.state = AppState::FileList;
+    app.pr_summaries
+        .insert((1, "abc123".to_string()), summary("cached"));
+    app.open_pr_summary();
+    assert_eq!(app.state, AppState::PrSummary);
+    assert_eq!(
+        app.pr_summary.as_ref().unwrap().summary,
+        Some(summary("cached"))
+    );
+    assert!(app.pr_summary_receiver.is_none());
+
+    // 別の head の結果は表示中の要約を置き換えない
+    let (tx, rx) = mpsc::channel(1);
+    tx.send(("def456".to_string(), Ok(summary("new head"))))
+        .await
+        .unwrap();
+    app.pr_summary_receiver = Some((1, rx));
+    app.poll_pr_summary_updates();
+    assert_eq!(
+        app.pr_summary.as_ref().unwrap().summary,
+        Some(summary("cached"))
+    );
+    assert!(app.pr_summaries.contains_key(&(1, "def456".to_string())));
+
+    app.handle_pr_summary_input(make_key(KeyCode::Char('q')), 10);
+    assert_eq!(app.state, AppState::FileList);
+    assert!(app.pr_summary.is_none());
+}
+
+#[test]
+fn test_pr_overview_is_not_available_in_local_mode() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    app.state = AppState::FileList;
+    app.local_mode = true;
+    app.open_pr_overview();
+    assert_eq!(app.state, AppState::FileList);
+    assert!(app.pr_overview.is_none());
+}
+
+#[test]
+fn test_pr_create_is_only_available_in_local_mode() {
+    let mut app = App::new_for_test();
+    app.local_mode = false;
+    app.open_pr_create();
+    assert!(app.pr_create.is_none());
+    assert!(app
+        .submission_result
+        .as_ref()
+        .unwrap()
+        .1
+        .contains("only available in local mode"));
+}
+
+#[tokio::test]
+async fn test_created_pr_switches_from_local_to_pr_mode() {
+    let (retry_tx, mut retry_rx) = mpsc::channel::<RefreshRequest>(4);
+    let (_data_tx, data_rx) = mpsc::channel(2);
+    let mut app = App::new_for_test();
+    app.retry_sender = Some(retry_tx);
+    app.data_receiver = Some((0, data_rx));
+    app.local_mode = true;
+    app.pr_number = Some(0);
+    app.original_pr_number = None;
+    app.selected_file = 2;
+
+    let (tx, rx) = mpsc::channel(1);
+    tx.send(Ok(super::pr_create::CreatedPr {
+        number: 42,
+        existed: false,
+    }))
+    .await
+    .unwrap();
+    app.pr_create_receiver = Some(rx);
+    app.poll_pr_create_updates();
+
+    assert!(!app.local_mode);
+    assert_eq!(app.pr_number, Some(42));
+    assert_eq!(app.original_pr_number, Some(42));
+    assert_eq!(app.state, AppState::FileList);
+    assert_eq!(app.selected_file, 0);
+    assert!(app.pr_create_receiver.is_none());
+    assert!(app.submission_result.as_ref().unwrap().1.contains("#42"));
+    assert!(matches!(
+        retry_rx.try_recv(),
+        Ok(RefreshRequest::PrRefresh { pr_number: 42, .. })
+    ));
+
+    // ローカルモードに戻ると、PR を作る前の表示位置から続けられる
+    app.toggle_local_mode();
+    assert!(app.local_mode);
+    assert_eq!(app.selected_file, 2);
+}
+
+#[tokio::test]
+async fn test_people_picker_lists_suggested_first_and_switches_target() {
+    let mut app = App::new_for_test();
+    app.open_people_picker();
+    assert!(app.people_picker.as_ref().unwrap().people.is_none());
+
+    let (tx, rx) = mpsc::channel(1);
+    let people = github::PrPeople {
+        suggested_reviewers: vec!["bob".to_string()],
+        requested_reviewers: vec!["carol".to_string()],
+        assignees: vec!["alice".to_string()],
+    };
+    let assignable = vec!["alice".to_string(), "bob".to_string(), "carol".to_string()];
+    tx.send(Ok((people, assignable))).await.unwrap();
+    app.people_receiver = Some((1, rx));
+    app.poll_people_updates();
+
+    let labels = |app: &App| -> Vec<(String, bool)> {
+        app.people_picker
+            .as_ref()
+            .unwrap()
+            .picker
+            .items()
+            .iter()
+            .map(|item| (item.label.clone(), item.checked))
+            .collect()
+    };
+    assert_eq!(
+        labels(&app),
+        [
+            ("bob".to_string(), false),
+            ("carol".to_string(), true),
+            ("alice".to_string(), false)
+        ]
+    );
+
+    app.handle_people_picker_input(&make_key(KeyCode::Tab));
+    assert_eq!(
+        app.people_picker.as_ref().unwrap().target,
+        PeopleTarget::Assignees
+    );
+    assert_eq!(
+        labels(&app),
+        [
+            ("alice".to_string(), true),
+            ("bob".to_string(), false),
+            ("carol".to_string(), false)
+        ]
+    );
+
+    app.handle_people_picker_input(&make_key(KeyCode::Esc));
+    assert!(app.people_picker.is_none());
+}
+
+#[tokio::test]
+async fn test_label_picker_toggles_and_applies_changes() {
+    let mut app = make_vendored_app();
+    if let DataState::Loaded { ref mut pr, .. } = app.data_state {
+        pr.labels = vec![github::Label {
+            name: "wip".to_string(),
+        }];
+    }
+    app.open_label_picker();
+
+    let (tx, rx) = mpsc::channel(1);
+    let label = |name: &str| github::RepoLabel {
+        name: name.to_string(),
+        color: "ededed".to_string(),
+        description: None,
+    };
+    tx.send(Ok(vec![label("bug"), label("wip")])).await.unwrap();
+    app.label_list_receiver = Some((1, rx));
+    app.poll_label_updates();
+
+    let state = app.label_picker.as_ref().unwrap();
+    let items: Vec<(&str, bool)> = state
+        .picker
+        .items()
+        .iter()
+        .map(|item| (item.label.as_str(), item.checked))
+        .collect();
+    assert_eq!(items, [("wip", true), ("bug", false)]);
+
+    // wip を外して bug を付ける
+    app.handle_label_picker_input(&make_key(KeyCode::Tab));
+    app.handle_label_picker_input(&make_key(KeyCode::Down));
+    app.handle_label_picker_input(&make_key(KeyCode::Tab));
+    app.handle_label_picker_input(&make_key(KeyCode::Enter));
+    assert!(app.label_picker.as_ref().unwrap().applying);
+
+    let (tx, rx) = mpsc::channel(1);
+    tx.send(Ok(vec!["bug".to_string()])).await.unwrap();
+    app.label_update_receiver = Some((1, rx));
+    app.poll_label_updates();
+    assert!(app.label_picker.is_none());
+    let labels: Vec<&str> = app
+        .pr()
+        .unwrap()
+        .labels
+        .iter()
+        .map(|l| l.name.as_str())
+        .collect();
+    assert_eq!(labels, ["bug"]);
+}
+
+#[test]
+fn test_wait_for_review_quits_after_review() {
+    let mut app = App::new_for_test();
+    app.record_review_outcome(ReviewAction::Comment);
+    assert_eq!(app.review_outcome(), Some(ReviewAction::Comment));
+    assert!(!app.should_quit);
+
+    let mut app = App::new_for_test();
+    app.set_wait_for_review(true);
+    assert_eq!(app.review_outcome(), None);
+    app.record_review_outcome(ReviewAction::Approve);
+    assert_eq!(app.review_outcome(), Some(ReviewAction::Approve));
+    assert!(app.should_quit);
+}
+
+fn make_local_data_root() -> tempfile::TempDir {
+    let root = tempfile::tempdir().unwrap();
+    for (dir, pr) in [("owner_repo_1", 1), ("owner_repo_2", 2)] {
+        let dir = root.path().join("rally").join(dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("session.json"),
+            format!(r#"{{"repo":"owner/repo","pr_number":{}}}"#, pr),
+        )
+        .unwrap();
+    }
+    root
+}
+
+#[tokio::test]
+async fn test_local_data_archive_restore_and_delete() {
+    let root = make_local_data_root();
+    let mut app = App::new_for_test();
+    app.state = AppState::PullRequestList;
+    app.open_local_data_at(root.path().to_path_buf());
+    assert_eq!(app.state, AppState::LocalData);
+    assert_eq!(app.local_data.as_ref().unwrap().artifacts.len(), 2);
+
+    // 通常のデータは直接削除できない
+    app.handle_local_data_input(make_key(KeyCode::Char('x')));
+    assert!(matches!(app.submission_result, Some((false, _))));
+
+    // Space で 2 件マークしてまとめてアーカイブ
+    app.handle_local_data_input(make_key(KeyCode::Char(' ')));
+    app.handle_local_data_input(make_key(KeyCode::Char(' ')));
+    app.handle_local_data_input(make_key(KeyCode::Char('d')));
+    assert_eq!(
+        app.submission_result,
+        Some((true, "Archived 2 item(s)".to_string()))
+    );
+    let view = app.local_data.as_ref().unwrap();
+    assert!(view.artifacts.iter().all(|a| a.archived));
+    assert!(view.marked.is_empty());
+    assert!(root.path().join("archive/rally/owner_repo_1").exists());
+
+    app.local_data.as_mut().unwrap().selected = 0;
+    app.handle_local_data_input(make_key(KeyCode::Char('u')));
+    assert!(root.path().join("rally/owner_repo_1/session.json").exists());
+
+    app.local_data.as_mut().unwrap().selected = 1;
+    app.handle_local_data_input(make_key(KeyCode::Char('x')));
+    assert!(!root.path().join("archive/rally/owner_repo_2").exists());
+    assert_eq!(app.local_data.as_ref().unwrap().artifacts.len(), 1);
+
+    app.handle_local_data_input(make_key(KeyCode::Char('q')));
+    assert_eq!(app.state, AppState::PullRequestList);
+    assert!(app.local_data.is_none());
+}
+
+#[tokio::test]
+async fn test_local_data_prunes_closed_prs() {
+    let root = make_local_data_root();
+    let mut app = App::new_for_test();
+    app.state = AppState::FileList;
+    app.open_local_data_at(root.path().to_path_buf());
+
+    // PR の状態を取得中はまだ消さない
+    app.handle_local_data_input(make_key(KeyCode::Char('p')));
+    assert!(matches!(app.submission_result, Some((false, _))));
+
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.local_data_receiver = Some(rx);
+    tx.try_send(HashMap::from([
+        (("owner/repo".to_string(), 1), true),
+        (("owner/repo".to_string(), 2), false),
+    ]))
+    .unwrap();
+    app.poll_local_data_updates();
+    assert!(!app.local_data.as_ref().unwrap().states_loading);
+
+    app.handle_local_data_input(make_key(KeyCode::Char('p')));
+    assert_eq!(
+        app.submission_result,
+        Some((true, "Archived 1 item(s)".to_string()))
+    );
+    assert!(root.path().join("archive/rally/owner_repo_1").exists());
+    assert!(root.path().join("rally/owner_repo_2").exists());
+}
+
+fn make_pr_summaries(numbers: &[u32]) -> Vec<crate::github::PullRequestSummary> {
+    numbers
+        .iter()
+        .map(|&number| crate::github::PullRequestSummary {
+            number,
+            title: format!("PR {}", number),
+            state: "open".to_string(),
+            author: crate::github::User {
+                login: "user".to_string(),
+            },
+            is_draft: false,
+            labels: vec![],
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_pr_coverage_requested_for_visible_rows_only() {
+    let mut app = App::new_for_test();
+    app.state = AppState::PullRequestList;
+    app.pr_list = Some(make_pr_summaries(&[10, 11, 12, 13, 14]));
+    app.pr_list_scroll_offset = 1;
+
+    app.request_visible_pr_coverage(2);
+    let mut requested: Vec<u32> = app.pr_coverage_requested.iter().copied().collect();
+    requested.sort();
+    assert_eq!(requested, vec![11, 12]);
+    assert!(app.pr_coverage_receiver.is_some());
+
+    // 取得中は次のバッチを投げない
+    app.pr_list_scroll_offset = 3;
+    app.request_visible_pr_coverage(2);
+    assert_eq!(app.pr_coverage_requested.len(), 2);
+
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.pr_coverage_receiver = Some(rx);
+    let coverage = crate::github::ReviewCoverage {
+        viewed: 1,
+        total: 4,
+    };
+    tx.try_send(Ok(HashMap::from([(11, coverage)]))).unwrap();
+    app.poll_pr_coverage_updates();
+    assert_eq!(app.pr_review_coverage.get(&11), Some(&coverage));
+    assert!(app.pr_coverage_receiver.is_none());
+
+    app.state = AppState::FileList;
+    app.request_visible_pr_coverage(2);
+    assert!(app.pr_coverage_receiver.is_none());
+}
+
+#[test]
+fn test_back_to_pr_list_refreshes_reviewed_pr_coverage() {
+    let mut app = App::new_for_test();
+    app.started_from_pr_list = true;
+    app.pr_number = Some(11);
+    app.state = AppState::FileList;
+    let coverage = crate::github::ReviewCoverage {
+        viewed: 1,
+        total: 4,
+    };
+    app.pr_review_coverage.insert(11, coverage);
+    app.pr_review_coverage.insert(12, coverage);
+    app.pr_coverage_requested.extend([11, 12]);
+
+    app.back_to_pr_list();
+    assert_eq!(app.state, AppState::PullRequestList);
+    assert!(!app.pr_review_coverage.contains_key(&11));
+    assert!(!app.pr_coverage_requested.contains(&11));
+    assert!(app.pr_review_coverage.contains_key(&12));
+}
+
+fn make_comparison(divergent: &[usize], len: usize) -> crate::compare::Comparison {
+    use crate::compare::{CompareRow, RowKind};
+    let rows = (0..len)
+        .map(|i| CompareRow {
+            kind: if divergent.contains(&i) {
+                RowKind::Changed
+            } else {
+                RowKind::Same
+            },
+            pr: Some((i + 1, format!("line {}", i))),
+            local: Some((i + 1, format!("line {}", i))),
+        })
+        .collect();
+    crate::compare::Comparison {
+        path: "src/a.rs".to_string(),
+        head_sha: "abc1234".to_string(),
+        rows,
+        pr_missing: false,
+        local_missing: false,
+    }
+}
+
+#[tokio::test]
+async fn test_local_compare_jumps_between_differences() {
+    let mut app = make_ipc_app();
+    app.state = AppState::DiffView;
+    app.open_local_compare();
+    assert_eq!(app.state, AppState::LocalCompare);
+
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.local_compare_receiver = Some(rx);
+    tx.try_send(Ok(make_comparison(&[10, 11, 30], 60))).unwrap();
+    app.poll_local_compare_updates();
+    // 最初の食い違いの 3 行前から表示する
+    assert_eq!(app.local_compare.as_ref().unwrap().scroll, 7);
+
+    app.handle_local_compare_input(make_key(KeyCode::Char('n')), 20);
+    assert_eq!(app.local_compare.as_ref().unwrap().scroll, 27);
+    app.handle_local_compare_input(make_key(KeyCode::Char('n')), 20);
+    assert_eq!(app.local_compare.as_ref().unwrap().scroll, 27);
+    app.handle_local_compare_input(make_key(KeyCode::Char('N')), 20);
+    assert_eq!(app.local_compare.as_ref().unwrap().scroll, 7);
+    app.handle_local_compare_input(make_key(KeyCode::Char('G')), 20);
+    assert_eq!(app.local_compare.as_ref().unwrap().scroll, 40);
+
+    app.handle_local_compare_input(make_key(KeyCode::Char('q')), 20);
+    assert!(app.local_compare.is_none());
+    assert_eq!(app.state, AppState::DiffView);
+}
+
+#[tokio::test]
+async fn test_local_compare_shows_load_error() {
+    let mut app = make_ipc_app();
+    app.state = AppState::DiffView;
+    app.open_local_compare();
+    let (tx, rx) = tokio::sync::mpsc::channel(1);
+    app.local_compare_receiver = Some(rx);
+    tx.try_send(Err("Not inside a local checkout".to_string()))
+        .unwrap();
+    app.poll_local_compare_updates();
+    let view = app.local_compare.as_ref().unwrap();
+    assert_eq!(view.error.as_deref(), Some("Not inside a local checkout"));
+    assert!(view.comparison.is_none());
+
+    app.handle_local_compare_input(make_key(KeyCode::Esc), 20);
+    assert_eq!(app.state, AppState::DiffView);
+}
+
+#[test]
+fn test_local_compare_unavailable_in_local_mode() {
+    let mut app = make_ipc_app();
+    app.local_mode = true;
+    app.state = AppState::DiffView;
+    app.open_local_compare();
+    assert_eq!(app.state, AppState::DiffView);
+    assert!(app.local_compare.is_none());
+}
+
+#[tokio::test]
+async fn test_path_scope_filters_files_and_comments() {
+    let mut config = Config::default();
+    config.scope.paths = vec!["services/api".to_string()];
+    let (mut app, _tx) = App::new_loading("owner/repo", 1, config);
+
+    let make_file = |name: &str| ChangedFile {
+        filename: name.to_string(),
+        status: "modified".to_string(),
+        additions: 1,
+        deletions: 0,
+        patch: Some("@@ -1 +1 @@\n+x".to_string()),
+        viewed: false,
+    };
+    app.handle_data_result(
+        1,
+        DataLoadResult::Success {
+            pr: Box::new(make_local_pr()),
+            files: vec![
+                make_file("services/api/main.go"),
+                make_file("services/web/index.ts"),
+                make_file("services/api/handler.go"),
+            ],
+        },
+    );
+    let names: Vec<_> = app.files().iter().map(|f| f.filename.as_str()).collect();
+    assert_eq!(
+        names,
+        vec!["services/api/main.go", "services/api/handler.go"]
+    );
+
+    let make_comment = |id: u64, path: &str| crate::github::comment::ReviewComment {
+        id,
+        path: path.to_string(),
+        line: Some(1),
+        start_line: None,
+        body: "nit".to_string(),
+        user: crate::github::User {
+            login: "reviewer".to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+    };
+    let (comment_tx, comment_rx) = mpsc::channel(1);
+    app.comment_receiver = Some((1, comment_rx));
+    comment_tx
+        .send(Ok(vec![
+            make_comment(1, "services/web/index.ts"),
+            make_comment(2, "services/api/main.go"),
+        ]))
+        .await
+        .unwrap();
+    app.poll_comment_updates();
+    let ids: Vec<u64> = app
+        .review_comments
+        .as_ref()
+        .unwrap()
+        .iter()
+        .map(|c| c.id)
+        .collect();
+    assert_eq!(ids, vec![2]);
+
+    let body = app.path_scope.review_body("LGTM", app.files());
+    assert!(body.ends_with("- `services/api/` (2 files)"));
+}
+
+#[test]
+fn test_rally_replay_steps_through_transcript() {
+    use crate::ai::orchestrator::RallyEvent;
+    use crate::ai::transcript::Transcript;
+    use crate::ai::RallyState;
+
+    let mut app = App::new_for_test();
+    app.state = AppState::FileList;
+    let transcript = Transcript::new(
+        "owner/repo",
+        1,
+        vec![
+            RallyEvent::IterationStarted(1),
+            RallyEvent::StateChanged(RallyState::ReviewerReviewing),
+            RallyEvent::Log("reviewing".to_string()),
+        ],
+    );
+
+    app.start_rally_replay(transcript);
+    assert_eq!(app.state, AppState::AiRally);
+    assert!(app.is_replaying_rally());
+
+    // Pause, then step manually through the existing event handler
+    app.toggle_rally_replay();
+    assert!(!app.rally_replay.as_ref().unwrap().playing);
+    app.step_rally_replay();
+    app.step_rally_replay();
+    app.poll_rally_events();
+
+    let rally_state = app.ai_rally_state.as_ref().unwrap();
+    assert_eq!(rally_state.iteration, 1);
+    assert_eq!(rally_state.state, RallyState::ReviewerReviewing);
+    assert_eq!(rally_state.history.len(), 2);
+    assert_eq!(app.rally_replay.as_ref().unwrap().position(), 2);
+
+    app.step_rally_replay();
+    app.poll_rally_events();
+    let replay = app.rally_replay.as_ref().unwrap();
+    assert!(replay.is_finished());
+    assert!(!replay.playing);
+    assert!(app
+        .ai_rally_state
+        .as_ref()
+        .unwrap()
+        .logs
+        .iter()
+        .any(|l| l.message == "reviewing"));
+
+    app.stop_rally_replay();
+    assert_eq!(app.state, AppState::FileList);
+    assert!(app.ai_rally_state.is_none());
+    assert!(!app.is_replaying_rally());
+}
+
+#[test]
+fn test_pr_watch_files_change_refreshes_without_reloading() {
+    use crate::pr_watch::PrChange;
+
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    let (retry_tx, mut retry_rx) = mpsc::channel(1);
+    app.retry_sender = Some(retry_tx);
+
+    app.apply_pr_changes(&[PrChange::Files, PrChange::Discussion]);
+
+    // Files only: a background PR refresh, without going back to Loading
+    assert!(matches!(
+        retry_rx.try_recv(),
+        Ok(RefreshRequest::PrRefresh { pr_number: 1, .. })
+    ));
+    assert!(matches!(app.data_state, DataState::Loaded { .. }));
+    // Comments that were never loaded are not fetched
+    assert!(app.discussion_comment_receiver.is_none());
+    assert_eq!(
+        app.submission_result,
+        Some((true, "PR updated: new commits, conversation".to_string()))
+    );
+}
+
+#[test]
+fn test_pr_watch_disabled_by_default() {
+    let mut app = make_app_with_patch("@@ -1 +1 @@\n-a\n+b");
+    app.ensure_pr_watch();
+    assert!(app.pr_watch.is_none());
+
+    app.config.watch.enabled = true;
+    app.local_mode = true;
+    app.ensure_pr_watch();
+    assert!(app.pr_watch.is_none());
+}
+
+fn make_two_file_app() -> App {
+    let patch: String = std::iter::once("@@ -1,20 +1,20 @@".to_string())
+        .chain((0..20).map(|i| format!("+line {}", i)))
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut app = make_app_with_patch(&patch);
+    if let DataState::Loaded { ref mut files, .. } = app.data_state {
+        let mut second = files[0].clone();
+        second.filename = "other.rs".to_string();
+        files.push(second);
+    }
+    app.local_mode = true;
+    app.state = AppState::SplitViewDiff;
+    app.sync_diff_to_selected_file();
+    app
+}
+
+#[tokio::test]
+async fn test_file_view_state_restored_when_returning_to_file() {
+    let mut app = make_two_file_app();
+    app.selected_line = 12;
+    app.scroll_offset = 4;
+    app.remember_file_view_state();
+
+    app.selected_file = 1;
+    app.sync_diff_to_selected_file();
+    assert_eq!(app.selected_line, 0);
+    assert_eq!(app.scroll_offset, 0);
+    app.selected_line = 3;
+    app.remember_file_view_state();
+
+    app.selected_file = 0;
+    app.sync_diff_to_selected_file();
+    assert_eq!(app.selected_line, 12);
+    assert_eq!(app.scroll_offset, 4);
+
+    app.selected_file = 1;
+    app.sync_diff_to_selected_file();
+    assert_eq!(app.selected_line, 3);
+}
+
+#[tokio::test]
+async fn test_file_view_state_disabled_by_config() {
+    let mut app = make_two_file_app();
+    app.config.diff.remember_position = false;
+    app.selected_line = 12;
+    app.remember_file_view_state();
+    assert!(app.file_view_states.is_empty());
+
+    app.selected_file = 1;
+    app.sync_diff_to_selected_file();
+    app.selected_file = 0;
+    app.sync_diff_to_selected_file();
+    assert_eq!(app.selected_line, 0);
+}
+
+#[tokio::test]
+async fn test_file_view_state_clamped_to_shorter_diff() {
+    let mut app = make_two_file_app();
+    app.file_view_states.insert(
+        "other.rs".to_string(),
+        FileViewState {
+            selected_line: 500,
+            scroll_offset: 490,
+            comment_panel_open: true,
+            ..Default::default()
+        },
+    );
+    app.selected_file = 1;
+    app.sync_diff_to_selected_file();
+    assert_eq!(app.selected_line, 20);
+    assert!(app.scroll_offset <= app.selected_line);
+    // No comments on that line, so the panel stays closed
+    assert!(!app.comment_panel_open);
+}
+
+#[test]
+fn test_learning_mode_hints_home_row_key_for_arrows() {
+    let mut app = make_ipc_app();
+    app.state = AppState::FileList;
+    app.config.learning.enabled = true;
+
+    app.record_key_usage(&make_key(KeyCode::Char('j')));
+    assert!(app.key_hint_text().is_none());
+
+    app.record_key_usage(&make_key(KeyCode::Down));
+    assert_eq!(app.key_hint_text(), Some("Down → j  (move down)"));
+
+    app.record_key_usage(&make_key(KeyCode::Down));
+    assert_eq!(app.key_usage.total(), 3);
+    assert_eq!(
+        app.key_usage.ranked(),
+        vec![("Down", 2, Some("j")), ("j", 1, None)]
+    );
+}
+
+#[test]
+fn test_learning_mode_hints_the_binding_of_the_current_view() {
+    use crate::keybinding::{KeyBinding, KeySequence};
+
+    let mut app = make_ipc_app();
+    app.config.learning.enabled = true;
+    app.config.keybindings.views.diff_view.insert(
+        "move_down".to_string(),
+        KeySequence::single(KeyBinding::char('e')),
+    );
+
+    app.state = AppState::DiffView;
+    app.record_key_usage(&make_key(KeyCode::Down));
+    assert_eq!(app.key_hint_text(), Some("Down → e  (move down)"));
+
+    app.state = AppState::FileList;
+    app.record_key_usage(&make_key(KeyCode::Down));
+    assert_eq!(app.key_hint_text(), Some("Down → j  (move down)"));
+}
+
+#[test]
+fn test_learning_mode_disabled_by_default() {
+    let mut app = make_ipc_app();
+    app.state = AppState::FileList;
+    app.record_key_usage(&make_key(KeyCode::Down));
+    assert!(app.key_hint_text().is_none());
+    assert_eq!(app.key_usage.total(), 0);
+}
+
+#[test]
+fn test_learning_mode_skips_hint_while_filtering() {
+    let mut app = make_ipc_app();
+    app.state = AppState::FileList;
+    app.config.learning.enabled = true;
+    app.file_list_filter = Some(crate::filter::ListFilter {
+        input_active: true,
+        ..Default::default()
+    });
+
+    app.record_key_usage(&make_key(KeyCode::Down));
+    assert!(app.key_hint_text().is_none());
+    assert_eq!(app.key_usage.total(), 1);
+}
+
+fn make_batch_reply_app() -> App {
+    let mut app = App::new_for_test();
+    app.review_comments = Some(vec![
+        make_fixup_comment(1, "src/a.rs", 10),
+        make_fixup_comment(2, "src/b.rs", 20),
+        make_fixup_comment(3, "src/c.rs", 30),
+    ]);
+    app.state = AppState::CommentList;
+    app.comment_tab = CommentTab::Review;
+    app
+}
+
+#[test]
+fn test_batch_reply_targets_marked_threads() {
+    let mut app = make_batch_reply_app();
+
+    app.toggle_review_comment_mark();
+    app.toggle_review_comment_mark();
+    // 2 件目のマークを外す
+    app.selected_comment = 1;
+    app.toggle_review_comment_mark();
+    app.selected_comment = 2;
+    app.toggle_review_comment_mark();
+    assert_eq!(app.marked_review_comments, HashSet::from([1, 3]));
+
+    app.enter_batch_reply_input();
+
+    assert_eq!(app.state, AppState::TextInput);
+    let Some(InputMode::BatchReply { targets }) = &app.input_mode else {
+        panic!("expected batch reply input");
+    };
+    let locations: Vec<&str> = targets.iter().map(|t| t.location.as_str()).collect();
+    assert_eq!(locations, vec!["src/a.rs:10", "src/c.rs:30"]);
+}
+
+#[test]
+fn test_batch_reply_without_marks_uses_selected_thread() {
+    let mut app = make_batch_reply_app();
+    app.selected_comment = 1;
+
+    app.enter_batch_reply_input();
+
+    let Some(InputMode::BatchReply { targets }) = &app.input_mode else {
+        panic!("expected batch reply input");
+    };
+    assert_eq!(targets.len(), 1);
+    assert_eq!(targets[0].comment_id, 2);
+}
+
+#[test]
+fn test_batch_reply_outcomes_keep_failed_threads_marked() {
+    let mut app = make_batch_reply_app();
+    app.marked_review_comments = HashSet::from([1, 2, 3]);
+    let target = |id: u64, location: &str| BatchReplyTarget {
+        comment_id: id,
+        user: "reviewer".to_string(),
+        location: location.to_string(),
+    };
+
+    app.apply_batch_reply_outcomes(&[
+        BatchReplyOutcome {
+            target: target(1, "src/a.rs:10"),
+            error: None,
+        },
+        BatchReplyOutcome {
+            target: target(2, "src/b.rs:20"),
+            error: Some("HTTP 422".to_string()),
+        },
+        BatchReplyOutcome {
+            target: target(3, "src/c.rs:30"),
+            error: None,
+        },
+    ]);
+
+    assert_eq!(app.marked_review_comments, HashSet::from([2]));
+    assert_eq!(
+        app.batch_reply_errors.get(&2).map(String::as_str),
+        Some("HTTP 422")
+    );
+    assert_eq!(
+        app.submission_result,
+        Some((
+            false,
+            "Replied to 2/3 thread(s); 1 failed (still marked), e.g. src/b.rs:20: HTTP 422"
+                .to_string()
+        ))
+    );
+}
+
+fn make_manifest_app() -> App {
+    let mut app = make_app_with_files(&[
+        ("src/main.rs", "modified", "@@ -1 +1 @@\n-a\n+b"),
+        (
+            "Cargo.toml",
+            "modified",
+            "@@ -1,2 +1,3 @@\n-serde = \"1.0.200\"\n+serde = \"1.0.228\"\n+foo = \"1.0.0\"",
+        ),
+    ]);
+    app.state = AppState::DiffView;
+    app
+}
+
+#[test]
+fn test_manifest_summary_follows_selected_file() {
+    let mut app = make_manifest_app();
+    app.refresh_manifest_summary();
+    assert!(app.manifest_summary().is_none());
+    assert_eq!(app.manifest_summary_rows(30), 0);
+
+    app.selected_file = 1;
+    // 再計算されるまでは前のファイルの要約を使わない
+    assert!(app.manifest_summary().is_none());
+    app.refresh_manifest_summary();
+    let summary = app.manifest_summary().unwrap();
+    assert_eq!(summary.count(crate::manifest_diff::ChangeKind::Added), 1);
+    assert_eq!(summary.count(crate::manifest_diff::ChangeKind::Upgraded), 1);
+    // ヘッダー + 2 件 + 枠、ただし diff 領域の 1/3 まで
+    assert_eq!(app.manifest_summary_rows(30), 5);
+    assert_eq!(app.manifest_summary_rows(9), 3);
+    // データベース未設定なら照会しない
+    assert!(app.advisory_db_receiver.is_none());
+    assert!(app.dependency_advisories(&summary.changes[0]).is_empty());
+}
+
+#[tokio::test]
+async fn test_manifest_advisories_loaded_from_configured_db() {
+    let dir = tempfile::tempdir().unwrap();
+    std::fs::write(
+        dir.path().join("RUSTSEC-2024-0001.json"),
+        r#"{"id": "RUSTSEC-2024-0001", "summary": "Use-after-free",
+            "affected": [{"package": {"ecosystem": "crates.io", "name": "foo"},
+                          "versions": ["1.0.0"]}]}"#,
+    )
+    .unwrap();
+    let mut app = make_manifest_app();
+    app.config.dependencies.advisory_db = Some(dir.path().display().to_string());
+    app.selected_file = 1;
+    app.refresh_manifest_summary();
+    assert!(app.advisory_db_receiver.is_some());
+
+    for _ in 0..100 {
+        app.poll_advisory_db_updates();
+        if app.advisory_db.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    let summary = app.manifest_summary().unwrap().clone();
+    let foo = summary.changes.iter().find(|c| c.name == "foo").unwrap();
+    let serde = summary.changes.iter().find(|c| c.name == "serde").unwrap();
+    let ids: Vec<&str> = app
+        .dependency_advisories(foo)
+        .iter()
+        .map(|a| a.id.as_str())
+        .collect();
+    assert_eq!(ids, vec!["RUSTSEC-2024-0001"]);
+    assert!(app.dependency_advisories(serde).is_empty());
+}
+
+#[tokio::test]
+async fn test_manifest_advisory_db_load_failure_is_reported_once() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = make_manifest_app();
+    app.config.dependencies.advisory_db = Some(dir.path().join("missing").display().to_string());
+    app.selected_file = 1;
+    app.refresh_manifest_summary();
+
+    for _ in 0..100 {
+        app.poll_advisory_db_updates();
+        if app.advisory_db.is_some() {
+            break;
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+    }
+    assert!(app.advisory_db.as_ref().is_some_and(|db| db.is_empty()));
+    assert!(matches!(app.submission_result, Some((false, _))));
+    // 失敗後は読み込み直さない
+    app.manifest_summary = None;
+    app.refresh_manifest_summary();
+    assert!(app.advisory_db_receiver.is_none());
+}
+
+fn make_review_draft_app() -> App {
+    let mut app = make_vendored_app();
+    app.pr_number = Some(7);
+    app.review_draft_mode = true;
+    app
+}
+
+fn draft_context(start_line_number: Option<u32>) -> LineInputContext {
+    LineInputContext {
+        file_index: 0,
+        line_number: 1,
+        diff_position: 2,
+        start_line_number,
+    }
+}
+
+#[test]
+fn test_review_draft_mode_queues_comments_instead_of_posting() {
+    let mut app = make_review_draft_app();
+    let submit = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+
+    app.input_mode = Some(InputMode::Comment(draft_context(None)));
+    app.input_text_area.set_content("nit: rename");
+    app.state = AppState::TextInput;
+    app.preview_return_state = AppState::DiffView;
+    app.handle_text_input(submit).unwrap();
+
+    app.input_mode = Some(InputMode::Suggestion {
+        context: draft_context(Some(1)),
+        original_code: "b".to_string(),
+    });
+    app.input_text_area.set_content("c\n");
+    app.handle_text_input(submit).unwrap();
+
+    // 即時投稿はせず、下書きに貯まる
+    assert!(app.comment_submit_receiver.is_none());
+    assert!(!app.comment_submitting);
+    assert_eq!(app.state, AppState::DiffView);
+    let drafts = app.review_drafts();
+    assert_eq!(drafts.len(), 2);
+    assert_eq!(drafts[0].path, "src/main.rs");
+    assert_eq!(drafts[0].body, "nit: rename");
+    assert_eq!(drafts[0].position, 2);
+    assert_eq!(drafts[0].start_line, None);
+    assert_eq!(drafts[1].body, "```suggestion\nc\n```");
+    assert_eq!(drafts[1].start_line, Some(1));
+
+    // 下書きは PR ごと
+    app.pr_number = Some(8);
+    assert!(app.review_drafts().is_empty());
+}
+
+#[tokio::test]
+async fn test_review_drafts_panel_discard_and_jump() {
+    let mut app = make_review_draft_app();
+    app.state = AppState::FileList;
+    app.open_review_drafts();
+    assert!(!app.review_drafts_open);
+    assert!(matches!(app.submission_result, Some((false, _))));
+
+    app.queue_review_draft(draft_context(None), "first".to_string());
+    app.queue_review_draft(draft_context(None), "second".to_string());
+    app.open_review_drafts();
+    assert!(app.review_drafts_open);
+
+    app.handle_review_drafts_input(&KeyEvent::new(KeyCode::Char('j'), KeyModifiers::NONE));
+    assert_eq!(app.selected_review_draft, 1);
+    app.handle_review_drafts_input(&KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
+    assert_eq!(app.review_drafts().len(), 1);
+    assert_eq!(app.review_drafts()[0].body, "first");
+    assert_eq!(app.selected_review_draft, 0);
+
+    // Enter でコメント位置の diff を開く
+    app.handle_review_drafts_input(&KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(!app.review_drafts_open);
+    assert_eq!(app.state, AppState::DiffView);
+    assert_eq!(app.diff_view_return_state, AppState::FileList);
+    assert_eq!(app.selected_file, 0);
+    assert_eq!(app.selected_line, 2);
+
+    // 最後の下書きを破棄すると一覧を閉じる
+    app.open_review_drafts();
+    app.handle_review_drafts_input(&KeyEvent::new(KeyCode::Char('d'), KeyModifiers::NONE));
+    assert!(app.review_drafts().is_empty());
+    assert!(!app.review_drafts_open);
+}
+
+#[test]
+fn test_toggle_review_draft_mode() {
+    let mut app = make_review_draft_app();
+    app.review_draft_mode = false;
+    app.toggle_review_draft_mode();
+    assert!(app.review_draft_mode);
+    app.queue_review_draft(draft_context(None), "kept".to_string());
+    app.toggle_review_draft_mode();
+    assert!(!app.review_draft_mode);
+    assert_eq!(
+        app.submission_result,
+        Some((
+            true,
+            "Draft mode off (1 pending comment(s) kept)".to_string()
+        ))
+    );
+
+    app.local_mode = true;
+    app.toggle_review_draft_mode();
+    assert!(!app.review_draft_mode);
+}
+
+#[tokio::test]
+async fn test_quit_with_unsent_drafts_asks_first() {
+    let mut app = make_review_draft_app();
+    app.state = AppState::FileList;
+    app.request_quit();
+    assert!(app.should_quit);
+
+    let mut app = make_review_draft_app();
+    app.state = AppState::FileList;
+    app.queue_review_draft(draft_context(None), "unsent".to_string());
+    app.request_quit();
+    assert!(!app.should_quit);
+    assert_eq!(app.focused_popup(), Some(PopupId::QuitSummary));
+    assert_eq!(app.quit_summary.as_ref().map(|s| s.unsent_drafts), Some(1));
+
+    // Esc で終了を取り消す
+    app.handle_quit_summary_input(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE))
+        .await
+        .unwrap();
+    assert!(app.quit_summary.is_none());
+    assert!(!app.should_quit);
+
+    app.request_quit();
+    app.handle_quit_summary_input(&KeyEvent::new(KeyCode::Char('q'), KeyModifiers::NONE))
+        .await
+        .unwrap();
+    assert!(app.should_quit);
+}
+
+fn make_commit(sha: &str) -> crate::github::PrCommit {
+    crate::github::PrCommit {
+        sha: sha.to_string(),
+        headline: "Fix typo".to_string(),
+        author: "alice".to_string(),
+        date: "2024-01-01T00:00:00Z".to_string(),
+        is_merge: false,
+    }
+}
+
+#[test]
+fn test_commit_view_swaps_files_and_restores_pr_files() {
+    let mut app = make_vendored_app();
+    app.state = AppState::FileList;
+    app.selected_file = 2;
+    let commit_file = ChangedFile {
+        filename: "README.md".to_string(),
+        status: "added".to_string(),
+        additions: 1,
+        deletions: 0,
+        patch: Some("@@ -0,0 +1 @@\n+hello".to_string()),
+        viewed: false,
+    };
+
+    app.enter_commit_view(make_commit("1111111aaaa"), vec![commit_file.clone()]);
+    assert_eq!(app.files().len(), 1);
+    assert_eq!(app.files()[0].filename, "README.md");
+    assert_eq!(app.selected_file, 0);
+    assert_eq!(app.state, AppState::FileList);
+    // コミット単体の diff にはコメントできない
+    assert!(app.is_file_preview(0));
+
+    // 別のコミットに切り替えても PR 全体のファイル一覧は保持される
+    app.enter_commit_view(make_commit("2222222bbbb"), vec![commit_file]);
+    assert_eq!(
+        app.commit_view.as_ref().map(|v| v.commit.sha.as_str()),
+        Some("2222222bbbb")
+    );
+
+    app.exit_commit_view();
+    assert!(app.commit_view.is_none());
+    assert_eq!(app.files().len(), 3);
+    assert_eq!(app.selected_file, 2);
+    assert!(!app.is_file_preview(0));
+}
+
+#[tokio::test]
+async fn test_switch_repo_reloads_pr_list_and_parks_drafts() {
+    let mut app = App::new_pr_list("owner/api", Config::default());
+    app.pr_list_loading = false;
+    app.pr_list = Some(vec![]);
+
+    // 切り替え先が 1 つしかなければ開かない
+    app.handle_pr_list_input(make_key(KeyCode::Char('W')))
+        .await
+        .unwrap();
+    assert!(app.repo_switcher.is_none());
+
+    app.set_workspace_repos(vec!["owner/api".to_string(), "owner/web".to_string()]);
+    app.pending_review.add(
+        1,
+        crate::github::DraftReviewComment {
+            path: "src/lib.rs".to_string(),
+            body: "nit".to_string(),
+            position: 1,
+            start_line: None,
+            line: 1,
+        },
+    );
+    app.handle_pr_list_input(make_key(KeyCode::Char('W')))
+        .await
+        .unwrap();
+    assert_eq!(app.focused_popup(), Some(PopupId::Repos));
+
+    // owner/web を選ぶ
+    app.handle_repo_switcher_input(&make_key(KeyCode::Down));
+    app.handle_repo_switcher_input(&make_key(KeyCode::Enter));
+    assert!(app.repo_switcher.is_none());
+    assert_eq!(app.repo, "owner/web");
+    assert!(app.pr_list.is_none());
+    assert!(app.pr_list_loading);
+    assert!(app.pending_review.comments(1).is_empty());
+
+    // 戻ると下書きも戻る
+    app.switch_repo("owner/api".to_string());
+    assert_eq!(app.repo, "owner/api");
+    assert_eq!(app.pending_review.comments(1).len(), 1);
+}
+
+fn type_diff_search(app: &mut App, text: &str) {
+    app.start_diff_search();
+    for c in text.chars() {
+        assert!(app.handle_diff_search_input(&make_key(KeyCode::Char(c)), 20));
+    }
+}
+
+#[tokio::test]
+async fn test_diff_search_jumps_between_matches_and_files() {
+    let mut app = make_finder_app();
+    app.local_mode = true;
+    app.state = AppState::DiffView;
+    app.sync_diff_to_selected_file();
+
+    // 表示中のファイルだけを検索し、端まで行ったら先頭に戻る
+    type_diff_search(&mut app, "MOD");
+    assert!(app.handle_diff_search_input(&make_key(KeyCode::Enter), 20));
+    // 大文字を含むので区別する
+    assert_eq!(app.diff_search.as_ref().unwrap().position, None);
+    assert!(app.submission_result.as_ref().unwrap().1.contains("not found"));
+
+    type_diff_search(&mut app, "mod");
+    app.handle_diff_search_input(&make_key(KeyCode::Enter), 20);
+    assert_eq!(app.selected_line, 1);
+    assert_eq!(app.diff_search.as_ref().unwrap().position, Some((0, 2)));
+    app.jump_to_search_match(true, 20);
+    assert_eq!(app.selected_line, 2);
+    app.jump_to_search_match(true, 20);
+    assert_eq!(app.selected_line, 1);
+    assert!(app.submission_result.as_ref().unwrap().1.contains("BOTTOM"));
+
+    // Tab で全ファイルが対象になり、次に一致するファイルを開く
+    type_diff_search(&mut app, "fn");
+    app.handle_diff_search_input(&make_key(KeyCode::Tab), 20);
+    app.handle_diff_search_input(&make_key(KeyCode::Enter), 20);
+    assert_eq!((app.selected_file, app.selected_line), (1, 3));
+    app.jump_to_search_match(true, 20);
+    assert_eq!((app.selected_file, app.selected_line), (2, 2));
+    app.jump_to_search_match(false, 20);
+    assert_eq!((app.selected_file, app.selected_line), (1, 3));
+
+    // 入力の取り消しでは前回の検索が残り、Esc で消える
+    type_diff_search(&mut app, "zzz");
+    app.handle_diff_search_input(&make_key(KeyCode::Esc), 20);
+    assert_eq!(
+        app.diff_search.as_ref().unwrap().query.as_ref().unwrap().text(),
+        "fn"
+    );
+    assert!(!app.handle_diff_search_input(&make_key(KeyCode::Esc), 20));
+    app.clear_diff_search();
+    assert!(!app.has_diff_search());
+}
+
+#[tokio::test]
+async fn test_grep_lists_matches_across_files_and_opens_selection() {
+    let mut app = make_finder_app();
+    app.open_grep();
+    assert!(app.is_popup_open(PopupId::Grep));
+    for c in "fn".chars() {
+        app.handle_grep_input(&make_key(KeyCode::Char(c)));
+    }
+    app.advance_grep();
+
+    let grep = app.grep.as_ref().unwrap();
+    assert!(!grep.is_searching());
+    assert_eq!(grep.file_count(), 2);
+    let found: Vec<(usize, Option<u32>)> = grep
+        .results
+        .iter()
+        .map(|m| (m.hit.file_index, m.line_number))
+        .collect();
+    assert_eq!(found, vec![(1, Some(3)), (2, Some(2))]);
+
+    app.handle_grep_input(&make_key(KeyCode::Down));
+    app.handle_grep_input(&make_key(KeyCode::Enter));
+    assert!(!app.is_popup_open(PopupId::Grep));
+    assert_eq!(app.state, AppState::SplitViewDiff);
+    assert_eq!(app.selected_file, 2);
+    assert_eq!(app.selected_line, 2);
+    let search = app.diff_search.as_ref().unwrap();
+    assert!(search.all_files);
+    assert_eq!(search.query.as_ref().unwrap().text(), "fn");
+}
+
+#[tokio::test]
+async fn test_file_list_follows_natural_order_and_sort_toggle() {
+    let mut app = App::new_for_test();
+    let file = |name: &str, additions: u32| ChangedFile {
+        filename: name.to_string(),
+        status: "modified".to_string(),
+        additions,
+        deletions: 0,
+        patch: None,
+        viewed: false,
+    };
+    app.data_state = DataState::Loaded {
+        pr: Box::new(make_local_pr()),
+        files: vec![
+            file("src/page10.rs", 1),
+            file("src/page2.rs", 9),
+            file("README.md", 5),
+        ],
+    };
+    app.sync_file_order();
+    assert_eq!(app.file_order(), &[1, 0, 2]);
+
+    app.selected_file = 1;
+    app.move_file_selection(1);
+    assert_eq!(app.selected_file, 0);
+    app.move_file_selection(5);
+    assert_eq!(app.selected_file, 2);
+
+    // フィルタ結果も一覧と同じ順に並ぶ
+    let mut filter = crate::filter::ListFilter::new();
+    filter.query = "page".to_string();
+    app.file_list_filter = Some(filter);
+    app.reapply_filter("file");
+    assert_eq!(app.file_list_filter.as_ref().unwrap().matched_indices, vec![1, 0]);
+
+    app.cycle_file_sort();
+    assert_eq!(app.file_sort(), crate::file_sort::FileSort::Additions);
+    assert_eq!(app.file_order(), &[1, 2, 0]);
+    assert_eq!(app.file_list_filter.as_ref().unwrap().matched_indices, vec![1, 0]);
+    app.file_list_filter = None;
+    app.selected_file = 2;
+    app.move_file_selection(-1);
+    assert_eq!(app.selected_file, 1);
+}
+
+#[tokio::test]
+async fn test_user_info_uses_cache_and_codeowners() {
+    let mut app = make_ipc_app();
+    app.state = AppState::CommentList;
+    app.comment_tab = CommentTab::Review;
+    app.review_comments = Some(vec![crate::github::comment::ReviewComment {
+        id: 10,
+        path: "src/b.rs".to_string(),
+        line: Some(10),
+        start_line: None,
+        body: "nit".to_string(),
+        user: crate::github::User {
+            login: "Alice".to_string(),
+        },
+        created_at: "2024-01-01T00:00:00Z".to_string(),
+    }]);
+    app.user_info_cache.insert(
+        (app.repo.clone(), "alice".to_string()),
+        crate::github::UserInfo {
+            login: "Alice".to_string(),
+            permission: Some("WRITE".to_string()),
+            ..Default::default()
+        },
+    );
+    app.codeowners_cache.insert(
+        app.repo.clone(),
+        Some(crate::codeowners::CodeOwners::parse("* @bob\nsrc/b.rs @alice\n")),
+    );
+
+    app.open_user_info();
+    assert_eq!(app.focused_popup(), Some(PopupId::UserInfo));
+    // キャッシュがあれば取得し直さない
+    assert!(app.user_info_receiver.is_none());
+    let state = app.user_info.as_ref().unwrap();
+    assert_eq!(state.login, "Alice");
+    assert!(state.info.as_ref().is_some_and(|info| info.is_maintainer()));
+    assert_eq!(state.owned_files, Some(vec!["src/b.rs".to_string()]));
+
+    app.handle_user_info_input(&KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(app.user_info.is_none());
+}
+
+#[tokio::test]
+async fn test_checkout_popup_requires_stash_for_dirty_tree() {
+    let dir = tempfile::tempdir().unwrap();
+    let git = |args: &[&str]| {
+        std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir.path())
+            .env("GIT_AUTHOR_NAME", "octorus-test")
+            .env("GIT_AUTHOR_EMAIL", "octorus-test@example.com")
+            .env("GIT_COMMITTER_NAME", "octorus-test")
+            .env("GIT_COMMITTER_EMAIL", "octorus-test@example.com")
+            .output()
+            .unwrap();
+    };
+    git(&["init", "-q"]);
+    std::fs::write(dir.path().join("a.txt"), "a\n").unwrap();
+    git(&["add", "a.txt"]);
+    git(&["commit", "-q", "-m", "init"]);
+    std::fs::write(dir.path().join("a.txt"), "changed\n").unwrap();
+
+    let mut app = make_ipc_app();
+    app.working_dir = Some(dir.path().display().to_string());
+    app.open_checkout();
+    assert_eq!(app.focused_popup(), Some(PopupId::Checkout));
+    let state = app.checkout.as_ref().unwrap();
+    assert_eq!(state.dirty, vec!["a.txt".to_string()]);
+
+    // 未コミットの変更があれば y では実行せず、s（stash）だけを受け付ける
+    app.handle_checkout_input(&make_key(KeyCode::Char('y')));
+    assert!(matches!(
+        app.checkout.as_ref().unwrap().phase,
+        CheckoutPhase::Confirm
+    ));
+    assert!(app.checkout_receiver.is_none());
+    app.handle_checkout_input(&make_key(KeyCode::Esc));
+    assert!(app.checkout.is_none());
+
+    // 結果はフッターにも出し、L でローカルモードに切り替えられる
+    app.open_checkout();
+    let (tx, rx) = mpsc::channel(1);
+    app.checkout_receiver = Some(rx);
+    app.checkout.as_mut().unwrap().phase = CheckoutPhase::Running;
+    tx.send(Ok(crate::checkout::CheckoutResult {
+        branch: "feature".to_string(),
+        update: crate::checkout::BranchUpdate::Created,
+        stashed: true,
+    }))
+    .await
+    .unwrap();
+    app.poll_checkout_updates();
+    assert!(app.checkout_receiver.is_none());
+    assert_eq!(
+        app.submission_result,
+        Some((
+            true,
+            "Checked out feature (created); local changes are in git stash".to_string()
+        ))
+    );
+    assert!(matches!(
+        app.checkout.as_ref().unwrap().phase,
+        CheckoutPhase::Done(Ok(_))
+    ));
+    app.handle_checkout_input(&make_key(KeyCode::Char('L')));
+    assert!(app.checkout.is_none());
+    assert!(app.local_mode);
+
+    app.open_checkout();
+    assert!(app.checkout.is_none());
+    assert_eq!(
+        app.submission_result,
+        Some((false, "Checkout is only available for a PR".to_string()))
+    );
+}
+
+#[test]
+fn test_should_refresh_local_change_ignores_access_events() {
+    let paths = vec![PathBuf::from("src/main.rs")];
+    let kind = EventKind::Access(AccessKind::Close(AccessMode::Write));
+
+    assert!(!should_refresh_local_change(&paths, &kind));
+}
+
+#[test]
+fn test_should_refresh_local_change_ignores_git_paths() {
+    let paths = vec![PathBuf::from(".git/HEAD"), PathBuf::from(".git/index.lock")];
+    let kind = EventKind::Create(CreateKind::File);
+
+    assert!(!should_refresh_local_change(&paths, &kind));
+}
+
+#[test]
+fn test_should_refresh_local_change_refreshes_subdir_change() {
+    let paths = vec![
+        PathBuf::from(".git/HEAD"),
+        PathBuf::from("src/subdir/changed.rs"),
+    ];
+    let kind = EventKind::Create(CreateKind::File);
+
+    assert!(should_refresh_local_change(&paths, &kind));
+}
+
+#[test]
+fn test_is_git_file_identifies_git_path() {
+    assert!(is_git_file(Path::new(".git/refs/heads/main")));
+    assert!(!is_git_file(Path::new("src/main.rs")));
+}
+
+#[test]
+fn test_should_refresh_local_change_ignores_octorus_paths() {
+    let paths = vec![
+        PathBuf::from(".octorus/config.toml"),
+        PathBuf::from(".octorus/prompts/reviewer.md"),
+    ];
+    let kind = EventKind::Create(CreateKind::File);
+
+    assert!(!should_refresh_local_change(&paths, &kind));
+}
+
+#[test]
+fn test_is_octorus_config_file() {
+    assert!(is_octorus_config_file(Path::new(".octorus/config.toml")));
+    assert!(is_octorus_config_file(Path::new(".octorus/prompts/reviewer.md")));
+    assert!(!is_octorus_config_file(Path::new("src/main.rs")));
+}
+
+#[tokio::test]
+async fn test_switch_local_dir_parks_and_restores_view() {
+    let mut app = make_finder_app();
+    app.local_mode = true;
+    app.pr_number = Some(0);
+    app.working_dir = Some("/src/api".to_string());
+    let cache_key = PrCacheKey {
+        repo: app.repo.clone(),
+        pr_number: 0,
+    };
+    let files = app.files().to_vec();
+    app.session_cache.put_pr_data(
+        cache_key.clone(),
+        PrData {
+            pr: Box::new(make_local_pr()),
+            files,
+            pr_updated_at: "x".to_string(),
+        },
+    );
+    app.selected_file = 2;
+
+    // 切り替え先がなければ開かない
+    app.set_local_repos(crate::local_repos::LocalRepoRegistry::default());
+    app.open_repo_switcher();
+    assert!(app.repo_switcher.is_none());
+
+    let mut registry = crate::local_repos::LocalRepoRegistry::default();
+    registry.record("/src/web");
+    app.set_local_repos(registry);
+    app.open_repo_switcher();
+    assert_eq!(app.focused_popup(), Some(PopupId::Repos));
+
+    // /src/web を選ぶと読み込み直しになる
+    app.handle_repo_switcher_input(&make_key(KeyCode::Down));
+    app.handle_repo_switcher_input(&make_key(KeyCode::Enter));
+    assert_eq!(app.working_dir.as_deref(), Some("/src/web"));
+    assert!(matches!(app.data_state, DataState::Loading));
+    assert_eq!(app.selected_file, 0);
+    assert!(app.session_cache.get_pr_data(&cache_key).is_none());
+
+    // 戻ると前の diff と選択位置が戻る
+    app.switch_local_dir("/src/api".to_string());
+    assert_eq!(app.working_dir.as_deref(), Some("/src/api"));
+    assert_eq!(app.files().len(), 3);
+    assert_eq!(app.selected_file, 2);
+    assert!(app.parked_local_repos.contains_key("/src/web"));
+}
+
+#[tokio::test]
+async fn test_review_progress_marks_and_notes_files() {
+    let dir = tempfile::tempdir().unwrap();
+    let mut app = make_two_file_app();
+    app.local_mode = false;
+    app.state = AppState::FileList;
+    app.review_progress = Some(crate::review_progress::ReviewProgress::load_from(
+        dir.path().join("progress.json"),
+    ));
+    assert_eq!(app.review_progress_counts(), Some((0, 2)));
+
+    app.selected_file = 1;
+    let mut terminal = ratatui::Terminal::new(ratatui::backend::TestBackend::new(80, 24)).unwrap();
+    app.handle_file_list_input(make_key(KeyCode::Char('m')), &mut terminal)
+        .await
+        .unwrap();
+    assert_eq!(app.review_progress_counts(), Some((1, 2)));
+    assert!(app.review_progress().unwrap().is_reviewed("other.rs"));
+
+    // gn でメモを書き、空で保存すると消える
+    let submit = KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL);
+    app.open_file_note();
+    assert_eq!(app.state, AppState::TextInput);
+    app.input_text_area.set_content("check the fallback");
+    app.handle_text_input(submit).unwrap();
+    assert_eq!(app.state, AppState::FileList);
+    assert_eq!(
+        app.review_progress().unwrap().note("other.rs"),
+        Some("check the fallback")
+    );
+    app.open_file_note();
+    assert_eq!(app.input_text_area.content(), "check the fallback");
+    app.input_text_area.set_content("");
+    app.handle_text_input(submit).unwrap();
+    assert_eq!(app.review_progress().unwrap().note("other.rs"), None);
+
+    // ローカルモードでは進捗を持たない
+    app.local_mode = true;
+    app.sync_review_progress();
+    assert!(app.review_progress().is_none());
+    app.toggle_file_reviewed();
+    assert_eq!(app.submission_result.as_ref().map(|(ok, _)| *ok), Some(false));
+}
+
+#[test]
+fn test_approve_and_next_walks_the_review_queue() {
+    use crate::github::PullRequestSummary;
+    let summary = |number: u32| PullRequestSummary {
+        number,
+        title: format!("PR {}", number),
+        state: "open".to_string(),
+        author: crate::github::User {
+            login: "user".to_string(),
+        },
+        is_draft: false,
+        labels: vec![],
+        updated_at: "2024-01-01T00:00:00Z".to_string(),
+    };
+    let mut app = App::new_for_test();
+    app.local_mode = false;
+    app.started_from_pr_list = true;
+    app.pr_list = Some(vec![summary(3), summary(5), summary(8)]);
+    app.set_review_queue_from_pr_list();
+    app.select_pr(5);
+    assert_eq!(app.review_queue_position(), Some((2, 3)));
+
+    // approve & next の途中でなければ進まない
+    app.advance_review_queue_after_approve();
+    assert_eq!(app.pr_number, Some(5));
+
+    app.approve_then_next = true;
+    app.advance_review_queue_after_approve();
+    assert_eq!(app.pr_number, Some(8));
+    assert_eq!(app.review_queue_position(), Some((3, 3)));
+    assert!(!app.approve_then_next);
+
+    // 最後の PR を承認すると一覧に戻る
+    app.approve_then_next = true;
+    app.advance_review_queue_after_approve();
+    assert_eq!(app.state, AppState::PullRequestList);
+}
+
+fn make_draft_app(state: &str) -> App {
+    let mut app = App::new_for_test();
+    app.pr_number = Some(1);
+    app.data_state = DataState::Loaded {
+        pr: Box::new(PullRequest {
+            number: 1,
+            state: state.to_string(),
+            draft: true,
+            ..make_local_pr()
+        }),
+        files: vec![],
+    };
+    app
+}
+
+#[test]
+fn test_draft_toggle_requires_an_open_pr() {
+    let mut app = make_draft_app("closed");
+    app.request_draft_toggle();
+    assert!(!app.is_pending_draft_toggle());
+    assert_eq!(
+        app.submission_result,
+        Some((
+            false,
+            "Only open PRs can change their draft status".to_string()
+        ))
+    );
+}
+
+#[tokio::test]
+async fn test_draft_toggle_cancelled_by_other_key() {
+    let mut app = make_draft_app("open");
+    app.request_draft_toggle();
+    assert_eq!(app.pending_draft_toggle, Some(false));
+    assert!(app
+        .draft_toggle_prompt()
+        .unwrap()
+        .starts_with("Mark this PR as ready for review?"));
+
+    app.confirm_draft_toggle(&make_key(KeyCode::Char('n')))
+        .await;
+    assert!(!app.is_pending_draft_toggle());
+    assert!(app.pr().unwrap().draft);
+    assert_eq!(
+        app.submission_result,
+        Some((false, "Draft status unchanged".to_string()))
+    );
+}
+
+#[tokio::test]
+async fn test_inbox_returns_to_pr_list_or_quits_when_started_with_it() {
+    let mut app = App::new_for_test();
+    app.state = AppState::PullRequestList;
+    app.open_inbox();
+    assert_eq!(app.state, AppState::Inbox);
+    app.handle_inbox_input(make_key(KeyCode::Esc)).await;
+    assert_eq!(app.state, AppState::PullRequestList);
+    assert!(app.inbox.is_none());
+    assert!(!app.should_quit);
+
+    // `or inbox` で起動したときは閉じると終了する
+    app.open_inbox_on_start();
+    app.handle_inbox_input(make_key(KeyCode::Char('q'))).await;
+    assert!(app.should_quit);
+}
+
+fn running_rally_state() -> AiRallyState {
+    AiRallyState {
+        iteration: 1,
+        max_iterations: 10,
+        state: crate::ai::RallyState::ReviewerReviewing,
+        history: vec![],
+        logs: vec![],
+        log_scroll_offset: 0,
+        selected_log_index: None,
+        showing_log_detail: false,
+        pending_question: None,
+        pending_permission: None,
+        pending_review_post: None,
+        pending_fix_post: None,
+        last_visible_log_height: 10,
+        usage: RallyUsage::default(),
+    }
+}
+
+#[test]
+fn test_pr_tabs_keep_view_state_per_pr() {
+    let mut app = App::new_for_test();
+    app.started_from_pr_list = true;
+    app.state = AppState::PullRequestList;
+
+    app.open_pr_tab(1, false);
+    app.selected_file = 3;
+    app.scroll_offset = 12;
+    app.state = AppState::DiffView;
+    app.back_to_pr_list();
+    assert_eq!(app.state, AppState::PullRequestList);
+
+    // 新しいタブで開いた PR は最初から
+    app.open_pr_tab(2, true);
+    assert_eq!(app.pr_tabs.prs(), &[1, 2]);
+    assert_eq!(app.pr_number, Some(2));
+    assert_eq!(app.selected_file, 0);
+    app.selected_file = 5;
+
+    // 元のタブに戻ると表示位置と画面が戻る
+    app.switch_pr_tab(0);
+    assert_eq!(app.pr_number, Some(1));
+    assert_eq!(app.state, AppState::DiffView);
+    assert_eq!(app.selected_file, 3);
+    assert_eq!(app.scroll_offset, 12);
+
+    app.switch_pr_tab(1);
+    assert_eq!(app.pr_number, Some(2));
+    assert_eq!(app.selected_file, 5);
+
+    // Enter で開くと表示中のタブを置き換える
+    app.back_to_pr_list();
+    app.open_pr_tab(3, false);
+    assert_eq!(app.pr_tabs.prs(), &[1, 3]);
+
+    // 閉じると隣のタブ、最後のタブなら PR 一覧に戻る
+    app.close_pr_tab();
+    assert_eq!(app.pr_tabs.prs(), &[1]);
+    assert_eq!(app.pr_number, Some(1));
+    assert_eq!(app.selected_file, 3);
+    app.close_pr_tab();
+    assert!(app.pr_tabs.prs().is_empty());
+    assert_eq!(app.state, AppState::PullRequestList);
+}
+
+#[test]
+fn test_pr_tabs_keep_running_rally_when_switching() {
+    let mut app = App::new_for_test();
+    app.started_from_pr_list = true;
+    app.state = AppState::PullRequestList;
+
+    app.open_pr_tab(1, false);
+    app.ai_rally_state = Some(running_rally_state());
+    let (event_tx, event_rx) = mpsc::channel(100);
+    app.rally_event_receiver = Some(event_rx);
+    app.back_to_pr_list();
+
+    // Rally のあるタブは置き換えず、新しいタブで開く
+    app.open_pr_tab(2, false);
+    assert_eq!(app.pr_tabs.prs(), &[1, 2]);
+    assert!(app.ai_rally_state.is_none());
+    assert!(app.pr_tabs.has_parked_rally(1));
+
+    // 表示していない間もイベントを取り込む
+    event_tx.try_send(RallyEvent::IterationStarted(2)).unwrap();
+    app.poll_parked_rallies();
+
+    // 動いている Rally のタブは閉じられない
+    app.switch_pr_tab(0);
+    assert_eq!(app.ai_rally_state.as_ref().unwrap().iteration, 2);
+    assert!(app.rally_event_receiver.is_some());
+    app.close_pr_tab();
+    assert_eq!(app.pr_tabs.prs(), &[1, 2]);
+    assert!(app.ai_rally_state.is_some());
+}
+
+#[test]
+fn test_rally_usage_is_tracked_per_iteration() {
+    use crate::ai::adapter::TokenUsage;
+
+    let mut app = App::new_for_test();
+    app.ai_rally_state = Some(running_rally_state());
+    let (event_tx, event_rx) = mpsc::channel(100);
+    app.rally_event_receiver = Some(event_rx);
+
+    let usage = |input_tokens, output_tokens, cost_usd| TokenUsage {
+        input_tokens,
+        output_tokens,
+        cost_usd,
+    };
+    for event in [
+        RallyEvent::UsageRecorded(usage(1_000, 200, Some(0.1))),
+        RallyEvent::UsageRecorded(usage(500, 100, None)),
+        RallyEvent::IterationStarted(2),
+        RallyEvent::UsageRecorded(usage(300, 50, Some(0.05))),
+    ] {
+        event_tx.try_send(event).unwrap();
+    }
+    app.poll_rally_events();
+
+    let usage = &app.ai_rally_state.as_ref().unwrap().usage;
+    assert_eq!(usage.total.total_tokens(), 2_150);
+    assert!((usage.total.cost_usd.unwrap() - 0.15).abs() < 1e-9);
+    assert_eq!(usage.per_iteration.len(), 2);
+    assert_eq!(usage.iteration(1).total_tokens(), 1_800);
+    assert_eq!(usage.iteration(2).total_tokens(), 350);
+    assert!(usage.iteration(3).is_empty());
+}
+
+#[test]
+fn test_review_summary_counts_items() {
+    let summary = ReviewSummary {
+        action: ReviewAction::RequestChanges,
+        unresolved_threads: 1,
+        thread_heads: Vec::new(),
+        drafts: Vec::new(),
+        unviewed_files: vec!["src/a.rs".to_string(), "src/b.rs".to_string()],
+    };
+    assert!(!summary.is_empty());
+    assert_eq!(summary.summary(), "1 unresolved thread, 2 unviewed files");
+
+    let clean = ReviewSummary {
+        unresolved_threads: 0,
+        unviewed_files: Vec::new(),
+        ..summary
+    };
+    assert!(clean.is_empty());
+}
@@ -0,0 +1,96 @@
+use std::time::Instant;
+
+use tokio::sync::mpsc;
+
+use super::types::*;
+use super::App;
+
+impl App {
+    /// コメントパネルで選択中のコメント（ID, 本文）
+    fn selected_inline_comment_body(&self) -> Option<(u64, String)> {
+        let indices = self.get_comment_indices_at_current_line();
+        let local_idx = self
+            .selected_inline_comment
+            .min(indices.len().checked_sub(1)?);
+        let comment = self.review_comments.as_ref()?.get(indices[local_idx])?;
+        Some((comment.id, comment.body.clone()))
+    }
+
+    /// 選択中のコメントを翻訳する。翻訳済みなら表示を取り消す
+    pub(crate) fn toggle_comment_translation(&mut self) {
+        let Some((comment_id, body)) = self.selected_inline_comment_body() else {
+            return;
+        };
+
+        match self.comment_translations.get(&comment_id) {
+            Some(CommentTranslation::Pending) => return,
+            Some(_) => {
+                self.comment_translations.remove(&comment_id);
+                return;
+            }
+            None => {}
+        }
+        if self.translate_receiver.is_some() {
+            self.set_translate_status(false, "Another translation is in progress".to_string());
+            return;
+        }
+
+        let Some(language) = self.config.translate.language.clone() else {
+            self.set_translate_status(
+                false,
+                "Set [translate] language in config.toml to enable translation".to_string(),
+            );
+            return;
+        };
+        let backend = match crate::translate::backend_from_config(&self.config, &self.repo) {
+            Ok(backend) => backend,
+            Err(e) => {
+                self.set_translate_status(false, format!("Translation unavailable: {}", e));
+                return;
+            }
+        };
+
+        self.comment_translations
+            .insert(comment_id, CommentTranslation::Pending);
+        let (tx, rx) = mpsc::channel(1);
+        self.translate_receiver = Some(rx);
+        tokio::spawn(async move {
+            let result = backend
+                .translate(&body, &language)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = tx.send((comment_id, result)).await;
+        });
+    }
+
+    pub(crate) fn poll_translate_updates(&mut self) {
+        let Some(ref mut rx) = self.translate_receiver else {
+            return;
+        };
+
+        match rx.try_recv() {
+            Ok((comment_id, result)) => {
+                self.translate_receiver = None;
+                let translation = match result {
+                    Ok(text) => CommentTranslation::Done(text),
+                    // パネルでは 1 行で表示する
+                    Err(e) => CommentTranslation::Failed(
+                        e.split_whitespace().collect::<Vec<_>>().join(" "),
+                    ),
+                };
+                self.comment_translations.insert(comment_id, translation);
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.translate_receiver = None;
+                self.comment_translations
+                    .retain(|_, t| *t != CommentTranslation::Pending);
+            }
+        }
+    }
+
+    fn set_translate_status(&mut self, ok: bool, message: String) {
+        self.submission_result = Some((ok, message));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
@@ -1,17 +1,20 @@
 use lasso::{Rodeo, Spur};
 use ratatui::style::Style;
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
+use crate::ai::adapter::TokenUsage;
 use crate::ai::orchestrator::RallyEvent;
 use crate::ai::RallyState;
-use crate::diff::LineType;
+use crate::diff::{LineType, ParseWarning};
 use crate::github::comment::{DiscussionComment, ReviewComment};
 use crate::github::{ChangedFile, PullRequest};
 
+pub use crate::github::ReviewAction;
+
 /// コメントのdiff内位置を表す構造体
 #[derive(Debug, Clone)]
 pub struct CommentPosition {
@@ -25,6 +28,8 @@ pub struct JumpLocation {
     pub file_index: usize,
     pub line_index: usize,
     pub scroll_offset: usize,
+    /// 記録したときに表示していたコミット単体の diff（PR 全体の diff なら None）
+    pub commit_sha: Option<String>,
 }
 
 /// シンボル選択ポップアップの状態
@@ -36,6 +41,68 @@ pub struct SymbolPopupState {
     pub selected: usize,
 }
 
+/// コメント本文の翻訳状態（コメント ID ごと）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommentTranslation {
+    Pending,
+    Done(String),
+    Failed(String),
+}
+
+/// ファジーファインダーの候補（ファイルまたはシンボル定義）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FinderItem {
+    /// マッチ対象の文字列（ファイルパスまたはシンボル名）
+    pub label: String,
+    pub file_index: usize,
+    /// シンボルの場合は定義行の diff 行インデックス
+    pub diff_line: Option<usize>,
+}
+
+/// ファジーファインダー（Ctrl-f）の状態
+#[derive(Debug, Clone)]
+pub struct FileFinderState {
+    pub query: String,
+    pub items: Vec<FinderItem>,
+    /// スコア順の (items のインデックス, label 内の一致位置)
+    pub matches: Vec<(usize, Vec<usize>)>,
+    pub selected: usize,
+}
+
+/// ファイルごとに覚えておく diff 表示位置（`[diff] remember_position`）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FileViewState {
+    pub selected_line: usize,
+    pub scroll_offset: usize,
+    /// その行でコメントパネルを開いていたか
+    pub comment_panel_open: bool,
+    pub comment_panel_scroll: u16,
+    pub selected_inline_comment: usize,
+}
+
+/// セッション操作履歴（監査ログ）パネルの状態
+#[derive(Debug, Clone)]
+pub struct SessionActivityState {
+    /// このセッションで記録されたエントリ（古い順）
+    pub entries: Vec<crate::audit::AuditEntry>,
+    pub scroll: usize,
+}
+
+/// fixup コミット支援ポップアップの状態（ローカルモード）
+#[derive(Debug, Clone)]
+pub struct FixupPopupState {
+    /// コメントごとにまとめた hunk
+    pub groups: Vec<crate::fixup::FixupGroup>,
+    /// グループごとのコミット対象フラグ
+    pub checked: Vec<bool>,
+    /// 選択中のインデックス
+    pub selected: usize,
+    /// どのコメントにも紐付かなかった hunk 数
+    pub unassigned: usize,
+    /// rebase 先のベースブランチ
+    pub base_branch: String,
+}
+
 /// インターン済みの Span（アロケーション削減）
 ///
 /// 文字列をインターナーに格納し、4バイトの Spur で参照することで
@@ -71,6 +138,11 @@ pub struct DiffCache {
     pub highlighted: bool,
     /// Markdown リッチ表示モードで構築されたかどうか
     pub markdown_rich: bool,
+    /// patch のパース中に見つかった問題（壊れたハンクヘッダーなど）
+    pub parse_warnings: Vec<ParseWarning>,
+    /// 大きな patch のプレーンキャッシュで、表示位置の周りだけ先にハイライトしたチャンク
+    /// （[`crate::ui::diff_view::LAZY_HIGHLIGHT_CHUNK`] 行ごと）。対象外なら空
+    pub highlighted_chunks: Vec<bool>,
 }
 
 impl DiffCache {
@@ -134,6 +206,36 @@ pub enum InputMode {
         reply_to_user: String,
         reply_to_body: String,
     },
+    /// 複数スレッドへの同じ返信
+    BatchReply {
+        targets: Vec<BatchReplyTarget>,
+    },
+    /// 放置されている PR へのリマインド（PR の会話タブに投稿）
+    Nudge {
+        pr_number: u32,
+        title: String,
+        days: i64,
+    },
+    /// レビューアジェンダ（PR の会話タブに投稿）
+    ReviewAgenda { pr_number: u32 },
+    /// ファイルごとのメモ（ローカルにのみ保存）
+    FileNote { filename: String },
+}
+
+/// 一括返信の対象スレッド
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchReplyTarget {
+    pub comment_id: u64,
+    pub user: String,
+    /// `path:line`
+    pub location: String,
+}
+
+/// 一括返信の 1 スレッド分の結果
+#[derive(Debug, Clone)]
+pub struct BatchReplyOutcome {
+    pub target: BatchReplyTarget,
+    pub error: Option<String>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -147,6 +249,22 @@ pub enum AppState {
     AiRally,
     SplitViewFileList,
     SplitViewDiff,
+    /// PR の head 版と作業ツリー版の比較
+    LocalCompare,
+    /// ローカルに保存したデータ（Rally セッション・ログ）の管理
+    LocalData,
+    /// PR のコミット一覧
+    CommitList,
+    /// head コミットの CI チェック一覧（失敗ジョブのログ）
+    Checks,
+    /// 作業ツリーのコンフリクト解消（Local Mode）
+    ConflictResolve,
+    /// PR の概要（本文・ラベル・レビュアー・リンクされた Issue）
+    PrOverview,
+    /// AI による PR の要約（リスクのある箇所・読む順番・破壊的変更）
+    PrSummary,
+    /// 自分宛ての PR の通知の受信箱
+    Inbox,
 }
 
 /// Variant for diff view handling (fullscreen vs split pane)
@@ -174,6 +292,7 @@ pub enum LogEventType {
 /// Structured log entry for AI Rally
 #[derive(Debug, Clone)]
 pub struct LogEntry {
+    /// RFC 3339 (UTC)。表示時に `TimeFormatter` で整形する
     pub timestamp: String,
     pub event_type: LogEventType,
     pub message: String,
@@ -181,9 +300,8 @@ pub struct LogEntry {
 
 impl LogEntry {
     pub fn new(event_type: LogEventType, message: String) -> Self {
-        let now = chrono::Local::now();
         Self {
-            timestamp: now.format("%H:%M:%S").to_string(),
+            timestamp: chrono::Utc::now().to_rfc3339(),
             event_type,
             message,
         }
@@ -220,6 +338,36 @@ pub struct AiRallyState {
     pub pending_fix_post: Option<crate::ai::orchestrator::FixPostInfo>,
     /// Last rendered visible log height (updated by UI render)
     pub last_visible_log_height: usize,
+    /// Tokens and cost reported by the agents
+    pub usage: RallyUsage,
+}
+
+/// Token usage of an AI Rally, in total and per iteration
+#[derive(Debug, Clone, Default)]
+pub struct RallyUsage {
+    pub total: TokenUsage,
+    /// (iteration, usage) in iteration order
+    pub per_iteration: Vec<(u32, TokenUsage)>,
+}
+
+impl RallyUsage {
+    /// Add usage reported during `iteration`
+    pub fn record(&mut self, iteration: u32, usage: &TokenUsage) {
+        self.total.add(usage);
+        match self.per_iteration.last_mut() {
+            Some((last, current)) if *last == iteration => current.add(usage),
+            _ => self.per_iteration.push((iteration, *usage)),
+        }
+    }
+
+    /// Usage of `iteration` so far
+    pub fn iteration(&self, iteration: u32) -> TokenUsage {
+        self.per_iteration
+            .iter()
+            .find(|(i, _)| *i == iteration)
+            .map(|(_, usage)| *usage)
+            .unwrap_or_default()
+    }
 }
 
 impl AiRallyState {
@@ -248,13 +396,6 @@ impl AiRallyState {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq)]
-pub enum ReviewAction {
-    Approve,
-    RequestChanges,
-    Comment,
-}
-
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub(super) enum PendingApproveChoice {
     Ignore,
@@ -279,13 +420,20 @@ pub enum CommentTab {
 /// リトライリクエストの種類（統一リトライループで使用）
 #[derive(Debug, Clone)]
 pub enum RefreshRequest {
-    PrRefresh { pr_number: u32 },
-    LocalRefresh,
+    /// `repo` はリポジトリを切り替えたときに読み込み先を追従させるため
+    PrRefresh {
+        repo: String,
+        pr_number: u32,
+    },
+    /// `working_dir` はローカルモードでディレクトリを切り替えたときに読み込み先を追従させるため
+    LocalRefresh { working_dir: Option<String> },
 }
 
 /// PRファイルの viewed 変更結果
 #[derive(Debug, Clone)]
 pub(super) enum MarkViewedResult {
+    /// 途中経過（処理済みのファイル数）
+    Progress { done: usize },
     Completed {
         marked_paths: Vec<String>,
         total_targets: usize,
@@ -294,6 +442,28 @@ pub(super) enum MarkViewedResult {
     },
 }
 
+/// Discussion コメントのタスク切り替え結果
+#[derive(Debug)]
+pub(super) struct TaskToggleResult {
+    /// 取得した認証ユーザー（次回以降の判定に使う）
+    pub viewer_login: Option<String>,
+    /// 更新後のコメントと、チェックを付けたか
+    pub result: Result<(DiscussionComment, bool), String>,
+}
+
+/// ファイルの多い PR で、patch を files API のページ単位で後から取得した状況
+#[derive(Debug, Clone, Default)]
+pub(super) struct LazyPatchPages {
+    /// files API のページ数
+    pub page_count: usize,
+    /// 取得を始めたページ（1 始まり）
+    pub requested: HashSet<usize>,
+    /// 取得の終わったページ
+    pub loaded: HashSet<usize>,
+    /// 取得の終わったページにあったファイル（patch がなくても取り直さない）
+    pub fetched_files: HashSet<String>,
+}
+
 /// ファイルウォッチャーのハンドル
 ///
 /// `active` フラグで callback の処理を制御する。
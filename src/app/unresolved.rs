@@ -0,0 +1,108 @@
+use std::time::Instant;
+
+use super::App;
+
+impl App {
+    fn set_unresolved_message(&mut self, success: bool, message: impl Into<String>) {
+        self.submission_result = Some((success, message.into()));
+        self.submission_result_time = Some(Instant::now());
+    }
+
+    /// 未解決のスレッドの最初のコメント（`review_comments` の添字）。
+    /// コメントかスレッドの状態をまだ取得していなければ None
+    pub fn unresolved_thread_heads(&self) -> Option<Vec<usize>> {
+        let comments = self.review_comments.as_ref()?;
+        if !comments.is_empty()
+            && !comments
+                .iter()
+                .any(|c| self.review_thread_states.contains_key(&c.id))
+        {
+            return None;
+        }
+        Some(
+            comments
+                .iter()
+                .enumerate()
+                .filter(|(_, c)| {
+                    self.review_thread_states
+                        .get(&c.id)
+                        .is_some_and(|state| state.thread_id == c.id && !state.resolved)
+                })
+                .map(|(i, _)| i)
+                .collect(),
+        )
+    }
+
+    /// ヘッダーに表示する未解決のスレッド数
+    pub fn unresolved_thread_count(&self) -> Option<usize> {
+        self.unresolved_thread_heads().map(|heads| heads.len())
+    }
+
+    /// 未解決のスレッドのコメントか
+    pub(crate) fn is_in_unresolved_thread(&self, comment_id: u64) -> bool {
+        self.review_thread_states
+            .get(&comment_id)
+            .is_some_and(|state| !state.resolved)
+    }
+
+    /// コメント一覧の Review タブを未解決のスレッドだけに絞り込む
+    pub(crate) fn toggle_unresolved_only(&mut self) {
+        self.comment_list_unresolved_only = !self.comment_list_unresolved_only;
+        self.normalize_comment_list_selection();
+    }
+
+    /// diff で次の未解決のスレッドへ移る。今のファイルになければ次のファイルへ進み、
+    /// 最後まで行けば先頭に戻る
+    pub(crate) fn jump_to_next_unresolved_thread(&mut self) {
+        if self.commit_view.is_some() {
+            self.set_unresolved_message(false, "Unresolved threads are shown on the PR diff");
+            return;
+        }
+        let Some(heads) = self.unresolved_thread_heads() else {
+            self.set_unresolved_message(false, "Review threads are still loading");
+            return;
+        };
+        let Some(ref comments) = self.review_comments else {
+            return;
+        };
+        let files = self.files();
+        // 行が残っているスレッドの (ファイル, diff の行)
+        let mut targets: Vec<(usize, usize)> = heads
+            .iter()
+            .filter_map(|&i| {
+                let comment = comments.get(i)?;
+                let file_index = files.iter().position(|f| f.filename == comment.path)?;
+                let patch = files[file_index].patch.as_deref()?;
+                let line_index = Self::find_diff_line_index(patch, comment.line?)?;
+                Some((file_index, line_index))
+            })
+            .collect();
+        targets.sort_unstable();
+        targets.dedup();
+        if targets.is_empty() {
+            self.set_unresolved_message(true, "No unresolved threads in the diff");
+            return;
+        }
+
+        let current = (self.selected_file, self.selected_line);
+        let position = targets
+            .iter()
+            .position(|&target| target > current)
+            .unwrap_or(0);
+        let (file_index, line_index) = targets[position];
+        if file_index != self.selected_file {
+            self.selected_file = file_index;
+            self.update_diff_line_count();
+            self.update_file_comment_positions();
+            self.ensure_diff_cache();
+        }
+        self.selected_line = line_index;
+        self.scroll_offset = line_index;
+        self.comment_panel_scroll = 0;
+        self.selected_inline_comment = 0;
+        self.set_unresolved_message(
+            true,
+            format!("Unresolved thread {}/{}", position + 1, targets.len()),
+        );
+    }
+}
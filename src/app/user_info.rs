@@ -0,0 +1,160 @@
+use std::time::Instant;
+
+use crossterm::event::{KeyCode, KeyEvent};
+use tokio::sync::mpsc;
+
+use crate::codeowners::{CodeOwners, CODEOWNERS_PATHS};
+use crate::github::{self, UserInfo};
+use crate::ui::popup::PopupKey;
+
+use super::types::*;
+use super::{App, PopupId};
+
+/// ユーザー情報の読み込み結果（ログイン名、情報、まだ読んでいなければ CODEOWNERS）
+pub(crate) type UserInfoLoadResult = (String, Result<UserInfo, String>, Option<Option<CodeOwners>>);
+
+/// コメントの作成者の情報のポップアップ
+#[derive(Debug, Clone)]
+pub struct UserInfoState {
+    pub login: String,
+    /// 読み込み中は None
+    pub info: Option<UserInfo>,
+    pub error: Option<String>,
+    /// 変更されたファイルのうち、このユーザーが CODEOWNER のもの。CODEOWNERS がなければ None
+    pub owned_files: Option<Vec<String>>,
+}
+
+impl App {
+    /// u: 選択中のコメントの作成者の情報を開く。取得済みならキャッシュを使う
+    pub(crate) fn open_user_info(&mut self) {
+        let login = match self.comment_tab {
+            CommentTab::Review => self
+                .review_comments
+                .as_ref()
+                .and_then(|c| c.get(self.selected_comment))
+                .map(|c| c.user.login.clone()),
+            CommentTab::Discussion => self
+                .discussion_comments
+                .as_ref()
+                .and_then(|c| c.get(self.selected_discussion_comment))
+                .map(|c| c.user.login.clone()),
+        };
+        let Some(login) = login else {
+            return;
+        };
+        if self.local_mode {
+            self.submission_result =
+                Some((false, "User info is not available in local mode".into()));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+
+        let cache_key = (self.repo.clone(), login.to_ascii_lowercase());
+        let cached = self.user_info_cache.get(&cache_key).cloned();
+        let has_codeowners = self.codeowners_cache.contains_key(&self.repo);
+        self.user_info = Some(UserInfoState {
+            login: login.clone(),
+            info: cached.clone(),
+            error: None,
+            owned_files: None,
+        });
+        self.push_popup(PopupId::UserInfo);
+        if cached.is_some() && has_codeowners {
+            self.refresh_user_owned_files();
+            return;
+        }
+
+        let (tx, rx) = mpsc::channel(1);
+        self.user_info_receiver = Some(rx);
+        let repo = self.repo.clone();
+        let base = self.pr().map(|pr| pr.base.sha.clone());
+        tokio::spawn(async move {
+            let info = match cached {
+                Some(info) => Ok(info),
+                None => github::fetch_user_info(&repo, &login)
+                    .await
+                    .map_err(|e| format!("{:#}", e)),
+            };
+            let codeowners = match base {
+                Some(base) if !has_codeowners => Some(fetch_codeowners(&repo, &base).await),
+                _ => None,
+            };
+            let _ = tx.send((login, info, codeowners)).await;
+        });
+    }
+
+    pub(crate) fn poll_user_info_updates(&mut self) {
+        let Some(ref mut rx) = self.user_info_receiver else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok((login, info, codeowners)) => {
+                self.user_info_receiver = None;
+                if let Some(codeowners) = codeowners {
+                    self.codeowners_cache.insert(self.repo.clone(), codeowners);
+                }
+                if let Ok(ref info) = info {
+                    self.user_info_cache.insert(
+                        (self.repo.clone(), login.to_ascii_lowercase()),
+                        info.clone(),
+                    );
+                }
+                // 読み込み中に別のユーザーを開いた場合は結果をキャッシュするだけ
+                let Some(ref mut state) = self.user_info else {
+                    return;
+                };
+                if state.login != login {
+                    return;
+                }
+                match info {
+                    Ok(info) => state.info = Some(info),
+                    Err(e) => state.error = Some(e),
+                }
+                self.refresh_user_owned_files();
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+            Err(mpsc::error::TryRecvError::Disconnected) => {
+                self.user_info_receiver = None;
+            }
+        }
+    }
+
+    /// 変更されたファイルのうち、表示中のユーザーが CODEOWNER のものを求める
+    fn refresh_user_owned_files(&mut self) {
+        let Some(login) = self.user_info.as_ref().map(|s| s.login.clone()) else {
+            return;
+        };
+        let owned = self
+            .codeowners_cache
+            .get(&self.repo)
+            .and_then(Option::as_ref)
+            .map(|codeowners| {
+                let paths: Vec<&str> = self.files().iter().map(|f| f.filename.as_str()).collect();
+                codeowners
+                    .owned_by(&login, &paths)
+                    .into_iter()
+                    .map(String::from)
+                    .collect()
+            });
+        if let Some(ref mut state) = self.user_info {
+            state.owned_files = owned;
+        }
+    }
+
+    pub(crate) fn handle_user_info_input(&mut self, key: &KeyEvent) {
+        let action = PopupKey::from_key(key, &self.config.keybindings);
+        if action == PopupKey::Close || key.code == KeyCode::Char('u') {
+            self.close_popup(PopupId::UserInfo);
+        }
+    }
+}
+
+/// base 時点の CODEOWNERS を GitHub と同じ順に探す。見つからなければ None
+async fn fetch_codeowners(repo: &str, rev: &str) -> Option<CodeOwners> {
+    for path in CODEOWNERS_PATHS {
+        if let Ok(text) = crate::file_content::fetch_from_github(repo, rev, path).await {
+            return Some(CodeOwners::parse(&text));
+        }
+    }
+    None
+}
@@ -0,0 +1,57 @@
+use crossterm::event::KeyEvent;
+
+use crate::ui::popup::PopupKey;
+use crate::vendored;
+
+use super::{App, PopupId};
+
+impl App {
+    /// vendored 依存のファイルで、diff を折りたたんで表示するか。
+    /// ライセンスファイルは見落とさないよう常に展開する
+    pub fn is_vendored_collapsed(&self, file_index: usize) -> bool {
+        self.files().get(file_index).is_some_and(|f| {
+            vendored::is_vendored(&f.filename)
+                && !vendored::is_license_file(&f.filename)
+                && !self.vendored_expanded.contains(&f.filename)
+        })
+    }
+
+    /// diff ヘッダーに添える vendored 表示
+    pub(crate) fn vendored_label(&self, file_index: usize) -> Option<&'static str> {
+        let file = self.files().get(file_index)?;
+        if !vendored::is_vendored(&file.filename) {
+            return None;
+        }
+        if vendored::is_license_file(&file.filename) {
+            Some("vendored licence")
+        } else if self.is_vendored_collapsed(file_index) {
+            Some("vendored, collapsed")
+        } else {
+            Some("vendored")
+        }
+    }
+
+    /// 選択中の vendored ファイルの diff を展開/折りたたむ
+    pub(crate) fn toggle_vendored_expand(&mut self) {
+        let Some(file) = self.files().get(self.selected_file) else {
+            return;
+        };
+        if !vendored::is_vendored(&file.filename) || vendored::is_license_file(&file.filename) {
+            return;
+        }
+        let filename = file.filename.clone();
+        if !self.vendored_expanded.remove(&filename) {
+            self.vendored_expanded.insert(filename);
+        }
+        self.selected_line = 0;
+        self.scroll_offset = 0;
+    }
+
+    pub(crate) fn handle_vendored_summary_input(&mut self, key: &KeyEvent) {
+        let kb = &self.config.keybindings;
+        if PopupKey::from_key(key, kb) == PopupKey::Close || self.matches_single_key(key, &kb.help)
+        {
+            self.close_popup(PopupId::VendoredSummary);
+        }
+    }
+}
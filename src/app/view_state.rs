@@ -0,0 +1,63 @@
+use super::types::FileViewState;
+use super::{App, AppState, DataState};
+
+impl App {
+    /// diff を表示中なら、選択中ファイルの表示位置を記録する（毎フレーム呼ばれる）
+    pub(crate) fn remember_file_view_state(&mut self) {
+        if !self.config.diff.remember_position
+            || !matches!(
+                self.state,
+                AppState::DiffView | AppState::SplitViewDiff | AppState::SplitViewFileList
+            )
+        {
+            return;
+        }
+        let current = FileViewState {
+            selected_line: self.selected_line,
+            scroll_offset: self.scroll_offset,
+            comment_panel_open: self.comment_panel_open,
+            comment_panel_scroll: self.comment_panel_scroll,
+            selected_inline_comment: self.selected_inline_comment,
+        };
+        // files() 経由だと self 全体を借用するため、data_state を直接参照する
+        let DataState::Loaded { ref files, .. } = self.data_state else {
+            return;
+        };
+        let Some(file) = files.get(self.selected_file) else {
+            return;
+        };
+        match self.file_view_states.get_mut(&file.filename) {
+            Some(state) => *state = current,
+            None => {
+                self.file_view_states.insert(file.filename.clone(), current);
+            }
+        }
+    }
+
+    /// 選択中ファイルの記録済み位置を復元する。記録がなければ何もしない
+    pub(crate) fn restore_file_view_state(&mut self) {
+        if !self.config.diff.remember_position {
+            return;
+        }
+        let Some(state) = self
+            .files()
+            .get(self.selected_file)
+            .and_then(|f| self.file_view_states.get(&f.filename))
+            .copied()
+        else {
+            return;
+        };
+        // push などで diff が短くなっていても範囲内に収める
+        let last_line = self.diff_line_count.saturating_sub(1);
+        self.selected_line = state.selected_line.min(last_line);
+        self.scroll_offset = state.scroll_offset.min(self.selected_line);
+        // コメントが消えた行ではパネルを開かない
+        if state.comment_panel_open && self.has_comment_at_current_line() {
+            self.comment_panel_open = true;
+            self.comment_panel_scroll = state.comment_panel_scroll;
+            self.selected_inline_comment = state
+                .selected_inline_comment
+                .min(self.get_comment_indices_at_current_line().len() - 1);
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use std::time::Instant;
+
+use crossterm::event::KeyEvent;
+
+use crate::cache::{PrCacheKey, PrData};
+use crate::local_repos::{self, LocalRepoRegistry};
+use crate::ui::picker::{Picker, PickerAction, PickerItem};
+
+use super::types::{DataState, RefreshRequest, ViewSnapshot, WatcherHandle};
+use super::{App, AppState, PopupId};
+
+/// ローカルモードで切り替えて離れたディレクトリの状態。戻ったときにそのまま使う
+pub(crate) struct LocalRepoSession {
+    snapshot: ViewSnapshot,
+    /// 止めたファイルウォッチャー（戻ったら再開する）
+    watcher: Option<WatcherHandle>,
+    data: Option<PrData>,
+}
+
+impl App {
+    /// 切り替えて見るリポジトリ（`--repo` の複数指定 / `[workspace] repos`）
+    pub fn set_workspace_repos(&mut self, repos: Vec<String>) {
+        self.workspace_repos = repos;
+    }
+
+    /// ローカルモードで切り替えるディレクトリ（`--dir` / `[workspace] dirs` / 最近開いたもの）
+    pub fn set_local_repos(&mut self, registry: LocalRepoRegistry) {
+        self.local_repos = Some(registry);
+    }
+
+    /// 切り替え先のディレクトリ。表示中のディレクトリは必ず含める
+    fn local_repo_dirs(&self) -> Vec<String> {
+        let mut dirs = self
+            .local_repos
+            .as_ref()
+            .map(LocalRepoRegistry::entries)
+            .unwrap_or_default();
+        if let Some(ref current) = self.working_dir {
+            if !dirs.contains(current) {
+                dirs.insert(0, current.clone());
+            }
+        }
+        dirs
+    }
+
+    /// リポジトリ切り替えのポップアップを開く。ローカルモードではディレクトリを選ぶ
+    pub(crate) fn open_repo_switcher(&mut self) {
+        if self.local_mode {
+            let dirs = self.local_repo_dirs();
+            if dirs.len() < 2 {
+                self.submission_result = Some((
+                    false,
+                    "Open other repositories with --dir or set [workspace] dirs to switch directories"
+                        .to_string(),
+                ));
+                self.submission_result_time = Some(Instant::now());
+                return;
+            }
+            let items = dirs
+                .iter()
+                .map(|dir| PickerItem {
+                    label: local_repos::dir_name(dir),
+                    detail: Some(local_repos::short_path(dir)),
+                    checked: self.working_dir.as_ref() == Some(dir),
+                })
+                .collect();
+            self.repo_switcher = Some(Picker::new(items));
+            self.push_popup(PopupId::Repos);
+            return;
+        }
+        if self.workspace_repos.len() < 2 {
+            self.submission_result = Some((
+                false,
+                "Pass several --repo values or set [workspace] repos to switch repositories"
+                    .to_string(),
+            ));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+        let items = self
+            .workspace_repos
+            .iter()
+            .map(|repo| PickerItem {
+                label: self.anonymizer.repo(repo).into_owned(),
+                detail: None,
+                checked: *repo == self.repo,
+            })
+            .collect();
+        self.repo_switcher = Some(Picker::new(items));
+        self.push_popup(PopupId::Repos);
+    }
+
+    pub(crate) fn handle_repo_switcher_input(&mut self, key: &KeyEvent) {
+        let Some(ref mut picker) = self.repo_switcher else {
+            return;
+        };
+        match picker.handle_key(key) {
+            PickerAction::Continue => {}
+            PickerAction::Close => self.close_popup(PopupId::Repos),
+            PickerAction::Select(index) => {
+                self.close_popup(PopupId::Repos);
+                if self.local_mode {
+                    if let Some(dir) = self.local_repo_dirs().get(index).cloned() {
+                        self.switch_local_dir(dir);
+                    }
+                } else if let Some(repo) = self.workspace_repos.get(index).cloned() {
+                    self.switch_repo(repo);
+                }
+            }
+        }
+    }
+
+    /// PR 一覧を別のリポジトリのものに切り替える。
+    /// 取得済みの PR データはセッションキャッシュにリポジトリごとに残るので、戻っても再利用される
+    pub(crate) fn switch_repo(&mut self, repo: String) {
+        if repo == self.repo || self.state != AppState::PullRequestList {
+            return;
+        }
+        // 下書きは PR 番号で持っているため、リポジトリごとに退避する
+        let drafts = std::mem::take(&mut self.pending_review);
+        let previous = std::mem::replace(&mut self.repo, repo);
+        self.parked_reviews.insert(previous.clone(), drafts);
+        self.pending_review = self.parked_reviews.remove(&self.repo).unwrap_or_default();
+        // PR のタブもリポジトリごとに退避する
+        self.swap_pr_tabs(previous);
+
+        self.anonymizer.set_repo(&self.repo);
+        self.review_thread_states.clear();
+        self.thread_states_pr = None;
+        self.pr_list = None;
+        self.reload_pr_list();
+    }
+
+    /// ローカルモードで表示するリポジトリのディレクトリを切り替える。
+    /// 表示位置・読み込んだ diff・ファイルウォッチャーはディレクトリごとに残し、戻ったときに使う
+    pub(crate) fn switch_local_dir(&mut self, dir: String) {
+        if !self.local_mode || self.working_dir.as_deref() == Some(dir.as_str()) {
+            return;
+        }
+        if matches!(self.state, AppState::AiRally) {
+            self.submission_result = Some((
+                false,
+                "Cannot switch directories during AI Rally".to_string(),
+            ));
+            self.submission_result_time = Some(Instant::now());
+            return;
+        }
+
+        let cache_key = PrCacheKey {
+            repo: self.repo.clone(),
+            pr_number: 0,
+        };
+        self.deactivate_watcher();
+        let parked = LocalRepoSession {
+            watcher: self.watcher_handle.take(),
+            snapshot: self.save_view_snapshot(),
+            data: self.session_cache.take_pr_data(&cache_key),
+        };
+        if let Some(previous) = self.working_dir.replace(dir.clone()) {
+            self.parked_local_repos.insert(previous, parked);
+        }
+
+        // 前のディレクトリの読み込み中の diff とファイルごとの表示状態を破棄
+        self.batch_diff_receiver = None;
+        self.lazy_diff_receiver = None;
+        self.lazy_diff_pending_file = None;
+        self.file_view_states.clear();
+        self.base_file_patches.clear();
+        self.vendored_expanded.clear();
+        self.file_list_filter = None;
+
+        match self.parked_local_repos.remove(&dir) {
+            Some(session) => {
+                self.restore_view_snapshot(session.snapshot);
+                self.watcher_handle = session.watcher;
+                if let Some(data) = session.data {
+                    self.session_cache.put_pr_data(cache_key.clone(), data);
+                }
+            }
+            None => {
+                self.selected_file = 0;
+                self.file_list_scroll_offset = 0;
+                self.selected_line = 0;
+                self.scroll_offset = 0;
+                self.diff_cache = None;
+                self.highlighted_cache_store.clear();
+                self.local_file_signatures.clear();
+                self.local_file_patch_signatures.clear();
+                self.diff_cache_receiver = None;
+                self.prefetch_receiver = None;
+            }
+        }
+        self.pr_number = Some(0);
+
+        if let Some(cached) = self.session_cache.get_pr_data(&cache_key) {
+            self.data_state = DataState::Loaded {
+                pr: cached.pr.clone(),
+                files: cached.files.clone(),
+            };
+            self.diff_line_count = Self::calc_diff_line_count(&cached.files, self.selected_file);
+            self.start_prefetch_all_files();
+        } else {
+            self.data_state = DataState::Loading;
+        }
+        self.activate_watcher();
+        if let Some(ref mut registry) = self.local_repos {
+            registry.record(&dir);
+        }
+
+        // retry_load の try_send は前の読み込みが詰まっていると捨てられるため、待ってでも送る
+        if let Some(ref tx) = self.retry_sender {
+            let tx = tx.clone();
+            let request = RefreshRequest::LocalRefresh {
+                working_dir: Some(dir.clone()),
+            };
+            tokio::spawn(async move {
+                let _ = tx.send(request).await;
+            });
+        }
+        self.submission_result = Some((
+            true,
+            format!("Switched to {}", local_repos::short_path(&dir)),
+        ));
+        self.submission_result_time = Some(Instant::now());
+    }
+}
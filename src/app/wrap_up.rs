@@ -0,0 +1,190 @@
+use std::collections::HashSet;
+
+use anyhow::Result;
+use crossterm::event::{KeyCode, KeyEvent};
+
+use crate::github::comment::ReviewComment;
+use crate::github::{self, DraftReviewComment, ReviewAction, ReviewThreadState};
+use crate::ui::TuiTerminal;
+
+use super::{App, AppState, PopupId};
+
+/// 承認・変更要求の前に見直したほうがよい項目
+#[derive(Debug, Clone)]
+pub struct ReviewSummary {
+    /// 確認した後に送信するレビュー
+    pub action: ReviewAction,
+    /// 未解決のレビュースレッド数
+    pub unresolved_threads: usize,
+    /// 未解決のスレッドの最初のコメント（読み込み済みのものだけ）
+    pub thread_heads: Vec<ReviewComment>,
+    /// レビューと一緒に送信される下書きのインラインコメント
+    pub drafts: Vec<DraftReviewComment>,
+    /// まだ viewed にしていないファイル
+    pub unviewed_files: Vec<String>,
+}
+
+impl ReviewSummary {
+    pub fn is_empty(&self) -> bool {
+        self.unresolved_threads == 0 && self.drafts.is_empty() && self.unviewed_files.is_empty()
+    }
+
+    /// `4 unresolved threads, 2 unsent drafts, 1 unviewed file`
+    pub fn summary(&self) -> String {
+        let mut parts = Vec::new();
+        if self.unresolved_threads > 0 {
+            parts.push(plural(self.unresolved_threads, "unresolved thread"));
+        }
+        if !self.drafts.is_empty() {
+            parts.push(plural(self.drafts.len(), "unsent draft"));
+        }
+        if !self.unviewed_files.is_empty() {
+            parts.push(plural(self.unviewed_files.len(), "unviewed file"));
+        }
+        parts.join(", ")
+    }
+}
+
+fn plural(count: usize, noun: &str) -> String {
+    if count == 1 {
+        format!("1 {}", noun)
+    } else {
+        format!("{} {}s", count, noun)
+    }
+}
+
+impl App {
+    /// a / r: 承認・変更要求を送信する。未解決のスレッド・下書き・未確認のファイルが残っていれば、
+    /// 先にまとめを見せて確認する
+    pub(crate) async fn start_review(
+        &mut self,
+        action: ReviewAction,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        let summary = self.review_summary_items(action).await;
+        if summary.is_empty() {
+            return self.submit_review(action, terminal).await;
+        }
+        self.review_summary = Some(summary);
+        self.push_popup(PopupId::ReviewSummary);
+        Ok(())
+    }
+
+    /// スレッドの状態は取得し直す（取得できなければ読み込み済みのコメントの状態で数える）
+    async fn review_summary_items(&mut self, action: ReviewAction) -> ReviewSummary {
+        let pr_number = self.pr_number();
+        let states: Vec<ReviewThreadState> =
+            match github::fetch_review_thread_states(&self.repo, pr_number).await {
+                Ok(states) => {
+                    let current = states.values().copied().collect();
+                    self.thread_states_pr = Some(pr_number);
+                    self.review_thread_states.extend(states);
+                    current
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to fetch review thread states");
+                    self.review_comments
+                        .iter()
+                        .flatten()
+                        .filter_map(|comment| self.review_thread_states.get(&comment.id))
+                        .copied()
+                        .collect()
+                }
+            };
+        let unresolved: HashSet<u64> = states
+            .iter()
+            .filter(|state| !state.resolved)
+            .map(|state| state.thread_id)
+            .collect();
+        let mut thread_heads: Vec<ReviewComment> = self
+            .review_comments
+            .iter()
+            .flatten()
+            .filter(|comment| unresolved.contains(&comment.id))
+            .cloned()
+            .collect();
+        thread_heads.sort_by(|a, b| (&a.path, a.line).cmp(&(&b.path, b.line)));
+
+        // コミット単体の diff を表示中でも PR 全体のファイルで数える
+        let files = match self.commit_view {
+            Some(ref view) => view.pr_files(),
+            None => self.files(),
+        };
+        let unviewed_files = files
+            .iter()
+            .filter(|file| !file.viewed)
+            .map(|file| file.filename.clone())
+            .collect();
+        ReviewSummary {
+            action,
+            unresolved_threads: unresolved.len(),
+            thread_heads,
+            drafts: self.review_drafts().to_vec(),
+            unviewed_files,
+        }
+    }
+
+    /// y / Enter（承認なら a も）: そのまま送信、r: 見直す（スレッドがあればコメント一覧、
+    /// 下書きがあれば下書き一覧、どちらもなければ最初の未確認のファイル）、Esc / n: 取り消す
+    pub(crate) async fn handle_review_summary_input(
+        &mut self,
+        key: &KeyEvent,
+        terminal: &mut dyn TuiTerminal,
+    ) -> Result<()> {
+        let Some(ref summary) = self.review_summary else {
+            return Ok(());
+        };
+        let action = summary.action;
+        let confirm = matches!(key.code, KeyCode::Enter | KeyCode::Char('y'))
+            || (action == ReviewAction::Approve && key.code == KeyCode::Char('a'));
+        if confirm {
+            self.close_popup(PopupId::ReviewSummary);
+            self.submit_review(action, terminal).await?;
+            if self.pending_approve_body.is_none() {
+                self.approve_then_next = false;
+            }
+            return Ok(());
+        }
+        match key.code {
+            KeyCode::Char('r') => {
+                let has_threads = summary.unresolved_threads > 0;
+                let has_drafts = !summary.drafts.is_empty();
+                let first_unviewed = summary.unviewed_files.first().cloned();
+                self.close_popup(PopupId::ReviewSummary);
+                self.approve_then_next = false;
+                if has_threads {
+                    self.previous_state = self.state;
+                    self.open_comment_list();
+                } else if has_drafts {
+                    self.open_review_drafts();
+                } else if let Some(filename) = first_unviewed {
+                    self.open_unviewed_file(&filename);
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('n') => {
+                self.close_popup(PopupId::ReviewSummary);
+                self.approve_then_next = false;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// まだ viewed にしていないファイルの diff を開く
+    fn open_unviewed_file(&mut self, filename: &str) {
+        self.exit_commit_view();
+        let Some(file_index) = self.files().iter().position(|f| f.filename == filename) else {
+            return;
+        };
+        if self.state != AppState::DiffView {
+            self.diff_view_return_state = AppState::FileList;
+        }
+        self.selected_file = file_index;
+        self.state = AppState::DiffView;
+        self.selected_line = 0;
+        self.scroll_offset = 0;
+        self.update_diff_line_count();
+        self.update_file_comment_positions();
+        self.ensure_diff_cache();
+    }
+}
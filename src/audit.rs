@@ -0,0 +1,243 @@
+//! セッション中に行った変更操作の監査ログ
+//!
+//! コメント投稿・レビュー送信・viewed マーク・AI Rally の投稿などの完了時に、
+//! `~/.cache/octorus/audit.jsonl` へ 1 行 1 エントリの JSON を追記する（追記のみ、書き換えない）。
+//! 各エントリにはプロセスごとのセッション ID が付き、「Session activity」パネルは
+//! 同じセッション ID のエントリだけを読み出して表示する。
+
+use std::fs::OpenOptions;
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::cache::cache_dir;
+
+/// 記録対象の操作
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AuditAction {
+    Comment,
+    Suggestion,
+    Reply,
+    Approve,
+    RequestChanges,
+    ReviewComment,
+    MarkViewed,
+    UnmarkViewed,
+    RallyReview,
+    RallyComment,
+    RallyFix,
+    /// 放置されている PR へのリマインド
+    Nudge,
+    /// レビューアジェンダの投稿
+    ReviewAgenda,
+    /// レビュー依頼
+    RequestReviewer,
+    /// レビュー依頼の取り消し
+    RemoveReviewer,
+    Assign,
+    Unassign,
+    /// ラベルの付け外し
+    EditLabels,
+    /// 自分の Discussion コメントの編集
+    EditComment,
+    /// 自分の Discussion コメントの削除
+    DeleteComment,
+    /// Draft の PR をレビュー可能にする
+    MarkReadyForReview,
+    /// PR を Draft に戻す
+    ConvertToDraft,
+}
+
+impl AuditAction {
+    pub fn label(self) -> &'static str {
+        match self {
+            AuditAction::Comment => "comment",
+            AuditAction::Suggestion => "suggestion",
+            AuditAction::Reply => "reply",
+            AuditAction::Approve => "approve",
+            AuditAction::RequestChanges => "request changes",
+            AuditAction::ReviewComment => "review comment",
+            AuditAction::MarkViewed => "mark viewed",
+            AuditAction::UnmarkViewed => "unmark viewed",
+            AuditAction::RallyReview => "rally review",
+            AuditAction::RallyComment => "rally inline comment",
+            AuditAction::RallyFix => "rally fix summary",
+            AuditAction::Nudge => "nudge",
+            AuditAction::ReviewAgenda => "review agenda",
+            AuditAction::RequestReviewer => "request reviewer",
+            AuditAction::RemoveReviewer => "remove reviewer",
+            AuditAction::Assign => "assign",
+            AuditAction::Unassign => "unassign",
+            AuditAction::EditLabels => "edit labels",
+            AuditAction::EditComment => "edit comment",
+            AuditAction::DeleteComment => "delete comment",
+            AuditAction::MarkReadyForReview => "mark ready for review",
+            AuditAction::ConvertToDraft => "convert to draft",
+        }
+    }
+}
+
+/// 監査ログの 1 エントリ
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    /// RFC 3339 (UTC)
+    pub timestamp: String,
+    pub session: String,
+    pub repo: String,
+    pub pr_number: u32,
+    pub action: AuditAction,
+    /// 対象（`path:line`、コメント ID など）
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    pub ok: bool,
+    /// 失敗時の API エラーなど
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditEntry {
+    pub fn new<T, E: std::fmt::Display>(
+        repo: &str,
+        pr_number: u32,
+        action: AuditAction,
+        target: Option<String>,
+        result: &std::result::Result<T, E>,
+    ) -> Self {
+        Self {
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            session: session_id().to_string(),
+            repo: repo.to_string(),
+            pr_number,
+            action,
+            target,
+            ok: result.is_ok(),
+            error: result.as_ref().err().map(|e| e.to_string()),
+        }
+    }
+}
+
+/// このプロセスのセッション ID（`<pid>-<起動時刻>`）
+pub fn session_id() -> &'static str {
+    static SESSION: OnceLock<String> = OnceLock::new();
+    SESSION.get_or_init(|| {
+        format!(
+            "{}-{}",
+            std::process::id(),
+            chrono::Utc::now().format("%Y%m%dT%H%M%S")
+        )
+    })
+}
+
+/// 監査ログのパス: ~/.cache/octorus/audit.jsonl
+pub fn log_path() -> PathBuf {
+    cache_dir().join("audit.jsonl")
+}
+
+/// エントリを追記する（失敗してもアプリは止めない）
+pub fn record(entry: &AuditEntry) {
+    if let Err(e) = append_to(&log_path(), entry) {
+        tracing::warn!("Failed to write audit log: {:#}", e);
+    }
+}
+
+pub fn append_to(path: &Path, entry: &AuditEntry) -> Result<()> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir).context("Failed to create audit log directory")?;
+    }
+    let mut line = serde_json::to_string(entry).context("Failed to serialize audit entry")?;
+    line.push('\n');
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .context("Failed to open audit log")?;
+    file.write_all(line.as_bytes())
+        .context("Failed to append audit entry")
+}
+
+/// 現在のセッションのエントリを古い順に読み出す
+pub fn read_session() -> Vec<AuditEntry> {
+    read_session_from(&log_path(), session_id())
+}
+
+pub fn read_session_from(path: &Path, session: &str) -> Vec<AuditEntry> {
+    let Ok(file) = std::fs::File::open(path) else {
+        return Vec::new();
+    };
+    BufReader::new(file)
+        .lines()
+        .map_while(|line| line.ok())
+        .filter_map(|line| serde_json::from_str::<AuditEntry>(&line).ok())
+        .filter(|entry| entry.session == session)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(session: &str, action: AuditAction, ok: bool) -> AuditEntry {
+        AuditEntry {
+            timestamp: "2024-01-01T00:00:00+00:00".to_string(),
+            session: session.to_string(),
+            repo: "owner/repo".to_string(),
+            pr_number: 1,
+            action,
+            target: Some("src/main.rs:10".to_string()),
+            ok,
+            error: (!ok).then(|| "HTTP 422".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_entry_new_captures_result() {
+        let ok: std::result::Result<(), String> = Ok(());
+        let entry = AuditEntry::new("o/r", 3, AuditAction::Approve, None, &ok);
+        assert!(entry.ok);
+        assert!(entry.error.is_none());
+        assert_eq!(entry.session, session_id());
+
+        let err: std::result::Result<(), String> = Err("boom".to_string());
+        let entry = AuditEntry::new("o/r", 3, AuditAction::Reply, Some("#42".into()), &err);
+        assert!(!entry.ok);
+        assert_eq!(entry.error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_append_and_read_session_only() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("audit.jsonl");
+
+        append_to(&path, &entry("a", AuditAction::Comment, true)).unwrap();
+        append_to(&path, &entry("b", AuditAction::Approve, true)).unwrap();
+        append_to(&path, &entry("a", AuditAction::MarkViewed, false)).unwrap();
+
+        let entries = read_session_from(&path, "a");
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].action, AuditAction::Comment);
+        assert_eq!(entries[1].action, AuditAction::MarkViewed);
+        assert_eq!(entries[1].error.as_deref(), Some("HTTP 422"));
+    }
+
+    #[test]
+    fn test_read_session_skips_corrupt_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("audit.jsonl");
+        std::fs::write(&path, "not json\n").unwrap();
+        append_to(&path, &entry("a", AuditAction::Reply, true)).unwrap();
+
+        assert_eq!(read_session_from(&path, "a").len(), 1);
+        assert!(read_session_from(&dir.path().join("missing"), "a").is_empty());
+    }
+
+    #[test]
+    fn test_action_serializes_snake_case() {
+        let json = serde_json::to_string(&entry("a", AuditAction::RequestChanges, true)).unwrap();
+        assert!(json.contains("\"action\":\"request_changes\""));
+        assert!(!json.contains("\"error\""));
+    }
+}
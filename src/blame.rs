@@ -0,0 +1,257 @@
+//! diff のガターに表示する blame 情報
+//!
+//! コンテキスト行（PR で変更していない行）ごとに、その行を最後に変更したコミットの
+//! author と経過時間を表示する。ローカルの git で head のコミットを blame できれば
+//! それを使い、できなければ GitHub の blame API から取得する。
+
+use std::collections::HashMap;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use tokio::process::Command;
+
+use crate::github::{self, BlameRange};
+
+/// ある行を最後に変更したコミット
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameCommit {
+    pub sha: String,
+    pub author: String,
+    pub time: Option<DateTime<Utc>>,
+}
+
+impl BlameCommit {
+    /// まだコミットしていない行か（ローカルモードで作業ツリーを blame したとき）
+    pub fn is_uncommitted(&self) -> bool {
+        !self.sha.is_empty() && self.sha.bytes().all(|b| b == b'0')
+    }
+
+    /// ガターに出す短い経過時間（`3mo`・`2y` など）
+    pub fn age(&self, now: DateTime<Utc>) -> String {
+        if self.is_uncommitted() {
+            return "new".to_string();
+        }
+        match self.time {
+            Some(time) => {
+                let relative = crate::time_format::relative(time, now);
+                match relative.strip_suffix(" ago") {
+                    Some(age) => age.to_string(),
+                    None => "now".to_string(),
+                }
+            }
+            None => String::new(),
+        }
+    }
+}
+
+/// ファイル全体の blame。行番号は blame したリビジョン（head・作業ツリー）の行番号
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileBlame {
+    commits: Vec<BlameCommit>,
+    /// 行（0 始まり）ごとの `commits` の添字
+    lines: Vec<Option<usize>>,
+}
+
+impl FileBlame {
+    /// `line`（1 始まり）を最後に変更したコミット
+    pub fn get(&self, line: u32) -> Option<&BlameCommit> {
+        let index = (*self.lines.get((line as usize).checked_sub(1)?)?)?;
+        self.commits.get(index)
+    }
+
+    fn set(&mut self, line: u32, commit: usize) {
+        let Some(index) = (line as usize).checked_sub(1) else {
+            return;
+        };
+        if self.lines.len() <= index {
+            self.lines.resize(index + 1, None);
+        }
+        self.lines[index] = Some(commit);
+    }
+
+    /// GitHub の blame API の区間から作る
+    pub fn from_ranges(ranges: &[BlameRange]) -> Self {
+        let mut blame = Self::default();
+        let mut index_of: HashMap<&str, usize> = HashMap::new();
+        for range in ranges {
+            let index = *index_of.entry(&range.sha).or_insert_with(|| {
+                blame.commits.push(BlameCommit {
+                    sha: range.sha.clone(),
+                    author: range.author.clone(),
+                    time: DateTime::parse_from_rfc3339(&range.date)
+                        .ok()
+                        .map(|t| t.with_timezone(&Utc)),
+                });
+                blame.commits.len() - 1
+            });
+            for line in range.start..=range.end {
+                blame.set(line, index);
+            }
+        }
+        blame
+    }
+
+    /// `git blame --porcelain` の出力から作る
+    pub fn parse_porcelain(output: &str) -> Self {
+        let mut blame = Self::default();
+        let mut index_of: HashMap<String, usize> = HashMap::new();
+        // 次の行がエントリの見出し（`<sha> <元の行> <行> [<行数>]`）か
+        let mut expect_header = true;
+        let mut current = None;
+        for line in output.lines() {
+            if line.starts_with('\t') {
+                expect_header = true;
+                continue;
+            }
+            if expect_header {
+                expect_header = false;
+                let mut parts = line.split_whitespace();
+                let (Some(sha), Some(_), Some(final_line)) =
+                    (parts.next(), parts.next(), parts.next())
+                else {
+                    current = None;
+                    continue;
+                };
+                let index = *index_of.entry(sha.to_string()).or_insert_with(|| {
+                    blame.commits.push(BlameCommit {
+                        sha: sha.to_string(),
+                        author: String::new(),
+                        time: None,
+                    });
+                    blame.commits.len() - 1
+                });
+                if let Ok(final_line) = final_line.parse() {
+                    blame.set(final_line, index);
+                }
+                current = Some(index);
+                continue;
+            }
+            let Some(commit) = current.and_then(|i| blame.commits.get_mut(i)) else {
+                continue;
+            };
+            if let Some(author) = line.strip_prefix("author ") {
+                commit.author = author.to_string();
+            } else if let Some(time) = line.strip_prefix("author-time ") {
+                commit.time = time
+                    .parse()
+                    .ok()
+                    .and_then(|secs| DateTime::from_timestamp(secs, 0));
+            }
+        }
+        blame
+    }
+}
+
+/// ローカルの git でファイルを blame する。`rev` が None なら作業ツリーを blame する
+pub async fn blame_from_git(
+    working_dir: Option<&str>,
+    rev: Option<&str>,
+    path: &str,
+) -> Result<FileBlame> {
+    let root = crate::file_content::repo_root(working_dir).await?;
+    let mut cmd = Command::new("git");
+    cmd.arg("blame").arg("--porcelain");
+    if let Some(rev) = rev {
+        cmd.arg(rev);
+    }
+    cmd.args(["--", path]).current_dir(root);
+    let output = cmd.output().await.context("Failed to run git blame")?;
+    if !output.status.success() {
+        bail!(
+            "git blame failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(FileBlame::parse_porcelain(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+}
+
+/// PR の head 時点のファイルを blame する。head のコミットが手元になければ GitHub から取得する
+pub async fn fetch_blame(
+    repo: &str,
+    working_dir: Option<&str>,
+    rev: &str,
+    path: &str,
+) -> Result<FileBlame> {
+    match blame_from_git(working_dir, Some(rev), path).await {
+        Ok(blame) => Ok(blame),
+        Err(_) => {
+            let ranges = github::fetch_blame(repo, rev, path).await?;
+            Ok(FileBlame::from_ranges(&ranges))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SHA_A: &str = "1111111111111111111111111111111111111111";
+    const SHA_B: &str = "2222222222222222222222222222222222222222";
+    const UNCOMMITTED: &str = "0000000000000000000000000000000000000000";
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_porcelain_reuses_commit_details() {
+        let output = format!(
+            "{a} 1 1 2\nauthor Alice\nauthor-mail <alice@example.com>\nauthor-time 1704067200\n\
+             author-tz +0000\nsummary first\nfilename src/lib.rs\n\tfn main() {{\n\
+             {a} 2 2\n\t}}\n\
+             {b} 5 3 1\nauthor Bob\nauthor-time 1706745600\nfilename src/lib.rs\n\tauthor x\n",
+            a = SHA_A,
+            b = SHA_B,
+        );
+        let blame = FileBlame::parse_porcelain(&output);
+        assert_eq!(blame.get(1).unwrap().author, "Alice");
+        assert_eq!(blame.get(2).unwrap().sha, SHA_A);
+        let third = blame.get(3).unwrap();
+        assert_eq!(third.author, "Bob");
+        assert_eq!(third.time, Some(at("2024-02-01T00:00:00Z")));
+        assert!(blame.get(4).is_none());
+        assert!(blame.get(0).is_none());
+    }
+
+    #[test]
+    fn test_from_ranges() {
+        let ranges = vec![
+            BlameRange {
+                start: 1,
+                end: 2,
+                sha: SHA_A.to_string(),
+                author: "alice".to_string(),
+                date: "2024-01-01T09:00:00+09:00".to_string(),
+            },
+            BlameRange {
+                start: 3,
+                end: 3,
+                sha: SHA_B.to_string(),
+                author: "bob".to_string(),
+                date: "not a date".to_string(),
+            },
+        ];
+        let blame = FileBlame::from_ranges(&ranges);
+        assert_eq!(blame.get(2).unwrap().author, "alice");
+        assert_eq!(blame.get(1).unwrap().time, Some(at("2024-01-01T00:00:00Z")));
+        assert_eq!(blame.get(3).unwrap().time, None);
+        assert!(blame.get(4).is_none());
+    }
+
+    #[test]
+    fn test_age() {
+        let now = at("2024-06-01T00:00:00Z");
+        let commit = |sha: &str, time: Option<&str>| BlameCommit {
+            sha: sha.to_string(),
+            author: "alice".to_string(),
+            time: time.map(at),
+        };
+        assert_eq!(commit(SHA_A, Some("2024-01-01T00:00:00Z")).age(now), "5mo");
+        assert_eq!(commit(SHA_A, Some("2024-06-01T00:00:00Z")).age(now), "now");
+        assert_eq!(commit(SHA_A, None).age(now), "");
+        assert!(commit(UNCOMMITTED, None).is_uncommitted());
+        assert_eq!(commit(UNCOMMITTED, None).age(now), "new");
+    }
+}
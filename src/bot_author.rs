@@ -0,0 +1,109 @@
+//! bot アカウントの判定とコメント一覧での折りたたみ
+//!
+//! 依存更新・カバレッジ・CI などの bot のコメントを人のコメントと分け、
+//! コメント一覧では末尾の「Bot activity」にまとめて折りたたむ。
+
+/// 既定で bot とみなす作成者（`[comments] bot_authors` で追加できる）
+const DEFAULT_BOT_PATTERNS: &[&str] = &[
+    "*[bot]",
+    "*-bot",
+    "*-robot",
+    "dependabot",
+    "github-actions",
+    "renovate",
+    "coveralls",
+    "codecov-*",
+];
+
+/// bot のコメントか
+pub fn is_bot(login: &str, extra_patterns: &[String]) -> bool {
+    DEFAULT_BOT_PATTERNS
+        .iter()
+        .copied()
+        .chain(extra_patterns.iter().map(String::as_str))
+        .any(|pattern| author_matches(pattern, login))
+}
+
+/// コメント一覧の表示順（人のコメント → bot のコメント）。
+/// 折りたたみ中は bot のコメントを含めない。2 つ目の値は bot のコメント数
+pub fn fold_order<'a>(
+    logins: impl Iterator<Item = &'a str>,
+    extra_patterns: &[String],
+    expanded: bool,
+) -> (Vec<usize>, usize) {
+    let mut order = Vec::new();
+    let mut bots = Vec::new();
+    for (i, login) in logins.enumerate() {
+        if is_bot(login, extra_patterns) {
+            bots.push(i);
+        } else {
+            order.push(i);
+        }
+    }
+    let bot_count = bots.len();
+    if expanded {
+        order.extend(bots);
+    }
+    (order, bot_count)
+}
+
+/// `*` を任意の文字列として作成者のパターンに一致するか（大文字小文字は区別しない）
+pub(crate) fn author_matches(pattern: &str, login: &str) -> bool {
+    let pattern = pattern.to_lowercase();
+    let login = login.to_lowercase();
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = login.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        // `*` を含まなければ完全一致
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_author_matches() {
+        assert!(author_matches("*[bot]", "dependabot[bot]"));
+        assert!(author_matches("renovate", "Renovate"));
+        assert!(!author_matches("renovate", "renovate-helper"));
+        assert!(author_matches("ci-*-bot", "ci-linux-bot"));
+        assert!(!author_matches("ci-*-bot", "ci-bot"));
+        assert!(author_matches("*", "anyone"));
+    }
+
+    #[test]
+    fn test_is_bot() {
+        assert!(is_bot("codecov[bot]", &[]));
+        assert!(is_bot("k8s-ci-robot", &[]));
+        assert!(is_bot("codecov-commenter", &[]));
+        assert!(!is_bot("alice", &[]));
+        assert!(!is_bot("abbot", &[]));
+        assert!(is_bot("deploy-user", &["deploy-*".to_string()]));
+    }
+
+    #[test]
+    fn test_fold_order_moves_bots_to_the_end() {
+        let logins = ["alice", "dependabot[bot]", "bob", "github-actions"];
+        assert_eq!(
+            fold_order(logins.iter().copied(), &[], false),
+            (vec![0, 2], 2)
+        );
+        assert_eq!(
+            fold_order(logins.iter().copied(), &[], true),
+            (vec![0, 2, 1, 3], 2)
+        );
+    }
+}
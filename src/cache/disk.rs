@@ -0,0 +1,348 @@
+//! PR データのディスクキャッシュ
+//!
+//! `SessionCache` の下に置く永続層。PR 本体・変更ファイル（patch を含む）・コメントを
+//! キャッシュディレクトリの `prs/<owner>_<repo>-<PR 番号>.json` に保存し、
+//! 再起動した直後やオフラインでも前回のデータをすぐに表示できるようにする。
+//! 最後に保存してから `ttl` を過ぎたエントリは読まずに消し、合計サイズが `max_bytes` を
+//! 超えたら保存の古いエントリから消す。ローカルモード（PR 番号 0）は保存しない。
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::{cache_dir, sanitize_repo_name, PrCacheKey};
+use crate::config::CacheConfig;
+use crate::github::comment::{DiscussionComment, ReviewComment};
+use crate::github::{ChangedFile, PullRequest};
+
+const DISK_CACHE_DIR: &str = "prs";
+
+/// 1 つの PR の保存内容
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiskEntry {
+    pub pr: PullRequest,
+    pub files: Vec<ChangedFile>,
+    #[serde(default)]
+    pub review_comments: Option<Vec<ReviewComment>>,
+    #[serde(default)]
+    pub discussion_comments: Option<Vec<DiscussionComment>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DiskCache {
+    dir: PathBuf,
+    ttl: Duration,
+    max_bytes: u64,
+}
+
+impl DiskCache {
+    pub fn new(dir: PathBuf, ttl: Duration, max_bytes: u64) -> Self {
+        Self {
+            dir,
+            ttl,
+            max_bytes,
+        }
+    }
+
+    /// `[cache]` の設定から作る。無効なら None
+    pub fn from_config(config: &CacheConfig) -> Option<Self> {
+        config.enabled.then(|| {
+            Self::new(
+                cache_dir().join(DISK_CACHE_DIR),
+                Duration::from_secs(config.ttl_hours * 60 * 60),
+                config.max_size_mb * 1024 * 1024,
+            )
+        })
+    }
+
+    fn entry_path(&self, key: &PrCacheKey) -> Option<PathBuf> {
+        if key.pr_number == 0 {
+            return None;
+        }
+        let repo = sanitize_repo_name(&key.repo).ok()?;
+        Some(self.dir.join(format!("{}-{}.json", repo, key.pr_number)))
+    }
+
+    fn is_expired(&self, modified: SystemTime) -> bool {
+        SystemTime::now()
+            .duration_since(modified)
+            .is_ok_and(|age| age >= self.ttl)
+    }
+
+    /// 保存済みのエントリを読む。期限切れや壊れたエントリは消して None
+    pub fn load(&self, key: &PrCacheKey) -> Option<DiskEntry> {
+        let path = self.entry_path(key)?;
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        if self.is_expired(modified) {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+        let entry = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok());
+        if entry.is_none() {
+            let _ = std::fs::remove_file(&path);
+        }
+        entry
+    }
+
+    /// PR 本体と変更ファイルを保存する。保存済みのコメントは残す
+    pub fn save_pr_data(
+        &self,
+        key: &PrCacheKey,
+        pr: &PullRequest,
+        files: &[ChangedFile],
+    ) -> Result<()> {
+        let Some(path) = self.entry_path(key) else {
+            return Ok(());
+        };
+        let entry = match self.load(key) {
+            Some(previous) => DiskEntry {
+                pr: pr.clone(),
+                files: files.to_vec(),
+                ..previous
+            },
+            None => DiskEntry {
+                pr: pr.clone(),
+                files: files.to_vec(),
+                review_comments: None,
+                discussion_comments: None,
+            },
+        };
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(path, serde_json::to_string(&entry)?)?;
+        self.evict()?;
+        Ok(())
+    }
+
+    /// エントリを消す（なければ何もしない）
+    pub fn remove(&self, key: &PrCacheKey) -> Result<()> {
+        let Some(path) = self.entry_path(key) else {
+            return Ok(());
+        };
+        match std::fs::remove_file(path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+            _ => Ok(()),
+        }
+    }
+
+    /// 保存済みのエントリのコメントを差し替える（None で消す）。エントリがなければ何もしない
+    pub fn save_review_comments(
+        &self,
+        key: &PrCacheKey,
+        comments: Option<&[ReviewComment]>,
+    ) -> Result<()> {
+        self.update(key, |entry| {
+            entry.review_comments = comments.map(<[ReviewComment]>::to_vec)
+        })
+    }
+
+    pub fn save_discussion_comments(
+        &self,
+        key: &PrCacheKey,
+        comments: Option<&[DiscussionComment]>,
+    ) -> Result<()> {
+        self.update(key, |entry| {
+            entry.discussion_comments = comments.map(<[DiscussionComment]>::to_vec)
+        })
+    }
+
+    fn update(&self, key: &PrCacheKey, f: impl FnOnce(&mut DiskEntry)) -> Result<()> {
+        let (Some(path), Some(mut entry)) = (self.entry_path(key), self.load(key)) else {
+            return Ok(());
+        };
+        f(&mut entry);
+        std::fs::write(path, serde_json::to_string(&entry)?)?;
+        Ok(())
+    }
+
+    /// 期限切れのエントリを消し、合計サイズが上限を超えていれば保存の古い順に消す。
+    /// 消したエントリ数を返す
+    pub fn evict(&self) -> Result<usize> {
+        let Ok(dir) = std::fs::read_dir(&self.dir) else {
+            return Ok(0);
+        };
+        let mut entries: Vec<(PathBuf, SystemTime, u64)> = dir
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((entry.path(), metadata.modified().ok()?, metadata.len()))
+            })
+            .collect();
+
+        let mut removed = 0;
+        entries.retain(|(path, modified, _)| {
+            if self.is_expired(*modified) {
+                removed += usize::from(std::fs::remove_file(path).is_ok());
+                false
+            } else {
+                true
+            }
+        });
+
+        entries.sort_by_key(|(_, modified, _)| *modified);
+        let mut total: u64 = entries.iter().map(|(_, _, size)| size).sum();
+        for (path, _, size) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            std::fs::remove_file(&path)?;
+            total -= size;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::{Branch, User};
+
+    fn make_pr(number: u32) -> PullRequest {
+        PullRequest {
+            number,
+            node_id: None,
+            title: format!("PR {}", number),
+            body: None,
+            state: "open".to_string(),
+            head: Branch {
+                ref_name: "feature".to_string(),
+                sha: "abc123".to_string(),
+            },
+            base: Branch {
+                ref_name: "main".to_string(),
+                sha: "def456".to_string(),
+            },
+            user: User {
+                login: "testuser".to_string(),
+            },
+            updated_at: "2024-01-01T00:00:00Z".to_string(),
+            labels: Vec::new(),
+            draft: false,
+        }
+    }
+
+    fn make_file(name: &str) -> ChangedFile {
+        ChangedFile {
+            filename: name.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some("@@ -1 +1,2 @@\n line\n+added".to_string()),
+            viewed: false,
+        }
+    }
+
+    fn key(pr_number: u32) -> PrCacheKey {
+        PrCacheKey {
+            repo: "owner/repo".to_string(),
+            pr_number,
+        }
+    }
+
+    fn backdate(cache: &DiskCache, key: &PrCacheKey, age: Duration) {
+        let file = std::fs::File::options()
+            .write(true)
+            .open(cache.entry_path(key).unwrap())
+            .unwrap();
+        file.set_modified(SystemTime::now() - age).unwrap();
+    }
+
+    #[test]
+    fn test_round_trip_keeps_comments_across_pr_updates() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(
+            root.path().to_path_buf(),
+            Duration::from_secs(3600),
+            u64::MAX,
+        );
+
+        cache
+            .save_pr_data(&key(1), &make_pr(1), &[make_file("src/a.rs")])
+            .unwrap();
+        let comment = DiscussionComment {
+            id: 1,
+            node_id: String::new(),
+            body: "LGTM".to_string(),
+            user: User {
+                login: "reviewer".to_string(),
+            },
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+            updated_at: None,
+        };
+        cache
+            .save_discussion_comments(&key(1), Some(std::slice::from_ref(&comment)))
+            .unwrap();
+        cache
+            .save_pr_data(&key(1), &make_pr(1), &[make_file("src/b.rs")])
+            .unwrap();
+
+        let entry = cache.load(&key(1)).unwrap();
+        assert_eq!(entry.files[0].filename, "src/b.rs");
+        assert_eq!(
+            entry.files[0].patch.as_deref(),
+            make_file("x").patch.as_deref()
+        );
+        assert_eq!(entry.discussion_comments.unwrap()[0].body, "LGTM");
+        assert!(entry.review_comments.is_none());
+
+        // エントリのない PR とローカルモードは保存しない
+        cache.save_review_comments(&key(2), Some(&[])).unwrap();
+        assert!(cache.load(&key(2)).is_none());
+        cache.save_pr_data(&key(0), &make_pr(0), &[]).unwrap();
+        assert!(cache.load(&key(0)).is_none());
+    }
+
+    #[test]
+    fn test_expired_entries_are_dropped() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = DiskCache::new(
+            root.path().to_path_buf(),
+            Duration::from_secs(3600),
+            u64::MAX,
+        );
+        cache.save_pr_data(&key(1), &make_pr(1), &[]).unwrap();
+        cache.save_pr_data(&key(2), &make_pr(2), &[]).unwrap();
+
+        backdate(&cache, &key(1), Duration::from_secs(7200));
+        assert!(cache.load(&key(1)).is_none());
+        assert!(!cache.entry_path(&key(1)).unwrap().exists());
+
+        backdate(&cache, &key(2), Duration::from_secs(7200));
+        assert_eq!(cache.evict().unwrap(), 1);
+        assert!(!cache.entry_path(&key(2)).unwrap().exists());
+    }
+
+    #[test]
+    fn test_evicts_oldest_entries_over_size_limit() {
+        let root = tempfile::tempdir().unwrap();
+        let unbounded = DiskCache::new(
+            root.path().to_path_buf(),
+            Duration::from_secs(3600),
+            u64::MAX,
+        );
+        for (number, age) in [(1, 300), (2, 200), (3, 100)] {
+            unbounded
+                .save_pr_data(&key(number), &make_pr(number), &[make_file("src/a.rs")])
+                .unwrap();
+            backdate(&unbounded, &key(number), Duration::from_secs(age));
+        }
+        let entry_size = std::fs::metadata(unbounded.entry_path(&key(3)).unwrap())
+            .unwrap()
+            .len();
+
+        let bounded = DiskCache::new(
+            root.path().to_path_buf(),
+            Duration::from_secs(3600),
+            entry_size * 2,
+        );
+        assert_eq!(bounded.evict().unwrap(), 1);
+        assert!(bounded.load(&key(1)).is_none());
+        assert!(bounded.load(&key(2)).is_some());
+        assert!(bounded.load(&key(3)).is_some());
+    }
+}
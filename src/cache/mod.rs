@@ -1,12 +1,21 @@
+mod disk;
+mod outbox;
+mod responses;
+
 use std::collections::HashMap;
 use std::path::PathBuf;
 
 use anyhow::Result;
+use tracing::warn;
 use xdg::BaseDirectories;
 
 use crate::github::comment::{DiscussionComment, ReviewComment};
 use crate::github::{ChangedFile, PullRequest};
 
+pub use disk::{DiskCache, DiskEntry};
+pub use outbox::{is_retryable, Outbox, OutboxEntry, OutboxPayload};
+pub use responses::{CachedResponse, ResponseCache};
+
 /// セッションキャッシュが保持するPRデータの最大エントリ数。
 /// 超過時は最も古いエントリ（LRU）を削除してメモリ増加を防止する。
 const MAX_PR_CACHE_ENTRIES: usize = 5;
@@ -95,12 +104,16 @@ pub struct PrData {
 /// PRデータは最大 `MAX_PR_CACHE_ENTRIES` 件まで保持し、超過時は最も古い
 /// エントリを削除する。コメントデータは対応するPRデータが存在するキーにのみ
 /// 保存可能で、`pr_data` のライフサイクルと連動して管理される。
+///
+/// `disk` があれば PR データとコメントの保存をディスクにも書き込み、メモリにない
+/// PR はディスクから読み戻す。lazy diff で後から埋めた patch はメモリにだけ持つ。
 pub struct SessionCache {
     pr_data: HashMap<PrCacheKey, PrData>,
     /// アクセス順序リスト（末尾が最新）。LRU eviction に使用。
     access_order: Vec<PrCacheKey>,
     review_comments: HashMap<PrCacheKey, Vec<ReviewComment>>,
     discussion_comments: HashMap<PrCacheKey, Vec<DiscussionComment>>,
+    disk: Option<DiskCache>,
 }
 
 impl Default for SessionCache {
@@ -116,6 +129,47 @@ impl SessionCache {
             access_order: Vec::new(),
             review_comments: HashMap::new(),
             discussion_comments: HashMap::new(),
+            disk: None,
+        }
+    }
+
+    /// ディスクキャッシュを下に置いたセッションキャッシュ
+    pub fn with_disk(disk: DiskCache) -> Self {
+        Self {
+            disk: Some(disk),
+            ..Self::new()
+        }
+    }
+
+    /// メモリにない PR をディスクから読み戻す
+    fn restore_from_disk(&mut self, key: &PrCacheKey) {
+        let Some(entry) = self.disk.as_ref().and_then(|disk| disk.load(key)) else {
+            return;
+        };
+        if let Some(comments) = entry.review_comments {
+            self.review_comments.insert(key.clone(), comments);
+        }
+        if let Some(comments) = entry.discussion_comments {
+            self.discussion_comments.insert(key.clone(), comments);
+        }
+        self.touch(key);
+        self.pr_data.insert(
+            key.clone(),
+            PrData {
+                pr_updated_at: entry.pr.updated_at.clone(),
+                pr: Box::new(entry.pr),
+                files: entry.files,
+            },
+        );
+        self.evict_if_needed();
+    }
+
+    /// ディスクへの書き込みは失敗しても表示に影響しないため、ログだけ残す
+    fn write_to_disk(&self, write: impl FnOnce(&DiskCache) -> Result<()>) {
+        if let Some(ref disk) = self.disk {
+            if let Err(e) = write(disk) {
+                warn!("Failed to write disk cache: {}", e);
+            }
         }
     }
 
@@ -142,6 +196,9 @@ impl SessionCache {
     }
 
     pub fn get_pr_data(&mut self, key: &PrCacheKey) -> Option<&PrData> {
+        if !self.pr_data.contains_key(key) {
+            self.restore_from_disk(key);
+        }
         if self.pr_data.contains_key(key) {
             self.touch(key);
             self.pr_data.get(key)
@@ -151,11 +208,20 @@ impl SessionCache {
     }
 
     pub fn put_pr_data(&mut self, key: PrCacheKey, data: PrData) {
+        self.write_to_disk(|disk| disk.save_pr_data(&key, &data.pr, &data.files));
         self.touch(&key);
         self.pr_data.insert(key, data);
         self.evict_if_needed();
     }
 
+    /// エントリを取り出してキャッシュから削除する（ローカルモードでディレクトリを切り替えるときの退避用）
+    pub fn take_pr_data(&mut self, key: &PrCacheKey) -> Option<PrData> {
+        self.access_order.retain(|k| k != key);
+        self.review_comments.remove(key);
+        self.discussion_comments.remove(key);
+        self.pr_data.remove(key)
+    }
+
     pub fn get_review_comments(&self, key: &PrCacheKey) -> Option<&[ReviewComment]> {
         self.review_comments.get(key).map(|v| v.as_slice())
     }
@@ -163,11 +229,13 @@ impl SessionCache {
     /// レビューコメントを保存する。対応する `pr_data` が存在しないキーには保存しない。
     pub fn put_review_comments(&mut self, key: PrCacheKey, comments: Vec<ReviewComment>) {
         if self.pr_data.contains_key(&key) {
+            self.write_to_disk(|disk| disk.save_review_comments(&key, Some(&comments)));
             self.review_comments.insert(key, comments);
         }
     }
 
     pub fn remove_review_comments(&mut self, key: &PrCacheKey) {
+        self.write_to_disk(|disk| disk.save_review_comments(key, None));
         self.review_comments.remove(key);
     }
 
@@ -178,14 +246,33 @@ impl SessionCache {
     /// ディスカッションコメントを保存する。対応する `pr_data` が存在しないキーには保存しない。
     pub fn put_discussion_comments(&mut self, key: PrCacheKey, comments: Vec<DiscussionComment>) {
         if self.pr_data.contains_key(&key) {
+            self.write_to_disk(|disk| disk.save_discussion_comments(&key, Some(&comments)));
             self.discussion_comments.insert(key, comments);
         }
     }
 
     pub fn remove_discussion_comments(&mut self, key: &PrCacheKey) {
+        self.write_to_disk(|disk| disk.save_discussion_comments(key, None));
         self.discussion_comments.remove(key);
     }
 
+    /// ファイルの多い PR で続きのページの変更ファイルを追加する。
+    /// 揃うまではディスクから消しておき、再起動後に途中までの一覧が使われないようにする
+    pub fn extend_files(&mut self, key: &PrCacheKey, files: Vec<ChangedFile>, complete: bool) {
+        let Some(pr_data) = self.pr_data.get_mut(key) else {
+            return;
+        };
+        pr_data.files.extend(files);
+        let Some(pr_data) = self.pr_data.get(key) else {
+            return;
+        };
+        if complete {
+            self.write_to_disk(|disk| disk.save_pr_data(key, &pr_data.pr, &pr_data.files));
+        } else {
+            self.write_to_disk(|disk| disk.remove(key));
+        }
+    }
+
     /// 特定ファイルの patch を更新（lazy diff ロード結果の反映用）
     pub fn update_file_patch(&mut self, key: &PrCacheKey, filename: &str, patch: Option<String>) {
         if let Some(pr_data) = self.pr_data.get_mut(key) {
@@ -237,6 +324,8 @@ mod tests {
                 login: "testuser".to_string(),
             },
             updated_at: updated_at.to_string(),
+            labels: Vec::new(),
+            draft: false,
         }
     }
 
@@ -702,4 +791,109 @@ mod tests {
         assert!(cache.get_review_comments(&evicted_key).is_none());
         assert!(cache.get_discussion_comments(&evicted_key).is_none());
     }
+
+    #[test]
+    fn test_session_cache_take_pr_data() {
+        let mut cache = SessionCache::new();
+        let key = PrCacheKey {
+            repo: "owner/repo".to_string(),
+            pr_number: 0,
+        };
+        assert!(cache.take_pr_data(&key).is_none());
+        cache.put_pr_data(
+            key.clone(),
+            PrData {
+                pr: Box::new(make_test_pr("Local", "2024-01-01")),
+                files: vec![],
+                pr_updated_at: "2024-01-01".to_string(),
+            },
+        );
+        cache.put_review_comments(key.clone(), vec![]);
+
+        let taken = cache.take_pr_data(&key).unwrap();
+        assert_eq!(taken.pr.title, "Local");
+        assert!(cache.get_pr_data(&key).is_none());
+        assert!(cache.get_review_comments(&key).is_none());
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn test_session_cache_restores_from_disk() {
+        let root = tempfile::tempdir().unwrap();
+        let disk = || {
+            DiskCache::new(
+                root.path().to_path_buf(),
+                std::time::Duration::from_secs(3600),
+                u64::MAX,
+            )
+        };
+        let key = PrCacheKey {
+            repo: "owner/repo".to_string(),
+            pr_number: 1,
+        };
+
+        let mut cache = SessionCache::with_disk(disk());
+        cache.put_pr_data(
+            key.clone(),
+            PrData {
+                pr: Box::new(make_test_pr("Cached", "2024-01-01")),
+                files: vec![],
+                pr_updated_at: "2024-01-01".to_string(),
+            },
+        );
+        cache.put_review_comments(key.clone(), vec![]);
+
+        // 再起動後の新しいセッションでもディスクから読める
+        let mut restarted = SessionCache::with_disk(disk());
+        assert_eq!(restarted.get_pr_data(&key).unwrap().pr.title, "Cached");
+        assert_eq!(restarted.get_review_comments(&key).map(<[_]>::len), Some(0));
+        assert!(restarted.get_discussion_comments(&key).is_none());
+
+        restarted.remove_review_comments(&key);
+        let mut restarted = SessionCache::with_disk(disk());
+        assert!(restarted.get_pr_data(&key).is_some());
+        assert!(restarted.get_review_comments(&key).is_none());
+    }
+
+    #[test]
+    fn test_session_cache_keeps_partial_file_list_off_disk() {
+        let root = tempfile::tempdir().unwrap();
+        let disk = || {
+            DiskCache::new(
+                root.path().to_path_buf(),
+                std::time::Duration::from_secs(3600),
+                u64::MAX,
+            )
+        };
+        let key = PrCacheKey {
+            repo: "owner/repo".to_string(),
+            pr_number: 2,
+        };
+        let file = |name: &str| ChangedFile {
+            filename: name.to_string(),
+            status: "added".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+            viewed: false,
+        };
+
+        let mut cache = SessionCache::with_disk(disk());
+        cache.put_pr_data(
+            key.clone(),
+            PrData {
+                pr: Box::new(make_test_pr("Huge", "2024-01-01")),
+                files: vec![file("a.rs")],
+                pr_updated_at: "2024-01-01".to_string(),
+            },
+        );
+        // 続きのページを読み込んでいる間はディスクに残さない
+        cache.extend_files(&key, vec![file("b.rs")], false);
+        assert_eq!(cache.get_pr_data(&key).unwrap().files.len(), 2);
+        assert!(SessionCache::with_disk(disk()).get_pr_data(&key).is_none());
+
+        cache.extend_files(&key, vec![file("c.rs")], true);
+        let mut restarted = SessionCache::with_disk(disk());
+        assert_eq!(restarted.get_pr_data(&key).unwrap().files.len(), 3);
+    }
 }
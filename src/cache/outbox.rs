@@ -0,0 +1,384 @@
+//! 送信に失敗したコメント・レビューの送信待ち
+//!
+//! ネットワークの切断やレート制限で送信できなかったインラインコメント・返信・レビューを
+//! キャッシュディレクトリの `outbox.json` に保存しておき、時間をおいて送り直す。
+//! 失敗するたびに次に送るまでの間隔を倍にする（[`RETRY_BASE_SECS`] から [`RETRY_MAX_SECS`] まで）。
+//! 送り直すかどうかは [`is_retryable`] で判定し、内容の誤り（422 など）は保存しない。
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use super::cache_dir;
+use crate::github::{DraftReviewComment, ReviewAction};
+
+const OUTBOX_FILE: &str = "outbox.json";
+/// 自動では送り直さない送信待ちの `next_attempt_at`
+const HELD: i64 = i64::MAX;
+
+/// 最初に送り直すまでの秒数
+pub const RETRY_BASE_SECS: i64 = 30;
+/// 送り直す間隔の上限（秒）
+pub const RETRY_MAX_SECS: i64 = 30 * 60;
+
+/// 送信する内容
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum OutboxPayload {
+    /// インラインコメント（サジェスチョンを含む）
+    Comment {
+        commit_id: String,
+        path: String,
+        /// patch 内オフセット（単一行コメントで使用）
+        position: u32,
+        /// 複数行コメントの開始行。None なら単一行コメント
+        start_line: Option<u32>,
+        line: u32,
+        body: String,
+        #[serde(default)]
+        suggestion: bool,
+    },
+    Reply {
+        comment_id: u64,
+        body: String,
+    },
+    /// レビュー（下書きのインラインコメントを含む）
+    Review {
+        commit_id: String,
+        action: ReviewAction,
+        body: String,
+        #[serde(default)]
+        comments: Vec<DraftReviewComment>,
+    },
+}
+
+impl OutboxPayload {
+    /// 一覧に出す種類
+    pub fn kind_label(&self) -> &'static str {
+        match self {
+            OutboxPayload::Comment {
+                suggestion: true, ..
+            } => "suggestion",
+            OutboxPayload::Comment { .. } => "comment",
+            OutboxPayload::Reply { .. } => "reply",
+            OutboxPayload::Review {
+                action: ReviewAction::Approve,
+                ..
+            } => "approve",
+            OutboxPayload::Review {
+                action: ReviewAction::RequestChanges,
+                ..
+            } => "request changes",
+            OutboxPayload::Review { .. } => "review comment",
+        }
+    }
+
+    /// 送信先（`path:line`、返信先のコメント ID など）
+    pub fn target(&self) -> Option<String> {
+        match self {
+            OutboxPayload::Comment {
+                path,
+                start_line: Some(start),
+                line,
+                ..
+            } => Some(format!("{}:{}-{}", path, start, line)),
+            OutboxPayload::Comment { path, line, .. } => Some(format!("{}:{}", path, line)),
+            OutboxPayload::Reply { comment_id, .. } => Some(format!("#{}", comment_id)),
+            OutboxPayload::Review { comments, .. } if !comments.is_empty() => {
+                Some(format!("{} inline comment(s)", comments.len()))
+            }
+            OutboxPayload::Review { .. } => None,
+        }
+    }
+
+    pub fn body(&self) -> &str {
+        match self {
+            OutboxPayload::Comment { body, .. }
+            | OutboxPayload::Reply { body, .. }
+            | OutboxPayload::Review { body, .. } => body,
+        }
+    }
+}
+
+/// 送信待ちの 1 件
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OutboxEntry {
+    pub id: u64,
+    pub repo: String,
+    pub pr_number: u32,
+    pub payload: OutboxPayload,
+    /// 送信を試みた回数
+    pub attempts: u32,
+    pub last_error: String,
+    /// RFC 3339 (UTC)
+    pub queued_at: String,
+    /// 次に送り直す時刻（UNIX ミリ秒）
+    pub next_attempt_at: i64,
+}
+
+impl OutboxEntry {
+    /// 自動では送り直さない（手動で送り直すのを待っている）
+    pub fn is_held(&self) -> bool {
+        self.next_attempt_at == HELD
+    }
+}
+
+/// 送信待ちの一覧
+#[derive(Debug, Default)]
+pub struct Outbox {
+    entries: Vec<OutboxEntry>,
+    /// 保存先。None なら保存しない
+    path: Option<PathBuf>,
+}
+
+impl Outbox {
+    /// キャッシュディレクトリに保存した送信待ちを読み込む
+    pub fn load() -> Self {
+        Self::load_from(cache_dir().join(OUTBOX_FILE))
+    }
+
+    pub fn load_from(path: PathBuf) -> Self {
+        let entries = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            entries,
+            path: Some(path),
+        }
+    }
+
+    pub fn entries(&self) -> &[OutboxEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 1 回目の送信に失敗した内容を追加して保存する
+    pub fn push(
+        &mut self,
+        repo: &str,
+        pr_number: u32,
+        payload: OutboxPayload,
+        error: &str,
+        now_ms: i64,
+    ) -> Result<u64> {
+        let id = self
+            .entries
+            .iter()
+            .map(|entry| entry.id + 1)
+            .max()
+            .unwrap_or(1);
+        self.entries.push(OutboxEntry {
+            id,
+            repo: repo.to_string(),
+            pr_number,
+            payload,
+            attempts: 1,
+            last_error: error.to_string(),
+            queued_at: Utc::now().to_rfc3339(),
+            next_attempt_at: now_ms + retry_delay_ms(1),
+        });
+        self.save()?;
+        Ok(id)
+    }
+
+    /// 送り直す時刻になった送信待ち
+    pub fn due(&self, now_ms: i64) -> Vec<OutboxEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.next_attempt_at <= now_ms)
+            .cloned()
+            .collect()
+    }
+
+    /// 次に送り直す時刻が最も早いもの
+    pub fn next_attempt_at(&self) -> Option<i64> {
+        self.entries.iter().map(|entry| entry.next_attempt_at).min()
+    }
+
+    /// すべてを今すぐ送り直す対象にする
+    pub fn retry_all_now(&mut self, now_ms: i64) -> Result<()> {
+        for entry in &mut self.entries {
+            entry.next_attempt_at = now_ms;
+        }
+        self.save()
+    }
+
+    /// 送り直しに失敗した。次に送る時刻を遅らせる。
+    /// 時間をおいても送れないエラーなら、手動で送り直すまで止める
+    pub fn record_failure(&mut self, id: u64, error: &str, now_ms: i64) -> Result<()> {
+        if let Some(entry) = self.entries.iter_mut().find(|entry| entry.id == id) {
+            entry.attempts += 1;
+            entry.last_error = error.to_string();
+            entry.next_attempt_at = if is_retryable(error) {
+                now_ms + retry_delay_ms(entry.attempts)
+            } else {
+                HELD
+            };
+        }
+        self.save()
+    }
+
+    /// 送信できた・送るのをやめたものを取り除く
+    pub fn remove(&mut self, id: u64) -> Result<Option<OutboxEntry>> {
+        let Some(index) = self.entries.iter().position(|entry| entry.id == id) else {
+            return Ok(None);
+        };
+        let entry = self.entries.remove(index);
+        self.save()?;
+        Ok(Some(entry))
+    }
+
+    fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if self.entries.is_empty() {
+            if path.exists() {
+                std::fs::remove_file(path)?;
+            }
+            return Ok(());
+        }
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(&self.entries)?)?;
+        Ok(())
+    }
+}
+
+/// `attempts` 回失敗した後、次に送るまでの間隔
+fn retry_delay_ms(attempts: u32) -> i64 {
+    let shift = attempts.saturating_sub(1).min(16);
+    (RETRY_BASE_SECS << shift).min(RETRY_MAX_SECS) * 1000
+}
+
+/// 時間をおけば送れそうな失敗か（ネットワークの切断、レート制限、GitHub 側の一時的な障害）
+pub fn is_retryable(error: &str) -> bool {
+    let error = error.to_lowercase();
+    [
+        "rate limit",
+        "timed out",
+        "timeout",
+        "connection",
+        "connect error",
+        "error connecting",
+        "could not resolve",
+        "dns error",
+        "network",
+        "500 internal server error",
+        "502 bad gateway",
+        "503 service unavailable",
+        "504 gateway timeout",
+        "http 500",
+        "http 502",
+        "http 503",
+        "http 504",
+    ]
+    .iter()
+    .any(|pattern| error.contains(pattern))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn reply(body: &str) -> OutboxPayload {
+        OutboxPayload::Reply {
+            comment_id: 42,
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_outbox_persists_and_backs_off() {
+        let root = tempfile::tempdir().unwrap();
+        let path = root.path().join(OUTBOX_FILE);
+        let mut outbox = Outbox::load_from(path.clone());
+        let id = outbox
+            .push("owner/repo", 7, reply("thanks"), "connection reset", 0)
+            .unwrap();
+        let review = OutboxPayload::Review {
+            commit_id: "abc".to_string(),
+            action: ReviewAction::Approve,
+            body: String::new(),
+            comments: vec![DraftReviewComment {
+                path: "src/a.rs".to_string(),
+                body: "nit".to_string(),
+                position: 3,
+                start_line: None,
+                line: 10,
+            }],
+        };
+        outbox
+            .push(
+                "owner/repo",
+                8,
+                review.clone(),
+                "API rate limit exceeded",
+                0,
+            )
+            .unwrap();
+
+        let mut loaded = Outbox::load_from(path.clone());
+        assert_eq!(loaded.entries(), outbox.entries());
+        assert_eq!(loaded.entries()[1].payload, review);
+        assert_eq!(loaded.entries()[1].payload.kind_label(), "approve");
+
+        // 最初は 30 秒後、失敗するたびに倍になる
+        assert!(loaded.due(29_999).is_empty());
+        assert_eq!(loaded.due(30_000).len(), 2);
+        loaded.record_failure(id, "timed out", 30_000).unwrap();
+        assert_eq!(loaded.entries()[0].attempts, 2);
+        assert_eq!(loaded.entries()[0].next_attempt_at, 90_000);
+        assert_eq!(loaded.next_attempt_at(), Some(30_000));
+
+        // 内容の誤りで失敗したら自動では送らない
+        loaded
+            .record_failure(id, "Validation Failed (422 Unprocessable Entity)", 30_000)
+            .unwrap();
+        assert!(loaded.entries()[0].is_held());
+        assert!(loaded.due(i64::MAX - 1).iter().all(|entry| entry.id != id));
+
+        loaded.retry_all_now(40_000).unwrap();
+        assert_eq!(loaded.due(40_000).len(), 2);
+
+        // 空になったら保存先のファイルも消す
+        assert!(loaded.remove(id).unwrap().is_some());
+        assert!(loaded.remove(id).unwrap().is_none());
+        let second = loaded.entries()[0].id;
+        loaded.remove(second).unwrap();
+        assert!(!path.exists());
+        assert!(Outbox::load_from(path).is_empty());
+    }
+
+    #[test]
+    fn test_retry_delay_is_capped() {
+        assert_eq!(retry_delay_ms(1), 30_000);
+        assert_eq!(retry_delay_ms(3), 120_000);
+        assert_eq!(retry_delay_ms(40), RETRY_MAX_SECS * 1000);
+    }
+
+    #[test]
+    fn test_is_retryable() {
+        assert!(is_retryable(
+            "API rate limit exceeded for user (403 Forbidden)"
+        ));
+        assert!(is_retryable("error connecting to api.github.com"));
+        assert!(is_retryable("HTTP 502: Bad Gateway"));
+        assert!(is_retryable("503 Service Unavailable"));
+        assert!(!is_retryable(
+            "Validation Failed (422 Unprocessable Entity)"
+        ));
+        assert!(!is_retryable("Not Found (404 Not Found)"));
+    }
+}
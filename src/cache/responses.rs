@@ -0,0 +1,236 @@
+//! GitHub API のレスポンスキャッシュ（ETag による条件付き GET）
+//!
+//! GET したレスポンスの本文を ETag と一緒にキャッシュディレクトリの `responses/` に保存しておき、
+//! 次に同じエンドポイントを取得するときは `If-None-Match` を付けて問い合わせる。
+//! 変わっていなければ GitHub は本文なしの 304 を返すため、保存した本文をそのまま使う。
+//! 再読み込みで変わっていない変更ファイルやコメントのページをダウンロードし直さずに済み、
+//! 304 はレート制限にも数えられない。
+//!
+//! ページネーションはページごとに保存する（次のページの URL も保存する）。
+//! 期限切れとサイズ上限による削除は [`super::DiskCache`] と同じ `[cache]` の設定に従う。
+
+use std::path::PathBuf;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::cache_dir;
+use crate::config::CacheConfig;
+
+const RESPONSES_DIR: &str = "responses";
+
+/// 保存したレスポンス
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedResponse {
+    pub etag: String,
+    pub body: String,
+    /// ページネーションの次のページの URL
+    #[serde(default)]
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ResponseCache {
+    dir: PathBuf,
+    ttl: Duration,
+    max_bytes: u64,
+}
+
+impl ResponseCache {
+    pub fn new(dir: PathBuf, ttl: Duration, max_bytes: u64) -> Self {
+        Self {
+            dir,
+            ttl,
+            max_bytes,
+        }
+    }
+
+    /// `[cache]` の設定から作る。無効なら None
+    pub fn from_config(config: &CacheConfig) -> Option<Self> {
+        config.enabled.then(|| {
+            Self::new(
+                cache_dir().join(RESPONSES_DIR),
+                Duration::from_secs(config.ttl_hours * 60 * 60),
+                config.max_size_mb * 1024 * 1024,
+            )
+        })
+    }
+
+    /// `Accept` とエンドポイントごとの保存先
+    fn entry_path(&self, endpoint: &str, accept: Option<&str>) -> PathBuf {
+        let key = format!("{}\n{}", accept.unwrap_or_default(), endpoint);
+        self.dir
+            .join(format!("{:016x}.json", fnv1a(key.as_bytes())))
+    }
+
+    fn is_expired(&self, modified: SystemTime) -> bool {
+        SystemTime::now()
+            .duration_since(modified)
+            .is_ok_and(|age| age >= self.ttl)
+    }
+
+    /// 保存済みのレスポンス。期限切れや壊れたものは消して None
+    pub fn load(&self, endpoint: &str, accept: Option<&str>) -> Option<CachedResponse> {
+        let path = self.entry_path(endpoint, accept);
+        let modified = std::fs::metadata(&path).and_then(|m| m.modified()).ok()?;
+        if self.is_expired(modified) {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+        let response = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok());
+        if response.is_none() {
+            let _ = std::fs::remove_file(&path);
+        }
+        response
+    }
+
+    pub fn store(
+        &self,
+        endpoint: &str,
+        accept: Option<&str>,
+        response: &CachedResponse,
+    ) -> Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        std::fs::write(
+            self.entry_path(endpoint, accept),
+            serde_json::to_string(response)?,
+        )?;
+        self.evict()?;
+        Ok(())
+    }
+
+    /// 304 で使い続けているレスポンスの期限を延ばす
+    pub fn touch(&self, endpoint: &str, accept: Option<&str>) {
+        if let Ok(file) = std::fs::File::options()
+            .write(true)
+            .open(self.entry_path(endpoint, accept))
+        {
+            let _ = file.set_modified(SystemTime::now());
+        }
+    }
+
+    /// 期限切れのレスポンスを消し、合計サイズが上限を超えていれば保存の古い順に消す。
+    /// 消した数を返す
+    pub fn evict(&self) -> Result<usize> {
+        let Ok(dir) = std::fs::read_dir(&self.dir) else {
+            return Ok(0);
+        };
+        let mut entries: Vec<(PathBuf, SystemTime, u64)> = dir
+            .flatten()
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "json"))
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                Some((entry.path(), metadata.modified().ok()?, metadata.len()))
+            })
+            .collect();
+
+        let mut removed = 0;
+        entries.retain(|(path, modified, _)| {
+            if self.is_expired(*modified) {
+                removed += usize::from(std::fs::remove_file(path).is_ok());
+                false
+            } else {
+                true
+            }
+        });
+
+        entries.sort_by_key(|(_, modified, _)| *modified);
+        let mut total: u64 = entries.iter().map(|(_, _, size)| size).sum();
+        for (path, _, size) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            std::fs::remove_file(&path)?;
+            total -= size;
+            removed += 1;
+        }
+        Ok(removed)
+    }
+}
+
+/// 保存先のファイル名に使うハッシュ（実行ごとに変わらないもの）
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, &byte| {
+        (hash ^ u64::from(byte)).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn response(etag: &str, body: &str) -> CachedResponse {
+        CachedResponse {
+            etag: etag.to_string(),
+            body: body.to_string(),
+            next: None,
+        }
+    }
+
+    #[test]
+    fn test_responses_are_stored_per_endpoint_and_accept() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(
+            root.path().to_path_buf(),
+            Duration::from_secs(3600),
+            u64::MAX,
+        );
+        let endpoint = "repos/owner/repo/pulls/1";
+        cache
+            .store(endpoint, None, &response("\"a\"", "{}"))
+            .unwrap();
+        cache
+            .store(
+                endpoint,
+                Some("application/vnd.github.v3.diff"),
+                &response("\"b\"", "diff"),
+            )
+            .unwrap();
+
+        assert_eq!(cache.load(endpoint, None), Some(response("\"a\"", "{}")));
+        assert_eq!(
+            cache
+                .load(endpoint, Some("application/vnd.github.v3.diff"))
+                .unwrap()
+                .body,
+            "diff"
+        );
+        assert!(cache.load("repos/owner/repo/pulls/2", None).is_none());
+    }
+
+    #[test]
+    fn test_expired_responses_are_dropped_unless_touched() {
+        let root = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(
+            root.path().to_path_buf(),
+            Duration::from_secs(3600),
+            u64::MAX,
+        );
+        let backdate = |endpoint: &str| {
+            let file = std::fs::File::options()
+                .write(true)
+                .open(cache.entry_path(endpoint, None))
+                .unwrap();
+            file.set_modified(SystemTime::now() - Duration::from_secs(7200))
+                .unwrap();
+        };
+        cache.store("a", None, &response("1", "a")).unwrap();
+        cache.store("b", None, &response("2", "b")).unwrap();
+        backdate("a");
+        backdate("b");
+        cache.touch("b", None);
+
+        assert!(cache.load("a", None).is_none());
+        assert!(!cache.entry_path("a", None).exists());
+        assert!(cache.load("b", None).is_some());
+    }
+
+    #[test]
+    fn test_fnv1a_is_stable() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+    }
+}
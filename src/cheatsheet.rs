@@ -0,0 +1,610 @@
+//! ヘルプ画面のキーバインド一覧（チートシート）
+//!
+//! 画面・状態ごとのグループに「キーのテンプレート」と説明を並べた表から作る。
+//! テンプレートの `{move_down}` などはアクション名で、[`KeybindingsConfig::bindings`] の
+//! 現在のキーに置き換えるため、ユーザーの割り当て変更がそのまま表示に反映される。
+//! グループに画面（[`KeybindingView`]）があれば `[keybindings.views.<view>]` の上書きも反映する。
+//! `Esc` や `Tab` など割り当てを変えられないキーはテンプレートにそのまま書く。
+
+use crate::config::{KeybindingView, KeybindingsConfig};
+
+/// 1 行分の定義（キーのテンプレート, 説明）
+type Row = (&'static str, &'static str);
+
+/// グループの定義
+struct GroupSpec {
+    title: &'static str,
+    /// 画面内の状態（フォーカス中のペインなど）
+    state: Option<&'static str>,
+    /// 上書きを反映する画面。None なら共通の割り当てを使う
+    view: Option<KeybindingView>,
+    rows: &'static [Row],
+}
+
+const GROUPS: &[GroupSpec] = &[
+    GroupSpec {
+        title: "PR List",
+        state: None,
+        view: Some(KeybindingView::PrList),
+        rows: &[
+            ("{move_down}/{move_up}, Down/Up", "Move selection"),
+            ("{page_down}/{page_up}", "Page down/up"),
+            ("{jump_to_first}/{jump_to_last}", "Jump to first/last PR"),
+            ("{open_panel}", "Open PR"),
+            ("{open_pr_tab}", "Open PR in a new tab"),
+            ("1-9", "Go to an open PR tab"),
+            ("{open_in_browser}", "Open PR in browser"),
+            (
+                "{pr_state_open}/{pr_state_closed}/{pr_state_all}",
+                "Show open/closed/all PRs",
+            ),
+            ("{filter}", "Filter list"),
+            ("{refresh}", "Refresh (clear cache and reload)"),
+            ("{toggle_local_mode}", "Toggle local diff mode"),
+            (
+                "{toggle_time_format}",
+                "Toggle relative/absolute timestamps",
+            ),
+            ("{local_data}", "Manage local data (rally sessions, logs)"),
+            ("{outbox}", "Outbox (submissions waiting to be retried)"),
+            ("{nudge}", "Nudge stale PR"),
+            ("{switch_repo}", "Switch repository"),
+            ("{inbox}", "Notifications inbox (review requests)"),
+            ("{help}", "Toggle help"),
+            ("{quit}", "Quit"),
+        ],
+    },
+    GroupSpec {
+        title: "File List View",
+        state: None,
+        view: Some(KeybindingView::FileList),
+        rows: &[
+            ("{move_down}/{move_up}, Down/Up", "Move selection"),
+            ("{open_panel}", "Open split view"),
+            ("{mark_viewed}", "Mark selected file as viewed"),
+            (
+                "{mark_viewed_directory}",
+                "Mark selected directory as viewed",
+            ),
+            ("{mark_reviewed}", "Mark file as reviewed (saved locally)"),
+            ("{file_note}", "Edit a note for the file (saved locally)"),
+            ("{approve}", "Approve PR"),
+            (
+                "{approve_next}",
+                "Approve and open the next PR in the queue",
+            ),
+            ("{request_changes}", "Request changes"),
+            ("{comment}", "Comment only"),
+            ("{comment_list}", "View review comments"),
+            ("{review_drafts}", "Pending review comments"),
+            (
+                "{toggle_review_draft}",
+                "Toggle draft mode (queue comments for one review)",
+            ),
+            ("{commit_list}", "Browse commits (view one commit's diff)"),
+            ("{checks}", "CI checks (open failed job logs)"),
+            (
+                "{pr_overview}",
+                "PR overview (description, reviewers, linked issues)",
+            ),
+            (
+                "{pr_summary}",
+                "AI summary (risk areas, review order, breaking changes)",
+            ),
+            (
+                "{review_agenda}",
+                "Review agenda (post or save for pair review)",
+            ),
+            ("{request_reviewers}", "Request reviewers / assign users"),
+            ("{edit_labels}", "Edit labels"),
+            (
+                "{toggle_pr_draft}",
+                "Mark ready for review / convert to draft",
+            ),
+            ("{ai_rally}", "Start AI Rally"),
+            ("{mark_rally_file}", "Mark file for a scoped AI Rally"),
+            (
+                "{ai_rally_files}",
+                "AI Rally on the marked files (or this file)",
+            ),
+            ("{resume_ai_rally}", "Resume the unfinished AI Rally"),
+            ("{open_in_browser}", "Open PR in browser"),
+            ("1-9, {prev_pr_tab}/{next_pr_tab}", "Switch PR tab"),
+            ("{close_pr_tab}", "Close PR tab"),
+            ("{refresh}", "Refresh (clear cache and reload)"),
+            ("{help}", "Toggle help"),
+            ("{toggle_local_mode}", "Toggle local diff mode"),
+            ("{checkout_pr}", "Check out the PR branch"),
+            ("{create_pr}", "Create a PR from the branch (local mode)"),
+            ("{toggle_auto_focus}", "Toggle auto-focus (local mode)"),
+            ("{fixup}", "Fixup commits for review comments (local mode)"),
+            (
+                "{resolve_conflicts}",
+                "Resolve merge conflicts (local mode)",
+            ),
+            (
+                "{session_activity}",
+                "Session activity (actions posted this session)",
+            ),
+            ("{key_stats}", "Key usage stats (learning mode)"),
+            ("{local_data}", "Manage local data (rally sessions, logs)"),
+            ("{outbox}", "Outbox (submissions waiting to be retried)"),
+            ("{switch_repo}", "Switch repository (local mode)"),
+            (
+                "{toggle_vendored}",
+                "Vendored dependencies and licence changes",
+            ),
+            ("{filter}", "Filter list"),
+            ("{file_finder}", "Fuzzy find files and symbols"),
+            ("{grep}", "Search all changed files (grep)"),
+            ("{sort_files}", "Sort files (path/additions/status/recent)"),
+            ("{quit}", "Quit (asks first if review drafts are unsent)"),
+        ],
+    },
+    GroupSpec {
+        title: "Split View",
+        state: Some("File List Focus"),
+        view: Some(KeybindingView::FileList),
+        rows: &[
+            (
+                "{move_down}/{move_up}, Down/Up",
+                "Move file selection (diff follows)",
+            ),
+            ("{filter}", "Filter list"),
+            ("{open_panel}, Right, {move_right}", "Focus diff pane"),
+            ("Left, {move_left}, {quit}", "Back to file list"),
+        ],
+    },
+    GroupSpec {
+        title: "Split View",
+        state: Some("Diff Focus"),
+        view: Some(KeybindingView::DiffView),
+        rows: &[
+            ("{move_down}/{move_up}, Down/Up", "Scroll diff"),
+            ("{page_down}/{page_up}", "Page scroll (also J/K)"),
+            ("{go_to_definition}", "Go to definition"),
+            ("{go_to_file}", "Open file in $EDITOR"),
+            ("{lfs_preview}", "Preview Git LFS object contents"),
+            ("{view_base_file}", "View full file (deleted files at base)"),
+            ("{toggle_blame}", "Show/hide blame for unchanged lines"),
+            ("{line_history}", "History of the selected line(s)"),
+            ("{toggle_vendored}", "Expand/collapse vendored file"),
+            ("{compare_local}", "Compare with local working tree"),
+            ("{checkout_pr}", "Check out the PR branch"),
+            ("{explain_file}", "Explain this file's change (AI)"),
+            ("{draft_comment}", "Draft a comment on the line (AI)"),
+            (
+                "{ai_rally_files}",
+                "AI Rally on this file (or the marked files)",
+            ),
+            ("{resume_ai_rally}", "Resume the unfinished AI Rally"),
+            (
+                "{resolve_conflicts}",
+                "Resolve merge conflicts (local mode)",
+            ),
+            ("{jump_to_first}/{jump_to_last}", "Jump to first/last line"),
+            ("{jump_back}", "Jump back"),
+            ("{next_comment}/{prev_comment}", "Next/prev comment"),
+            ("{next_unresolved_thread}", "Next unresolved thread"),
+            (
+                "{search}",
+                "Search in diff (Tab: all files, n/N: next/prev match)",
+            ),
+            ("{grep}", "Search all changed files (grep)"),
+            ("{open_panel}", "Open comment panel"),
+            ("Right, {move_right}", "Open fullscreen diff"),
+            ("Left, {move_left}", "Back to file focus"),
+            ("{quit}", "Back to file list"),
+        ],
+    },
+    GroupSpec {
+        title: "Diff View",
+        state: None,
+        view: Some(KeybindingView::DiffView),
+        rows: &[
+            ("{move_down}/{move_up}, Down/Up", "Move line selection"),
+            ("{page_down}", "Page down (also J)"),
+            ("{page_up}", "Page up (also K)"),
+            ("{jump_to_first}/{jump_to_last}", "Jump to first/last line"),
+            ("{jump_back}", "Jump back"),
+            ("{next_comment}", "Jump to next comment"),
+            ("{prev_comment}", "Jump to previous comment"),
+            ("{next_unresolved_thread}", "Jump to next unresolved thread"),
+            ("{go_to_definition}", "Go to definition"),
+            ("{go_to_file}", "Open file in $EDITOR"),
+            ("{lfs_preview}", "Preview Git LFS object contents"),
+            ("{view_base_file}", "View full file (deleted files at base)"),
+            ("{toggle_blame}", "Show/hide blame for unchanged lines"),
+            ("{line_history}", "History of the selected line(s)"),
+            ("{toggle_vendored}", "Expand/collapse vendored file"),
+            (
+                "{toggle_review_draft}",
+                "Toggle draft mode (queue comments for one review)",
+            ),
+            ("{commit_list}", "Browse commits (view one commit's diff)"),
+            ("{checks}", "CI checks (open failed job logs)"),
+            (
+                "{review_agenda}",
+                "Review agenda (post or save for pair review)",
+            ),
+            ("{request_reviewers}", "Request reviewers / assign users"),
+            ("{edit_labels}", "Edit labels"),
+            (
+                "{toggle_pr_draft}",
+                "Mark ready for review / convert to draft",
+            ),
+            ("{review_drafts}", "Pending review comments"),
+            ("{compare_local}", "Compare with local working tree"),
+            ("{checkout_pr}", "Check out the PR branch"),
+            ("{explain_file}", "Explain this file's change (AI)"),
+            (
+                "{ai_rally_files}",
+                "AI Rally on this file (or the marked files)",
+            ),
+            ("{resume_ai_rally}", "Resume the unfinished AI Rally"),
+            (
+                "{resolve_conflicts}",
+                "Resolve merge conflicts (local mode)",
+            ),
+            (
+                "{search}",
+                "Search in diff (Tab: all files, n/N: next/prev match)",
+            ),
+            ("{grep}", "Search all changed files (grep)"),
+            ("{open_panel}", "Open comment panel"),
+            ("{comment}", "Add comment at line"),
+            ("{suggestion}", "Add suggestion at line"),
+            ("{draft_comment}", "Draft a comment at line (AI)"),
+            ("{multiline_select}/Shift+Enter", "Multiline select mode"),
+            ("{toggle_markdown_rich}", "Toggle markdown rich display"),
+            (
+                "{toggle_time_format}",
+                "Toggle relative/absolute timestamps",
+            ),
+            ("1-9, {prev_pr_tab}/{next_pr_tab}", "Switch PR tab"),
+            ("{close_pr_tab}", "Close PR tab"),
+            ("{quit}, Esc", "Back to file list"),
+        ],
+    },
+    GroupSpec {
+        title: "Diff View",
+        state: Some("Multiline Select Mode"),
+        view: Some(KeybindingView::DiffView),
+        rows: &[
+            ("{move_down}/{move_up}", "Extend selection"),
+            ("{comment}", "Comment on selection"),
+            ("{suggestion}", "Suggest on selection"),
+            ("{draft_comment}", "Draft a comment on selection (AI)"),
+            ("Esc", "Cancel selection"),
+        ],
+    },
+    GroupSpec {
+        title: "Diff View",
+        state: Some("Comment Panel (focused)"),
+        view: Some(KeybindingView::DiffView),
+        rows: &[
+            ("{move_down}/{move_up}", "Scroll panel"),
+            ("{comment}", "Add comment"),
+            ("{suggestion}", "Add suggestion"),
+            ("{draft_comment}", "Draft a comment (AI)"),
+            ("{reply}", "Reply to comment"),
+            ("{translate_comment}", "Translate selected comment"),
+            ("Tab/Shift-Tab", "Select reply target (multiple)"),
+            ("{next_comment}/{prev_comment}", "Jump to next/prev comment"),
+            ("Esc/{quit}", "Close panel"),
+        ],
+    },
+    GroupSpec {
+        title: "Comment List View",
+        state: None,
+        view: Some(KeybindingView::CommentList),
+        rows: &[
+            (
+                "{comments_prev_tab}, {comments_next_tab}",
+                "Switch tab (Review/Discussion)",
+            ),
+            ("{move_down}/{move_up}, Down/Up", "Move selection"),
+            ("{comments_page_down}/{comments_page_up}", "Page down/up"),
+            (
+                "{open_panel}",
+                "Review: Jump to file | Discussion: View detail",
+            ),
+            ("{comments_mark}", "Review: Mark thread for batch reply"),
+            (
+                "{comments_batch_reply}",
+                "Review: Reply to all marked threads",
+            ),
+            (
+                "{comments_apply_suggestion}",
+                "Review: Apply suggestion to local file (y to confirm)",
+            ),
+            ("{comments_toggle_bots}", "Expand/collapse bot activity"),
+            (
+                "{comments_unresolved_only}",
+                "Review: Show only unresolved threads",
+            ),
+            ("{comments_user_info}", "Show the comment author's profile"),
+            ("Tab/Shift-Tab", "Detail: Select next/previous task item"),
+            (
+                "{comments_toggle_task}",
+                "Detail: Check/uncheck task item (own comments)",
+            ),
+            ("{comments_edit}", "Discussion: Edit own comment in $EDITOR"),
+            (
+                "{comments_delete}",
+                "Discussion: Delete own comment (y to confirm)",
+            ),
+            (
+                "{toggle_time_format}",
+                "Toggle relative/absolute timestamps",
+            ),
+            ("{quit}, Esc", "Back to file list"),
+        ],
+    },
+    GroupSpec {
+        title: "Local Data",
+        state: None,
+        view: None,
+        rows: &[
+            ("Space", "Mark item for bulk action"),
+            ("d", "Archive (soft delete)"),
+            ("u", "Restore from archive"),
+            ("x", "Delete archived item permanently"),
+            ("p", "Archive data of closed/merged PRs"),
+            ("{quit}, Esc", "Back"),
+        ],
+    },
+    GroupSpec {
+        title: "Input Mode (Comment/Suggestion/Reply)",
+        state: None,
+        view: None,
+        rows: &[("{submit}", "Submit"), ("Esc", "Cancel input")],
+    },
+    GroupSpec {
+        title: "AI Rally View",
+        state: None,
+        view: Some(KeybindingView::AiRally),
+        rows: &[
+            ("{move_down}/{move_up}, Down/Up", "Select log entry"),
+            (
+                "{rally_log_page_down}/{rally_log_page_up}",
+                "Page through log",
+            ),
+            (
+                "{rally_log_top}/{rally_log_bottom}",
+                "Jump to first/last entry",
+            ),
+            ("{open_panel}", "Show log entry detail"),
+            ("{rally_background}", "Keep running in the background"),
+            ("{rally_retry}", "Retry after an error"),
+            ("{rally_export}", "Save transcript (for --replay)"),
+            ("{rally_export_markdown}", "Save transcript as Markdown"),
+            (
+                "{toggle_time_format}",
+                "Toggle relative/absolute timestamps",
+            ),
+            ("{quit}, Esc", "Abort rally"),
+        ],
+    },
+    GroupSpec {
+        title: "AI Rally View",
+        state: Some("When AI requests permission or clarification"),
+        view: Some(KeybindingView::AiRally),
+        rows: &[
+            ("{rally_approve}", "Grant permission / Answer yes"),
+            ("{rally_deny}", "Deny permission / Skip"),
+        ],
+    },
+    GroupSpec {
+        title: "AI Rally View",
+        state: Some("When replaying a transcript with --replay"),
+        view: Some(KeybindingView::AiRally),
+        rows: &[
+            ("{rally_replay_pause}", "Play / pause"),
+            ("{move_right}, Right", "Step one event"),
+            ("{quit}, Esc", "Stop replay"),
+        ],
+    },
+];
+
+/// チートシートの 1 行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheatsheetEntry {
+    /// 現在の割り当てで表示するキー
+    pub keys: String,
+    pub description: &'static str,
+}
+
+/// 画面・状態ごとのまとまり
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheatsheetGroup {
+    pub title: &'static str,
+    pub state: Option<&'static str>,
+    pub entries: Vec<CheatsheetEntry>,
+}
+
+/// 現在の割り当て（画面ごとの上書きを含む）でチートシートを作る
+pub fn build(kb: &KeybindingsConfig) -> Vec<CheatsheetGroup> {
+    GROUPS
+        .iter()
+        .map(|group| {
+            let view_kb = group.view.map(|view| kb.for_view(view));
+            let kb = view_kb.as_ref().unwrap_or(kb);
+            CheatsheetGroup {
+                title: group.title,
+                state: group.state,
+                entries: group
+                    .rows
+                    .iter()
+                    .map(|(template, description)| CheatsheetEntry {
+                        keys: expand(template, kb),
+                        description,
+                    })
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// テンプレートの `{action}` を現在のキーに置き換える。知らないアクション名はそのまま残す
+fn expand(template: &str, kb: &KeybindingsConfig) -> String {
+    let bindings = kb.bindings();
+    let mut keys = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let Some(len) = rest[start..].find('}') else {
+            break;
+        };
+        let name = &rest[start + 1..start + len];
+        keys.push_str(&rest[..start]);
+        match bindings.iter().find(|(action, _)| *action == name) {
+            Some((_, seq)) => keys.push_str(&seq.display()),
+            None => keys.push_str(&rest[start..=start + len]),
+        }
+        rest = &rest[start + len + 1..];
+    }
+    keys.push_str(rest);
+    keys
+}
+
+/// `query` を含む行だけを残す（大文字小文字を区別しない）。
+/// グループ名か状態名に一致すればそのグループの行はすべて残し、行の残らないグループは除く
+pub fn filter(groups: Vec<CheatsheetGroup>, query: &str) -> Vec<CheatsheetGroup> {
+    let query = query.to_lowercase();
+    if query.is_empty() {
+        return groups;
+    }
+    groups
+        .into_iter()
+        .filter_map(|mut group| {
+            let heading_matches = group.title.to_lowercase().contains(&query)
+                || group
+                    .state
+                    .is_some_and(|state| state.to_lowercase().contains(&query));
+            if !heading_matches {
+                group.entries.retain(|entry| {
+                    entry.keys.to_lowercase().contains(&query)
+                        || entry.description.to_lowercase().contains(&query)
+                });
+            }
+            (!group.entries.is_empty()).then_some(group)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::keybinding::{KeyBinding, KeySequence};
+
+    fn entry<'a>(
+        groups: &'a [CheatsheetGroup],
+        title: &str,
+        description: &str,
+    ) -> &'a CheatsheetEntry {
+        groups
+            .iter()
+            .filter(|group| group.title == title && group.state.is_none())
+            .flat_map(|group| &group.entries)
+            .find(|entry| entry.description == description)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_every_template_names_a_known_action() {
+        for group in build(&KeybindingsConfig::default()) {
+            for entry in group.entries {
+                assert!(
+                    !entry.keys.contains('{'),
+                    "unknown action in {}: {}",
+                    group.title,
+                    entry.keys
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_build_uses_current_bindings() {
+        let groups = build(&KeybindingsConfig::default());
+        assert_eq!(
+            entry(&groups, "File List View", "Move selection").keys,
+            "j/k, Down/Up"
+        );
+        assert_eq!(entry(&groups, "Diff View", "Go to definition").keys, "gd");
+
+        let kb = KeybindingsConfig {
+            quit: KeySequence::single(KeyBinding::char('x')),
+            ..Default::default()
+        };
+        let groups = build(&kb);
+        assert_eq!(
+            entry(
+                &groups,
+                "File List View",
+                "Quit (asks first if review drafts are unsent)"
+            )
+            .keys,
+            "x"
+        );
+        assert_eq!(
+            entry(&groups, "AI Rally View", "Abort rally").keys,
+            "x, Esc"
+        );
+    }
+
+    #[test]
+    fn test_build_applies_view_overrides_per_group() {
+        let mut kb = KeybindingsConfig::default();
+        kb.views.diff_view.insert(
+            "quit".to_string(),
+            KeySequence::single(KeyBinding::char('x')),
+        );
+        let groups = build(&kb);
+        assert_eq!(
+            entry(&groups, "Diff View", "Back to file list").keys,
+            "x, Esc"
+        );
+        assert_eq!(
+            entry(
+                &groups,
+                "File List View",
+                "Quit (asks first if review drafts are unsent)"
+            )
+            .keys,
+            "q"
+        );
+    }
+
+    #[test]
+    fn test_filter_matches_keys_descriptions_and_headings() {
+        let groups = build(&KeybindingsConfig::default());
+        assert_eq!(filter(groups.clone(), ""), groups);
+
+        let found = filter(groups.clone(), "LABELS");
+        assert!(found
+            .iter()
+            .flat_map(|group| &group.entries)
+            .all(|entry| entry.description == "Edit labels"));
+        assert_eq!(
+            found.iter().map(|group| group.title).collect::<Vec<_>>(),
+            vec!["File List View", "Diff View"]
+        );
+
+        let found = filter(groups.clone(), "gd");
+        assert!(found
+            .iter()
+            .flat_map(|group| &group.entries)
+            .all(|entry| entry.description == "Go to definition"));
+
+        // 見出しに一致すればグループの行をすべて残す
+        let found = filter(groups.clone(), "local data");
+        let local_data = found
+            .iter()
+            .find(|group| group.title == "Local Data")
+            .unwrap();
+        assert_eq!(local_data.entries.len(), 6);
+
+        assert!(filter(groups, "no such binding").is_empty());
+    }
+}
@@ -0,0 +1,354 @@
+//! PR のブランチのチェックアウト
+//!
+//! リポジトリを指すリモートから `pull/<n>/head` を取得し、head ブランチと同じ名前の
+//! ローカルブランチに切り替える。fork の PR でも同じ手順で取得できるので `gh` には頼らない。
+//! 既存のブランチは fast-forward できるときだけ進め、ローカルのコミットを失う操作はしない。
+
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+
+/// チェックアウトする PR
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckoutTarget {
+    /// `owner/repo`
+    pub repo: String,
+    pub pr_number: u32,
+    pub head_ref: String,
+    pub base_ref: String,
+}
+
+impl CheckoutTarget {
+    /// 切り替え先のローカルブランチ名。
+    /// head が base と同じ名前（fork の main からの PR など）なら `pr-<n>` にする
+    pub fn local_branch(&self) -> String {
+        if self.head_ref.is_empty() || self.head_ref == self.base_ref {
+            format!("pr-{}", self.pr_number)
+        } else {
+            self.head_ref.clone()
+        }
+    }
+}
+
+/// ローカルブランチの変化
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BranchUpdate {
+    Created,
+    FastForwarded,
+    UpToDate,
+}
+
+/// チェックアウトの結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckoutResult {
+    pub branch: String,
+    pub update: BranchUpdate,
+    /// 未コミットの変更を `git stash` に退避したか
+    pub stashed: bool,
+}
+
+impl CheckoutResult {
+    pub fn summary(&self) -> String {
+        let update = match self.update {
+            BranchUpdate::Created => "created",
+            BranchUpdate::FastForwarded => "fast-forwarded",
+            BranchUpdate::UpToDate => "up to date",
+        };
+        let stashed = if self.stashed {
+            "; local changes are in git stash"
+        } else {
+            ""
+        };
+        format!("Checked out {} ({}){}", self.branch, update, stashed)
+    }
+}
+
+fn run_git(working_dir: Option<&str>, args: &[&str]) -> Result<String> {
+    Ok(run_git_raw(working_dir, args)?.trim().to_string())
+}
+
+/// 出力を trim せずに返す（先頭の空白に意味がある porcelain 出力用）
+fn run_git_raw(working_dir: Option<&str>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new("git");
+    if let Some(dir) = working_dir {
+        command.current_dir(dir);
+    }
+    let output = command
+        .args(args)
+        .output()
+        .context("failed to spawn git command")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// 未コミットの変更があるファイル。未追跡のファイルは切り替えの邪魔にならないので含めない
+pub fn dirty_files(working_dir: Option<&str>) -> Result<Vec<String>> {
+    let status = run_git_raw(
+        working_dir,
+        &["status", "--porcelain", "-z", "--untracked-files=no"],
+    )?;
+    Ok(parse_porcelain_z(&status))
+}
+
+/// `git status --porcelain -z` の出力からファイル名を取り出す。
+/// 名前の変更・コピーは `XY 新しい名前\0元の名前\0` なので、新しい名前だけを使う
+fn parse_porcelain_z(status: &str) -> Vec<String> {
+    let mut files = Vec::new();
+    let mut entries = status.split('\0');
+    while let Some(entry) = entries.next() {
+        let (Some(code), Some(path)) = (entry.get(..2), entry.get(3..)) else {
+            continue;
+        };
+        files.push(path.to_string());
+        if code.contains(['R', 'C']) {
+            entries.next();
+        }
+    }
+    files
+}
+
+/// `git remote -v` の出力から `repo` を指すリモートを探す（`origin` を優先）
+pub fn remote_for_repo(remotes: &str, repo: &str) -> Option<String> {
+    let repo = repo.to_ascii_lowercase();
+    let mut matches: Vec<&str> = remotes
+        .lines()
+        .filter(|line| line.ends_with("(fetch)"))
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            let url = parts.next()?.to_ascii_lowercase();
+            let path = url.trim_end_matches('/').trim_end_matches(".git");
+            let points_to_repo = path
+                .strip_suffix(repo.as_str())
+                .is_some_and(|prefix| prefix.ends_with('/') || prefix.ends_with(':'));
+            points_to_repo.then_some(name)
+        })
+        .collect();
+    matches.sort_by_key(|name| *name != "origin");
+    matches.first().map(|name| name.to_string())
+}
+
+/// PR の head を取得してローカルブランチに切り替える。
+/// `stash` が false のとき未コミットの変更があれば何もせずにエラーを返す
+pub fn checkout_pr(
+    working_dir: Option<&str>,
+    target: &CheckoutTarget,
+    stash: bool,
+) -> Result<CheckoutResult> {
+    let remotes = run_git(working_dir, &["remote", "-v"])?;
+    let remote = remote_for_repo(&remotes, &target.repo)
+        .with_context(|| format!("No git remote points to {}", target.repo))?;
+
+    // 取得は作業ツリーに触れないので、stash より先に済ませる
+    let refspec = format!("pull/{}/head", target.pr_number);
+    run_git(working_dir, &["fetch", "--quiet", &remote, &refspec])?;
+    let fetched = run_git(working_dir, &["rev-parse", "FETCH_HEAD"])?;
+
+    let branch = target.local_branch();
+    let branch_ref = format!("refs/heads/{}", branch);
+    let local = run_git(
+        working_dir,
+        &["rev-parse", "--verify", "--quiet", &branch_ref],
+    )
+    .ok();
+    if let Some(ref local) = local {
+        if *local != fetched
+            && run_git(
+                working_dir,
+                &["merge-base", "--is-ancestor", local, &fetched],
+            )
+            .is_err()
+        {
+            bail!(
+                "Local branch {} has commits that are not in the PR; update it manually",
+                branch
+            );
+        }
+    }
+
+    let dirty = dirty_files(working_dir)?;
+    let stashed = !dirty.is_empty();
+    if stashed {
+        if !stash {
+            bail!(
+                "{} file(s) have uncommitted changes; commit or stash them first",
+                dirty.len()
+            );
+        }
+        let message = format!("octorus: before checking out PR #{}", target.pr_number);
+        run_git(working_dir, &["stash", "push", "--quiet", "-m", &message])?;
+    }
+
+    let switched = switch_branch(working_dir, &branch, local.as_deref(), &fetched);
+    match switched {
+        Ok(update) => Ok(CheckoutResult {
+            branch,
+            update,
+            stashed,
+        }),
+        Err(e) if stashed => {
+            // 切り替えに失敗したら退避した変更を戻す
+            let restored = run_git(working_dir, &["stash", "pop", "--quiet"]);
+            match restored {
+                Ok(_) => Err(e),
+                Err(_) => Err(e.context("Uncommitted changes are left in git stash")),
+            }
+        }
+        Err(e) => Err(e),
+    }
+}
+
+fn switch_branch(
+    working_dir: Option<&str>,
+    branch: &str,
+    local: Option<&str>,
+    fetched: &str,
+) -> Result<BranchUpdate> {
+    let Some(local) = local else {
+        run_git(working_dir, &["switch", "--quiet", "-c", branch, fetched])?;
+        return Ok(BranchUpdate::Created);
+    };
+    let current = run_git(working_dir, &["symbolic-ref", "--quiet", "--short", "HEAD"]).ok();
+    if current.as_deref() != Some(branch) {
+        run_git(working_dir, &["switch", "--quiet", branch])?;
+    }
+    if local == fetched {
+        return Ok(BranchUpdate::UpToDate);
+    }
+    run_git(working_dir, &["merge", "--quiet", "--ff-only", fetched])?;
+    Ok(BranchUpdate::FastForwarded)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "octorus-test")
+            .env("GIT_AUTHOR_EMAIL", "octorus-test@example.com")
+            .env("GIT_COMMITTER_NAME", "octorus-test")
+            .env("GIT_COMMITTER_EMAIL", "octorus-test@example.com")
+            .status()
+            .expect("failed to run git");
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn commit(dir: &Path, file: &str, content: &str) {
+        std::fs::write(dir.join(file), content).unwrap();
+        git(dir, &["add", file]);
+        git(dir, &["commit", "-q", "-m", file]);
+    }
+
+    fn target() -> CheckoutTarget {
+        CheckoutTarget {
+            repo: "owner/repo".to_string(),
+            pr_number: 7,
+            head_ref: "feature".to_string(),
+            base_ref: "main".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_remote_for_repo() {
+        let remotes = "\
+upstream\tgit@github.com:Owner/Repo.git (fetch)
+upstream\tgit@github.com:Owner/Repo.git (push)
+origin\thttps://github.com/owner/repo (fetch)
+origin\thttps://github.com/owner/repo (push)
+fork\thttps://github.com/me/other-repo.git (fetch)
+";
+        assert_eq!(
+            remote_for_repo(remotes, "owner/repo").as_deref(),
+            Some("origin")
+        );
+        assert_eq!(
+            remote_for_repo(remotes, "me/other-repo").as_deref(),
+            Some("fork")
+        );
+        assert_eq!(remote_for_repo(remotes, "er/repo"), None);
+        assert_eq!(remote_for_repo(remotes, "owner/missing"), None);
+    }
+
+    #[test]
+    fn test_parse_porcelain_z() {
+        let status = " M a.txt\0R  new name.txt\0old name.txt\0M  src/b.rs\0";
+        assert_eq!(
+            parse_porcelain_z(status),
+            ["a.txt", "new name.txt", "src/b.rs"]
+        );
+        assert!(parse_porcelain_z("").is_empty());
+    }
+
+    #[test]
+    fn test_local_branch_avoids_base_name() {
+        assert_eq!(target().local_branch(), "feature");
+        let from_fork_main = CheckoutTarget {
+            head_ref: "main".to_string(),
+            ..target()
+        };
+        assert_eq!(from_fork_main.local_branch(), "pr-7");
+    }
+
+    #[test]
+    fn test_checkout_pr_creates_updates_and_stashes() {
+        let tempdir = tempdir().unwrap();
+        let origin = tempdir.path().join("owner").join("repo");
+        std::fs::create_dir_all(&origin).unwrap();
+        git(&origin, &["init", "-q", "-b", "main"]);
+        commit(&origin, "a.txt", "a\n");
+        git(&origin, &["switch", "-q", "-c", "feature"]);
+        commit(&origin, "b.txt", "b\n");
+        git(&origin, &["update-ref", "refs/pull/7/head", "feature"]);
+        git(&origin, &["switch", "-q", "main"]);
+
+        let clone = tempdir.path().join("clone");
+        git(
+            tempdir.path(),
+            &["clone", "-q", origin.to_str().unwrap(), "clone"],
+        );
+        let wd = clone.to_str();
+
+        let result = checkout_pr(wd, &target(), false).unwrap();
+        assert_eq!(result.update, BranchUpdate::Created);
+        assert!(clone.join("b.txt").exists());
+        assert_eq!(
+            run_git(wd, &["branch", "--show-current"]).unwrap(),
+            "feature"
+        );
+
+        let result = checkout_pr(wd, &target(), false).unwrap();
+        assert_eq!(result.update, BranchUpdate::UpToDate);
+
+        // PR に push されたコミットは fast-forward で取り込む
+        git(&origin, &["switch", "-q", "feature"]);
+        commit(&origin, "c.txt", "c\n");
+        git(&origin, &["update-ref", "refs/pull/7/head", "feature"]);
+        git(&clone, &["switch", "-q", "main"]);
+        std::fs::write(clone.join("a.txt"), "changed\n").unwrap();
+
+        let err = checkout_pr(wd, &target(), false).unwrap_err();
+        assert!(err.to_string().contains("uncommitted changes"), "{}", err);
+        assert_eq!(run_git(wd, &["branch", "--show-current"]).unwrap(), "main");
+
+        let result = checkout_pr(wd, &target(), true).unwrap();
+        assert_eq!(result.update, BranchUpdate::FastForwarded);
+        assert!(result.stashed);
+        assert!(clone.join("c.txt").exists());
+        assert!(dirty_files(wd).unwrap().is_empty());
+        assert!(run_git(wd, &["stash", "list"])
+            .unwrap()
+            .contains("before checking out PR #7"));
+
+        // PR にないローカルのコミットは失わない
+        commit(&clone, "local.txt", "local\n");
+        let err = checkout_pr(wd, &target(), false).unwrap_err();
+        assert!(err.to_string().contains("not in the PR"), "{}", err);
+    }
+}
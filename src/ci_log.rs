@@ -0,0 +1,121 @@
+//! GitHub Actions のジョブのログ（`GET /actions/jobs/{job_id}/logs`）の整形
+//!
+//! ログは各行が `タイムスタンプ 本文` の形式で、ステップは `##[group]` の行から始まる。
+//! その行を見出しにしてタイムスタンプを除いた本文だけを残す。エスケープシーケンスは取り除く。
+
+/// 表示する最大行数（超えた分は先頭から捨てる。失敗の原因はたいてい末尾にある）
+pub const MAX_LOG_LINES: usize = 5000;
+
+/// 整形したログの 1 行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CiLogLine {
+    /// ステップ（`##[group]`）の見出し
+    Step(String),
+    /// `##[error]` が付いた行（印は取り除く）
+    Error(String),
+    Text(String),
+}
+
+/// 整形したログ
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CiLog {
+    pub lines: Vec<CiLogLine>,
+    /// 行数の上限で捨てた行数
+    pub omitted: usize,
+}
+
+/// ジョブのログを整形する
+pub fn parse(raw: &str) -> CiLog {
+    let mut lines = Vec::new();
+    for raw_line in raw.trim_start_matches('\u{feff}').lines() {
+        let body = strip_ansi(strip_timestamp(raw_line));
+        if let Some(message) = body.strip_prefix("##[error]") {
+            lines.push(CiLogLine::Error(message.to_string()));
+        } else if let Some(title) = body.strip_prefix("##[group]") {
+            lines.push(CiLogLine::Step(title.to_string()));
+        } else if !body.starts_with("##[endgroup]") {
+            // 折りたたみの終わりの印は TUI では意味がないので捨てる
+            lines.push(CiLogLine::Text(body));
+        }
+    }
+    let omitted = lines.len().saturating_sub(MAX_LOG_LINES);
+    lines.drain(..omitted);
+    CiLog { lines, omitted }
+}
+
+/// 行頭の `2024-01-01T00:00:00.0000000Z ` を取り除く
+fn strip_timestamp(body: &str) -> &str {
+    match body.split_once(' ') {
+        Some((stamp, rest))
+            if stamp.len() >= 20
+                && stamp.ends_with('Z')
+                && stamp.as_bytes()[..4].iter().all(u8::is_ascii_digit) =>
+        {
+            rest
+        }
+        _ => body,
+    }
+}
+
+/// 色付けなどの ANSI エスケープシーケンス（CSI）を取り除く
+fn strip_ansi(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut chars = text.chars();
+    while let Some(c) = chars.next() {
+        if c != '\u{1b}' {
+            result.push(c);
+            continue;
+        }
+        if chars.next() == Some('[') {
+            // パラメータの後の英字で終わる
+            for c in chars.by_ref() {
+                if c.is_ascii_alphabetic() {
+                    break;
+                }
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_groups_lines_by_step() {
+        let raw = "\u{feff}2024-01-01T00:00:01.1234567Z ##[group]Run cargo test\n\
+                   2024-01-01T00:00:01.2234567Z cargo test --locked\n\
+                   2024-01-01T00:00:01.3234567Z ##[endgroup]\n\
+                   2024-01-01T00:00:02.1234567Z \u{1b}[31mtest foo ... FAILED\u{1b}[0m\n\
+                   2024-01-01T00:00:04.1234567Z ##[error]Process completed with exit code 101.\n";
+        let log = parse(raw);
+        assert_eq!(
+            log.lines,
+            vec![
+                CiLogLine::Step("Run cargo test".to_string()),
+                CiLogLine::Text("cargo test --locked".to_string()),
+                CiLogLine::Text("test foo ... FAILED".to_string()),
+                CiLogLine::Error("Process completed with exit code 101.".to_string()),
+            ]
+        );
+        assert_eq!(log.omitted, 0);
+    }
+
+    #[test]
+    fn test_parse_keeps_unexpected_lines() {
+        let log = parse("plain output\n");
+        assert_eq!(log.lines, vec![CiLogLine::Text("plain output".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_keeps_the_tail_of_long_logs() {
+        let raw: String = (0..MAX_LOG_LINES + 10)
+            .map(|i| format!("line {}\n", i))
+            .collect();
+        let log = parse(&raw);
+        assert_eq!(log.lines.len(), MAX_LOG_LINES);
+        assert_eq!(log.omitted, 10);
+        assert_eq!(log.lines[0], CiLogLine::Text("line 10".to_string()));
+    }
+}
@@ -0,0 +1,214 @@
+//! TUI を起動せずにコメント・レビューを送信する（`or comment` / `or approve`）
+//!
+//! 行番号から diff の position への変換は TUI のコメント入力と同じ [`diff`] の関数を使い、
+//! 送信は [`loader::submit_payload`] に任せるので、TUI から送ったものと同じく監査ログに残る。
+
+use anyhow::{anyhow, bail, Result};
+
+use crate::cache::OutboxPayload;
+use crate::diff;
+use crate::github::{self, ChangedFile, ReviewAction};
+use crate::loader;
+
+/// コマンドラインから送る行コメント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineComment {
+    pub path: String,
+    /// 範囲の開始行（new file の行番号。1 行なら None）
+    pub start_line: Option<u32>,
+    /// コメントする行（範囲なら最終行）
+    pub line: u32,
+    pub body: String,
+}
+
+impl LineComment {
+    /// `src/a.rs:10` / `src/a.rs:10-12` の形式の位置
+    pub fn location(&self) -> String {
+        match self.start_line {
+            Some(start) => format!("{}:{}-{}", self.path, start, self.line),
+            None => format!("{}:{}", self.path, self.line),
+        }
+    }
+}
+
+/// 変更ファイルの patch から送信内容を作る。
+/// ファイルが PR に含まれない、行が diff にない、範囲がハンクをまたぐ場合はエラー
+pub fn comment_payload(
+    files: &[ChangedFile],
+    commit_id: &str,
+    comment: &LineComment,
+) -> Result<OutboxPayload> {
+    if comment.body.trim().is_empty() {
+        bail!("The comment body is empty");
+    }
+    let file = files
+        .iter()
+        .find(|f| f.filename == comment.path)
+        .ok_or_else(|| anyhow!("{} is not changed in this PR", comment.path))?;
+    let patch = file
+        .patch
+        .as_deref()
+        .ok_or_else(|| anyhow!("{} has no diff to comment on", comment.path))?;
+    let not_in_diff = |line: u32| anyhow!("Line {} of {} is not in the diff", line, comment.path);
+
+    let end = diff::new_line_index(patch, comment.line).ok_or_else(|| not_in_diff(comment.line))?;
+    let position = diff::get_line_info(patch, end)
+        .and_then(|info| info.diff_position)
+        .ok_or_else(|| not_in_diff(comment.line))?;
+    // 1 行だけの範囲は単一行コメントとして送る
+    let start_line = comment.start_line.filter(|&start| start != comment.line);
+    if let Some(start) = start_line {
+        if start > comment.line {
+            bail!("The start line {} is after line {}", start, comment.line);
+        }
+        let first = diff::new_line_index(patch, start).ok_or_else(|| not_in_diff(start))?;
+        // 削除行は挟んでもよいが、ハンクはまたげない
+        let crosses_hunk = diff::patch_lines(patch)
+            .skip(first)
+            .take(end - first + 1)
+            .any(|line| line.line_type == diff::LineType::Header);
+        if crosses_hunk {
+            bail!(
+                "Lines {}-{} of {} are not in a single hunk of the diff",
+                start,
+                comment.line,
+                comment.path
+            );
+        }
+    }
+
+    Ok(OutboxPayload::Comment {
+        commit_id: commit_id.to_string(),
+        path: comment.path.clone(),
+        position,
+        start_line,
+        line: comment.line,
+        body: comment.body.clone(),
+        suggestion: comment.body.contains("```suggestion"),
+    })
+}
+
+/// PR の head コミットに行コメントを送る
+pub async fn post_comment(repo: &str, pr_number: u32, comment: &LineComment) -> Result<()> {
+    let (pr, files) = tokio::try_join!(
+        github::fetch_pr(repo, pr_number),
+        github::fetch_changed_files(repo, pr_number)
+    )?;
+    let payload = comment_payload(&files, &pr.head.sha, comment)?;
+    loader::submit_payload(repo, pr_number, &payload).await
+}
+
+/// インラインコメントなしのレビューを送る
+pub async fn post_review(
+    repo: &str,
+    pr_number: u32,
+    action: ReviewAction,
+    body: &str,
+) -> Result<()> {
+    let pr = github::fetch_pr(repo, pr_number).await?;
+    let payload = OutboxPayload::Review {
+        commit_id: pr.head.sha,
+        action,
+        body: body.to_string(),
+        comments: Vec::new(),
+    };
+    loader::submit_payload(repo, pr_number, &payload).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATCH: &str = "@@ -1,3 +1,4 @@\n fn main() {\n-    old();\n+    run();\n+    done();\n }\n@@ -20,2 +21,2 @@\n-    a();\n+    b();\n ";
+
+    fn files() -> Vec<ChangedFile> {
+        vec![ChangedFile {
+            filename: "src/main.rs".to_string(),
+            status: "modified".to_string(),
+            additions: 3,
+            deletions: 2,
+            patch: Some(PATCH.to_string()),
+            viewed: false,
+        }]
+    }
+
+    fn comment(start_line: Option<u32>, line: u32) -> LineComment {
+        LineComment {
+            path: "src/main.rs".to_string(),
+            start_line,
+            line,
+            body: "Handle the error".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_comment_payload_single_line() {
+        let payload = comment_payload(&files(), "abc", &comment(None, 3)).unwrap();
+        assert_eq!(
+            payload,
+            OutboxPayload::Comment {
+                commit_id: "abc".to_string(),
+                path: "src/main.rs".to_string(),
+                position: 4,
+                start_line: None,
+                line: 3,
+                body: "Handle the error".to_string(),
+                suggestion: false,
+            }
+        );
+        // 開始行と終了行が同じなら単一行コメント
+        assert_eq!(
+            comment_payload(&files(), "abc", &comment(Some(3), 3)).unwrap(),
+            payload
+        );
+    }
+
+    #[test]
+    fn test_comment_payload_range() {
+        let payload = comment_payload(&files(), "abc", &comment(Some(1), 3)).unwrap();
+        assert!(matches!(
+            payload,
+            OutboxPayload::Comment {
+                start_line: Some(1),
+                line: 3,
+                ..
+            }
+        ));
+        assert_eq!(payload.target().as_deref(), Some("src/main.rs:1-3"));
+    }
+
+    #[test]
+    fn test_comment_payload_rejects_lines_outside_the_diff() {
+        let error = |comment: LineComment| {
+            comment_payload(&files(), "abc", &comment)
+                .unwrap_err()
+                .to_string()
+        };
+        assert_eq!(
+            error(comment(None, 10)),
+            "Line 10 of src/main.rs is not in the diff"
+        );
+        assert_eq!(
+            error(comment(Some(3), 21)),
+            "Lines 3-21 of src/main.rs are not in a single hunk of the diff"
+        );
+        assert_eq!(
+            error(comment(Some(4), 3)),
+            "The start line 4 is after line 3"
+        );
+
+        let mut other = comment(None, 3);
+        other.path = "src/lib.rs".to_string();
+        assert_eq!(error(other), "src/lib.rs is not changed in this PR");
+
+        let mut empty = comment(None, 3);
+        empty.body = "  \n".to_string();
+        assert_eq!(error(empty), "The comment body is empty");
+    }
+
+    #[test]
+    fn test_location() {
+        assert_eq!(comment(None, 3).location(), "src/main.rs:3");
+        assert_eq!(comment(Some(1), 3).location(), "src/main.rs:1-3");
+    }
+}
@@ -0,0 +1,186 @@
+//! CODEOWNERS の解釈
+//!
+//! GitHub と同じく `.github/CODEOWNERS`・`CODEOWNERS`・`docs/CODEOWNERS` の順に探し、
+//! 最初に見つかったものを使う。パターンは gitignore と同じ書き方で、最後に一致した行が優先される。
+
+/// CODEOWNERS を探すパス（GitHub が探す順）
+pub const CODEOWNERS_PATHS: [&str; 3] = [".github/CODEOWNERS", "CODEOWNERS", "docs/CODEOWNERS"];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Rule {
+    pattern: String,
+    /// `@` を除いたオーナー（ユーザー・`org/team`・メールアドレス）
+    owners: Vec<String>,
+}
+
+/// 解釈した CODEOWNERS
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodeOwners {
+    rules: Vec<Rule>,
+}
+
+impl CodeOwners {
+    pub fn parse(text: &str) -> Self {
+        let rules = text
+            .lines()
+            .filter_map(|line| {
+                let line = line.split_once('#').map_or(line, |(rule, _)| rule).trim();
+                let mut parts = line.split_whitespace();
+                let pattern = parts.next()?.to_string();
+                let owners = parts
+                    .map(|owner| owner.trim_start_matches('@').to_string())
+                    .collect();
+                Some(Rule { pattern, owners })
+            })
+            .collect();
+        Self { rules }
+    }
+
+    /// `path` のオーナー。最後に一致した行のオーナーで、オーナーのない行に一致すれば空
+    pub fn owners_of(&self, path: &str) -> &[String] {
+        self.rules
+            .iter()
+            .rev()
+            .find(|rule| pattern_matches(&rule.pattern, path))
+            .map_or(&[], |rule| rule.owners.as_slice())
+    }
+
+    /// `login` がオーナーのパス（チーム経由は含まない）
+    pub fn owned_by<'a>(&self, login: &str, paths: &[&'a str]) -> Vec<&'a str> {
+        paths
+            .iter()
+            .copied()
+            .filter(|path| {
+                self.owners_of(path)
+                    .iter()
+                    .any(|owner| owner.eq_ignore_ascii_case(login))
+            })
+            .collect()
+    }
+}
+
+/// gitignore 形式のパターンがパスに一致するか。
+/// 先頭か途中に `/` があればリポジトリのルートからの位置で、なければどの階層にも一致する。
+/// ディレクトリに一致したパターンはその下のファイルすべてに一致する
+fn pattern_matches(pattern: &str, path: &str) -> bool {
+    let dir_only = pattern.ends_with('/');
+    let trimmed = pattern.trim_end_matches('/');
+    let anchored = trimmed.contains('/');
+    let pattern = trimmed.trim_start_matches('/');
+    if pattern.is_empty() {
+        return false;
+    }
+    let pattern_parts: Vec<&str> = pattern.split('/').collect();
+    let path_parts: Vec<&str> = path.split('/').collect();
+    // パスの先頭から（アンカーなしならどの階層からでも）パターンを当てはめ、
+    // パターンの残りがなくなった時点でパスが残っていればディレクトリとして一致
+    let starts = if anchored { 0..1 } else { 0..path_parts.len() };
+    starts.into_iter().any(|start| {
+        match_parts(&pattern_parts, &path_parts[start..]).is_some_and(|rest| {
+            // ファイル自体に一致したときは、ディレクトリ限定のパターンなら不一致
+            rest > 0 || !dir_only
+        })
+    })
+}
+
+/// パターンの要素をパスの要素の先頭に当てはめ、一致すれば残ったパス要素の数を返す
+fn match_parts(pattern: &[&str], path: &[&str]) -> Option<usize> {
+    let Some((first, rest)) = pattern.split_first() else {
+        return Some(path.len());
+    };
+    if *first == "**" {
+        // 0 個以上の要素に一致
+        return (0..=path.len()).find_map(|skip| match_parts(rest, &path[skip..]));
+    }
+    let (segment, path_rest) = path.split_first()?;
+    if segment_matches(first, segment) {
+        match_parts(rest, path_rest)
+    } else {
+        None
+    }
+}
+
+/// 1 つのパス要素に対する `*` と `?` のワイルドカード一致
+fn segment_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "\
+# デフォルトのオーナー
+*       @org/core
+*.rs    @alice @Bob # Rust
+/docs/  @carol
+src/ui/**/mod.rs @dave
+build/
+apps/*/config.toml @erin
+";
+
+    #[test]
+    fn test_last_matching_rule_wins() {
+        let owners = CodeOwners::parse(SAMPLE);
+        assert_eq!(owners.owners_of("README.md"), ["org/core"]);
+        assert_eq!(owners.owners_of("src/lib.rs"), ["alice", "Bob"]);
+        assert_eq!(owners.owners_of("docs/guide/intro.md"), ["carol"]);
+        assert_eq!(owners.owners_of("src/ui/popup/mod.rs"), ["dave"]);
+        assert_eq!(owners.owners_of("src/ui/mod.rs"), ["dave"]);
+        assert_eq!(owners.owners_of("apps/web/config.toml"), ["erin"]);
+        assert_eq!(
+            owners.owners_of("apps/web/nested/config.toml"),
+            ["org/core"]
+        );
+        // オーナーのない行はオーナーを外す
+        assert!(owners.owners_of("build/out.txt").is_empty());
+    }
+
+    #[test]
+    fn test_anchoring_and_directories() {
+        // 末尾の `/` だけではアンカーにならない
+        assert!(pattern_matches("docs/", "sub/docs/a.md"));
+        assert!(pattern_matches("docs", "sub/docs/a.md"));
+        assert!(pattern_matches("/docs", "docs/a.md"));
+        assert!(!pattern_matches("/docs", "sub/docs/a.md"));
+        assert!(!pattern_matches("docs/", "docs"));
+        assert!(pattern_matches("**/test_*.py", "a/b/test_x.py"));
+        assert!(pattern_matches("*.m?", "lib/x.md"));
+        assert!(!pattern_matches("*.md", "lib/x.rs"));
+    }
+
+    #[test]
+    fn test_owned_by_ignores_case_and_teams() {
+        let owners = CodeOwners::parse(SAMPLE);
+        let paths = ["src/lib.rs", "README.md", "docs/a.md", "src/main.rs"];
+        assert_eq!(
+            owners.owned_by("bob", &paths),
+            vec!["src/lib.rs", "src/main.rs"]
+        );
+        assert!(owners.owned_by("core", &paths).is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+//! AI によるレビューコメントの下書き
+//!
+//! 選択中の行（または範囲）とファイルの patch を `ai.reviewer` のエージェントに 1 回渡し、
+//! コメントの本文だけを受け取る。下書きはコメントの入力欄に入るので、送信前に編集できる
+
+use anyhow::Result;
+
+use crate::ai::oneshot::{self, OneshotAgent};
+use crate::config::Config;
+
+/// プロンプトに含める patch の最大文字数
+const MAX_PATCH_CHARS: usize = 40_000;
+
+/// 下書きを頼むコメントの位置
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommentTarget {
+    pub pr_title: Option<String>,
+    pub filename: String,
+    /// 範囲の開始行（new file の行番号。1 行なら None）
+    pub start_line: Option<u32>,
+    /// コメントする行（範囲なら最終行）
+    pub line: u32,
+    /// 選択した行（diff の `+` / ` ` の接頭辞付き）
+    pub selected: String,
+    pub patch: String,
+}
+
+impl CommentTarget {
+    /// `src/a.rs:10` / `src/a.rs:10-12` の形式の位置
+    pub fn location(&self) -> String {
+        match self.start_line {
+            Some(start) => format!("{}:{}-{}", self.filename, start, self.line),
+            None => format!("{}:{}", self.filename, self.line),
+        }
+    }
+}
+
+/// 下書きを頼むエージェント
+pub struct CommentDrafter {
+    agent: OneshotAgent,
+}
+
+impl CommentDrafter {
+    pub fn from_config(config: &Config, repo: &str) -> Result<Self> {
+        Ok(Self {
+            agent: OneshotAgent::from_config(config, repo, "comment drafts")?,
+        })
+    }
+
+    /// ステータス表示用の名前
+    pub fn name(&self) -> &'static str {
+        self.agent.name()
+    }
+
+    pub async fn draft(&self, target: &CommentTarget) -> Result<String> {
+        self.agent.run(&build_prompt(target)).await
+    }
+}
+
+pub fn build_prompt(target: &CommentTarget) -> String {
+    let patch = oneshot::truncate_patch(&target.patch, MAX_PATCH_CHARS);
+    let pr = target
+        .pr_title
+        .as_ref()
+        .map(|title| format!(" of the pull request \"{}\"", title))
+        .unwrap_or_default();
+    format!(
+        "Draft a code review comment on `{location}`{pr}. \
+Point out the most important problem with the selected lines, or ask a short question if \
+their intent is unclear. Reply with the comment body only, in GitHub Markdown, in at most a \
+few sentences; use a ```suggestion block only for a concrete replacement of the selected \
+lines. Do not use any tools.\n\n\
+Selected lines:\n```diff\n{selected}\n```\n\n\
+Change to the whole file for context:\n```diff\n{patch}\n```",
+        location = target.location(),
+        selected = target.selected,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn target(patch: &str) -> CommentTarget {
+        CommentTarget {
+            pr_title: Some("Add retry".to_string()),
+            filename: "src/net.rs".to_string(),
+            start_line: Some(3),
+            line: 4,
+            selected: "+let a = 1;\n+let b = a.unwrap();".to_string(),
+            patch: patch.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_prompt_contains_selection_and_patch() {
+        let prompt = build_prompt(&target("@@ -1 +1,4 @@\n+x"));
+        assert!(prompt.starts_with(
+            "Draft a code review comment on `src/net.rs:3-4` of the pull request \"Add retry\"."
+        ));
+        assert!(prompt.contains("```diff\n+let a = 1;\n+let b = a.unwrap();\n```"));
+        assert!(prompt.ends_with("```diff\n@@ -1 +1,4 @@\n+x\n```"));
+
+        let long = "+x\n".repeat(MAX_PATCH_CHARS);
+        let prompt = build_prompt(&target(&long));
+        assert!(prompt.contains("(patch truncated)"));
+        assert!(prompt.len() < MAX_PATCH_CHARS + 2_000);
+    }
+
+    #[test]
+    fn test_location_of_single_line() {
+        let mut target = target("");
+        target.start_line = None;
+        assert_eq!(target.location(), "src/net.rs:4");
+    }
+
+    #[test]
+    fn test_drafter_from_config() {
+        let mut config: Config = toml::from_str("").unwrap();
+        assert_eq!(
+            CommentDrafter::from_config(&config, "owner/repo")
+                .unwrap()
+                .name(),
+            "claude"
+        );
+        config.ai.reviewer = "unknown".to_string();
+        assert!(CommentDrafter::from_config(&config, "owner/repo").is_err());
+    }
+}
@@ -0,0 +1,305 @@
+//! PR の head 版と手元の作業ツリー版の比較
+//!
+//! レビューコメントへの修正を手元で進めているときに、PR に push 済みの内容と
+//! 作業ツリーの内容がどこで食い違っているかを左右に並べて表示するためのデータを作る。
+//! 2 つのテキストの差分は `git diff --no-index` で取り、全行をコンテキストに含めた
+//! patch から左右の行を対応付ける。
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+use tokio::process::Command;
+
+use crate::file_content;
+
+/// 比較表示の 1 行の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowKind {
+    /// 両方で同じ行
+    Same,
+    /// 両方にあるが内容が異なる行
+    Changed,
+    /// PR にだけある行
+    PrOnly,
+    /// 手元にだけある行
+    LocalOnly,
+}
+
+/// 比較表示の 1 行（行番号は 1 始まり）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompareRow {
+    pub kind: RowKind,
+    pub pr: Option<(usize, String)>,
+    pub local: Option<(usize, String)>,
+}
+
+/// 1 ファイルの比較結果
+#[derive(Debug, Clone)]
+pub struct Comparison {
+    pub path: String,
+    /// PR の head コミット
+    pub head_sha: String,
+    pub rows: Vec<CompareRow>,
+    /// PR の head にファイルがない（PR で削除済み）
+    pub pr_missing: bool,
+    /// 作業ツリーにファイルがない
+    pub local_missing: bool,
+}
+
+impl Comparison {
+    /// 食い違っている箇所（連続する Same 以外の行）の先頭行インデックス
+    pub fn divergent_blocks(&self) -> Vec<usize> {
+        let mut starts = Vec::new();
+        let mut in_block = false;
+        for (i, row) in self.rows.iter().enumerate() {
+            let divergent = row.kind != RowKind::Same;
+            if divergent && !in_block {
+                starts.push(i);
+            }
+            in_block = divergent;
+        }
+        starts
+    }
+}
+
+/// 全行をコンテキストに含めた unified diff（`@@` 以降）から左右の行を対応付ける。
+/// 削除行と追加行が連続する箇所は順に組にして Changed とし、余りを片側だけの行にする。
+/// `patch` が空（差分なし）なら `pr_text` の全行を Same とする
+pub fn align(pr_text: &str, patch: &str) -> Vec<CompareRow> {
+    if patch.trim().is_empty() {
+        return pr_text
+            .lines()
+            .enumerate()
+            .map(|(i, line)| CompareRow {
+                kind: RowKind::Same,
+                pr: Some((i + 1, line.to_string())),
+                local: Some((i + 1, line.to_string())),
+            })
+            .collect();
+    }
+
+    let mut rows = Vec::new();
+    let mut removed: Vec<String> = Vec::new();
+    let mut added: Vec<String> = Vec::new();
+    let (mut pr_no, mut local_no) = (0usize, 0usize);
+
+    for line in patch.lines() {
+        if line.starts_with("@@") || line.starts_with('\\') {
+            continue;
+        }
+        if let Some(text) = line.strip_prefix('-') {
+            removed.push(text.to_string());
+        } else if let Some(text) = line.strip_prefix('+') {
+            added.push(text.to_string());
+        } else {
+            flush_pending(
+                &mut rows,
+                &mut removed,
+                &mut added,
+                &mut pr_no,
+                &mut local_no,
+            );
+            let text = line.strip_prefix(' ').unwrap_or(line).to_string();
+            pr_no += 1;
+            local_no += 1;
+            rows.push(CompareRow {
+                kind: RowKind::Same,
+                pr: Some((pr_no, text.clone())),
+                local: Some((local_no, text)),
+            });
+        }
+    }
+    flush_pending(
+        &mut rows,
+        &mut removed,
+        &mut added,
+        &mut pr_no,
+        &mut local_no,
+    );
+    rows
+}
+
+/// 溜まっている削除行・追加行を左右の行として出力する
+fn flush_pending(
+    rows: &mut Vec<CompareRow>,
+    removed: &mut Vec<String>,
+    added: &mut Vec<String>,
+    pr_no: &mut usize,
+    local_no: &mut usize,
+) {
+    let pairs = removed.len().max(added.len());
+    let mut removed = removed.drain(..);
+    let mut added = added.drain(..);
+    for _ in 0..pairs {
+        let pr = removed.next().map(|line| {
+            *pr_no += 1;
+            (*pr_no, line)
+        });
+        let local = added.next().map(|line| {
+            *local_no += 1;
+            (*local_no, line)
+        });
+        let kind = match (&pr, &local) {
+            (Some(_), Some(_)) => RowKind::Changed,
+            (Some(_), None) => RowKind::PrOnly,
+            _ => RowKind::LocalOnly,
+        };
+        rows.push(CompareRow { kind, pr, local });
+    }
+}
+
+/// PR の head 版と作業ツリー版を読み込んで比較する
+pub async fn load(
+    repo: &str,
+    head_sha: &str,
+    working_dir: Option<&str>,
+    path: &str,
+) -> Result<Comparison> {
+    let local_path = repo_root(working_dir).await?.join(path);
+    let local = match std::fs::read(&local_path) {
+        Ok(bytes) => Some(as_text(bytes)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+        Err(e) => {
+            return Err(e).with_context(|| format!("Failed to read {}", local_path.display()))
+        }
+    };
+    // PR で削除されたファイルは head に存在しない
+    let pr = file_content::fetch_from_github(repo, head_sha, path)
+        .await
+        .ok();
+    if pr.is_none() && local.is_none() {
+        bail!("{} exists neither at the PR head nor locally", path);
+    }
+
+    let pr_text = pr.as_deref().unwrap_or("");
+    let local_text = local.as_deref().unwrap_or("");
+    let patch = full_context_diff(pr_text, local_text).await?;
+    Ok(Comparison {
+        path: path.to_string(),
+        head_sha: head_sha.to_string(),
+        rows: align(pr_text, &patch),
+        pr_missing: pr.is_none(),
+        local_missing: local.is_none(),
+    })
+}
+
+pub(crate) async fn repo_root(working_dir: Option<&str>) -> Result<PathBuf> {
+    let mut cmd = Command::new("git");
+    cmd.args(["rev-parse", "--show-toplevel"]);
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
+    let output = cmd.output().await.context("Failed to run git")?;
+    if !output.status.success() {
+        bail!("Not inside a local checkout of the repository");
+    }
+    Ok(Path::new(String::from_utf8_lossy(&output.stdout).trim()).to_path_buf())
+}
+
+fn as_text(bytes: Vec<u8>) -> Result<String> {
+    if bytes.len() > file_content::MAX_FILE_BYTES {
+        bail!("File is too large to compare");
+    }
+    if bytes.contains(&0) {
+        bail!("File is binary");
+    }
+    String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("File is not UTF-8 text"))
+}
+
+/// 2 つのテキストの全行コンテキスト付き unified diff（`@@` 以降、差分がなければ空）
+async fn full_context_diff(old: &str, new: &str) -> Result<String> {
+    let dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let old_path = dir.path().join("pr");
+    let new_path = dir.path().join("local");
+    std::fs::write(&old_path, old).context("Failed to write temporary file")?;
+    std::fs::write(&new_path, new).context("Failed to write temporary file")?;
+
+    let context = format!("--unified={}", old.lines().count().max(new.lines().count()));
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--no-ext-diff",
+            "--no-color",
+            "--no-index",
+            &context,
+            "--",
+        ])
+        .arg(&old_path)
+        .arg(&new_path)
+        .output()
+        .await
+        .context("Failed to run git diff")?;
+    // --no-index は差分があると終了コード 1 を返す
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git diff failed: {}", stderr.trim());
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("@@"))
+        .collect::<Vec<_>>()
+        .join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn kinds(rows: &[CompareRow]) -> Vec<RowKind> {
+        rows.iter().map(|r| r.kind).collect()
+    }
+
+    #[test]
+    fn test_align_identical() {
+        let rows = align("a\nb\n", "");
+        assert_eq!(kinds(&rows), vec![RowKind::Same, RowKind::Same]);
+        assert_eq!(rows[1].local, Some((2, "b".to_string())));
+    }
+
+    #[test]
+    fn test_align_pairs_changes_and_keeps_line_numbers() {
+        let patch = "@@ -1,4 +1,4 @@\n a\n-b\n-c\n+B\n d\n+e";
+        let rows = align("", patch);
+        assert_eq!(
+            kinds(&rows),
+            vec![
+                RowKind::Same,
+                RowKind::Changed,
+                RowKind::PrOnly,
+                RowKind::Same,
+                RowKind::LocalOnly
+            ]
+        );
+        assert_eq!(rows[1].pr, Some((2, "b".to_string())));
+        assert_eq!(rows[1].local, Some((2, "B".to_string())));
+        assert_eq!(rows[2].local, None);
+        assert_eq!(rows[3].pr, Some((4, "d".to_string())));
+        assert_eq!(rows[3].local, Some((3, "d".to_string())));
+        assert_eq!(rows[4].local, Some((4, "e".to_string())));
+    }
+
+    #[test]
+    fn test_divergent_blocks() {
+        let patch = "@@ -1,4 +1,4 @@\n a\n-b\n-c\n+B\n d\n+e";
+        let comparison = Comparison {
+            path: "f".to_string(),
+            head_sha: "abc".to_string(),
+            rows: align("", patch),
+            pr_missing: false,
+            local_missing: false,
+        };
+        assert_eq!(comparison.divergent_blocks(), vec![1, 4]);
+    }
+
+    #[tokio::test]
+    async fn test_full_context_diff_includes_every_line() {
+        let pr = "1\n2\n3\n4\n5\n6\n7\n8\n9\n10\n";
+        let local = "1\n2\n3\n4\n5\n6\n7\n8\n9\nten\n";
+        let patch = full_context_diff(pr, local).await.unwrap();
+        let rows = align(pr, &patch);
+        assert_eq!(rows.len(), 10);
+        assert_eq!(rows[9].kind, RowKind::Changed);
+        assert!(full_context_diff(pr, pr).await.unwrap().is_empty());
+    }
+}
@@ -1,11 +1,13 @@
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use xdg::BaseDirectories;
 
+use crate::config_check::{self, ConfigIssue};
 use crate::keybinding::{KeyBinding, KeySequence, NamedKey};
+use crate::term_background::Background;
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 #[serde(default)]
@@ -14,6 +16,24 @@ pub struct Config {
     pub diff: DiffConfig,
     pub keybindings: KeybindingsConfig,
     pub ai: AiConfig,
+    pub follow: FollowConfig,
+    pub translate: TranslateConfig,
+    pub time: TimeConfig,
+    pub scope: ScopeConfig,
+    pub workspace: WorkspaceConfig,
+    pub watch: WatchConfig,
+    pub polling: PollingConfig,
+    pub cache: CacheConfig,
+    pub sync: SyncConfig,
+    pub learning: LearningConfig,
+    pub nudge: NudgeConfig,
+    pub comments: CommentsConfig,
+    pub github: GithubConfig,
+    pub ui: UiConfig,
+    pub dependencies: DependenciesConfig,
+    pub env: EnvConfig,
+    /// レビュー本文のテンプレート（`[[review_templates]]`）
+    pub review_templates: Vec<ReviewTemplate>,
     #[serde(skip)]
     pub project_root: PathBuf,
     /// Path of the global config file if it was loaded successfully.
@@ -33,10 +53,15 @@ pub struct Config {
 pub struct AiConfig {
     pub reviewer: String,
     pub reviewee: String,
+    /// Second reviewer agent run in parallel with `reviewer`; their findings are merged
+    pub co_reviewer: Option<String>,
     pub max_iterations: u32,
     pub timeout_secs: u64,
     /// Custom prompt directory (default: ~/.config/octorus/prompts/)
     pub prompt_dir: Option<String>,
+    /// Coding guidelines passed to the agents as `{{guidelines}}`, relative to the
+    /// repository root (default: .octorus/guidelines.md when it exists)
+    pub guidelines_file: Option<String>,
     /// Additional tools for reviewer (Claude adapter only).
     /// Use Claude Code's --allowedTools format (e.g., "Skill", "Bash(git push:*)").
     #[serde(default)]
@@ -49,23 +74,469 @@ pub struct AiConfig {
     /// Default is false (confirmation prompt before posting).
     #[serde(default)]
     pub auto_post: bool,
+    /// If true, AI Rally runs read-only: nothing is posted to the PR and the reviewee's
+    /// file-writing tools are disabled, so fixes are only described (also `--dry-run`).
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Approximate token budget for the diff sent to the reviewer in one invocation.
+    /// Larger diffs are prioritized and split across several reviewer runs (0 disables).
+    #[serde(default = "default_context_budget_tokens")]
+    pub context_budget_tokens: usize,
+    /// Abort the rally once the agents have used this many tokens in total (0 disables).
+    #[serde(default)]
+    pub budget_tokens: u64,
+    /// Abort the rally once its cost reaches this many USD (0 disables).
+    /// Uses the cost reported by the agent, or the estimate from the prices below.
+    #[serde(default)]
+    pub budget_usd: f64,
+    /// USD per million input tokens, used to estimate cost for agents that do not report it.
+    #[serde(default)]
+    pub input_cost_per_mtok: f64,
+    /// USD per million output tokens, used to estimate cost for agents that do not report it.
+    #[serde(default)]
+    pub output_cost_per_mtok: f64,
+    /// Gemini CLI settings, used when `reviewer` or `reviewee` is "gemini".
+    #[serde(default)]
+    pub gemini: GeminiConfig,
+    /// OpenAI-compatible endpoint settings, used when `reviewer` or `reviewee` is "openai".
+    #[serde(default)]
+    pub openai: OpenAiConfig,
+}
+
+fn default_context_budget_tokens() -> usize {
+    100_000
+}
+
+/// Settings for the Gemini CLI adapter.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GeminiConfig {
+    /// Model passed to `gemini --model` (default: the CLI's default model)
+    pub model: Option<String>,
+    /// API key passed to the CLI as `GEMINI_API_KEY`.
+    /// When unset, the CLI uses the environment or its own login.
+    pub api_key: Option<String>,
+}
+
+/// Settings for the OpenAI-compatible chat adapter (ollama, vLLM, LM Studio, ...).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OpenAiConfig {
+    /// Base URL of the API, including the version path (e.g. `http://localhost:11434/v1`)
+    pub base_url: String,
+    /// Model name sent with each request (required)
+    pub model: String,
+    /// Sent as a Bearer token when set. Local servers usually need none.
+    pub api_key: Option<String>,
+}
+
+impl Default for OpenAiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: "http://localhost:11434/v1".to_owned(),
+            model: String::new(),
+            api_key: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(default)]
 pub struct DiffConfig {
     pub theme: String,
+    /// 端末の背景が明るいときに `theme` の代わりに使うテーマ
+    #[serde(default = "default_light_theme")]
+    pub light_theme: String,
     #[serde(deserialize_with = "deserialize_tab_width")]
     pub tab_width: u8,
     /// 追加/削除行に背景色を表示するかどうか
     #[serde(default = "default_true")]
     pub bg_color: bool,
+    /// ファイルを切り替えて戻ったときにカーソル位置・スクロールを復元するかどうか
+    #[serde(default = "default_true")]
+    pub remember_position: bool,
+    /// 解決済みスレッドのコメントをガターに表示しない
+    #[serde(default)]
+    pub hide_resolved_comments: bool,
+    /// 後続のコミットで outdated になったスレッドのコメントをガターに表示しない
+    #[serde(default)]
+    pub hide_outdated_comments: bool,
+    /// ガターに表示しないコメントの作成者（`*` は任意の文字列に一致。例: `"*[bot]"`）
+    #[serde(default)]
+    pub hide_comment_authors: Vec<String>,
+}
+
+/// エディタ追従用ジャンプイベントの出力設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct FollowConfig {
+    /// 書き込み先の FIFO / UNIX ソケット（未設定なら無効）
+    pub path: Option<String>,
+    /// イベント送信の最小間隔（ミリ秒）
+    pub throttle_ms: u64,
+}
+
+impl Default for FollowConfig {
+    fn default() -> Self {
+        Self {
+            path: None,
+            throttle_ms: 100,
+        }
+    }
+}
+
+/// コメント本文の翻訳設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TranslateConfig {
+    /// 翻訳先の言語（例: "ja", "English"）。未設定なら翻訳は無効
+    pub language: Option<String>,
+    /// 翻訳コマンドのテンプレート。`{lang}` は言語に置換され、本文は stdin で渡す。
+    /// 未設定なら `ai.reviewer` のエージェントで翻訳する
+    pub command: Option<String>,
+}
+
+/// 日時の表示方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TimeStyle {
+    /// "3h ago" のような相対表示
+    #[default]
+    Relative,
+    /// `format` による絶対表示
+    Absolute,
+}
+
+/// 日時表示の設定（PR 一覧・コメント・AI Rally ログ）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimeConfig {
+    pub style: TimeStyle,
+    /// 絶対表示の strftime 形式
+    pub format: String,
+    /// "local"、"utc"、または "+09:00" のような UTC オフセット
+    pub timezone: String,
+}
+
+impl Default for TimeConfig {
+    fn default() -> Self {
+        Self {
+            style: TimeStyle::Relative,
+            format: "%Y-%m-%d %H:%M".to_string(),
+            timezone: "local".to_string(),
+        }
+    }
+}
+
+/// モノレポ向けのパススコープ（`--paths` で上書き）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ScopeConfig {
+    /// セッションで扱うディレクトリ。空なら全ファイルが対象
+    pub paths: Vec<String>,
+}
+
+/// 1 セッションで切り替えて見るリポジトリ（`--repo` を複数渡すと上書き）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WorkspaceConfig {
+    /// `owner/repo` 形式のリポジトリ
+    pub repos: Vec<String>,
+    /// ローカルモードで切り替えるリポジトリのディレクトリ（`~/` はホームに展開）
+    pub dirs: Vec<String>,
+}
+
+impl WorkspaceConfig {
+    /// 起動時のリポジトリを先頭に、設定のリポジトリを重複なく並べる
+    pub fn repos_with(&self, primary: &str) -> Vec<String> {
+        let mut repos = vec![primary.to_string()];
+        for repo in &self.repos {
+            if !repos.contains(repo) {
+                repos.push(repo.clone());
+            }
+        }
+        repos
+    }
+}
+
+/// 開いている PR の更新監視（push / 新着コメントの自動反映）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WatchConfig {
+    pub enabled: bool,
+    /// ポーリング間隔（秒、5 秒未満は 5 秒として扱う）
+    pub interval_secs: u64,
+}
+
+impl Default for WatchConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            interval_secs: 20,
+        }
+    }
+}
+
+/// 活動状況に応じたポーリング間隔（読み込み中は速く、放置中は遅く）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PollingConfig {
+    /// false ならイベント待ちは常に 100ms、PR 監視は `[watch] interval_secs` ごと
+    pub adaptive: bool,
+    /// 何も実行しておらず、この秒数入力がなければアイドルとみなす
+    pub idle_after_secs: u64,
+}
+
+impl Default for PollingConfig {
+    fn default() -> Self {
+        Self {
+            adaptive: true,
+            idle_after_secs: 60,
+        }
+    }
+}
+
+/// PR データのディスクキャッシュ（再起動後やオフラインでも前回のデータをすぐに表示）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    pub enabled: bool,
+    /// 最後に保存してからこの時間を過ぎたエントリは使わずに消す
+    pub ttl_hours: u64,
+    /// キャッシュ全体の上限（MB）。超えたら保存の古い PR から消す
+    pub max_size_mb: u64,
+}
+
+impl Default for CacheConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_hours: 24 * 7,
+            max_size_mb: 200,
+        }
+    }
+}
+
+/// レビュー進捗の同期先
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SyncBackendKind {
+    /// 同期しない
+    #[default]
+    Off,
+    /// private gist（`gist_id`）のファイル
+    Gist,
+    /// リポジトリの `refs/octorus/progress/*`（`remote` に push する）
+    Git,
+}
+
+/// レビュー進捗（見終えたファイルとメモ）のマシン間同期
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SyncConfig {
+    pub backend: SyncBackendKind,
+    pub gist_id: String,
+    pub remote: String,
+}
+
+impl Default for SyncConfig {
+    fn default() -> Self {
+        Self {
+            backend: SyncBackendKind::Off,
+            gist_id: String::new(),
+            remote: "origin".to_string(),
+        }
+    }
+}
+
+/// 学習モード（矢印キーなどで操作したときに対応するキーバインドを表示）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LearningConfig {
+    pub enabled: bool,
+}
+
+/// 放置されている PR へのリマインドコメント
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NudgeConfig {
+    /// 最終更新からこの日数以上経った PR をリマインドの対象にする
+    pub stale_days: u32,
+    /// コメントのテンプレート（`{{days}}`・`{{reviewers}}`・`{{author}}`・`{{number}}`・`{{title}}` を置換）
+    pub template: String,
+}
+
+impl Default for NudgeConfig {
+    fn default() -> Self {
+        Self {
+            stale_days: 3,
+            template: crate::nudge::DEFAULT_TEMPLATE.to_string(),
+        }
+    }
+}
+
+/// レビューのエディタを開いたときに本文へ入れるテンプレート。
+/// `labels` と `paths` のどちらかに一致した PR で使う（両方空なら常に使う）
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReviewTemplate {
+    /// エディタのヘッダーに表示する名前
+    pub name: String,
+    /// PR のラベル（大文字小文字は区別しない、`*` は任意の文字列に一致）
+    pub labels: Vec<String>,
+    /// 変更されたファイルのパス。`*` を含まなければそのファイルかディレクトリ以下に一致
+    pub paths: Vec<String>,
+    /// 本文（Markdown のチェックリストなど）
+    pub body: String,
+}
+
+/// コメント一覧の設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CommentsConfig {
+    /// bot として折りたたむ作成者（`*` は任意の文字列に一致）。
+    /// `*[bot]` や dependabot などの既定のパターンに追加される
+    pub bot_authors: Vec<String>,
+}
+
+/// GitHub API トークンの取得元
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CredentialSource {
+    /// 環境変数 `GH_TOKEN` / `GITHUB_TOKEN`
+    #[default]
+    Env,
+    /// OS のキーチェーン（macOS Keychain / libsecret / Windows 資格情報マネージャー）
+    Keychain,
+}
+
+/// GitHub API の呼び出し方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GithubBackendKind {
+    /// gh CLI があれば gh、なければトークンで API を直接呼ぶ
+    #[default]
+    Auto,
+    /// 常に gh CLI を使う
+    Gh,
+    /// 常にトークンで REST / GraphQL API を直接呼ぶ
+    Api,
+}
+
+/// GitHub への接続設定
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GithubConfig {
+    pub backend: GithubBackendKind,
+    /// API を直接呼ぶときの REST API のベース URL（GitHub Enterprise Server では `https://HOST/api/v3`）
+    pub api_url: String,
+    pub credential: CredentialSource,
+    /// キーチェーン項目のサービス名
+    pub keychain_service: String,
+    /// キーチェーン項目のアカウント名
+    pub keychain_account: String,
+}
+
+impl Default for GithubConfig {
+    fn default() -> Self {
+        Self {
+            backend: GithubBackendKind::Auto,
+            api_url: "https://api.github.com".to_string(),
+            credential: CredentialSource::Env,
+            keychain_service: "octorus".to_string(),
+            keychain_account: "github.com".to_string(),
+        }
+    }
+}
+
+/// ローディング表示のスピナーの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SpinnerStyle {
+    /// `⠋⠙⠹…`
+    #[default]
+    Braille,
+    /// `⣾⣽⣻…`
+    Dots,
+    /// `|/-\`（Unicode を表示できない端末向け）
+    Line,
+    /// `◜◠◝…`
+    Arc,
+}
+
+impl SpinnerStyle {
+    pub fn frames(self) -> &'static [&'static str] {
+        match self {
+            Self::Braille => &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"],
+            Self::Dots => &["⣾", "⣽", "⣻", "⢿", "⡿", "⣟", "⣯", "⣷"],
+            Self::Line => &["|", "/", "-", "\\"],
+            Self::Arc => &["◜", "◠", "◝", "◞", "◡", "◟"],
+        }
+    }
+}
+
+/// 件数がわかっている処理の進捗表示の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressStyle {
+    /// `████░░░░`
+    #[default]
+    Bar,
+    /// `[###-----]`
+    Ascii,
+    /// バーなし（件数と割合だけ）
+    Percent,
+}
+
+/// 配色を合わせる端末の背景
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackgroundMode {
+    /// 起動時に端末に問い合わせて判定する（分からなければ dark）
+    #[default]
+    Auto,
+    Dark,
+    Light,
+}
+
+/// 表示まわりの設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UiConfig {
+    pub spinner: SpinnerStyle,
+    pub progress: ProgressStyle,
+    pub background: BackgroundMode,
+}
+
+/// 依存マニフェストの差分要約の設定
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct DependenciesConfig {
+    /// OSV 形式の脆弱性データベース（JSON のディレクトリ）。未設定なら照会しない。
+    /// 相対パスはプロジェクトルートから解決する
+    pub advisory_db: Option<String>,
+}
+
+/// AI エージェントや翻訳コマンドなど、起動するサブプロセスに渡す環境変数
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct EnvConfig {
+    /// すべてのリポジトリで渡す環境変数
+    pub vars: BTreeMap<String, String>,
+    /// リポジトリ（`owner/name`）ごとの環境変数。`vars` より優先する
+    pub repos: BTreeMap<String, BTreeMap<String, String>>,
+    /// ログで値を伏せる変数名。名前に TOKEN / SECRET / PASSWORD / KEY などを含む変数は常に伏せる
+    pub secrets: Vec<String>,
 }
 
 fn default_true() -> bool {
     true
 }
 
+fn default_light_theme() -> String {
+    "base16-ocean.light".to_owned()
+}
+
 /// Deserialize tab_width with clamping: values below 1 are clamped to 1.
 fn deserialize_tab_width<'de, D>(deserializer: D) -> Result<u8, D::Error>
 where
@@ -80,7 +551,9 @@ where
 /// Supports three formats in TOML:
 /// - Simple string: `move_down = "j"`
 /// - Object with modifiers: `page_down = { key = "d", ctrl = true }`
-/// - Array for sequences: `jump_to_first = ["g", "g"]`
+/// - Array for sequences of any length: `jump_to_first = ["g", "g"]`
+///
+/// `[keybindings.views.<view>]` tables override actions in a single view.
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
 pub struct KeybindingsConfig {
@@ -96,13 +569,16 @@ pub struct KeybindingsConfig {
     pub jump_back: KeySequence,
     pub next_comment: KeySequence,
     pub prev_comment: KeySequence,
+    pub next_unresolved_thread: KeySequence,
 
     // Actions
     pub approve: KeySequence,
+    pub approve_next: KeySequence,
     pub request_changes: KeySequence,
     pub comment: KeySequence,
     pub suggestion: KeySequence,
     pub reply: KeySequence,
+    pub translate_comment: KeySequence,
     pub refresh: KeySequence,
     pub submit: KeySequence,
 
@@ -110,26 +586,172 @@ pub struct KeybindingsConfig {
     pub quit: KeySequence,
     pub help: KeySequence,
     pub comment_list: KeySequence,
+    pub review_drafts: KeySequence,
     pub ai_rally: KeySequence,
     pub open_panel: KeySequence,
 
     // Diff operations
     pub go_to_definition: KeySequence,
     pub go_to_file: KeySequence,
+    pub lfs_preview: KeySequence,
+    pub view_base_file: KeySequence,
+    pub toggle_blame: KeySequence,
+    pub line_history: KeySequence,
+    pub toggle_vendored: KeySequence,
+    pub toggle_review_draft: KeySequence,
+    pub compare_local: KeySequence,
+    pub commit_list: KeySequence,
+    pub checks: KeySequence,
+    pub pr_overview: KeySequence,
+    pub pr_summary: KeySequence,
+    pub review_agenda: KeySequence,
+    pub request_reviewers: KeySequence,
+    pub edit_labels: KeySequence,
+    pub toggle_pr_draft: KeySequence,
+    pub explain_file: KeySequence,
+    pub draft_comment: KeySequence,
+    pub mark_reviewed: KeySequence,
+    pub file_note: KeySequence,
     pub open_in_browser: KeySequence,
 
     // Local mode
     pub toggle_local_mode: KeySequence,
+    pub checkout_pr: KeySequence,
+    pub create_pr: KeySequence,
     pub toggle_auto_focus: KeySequence,
+    pub fixup: KeySequence,
+    pub resolve_conflicts: KeySequence,
+
+    // Session activity (audit log)
+    pub session_activity: KeySequence,
+    pub key_stats: KeySequence,
+    pub local_data: KeySequence,
+    pub outbox: KeySequence,
+    pub nudge: KeySequence,
+    pub switch_repo: KeySequence,
+    pub inbox: KeySequence,
+
+    // PR tabs
+    pub open_pr_tab: KeySequence,
+    pub next_pr_tab: KeySequence,
+    pub prev_pr_tab: KeySequence,
+    pub close_pr_tab: KeySequence,
+
+    // PR list state filter
+    pub pr_state_open: KeySequence,
+    pub pr_state_closed: KeySequence,
+    pub pr_state_all: KeySequence,
 
     // Markdown rich display
     pub toggle_markdown_rich: KeySequence,
+    pub toggle_time_format: KeySequence,
 
     // List filter
     pub filter: KeySequence,
+    pub file_finder: KeySequence,
+    pub search: KeySequence,
+    pub grep: KeySequence,
+    pub sort_files: KeySequence,
 
     // Multiline selection (fallback for Shift+Enter)
     pub multiline_select: KeySequence,
+
+    // Viewed marks (file list)
+    pub mark_viewed: KeySequence,
+    pub mark_viewed_directory: KeySequence,
+
+    // AI Rally
+    pub ai_rally_files: KeySequence,
+    pub resume_ai_rally: KeySequence,
+    pub mark_rally_file: KeySequence,
+    pub rally_background: KeySequence,
+    pub rally_approve: KeySequence,
+    pub rally_deny: KeySequence,
+    pub rally_retry: KeySequence,
+    pub rally_export: KeySequence,
+    pub rally_export_markdown: KeySequence,
+    pub rally_replay_pause: KeySequence,
+    pub rally_log_page_down: KeySequence,
+    pub rally_log_page_up: KeySequence,
+    pub rally_log_top: KeySequence,
+    pub rally_log_bottom: KeySequence,
+
+    // Comment list
+    pub comments_prev_tab: KeySequence,
+    pub comments_next_tab: KeySequence,
+    pub comments_page_down: KeySequence,
+    pub comments_page_up: KeySequence,
+    pub comments_toggle_bots: KeySequence,
+    pub comments_user_info: KeySequence,
+    pub comments_mark: KeySequence,
+    pub comments_batch_reply: KeySequence,
+    pub comments_unresolved_only: KeySequence,
+    pub comments_apply_suggestion: KeySequence,
+    pub comments_edit: KeySequence,
+    pub comments_delete: KeySequence,
+    pub comments_toggle_task: KeySequence,
+
+    // Per-view overrides
+    pub views: KeybindingViews,
+}
+
+/// Views whose keybindings can be overridden with `[keybindings.views.<view>]`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeybindingView {
+    PrList,
+    FileList,
+    DiffView,
+    CommentList,
+    AiRally,
+}
+
+impl KeybindingView {
+    pub const ALL: [KeybindingView; 5] = [
+        KeybindingView::PrList,
+        KeybindingView::FileList,
+        KeybindingView::DiffView,
+        KeybindingView::CommentList,
+        KeybindingView::AiRally,
+    ];
+
+    /// Table name under `[keybindings.views]`
+    pub fn name(self) -> &'static str {
+        match self {
+            KeybindingView::PrList => "pr_list",
+            KeybindingView::FileList => "file_list",
+            KeybindingView::DiffView => "diff_view",
+            KeybindingView::CommentList => "comment_list",
+            KeybindingView::AiRally => "ai_rally",
+        }
+    }
+}
+
+/// Per-view overrides (action name → key sequence)
+///
+/// ```toml
+/// [keybindings.views.diff_view]
+/// quit = ["Space", "q"]
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct KeybindingViews {
+    pub pr_list: BTreeMap<String, KeySequence>,
+    pub file_list: BTreeMap<String, KeySequence>,
+    pub diff_view: BTreeMap<String, KeySequence>,
+    pub comment_list: BTreeMap<String, KeySequence>,
+    pub ai_rally: BTreeMap<String, KeySequence>,
+}
+
+impl KeybindingViews {
+    pub fn overrides(&self, view: KeybindingView) -> &BTreeMap<String, KeySequence> {
+        match view {
+            KeybindingView::PrList => &self.pr_list,
+            KeybindingView::FileList => &self.file_list,
+            KeybindingView::DiffView => &self.diff_view,
+            KeybindingView::CommentList => &self.comment_list,
+            KeybindingView::AiRally => &self.ai_rally,
+        }
+    }
 }
 
 impl Default for AiConfig {
@@ -137,12 +759,22 @@ impl Default for AiConfig {
         Self {
             reviewer: "claude".to_owned(),
             reviewee: "claude".to_owned(),
+            co_reviewer: None,
             max_iterations: 10,
             timeout_secs: 600,
             prompt_dir: None,
+            guidelines_file: None,
             reviewer_additional_tools: Vec::new(),
             reviewee_additional_tools: Vec::new(),
             auto_post: false,
+            dry_run: false,
+            context_budget_tokens: default_context_budget_tokens(),
+            budget_tokens: 0,
+            budget_usd: 0.0,
+            input_cost_per_mtok: 0.0,
+            output_cost_per_mtok: 0.0,
+            gemini: GeminiConfig::default(),
+            openai: OpenAiConfig::default(),
         }
     }
 }
@@ -151,12 +783,35 @@ impl Default for DiffConfig {
     fn default() -> Self {
         Self {
             theme: "base16-ocean.dark".to_owned(),
+            light_theme: default_light_theme(),
             tab_width: 4,
             bg_color: true,
+            remember_position: true,
+            hide_resolved_comments: false,
+            hide_outdated_comments: false,
+            hide_comment_authors: Vec::new(),
         }
     }
 }
 
+/// テーマの指定がファイルのパス（`.tmTheme` か Helix の `theme.toml`）なら、そのパス
+///
+/// `~/` はホームディレクトリに展開し、相対パスは設定ディレクトリ（`~/.config/octorus/`）から解決する
+pub fn theme_file_path(theme: &str) -> Option<PathBuf> {
+    let is_file = Path::new(theme)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("tmTheme") || ext.eq_ignore_ascii_case("toml"));
+    if !is_file {
+        return None;
+    }
+    let path = PathBuf::from(crate::local_repos::expand_home(theme));
+    if path.is_absolute() {
+        return Some(path);
+    }
+    let config_path = Config::config_path();
+    Some(config_path.parent().unwrap_or(Path::new("")).join(path))
+}
+
 impl Default for KeybindingsConfig {
     fn default() -> Self {
         Self {
@@ -172,13 +827,19 @@ impl Default for KeybindingsConfig {
             jump_back: KeySequence::single(KeyBinding::ctrl('o')),
             next_comment: KeySequence::single(KeyBinding::char('n')),
             prev_comment: KeySequence::single(KeyBinding::char('N')),
+            next_unresolved_thread: KeySequence::double(
+                KeyBinding::char('g'),
+                KeyBinding::char('U'),
+            ),
 
             // Actions
             approve: KeySequence::single(KeyBinding::char('a')),
+            approve_next: KeySequence::single(KeyBinding::ctrl('n')),
             request_changes: KeySequence::single(KeyBinding::char('r')),
             comment: KeySequence::single(KeyBinding::char('c')),
             suggestion: KeySequence::single(KeyBinding::char('s')),
             reply: KeySequence::single(KeyBinding::char('r')),
+            translate_comment: KeySequence::single(KeyBinding::char('T')),
             refresh: KeySequence::single(KeyBinding::char('R')),
             submit: KeySequence::single(KeyBinding::ctrl('s')),
 
@@ -186,43 +847,121 @@ impl Default for KeybindingsConfig {
             quit: KeySequence::single(KeyBinding::char('q')),
             help: KeySequence::single(KeyBinding::char('?')),
             comment_list: KeySequence::single(KeyBinding::char('C')),
+            review_drafts: KeySequence::single(KeyBinding::char('P')),
             ai_rally: KeySequence::single(KeyBinding::char('A')),
             open_panel: KeySequence::single(KeyBinding::named(NamedKey::Enter)),
 
             // Diff operations
             go_to_definition: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('d')),
             go_to_file: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('f')),
+            lfs_preview: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('l')),
+            view_base_file: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('b')),
+            toggle_blame: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('B')),
+            line_history: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('L')),
+            toggle_vendored: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('v')),
+            toggle_review_draft: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('p')),
+            compare_local: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('c')),
+            commit_list: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('h')),
+            checks: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('s')),
+            pr_overview: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('i')),
+            pr_summary: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('S')),
+            review_agenda: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('a')),
+            request_reviewers: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('r')),
+            edit_labels: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('t')),
+            toggle_pr_draft: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('w')),
+            explain_file: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('e')),
+            draft_comment: KeySequence::single(KeyBinding::char('E')),
+            mark_reviewed: KeySequence::single(KeyBinding::char('m')),
+            file_note: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('n')),
             open_in_browser: KeySequence::single(KeyBinding::char('O')),
 
             // Local mode
             toggle_local_mode: KeySequence::single(KeyBinding::char('L')),
+            checkout_pr: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('o')),
+            create_pr: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('u')),
             toggle_auto_focus: KeySequence::single(KeyBinding::char('F')),
+            fixup: KeySequence::single(KeyBinding::char('X')),
+            resolve_conflicts: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('m')),
+
+            // Session activity (audit log)
+            session_activity: KeySequence::single(KeyBinding::char('H')),
+            key_stats: KeySequence::single(KeyBinding::char('S')),
+            local_data: KeySequence::single(KeyBinding::char('D')),
+            outbox: KeySequence::single(KeyBinding::char('U')),
+            nudge: KeySequence::single(KeyBinding::char('B')),
+            switch_repo: KeySequence::single(KeyBinding::char('W')),
+            inbox: KeySequence::single(KeyBinding::char('I')),
+
+            // PR tabs
+            open_pr_tab: KeySequence::single(KeyBinding::named(NamedKey::Tab)),
+            next_pr_tab: KeySequence::single(KeyBinding::char(']')),
+            prev_pr_tab: KeySequence::single(KeyBinding::char('[')),
+            close_pr_tab: KeySequence::single(KeyBinding::char('Q')),
+
+            // PR list state filter
+            pr_state_open: KeySequence::single(KeyBinding::char('o')),
+            pr_state_closed: KeySequence::single(KeyBinding::char('c')),
+            pr_state_all: KeySequence::single(KeyBinding::char('a')),
 
             // Markdown rich display
             toggle_markdown_rich: KeySequence::single(KeyBinding::char('M')),
+            toggle_time_format: KeySequence::single(KeyBinding::char('t')),
 
             // List filter
             filter: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('/')),
+            file_finder: KeySequence::single(KeyBinding::ctrl('f')),
+            search: KeySequence::single(KeyBinding::char('/')),
+            grep: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('f')),
+            sort_files: KeySequence::double(KeyBinding::char(' '), KeyBinding::char('s')),
 
             // Multiline selection (fallback for Shift+Enter)
             multiline_select: KeySequence::single(KeyBinding::char('V')),
+
+            // Viewed marks (file list)
+            mark_viewed: KeySequence::single(KeyBinding::char('v')),
+            mark_viewed_directory: KeySequence::single(KeyBinding::char('V')),
+
+            // AI Rally
+            ai_rally_files: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('A')),
+            resume_ai_rally: KeySequence::double(KeyBinding::char('g'), KeyBinding::char('R')),
+            mark_rally_file: KeySequence::single(KeyBinding::char('i')),
+            rally_background: KeySequence::single(KeyBinding::char('b')),
+            rally_approve: KeySequence::single(KeyBinding::char('y')),
+            rally_deny: KeySequence::single(KeyBinding::char('n')),
+            rally_retry: KeySequence::single(KeyBinding::char('r')),
+            rally_export: KeySequence::single(KeyBinding::char('w')),
+            rally_export_markdown: KeySequence::single(KeyBinding::char('W')),
+            rally_replay_pause: KeySequence::single(KeyBinding::char(' ')),
+            rally_log_page_down: KeySequence::single(KeyBinding::char('J')),
+            rally_log_page_up: KeySequence::single(KeyBinding::char('K')),
+            rally_log_top: KeySequence::single(KeyBinding::char('g')),
+            rally_log_bottom: KeySequence::single(KeyBinding::char('G')),
+
+            // Comment list
+            comments_prev_tab: KeySequence::single(KeyBinding::char('[')),
+            comments_next_tab: KeySequence::single(KeyBinding::char(']')),
+            comments_page_down: KeySequence::single(KeyBinding::char('J')),
+            comments_page_up: KeySequence::single(KeyBinding::char('K')),
+            comments_toggle_bots: KeySequence::single(KeyBinding::char('b')),
+            comments_user_info: KeySequence::single(KeyBinding::char('u')),
+            comments_mark: KeySequence::single(KeyBinding::char(' ')),
+            comments_batch_reply: KeySequence::single(KeyBinding::char('R')),
+            comments_unresolved_only: KeySequence::single(KeyBinding::char('f')),
+            comments_apply_suggestion: KeySequence::single(KeyBinding::char('a')),
+            comments_edit: KeySequence::single(KeyBinding::char('e')),
+            comments_delete: KeySequence::single(KeyBinding::char('D')),
+            comments_toggle_task: KeySequence::single(KeyBinding::char('x')),
+
+            // Per-view overrides
+            views: KeybindingViews::default(),
         }
     }
 }
 
 impl KeybindingsConfig {
-    /// Validate keybindings for conflicts
-    ///
-    /// Detects:
-    /// - Single keys that conflict with sequence prefixes
-    /// - Duplicate keybindings for different actions
-    pub fn validate(&self) -> Result<(), Vec<String>> {
-        let mut errors = Vec::new();
-        let mut single_keys: HashMap<KeyBinding, &str> = HashMap::new();
-        let mut sequence_prefixes: HashMap<KeyBinding, &str> = HashMap::new();
-
-        // Collect all keybindings
-        let bindings: Vec<(&str, &KeySequence)> = vec![
+    /// All actions with their key sequences (the names used in config.toml)
+    pub fn bindings(&self) -> Vec<(&'static str, &KeySequence)> {
+        vec![
             ("move_down", &self.move_down),
             ("move_up", &self.move_up),
             ("move_left", &self.move_left),
@@ -234,70 +973,256 @@ impl KeybindingsConfig {
             ("jump_back", &self.jump_back),
             ("next_comment", &self.next_comment),
             ("prev_comment", &self.prev_comment),
+            ("next_unresolved_thread", &self.next_unresolved_thread),
             ("approve", &self.approve),
+            ("approve_next", &self.approve_next),
             ("request_changes", &self.request_changes),
             ("comment", &self.comment),
             ("suggestion", &self.suggestion),
             ("reply", &self.reply),
+            ("translate_comment", &self.translate_comment),
             ("refresh", &self.refresh),
             ("submit", &self.submit),
             ("quit", &self.quit),
             ("help", &self.help),
             ("comment_list", &self.comment_list),
+            ("review_drafts", &self.review_drafts),
             ("ai_rally", &self.ai_rally),
             ("open_panel", &self.open_panel),
             ("go_to_definition", &self.go_to_definition),
             ("go_to_file", &self.go_to_file),
+            ("lfs_preview", &self.lfs_preview),
+            ("view_base_file", &self.view_base_file),
+            ("toggle_blame", &self.toggle_blame),
+            ("line_history", &self.line_history),
+            ("toggle_vendored", &self.toggle_vendored),
+            ("toggle_review_draft", &self.toggle_review_draft),
+            ("compare_local", &self.compare_local),
+            ("commit_list", &self.commit_list),
+            ("checks", &self.checks),
+            ("pr_overview", &self.pr_overview),
+            ("pr_summary", &self.pr_summary),
+            ("review_agenda", &self.review_agenda),
+            ("request_reviewers", &self.request_reviewers),
+            ("edit_labels", &self.edit_labels),
+            ("toggle_pr_draft", &self.toggle_pr_draft),
+            ("explain_file", &self.explain_file),
+            ("draft_comment", &self.draft_comment),
+            ("mark_reviewed", &self.mark_reviewed),
+            ("file_note", &self.file_note),
             ("open_in_browser", &self.open_in_browser),
             ("toggle_local_mode", &self.toggle_local_mode),
+            ("checkout_pr", &self.checkout_pr),
+            ("create_pr", &self.create_pr),
             ("toggle_auto_focus", &self.toggle_auto_focus),
+            ("fixup", &self.fixup),
+            ("resolve_conflicts", &self.resolve_conflicts),
+            ("session_activity", &self.session_activity),
+            ("key_stats", &self.key_stats),
+            ("local_data", &self.local_data),
+            ("outbox", &self.outbox),
+            ("nudge", &self.nudge),
+            ("switch_repo", &self.switch_repo),
+            ("inbox", &self.inbox),
+            ("open_pr_tab", &self.open_pr_tab),
+            ("next_pr_tab", &self.next_pr_tab),
+            ("prev_pr_tab", &self.prev_pr_tab),
+            ("close_pr_tab", &self.close_pr_tab),
+            ("pr_state_open", &self.pr_state_open),
+            ("pr_state_closed", &self.pr_state_closed),
+            ("pr_state_all", &self.pr_state_all),
             ("toggle_markdown_rich", &self.toggle_markdown_rich),
+            ("toggle_time_format", &self.toggle_time_format),
             ("filter", &self.filter),
+            ("file_finder", &self.file_finder),
+            ("search", &self.search),
+            ("grep", &self.grep),
+            ("sort_files", &self.sort_files),
             ("multiline_select", &self.multiline_select),
-        ];
+            ("mark_viewed", &self.mark_viewed),
+            ("mark_viewed_directory", &self.mark_viewed_directory),
+            ("ai_rally_files", &self.ai_rally_files),
+            ("resume_ai_rally", &self.resume_ai_rally),
+            ("mark_rally_file", &self.mark_rally_file),
+            ("rally_background", &self.rally_background),
+            ("rally_approve", &self.rally_approve),
+            ("rally_deny", &self.rally_deny),
+            ("rally_retry", &self.rally_retry),
+            ("rally_export", &self.rally_export),
+            ("rally_export_markdown", &self.rally_export_markdown),
+            ("rally_replay_pause", &self.rally_replay_pause),
+            ("rally_log_page_down", &self.rally_log_page_down),
+            ("rally_log_page_up", &self.rally_log_page_up),
+            ("rally_log_top", &self.rally_log_top),
+            ("rally_log_bottom", &self.rally_log_bottom),
+            ("comments_prev_tab", &self.comments_prev_tab),
+            ("comments_next_tab", &self.comments_next_tab),
+            ("comments_page_down", &self.comments_page_down),
+            ("comments_page_up", &self.comments_page_up),
+            ("comments_toggle_bots", &self.comments_toggle_bots),
+            ("comments_user_info", &self.comments_user_info),
+            ("comments_mark", &self.comments_mark),
+            ("comments_batch_reply", &self.comments_batch_reply),
+            ("comments_unresolved_only", &self.comments_unresolved_only),
+            ("comments_apply_suggestion", &self.comments_apply_suggestion),
+            ("comments_edit", &self.comments_edit),
+            ("comments_delete", &self.comments_delete),
+            ("comments_toggle_task", &self.comments_toggle_task),
+        ]
+    }
 
-        for (name, seq) in &bindings {
-            if seq.0.is_empty() {
-                errors.push(format!("keybinding '{}' is empty", name));
-                continue;
-            }
+    fn bindings_mut(&mut self) -> Vec<(&'static str, &mut KeySequence)> {
+        vec![
+            ("move_down", &mut self.move_down),
+            ("move_up", &mut self.move_up),
+            ("move_left", &mut self.move_left),
+            ("move_right", &mut self.move_right),
+            ("page_down", &mut self.page_down),
+            ("page_up", &mut self.page_up),
+            ("jump_to_first", &mut self.jump_to_first),
+            ("jump_to_last", &mut self.jump_to_last),
+            ("jump_back", &mut self.jump_back),
+            ("next_comment", &mut self.next_comment),
+            ("prev_comment", &mut self.prev_comment),
+            ("next_unresolved_thread", &mut self.next_unresolved_thread),
+            ("approve", &mut self.approve),
+            ("approve_next", &mut self.approve_next),
+            ("request_changes", &mut self.request_changes),
+            ("comment", &mut self.comment),
+            ("suggestion", &mut self.suggestion),
+            ("reply", &mut self.reply),
+            ("translate_comment", &mut self.translate_comment),
+            ("refresh", &mut self.refresh),
+            ("submit", &mut self.submit),
+            ("quit", &mut self.quit),
+            ("help", &mut self.help),
+            ("comment_list", &mut self.comment_list),
+            ("review_drafts", &mut self.review_drafts),
+            ("ai_rally", &mut self.ai_rally),
+            ("open_panel", &mut self.open_panel),
+            ("go_to_definition", &mut self.go_to_definition),
+            ("go_to_file", &mut self.go_to_file),
+            ("lfs_preview", &mut self.lfs_preview),
+            ("view_base_file", &mut self.view_base_file),
+            ("toggle_blame", &mut self.toggle_blame),
+            ("line_history", &mut self.line_history),
+            ("toggle_vendored", &mut self.toggle_vendored),
+            ("toggle_review_draft", &mut self.toggle_review_draft),
+            ("compare_local", &mut self.compare_local),
+            ("commit_list", &mut self.commit_list),
+            ("checks", &mut self.checks),
+            ("pr_overview", &mut self.pr_overview),
+            ("pr_summary", &mut self.pr_summary),
+            ("review_agenda", &mut self.review_agenda),
+            ("request_reviewers", &mut self.request_reviewers),
+            ("edit_labels", &mut self.edit_labels),
+            ("toggle_pr_draft", &mut self.toggle_pr_draft),
+            ("explain_file", &mut self.explain_file),
+            ("draft_comment", &mut self.draft_comment),
+            ("mark_reviewed", &mut self.mark_reviewed),
+            ("file_note", &mut self.file_note),
+            ("open_in_browser", &mut self.open_in_browser),
+            ("toggle_local_mode", &mut self.toggle_local_mode),
+            ("checkout_pr", &mut self.checkout_pr),
+            ("create_pr", &mut self.create_pr),
+            ("toggle_auto_focus", &mut self.toggle_auto_focus),
+            ("fixup", &mut self.fixup),
+            ("resolve_conflicts", &mut self.resolve_conflicts),
+            ("session_activity", &mut self.session_activity),
+            ("key_stats", &mut self.key_stats),
+            ("local_data", &mut self.local_data),
+            ("outbox", &mut self.outbox),
+            ("nudge", &mut self.nudge),
+            ("switch_repo", &mut self.switch_repo),
+            ("inbox", &mut self.inbox),
+            ("open_pr_tab", &mut self.open_pr_tab),
+            ("next_pr_tab", &mut self.next_pr_tab),
+            ("prev_pr_tab", &mut self.prev_pr_tab),
+            ("close_pr_tab", &mut self.close_pr_tab),
+            ("pr_state_open", &mut self.pr_state_open),
+            ("pr_state_closed", &mut self.pr_state_closed),
+            ("pr_state_all", &mut self.pr_state_all),
+            ("toggle_markdown_rich", &mut self.toggle_markdown_rich),
+            ("toggle_time_format", &mut self.toggle_time_format),
+            ("filter", &mut self.filter),
+            ("file_finder", &mut self.file_finder),
+            ("search", &mut self.search),
+            ("grep", &mut self.grep),
+            ("sort_files", &mut self.sort_files),
+            ("multiline_select", &mut self.multiline_select),
+            ("mark_viewed", &mut self.mark_viewed),
+            ("mark_viewed_directory", &mut self.mark_viewed_directory),
+            ("ai_rally_files", &mut self.ai_rally_files),
+            ("resume_ai_rally", &mut self.resume_ai_rally),
+            ("mark_rally_file", &mut self.mark_rally_file),
+            ("rally_background", &mut self.rally_background),
+            ("rally_approve", &mut self.rally_approve),
+            ("rally_deny", &mut self.rally_deny),
+            ("rally_retry", &mut self.rally_retry),
+            ("rally_export", &mut self.rally_export),
+            ("rally_export_markdown", &mut self.rally_export_markdown),
+            ("rally_replay_pause", &mut self.rally_replay_pause),
+            ("rally_log_page_down", &mut self.rally_log_page_down),
+            ("rally_log_page_up", &mut self.rally_log_page_up),
+            ("rally_log_top", &mut self.rally_log_top),
+            ("rally_log_bottom", &mut self.rally_log_bottom),
+            ("comments_prev_tab", &mut self.comments_prev_tab),
+            ("comments_next_tab", &mut self.comments_next_tab),
+            ("comments_page_down", &mut self.comments_page_down),
+            ("comments_page_up", &mut self.comments_page_up),
+            ("comments_toggle_bots", &mut self.comments_toggle_bots),
+            ("comments_user_info", &mut self.comments_user_info),
+            ("comments_mark", &mut self.comments_mark),
+            ("comments_batch_reply", &mut self.comments_batch_reply),
+            (
+                "comments_unresolved_only",
+                &mut self.comments_unresolved_only,
+            ),
+            (
+                "comments_apply_suggestion",
+                &mut self.comments_apply_suggestion,
+            ),
+            ("comments_edit", &mut self.comments_edit),
+            ("comments_delete", &mut self.comments_delete),
+            ("comments_toggle_task", &mut self.comments_toggle_task),
+        ]
+    }
 
-            if seq.is_single() {
-                let key = seq.0[0];
-                if let Some(existing) = single_keys.get(&key) {
-                    // Allow same key for different contexts (e.g., 'r' for reply and request_changes)
-                    // This is intentional - context determines which action is triggered
-                    if !is_context_compatible(name, existing) {
-                        errors.push(format!(
-                            "duplicate keybinding: '{}' and '{}' both use {}",
-                            name,
-                            existing,
-                            key.display()
-                        ));
-                    }
-                } else {
-                    single_keys.insert(key, name);
-                }
-            } else {
-                // For sequences, track the first key as a prefix
-                if let Some(first) = seq.first() {
-                    sequence_prefixes.insert(*first, name);
+    /// Keybindings active in `view`: the global bindings with `[keybindings.views.<view>]` applied
+    ///
+    /// Unknown action names in the view table are ignored (`or config check` reports them).
+    pub fn for_view(&self, view: KeybindingView) -> Self {
+        let mut config = self.clone();
+        let overrides = self.views.overrides(view);
+        if !overrides.is_empty() {
+            for (name, seq) in config.bindings_mut() {
+                if let Some(over) = overrides.get(name) {
+                    *seq = over.clone();
                 }
             }
         }
+        config
+    }
 
-        // Check for conflicts between single keys and sequence prefixes
-        for (key, single_name) in &single_keys {
-            if let Some(seq_name) = sequence_prefixes.get(key) {
-                // Only warn if they're in the same context
-                if !is_context_compatible(single_name, seq_name) {
-                    errors.push(format!(
-                        "keybinding conflict: '{}' ({}) conflicts with sequence prefix for '{}' ({})",
-                        single_name,
-                        key.display(),
-                        seq_name,
-                        key.display()
-                    ));
+    /// Validate keybindings for conflicts
+    ///
+    /// Detects:
+    /// - Keys or sequences that conflict with a prefix of a longer sequence
+    /// - Duplicate keybindings for different actions
+    ///
+    /// Views with overrides are validated with the overrides applied; only conflicts
+    /// that the global bindings do not already have are reported, prefixed with the view.
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let global = binding_conflicts(&self.bindings());
+        let mut errors = global.clone();
+        for view in KeybindingView::ALL {
+            if self.views.overrides(view).is_empty() {
+                continue;
+            }
+            let merged = self.for_view(view);
+            for conflict in binding_conflicts(&merged.bindings()) {
+                if !global.contains(&conflict) {
+                    errors.push(format!("[keybindings.views.{}] {}", view.name(), conflict));
                 }
             }
         }
@@ -310,6 +1235,113 @@ impl KeybindingsConfig {
     }
 }
 
+/// Conflicts between the given bindings (see [`KeybindingsConfig::validate`])
+fn binding_conflicts(bindings: &[(&'static str, &KeySequence)]) -> Vec<String> {
+    let mut errors = Vec::new();
+    let mut sequences: HashMap<&KeySequence, &str> = HashMap::new();
+    let mut sequence_prefixes: HashMap<&[KeyBinding], &str> = HashMap::new();
+
+    for (name, seq) in bindings {
+        if seq.0.is_empty() {
+            errors.push(format!("keybinding '{}' is empty", name));
+            continue;
+        }
+
+        if let Some(existing) = sequences.get(seq) {
+            // Allow same key for different contexts (e.g., 'r' for reply and request_changes)
+            // This is intentional - context determines which action is triggered
+            if !is_context_compatible(name, existing) {
+                errors.push(format!(
+                    "duplicate keybinding: '{}' and '{}' both use {}",
+                    name,
+                    existing,
+                    seq.display()
+                ));
+            }
+        } else {
+            sequences.insert(seq, name);
+        }
+
+        // For sequences, track every proper prefix (the first key, the first two keys, ...)
+        for len in 1..seq.0.len() {
+            sequence_prefixes.insert(&seq.0[..len], name);
+        }
+    }
+
+    // Check for conflicts between keys (or shorter sequences) and sequence prefixes
+    for (name, seq) in bindings {
+        if let Some(seq_name) = sequence_prefixes.get(seq.0.as_slice()) {
+            // Only warn if they're in the same context
+            if !is_context_compatible(name, seq_name) {
+                errors.push(format!(
+                    "keybinding conflict: '{}' ({}) conflicts with sequence prefix for '{}' ({})",
+                    name,
+                    seq.display(),
+                    seq_name,
+                    seq.display()
+                ));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Prefixes of actions that exist in a single view, with the global actions that view also handles
+const VIEW_SCOPED_ACTIONS: &[(&str, &[&str])] = &[
+    (
+        "rally_",
+        &[
+            "move_down",
+            "move_up",
+            "move_right",
+            "quit",
+            "open_panel",
+            "toggle_time_format",
+        ],
+    ),
+    (
+        "comments_",
+        &[
+            "move_down",
+            "move_up",
+            "page_down",
+            "page_up",
+            "quit",
+            "open_panel",
+            "toggle_time_format",
+        ],
+    ),
+    (
+        "pr_state_",
+        &[
+            "move_down",
+            "move_up",
+            "page_down",
+            "page_up",
+            "jump_to_first",
+            "jump_to_last",
+            "filter",
+            "quit",
+            "help",
+            "open_panel",
+            "open_pr_tab",
+            "next_pr_tab",
+            "prev_pr_tab",
+            "close_pr_tab",
+            "open_in_browser",
+            "refresh",
+            "toggle_local_mode",
+            "toggle_time_format",
+            "local_data",
+            "outbox",
+            "inbox",
+            "switch_repo",
+            "nudge",
+        ],
+    ),
+];
+
 /// Check if two keybindings are in compatible contexts
 /// (i.e., they won't conflict because they're used in different views)
 fn is_context_compatible(name1: &str, name2: &str) -> bool {
@@ -322,6 +1354,7 @@ fn is_context_compatible(name1: &str, name2: &str) -> bool {
         &["reply", "request_changes"],
         &["toggle_local_mode", "move_right"], // L vs l: different cases
         &["toggle_auto_focus", "go_to_file"], // F vs gf: different sequence lengths
+        &["mark_viewed_directory", "multiline_select"], // file list vs diff view
     ];
 
     for group in context_groups {
@@ -330,7 +1363,19 @@ fn is_context_compatible(name1: &str, name2: &str) -> bool {
         }
     }
 
-    false
+    // View-scoped keys (`rally_*`, `comments_*`, `pr_state_*`) are only active in their own
+    // view, which shares just the listed global actions
+    let scope = |name: &str| {
+        VIEW_SCOPED_ACTIONS
+            .iter()
+            .find(|(prefix, _)| name.starts_with(prefix))
+    };
+    match (scope(name1), scope(name2)) {
+        (Some((prefix1, _)), Some((prefix2, _))) => prefix1 != prefix2,
+        (Some((_, shared)), None) => !shared.contains(&name2),
+        (None, Some((_, shared))) => !shared.contains(&name1),
+        (None, None) => false,
+    }
 }
 
 // Custom Serialize for KeybindingsConfig to maintain backwards compatibility
@@ -358,40 +1403,21 @@ impl Serialize for KeybindingsConfig {
             }
         }
 
-        map.serialize_entry("move_down", &seq_to_value(&self.move_down))?;
-        map.serialize_entry("move_up", &seq_to_value(&self.move_up))?;
-        map.serialize_entry("move_left", &seq_to_value(&self.move_left))?;
-        map.serialize_entry("move_right", &seq_to_value(&self.move_right))?;
-        map.serialize_entry("page_down", &seq_to_value(&self.page_down))?;
-        map.serialize_entry("page_up", &seq_to_value(&self.page_up))?;
-        map.serialize_entry("jump_to_first", &seq_to_value(&self.jump_to_first))?;
-        map.serialize_entry("jump_to_last", &seq_to_value(&self.jump_to_last))?;
-        map.serialize_entry("jump_back", &seq_to_value(&self.jump_back))?;
-        map.serialize_entry("next_comment", &seq_to_value(&self.next_comment))?;
-        map.serialize_entry("prev_comment", &seq_to_value(&self.prev_comment))?;
-        map.serialize_entry("approve", &seq_to_value(&self.approve))?;
-        map.serialize_entry("request_changes", &seq_to_value(&self.request_changes))?;
-        map.serialize_entry("comment", &seq_to_value(&self.comment))?;
-        map.serialize_entry("suggestion", &seq_to_value(&self.suggestion))?;
-        map.serialize_entry("reply", &seq_to_value(&self.reply))?;
-        map.serialize_entry("refresh", &seq_to_value(&self.refresh))?;
-        map.serialize_entry("submit", &seq_to_value(&self.submit))?;
-        map.serialize_entry("quit", &seq_to_value(&self.quit))?;
-        map.serialize_entry("help", &seq_to_value(&self.help))?;
-        map.serialize_entry("comment_list", &seq_to_value(&self.comment_list))?;
-        map.serialize_entry("ai_rally", &seq_to_value(&self.ai_rally))?;
-        map.serialize_entry("open_panel", &seq_to_value(&self.open_panel))?;
-        map.serialize_entry("go_to_definition", &seq_to_value(&self.go_to_definition))?;
-        map.serialize_entry("go_to_file", &seq_to_value(&self.go_to_file))?;
-        map.serialize_entry("open_in_browser", &seq_to_value(&self.open_in_browser))?;
-        map.serialize_entry("toggle_local_mode", &seq_to_value(&self.toggle_local_mode))?;
-        map.serialize_entry("toggle_auto_focus", &seq_to_value(&self.toggle_auto_focus))?;
-        map.serialize_entry(
-            "toggle_markdown_rich",
-            &seq_to_value(&self.toggle_markdown_rich),
-        )?;
-        map.serialize_entry("filter", &seq_to_value(&self.filter))?;
-        map.serialize_entry("multiline_select", &seq_to_value(&self.multiline_select))?;
+        for (name, seq) in self.bindings() {
+            map.serialize_entry(name, &seq_to_value(seq))?;
+        }
+
+        let mut views = toml::map::Map::new();
+        for view in KeybindingView::ALL {
+            let overrides = self
+                .views
+                .overrides(view)
+                .iter()
+                .map(|(name, seq)| (name.clone(), seq_to_value(seq)))
+                .collect();
+            views.insert(view.name().to_string(), toml::Value::Table(overrides));
+        }
+        map.serialize_entry("views", &views)?;
 
         map.end()
     }
@@ -476,6 +1502,18 @@ impl Config {
         Self::load_from_paths(&global_path, &local_path, project_root)
     }
 
+    /// Load config like [`Config::load`] / [`Config::load_for_dir`], but return
+    /// the problems found instead of failing on them (for `or config check`).
+    pub fn load_with_issues(dir: Option<&Path>) -> Result<(Self, Vec<ConfigIssue>)> {
+        let global_path = Self::config_path();
+        let project_root = match dir {
+            Some(dir) => find_project_root_in(dir),
+            None => find_project_root(),
+        };
+        let local_path = project_root.join(".octorus/config.toml");
+        Self::load_checked(&global_path, &local_path, project_root)
+    }
+
     /// Load config by merging global and local TOML files.
     /// Local values override global values at the TOML table level (deep merge).
     ///
@@ -486,11 +1524,39 @@ impl Config {
     /// analogous to `.editorconfig` or `.vscode/settings.json` — the user
     /// explicitly runs `or` in the repo, implying trust. Do NOT add key
     /// stripping or filtering here.
+    ///
+    /// Warnings (unknown keys, keybinding conflicts) are printed to stderr;
+    /// invalid values fail with every error listed.
     pub fn load_from_paths(
         global_path: &Path,
         local_path: &Path,
         project_root: PathBuf,
     ) -> Result<Self> {
+        let (config, issues) = Self::load_checked(global_path, local_path, project_root)?;
+        let mut errors = Vec::new();
+        for issue in issues {
+            if issue.is_error() {
+                errors.push(format!("  {}", issue));
+            } else {
+                eprintln!("Warning: {}", issue);
+            }
+        }
+        if !errors.is_empty() {
+            anyhow::bail!(
+                "Invalid config:\n{}\nRun `or config check` to see the merged configuration",
+                errors.join("\n")
+            );
+        }
+        Ok(config)
+    }
+
+    /// Merge and deserialize the config files, collecting validation issues.
+    /// Values that fail validation are reported and replaced by their defaults.
+    fn load_checked(
+        global_path: &Path,
+        local_path: &Path,
+        project_root: PathBuf,
+    ) -> Result<(Self, Vec<ConfigIssue>)> {
         let mut base_value: toml::Value = if global_path.exists() {
             let content =
                 fs::read_to_string(global_path).context("Failed to read global config file")?;
@@ -507,9 +1573,11 @@ impl Config {
             deep_merge_toml(&mut base_value, local_value);
         }
 
+        let mut issues = config_check::check_value(&mut base_value);
         let mut config: Config = base_value
             .try_into()
             .context("Failed to deserialize merged config")?;
+        issues.extend(config_check::check_config(&config));
         config.project_root = project_root;
         config.loaded_global_config = if global_path.exists() {
             Some(global_path.to_path_buf())
@@ -523,14 +1591,7 @@ impl Config {
         };
         config.local_overrides = Self::collect_local_override_keys(local_path);
 
-        // Validate keybindings and warn on conflicts
-        if let Err(errors) = config.keybindings.validate() {
-            for error in errors {
-                eprintln!("Warning: {}", error);
-            }
-        }
-
-        Ok(config)
+        Ok((config, issues))
     }
 
     /// Parse the local config file and collect dotted key paths that are set.
@@ -543,10 +1604,32 @@ impl Config {
         let Ok(toml::Value::Table(table)) = content.parse::<toml::Value>() else {
             return overrides;
         };
-        if table.contains_key("editor") {
-            overrides.insert("editor".to_string());
+        for key in ["editor", "review_templates"] {
+            if table.contains_key(key) {
+                overrides.insert(key.to_string());
+            }
         }
-        for section in ["diff", "ai", "keybindings"] {
+        for section in [
+            "diff",
+            "ai",
+            "keybindings",
+            "follow",
+            "translate",
+            "time",
+            "scope",
+            "workspace",
+            "watch",
+            "polling",
+            "cache",
+            "sync",
+            "learning",
+            "nudge",
+            "comments",
+            "github",
+            "ui",
+            "dependencies",
+            "env",
+        ] {
             if let Some(toml::Value::Table(sub)) = table.get(section) {
                 for key in sub.keys() {
                     overrides.insert(format!("{}.{}", section, key));
@@ -556,75 +1639,503 @@ impl Config {
         overrides
     }
 
+    /// `ui.background = "auto"` を判定結果で確定させ、明るい背景なら `diff.light_theme` を使う。
+    /// `detected` が None（判定できなかった）なら dark とみなす
+    pub fn apply_background(&mut self, detected: Option<Background>) {
+        if self.ui.background == BackgroundMode::Auto {
+            self.ui.background = match detected {
+                Some(Background::Light) => BackgroundMode::Light,
+                Some(Background::Dark) | None => BackgroundMode::Dark,
+            };
+        }
+        if self.ui.background == BackgroundMode::Light {
+            self.diff.theme = self.diff.light_theme.clone();
+        }
+    }
+
     pub fn config_path() -> PathBuf {
         BaseDirectories::with_prefix("octorus")
             .map(|dirs| dirs.get_config_home().join("config.toml"))
             .unwrap_or_else(|_| PathBuf::from("config.toml"))
     }
-}
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use insta::assert_json_snapshot;
+
+    #[test]
+    fn test_default_keybindings() {
+        let config = KeybindingsConfig::default();
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_parse_simple_keybinding() {
+        let toml_str = r#"
+            [keybindings]
+            move_down = "n"
+            move_up = "e"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.keybindings.move_down.display(), "n");
+        assert_eq!(config.keybindings.move_up.display(), "e");
+    }
+
+    #[test]
+    fn test_parse_modifier_keybinding() {
+        let toml_str = r#"
+            [keybindings]
+            page_down = { key = "f", ctrl = true }
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.keybindings.page_down.display(), "Ctrl-f");
+    }
+
+    #[test]
+    fn test_parse_sequence_keybinding() {
+        let toml_str = r#"
+            [keybindings]
+            jump_to_first = ["g", "g"]
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.keybindings.jump_to_first.display(), "gg");
+    }
+
+    #[test]
+    fn test_view_overrides_apply_only_to_their_view() {
+        let toml_str = r#"
+            [keybindings]
+            mark_viewed = "x"
+
+            [keybindings.views.diff_view]
+            quit = ["Space", "q", "q"]
+            rally_approve = "Y"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        let kb = &config.keybindings;
+        assert_eq!(kb.mark_viewed.display(), "x");
+        assert_eq!(kb.quit.display(), "q");
+
+        let diff = kb.for_view(KeybindingView::DiffView);
+        assert_eq!(diff.quit.display(), "Spaceqq");
+        assert_eq!(diff.rally_approve.display(), "Y");
+        assert_eq!(diff.mark_viewed.display(), "x");
+        assert_eq!(kb.for_view(KeybindingView::FileList).quit.display(), "q");
+        assert!(kb.validate().is_ok());
+
+        // Overrides survive a serialization round trip
+        let value = toml::Value::try_from(&config).unwrap();
+        let round_trip: Config = value.try_into().unwrap();
+        assert_eq!(
+            round_trip
+                .keybindings
+                .for_view(KeybindingView::DiffView)
+                .quit
+                .display(),
+            "Spaceqq"
+        );
+    }
+
+    #[test]
+    fn test_validate_reports_sequence_prefix_conflicts() {
+        // "gg" is a prefix of the longer sequence, which could never be typed
+        let config = KeybindingsConfig {
+            explain_file: KeySequence(vec![
+                KeyBinding::char('g'),
+                KeyBinding::char('g'),
+                KeyBinding::char('e'),
+            ]),
+            ..Default::default()
+        };
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("'jump_to_first' (gg)"));
+        assert!(errors[0].contains("'explain_file'"));
+
+        // Duplicate sequences are reported like duplicate keys
+        let mut config = KeybindingsConfig::default();
+        config.grep = config.sort_files.clone();
+        let errors = config.validate().unwrap_err();
+        assert!(errors[0].starts_with("duplicate keybinding: 'sort_files' and 'grep'"));
+    }
+
+    #[test]
+    fn test_validate_reports_conflicts_within_a_view() {
+        let mut config = KeybindingsConfig::default();
+        config.views.file_list.insert(
+            "quit".to_string(),
+            KeySequence::single(KeyBinding::char('v')),
+        );
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("[keybindings.views.file_list] duplicate keybinding"));
+        assert!(errors[0].contains("'mark_viewed'"));
+
+        // Rally keys only clash with the few actions the rally view shares
+        let mut config = KeybindingsConfig::default();
+        config.rally_approve = config.approve.clone();
+        assert!(config.validate().is_ok());
+        config.rally_approve = config.open_panel.clone();
+        assert!(config.validate().is_err());
+
+        // Comment list and PR list keys likewise, and never with each other
+        let mut config = KeybindingsConfig::default();
+        config.comments_mark = config.pr_state_open.clone();
+        assert!(config.validate().is_ok());
+        config.views.comment_list.insert(
+            "comments_edit".to_string(),
+            KeySequence::single(KeyBinding::char('j')),
+        );
+        let errors = config.validate().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].starts_with("[keybindings.views.comment_list] duplicate keybinding"));
+    }
+
+    #[test]
+    fn test_backwards_compatible_defaults() {
+        // Empty config should use all defaults
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.keybindings.approve.display(), "a");
+        assert_eq!(config.keybindings.request_changes.display(), "r");
+        assert_eq!(config.keybindings.filter.display(), "Space/");
+    }
+
+    #[test]
+    fn test_backwards_compatible_without_filter_key() {
+        // Config without filter key should deserialize with default
+        let toml_str = r#"
+            [keybindings]
+            move_down = "j"
+            move_up = "k"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.keybindings.filter.display(), "Space/");
+    }
+
+    #[test]
+    fn test_parse_follow_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.follow.path.is_none());
+        assert_eq!(config.follow.throttle_ms, 100);
+
+        let toml_str = r#"
+            [follow]
+            path = "/tmp/or-follow"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.follow.path.as_deref(), Some("/tmp/or-follow"));
+        assert_eq!(config.follow.throttle_ms, 100);
+    }
+
+    #[test]
+    fn test_parse_translate_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.translate.language.is_none());
+        assert!(config.translate.command.is_none());
+
+        let toml_str = r#"
+            [translate]
+            language = "ja"
+            command = "trans -b :{lang}"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.translate.language.as_deref(), Some("ja"));
+        assert_eq!(
+            config.translate.command.as_deref(),
+            Some("trans -b :{lang}")
+        );
+    }
+
+    #[test]
+    fn test_parse_time_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.time.style, TimeStyle::Relative);
+        assert_eq!(config.time.format, "%Y-%m-%d %H:%M");
+        assert_eq!(config.time.timezone, "local");
+
+        let toml_str = r#"
+            [time]
+            style = "absolute"
+            timezone = "+09:00"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.time.style, TimeStyle::Absolute);
+        assert_eq!(config.time.format, "%Y-%m-%d %H:%M");
+        assert_eq!(config.time.timezone, "+09:00");
+    }
+
+    #[test]
+    fn test_parse_ui_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.ui.spinner, SpinnerStyle::Braille);
+        assert_eq!(config.ui.progress, ProgressStyle::Bar);
+
+        let toml_str = r#"
+            [ui]
+            spinner = "line"
+            progress = "ascii"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ui.spinner, SpinnerStyle::Line);
+        assert_eq!(config.ui.spinner.frames(), &["|", "/", "-", "\\"]);
+        assert_eq!(config.ui.progress, ProgressStyle::Ascii);
+    }
+
+    #[test]
+    fn test_apply_background() {
+        let mut config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.ui.background, BackgroundMode::Auto);
+        config.apply_background(None);
+        assert_eq!(config.ui.background, BackgroundMode::Dark);
+        assert_eq!(config.diff.theme, "base16-ocean.dark");
+
+        let mut config: Config = toml::from_str("").unwrap();
+        config.apply_background(Some(Background::Light));
+        assert_eq!(config.ui.background, BackgroundMode::Light);
+        assert_eq!(config.diff.theme, "base16-ocean.light");
+
+        // 明示した背景は判定結果より優先する
+        let toml_str = r#"
+            [ui]
+            background = "light"
+            [diff]
+            light_theme = "InspiredGitHub"
+        "#;
+        let mut config: Config = toml::from_str(toml_str).unwrap();
+        config.apply_background(Some(Background::Dark));
+        assert_eq!(config.ui.background, BackgroundMode::Light);
+        assert_eq!(config.diff.theme, "InspiredGitHub");
+    }
+
+    #[test]
+    fn test_theme_file_path() {
+        assert_eq!(theme_file_path("Dracula"), None);
+        assert_eq!(theme_file_path("base16-ocean.dark"), None);
+        assert_eq!(
+            theme_file_path("/themes/nord.tmTheme"),
+            Some(PathBuf::from("/themes/nord.tmTheme"))
+        );
+        // 相対パスは設定ディレクトリから
+        let path = theme_file_path("helix/onedark.toml").unwrap();
+        assert_eq!(
+            path,
+            Config::config_path()
+                .parent()
+                .unwrap()
+                .join("helix/onedark.toml")
+        );
+    }
+
+    #[test]
+    fn test_parse_dependencies_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.dependencies.advisory_db, None);
+
+        let toml_str = r#"
+            [dependencies]
+            advisory_db = "~/osv"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.dependencies.advisory_db.as_deref(), Some("~/osv"));
+    }
+
+    #[test]
+    fn test_parse_scope_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.scope.paths.is_empty());
+
+        let toml_str = r#"
+            [scope]
+            paths = ["services/api", "libs/shared"]
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.scope.paths, vec!["services/api", "libs/shared"]);
+    }
+
+    #[test]
+    fn test_parse_review_templates() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.review_templates.is_empty());
+
+        let toml_str = r#"
+            [[review_templates]]
+            name = "Database"
+            paths = ["migrations/"]
+            body = "- [ ] Migration is reversible"
+
+            [[review_templates]]
+            name = "Security"
+            labels = ["security"]
+            body = "- [ ] No secrets in logs"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.review_templates.len(), 2);
+        assert_eq!(config.review_templates[0].paths, vec!["migrations/"]);
+        assert!(config.review_templates[0].labels.is_empty());
+        assert_eq!(config.review_templates[1].labels, vec!["security"]);
+    }
+
+    #[test]
+    fn test_parse_workspace_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.workspace.repos.is_empty());
+        assert!(config.workspace.dirs.is_empty());
+        assert_eq!(config.workspace.repos_with("me/app"), vec!["me/app"]);
+
+        let toml_str = r#"
+            [workspace]
+            repos = ["me/api", "me/app"]
+            dirs = ["~/src/api"]
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(
+            config.workspace.repos_with("me/app"),
+            vec!["me/app", "me/api"]
+        );
+        assert_eq!(config.workspace.dirs, vec!["~/src/api"]);
+    }
+
+    #[test]
+    fn test_parse_watch_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.watch.enabled);
+        assert_eq!(config.watch.interval_secs, 20);
+
+        let toml_str = r#"
+            [watch]
+            enabled = true
+            interval_secs = 10
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(config.watch.enabled);
+        assert_eq!(config.watch.interval_secs, 10);
+    }
+
+    #[test]
+    fn test_parse_learning_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.learning.enabled);
+
+        let config: Config = toml::from_str("[learning]\nenabled = true").unwrap();
+        assert!(config.learning.enabled);
+    }
+
+    #[test]
+    fn test_parse_nudge_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.nudge.stale_days, 3);
+        assert_eq!(config.nudge.template, crate::nudge::DEFAULT_TEMPLATE);
+
+        let config: Config =
+            toml::from_str("[nudge]\nstale_days = 7\ntemplate = \"ping {{reviewers}}\"").unwrap();
+        assert_eq!(config.nudge.stale_days, 7);
+        assert_eq!(config.nudge.template, "ping {{reviewers}}");
+    }
+
+    #[test]
+    fn test_parse_diff_comment_filters() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.diff.hide_resolved_comments);
+        assert!(!config.diff.hide_outdated_comments);
+        assert!(config.diff.hide_comment_authors.is_empty());
+
+        let toml_str = r#"
+            [diff]
+            hide_resolved_comments = true
+            hide_outdated_comments = true
+            hide_comment_authors = ["*[bot]", "renovate"]
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert!(config.diff.hide_resolved_comments);
+        assert!(config.diff.hide_outdated_comments);
+        assert_eq!(config.diff.hide_comment_authors, vec!["*[bot]", "renovate"]);
+        // 他の diff 設定は既定値のまま
+        assert_eq!(config.diff.tab_width, 4);
+    }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use insta::assert_json_snapshot;
+    #[test]
+    fn test_parse_comments_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.comments.bot_authors.is_empty());
+
+        let config: Config =
+            toml::from_str("[comments]\nbot_authors = [\"deploy-*\"]").unwrap();
+        assert_eq!(config.comments.bot_authors, vec!["deploy-*"]);
+    }
 
     #[test]
-    fn test_default_keybindings() {
-        let config = KeybindingsConfig::default();
-        assert!(config.validate().is_ok());
+    fn test_parse_polling_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.polling.adaptive);
+        assert_eq!(config.polling.idle_after_secs, 60);
+
+        let config: Config =
+            toml::from_str("[polling]\nadaptive = false\nidle_after_secs = 300").unwrap();
+        assert!(!config.polling.adaptive);
+        assert_eq!(config.polling.idle_after_secs, 300);
     }
 
     #[test]
-    fn test_parse_simple_keybinding() {
-        let toml_str = r#"
-            [keybindings]
-            move_down = "n"
-            move_up = "e"
-        "#;
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.keybindings.move_down.display(), "n");
-        assert_eq!(config.keybindings.move_up.display(), "e");
+    fn test_parse_sync_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.sync.backend, SyncBackendKind::Off);
+        assert_eq!(config.sync.remote, "origin");
+
+        let config: Config =
+            toml::from_str("[sync]\nbackend = \"gist\"\ngist_id = \"abc123\"").unwrap();
+        assert_eq!(config.sync.backend, SyncBackendKind::Gist);
+        assert_eq!(config.sync.gist_id, "abc123");
     }
 
     #[test]
-    fn test_parse_modifier_keybinding() {
-        let toml_str = r#"
-            [keybindings]
-            page_down = { key = "f", ctrl = true }
-        "#;
-        let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.keybindings.page_down.display(), "Ctrl-f");
+    fn test_parse_cache_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(config.cache.enabled);
+        assert_eq!(config.cache.ttl_hours, 168);
+        assert_eq!(config.cache.max_size_mb, 200);
+
+        let config: Config =
+            toml::from_str("[cache]\nenabled = false\nmax_size_mb = 50").unwrap();
+        assert!(!config.cache.enabled);
+        assert_eq!(config.cache.ttl_hours, 168);
+        assert_eq!(config.cache.max_size_mb, 50);
     }
 
     #[test]
-    fn test_parse_sequence_keybinding() {
+    fn test_parse_github_config() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.github.credential, CredentialSource::Env);
+        assert_eq!(config.github.keychain_service, "octorus");
+
         let toml_str = r#"
-            [keybindings]
-            jump_to_first = ["g", "g"]
+            [github]
+            credential = "keychain"
+            keychain_account = "work"
         "#;
         let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.keybindings.jump_to_first.display(), "gg");
+        assert_eq!(config.github.credential, CredentialSource::Keychain);
+        assert_eq!(config.github.keychain_service, "octorus");
+        assert_eq!(config.github.keychain_account, "work");
+        assert!(toml::from_str::<Config>("[github]\ncredential = \"plain\"").is_err());
     }
 
     #[test]
-    fn test_backwards_compatible_defaults() {
-        // Empty config should use all defaults
+    fn test_parse_github_backend() {
         let config: Config = toml::from_str("").unwrap();
-        assert_eq!(config.keybindings.approve.display(), "a");
-        assert_eq!(config.keybindings.request_changes.display(), "r");
-        assert_eq!(config.keybindings.filter.display(), "Space/");
-    }
+        assert_eq!(config.github.backend, GithubBackendKind::Auto);
+        assert_eq!(config.github.api_url, "https://api.github.com");
 
-    #[test]
-    fn test_backwards_compatible_without_filter_key() {
-        // Config without filter key should deserialize with default
         let toml_str = r#"
-            [keybindings]
-            move_down = "j"
-            move_up = "k"
+            [github]
+            backend = "api"
+            api_url = "https://ghe.example.com/api/v3"
         "#;
         let config: Config = toml::from_str(toml_str).unwrap();
-        assert_eq!(config.keybindings.filter.display(), "Space/");
+        assert_eq!(config.github.backend, GithubBackendKind::Api);
+        assert_eq!(config.github.api_url, "https://ghe.example.com/api/v3");
+        assert!(toml::from_str::<Config>("[github]\nbackend = \"octocrab\"").is_err());
     }
 
     #[test]
@@ -634,12 +2145,29 @@ mod tests {
         {
           "reviewer": "claude",
           "reviewee": "claude",
+          "co_reviewer": null,
           "max_iterations": 10,
           "timeout_secs": 600,
           "prompt_dir": null,
+          "guidelines_file": null,
           "reviewer_additional_tools": [],
           "reviewee_additional_tools": [],
-          "auto_post": false
+          "auto_post": false,
+          "dry_run": false,
+          "context_budget_tokens": 100000,
+          "budget_tokens": 0,
+          "budget_usd": 0.0,
+          "input_cost_per_mtok": 0.0,
+          "output_cost_per_mtok": 0.0,
+          "gemini": {
+            "model": null,
+            "api_key": null
+          },
+          "openai": {
+            "base_url": "http://localhost:11434/v1",
+            "model": "",
+            "api_key": null
+          }
         }
         "#);
     }
@@ -658,12 +2186,29 @@ mod tests {
         {
           "reviewer": "codex",
           "reviewee": "claude",
+          "co_reviewer": null,
           "max_iterations": 5,
           "timeout_secs": 300,
           "prompt_dir": null,
+          "guidelines_file": null,
           "reviewer_additional_tools": [],
           "reviewee_additional_tools": [],
-          "auto_post": false
+          "auto_post": false,
+          "dry_run": false,
+          "context_budget_tokens": 100000,
+          "budget_tokens": 0,
+          "budget_usd": 0.0,
+          "input_cost_per_mtok": 0.0,
+          "output_cost_per_mtok": 0.0,
+          "gemini": {
+            "model": null,
+            "api_key": null
+          },
+          "openai": {
+            "base_url": "http://localhost:11434/v1",
+            "model": "",
+            "api_key": null
+          }
         }
         "#);
     }
@@ -680,9 +2225,11 @@ mod tests {
         {
           "reviewer": "claude",
           "reviewee": "claude",
+          "co_reviewer": null,
           "max_iterations": 10,
           "timeout_secs": 600,
           "prompt_dir": null,
+          "guidelines_file": null,
           "reviewer_additional_tools": [
             "Skill",
             "WebSearch"
@@ -690,7 +2237,22 @@ mod tests {
           "reviewee_additional_tools": [
             "Bash(git push:*)"
           ],
-          "auto_post": false
+          "auto_post": false,
+          "dry_run": false,
+          "context_budget_tokens": 100000,
+          "budget_tokens": 0,
+          "budget_usd": 0.0,
+          "input_cost_per_mtok": 0.0,
+          "output_cost_per_mtok": 0.0,
+          "gemini": {
+            "model": null,
+            "api_key": null
+          },
+          "openai": {
+            "base_url": "http://localhost:11434/v1",
+            "model": "",
+            "api_key": null
+          }
         }
         "#);
     }
@@ -705,6 +2267,56 @@ mod tests {
         assert!(config.ai.auto_post);
     }
 
+    #[test]
+    fn test_parse_ai_config_dry_run() {
+        let config: Config = toml::from_str("").unwrap();
+        assert!(!config.ai.dry_run);
+
+        let config: Config = toml::from_str("[ai]\ndry_run = true").unwrap();
+        assert!(config.ai.dry_run);
+    }
+
+    #[test]
+    fn test_parse_ai_config_context_budget() {
+        let toml_str = r#"
+            [ai]
+            context_budget_tokens = 20000
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ai.context_budget_tokens, 20000);
+    }
+
+    #[test]
+    fn test_parse_ai_config_rally_budget() {
+        let toml_str = r#"
+            [ai]
+            budget_tokens = 500000
+            budget_usd = 2.5
+            input_cost_per_mtok = 3
+            output_cost_per_mtok = 15.0
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ai.budget_tokens, 500_000);
+        assert_eq!(config.ai.budget_usd, 2.5);
+        assert_eq!(config.ai.input_cost_per_mtok, 3.0);
+        assert_eq!(config.ai.output_cost_per_mtok, 15.0);
+    }
+
+    #[test]
+    fn test_parse_ai_config_gemini() {
+        let toml_str = r#"
+            [ai]
+            reviewer = "gemini"
+
+            [ai.gemini]
+            model = "gemini-2.5-pro"
+        "#;
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.ai.reviewer, "gemini");
+        assert_eq!(config.ai.gemini.model.as_deref(), Some("gemini-2.5-pro"));
+        assert!(config.ai.gemini.api_key.is_none());
+    }
+
     #[test]
     fn test_parse_ai_config_auto_post_default() {
         let config: Config = toml::from_str("").unwrap();
@@ -817,12 +2429,268 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_fixup_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.fixup.display(), "X");
+    }
+
     #[test]
     fn test_multiline_select_default_key() {
         let config = KeybindingsConfig::default();
         assert_eq!(config.multiline_select.display(), "V");
     }
 
+    #[test]
+    fn test_toggle_review_draft_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.toggle_review_draft.display(), "gp");
+    }
+
+    #[test]
+    fn test_review_drafts_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.review_drafts.display(), "P");
+    }
+
+    #[test]
+    fn test_toggle_vendored_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.toggle_vendored.display(), "gv");
+    }
+
+    #[test]
+    fn test_local_data_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.local_data.display(), "D");
+    }
+
+    #[test]
+    fn test_outbox_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.outbox.display(), "U");
+    }
+
+    #[test]
+    fn test_compare_local_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.compare_local.display(), "gc");
+    }
+
+    #[test]
+    fn test_explain_file_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.explain_file.display(), "ge");
+    }
+
+    #[test]
+    fn test_draft_comment_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.draft_comment.display(), "E");
+    }
+
+    #[test]
+    fn test_checkout_pr_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.checkout_pr.display(), "go");
+    }
+
+    #[test]
+    fn test_create_pr_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.create_pr.display(), "gu");
+    }
+
+    #[test]
+    fn test_commit_list_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.commit_list.display(), "gh");
+    }
+
+    #[test]
+    fn test_checks_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.checks.display(), "gs");
+    }
+
+    #[test]
+    fn test_pr_overview_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.pr_overview.display(), "gi");
+    }
+
+    #[test]
+    fn test_pr_summary_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.pr_summary.display(), "gS");
+    }
+
+    #[test]
+    fn test_review_agenda_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.review_agenda.display(), "ga");
+    }
+
+    #[test]
+    fn test_approve_next_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.approve_next.display(), "Ctrl-n");
+    }
+
+    #[test]
+    fn test_review_progress_default_keys() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.mark_reviewed.display(), "m");
+        assert_eq!(config.file_note.display(), "gn");
+    }
+
+    #[test]
+    fn test_request_reviewers_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.request_reviewers.display(), "gr");
+    }
+
+    #[test]
+    fn test_edit_labels_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.edit_labels.display(), "gt");
+    }
+
+    #[test]
+    fn test_toggle_pr_draft_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.toggle_pr_draft.display(), "gw");
+    }
+
+    #[test]
+    fn test_resolve_conflicts_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.resolve_conflicts.display(), "gm");
+    }
+
+    #[test]
+    fn test_nudge_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.nudge.display(), "B");
+    }
+
+    #[test]
+    fn test_search_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.search.display(), "/");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_grep_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.grep.display(), "Spacef");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_sort_files_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.sort_files.display(), "Spaces");
+        assert!(config.validate().is_ok());
+    }
+
+    #[test]
+    fn test_switch_repo_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.switch_repo.display(), "W");
+    }
+
+    #[test]
+    fn test_inbox_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.inbox.display(), "I");
+    }
+
+    #[test]
+    fn test_pr_tab_default_keys() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.open_pr_tab.display(), "Tab");
+        assert_eq!(config.next_pr_tab.display(), "]");
+        assert_eq!(config.prev_pr_tab.display(), "[");
+        assert_eq!(config.close_pr_tab.display(), "Q");
+    }
+
+    #[test]
+    fn test_key_stats_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.key_stats.display(), "S");
+    }
+
+    #[test]
+    fn test_view_base_file_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.view_base_file.display(), "gb");
+    }
+
+    #[test]
+    fn test_toggle_blame_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.toggle_blame.display(), "gB");
+    }
+
+    #[test]
+    fn test_line_history_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.line_history.display(), "gL");
+    }
+
+    #[test]
+    fn test_ai_rally_files_default_keys() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.ai_rally_files.display(), "gA");
+        assert_eq!(config.resume_ai_rally.display(), "gR");
+        assert_eq!(config.mark_rally_file.display(), "i");
+    }
+
+    #[test]
+    fn test_rally_export_default_keys() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.rally_export.display(), "w");
+        assert_eq!(config.rally_export_markdown.display(), "W");
+    }
+
+    #[test]
+    fn test_next_unresolved_thread_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.next_unresolved_thread.display(), "gU");
+    }
+
+    #[test]
+    fn test_lfs_preview_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.lfs_preview.display(), "gl");
+    }
+
+    #[test]
+    fn test_toggle_time_format_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.toggle_time_format.display(), "t");
+    }
+
+    #[test]
+    fn test_file_finder_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.file_finder.display(), "Ctrl-f");
+    }
+
+    #[test]
+    fn test_translate_comment_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.translate_comment.display(), "T");
+    }
+
+    #[test]
+    fn test_session_activity_default_key() {
+        let config = KeybindingsConfig::default();
+        assert_eq!(config.session_activity.display(), "H");
+    }
+
     #[test]
     fn test_parse_multiline_select_custom() {
         let toml_str = r#"
@@ -1122,4 +2990,30 @@ tab_width = 0
             Config::load_from_paths(&global, &local, dir.path().to_path_buf()).unwrap();
         assert_eq!(config.project_root, dir.path());
     }
+
+    #[test]
+    fn test_load_from_paths_rejects_invalid_values() {
+        let dir = tempfile::tempdir().unwrap();
+        let global = dir.path().join("global.toml");
+        let local = dir.path().join("local.toml");
+        fs::write(&global, "[ai]\nmax_iterations = 500\n").unwrap();
+        fs::write(&local, "[ui]\nspinner = \"brail\"\n[diff]\nthem = \"x\"\n").unwrap();
+
+        let error = Config::load_from_paths(&global, &local, dir.path().to_path_buf())
+            .unwrap_err()
+            .to_string();
+        assert!(error.contains("ui.spinner: unknown variant `brail`"), "{}", error);
+        assert!(
+            error.contains("ai.max_iterations: must be between 1 and 100 (got 500)"),
+            "{}",
+            error
+        );
+        // 知らないキーは警告だけで、エラーには含めない
+        assert!(!error.contains("diff.them"), "{}", error);
+
+        let (config, issues) =
+            Config::load_checked(&global, &local, dir.path().to_path_buf()).unwrap();
+        assert_eq!(config.ui.spinner, SpinnerStyle::Braille);
+        assert_eq!(issues.len(), 3);
+    }
 }
@@ -0,0 +1,682 @@
+//! 設定ファイルの検証
+//!
+//! グローバルとローカルの設定をマージした TOML を、[`Config`] に変換する前後で検証する。
+//!
+//! - [`check_value`] は変換前に、知らないキー（警告）と、型・キーの書式・列挙値の誤り（エラー）を検証する
+//! - [`check_config`] は変換後に、範囲外の数値・タイムゾーン（エラー）と、同じ画面でのキーバインドの衝突（警告）を検証する
+//! - [`check_themes`] はシンタックスハイライトのテーマ名（警告）を検証する。テーマの一覧は TUI 側にあるため呼び出し側が渡す
+//!
+//! エラーのある設定では起動せず、`or config check` で全体を確認できる。
+
+use std::fmt;
+
+use crate::config::{Config, ReviewTemplate};
+use crate::time_format::TimeZoneSetting;
+
+/// 値を持たないと出力されない（`Option` の）キー
+const OPTIONAL_KEYS: &[&str] = &[
+    "editor",
+    "ai.co_reviewer",
+    "ai.prompt_dir",
+    "ai.guidelines_file",
+    "ai.gemini.model",
+    "ai.gemini.api_key",
+    "ai.openai.api_key",
+    "follow.path",
+    "translate.language",
+    "translate.command",
+    "dependencies.advisory_db",
+];
+
+/// 中身を自由に書けるテーブル（キーを検証しない）
+const FREE_FORM_TABLES: &[&str] = &["env.vars", "env.repos"];
+
+/// 見つかった問題の重さ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    /// 起動しない
+    Error,
+    /// 起動するが、設定の一部が無視されるか既定値になる
+    Warning,
+}
+
+/// 設定の問題 1 件
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigIssue {
+    pub severity: Severity,
+    /// ドット区切りのキー（例: `diff.tab_width`）
+    pub key: String,
+    pub message: String,
+}
+
+impl ConfigIssue {
+    fn error(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Error,
+            key: key.into(),
+            message: message.into(),
+        }
+    }
+
+    fn warning(key: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            key: key.into(),
+            message: message.into(),
+        }
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity == Severity::Error
+    }
+}
+
+impl fmt::Display for ConfigIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.key, self.message)
+    }
+}
+
+/// マージした設定を変換前に検証する。
+/// 変換できない値はエラーとして報告して取り除くため、残りは [`Config`] に変換できる
+pub fn check_value(value: &mut toml::Value) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let Some(table) = value.as_table_mut() else {
+        return issues;
+    };
+    let defaults = toml::Value::try_from(Config::default()).unwrap_or(toml::Value::Boolean(false));
+    check_unknown_keys(table, defaults.as_table(), "", &mut issues);
+
+    let keys: Vec<String> = table.keys().cloned().collect();
+    for key in keys {
+        let Some(value) = table.get_mut(&key) else {
+            continue;
+        };
+        match value {
+            toml::Value::Table(section) => {
+                let names: Vec<String> = section.keys().cloned().collect();
+                for name in names {
+                    let path = format!("{}.{}", key, name);
+                    let nested = single(&key, single(&name, section[&name].clone()));
+                    if let Err(message) = deserialize_error(nested) {
+                        issues.push(ConfigIssue::error(path, message));
+                        section.remove(&name);
+                    }
+                }
+            }
+            toml::Value::Array(items) if key == "review_templates" => {
+                for (index, item) in items.iter_mut().enumerate() {
+                    let Some(template) = item.as_table_mut() else {
+                        continue;
+                    };
+                    let names: Vec<String> = template.keys().cloned().collect();
+                    for name in names {
+                        let path = format!("review_templates[{}].{}", index, name);
+                        let element = single(&name, template[&name].clone());
+                        let nested = single(&key, toml::Value::Array(vec![element]));
+                        if let Err(message) = deserialize_error(nested) {
+                            issues.push(ConfigIssue::error(path, message));
+                            template.remove(&name);
+                        }
+                    }
+                }
+            }
+            _ => {}
+        }
+        // セクションそのものの型の誤り（`diff = 1` など）
+        let Some(value) = table.get(&key) else {
+            continue;
+        };
+        if let Err(message) = deserialize_error(single(&key, value.clone())) {
+            issues.push(ConfigIssue::error(key.clone(), message));
+            table.remove(&key);
+        }
+    }
+    issues
+}
+
+/// `key = value` だけのテーブル
+fn single(key: &str, value: toml::Value) -> toml::Value {
+    let mut table = toml::map::Map::new();
+    table.insert(key.to_string(), value);
+    toml::Value::Table(table)
+}
+
+/// 1 つのキーだけを設定した [`Config`] に変換できなければ、その理由
+fn deserialize_error(value: toml::Value) -> Result<(), String> {
+    value
+        .try_into::<Config>()
+        .map(|_| ())
+        .map_err(|e| e.message().trim().to_string())
+}
+
+fn check_unknown_keys(
+    table: &toml::map::Map<String, toml::Value>,
+    known: Option<&toml::map::Map<String, toml::Value>>,
+    prefix: &str,
+    issues: &mut Vec<ConfigIssue>,
+) {
+    let Some(known) = known else {
+        return;
+    };
+    for (key, value) in table {
+        let path = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+        if FREE_FORM_TABLES.contains(&path.as_str()) {
+            continue;
+        }
+        if path == "review_templates" {
+            let template = toml::Value::try_from(ReviewTemplate::default()).ok();
+            for (index, item) in value.as_array().into_iter().flatten().enumerate() {
+                if let Some(item) = item.as_table() {
+                    let prefix = format!("review_templates[{}]", index);
+                    check_unknown_keys(
+                        item,
+                        template.as_ref().and_then(toml::Value::as_table),
+                        &prefix,
+                        issues,
+                    );
+                }
+            }
+            continue;
+        }
+        // 画面ごとの上書き（`[keybindings.views.file_list]` など）のキーは操作の名前
+        if path == "keybindings.views" {
+            let views = known.get(key).and_then(toml::Value::as_table);
+            for (view, overrides) in value.as_table().into_iter().flatten() {
+                let view_path = format!("{}.{}", path, view);
+                match views {
+                    Some(views) if views.contains_key(view) => {
+                        if let Some(overrides) = overrides.as_table() {
+                            check_unknown_keys(overrides, Some(known), &view_path, issues);
+                        }
+                    }
+                    _ => issues.push(unknown_key(
+                        view_path,
+                        view,
+                        views
+                            .into_iter()
+                            .flat_map(|views| views.keys().map(String::as_str)),
+                    )),
+                }
+            }
+            continue;
+        }
+        match known.get(key) {
+            // キーバインドの値（`{ key = "d", ctrl = true }` など）は既定値が文字列なので中まで見ない
+            Some(toml::Value::Table(sub)) => {
+                if let Some(value) = value.as_table() {
+                    check_unknown_keys(value, Some(sub), &path, issues);
+                }
+            }
+            Some(_) => {}
+            None if OPTIONAL_KEYS.contains(&path.as_str()) => {}
+            None => {
+                let candidates = known.keys().map(String::as_str).chain(
+                    OPTIONAL_KEYS
+                        .iter()
+                        .filter_map(|optional| optional_child(optional, prefix)),
+                );
+                issues.push(unknown_key(path, key, candidates));
+            }
+        }
+    }
+}
+
+/// `prefix` の直下にある省略可能なキーの名前
+fn optional_child<'a>(optional: &'a str, prefix: &str) -> Option<&'a str> {
+    if prefix.is_empty() {
+        return (!optional.contains('.')).then_some(optional);
+    }
+    optional
+        .strip_prefix(prefix)
+        .and_then(|rest| rest.strip_prefix('.'))
+}
+
+fn unknown_key<'a>(
+    path: String,
+    name: &str,
+    candidates: impl Iterator<Item = &'a str>,
+) -> ConfigIssue {
+    let message = match closest(name, candidates) {
+        Some(suggestion) => format!("unknown key (did you mean `{}`?)", suggestion),
+        None => "unknown key".to_string(),
+    };
+    ConfigIssue::warning(path, message)
+}
+
+/// 編集距離が 2 以内（短い名前なら 1 以内）で最も近い候補
+fn closest<'a>(name: &str, candidates: impl Iterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = if name.chars().count() <= 4 { 1 } else { 2 };
+    candidates
+        .map(|candidate| (edit_distance(name, candidate), candidate))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// 変換後の設定を検証する
+pub fn check_config(config: &Config) -> Vec<ConfigIssue> {
+    let mut issues = Vec::new();
+    let ranges: [(&str, u64, u64, u64); 7] = [
+        ("diff.tab_width", config.diff.tab_width.into(), 1, 16),
+        ("ai.max_iterations", config.ai.max_iterations.into(), 1, 100),
+        ("ai.timeout_secs", config.ai.timeout_secs, 1, u64::MAX),
+        (
+            "polling.idle_after_secs",
+            config.polling.idle_after_secs,
+            1,
+            u64::MAX,
+        ),
+        ("cache.ttl_hours", config.cache.ttl_hours, 1, 24 * 365),
+        ("cache.max_size_mb", config.cache.max_size_mb, 1, u64::MAX),
+        ("nudge.stale_days", config.nudge.stale_days.into(), 1, 365),
+    ];
+    for (key, value, min, max) in ranges {
+        if value < min || value > max {
+            let message = if max == u64::MAX {
+                format!("must be at least {} (got {})", min, value)
+            } else {
+                format!("must be between {} and {} (got {})", min, max, value)
+            };
+            issues.push(ConfigIssue::error(key, message));
+        }
+    }
+    let amounts = [
+        ("ai.budget_usd", config.ai.budget_usd),
+        ("ai.input_cost_per_mtok", config.ai.input_cost_per_mtok),
+        ("ai.output_cost_per_mtok", config.ai.output_cost_per_mtok),
+    ];
+    for (key, value) in amounts {
+        if !(value >= 0.0 && value.is_finite()) {
+            issues.push(ConfigIssue::error(
+                key,
+                format!("must be zero or more (got {})", value),
+            ));
+        }
+    }
+
+    if TimeZoneSetting::parse(&config.time.timezone).is_none() {
+        issues.push(ConfigIssue::error(
+            "time.timezone",
+            format!(
+                "invalid timezone {:?} (expected \"local\", \"utc\" or an offset such as \"+09:00\")",
+                config.time.timezone
+            ),
+        ));
+    }
+
+    let uses_openai = [&config.ai.reviewer, &config.ai.reviewee]
+        .into_iter()
+        .chain(config.ai.co_reviewer.as_ref())
+        .any(|agent| agent.eq_ignore_ascii_case("openai"));
+    if uses_openai && config.ai.openai.model.trim().is_empty() {
+        issues.push(ConfigIssue::error(
+            "ai.openai.model",
+            "must be set to use the \"openai\" agent",
+        ));
+    }
+    if config
+        .ai
+        .co_reviewer
+        .as_ref()
+        .is_some_and(|agent| agent.eq_ignore_ascii_case(&config.ai.reviewer))
+    {
+        issues.push(ConfigIssue::warning(
+            "ai.co_reviewer",
+            "is the same agent as ai.reviewer; their findings cannot be told apart",
+        ));
+    }
+
+    if let Err(conflicts) = config.keybindings.validate() {
+        issues.extend(
+            conflicts
+                .into_iter()
+                .map(|message| ConfigIssue::warning("keybindings", message)),
+        );
+    }
+    issues
+}
+
+/// `diff.theme` と `diff.light_theme` が `available` にあるか（大文字小文字は区別しない）
+///
+/// `file_errors` はファイルのパスで指定したテーマを読み込めなかったときの（指定, エラー）
+pub fn check_themes(
+    config: &Config,
+    available: &[&str],
+    file_errors: &[(String, String)],
+) -> Vec<ConfigIssue> {
+    [
+        ("diff.theme", &config.diff.theme),
+        ("diff.light_theme", &config.diff.light_theme),
+    ]
+    .into_iter()
+    .filter(|(_, name)| !available.iter().any(|t| t.eq_ignore_ascii_case(name)))
+    .map(|(key, name)| {
+        if let Some((_, error)) = file_errors.iter().find(|(theme, _)| theme == name) {
+            return ConfigIssue::warning(
+                key,
+                format!(
+                    "failed to load theme file {:?}, using the default theme ({})",
+                    name, error
+                ),
+            );
+        }
+        let hint = match closest(name, available.iter().copied()) {
+            Some(suggestion) => format!("did you mean `{}`?", suggestion),
+            None => format!("available: {}", available.join(", ")),
+        };
+        ConfigIssue::warning(
+            key,
+            format!(
+                "unknown theme {:?}, using the default theme ({})",
+                name, hint
+            ),
+        )
+    })
+    .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(toml_str: &str) -> (Config, Vec<ConfigIssue>) {
+        let mut value: toml::Value = toml::from_str(toml_str).unwrap();
+        let mut issues = check_value(&mut value);
+        let config: Config = value.try_into().unwrap();
+        issues.extend(check_config(&config));
+        (config, issues)
+    }
+
+    fn messages(issues: &[ConfigIssue]) -> Vec<String> {
+        issues.iter().map(ToString::to_string).collect()
+    }
+
+    #[test]
+    fn test_default_config_has_no_issues() {
+        let (_, issues) = check("");
+        assert!(issues.is_empty(), "{:?}", issues);
+
+        // 省略可能なキー・自由に書けるテーブル・キーバインドの書き方はどれも知っているキー
+        let (_, issues) = check(
+            r#"
+editor = "hx"
+
+[ai]
+prompt_dir = "~/prompts"
+
+[follow]
+path = "/tmp/or.fifo"
+
+[translate]
+language = "ja"
+command = "trans {lang}"
+
+[dependencies]
+advisory_db = "advisories"
+
+[env.vars]
+ANY_NAME = "1"
+
+[env.repos."owner/repo"]
+OTHER = "2"
+
+[keybindings]
+page_down = { key = "d", ctrl = true }
+jump_to_first = ["g", "g"]
+
+[[review_templates]]
+name = "default"
+body = "- [ ] tests"
+"#,
+        );
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_unknown_keys_are_warnings_with_suggestions() {
+        let (config, issues) = check(
+            r#"
+editr = "vim"
+
+[diff]
+them = "Dracula"
+tab_width = 2
+
+[keybindings]
+aprove = "A"
+
+[keybindings.views.diff_view]
+qit = "x"
+
+[keybindings.views.file_lst]
+quit = "x"
+
+[[review_templates]]
+nme = "x"
+
+[colors]
+accent = "red"
+"#,
+        );
+        assert!(issues.iter().all(|issue| !issue.is_error()));
+        assert_eq!(
+            messages(&issues),
+            vec![
+                "colors: unknown key",
+                "diff.them: unknown key (did you mean `theme`?)",
+                "editr: unknown key (did you mean `editor`?)",
+                "keybindings.aprove: unknown key (did you mean `approve`?)",
+                "keybindings.views.diff_view.qit: unknown key (did you mean `quit`?)",
+                "keybindings.views.file_lst: unknown key (did you mean `file_list`?)",
+                "review_templates[0].nme: unknown key (did you mean `name`?)",
+            ]
+        );
+        // 知らないキーがあっても残りの設定は使う
+        assert_eq!(config.diff.tab_width, 2);
+    }
+
+    #[test]
+    fn test_invalid_values_are_errors_and_fall_back_to_defaults() {
+        let (config, issues) = check(
+            r#"
+github = 1
+
+[diff]
+tab_width = "wide"
+bg_color = false
+
+[keybindings]
+approve = "Hyper-x"
+move_down = "Down"
+
+[ui]
+spinner = "brail"
+
+[time]
+timezone = "Mars/Base"
+
+[ai]
+max_iterations = 0
+
+[cache]
+ttl_hours = 0
+"#,
+        );
+        let keys: Vec<&str> = issues.iter().map(|issue| issue.key.as_str()).collect();
+        assert_eq!(
+            keys,
+            vec![
+                "diff.tab_width",
+                "github",
+                "keybindings.approve",
+                "ui.spinner",
+                "ai.max_iterations",
+                "cache.ttl_hours",
+                "time.timezone",
+            ]
+        );
+        assert!(issues.iter().all(ConfigIssue::is_error));
+        assert!(issues[0].message.contains("expected u8"), "{}", issues[0]);
+        assert!(issues[3].message.contains("brail"), "{}", issues[3]);
+        assert_eq!(
+            issues[4].to_string(),
+            "ai.max_iterations: must be between 1 and 100 (got 0)"
+        );
+
+        // 誤った値は既定値になり、同じセクションの正しい値は残る
+        assert_eq!(config.diff.tab_width, 4);
+        assert!(!config.diff.bg_color);
+        assert_eq!(config.keybindings.approve.display(), "a");
+        assert_eq!(config.keybindings.move_down.display(), "Down");
+    }
+
+    #[test]
+    fn test_negative_budget_is_an_error() {
+        let (_, issues) = check("[ai]\nbudget_usd = -1.5\noutput_cost_per_mtok = 15");
+        assert_eq!(
+            messages(&issues),
+            vec!["ai.budget_usd: must be zero or more (got -1.5)"]
+        );
+    }
+
+    #[test]
+    fn test_openai_agent_requires_model() {
+        let (_, issues) = check("[ai]\nreviewee = \"openai\"");
+        assert_eq!(
+            messages(&issues),
+            vec!["ai.openai.model: must be set to use the \"openai\" agent"]
+        );
+
+        let (_, issues) =
+            check("[ai]\nreviewer = \"openai\"\n[ai.openai]\nmodel = \"qwen2.5-coder\"");
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_co_reviewer_checks() {
+        let (_, issues) = check("[ai]\nco_reviewer = \"openai\"");
+        assert_eq!(
+            messages(&issues),
+            vec!["ai.openai.model: must be set to use the \"openai\" agent"]
+        );
+
+        let (_, issues) = check("[ai]\nco_reviewer = \"Claude\"");
+        assert_eq!(
+            messages(&issues),
+            vec!["ai.co_reviewer: is the same agent as ai.reviewer; their findings cannot be told apart"]
+        );
+
+        let (_, issues) = check("[ai]\nco_reviewer = \"codex\"");
+        assert!(issues.is_empty(), "{:?}", issues);
+    }
+
+    #[test]
+    fn test_section_with_wrong_type_is_an_error() {
+        let mut value: toml::Value = toml::from_str("diff = 1\n[ui]\nspinner = \"dots\"").unwrap();
+        let issues = check_value(&mut value);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].key, "diff");
+        assert!(issues[0].is_error());
+        let config: Config = value.try_into().unwrap();
+        assert_eq!(config.ui.spinner, crate::config::SpinnerStyle::Dots);
+    }
+
+    #[test]
+    fn test_binding_conflicts_are_warnings() {
+        let (_, issues) = check(
+            r#"
+[keybindings]
+comment = "s"
+"#,
+        );
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].key, "keybindings");
+        assert!(issues[0].message.contains("'comment'"), "{}", issues[0]);
+    }
+
+    #[test]
+    fn test_check_themes() {
+        let available = ["base16-ocean.dark", "base16-ocean.light", "Dracula"];
+        let mut config = Config::default();
+        config.diff.theme = "dracula".to_string();
+        assert!(check_themes(&config, &available, &[]).is_empty());
+
+        config.diff.theme = "Draculla".to_string();
+        config.diff.light_theme = "solarized".to_string();
+        assert_eq!(
+            messages(&check_themes(&config, &available, &[])),
+            vec![
+                "diff.theme: unknown theme \"Draculla\", using the default theme (did you mean `Dracula`?)",
+                "diff.light_theme: unknown theme \"solarized\", using the default theme \
+                 (available: base16-ocean.dark, base16-ocean.light, Dracula)",
+            ]
+        );
+
+        // 読み込めたテーマファイルは指定そのものがテーマ名になる
+        config.diff.theme = "~/themes/nord.tmTheme".to_string();
+        config.diff.light_theme = "light.toml".to_string();
+        let available = ["base16-ocean.dark", "~/themes/nord.tmTheme"];
+        let errors = [("light.toml".to_string(), "missing palette".to_string())];
+        assert_eq!(
+            messages(&check_themes(&config, &available, &errors)),
+            vec![
+                "diff.light_theme: failed to load theme file \"light.toml\", \
+                 using the default theme (missing palette)"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_effective_config_round_trips() {
+        // `or config check` が出力するマージ後の設定は、そのまま設定ファイルとして使える
+        let (config, _) = check(
+            r#"
+editor = "hx"
+
+[keybindings]
+page_down = { key = "d", ctrl = true }
+jump_to_first = ["g", "g"]
+
+[env.vars]
+FOO = "1"
+"#,
+        );
+        let printed = toml::to_string_pretty(&config).unwrap();
+        let (reloaded, issues) = check(&printed);
+        assert!(issues.is_empty(), "{:?}\n{}", issues, printed);
+        assert_eq!(reloaded.editor.as_deref(), Some("hx"));
+        assert_eq!(
+            reloaded.keybindings.page_down.display(),
+            config.keybindings.page_down.display()
+        );
+        assert_eq!(reloaded.env.vars["FOO"], "1");
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("theme", "them"), 1);
+        assert_eq!(edit_distance("aprove", "approve"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(closest("zzz", ["editor", "diff"].into_iter()), None);
+    }
+}
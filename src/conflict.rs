@@ -0,0 +1,311 @@
+//! 作業ツリーのコンフリクトマーカーの解析と解消
+//!
+//! Local Mode でマージ・リベース中にコンフリクトしたファイルを、コンフリクトごとに
+//! ours / base / theirs を並べて表示し、どちらを採用するかを選んで書き戻すためのデータを作る。
+//! `merge.conflictStyle = diff3`（`zdiff3`）の base 部分にも対応する。
+
+use anyhow::{bail, Context, Result};
+
+const OURS_MARKER: &str = "<<<<<<<";
+const BASE_MARKER: &str = "|||||||";
+const SEPARATOR: &str = "=======";
+const THEIRS_MARKER: &str = ">>>>>>>";
+
+/// コンフリクトの解消方法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    Ours,
+    Theirs,
+}
+
+/// 1 つのコンフリクト（`<<<<<<<` から `>>>>>>>` まで）
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConflictBlock {
+    /// `<<<<<<<` の行番号（1 始まり）
+    pub start_line: usize,
+    /// `<<<<<<< HEAD` の `HEAD` の部分
+    pub ours_label: String,
+    /// `>>>>>>> branch` の `branch` の部分
+    pub theirs_label: String,
+    pub ours: Vec<String>,
+    /// diff3 形式のときだけある
+    pub base: Option<Vec<String>>,
+    pub theirs: Vec<String>,
+    pub resolution: Option<Resolution>,
+}
+
+impl ConflictBlock {
+    /// 選んだ側の行（未解消なら None）
+    pub fn resolved_lines(&self) -> Option<&[String]> {
+        match self.resolution? {
+            Resolution::Ours => Some(&self.ours),
+            Resolution::Theirs => Some(&self.theirs),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Segment {
+    /// コンフリクトしていない行
+    Common(Vec<String>),
+    Conflict(ConflictBlock),
+}
+
+/// コンフリクトマーカーを含む 1 ファイル
+#[derive(Debug, Clone)]
+pub struct ConflictFile {
+    pub path: String,
+    pub segments: Vec<Segment>,
+    /// 書き戻すときに元の改行コードと末尾の改行を保つ
+    crlf: bool,
+    trailing_newline: bool,
+}
+
+impl ConflictFile {
+    /// コンフリクトマーカーを解析する。マーカーの対応が取れないときはエラー
+    pub fn parse(path: &str, text: &str) -> Result<Self> {
+        let crlf = text.contains("\r\n");
+        let mut segments = Vec::new();
+        let mut common = Vec::new();
+        let mut block: Option<(ConflictBlock, Section)> = None;
+
+        for (i, raw) in text.lines().enumerate() {
+            let line_no = i + 1;
+            let line = raw.strip_suffix('\r').unwrap_or(raw);
+            let Some((ref mut current, ref mut section)) = block else {
+                if let Some(label) = marker_label(line, OURS_MARKER) {
+                    if !common.is_empty() {
+                        segments.push(Segment::Common(std::mem::take(&mut common)));
+                    }
+                    block = Some((
+                        ConflictBlock {
+                            start_line: line_no,
+                            ours_label: label.to_string(),
+                            theirs_label: String::new(),
+                            ours: Vec::new(),
+                            base: None,
+                            theirs: Vec::new(),
+                            resolution: None,
+                        },
+                        Section::Ours,
+                    ));
+                } else {
+                    common.push(line.to_string());
+                }
+                continue;
+            };
+
+            if marker_label(line, OURS_MARKER).is_some() {
+                bail!("Nested conflict markers at line {}", line_no);
+            }
+            match section {
+                Section::Ours if marker_label(line, BASE_MARKER).is_some() => {
+                    current.base = Some(Vec::new());
+                    *section = Section::Base;
+                }
+                Section::Ours | Section::Base if line == SEPARATOR => *section = Section::Theirs,
+                Section::Theirs => {
+                    if let Some(label) = marker_label(line, THEIRS_MARKER) {
+                        current.theirs_label = label.to_string();
+                        if let Some((finished, _)) = block.take() {
+                            segments.push(Segment::Conflict(finished));
+                        }
+                    } else {
+                        current.theirs.push(line.to_string());
+                    }
+                }
+                Section::Ours => current.ours.push(line.to_string()),
+                Section::Base => {
+                    if let Some(ref mut base) = current.base {
+                        base.push(line.to_string());
+                    }
+                }
+            }
+        }
+
+        if let Some((unterminated, _)) = block {
+            bail!(
+                "Conflict starting at line {} is not terminated",
+                unterminated.start_line
+            );
+        }
+        if !common.is_empty() {
+            segments.push(Segment::Common(common));
+        }
+        Ok(Self {
+            path: path.to_string(),
+            segments,
+            crlf,
+            trailing_newline: text.ends_with('\n'),
+        })
+    }
+
+    pub fn conflicts(&self) -> impl Iterator<Item = &ConflictBlock> {
+        self.segments.iter().filter_map(|segment| match segment {
+            Segment::Conflict(block) => Some(block),
+            Segment::Common(_) => None,
+        })
+    }
+
+    pub fn conflict_mut(&mut self, index: usize) -> Option<&mut ConflictBlock> {
+        self.segments
+            .iter_mut()
+            .filter_map(|segment| match segment {
+                Segment::Conflict(block) => Some(block),
+                Segment::Common(_) => None,
+            })
+            .nth(index)
+    }
+
+    pub fn conflict_count(&self) -> usize {
+        self.conflicts().count()
+    }
+
+    pub fn resolved_count(&self) -> usize {
+        self.conflicts().filter(|b| b.resolution.is_some()).count()
+    }
+
+    /// 全コンフリクトを解消した内容（未解消のものがあれば None）
+    pub fn resolved_text(&self) -> Option<String> {
+        let mut lines: Vec<&str> = Vec::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Common(common) => lines.extend(common.iter().map(String::as_str)),
+                Segment::Conflict(block) => {
+                    lines.extend(block.resolved_lines()?.iter().map(String::as_str))
+                }
+            }
+        }
+        let newline = if self.crlf { "\r\n" } else { "\n" };
+        let mut text = lines.join(newline);
+        if self.trailing_newline && !lines.is_empty() {
+            text.push_str(newline);
+        }
+        Some(text)
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Section {
+    Ours,
+    Base,
+    Theirs,
+}
+
+/// マーカー行ならラベル（マーカーの後ろ）を返す
+fn marker_label<'a>(line: &'a str, marker: &str) -> Option<&'a str> {
+    let rest = line.strip_prefix(marker)?;
+    if rest.is_empty() {
+        Some("")
+    } else {
+        rest.strip_prefix(' ')
+    }
+}
+
+/// patch がコンフリクトマーカーを追加しているか（ファイル一覧の表示用）
+pub fn patch_has_conflict_markers(patch: &str) -> bool {
+    patch.contains("\n+<<<<<<< ") && patch.contains("\n+>>>>>>> ")
+}
+
+/// 作業ツリーのファイルを読み込んでコンフリクトを解析する
+pub async fn load(working_dir: Option<&str>, path: &str) -> Result<ConflictFile> {
+    let full_path = crate::compare::repo_root(working_dir).await?.join(path);
+    let bytes = std::fs::read(&full_path)
+        .with_context(|| format!("Failed to read {}", full_path.display()))?;
+    if bytes.len() > crate::file_content::MAX_FILE_BYTES {
+        bail!("File is too large to resolve");
+    }
+    let text = String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("File is not UTF-8 text"))?;
+    let file = ConflictFile::parse(path, &text)?;
+    if file.conflict_count() == 0 {
+        bail!("No conflict markers in {}", path);
+    }
+    Ok(file)
+}
+
+/// 解消した内容を作業ツリーに書き戻す
+pub async fn write_back(working_dir: Option<&str>, file: &ConflictFile) -> Result<()> {
+    let Some(text) = file.resolved_text() else {
+        bail!("Resolve every conflict before writing");
+    };
+    let full_path = crate::compare::repo_root(working_dir)
+        .await?
+        .join(&file.path);
+    std::fs::write(&full_path, text)
+        .with_context(|| format!("Failed to write {}", full_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MERGE: &str =
+        "fn main() {\n<<<<<<< HEAD\n    a();\n=======\n    b();\n    c();\n>>>>>>> feature\n}\n";
+
+    #[test]
+    fn test_parse_two_way_conflict() {
+        let file = ConflictFile::parse("src/main.rs", MERGE).unwrap();
+        assert_eq!(file.segments.len(), 3);
+        let block = file.conflicts().next().unwrap();
+        assert_eq!(block.start_line, 2);
+        assert_eq!(block.ours_label, "HEAD");
+        assert_eq!(block.theirs_label, "feature");
+        assert_eq!(block.ours, vec!["    a();"]);
+        assert_eq!(block.base, None);
+        assert_eq!(block.theirs, vec!["    b();", "    c();"]);
+    }
+
+    #[test]
+    fn test_parse_diff3_base() {
+        let text =
+            "<<<<<<< ours\nx\n||||||| merged common ancestors\nbase\n=======\ny\n>>>>>>> theirs\n";
+        let file = ConflictFile::parse("f", text).unwrap();
+        let block = file.conflicts().next().unwrap();
+        assert_eq!(block.base, Some(vec!["base".to_string()]));
+        assert_eq!(block.theirs, vec!["y"]);
+    }
+
+    #[test]
+    fn test_resolved_text_requires_every_conflict() {
+        let text = format!("{}{}", MERGE, MERGE);
+        let mut file = ConflictFile::parse("f", &text).unwrap();
+        assert_eq!(file.conflict_count(), 2);
+        file.conflict_mut(0).unwrap().resolution = Some(Resolution::Ours);
+        assert_eq!(file.resolved_count(), 1);
+        assert_eq!(file.resolved_text(), None);
+
+        file.conflict_mut(1).unwrap().resolution = Some(Resolution::Theirs);
+        assert_eq!(
+            file.resolved_text().unwrap(),
+            "fn main() {\n    a();\n}\nfn main() {\n    b();\n    c();\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_resolved_text_keeps_crlf_and_missing_trailing_newline() {
+        let text = "a\r\n<<<<<<< HEAD\r\nb\r\n=======\r\nc\r\n>>>>>>> x\r\nd";
+        let mut file = ConflictFile::parse("f", text).unwrap();
+        file.conflict_mut(0).unwrap().resolution = Some(Resolution::Theirs);
+        assert_eq!(file.resolved_text().unwrap(), "a\r\nc\r\nd");
+    }
+
+    #[test]
+    fn test_parse_rejects_broken_markers() {
+        assert!(ConflictFile::parse("f", "<<<<<<< HEAD\na\n=======\nb\n").is_err());
+        assert!(ConflictFile::parse("f", "<<<<<<< HEAD\n<<<<<<< HEAD\n").is_err());
+    }
+
+    #[test]
+    fn test_marker_needs_exact_prefix() {
+        // 8 文字以上の記号やマーカーに見えるだけの行は通常の行として扱う
+        let file = ConflictFile::parse("f", "<<<<<<<< not a marker\n======= x\n").unwrap();
+        assert_eq!(file.conflict_count(), 0);
+    }
+
+    #[test]
+    fn test_patch_has_conflict_markers() {
+        let patch = "@@ -1 +1,5 @@\n+<<<<<<< HEAD\n+a\n+=======\n+b\n+>>>>>>> main";
+        assert!(patch_has_conflict_markers(patch));
+        assert!(!patch_has_conflict_markers("@@ -1 +1 @@\n-a\n+b"));
+    }
+}
@@ -0,0 +1,309 @@
+//! GitHub API トークンの取得
+//!
+//! gh CLI を介さずに API を呼ぶバックエンド向けに、トークンの取得元を
+//! [`CredentialStore`] で抽象化している。`[github] credential` で選択する。
+//!
+//! - [`EnvStore`]: 環境変数 `GH_TOKEN` / `GITHUB_TOKEN`
+//! - [`MacKeychain`]: macOS キーチェーン（`security find-generic-password`）
+//! - [`SecretService`]: libsecret（`secret-tool lookup`）
+//! - [`WindowsCredentialManager`]: Windows 資格情報マネージャー（PowerShell 経由で `CredRead`）
+//!
+//! キーチェーン系はいずれも `[github] keychain_service` / `keychain_account` で
+//! 指定したサービス名・アカウント名の項目を読む。
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use tokio::process::Command;
+
+use crate::config::{CredentialSource, GithubConfig};
+
+/// トークンを読む環境変数（先に見つかったものを使う）
+pub const TOKEN_ENV_VARS: [&str; 2] = ["GH_TOKEN", "GITHUB_TOKEN"];
+
+/// トークンの取得元
+#[async_trait]
+pub trait CredentialStore: Send + Sync {
+    /// エラー表示用の名前
+    fn name(&self) -> &'static str;
+
+    /// トークンを取得する。見つからなければ `Ok(None)`
+    async fn token(&self) -> Result<Option<String>>;
+}
+
+/// 設定に応じた取得元
+pub fn store_from_config(config: &GithubConfig) -> Box<dyn CredentialStore> {
+    let service = config.keychain_service.clone();
+    let account = config.keychain_account.clone();
+    match config.credential {
+        CredentialSource::Env => Box::new(EnvStore),
+        CredentialSource::Keychain => {
+            if cfg!(target_os = "macos") {
+                Box::new(MacKeychain { service, account })
+            } else if cfg!(windows) {
+                Box::new(WindowsCredentialManager { service, account })
+            } else {
+                Box::new(SecretService { service, account })
+            }
+        }
+    }
+}
+
+/// 設定された取得元からトークンを読む。見つからなければ登録方法を添えてエラーにする
+pub async fn read_token(config: &GithubConfig) -> Result<String> {
+    let store = store_from_config(config);
+    match store.token().await? {
+        Some(token) => Ok(token),
+        None => bail!(
+            "No GitHub token found in {}. {}",
+            store.name(),
+            setup_hint(config)
+        ),
+    }
+}
+
+fn setup_hint(config: &GithubConfig) -> String {
+    let (service, account) = (&config.keychain_service, &config.keychain_account);
+    match config.credential {
+        CredentialSource::Env => format!("Set {}.", TOKEN_ENV_VARS.join(" or ")),
+        CredentialSource::Keychain if cfg!(target_os = "macos") => format!(
+            "Store it with: security add-generic-password -s {} -a {} -w",
+            service, account
+        ),
+        CredentialSource::Keychain if cfg!(windows) => format!(
+            "Store it with: cmdkey /generic:{} /user:{} /pass",
+            service, account
+        ),
+        CredentialSource::Keychain => format!(
+            "Store it with: secret-tool store --label=octorus service {} account {}",
+            service, account
+        ),
+    }
+}
+
+/// 環境変数からのトークン
+pub struct EnvStore;
+
+impl EnvStore {
+    fn lookup(get: impl Fn(&str) -> Option<String>) -> Option<String> {
+        TOKEN_ENV_VARS
+            .iter()
+            .filter_map(|name| get(name))
+            .map(|value| value.trim().to_string())
+            .find(|value| !value.is_empty())
+    }
+}
+
+#[async_trait]
+impl CredentialStore for EnvStore {
+    fn name(&self) -> &'static str {
+        "environment"
+    }
+
+    async fn token(&self) -> Result<Option<String>> {
+        Ok(Self::lookup(|name| std::env::var(name).ok()))
+    }
+}
+
+/// macOS キーチェーンの汎用パスワード項目
+pub struct MacKeychain {
+    service: String,
+    account: String,
+}
+
+impl MacKeychain {
+    fn args(&self) -> Vec<String> {
+        vec![
+            "find-generic-password".to_string(),
+            "-s".to_string(),
+            self.service.clone(),
+            "-a".to_string(),
+            self.account.clone(),
+            "-w".to_string(),
+        ]
+    }
+}
+
+#[async_trait]
+impl CredentialStore for MacKeychain {
+    fn name(&self) -> &'static str {
+        "macOS Keychain"
+    }
+
+    async fn token(&self) -> Result<Option<String>> {
+        run_lookup("security", &self.args()).await
+    }
+}
+
+/// libsecret（GNOME Keyring / KWallet など Secret Service 対応のストア）
+pub struct SecretService {
+    service: String,
+    account: String,
+}
+
+impl SecretService {
+    fn args(&self) -> Vec<String> {
+        vec![
+            "lookup".to_string(),
+            "service".to_string(),
+            self.service.clone(),
+            "account".to_string(),
+            self.account.clone(),
+        ]
+    }
+}
+
+#[async_trait]
+impl CredentialStore for SecretService {
+    fn name(&self) -> &'static str {
+        "Secret Service"
+    }
+
+    async fn token(&self) -> Result<Option<String>> {
+        run_lookup("secret-tool", &self.args()).await
+    }
+}
+
+/// Windows 資格情報マネージャーの汎用資格情報
+pub struct WindowsCredentialManager {
+    service: String,
+    account: String,
+}
+
+/// CredReadW で汎用資格情報を読み、パスワードを stdout に出す PowerShell スクリプト
+const WINDOWS_CRED_READ_SCRIPT: &str = r#"
+$sig = @'
+[DllImport("advapi32.dll", CharSet = CharSet.Unicode, SetLastError = true)]
+public static extern bool CredReadW(string target, int type, int flags, out IntPtr cred);
+[DllImport("advapi32.dll")]
+public static extern void CredFree(IntPtr cred);
+[StructLayout(LayoutKind.Sequential, CharSet = CharSet.Unicode)]
+public struct CREDENTIAL {
+  public int Flags; public int Type; public string TargetName; public string Comment;
+  public long LastWritten; public int CredentialBlobSize; public IntPtr CredentialBlob;
+  public int Persist; public int AttributeCount; public IntPtr Attributes;
+  public string TargetAlias; public string UserName;
+}
+'@
+$api = Add-Type -MemberDefinition $sig -Name CredApi -Namespace Octorus -PassThru
+$ptr = [IntPtr]::Zero
+if (-not $api[0]::CredReadW($env:OCTORUS_CRED_TARGET, 1, 0, [ref]$ptr)) { exit 1 }
+$cred = [Runtime.InteropServices.Marshal]::PtrToStructure($ptr, [type]'Octorus.CredApi+CREDENTIAL')
+if ($env:OCTORUS_CRED_USER -and $cred.UserName -ne $env:OCTORUS_CRED_USER) { $api[0]::CredFree($ptr); exit 1 }
+$token = [Runtime.InteropServices.Marshal]::PtrToStringUni($cred.CredentialBlob, $cred.CredentialBlobSize / 2)
+$api[0]::CredFree($ptr)
+[Console]::Out.Write($token)
+"#;
+
+#[async_trait]
+impl CredentialStore for WindowsCredentialManager {
+    fn name(&self) -> &'static str {
+        "Windows Credential Manager"
+    }
+
+    async fn token(&self) -> Result<Option<String>> {
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command"])
+            .arg(WINDOWS_CRED_READ_SCRIPT)
+            .env("OCTORUS_CRED_TARGET", &self.service)
+            .env("OCTORUS_CRED_USER", &self.account)
+            .output()
+            .await
+            .context("Failed to run powershell")?;
+        Ok(token_from_output(output.status.success(), &output.stdout))
+    }
+}
+
+/// 見つからない場合はどのツールも非 0 で終了するため、失敗は「未登録」として扱う
+async fn run_lookup(program: &str, args: &[String]) -> Result<Option<String>> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .await
+        .with_context(|| format!("Failed to run {}", program))?;
+    Ok(token_from_output(output.status.success(), &output.stdout))
+}
+
+fn token_from_output(success: bool, stdout: &[u8]) -> Option<String> {
+    if !success {
+        return None;
+    }
+    let token = String::from_utf8_lossy(stdout).trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_store_prefers_gh_token_and_skips_empty() {
+        let lookup = |vars: &[(&str, &str)]| {
+            let vars: Vec<(String, String)> = vars
+                .iter()
+                .map(|(k, v)| (k.to_string(), v.to_string()))
+                .collect();
+            EnvStore::lookup(|name| vars.iter().find(|(k, _)| k == name).map(|(_, v)| v.clone()))
+        };
+        assert_eq!(
+            lookup(&[("GH_TOKEN", "a"), ("GITHUB_TOKEN", "b")]),
+            Some("a".to_string())
+        );
+        assert_eq!(
+            lookup(&[("GH_TOKEN", " "), ("GITHUB_TOKEN", "b\n")]),
+            Some("b".to_string())
+        );
+        assert_eq!(lookup(&[]), None);
+    }
+
+    #[test]
+    fn test_keychain_lookup_args() {
+        let mac = MacKeychain {
+            service: "octorus".to_string(),
+            account: "github.com".to_string(),
+        };
+        assert_eq!(
+            mac.args(),
+            [
+                "find-generic-password",
+                "-s",
+                "octorus",
+                "-a",
+                "github.com",
+                "-w"
+            ]
+        );
+        let secret = SecretService {
+            service: "octorus".to_string(),
+            account: "github.com".to_string(),
+        };
+        assert_eq!(
+            secret.args(),
+            ["lookup", "service", "octorus", "account", "github.com"]
+        );
+    }
+
+    #[test]
+    fn test_token_from_output() {
+        assert_eq!(
+            token_from_output(true, b"ghp_abc\n"),
+            Some("ghp_abc".to_string())
+        );
+        assert_eq!(token_from_output(true, b"\n"), None);
+        assert_eq!(token_from_output(false, b"ghp_abc"), None);
+    }
+
+    #[test]
+    fn test_store_from_config() {
+        let mut config = GithubConfig::default();
+        assert_eq!(store_from_config(&config).name(), "environment");
+        config.credential = CredentialSource::Keychain;
+        let expected = if cfg!(target_os = "macos") {
+            "macOS Keychain"
+        } else if cfg!(windows) {
+            "Windows Credential Manager"
+        } else {
+            "Secret Service"
+        };
+        assert_eq!(store_from_config(&config).name(), expected);
+    }
+}
@@ -6,6 +6,7 @@
 //! - New file line numbers for suggestion positioning
 //! - Unified diff parsing for splitting multi-file diffs
 
+use std::borrow::Cow;
 use std::collections::HashMap;
 use tracing::warn;
 
@@ -55,92 +56,517 @@ fn parse_hunk_header(line: &str) -> Option<u32> {
     num_str.parse().ok()
 }
 
-/// Get information about a specific line in a patch
+/// Line counts declared by a hunk header
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HunkRange {
+    /// Old-side line counts, one per parent (combined diffs have several)
+    pub(crate) old_counts: Vec<u32>,
+    pub(crate) new_start: u32,
+    pub(crate) new_count: u32,
+}
+
+/// Parse a complete hunk header, including combined diff headers (`@@@ -a,b -c,d +e,f @@@`).
 ///
-/// # Arguments
-/// * `patch` - The full patch content
-/// * `line_index` - Zero-based index of the line to analyze
+/// Returns `None` unless the header has exactly one `-` range per parent, one `+` range
+/// and a closing marker of the same length. Omitted counts default to 1.
+pub(crate) fn parse_hunk_range(line: &str) -> Option<HunkRange> {
+    let marker_len = line.bytes().take_while(|&b| b == b'@').count();
+    if marker_len < 2 {
+        return None;
+    }
+    let parents = marker_len - 1;
+    let marker = &line[..marker_len];
+
+    let mut old_counts = Vec::with_capacity(parents);
+    let mut new_range = None;
+    let mut closed = false;
+    for token in line[marker_len..].split_whitespace() {
+        if token == marker {
+            closed = true;
+            break;
+        }
+        if let Some(range) = token.strip_prefix('-') {
+            old_counts.push(parse_range(range)?.1);
+        } else if let Some(range) = token.strip_prefix('+') {
+            if new_range.is_some() {
+                return None;
+            }
+            new_range = Some(parse_range(range)?);
+        } else {
+            return None;
+        }
+    }
+
+    let (new_start, new_count) = new_range?;
+    (closed && old_counts.len() == parents).then_some(HunkRange {
+        old_counts,
+        new_start,
+        new_count,
+    })
+}
+
+/// Parse `start[,count]` from a hunk header range
+fn parse_range(range: &str) -> Option<(u32, u32)> {
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// A problem found while walking a patch.
 ///
-/// # Returns
-/// * `Some(DiffLineInfo)` - Information about the line if valid
-/// * `None` - If the line index is out of bounds
-pub fn get_line_info(patch: &str, line_index: usize) -> Option<DiffLineInfo> {
-    let lines: Vec<&str> = patch.lines().collect();
+/// Warnings never stop parsing: the offending line is still classified on a
+/// best-effort basis, so the diff can be displayed and commented on as before.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseWarning {
+    /// Zero-based index of the line the warning refers to
+    pub line_index: usize,
+    pub kind: ParseWarningKind,
+}
 
-    if line_index >= lines.len() {
-        return None;
+/// Kind of [`ParseWarning`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseWarningKind {
+    /// A `@@` line that could not be parsed as a hunk header
+    InvalidHunkHeader,
+    /// The number of lines in a hunk does not match its header.
+    /// `line_index` points at the hunk header.
+    HunkLengthMismatch {
+        expected_old: u32,
+        actual_old: u32,
+        expected_new: u32,
+        actual_new: u32,
+    },
+    /// A line that is neither a diff line nor part of a file header
+    UnexpectedLine,
+}
+
+impl std::fmt::Display for ParseWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let line = self.line_index + 1;
+        match &self.kind {
+            ParseWarningKind::InvalidHunkHeader => write!(f, "line {}: invalid hunk header", line),
+            ParseWarningKind::HunkLengthMismatch {
+                expected_old,
+                actual_old,
+                expected_new,
+                actual_new,
+            } => write!(
+                f,
+                "line {}: hunk declares -{} +{} lines but has -{} +{}",
+                line, expected_old, expected_new, actual_old, actual_new
+            ),
+            ParseWarningKind::UnexpectedLine => write!(f, "line {}: unexpected line", line),
+        }
     }
+}
 
-    // Track the current new file line number
-    let mut new_line_number: Option<u32> = None;
-    // Track the position within the patch (1-based, skipping meta lines)
-    let mut position_counter: Option<u32> = None;
+/// Where the walker is within the patch
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum WalkState {
+    /// Before any `diff ` or `@@` line, or between hunks
+    Outside,
+    /// Extended header lines after `diff ` (mode changes, renames, binary payloads)
+    FileHeader,
+    /// Inside a hunk body
+    Hunk,
+}
 
-    for (i, line) in lines.iter().enumerate() {
-        let (line_type, content) = classify_line(line);
+/// The hunk currently being walked
+struct HunkProgress {
+    header_index: usize,
+    /// Number of parent columns in front of each line (1 unless combined)
+    parents: usize,
+    /// Declared counts, `None` when the header could not be parsed
+    range: Option<HunkRange>,
+    /// Lines seen so far on each old side
+    old_seen: Vec<u32>,
+    new_seen: u32,
+}
 
-        // Update position counter and line number BEFORE checking target
-        match line_type {
-            LineType::Meta => {
-                // Meta lines don't count toward position
-            }
-            LineType::Header => {
-                new_line_number = parse_hunk_header(line);
-                // First @@ initializes to 0 (not counted); subsequent @@ lines increment
-                position_counter = Some(position_counter.map_or(0, |p| p + 1));
-            }
-            LineType::Added | LineType::Context => {
-                position_counter = position_counter.map(|p| p + 1);
-            }
-            LineType::Removed => {
-                position_counter = position_counter.map(|p| p + 1);
+impl HunkProgress {
+    /// Whether the header still expects more lines.
+    /// Unparseable headers never do, so `---`/`+++` keep their old meaning there.
+    fn expects_more(&self) -> bool {
+        self.range.as_ref().is_some_and(|range| {
+            self.new_seen < range.new_count
+                || range
+                    .old_counts
+                    .iter()
+                    .zip(&self.old_seen)
+                    .any(|(expected, seen)| seen < expected)
+        })
+    }
+
+    /// Count one body line given its parent columns
+    fn count(&mut self, columns: &str) {
+        let in_new = !columns.contains('-');
+        if in_new {
+            self.new_seen += 1;
+        }
+        for (seen, column) in self.old_seen.iter_mut().zip(columns.bytes()) {
+            // A parent has the line if it was removed from it, or if the line
+            // survives into the result without being added relative to it
+            if column == b'-' || (in_new && column == b' ') {
+                *seen += 1;
             }
         }
+    }
 
-        if i == line_index {
-            // For the target line, return the info
-            let current_new_line = match line_type {
-                LineType::Removed | LineType::Header | LineType::Meta => None,
-                _ => new_line_number,
-            };
+    fn mismatch(&self) -> Option<ParseWarning> {
+        let range = self.range.as_ref()?;
+        // Combined diffs report the first parent, which is what reviewers compare against
+        let expected_old = range.old_counts.first().copied().unwrap_or(0);
+        let actual_old = self.old_seen.first().copied().unwrap_or(0);
+        let mismatched = self.new_seen != range.new_count || self.old_seen != range.old_counts;
+        mismatched.then_some(ParseWarning {
+            line_index: self.header_index,
+            kind: ParseWarningKind::HunkLengthMismatch {
+                expected_old,
+                actual_old,
+                expected_new: range.new_count,
+                actual_new: self.new_seen,
+            },
+        })
+    }
+}
 
-            let current_position = match line_type {
-                // Meta lines and the first @@ header (position 0) have no valid position
-                LineType::Meta => None,
-                LineType::Header if position_counter == Some(0) => None,
-                _ => position_counter,
-            };
+/// One line produced by [`PatchWalker`]
+#[derive(Debug, Clone, Copy)]
+struct WalkedLine<'a> {
+    line_type: LineType,
+    /// The line without its diff prefix columns
+    content: &'a str,
+    /// The diff prefix columns, one per parent (empty for headers and meta lines)
+    origin: &'a str,
+    /// For Added/Context lines, the line's own number in the new file.
+    /// For Removed lines, the next new-file line; for headers, the hunk start.
+    new_line_number: Option<u32>,
+    /// Position counter after this line (see [`DiffLineInfo::diff_position`])
+    position: Option<u32>,
+    /// Whether this line occupies a review comment position
+    positioned: bool,
+}
+
+/// Stateful line classifier shared by all patch lookups.
+///
+/// Unlike [`classify_line`], it knows where file headers and hunks begin and end,
+/// so extended headers, binary payloads, combined diff columns and body lines that
+/// happen to start with `---`/`+++` are classified correctly.
+struct PatchWalker<'a> {
+    lines: std::iter::Enumerate<std::str::Lines<'a>>,
+    state: WalkState,
+    hunk: Option<HunkProgress>,
+    new_line_number: Option<u32>,
+    position: Option<u32>,
+    warnings: Vec<ParseWarning>,
+}
+
+impl<'a> PatchWalker<'a> {
+    fn new(patch: &'a str) -> Self {
+        Self {
+            lines: patch.lines().enumerate(),
+            state: WalkState::Outside,
+            hunk: None,
+            new_line_number: None,
+            position: None,
+            warnings: Vec::new(),
+        }
+    }
+
+    /// Walk the whole patch and return the warnings found
+    fn into_warnings(mut self) -> Vec<ParseWarning> {
+        for _ in self.by_ref() {}
+        self.warnings
+    }
+
+    fn close_hunk(&mut self) {
+        if let Some(hunk) = self.hunk.take() {
+            self.warnings.extend(hunk.mismatch());
+        }
+    }
 
-            return Some(DiffLineInfo {
-                line_content: content.to_string(),
-                line_type,
-                new_line_number: current_new_line,
-                diff_position: current_position,
+    fn start_hunk(&mut self, index: usize, line: &'a str) -> WalkedLine<'a> {
+        self.close_hunk();
+        let range = parse_hunk_range(line);
+        if range.is_none() {
+            self.warnings.push(ParseWarning {
+                line_index: index,
+                kind: ParseWarningKind::InvalidHunkHeader,
             });
         }
+        let parents = range.as_ref().map_or(1, |r| r.old_counts.len()).max(1);
+        self.new_line_number = range
+            .as_ref()
+            .map(|r| r.new_start)
+            .or_else(|| parse_hunk_header(line));
+        // First @@ initializes to 0 (not counted); subsequent @@ lines increment
+        self.position = Some(self.position.map_or(0, |p| p + 1));
+        self.hunk = Some(HunkProgress {
+            header_index: index,
+            parents,
+            old_seen: vec![0; parents],
+            new_seen: 0,
+            range,
+        });
+        self.state = WalkState::Hunk;
+        WalkedLine {
+            line_type: LineType::Header,
+            content: line,
+            origin: "",
+            new_line_number: self.new_line_number,
+            position: self.position,
+            positioned: self.position != Some(0),
+        }
+    }
+
+    /// Classify a line inside a hunk body
+    fn hunk_line(&mut self, index: usize, line: &'a str) -> WalkedLine<'a> {
+        let Some(hunk) = self.hunk.as_mut() else {
+            return self.loose_line(index, line);
+        };
+
+        if line.starts_with('\\') {
+            // "\ No newline at end of file" occupies a position but no file line
+            self.position = self.position.map(|p| p + 1);
+            return self.walked(LineType::Meta, line, false);
+        }
+
+        let expects_more = hunk.expects_more();
+        if !expects_more && (line.starts_with("---") || line.starts_with("+++")) {
+            // The hunk is complete, so this is the start of another file's header
+            self.close_hunk();
+            self.state = WalkState::Outside;
+            return self.walked(LineType::Meta, line, false);
+        }
 
-        // Update new_line_number for next iteration
+        let parents = hunk.parents;
+        let columns_valid = line.len() >= parents
+            && line.as_bytes()[..parents]
+                .iter()
+                .all(|b| matches!(b, b' ' | b'+' | b'-'));
+        let (line_type, content, columns) = if columns_valid {
+            let (columns, content) = line.split_at(parents);
+            let columns = Cow::Borrowed(columns);
+            let line_type = if columns.contains('-') {
+                LineType::Removed
+            } else if columns.contains('+') {
+                LineType::Added
+            } else {
+                LineType::Context
+            };
+            (line_type, content, columns)
+        } else {
+            // Empty lines are context lines whose trailing space was stripped
+            if !line.is_empty() {
+                self.warnings.push(ParseWarning {
+                    line_index: index,
+                    kind: ParseWarningKind::UnexpectedLine,
+                });
+            }
+            let (line_type, content) = classify_line(line);
+            let column = match line_type {
+                LineType::Added => "+",
+                LineType::Removed => "-",
+                _ => " ",
+            };
+            (line_type, content, Cow::Owned(column.repeat(parents)))
+        };
+
+        if let Some(hunk) = self.hunk.as_mut() {
+            hunk.count(&columns);
+        }
+        self.position = self.position.map(|p| p + 1);
+        let origin = &line[..line.len() - content.len()];
+        WalkedLine {
+            origin,
+            ..self.walked(line_type, content, true)
+        }
+    }
+
+    /// Classify a line outside any file header or hunk
+    fn loose_line(&mut self, index: usize, line: &'a str) -> WalkedLine<'a> {
+        let (line_type, content) = classify_line(line);
         match line_type {
-            LineType::Added | LineType::Context => {
-                if let Some(n) = new_line_number {
-                    new_line_number = Some(n + 1);
+            LineType::Meta => self.walked(line_type, content, false),
+            _ => {
+                if self.position.is_some() {
+                    // Text after a complete hunk that does not start a new one
+                    self.warnings.push(ParseWarning {
+                        line_index: index,
+                        kind: ParseWarningKind::UnexpectedLine,
+                    });
+                }
+                self.position = self.position.map(|p| p + 1);
+                let origin = &line[..line.len() - content.len()];
+                WalkedLine {
+                    origin,
+                    ..self.walked(line_type, content, true)
                 }
             }
-            _ => {}
         }
     }
 
-    None
+    fn walked(
+        &mut self,
+        line_type: LineType,
+        content: &'a str,
+        positioned: bool,
+    ) -> WalkedLine<'a> {
+        let new_line_number = self.new_line_number;
+        if matches!(line_type, LineType::Added | LineType::Context) {
+            self.new_line_number = self.new_line_number.map(|n| n + 1);
+        }
+        WalkedLine {
+            line_type,
+            content,
+            origin: "",
+            new_line_number,
+            position: self.position,
+            positioned,
+        }
+    }
+}
+
+impl<'a> Iterator for PatchWalker<'a> {
+    type Item = WalkedLine<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Some((index, line)) = self.lines.next() else {
+            self.close_hunk();
+            return None;
+        };
+
+        if line.starts_with("@@") {
+            return Some(self.start_hunk(index, line));
+        }
+        if line.starts_with("diff ") {
+            self.close_hunk();
+            self.state = WalkState::FileHeader;
+            return Some(self.walked(LineType::Meta, line, false));
+        }
+
+        Some(match self.state {
+            // Everything up to the first hunk is header, including binary payloads
+            WalkState::FileHeader => self.walked(LineType::Meta, line, false),
+            WalkState::Hunk => self.hunk_line(index, line),
+            WalkState::Outside => self.loose_line(index, line),
+        })
+    }
+}
+
+/// Classify every line of a patch, taking file headers and hunk boundaries into account.
+///
+/// Prefer this over calling [`classify_line`] per line when the whole patch is available:
+/// it recognizes binary payloads, combined diffs and hunk lines starting with `---`/`+++`.
+pub fn classify_patch_lines(patch: &str) -> impl Iterator<Item = (LineType, &str)> {
+    PatchWalker::new(patch).map(|line| (line.line_type, line.content))
+}
+
+/// A patch line classified by [`patch_lines`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PatchLine<'a> {
+    pub line_type: LineType,
+    /// The line without its diff prefix columns
+    pub content: &'a str,
+    /// The diff prefix columns: one character per parent, so a regular diff line
+    /// has a single `+`/`-`/space and a combined diff line (`diff --cc`) has one
+    /// column for each parent of the merge. Empty for headers and meta lines.
+    pub origin: &'a str,
+}
+
+impl PatchLine<'_> {
+    /// Per-parent origins of a combined diff line, `None` for regular diff lines
+    pub fn parent_origins(&self) -> Option<impl Iterator<Item = ParentOrigin> + '_> {
+        (self.origin.len() > 1).then(|| self.origin.bytes().map(ParentOrigin::from_column))
+    }
+}
+
+/// How a combined diff line relates to one parent of the merge
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParentOrigin {
+    /// The line is not in this parent (`+` column)
+    Added,
+    /// The line is only in this parent (`-` column)
+    Removed,
+    /// The line is unchanged relative to this parent (space column)
+    Unchanged,
+}
+
+impl ParentOrigin {
+    fn from_column(column: u8) -> Self {
+        match column {
+            b'+' => ParentOrigin::Added,
+            b'-' => ParentOrigin::Removed,
+            _ => ParentOrigin::Unchanged,
+        }
+    }
+}
+
+/// Like [`classify_patch_lines`], but also returns the prefix columns of each line
+/// so combined diffs can be rendered with per-parent origins.
+pub fn patch_lines(patch: &str) -> impl Iterator<Item = PatchLine<'_>> {
+    PatchWalker::new(patch).map(|line| PatchLine {
+        line_type: line.line_type,
+        content: line.content,
+        origin: line.origin,
+    })
+}
+
+/// Collect the problems found while parsing a patch (malformed hunk headers,
+/// hunks whose length does not match the header, stray lines).
+pub fn patch_warnings(patch: &str) -> Vec<ParseWarning> {
+    PatchWalker::new(patch).into_warnings()
+}
+
+/// Get information about a specific line in a patch
+///
+/// # Arguments
+/// * `patch` - The full patch content
+/// * `line_index` - Zero-based index of the line to analyze
+///
+/// # Returns
+/// * `Some(DiffLineInfo)` - Information about the line if valid
+/// * `None` - If the line index is out of bounds
+pub fn get_line_info(patch: &str, line_index: usize) -> Option<DiffLineInfo> {
+    line_infos(patch).nth(line_index)
 }
 
-/// Classify a line and extract its content without the prefix
+/// Like [`get_line_info`], but for every line of the patch in order.
+pub fn line_infos(patch: &str) -> impl Iterator<Item = DiffLineInfo> + '_ {
+    PatchWalker::new(patch).map(|line| {
+        let new_line_number = match line.line_type {
+            LineType::Added | LineType::Context => line.new_line_number,
+            LineType::Removed | LineType::Header | LineType::Meta => None,
+        };
+        // Meta lines and the first @@ header have no valid position
+        let diff_position = if line.positioned && line.line_type != LineType::Meta {
+            line.position
+        } else {
+            None
+        };
+
+        DiffLineInfo {
+            line_content: line.content.to_string(),
+            line_type: line.line_type,
+            new_line_number,
+            diff_position,
+        }
+    })
+}
+
+/// Classify a line and extract its content without the prefix.
+///
+/// This looks at the line alone; use [`classify_patch_lines`] to classify a whole patch.
 pub fn classify_line(line: &str) -> (LineType, &str) {
     if line.starts_with("@@") {
         (LineType::Header, line)
     } else if line.starts_with("+++")
         || line.starts_with("---")
-        || line.starts_with("diff ")
-        || line.starts_with("index ")
+        || line.starts_with('\\')
+        || EXTENDED_HEADER_PREFIXES
+            .iter()
+            .any(|prefix| line.starts_with(prefix))
     {
         (LineType::Meta, line)
     } else if let Some(content) = line.strip_prefix('+') {
@@ -155,6 +581,24 @@ pub fn classify_line(line: &str) -> (LineType, &str) {
     }
 }
 
+/// Git extended header lines that can appear between `diff --git` and the first hunk
+const EXTENDED_HEADER_PREFIXES: &[&str] = &[
+    "diff ",
+    "index ",
+    "old mode ",
+    "new mode ",
+    "new file mode ",
+    "deleted file mode ",
+    "similarity index ",
+    "dissimilarity index ",
+    "rename from ",
+    "rename to ",
+    "copy from ",
+    "copy to ",
+    "Binary files ",
+    "GIT binary patch",
+];
+
 /// Check if a line at the given index can have a suggestion
 /// Only Added and Context lines can have suggestions
 #[allow(dead_code)]
@@ -169,19 +613,18 @@ pub fn can_suggest_at_line(patch: &str, line_index: usize) -> bool {
 /// Returns `true` when every line in the range is `Added` or `Context` and no `Header` line
 /// appears between `start` and `end` (i.e. the range does not cross a hunk boundary).
 pub fn validate_multiline_range(patch: &str, start: usize, end: usize) -> bool {
-    let lines: Vec<&str> = patch.lines().collect();
-    for idx in start..=end {
-        let Some(line) = lines.get(idx) else {
-            return false;
-        };
-        let (line_type, _) = classify_line(line);
-        match line_type {
-            LineType::Added | LineType::Context => {}
+    if start > end {
+        return true;
+    }
+    let mut checked = 0;
+    for line in PatchWalker::new(patch).skip(start).take(end - start + 1) {
+        match line.line_type {
+            LineType::Added | LineType::Context => checked += 1,
             // Removed, Header, or Meta lines inside the range → invalid
             _ => return false,
         }
     }
-    true
+    checked == end - start + 1
 }
 
 /// Convert a file line number (new_line_number) to a patch position.
@@ -195,32 +638,61 @@ pub fn validate_multiline_range(patch: &str, start: usize, end: usize) -> bool {
 /// Works with both GitHub API patches (starting with `@@`) and local diff patches
 /// (starting with `diff --git` meta lines).
 pub fn line_number_to_position(patch: &str, target_line: u32) -> Option<u32> {
-    let mut new_line_number: Option<u32> = None;
-    let mut position_counter: Option<u32> = None;
+    PatchWalker::new(patch)
+        .find(|line| {
+            matches!(line.line_type, LineType::Added | LineType::Context)
+                && line.new_line_number == Some(target_line)
+        })
+        .and_then(|line| line.position)
+}
 
-    for line in patch.lines() {
-        let (line_type, _) = classify_line(line);
+/// Find the zero-based patch line index of the new-file line `target_line`.
+///
+/// The index is the one used by `get_line_info` and `patch_lines`, so a line
+/// number from outside the diff view can be looked up the same way.
+pub fn new_line_index(patch: &str, target_line: u32) -> Option<usize> {
+    PatchWalker::new(patch).position(|line| {
+        matches!(line.line_type, LineType::Added | LineType::Context)
+            && line.new_line_number == Some(target_line)
+    })
+}
 
-        match line_type {
-            LineType::Meta => continue,
-            LineType::Header => {
-                new_line_number = parse_hunk_header(line);
-                // First @@ initializes to 0 (not counted); subsequent @@ lines increment
-                position_counter = Some(position_counter.map_or(0, |p| p + 1));
-            }
-            LineType::Added | LineType::Context => {
-                position_counter = position_counter.map(|p| p + 1);
-                if new_line_number == Some(target_line) {
-                    return position_counter;
-                }
-                new_line_number = new_line_number.map(|n| n + 1);
-            }
-            LineType::Removed => {
-                position_counter = position_counter.map(|p| p + 1);
-            }
-        }
+/// Look up the content of the new-file line `target_line` in a patch.
+///
+/// Only Added and Context lines carry new-file line numbers, so removed lines
+/// and lines outside the hunks return `None`.
+pub fn new_line_content(patch: &str, target_line: u32) -> Option<&str> {
+    PatchWalker::new(patch)
+        .find(|line| {
+            matches!(line.line_type, LineType::Added | LineType::Context)
+                && line.new_line_number == Some(target_line)
+        })
+        .map(|line| line.content)
+}
+
+/// Resolve the new-file line number closest to the diff line at `line_index`.
+///
+/// Unlike `get_line_info`, removed lines map to the new-file line just before the
+/// deletion and hunk headers map to the hunk start, so every line inside a hunk
+/// has a position in the new file. Meta lines and out-of-range indices return `None`.
+pub fn nearest_new_line_number(patch: &str, line_index: usize) -> Option<u32> {
+    let line = PatchWalker::new(patch).nth(line_index)?;
+    match line.line_type {
+        LineType::Header | LineType::Added | LineType::Context => line.new_line_number,
+        LineType::Removed => line.new_line_number.map(|n| n.saturating_sub(1).max(1)),
+        LineType::Meta => None,
     }
-    None
+}
+
+/// The new-file line number of every patch line that is unchanged (a context line).
+///
+/// Yields one item per patch line, so it lines up with the rows of the diff view;
+/// added, removed, header and meta lines yield `None`.
+pub fn context_line_numbers(patch: &str) -> impl Iterator<Item = Option<u32>> + '_ {
+    PatchWalker::new(patch).map(|line| match line.line_type {
+        LineType::Context => line.new_line_number,
+        _ => None,
+    })
 }
 
 /// Parse a unified diff output into a map of filename -> patch content
@@ -246,7 +718,7 @@ pub fn parse_unified_diff(unified_diff: &str) -> HashMap<String, String> {
     let mut pending_minus_filename: Option<String> = None;
 
     for (i, line) in lines.iter().enumerate() {
-        if line.starts_with("diff --git ") {
+        if line.starts_with("diff --git ") || combined_diff_filename(line).is_some() {
             // Save previous file's patch if any
             if let (Some(filename), Some(start)) = (&current_filename, current_patch_start) {
                 let patch = lines[start..i].join("\n");
@@ -256,7 +728,7 @@ pub fn parse_unified_diff(unified_diff: &str) -> HashMap<String, String> {
             }
 
             // Extract filename for new file
-            current_filename = extract_filename(line);
+            current_filename = combined_diff_filename(line).or_else(|| extract_filename(line));
             current_patch_start = Some(i);
             pending_minus_filename = None;
         } else if current_filename.is_none() && current_patch_start.is_some() {
@@ -289,6 +761,17 @@ pub fn parse_unified_diff(unified_diff: &str) -> HashMap<String, String> {
     result
 }
 
+/// Extract the filename from a combined diff header (`diff --cc path` or `diff --combined path`).
+///
+/// Combined diffs are produced for merge commits (`git show --cc`) and name the path
+/// once, without `a/`/`b/` prefixes.
+fn combined_diff_filename(line: &str) -> Option<String> {
+    let path = line
+        .strip_prefix("diff --cc ")
+        .or_else(|| line.strip_prefix("diff --combined "))?;
+    (!path.is_empty()).then(|| path.to_string())
+}
+
 /// Strip the single-char diff prefix (a/, b/, w/, etc.) from a --- or +++ path.
 fn strip_diff_prefix(path: &str) -> Option<String> {
     if path.len() >= 2 && path.as_bytes()[1] == b'/' {
@@ -963,12 +1446,52 @@ index 1234567..abcdefg 100644
         assert_eq!(line_number_to_position(patch, 3), Some(3));
     }
 
+    #[test]
+    fn test_new_line_index() {
+        let patch = "@@ -1,3 +1,3 @@\n-old1\n+new1\n ctx\n@@ -10,2 +10,2 @@\n-old2\n+new2";
+        assert_eq!(new_line_index(patch, 1), Some(2));
+        assert_eq!(new_line_index(patch, 2), Some(3));
+        assert_eq!(new_line_index(patch, 10), Some(6));
+        assert_eq!(new_line_index(patch, 5), None);
+    }
+
     #[test]
     fn test_line_number_to_position_nonexistent_line() {
         assert_eq!(line_number_to_position(SAMPLE_PATCH, 999), None);
         assert_eq!(line_number_to_position(SAMPLE_PATCH, 0), None);
     }
 
+    // --- nearest_new_line_number tests ---
+
+    #[test]
+    fn test_nearest_new_line_number_maps_removed_lines() {
+        let patch = "diff --git a/a.rs b/a.rs\n--- a/a.rs\n+++ b/a.rs\n@@ -10,3 +10,2 @@\n keep\n-gone\n tail";
+        assert_eq!(nearest_new_line_number(patch, 0), None);
+        assert_eq!(nearest_new_line_number(patch, 3), Some(10));
+        assert_eq!(nearest_new_line_number(patch, 4), Some(10));
+        assert_eq!(nearest_new_line_number(patch, 5), Some(10));
+        assert_eq!(nearest_new_line_number(patch, 6), Some(11));
+        assert_eq!(nearest_new_line_number(patch, 7), None);
+    }
+
+    #[test]
+    fn test_context_line_numbers_only_for_unchanged_lines() {
+        let patch = "@@ -10,3 +10,3 @@\n keep\n-old\n+new\n tail";
+        let numbers: Vec<_> = context_line_numbers(patch).collect();
+        assert_eq!(numbers, vec![None, Some(10), None, None, Some(12)]);
+    }
+
+    // --- new_line_content tests ---
+
+    #[test]
+    fn test_new_line_content_skips_removed_lines() {
+        let patch = "diff --git a/foo.rs b/foo.rs\n--- a/foo.rs\n+++ b/foo.rs\n@@ -1,3 +1,3 @@\n fn main() {\n-    old();\n+    new();\n }";
+        assert_eq!(new_line_content(patch, 1), Some("fn main() {"));
+        assert_eq!(new_line_content(patch, 2), Some("    new();"));
+        assert_eq!(new_line_content(patch, 3), Some("}"));
+        assert_eq!(new_line_content(patch, 4), None);
+    }
+
     // --- validate_multiline_range tests ---
 
     #[test]
@@ -1094,4 +1617,321 @@ index 1234567..abcdefg 100644
         // Valid range within the hunk (indices 5..=7)
         assert!(validate_multiline_range(patch, 5, 7));
     }
+
+    // ============================================
+    // Malformed / unusual patch tests
+    // ============================================
+
+    fn line_types(patch: &str) -> Vec<LineType> {
+        classify_patch_lines(patch).map(|(t, _)| t).collect()
+    }
+
+    #[test]
+    fn test_mode_only_change_is_all_meta() {
+        let patch = "diff --git a/run.sh b/run.sh\nold mode 100644\nnew mode 100755";
+        assert_eq!(line_types(patch), vec![LineType::Meta; 3]);
+        assert!(patch_warnings(patch).is_empty());
+        assert_eq!(line_number_to_position(patch, 1), None);
+        assert_eq!(nearest_new_line_number(patch, 2), None);
+    }
+
+    #[test]
+    fn test_copy_detection_headers_are_meta() {
+        let patch = "diff --git a/a.rs b/b.rs\nsimilarity index 90%\ncopy from a.rs\ncopy to b.rs\n--- a/a.rs\n+++ b/b.rs\n@@ -1 +1 @@\n-old\n+new";
+        let types = line_types(patch);
+        assert!(types[..6].iter().all(|t| *t == LineType::Meta));
+        let info = get_line_info(patch, 8).unwrap();
+        assert_eq!(info.line_type, LineType::Added);
+        assert_eq!(info.new_line_number, Some(1));
+        assert_eq!(info.diff_position, Some(2));
+        assert!(patch_warnings(patch).is_empty());
+    }
+
+    #[test]
+    fn test_binary_patch_payload_is_meta() {
+        let patch = "diff --git a/logo.png b/logo.png\nindex 1234567..89abcde 100644\nGIT binary patch\nliteral 12\nTcmZ?wbhEHbRA2yqU--\n+-lol\n\nliteral 0\nHcmV?d00001";
+        assert_eq!(line_types(patch), vec![LineType::Meta; 9]);
+        assert!(patch_warnings(patch).is_empty());
+        // Base85 payload lines starting with + or - are not diff lines
+        assert_eq!(get_line_info(patch, 5).unwrap().new_line_number, None);
+        assert!(!validate_multiline_range(patch, 4, 5));
+    }
+
+    #[test]
+    fn test_hunk_lines_starting_with_dashes_and_pluses() {
+        // A removed "-- comment" line and an added "++i;" line look like file headers
+        let patch = "@@ -1,2 +1,2 @@\n--- comment\n+++i;\n ctx";
+        let info = get_line_info(patch, 1).unwrap();
+        assert_eq!(info.line_type, LineType::Removed);
+        assert_eq!(info.line_content, "-- comment");
+        assert_eq!(info.diff_position, Some(1));
+        let info = get_line_info(patch, 2).unwrap();
+        assert_eq!(info.line_type, LineType::Added);
+        assert_eq!(info.line_content, "++i;");
+        assert_eq!(info.new_line_number, Some(1));
+        assert_eq!(info.diff_position, Some(2));
+        assert_eq!(line_number_to_position(patch, 2), Some(3));
+        assert!(patch_warnings(patch).is_empty());
+    }
+
+    #[test]
+    fn test_file_headers_after_complete_hunk_are_meta() {
+        let patch = "--- a/x\n+++ b/x\n@@ -1 +1 @@\n-a\n+b\n--- a/y\n+++ b/y\n@@ -5 +5 @@\n-c\n+d";
+        let types = line_types(patch);
+        assert_eq!(types[5], LineType::Meta);
+        assert_eq!(types[6], LineType::Meta);
+        assert_eq!(get_line_info(patch, 9).unwrap().new_line_number, Some(5));
+        assert!(patch_warnings(patch).is_empty());
+    }
+
+    #[test]
+    fn test_combined_diff() {
+        let patch = "diff --cc f.rs\nindex 1111111,2222222..3333333\n--- a/f.rs\n+++ b/f.rs\n@@@ -1,2 -1,2 +1,3 @@@\n  same\n+ ours\n +theirs\n- gone";
+        let types = line_types(patch);
+        assert_eq!(
+            types[4..],
+            [
+                LineType::Header,
+                LineType::Context,
+                LineType::Added,
+                LineType::Added,
+                LineType::Removed,
+            ]
+        );
+        let info = get_line_info(patch, 7).unwrap();
+        assert_eq!(info.line_content, "theirs");
+        assert_eq!(info.new_line_number, Some(3));
+        assert_eq!(new_line_content(patch, 2), Some("ours"));
+        // "- gone" exists only in the first parent, which makes its old count 3
+        assert_eq!(
+            patch_warnings(patch),
+            vec![ParseWarning {
+                line_index: 4,
+                kind: ParseWarningKind::HunkLengthMismatch {
+                    expected_old: 2,
+                    actual_old: 3,
+                    expected_new: 3,
+                    actual_new: 3,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn test_combined_diff_origins() {
+        let patch = "@@@ -1,2 -1,2 +1,2 @@@\n  same\n+ ours\n- gone";
+        let lines: Vec<PatchLine> = patch_lines(patch).collect();
+        assert_eq!(lines[0].origin, "");
+        assert!(lines[0].parent_origins().is_none());
+        assert_eq!(lines[2].origin, "+ ");
+        assert_eq!(lines[2].content, "ours");
+        assert_eq!(
+            lines[2].parent_origins().unwrap().collect::<Vec<_>>(),
+            vec![ParentOrigin::Added, ParentOrigin::Unchanged]
+        );
+        assert_eq!(
+            lines[3].parent_origins().unwrap().collect::<Vec<_>>(),
+            vec![ParentOrigin::Removed, ParentOrigin::Unchanged]
+        );
+
+        // Regular diff lines have a single origin column
+        let line = patch_lines("@@ -1 +1 @@\n+x").nth(1).unwrap();
+        assert_eq!(line.origin, "+");
+        assert!(line.parent_origins().is_none());
+    }
+
+    #[test]
+    fn test_parse_combined_diff_files() {
+        let diff = "diff --cc src/a.rs\nindex 1111111,2222222..3333333\n--- a/src/a.rs\n+++ b/src/a.rs\n@@@ -1,1 -1,1 +1,2 @@@\n  a\n++b\ndiff --combined src/b file.rs\nindex 4444444,5555555..6666666\n--- a/src/b file.rs\n+++ b/src/b file.rs\n@@@ -1,1 -1,1 +1,1 @@@\n  c";
+        let result = parse_unified_diff(diff);
+        assert_eq!(result.len(), 2);
+        assert!(result["src/a.rs"].starts_with("diff --cc src/a.rs"));
+        assert!(result["src/a.rs"].ends_with("++b"));
+        assert!(result["src/b file.rs"].starts_with("diff --combined src/b file.rs"));
+        assert!(patch_warnings(&result["src/a.rs"]).is_empty());
+    }
+
+    #[test]
+    fn test_no_newline_marker_does_not_advance_line_number() {
+        let patch =
+            "@@ -1 +1 @@\n-a\n\\ No newline at end of file\n+b\n\\ No newline at end of file";
+        let marker = get_line_info(patch, 2).unwrap();
+        assert_eq!(marker.line_type, LineType::Meta);
+        assert_eq!(marker.diff_position, None);
+        let info = get_line_info(patch, 3).unwrap();
+        assert_eq!(info.new_line_number, Some(1));
+        // The marker still occupies a position in the patch
+        assert_eq!(info.diff_position, Some(3));
+        assert_eq!(line_number_to_position(patch, 2), None);
+        assert!(patch_warnings(patch).is_empty());
+    }
+
+    #[test]
+    fn test_patch_warnings() {
+        let patch = "@@ -1,2 +1,2 @@\n a\n@@ bogus @@\n x\nstray\n+y";
+        let warnings = patch_warnings(patch);
+        assert_eq!(
+            warnings,
+            vec![
+                ParseWarning {
+                    line_index: 0,
+                    kind: ParseWarningKind::HunkLengthMismatch {
+                        expected_old: 2,
+                        actual_old: 1,
+                        expected_new: 2,
+                        actual_new: 1,
+                    },
+                },
+                ParseWarning {
+                    line_index: 2,
+                    kind: ParseWarningKind::InvalidHunkHeader,
+                },
+                ParseWarning {
+                    line_index: 4,
+                    kind: ParseWarningKind::UnexpectedLine,
+                },
+            ]
+        );
+        assert_eq!(
+            warnings[0].to_string(),
+            "line 1: hunk declares -2 +2 lines but has -1 +1"
+        );
+        assert_eq!(warnings[1].to_string(), "line 3: invalid hunk header");
+        // The bogus header still starts a hunk, falling back to the old lookup
+        assert_eq!(get_line_info(patch, 5).unwrap().line_type, LineType::Added);
+    }
+
+    #[test]
+    fn test_parse_hunk_range() {
+        assert_eq!(
+            parse_hunk_range("@@ -1,4 +1,5 @@ fn main() {"),
+            Some(HunkRange {
+                old_counts: vec![4],
+                new_start: 1,
+                new_count: 5,
+            })
+        );
+        assert_eq!(
+            parse_hunk_range("@@ -3 +0,0 @@"),
+            Some(HunkRange {
+                old_counts: vec![1],
+                new_start: 0,
+                new_count: 0,
+            })
+        );
+        assert_eq!(
+            parse_hunk_range("@@@ -1,2 -3 +1,3 @@@").map(|r| r.old_counts),
+            Some(vec![2, 1])
+        );
+        assert_eq!(parse_hunk_range("@@ -1 +42"), None);
+        assert_eq!(parse_hunk_range("@@@ -1 +1 @@@"), None);
+        assert_eq!(parse_hunk_range("@@ -x +1 @@"), None);
+    }
+
+    // ============================================
+    // Property tests
+    // ============================================
+
+    /// A hunk body line: 0 = context, 1 = added, 2 = removed
+    fn render_hunk(start: u32, body: &[(u8, String)]) -> String {
+        let old = body.iter().filter(|(k, _)| *k != 1).count();
+        let new = body.iter().filter(|(k, _)| *k != 2).count();
+        let mut patch = format!("@@ -{},{} +{},{} @@", start, old, start, new);
+        for (kind, text) in body {
+            let prefix = [' ', '+', '-'][*kind as usize];
+            patch.push('\n');
+            patch.push(prefix);
+            patch.push_str(text);
+        }
+        patch
+    }
+
+    proptest::proptest! {
+        #[test]
+        fn prop_lookups_never_panic(patch in "(?s).{0,300}", index in 0usize..40, target in 0u32..40) {
+            let _ = get_line_info(&patch, index);
+            let _ = nearest_new_line_number(&patch, index);
+            let _ = line_number_to_position(&patch, target);
+            let _ = new_line_content(&patch, target);
+            let _ = validate_multiline_range(&patch, index, index + 3);
+            let _ = patch_warnings(&patch);
+            let _ = parse_unified_diff(&patch);
+        }
+
+        #[test]
+        fn prop_lookups_never_panic_on_diff_like_lines(
+            lines in proptest::collection::vec(
+                "(@@@? -?[0-9,]{0,4} \\+?[0-9,]{0,4} @@@?|[-+ \\\\]{0,3}[a-z-]{0,5}|diff --git a/x b/x|GIT binary patch)",
+                0..20,
+            ),
+            index in 0usize..20,
+        ) {
+            let patch = lines.join("\n");
+            let _ = get_line_info(&patch, index);
+            let _ = nearest_new_line_number(&patch, index);
+            let _ = line_number_to_position(&patch, index as u32);
+            let _ = patch_warnings(&patch);
+        }
+
+        #[test]
+        fn prop_well_formed_hunk_round_trips(
+            start in 1u32..1000,
+            // Body text may itself start with diff markers such as "--" or "++"
+            body in proptest::collection::vec((0u8..3, "[-+ a-z@]{0,6}"), 1..30),
+        ) {
+            let patch = render_hunk(start, &body);
+            proptest::prop_assert!(patch_warnings(&patch).is_empty());
+
+            let mut expected_line = start;
+            for (i, (kind, text)) in body.iter().enumerate() {
+                let info = get_line_info(&patch, i + 1).unwrap();
+                proptest::prop_assert_eq!(&info.line_content, text);
+                proptest::prop_assert_eq!(info.diff_position, Some(i as u32 + 1));
+                if *kind == 2 {
+                    proptest::prop_assert_eq!(info.line_type, LineType::Removed);
+                    proptest::prop_assert_eq!(info.new_line_number, None);
+                } else {
+                    proptest::prop_assert_eq!(info.new_line_number, Some(expected_line));
+                    proptest::prop_assert_eq!(
+                        line_number_to_position(&patch, expected_line),
+                        Some(i as u32 + 1)
+                    );
+                    proptest::prop_assert_eq!(new_line_content(&patch, expected_line), Some(text.as_str()));
+                    expected_line += 1;
+                }
+            }
+        }
+
+        #[test]
+        fn prop_unified_diff_splits_files_exactly(
+            files in proptest::collection::btree_map(
+                "[a-z]{1,8}\\.rs",
+                proptest::collection::vec((0u8..3, "[-+ a-z]{0,6}"), 1..10),
+                1..5,
+            ),
+        ) {
+            let patches: Vec<(String, String)> = files
+                .iter()
+                .map(|(name, body)| {
+                    let patch = format!(
+                        "diff --git a/{name} b/{name}\nindex 1111111..2222222 100644\n--- a/{name}\n+++ b/{name}\n{}",
+                        render_hunk(1, body)
+                    );
+                    (name.clone(), patch)
+                })
+                .collect();
+            let unified = patches
+                .iter()
+                .map(|(_, patch)| patch.as_str())
+                .collect::<Vec<_>>()
+                .join("\n");
+
+            let parsed = parse_unified_diff(&unified);
+            proptest::prop_assert_eq!(parsed.len(), patches.len());
+            for (name, patch) in &patches {
+                proptest::prop_assert_eq!(parsed.get(name), Some(patch));
+            }
+            proptest::prop_assert!(patch_warnings(&unified).is_empty());
+        }
+    }
 }
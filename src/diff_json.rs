@@ -0,0 +1,160 @@
+//! PR の diff を JSON で書き出す（`or diff --json`）
+//!
+//! [`diff::parse_unified_diff`] で分けたファイルごとの patch に、行ごとの種類・
+//! new file の行番号・レビューコメントの position を添える。
+//! エディタのプラグインなど外部のツールが octorus と同じ diff の解釈を使えるようにする
+
+use anyhow::Result;
+use serde::Serialize;
+
+use crate::diff::{self, LineType};
+use crate::github;
+
+/// PR の diff 全体
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffExport {
+    pub repo: String,
+    pub pr_number: u32,
+    /// コメントの `commit_id` に使う head コミット
+    pub head_sha: String,
+    /// ファイル名順
+    pub files: Vec<DiffFile>,
+}
+
+/// 1 ファイル分の patch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffFile {
+    pub filename: String,
+    pub additions: usize,
+    pub deletions: usize,
+    pub patch: String,
+    pub lines: Vec<DiffLine>,
+}
+
+/// patch の 1 行
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DiffLine {
+    /// `added` / `removed` / `context` / `header` / `meta`
+    #[serde(rename = "type")]
+    pub line_type: &'static str,
+    /// diff の接頭辞を除いた内容
+    pub content: String,
+    /// new file の行番号（追加行と変更のない行のみ）
+    pub new_line: Option<u32>,
+    /// レビューコメントの position（GitHub API の `position`）
+    pub position: Option<u32>,
+}
+
+fn line_type_name(line_type: LineType) -> &'static str {
+    match line_type {
+        LineType::Added => "added",
+        LineType::Removed => "removed",
+        LineType::Context => "context",
+        LineType::Header => "header",
+        LineType::Meta => "meta",
+    }
+}
+
+/// unified diff をファイルごとに分け、行の情報を添える
+pub fn files_from_diff(unified_diff: &str) -> Vec<DiffFile> {
+    let mut files: Vec<DiffFile> = diff::parse_unified_diff(unified_diff)
+        .into_iter()
+        .map(|(filename, patch)| {
+            let lines: Vec<DiffLine> = diff::line_infos(&patch)
+                .map(|info| DiffLine {
+                    line_type: line_type_name(info.line_type),
+                    content: info.line_content,
+                    new_line: info.new_line_number,
+                    position: info.diff_position,
+                })
+                .collect();
+            let count = |line_type| lines.iter().filter(|l| l.line_type == line_type).count();
+            DiffFile {
+                filename,
+                additions: count("added"),
+                deletions: count("removed"),
+                lines,
+                patch,
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| a.filename.cmp(&b.filename));
+    files
+}
+
+/// PR と diff を取得して変換
+pub async fn fetch_pr_export(repo: &str, pr_number: u32) -> Result<DiffExport> {
+    let (pr, unified_diff) = tokio::try_join!(
+        github::fetch_pr(repo, pr_number),
+        github::fetch_pr_diff(repo, pr_number)
+    )?;
+    Ok(DiffExport {
+        repo: repo.to_string(),
+        pr_number,
+        head_sha: pr.head.sha,
+        files: files_from_diff(&unified_diff),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/main.rs b/src/main.rs
+index 123..456 100644
+--- a/src/main.rs
++++ b/src/main.rs
+@@ -1,2 +1,2 @@
+ fn main() {
+-    old();
++    new();
+diff --git a/README.md b/README.md
+new file mode 100644
+--- /dev/null
++++ b/README.md
+@@ -0,0 +1 @@
++# Title
+";
+
+    #[test]
+    fn test_files_from_diff() {
+        let files = files_from_diff(DIFF);
+        let names: Vec<&str> = files.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(names, ["README.md", "src/main.rs"]);
+
+        let main = &files[1];
+        assert_eq!((main.additions, main.deletions), (1, 1));
+        assert!(main.patch.starts_with("diff --git a/src/main.rs"));
+        let body: Vec<(&str, Option<u32>, Option<u32>)> = main
+            .lines
+            .iter()
+            .filter(|l| l.line_type != "meta")
+            .map(|l| (l.line_type, l.new_line, l.position))
+            .collect();
+        assert_eq!(
+            body,
+            [
+                ("header", None, None),
+                ("context", Some(1), Some(1)),
+                ("removed", None, Some(2)),
+                ("added", Some(2), Some(3)),
+            ]
+        );
+        assert_eq!(main.lines.last().unwrap().content, "    new();");
+    }
+
+    #[test]
+    fn test_diff_line_json() {
+        let line = DiffLine {
+            line_type: "added",
+            content: "# Title".to_string(),
+            new_line: Some(1),
+            position: Some(1),
+        };
+        assert_eq!(
+            serde_json::to_string(&line).unwrap(),
+            r##"{"type":"added","content":"# Title","new_line":1,"position":1}"##
+        );
+    }
+}
@@ -0,0 +1,249 @@
+//! diff 内の全文検索（`/`）と全ファイルの grep（`Space f`）
+//!
+//! クエリに大文字が含まれるときだけ大文字小文字を区別する（smart case）。
+//! 検索対象は patch の各行で、`+` / `-` / ` ` の先頭記号は含めない。
+
+use crate::diff::{patch_lines, LineType};
+
+/// 確定した検索クエリ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery {
+    text: String,
+    case_sensitive: bool,
+}
+
+/// 一致した行（ファイルのインデックスと patch 内の行インデックス）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct SearchHit {
+    pub file_index: usize,
+    pub line: usize,
+}
+
+/// grep の結果の 1 行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GrepMatch {
+    pub hit: SearchHit,
+    pub line_type: LineType,
+    /// ファイルの行番号（削除行は変更前、それ以外は変更後の行番号）
+    pub line_number: Option<u32>,
+    /// 行頭の空白を除いた本文
+    pub snippet: String,
+    /// `snippet` 内の一致箇所（バイト範囲）
+    pub ranges: Vec<(usize, usize)>,
+}
+
+impl SearchQuery {
+    /// 空のクエリなら None
+    pub fn new(text: &str) -> Option<Self> {
+        if text.is_empty() {
+            return None;
+        }
+        Some(Self {
+            text: text.to_string(),
+            case_sensitive: text.chars().any(char::is_uppercase),
+        })
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    fn fold(&self, c: char) -> char {
+        if self.case_sensitive {
+            c
+        } else {
+            c.to_lowercase().next().unwrap_or(c)
+        }
+    }
+
+    /// `haystack` 内の一致箇所（バイト範囲、重ならない）
+    pub fn ranges(&self, haystack: &str) -> Vec<(usize, usize)> {
+        let needle: Vec<char> = self.text.chars().map(|c| self.fold(c)).collect();
+        let chars: Vec<(usize, char)> = haystack
+            .char_indices()
+            .map(|(i, c)| (i, self.fold(c)))
+            .collect();
+        let mut ranges = Vec::new();
+        let mut i = 0;
+        while i + needle.len() <= chars.len() {
+            if chars[i..i + needle.len()]
+                .iter()
+                .zip(&needle)
+                .all(|((_, c), n)| c == n)
+            {
+                let start = chars[i].0;
+                let end = chars
+                    .get(i + needle.len())
+                    .map_or(haystack.len(), |(pos, _)| *pos);
+                ranges.push((start, end));
+                i += needle.len();
+            } else {
+                i += 1;
+            }
+        }
+        ranges
+    }
+
+    pub fn is_match(&self, haystack: &str) -> bool {
+        !self.ranges(haystack).is_empty()
+    }
+
+    /// patch の中で一致する行のインデックス
+    pub fn matching_lines(&self, patch: &str) -> Vec<usize> {
+        patch
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| self.is_match(line_content(line)))
+            .map(|(i, _)| i)
+            .collect()
+    }
+}
+
+/// patch のコード行（追加・削除・コンテキスト）から一致する行を探す
+pub fn grep_patch(query: &SearchQuery, file_index: usize, patch: &str) -> Vec<GrepMatch> {
+    let mut matches = Vec::new();
+    let (mut old_line, mut new_line) = (None, None);
+    for (line, patch_line) in patch_lines(patch).enumerate() {
+        let line_number = match patch_line.line_type {
+            LineType::Header => {
+                (old_line, new_line) = hunk_starts(patch_line.content).unzip();
+                continue;
+            }
+            LineType::Meta => continue,
+            LineType::Removed => advance(&mut old_line),
+            LineType::Added => advance(&mut new_line),
+            LineType::Context => {
+                advance(&mut old_line);
+                advance(&mut new_line)
+            }
+        };
+        let snippet = patch_line.content.trim_start();
+        let ranges = query.ranges(snippet);
+        if ranges.is_empty() {
+            continue;
+        }
+        matches.push(GrepMatch {
+            hit: SearchHit { file_index, line },
+            line_type: patch_line.line_type,
+            line_number,
+            snippet: snippet.to_string(),
+            ranges,
+        });
+    }
+    matches
+}
+
+/// 現在の行番号を返して次の行に進める
+fn advance(counter: &mut Option<u32>) -> Option<u32> {
+    let current = *counter;
+    if let Some(n) = counter.as_mut() {
+        *n += 1;
+    }
+    current
+}
+
+/// hunk ヘッダー（`@@ -a,b +c,d @@`）の変更前・変更後の開始行
+fn hunk_starts(header: &str) -> Option<(u32, u32)> {
+    let mut ranges = header.split(' ').skip(1).take(2);
+    let start = |range: Option<&str>, sign: char| -> Option<u32> {
+        range?.strip_prefix(sign)?.split(',').next()?.parse().ok()
+    };
+    let old = start(ranges.next(), '-')?;
+    let new = start(ranges.next(), '+')?;
+    Some((old, new))
+}
+
+/// diff の行から先頭の記号を除いた本文。hunk ヘッダーはそのまま
+fn line_content(line: &str) -> &str {
+    match line.as_bytes().first() {
+        Some(b'+' | b'-' | b' ') => &line[1..],
+        _ => line,
+    }
+}
+
+/// `current` の次（`forward` が false なら前）の一致。端まで行ったら反対側に戻る。
+/// 戻ったかどうかも返す
+pub fn step(hits: &[SearchHit], current: SearchHit, forward: bool) -> Option<(usize, bool)> {
+    if hits.is_empty() {
+        return None;
+    }
+    if forward {
+        match hits.iter().position(|hit| *hit > current) {
+            Some(i) => Some((i, false)),
+            None => Some((0, true)),
+        }
+    } else {
+        match hits.iter().rposition(|hit| *hit < current) {
+            Some(i) => Some((i, false)),
+            None => Some((hits.len() - 1, true)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_smart_case() {
+        let query = SearchQuery::new("foo").unwrap();
+        assert_eq!(query.ranges("Foo foo FOO"), vec![(0, 3), (4, 7), (8, 11)]);
+        let query = SearchQuery::new("Foo").unwrap();
+        assert_eq!(query.ranges("Foo foo FOO"), vec![(0, 3)]);
+        assert!(SearchQuery::new("").is_none());
+    }
+
+    #[test]
+    fn test_ranges_are_byte_offsets() {
+        let query = SearchQuery::new("é").unwrap();
+        assert_eq!(query.ranges("aÉé"), vec![(1, 3), (3, 5)]);
+        let query = SearchQuery::new("aa").unwrap();
+        assert_eq!(query.ranges("aaaa"), vec![(0, 2), (2, 4)]);
+    }
+
+    #[test]
+    fn test_matching_lines_ignore_diff_prefix() {
+        let patch = "@@ -1,2 +1,2 @@\n-let a = 1;\n+let a = 2;\n fn main() {}";
+        let query = SearchQuery::new("let").unwrap();
+        assert_eq!(query.matching_lines(patch), vec![1, 2]);
+        let query = SearchQuery::new("+").unwrap();
+        assert_eq!(query.matching_lines(patch), vec![0]);
+    }
+
+    #[test]
+    fn test_grep_patch_reports_line_numbers() {
+        let patch = "@@ -10,3 +10,3 @@ fn main() {\n     let a = 1;\n-    let b = 2;\n+    let b = 3;\n@@ -40 +40,2 @@\n+let c;\n x";
+        let query = SearchQuery::new("let").unwrap();
+        let matches = grep_patch(&query, 4, patch);
+        let summary: Vec<_> = matches
+            .iter()
+            .map(|m| (m.hit.line, m.line_type, m.line_number, m.snippet.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, LineType::Context, Some(10), "let a = 1;"),
+                (2, LineType::Removed, Some(11), "let b = 2;"),
+                (3, LineType::Added, Some(11), "let b = 3;"),
+                (5, LineType::Added, Some(40), "let c;"),
+            ]
+        );
+        assert_eq!(matches[0].hit.file_index, 4);
+        assert_eq!(matches[0].ranges, vec![(0, 3)]);
+        // hunk ヘッダーの関数名には一致させない
+        let query = SearchQuery::new("main").unwrap();
+        assert!(grep_patch(&query, 0, patch).is_empty());
+    }
+
+    #[test]
+    fn test_step_wraps_around() {
+        let hit = |file_index, line| SearchHit { file_index, line };
+        let hits = [hit(0, 3), hit(0, 8), hit(2, 1)];
+        assert_eq!(step(&hits, hit(0, 3), true), Some((1, false)));
+        assert_eq!(step(&hits, hit(1, 0), true), Some((2, false)));
+        assert_eq!(step(&hits, hit(2, 1), true), Some((0, true)));
+        assert_eq!(step(&hits, hit(0, 5), false), Some((0, false)));
+        assert_eq!(step(&hits, hit(0, 3), false), Some((2, true)));
+        assert_eq!(step(&[], hit(0, 0), true), None);
+    }
+}
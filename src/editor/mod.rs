@@ -5,6 +5,8 @@ use std::fs;
 use std::process::Command;
 use tempfile::NamedTempFile;
 
+use crate::review_template::MatchedTemplates;
+
 /// エディタのテンプレート設定
 struct EditorTemplate<'a> {
     header: Cow<'a, str>,
@@ -147,16 +149,77 @@ pub fn open_comment_editor(
     )
 }
 
-/// Open external editor for review submission
-pub fn open_review_editor(editor: Option<&str>) -> Result<Option<String>> {
+/// Open external editor for review submission.
+/// Matching review templates are pre-filled as the body.
+pub fn open_review_editor(
+    editor: Option<&str>,
+    template: Option<&MatchedTemplates<'_>>,
+) -> Result<Option<String>> {
+    let Some(template) = template else {
+        return open_editor_internal(
+            editor,
+            EditorTemplate {
+                header: Cow::Borrowed(
+                    "<!-- Enter your review comment -->\n\
+                     <!-- Save and close to submit -->",
+                ),
+                initial_content: None,
+            },
+        );
+    };
+    let names = if template.names.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", template.names.join(", "))
+    };
     open_editor_internal(
         editor,
         EditorTemplate {
-            header: Cow::Borrowed(
+            header: Cow::Owned(format!(
                 "<!-- Enter your review comment -->\n\
-                 <!-- Save and close to submit -->",
+                 <!-- Pre-filled from review templates{} -->\n\
+                 <!-- Save and close to submit, delete all content to cancel -->",
+                names
+            )),
+            initial_content: Some(Cow::Borrowed(&template.body)),
+        },
+    )
+}
+
+/// Open external editor to edit one of your own PR conversation comments.
+/// The current body is pre-filled.
+pub fn open_comment_edit_editor(editor: Option<&str>, body: &str) -> Result<Option<String>> {
+    open_editor_internal(
+        editor,
+        EditorTemplate {
+            header: Cow::Borrowed(
+                "<!-- octorus: Edit your comment -->\n\
+                 <!-- Lines starting with <!-- are removed -->\n\
+                 <!-- Save and close to update, delete all content to cancel -->",
             ),
-            initial_content: None,
+            initial_content: Some(Cow::Borrowed(body)),
+        },
+    )
+}
+
+/// Open external editor to write the title and description of a new pull request.
+/// The first line is the title and the rest is the description.
+pub fn open_pr_create_editor(
+    editor: Option<&str>,
+    head: &str,
+    base: &str,
+    draft: &str,
+) -> Result<Option<String>> {
+    open_editor_internal(
+        editor,
+        EditorTemplate {
+            header: Cow::Owned(format!(
+                "<!-- octorus: Create a pull request from {} into {} -->\n\
+                 <!-- The first line is the title, the rest is the description -->\n\
+                 <!-- Save and close to create, delete all content to cancel -->",
+                head, base
+            )),
+            initial_content: Some(Cow::Borrowed(draft)),
         },
     )
 }
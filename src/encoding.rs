@@ -0,0 +1,297 @@
+//! ファイル・diff の文字コード判定と変換
+//!
+//! Git や GitHub から受け取るバイト列は UTF-8 とは限らない。UTF-8 として不正なバイトは
+//! 捨てずに私用領域の文字（U+10FF80〜U+10FFFF）に 1 バイトずつ退避するため、
+//! [`encode_lossless`] で元のバイト列に戻せる。
+//!
+//! 表示時は [`display_patch`] が patch ごとに文字コードを推定し、Latin-1 と判定できれば
+//! 退避した文字を Latin-1 の文字に置き換える。判定できないバイトは退避した文字のまま残り、
+//! diff ビューで `\xNN` として表示される。
+
+use std::borrow::Cow;
+
+/// 退避したバイトの文字コードの先頭（U+10FF80 が 0x80 に対応する）
+const ESCAPE_BASE: u32 = 0x10FF00;
+
+const BOM: char = '\u{FEFF}';
+
+/// 推定した文字コード
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    /// BOM 付き UTF-8
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    Latin1,
+    /// どの文字コードとしても解釈できない
+    Unknown,
+}
+
+impl TextEncoding {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Utf8 => "UTF-8",
+            Self::Utf8Bom => "UTF-8 with BOM",
+            Self::Utf16Le => "UTF-16LE",
+            Self::Utf16Be => "UTF-16BE",
+            Self::Latin1 => "Latin-1",
+            Self::Unknown => "unknown encoding",
+        }
+    }
+
+    pub fn is_utf8(self) -> bool {
+        matches!(self, Self::Utf8 | Self::Utf8Bom)
+    }
+}
+
+/// ファイル全体のバイト列から文字コードを推定する
+pub fn detect(bytes: &[u8]) -> TextEncoding {
+    if bytes.starts_with(b"\xEF\xBB\xBF") {
+        return TextEncoding::Utf8Bom;
+    }
+    if bytes.starts_with(b"\xFF\xFE") {
+        return TextEncoding::Utf16Le;
+    }
+    if bytes.starts_with(b"\xFE\xFF") {
+        return TextEncoding::Utf16Be;
+    }
+    if let Some(encoding) = detect_utf16_without_bom(bytes) {
+        return encoding;
+    }
+    if std::str::from_utf8(bytes).is_ok() {
+        TextEncoding::Utf8
+    } else if looks_like_latin1(bytes) {
+        TextEncoding::Latin1
+    } else {
+        TextEncoding::Unknown
+    }
+}
+
+/// BOM のない UTF-16。ASCII 主体のテキストなら上位バイトの 0 が偶数・奇数の片側に偏る
+fn detect_utf16_without_bom(bytes: &[u8]) -> Option<TextEncoding> {
+    if bytes.len() < 4 || !bytes.len().is_multiple_of(2) {
+        return None;
+    }
+    let pairs = bytes.len() / 2;
+    let zeros_at = |parity: usize| {
+        bytes
+            .iter()
+            .skip(parity)
+            .step_by(2)
+            .filter(|&&b| b == 0)
+            .count()
+    };
+    let (even, odd) = (zeros_at(0), zeros_at(1));
+    if odd * 10 >= pairs * 4 && even == 0 {
+        Some(TextEncoding::Utf16Le)
+    } else if even * 10 >= pairs * 4 && odd == 0 {
+        Some(TextEncoding::Utf16Be)
+    } else {
+        None
+    }
+}
+
+/// NUL と C1 制御文字（0x80〜0x9F）を含まなければ Latin-1 のテキストとみなす
+fn looks_like_latin1(bytes: &[u8]) -> bool {
+    !bytes.iter().any(|&b| b == 0 || (0x80..0xA0).contains(&b))
+}
+
+/// ファイル全体を推定した文字コードで文字列にする。BOM は取り除く。
+///
+/// Latin-1 はバイトと文字が 1 対 1 に対応するので元に戻せる。
+/// 判定できなかったバイトは [`decode_lossless`] と同じく退避する
+pub fn decode(bytes: &[u8]) -> (String, TextEncoding) {
+    let encoding = detect(bytes);
+    let text = match encoding {
+        TextEncoding::Utf8 => decode_lossless(bytes),
+        TextEncoding::Utf8Bom => decode_lossless(&bytes[3..]),
+        TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+            let body = bytes
+                .strip_prefix(b"\xFF\xFE")
+                .or_else(|| bytes.strip_prefix(b"\xFE\xFF"))
+                .unwrap_or(bytes);
+            let units = body.chunks_exact(2).map(|pair| {
+                if encoding == TextEncoding::Utf16Le {
+                    u16::from_le_bytes([pair[0], pair[1]])
+                } else {
+                    u16::from_be_bytes([pair[0], pair[1]])
+                }
+            });
+            char::decode_utf16(units)
+                .map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER))
+                .collect()
+        }
+        TextEncoding::Latin1 => bytes.iter().map(|&b| char::from(b)).collect(),
+        TextEncoding::Unknown => decode_lossless(bytes),
+    };
+    (text, encoding)
+}
+
+/// UTF-8 として読み、不正なバイトは 1 バイトずつ私用領域の文字に退避する
+pub fn decode_lossless(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len());
+    for chunk in bytes.utf8_chunks() {
+        text.push_str(chunk.valid());
+        text.extend(chunk.invalid().iter().map(|&b| escape_byte(b)));
+    }
+    text
+}
+
+fn escape_byte(byte: u8) -> char {
+    // 不正なバイトは必ず 0x80 以上なので、私用領域の範囲に収まる
+    char::from_u32(ESCAPE_BASE + u32::from(byte)).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+/// [`decode_lossless`] で退避した文字なら元のバイト
+pub fn escaped_byte(c: char) -> Option<u8> {
+    let code = u32::from(c).checked_sub(ESCAPE_BASE)?;
+    u8::try_from(code).ok().filter(|&b| b >= 0x80)
+}
+
+/// [`decode_lossless`] の逆変換
+pub fn encode_lossless(text: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(text.len());
+    for c in text.chars() {
+        match escaped_byte(c) {
+            Some(b) => bytes.push(b),
+            None => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+    bytes
+}
+
+/// patch の文字コードを推定する。diff の記号を除いた本文のバイト列で判定する
+pub fn detect_patch(patch: &str) -> TextEncoding {
+    if !patch.chars().any(|c| escaped_byte(c).is_some()) {
+        let has_bom = patch
+            .lines()
+            .any(|line| line.get(1..).is_some_and(|rest| rest.starts_with(BOM)));
+        return if has_bom {
+            TextEncoding::Utf8Bom
+        } else {
+            TextEncoding::Utf8
+        };
+    }
+    let bytes = encode_lossless(patch);
+    if looks_like_latin1(&bytes) {
+        TextEncoding::Latin1
+    } else {
+        TextEncoding::Unknown
+    }
+}
+
+/// 表示用の patch。Latin-1 と判定できれば退避した文字を Latin-1 の文字に置き換える。
+///
+/// 1 バイトを 1 文字に置き換えるだけなので、行数や行インデックスは変わらない
+pub fn display_patch(patch: &str) -> Cow<'_, str> {
+    if detect_patch(patch) != TextEncoding::Latin1 {
+        return Cow::Borrowed(patch);
+    }
+    Cow::Owned(
+        patch
+            .chars()
+            .map(|c| escaped_byte(c).map_or(c, char::from))
+            .collect(),
+    )
+}
+
+/// 表示できないバイトや文字の代わりに出す目印。そのまま表示してよい文字なら None
+pub fn indicator(c: char) -> Option<String> {
+    if let Some(byte) = escaped_byte(c) {
+        return Some(format!("\\x{:02X}", byte));
+    }
+    match c {
+        BOM => Some("<BOM>".to_string()),
+        char::REPLACEMENT_CHARACTER => Some("\u{FFFD}".to_string()),
+        _ => None,
+    }
+}
+
+/// suggestion を作れない patch ならその理由。
+///
+/// suggestion は UTF-8 の文字列として GitHub に送るため、元のファイルが UTF-8 でなければ
+/// 適用したときに該当行のバイト列が変わってしまう。GitHub が置換文字（U+FFFD）に
+/// 変換した patch も元のバイト列が分からないので対象外にする
+pub fn suggestion_error(patch: &str) -> Option<String> {
+    let encoding = detect_patch(patch);
+    if !encoding.is_utf8() {
+        return Some(format!(
+            "Cannot suggest on a non-UTF-8 file ({})",
+            encoding.label()
+        ));
+    }
+    if patch.contains(char::REPLACEMENT_CHARACTER) {
+        return Some("Cannot suggest on a non-UTF-8 file (undecodable bytes)".to_string());
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_encodings() {
+        assert_eq!(detect(b"plain text"), TextEncoding::Utf8);
+        assert_eq!(detect("caf\u{e9}".as_bytes()), TextEncoding::Utf8);
+        assert_eq!(detect(b"\xEF\xBB\xBFhello"), TextEncoding::Utf8Bom);
+        assert_eq!(detect(b"\xFF\xFEh\0i\0"), TextEncoding::Utf16Le);
+        assert_eq!(detect(b"\0h\0i\0!\0?"), TextEncoding::Utf16Be);
+        assert_eq!(detect(b"h\0i\0!\0?\0"), TextEncoding::Utf16Le);
+        assert_eq!(detect(b"caf\xE9"), TextEncoding::Latin1);
+        assert_eq!(detect(b"\x81\x40\x82"), TextEncoding::Unknown);
+    }
+
+    #[test]
+    fn test_decode_whole_file() {
+        assert_eq!(decode(b"\xEF\xBB\xBFhi").0, "hi");
+        assert_eq!(decode(b"\xFF\xFEh\0i\0").0, "hi");
+        assert_eq!(decode(b"\xFE\xFF\0h\0i").0, "hi");
+        assert_eq!(decode(b"caf\xE9").0, "caf\u{e9}");
+    }
+
+    #[test]
+    fn test_decode_lossless_roundtrip() {
+        let bytes = b"ok \xE9t\xE9 \x81\x40 \xE3\x81\x82".to_vec();
+        let text = decode_lossless(&bytes);
+        assert!(text.starts_with("ok "));
+        assert!(text.ends_with("\u{3042}"));
+        assert_eq!(encode_lossless(&text), bytes);
+        assert_eq!(escaped_byte(text.chars().nth(3).unwrap()), Some(0xE9));
+        assert_eq!(escaped_byte('a'), None);
+        assert_eq!(decode_lossless("\u{3042}".as_bytes()), "\u{3042}");
+    }
+
+    #[test]
+    fn test_display_patch_uses_latin1_when_possible() {
+        let latin1 = decode_lossless(b"@@ -1 +1 @@\n-caf\xE9\n+caf\xE9!");
+        assert_eq!(detect_patch(&latin1), TextEncoding::Latin1);
+        assert_eq!(
+            display_patch(&latin1),
+            "@@ -1 +1 @@\n-caf\u{e9}\n+caf\u{e9}!"
+        );
+
+        let unknown = decode_lossless(b"@@ -1 +1 @@\n+\x81\x40");
+        assert_eq!(detect_patch(&unknown), TextEncoding::Unknown);
+        assert!(matches!(display_patch(&unknown), Cow::Borrowed(_)));
+        let first = unknown.lines().nth(1).unwrap().chars().nth(1).unwrap();
+        assert_eq!(indicator(first).as_deref(), Some("\\x81"));
+
+        let bom = "@@ -1 +1 @@\n+\u{FEFF}fn main() {}";
+        assert_eq!(detect_patch(bom), TextEncoding::Utf8Bom);
+        assert_eq!(indicator(BOM).as_deref(), Some("<BOM>"));
+    }
+
+    #[test]
+    fn test_suggestion_error_for_non_utf8_patch() {
+        assert_eq!(suggestion_error("@@ -1 +1 @@\n+caf\u{e9}"), None);
+        assert_eq!(suggestion_error("@@ -1 +1 @@\n+\u{FEFF}x"), None);
+        let latin1 = decode_lossless(b"@@ -1 +1 @@\n+caf\xE9");
+        assert_eq!(
+            suggestion_error(&latin1).as_deref(),
+            Some("Cannot suggest on a non-UTF-8 file (Latin-1)")
+        );
+        assert!(suggestion_error("@@ -1 +1 @@\n+caf\u{FFFD}").is_some());
+    }
+}
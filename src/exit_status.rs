@@ -0,0 +1,45 @@
+//! `--wait-for-review` の終了コード
+//!
+//! ラッパースクリプトがレビュー結果に応じて処理を続けられるよう、セッションで最後に
+//! 送信したレビューを終了コードで返す（例: 自分が approve したら自動マージする）。
+//!
+//! | Code | Meaning |
+//! |------|---------|
+//! | 0 | Approved |
+//! | 1 | Error |
+//! | 3 | Changes requested |
+//! | 4 | Commented |
+//! | 5 | Aborted (quit without submitting a review) |
+//!
+//! 2 は clap の引数エラーが使うため避けている。
+
+use crate::github::ReviewAction;
+
+pub const APPROVED: i32 = 0;
+pub const ERROR: i32 = 1;
+pub const CHANGES_REQUESTED: i32 = 3;
+pub const COMMENTED: i32 = 4;
+pub const ABORTED: i32 = 5;
+
+/// セッションで最後に送信したレビューに対応する終了コード
+pub fn for_review(outcome: Option<ReviewAction>) -> i32 {
+    match outcome {
+        Some(ReviewAction::Approve) => APPROVED,
+        Some(ReviewAction::RequestChanges) => CHANGES_REQUESTED,
+        Some(ReviewAction::Comment) => COMMENTED,
+        None => ABORTED,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_for_review() {
+        assert_eq!(for_review(Some(ReviewAction::Approve)), 0);
+        assert_eq!(for_review(Some(ReviewAction::RequestChanges)), 3);
+        assert_eq!(for_review(Some(ReviewAction::Comment)), 4);
+        assert_eq!(for_review(None), 5);
+    }
+}
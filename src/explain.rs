@@ -0,0 +1,100 @@
+//! ファイル単位の変更の説明
+//!
+//! 選択中のファイルの patch だけを `ai.reviewer` のエージェントに 1 回渡し、短い説明を受け取る。
+//! ラリーと違ってセッションもツールも使わないので、リポジトリの他のファイルは読まない
+
+use anyhow::Result;
+
+use crate::ai::oneshot::{self, OneshotAgent};
+use crate::config::Config;
+
+/// プロンプトに含める patch の最大文字数
+const MAX_PATCH_CHARS: usize = 40_000;
+
+/// 説明を頼むファイルの変更
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    /// PR のタイトル（ローカルモードでは None）
+    pub pr_title: Option<String>,
+    pub filename: String,
+    /// `added` / `modified` / `removed` / `renamed`
+    pub status: String,
+    pub patch: String,
+}
+
+/// 説明を頼むエージェント
+pub struct Explainer {
+    agent: OneshotAgent,
+}
+
+impl Explainer {
+    pub fn from_config(config: &Config, repo: &str) -> Result<Self> {
+        Ok(Self {
+            agent: OneshotAgent::from_config(config, repo, "explanations")?,
+        })
+    }
+
+    /// ステータス表示用の名前
+    pub fn name(&self) -> &'static str {
+        self.agent.name()
+    }
+
+    pub async fn explain(&self, change: &FileChange) -> Result<String> {
+        self.agent.run(&build_prompt(change)).await
+    }
+}
+
+pub fn build_prompt(change: &FileChange) -> String {
+    let patch = oneshot::truncate_patch(&change.patch, MAX_PATCH_CHARS);
+    let pr = change
+        .pr_title
+        .as_ref()
+        .map(|title| format!(" in the pull request \"{}\"", title))
+        .unwrap_or_default();
+    format!(
+        "Explain the following change to `{filename}` ({status}){pr} for a code reviewer. \
+Reply in concise Markdown: one sentence on what the change does, then at most five bullet \
+points on notable details and anything a reviewer should double-check. \
+Do not restate the diff line by line and do not use any tools.\n\n```diff\n{patch}\n```",
+        filename = change.filename,
+        status = change.status,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(patch: &str) -> FileChange {
+        FileChange {
+            pr_title: Some("Add retry".to_string()),
+            filename: "src/net.rs".to_string(),
+            status: "modified".to_string(),
+            patch: patch.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_build_prompt_contains_file_and_patch() {
+        let prompt = build_prompt(&change("@@ -1 +1 @@\n-a\n+b"));
+        assert!(prompt.contains("`src/net.rs` (modified) in the pull request \"Add retry\""));
+        assert!(prompt.ends_with("```diff\n@@ -1 +1 @@\n-a\n+b\n```"));
+
+        let long = "+x\n".repeat(MAX_PATCH_CHARS);
+        let prompt = build_prompt(&change(&long));
+        assert!(prompt.contains("(patch truncated)"));
+        assert!(prompt.len() < MAX_PATCH_CHARS + 1_000);
+    }
+
+    #[test]
+    fn test_explainer_from_config() {
+        let mut config: Config = toml::from_str("").unwrap();
+        let explainer = Explainer::from_config(&config, "owner/repo").unwrap();
+        assert_eq!(explainer.name(), "claude");
+        config.ai.reviewer = "codex".to_string();
+        let explainer = Explainer::from_config(&config, "owner/repo").unwrap();
+        assert_eq!(explainer.name(), "codex");
+        config.ai.reviewer = "unknown".to_string();
+        assert!(Explainer::from_config(&config, "owner/repo").is_err());
+    }
+}
@@ -0,0 +1,226 @@
+//! 指定リビジョンのファイル内容の取得
+//!
+//! 削除されたファイルは patch に削除行しか現れないため、base コミット時点の
+//! ファイル全体を取得し、全行をコンテキスト行とする patch に変換して
+//! 通常の diff ビューでシンタックスハイライト付き・読み取り専用で表示する。
+//! それ以外のファイルは head 時点のファイル全体に diff の追加・削除行を差し込み、
+//! hunk の外側も含めて読めるようにする。
+
+use anyhow::{bail, Context, Result};
+use tokio::process::Command;
+
+use crate::encoding::TextEncoding;
+use crate::github;
+
+/// 全体表示するファイルの上限サイズ
+pub const MAX_FILE_BYTES: usize = 1024 * 1024;
+
+/// GitHub の contents API から `rev` 時点のファイル内容を取得する
+pub async fn fetch_from_github(repo: &str, rev: &str, path: &str) -> Result<String> {
+    let endpoint = format!(
+        "repos/{}/contents/{}?ref={}",
+        repo,
+        encode_path(path),
+        encode_path(rev)
+    );
+    let content = github::gh_api_text(&endpoint, Some("application/vnd.github.raw")).await?;
+    as_text(crate::encoding::encode_lossless(&content))
+}
+
+/// ローカルリポジトリから `git show <rev>:<path>` でファイル内容を取得する
+pub async fn fetch_from_git(working_dir: Option<&str>, rev: &str, path: &str) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(["show", &format!("{}:{}", rev, path)]);
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
+    let output = cmd.output().await.context("Failed to run git show")?;
+    if !output.status.success() {
+        bail!(
+            "git show failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    as_text(output.stdout)
+}
+
+/// 作業ツリーのファイル内容を読む（ローカルモードの diff は作業ツリーと HEAD の比較）
+pub async fn read_worktree(working_dir: Option<&str>, path: &str) -> Result<String> {
+    let root = repo_root(working_dir).await?;
+    let bytes = std::fs::read(std::path::Path::new(&root).join(path))
+        .with_context(|| format!("Failed to read {}", path))?;
+    as_text(bytes)
+}
+
+/// `working_dir` を含むリポジトリのルート。diff のパスはルートからの相対パス
+pub(crate) async fn repo_root(working_dir: Option<&str>) -> Result<String> {
+    let mut cmd = Command::new("git");
+    cmd.args(["rev-parse", "--show-toplevel"]);
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
+    let output = cmd.output().await.context("Failed to run git rev-parse")?;
+    if !output.status.success() {
+        bail!(
+            "git rev-parse failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn as_text(bytes: Vec<u8>) -> Result<String> {
+    if bytes.len() > MAX_FILE_BYTES {
+        bail!("File is too large to show ({} bytes)", bytes.len());
+    }
+    // UTF-16 は NUL を含むので、バイナリ判定より先に文字コードを見る
+    let (text, encoding) = crate::encoding::decode(&bytes);
+    if bytes.contains(&0) && !matches!(encoding, TextEncoding::Utf16Le | TextEncoding::Utf16Be) {
+        bail!("File is binary");
+    }
+    Ok(text)
+}
+
+/// ファイル内容を全行コンテキスト行の patch にする（コメント不可の読み取り専用表示用）
+pub fn full_file_patch(content: &str) -> String {
+    let count = content.lines().count();
+    let start = if count == 0 { 0 } else { 1 };
+    let mut patch = format!("@@ -{},{} +{},{} @@", start, count, start, count);
+    for line in content.lines() {
+        patch.push_str("\n ");
+        patch.push_str(line);
+    }
+    patch
+}
+
+/// head 時点のファイル内容に `patch` の hunk を差し込み、ファイル全体を 1 つの hunk にした patch を作る。
+/// hunk の外側はコンテキスト行になり、追加・削除行は diff と同じ印で表示される。
+/// 解釈できない hunk ヘッダー（結合 diff など）があれば None
+pub fn full_file_diff_patch(content: &str, patch: &str) -> Option<String> {
+    let lines: Vec<&str> = content.lines().collect();
+    let mut body: Vec<String> = Vec::new();
+    // 次に出力する head 側の行（1 始まり）
+    let mut next = 1;
+    let mut in_hunk = false;
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            let range = crate::diff::parse_hunk_range(line)?;
+            if range.old_counts.len() != 1 {
+                return None;
+            }
+            // 削除だけの hunk の開始行は、削除位置の直前の行を指す
+            let start = if range.new_count == 0 {
+                range.new_start as usize + 1
+            } else {
+                range.new_start as usize
+            };
+            while next < start && next <= lines.len() {
+                body.push(format!(" {}", lines[next - 1]));
+                next += 1;
+            }
+            in_hunk = true;
+            continue;
+        }
+        if !in_hunk {
+            continue;
+        }
+        match line.chars().next() {
+            Some('-') => body.push(line.to_string()),
+            // "\ No newline at end of file"
+            Some('\\') => {}
+            Some('+') | Some(' ') => {
+                body.push(line.to_string());
+                next += 1;
+            }
+            // 末尾の空白を削られた空のコンテキスト行
+            _ => {
+                body.push(" ".to_string());
+                next += 1;
+            }
+        }
+    }
+    for line in lines.iter().skip(next.saturating_sub(1)) {
+        body.push(format!(" {}", line));
+    }
+
+    let old_count = body.iter().filter(|l| !l.starts_with('+')).count();
+    let new_count = body.iter().filter(|l| !l.starts_with('-')).count();
+    let start = |count: usize| if count == 0 { 0 } else { 1 };
+    let mut result = format!(
+        "@@ -{},{} +{},{} @@",
+        start(old_count),
+        old_count,
+        start(new_count),
+        new_count
+    );
+    for line in body {
+        result.push('\n');
+        result.push_str(&line);
+    }
+    Some(result)
+}
+
+/// URL のパス部分に使えない文字をパーセントエンコードする（`/` は区切りとして残す）
+pub(crate) fn encode_path(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_file_patch() {
+        assert_eq!(
+            full_file_patch("fn main() {\n}\n"),
+            "@@ -1,2 +1,2 @@\n fn main() {\n }"
+        );
+        assert_eq!(full_file_patch(""), "@@ -0,0 +0,0 @@");
+    }
+
+    #[test]
+    fn test_full_file_diff_patch_keeps_markers_outside_hunks() {
+        let content = "a\nb\nc2\nd\ne\nf\ng\n";
+        let patch = "@@ -2,3 +2,3 @@ fn x\n b\n-c\n+c2\n d\n@@ -7,1 +6,0 @@\n-gone";
+        assert_eq!(
+            full_file_diff_patch(content, patch).unwrap(),
+            "@@ -1,8 +1,7 @@\n a\n b\n-c\n+c2\n d\n e\n f\n-gone\n g"
+        );
+        // 新規ファイルは全行が追加行のまま
+        assert_eq!(
+            full_file_diff_patch("x\n", "@@ -0,0 +1 @@\n+x\n\\ No newline at end of file").unwrap(),
+            "@@ -0,0 +1,1 @@\n+x"
+        );
+        assert!(full_file_diff_patch("x", "@@@ -1 -1 +1 @@@\n  x").is_none());
+    }
+
+    #[test]
+    fn test_encode_path() {
+        assert_eq!(encode_path("src/lib.rs"), "src/lib.rs");
+        assert_eq!(encode_path("docs/a b#1.md"), "docs/a%20b%231.md");
+        assert_eq!(encode_path("資料.md"), "%E8%B3%87%E6%96%99.md");
+    }
+
+    #[test]
+    fn test_as_text_rejects_binary_and_large_files() {
+        assert_eq!(as_text(b"hello".to_vec()).unwrap(), "hello");
+        assert!(as_text(vec![b'a', 0, b'b']).is_err());
+        assert!(as_text(vec![b'a'; MAX_FILE_BYTES + 1]).is_err());
+    }
+
+    #[test]
+    fn test_as_text_decodes_other_encodings() {
+        assert_eq!(as_text(b"\xFF\xFEh\0i\0".to_vec()).unwrap(), "hi");
+        assert_eq!(as_text(b"caf\xE9".to_vec()).unwrap(), "caf\u{e9}");
+        assert_eq!(as_text(b"\xEF\xBB\xBFok".to_vec()).unwrap(), "ok");
+    }
+}
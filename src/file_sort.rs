@@ -0,0 +1,238 @@
+//! ファイル一覧の並び順
+//!
+//! パスは自然順（`file2` が `file10` より前）で、大文字小文字を区別せずに比べる。
+//! パス順ではトップレベルのディレクトリごとにまとめ、一覧に見出しを入れる。
+//! 並び順は表示だけのもので、ファイルのインデックス自体は変えない。
+
+use std::cmp::Ordering;
+use std::time::SystemTime;
+
+use crate::github::ChangedFile;
+
+/// ファイル一覧の並び順（セッション中だけ有効）
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum FileSort {
+    /// パスの自然順。トップレベルのディレクトリごとにまとめる
+    #[default]
+    Path,
+    /// 追加行数の多い順
+    Additions,
+    /// 変更の種類（追加・変更・名前変更・コピー・削除）順
+    Status,
+    /// 作業ツリーで最近変更した順（ローカルモードのみ）
+    Recent,
+}
+
+impl FileSort {
+    pub fn label(self) -> &'static str {
+        match self {
+            FileSort::Path => "path",
+            FileSort::Additions => "additions",
+            FileSort::Status => "status",
+            FileSort::Recent => "recently changed",
+        }
+    }
+
+    /// 次の並び順。Recent はローカルモードでだけ選べる
+    pub fn next(self, local_mode: bool) -> Self {
+        match self {
+            FileSort::Path => FileSort::Additions,
+            FileSort::Additions => FileSort::Status,
+            FileSort::Status if local_mode => FileSort::Recent,
+            FileSort::Status | FileSort::Recent => FileSort::Path,
+        }
+    }
+
+    /// ディレクトリの見出しを入れるか
+    pub fn groups_by_directory(self) -> bool {
+        self == FileSort::Path
+    }
+}
+
+/// 自然順で比べる。数字の並びは数値として比べ、それ以外は大文字小文字を区別しない
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a_rest, mut b_rest) = (a, b);
+    while let (Some(ac), Some(bc)) = (a_rest.chars().next(), b_rest.chars().next()) {
+        if ac.is_ascii_digit() && bc.is_ascii_digit() {
+            let a_len = a_rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(a_rest.len());
+            let b_len = b_rest
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(b_rest.len());
+            let a_num = a_rest[..a_len].trim_start_matches('0');
+            let b_num = b_rest[..b_len].trim_start_matches('0');
+            let ordering = a_num.len().cmp(&b_num.len()).then_with(|| a_num.cmp(b_num));
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+            a_rest = &a_rest[a_len..];
+            b_rest = &b_rest[b_len..];
+            continue;
+        }
+        let ordering = ac.to_lowercase().cmp(bc.to_lowercase());
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+        a_rest = &a_rest[ac.len_utf8()..];
+        b_rest = &b_rest[bc.len_utf8()..];
+    }
+    a_rest
+        .is_empty()
+        .cmp(&b_rest.is_empty())
+        .reverse()
+        // 大文字小文字や先頭の 0 だけが違う場合も順序を決める
+        .then_with(|| a.cmp(b))
+}
+
+/// パスを `/` で区切った要素ごとに自然順で比べる
+pub fn path_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_parts = a.split('/');
+    let mut b_parts = b.split('/');
+    loop {
+        match (a_parts.next(), b_parts.next()) {
+            (Some(a_part), Some(b_part)) => match natural_cmp(a_part, b_part) {
+                Ordering::Equal => continue,
+                ordering => return ordering,
+            },
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+        }
+    }
+}
+
+/// 見出しに使うトップレベルのディレクトリ。ルート直下のファイルは None
+pub fn top_level_dir(path: &str) -> Option<&str> {
+    path.split_once('/').map(|(dir, _)| dir)
+}
+
+fn status_rank(status: &str) -> u8 {
+    match status {
+        "added" => 0,
+        "modified" => 1,
+        "renamed" => 2,
+        "copied" => 3,
+        "removed" => 4,
+        _ => 5,
+    }
+}
+
+/// 表示順に並べたファイルのインデックス。
+/// `modified` はファイルごとの更新日時で、Recent のときだけ使う
+pub fn sorted_indices(
+    files: &[ChangedFile],
+    sort: FileSort,
+    modified: &[Option<SystemTime>],
+) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..files.len()).collect();
+    let by_path = |a: usize, b: usize| path_cmp(&files[a].filename, &files[b].filename);
+    match sort {
+        // ディレクトリのあとにルート直下のファイルを並べる
+        FileSort::Path => indices.sort_by(|&a, &b| {
+            let a_root = top_level_dir(&files[a].filename).is_none();
+            let b_root = top_level_dir(&files[b].filename).is_none();
+            a_root.cmp(&b_root).then_with(|| by_path(a, b))
+        }),
+        FileSort::Additions => indices.sort_by(|&a, &b| {
+            files[b]
+                .additions
+                .cmp(&files[a].additions)
+                .then_with(|| by_path(a, b))
+        }),
+        FileSort::Status => indices.sort_by(|&a, &b| {
+            status_rank(&files[a].status)
+                .cmp(&status_rank(&files[b].status))
+                .then_with(|| by_path(a, b))
+        }),
+        // 新しい順。更新日時が分からないファイルは最後
+        FileSort::Recent => indices.sort_by(|&a, &b| {
+            let a_time = modified.get(a).copied().flatten();
+            let b_time = modified.get(b).copied().flatten();
+            b_time.cmp(&a_time).then_with(|| by_path(a, b))
+        }),
+    }
+    indices
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    fn file(name: &str, status: &str, additions: u32) -> ChangedFile {
+        ChangedFile {
+            filename: name.to_string(),
+            status: status.to_string(),
+            additions,
+            deletions: 0,
+            patch: None,
+            viewed: false,
+        }
+    }
+
+    #[test]
+    fn test_natural_cmp() {
+        let mut names = vec!["file10.rs", "File2.rs", "file1.rs", "file02.rs", "file.rs"];
+        names.sort_by(|a, b| natural_cmp(a, b));
+        assert_eq!(
+            names,
+            vec!["file.rs", "file1.rs", "File2.rs", "file02.rs", "file10.rs"]
+        );
+        assert_eq!(natural_cmp("a", "a"), Ordering::Equal);
+        assert_eq!(natural_cmp("v1.10", "v1.9"), Ordering::Greater);
+    }
+
+    #[test]
+    fn test_path_sort_groups_directories_before_root_files() {
+        let files = vec![
+            file("README.md", "modified", 1),
+            file("src/page10.rs", "modified", 1),
+            file("src/page2.rs", "added", 1),
+            file("docs/guide.md", "modified", 1),
+            file("src/app/mod.rs", "modified", 1),
+        ];
+        let order = sorted_indices(&files, FileSort::Path, &[]);
+        let names: Vec<&str> = order.iter().map(|&i| files[i].filename.as_str()).collect();
+        assert_eq!(
+            names,
+            vec![
+                "docs/guide.md",
+                "src/app/mod.rs",
+                "src/page2.rs",
+                "src/page10.rs",
+                "README.md"
+            ]
+        );
+        assert_eq!(top_level_dir("src/app/mod.rs"), Some("src"));
+        assert_eq!(top_level_dir("README.md"), None);
+    }
+
+    #[test]
+    fn test_sort_by_additions_status_and_recent() {
+        let files = vec![
+            file("a.rs", "removed", 0),
+            file("b.rs", "modified", 30),
+            file("c.rs", "added", 5),
+        ];
+        assert_eq!(
+            sorted_indices(&files, FileSort::Additions, &[]),
+            vec![1, 2, 0]
+        );
+        assert_eq!(sorted_indices(&files, FileSort::Status, &[]), vec![2, 1, 0]);
+
+        let now = SystemTime::now();
+        let modified = vec![Some(now - Duration::from_secs(60)), None, Some(now)];
+        assert_eq!(
+            sorted_indices(&files, FileSort::Recent, &modified),
+            vec![2, 0, 1]
+        );
+    }
+
+    #[test]
+    fn test_next_sort_skips_recent_outside_local_mode() {
+        assert_eq!(FileSort::Status.next(false), FileSort::Path);
+        assert_eq!(FileSort::Status.next(true), FileSort::Recent);
+        assert_eq!(FileSort::Recent.next(true), FileSort::Path);
+    }
+}
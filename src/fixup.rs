@@ -0,0 +1,542 @@
+//! レビューコメント対応用の fixup コミット支援（ローカルモード）
+//!
+//! 未コミットの変更を hunk 単位に分割し、近傍のレビューコメントに紐付けて
+//! `fixup! <subject>` 形式のコミットを作成する。最後に
+//! `git rebase -i --autosquash` で PR ベースに対して畳み込む。
+
+use anyhow::{Context, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+use crate::github::comment::ReviewComment;
+use crate::github::ChangedFile;
+
+/// hunk とコメント行の距離がこの行数以内なら同じ指摘への対応とみなす
+const FIXUP_MATCH_WINDOW: u32 = 5;
+
+/// コミットメッセージに含めるコメント抜粋の最大文字数
+const FIXUP_EXCERPT_CHARS: usize = 72;
+
+/// patch から切り出した単一 hunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixupHunk {
+    pub filename: String,
+    /// `diff --git` 〜 `+++` までのファイルヘッダ（`git apply` に必要）
+    pub file_header: String,
+    /// `@@` 行を含む hunk 本体
+    pub body: String,
+    pub new_start: u32,
+    pub new_count: u32,
+}
+
+/// 1つのレビューコメントに対応する hunk のまとまり
+#[derive(Debug, Clone)]
+pub struct FixupGroup {
+    pub comment_id: u64,
+    pub comment_path: String,
+    pub comment_line: Option<u32>,
+    pub comment_author: String,
+    pub comment_body: String,
+    pub hunks: Vec<FixupHunk>,
+}
+
+impl FixupGroup {
+    /// コメント本文の1行目（表示・コミットメッセージ用）
+    pub fn excerpt(&self) -> String {
+        let first_line = self.comment_body.lines().next().unwrap_or("").trim();
+        if first_line.chars().count() > FIXUP_EXCERPT_CHARS {
+            let truncated: String = first_line.chars().take(FIXUP_EXCERPT_CHARS).collect();
+            format!("{}...", truncated)
+        } else {
+            first_line.to_string()
+        }
+    }
+}
+
+/// コメントとの紐付け結果
+#[derive(Debug, Default)]
+pub struct FixupPlan {
+    pub groups: Vec<FixupGroup>,
+    /// どのコメントにも紐付かなかった hunk
+    pub unassigned: Vec<FixupHunk>,
+}
+
+/// `@@ -a,b +c,d @@` から新ファイル側の (start, count) を取り出す
+fn parse_new_range(header: &str) -> Option<(u32, u32)> {
+    let plus_pos = header.find('+')?;
+    let after_plus = &header[plus_pos + 1..];
+    let end = after_plus.find(' ').unwrap_or(after_plus.len());
+    let range = &after_plus[..end];
+    match range.split_once(',') {
+        Some((start, count)) => Some((start.parse().ok()?, count.parse().ok()?)),
+        None => Some((range.parse().ok()?, 1)),
+    }
+}
+
+/// ファイル patch を hunk 単位に分割する
+pub fn split_hunks(filename: &str, patch: &str) -> Vec<FixupHunk> {
+    let mut header_lines: Vec<&str> = Vec::new();
+    let mut hunks = Vec::new();
+    let mut current: Option<(Vec<&str>, u32, u32)> = None;
+
+    for line in patch.lines() {
+        if line.starts_with("@@") {
+            if let Some((body, start, count)) = current.take() {
+                hunks.push((body, start, count));
+            }
+            let (start, count) = parse_new_range(line).unwrap_or((0, 0));
+            current = Some((vec![line], start, count));
+        } else if let Some((ref mut body, _, _)) = current {
+            body.push(line);
+        } else {
+            header_lines.push(line);
+        }
+    }
+    if let Some(last) = current {
+        hunks.push(last);
+    }
+
+    let file_header = header_lines.join("\n");
+    hunks
+        .into_iter()
+        .map(|(body, new_start, new_count)| FixupHunk {
+            filename: filename.to_string(),
+            file_header: file_header.clone(),
+            body: body.join("\n"),
+            new_start,
+            new_count,
+        })
+        .collect()
+}
+
+/// hunk の新ファイル側範囲とコメント行の距離（範囲内なら 0）
+fn hunk_distance(hunk: &FixupHunk, line: u32) -> u32 {
+    let start = hunk.new_start;
+    let end = start + hunk.new_count.max(1) - 1;
+    if line < start {
+        start - line
+    } else {
+        line.saturating_sub(end)
+    }
+}
+
+/// 変更ファイルの hunk を最寄りのレビューコメントに紐付ける
+///
+/// 同じ距離のコメントが複数ある場合（スレッドの返信など）は id の小さい方を採用する。
+pub fn build_fixup_plan(files: &[ChangedFile], comments: &[ReviewComment]) -> FixupPlan {
+    let mut plan = FixupPlan::default();
+
+    for file in files {
+        let Some(patch) = file.patch.as_deref() else {
+            continue;
+        };
+        for hunk in split_hunks(&file.filename, patch) {
+            let best = comments
+                .iter()
+                .filter(|c| c.path == file.filename)
+                .filter_map(|c| c.line.map(|line| (c, hunk_distance(&hunk, line))))
+                .filter(|(_, distance)| *distance <= FIXUP_MATCH_WINDOW)
+                .min_by_key(|(c, distance)| (*distance, c.id));
+
+            match best {
+                Some((comment, _)) => {
+                    if let Some(group) = plan.groups.iter_mut().find(|g| g.comment_id == comment.id)
+                    {
+                        group.hunks.push(hunk);
+                    } else {
+                        plan.groups.push(FixupGroup {
+                            comment_id: comment.id,
+                            comment_path: comment.path.clone(),
+                            comment_line: comment.line,
+                            comment_author: comment.user.login.clone(),
+                            comment_body: comment.body.clone(),
+                            hunks: vec![hunk],
+                        });
+                    }
+                }
+                None => plan.unassigned.push(hunk),
+            }
+        }
+    }
+
+    plan.groups.sort_by(|a, b| {
+        a.comment_path
+            .cmp(&b.comment_path)
+            .then(a.comment_line.cmp(&b.comment_line))
+    });
+    plan
+}
+
+/// `git apply --cached` に渡す patch を組み立てる（ファイルごとにヘッダを1回だけ出力）
+pub fn build_group_patch(hunks: &[FixupHunk]) -> String {
+    let mut patch = String::new();
+    let mut last_file: Option<&str> = None;
+    for hunk in hunks {
+        if last_file != Some(hunk.filename.as_str()) {
+            patch.push_str(&hunk.file_header);
+            patch.push('\n');
+            last_file = Some(hunk.filename.as_str());
+        }
+        patch.push_str(&hunk.body);
+        patch.push('\n');
+    }
+    patch
+}
+
+/// fixup コミットメッセージを生成する。
+///
+/// 1行目は `git rebase --autosquash` が認識する `fixup! <subject>` 形式。
+pub fn fixup_message(target_subject: &str, group: &FixupGroup) -> String {
+    let location = match group.comment_line {
+        Some(line) => format!("{}:{}", group.comment_path, line),
+        None => group.comment_path.clone(),
+    };
+    format!(
+        "fixup! {}\n\nAddress review comment {} by @{} ({})\n\n> {}\n",
+        target_subject,
+        group.comment_id,
+        group.comment_author,
+        location,
+        group.excerpt()
+    )
+}
+
+fn git_command(working_dir: Option<&str>) -> Command {
+    let mut command = Command::new("git");
+    if let Some(dir) = working_dir {
+        command.current_dir(dir);
+    }
+    command
+}
+
+fn run_git(working_dir: Option<&str>, args: &[&str]) -> Result<String> {
+    let output = git_command(working_dir)
+        .args(args)
+        .output()
+        .context("failed to spawn git command")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// PR ベースとの merge-base を解決する（`origin/<base>` を優先）
+pub fn resolve_rebase_base(working_dir: Option<&str>, base_branch: &str) -> Result<String> {
+    let remote = format!("origin/{}", base_branch);
+    run_git(working_dir, &["merge-base", "HEAD", &remote])
+        .or_else(|_| run_git(working_dir, &["merge-base", "HEAD", base_branch]))
+        .map(|s| s.trim().to_string())
+        .with_context(|| format!("could not find merge-base with {}", base_branch))
+}
+
+/// fixup 先のコミット (sha, subject) を探す。
+///
+/// `base..HEAD` のうち対象ファイルを最後に変更したコミットを優先し、
+/// 見つからなければ PR の最新コミットを使う。
+fn find_target_commit(
+    working_dir: Option<&str>,
+    base: &str,
+    path: &str,
+) -> Result<Option<(String, String)>> {
+    let range = format!("{}..HEAD", base);
+    let by_path = run_git(
+        working_dir,
+        &["log", "-n1", "--format=%H%x09%s", &range, "--", path],
+    )?;
+    let line = if by_path.trim().is_empty() {
+        run_git(working_dir, &["log", "-n1", "--format=%H%x09%s", &range])?
+    } else {
+        by_path
+    };
+    Ok(line
+        .trim()
+        .split_once('\t')
+        .map(|(sha, subject)| (sha.to_string(), subject.to_string())))
+}
+
+fn apply_to_index(working_dir: Option<&str>, patch: &str) -> Result<()> {
+    let mut child = git_command(working_dir)
+        .args(["apply", "--cached", "--recount", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to spawn git apply")?;
+    child
+        .stdin
+        .take()
+        .context("failed to open git apply stdin")?
+        .write_all(patch.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("git apply --cached failed: {}", stderr.trim());
+    }
+    Ok(())
+}
+
+/// 各グループを個別の fixup コミットとして作成し、作成数を返す。
+///
+/// ステージ済みの変更があると意図しない内容が混ざるため、事前に拒否する。
+/// ブロッキング処理のため `spawn_blocking` から呼び出すこと。
+pub fn create_fixup_commits(
+    working_dir: Option<&str>,
+    base_branch: &str,
+    groups: &[FixupGroup],
+) -> Result<usize> {
+    let staged = git_command(working_dir)
+        .args(["diff", "--cached", "--quiet"])
+        .status()
+        .context("failed to spawn git diff --cached")?;
+    if !staged.success() {
+        anyhow::bail!("Staged changes exist; commit or unstage them first");
+    }
+
+    let base = resolve_rebase_base(working_dir, base_branch)?;
+    // 作成した fixup コミットを後のグループの fixup 先にしないよう、先にすべて決めておく
+    let mut subjects = Vec::with_capacity(groups.len());
+    for group in groups {
+        let Some((_sha, subject)) = find_target_commit(working_dir, &base, &group.comment_path)?
+        else {
+            anyhow::bail!("No commits found between {} and HEAD", base_branch);
+        };
+        subjects.push(subject);
+    }
+
+    let mut created = 0;
+    for (group, subject) in groups.iter().zip(&subjects) {
+        apply_to_index(working_dir, &build_group_patch(&group.hunks))?;
+        let message = fixup_message(subject, group);
+        if let Err(e) = run_git(working_dir, &["commit", "-q", "-m", &message]) {
+            // 失敗したグループの変更をステージから戻して作業ツリーに残す
+            let _ = run_git(working_dir, &["reset", "-q"]);
+            return Err(e);
+        }
+        created += 1;
+    }
+
+    Ok(created)
+}
+
+/// `git rebase -i --autosquash <merge-base>` を端末上で実行する。
+///
+/// TUI を一時停止した状態で呼び出すこと（git が編集用エディタを起動するため）。
+pub fn run_autosquash_rebase(working_dir: Option<&str>, base_branch: &str) -> Result<bool> {
+    let base = resolve_rebase_base(working_dir, base_branch)?;
+    let status = git_command(working_dir)
+        .args(["rebase", "-i", "--autosquash", &base])
+        .status()
+        .context("failed to spawn git rebase")?;
+    Ok(status.success())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::User;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    const PATCH: &str = "diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -1,3 +1,3 @@
+ fn a() {}
+-fn b() {}
++fn b() { todo!() }
+ fn c() {}
+@@ -40,2 +40,3 @@
+ fn x() {}
++fn y() {}
+ fn z() {}";
+
+    fn comment(id: u64, path: &str, line: Option<u32>) -> ReviewComment {
+        ReviewComment {
+            id,
+            path: path.to_string(),
+            line,
+            start_line: None,
+            body: format!("comment {}\nsecond line", id),
+            user: User {
+                login: "alice".to_string(),
+            },
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    fn changed_file(filename: &str, patch: &str) -> ChangedFile {
+        ChangedFile {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions: 0,
+            deletions: 0,
+            patch: Some(patch.to_string()),
+            viewed: false,
+        }
+    }
+
+    #[test]
+    fn test_parse_new_range() {
+        assert_eq!(parse_new_range("@@ -1,3 +4,5 @@ fn"), Some((4, 5)));
+        assert_eq!(parse_new_range("@@ -1 +7 @@"), Some((7, 1)));
+        assert_eq!(parse_new_range("@@ garbage"), None);
+    }
+
+    #[test]
+    fn test_split_hunks_keeps_file_header() {
+        let hunks = split_hunks("src/lib.rs", PATCH);
+        assert_eq!(hunks.len(), 2);
+        assert!(hunks[0].file_header.starts_with("diff --git"));
+        assert!(hunks[0].file_header.ends_with("+++ b/src/lib.rs"));
+        assert_eq!((hunks[0].new_start, hunks[0].new_count), (1, 3));
+        assert_eq!((hunks[1].new_start, hunks[1].new_count), (40, 3));
+        assert!(hunks[1].body.starts_with("@@ -40,2 +40,3 @@"));
+    }
+
+    #[test]
+    fn test_build_fixup_plan_assigns_nearest_comment() {
+        let files = vec![changed_file("src/lib.rs", PATCH)];
+        let comments = vec![
+            comment(10, "src/lib.rs", Some(2)),
+            comment(11, "src/lib.rs", Some(44)),
+            comment(12, "src/other.rs", Some(2)),
+        ];
+        let plan = build_fixup_plan(&files, &comments);
+        assert_eq!(plan.groups.len(), 2);
+        assert_eq!(plan.groups[0].comment_id, 10);
+        assert_eq!(plan.groups[1].comment_id, 11);
+        assert!(plan.unassigned.is_empty());
+    }
+
+    #[test]
+    fn test_build_fixup_plan_leaves_far_hunks_unassigned() {
+        let files = vec![changed_file("src/lib.rs", PATCH)];
+        let comments = vec![comment(10, "src/lib.rs", Some(20))];
+        let plan = build_fixup_plan(&files, &comments);
+        assert!(plan.groups.is_empty());
+        assert_eq!(plan.unassigned.len(), 2);
+    }
+
+    #[test]
+    fn test_build_fixup_plan_prefers_lower_id_on_tie() {
+        let files = vec![changed_file("src/lib.rs", PATCH)];
+        let comments = vec![
+            comment(21, "src/lib.rs", Some(2)),
+            comment(20, "src/lib.rs", Some(2)),
+        ];
+        let plan = build_fixup_plan(&files, &comments);
+        assert_eq!(plan.groups[0].comment_id, 20);
+    }
+
+    #[test]
+    fn test_build_group_patch_writes_header_once_per_file() {
+        let hunks = split_hunks("src/lib.rs", PATCH);
+        let patch = build_group_patch(&hunks);
+        assert_eq!(patch.matches("diff --git").count(), 1);
+        assert_eq!(patch.matches("@@ -").count(), 2);
+    }
+
+    #[test]
+    fn test_fixup_message_format() {
+        let group = FixupGroup {
+            comment_id: 42,
+            comment_path: "src/lib.rs".to_string(),
+            comment_line: Some(3),
+            comment_author: "alice".to_string(),
+            comment_body: "Please handle the error\nmore".to_string(),
+            hunks: vec![],
+        };
+        let message = fixup_message("Add parser", &group);
+        assert!(message.starts_with("fixup! Add parser\n\n"));
+        assert!(message.contains("review comment 42 by @alice (src/lib.rs:3)"));
+        assert!(message.contains("> Please handle the error"));
+        assert!(!message.contains("more"));
+    }
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "octorus-test")
+            .env("GIT_AUTHOR_EMAIL", "octorus-test@example.com")
+            .env("GIT_COMMITTER_NAME", "octorus-test")
+            .env("GIT_COMMITTER_EMAIL", "octorus-test@example.com")
+            .status()
+            .expect("failed to run git");
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[test]
+    fn test_create_fixup_commits_in_repo() {
+        let tempdir = tempdir().unwrap();
+        let dir = tempdir.path();
+        let wd = dir.to_str();
+
+        git(dir, &["init", "-q", "-b", "main"]);
+        git(dir, &["config", "user.name", "octorus-test"]);
+        git(dir, &["config", "user.email", "octorus-test@example.com"]);
+        std::fs::write(dir.join("a.txt"), "one\ntwo\nthree\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", "Initial"]);
+        git(dir, &["checkout", "-q", "-b", "feature"]);
+        std::fs::write(dir.join("a.txt"), "one\ntwo!\nthree\n").unwrap();
+        git(dir, &["commit", "-q", "-am", "Change two"]);
+
+        // レビュー指摘への対応（未コミット）
+        std::fs::write(dir.join("a.txt"), "one\ntwo!!\nthree\n").unwrap();
+        let patch = run_git(wd, &["diff", "HEAD", "--", "a.txt"]).unwrap();
+        let files = vec![changed_file("a.txt", &patch)];
+        let plan = build_fixup_plan(&files, &[comment(7, "a.txt", Some(2))]);
+        assert_eq!(plan.groups.len(), 1);
+
+        let created = create_fixup_commits(wd, "main", &plan.groups).unwrap();
+        assert_eq!(created, 1);
+
+        let subject = run_git(wd, &["log", "-n1", "--format=%s"]).unwrap();
+        assert_eq!(subject.trim(), "fixup! Change two");
+        let status = run_git(wd, &["status", "--porcelain"]).unwrap();
+        assert!(status.trim().is_empty());
+
+        // 同じファイルの 2 つのグループは、どちらも元のコミットを fixup 先にする
+        let lines: Vec<String> = (1..=30).map(|n| format!("line {}", n)).collect();
+        std::fs::write(dir.join("b.txt"), lines.join("\n") + "\n").unwrap();
+        git(dir, &["add", "b.txt"]);
+        git(dir, &["commit", "-q", "-m", "Add b"]);
+        let mut edited = lines.clone();
+        edited[1] = "line 2!".to_string();
+        edited[24] = "line 25!".to_string();
+        std::fs::write(dir.join("b.txt"), edited.join("\n") + "\n").unwrap();
+        let patch = run_git(wd, &["diff", "HEAD", "--", "b.txt"]).unwrap();
+        let files = vec![changed_file("b.txt", &patch)];
+        let comments = [comment(8, "b.txt", Some(2)), comment(9, "b.txt", Some(25))];
+        let plan = build_fixup_plan(&files, &comments);
+        assert_eq!(plan.groups.len(), 2);
+
+        let created = create_fixup_commits(wd, "main", &plan.groups).unwrap();
+        assert_eq!(created, 2);
+        let subjects = run_git(wd, &["log", "-n2", "--format=%s"]).unwrap();
+        assert_eq!(subjects.lines().collect::<Vec<_>>(), ["fixup! Add b"; 2]);
+        let status = run_git(wd, &["status", "--porcelain"]).unwrap();
+        assert!(status.trim().is_empty());
+    }
+
+    #[test]
+    fn test_create_fixup_commits_rejects_staged_changes() {
+        let tempdir = tempdir().unwrap();
+        let dir = tempdir.path();
+
+        git(dir, &["init", "-q", "-b", "main"]);
+        git(dir, &["config", "user.name", "octorus-test"]);
+        git(dir, &["config", "user.email", "octorus-test@example.com"]);
+        std::fs::write(dir.join("a.txt"), "one\n").unwrap();
+        git(dir, &["add", "."]);
+        git(dir, &["commit", "-q", "-m", "Initial"]);
+        std::fs::write(dir.join("a.txt"), "two\n").unwrap();
+        git(dir, &["add", "."]);
+
+        let err = create_fixup_commits(dir.to_str(), "main", &[]).unwrap_err();
+        assert!(err.to_string().contains("Staged changes exist"));
+    }
+}
@@ -0,0 +1,237 @@
+//! diff 上のカーソル移動をエディタ側へ通知するジャンプイベント
+//!
+//! `[follow] path` に FIFO または UNIX ソケットを指定すると、diff ビューで移動するたびに
+//! 改行区切りの JSON を 1 行書き込む。エディタ側の小さなヘルパーがこれを読み、
+//! 同じファイル・行をフルファイルで開くことで追従表示できる。
+//!
+//! ```text
+//! {"event":"jump","path":"src/main.rs","line":42}
+//! ```
+//!
+//! - `path`: リポジトリルートからの相対パス
+//! - `line`: 新ファイル側の行番号（1 始まり）。削除行上ではその直前の行を指す
+//!
+//! 書き込みは専用スレッドで行うため、読み手がいなくても TUI はブロックしない。
+//! 読み手がいない間のイベントは最新の 1 件だけ残して破棄する。
+
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc as std_mpsc;
+use std::time::{Duration, Instant};
+
+use serde::Serialize;
+
+use crate::config::FollowConfig;
+
+/// ジャンプイベント
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "event", rename = "jump")]
+pub struct JumpEvent {
+    pub path: String,
+    pub line: u32,
+}
+
+impl JumpEvent {
+    pub fn to_line(&self) -> String {
+        let mut line = serde_json::to_string(self).unwrap_or_default();
+        line.push('\n');
+        line
+    }
+}
+
+/// 同一位置の重複を除き、一定間隔以内の連続移動は最後の 1 件にまとめる
+#[derive(Debug)]
+pub struct Throttle {
+    interval: Duration,
+    last_sent: Option<JumpEvent>,
+    last_sent_at: Option<Instant>,
+    pending: Option<JumpEvent>,
+}
+
+impl Throttle {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            last_sent: None,
+            last_sent_at: None,
+            pending: None,
+        }
+    }
+
+    /// 新しい位置を記録し、今送るべきイベントがあれば返す
+    pub fn push(&mut self, event: JumpEvent, now: Instant) -> Option<JumpEvent> {
+        if self.last_sent.as_ref() == Some(&event) {
+            self.pending = None;
+            return None;
+        }
+        self.pending = Some(event);
+        self.poll(now)
+    }
+
+    /// 保留中のイベントが送信可能になっていれば返す
+    pub fn poll(&mut self, now: Instant) -> Option<JumpEvent> {
+        let ready = self
+            .last_sent_at
+            .is_none_or(|at| now.duration_since(at) >= self.interval);
+        if !ready {
+            return None;
+        }
+        let event = self.pending.take()?;
+        self.last_sent = Some(event.clone());
+        self.last_sent_at = Some(now);
+        Some(event)
+    }
+}
+
+/// ジャンプイベントの送信元（App が保持する）
+pub struct FollowEmitter {
+    throttle: Throttle,
+    tx: std_mpsc::Sender<JumpEvent>,
+}
+
+impl FollowEmitter {
+    /// 設定が有効な場合のみ書き込みスレッドを起動する
+    pub fn from_config(config: &FollowConfig) -> Option<Self> {
+        let path = PathBuf::from(config.path.as_deref()?);
+        let (tx, rx) = std_mpsc::channel();
+        std::thread::spawn(move || writer_loop(path, rx));
+        Some(Self {
+            throttle: Throttle::new(Duration::from_millis(config.throttle_ms)),
+            tx,
+        })
+    }
+
+    pub fn emit(&mut self, event: JumpEvent) {
+        if let Some(event) = self.throttle.push(event, Instant::now()) {
+            let _ = self.tx.send(event);
+        }
+    }
+
+    /// 間引かれて保留中のイベントを送る（毎 tick 呼ぶ）
+    pub fn flush(&mut self) {
+        if let Some(event) = self.throttle.poll(Instant::now()) {
+            let _ = self.tx.send(event);
+        }
+    }
+}
+
+/// 書き込み先（FIFO またはソケット）を開く。FIFO は読み手が現れるまでブロックする
+fn open_sink(path: &Path) -> std::io::Result<Box<dyn Write>> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if std::fs::metadata(path)?.file_type().is_socket() {
+            return Ok(Box::new(std::os::unix::net::UnixStream::connect(path)?));
+        }
+    }
+    Ok(Box::new(
+        std::fs::OpenOptions::new().write(true).open(path)?,
+    ))
+}
+
+fn writer_loop(path: PathBuf, rx: std_mpsc::Receiver<JumpEvent>) {
+    let mut sink: Option<Box<dyn Write>> = None;
+
+    while let Ok(mut event) = rx.recv() {
+        if sink.is_none() {
+            match open_sink(&path) {
+                Ok(s) => sink = Some(s),
+                Err(e) => {
+                    tracing::debug!("follow: failed to open {}: {}", path.display(), e);
+                    continue;
+                }
+            }
+            // 読み手を待つ間に溜まったイベントは最新のものだけ送る
+            while let Ok(newer) = rx.try_recv() {
+                event = newer;
+            }
+        }
+        if let Some(ref mut s) = sink {
+            if s.write_all(event.to_line().as_bytes())
+                .and_then(|_| s.flush())
+                .is_err()
+            {
+                // 読み手が閉じた。次のイベントで開き直す
+                sink = None;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn jump(path: &str, line: u32) -> JumpEvent {
+        JumpEvent {
+            path: path.to_string(),
+            line,
+        }
+    }
+
+    #[test]
+    fn test_jump_event_format() {
+        assert_eq!(
+            jump("src/main.rs", 42).to_line(),
+            "{\"event\":\"jump\",\"path\":\"src/main.rs\",\"line\":42}\n"
+        );
+    }
+
+    #[test]
+    fn test_throttle_dedups_same_position() {
+        let mut throttle = Throttle::new(Duration::ZERO);
+        let now = Instant::now();
+        assert_eq!(throttle.push(jump("a.rs", 1), now), Some(jump("a.rs", 1)));
+        assert_eq!(throttle.push(jump("a.rs", 1), now), None);
+        assert_eq!(throttle.push(jump("a.rs", 2), now), Some(jump("a.rs", 2)));
+    }
+
+    #[test]
+    fn test_throttle_coalesces_rapid_moves() {
+        let mut throttle = Throttle::new(Duration::from_millis(100));
+        let start = Instant::now();
+        assert_eq!(throttle.push(jump("a.rs", 1), start), Some(jump("a.rs", 1)));
+
+        let soon = start + Duration::from_millis(10);
+        assert_eq!(throttle.push(jump("a.rs", 2), soon), None);
+        assert_eq!(throttle.push(jump("a.rs", 3), soon), None);
+        assert_eq!(throttle.poll(soon), None);
+
+        let later = start + Duration::from_millis(150);
+        assert_eq!(throttle.poll(later), Some(jump("a.rs", 3)));
+        assert_eq!(throttle.poll(later), None);
+    }
+
+    #[test]
+    fn test_throttle_drops_pending_when_returning_to_last_sent() {
+        let mut throttle = Throttle::new(Duration::from_millis(100));
+        let start = Instant::now();
+        throttle.push(jump("a.rs", 1), start);
+        throttle.push(jump("a.rs", 2), start);
+        throttle.push(jump("a.rs", 1), start);
+        assert_eq!(throttle.poll(start + Duration::from_millis(200)), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_emitter_writes_to_socket() {
+        use std::io::{BufRead, BufReader};
+        use std::os::unix::net::UnixListener;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("follow.sock");
+        let listener = UnixListener::bind(&path).unwrap();
+
+        let mut emitter = FollowEmitter::from_config(&FollowConfig {
+            path: Some(path.to_string_lossy().to_string()),
+            throttle_ms: 0,
+        })
+        .unwrap();
+        emitter.emit(jump("src/lib.rs", 7));
+
+        let (stream, _) = listener.accept().unwrap();
+        let mut line = String::new();
+        BufReader::new(stream).read_line(&mut line).unwrap();
+        assert_eq!(line, jump("src/lib.rs", 7).to_line());
+    }
+}
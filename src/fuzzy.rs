@@ -0,0 +1,188 @@
+//! ファイルパス・シンボル名向けのファジーマッチ
+//!
+//! パターンの各文字が候補内に順番に現れればマッチとみなし、
+//! 連続一致・単語境界（`/` `_` `-` `.` の直後、camelCase の大文字）・
+//! ファイル名部分での一致を高く評価する。大文字小文字は区別しない。
+
+const SCORE_MATCH: i64 = 16;
+const BONUS_CONSECUTIVE: i64 = 24;
+const BONUS_BOUNDARY: i64 = 20;
+const BONUS_FIRST_CHAR: i64 = 12;
+const BONUS_BASENAME: i64 = 8;
+const PENALTY_GAP: i64 = 6;
+
+/// マッチ結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    /// 一致した文字の位置（候補の char インデックス、昇順）
+    pub positions: Vec<usize>,
+}
+
+fn is_separator(c: char) -> bool {
+    matches!(c, '/' | '_' | '-' | '.' | ' ' | ':')
+}
+
+/// 候補の各位置に与える境界ボーナス
+fn position_bonus(chars: &[char], basename_start: usize) -> Vec<i64> {
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let mut bonus = 0;
+            if i == 0 {
+                bonus += BONUS_FIRST_CHAR;
+            } else {
+                let prev = chars[i - 1];
+                if is_separator(prev) || (prev.is_lowercase() && c.is_uppercase()) {
+                    bonus += BONUS_BOUNDARY;
+                }
+            }
+            if i >= basename_start {
+                bonus += BONUS_BASENAME;
+            }
+            bonus
+        })
+        .collect()
+}
+
+/// `pattern` で `candidate` をマッチングする。空パターンはスコア 0 でマッチ
+pub fn fuzzy_match(pattern: &str, candidate: &str) -> Option<FuzzyMatch> {
+    let pattern: Vec<char> = pattern
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect();
+    if pattern.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    let chars: Vec<char> = candidate.chars().collect();
+    let lower: Vec<char> = chars
+        .iter()
+        .map(|c| c.to_lowercase().next().unwrap_or(*c))
+        .collect();
+    let (m, n) = (pattern.len(), chars.len());
+    if m > n {
+        return None;
+    }
+    let basename_start = candidate
+        .rfind('/')
+        .map(|i| candidate[..=i].chars().count())
+        .unwrap_or(0);
+    let bonus = position_bonus(&chars, basename_start);
+
+    // score[i][j]: pattern[..=i] を pattern[i] == candidate[j] で終えたときの最大スコア
+    let mut score = vec![vec![None::<i64>; n]; m];
+    let mut back = vec![vec![usize::MAX; n]; m];
+
+    for i in 0..m {
+        // 直前行の j' < j - 1 までの最大値（ギャップあり遷移用）
+        let mut best_prev: Option<(i64, usize)> = None;
+        for j in 0..n {
+            if i > 0 && j >= 2 {
+                if let Some(s) = score[i - 1][j - 2] {
+                    if best_prev.is_none_or(|(b, _)| s > b) {
+                        best_prev = Some((s, j - 2));
+                    }
+                }
+            }
+            if lower[j] != pattern[i] {
+                continue;
+            }
+            let base = SCORE_MATCH + bonus[j];
+            if i == 0 {
+                // 先頭までの読み飛ばしは軽く減点
+                let skipped = if j > 0 { PENALTY_GAP } else { 0 };
+                score[i][j] = Some(base - skipped);
+                continue;
+            }
+            let consecutive = (j > 0)
+                .then(|| score[i - 1][j - 1])
+                .flatten()
+                .map(|s| (s + base + BONUS_CONSECUTIVE, j - 1));
+            let gapped = best_prev.map(|(s, k)| (s + base - PENALTY_GAP, k));
+            let best = match (consecutive, gapped) {
+                (Some(a), Some(b)) => Some(if a.0 >= b.0 { a } else { b }),
+                (a, b) => a.or(b),
+            };
+            if let Some((s, k)) = best {
+                score[i][j] = Some(s);
+                back[i][j] = k;
+            }
+        }
+    }
+
+    let (best_score, mut j) = (0..n)
+        .filter_map(|j| score[m - 1][j].map(|s| (s, j)))
+        .max_by_key(|&(s, j)| (s, std::cmp::Reverse(j)))?;
+
+    let mut positions = vec![0; m];
+    for i in (0..m).rev() {
+        positions[i] = j;
+        j = back[i][j];
+    }
+
+    // 同点なら短い候補を優先
+    Some(FuzzyMatch {
+        score: best_score * 4 - n as i64,
+        positions,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_empty_pattern_matches_everything() {
+        let m = fuzzy_match("", "src/main.rs").unwrap();
+        assert_eq!(m.score, 0);
+        assert!(m.positions.is_empty());
+    }
+
+    #[test]
+    fn test_subsequence_required() {
+        assert!(fuzzy_match("mn", "src/main.rs").is_some());
+        assert!(fuzzy_match("nm", "main").is_none());
+        assert!(fuzzy_match("toolong", "abc").is_none());
+    }
+
+    #[test]
+    fn test_case_insensitive_positions() {
+        let m = fuzzy_match("APP", "src/app/mod.rs").unwrap();
+        assert_eq!(m.positions, vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn test_prefers_consecutive_and_boundary_matches() {
+        let contiguous = fuzzy_match("mod", "src/app/mod.rs").unwrap();
+        let scattered = fuzzy_match("mod", "src/my_other_dir.rs").unwrap();
+        assert!(contiguous.score > scattered.score);
+
+        let boundary = fuzzy_match("fl", "src/ui/file_list.rs").unwrap();
+        assert_eq!(boundary.positions, vec![7, 12]);
+    }
+
+    #[test]
+    fn test_prefers_basename_match() {
+        let basename = fuzzy_match("config", "src/config.rs").unwrap();
+        let dirname = fuzzy_match("config", "config/src/lib.rs").unwrap();
+        assert!(basename.score > dirname.score);
+    }
+
+    #[test]
+    fn test_camel_case_boundary() {
+        let m = fuzzy_match("dv", "renderDiffView").unwrap();
+        assert_eq!(m.positions, vec![6, 10]);
+    }
+
+    #[test]
+    fn test_multibyte_candidates() {
+        let m = fuzzy_match("md", "docs/日本語/README.md").unwrap();
+        assert_eq!(m.positions.len(), 2);
+    }
+}
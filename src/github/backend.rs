@@ -0,0 +1,845 @@
+//! GitHub API の呼び出し方法
+//!
+//! API 呼び出しは [`GithubBackend`] で抽象化している。`[github] backend` で選択する。
+//!
+//! - [`GhCli`]: `gh api` を実行する（gh のログイン情報を使う）
+//! - [`RestClient`]: [`crate::credentials`] で取得したトークンで REST / GraphQL API を直接呼ぶ
+//!
+//! `auto`（既定）では gh CLI が PATH にあれば gh、なければ API を直接呼ぶ。
+//! GET はどちらも ETag による条件付きリクエスト（[`GithubBackend::get_conditional`]）に対応する。
+//! レスポンスのレート制限のヘッダーとレート制限のエラーは [`super::rate_limit`] に記録する。
+//! バックエンドは起動時に [`init_backend`] で一度だけ決め、未初期化の場合は gh を使う。
+//! テストでは `MockBackend::install` でモックに差し替えられる。
+
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use async_trait::async_trait;
+use serde_json::{json, Map, Value};
+
+use super::client::{
+    flatten_pages, gh_command, gh_failure, gh_output, DetectRepoError, FieldValue,
+};
+use super::rate_limit;
+use crate::config::{GithubBackendKind, GithubConfig};
+use crate::credentials;
+
+/// 条件付き GET の結果
+#[derive(Debug, Clone, PartialEq)]
+pub enum Conditional {
+    /// `If-None-Match` に渡した ETag から変わっていない（304）
+    NotModified,
+    Modified {
+        body: String,
+        etag: Option<String>,
+        /// ページネーションの次のページの URL
+        next: Option<String>,
+    },
+}
+
+/// GitHub API の呼び出し方法
+#[async_trait]
+pub trait GithubBackend: Send + Sync {
+    /// エラー表示用の名前
+    fn name(&self) -> &'static str;
+
+    /// GET した本文をそのまま返す。`accept` で `Accept` ヘッダーを差し替える
+    async fn get(&self, endpoint: &str, accept: Option<&str>) -> Result<String>;
+
+    /// `etag` を `If-None-Match` に付けて GET する。ページネーションは 1 ページずつ
+    async fn get_conditional(
+        &self,
+        endpoint: &str,
+        accept: Option<&str>,
+        etag: Option<&str>,
+    ) -> Result<Conditional>;
+
+    /// 配列を返すエンドポイントの全ページを取得し、1 つの配列にまとめる
+    async fn get_paginated(&self, endpoint: &str) -> Result<Value>;
+
+    /// `POST` / `PATCH` などでフィールドを送る。フィールドのキーは gh の `-f` / `-F` と同じ書式
+    async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        fields: &[(&str, FieldValue<'_>)],
+    ) -> Result<Value>;
+
+    /// GraphQL クエリを実行し、`data` を含むレスポンス全体を返す
+    async fn graphql(&self, query: &str, fields: &[(&str, FieldValue<'_>)]) -> Result<Value>;
+
+    /// カレントディレクトリのリポジトリ名（`owner/name`）
+    async fn detect_repo(&self) -> std::result::Result<String, DetectRepoError>;
+
+    /// PR をブラウザで開く
+    async fn open_in_browser(&self, repo: &str, pr_number: u32) -> Result<()>;
+}
+
+static BACKEND: OnceLock<Box<dyn GithubBackend>> = OnceLock::new();
+
+/// 設定に従ってバックエンドを決める。2 回目以降の呼び出しは何もしない
+pub async fn init_backend(config: &GithubConfig) -> Result<()> {
+    if BACKEND.get().is_some() {
+        return Ok(());
+    }
+    let backend = backend_from_config(config).await?;
+    tracing::debug!(backend = backend.name(), "GitHub backend selected");
+    let _ = BACKEND.set(backend);
+    Ok(())
+}
+
+async fn backend_from_config(config: &GithubConfig) -> Result<Box<dyn GithubBackend>> {
+    let use_gh = match config.backend {
+        GithubBackendKind::Gh => true,
+        GithubBackendKind::Api => false,
+        GithubBackendKind::Auto => which::which("gh").is_ok(),
+    };
+    if use_gh {
+        return Ok(Box::new(GhCli));
+    }
+    let token = credentials::read_token(config).await.map_err(|e| {
+        if config.backend == GithubBackendKind::Auto {
+            e.context("gh CLI not found, so octorus needs a token to call the GitHub API")
+        } else {
+            e
+        }
+    })?;
+    Ok(Box::new(RestClient::new(&config.api_url, token)?))
+}
+
+/// 現在のバックエンド
+pub(super) fn backend() -> &'static dyn GithubBackend {
+    #[cfg(test)]
+    if let Some(mock) = super::mock::MockBackend::installed() {
+        return mock;
+    }
+    match BACKEND.get() {
+        Some(backend) => backend.as_ref(),
+        None => &GhCli,
+    }
+}
+
+/// gh CLI 経由の呼び出し
+pub struct GhCli;
+
+#[async_trait]
+impl GithubBackend for GhCli {
+    fn name(&self) -> &'static str {
+        "gh"
+    }
+
+    async fn get(&self, endpoint: &str, accept: Option<&str>) -> Result<String> {
+        match accept {
+            Some(accept) => {
+                let header = format!("Accept: {}", accept);
+                gh_command(&["api", "-H", &header, endpoint]).await
+            }
+            None => gh_command(&["api", endpoint]).await,
+        }
+    }
+
+    async fn get_conditional(
+        &self,
+        endpoint: &str,
+        accept: Option<&str>,
+        etag: Option<&str>,
+    ) -> Result<Conditional> {
+        let mut args = vec!["api".to_string(), "--include".to_string()];
+        if let Some(accept) = accept {
+            args.push("-H".to_string());
+            args.push(format!("Accept: {}", accept));
+        }
+        if let Some(etag) = etag {
+            args.push("-H".to_string());
+            args.push(format!("If-None-Match: {}", etag));
+        }
+        args.push(endpoint.to_string());
+        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        let output = gh_output(&args_refs).await?;
+        let response = parse_included_response(&output.stdout);
+        if let Some(ref response) = response {
+            rate_limit::record_headers(|name| response.header(name));
+        }
+        // gh は 304 でも失敗として終了するため、ステータス行で判定する
+        match response {
+            Some(response) if response.status == 304 => Ok(Conditional::NotModified),
+            Some(response) if output.status.success() => Ok(Conditional::Modified {
+                body: crate::encoding::decode_lossless(response.body),
+                etag: response.header("etag").map(str::to_string),
+                next: response.header("link").and_then(next_page_url),
+            }),
+            _ => Err(gh_failure(&output)),
+        }
+    }
+
+    async fn get_paginated(&self, endpoint: &str) -> Result<Value> {
+        let output = gh_command(&["api", "--paginate", "--slurp", endpoint]).await?;
+        let pages: Vec<Value> =
+            serde_json::from_str(&output).context("Failed to parse gh api paginated response")?;
+        flatten_pages(pages)
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        fields: &[(&str, FieldValue<'_>)],
+    ) -> Result<Value> {
+        let mut args = vec![
+            "api".to_string(),
+            "--method".to_string(),
+            method.to_string(),
+            endpoint.to_string(),
+        ];
+        push_field_args(&mut args, fields);
+        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        tracing::debug!(args = ?args_refs, method, "gh api request");
+        let output = gh_command(&args_refs).await?;
+        // 既読化のように 205 で本文を返さない API がある
+        if output.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&output).context("Failed to parse gh api response as JSON")
+    }
+
+    async fn graphql(&self, query: &str, fields: &[(&str, FieldValue<'_>)]) -> Result<Value> {
+        let mut args = vec![
+            "api".to_string(),
+            "graphql".to_string(),
+            "-f".to_string(),
+            format!("query={}", query),
+        ];
+        push_field_args(&mut args, fields);
+        let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
+        tracing::debug!(args = ?args_refs, "gh api graphql");
+        let output = gh_command(&args_refs).await?;
+        serde_json::from_str(&output).context("Failed to parse gh graphql response as JSON")
+    }
+
+    async fn detect_repo(&self) -> std::result::Result<String, DetectRepoError> {
+        super::client::detect_repo_with_gh().await
+    }
+
+    async fn open_in_browser(&self, repo: &str, pr_number: u32) -> Result<()> {
+        gh_command(&["pr", "view", &pr_number.to_string(), "-R", repo, "--web"]).await?;
+        Ok(())
+    }
+}
+
+/// `gh api --include` の出力（ステータス行とヘッダーの後に本文）
+struct IncludedResponse<'a> {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: &'a [u8],
+}
+
+impl IncludedResponse<'_> {
+    /// ヘッダーの値（名前は大文字小文字を区別しない）
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+fn parse_included_response(output: &[u8]) -> Option<IncludedResponse<'_>> {
+    let (head_len, separator_len) = output
+        .windows(4)
+        .position(|w| w == b"\r\n\r\n")
+        .map(|i| (i, 4))
+        .or_else(|| output.windows(2).position(|w| w == b"\n\n").map(|i| (i, 2)))?;
+    let head = std::str::from_utf8(&output[..head_len]).ok()?;
+    let mut lines = head.lines();
+    // `HTTP/2.0 304 Not Modified`
+    let status = lines.next()?.split_whitespace().nth(1)?.parse().ok()?;
+    let headers = lines
+        .filter_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            Some((key.trim().to_string(), value.trim().to_string()))
+        })
+        .collect();
+    Some(IncludedResponse {
+        status,
+        headers,
+        body: &output[head_len + separator_len..],
+    })
+}
+
+fn push_field_args(args: &mut Vec<String>, fields: &[(&str, FieldValue<'_>)]) {
+    for (key, value) in fields {
+        match value {
+            FieldValue::String(v) => {
+                args.push("-f".to_string());
+                args.push(format!("{}={}", key, v));
+            }
+            FieldValue::Raw(v) => {
+                args.push("-F".to_string());
+                args.push(format!("{}={}", key, v));
+            }
+        }
+    }
+}
+
+/// REST API の 1 ページあたりの件数（エンドポイントで指定がない場合）
+const DEFAULT_PER_PAGE: &str = "per_page=100";
+/// 1 リクエストのタイムアウト
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// トークンで REST / GraphQL API を直接呼ぶクライアント
+pub struct RestClient {
+    http: reqwest::Client,
+    token: String,
+    /// REST API のベース URL（末尾の `/` なし）
+    api_url: String,
+}
+
+impl RestClient {
+    pub fn new(api_url: &str, token: String) -> Result<Self> {
+        let http = reqwest::Client::builder()
+            .user_agent(concat!("octorus/", env!("CARGO_PKG_VERSION")))
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .context("Failed to build HTTP client")?;
+        Ok(Self {
+            http,
+            token,
+            api_url: api_url.trim_end_matches('/').to_string(),
+        })
+    }
+
+    fn url(&self, endpoint: &str) -> String {
+        if endpoint.starts_with("https://") || endpoint.starts_with("http://") {
+            endpoint.to_string()
+        } else {
+            format!("{}/{}", self.api_url, endpoint.trim_start_matches('/'))
+        }
+    }
+
+    /// GraphQL のエンドポイント。GitHub Enterprise Server は `/api/v3` が `/api/graphql` になる
+    fn graphql_url(&self) -> String {
+        match self.api_url.strip_suffix("/v3") {
+            Some(base) => format!("{}/graphql", base),
+            None => format!("{}/graphql", self.api_url),
+        }
+    }
+
+    /// ブラウザで開く GitHub のホスト（`https://github.com` など）
+    fn web_url(&self) -> String {
+        if let Some(base) = self.api_url.strip_suffix("/api/v3") {
+            return base.to_string();
+        }
+        self.api_url.replacen("://api.", "://", 1)
+    }
+
+    fn request(&self, method: reqwest::Method, url: &str) -> reqwest::RequestBuilder {
+        self.http
+            .request(method, url)
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", "2022-11-28")
+    }
+
+    async fn execute(&self, request: reqwest::RequestBuilder) -> Result<reqwest::Response> {
+        let response = request.send().await.context("GitHub API request failed")?;
+        Self::check_status(response).await
+    }
+
+    /// 成功以外のステータスをエラーにする
+    async fn check_status(response: reqwest::Response) -> Result<reqwest::Response> {
+        Self::record_rate_limit(&response);
+        let status = response.status();
+        if status.is_success() {
+            return Ok(response);
+        }
+        let body = response.text().await.unwrap_or_default();
+        tracing::debug!(%status, body = %body, "GitHub API request failed");
+        let message = error_message(status, &body);
+        rate_limit::record_error(&message);
+        bail!("GitHub API request failed: {}", message)
+    }
+
+    fn record_rate_limit(response: &reqwest::Response) {
+        rate_limit::record_headers(|name| {
+            response.headers().get(name).and_then(|v| v.to_str().ok())
+        });
+    }
+}
+
+#[async_trait]
+impl GithubBackend for RestClient {
+    fn name(&self) -> &'static str {
+        "api"
+    }
+
+    async fn get(&self, endpoint: &str, accept: Option<&str>) -> Result<String> {
+        let mut request = self.request(reqwest::Method::GET, &self.url(endpoint));
+        if let Some(accept) = accept {
+            request = request.header("Accept", accept);
+        }
+        let response = self.execute(request).await?;
+        let bytes = response
+            .bytes()
+            .await
+            .context("Failed to read GitHub API response")?;
+        // raw の diff やファイル内容は UTF-8 とは限らない
+        Ok(crate::encoding::decode_lossless(&bytes))
+    }
+
+    async fn get_conditional(
+        &self,
+        endpoint: &str,
+        accept: Option<&str>,
+        etag: Option<&str>,
+    ) -> Result<Conditional> {
+        let mut request = self.request(reqwest::Method::GET, &self.url(endpoint));
+        if let Some(accept) = accept {
+            request = request.header("Accept", accept);
+        }
+        if let Some(etag) = etag {
+            request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+        }
+        let response = request.send().await.context("GitHub API request failed")?;
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            Self::record_rate_limit(&response);
+            return Ok(Conditional::NotModified);
+        }
+        let response = Self::check_status(response).await?;
+        let header = |name: reqwest::header::HeaderName| {
+            response
+                .headers()
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(str::to_string)
+        };
+        let etag = header(reqwest::header::ETAG);
+        let next = header(reqwest::header::LINK).and_then(|link| next_page_url(&link));
+        let bytes = response
+            .bytes()
+            .await
+            .context("Failed to read GitHub API response")?;
+        Ok(Conditional::Modified {
+            body: crate::encoding::decode_lossless(&bytes),
+            etag,
+            next,
+        })
+    }
+
+    async fn get_paginated(&self, endpoint: &str) -> Result<Value> {
+        let mut pages = Vec::new();
+        let mut next = Some(with_per_page(&self.url(endpoint)));
+        while let Some(url) = next.take() {
+            let response = self
+                .execute(self.request(reqwest::Method::GET, &url))
+                .await?;
+            next = response
+                .headers()
+                .get(reqwest::header::LINK)
+                .and_then(|v| v.to_str().ok())
+                .and_then(next_page_url);
+            let page: Value = response
+                .json()
+                .await
+                .context("Failed to parse GitHub API paginated response")?;
+            pages.push(page);
+        }
+        flatten_pages(pages)
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        fields: &[(&str, FieldValue<'_>)],
+    ) -> Result<Value> {
+        let method = reqwest::Method::from_bytes(method.as_bytes())
+            .map_err(|_| anyhow!("Invalid HTTP method: {}", method))?;
+        let body = fields_to_json(fields)?;
+        tracing::debug!(%method, endpoint, "GitHub API request");
+        let request = self.request(method, &self.url(endpoint)).json(&body);
+        let text = self.execute(request).await?.text().await?;
+        if text.trim().is_empty() {
+            return Ok(Value::Null);
+        }
+        serde_json::from_str(&text).context("Failed to parse GitHub API response as JSON")
+    }
+
+    async fn graphql(&self, query: &str, fields: &[(&str, FieldValue<'_>)]) -> Result<Value> {
+        let body = json!({ "query": query, "variables": fields_to_json(fields)? });
+        tracing::debug!("GitHub GraphQL request");
+        let request = self
+            .request(reqwest::Method::POST, &self.graphql_url())
+            .json(&body);
+        // errors は呼び出し側で確認する（一部の結果だけが取れる場合があるため）
+        self.execute(request)
+            .await?
+            .json()
+            .await
+            .context("Failed to parse GitHub GraphQL response as JSON")
+    }
+
+    async fn detect_repo(&self) -> std::result::Result<String, DetectRepoError> {
+        let output = tokio::process::Command::new("git")
+            .args(["remote", "-v"])
+            .output()
+            .await
+            .map_err(|_| DetectRepoError::NotGitRepo)?;
+        if !output.status.success() {
+            return Err(DetectRepoError::NotGitRepo);
+        }
+        let host = self
+            .web_url()
+            .split("://")
+            .nth(1)
+            .unwrap_or_default()
+            .to_string();
+        repo_from_remotes(&String::from_utf8_lossy(&output.stdout), &host)
+            .ok_or(DetectRepoError::NoGitHubRemote)
+    }
+
+    async fn open_in_browser(&self, repo: &str, pr_number: u32) -> Result<()> {
+        let url = format!("{}/{}/pull/{}", self.web_url(), repo, pr_number);
+        open_url(&url).await
+    }
+}
+
+/// エラーレスポンスの `message` を優先し、なければ本文を 200 文字に切り詰めて使う
+fn error_message(status: reqwest::StatusCode, body: &str) -> String {
+    let message = serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|v| v["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| body.trim().chars().take(200).collect());
+    if message.is_empty() {
+        status.to_string()
+    } else {
+        format!("{} ({})", message, status)
+    }
+}
+
+/// 1 ページあたりの件数の指定がなければ [`DEFAULT_PER_PAGE`] を付ける
+pub(super) fn with_per_page(url: &str) -> String {
+    if url.contains("per_page=") {
+        return url.to_string();
+    }
+    let separator = if url.contains('?') { '&' } else { '?' };
+    format!("{}{}{}", url, separator, DEFAULT_PER_PAGE)
+}
+
+/// `Link` ヘッダーから次のページの URL を取り出す
+fn next_page_url(link: &str) -> Option<String> {
+    link.split(',').find_map(|part| {
+        let (url, params) = part.split_once(';')?;
+        params
+            .split(';')
+            .any(|p| p.trim() == r#"rel="next""#)
+            .then(|| {
+                url.trim()
+                    .trim_start_matches('<')
+                    .trim_end_matches('>')
+                    .to_string()
+            })
+    })
+}
+
+/// gh の `-f` / `-F` と同じ書式のフィールドを JSON にする。
+///
+/// - `key[]` は配列に追加する
+/// - `key[][sub]` は配列の最後のオブジェクトに入れる。`sub` が既にあれば次の要素に進む
+/// - `key[sub]` はオブジェクトに入れる。`key[sub][name]` のように続ければ入れ子のオブジェクトにする
+/// - `-F`（[`FieldValue::Raw`]）の `true` / `false` / `null` / 整数は型付きの値にする
+pub(super) fn fields_to_json(fields: &[(&str, FieldValue<'_>)]) -> Result<Value> {
+    let mut root = Map::new();
+    for (key, value) in fields {
+        let value = match value {
+            FieldValue::String(v) => Value::String(v.to_string()),
+            FieldValue::Raw(v) => raw_value(v),
+        };
+        let (name, rest) = match key.find('[') {
+            Some(i) => (&key[..i], &key[i..]),
+            None => (*key, ""),
+        };
+        match rest {
+            "" => {
+                root.insert(name.to_string(), value);
+            }
+            "[]" => array_entry(&mut root, name, key)?.push(value),
+            _ if rest.starts_with("[][") => {
+                let Some(sub) = rest
+                    .strip_prefix("[][")
+                    .and_then(|s| s.strip_suffix(']'))
+                    .filter(|s| !s.contains(['[', ']']))
+                else {
+                    bail!("Unsupported field key: {}", key);
+                };
+                let items = array_entry(&mut root, name, key)?;
+                let needs_new = match items.last() {
+                    Some(Value::Object(last)) => last.contains_key(sub),
+                    _ => true,
+                };
+                if needs_new {
+                    items.push(Value::Object(Map::new()));
+                }
+                if let Some(Value::Object(last)) = items.last_mut() {
+                    last.insert(sub.to_string(), value);
+                }
+            }
+            _ => {
+                let Some(path) = rest
+                    .strip_prefix('[')
+                    .and_then(|s| s.strip_suffix(']'))
+                    .map(|s| s.split("][").collect::<Vec<_>>())
+                    .filter(|path| {
+                        path.iter()
+                            .all(|s| !s.is_empty() && !s.contains(['[', ']']))
+                    })
+                else {
+                    bail!("Unsupported field key: {}", key);
+                };
+                let mut object = object_entry(&mut root, name, key)?;
+                for sub in &path[..path.len() - 1] {
+                    object = object_entry(object, sub, key)?;
+                }
+                object.insert(path[path.len() - 1].to_string(), value);
+            }
+        }
+    }
+    Ok(Value::Object(root))
+}
+
+fn array_entry<'a>(
+    root: &'a mut Map<String, Value>,
+    name: &str,
+    key: &str,
+) -> Result<&'a mut Vec<Value>> {
+    match root
+        .entry(name.to_string())
+        .or_insert_with(|| Value::Array(Vec::new()))
+    {
+        Value::Array(items) => Ok(items),
+        _ => bail!("Field {} conflicts with {}", key, name),
+    }
+}
+
+fn object_entry<'a>(
+    parent: &'a mut Map<String, Value>,
+    name: &str,
+    key: &str,
+) -> Result<&'a mut Map<String, Value>> {
+    match parent
+        .entry(name.to_string())
+        .or_insert_with(|| Value::Object(Map::new()))
+    {
+        Value::Object(object) => Ok(object),
+        _ => bail!("Field {} conflicts with {}", key, name),
+    }
+}
+
+fn raw_value(value: &str) -> Value {
+    match value {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        "null" => Value::Null,
+        _ => value
+            .parse::<i64>()
+            .map(Value::from)
+            .unwrap_or_else(|_| Value::String(value.to_string())),
+    }
+}
+
+/// `git remote -v` の出力から `host` のリポジトリを探す（origin を優先）
+fn repo_from_remotes(remotes: &str, host: &str) -> Option<String> {
+    let mut candidates: Vec<(&str, String)> = remotes
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let name = parts.next()?;
+            let url = parts.next()?;
+            Some((name, repo_from_remote_url(url, host)?))
+        })
+        .collect();
+    candidates.sort_by_key(|(name, _)| *name != "origin");
+    candidates.into_iter().next().map(|(_, repo)| repo)
+}
+
+/// `git@HOST:owner/name.git` / `https://HOST/owner/name` / `ssh://git@HOST/owner/name` を解釈する
+fn repo_from_remote_url(url: &str, host: &str) -> Option<String> {
+    let path = if let Some((_, rest)) = url.split_once("://") {
+        let rest = rest.rsplit_once('@').map_or(rest, |(_, r)| r);
+        let (remote_host, path) = rest.split_once('/')?;
+        // ssh://git@HOST:22/owner/name のようなポート指定は無視する
+        if remote_host.split(':').next()? != host {
+            return None;
+        }
+        path
+    } else {
+        let rest = url.rsplit_once('@').map_or(url, |(_, r)| r);
+        let (remote_host, path) = rest.split_once(':')?;
+        if remote_host != host {
+            return None;
+        }
+        path
+    };
+    let path = path.trim_end_matches('/').trim_end_matches(".git");
+    let (owner, name) = path.split_once('/')?;
+    if owner.is_empty() || name.is_empty() || name.contains('/') {
+        return None;
+    }
+    Some(format!("{}/{}", owner, name))
+}
+
+async fn open_url(url: &str) -> Result<()> {
+    let mut cmd = if cfg!(target_os = "macos") {
+        tokio::process::Command::new("open")
+    } else if cfg!(windows) {
+        let mut cmd = tokio::process::Command::new("cmd");
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        tokio::process::Command::new("xdg-open")
+    };
+    let status = cmd
+        .arg(url)
+        .stdout(std::process::Stdio::null())
+        .stderr(std::process::Stdio::null())
+        .status()
+        .await
+        .context("Failed to open browser")?;
+    if !status.success() {
+        bail!("Failed to open {}", url);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fields_to_json_matches_gh_field_syntax() {
+        let fields = [
+            ("event", FieldValue::String("COMMENT")),
+            ("comments[][path]", FieldValue::String("src/a.rs")),
+            ("comments[][position]", FieldValue::Raw("3")),
+            ("comments[][path]", FieldValue::String("src/b.rs")),
+            ("comments[][line]", FieldValue::Raw("6")),
+            ("labels[]", FieldValue::String("bug")),
+            ("labels[]", FieldValue::String("ui")),
+            ("meta[draft]", FieldValue::Raw("true")),
+            ("files[a.json][content]", FieldValue::String("{}")),
+            ("pr", FieldValue::Raw("PR_kw")),
+        ];
+        assert_eq!(
+            fields_to_json(&fields).unwrap(),
+            json!({
+                "event": "COMMENT",
+                "comments": [
+                    {"path": "src/a.rs", "position": 3},
+                    {"path": "src/b.rs", "line": 6},
+                ],
+                "labels": ["bug", "ui"],
+                "meta": {"draft": true},
+                "files": {"a.json": {"content": "{}"}},
+                "pr": "PR_kw",
+            })
+        );
+        assert!(fields_to_json(&[("a[][b][c]", FieldValue::String("x"))]).is_err());
+        assert!(fields_to_json(&[("a[b][]", FieldValue::String("x"))]).is_err());
+        assert!(fields_to_json(&[
+            ("a", FieldValue::String("x")),
+            ("a[]", FieldValue::String("y"))
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_next_page_url() {
+        let link = r#"<https://api.github.com/repositories/1/pulls/2/files?per_page=100&page=2>; rel="next", <https://api.github.com/repositories/1/pulls/2/files?per_page=100&page=5>; rel="last""#;
+        assert_eq!(
+            next_page_url(link).as_deref(),
+            Some("https://api.github.com/repositories/1/pulls/2/files?per_page=100&page=2")
+        );
+        let last = r#"<https://api.github.com/x?page=1>; rel="prev", <https://api.github.com/x?page=1>; rel="first""#;
+        assert_eq!(next_page_url(last), None);
+    }
+
+    #[test]
+    fn test_parse_included_response() {
+        let output = b"HTTP/2.0 200 OK\r\nEtag: W/\"abc\"\r\nLink: <https://api.github.com/x?page=2>; rel=\"next\"\r\n\r\n[1]";
+        let response = parse_included_response(output).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.header("ETag"), Some("W/\"abc\""));
+        assert_eq!(
+            response.header("link").and_then(next_page_url).as_deref(),
+            Some("https://api.github.com/x?page=2")
+        );
+        assert_eq!(response.body, b"[1]");
+
+        let not_modified =
+            parse_included_response(b"HTTP/2.0 304 Not Modified\r\nEtag: x\r\n\r\n").unwrap();
+        assert_eq!(not_modified.status, 304);
+        assert!(not_modified.body.is_empty());
+        assert!(parse_included_response(b"gh: not found").is_none());
+    }
+
+    #[test]
+    fn test_with_per_page() {
+        assert_eq!(
+            with_per_page("repos/o/r/pulls/1/files"),
+            "repos/o/r/pulls/1/files?per_page=100"
+        );
+        assert_eq!(
+            with_per_page("repos/o/r/labels?a=1"),
+            "repos/o/r/labels?a=1&per_page=100"
+        );
+        assert_eq!(with_per_page("x?per_page=30"), "x?per_page=30");
+    }
+
+    #[test]
+    fn test_rest_client_urls() {
+        let client = RestClient::new("https://api.github.com/", "t".to_string()).unwrap();
+        assert_eq!(
+            client.url("repos/o/r/pulls/1"),
+            "https://api.github.com/repos/o/r/pulls/1"
+        );
+        assert_eq!(client.graphql_url(), "https://api.github.com/graphql");
+        assert_eq!(client.web_url(), "https://github.com");
+
+        let ghes = RestClient::new("https://ghe.example.com/api/v3", "t".to_string()).unwrap();
+        assert_eq!(ghes.graphql_url(), "https://ghe.example.com/api/graphql");
+        assert_eq!(ghes.web_url(), "https://ghe.example.com");
+    }
+
+    #[test]
+    fn test_repo_from_remotes() {
+        let remotes = "\
+upstream\tgit@github.com:ushironoko/octorus.git (fetch)
+origin\thttps://github.com/me/octorus (fetch)
+work\tssh://git@ghe.example.com:22/team/app.git (fetch)
+";
+        assert_eq!(
+            repo_from_remotes(remotes, "github.com").as_deref(),
+            Some("me/octorus")
+        );
+        assert_eq!(
+            repo_from_remotes(remotes, "ghe.example.com").as_deref(),
+            Some("team/app")
+        );
+        assert_eq!(repo_from_remotes(remotes, "gitlab.com"), None);
+        assert_eq!(
+            repo_from_remote_url("git@github.com:o/r.git", "github.com").as_deref(),
+            Some("o/r")
+        );
+        assert_eq!(repo_from_remote_url("git@github.com:o", "github.com"), None);
+    }
+
+    #[test]
+    fn test_error_message_prefers_api_message() {
+        let status = reqwest::StatusCode::NOT_FOUND;
+        assert_eq!(
+            error_message(status, r#"{"message": "Not Found"}"#),
+            "Not Found (404 Not Found)"
+        );
+        assert_eq!(error_message(status, ""), "404 Not Found");
+    }
+}
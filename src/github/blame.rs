@@ -0,0 +1,131 @@
+use anyhow::{Context, Result};
+
+use super::client::{gh_api_graphql, FieldValue};
+
+/// blame の 1 区間。同じコミットが最後に変更した連続した行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameRange {
+    /// 開始行（1 始まり、両端を含む）
+    pub start: u32,
+    pub end: u32,
+    pub sha: String,
+    /// コミットの author の名前（GitHub のユーザーに紐づけばログイン名）
+    pub author: String,
+    /// author の日時（RFC 3339）
+    pub date: String,
+}
+
+const BLAME_QUERY: &str = r#"
+query($owner: String!, $name: String!, $rev: String!, $path: String!) {
+  repository(owner: $owner, name: $name) {
+    object(expression: $rev) {
+      ... on Commit {
+        blame(path: $path) {
+          ranges {
+            startingLine
+            endingLine
+            commit { oid author { name date user { login } } }
+          }
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// `rev` 時点のファイルの blame を GitHub の GraphQL API から取得する
+pub async fn fetch_blame(repo: &str, rev: &str, path: &str) -> Result<Vec<BlameRange>> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let response = gh_api_graphql(
+        BLAME_QUERY,
+        &[
+            ("owner", FieldValue::String(owner)),
+            ("name", FieldValue::String(name)),
+            ("rev", FieldValue::String(rev)),
+            ("path", FieldValue::String(path)),
+        ],
+    )
+    .await?;
+    if let Some(errors) = response.get("errors") {
+        anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+    }
+    parse_blame(&response)
+}
+
+fn parse_blame(response: &serde_json::Value) -> Result<Vec<BlameRange>> {
+    let ranges = response
+        .pointer("/data/repository/object/blame/ranges")
+        .and_then(|v| v.as_array())
+        .context("Blame not found")?;
+    Ok(ranges
+        .iter()
+        .filter_map(|range| {
+            let commit = range.get("commit")?;
+            let author = commit
+                .pointer("/author/user/login")
+                .or_else(|| commit.pointer("/author/name"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default();
+            Some(BlameRange {
+                start: range.get("startingLine")?.as_u64()? as u32,
+                end: range.get("endingLine")?.as_u64()? as u32,
+                sha: commit.get("oid")?.as_str()?.to_string(),
+                author: author.to_string(),
+                date: commit
+                    .pointer("/author/date")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .to_string(),
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_blame_prefers_login() {
+        let response = json!({
+            "data": { "repository": { "object": { "blame": { "ranges": [
+                {
+                    "startingLine": 1,
+                    "endingLine": 3,
+                    "commit": {
+                        "oid": "abc",
+                        "author": {
+                            "name": "Alice Example",
+                            "date": "2024-01-01T00:00:00+09:00",
+                            "user": { "login": "alice" }
+                        }
+                    }
+                },
+                {
+                    "startingLine": 4,
+                    "endingLine": 4,
+                    "commit": {
+                        "oid": "def",
+                        "author": { "name": "Bob", "date": "2024-02-01T00:00:00Z", "user": null }
+                    }
+                }
+            ] } } } }
+        });
+        let ranges = parse_blame(&response).unwrap();
+        assert_eq!(ranges.len(), 2);
+        assert_eq!(ranges[0].author, "alice");
+        assert_eq!((ranges[0].start, ranges[0].end), (1, 3));
+        assert_eq!(ranges[1].author, "Bob");
+        assert_eq!(ranges[1].sha, "def");
+        assert_eq!(ranges[1].date, "2024-02-01T00:00:00Z");
+    }
+
+    #[test]
+    fn test_parse_blame_missing_object() {
+        let response = json!({ "data": { "repository": { "object": null } } });
+        assert!(parse_blame(&response).is_err());
+    }
+}
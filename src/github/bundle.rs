@@ -0,0 +1,543 @@
+//! PR を開くときのデータを GraphQL の 1 回の問い合わせでまとめて取得する
+//!
+//! REST では PR の情報・変更ファイル・viewed の状態・レビューコメント・レビュー・
+//! スレッドの状態・会話コメントをそれぞれ別に取得する必要があり、遅い回線では
+//! 最初の表示までの往復が積み重なる。ここではそれらを 1 つのクエリで取得する。
+//!
+//! GraphQL の変更ファイルには patch がないため、patch は呼び出し側で PR 全体の diff から補う。
+//! 変更ファイルは最初の 100 件だけを返し、残りは呼び出し側が [`fetch_pr_files_page`] で
+//! ページごとに取得する（数千ファイルの PR でも最初のページから表示できるようにするため）。
+//! コメント類がどれか 100 件を超える場合はコメントを返さず（[`PrBundle::comments`] が None）、
+//! 従来どおり REST で取得してもらう。
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::client::{gh_api_graphql, FieldValue};
+use super::comment::{
+    with_review_bodies, DiscussionComment, Review, ReviewComment, ReviewThreadState,
+};
+use super::pr::{Branch, ChangedFile, Label, PullRequest, User};
+
+const BUNDLE_QUERY: &str = r#"
+query PrBundle($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      id
+      number
+      title
+      body
+      state
+      isDraft
+      updatedAt
+      headRefName
+      headRefOid
+      baseRefName
+      baseRefOid
+      author { login }
+      labels(first: 100) { nodes { name } }
+      changedFiles
+      files(first: 100) {
+        nodes { path additions deletions changeType viewerViewedState }
+        pageInfo { hasNextPage endCursor }
+      }
+      reviewThreads(first: 100) {
+        nodes {
+          isResolved
+          isOutdated
+          comments(first: 100) {
+            nodes { databaseId path line startLine body createdAt author { login } }
+            pageInfo { hasNextPage endCursor }
+          }
+        }
+        pageInfo { hasNextPage endCursor }
+      }
+      reviews(first: 100) {
+        nodes { databaseId body state submittedAt author { login } }
+        pageInfo { hasNextPage endCursor }
+      }
+      comments(first: 100) {
+        nodes { id databaseId body createdAt updatedAt author { login } }
+        pageInfo { hasNextPage endCursor }
+      }
+    }
+  }
+}
+"#;
+
+/// 101 件目以降の変更ファイル
+const FILES_PAGE_QUERY: &str = r#"
+query PrBundleFiles($pullRequestId: ID!, $after: String) {
+  node(id: $pullRequestId) {
+    ... on PullRequest {
+      files(first: 100, after: $after) {
+        nodes { path additions deletions changeType viewerViewedState }
+        pageInfo { hasNextPage endCursor }
+      }
+    }
+  }
+}
+"#;
+
+/// PR を開くときに表示するコメント類
+#[derive(Debug, Clone, Default)]
+pub struct PrComments {
+    /// インラインコメントと本文付きのレビュー（作成日時順）
+    pub review_comments: Vec<ReviewComment>,
+    pub discussion_comments: Vec<DiscussionComment>,
+    pub thread_states: HashMap<u64, ReviewThreadState>,
+}
+
+/// 1 回の問い合わせで取得した PR のデータ
+#[derive(Debug, Clone)]
+pub struct PrBundle {
+    pub pr: PullRequest,
+    /// 最初のページの変更ファイル。patch はまだ入っていない（viewed は反映済み）
+    pub files: Vec<ChangedFile>,
+    /// 変更ファイルの総数
+    pub total_files: usize,
+    /// 続きのページのカーソル。1 ページに収まっていれば None
+    pub files_cursor: Option<String>,
+    /// 100 件を超えて取り切れなかった場合は None
+    pub comments: Option<PrComments>,
+}
+
+/// 変更ファイルの続きの 1 ページ
+#[derive(Debug, Clone)]
+pub struct FilesPage {
+    /// patch はまだ入っていない（viewed は反映済み）
+    pub files: Vec<ChangedFile>,
+    /// 次のページのカーソル。最後のページなら None
+    pub next: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PageInfo {
+    has_next_page: bool,
+    end_cursor: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Connection<T> {
+    nodes: Vec<T>,
+    #[serde(default)]
+    page_info: Option<PageInfo>,
+}
+
+impl<T> Connection<T> {
+    fn has_next_page(&self) -> bool {
+        self.page_info
+            .as_ref()
+            .is_some_and(|page_info| page_info.has_next_page)
+    }
+
+    fn next_cursor(&self) -> Option<String> {
+        self.page_info
+            .as_ref()
+            .filter(|page_info| page_info.has_next_page)
+            .and_then(|page_info| page_info.end_cursor.clone())
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Actor {
+    login: String,
+}
+
+/// 削除されたユーザーは author が null になる（REST の表示に合わせて ghost にする）
+fn user(actor: Option<Actor>) -> User {
+    User {
+        login: actor.map_or_else(|| "ghost".to_string(), |actor| actor.login),
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileNode {
+    path: String,
+    additions: u32,
+    deletions: u32,
+    change_type: String,
+    viewer_viewed_state: Option<String>,
+}
+
+impl FileNode {
+    fn into_changed_file(self) -> ChangedFile {
+        // REST の files API の status に合わせる
+        let status = match self.change_type.as_str() {
+            "ADDED" => "added",
+            "DELETED" => "removed",
+            "RENAMED" => "renamed",
+            "COPIED" => "copied",
+            "CHANGED" => "changed",
+            _ => "modified",
+        };
+        ChangedFile {
+            filename: self.path,
+            status: status.to_string(),
+            additions: self.additions,
+            deletions: self.deletions,
+            patch: None,
+            viewed: self.viewer_viewed_state.as_deref() == Some("VIEWED"),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadCommentNode {
+    database_id: u64,
+    path: String,
+    line: Option<u32>,
+    start_line: Option<u32>,
+    body: String,
+    created_at: String,
+    author: Option<Actor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadNode {
+    is_resolved: bool,
+    is_outdated: bool,
+    comments: Connection<ThreadCommentNode>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ReviewNode {
+    database_id: u64,
+    body: Option<String>,
+    state: String,
+    submitted_at: Option<String>,
+    author: Option<Actor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct IssueCommentNode {
+    id: String,
+    database_id: u64,
+    body: String,
+    created_at: String,
+    updated_at: Option<String>,
+    author: Option<Actor>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PullRequestNode {
+    id: String,
+    number: u32,
+    title: String,
+    body: Option<String>,
+    state: String,
+    #[serde(default)]
+    is_draft: bool,
+    updated_at: String,
+    head_ref_name: String,
+    head_ref_oid: String,
+    base_ref_name: String,
+    base_ref_oid: String,
+    author: Option<Actor>,
+    labels: Connection<Label>,
+    changed_files: usize,
+    files: Connection<FileNode>,
+    review_threads: Connection<ThreadNode>,
+    reviews: Connection<ReviewNode>,
+    comments: Connection<IssueCommentNode>,
+}
+
+impl PullRequestNode {
+    fn pull_request(&mut self) -> PullRequest {
+        PullRequest {
+            number: self.number,
+            node_id: Some(self.id.clone()),
+            title: std::mem::take(&mut self.title),
+            body: self.body.take(),
+            // REST と同じく merged も closed として扱う
+            state: match self.state.as_str() {
+                "OPEN" => "open".to_string(),
+                _ => "closed".to_string(),
+            },
+            head: Branch {
+                ref_name: std::mem::take(&mut self.head_ref_name),
+                sha: std::mem::take(&mut self.head_ref_oid),
+            },
+            base: Branch {
+                ref_name: std::mem::take(&mut self.base_ref_name),
+                sha: std::mem::take(&mut self.base_ref_oid),
+            },
+            user: user(self.author.take()),
+            updated_at: std::mem::take(&mut self.updated_at),
+            labels: std::mem::take(&mut self.labels.nodes),
+            draft: self.is_draft,
+        }
+    }
+
+    /// どの接続も 1 ページに収まっていればコメント類を組み立てる
+    fn comments(&mut self) -> Option<PrComments> {
+        let truncated = self.review_threads.has_next_page()
+            || self.reviews.has_next_page()
+            || self.comments.has_next_page()
+            || self
+                .review_threads
+                .nodes
+                .iter()
+                .any(|thread| thread.comments.has_next_page());
+        if truncated {
+            return None;
+        }
+
+        let mut inline = Vec::new();
+        let mut thread_states = HashMap::new();
+        for thread in std::mem::take(&mut self.review_threads.nodes) {
+            let thread_id = thread
+                .comments
+                .nodes
+                .first()
+                .map_or(0, |comment| comment.database_id);
+            let state = ReviewThreadState {
+                resolved: thread.is_resolved,
+                outdated: thread.is_outdated,
+                thread_id,
+            };
+            for comment in thread.comments.nodes {
+                thread_states.insert(comment.database_id, state);
+                inline.push(ReviewComment {
+                    id: comment.database_id,
+                    path: comment.path,
+                    line: comment.line,
+                    start_line: comment.start_line,
+                    body: comment.body,
+                    user: user(comment.author),
+                    created_at: comment.created_at,
+                });
+            }
+        }
+        let reviews = std::mem::take(&mut self.reviews.nodes)
+            .into_iter()
+            .map(|review| Review {
+                id: review.database_id,
+                body: review.body,
+                state: review.state,
+                user: user(review.author),
+                submitted_at: review.submitted_at,
+            })
+            .collect();
+        let discussion_comments = std::mem::take(&mut self.comments.nodes)
+            .into_iter()
+            .map(|comment| DiscussionComment {
+                id: comment.database_id,
+                node_id: comment.id,
+                body: comment.body,
+                user: user(comment.author),
+                created_at: comment.created_at,
+                updated_at: comment.updated_at,
+            })
+            .collect();
+
+        Some(PrComments {
+            review_comments: with_review_bodies(inline, reviews),
+            discussion_comments,
+            thread_states,
+        })
+    }
+}
+
+/// PR の情報・最初のページの変更ファイル（patch なし）・コメント類をまとめて取得する
+pub async fn fetch_pr_bundle(repo: &str, pr_number: u32) -> Result<PrBundle> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let number = pr_number.to_string();
+    let response = gh_api_graphql(
+        BUNDLE_QUERY,
+        &[
+            ("owner", FieldValue::String(owner)),
+            ("name", FieldValue::String(name)),
+            ("number", FieldValue::Raw(&number)),
+        ],
+    )
+    .await?;
+    let mut node = parse_bundle_response(response)?;
+
+    let pr = node.pull_request();
+    let comments = node.comments();
+    let files_cursor = node.files.next_cursor();
+    let files = node
+        .files
+        .nodes
+        .into_iter()
+        .map(FileNode::into_changed_file)
+        .collect();
+
+    Ok(PrBundle {
+        pr,
+        files,
+        total_files: node.changed_files,
+        files_cursor,
+        comments,
+    })
+}
+
+fn parse_bundle_response(mut response: serde_json::Value) -> Result<PullRequestNode> {
+    if let Some(errors) = response.get("errors") {
+        anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+    }
+    let node = response
+        .pointer_mut("/data/repository/pullRequest")
+        .map(serde_json::Value::take)
+        .filter(|node| !node.is_null())
+        .context("Pull request not found in GraphQL response")?;
+    serde_json::from_value(node).context("Failed to parse pull request GraphQL response")
+}
+
+/// [`PrBundle::files_cursor`]（または前のページの [`FilesPage::next`]）に続く変更ファイルを取得する
+pub async fn fetch_pr_files_page(pr_node_id: &str, cursor: &str) -> Result<FilesPage> {
+    let mut response = gh_api_graphql(
+        FILES_PAGE_QUERY,
+        &[
+            ("pullRequestId", FieldValue::String(pr_node_id)),
+            ("after", FieldValue::String(cursor)),
+        ],
+    )
+    .await?;
+    if let Some(errors) = response.get("errors") {
+        anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+    }
+    let files = response
+        .pointer_mut("/data/node/files")
+        .map(serde_json::Value::take)
+        .context("GitHub GraphQL response missing files")?;
+    let files: Connection<FileNode> =
+        serde_json::from_value(files).context("Failed to parse changed files GraphQL response")?;
+    Ok(FilesPage {
+        next: files.next_cursor(),
+        files: files
+            .nodes
+            .into_iter()
+            .map(FileNode::into_changed_file)
+            .collect(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn bundle_response(threads_have_more: bool) -> serde_json::Value {
+        json!({ "data": { "repository": { "pullRequest": {
+            "id": "PR_1",
+            "number": 7,
+            "title": "Add greeting",
+            "body": null,
+            "state": "MERGED",
+            "isDraft": true,
+            "updatedAt": "2024-01-02T00:00:00Z",
+            "headRefName": "feature",
+            "headRefOid": "abc",
+            "baseRefName": "main",
+            "baseRefOid": "def",
+            "author": null,
+            "labels": { "nodes": [{ "name": "bug" }] },
+            "changedFiles": 2,
+            "files": {
+                "nodes": [
+                    { "path": "src/lib.rs", "additions": 3, "deletions": 1,
+                      "changeType": "MODIFIED", "viewerViewedState": "VIEWED" },
+                    { "path": "old.rs", "additions": 0, "deletions": 4,
+                      "changeType": "DELETED", "viewerViewedState": "UNVIEWED" },
+                ],
+                "pageInfo": { "hasNextPage": false, "endCursor": null },
+            },
+            "reviewThreads": {
+                "nodes": [{
+                    "isResolved": true,
+                    "isOutdated": false,
+                    "comments": {
+                        "nodes": [
+                            { "databaseId": 11, "path": "src/lib.rs", "line": 2, "startLine": null,
+                              "body": "nit", "createdAt": "2024-01-01T00:00:02Z",
+                              "author": { "login": "reviewer" } },
+                            { "databaseId": 12, "path": "src/lib.rs", "line": 2, "startLine": null,
+                              "body": "done", "createdAt": "2024-01-01T00:00:03Z",
+                              "author": { "login": "author" } },
+                        ],
+                        "pageInfo": { "hasNextPage": false, "endCursor": null },
+                    },
+                }],
+                "pageInfo": { "hasNextPage": threads_have_more, "endCursor": "c" },
+            },
+            "reviews": {
+                "nodes": [
+                    { "databaseId": 21, "body": "Looks good", "state": "APPROVED",
+                      "submittedAt": "2024-01-01T00:00:01Z", "author": { "login": "reviewer" } },
+                    { "databaseId": 22, "body": "", "state": "COMMENTED",
+                      "submittedAt": "2024-01-01T00:00:04Z", "author": { "login": "reviewer" } },
+                ],
+                "pageInfo": { "hasNextPage": false, "endCursor": null },
+            },
+            "comments": {
+                "nodes": [
+                    { "id": "IC_1", "databaseId": 31, "body": "Thanks",
+                      "createdAt": "2024-01-01T00:00:00Z", "updatedAt": "2024-01-01T00:00:00Z",
+                      "author": { "login": "author" } },
+                ],
+                "pageInfo": { "hasNextPage": false, "endCursor": null },
+            },
+        } } } })
+    }
+
+    #[test]
+    fn test_bundle_maps_to_rest_shapes() {
+        let mut node = parse_bundle_response(bundle_response(false)).unwrap();
+        let pr = node.pull_request();
+        assert_eq!(pr.node_id.as_deref(), Some("PR_1"));
+        assert_eq!(pr.state, "closed");
+        assert_eq!(pr.user.login, "ghost");
+        assert_eq!(pr.head.sha, "abc");
+        assert_eq!(pr.labels[0].name, "bug");
+        assert!(pr.draft);
+
+        assert_eq!(node.changed_files, 2);
+        assert_eq!(node.files.next_cursor(), None);
+        let files: Vec<ChangedFile> = std::mem::take(&mut node.files.nodes)
+            .into_iter()
+            .map(FileNode::into_changed_file)
+            .collect();
+        assert_eq!(files[0].status, "modified");
+        assert!(files[0].viewed);
+        assert_eq!(files[1].status, "removed");
+        assert!(!files[1].viewed);
+
+        let comments = node.comments().unwrap();
+        // 本文のあるレビューだけを、インラインコメントと作成日時順に並べる
+        let ids: Vec<u64> = comments.review_comments.iter().map(|c| c.id).collect();
+        assert_eq!(ids, vec![21, 11, 12]);
+        assert_eq!(comments.review_comments[0].path, "[PR Review]");
+        assert_eq!(comments.thread_states[&12].thread_id, 11);
+        assert!(comments.thread_states[&12].resolved);
+        assert_eq!(comments.discussion_comments[0].node_id, "IC_1");
+        assert!(!comments.discussion_comments[0].is_edited());
+    }
+
+    #[test]
+    fn test_truncated_comments_are_left_to_rest() {
+        let mut node = parse_bundle_response(bundle_response(true)).unwrap();
+        assert!(node.comments().is_none());
+    }
+
+    #[test]
+    fn test_missing_pull_request_is_an_error() {
+        let response = json!({ "data": { "repository": { "pullRequest": null } } });
+        assert!(parse_bundle_response(response).is_err());
+        let response = json!({ "errors": [{ "message": "Could not resolve" }] });
+        assert!(parse_bundle_response(response).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::client::{gh_api, gh_api_text};
+
+/// ジョブのログの取得に使う Accept（ログ本体はリダイレクト先のプレーンテキスト）
+const LOG_ACCEPT: &str = "application/vnd.github+json";
+
+/// コミットに付いた CI のチェック（GitHub Actions ではジョブ 1 つ）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct CheckRun {
+    pub id: u64,
+    pub name: String,
+    /// `queued` / `in_progress` / `completed`
+    pub status: String,
+    /// 完了したときの結果（`success` / `failure` など）
+    pub conclusion: Option<String>,
+    pub details_url: Option<String>,
+}
+
+impl CheckRun {
+    /// 失敗したチェックか
+    pub fn is_failing(&self) -> bool {
+        matches!(
+            self.conclusion.as_deref(),
+            Some("failure" | "timed_out" | "cancelled" | "startup_failure")
+        )
+    }
+
+    /// GitHub Actions のジョブなら workflow run の ID
+    /// （`details_url` が `.../actions/runs/{run}/job/{job}` の形式）
+    pub fn actions_run_id(&self) -> Option<u64> {
+        let url = self.details_url.as_deref()?;
+        let (_, rest) = url.split_once("/actions/runs/")?;
+        let (run, job) = rest.split_once("/job/")?;
+        // ジョブの ID は check run の ID と同じ
+        let job = job.split(['/', '?', '#']).next()?;
+        if job.parse::<u64>().ok()? != self.id {
+            return None;
+        }
+        run.parse().ok()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CheckRunsResponse {
+    #[serde(default)]
+    check_runs: Vec<CheckRun>,
+}
+
+/// コミットの CI のチェックを取得（`GET /commits/{sha}/check-runs`、最大 100 件）。
+/// 失敗したものを先に並べる
+pub async fn fetch_check_runs(repo: &str, sha: &str) -> Result<Vec<CheckRun>> {
+    let endpoint = format!("repos/{}/commits/{}/check-runs?per_page=100", repo, sha);
+    parse_check_runs(gh_api(&endpoint).await?)
+}
+
+fn parse_check_runs(json: serde_json::Value) -> Result<Vec<CheckRun>> {
+    let response: CheckRunsResponse =
+        serde_json::from_value(json).context("Failed to parse check runs response")?;
+    let mut runs = response.check_runs;
+    runs.sort_by_key(|run| !run.is_failing());
+    Ok(runs)
+}
+
+/// GitHub Actions のジョブのログ（`GET /actions/jobs/{job_id}/logs`）
+pub async fn fetch_job_log(repo: &str, job_id: u64) -> Result<String> {
+    let endpoint = format!("repos/{}/actions/jobs/{}/logs", repo, job_id);
+    gh_api_text(&endpoint, Some(LOG_ACCEPT)).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_run(id: u64, conclusion: Option<&str>, details_url: Option<&str>) -> CheckRun {
+        CheckRun {
+            id,
+            name: "test".to_string(),
+            status: "completed".to_string(),
+            conclusion: conclusion.map(String::from),
+            details_url: details_url.map(String::from),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_fetch_job_log_uses_the_backend() {
+        let mock = crate::github::mock::MockBackend::install();
+        mock.add_text(
+            "repos/owner/repo/actions/jobs/456/logs",
+            LOG_ACCEPT,
+            "2024-01-01T00:00:01.1234567Z ##[error]boom\n",
+        );
+        let log = fetch_job_log("owner/repo", 456).await.unwrap();
+        assert!(log.ends_with("##[error]boom\n"));
+        assert!(fetch_job_log("owner/repo", 7).await.is_err());
+    }
+
+    #[test]
+    fn test_actions_run_id() {
+        let run = check_run(
+            456,
+            Some("failure"),
+            Some("https://github.com/owner/repo/actions/runs/123/job/456"),
+        );
+        assert_eq!(run.actions_run_id(), Some(123));
+
+        // GitHub Actions 以外（外部の CI）
+        let external = check_run(7, Some("failure"), Some("https://ci.example.com/builds/7"));
+        assert_eq!(external.actions_run_id(), None);
+        assert_eq!(check_run(1, None, None).actions_run_id(), None);
+    }
+
+    #[test]
+    fn test_parse_check_runs_puts_failures_first() {
+        let json = serde_json::json!({
+            "total_count": 3,
+            "check_runs": [
+                { "id": 1, "name": "lint", "status": "completed", "conclusion": "success",
+                  "details_url": null },
+                { "id": 2, "name": "test", "status": "completed", "conclusion": "failure",
+                  "details_url": "https://github.com/o/r/actions/runs/9/job/2" },
+                { "id": 3, "name": "build", "status": "in_progress", "conclusion": null,
+                  "details_url": null }
+            ]
+        });
+        let runs = parse_check_runs(json).unwrap();
+        let names: Vec<&str> = runs.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, ["test", "lint", "build"]);
+        assert!(runs[0].is_failing());
+        assert!(!runs[2].is_failing());
+    }
+}
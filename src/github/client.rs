@@ -1,7 +1,12 @@
 use anyhow::{Context, Result};
-use std::process::Command;
+use std::process::{Command, Output};
+use std::sync::OnceLock;
 use thiserror::Error;
 
+use super::backend::{backend, with_per_page, Conditional, GithubBackend};
+use crate::cache::{CachedResponse, ResponseCache};
+use crate::config::CacheConfig;
+
 #[derive(Debug, Error)]
 pub enum DetectRepoError {
     #[error("Not a git repository. Use --repo to specify.")]
@@ -12,8 +17,13 @@ pub enum DetectRepoError {
     GhError(String),
 }
 
-/// Detect the repository name from the current directory using `gh repo view`
+/// Detect the repository name from the current directory
 pub async fn detect_repo() -> std::result::Result<String, DetectRepoError> {
+    backend().detect_repo().await
+}
+
+/// Detect the repository name from the current directory using `gh repo view`
+pub(super) async fn detect_repo_with_gh() -> std::result::Result<String, DetectRepoError> {
     let result = tokio::task::spawn_blocking(|| {
         let output = Command::new("gh")
             .args([
@@ -68,69 +78,151 @@ pub async fn detect_repo() -> std::result::Result<String, DetectRepoError> {
 /// Execute gh CLI command and return stdout
 /// Uses spawn_blocking to avoid blocking the tokio runtime
 pub async fn gh_command(args: &[&str]) -> Result<String> {
+    let output = gh_output(args).await?;
+    if !output.status.success() {
+        return Err(gh_failure(&output));
+    }
+    // raw の diff やファイル内容は UTF-8 とは限らない
+    Ok(crate::encoding::decode_lossless(&output.stdout))
+}
+
+/// Execute gh CLI command and return its output whether or not it succeeded
+pub(super) async fn gh_output(args: &[&str]) -> Result<Output> {
     let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
 
     tokio::task::spawn_blocking(move || {
-        let output = Command::new("gh")
+        Command::new("gh")
             .args(&args)
             .output()
-            .context("Failed to execute gh CLI - is it installed?")?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            let stdout = String::from_utf8_lossy(&output.stdout);
-            let stderr_trimmed = stderr.trim();
-            let stdout_trimmed = stdout.trim();
-            // デバッグログには全文出力
-            tracing::debug!(
-                stderr = %stderr_trimmed,
-                stdout = %stdout_trimmed,
-                "gh command failed"
-            );
-            if stdout_trimmed.is_empty() {
-                anyhow::bail!("gh command failed: {}", stderr_trimmed);
-            } else {
-                // TUI 表示用に stdout を char 単位で 200 文字に制限
-                let truncated: String = stdout_trimmed.chars().take(200).collect();
-                let suffix = if stdout_trimmed.len() > truncated.len() {
-                    "..."
-                } else {
-                    ""
+            .context("Failed to execute gh CLI - is it installed?")
+    })
+    .await
+    .context("spawn_blocking task panicked")?
+}
+
+/// Build the error for a failed gh CLI command
+pub(super) fn gh_failure(output: &Output) -> anyhow::Error {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr_trimmed = stderr.trim();
+    let stdout_trimmed = stdout.trim();
+    super::rate_limit::record_error(&stderr);
+    // デバッグログには全文出力
+    tracing::debug!(
+        stderr = %stderr_trimmed,
+        stdout = %stdout_trimmed,
+        "gh command failed"
+    );
+    if stdout_trimmed.is_empty() {
+        anyhow::anyhow!("gh command failed: {}", stderr_trimmed)
+    } else {
+        // TUI 表示用に stdout を char 単位で 200 文字に制限
+        let truncated: String = stdout_trimmed.chars().take(200).collect();
+        let suffix = if stdout_trimmed.len() > truncated.len() {
+            "..."
+        } else {
+            ""
+        };
+        anyhow::anyhow!(
+            "gh command failed: {} ({}{})",
+            stderr_trimmed,
+            truncated,
+            suffix
+        )
+    }
+}
+
+static RESPONSE_CACHE: OnceLock<ResponseCache> = OnceLock::new();
+
+/// Revalidate GET responses with ETags and reuse the saved body when GitHub
+/// answers 304 Not Modified. Does nothing if `[cache]` is disabled.
+pub fn enable_response_cache(config: &CacheConfig) {
+    if let Some(cache) = ResponseCache::from_config(config) {
+        let _ = RESPONSE_CACHE.set(cache);
+    }
+}
+
+/// GET through the response cache: send the saved ETag and reuse the saved body on 304
+pub(super) async fn cached_get(
+    backend: &dyn GithubBackend,
+    cache: &ResponseCache,
+    endpoint: &str,
+    accept: Option<&str>,
+) -> Result<(String, Option<String>)> {
+    let saved = cache.load(endpoint, accept);
+    let etag = saved.as_ref().map(|saved| saved.etag.as_str());
+    match backend.get_conditional(endpoint, accept, etag).await? {
+        Conditional::NotModified => match saved {
+            Some(saved) => {
+                cache.touch(endpoint, accept);
+                Ok((saved.body, saved.next))
+            }
+            // 送っていない ETag で 304 は返らないが、念のため取り直す
+            None => Ok((backend.get(endpoint, accept).await?, None)),
+        },
+        Conditional::Modified { body, etag, next } => {
+            if let Some(etag) = etag {
+                let response = CachedResponse {
+                    etag,
+                    body: body.clone(),
+                    next: next.clone(),
                 };
-                anyhow::bail!(
-                    "gh command failed: {} ({}{})",
-                    stderr_trimmed,
-                    truncated,
-                    suffix
-                );
+                if let Err(e) = cache.store(endpoint, accept, &response) {
+                    tracing::debug!(error = %e, endpoint, "failed to save response cache");
+                }
             }
+            Ok((body, next))
         }
+    }
+}
 
-        String::from_utf8(output.stdout).context("gh output contains invalid UTF-8")
-    })
-    .await
-    .context("spawn_blocking task panicked")?
+/// Paginated GET through the response cache. Each page is revalidated separately,
+/// so only the pages that changed are downloaded again.
+pub(super) async fn cached_get_paginated(
+    backend: &dyn GithubBackend,
+    cache: &ResponseCache,
+    endpoint: &str,
+) -> Result<serde_json::Value> {
+    let mut pages = Vec::new();
+    let mut next = Some(with_per_page(endpoint));
+    while let Some(url) = next.take() {
+        let (body, next_url) = cached_get(backend, cache, &url, None).await?;
+        let page: serde_json::Value =
+            serde_json::from_str(&body).context("Failed to parse GitHub API paginated response")?;
+        pages.push(page);
+        next = next_url;
+    }
+    flatten_pages(pages)
 }
 
-/// Execute gh api command with JSON output
+/// Execute a GET request against the REST API and parse the JSON response
 pub async fn gh_api(endpoint: &str) -> Result<serde_json::Value> {
-    let output = gh_command(&["api", endpoint]).await?;
+    let output = gh_api_text(endpoint, None).await?;
     serde_json::from_str(&output).context("Failed to parse gh api response as JSON")
 }
 
-/// Execute gh api command with automatic pagination for array endpoints.
+/// Execute a GET request and return the body as-is (e.g. raw file contents or a diff).
+/// `accept` overrides the `Accept` header.
+pub async fn gh_api_text(endpoint: &str, accept: Option<&str>) -> Result<String> {
+    match RESPONSE_CACHE.get() {
+        Some(cache) => Ok(cached_get(backend(), cache, endpoint, accept).await?.0),
+        None => backend().get(endpoint, accept).await,
+    }
+}
+
+/// Execute a GET request with automatic pagination for array endpoints.
 /// Fetches all pages and merges into a single JSON array.
 /// Caller should include `per_page=100` in endpoint if desired.
 pub async fn gh_api_paginate(endpoint: &str) -> Result<serde_json::Value> {
-    let output = gh_command(&["api", "--paginate", "--slurp", endpoint]).await?;
-    let pages: Vec<serde_json::Value> =
-        serde_json::from_str(&output).context("Failed to parse gh api paginated response")?;
-    flatten_pages(pages)
+    match RESPONSE_CACHE.get() {
+        Some(cache) => cached_get_paginated(backend(), cache, endpoint).await,
+        None => backend().get_paginated(endpoint).await,
+    }
 }
 
 /// Flatten an array of JSON arrays (from --paginate --slurp) into a single array.
 /// Returns an error if any page is not a JSON array.
-fn flatten_pages(pages: Vec<serde_json::Value>) -> Result<serde_json::Value> {
+pub(super) fn flatten_pages(pages: Vec<serde_json::Value>) -> Result<serde_json::Value> {
     let mut result = Vec::new();
     for (i, page) in pages.iter().enumerate() {
         match page {
@@ -156,7 +248,7 @@ fn other_type_name(value: &serde_json::Value) -> &'static str {
     }
 }
 
-/// Field type for gh api command
+/// Field type for API requests (same key syntax as `gh api -f` / `-F`)
 pub enum FieldValue<'a> {
     /// String field (-f)
     String(&'a str),
@@ -164,64 +256,41 @@ pub enum FieldValue<'a> {
     Raw(&'a str),
 }
 
-/// Execute gh api with method and fields
+/// Execute a POST request with fields
 pub async fn gh_api_post(
     endpoint: &str,
     fields: &[(&str, FieldValue<'_>)],
 ) -> Result<serde_json::Value> {
-    let mut args = vec![
-        "api".to_string(),
-        "--method".to_string(),
-        "POST".to_string(),
-        endpoint.to_string(),
-    ];
-    for (key, value) in fields {
-        match value {
-            FieldValue::String(v) => {
-                args.push("-f".to_string());
-                args.push(format!("{}={}", key, v));
-            }
-            FieldValue::Raw(v) => {
-                args.push("-F".to_string());
-                args.push(format!("{}={}", key, v));
-            }
-        }
-    }
-    let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    tracing::debug!(args = ?args_refs, "gh api post");
-    let output = gh_command(&args_refs).await?;
-    serde_json::from_str(&output).context("Failed to parse gh api response as JSON")
+    backend().send("POST", endpoint, fields).await
 }
 
-/// Execute gh graphql API with query and variables.
+/// Execute a PATCH request with fields (e.g. editing a comment body)
+pub async fn gh_api_patch(
+    endpoint: &str,
+    fields: &[(&str, FieldValue<'_>)],
+) -> Result<serde_json::Value> {
+    backend().send("PATCH", endpoint, fields).await
+}
+
+/// Execute a DELETE request with fields (e.g. removing requested reviewers)
+pub async fn gh_api_delete(
+    endpoint: &str,
+    fields: &[(&str, FieldValue<'_>)],
+) -> Result<serde_json::Value> {
+    backend().send("DELETE", endpoint, fields).await
+}
+
+/// Execute a GraphQL query with variables.
 pub async fn gh_api_graphql(
     query: &str,
     fields: &[(&str, FieldValue<'_>)],
 ) -> Result<serde_json::Value> {
-    let mut args = vec![
-        "api".to_string(),
-        "graphql".to_string(),
-        "-f".to_string(),
-        format!("query={}", query),
-    ];
-
-    for (key, value) in fields {
-        match value {
-            FieldValue::String(v) => {
-                args.push("-f".to_string());
-                args.push(format!("{}={}", key, v));
-            }
-            FieldValue::Raw(v) => {
-                args.push("-F".to_string());
-                args.push(format!("{}={}", key, v));
-            }
-        }
-    }
+    backend().graphql(query, fields).await
+}
 
-    let args_refs: Vec<&str> = args.iter().map(|s| s.as_str()).collect();
-    tracing::debug!(args = ?args_refs, "gh api graphql");
-    let output = gh_command(&args_refs).await?;
-    serde_json::from_str(&output).context("Failed to parse gh graphql response as JSON")
+/// Open a pull request in the browser
+pub async fn open_pr_in_browser(repo: &str, pr_number: u32) -> Result<()> {
+    backend().open_in_browser(repo, pr_number).await
 }
 
 #[cfg(test)]
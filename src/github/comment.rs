@@ -1,9 +1,14 @@
+use std::collections::HashMap;
+
 use anyhow::{Context, Result};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-use super::client::{gh_api_paginate, gh_api_post, FieldValue};
-use super::pr::User;
+use super::client::{
+    gh_api, gh_api_graphql, gh_api_paginate, gh_api_patch, gh_api_post, FieldValue,
+};
+use super::pr::{ReviewAction, User};
 
 /// ジェネリックなfetch & parse関数（ページネーション対応）
 async fn fetch_and_parse<T: DeserializeOwned>(
@@ -19,6 +24,9 @@ pub struct ReviewComment {
     pub id: u64,
     pub path: String,
     pub line: Option<u32>,
+    /// 複数行のコメントの開始行（`line` が終了行）。1 行のコメントでは None
+    #[serde(default)]
+    pub start_line: Option<u32>,
     pub body: String,
     pub user: User,
     pub created_at: String,
@@ -36,9 +44,23 @@ pub async fn fetch_review_comments(repo: &str, pr_number: u32) -> Result<Vec<Rev
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DiscussionComment {
     pub id: u64,
+    /// GraphQL の ID（編集・削除の mutation に使う）
+    #[serde(default)]
+    pub node_id: String,
     pub body: String,
     pub user: User,
     pub created_at: String,
+    #[serde(default)]
+    pub updated_at: Option<String>,
+}
+
+impl DiscussionComment {
+    /// 投稿後に本文が編集されているか
+    pub fn is_edited(&self) -> bool {
+        self.updated_at
+            .as_deref()
+            .is_some_and(|updated| updated != self.created_at)
+    }
 }
 
 pub async fn fetch_discussion_comments(
@@ -52,6 +74,105 @@ pub async fn fetch_discussion_comments(
     .await
 }
 
+/// PR の会話タブにコメントを投稿する
+pub async fn create_discussion_comment(
+    repo: &str,
+    pr_number: u32,
+    body: &str,
+) -> Result<DiscussionComment> {
+    let endpoint = format!("repos/{}/issues/{}/comments", repo, pr_number);
+    let json = gh_api_post(&endpoint, &[("body", FieldValue::String(body))]).await?;
+    serde_json::from_value(json).context("Failed to parse created comment response")
+}
+
+/// Discussion コメントの本文を書き換える（タスクリストのチェック切り替えなど）
+pub async fn update_discussion_comment(
+    repo: &str,
+    comment_id: u64,
+    body: &str,
+) -> Result<DiscussionComment> {
+    let endpoint = format!("repos/{}/issues/comments/{}", repo, comment_id);
+    let json = gh_api_patch(&endpoint, &[("body", FieldValue::String(body))]).await?;
+    serde_json::from_value(json).context("Failed to parse updated comment response")
+}
+
+const UPDATE_ISSUE_COMMENT_MUTATION: &str = r#"
+mutation($id: ID!, $body: String!) {
+  updateIssueComment(input: { id: $id, body: $body }) {
+    issueComment {
+      body
+      lastEditedAt
+    }
+  }
+}
+"#;
+
+const DELETE_ISSUE_COMMENT_MUTATION: &str = r#"
+mutation($id: ID!) {
+  deleteIssueComment(input: { id: $id }) {
+    clientMutationId
+  }
+}
+"#;
+
+/// 編集後の Discussion コメント
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EditedComment {
+    pub body: String,
+    pub last_edited_at: Option<String>,
+}
+
+/// 自分の Discussion コメントを編集する
+pub async fn edit_discussion_comment(node_id: &str, body: &str) -> Result<EditedComment> {
+    let response = gh_api_graphql(
+        UPDATE_ISSUE_COMMENT_MUTATION,
+        &[
+            ("id", FieldValue::String(node_id)),
+            ("body", FieldValue::String(body)),
+        ],
+    )
+    .await?;
+    parse_edited_comment(&response)
+}
+
+fn parse_edited_comment(response: &Value) -> Result<EditedComment> {
+    if let Some(errors) = response.get("errors") {
+        anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+    }
+    let Some(comment) = response.pointer("/data/updateIssueComment/issueComment") else {
+        anyhow::bail!("GitHub GraphQL response missing issueComment");
+    };
+    Ok(EditedComment {
+        body: comment["body"]
+            .as_str()
+            .context("GitHub GraphQL response missing comment body")?
+            .to_string(),
+        last_edited_at: comment["lastEditedAt"].as_str().map(str::to_string),
+    })
+}
+
+/// 自分の Discussion コメントを削除する
+pub async fn delete_discussion_comment(node_id: &str) -> Result<()> {
+    let response = gh_api_graphql(
+        DELETE_ISSUE_COMMENT_MUTATION,
+        &[("id", FieldValue::String(node_id))],
+    )
+    .await?;
+    if let Some(errors) = response.get("errors") {
+        anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+    }
+    Ok(())
+}
+
+/// 認証中のユーザーのログイン名
+pub async fn fetch_viewer_login() -> Result<String> {
+    let json = gh_api("user").await?;
+    json["login"]
+        .as_str()
+        .map(str::to_string)
+        .context("Failed to read login from user response")
+}
+
 /// PR レビュー（全体コメント）
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Review {
@@ -70,6 +191,132 @@ pub async fn fetch_reviews(repo: &str, pr_number: u32) -> Result<Vec<Review>> {
     .await
 }
 
+/// インラインコメントに本文付きのレビューを `[PR Review]` として加え、作成日時順に並べる
+pub fn with_review_bodies(
+    mut comments: Vec<ReviewComment>,
+    reviews: Vec<Review>,
+) -> Vec<ReviewComment> {
+    for review in reviews {
+        let Some(body) = review.body.filter(|body| !body.trim().is_empty()) else {
+            continue;
+        };
+        comments.push(ReviewComment {
+            id: review.id,
+            path: "[PR Review]".to_string(),
+            line: None,
+            start_line: None,
+            body,
+            user: review.user,
+            created_at: review.submitted_at.unwrap_or_default(),
+        });
+    }
+    comments.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+    comments
+}
+
+/// レビューコメントが属するスレッドの状態
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ReviewThreadState {
+    pub resolved: bool,
+    /// スレッド作成後のコミットで対象行が変わっている
+    pub outdated: bool,
+    /// スレッドの最初のコメントの ID（同じスレッドのコメントを 1 つに数える）
+    pub thread_id: u64,
+}
+
+const REVIEW_THREADS_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewThreads(first: 100, after: $after) {
+        nodes {
+          isResolved
+          isOutdated
+          comments(first: 100) {
+            nodes {
+              databaseId
+            }
+          }
+        }
+        pageInfo {
+          hasNextPage
+          endCursor
+        }
+      }
+    }
+  }
+}
+"#;
+
+/// レビューコメントの ID → 所属スレッドの状態（REST のコメントには resolved / outdated がない）
+pub async fn fetch_review_thread_states(
+    repo: &str,
+    pr_number: u32,
+) -> Result<HashMap<u64, ReviewThreadState>> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let number = pr_number.to_string();
+    let mut states = HashMap::new();
+    let mut after: Option<String> = None;
+
+    loop {
+        let mut fields = vec![
+            ("owner", FieldValue::String(owner)),
+            ("name", FieldValue::String(name)),
+            ("number", FieldValue::Raw(&number)),
+        ];
+        if let Some(cursor) = after.as_deref() {
+            fields.push(("after", FieldValue::String(cursor)));
+        }
+        let response = gh_api_graphql(REVIEW_THREADS_QUERY, &fields).await?;
+        if let Some(errors) = response.get("errors") {
+            anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+        }
+
+        let next = parse_review_thread_states(&response, &mut states)?;
+        match next {
+            Some(cursor) => after = Some(cursor),
+            None => break,
+        }
+    }
+    Ok(states)
+}
+
+/// reviewThreads の 1 ページ分を `states` に追加し、次のページのカーソルを返す
+fn parse_review_thread_states(
+    response: &Value,
+    states: &mut HashMap<u64, ReviewThreadState>,
+) -> Result<Option<String>> {
+    let Some(threads) = response.pointer("/data/repository/pullRequest/reviewThreads") else {
+        anyhow::bail!("GitHub GraphQL response missing reviewThreads");
+    };
+    for thread in threads["nodes"].as_array().into_iter().flatten() {
+        let comments = thread.pointer("/comments/nodes").and_then(Value::as_array);
+        let ids: Vec<u64> = comments
+            .into_iter()
+            .flatten()
+            .filter_map(|c| c["databaseId"].as_u64())
+            .collect();
+        let state = ReviewThreadState {
+            resolved: thread["isResolved"].as_bool().unwrap_or(false),
+            outdated: thread["isOutdated"].as_bool().unwrap_or(false),
+            thread_id: ids.first().copied().unwrap_or_default(),
+        };
+        for id in ids {
+            states.insert(id, state);
+        }
+    }
+    let page_info = &threads["pageInfo"];
+    if page_info["hasNextPage"].as_bool() != Some(true) {
+        return Ok(None);
+    }
+    match page_info["endCursor"].as_str() {
+        Some(cursor) => Ok(Some(cursor.to_string())),
+        None => anyhow::bail!("GitHub GraphQL pageInfo missing endCursor"),
+    }
+}
+
 pub async fn create_review_comment(
     repo: &str,
     pr_number: u32,
@@ -147,3 +394,194 @@ pub async fn create_reply_comment(
     let json = gh_api_post(&endpoint, &[("body", FieldValue::String(body))]).await?;
     serde_json::from_value(json).context("Failed to parse reply comment response")
 }
+
+/// レビューにまとめて送信する下書きのインラインコメント
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DraftReviewComment {
+    pub path: String,
+    pub body: String,
+    /// patch 内オフセット（単一行コメントで使用）
+    pub position: u32,
+    /// 複数行コメントの開始行。None なら単一行コメント
+    pub start_line: Option<u32>,
+    /// コメント対象の行番号（新しいファイル側）
+    pub line: u32,
+}
+
+/// 下書きのインラインコメントを 1 つのレビューとして送信する。
+///
+/// `gh pr review` はインラインコメントを受け付けないため、
+/// `POST /pulls/{n}/reviews` に `comments[][...]` 形式でまとめて渡す。
+pub async fn submit_review_with_comments(
+    repo: &str,
+    pr_number: u32,
+    commit_id: &str,
+    action: ReviewAction,
+    body: &str,
+    comments: &[DraftReviewComment],
+) -> Result<()> {
+    let endpoint = format!("repos/{}/pulls/{}/reviews", repo, pr_number);
+    let fields = review_fields(commit_id, action, body, comments);
+    let fields: Vec<(&str, FieldValue<'_>)> = fields
+        .iter()
+        .map(|(key, value, raw)| {
+            let value = if *raw {
+                FieldValue::Raw(value)
+            } else {
+                FieldValue::String(value)
+            };
+            (*key, value)
+        })
+        .collect();
+    gh_api_post(&endpoint, &fields).await?;
+    Ok(())
+}
+
+/// レビュー作成 API のフィールド（キー, 値, `-F` で渡すか）。
+/// gh は同じキーが現れた時点で `comments[]` の次の要素に進むため、
+/// コメントごとにキーを重複させない
+fn review_fields(
+    commit_id: &str,
+    action: ReviewAction,
+    body: &str,
+    comments: &[DraftReviewComment],
+) -> Vec<(&'static str, String, bool)> {
+    let event = match action {
+        ReviewAction::Approve => "APPROVE",
+        ReviewAction::RequestChanges => "REQUEST_CHANGES",
+        ReviewAction::Comment => "COMMENT",
+    };
+    let mut fields = vec![
+        ("commit_id", commit_id.to_string(), false),
+        ("event", event.to_string(), false),
+        ("body", body.to_string(), false),
+    ];
+    for comment in comments {
+        fields.push(("comments[][path]", comment.path.clone(), false));
+        fields.push(("comments[][body]", comment.body.clone(), false));
+        match comment.start_line {
+            // NOTE: create_multiline_review_comment と同じく新しいファイル側の行を指定する
+            Some(start) => {
+                fields.push(("comments[][start_line]", start.to_string(), true));
+                fields.push(("comments[][line]", comment.line.to_string(), true));
+                fields.push(("comments[][start_side]", "RIGHT".to_string(), false));
+                fields.push(("comments[][side]", "RIGHT".to_string(), false));
+            }
+            None => fields.push(("comments[][position]", comment.position.to_string(), true)),
+        }
+    }
+    fields
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_review_thread_states() {
+        let response = serde_json::json!({
+            "data": {"repository": {"pullRequest": {"reviewThreads": {
+                "nodes": [
+                    {"isResolved": true, "isOutdated": false,
+                     "comments": {"nodes": [{"databaseId": 1}, {"databaseId": 2}]}},
+                    {"isResolved": false, "isOutdated": true,
+                     "comments": {"nodes": [{"databaseId": 3}]}}
+                ],
+                "pageInfo": {"hasNextPage": true, "endCursor": "abc"}
+            }}}}
+        });
+        let mut states = HashMap::new();
+        let next = parse_review_thread_states(&response, &mut states).unwrap();
+        assert_eq!(next.as_deref(), Some("abc"));
+        assert_eq!(states.len(), 3);
+        assert!(states[&2].resolved && !states[&2].outdated);
+        assert!(!states[&3].resolved && states[&3].outdated);
+        assert_eq!(states[&2].thread_id, 1);
+        assert_eq!(states[&3].thread_id, 3);
+
+        let missing = serde_json::json!({"data": {"repository": null}});
+        assert!(parse_review_thread_states(&missing, &mut states).is_err());
+    }
+
+    #[test]
+    fn test_parse_edited_comment() {
+        let response = serde_json::json!({
+            "data": {"updateIssueComment": {"issueComment": {
+                "body": "fixed typo",
+                "lastEditedAt": "2024-01-02T00:00:00Z"
+            }}}
+        });
+        assert_eq!(
+            parse_edited_comment(&response).unwrap(),
+            EditedComment {
+                body: "fixed typo".to_string(),
+                last_edited_at: Some("2024-01-02T00:00:00Z".to_string()),
+            }
+        );
+
+        let denied = serde_json::json!({
+            "data": {"updateIssueComment": null},
+            "errors": [{"message": "Resource not accessible"}]
+        });
+        assert!(parse_edited_comment(&denied).is_err());
+    }
+
+    #[test]
+    fn test_discussion_comment_edited_indicator() {
+        let json = serde_json::json!({
+            "id": 1,
+            "node_id": "IC_kwDO",
+            "body": "hi",
+            "user": {"login": "me"},
+            "created_at": "2024-01-01T00:00:00Z",
+            "updated_at": "2024-01-01T00:00:00Z"
+        });
+        let mut comment: DiscussionComment = serde_json::from_value(json).unwrap();
+        assert_eq!(comment.node_id, "IC_kwDO");
+        assert!(!comment.is_edited());
+        comment.updated_at = Some("2024-01-03T00:00:00Z".to_string());
+        assert!(comment.is_edited());
+    }
+
+    #[test]
+    fn test_review_fields_with_single_and_multiline_comments() {
+        let comments = vec![
+            DraftReviewComment {
+                path: "src/a.rs".to_string(),
+                body: "nit".to_string(),
+                position: 3,
+                start_line: None,
+                line: 10,
+            },
+            DraftReviewComment {
+                path: "src/b.rs".to_string(),
+                body: "extract this".to_string(),
+                position: 7,
+                start_line: Some(4),
+                line: 6,
+            },
+        ];
+        let fields = review_fields("abc123", ReviewAction::RequestChanges, "", &comments);
+        let fields: Vec<(&str, &str, bool)> = fields
+            .iter()
+            .map(|(k, v, raw)| (*k, v.as_str(), *raw))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![
+                ("commit_id", "abc123", false),
+                ("event", "REQUEST_CHANGES", false),
+                ("body", "", false),
+                ("comments[][path]", "src/a.rs", false),
+                ("comments[][body]", "nit", false),
+                ("comments[][position]", "3", true),
+                ("comments[][path]", "src/b.rs", false),
+                ("comments[][body]", "extract this", false),
+                ("comments[][start_line]", "4", true),
+                ("comments[][line]", "6", true),
+                ("comments[][start_side]", "RIGHT", false),
+                ("comments[][side]", "RIGHT", false),
+            ]
+        );
+    }
+}
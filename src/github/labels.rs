@@ -0,0 +1,88 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::client::{gh_api_delete, gh_api_paginate, gh_api_post, FieldValue};
+
+/// リポジトリのラベル
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct RepoLabel {
+    pub name: String,
+    /// 16 進の色（`#` なし）
+    #[serde(default)]
+    pub color: String,
+    #[serde(default)]
+    pub description: Option<String>,
+}
+
+/// リポジトリのラベル一覧（`GET /repos/{repo}/labels`）
+pub async fn fetch_repo_labels(repo: &str) -> Result<Vec<RepoLabel>> {
+    let endpoint = format!("repos/{}/labels?per_page=100", repo);
+    serde_json::from_value(gh_api_paginate(&endpoint).await?)
+        .context("Failed to parse labels response")
+}
+
+/// PR にラベルを追加する（`POST /issues/{n}/labels`）
+pub async fn add_labels(repo: &str, pr_number: u32, names: &[String]) -> Result<()> {
+    if names.is_empty() {
+        return Ok(());
+    }
+    let endpoint = format!("repos/{}/issues/{}/labels", repo, pr_number);
+    let fields: Vec<(&str, FieldValue<'_>)> = names
+        .iter()
+        .map(|name| ("labels[]", FieldValue::String(name)))
+        .collect();
+    gh_api_post(&endpoint, &fields).await?;
+    Ok(())
+}
+
+/// PR からラベルを外す（`DELETE /issues/{n}/labels/{name}`）
+pub async fn remove_label(repo: &str, pr_number: u32, name: &str) -> Result<()> {
+    let endpoint = format!(
+        "repos/{}/issues/{}/labels/{}",
+        repo,
+        pr_number,
+        encode_label(name)
+    );
+    gh_api_delete(&endpoint, &[]).await?;
+    Ok(())
+}
+
+/// ラベル名を URL のパスの 1 要素としてパーセントエンコードする
+fn encode_label(name: &str) -> String {
+    let mut encoded = String::with_capacity(name.len());
+    for byte in name.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_encode_label() {
+        assert_eq!(encode_label("bug"), "bug");
+        assert_eq!(encode_label("good first issue"), "good%20first%20issue");
+        assert_eq!(encode_label("area/ui"), "area%2Fui");
+        assert_eq!(encode_label("バグ"), "%E3%83%90%E3%82%B0");
+    }
+
+    #[test]
+    fn test_parse_repo_labels() {
+        let labels: Vec<RepoLabel> = serde_json::from_value(json!([
+            { "name": "bug", "color": "d73a4a", "description": "Something isn't working" },
+            { "name": "wip", "color": "ededed", "description": null }
+        ]))
+        .unwrap();
+        assert_eq!(labels[0].name, "bug");
+        assert_eq!(labels[0].color, "d73a4a");
+        assert_eq!(labels[1].description, None);
+    }
+}
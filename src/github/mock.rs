@@ -0,0 +1,1021 @@
+//! テスト用の GitHub バックエンド
+//!
+//! [`MockBackend::install`] でプロセスのバックエンドをモックに差し替えると、
+//! 以降の API 呼び出しは登録したフィクスチャを返し、書き込み系のリクエストは記録される。
+//! バックエンドはプロセスで 1 つなので、テストごとに別のリポジトリ名でフィクスチャを登録する。
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::{Mutex, OnceLock};
+
+use anyhow::{bail, Result};
+use async_trait::async_trait;
+use serde_json::{json, Value};
+
+use super::backend::{fields_to_json, Conditional, GithubBackend};
+use super::client::{DetectRepoError, FieldValue};
+use super::comment::{DiscussionComment, ReviewComment, ReviewThreadState};
+use super::pr::{Branch, ChangedFile, PullRequest, User};
+
+/// フィクスチャの日時
+const FIXTURE_TIME: &str = "2024-01-01T00:00:00Z";
+/// モックが認証中とみなすユーザー
+pub(crate) const VIEWER: &str = "octorus-tester";
+const DIFF_ACCEPT: &str = "application/vnd.github.v3.diff";
+/// GitHub が PR 全体の diff を返すファイル数の上限（超えると 406 になる）
+const MAX_DIFF_FILES: usize = 300;
+
+type GraphqlHandler = Box<dyn Fn(&str, &Value) -> Option<Value> + Send + Sync>;
+
+/// 記録したリクエスト。GraphQL は `endpoint` が `graphql` で、`body` に変数が入る
+#[derive(Debug, Clone)]
+pub(crate) struct MockRequest {
+    pub method: String,
+    pub endpoint: String,
+    pub body: Value,
+}
+
+#[derive(Default)]
+struct MockState {
+    /// `METHOD パス`（クエリ文字列を除く）→ レスポンス
+    json: HashMap<String, Value>,
+    /// (パス, Accept) → 本文
+    text: HashMap<(String, String), String>,
+    graphql: Vec<GraphqlHandler>,
+    /// `METHOD パス` → 次の 1 回だけ返すエラー
+    failures: HashMap<String, String>,
+    requests: Vec<MockRequest>,
+    next_id: u64,
+}
+
+/// 固定のフィクスチャを返す GitHub バックエンド
+#[derive(Default)]
+pub(crate) struct MockBackend {
+    state: Mutex<MockState>,
+}
+
+static MOCK: OnceLock<MockBackend> = OnceLock::new();
+
+fn path_of(endpoint: &str) -> &str {
+    let path = endpoint.split('?').next().unwrap_or(endpoint);
+    path.trim_start_matches('/')
+}
+
+/// `page` を指定した GET には、配列のフィクスチャからそのページだけを返す
+fn page_of(endpoint: &str, value: &Value) -> Value {
+    let query = |name: &str| {
+        endpoint
+            .split_once('?')?
+            .1
+            .split('&')
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))?
+            .parse::<usize>()
+            .ok()
+    };
+    match (query("page"), value.as_array()) {
+        (Some(page), Some(items)) => {
+            let per_page = query("per_page").unwrap_or(30);
+            let start = page.saturating_sub(1) * per_page;
+            json!(items.iter().skip(start).take(per_page).collect::<Vec<_>>())
+        }
+        _ => value.clone(),
+    }
+}
+
+fn key(method: &str, endpoint: &str) -> String {
+    format!("{} {}", method, path_of(endpoint))
+}
+
+impl MockBackend {
+    /// モックをプロセスのバックエンドにする。何度呼んでも同じモックを返す
+    pub(crate) fn install() -> &'static MockBackend {
+        MOCK.get_or_init(MockBackend::default)
+    }
+
+    pub(super) fn installed() -> Option<&'static MockBackend> {
+        MOCK.get()
+    }
+
+    fn state(&self) -> std::sync::MutexGuard<'_, MockState> {
+        // テストの panic でロックが壊れても、ほかのテストは続けられるようにする
+        self.state.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// GET（ページネーションを含む）のレスポンスを登録する
+    pub(crate) fn add_json(&self, endpoint: &str, value: Value) {
+        self.add_response("GET", endpoint, value);
+    }
+
+    /// `POST` などのレスポンスを登録する。登録がなければ送った内容に ID などを足して返す
+    pub(crate) fn add_response(&self, method: &str, endpoint: &str, value: Value) {
+        self.state().json.insert(key(method, endpoint), value);
+    }
+
+    /// `POST` などを次の 1 回だけ `error` で失敗させる（リクエストは記録しない）
+    pub(crate) fn fail_once(&self, method: &str, endpoint: &str, error: &str) {
+        self.state()
+            .failures
+            .insert(key(method, endpoint), error.to_string());
+    }
+
+    /// `Accept` を指定した GET の本文を登録する
+    pub(crate) fn add_text(&self, endpoint: &str, accept: &str, text: &str) {
+        self.state().text.insert(
+            (path_of(endpoint).to_string(), accept.to_string()),
+            text.to_string(),
+        );
+    }
+
+    /// GraphQL のハンドラを登録する。クエリと変数を受け取り、答えられなければ None を返す
+    pub(crate) fn add_graphql(
+        &self,
+        handler: impl Fn(&str, &Value) -> Option<Value> + Send + Sync + 'static,
+    ) {
+        self.state().graphql.push(Box::new(handler));
+    }
+
+    /// PR の取得に使うエンドポイントをまとめて登録する
+    pub(crate) fn add_pr(&self, fixture: &PrFixture) {
+        let base = format!("repos/{}/pulls/{}", fixture.repo, fixture.pr.number);
+        self.add_json(&base, json!(fixture.pr));
+        self.add_json(&format!("{}/files", base), json!(fixture.files));
+        if fixture.files.len() <= MAX_DIFF_FILES {
+            self.add_text(&base, DIFF_ACCEPT, &fixture.diff);
+        }
+        self.add_json(
+            &format!("{}/comments", base),
+            json!(fixture.review_comments),
+        );
+        self.add_json(&format!("{}/reviews", base), json!([]));
+        self.add_json(
+            &format!(
+                "repos/{}/issues/{}/comments",
+                fixture.repo, fixture.pr.number
+            ),
+            json!(fixture.discussion_comments),
+        );
+
+        let node_id = fixture.pr.node_id.clone().unwrap_or_default();
+        let viewed: Vec<Value> = fixture
+            .files
+            .iter()
+            .map(|file| {
+                let state = if fixture.viewed.contains(&file.filename) {
+                    "VIEWED"
+                } else {
+                    "UNVIEWED"
+                };
+                json!({ "path": file.filename, "viewerViewedState": state })
+            })
+            .collect();
+        let (owner, name) = fixture.repo.split_once('/').unwrap_or_default();
+        let (owner, name) = (owner.to_string(), name.to_string());
+        let number = fixture.pr.number;
+        let threads: Vec<Value> = fixture
+            .review_comments
+            .iter()
+            .map(|comment| {
+                let state = fixture
+                    .thread_states
+                    .get(&comment.id)
+                    .copied()
+                    .unwrap_or_default();
+                json!({
+                    "isResolved": state.resolved,
+                    "isOutdated": state.outdated,
+                    "comments": { "nodes": [{ "databaseId": comment.id }] },
+                })
+            })
+            .collect();
+        let bundle = bundle_pull_request(fixture);
+        let bundle_files: Vec<Value> = fixture
+            .files
+            .iter()
+            .map(|file| bundle_file(file, fixture.viewed.contains(&file.filename)))
+            .collect();
+        self.add_graphql(move |query, vars| {
+            // PR をまとめて取得するクエリ。変更ファイルは GitHub と同じく 100 件ずつ返す
+            if query.contains("query PrBundleFiles") && vars["pullRequestId"] == node_id.as_str() {
+                let offset = vars["after"].as_str()?.parse().ok()?;
+                return Some(json!({ "data": { "node": {
+                    "files": files_page(&bundle_files, offset),
+                } } }));
+            }
+            if query.contains("query PrBundle(") {
+                let same_pr = vars["owner"] == owner.as_str()
+                    && vars["name"] == name.as_str()
+                    && vars["number"] == number;
+                if !same_pr {
+                    return None;
+                }
+                let mut pull_request = bundle.clone();
+                pull_request["files"] = files_page(&bundle_files, 0);
+                return Some(json!({ "data": { "repository": { "pullRequest": pull_request } } }));
+            }
+            if query.contains("viewerViewedState") && vars["pullRequestId"] == node_id.as_str() {
+                return Some(json!({ "data": { "node": { "files": {
+                    "nodes": viewed,
+                    "pageInfo": { "hasNextPage": false, "endCursor": null },
+                } } } }));
+            }
+            let same_pr = vars["owner"] == owner.as_str()
+                && vars["name"] == name.as_str()
+                && vars["number"] == number;
+            if query.contains("reviewThreads") && same_pr {
+                return Some(
+                    json!({ "data": { "repository": { "pullRequest": { "reviewThreads": {
+                        "nodes": threads,
+                        "pageInfo": { "hasNextPage": false, "endCursor": null },
+                    } } } } }),
+                );
+            }
+            None
+        });
+    }
+
+    /// エンドポイントが `prefix` で始まる記録済みのリクエスト（古い順）
+    pub(crate) fn requests_to(&self, prefix: &str) -> Vec<MockRequest> {
+        self.state()
+            .requests
+            .iter()
+            .filter(|request| request.endpoint.starts_with(prefix))
+            .cloned()
+            .collect()
+    }
+
+    fn record(&self, method: &str, endpoint: &str, body: Value) -> u64 {
+        let mut state = self.state();
+        state.next_id += 1;
+        state.requests.push(MockRequest {
+            method: method.to_string(),
+            endpoint: path_of(endpoint).to_string(),
+            body,
+        });
+        state.next_id
+    }
+}
+
+#[async_trait]
+impl GithubBackend for MockBackend {
+    fn name(&self) -> &'static str {
+        "mock"
+    }
+
+    async fn get(&self, endpoint: &str, accept: Option<&str>) -> Result<String> {
+        let state = self.state();
+        let found = match accept {
+            Some(accept) => state
+                .text
+                .get(&(path_of(endpoint).to_string(), accept.to_string()))
+                .cloned(),
+            None => state
+                .json
+                .get(&key("GET", endpoint))
+                .map(|value| page_of(endpoint, value).to_string()),
+        };
+        match found {
+            Some(body) => Ok(body),
+            None => bail!("mock: no fixture for GET {}", endpoint),
+        }
+    }
+
+    /// 本文のハッシュを ETag にし、変わっていなければ 304 を返す。
+    /// 条件付き GET は `body` に送られた ETag と 304 かどうかを入れて記録する
+    async fn get_conditional(
+        &self,
+        endpoint: &str,
+        accept: Option<&str>,
+        etag: Option<&str>,
+    ) -> Result<Conditional> {
+        let body = self.get(endpoint, accept).await?;
+        let mut hasher = DefaultHasher::new();
+        body.hash(&mut hasher);
+        let current = format!("\"{:016x}\"", hasher.finish());
+        let not_modified = etag == Some(current.as_str());
+        self.record(
+            "GET",
+            endpoint,
+            json!({ "if_none_match": etag, "not_modified": not_modified }),
+        );
+        if not_modified {
+            return Ok(Conditional::NotModified);
+        }
+        Ok(Conditional::Modified {
+            body,
+            etag: Some(current),
+            next: None,
+        })
+    }
+
+    async fn get_paginated(&self, endpoint: &str) -> Result<Value> {
+        match self.state().json.get(&key("GET", endpoint)) {
+            Some(value) => Ok(value.clone()),
+            None => bail!("mock: no fixture for GET {}", endpoint),
+        }
+    }
+
+    async fn send(
+        &self,
+        method: &str,
+        endpoint: &str,
+        fields: &[(&str, FieldValue<'_>)],
+    ) -> Result<Value> {
+        let body = fields_to_json(fields)?;
+        if let Some(error) = self.state().failures.remove(&key(method, endpoint)) {
+            bail!("{}", error);
+        }
+        let id = self.record(method, endpoint, body.clone());
+        if let Some(response) = self.state().json.get(&key(method, endpoint)) {
+            return Ok(response.clone());
+        }
+        // 作成系の API と同じく、送った内容に ID・作成者・日時を足して返す
+        let mut response = body;
+        if let Value::Object(ref mut object) = response {
+            object.insert("id".to_string(), json!(id));
+            object.insert("node_id".to_string(), json!(format!("MOCK_{}", id)));
+            object.insert("user".to_string(), json!({ "login": VIEWER }));
+            object.insert("created_at".to_string(), json!(FIXTURE_TIME));
+        }
+        Ok(response)
+    }
+
+    async fn graphql(&self, query: &str, fields: &[(&str, FieldValue<'_>)]) -> Result<Value> {
+        let vars = fields_to_json(fields)?;
+        self.record(
+            "POST",
+            "graphql",
+            json!({ "query": query, "variables": vars }),
+        );
+        let state = self.state();
+        match state
+            .graphql
+            .iter()
+            .find_map(|handler| handler(query, &vars))
+        {
+            Some(response) => Ok(response),
+            None => bail!("mock: no fixture for GraphQL query"),
+        }
+    }
+
+    async fn detect_repo(&self) -> std::result::Result<String, DetectRepoError> {
+        Err(DetectRepoError::NotGitRepo)
+    }
+
+    async fn open_in_browser(&self, repo: &str, pr_number: u32) -> Result<()> {
+        self.record(
+            "OPEN",
+            &format!("repos/{}/pulls/{}", repo, pr_number),
+            Value::Null,
+        );
+        Ok(())
+    }
+}
+
+/// GraphQL のページの大きさ
+const GRAPHQL_PAGE_SIZE: usize = 100;
+
+fn connection(nodes: Vec<Value>) -> Value {
+    json!({ "nodes": nodes, "pageInfo": { "hasNextPage": false, "endCursor": null } })
+}
+
+/// `offset` 件目からの 1 ページ。カーソルは次のページの先頭の位置
+fn files_page(files: &[Value], offset: usize) -> Value {
+    let end = (offset + GRAPHQL_PAGE_SIZE).min(files.len());
+    let has_next_page = end < files.len();
+    json!({
+        "nodes": files.get(offset..end).unwrap_or_default(),
+        "pageInfo": {
+            "hasNextPage": has_next_page,
+            "endCursor": has_next_page.then(|| end.to_string()),
+        },
+    })
+}
+
+fn bundle_file(file: &ChangedFile, viewed: bool) -> Value {
+    let change_type = match file.status.as_str() {
+        "added" => "ADDED",
+        "removed" => "DELETED",
+        "renamed" => "RENAMED",
+        "copied" => "COPIED",
+        "changed" => "CHANGED",
+        _ => "MODIFIED",
+    };
+    json!({
+        "path": file.filename,
+        "additions": file.additions,
+        "deletions": file.deletions,
+        "changeType": change_type,
+        "viewerViewedState": if viewed { "VIEWED" } else { "UNVIEWED" },
+    })
+}
+
+/// PR をまとめて取得するクエリの `pullRequest`（変更ファイルを除く）
+fn bundle_pull_request(fixture: &PrFixture) -> Value {
+    let pr = &fixture.pr;
+    let threads = fixture
+        .review_comments
+        .iter()
+        .map(|comment| {
+            let state = fixture
+                .thread_states
+                .get(&comment.id)
+                .copied()
+                .unwrap_or_default();
+            json!({
+                "isResolved": state.resolved,
+                "isOutdated": state.outdated,
+                "comments": connection(vec![json!({
+                    "databaseId": comment.id,
+                    "path": comment.path,
+                    "line": comment.line,
+                    "startLine": comment.start_line,
+                    "body": comment.body,
+                    "createdAt": comment.created_at,
+                    "author": { "login": comment.user.login },
+                })]),
+            })
+        })
+        .collect();
+    let comments = fixture
+        .discussion_comments
+        .iter()
+        .map(|comment| {
+            json!({
+                "id": comment.node_id,
+                "databaseId": comment.id,
+                "body": comment.body,
+                "createdAt": comment.created_at,
+                "updatedAt": comment.updated_at,
+                "author": { "login": comment.user.login },
+            })
+        })
+        .collect();
+    json!({
+        "id": pr.node_id,
+        "number": pr.number,
+        "title": pr.title,
+        "body": pr.body,
+        "state": pr.state.to_uppercase(),
+        "isDraft": pr.draft,
+        "updatedAt": pr.updated_at,
+        "headRefName": pr.head.ref_name,
+        "headRefOid": pr.head.sha,
+        "baseRefName": pr.base.ref_name,
+        "baseRefOid": pr.base.sha,
+        "author": { "login": pr.user.login },
+        "changedFiles": fixture.files.len(),
+        "labels": connection(pr.labels.iter().map(|label| json!({ "name": label.name })).collect()),
+        "reviewThreads": connection(threads),
+        "reviews": connection(Vec::new()),
+        "comments": connection(comments),
+    })
+}
+
+/// モックに登録する PR
+#[derive(Debug, Clone)]
+pub(crate) struct PrFixture {
+    /// `owner/repo`
+    pub repo: String,
+    pub pr: PullRequest,
+    /// files API の結果。大きなファイルは GitHub と同じく patch を None にする
+    pub files: Vec<ChangedFile>,
+    /// PR 全体の diff（patch のないファイルの補完に使われる）。
+    /// GitHub と同じく [`MAX_DIFF_FILES`] を超える PR では取得できない
+    pub diff: String,
+    pub review_comments: Vec<ReviewComment>,
+    pub discussion_comments: Vec<DiscussionComment>,
+    /// コメント ID → スレッドの状態（なければ未解決で最新）
+    pub thread_states: HashMap<u64, ReviewThreadState>,
+    /// viewed にしたファイル
+    pub viewed: Vec<String>,
+}
+
+/// `lines` 行を追加するだけの patch
+pub(crate) fn added_lines_patch(lines: usize) -> String {
+    let mut patch = format!("@@ -0,0 +1,{} @@", lines);
+    for i in 1..=lines {
+        patch.push_str(&format!("\n+line {}", i));
+    }
+    patch
+}
+
+/// unified diff の 1 ファイル分
+fn file_diff(filename: &str, patch: &str) -> String {
+    format!(
+        "diff --git a/{0} b/{0}\n--- a/{0}\n+++ b/{0}\n{1}\n",
+        filename, patch
+    )
+}
+
+impl PrFixture {
+    pub(crate) fn new(repo: &str, number: u32, title: &str) -> Self {
+        let branch = |ref_name: &str, sha: &str| Branch {
+            ref_name: ref_name.to_string(),
+            sha: sha.to_string(),
+        };
+        Self {
+            repo: repo.to_string(),
+            pr: PullRequest {
+                number,
+                node_id: Some(format!("PR_{}_{}", repo, number)),
+                title: title.to_string(),
+                body: Some(format!("Fixture for {}", title)),
+                state: "open".to_string(),
+                head: branch("feature", "1111111111111111111111111111111111111111"),
+                base: branch("main", "0000000000000000000000000000000000000000"),
+                user: User {
+                    login: "author".to_string(),
+                },
+                updated_at: FIXTURE_TIME.to_string(),
+                labels: Vec::new(),
+                draft: false,
+            },
+            files: Vec::new(),
+            diff: String::new(),
+            review_comments: Vec::new(),
+            discussion_comments: Vec::new(),
+            thread_states: HashMap::new(),
+            viewed: Vec::new(),
+        }
+    }
+
+    /// 変更ファイルを足す。patch が None のファイルは diff にだけ載せる（`full_patch`）
+    pub(crate) fn file(
+        mut self,
+        filename: &str,
+        status: &str,
+        patch: Option<&str>,
+        full_patch: Option<&str>,
+    ) -> Self {
+        let counted = patch.or(full_patch).unwrap_or_default();
+        let count = |prefix: char| {
+            counted
+                .lines()
+                .filter(|line| line.starts_with(prefix))
+                .count() as u32
+        };
+        self.files.push(ChangedFile {
+            filename: filename.to_string(),
+            status: status.to_string(),
+            additions: count('+'),
+            deletions: count('-'),
+            patch: patch.map(str::to_string),
+            viewed: false,
+        });
+        if let Some(diff_patch) = patch.or(full_patch) {
+            self.diff.push_str(&file_diff(filename, diff_patch));
+        }
+        self
+    }
+
+    /// レビューコメントを足す。`line` が None のコメントは GitHub と同じく outdated 扱い
+    pub(crate) fn review_comment(
+        mut self,
+        id: u64,
+        path: &str,
+        line: Option<u32>,
+        body: &str,
+        state: ReviewThreadState,
+    ) -> Self {
+        self.review_comments.push(ReviewComment {
+            id,
+            path: path.to_string(),
+            line,
+            start_line: None,
+            body: body.to_string(),
+            user: User {
+                login: "reviewer".to_string(),
+            },
+            created_at: FIXTURE_TIME.to_string(),
+        });
+        self.thread_states.insert(id, state);
+        self
+    }
+}
+
+/// 2 ファイルの小さな PR
+pub(crate) fn small_pr(repo: &str, number: u32) -> PrFixture {
+    PrFixture::new(repo, number, "Add greeting")
+        .file(
+            "src/lib.rs",
+            "modified",
+            Some("@@ -1,2 +1,3 @@\n fn main() {\n+    greet();\n }"),
+            None,
+        )
+        .file(
+            "README.md",
+            "added",
+            Some("@@ -0,0 +1 @@\n+# Greeting"),
+            None,
+        )
+}
+
+/// `file_count` ファイルの大きな PR。
+/// 25 ファイルごとに files API が patch を返さない大きなファイルを混ぜる
+pub(crate) fn large_pr(repo: &str, number: u32, file_count: usize) -> PrFixture {
+    let mut fixture = PrFixture::new(repo, number, "Generate modules");
+    for i in 0..file_count {
+        let filename = format!("src/generated/module_{:03}.rs", i);
+        fixture = if i % 25 == 0 {
+            let full = added_lines_patch(400);
+            fixture.file(&filename, "added", None, Some(&full))
+        } else {
+            let patch = added_lines_patch(5);
+            fixture.file(&filename, "added", Some(&patch), None)
+        };
+    }
+    fixture
+}
+
+/// リネームを含む PR。中身の変わらないリネームには patch も diff もない
+pub(crate) fn renamed_pr(repo: &str, number: u32) -> PrFixture {
+    PrFixture::new(repo, number, "Move modules")
+        .file("src/new_name.rs", "renamed", None, None)
+        .file(
+            "src/edited_name.rs",
+            "renamed",
+            Some("@@ -1,2 +1,2 @@\n fn a() {}\n-fn b() {}\n+fn c() {}"),
+            None,
+        )
+}
+
+/// 最新・outdated・解決済みのレビューコメントがある PR
+pub(crate) fn outdated_comments_pr(repo: &str, number: u32) -> PrFixture {
+    small_pr(repo, number)
+        .review_comment(
+            101,
+            "src/lib.rs",
+            Some(2),
+            "Why greet here?",
+            ReviewThreadState::default(),
+        )
+        .review_comment(
+            102,
+            "src/lib.rs",
+            None,
+            "This line was removed later",
+            ReviewThreadState {
+                resolved: false,
+                outdated: true,
+                ..ReviewThreadState::default()
+            },
+        )
+        .review_comment(
+            103,
+            "README.md",
+            Some(1),
+            "Fixed, thanks",
+            ReviewThreadState {
+                resolved: true,
+                outdated: false,
+                ..ReviewThreadState::default()
+            },
+        )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::{Outbox, OutboxPayload};
+    use crate::github;
+    use crate::loader::{self, CommentSubmitResult, DataLoadResult, FetchMode};
+    use tokio::sync::mpsc;
+
+    /// PR を読み込み、一緒に送られたコメント類も返す。続きのページの変更ファイルもまとめる
+    async fn load_with_comments(
+        repo: &str,
+        number: u32,
+    ) -> (PullRequest, Vec<ChangedFile>, Option<github::PrComments>) {
+        let (tx, mut rx) = mpsc::channel(2);
+        tokio::spawn(loader::fetch_pr_data(
+            repo.to_string(),
+            number,
+            FetchMode::Fresh,
+            tx,
+        ));
+        let (pr, mut files) = match rx.recv().await.unwrap() {
+            DataLoadResult::Success { pr, files } => (*pr, files),
+            DataLoadResult::Comments { .. } => panic!("comments sent before the PR"),
+            DataLoadResult::FilePage(_) => panic!("file page sent before the PR"),
+            DataLoadResult::Error(e) => panic!("load failed: {}", e),
+        };
+        let mut comments = None;
+        while let Some(result) = rx.recv().await {
+            match result {
+                DataLoadResult::Comments {
+                    comments: loaded, ..
+                } => comments = Some(*loaded),
+                DataLoadResult::FilePage(page) => files.extend(page.files),
+                DataLoadResult::Success { .. } => panic!("PR sent twice"),
+                DataLoadResult::Error(e) => panic!("load failed: {}", e),
+            }
+        }
+        (pr, files, comments)
+    }
+
+    async fn load(repo: &str, number: u32) -> (PullRequest, Vec<ChangedFile>) {
+        let (pr, files, _) = load_with_comments(repo, number).await;
+        (pr, files)
+    }
+
+    #[tokio::test]
+    async fn test_loader_fetches_pr_and_comments_in_one_query() {
+        let mock = MockBackend::install();
+        let mut fixture = outdated_comments_pr("mock/bundle", 6);
+        fixture.viewed = vec!["README.md".to_string()];
+        mock.add_pr(&fixture);
+
+        let (pr, files, comments) = load_with_comments("mock/bundle", 6).await;
+        assert_eq!(pr.title, "Add greeting");
+        assert_eq!(pr.node_id, fixture.pr.node_id);
+        let loaded: Vec<(&str, &str, bool)> = files
+            .iter()
+            .map(|f| (f.filename.as_str(), f.status.as_str(), f.viewed))
+            .collect();
+        assert_eq!(
+            loaded,
+            vec![
+                ("src/lib.rs", "modified", false),
+                ("README.md", "added", true)
+            ]
+        );
+        // patch は diff から files API と同じ形で埋める
+        assert_eq!(files[0].patch, fixture.files[0].patch);
+
+        let comments = comments.expect("comments should come with the PR");
+        assert_eq!(comments.review_comments.len(), 3);
+        assert!(comments.thread_states[&102].outdated);
+        assert!(comments.thread_states[&103].resolved);
+
+        let queries: Vec<MockRequest> = mock
+            .requests_to("graphql")
+            .into_iter()
+            .filter(|request| request.body["variables"]["owner"] == "mock")
+            .filter(|request| request.body["variables"]["name"] == "bundle")
+            .collect();
+        assert_eq!(queries.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_loader_falls_back_to_rest_without_graphql() {
+        let mock = MockBackend::install();
+        let fixture = small_pr("mock/rest-only", 7);
+        // GraphQL のハンドラを登録せずに REST のフィクスチャだけを登録する
+        let base = "repos/mock/rest-only/pulls/7";
+        mock.add_json(base, json!(fixture.pr));
+        mock.add_json(&format!("{}/files", base), json!(fixture.files));
+
+        let (pr, files, comments) = load_with_comments("mock/rest-only", 7).await;
+        assert_eq!(pr.number, 7);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].patch, fixture.files[0].patch);
+        assert!(comments.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_loader_fills_large_files_from_diff() {
+        let mock = MockBackend::install();
+        let mut fixture = large_pr("mock/large", 1, 120);
+        fixture.viewed = vec!["src/generated/module_001.rs".to_string()];
+        mock.add_pr(&fixture);
+
+        let (pr, files) = load("mock/large", 1).await;
+        assert_eq!(pr.title, "Generate modules");
+        assert_eq!(files.len(), 120);
+        // files API に patch がないファイルは PR 全体の diff から補う
+        let large = &files[25];
+        assert!(large.patch.as_deref().unwrap().ends_with("+line 400"));
+        assert_eq!(large.additions, 400);
+        assert!(files.iter().all(|file| file.patch.is_some()));
+        assert!(files[1].viewed);
+        assert!(!files[2].viewed);
+    }
+
+    #[tokio::test]
+    async fn test_loader_streams_pages_of_huge_pr() {
+        let mock = MockBackend::install();
+        mock.add_pr(&large_pr("mock/huge", 8, 350));
+
+        let (tx, mut rx) = mpsc::channel(2);
+        tokio::spawn(loader::fetch_pr_data(
+            "mock/huge".to_string(),
+            8,
+            FetchMode::Fresh,
+            tx,
+        ));
+        // diff は大きすぎて取得できないので、最初のページを patch なしで先に送る
+        let mut files = match rx.recv().await.unwrap() {
+            DataLoadResult::Success { files, .. } => files,
+            _ => panic!("the first page should come first"),
+        };
+        assert_eq!(files.len(), 100);
+        assert!(files.iter().all(|file| file.patch.is_none()));
+
+        let mut pages = Vec::new();
+        while let Some(result) = rx.recv().await {
+            if let DataLoadResult::FilePage(page) = result {
+                assert_eq!(page.total, 350);
+                assert!(page.patches_pending);
+                pages.push((page.files.len(), page.loaded, page.done));
+                files.extend(page.files);
+            }
+        }
+        // 総数を知らせてから、残りのページを届いた順に送る
+        assert_eq!(
+            pages,
+            vec![
+                (0, 100, false),
+                (100, 200, false),
+                (100, 300, false),
+                (50, 350, true)
+            ]
+        );
+        assert_eq!(files.len(), 350);
+        assert_eq!(files[349].filename, "src/generated/module_349.rs");
+
+        // patch は選択したファイルを含むページだけを files API で取得する
+        let (tx, mut rx) = mpsc::channel(1);
+        loader::fetch_pr_patch_page("mock/huge".to_string(), 8, 4, tx).await;
+        let result = rx.recv().await.unwrap();
+        assert_eq!(result.page, 4);
+        let patches = result.patches.unwrap();
+        assert_eq!(patches.len(), 50);
+        assert_eq!(patches[1].filename, "src/generated/module_301.rs");
+        assert!(patches[1].patch.is_some());
+        // files API が patch を返さない大きなファイル
+        assert!(patches[0].patch.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_loader_keeps_pure_renames_without_patch() {
+        MockBackend::install().add_pr(&renamed_pr("mock/renamed", 2));
+
+        let (_, files) = load("mock/renamed", 2).await;
+        let statuses: Vec<(&str, &str, bool)> = files
+            .iter()
+            .map(|f| (f.filename.as_str(), f.status.as_str(), f.patch.is_some()))
+            .collect();
+        assert_eq!(
+            statuses,
+            vec![
+                ("src/new_name.rs", "renamed", false),
+                ("src/edited_name.rs", "renamed", true),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_response_cache_reuses_unchanged_pages() {
+        use super::super::client::{cached_get, cached_get_paginated};
+        use crate::cache::ResponseCache;
+        use std::time::Duration;
+
+        let mock = MockBackend::install();
+        let fixture = large_pr("mock/etag", 9, 3);
+        mock.add_pr(&fixture);
+        let root = tempfile::tempdir().unwrap();
+        let cache = ResponseCache::new(
+            root.path().to_path_buf(),
+            Duration::from_secs(3600),
+            u64::MAX,
+        );
+        let files = "repos/mock/etag/pulls/9/files";
+        let conditional = |mock: &MockBackend| -> Vec<(Value, Value)> {
+            mock.requests_to(files)
+                .into_iter()
+                .filter(|request| request.method == "GET")
+                .map(|request| {
+                    (
+                        request.body["if_none_match"].clone(),
+                        request.body["not_modified"].clone(),
+                    )
+                })
+                .collect()
+        };
+
+        let first = cached_get_paginated(mock, &cache, files).await.unwrap();
+        let second = cached_get_paginated(mock, &cache, files).await.unwrap();
+        assert_eq!(first, second);
+        assert_eq!(first.as_array().unwrap().len(), 3);
+        let requests = conditional(mock);
+        assert_eq!(requests.len(), 2);
+        // 2 回目は保存した ETag で問い合わせ、304 で保存した本文を使う
+        assert_eq!(requests[0], (Value::Null, json!(false)));
+        assert!(requests[1].0.is_string());
+        assert_eq!(requests[1].1, json!(true));
+
+        // 変わったページは取り直す
+        let mut changed = fixture.files.clone();
+        changed.truncate(1);
+        mock.add_json(files, json!(changed));
+        let third = cached_get_paginated(mock, &cache, files).await.unwrap();
+        assert_eq!(third.as_array().unwrap().len(), 1);
+        assert_eq!(conditional(mock)[2].1, json!(false));
+
+        let (body, _) = cached_get(mock, &cache, "repos/mock/etag/pulls/9", None)
+            .await
+            .unwrap();
+        let pr: PullRequest = serde_json::from_str(&body).unwrap();
+        assert_eq!(pr.number, 9);
+    }
+
+    #[tokio::test]
+    async fn test_mock_serves_comments_and_thread_states() {
+        MockBackend::install().add_pr(&outdated_comments_pr("mock/comments", 3));
+
+        let comments = github::comment::fetch_review_comments("mock/comments", 3)
+            .await
+            .unwrap();
+        assert_eq!(comments.len(), 3);
+        assert_eq!(comments[1].line, None);
+
+        let states = github::fetch_review_thread_states("mock/comments", 3)
+            .await
+            .unwrap();
+        assert!(states[&102].outdated);
+        assert!(states[&103].resolved);
+        assert!(!states[&101].resolved && !states[&101].outdated);
+        assert_eq!(states[&101].thread_id, 101);
+
+        assert!(github::fetch_review_thread_states("mock/other", 3)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_mock_records_writes() {
+        let mock = MockBackend::install();
+        mock.add_pr(&small_pr("mock/writes", 4));
+
+        let created =
+            github::create_review_comment("mock/writes", 4, "abc", "src/lib.rs", 2, "nit")
+                .await
+                .unwrap();
+        assert_eq!(created.body, "nit");
+        assert_eq!(created.user.login, VIEWER);
+        github::submit_review("mock/writes", 4, github::ReviewAction::Approve, "")
+            .await
+            .unwrap();
+
+        let requests = mock.requests_to("repos/mock/writes/");
+        assert_eq!(requests.len(), 2);
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].endpoint, "repos/mock/writes/pulls/4/comments");
+        assert_eq!(requests[0].body["position"], 2);
+        assert_eq!(requests[1].endpoint, "repos/mock/writes/pulls/4/reviews");
+        assert_eq!(requests[1].body["event"], "APPROVE");
+
+        assert!(github::fetch_pr("mock/missing", 1).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_retryable_failure_is_sent_again_from_outbox() {
+        let mock = MockBackend::install();
+        mock.add_pr(&small_pr("mock/outbox", 5));
+        let endpoint = "repos/mock/outbox/pulls/5/comments";
+        let payload = comment_payload("nit");
+
+        mock.fail_once("POST", endpoint, "error connecting to api.github.com");
+        let result = loader::submit_payload("mock/outbox", 5, &payload).await;
+        let CommentSubmitResult::Retryable { error, payload } =
+            CommentSubmitResult::new(result, payload)
+        else {
+            panic!("expected a retryable failure");
+        };
+        assert!(mock.requests_to(endpoint).is_empty());
+
+        let mut outbox = Outbox::default();
+        let id = outbox.push("mock/outbox", 5, payload, &error, 0).unwrap();
+        let (tx, mut rx) = mpsc::channel(1);
+        tokio::spawn(loader::retry_outbox(outbox.due(i64::MAX - 1), tx));
+        let sent = rx.recv().await.unwrap();
+        assert_eq!(sent.id, id);
+        assert!(sent.result.is_ok());
+        assert_eq!(mock.requests_to(endpoint)[0].body["body"], "nit");
+
+        // 内容の誤りは送信待ちに入れない
+        mock.fail_once(
+            "POST",
+            endpoint,
+            "Validation Failed (422 Unprocessable Entity)",
+        );
+        let payload = comment_payload("typo");
+        let result = loader::submit_payload("mock/outbox", 5, &payload).await;
+        assert!(matches!(
+            CommentSubmitResult::new(result, payload),
+            CommentSubmitResult::Error(_)
+        ));
+    }
+
+    fn comment_payload(body: &str) -> OutboxPayload {
+        OutboxPayload::Comment {
+            commit_id: "abc".to_string(),
+            path: "src/lib.rs".to_string(),
+            position: 2,
+            start_line: None,
+            line: 2,
+            body: body.to_string(),
+            suggestion: false,
+        }
+    }
+}
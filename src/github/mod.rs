@@ -1,12 +1,45 @@
+mod backend;
+mod blame;
+mod bundle;
+mod checks;
 mod client;
 pub mod comment;
+mod labels;
+#[cfg(test)]
+pub(crate) mod mock;
+mod notifications;
+mod overview;
+mod people;
 mod pr;
+mod rate_limit;
 
 // Explicit re-exports - only export what is actually used
-pub use client::{detect_repo, gh_command, DetectRepoError};
-pub use comment::{create_multiline_review_comment, create_reply_comment, create_review_comment};
+pub use backend::init_backend;
+pub use blame::{fetch_blame, BlameRange};
+pub use bundle::{fetch_pr_bundle, fetch_pr_files_page, PrBundle, PrComments};
+pub use checks::{fetch_check_runs, fetch_job_log, CheckRun};
+pub use client::{
+    detect_repo, enable_response_cache, gh_api, gh_api_patch, gh_api_text, gh_command,
+    open_pr_in_browser, DetectRepoError, FieldValue,
+};
+pub use comment::{
+    create_discussion_comment, create_multiline_review_comment, create_reply_comment,
+    create_review_comment, fetch_review_thread_states, submit_review_with_comments,
+    DraftReviewComment, ReviewThreadState,
+};
+pub use labels::{add_labels, fetch_repo_labels, remove_label, RepoLabel};
+pub use notifications::{fetch_pr_notifications, mark_notification_read, Notification};
+pub use overview::{fetch_pr_overview, LinkedIssue, PrOverview, ReviewerStatus};
+pub use people::{
+    add_assignee, fetch_assignable_users, fetch_pr_people, fetch_user_info, remove_assignee,
+    remove_requested_reviewer, request_reviewer, PrPeople, UserInfo, UserPr,
+};
 pub use pr::{
-    fetch_changed_files, fetch_files_viewed_state, fetch_pr, fetch_pr_diff, fetch_pr_list,
-    fetch_pr_list_with_offset, mark_file_as_viewed, submit_review, unmark_file_as_viewed, Branch,
-    ChangedFile, Label, PrListPage, PrStateFilter, PullRequest, PullRequestSummary, User,
+    create_pull_request, fetch_changed_files, fetch_changed_files_page, fetch_commit_files,
+    fetch_file_commits, fetch_files_viewed_state, fetch_pr, fetch_pr_commits, fetch_pr_diff,
+    fetch_pr_list, fetch_pr_list_with_offset, fetch_requested_reviewers, fetch_review_coverage,
+    find_open_pr_for_branch, mark_file_as_viewed, set_pr_draft, submit_review,
+    unmark_file_as_viewed, Branch, ChangedFile, Label, PrCommit, PrListPage, PrStateFilter,
+    PullRequest, PullRequestSummary, ReviewAction, ReviewCoverage, User, COVERAGE_BATCH_SIZE,
 };
+pub use rate_limit::{current_rate_limit, is_rate_limit_error, rate_limited_interval, RateLimit};
@@ -0,0 +1,152 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::client::{gh_api_paginate, gh_api_patch};
+
+/// PR に関する通知
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notification {
+    /// 通知のスレッド ID（既読にするときに使う）
+    pub id: String,
+    /// `owner/repo`
+    pub repo: String,
+    pub pr_number: u32,
+    pub title: String,
+    /// 通知の理由（`review_requested`・`mention`・`author` など）
+    pub reason: String,
+    pub unread: bool,
+    pub updated_at: String,
+}
+
+impl Notification {
+    /// レビュー依頼の通知か
+    pub fn is_review_request(&self) -> bool {
+        self.reason == "review_requested"
+    }
+}
+
+#[derive(Deserialize)]
+struct ThreadResponse {
+    id: String,
+    reason: String,
+    unread: bool,
+    updated_at: String,
+    subject: SubjectResponse,
+    repository: RepositoryResponse,
+}
+
+#[derive(Deserialize)]
+struct SubjectResponse {
+    title: String,
+    #[serde(default)]
+    url: Option<String>,
+    #[serde(rename = "type")]
+    kind: String,
+}
+
+#[derive(Deserialize)]
+struct RepositoryResponse {
+    full_name: String,
+}
+
+/// 自分宛ての通知のうち PR に関するもの（`GET /notifications`）。新しい順
+pub async fn fetch_pr_notifications() -> Result<Vec<Notification>> {
+    let response = gh_api_paginate("notifications?per_page=50").await?;
+    parse_notifications(response)
+}
+
+fn parse_notifications(response: serde_json::Value) -> Result<Vec<Notification>> {
+    let threads: Vec<ThreadResponse> =
+        serde_json::from_value(response).context("Failed to parse notifications response")?;
+    let mut notifications: Vec<Notification> = threads
+        .into_iter()
+        .filter(|thread| thread.subject.kind == "PullRequest")
+        .filter_map(|thread| {
+            // subject.url は `https://api.github.com/repos/{owner}/{repo}/pulls/{n}`
+            let pr_number = thread
+                .subject
+                .url
+                .as_deref()?
+                .rsplit_once("/pulls/")?
+                .1
+                .parse()
+                .ok()?;
+            Some(Notification {
+                id: thread.id,
+                repo: thread.repository.full_name,
+                pr_number,
+                title: thread.subject.title,
+                reason: thread.reason,
+                unread: thread.unread,
+                updated_at: thread.updated_at,
+            })
+        })
+        .collect();
+    notifications.sort_by(|a, b| b.updated_at.cmp(&a.updated_at));
+    Ok(notifications)
+}
+
+/// 通知を既読にする（`PATCH /notifications/threads/{id}`）
+pub async fn mark_notification_read(thread_id: &str) -> Result<()> {
+    gh_api_patch(&format!("notifications/threads/{}", thread_id), &[]).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn thread(
+        id: &str,
+        kind: &str,
+        url: Option<&str>,
+        reason: &str,
+        updated_at: &str,
+    ) -> serde_json::Value {
+        json!({
+            "id": id,
+            "reason": reason,
+            "unread": true,
+            "updated_at": updated_at,
+            "subject": { "title": format!("Thread {}", id), "url": url, "type": kind },
+            "repository": { "full_name": "owner/repo" },
+        })
+    }
+
+    #[test]
+    fn test_parse_notifications_keeps_prs_newest_first() {
+        let response = json!([
+            thread(
+                "1",
+                "PullRequest",
+                Some("https://api.github.com/repos/owner/repo/pulls/12"),
+                "review_requested",
+                "2024-01-01T00:00:00Z",
+            ),
+            thread(
+                "2",
+                "Issue",
+                Some("https://api.github.com/repos/owner/repo/issues/3"),
+                "mention",
+                "2024-01-03T00:00:00Z",
+            ),
+            thread(
+                "3",
+                "PullRequest",
+                Some("https://api.github.com/repos/owner/repo/pulls/40"),
+                "mention",
+                "2024-01-02T00:00:00Z",
+            ),
+            thread("4", "PullRequest", None, "author", "2024-01-04T00:00:00Z"),
+        ]);
+        let notifications = parse_notifications(response).unwrap();
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications[0].id, "3");
+        assert_eq!(notifications[0].pr_number, 40);
+        assert!(!notifications[0].is_review_request());
+        assert_eq!(notifications[1].repo, "owner/repo");
+        assert_eq!(notifications[1].pr_number, 12);
+        assert!(notifications[1].is_review_request());
+    }
+}
@@ -0,0 +1,181 @@
+use anyhow::{Context, Result};
+
+use super::client::{gh_api_graphql, FieldValue};
+
+/// PR の概要画面に表示する、PR 本体（[`super::PullRequest`]）にない情報
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrOverview {
+    /// レビュアーとその状態（依頼中のユーザー・チームと、レビュー済みのユーザー）
+    pub reviewers: Vec<ReviewerStatus>,
+    /// マージすると閉じる Issue
+    pub linked_issues: Vec<LinkedIssue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReviewerStatus {
+    /// ユーザーのログイン名、またはチームの `org/slug`
+    pub name: String,
+    /// `REQUESTED` / `APPROVED` / `CHANGES_REQUESTED` / `COMMENTED` / `DISMISSED`
+    pub state: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LinkedIssue {
+    pub number: u32,
+    pub title: String,
+    /// `OPEN` / `CLOSED`
+    pub state: String,
+}
+
+const PR_OVERVIEW_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      reviewRequests(first: 100) {
+        nodes {
+          requestedReviewer {
+            ... on User { login }
+            ... on Team { combinedSlug }
+          }
+        }
+      }
+      latestReviews(first: 100) { nodes { state author { login } } }
+      closingIssuesReferences(first: 50) { nodes { number title state } }
+    }
+  }
+}
+"#;
+
+/// PR のレビュアーの状態とリンクされた Issue を取得
+pub async fn fetch_pr_overview(repo: &str, pr_number: u32) -> Result<PrOverview> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let number = pr_number.to_string();
+    let response = gh_api_graphql(
+        PR_OVERVIEW_QUERY,
+        &[
+            ("owner", FieldValue::String(owner)),
+            ("name", FieldValue::String(name)),
+            ("number", FieldValue::Raw(&number)),
+        ],
+    )
+    .await?;
+    if let Some(errors) = response.get("errors") {
+        anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+    }
+    parse_pr_overview(&response)
+}
+
+fn parse_pr_overview(response: &serde_json::Value) -> Result<PrOverview> {
+    let pr = response
+        .pointer("/data/repository/pullRequest")
+        .filter(|pr| !pr.is_null())
+        .context("Pull request not found")?;
+    let nodes = |pointer: &str| {
+        pr.pointer(pointer)
+            .and_then(|v| v.as_array())
+            .cloned()
+            .unwrap_or_default()
+    };
+
+    // 依頼中のレビュアーを先に、再依頼されていないレビュー済みのユーザーを後に並べる
+    let mut reviewers: Vec<ReviewerStatus> = nodes("/reviewRequests/nodes")
+        .iter()
+        .filter_map(|node| {
+            let reviewer = node.get("requestedReviewer")?;
+            let name = reviewer
+                .get("login")
+                .or_else(|| reviewer.get("combinedSlug"))?
+                .as_str()?;
+            Some(ReviewerStatus {
+                name: name.to_string(),
+                state: "REQUESTED".to_string(),
+            })
+        })
+        .collect();
+    for node in nodes("/latestReviews/nodes") {
+        let (Some(login), Some(state)) = (
+            node.pointer("/author/login").and_then(|v| v.as_str()),
+            node.get("state").and_then(|v| v.as_str()),
+        ) else {
+            continue;
+        };
+        if !reviewers.iter().any(|r| r.name == login) {
+            reviewers.push(ReviewerStatus {
+                name: login.to_string(),
+                state: state.to_string(),
+            });
+        }
+    }
+
+    let linked_issues = nodes("/closingIssuesReferences/nodes")
+        .iter()
+        .filter_map(|node| {
+            Some(LinkedIssue {
+                number: u32::try_from(node.get("number")?.as_u64()?).ok()?,
+                title: node.get("title")?.as_str()?.to_string(),
+                state: node.get("state")?.as_str()?.to_string(),
+            })
+        })
+        .collect();
+
+    Ok(PrOverview {
+        reviewers,
+        linked_issues,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_pr_overview() {
+        let response = json!({
+            "data": { "repository": { "pullRequest": {
+                "reviewRequests": { "nodes": [
+                    { "requestedReviewer": { "login": "bob" } },
+                    { "requestedReviewer": { "combinedSlug": "acme/core" } },
+                    // 権限のないチームは空になる
+                    { "requestedReviewer": {} }
+                ] },
+                "latestReviews": { "nodes": [
+                    { "state": "APPROVED", "author": { "login": "alice" } },
+                    // 再依頼されたユーザーは依頼中として表示する
+                    { "state": "CHANGES_REQUESTED", "author": { "login": "bob" } },
+                    { "state": "COMMENTED", "author": null }
+                ] },
+                "closingIssuesReferences": { "nodes": [
+                    { "number": 12, "title": "Crash on empty diff", "state": "OPEN" }
+                ] }
+            } } }
+        });
+        let status = |name: &str, state: &str| ReviewerStatus {
+            name: name.into(),
+            state: state.into(),
+        };
+        assert_eq!(
+            parse_pr_overview(&response).unwrap(),
+            PrOverview {
+                reviewers: vec![
+                    status("bob", "REQUESTED"),
+                    status("acme/core", "REQUESTED"),
+                    status("alice", "APPROVED"),
+                ],
+                linked_issues: vec![LinkedIssue {
+                    number: 12,
+                    title: "Crash on empty diff".into(),
+                    state: "OPEN".into(),
+                }],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_pr_overview_missing_pr() {
+        let response = json!({ "data": { "repository": { "pullRequest": null } } });
+        assert!(parse_pr_overview(&response).is_err());
+    }
+}
@@ -0,0 +1,324 @@
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::client::{gh_api_delete, gh_api_graphql, gh_api_paginate, gh_api_post, FieldValue};
+
+/// PR のレビュアー・アサイニーの現状
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrPeople {
+    /// GitHub が提案するレビュアー（変更箇所を触ったことのある人）
+    pub suggested_reviewers: Vec<String>,
+    /// レビュー依頼中のユーザー（チームは含まない）
+    pub requested_reviewers: Vec<String>,
+    pub assignees: Vec<String>,
+}
+
+const PR_PEOPLE_QUERY: &str = r#"
+query($owner: String!, $name: String!, $number: Int!) {
+  repository(owner: $owner, name: $name) {
+    pullRequest(number: $number) {
+      suggestedReviewers { reviewer { login } }
+      reviewRequests(first: 100) {
+        nodes { requestedReviewer { ... on User { login } } }
+      }
+      assignees(first: 100) { nodes { login } }
+    }
+  }
+}
+"#;
+
+/// 提案されたレビュアー・レビュー依頼中のユーザー・アサイニーを取得
+pub async fn fetch_pr_people(repo: &str, pr_number: u32) -> Result<PrPeople> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let number = pr_number.to_string();
+    let response = gh_api_graphql(
+        PR_PEOPLE_QUERY,
+        &[
+            ("owner", FieldValue::String(owner)),
+            ("name", FieldValue::String(name)),
+            ("number", FieldValue::Raw(&number)),
+        ],
+    )
+    .await?;
+    if let Some(errors) = response.get("errors") {
+        anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+    }
+    parse_pr_people(&response)
+}
+
+fn parse_pr_people(response: &serde_json::Value) -> Result<PrPeople> {
+    let pr = response
+        .pointer("/data/repository/pullRequest")
+        .filter(|pr| !pr.is_null())
+        .context("Pull request not found")?;
+    let logins = |pointer: &str, login: &str| -> Vec<String> {
+        pr.pointer(pointer)
+            .and_then(|v| v.as_array())
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .filter_map(|node| node.pointer(login)?.as_str())
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    Ok(PrPeople {
+        suggested_reviewers: logins("/suggestedReviewers", "/reviewer/login"),
+        requested_reviewers: logins("/reviewRequests/nodes", "/requestedReviewer/login"),
+        assignees: logins("/assignees/nodes", "/login"),
+    })
+}
+
+/// ユーザー情報のポップアップに表示する内容
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct UserInfo {
+    pub login: String,
+    pub name: Option<String>,
+    pub company: Option<String>,
+    /// リポジトリでの権限（`ADMIN` / `MAINTAIN` / `WRITE` / `TRIAGE` / `READ`）。
+    /// 閲覧者に push 権限がないと取得できないため None になる
+    pub permission: Option<String>,
+    /// このリポジトリで作成した PR の数
+    pub pr_count: u64,
+    /// このリポジトリで最近更新された PR
+    pub recent_prs: Vec<UserPr>,
+}
+
+impl UserInfo {
+    /// リポジトリに書き込める（メンテナー）か
+    pub fn is_maintainer(&self) -> bool {
+        matches!(
+            self.permission.as_deref(),
+            Some("ADMIN" | "MAINTAIN" | "WRITE")
+        )
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UserPr {
+    pub number: u32,
+    pub title: String,
+    /// `OPEN` / `CLOSED` / `MERGED`
+    pub state: String,
+}
+
+const USER_INFO_QUERY: &str = r#"
+query($owner: String!, $name: String!, $login: String!, $search: String!) {
+  user(login: $login) { login name company }
+  repository(owner: $owner, name: $name) {
+    collaborators(query: $login, first: 10) { edges { permission node { login } } }
+  }
+  search(query: $search, type: ISSUE, first: 5) {
+    issueCount
+    nodes { ... on PullRequest { number title state } }
+  }
+}
+"#;
+
+/// ユーザーのプロフィールとこのリポジトリでの権限・最近の PR を取得する。
+/// bot のアカウントや権限が見えない場合も取れた分だけ返す
+pub async fn fetch_user_info(repo: &str, login: &str) -> Result<UserInfo> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let search = format!("repo:{} is:pr author:{} sort:updated-desc", repo, login);
+    let response = gh_api_graphql(
+        USER_INFO_QUERY,
+        &[
+            ("owner", FieldValue::String(owner)),
+            ("name", FieldValue::String(name)),
+            ("login", FieldValue::String(login)),
+            ("search", FieldValue::String(&search)),
+        ],
+    )
+    .await?;
+    parse_user_info(login, &response)
+}
+
+fn parse_user_info(login: &str, response: &serde_json::Value) -> Result<UserInfo> {
+    let data = response
+        .get("data")
+        .filter(|data| !data.is_null())
+        .with_context(|| match response.get("errors") {
+            Some(errors) => format!("GitHub GraphQL returned errors: {}", errors),
+            None => "Empty GraphQL response".to_string(),
+        })?;
+    let text = |pointer: &str| {
+        data.pointer(pointer)
+            .and_then(|v| v.as_str())
+            .filter(|s| !s.is_empty())
+            .map(String::from)
+    };
+    let permission = data
+        .pointer("/repository/collaborators/edges")
+        .and_then(|v| v.as_array())
+        .and_then(|edges| {
+            edges.iter().find(|edge| {
+                edge.pointer("/node/login")
+                    .and_then(|v| v.as_str())
+                    .is_some_and(|l| l.eq_ignore_ascii_case(login))
+            })
+        })
+        .and_then(|edge| edge.get("permission")?.as_str())
+        .map(String::from);
+    let recent_prs = data
+        .pointer("/search/nodes")
+        .and_then(|v| v.as_array())
+        .map(|nodes| {
+            nodes
+                .iter()
+                .filter_map(|node| {
+                    Some(UserPr {
+                        number: node.get("number")?.as_u64()? as u32,
+                        title: node.get("title")?.as_str()?.to_string(),
+                        state: node.get("state")?.as_str()?.to_string(),
+                    })
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Ok(UserInfo {
+        login: text("/user/login").unwrap_or_else(|| login.to_string()),
+        name: text("/user/name"),
+        company: text("/user/company"),
+        permission,
+        pr_count: data
+            .pointer("/search/issueCount")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0),
+        recent_prs,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct UserResponse {
+    login: String,
+}
+
+/// リポジトリでアサインできるユーザー（コラボレーター・Org のメンバー）
+pub async fn fetch_assignable_users(repo: &str) -> Result<Vec<String>> {
+    let endpoint = format!("repos/{}/assignees?per_page=100", repo);
+    let users: Vec<UserResponse> = serde_json::from_value(gh_api_paginate(&endpoint).await?)
+        .context("Failed to parse assignees response")?;
+    Ok(users.into_iter().map(|user| user.login).collect())
+}
+
+/// レビューを依頼する（`POST /pulls/{n}/requested_reviewers`）
+pub async fn request_reviewer(repo: &str, pr_number: u32, login: &str) -> Result<()> {
+    let endpoint = format!("repos/{}/pulls/{}/requested_reviewers", repo, pr_number);
+    gh_api_post(&endpoint, &[("reviewers[]", FieldValue::String(login))]).await?;
+    Ok(())
+}
+
+/// レビュー依頼を取り消す
+pub async fn remove_requested_reviewer(repo: &str, pr_number: u32, login: &str) -> Result<()> {
+    let endpoint = format!("repos/{}/pulls/{}/requested_reviewers", repo, pr_number);
+    gh_api_delete(&endpoint, &[("reviewers[]", FieldValue::String(login))]).await?;
+    Ok(())
+}
+
+/// PR にアサインする（`POST /issues/{n}/assignees`）
+pub async fn add_assignee(repo: &str, pr_number: u32, login: &str) -> Result<()> {
+    let endpoint = format!("repos/{}/issues/{}/assignees", repo, pr_number);
+    gh_api_post(&endpoint, &[("assignees[]", FieldValue::String(login))]).await?;
+    Ok(())
+}
+
+/// アサインを外す
+pub async fn remove_assignee(repo: &str, pr_number: u32, login: &str) -> Result<()> {
+    let endpoint = format!("repos/{}/issues/{}/assignees", repo, pr_number);
+    gh_api_delete(&endpoint, &[("assignees[]", FieldValue::String(login))]).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_parse_pr_people() {
+        let response = json!({
+            "data": { "repository": { "pullRequest": {
+                "suggestedReviewers": [
+                    { "reviewer": { "login": "alice" } },
+                    { "reviewer": { "login": "bob" } }
+                ],
+                "reviewRequests": { "nodes": [
+                    { "requestedReviewer": { "login": "bob" } },
+                    // チームへの依頼は login を持たない
+                    { "requestedReviewer": {} }
+                ] },
+                "assignees": { "nodes": [ { "login": "carol" } ] }
+            } } }
+        });
+        assert_eq!(
+            parse_pr_people(&response).unwrap(),
+            PrPeople {
+                suggested_reviewers: vec!["alice".into(), "bob".into()],
+                requested_reviewers: vec!["bob".into()],
+                assignees: vec!["carol".into()],
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_user_info() {
+        let response = json!({
+            "data": {
+                "user": { "login": "Alice", "name": "Alice A", "company": "" },
+                "repository": { "collaborators": { "edges": [
+                    { "permission": "READ", "node": { "login": "alice-bot" } },
+                    { "permission": "MAINTAIN", "node": { "login": "Alice" } }
+                ] } },
+                "search": { "issueCount": 12, "nodes": [
+                    { "number": 40, "title": "Fix parser", "state": "MERGED" },
+                    {}
+                ] }
+            }
+        });
+        let info = parse_user_info("alice", &response).unwrap();
+        assert_eq!(
+            info,
+            UserInfo {
+                login: "Alice".into(),
+                name: Some("Alice A".into()),
+                company: None,
+                permission: Some("MAINTAIN".into()),
+                pr_count: 12,
+                recent_prs: vec![UserPr {
+                    number: 40,
+                    title: "Fix parser".into(),
+                    state: "MERGED".into(),
+                }],
+            }
+        );
+        assert!(info.is_maintainer());
+    }
+
+    #[test]
+    fn test_parse_user_info_without_profile_or_permission() {
+        // bot のアカウントは user が null、push 権限がなければ collaborators がエラーになる
+        let response = json!({
+            "data": { "user": null, "repository": { "collaborators": null },
+                      "search": { "issueCount": 0, "nodes": [] } },
+            "errors": [{ "message": "Must have push access" }]
+        });
+        let info = parse_user_info("dependabot[bot]", &response).unwrap();
+        assert_eq!(info.login, "dependabot[bot]");
+        assert_eq!(info.permission, None);
+        assert!(!info.is_maintainer());
+
+        let response = json!({ "errors": [{ "message": "boom" }] });
+        assert!(parse_user_info("alice", &response).is_err());
+    }
+
+    #[test]
+    fn test_parse_pr_people_missing_pr() {
+        let response = json!({ "data": { "repository": { "pullRequest": null } } });
+        assert!(parse_pr_people(&response).is_err());
+    }
+}
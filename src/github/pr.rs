@@ -2,8 +2,9 @@ use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
-use super::client::{gh_api, gh_api_graphql, gh_api_paginate, gh_command, FieldValue};
-use crate::app::ReviewAction;
+use super::client::{
+    gh_api, gh_api_graphql, gh_api_paginate, gh_api_post, gh_api_text, FieldValue,
+};
 
 /// PR状態フィルタ（型安全）
 #[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
@@ -23,6 +24,16 @@ impl PrStateFilter {
         }
     }
 
+    /// GraphQL の `PullRequestState`（`gh pr list -s closed` と同じくマージ済みを含む）。
+    /// 空なら全状態
+    pub fn graphql_states(&self) -> &'static [&'static str] {
+        match self {
+            Self::Open => &["OPEN"],
+            Self::Closed => &["CLOSED", "MERGED"],
+            Self::All => &[],
+        }
+    }
+
     pub fn display_name(&self) -> &'static str {
         match self {
             Self::Open => "open",
@@ -70,6 +81,11 @@ pub struct PullRequest {
     pub base: Branch,
     pub user: User,
     pub updated_at: String,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    /// 下書き（Draft）の PR か
+    #[serde(default)]
+    pub draft: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -107,36 +123,234 @@ pub async fn fetch_changed_files(repo: &str, pr_number: u32) -> Result<Vec<Chang
     serde_json::from_value(json).context("Failed to parse changed files response")
 }
 
+/// files API の 1 ページ分（`page` は 1 始まり、1 ページ 100 件）の変更ファイル
+pub async fn fetch_changed_files_page(
+    repo: &str,
+    pr_number: u32,
+    page: usize,
+) -> Result<Vec<ChangedFile>> {
+    let endpoint = format!(
+        "repos/{}/pulls/{}/files?per_page=100&page={}",
+        repo, pr_number, page
+    );
+    let json = gh_api(&endpoint).await?;
+    serde_json::from_value(json).context("Failed to parse changed files response")
+}
+
+/// レビューの種類（`POST /pulls/{n}/reviews` の `event`）
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReviewAction {
+    Approve,
+    RequestChanges,
+    Comment,
+}
+
 pub async fn submit_review(
     repo: &str,
     pr_number: u32,
     action: ReviewAction,
     body: &str,
 ) -> Result<()> {
-    let action_flag = match action {
-        ReviewAction::Approve => "--approve",
-        ReviewAction::RequestChanges => "--request-changes",
-        ReviewAction::Comment => "--comment",
+    let event = match action {
+        ReviewAction::Approve => "APPROVE",
+        ReviewAction::RequestChanges => "REQUEST_CHANGES",
+        ReviewAction::Comment => "COMMENT",
     };
-
-    gh_command(&[
-        "pr",
-        "review",
-        &pr_number.to_string(),
-        action_flag,
-        "-b",
-        body,
-        "-R",
-        repo,
-    ])
+    let endpoint = format!("repos/{}/pulls/{}/reviews", repo, pr_number);
+    gh_api_post(
+        &endpoint,
+        &[
+            ("event", FieldValue::String(event)),
+            ("body", FieldValue::String(body)),
+        ],
+    )
     .await?;
 
     Ok(())
 }
 
-/// Fetch the raw diff for a PR using `gh pr diff`
+/// `branch`（`repo` 自体のブランチ）から開いている PR の番号
+pub async fn find_open_pr_for_branch(repo: &str, branch: &str) -> Result<Option<u32>> {
+    let owner = repo.split('/').next().unwrap_or(repo);
+    let endpoint = format!("repos/{}/pulls?state=open&head={}:{}", repo, owner, branch);
+    let json = gh_api(&endpoint).await?;
+    Ok(json
+        .as_array()
+        .and_then(|prs| prs.first())
+        .and_then(|pr| pr.get("number"))
+        .and_then(|number| number.as_u64())
+        .and_then(|number| u32::try_from(number).ok()))
+}
+
+/// `head` ブランチから `base` への PR を作成し、その番号を返す
+pub async fn create_pull_request(
+    repo: &str,
+    head: &str,
+    base: &str,
+    title: &str,
+    body: &str,
+) -> Result<u32> {
+    let endpoint = format!("repos/{}/pulls", repo);
+    let json = gh_api_post(
+        &endpoint,
+        &[
+            ("title", FieldValue::String(title)),
+            ("head", FieldValue::String(head)),
+            ("base", FieldValue::String(base)),
+            ("body", FieldValue::String(body)),
+        ],
+    )
+    .await?;
+    json.get("number")
+        .and_then(|number| number.as_u64())
+        .and_then(|number| u32::try_from(number).ok())
+        .context("Failed to parse the created pull request")
+}
+
+/// Fetch the raw diff for a PR (same output as `gh pr diff`)
 pub async fn fetch_pr_diff(repo: &str, pr_number: u32) -> Result<String> {
-    gh_command(&["pr", "diff", &pr_number.to_string(), "-R", repo]).await
+    let endpoint = format!("repos/{}/pulls/{}", repo, pr_number);
+    gh_api_text(&endpoint, Some("application/vnd.github.v3.diff")).await
+}
+
+/// PR に含まれるコミット（古い順）
+#[derive(Debug, Clone, PartialEq)]
+pub struct PrCommit {
+    pub sha: String,
+    /// コミットメッセージの 1 行目
+    pub headline: String,
+    /// GitHub ユーザーに紐づかないコミットは Git の author 名
+    pub author: String,
+    /// author date（RFC 3339）
+    pub date: String,
+    /// 親が 2 つ以上あるマージコミットか
+    pub is_merge: bool,
+}
+
+impl PrCommit {
+    pub fn short_sha(&self) -> &str {
+        self.sha.get(..7).unwrap_or(&self.sha)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitResponse {
+    sha: String,
+    commit: CommitDetail,
+    author: Option<User>,
+    #[serde(default)]
+    parents: Vec<serde::de::IgnoredAny>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitDetail {
+    message: String,
+    author: Option<GitActor>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GitActor {
+    name: String,
+    date: Option<String>,
+}
+
+impl From<CommitResponse> for PrCommit {
+    fn from(response: CommitResponse) -> Self {
+        let git_author = response.commit.author;
+        Self {
+            headline: response
+                .commit
+                .message
+                .lines()
+                .next()
+                .unwrap_or_default()
+                .to_string(),
+            author: response
+                .author
+                .map(|user| user.login)
+                .or_else(|| git_author.as_ref().map(|a| a.name.clone()))
+                .unwrap_or_default(),
+            date: git_author.and_then(|a| a.date).unwrap_or_default(),
+            is_merge: response.parents.len() > 1,
+            sha: response.sha,
+        }
+    }
+}
+
+/// PR のコミット一覧を取得（`GET /pulls/{n}/commits`、最大 250 件）
+pub async fn fetch_pr_commits(repo: &str, pr_number: u32) -> Result<Vec<PrCommit>> {
+    let endpoint = format!("repos/{}/pulls/{}/commits?per_page=100", repo, pr_number);
+    let json = gh_api_paginate(&endpoint).await?;
+    let commits: Vec<CommitResponse> =
+        serde_json::from_value(json).context("Failed to parse PR commits response")?;
+    Ok(commits.into_iter().map(PrCommit::from).collect())
+}
+
+/// `rev` から辿れる、`path` を変更したコミット（`GET /commits?path=`、新しい順に最大 50 件）
+pub async fn fetch_file_commits(repo: &str, rev: &str, path: &str) -> Result<Vec<PrCommit>> {
+    let endpoint = format!(
+        "repos/{}/commits?sha={}&path={}&per_page=50",
+        repo,
+        crate::file_content::encode_path(rev),
+        crate::file_content::encode_path(path)
+    );
+    let json = gh_api(&endpoint).await?;
+    let commits: Vec<CommitResponse> =
+        serde_json::from_value(json).context("Failed to parse commits response")?;
+    Ok(commits.into_iter().map(PrCommit::from).collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct RequestedReviewersResponse {
+    #[serde(default)]
+    users: Vec<User>,
+    #[serde(default)]
+    teams: Vec<TeamResponse>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TeamResponse {
+    slug: String,
+}
+
+/// レビュー依頼中のユーザーとチーム（チームは `org/slug`）
+pub async fn fetch_requested_reviewers(repo: &str, pr_number: u32) -> Result<Vec<String>> {
+    let endpoint = format!("repos/{}/pulls/{}/requested_reviewers", repo, pr_number);
+    parse_requested_reviewers(gh_api(&endpoint).await?, repo)
+}
+
+fn parse_requested_reviewers(json: serde_json::Value, repo: &str) -> Result<Vec<String>> {
+    let response: RequestedReviewersResponse =
+        serde_json::from_value(json).context("Failed to parse requested reviewers response")?;
+    let org = repo.split('/').next().unwrap_or(repo);
+    Ok(response
+        .users
+        .into_iter()
+        .map(|user| user.login)
+        .chain(
+            response
+                .teams
+                .into_iter()
+                .map(|team| format!("{}/{}", org, team.slug)),
+        )
+        .collect())
+}
+
+#[derive(Debug, Deserialize)]
+struct CommitFilesResponse {
+    #[serde(default)]
+    files: Vec<ChangedFile>,
+}
+
+/// 1 コミットで変更されたファイルと patch を取得（`GET /commits/{sha}`）。
+/// マージコミットは第 1 親との差分になる
+pub async fn fetch_commit_files(repo: &str, sha: &str) -> Result<Vec<ChangedFile>> {
+    let endpoint = format!("repos/{}/commits/{}", repo, sha);
+    let json = gh_api(&endpoint).await?;
+    let response: CommitFilesResponse =
+        serde_json::from_value(json).context("Failed to parse commit response")?;
+    Ok(response.files)
 }
 
 #[derive(Debug, Deserialize)]
@@ -243,6 +457,121 @@ query($pullRequestId: ID!, $after: String) {
     Ok(viewed_state)
 }
 
+/// PR 一覧に表示するレビュー済みファイルの割合
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReviewCoverage {
+    pub viewed: usize,
+    pub total: usize,
+}
+
+impl ReviewCoverage {
+    /// viewed 済みファイルの割合（0〜100）。ファイルがなければ None
+    pub fn percent(&self) -> Option<u8> {
+        if self.total == 0 {
+            return None;
+        }
+        Some((self.viewed * 100 / self.total).min(100) as u8)
+    }
+}
+
+/// 1 回の GraphQL クエリで問い合わせる PR の上限
+pub const COVERAGE_BATCH_SIZE: usize = 20;
+
+/// 複数 PR の viewed 状態をエイリアスで 1 クエリにまとめる
+fn build_coverage_query(numbers: &[u32]) -> String {
+    let mut query = String::from(
+        "query($owner: String!, $name: String!) {\n  repository(owner: $owner, name: $name) {\n",
+    );
+    for number in numbers {
+        query.push_str(&format!(
+            "    pr{}: pullRequest(number: {}) {{ id files(first: 100) {{ totalCount nodes {{ viewerViewedState }} pageInfo {{ hasNextPage }} }} }}\n",
+            number, number
+        ));
+    }
+    query.push_str("  }\n}\n");
+    query
+}
+
+/// エイリアスごとの結果を集計する。
+/// ファイルが 100 件を超える PR は node id を返し、残りは [`fetch_files_viewed_state`] で数える
+fn parse_coverage_response(
+    response: &serde_json::Value,
+    numbers: &[u32],
+) -> (HashMap<u32, ReviewCoverage>, Vec<(u32, String)>) {
+    let mut coverage = HashMap::new();
+    let mut paginated = Vec::new();
+    let Some(repository) = response.pointer("/data/repository") else {
+        return (coverage, paginated);
+    };
+    for &number in numbers {
+        // 存在しない PR は null になる
+        let Some(pr) = repository
+            .get(format!("pr{}", number))
+            .filter(|pr| !pr.is_null())
+        else {
+            continue;
+        };
+        let files = &pr["files"];
+        let total = files["totalCount"].as_u64().unwrap_or(0) as usize;
+        if files["pageInfo"]["hasNextPage"].as_bool() == Some(true) {
+            if let Some(id) = pr["id"].as_str() {
+                paginated.push((number, id.to_string()));
+                continue;
+            }
+        }
+        let viewed = files["nodes"]
+            .as_array()
+            .map(|nodes| {
+                nodes
+                    .iter()
+                    .filter(|n| n["viewerViewedState"].as_str() == Some("VIEWED"))
+                    .count()
+            })
+            .unwrap_or(0);
+        coverage.insert(number, ReviewCoverage { viewed, total });
+    }
+    (coverage, paginated)
+}
+
+/// PR 一覧の各 PR について viewed 済みファイル数を取得する（最大 [`COVERAGE_BATCH_SIZE`] 件）
+pub async fn fetch_review_coverage(
+    repo: &str,
+    numbers: &[u32],
+) -> Result<HashMap<u32, ReviewCoverage>> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let numbers = &numbers[..numbers.len().min(COVERAGE_BATCH_SIZE)];
+    let query = build_coverage_query(numbers);
+    let response = gh_api_graphql(
+        &query,
+        &[
+            ("owner", FieldValue::String(owner)),
+            ("name", FieldValue::String(name)),
+        ],
+    )
+    .await?;
+    // 一部の PR が見つからない場合も errors が返るため、取れた分だけ使う
+    if response.pointer("/data/repository").is_none() {
+        if let Some(errors) = response.get("errors") {
+            anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+        }
+    }
+
+    let (mut coverage, paginated) = parse_coverage_response(&response, numbers);
+    for (number, node_id) in paginated {
+        let viewed_state = fetch_files_viewed_state(repo, &node_id).await?;
+        coverage.insert(
+            number,
+            ReviewCoverage {
+                viewed: viewed_state.values().filter(|&&v| v).count(),
+                total: viewed_state.len(),
+            },
+        );
+    }
+    Ok(coverage)
+}
+
 pub async fn mark_file_as_viewed(_repo: &str, pr_node_id: &str, path: &str) -> Result<()> {
     let query = r#"
 mutation($pullRequestId: ID!, $path: String!) {
@@ -293,6 +622,36 @@ mutation($pullRequestId: ID!, $path: String!) {
     Ok(())
 }
 
+/// PR を下書きに戻す（`draft` が true）か、レビュー可能にする
+pub async fn set_pr_draft(pr_node_id: &str, draft: bool) -> Result<()> {
+    let query = if draft {
+        r#"
+mutation($pullRequestId: ID!) {
+  convertPullRequestToDraft(input: { pullRequestId: $pullRequestId }) {
+    clientMutationId
+  }
+}
+"#
+    } else {
+        r#"
+mutation($pullRequestId: ID!) {
+  markPullRequestReadyForReview(input: { pullRequestId: $pullRequestId }) {
+    clientMutationId
+  }
+}
+"#
+    };
+
+    let response =
+        gh_api_graphql(query, &[("pullRequestId", FieldValue::String(pr_node_id))]).await?;
+
+    if let Some(errors) = response.get("errors") {
+        anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+    }
+
+    Ok(())
+}
+
 /// ページネーション結果
 pub struct PrListPage {
     pub items: Vec<PullRequestSummary>,
@@ -301,22 +660,7 @@ pub struct PrListPage {
 
 /// PR一覧取得（limit+1件取得してhas_moreを判定）
 pub async fn fetch_pr_list(repo: &str, state: PrStateFilter, limit: u32) -> Result<PrListPage> {
-    let output = gh_command(&[
-        "pr",
-        "list",
-        "-R",
-        repo,
-        "-s",
-        state.as_gh_arg(),
-        "--json",
-        "number,title,state,author,isDraft,labels,updatedAt",
-        "--limit",
-        &(limit + 1).to_string(),
-    ])
-    .await?;
-
-    let mut items: Vec<PullRequestSummary> =
-        serde_json::from_str(&output).context("Failed to parse PR list response")?;
+    let mut items = fetch_pr_summaries(repo, state, limit + 1).await?;
     let has_more = items.len() > limit as usize;
     items.truncate(limit as usize);
 
@@ -330,24 +674,9 @@ pub async fn fetch_pr_list_with_offset(
     offset: u32,
     limit: u32,
 ) -> Result<PrListPage> {
-    // gh pr list doesn't support offset directly, so we fetch offset+limit+1 and skip
+    // Cursor pagination doesn't support offset directly, so we fetch offset+limit+1 and skip
     let fetch_count = offset + limit + 1;
-    let output = gh_command(&[
-        "pr",
-        "list",
-        "-R",
-        repo,
-        "-s",
-        state.as_gh_arg(),
-        "--json",
-        "number,title,state,author,isDraft,labels,updatedAt",
-        "--limit",
-        &fetch_count.to_string(),
-    ])
-    .await?;
-
-    let all_items: Vec<PullRequestSummary> =
-        serde_json::from_str(&output).context("Failed to parse PR list response")?;
+    let all_items = fetch_pr_summaries(repo, state, fetch_count).await?;
 
     // Check if there are more items beyond what we're returning
     let has_more = all_items.len() > (offset + limit) as usize;
@@ -362,10 +691,191 @@ pub async fn fetch_pr_list_with_offset(
     Ok(PrListPage { items, has_more })
 }
 
+/// `gh pr list` と同じく作成日の新しい順
+const PR_LIST_QUERY: &str = r#"
+query($owner: String!, $name: String!, $states: [PullRequestState!], $first: Int!, $after: String) {
+  repository(owner: $owner, name: $name) {
+    pullRequests(states: $states, first: $first, after: $after, orderBy: { field: CREATED_AT, direction: DESC }) {
+      nodes {
+        number
+        title
+        state
+        isDraft
+        updatedAt
+        author { login }
+        labels(first: 20) { nodes { name } }
+      }
+      pageInfo {
+        hasNextPage
+        endCursor
+      }
+    }
+  }
+}
+"#;
+
+/// 新しい順に最大 `count` 件の PR を取得する
+async fn fetch_pr_summaries(
+    repo: &str,
+    state: PrStateFilter,
+    count: u32,
+) -> Result<Vec<PullRequestSummary>> {
+    let Some((owner, name)) = repo.split_once('/') else {
+        anyhow::bail!("Invalid repository: {}", repo);
+    };
+    let mut items = Vec::new();
+    let mut after: Option<String> = None;
+
+    while items.len() < count as usize {
+        let first = (count as usize - items.len()).min(100).to_string();
+        let mut fields = vec![
+            ("owner", FieldValue::String(owner)),
+            ("name", FieldValue::String(name)),
+            ("first", FieldValue::Raw(&first)),
+        ];
+        for state in state.graphql_states() {
+            fields.push(("states[]", FieldValue::String(state)));
+        }
+        if let Some(cursor) = after.as_deref() {
+            fields.push(("after", FieldValue::String(cursor)));
+        }
+
+        let response = gh_api_graphql(PR_LIST_QUERY, &fields).await?;
+        if let Some(errors) = response.get("errors") {
+            anyhow::bail!("GitHub GraphQL returned errors: {}", errors);
+        }
+        let Some(connection) = response.pointer("/data/repository/pullRequests") else {
+            anyhow::bail!("Repository not found: {}", repo);
+        };
+        items.extend(parse_pr_list_nodes(&connection["nodes"])?);
+
+        let page_info = &connection["pageInfo"];
+        match page_info["endCursor"].as_str() {
+            Some(cursor) if page_info["hasNextPage"].as_bool() == Some(true) => {
+                after = Some(cursor.to_string());
+            }
+            _ => break,
+        }
+    }
+
+    Ok(items)
+}
+
+/// GraphQL の PR ノードを `gh pr list --json` と同じ形に直して読む
+fn parse_pr_list_nodes(nodes: &serde_json::Value) -> Result<Vec<PullRequestSummary>> {
+    let nodes = nodes.as_array().map(Vec::as_slice).unwrap_or_default();
+    nodes
+        .iter()
+        .map(|node| {
+            let mut node = node.clone();
+            node["labels"] = node["labels"]["nodes"].take();
+            if node["labels"].is_null() {
+                node["labels"] = serde_json::json!([]);
+            }
+            // 削除されたユーザーの PR は author が null になる
+            if node["author"].is_null() {
+                node["author"] = serde_json::json!({ "login": "ghost" });
+            }
+            serde_json::from_value(node).context("Failed to parse PR list response")
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_requested_reviewers() {
+        let json = serde_json::json!({
+            "users": [{ "login": "bob" }],
+            "teams": [{ "slug": "backend", "name": "Backend" }]
+        });
+        assert_eq!(
+            parse_requested_reviewers(json, "acme/app").unwrap(),
+            vec!["bob".to_string(), "acme/backend".to_string()]
+        );
+        assert!(parse_requested_reviewers(serde_json::json!({}), "acme/app")
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_parse_pr_commits() {
+        let json = serde_json::json!([
+            {
+                "sha": "0123456789abcdef",
+                "commit": {
+                    "message": "Fix parser\n\nLonger description",
+                    "author": { "name": "Alice A", "date": "2024-01-02T03:04:05Z" }
+                },
+                "author": { "login": "alice" },
+                "parents": [{ "sha": "aaa" }]
+            },
+            {
+                "sha": "fedcba9876543210",
+                "commit": {
+                    "message": "Merge branch 'main'",
+                    "author": { "name": "Bob", "date": "2024-01-03T00:00:00Z" }
+                },
+                "author": null,
+                "parents": [{ "sha": "bbb" }, { "sha": "ccc" }]
+            }
+        ]);
+        let commits: Vec<PrCommit> = serde_json::from_value::<Vec<CommitResponse>>(json)
+            .unwrap()
+            .into_iter()
+            .map(PrCommit::from)
+            .collect();
+
+        assert_eq!(commits[0].headline, "Fix parser");
+        assert_eq!(commits[0].author, "alice");
+        assert_eq!(commits[0].short_sha(), "0123456");
+        assert!(!commits[0].is_merge);
+        // GitHub ユーザーに紐づかない author は Git の名前を使う
+        assert_eq!(commits[1].author, "Bob");
+        assert_eq!(commits[1].date, "2024-01-03T00:00:00Z");
+        assert!(commits[1].is_merge);
+    }
+
+    #[test]
+    fn test_pr_state_filter_graphql_states() {
+        assert_eq!(PrStateFilter::Open.graphql_states(), ["OPEN"]);
+        assert_eq!(PrStateFilter::Closed.graphql_states(), ["CLOSED", "MERGED"]);
+        assert!(PrStateFilter::All.graphql_states().is_empty());
+    }
+
+    #[test]
+    fn test_parse_pr_list_nodes() {
+        let nodes = serde_json::json!([
+            {
+                "number": 12,
+                "title": "Add feature",
+                "state": "OPEN",
+                "isDraft": false,
+                "updatedAt": "2024-01-02T03:04:05Z",
+                "author": { "login": "alice" },
+                "labels": { "nodes": [{ "name": "bug" }] }
+            },
+            {
+                "number": 11,
+                "title": "Old",
+                "state": "MERGED",
+                "isDraft": true,
+                "updatedAt": "2024-01-01T00:00:00Z",
+                "author": null,
+                "labels": { "nodes": [] }
+            }
+        ]);
+        let items = parse_pr_list_nodes(&nodes).unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[0].author.login, "alice");
+        assert_eq!(items[0].labels[0].name, "bug");
+        assert_eq!(items[1].state, "MERGED");
+        assert!(items[1].is_draft);
+        assert_eq!(items[1].author.login, "ghost");
+    }
+
     #[test]
     fn test_pr_state_filter_as_gh_arg() {
         assert_eq!(PrStateFilter::Open.as_gh_arg(), "open");
@@ -386,4 +896,63 @@ mod tests {
         assert_eq!(PrStateFilter::Closed.next(), PrStateFilter::All);
         assert_eq!(PrStateFilter::All.next(), PrStateFilter::Open);
     }
+
+    #[test]
+    fn test_review_coverage_percent() {
+        let coverage = |viewed, total| ReviewCoverage { viewed, total };
+        assert_eq!(coverage(0, 4).percent(), Some(0));
+        assert_eq!(coverage(1, 3).percent(), Some(33));
+        assert_eq!(coverage(4, 4).percent(), Some(100));
+        assert_eq!(coverage(0, 0).percent(), None);
+    }
+
+    #[test]
+    fn test_build_coverage_query_aliases_each_pr() {
+        let query = build_coverage_query(&[12, 34]);
+        assert!(query.contains("pr12: pullRequest(number: 12)"));
+        assert!(query.contains("pr34: pullRequest(number: 34)"));
+        assert!(query.contains("repository(owner: $owner, name: $name)"));
+    }
+
+    #[test]
+    fn test_parse_coverage_response() {
+        let response = serde_json::json!({
+            "data": {
+                "repository": {
+                    "pr1": {
+                        "id": "PR_1",
+                        "files": {
+                            "totalCount": 3,
+                            "nodes": [
+                                { "viewerViewedState": "VIEWED" },
+                                { "viewerViewedState": "UNVIEWED" },
+                                { "viewerViewedState": "DISMISSED" }
+                            ],
+                            "pageInfo": { "hasNextPage": false }
+                        }
+                    },
+                    "pr2": {
+                        "id": "PR_2",
+                        "files": {
+                            "totalCount": 150,
+                            "nodes": [],
+                            "pageInfo": { "hasNextPage": true }
+                        }
+                    },
+                    "pr3": null
+                }
+            }
+        });
+        let (coverage, paginated) = parse_coverage_response(&response, &[1, 2, 3]);
+        assert_eq!(
+            coverage.get(&1),
+            Some(&ReviewCoverage {
+                viewed: 1,
+                total: 3
+            })
+        );
+        assert!(!coverage.contains_key(&2));
+        assert!(!coverage.contains_key(&3));
+        assert_eq!(paginated, vec![(2, "PR_2".to_string())]);
+    }
 }
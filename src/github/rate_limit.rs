@@ -0,0 +1,293 @@
+//! GitHub API のレート制限の残り
+//!
+//! レスポンスの `X-RateLimit-*` ヘッダー（REST クライアントと `gh api --include`）と、
+//! gh / API の「rate limit exceeded」エラーから残りの回数を記録する。
+//! 記録はリソース（`core` / `graphql` など）ごとに持ち、フッターには最も残りの少ないものを表示する。
+//! 残りが少なくなったら、PR 監視などのバックグラウンドのポーリングは
+//! [`RateLimit::poll_interval`] で間隔を延ばし、使い切ったらリセットまで待つ。
+
+use std::sync::Mutex;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// 上限に対する残りの割合（%）がこれ以下なら残り少ないとみなす
+const LOW_PERCENT: u64 = 10;
+/// リセット時刻の分からないエラー（secondary rate limit など）で待つ秒数
+const ERROR_BACKOFF_SECS: u64 = 60;
+/// ヘッダーにリソースがないときのリソース名
+const DEFAULT_RESOURCE: &str = "core";
+
+/// あるリソースのレート制限の状態
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RateLimit {
+    pub resource: String,
+    /// 1 時間あたりの上限。エラーから記録しただけで分からなければ 0
+    pub limit: u64,
+    pub remaining: u64,
+    /// 残りが戻る時刻（UNIX 時間の秒）
+    pub reset: u64,
+}
+
+impl RateLimit {
+    /// レスポンスヘッダーから読み取る。`header` は名前（小文字）から値を返す
+    pub fn from_headers<'a>(header: impl Fn(&str) -> Option<&'a str>) -> Option<Self> {
+        let number = |name: &str| header(name)?.trim().parse::<u64>().ok();
+        Some(Self {
+            resource: header("x-ratelimit-resource")
+                .unwrap_or(DEFAULT_RESOURCE)
+                .trim()
+                .to_string(),
+            limit: number("x-ratelimit-limit")?,
+            remaining: number("x-ratelimit-remaining")?,
+            reset: number("x-ratelimit-reset")?,
+        })
+    }
+
+    pub fn is_exhausted(&self) -> bool {
+        self.remaining == 0
+    }
+
+    /// 残りが上限の [`LOW_PERCENT`]% 以下
+    pub fn is_low(&self) -> bool {
+        self.is_exhausted() || self.remaining * 100 <= self.limit * LOW_PERCENT
+    }
+
+    /// リセットまでの時間
+    pub fn until_reset(&self, now: u64) -> Duration {
+        Duration::from_secs(self.reset.saturating_sub(now))
+    }
+
+    /// バックグラウンドのポーリング間隔。残りが少なければ、残りの半分を
+    /// リセットまでに均等に使う間隔まで延ばす（もう半分は操作による取得に残す）。
+    /// 使い切っていればリセットまで待つ
+    pub fn poll_interval(&self, base: Duration, now: u64) -> Duration {
+        let until_reset = self.until_reset(now);
+        if self.is_exhausted() {
+            return base.max(until_reset);
+        }
+        if !self.is_low() {
+            return base;
+        }
+        let spread = until_reset * 2 / u32::try_from(self.remaining).unwrap_or(u32::MAX);
+        base.max(spread.min(until_reset))
+    }
+}
+
+/// リソースごとの最新の記録
+#[derive(Debug, Default)]
+pub struct RateLimits {
+    entries: Vec<RateLimit>,
+}
+
+impl RateLimits {
+    pub const fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, limit: RateLimit) {
+        match self
+            .entries
+            .iter_mut()
+            .find(|entry| entry.resource == limit.resource)
+        {
+            Some(entry) => *entry = limit,
+            None => self.entries.push(limit),
+        }
+    }
+
+    /// レート制限のエラーを受けた。リセット時刻が分からないので
+    /// [`ERROR_BACKOFF_SECS`] 後（ヘッダーでより遅い時刻が分かっていればそちら）まで使い切ったとみなす
+    pub fn record_exhausted(&mut self, now: u64) {
+        let previous = self
+            .entries
+            .iter()
+            .find(|entry| entry.resource == DEFAULT_RESOURCE);
+        let reset = previous
+            .filter(|entry| entry.is_exhausted())
+            .map_or(0, |entry| entry.reset)
+            .max(now + ERROR_BACKOFF_SECS);
+        let limit = previous.map_or(0, |entry| entry.limit);
+        self.record(RateLimit {
+            resource: DEFAULT_RESOURCE.to_string(),
+            limit,
+            remaining: 0,
+            reset,
+        });
+    }
+
+    /// リセット前の記録のうち、最も残りの割合が少ないもの
+    pub fn most_constrained(&self, now: u64) -> Option<RateLimit> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.reset > now)
+            .min_by(|a, b| {
+                // a.remaining / a.limit と b.remaining / b.limit を比べる（上限不明は 0 扱い）
+                (a.remaining * b.limit.max(1)).cmp(&(b.remaining * a.limit.max(1)))
+            })
+            .cloned()
+    }
+}
+
+static RATE_LIMITS: Mutex<RateLimits> = Mutex::new(RateLimits::new());
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+fn with_limits<T>(f: impl FnOnce(&mut RateLimits) -> T) -> T {
+    let mut limits = RATE_LIMITS.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut limits)
+}
+
+/// レスポンスヘッダーにレート制限があれば記録する
+pub(super) fn record_headers<'a>(header: impl Fn(&str) -> Option<&'a str>) {
+    if let Some(limit) = RateLimit::from_headers(header) {
+        with_limits(|limits| limits.record(limit));
+    }
+}
+
+/// gh / API のエラーメッセージがレート制限によるものなら記録する
+pub(super) fn record_error(message: &str) {
+    if is_rate_limit_error(message) {
+        with_limits(|limits| limits.record_exhausted(unix_now()));
+    }
+}
+
+/// `API rate limit exceeded` / `You have exceeded a secondary rate limit` など
+pub fn is_rate_limit_error(message: &str) -> bool {
+    message.to_ascii_lowercase().contains("rate limit")
+}
+
+/// 最も残りの少ないリソースのレート制限。まだレスポンスを受けていなければ None
+pub fn current_rate_limit() -> Option<RateLimit> {
+    with_limits(|limits| limits.most_constrained(unix_now()))
+}
+
+/// レート制限に合わせたバックグラウンドのポーリング間隔
+pub fn rate_limited_interval(base: Duration) -> Duration {
+    match current_rate_limit() {
+        Some(limit) => limit.poll_interval(base, unix_now()),
+        None => base,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(resource: &str, limit: u64, remaining: u64, reset: u64) -> RateLimit {
+        RateLimit {
+            resource: resource.to_string(),
+            limit,
+            remaining,
+            reset,
+        }
+    }
+
+    #[test]
+    fn test_from_headers() {
+        let headers = [
+            ("x-ratelimit-limit", "5000"),
+            ("x-ratelimit-remaining", "4999"),
+            ("x-ratelimit-reset", "1700000000"),
+            ("x-ratelimit-resource", "graphql"),
+        ];
+        let lookup = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, value)| *value)
+        };
+        assert_eq!(
+            RateLimit::from_headers(lookup),
+            Some(limit("graphql", 5000, 4999, 1_700_000_000))
+        );
+        // リソースがなければ core、数値が欠けていれば記録しない
+        assert_eq!(
+            RateLimit::from_headers(|name| lookup(name).filter(|_| !name.ends_with("resource")))
+                .unwrap()
+                .resource,
+            "core"
+        );
+        assert_eq!(
+            RateLimit::from_headers(|name| lookup(name).filter(|_| !name.ends_with("reset"))),
+            None
+        );
+    }
+
+    #[test]
+    fn test_poll_interval_backs_off_when_low() {
+        let base = Duration::from_secs(30);
+        let now = 1000;
+        // 十分に残っていればそのまま
+        assert_eq!(
+            limit("core", 5000, 4000, now + 3600).poll_interval(base, now),
+            base
+        );
+        // 残り 100 回をリセットまでの 3600 秒で半分だけ使う → 72 秒ごと
+        let low = limit("core", 5000, 100, now + 3600);
+        assert!(low.is_low());
+        assert_eq!(low.poll_interval(base, now), Duration::from_secs(72));
+        // 残り 1 回ならリセットまで待つ
+        assert_eq!(
+            limit("core", 5000, 1, now + 600).poll_interval(base, now),
+            Duration::from_secs(600)
+        );
+        // 使い切ったらリセットまで（過ぎていれば通常の間隔）
+        assert_eq!(
+            limit("core", 5000, 0, now + 600).poll_interval(base, now),
+            Duration::from_secs(600)
+        );
+        assert_eq!(
+            limit("core", 5000, 0, now - 1).poll_interval(base, now),
+            base
+        );
+    }
+
+    #[test]
+    fn test_most_constrained_ignores_reset_entries() {
+        let now = 1000;
+        let mut limits = RateLimits::new();
+        assert_eq!(limits.most_constrained(now), None);
+
+        limits.record(limit("core", 5000, 4000, now + 100));
+        limits.record(limit("graphql", 5000, 300, now + 100));
+        limits.record(limit("search", 30, 0, now - 10));
+        assert_eq!(limits.most_constrained(now).unwrap().resource, "graphql");
+
+        // 同じリソースは上書きする
+        limits.record(limit("graphql", 5000, 4500, now + 100));
+        assert_eq!(limits.most_constrained(now).unwrap().resource, "core");
+    }
+
+    #[test]
+    fn test_rate_limit_error_marks_core_exhausted() {
+        let now = 1000;
+        let mut limits = RateLimits::new();
+        limits.record(limit("core", 5000, 0, now + 1800));
+        limits.record_exhausted(now);
+        // ヘッダーで分かっているリセット時刻を縮めない
+        assert_eq!(
+            limits.most_constrained(now),
+            Some(limit("core", 5000, 0, now + 1800))
+        );
+
+        let mut limits = RateLimits::new();
+        limits.record_exhausted(now);
+        assert_eq!(
+            limits.most_constrained(now),
+            Some(limit("core", 0, 0, now + ERROR_BACKOFF_SECS))
+        );
+
+        assert!(is_rate_limit_error(
+            "gh command failed: HTTP 403: API rate limit exceeded for user ID 1."
+        ));
+        assert!(is_rate_limit_error(
+            "You have exceeded a secondary rate limit (403 Forbidden)"
+        ));
+        assert!(!is_rate_limit_error("Not Found (404 Not Found)"));
+    }
+}
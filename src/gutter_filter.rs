@@ -0,0 +1,102 @@
+//! diff のガターに表示するコメントの絞り込み
+//!
+//! コメントの多い PR で目印が埋もれないよう、`[diff]` の設定に従って
+//! 解決済み・outdated のスレッドや bot などのコメントをガターから外す。
+//! コメント一覧には引き続き表示する。
+
+use std::collections::HashMap;
+
+use crate::bot_author::author_matches;
+use crate::config::DiffConfig;
+use crate::github::comment::ReviewComment;
+use crate::github::ReviewThreadState;
+
+/// ガターから外すコメントか。スレッドの状態が未取得のコメントは状態では外さない
+pub fn is_hidden(
+    config: &DiffConfig,
+    comment: &ReviewComment,
+    thread_states: &HashMap<u64, ReviewThreadState>,
+) -> bool {
+    if config
+        .hide_comment_authors
+        .iter()
+        .any(|pattern| author_matches(pattern, &comment.user.login))
+    {
+        return true;
+    }
+    match thread_states.get(&comment.id) {
+        Some(state) => {
+            (config.hide_resolved_comments && state.resolved)
+                || (config.hide_outdated_comments && state.outdated)
+        }
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::User;
+
+    fn comment(id: u64, login: &str) -> ReviewComment {
+        ReviewComment {
+            id,
+            path: "src/main.rs".to_string(),
+            line: Some(1),
+            start_line: None,
+            body: "comment".to_string(),
+            user: User {
+                login: login.to_string(),
+            },
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_is_hidden_by_thread_state() {
+        let mut config = DiffConfig {
+            hide_resolved_comments: true,
+            ..DiffConfig::default()
+        };
+        let states = HashMap::from([
+            (
+                1,
+                ReviewThreadState {
+                    resolved: true,
+                    outdated: false,
+                    ..ReviewThreadState::default()
+                },
+            ),
+            (
+                2,
+                ReviewThreadState {
+                    resolved: false,
+                    outdated: true,
+                    ..ReviewThreadState::default()
+                },
+            ),
+        ]);
+        assert!(is_hidden(&config, &comment(1, "alice"), &states));
+        assert!(!is_hidden(&config, &comment(2, "alice"), &states));
+        // 状態が未取得なら表示する
+        assert!(!is_hidden(&config, &comment(3, "alice"), &states));
+
+        config.hide_outdated_comments = true;
+        assert!(is_hidden(&config, &comment(2, "alice"), &states));
+    }
+
+    #[test]
+    fn test_is_hidden_by_author() {
+        let config = DiffConfig {
+            hide_comment_authors: vec!["*[bot]".to_string()],
+            ..DiffConfig::default()
+        };
+        let states = HashMap::new();
+        assert!(is_hidden(
+            &config,
+            &comment(1, "coderabbitai[bot]"),
+            &states
+        ));
+        assert!(!is_hidden(&config, &comment(2, "alice"), &states));
+    }
+}
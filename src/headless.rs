@@ -4,10 +4,12 @@ use tokio::sync::mpsc;
 
 use crate::ai::adapter::{
     CommentSeverity, Context, ReviewAction, RevieweeOutput, RevieweeStatus, ReviewerOutput,
+    TokenUsage,
 };
 use crate::ai::orchestrator::{Orchestrator, OrchestratorCommand, RallyEvent, RallyState};
 use crate::config::Config;
 use crate::github;
+use crate::spawn_env::SpawnEnv;
 
 /// Run AI Rally in headless mode (no TUI).
 ///
@@ -19,6 +21,7 @@ pub async fn run_headless_rally(
     pr_number: u32,
     config: &Config,
     working_dir: Option<&str>,
+    resume: bool,
 ) -> Result<bool> {
     eprintln!("[Headless] Fetching PR #{} from {}...", pr_number, repo);
 
@@ -62,9 +65,11 @@ pub async fn run_headless_rally(
         external_comments: Vec::new(),
         local_mode: false,
         file_patches,
+        env: SpawnEnv::for_repo(&config.env, repo),
+        scope: Vec::new(),
     };
 
-    run_headless_with_context(repo, pr_number, config, context).await
+    run_headless_with_context(repo, pr_number, config, context, resume).await
 }
 
 /// Run AI Rally in headless mode for local diff.
@@ -76,6 +81,7 @@ pub async fn run_headless_rally_local(
     repo: &str,
     config: &Config,
     working_dir: Option<&str>,
+    resume: bool,
 ) -> Result<bool> {
     eprintln!("[Headless] Running local diff rally...");
 
@@ -165,9 +171,11 @@ pub async fn run_headless_rally_local(
         external_comments: Vec::new(),
         local_mode: true,
         file_patches: Vec::new(),
+        env: SpawnEnv::for_repo(&config.env, repo),
+        scope: Vec::new(),
     };
 
-    run_headless_with_context(repo, 0, config, context).await
+    run_headless_with_context(repo, 0, config, context, resume).await
 }
 
 /// Core headless execution logic shared between PR and local modes.
@@ -176,6 +184,7 @@ async fn run_headless_with_context(
     pr_number: u32,
     config: &Config,
     context: Context,
+    resume: bool,
 ) -> Result<bool> {
     let (event_tx, mut event_rx) = mpsc::channel(100);
     let (cmd_tx, cmd_rx) = mpsc::channel(10);
@@ -191,6 +200,9 @@ async fn run_headless_with_context(
         &config.project_root,
     )?;
     orchestrator.set_context(context);
+    if resume && !orchestrator.resume()? {
+        eprintln!("[Headless] No unfinished rally to resume, starting a new one");
+    }
 
     // Spawn orchestrator in background
     let orchestrator_handle = tokio::spawn(async move { orchestrator.run().await });
@@ -273,6 +285,7 @@ async fn run_headless_event_loop(
     let mut current_iteration: u32 = 0;
     let mut last_review: Option<ReviewerOutput> = None;
     let mut last_fix: Option<RevieweeOutput> = None;
+    let mut usage = TokenUsage::default();
 
     while let Some(event) = event_rx.recv().await {
         match event {
@@ -343,6 +356,10 @@ async fn run_headless_event_loop(
                 let truncated = truncate_str(&result, 200);
                 eprintln!("  < {}: {}", name, truncated);
             }
+            RallyEvent::UsageRecorded(delta) => {
+                usage.add(&delta);
+                eprintln!("  [Usage] {} (total {})", delta, usage);
+            }
             // Suppress AgentThinking and AgentText to prevent noise and JSON leakage
             RallyEvent::AgentThinking(_) | RallyEvent::AgentText(_) => {}
             // Auto-skip clarification (headless can't interact)
@@ -436,9 +453,14 @@ pub fn format_review_output(output: &ReviewerOutput) -> String {
                 CommentSeverity::Suggestion => "suggestion",
             };
             let location = format!("{}:{}", comment.path, comment.line);
+            let sources = if comment.sources.is_empty() {
+                String::new()
+            } else {
+                format!(" ({})", comment.sources.join(", "))
+            };
             lines.push(format!(
-                "    - {} [{}] {}",
-                location, severity, comment.body
+                "    - {} [{}]{} {}",
+                location, severity, sources, comment.body
             ));
         }
     }
@@ -647,12 +669,14 @@ mod tests {
                     line: 42,
                     body: "Variable should be constant".to_string(),
                     severity: CommentSeverity::Major,
+                    sources: Vec::new(),
                 },
                 ReviewComment {
                     path: "src/lib.rs".to_string(),
                     line: 10,
                     body: "Consider renaming".to_string(),
                     severity: CommentSeverity::Minor,
+                    sources: Vec::new(),
                 },
             ],
             blocking_issues: vec!["Error handling missing".to_string()],
@@ -680,6 +704,7 @@ mod tests {
                 line: 1,
                 body: "Update docs".to_string(),
                 severity: CommentSeverity::Suggestion,
+                sources: Vec::new(),
             }],
             blocking_issues: vec![],
         };
@@ -840,6 +865,7 @@ mod tests {
                     line: 10,
                     body: "Fix this".to_string(),
                     severity: CommentSeverity::Major,
+                    sources: Vec::new(),
                 }],
                 blocking_issues: vec!["Error handling".to_string()],
             }),
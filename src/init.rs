@@ -13,7 +13,7 @@ const DEFAULT_CONFIG: &str = r#"# Editor for writing review body.
 
 [diff]
 theme = "base16-ocean.dark"
-# Number of spaces per tab character in diff view (minimum: 1)
+# Number of spaces per tab character in diff view (1-16)
 tab_width = 4
 
 [keybindings]
@@ -28,6 +28,7 @@ reviewee = "claude"
 max_iterations = 10
 timeout_secs = 600
 # prompt_dir = "/custom/path/to/prompts"  # Optional: custom prompt directory
+# guidelines_file = "CONTRIBUTING.md"      # Optional: coding guidelines for the agents
 
 # Additional tools for reviewer agent (Claude only)
 # Specify in Claude Code --allowedTools format
@@ -37,6 +38,61 @@ timeout_secs = 600
 # NOTE: git push is disabled by default for safety.
 # To enable automatic push, add "Bash(git push:*)" to this list.
 # reviewee_additional_tools = ["Skill", "Bash(git push:*)"]
+
+# Approximate token budget for the diff sent to the reviewer at once.
+# Larger diffs are prioritized and reviewed in parts (0 disables)
+# context_budget_tokens = 100000
+
+# Write jump events ({"event":"jump","path":"...","line":N}) to a FIFO or
+# UNIX socket while moving in the diff, so an editor pane can follow along.
+# [follow]
+# path = "/tmp/octorus-follow"
+# throttle_ms = 100
+
+# Translate review comments in the comment panel (press T).
+# Without `command`, the `ai.reviewer` agent is used. `{lang}` is replaced
+# with `language` and the comment body is passed on stdin.
+# [translate]
+# language = "Japanese"
+# command = "trans -b :{lang}"
+
+# Timestamps in the PR list, comments and AI Rally logs (press t to toggle).
+# `timezone` is "local", "utc" or a fixed offset such as "+09:00".
+# [time]
+# style = "relative"
+# format = "%Y-%m-%d %H:%M"
+# timezone = "local"
+
+# Limit the session to the directories you own in a monorepo. Only these files
+# and their comments are shown, and submitted reviews list the covered paths.
+# Best placed in the project-local .octorus/config.toml. `--paths` overrides it.
+# [scope]
+# paths = ["services/api", "libs/shared"]
+
+# Watch the open PR and refresh only what changed: new commits reload the
+# file list, new comments reload just the comments.
+# [watch]
+# enabled = true
+# interval_secs = 20
+
+# Learning mode: when you navigate with arrow keys or Enter, briefly show the
+# equivalent home-row key, and collect key usage stats (`S` in the file list).
+# [learning]
+# enabled = true
+
+# Reminders for stale PRs (`B` in the PR list). The template can use
+# {{days}}, {{reviewers}}, {{author}}, {{number}} and {{title}}.
+# [nudge]
+# stale_days = 3
+# template = "Hi {{reviewers}}, friendly reminder: this PR has been waiting for {{days}} days."
+
+# Where to read the GitHub token when octorus calls the API without gh:
+# "env" (GH_TOKEN / GITHUB_TOKEN) or "keychain" (macOS Keychain, libsecret,
+# Windows Credential Manager).
+# [github]
+# credential = "keychain"
+# keychain_service = "octorus"
+# keychain_account = "github.com"
 "#;
 
 /// Default prompt templates (same as embedded in binary)
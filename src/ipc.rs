@@ -0,0 +1,252 @@
+//! 外部ツールから起動中の octorus を操作するための IPC (UNIX ドメインソケット)
+//!
+//! プロトコルは改行区切りの JSON。1 行に 1 コマンドを送ると、1 行の JSON で応答する。
+//!
+//! ```text
+//! {"command":"open","path":"src/main.rs","line":42}
+//! {"command":"refresh"}
+//! {"command":"comment","path":"src/main.rs","line":42,"body":"Nit: rename"}
+//! ```
+//!
+//! 応答は受理時 `{"ok":true}`、パース失敗時 `{"ok":false,"error":"..."}`。
+//! コマンドの実行結果は TUI のステータス表示に出る（応答は受理の通知のみ）。
+
+use serde::{Deserialize, Serialize};
+
+/// IPC で受け付けるコマンド
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+pub enum IpcCommand {
+    /// ファイルを diff ビューで開き、指定行（新ファイル側）へ移動
+    Open { path: String, line: Option<u32> },
+    /// 強制リフレッシュ（`R` と同じ）
+    Refresh,
+    /// 新ファイル側の行にレビューコメントを投稿（PR モードのみ）
+    Comment {
+        path: String,
+        line: u32,
+        body: String,
+    },
+}
+
+#[derive(Debug, Serialize)]
+struct IpcResponse {
+    ok: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<String>,
+}
+
+/// 1 行のリクエストをパース
+pub fn parse_command(line: &str) -> Result<IpcCommand, String> {
+    serde_json::from_str(line.trim()).map_err(|e| e.to_string())
+}
+
+fn response_line(result: &Result<(), String>) -> String {
+    let response = match result {
+        Ok(()) => IpcResponse {
+            ok: true,
+            error: None,
+        },
+        Err(e) => IpcResponse {
+            ok: false,
+            error: Some(e.clone()),
+        },
+    };
+    let mut line = serde_json::to_string(&response).unwrap_or_else(|_| "{\"ok\":false}".into());
+    line.push('\n');
+    line
+}
+
+#[cfg(unix)]
+pub use server::{bind, serve};
+
+#[cfg(unix)]
+mod server {
+    use std::os::unix::fs::{DirBuilderExt, FileTypeExt, PermissionsExt};
+    use std::path::Path;
+
+    use anyhow::{bail, Context, Result};
+    use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{UnixListener, UnixStream};
+    use tokio::sync::mpsc;
+
+    use super::{parse_command, response_line, IpcCommand};
+
+    /// ソケットを作成する。残骸のソケットファイルは削除してから bind するが、
+    /// ソケット以外のファイルがあれば消さずにエラーにする
+    pub fn bind(path: &Path) -> Result<UnixListener> {
+        match std::fs::symlink_metadata(path) {
+            Ok(meta) if meta.file_type().is_socket() => std::fs::remove_file(path)
+                .with_context(|| format!("Failed to remove stale socket {}", path.display()))?,
+            Ok(_) => bail!("{} exists and is not a socket", path.display()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to inspect {}", path.display()))
+            }
+        }
+
+        // 同一ユーザー以外からの操作を防ぐ。bind してから権限を絞るまでの間に
+        // 接続されないよう、0700 のディレクトリの中で作ってから移動する
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        let staging = parent.join(format!(".or-ipc-{}", std::process::id()));
+        std::fs::DirBuilder::new()
+            .mode(0o700)
+            .create(&staging)
+            .with_context(|| format!("Failed to create {}", staging.display()))?;
+        let staged = staging.join("or.sock");
+        let result = UnixListener::bind(&staged)
+            .with_context(|| format!("Failed to bind IPC socket {}", path.display()))
+            .and_then(|listener| {
+                std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(0o600))
+                    .context("Failed to set IPC socket permissions")?;
+                std::fs::rename(&staged, path)
+                    .with_context(|| format!("Failed to move IPC socket to {}", path.display()))?;
+                Ok(listener)
+            });
+        let _ = std::fs::remove_file(&staged);
+        let _ = std::fs::remove_dir(&staging);
+        result
+    }
+
+    /// 接続を受け付け、受信したコマンドを App へ転送する
+    pub async fn serve(listener: UnixListener, tx: mpsc::Sender<IpcCommand>) {
+        while let Ok((stream, _)) = listener.accept().await {
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                if let Err(e) = handle_connection(stream, tx).await {
+                    tracing::debug!("IPC connection closed: {}", e);
+                }
+            });
+        }
+    }
+
+    async fn handle_connection(stream: UnixStream, tx: mpsc::Sender<IpcCommand>) -> Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        while let Some(line) = lines.next_line().await? {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let result = match parse_command(&line) {
+                Ok(command) => tx
+                    .send(command)
+                    .await
+                    .map_err(|_| "octorus is shutting down".to_string()),
+                Err(e) => Err(e),
+            };
+            writer.write_all(response_line(&result).as_bytes()).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_open_command() {
+        let cmd = parse_command(r#"{"command":"open","path":"src/main.rs","line":42}"#).unwrap();
+        assert_eq!(
+            cmd,
+            IpcCommand::Open {
+                path: "src/main.rs".to_string(),
+                line: Some(42),
+            }
+        );
+
+        let cmd = parse_command(r#"{"command":"open","path":"README.md"}"#).unwrap();
+        assert_eq!(
+            cmd,
+            IpcCommand::Open {
+                path: "README.md".to_string(),
+                line: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_refresh_and_comment() {
+        assert_eq!(
+            parse_command(r#"{"command":"refresh"}"#).unwrap(),
+            IpcCommand::Refresh
+        );
+        assert_eq!(
+            parse_command(r#" {"command":"comment","path":"a.rs","line":3,"body":"nit"} "#)
+                .unwrap(),
+            IpcCommand::Comment {
+                path: "a.rs".to_string(),
+                line: 3,
+                body: "nit".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_unknown_command_fails() {
+        assert!(parse_command(r#"{"command":"explode"}"#).is_err());
+        assert!(parse_command("open src/main.rs").is_err());
+    }
+
+    #[test]
+    fn test_response_line() {
+        assert_eq!(response_line(&Ok(())), "{\"ok\":true}\n");
+        assert_eq!(
+            response_line(&Err("bad".to_string())),
+            "{\"ok\":false,\"error\":\"bad\"}\n"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_serve_forwards_commands() {
+        use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+        use tokio::sync::mpsc;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("or.sock");
+        let listener = bind(&path).unwrap();
+        let (tx, mut rx) = mpsc::channel(4);
+        tokio::spawn(serve(listener, tx));
+
+        let stream = tokio::net::UnixStream::connect(&path).await.unwrap();
+        let (reader, mut writer) = stream.into_split();
+        let mut lines = BufReader::new(reader).lines();
+
+        writer
+            .write_all(b"{\"command\":\"refresh\"}\n")
+            .await
+            .unwrap();
+        assert_eq!(lines.next_line().await.unwrap().unwrap(), "{\"ok\":true}");
+        assert_eq!(rx.recv().await, Some(IpcCommand::Refresh));
+
+        writer.write_all(b"not json\n").await.unwrap();
+        let reply = lines.next_line().await.unwrap().unwrap();
+        assert!(reply.starts_with("{\"ok\":false"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_bind_replaces_only_sockets() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("or.sock");
+        drop(bind(&path).unwrap());
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        // 前回の残骸のソケットは置き換える
+        let _listener = bind(&path).unwrap();
+        assert_eq!(std::fs::read_dir(dir.path()).unwrap().count(), 1);
+
+        let notes = dir.path().join("notes.txt");
+        std::fs::write(&notes, "keep me").unwrap();
+        let err = bind(&notes).unwrap_err();
+        assert!(err.to_string().contains("is not a socket"), "{}", err);
+        assert_eq!(std::fs::read_to_string(&notes).unwrap(), "keep me");
+    }
+}
@@ -3,7 +3,7 @@
 //! This module provides configurable keybinding support with:
 //! - Single keys (e.g., "j", "k")
 //! - Modifier keys (e.g., Ctrl+d, Ctrl+u)
-//! - Two-key sequences (e.g., "gg", "gd")
+//! - Key sequences of any length (e.g., "gg", "gd", `["Space", "g", "s"]`)
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use serde::de::{self, MapAccess, SeqAccess, Visitor};
@@ -440,11 +440,6 @@ impl<'de> Visitor<'de> for KeySequenceVisitor {
         if keys.is_empty() {
             return Err(de::Error::custom("key sequence cannot be empty"));
         }
-        if keys.len() > 2 {
-            return Err(de::Error::custom(
-                "key sequences longer than 2 keys are not supported",
-            ));
-        }
         Ok(KeySequence(keys))
     }
 }
@@ -797,6 +792,28 @@ mod tests {
         assert_eq!(test.key.0.len(), 2);
     }
 
+    #[test]
+    fn test_toml_deserialize_long_sequence() {
+        let toml_str = r#"key = ["Space", "g", { key = "s", ctrl = true }]"#;
+        #[derive(Deserialize)]
+        struct Test {
+            key: KeySequence,
+        }
+        let test: Test = toml::from_str(toml_str).unwrap();
+        assert_eq!(test.key.0.len(), 3);
+        assert_eq!(test.key.display(), "SpacegCtrl-s");
+
+        let mut state = SequenceState::new();
+        state.push(KeyBinding::char(' '));
+        state.push(KeyBinding::char('g'));
+        assert_eq!(state.matches(&test.key), SequenceMatch::Partial);
+        state.push(KeyBinding::ctrl('s'));
+        assert_eq!(state.matches(&test.key), SequenceMatch::Full);
+
+        let empty: Result<Test, _> = toml::from_str("key = []");
+        assert!(empty.is_err());
+    }
+
     #[test]
     fn test_display_simple() {
         assert_eq!(KeyBinding::char('j').display(), "j");
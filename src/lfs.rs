@@ -0,0 +1,373 @@
+//! Git LFS ポインタファイルの検出と実体の取得
+//!
+//! LFS 管理のファイルは diff 上ではポインタ（`version` / `oid` / `size` の 3 行）に
+//! なるため、ポインタ行をオブジェクトのメタデータ表示に置き換える。
+//! テキストの LFS オブジェクトは `git lfs smudge` で実体を取得し、内容の diff を作れる。
+
+use std::borrow::Cow;
+use std::process::Stdio;
+
+use anyhow::{bail, Context, Result};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::encoding::TextEncoding;
+
+const VERSION_PREFIX: &str = "version https://git-lfs.github.com/spec/";
+/// ポインタファイルの最大サイズ（これを超える diff は走査を打ち切る）
+const MAX_POINTER_BYTES: usize = 1024;
+
+/// プレビューする LFS オブジェクトの上限サイズ
+pub const MAX_PREVIEW_BYTES: u64 = 1024 * 1024;
+
+/// LFS ポインタ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LfsPointer {
+    /// `sha256:<hex>`
+    pub oid: String,
+    pub size: u64,
+}
+
+impl LfsPointer {
+    /// ポインタファイルの内容をパース
+    pub fn parse(text: &str) -> Option<Self> {
+        let mut lines = text.lines().filter(|l| !l.trim().is_empty());
+        if !lines.next()?.starts_with(VERSION_PREFIX) {
+            return None;
+        }
+        let mut oid = None;
+        let mut size = None;
+        for line in lines {
+            let (key, value) = line.split_once(' ')?;
+            match key {
+                "oid" => oid = Some(value.to_string()),
+                "size" => size = Some(value.parse().ok()?),
+                // ext-* などの拡張キーは無視
+                _ if key
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || "-.0123456789".contains(c)) => {}
+                _ => return None,
+            }
+        }
+        Some(Self {
+            oid: oid?,
+            size: size?,
+        })
+    }
+
+    /// `git lfs smudge` に渡すポインタ本文
+    pub fn to_pointer_text(&self) -> String {
+        format!(
+            "{}v1\noid {}\nsize {}\n",
+            VERSION_PREFIX, self.oid, self.size
+        )
+    }
+}
+
+/// diff の変更前後のポインタ（追加・削除の場合は片方のみ）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PatchPointers {
+    pub old: Option<LfsPointer>,
+    pub new: Option<LfsPointer>,
+}
+
+fn is_header_line(line: &str) -> bool {
+    line.starts_with("@@")
+        || line.starts_with("diff ")
+        || line.starts_with("index ")
+        || line.starts_with("--- ")
+        || line.starts_with("+++ ")
+        || line.starts_with("new file mode")
+        || line.starts_with("deleted file mode")
+        || line.starts_with("old mode")
+        || line.starts_with("new mode")
+        || line.starts_with('\\')
+}
+
+/// patch が LFS ポインタ同士の差分であれば変更前後のポインタを返す
+pub fn parse_patch(patch: &str) -> Option<PatchPointers> {
+    let mut old = String::new();
+    let mut new = String::new();
+    for line in patch.lines() {
+        if is_header_line(line) {
+            continue;
+        }
+        match line.chars().next() {
+            Some('-') => push_line(&mut old, &line[1..]),
+            Some('+') => push_line(&mut new, &line[1..]),
+            Some(' ') => {
+                push_line(&mut old, &line[1..]);
+                push_line(&mut new, &line[1..]);
+            }
+            _ => return None,
+        }
+        if old.len() > MAX_POINTER_BYTES || new.len() > MAX_POINTER_BYTES {
+            return None;
+        }
+    }
+
+    let parse_side = |text: &str| -> Option<Option<LfsPointer>> {
+        if text.trim().is_empty() {
+            Some(None)
+        } else {
+            LfsPointer::parse(text).map(Some)
+        }
+    };
+    let pointers = PatchPointers {
+        old: parse_side(&old)?,
+        new: parse_side(&new)?,
+    };
+    (pointers.old.is_some() || pointers.new.is_some()).then_some(pointers)
+}
+
+fn push_line(buf: &mut String, line: &str) {
+    buf.push_str(line);
+    buf.push('\n');
+}
+
+/// バイト数を "1.2 MiB" 形式で表示
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 5] = ["B", "KiB", "MiB", "GiB", "TiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} {}", value, UNITS[unit])
+    }
+}
+
+/// 表示用の patch を返す。LFS ポインタの差分なら各行をメタデータ表示に置き換える。
+///
+/// 行数は変えないため、行インデックスやコメント位置はそのまま使える。
+pub fn display_patch(patch: &str) -> Cow<'_, str> {
+    if parse_patch(patch).is_none() {
+        return Cow::Borrowed(patch);
+    }
+    let lines: Vec<String> = patch
+        .lines()
+        .map(|line| {
+            if is_header_line(line) || line.is_empty() {
+                return line.to_string();
+            }
+            let (marker, content) = line.split_at(1);
+            let replaced = if content.starts_with(VERSION_PREFIX) {
+                "Git LFS object".to_string()
+            } else if let Some(size) = content
+                .strip_prefix("size ")
+                .and_then(|s| s.parse::<u64>().ok())
+            {
+                format!("  size {} ({} bytes)", format_size(size), size)
+            } else {
+                format!("  {}", content)
+            };
+            format!("{}{}", marker, replaced)
+        })
+        .collect();
+    Cow::Owned(lines.join("\n"))
+}
+
+/// `git lfs smudge` でオブジェクトの実体を取得する
+pub async fn smudge(
+    working_dir: Option<&str>,
+    filename: &str,
+    pointer: &LfsPointer,
+) -> Result<Vec<u8>> {
+    let mut cmd = Command::new("git");
+    cmd.args(["lfs", "smudge", "--", filename]);
+    if let Some(dir) = working_dir {
+        cmd.current_dir(dir);
+    }
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+
+    let mut child = cmd
+        .spawn()
+        .context("Failed to run git lfs (is git-lfs installed?)")?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(pointer.to_pointer_text().as_bytes())
+            .await
+            .context("Failed to write LFS pointer")?;
+    }
+    let output = child
+        .wait_with_output()
+        .await
+        .context("Failed to wait for git lfs")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git lfs smudge failed: {}", stderr.trim());
+    }
+    Ok(output.stdout)
+}
+
+fn as_text(bytes: Vec<u8>) -> Result<String> {
+    let (text, encoding) = crate::encoding::decode(&bytes);
+    if bytes.contains(&0) && !matches!(encoding, TextEncoding::Utf16Le | TextEncoding::Utf16Be) {
+        bail!("LFS object is binary");
+    }
+    Ok(text)
+}
+
+/// 変更前後の LFS オブジェクトを取得し、内容の unified diff（`@@` 以降）を返す
+pub async fn fetch_content_diff(
+    working_dir: Option<String>,
+    filename: String,
+    pointers: PatchPointers,
+) -> Result<String> {
+    for pointer in [&pointers.old, &pointers.new].into_iter().flatten() {
+        if pointer.size > MAX_PREVIEW_BYTES {
+            bail!(
+                "LFS object is too large to preview ({})",
+                format_size(pointer.size)
+            );
+        }
+    }
+
+    let mut contents = Vec::with_capacity(2);
+    for pointer in [&pointers.old, &pointers.new] {
+        let text = match pointer {
+            Some(p) => as_text(smudge(working_dir.as_deref(), &filename, p).await?)?,
+            None => String::new(),
+        };
+        contents.push(text);
+    }
+    text_diff(&contents[0], &contents[1]).await
+}
+
+/// 2 つのテキストの unified diff を git diff --no-index で作る
+async fn text_diff(old: &str, new: &str) -> Result<String> {
+    let dir = tempfile::tempdir().context("Failed to create temporary directory")?;
+    let old_path = dir.path().join("old");
+    let new_path = dir.path().join("new");
+    std::fs::write(&old_path, old).context("Failed to write temporary file")?;
+    std::fs::write(&new_path, new).context("Failed to write temporary file")?;
+
+    let output = Command::new("git")
+        .args(["diff", "--no-ext-diff", "--no-color", "--no-index", "--"])
+        .arg(&old_path)
+        .arg(&new_path)
+        .output()
+        .await
+        .context("Failed to run git diff")?;
+    // --no-index は差分があると終了コード 1 を返す
+    if !matches!(output.status.code(), Some(0 | 1)) {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git diff failed: {}", stderr.trim());
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let hunks: Vec<&str> = stdout
+        .lines()
+        .skip_while(|line| !line.starts_with("@@"))
+        .collect();
+    if hunks.is_empty() {
+        bail!("LFS object contents are identical");
+    }
+    Ok(hunks.join("\n"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OID_A: &str = "sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393";
+    const OID_B: &str = "sha256:9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08";
+
+    fn modified_patch() -> String {
+        format!(
+            "@@ -1,3 +1,3 @@\n version https://git-lfs.github.com/spec/v1\n-oid {}\n-size 12345\n+oid {}\n+size 2048000",
+            OID_A, OID_B
+        )
+    }
+
+    #[test]
+    fn test_parse_pointer() {
+        let pointer = LfsPointer::parse(&format!(
+            "version https://git-lfs.github.com/spec/v1\noid {}\nsize 12345\n",
+            OID_A
+        ))
+        .unwrap();
+        assert_eq!(pointer.oid, OID_A);
+        assert_eq!(pointer.size, 12345);
+        assert_eq!(LfsPointer::parse(&pointer.to_pointer_text()), Some(pointer));
+
+        assert!(LfsPointer::parse("fn main() {}\n").is_none());
+        assert!(
+            LfsPointer::parse("version https://git-lfs.github.com/spec/v1\nsize 1\n").is_none()
+        );
+    }
+
+    #[test]
+    fn test_parse_patch_modified() {
+        let pointers = parse_patch(&modified_patch()).unwrap();
+        assert_eq!(pointers.old.unwrap().size, 12345);
+        assert_eq!(pointers.new.unwrap().oid, OID_B);
+    }
+
+    #[test]
+    fn test_parse_patch_added_file() {
+        let patch = format!(
+            "diff --git a/x.bin b/x.bin\nnew file mode 100644\n@@ -0,0 +1,3 @@\n+version https://git-lfs.github.com/spec/v1\n+oid {}\n+size 10",
+            OID_A
+        );
+        let pointers = parse_patch(&patch).unwrap();
+        assert!(pointers.old.is_none());
+        assert_eq!(pointers.new.unwrap().size, 10);
+    }
+
+    #[test]
+    fn test_parse_patch_rejects_regular_diff() {
+        assert!(parse_patch("@@ -1 +1 @@\n-a\n+b").is_none());
+        assert!(parse_patch("").is_none());
+    }
+
+    #[test]
+    fn test_display_patch_keeps_line_count() {
+        let patch = modified_patch();
+        let display = display_patch(&patch);
+        assert_eq!(display.lines().count(), patch.lines().count());
+        assert!(display.contains(" Git LFS object"));
+        assert!(display.contains("+  size 2.0 MiB (2048000 bytes)"));
+        assert!(display.contains(&format!("-  oid {}", OID_A)));
+
+        let regular = "@@ -1 +1 @@\n-a\n+b";
+        assert!(matches!(display_patch(regular), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_format_size() {
+        assert_eq!(format_size(512), "512 B");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024 * 1024), "5.0 GiB");
+    }
+
+    #[tokio::test]
+    async fn test_text_diff() {
+        let diff = text_diff("a\nb\n", "a\nc\n").await.unwrap();
+        assert!(diff.starts_with("@@"));
+        assert!(diff.contains("-b"));
+        assert!(diff.contains("+c"));
+        assert!(text_diff("same\n", "same\n").await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_fetch_rejects_large_objects() {
+        let pointers = PatchPointers {
+            old: None,
+            new: Some(LfsPointer {
+                oid: OID_A.to_string(),
+                size: MAX_PREVIEW_BYTES + 1,
+            }),
+        };
+        let err = fetch_content_diff(None, "big.txt".to_string(), pointers)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("too large"));
+    }
+}
@@ -1,27 +1,217 @@
 //! octorus - A TUI tool for GitHub PR review
 //!
-//! This library exposes modules for benchmarking purposes.
+//! The `or` binary is built on top of this library, and the PR-review logic
+//! can be embedded in other tools without starting the TUI:
+//!
+//! - [`diff`]: unified diff parsing and line/position lookup for review comments
+//! - [`github`]: PR, diff, comment, and review operations via the `gh` CLI or
+//!   the REST/GraphQL API (see [`github::init_backend`])
+//! - [`loader`]: background loading of PR data and local `git diff` output
+//! - [`cache`]: session cache for PR data and comments, backed by an on-disk cache
+//! - [`ai`]: the AI Rally orchestrator (reviewer/reviewee loop)
+//! - [`config`]: the `config.toml` settings that the orchestrator and the GitHub
+//!   backend read
+//!
+//! The most commonly used types are re-exported at the crate root.
+//!
+//! The terminal UI lives behind the default `tui` feature. To depend on the
+//! library alone, disable default features:
+//!
+//! ```toml
+//! [dependencies]
+//! octorus = { version = "0.5", default-features = false }
+//! ```
+//!
+//! ```
+//! use octorus::{get_line_info, line_number_to_position, parse_unified_diff, LineType};
+//!
+//! let diff = "\
+//! diff --git a/src/main.rs b/src/main.rs
+//! --- a/src/main.rs
+//! +++ b/src/main.rs
+//! @@ -1,2 +1,2 @@
+//!  fn main() {
+//! -    println!(\"old\");
+//! +    println!(\"new\");
+//! ";
+//! let patches = parse_unified_diff(diff);
+//! let patch = &patches["src/main.rs"];
+//! let index = patch.lines().position(|l| l.contains("new")).unwrap();
+//! let info = get_line_info(patch, index).unwrap();
+//! assert_eq!(info.line_type, LineType::Added);
+//! assert_eq!(info.new_line_number, Some(2));
+//! assert_eq!(line_number_to_position(patch, 2), Some(3));
+//! ```
+//!
+//! Modules marked `#[doc(hidden)]` are TUI internals used by the binary and
+//! the benchmarks. They are public only so that the binary can use them, and
+//! are not part of the stable API. Those that need the UI dependencies are
+//! only compiled with the `tui` feature.
 
-// All modules need to be public for the library to compile,
-// as they have internal dependencies.
+#[doc(hidden)]
+pub mod advisory;
+#[doc(hidden)]
+pub mod agenda;
 pub mod ai;
+#[doc(hidden)]
+pub mod anonymize;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub mod app;
+#[doc(hidden)]
+pub mod audit;
+#[doc(hidden)]
+pub mod blame;
+#[doc(hidden)]
+pub mod bot_author;
 pub mod cache;
+#[doc(hidden)]
+pub mod cheatsheet;
+#[doc(hidden)]
+pub mod checkout;
+#[doc(hidden)]
+pub mod ci_log;
+#[doc(hidden)]
+pub mod cli_review;
+#[doc(hidden)]
+pub mod codeowners;
+#[doc(hidden)]
+pub mod comment_draft;
+#[doc(hidden)]
+pub mod compare;
 pub mod config;
+#[doc(hidden)]
+pub mod config_check;
+#[doc(hidden)]
+pub mod conflict;
+#[doc(hidden)]
+pub mod credentials;
 pub mod diff;
+#[doc(hidden)]
+pub mod diff_json;
+#[doc(hidden)]
+pub mod diff_search;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub mod editor;
+#[doc(hidden)]
+pub mod encoding;
+#[doc(hidden)]
+pub mod exit_status;
+#[doc(hidden)]
+pub mod explain;
+#[doc(hidden)]
+pub mod file_content;
+#[doc(hidden)]
+pub mod file_sort;
+#[doc(hidden)]
 pub mod filter;
+#[doc(hidden)]
+pub mod fixup;
+#[doc(hidden)]
+pub mod follow;
+#[doc(hidden)]
+pub mod fuzzy;
 pub mod github;
+#[doc(hidden)]
+pub mod gutter_filter;
+#[doc(hidden)]
 pub mod headless;
+#[doc(hidden)]
+pub mod ipc;
+#[doc(hidden)]
 pub mod keybinding;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub mod language;
+#[doc(hidden)]
+pub mod lfs;
+#[doc(hidden)]
+pub mod line_history;
 pub mod loader;
+#[doc(hidden)]
+pub mod local_repos;
+#[doc(hidden)]
+pub mod local_store;
+#[doc(hidden)]
+pub mod manifest_diff;
+#[doc(hidden)]
+pub mod nudge;
+#[doc(hidden)]
+pub mod poll_rate;
+#[doc(hidden)]
+pub mod pr_create;
+#[doc(hidden)]
+pub mod pr_summary;
+#[doc(hidden)]
+pub mod pr_watch;
+#[doc(hidden)]
+pub mod progress;
+#[doc(hidden)]
+pub mod progress_sync;
+#[doc(hidden)]
+pub mod quickfix;
+#[doc(hidden)]
+pub mod review_progress;
+#[doc(hidden)]
+pub mod review_template;
+#[doc(hidden)]
+pub mod scope;
+#[doc(hidden)]
+pub mod session_summary;
+#[doc(hidden)]
+pub mod spawn_env;
+#[doc(hidden)]
+pub mod suggestion;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub mod symbol;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub mod syntax;
+#[doc(hidden)]
+pub mod term_background;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub mod text_width;
+#[doc(hidden)]
+pub mod time_format;
+#[doc(hidden)]
+pub mod translate;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub mod ui;
+#[doc(hidden)]
+pub mod vendored;
+
+// Library API
+pub use ai::orchestrator::{RallyEvent, RallyResult};
+pub use ai::{Orchestrator, RallyState};
+pub use cache::{PrCacheKey, PrData, SessionCache};
+pub use diff::{
+    classify_line, classify_patch_lines, get_line_info, line_number_to_position,
+    parse_unified_diff, patch_warnings, DiffLineInfo, LineType, ParseWarning, ParseWarningKind,
+};
+pub use github::comment::{DiscussionComment, ReviewComment};
+pub use github::{
+    fetch_changed_files, fetch_pr, fetch_pr_diff, init_backend, submit_review, ChangedFile,
+    PullRequest, ReviewAction,
+};
+pub use loader::{
+    fetch_local_diff, fetch_local_diffs_batched, fetch_pr_data, DataLoadResult, FetchMode,
+    SingleFileDiffResult,
+};
 
-// Re-export commonly used types for benchmarks
+// Re-exports used by the benchmarks (not part of the stable API)
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub use app::{CachedDiffLine, DiffCache, InternedSpan};
-pub use diff::{classify_line, get_line_info, LineType};
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub use syntax::ParserPool;
+#[cfg(feature = "tui")]
+#[doc(hidden)]
 pub use ui::diff_view::{build_diff_cache, render_cached_lines};
+#[cfg(feature = "tui")]
+#[doc(hidden)]
+pub use ui::palette::DiffPalette;
@@ -0,0 +1,196 @@
+//! diff で選択した行の変更履歴
+//!
+//! 選択した行（範囲）を変更したコミットを `git log -L` で新しい順に調べる。
+//! 調べるリビジョンのコミットが手元になければ、GitHub の API からファイルを変更した
+//! コミットを代わりに取得する（この場合は行単位では絞り込めない）。
+
+use anyhow::{bail, Context, Result};
+use tokio::process::Command;
+
+use crate::github::{self, PrCommit};
+
+/// 一覧に表示するコミットの上限
+const MAX_COMMITS: usize = 50;
+
+/// 行を変更したコミット
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineChange {
+    pub commit: PrCommit,
+    /// そのコミットの時点のファイルのパス（リネームを辿ったときは今のパスと違う）
+    pub path: String,
+    /// そのコミットの diff で行を変更した位置（新しい側の行番号）。GitHub から取得したときは None
+    pub line: Option<u32>,
+}
+
+/// 行の変更履歴
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineHistory {
+    pub changes: Vec<LineChange>,
+    /// `git log -L` で行単位に絞り込めたか。false ならファイルを変更したコミット全部
+    pub line_level: bool,
+}
+
+/// `git log -L` の各コミットの見出し（レコード区切り・フィールド区切り付き）
+const LOG_FORMAT: &str = "--format=%x1e%H%x1f%P%x1f%an%x1f%aI%x1f%s";
+
+/// `git log -L start,end:path` で行を変更したコミットを調べる。`rev` が None なら HEAD から辿る
+pub async fn git_line_log(
+    working_dir: Option<&str>,
+    rev: Option<&str>,
+    path: &str,
+    start: u32,
+    end: u32,
+) -> Result<Vec<LineChange>> {
+    let root = crate::file_content::repo_root(working_dir).await?;
+    let mut cmd = Command::new("git");
+    cmd.args([
+        "log",
+        LOG_FORMAT,
+        &format!("--max-count={}", MAX_COMMITS),
+        &format!("-L{},{}:{}", start, end, path),
+    ]);
+    if let Some(rev) = rev {
+        cmd.arg(rev);
+    }
+    cmd.current_dir(root);
+    let output = cmd.output().await.context("Failed to run git log")?;
+    if !output.status.success() {
+        bail!(
+            "git log failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(parse_line_log(
+        &String::from_utf8_lossy(&output.stdout),
+        path,
+    ))
+}
+
+/// `git log -L` の出力をコミットごとに分ける。各コミットの diff からその時点のパスと変更した行を拾う
+fn parse_line_log(output: &str, path: &str) -> Vec<LineChange> {
+    output
+        .split('\x1e')
+        .filter_map(|record| {
+            let mut lines = record.lines();
+            let mut fields = lines.next()?.split('\x1f');
+            let sha = fields.next()?.to_string();
+            let parents = fields.next()?;
+            let author = fields.next()?.to_string();
+            let date = fields.next()?.to_string();
+            let headline = fields.next().unwrap_or_default().to_string();
+            if sha.is_empty() {
+                return None;
+            }
+
+            let mut change_path = path.to_string();
+            let mut line = None;
+            // 変更した最初の行の新しい側の行番号。削除だけならその位置
+            let mut next_line: Option<u32> = None;
+            for diff_line in lines {
+                if let Some(new_path) = diff_line.strip_prefix("+++ b/") {
+                    change_path = new_path.to_string();
+                } else if diff_line.starts_with("@@") {
+                    if line.is_none() {
+                        next_line = crate::diff::parse_hunk_range(diff_line).map(|r| r.new_start);
+                    }
+                } else if let Some(current) = next_line.filter(|_| line.is_none()) {
+                    match diff_line.chars().next() {
+                        Some('+') | Some('-') => line = Some(current),
+                        _ => next_line = Some(current + 1),
+                    }
+                }
+            }
+
+            Some(LineChange {
+                commit: PrCommit {
+                    sha,
+                    headline,
+                    author,
+                    date,
+                    is_merge: parents.split_whitespace().count() > 1,
+                },
+                path: change_path,
+                line: line.or(next_line),
+            })
+        })
+        .collect()
+}
+
+/// 行の変更履歴を調べる。手元の git で調べられなければ、GitHub からファイルを変更したコミットを取得する
+pub async fn fetch_line_history(
+    repo: &str,
+    working_dir: Option<&str>,
+    rev: Option<&str>,
+    path: &str,
+    start: u32,
+    end: u32,
+) -> Result<LineHistory> {
+    let local_error = match git_line_log(working_dir, rev, path, start, end).await {
+        Ok(changes) => {
+            return Ok(LineHistory {
+                changes,
+                line_level: true,
+            })
+        }
+        Err(e) => e,
+    };
+    let Some(rev) = rev else {
+        return Err(local_error);
+    };
+    let commits = github::fetch_file_commits(repo, rev, path).await?;
+    Ok(LineHistory {
+        changes: commits
+            .into_iter()
+            .map(|commit| LineChange {
+                commit,
+                path: path.to_string(),
+                line: None,
+            })
+            .collect(),
+        line_level: false,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_log() {
+        let output = "\x1eaaaa\x1fpppp\x1fAlice\x1f2024-03-01T00:00:00+00:00\x1fTweak parser\n\
+            \n\
+            diff --git a/src/parse.rs b/src/parse.rs\n\
+            --- a/src/parse.rs\n\
+            +++ b/src/parse.rs\n\
+            @@ -10,3 +10,3 @@\n \
+            keep\n\
+            -old\n\
+            +new\n \
+            tail\n\
+            \x1ebbbb\x1fp1 p2\x1fBob\x1f2024-01-01T00:00:00+00:00\x1fMerge branch\n\
+            \n\
+            diff --git a/src/old.rs b/src/old.rs\n\
+            --- /dev/null\n\
+            +++ b/src/old.rs\n\
+            @@ -0,0 +5,2 @@\n\
+            +keep\n\
+            +old\n";
+        let changes = parse_line_log(output, "src/parse.rs");
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes[0].commit.sha, "aaaa");
+        assert_eq!(changes[0].commit.author, "Alice");
+        assert_eq!(changes[0].commit.headline, "Tweak parser");
+        assert!(!changes[0].commit.is_merge);
+        assert_eq!(changes[0].path, "src/parse.rs");
+        assert_eq!(changes[0].line, Some(11));
+        // リネームを辿った先の古いパス
+        assert_eq!(changes[1].path, "src/old.rs");
+        assert_eq!(changes[1].line, Some(5));
+        assert!(changes[1].commit.is_merge);
+    }
+
+    #[test]
+    fn test_parse_line_log_empty() {
+        assert!(parse_line_log("", "a.rs").is_empty());
+    }
+}
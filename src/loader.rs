@@ -5,8 +5,11 @@ use tokio::process::Command;
 use tokio::sync::mpsc;
 use tracing::warn;
 
+use crate::audit::{self, AuditAction, AuditEntry};
+use crate::cache::{self, OutboxEntry, OutboxPayload};
 use crate::diff;
-use crate::github::{self, ChangedFile, PullRequest};
+use crate::github::comment::ReviewComment;
+use crate::github::{self, ChangedFile, PrBundle, PrComments, PullRequest, ReviewAction};
 
 pub enum DataLoadResult {
     /// APIからデータ取得成功
@@ -14,22 +17,83 @@ pub enum DataLoadResult {
         pr: Box<PullRequest>,
         files: Vec<ChangedFile>,
     },
+    /// PR と一緒に取得できたコメント類（[`DataLoadResult::Success`] の後に送る）
+    Comments {
+        pr_number: u32,
+        comments: Box<PrComments>,
+    },
+    /// ファイルの多い PR の変更ファイルの続き
+    FilePage(FilePage),
     /// エラー
     Error(String),
 }
 
+/// ファイルの多い PR の変更ファイルの続きのページ
+///
+/// [`DataLoadResult::Success`] の直後にファイルなしで総数を知らせ、その後は取得したページごとに送る
+pub struct FilePage {
+    pub pr_number: u32,
+    pub files: Vec<ChangedFile>,
+    /// このページまでに送った変更ファイルの数
+    pub loaded: usize,
+    /// PR の変更ファイルの総数
+    pub total: usize,
+    /// patch がまだ入っていない（選択したファイルの patch を後から取得してもらう）
+    pub patches_pending: bool,
+    /// 最後のページ（途中で取得に失敗した場合も送る）
+    pub done: bool,
+}
+
 /// 単一ファイルの diff 結果（バッチ/オンデマンド共通）
 pub struct SingleFileDiffResult {
     pub filename: String,
     pub patch: Option<String>,
 }
 
+/// files API の 1 ページ分の patch（ファイルの多い PR のオンデマンド取得）
+pub struct PatchPageResult {
+    pub pr_number: u32,
+    /// 1 始まりのページ番号
+    pub page: usize,
+    /// 取得に失敗したら None
+    pub patches: Option<Vec<SingleFileDiffResult>>,
+}
+
+/// files API の 1 ページの件数
+pub const PATCH_PAGE_SIZE: usize = 100;
+
 /// コメント送信結果
 pub enum CommentSubmitResult {
     /// 送信成功
     Success,
     /// エラー
     Error(String),
+    /// 時間をおけば送れそうなエラー。内容は送信待ちに入れて送り直す
+    Retryable {
+        error: String,
+        payload: OutboxPayload,
+    },
+}
+
+impl CommentSubmitResult {
+    pub fn new(result: Result<()>, payload: OutboxPayload) -> Self {
+        match result {
+            Ok(()) => CommentSubmitResult::Success,
+            Err(e) if cache::is_retryable(&e.to_string()) => CommentSubmitResult::Retryable {
+                error: e.to_string(),
+                payload,
+            },
+            Err(e) => CommentSubmitResult::Error(e.to_string()),
+        }
+    }
+}
+
+/// 送信待ちを送り直した結果
+pub struct OutboxSendResult {
+    pub id: u64,
+    pub repo: String,
+    pub pr_number: u32,
+    pub result: Result<(), String>,
 }
 
 /// バックグラウンド取得モード
@@ -57,6 +121,77 @@ pub async fn fetch_pr_data(
     }
 }
 
+/// PR の 1 コミット分の変更ファイルと patch を読み込む
+///
+/// API はマージコミットを第 1 親との差分として返すため、マージコミットは
+/// 作業ディレクトリのリポジトリにあれば `git show --cc` の combined diff に差し替える。
+/// コミットがローカルにない場合は API の patch のまま返す。
+pub async fn fetch_commit_diff(
+    repo: &str,
+    commit: &github::PrCommit,
+    working_dir: Option<&str>,
+) -> Result<Vec<ChangedFile>> {
+    let mut files = github::fetch_commit_files(repo, &commit.sha).await?;
+    if commit.is_merge {
+        match run_git_command(working_dir, &["show", "--cc", "--format=", &commit.sha]).await {
+            Ok(output) => {
+                let mut patches = diff::parse_unified_diff(&output);
+                for file in &mut files {
+                    if let Some(patch) = patches.remove(&file.filename) {
+                        file.patch = Some(patch);
+                    }
+                }
+            }
+            Err(e) => warn!("combined diff unavailable for {}: {:#}", commit.sha, e),
+        }
+    }
+    Ok(files)
+}
+
+/// ローカルのリポジトリにあるコミット 1 つ分の変更ファイルと patch を `git show` で読み込む。
+/// GitHub に push していないコミットも表示できる
+pub async fn fetch_local_commit_diff(
+    working_dir: Option<&str>,
+    sha: &str,
+) -> Result<Vec<ChangedFile>> {
+    let output = run_git_command(working_dir, &["show", "--format=", "--no-color", sha]).await?;
+    Ok(changed_files_from_diff(&output))
+}
+
+/// unified diff をファイルごとの `ChangedFile` にする（ファイル名順）
+fn changed_files_from_diff(output: &str) -> Vec<ChangedFile> {
+    let mut files: Vec<ChangedFile> = diff::parse_unified_diff(output)
+        .into_iter()
+        .map(|(filename, patch)| {
+            let status = if patch.contains("\nnew file mode ") {
+                "added"
+            } else if patch.contains("\ndeleted file mode ") {
+                "removed"
+            } else {
+                "modified"
+            };
+            let (mut additions, mut deletions) = (0, 0);
+            for (line_type, _) in diff::classify_patch_lines(&patch) {
+                match line_type {
+                    diff::LineType::Added => additions += 1,
+                    diff::LineType::Removed => deletions += 1,
+                    _ => {}
+                }
+            }
+            ChangedFile {
+                filename,
+                status: status.to_string(),
+                additions,
+                deletions,
+                patch: Some(patch),
+                viewed: false,
+            }
+        })
+        .collect();
+    files.sort_by(|a, b| a.filename.cmp(&b.filename));
+    files
+}
+
 /// ローカル `git diff` から PR データを再構築して読み込み（2段階ロード版）
 ///
 /// Phase 1: name-status + numstat のみ → ファイル一覧（patch: None）を即座に送信
@@ -109,6 +244,8 @@ pub async fn fetch_local_diff(
             login: "local".to_string(),
         },
         updated_at: Utc::now().to_rfc3339(),
+        labels: Vec::new(),
+        draft: false,
     };
 
     let _ = tx
@@ -195,7 +332,192 @@ pub async fn fetch_single_file_diff(
     let _ = tx.send(SingleFileDiffResult { filename, patch }).await;
 }
 
+/// PR のデータを取得して送る
+///
+/// PR の情報・変更ファイル・コメント類は GraphQL の 1 回の問い合わせで取得し、
+/// patch は同時に取得した PR 全体の diff から埋める。GraphQL が使えない場合や
+/// diff が大きすぎて取得できない場合は REST の files API で取得する。
+///
+/// 変更ファイルが 1 ページに収まらない PR は最初のページだけで先に表示してもらい、
+/// 残りは [`DataLoadResult::FilePage`] で続けて送る。diff も取得できないほど大きな PR では
+/// patch を埋めず、選択したファイルのページの patch をアプリ側で [`fetch_pr_patch_page`] で取得する
 async fn fetch_and_send(repo: &str, pr_number: u32, tx: mpsc::Sender<DataLoadResult>) {
+    let (bundle, full_diff) = tokio::join!(
+        github::fetch_pr_bundle(repo, pr_number),
+        github::fetch_pr_diff(repo, pr_number)
+    );
+    let bundle = match bundle {
+        Ok(bundle) => bundle,
+        Err(e) => {
+            warn!(
+                "Failed to fetch PR with GraphQL, falling back to REST: {:#}",
+                e
+            );
+            fetch_and_send_rest(repo, pr_number, tx).await;
+            return;
+        }
+    };
+    let PrBundle {
+        pr,
+        mut files,
+        total_files,
+        files_cursor,
+        comments,
+    } = bundle;
+    let pr_node_id = pr.node_id.clone().unwrap_or_default();
+
+    let mut patch_map = match full_diff {
+        Ok(full_diff) => Some(diff::parse_unified_diff(&full_diff)),
+        Err(e) => {
+            warn!("Failed to fetch full diff: {}", e);
+            None
+        }
+    };
+    match patch_map.as_mut() {
+        Some(patch_map) => fill_patches(&mut files, patch_map),
+        // diff も取得できないほど大きな PR は、files API の全ページを待たずに patch を後から取得する
+        None if files_cursor.is_some() => {}
+        None => match github::fetch_changed_files(repo, pr_number).await {
+            Ok(mut rest_files) => {
+                apply_viewed_state(&mut rest_files, |filename| {
+                    files
+                        .iter()
+                        .find(|file| file.filename == filename)
+                        .is_some_and(|file| file.viewed)
+                });
+                files = rest_files;
+            }
+            Err(e) => {
+                let _ = tx.send(DataLoadResult::Error(e.to_string())).await;
+                return;
+            }
+        },
+    }
+
+    let files_len = files.len();
+    let _ = tx
+        .send(DataLoadResult::Success {
+            pr: Box::new(pr),
+            files,
+        })
+        .await;
+    // PR のデータの後に送り、セッションキャッシュに入れてもらう（ファイルを開いたときに取り直さずに済む）
+    if let Some(comments) = comments {
+        let _ = tx
+            .send(DataLoadResult::Comments {
+                pr_number,
+                comments: Box::new(comments),
+            })
+            .await;
+    }
+
+    let Some(mut cursor) = files_cursor else {
+        return;
+    };
+    let patches_pending = patch_map.is_none();
+    let mut loaded = files_len;
+    let page = |files: Vec<ChangedFile>, loaded: usize, done: bool| {
+        DataLoadResult::FilePage(FilePage {
+            pr_number,
+            files,
+            loaded,
+            total: total_files,
+            patches_pending,
+            done,
+        })
+    };
+    if tx.send(page(Vec::new(), loaded, false)).await.is_err() {
+        return;
+    }
+    loop {
+        match github::fetch_pr_files_page(&pr_node_id, &cursor).await {
+            Ok(mut next_page) => {
+                if let Some(patch_map) = patch_map.as_mut() {
+                    fill_patches(&mut next_page.files, patch_map);
+                }
+                let next = next_page.next;
+                loaded += next_page.files.len();
+                if tx
+                    .send(page(next_page.files, loaded, next.is_none()))
+                    .await
+                    .is_err()
+                {
+                    return;
+                }
+                match next {
+                    Some(next) => cursor = next,
+                    None => return,
+                }
+            }
+            Err(e) => {
+                warn!("Failed to fetch more changed files: {:#}", e);
+                let _ = tx.send(page(Vec::new(), loaded, true)).await;
+                return;
+            }
+        }
+    }
+}
+
+/// PR 全体の diff から patch を埋める
+fn fill_patches(files: &mut [ChangedFile], patch_map: &mut HashMap<String, String>) {
+    for file in files.iter_mut() {
+        file.patch = patch_map
+            .remove(&file.filename)
+            .and_then(|patch| hunks_only(&patch));
+    }
+}
+
+/// files API の `page` ページ目（[`PATCH_PAGE_SIZE`] 件ずつ）の patch を取得して送る
+pub async fn fetch_pr_patch_page(
+    repo: String,
+    pr_number: u32,
+    page: usize,
+    tx: mpsc::Sender<PatchPageResult>,
+) {
+    let patches = match github::fetch_changed_files_page(&repo, pr_number, page).await {
+        Ok(files) => Some(
+            files
+                .into_iter()
+                .map(|file| SingleFileDiffResult {
+                    filename: file.filename,
+                    patch: file.patch,
+                })
+                .collect(),
+        ),
+        Err(e) => {
+            warn!(
+                "Failed to fetch patches of changed files page {}: {}",
+                page, e
+            );
+            None
+        }
+    };
+    let _ = tx
+        .send(PatchPageResult {
+            pr_number,
+            page,
+            patches,
+        })
+        .await;
+}
+
+/// diff から取り出した patch を files API と同じく最初の hunk からにする。
+/// 中身の変わらないリネームやバイナリのように hunk がなければ None
+fn hunks_only(patch: &str) -> Option<String> {
+    if patch.starts_with("@@") {
+        return Some(patch.to_string());
+    }
+    patch.find("\n@@").map(|i| patch[i + 1..].to_string())
+}
+
+fn apply_viewed_state(files: &mut [ChangedFile], viewed: impl Fn(&str) -> bool) {
+    for file in files.iter_mut() {
+        file.viewed = viewed(&file.filename);
+    }
+}
+
+/// REST で PR の情報と変更ファイルを取得して送る（コメントはアプリ側で取得する）
+async fn fetch_and_send_rest(repo: &str, pr_number: u32, tx: mpsc::Sender<DataLoadResult>) {
     match tokio::try_join!(
         github::fetch_pr(repo, pr_number),
         github::fetch_changed_files(repo, pr_number)
@@ -204,10 +526,9 @@ async fn fetch_and_send(repo: &str, pr_number: u32, tx: mpsc::Sender<DataLoadRes
             if let Some(pr_node_id) = pr.node_id.as_deref() {
                 match github::fetch_files_viewed_state(repo, pr_node_id).await {
                     Ok(viewed_state) => {
-                        for file in files.iter_mut() {
-                            file.viewed =
-                                viewed_state.get(&file.filename).copied().unwrap_or(false);
-                        }
+                        apply_viewed_state(&mut files, |filename| {
+                            viewed_state.get(filename).copied().unwrap_or(false)
+                        });
                     }
                     Err(e) => {
                         warn!("Failed to fetch viewed-state for PR files: {}", e);
@@ -253,6 +574,104 @@ async fn fetch_and_send(repo: &str, pr_number: u32, tx: mpsc::Sender<DataLoadRes
     }
 }
 
+/// インラインのレビューコメントと、本文付きのレビューをまとめて作成日時順で取得
+///
+/// どちらかの取得に失敗しても、取得できた分だけを返す
+pub async fn fetch_all_review_comments(repo: &str, pr_number: u32) -> Vec<ReviewComment> {
+    let (review_comments_result, reviews_result) = tokio::join!(
+        github::comment::fetch_review_comments(repo, pr_number),
+        github::comment::fetch_reviews(repo, pr_number)
+    );
+
+    github::comment::with_review_bodies(
+        review_comments_result.unwrap_or_default(),
+        reviews_result.unwrap_or_default(),
+    )
+}
+
+/// コメント・返信・レビューを送信し、監査ログに記録する
+pub async fn submit_payload(repo: &str, pr_number: u32, payload: &OutboxPayload) -> Result<()> {
+    let (action, result) = match payload {
+        OutboxPayload::Comment {
+            commit_id,
+            path,
+            position,
+            start_line,
+            line,
+            body,
+            suggestion,
+        } => {
+            let result = match start_line {
+                Some(start) => {
+                    github::create_multiline_review_comment(
+                        repo, pr_number, commit_id, path, *start, *line, "RIGHT", body,
+                    )
+                    .await
+                }
+                None => {
+                    github::create_review_comment(repo, pr_number, commit_id, path, *position, body)
+                        .await
+                }
+            };
+            let action = if *suggestion {
+                AuditAction::Suggestion
+            } else {
+                AuditAction::Comment
+            };
+            (action, result.map(|_| ()))
+        }
+        OutboxPayload::Reply { comment_id, body } => {
+            let result = github::create_reply_comment(repo, pr_number, *comment_id, body).await;
+            (AuditAction::Reply, result.map(|_| ()))
+        }
+        OutboxPayload::Review {
+            commit_id,
+            action,
+            body,
+            comments,
+        } => {
+            let result = if comments.is_empty() {
+                github::submit_review(repo, pr_number, *action, body).await
+            } else {
+                github::submit_review_with_comments(
+                    repo, pr_number, commit_id, *action, body, comments,
+                )
+                .await
+            };
+            let audit_action = match action {
+                ReviewAction::Approve => AuditAction::Approve,
+                ReviewAction::RequestChanges => AuditAction::RequestChanges,
+                ReviewAction::Comment => AuditAction::ReviewComment,
+            };
+            (audit_action, result)
+        }
+    };
+    audit::record(&AuditEntry::new(
+        repo,
+        pr_number,
+        action,
+        payload.target(),
+        &result,
+    ));
+    result
+}
+
+/// 送信待ちを古い順に 1 件ずつ送り直す
+pub async fn retry_outbox(entries: Vec<OutboxEntry>, tx: mpsc::Sender<OutboxSendResult>) {
+    for entry in entries {
+        let result = submit_payload(&entry.repo, entry.pr_number, &entry.payload).await;
+        let sent = OutboxSendResult {
+            id: entry.id,
+            repo: entry.repo,
+            pr_number: entry.pr_number,
+            result: result.map_err(|e| e.to_string()),
+        };
+        if tx.send(sent).await.is_err() {
+            return;
+        }
+    }
+}
+
 async fn check_for_updates(
     repo: &str,
     pr_number: u32,
@@ -482,7 +901,8 @@ async fn run_git_command(working_dir: Option<&str>, args: &[&str]) -> Result<Str
         anyhow::bail!("git {} failed: {}", args.join(" "), stderr.trim());
     }
 
-    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+    // UTF-8 でないファイルの差分もバイト列を失わないようにする
+    Ok(crate::encoding::decode_lossless(&output.stdout))
 }
 
 fn parse_numstat_output(output: Option<&str>) -> HashMap<String, (u32, u32)> {
@@ -548,7 +968,7 @@ async fn run_git_no_index_diff(working_dir: Option<&str>, filename: &str) -> Res
         .await
         .context("failed to spawn git no-index diff command")?;
 
-    let stdout = String::from_utf8_lossy(&output.stdout).to_string();
+    let stdout = crate::encoding::decode_lossless(&output.stdout);
     if output.status.success() {
         return Ok(stdout);
     }
@@ -639,6 +1059,8 @@ mod tests {
         let files = match result {
             DataLoadResult::Success { files, .. } => files,
             DataLoadResult::Error(err) => panic!("unexpected error: {err}"),
+            DataLoadResult::Comments { .. } => panic!("unexpected comments"),
+            DataLoadResult::FilePage(_) => panic!("unexpected file page"),
         };
 
         let filenames: Vec<_> = files.iter().map(|file| file.filename.as_str()).collect();
@@ -688,6 +1110,8 @@ mod tests {
         let files = match result {
             DataLoadResult::Success { files, .. } => files,
             DataLoadResult::Error(err) => panic!("unexpected error: {err}"),
+            DataLoadResult::Comments { .. } => panic!("unexpected comments"),
+            DataLoadResult::FilePage(_) => panic!("unexpected file page"),
         };
 
         assert!(files.is_empty());
@@ -735,6 +1159,8 @@ mod tests {
         let files = match result {
             DataLoadResult::Success { files, .. } => files,
             DataLoadResult::Error(err) => panic!("unexpected error: {err}"),
+            DataLoadResult::Comments { .. } => panic!("unexpected comments"),
+            DataLoadResult::FilePage(_) => panic!("unexpected file page"),
         };
 
         let new_file = files
@@ -837,6 +1263,32 @@ mod tests {
         assert!(patch.contains("+pub fn hello()"));
     }
 
+    #[test]
+    fn test_changed_files_from_diff() {
+        let output = "diff --git a/src/new.rs b/src/new.rs\nnew file mode 100644\nindex 0000000..1111111\n--- /dev/null\n+++ b/src/new.rs\n@@ -0,0 +1,2 @@\n+a\n+b\ndiff --git a/src/lib.rs b/src/lib.rs\nindex 2222222..3333333 100644\n--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@\n keep\n-old\n+new\n";
+        let files = changed_files_from_diff(output);
+        assert_eq!(files.len(), 2);
+        assert_eq!(files[0].filename, "src/lib.rs");
+        assert_eq!(files[0].status, "modified");
+        assert_eq!((files[0].additions, files[0].deletions), (1, 1));
+        assert_eq!(files[1].filename, "src/new.rs");
+        assert_eq!(files[1].status, "added");
+        assert_eq!(files[1].additions, 2);
+    }
+
+    #[test]
+    fn test_hunks_only_drops_git_headers() {
+        let patch = "diff --git a/a.rs b/a.rs\nindex 1..2 100644\n--- a/a.rs\n+++ b/a.rs\n@@ -1 +1 @@\n-a\n+b";
+        assert_eq!(hunks_only(patch).as_deref(), Some("@@ -1 +1 @@\n-a\n+b"));
+        assert_eq!(
+            hunks_only("@@ -1 +1 @@\n-a\n+b").as_deref(),
+            Some("@@ -1 +1 @@\n-a\n+b")
+        );
+        let rename =
+            "diff --git a/a.rs b/b.rs\nsimilarity index 100%\nrename from a.rs\nrename to b.rs";
+        assert_eq!(hunks_only(rename), None);
+    }
+
     #[test]
     fn test_parse_name_status_output() {
         let output = "M\tsrc/foo.rs\nA\tsrc/new.rs\nD\tsrc/old.rs\nR100\told.rs\tnew.rs\nC100\tsrc.rs\tdst.rs\n";
@@ -1128,6 +1580,8 @@ index 1234567..abcdefg 100644
         let files = match result {
             DataLoadResult::Success { files, .. } => files,
             DataLoadResult::Error(err) => panic!("unexpected error: {err}"),
+            DataLoadResult::Comments { .. } => panic!("unexpected comments"),
+            DataLoadResult::FilePage(_) => panic!("unexpected file page"),
         };
 
         let non_ascii_file = files
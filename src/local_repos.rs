@@ -0,0 +1,186 @@
+//! ローカルモードで切り替えるリポジトリの一覧
+//!
+//! `[workspace] dirs` に書いたディレクトリと、ローカルモードで最近開いたディレクトリ
+//! （キャッシュディレクトリの `recent_dirs.json`）をまとめて扱う。
+//! ディレクトリは git のトップレベルの絶対パスで持ち、同じリポジトリを重複させない。
+
+use anyhow::{bail, Context, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use crate::cache;
+
+/// 記録する最近のディレクトリの数
+const MAX_RECENT_DIRS: usize = 10;
+
+const RECENT_DIRS_FILE: &str = "recent_dirs.json";
+
+/// ローカルモードで切り替えられるリポジトリのディレクトリ
+#[derive(Debug, Clone, Default)]
+pub struct LocalRepoRegistry {
+    configured: Vec<String>,
+    /// 新しい順
+    recent: Vec<String>,
+    /// 最近のディレクトリの保存先
+    path: Option<PathBuf>,
+}
+
+impl LocalRepoRegistry {
+    pub fn load(configured: &[String]) -> Self {
+        Self::load_from(cache::cache_dir().join(RECENT_DIRS_FILE), configured)
+    }
+
+    pub fn load_from(path: PathBuf, configured: &[String]) -> Self {
+        let recent = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        Self {
+            configured: configured.iter().map(|dir| expand_home(dir)).collect(),
+            recent,
+            path: Some(path),
+        }
+    }
+
+    /// 設定のディレクトリを先頭に、最近のディレクトリを新しい順に重複なく並べる
+    pub fn entries(&self) -> Vec<String> {
+        let mut entries: Vec<String> = Vec::new();
+        for dir in self.configured.iter().chain(&self.recent) {
+            if !entries.contains(dir) {
+                entries.push(dir.clone());
+            }
+        }
+        entries
+    }
+
+    /// ローカルモードで開いたディレクトリを最近の一覧の先頭に記録する。保存の失敗は無視する
+    pub fn record(&mut self, dir: &str) {
+        self.recent.retain(|recent| recent != dir);
+        self.recent.insert(0, dir.to_string());
+        self.recent.truncate(MAX_RECENT_DIRS);
+        if let Some(ref path) = self.path {
+            if let Some(parent) = path.parent() {
+                let _ = std::fs::create_dir_all(parent);
+            }
+            if let Ok(json) = serde_json::to_string_pretty(&self.recent) {
+                let _ = std::fs::write(path, json);
+            }
+        }
+    }
+}
+
+/// 先頭の `~/` をホームディレクトリに展開する
+pub fn expand_home(dir: &str) -> String {
+    let rest = match dir.strip_prefix("~/") {
+        Some(rest) => rest,
+        None if dir == "~" => "",
+        None => return dir.to_string(),
+    };
+    match std::env::var_os("HOME") {
+        Some(home) => PathBuf::from(home)
+            .join(rest)
+            .to_string_lossy()
+            .into_owned(),
+        None => dir.to_string(),
+    }
+}
+
+/// ディレクトリを含む git リポジトリのトップレベルの絶対パス
+pub fn resolve_dir(dir: &str) -> Result<String> {
+    let expanded = expand_home(dir);
+    let path =
+        std::fs::canonicalize(&expanded).with_context(|| format!("No such directory: {}", dir))?;
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .current_dir(&path)
+        .output()
+        .context("failed to spawn git command")?;
+    if !output.status.success() {
+        bail!("Not a git repository: {}", dir);
+    }
+    let toplevel = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(std::fs::canonicalize(&toplevel)
+        .map(|path| path.to_string_lossy().into_owned())
+        .unwrap_or(toplevel))
+}
+
+/// 一覧に表示するディレクトリ名
+pub fn dir_name(dir: &str) -> String {
+    Path::new(dir)
+        .file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_else(|| dir.to_string())
+}
+
+/// ホームディレクトリを `~` に縮めたパス
+pub fn short_path(dir: &str) -> String {
+    let Some(home) = std::env::var_os("HOME") else {
+        return dir.to_string();
+    };
+    match Path::new(dir).strip_prefix(&home) {
+        Ok(rest) if rest.as_os_str().is_empty() => "~".to_string(),
+        Ok(rest) => format!("~/{}", rest.display()),
+        Err(_) => dir.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_registry_lists_configured_then_recent_dirs() {
+        let tempdir = tempdir().unwrap();
+        let path = tempdir.path().join("cache").join(RECENT_DIRS_FILE);
+        let configured = vec!["/src/api".to_string(), "/src/web".to_string()];
+
+        let mut registry = LocalRepoRegistry::load_from(path.clone(), &configured);
+        assert_eq!(registry.entries(), configured);
+
+        registry.record("/src/tool");
+        registry.record("/src/web");
+        registry.record("/src/tool");
+        assert_eq!(
+            registry.entries(),
+            vec!["/src/api", "/src/web", "/src/tool"]
+        );
+
+        // 最近のディレクトリは保存され、次の起動でも新しい順に並ぶ
+        let registry = LocalRepoRegistry::load_from(path, &[]);
+        assert_eq!(registry.entries(), vec!["/src/tool", "/src/web"]);
+
+        let mut registry = LocalRepoRegistry::default();
+        for i in 0..MAX_RECENT_DIRS + 2 {
+            registry.record(&format!("/src/{}", i));
+        }
+        assert_eq!(registry.entries().len(), MAX_RECENT_DIRS);
+        assert_eq!(
+            registry.entries()[0],
+            format!("/src/{}", MAX_RECENT_DIRS + 1)
+        );
+    }
+
+    #[test]
+    fn test_resolve_dir_finds_repository_root() {
+        let tempdir = tempdir().unwrap();
+        let root = tempdir.path().join("app");
+        std::fs::create_dir_all(root.join("src")).unwrap();
+        assert!(resolve_dir(root.to_str().unwrap())
+            .unwrap_err()
+            .to_string()
+            .starts_with("Not a git repository"));
+        assert!(resolve_dir("/no/such/dir").is_err());
+
+        let status = Command::new("git")
+            .args(["init", "-q"])
+            .current_dir(&root)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        let expected = std::fs::canonicalize(&root).unwrap();
+        let resolved = resolve_dir(root.join("src").to_str().unwrap()).unwrap();
+        assert_eq!(Path::new(&resolved), expected);
+        assert_eq!(dir_name(&resolved), "app");
+    }
+}
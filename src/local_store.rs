@@ -0,0 +1,431 @@
+//! ローカルに保存したデータ（Rally セッション・トランスクリプト・ログ）の管理
+//!
+//! キャッシュディレクトリ（`~/.cache/octorus/`）以下を列挙し、PR ごとの
+//! 容量・最終更新日時を出す。削除はまず `archive/` への移動（ソフトデリート）で行い、
+//! アーカイブ済みのものだけを完全に削除できる。アーカイブからは元の場所に戻せる。
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+
+/// アーカイブ先のディレクトリ名（キャッシュディレクトリ直下）
+pub const ARCHIVE_DIR: &str = "archive";
+
+/// 保存データの種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    /// AI Rally のセッション（session.json・履歴・トランスクリプト）
+    RallySession,
+    /// 監査ログ・デバッグログ（PR に紐付かない）
+    Log,
+}
+
+impl ArtifactKind {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::RallySession => "rally session",
+            Self::Log => "log",
+        }
+    }
+}
+
+/// 保存データ 1 件
+#[derive(Debug, Clone)]
+pub struct Artifact {
+    pub kind: ArtifactKind,
+    /// 表示名（PR に紐付くものは `owner/repo#123`）
+    pub name: String,
+    /// 紐付く PR（リポジトリが分かる場合のみ）
+    pub pr: Option<(String, u32)>,
+    pub path: PathBuf,
+    /// キャッシュディレクトリからの相対パス（アーカイブ内でも同じ）
+    pub relative: PathBuf,
+    pub size: u64,
+    pub modified: Option<SystemTime>,
+    pub archived: bool,
+}
+
+/// キャッシュディレクトリ以下の保存データを列挙する（通常のもの → アーカイブ済みの順）
+pub fn scan(root: &Path) -> Vec<Artifact> {
+    let mut artifacts = Vec::new();
+    for archived in [false, true] {
+        let base = if archived {
+            root.join(ARCHIVE_DIR)
+        } else {
+            root.to_path_buf()
+        };
+        scan_rally_sessions(&base, archived, &mut artifacts);
+        scan_logs(&base, archived, &mut artifacts);
+    }
+    artifacts
+}
+
+/// ログファイル名（アーカイブでは日時付きの別名も含む）
+const LOG_FILES: [&str; 2] = ["audit.jsonl", "debug.log"];
+
+fn scan_logs(base: &Path, archived: bool, artifacts: &mut Vec<Artifact>) {
+    let Ok(entries) = fs::read_dir(base) else {
+        return;
+    };
+    let mut logs: Vec<Artifact> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_file())
+        .filter_map(|path| {
+            let file_name = path.file_name()?.to_string_lossy().to_string();
+            let original = original_name(&file_name);
+            if !LOG_FILES.contains(&original) || (!archived && original != file_name) {
+                return None;
+            }
+            let relative = PathBuf::from(original);
+            Some(artifact(
+                ArtifactKind::Log,
+                file_name,
+                None,
+                path,
+                relative,
+                archived,
+            ))
+        })
+        .collect();
+    logs.sort_by(|a, b| a.name.cmp(&b.name));
+    artifacts.extend(logs);
+}
+
+/// [`move_to`] が付けた日時サフィックス（`.YYYYmmddHHMMSS`）を除いた名前
+fn original_name(name: &str) -> &str {
+    match name.rsplit_once('.') {
+        Some((base, suffix))
+            if suffix.len() == 14 && suffix.bytes().all(|b| b.is_ascii_digit()) =>
+        {
+            base
+        }
+        _ => name,
+    }
+}
+
+fn scan_rally_sessions(base: &Path, archived: bool, artifacts: &mut Vec<Artifact>) {
+    let Ok(entries) = fs::read_dir(base.join("rally")) else {
+        return;
+    };
+    let mut sessions: Vec<Artifact> = entries
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| path.is_dir())
+        .filter_map(|path| {
+            let dir_name = path.file_name()?.to_string_lossy().to_string();
+            let pr = session_pr(&path);
+            let name = match pr {
+                Some((ref repo, number)) => format!("{}#{}", repo, number),
+                None => dir_name.clone(),
+            };
+            let relative = Path::new("rally").join(original_name(&dir_name));
+            Some(artifact(
+                ArtifactKind::RallySession,
+                name,
+                pr,
+                path,
+                relative,
+                archived,
+            ))
+        })
+        .collect();
+    sessions.sort_by(|a, b| a.name.cmp(&b.name));
+    artifacts.extend(sessions);
+}
+
+fn artifact(
+    kind: ArtifactKind,
+    name: String,
+    pr: Option<(String, u32)>,
+    path: PathBuf,
+    relative: PathBuf,
+    archived: bool,
+) -> Artifact {
+    let (size, modified) = disk_usage(&path);
+    Artifact {
+        kind,
+        name,
+        pr,
+        path,
+        relative,
+        size,
+        modified,
+        archived,
+    }
+}
+
+#[derive(Deserialize)]
+struct PrRef {
+    repo: String,
+    pr_number: u32,
+}
+
+/// Rally ディレクトリの PR。ディレクトリ名は `/` を `_` に置換しているため復元できず、
+/// session.json かトランスクリプトに記録されたリポジトリを使う
+fn session_pr(dir: &Path) -> Option<(String, u32)> {
+    let mut candidates = vec![dir.join("session.json")];
+    if let Ok(entries) = fs::read_dir(dir) {
+        candidates.extend(entries.flatten().map(|entry| entry.path()).filter(|path| {
+            path.file_name()
+                .is_some_and(|name| name.to_string_lossy().starts_with("transcript-"))
+        }));
+    }
+    candidates.iter().find_map(|path| {
+        let content = fs::read_to_string(path).ok()?;
+        let pr: PrRef = serde_json::from_str(&content).ok()?;
+        (!pr.repo.is_empty() && pr.pr_number > 0).then_some((pr.repo, pr.pr_number))
+    })
+}
+
+/// ファイル・ディレクトリの合計サイズと最終更新日時（ディレクトリは中身の最新）
+fn disk_usage(path: &Path) -> (u64, Option<SystemTime>) {
+    let Ok(meta) = fs::symlink_metadata(path) else {
+        return (0, None);
+    };
+    let modified = meta.modified().ok();
+    if !meta.is_dir() {
+        return (meta.len(), modified);
+    }
+    let mut size = 0;
+    let mut latest = modified;
+    if let Ok(entries) = fs::read_dir(path) {
+        for entry in entries.flatten() {
+            let (child_size, child_modified) = disk_usage(&entry.path());
+            size += child_size;
+            latest = latest.max(child_modified);
+        }
+    }
+    (size, latest)
+}
+
+/// アーカイブ（ソフトデリート）する。移動先のパスを返す
+pub fn archive(root: &Path, artifact: &Artifact) -> Result<PathBuf> {
+    if artifact.archived {
+        bail!("{} is already archived", artifact.name);
+    }
+    move_to(
+        &artifact.path,
+        &root.join(ARCHIVE_DIR).join(&artifact.relative),
+    )
+}
+
+/// アーカイブから元の場所に戻す
+pub fn restore(root: &Path, artifact: &Artifact) -> Result<PathBuf> {
+    if !artifact.archived {
+        bail!("{} is not archived", artifact.name);
+    }
+    let dest = root.join(&artifact.relative);
+    if dest.exists() {
+        bail!("{} already exists", dest.display());
+    }
+    move_to(&artifact.path, &dest)
+}
+
+/// アーカイブ済みのものを完全に削除する
+pub fn purge(artifact: &Artifact) -> Result<()> {
+    if !artifact.archived {
+        bail!("Archive {} before deleting it", artifact.name);
+    }
+    let result = if artifact.path.is_dir() {
+        fs::remove_dir_all(&artifact.path)
+    } else {
+        fs::remove_file(&artifact.path)
+    };
+    result.with_context(|| format!("Failed to delete {}", artifact.path.display()))
+}
+
+/// 移動先が既にあれば（同じ PR を何度もアーカイブした場合）日時を付けて別名にする
+fn move_to(from: &Path, dest: &Path) -> Result<PathBuf> {
+    let dest = if dest.exists() {
+        let name = dest
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        dest.with_file_name(format!(
+            "{}.{}",
+            name,
+            chrono::Utc::now().format("%Y%m%d%H%M%S")
+        ))
+    } else {
+        dest.to_path_buf()
+    };
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create {}", parent.display()))?;
+    }
+    fs::rename(from, &dest)
+        .with_context(|| format!("Failed to move {} to {}", from.display(), dest.display()))?;
+    Ok(dest)
+}
+
+/// アーカイブされていない PR 紐付きデータの (repo, PR 番号) 一覧（重複なし）
+pub fn active_prs(artifacts: &[Artifact]) -> Vec<(String, u32)> {
+    let mut prs: Vec<(String, u32)> = artifacts
+        .iter()
+        .filter(|a| !a.archived)
+        .filter_map(|a| a.pr.clone())
+        .collect();
+    prs.sort();
+    prs.dedup();
+    prs
+}
+
+/// PR がクローズ（マージを含む）されているかを取得する。取得できなかった PR は含めない
+pub async fn fetch_closed_states(prs: &[(String, u32)]) -> HashMap<(String, u32), bool> {
+    let mut states = HashMap::new();
+    for (repo, number) in prs {
+        match crate::github::fetch_pr(repo, *number).await {
+            Ok(pr) => {
+                states.insert((repo.clone(), *number), pr.state != "open");
+            }
+            Err(e) => {
+                tracing::warn!(repo = %repo, pr = number, error = %e, "Failed to fetch PR state");
+            }
+        }
+    }
+    states
+}
+
+/// クローズ済み PR に紐付く（アーカイブされていない）データのインデックス
+pub fn prunable(artifacts: &[Artifact], closed: &HashMap<(String, u32), bool>) -> Vec<usize> {
+    artifacts
+        .iter()
+        .enumerate()
+        .filter(|(_, a)| !a.archived)
+        .filter(|(_, a)| {
+            a.pr.as_ref()
+                .is_some_and(|pr| closed.get(pr).copied().unwrap_or(false))
+        })
+        .map(|(i, _)| i)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    fn make_store() -> tempfile::TempDir {
+        let root = tempfile::tempdir().unwrap();
+        write(
+            &root.path().join("rally/owner_repo_12/session.json"),
+            r#"{"repo":"owner/repo","pr_number":12,"iteration":1}"#,
+        );
+        write(
+            &root
+                .path()
+                .join("rally/owner_repo_12/history/001_review.json"),
+            "{}",
+        );
+        write(
+            &root
+                .path()
+                .join("rally/owner_repo_7/transcript-20240101.json"),
+            r#"{"version":1,"repo":"owner/repo","pr_number":7,"events":[]}"#,
+        );
+        write(&root.path().join("rally/unknown_3/history/x.json"), "{}");
+        write(&root.path().join("audit.jsonl"), "{}\n");
+        root
+    }
+
+    #[test]
+    fn test_scan_lists_sessions_and_logs() {
+        let root = make_store();
+        let artifacts = scan(root.path());
+        let names: Vec<&str> = artifacts.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["owner/repo#12", "owner/repo#7", "unknown_3", "audit.jsonl"]
+        );
+        assert_eq!(artifacts[0].pr, Some(("owner/repo".to_string(), 12)));
+        assert_eq!(artifacts[0].kind, ArtifactKind::RallySession);
+        assert!(artifacts[0].size > 0);
+        assert!(artifacts[0].modified.is_some());
+        assert_eq!(artifacts[2].pr, None);
+        assert_eq!(artifacts[3].kind, ArtifactKind::Log);
+        assert!(artifacts.iter().all(|a| !a.archived));
+    }
+
+    #[test]
+    fn test_archive_restore_and_purge() {
+        let root = make_store();
+        let artifacts = scan(root.path());
+        let dest = archive(root.path(), &artifacts[0]).unwrap();
+        assert!(dest.ends_with("archive/rally/owner_repo_12"));
+        assert!(!artifacts[0].path.exists());
+
+        let artifacts = scan(root.path());
+        let archived = artifacts.iter().find(|a| a.archived).unwrap();
+        assert_eq!(archived.name, "owner/repo#12");
+        // 通常のデータは完全削除できない
+        assert!(purge(&artifacts[0]).is_err());
+
+        restore(root.path(), archived).unwrap();
+        assert!(root
+            .path()
+            .join("rally/owner_repo_12/session.json")
+            .exists());
+
+        let artifacts = scan(root.path());
+        archive(root.path(), &artifacts[0]).unwrap();
+        let artifacts = scan(root.path());
+        let archived = artifacts.iter().find(|a| a.archived).unwrap();
+        purge(archived).unwrap();
+        assert!(scan(root.path()).iter().all(|a| !a.archived));
+    }
+
+    #[test]
+    fn test_archive_twice_keeps_both_copies() {
+        let root = make_store();
+        let first = scan(root.path());
+        archive(root.path(), &first[3]).unwrap();
+        write(&root.path().join("audit.jsonl"), "{}\n");
+        let second = scan(root.path());
+        let log = second
+            .iter()
+            .find(|a| a.name == "audit.jsonl" && !a.archived);
+        let dest = archive(root.path(), log.unwrap()).unwrap();
+        assert!(dest
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .starts_with("audit.jsonl."));
+
+        let archived: Vec<Artifact> = scan(root.path())
+            .into_iter()
+            .filter(|a| a.archived)
+            .collect();
+        assert_eq!(archived.len(), 2);
+        // 日時付きの別名からも元の名前に戻す
+        assert!(archived
+            .iter()
+            .all(|a| a.relative == Path::new("audit.jsonl")));
+    }
+
+    #[test]
+    fn test_prunable_only_closed_active_prs() {
+        let root = make_store();
+        let artifacts = scan(root.path());
+        assert_eq!(
+            active_prs(&artifacts),
+            vec![
+                ("owner/repo".to_string(), 7),
+                ("owner/repo".to_string(), 12)
+            ]
+        );
+        let closed = HashMap::from([
+            (("owner/repo".to_string(), 12), true),
+            (("owner/repo".to_string(), 7), false),
+        ]);
+        assert_eq!(prunable(&artifacts, &closed), vec![0]);
+    }
+}
@@ -4,19 +4,21 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, LeaveAlternateScreen},
 };
-use notify::{Config, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use std::io;
 use std::panic;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
-use std::time::Duration;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
 // Use modules from the library crate
 use octorus::app::RefreshRequest;
-use octorus::{app, cache, config, github, headless, loader, syntax};
+use octorus::local_repos::{self, LocalRepoRegistry};
+use octorus::{
+    app, cache, cli_review, config, config_check, diff_json, exit_status, github, headless, loader,
+    local_store, quickfix, syntax, term_background,
+};
 
 // init is only used by the binary, not needed for benchmarks
 mod init;
@@ -30,8 +32,10 @@ struct Args {
     command: Option<Commands>,
 
     /// Repository name (e.g., "owner/repo"). Auto-detected from current directory if omitted.
-    #[arg(short, long)]
-    repo: Option<String>,
+    /// Repeat or comma-separate to switch between several repositories in the PR list.
+    /// Overrides `[workspace] repos` in config.
+    #[arg(short, long, value_delimiter = ',')]
+    repo: Vec<String>,
 
     /// Pull request number. Shows PR list if omitted.
     #[arg(short, long, conflicts_with = "local")]
@@ -41,6 +45,15 @@ struct Args {
     #[arg(long, default_value = "false")]
     ai_rally: bool,
 
+    /// Resume the unfinished AI Rally of this PR instead of starting over
+    #[arg(long, default_value = "false", requires = "ai_rally")]
+    resume_rally: bool,
+
+    /// Run AI Rally read-only: reviews and fixes are shown but nothing is posted to the PR
+    /// and no files are written. Overrides `ai.dry_run` in config.
+    #[arg(long, default_value = "false")]
+    dry_run: bool,
+
     /// Show local git diff against current HEAD (no GitHub PR fetch)
     #[arg(long, default_value = "false", conflicts_with = "pr")]
     local: bool,
@@ -49,9 +62,41 @@ struct Args {
     #[arg(long, default_value = "false")]
     auto_focus: bool,
 
-    /// Working directory for AI agents (default: current directory)
-    #[arg(long)]
+    /// Repository directory: the diff shown in local mode and the working directory
+    /// for AI agents (default: current directory)
+    #[arg(long, visible_alias = "dir")]
     working_dir: Option<String>,
+
+    /// Listen on a UNIX socket for control commands from external tools (e.g. editor plugins)
+    #[arg(long)]
+    socket: Option<PathBuf>,
+
+    /// Limit the session to these directories (comma-separated), e.g. "services/api,libs/shared".
+    /// Overrides `[scope] paths` in config.
+    #[arg(long, value_delimiter = ',')]
+    paths: Vec<String>,
+
+    /// Replay a saved AI Rally transcript (JSON) in the AI Rally view
+    #[arg(long, conflicts_with = "ai_rally")]
+    replay: Option<PathBuf>,
+
+    /// Write the saved AI Rally of the PR (or the --replay transcript) as Markdown to FILE
+    /// and exit. With --ai-rally, the rally runs headless first and is exported when it ends
+    #[arg(long, value_name = "FILE")]
+    export: Option<PathBuf>,
+
+    /// Exit as soon as a review is submitted, with an exit code for the outcome:
+    /// 0 approved, 3 changes requested, 4 commented, 5 quit without reviewing, 1 error
+    #[arg(long, default_value = "false", conflicts_with_all = ["local", "ai_rally"])]
+    wait_for_review: bool,
+
+    /// Show usernames as aliases (user1, user2, ...) for screen recordings
+    #[arg(long, default_value = "false")]
+    anonymize: bool,
+
+    /// With --anonymize, also hide the repository and owner names
+    #[arg(long, default_value = "false", requires = "anonymize")]
+    anonymize_repo: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -66,7 +111,86 @@ enum Commands {
         local: bool,
     },
     /// Remove AI Rally session data
-    Clean,
+    Clean {
+        /// Only archive data of closed or merged PRs (restorable from the Local Data view)
+        #[arg(long, default_value = "false")]
+        closed: bool,
+    },
+    /// Export review comments as quickfix lines (file:line:col: message)
+    Quickfix {
+        /// Pull request number
+        #[arg(short, long)]
+        pr: u32,
+        /// Export the latest AI Rally reviewer findings instead of GitHub review comments
+        #[arg(long, default_value = "false")]
+        rally: bool,
+        /// Write to this file instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Post a review comment on lines of the PR diff without opening the TUI
+    Comment {
+        /// Pull request number
+        #[arg(short, long)]
+        pr: u32,
+        /// Path of the changed file
+        #[arg(short, long)]
+        file: String,
+        /// Line number in the new version of the file (the last line of a range)
+        #[arg(short, long)]
+        line: u32,
+        /// First line of a multi-line comment
+        #[arg(long)]
+        start_line: Option<u32>,
+        /// Comment body ("-" reads it from stdin)
+        #[arg(short, long)]
+        body: String,
+    },
+    /// Approve the PR without opening the TUI
+    Approve {
+        /// Pull request number
+        #[arg(short, long)]
+        pr: u32,
+        /// Review body ("-" reads it from stdin)
+        #[arg(short, long, default_value = "")]
+        body: String,
+    },
+    /// Print the PR diff without opening the TUI
+    Diff {
+        /// Pull request number
+        #[arg(short, long)]
+        pr: u32,
+        /// Print the parsed per-file diff with line numbers and comment positions as JSON
+        #[arg(long, default_value = "false")]
+        json: bool,
+    },
+    /// Inspect the configuration
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Open the notifications inbox (review requests) and jump into reviewing a PR
+    Inbox,
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Validate the config files and print the effective merged configuration
+    Check,
+}
+
+/// クローズ済み PR に紐付くローカルデータをアーカイブする（`or clean --closed`）
+async fn run_prune_closed() -> Result<()> {
+    let root = cache::cache_dir();
+    let artifacts = local_store::scan(&root);
+    let closed = local_store::fetch_closed_states(&local_store::active_prs(&artifacts)).await;
+    let targets = local_store::prunable(&artifacts, &closed);
+    for &i in &targets {
+        let dest = local_store::archive(&root, &artifacts[i])?;
+        println!("Archived {} -> {}", artifacts[i].name, dest.display());
+    }
+    println!("{} item(s) archived", targets.len());
+    Ok(())
 }
 
 /// Restore terminal to normal state
@@ -109,36 +233,113 @@ async fn main() -> Result<()> {
         }
     }
 
-    let args = Args::parse();
+    let mut args = Args::parse();
 
-    // Handle subcommands
-    if let Some(command) = args.command {
+    // Handle subcommands. `or inbox` starts the TUI like the PR list does.
+    let inbox = matches!(args.command, Some(Commands::Inbox));
+    if let Some(command) = args.command.take().filter(|_| !inbox) {
         return match command {
             Commands::Init { force, local } => init::run_init(force, local),
-            Commands::Clean => {
+            Commands::Clean { closed: true } => {
+                github::init_backend(&config::Config::load()?.github).await?;
+                run_prune_closed().await
+            }
+            Commands::Clean { closed: false } => {
                 cache::cleanup_rally_sessions();
                 let rally_dir = cache::cache_dir().join("rally");
                 println!("Rally sessions cleaned: {}", rally_dir.display());
                 Ok(())
             }
+            Commands::Quickfix { pr, rally, output } => {
+                let repo = resolve_cli_repo(&args).await?;
+                run_quickfix_export(&repo, pr, rally, output.as_deref()).await
+            }
+            Commands::Comment {
+                pr,
+                file,
+                line,
+                start_line,
+                body,
+            } => {
+                let repo = resolve_cli_repo(&args).await?;
+                let comment = cli_review::LineComment {
+                    path: file,
+                    start_line,
+                    line,
+                    body: read_body_arg(body)?,
+                };
+                cli_review::post_comment(&repo, pr, &comment).await?;
+                println!("Commented on {} in {}#{}", comment.location(), repo, pr);
+                Ok(())
+            }
+            Commands::Approve { pr, body } => {
+                let repo = resolve_cli_repo(&args).await?;
+                let body = read_body_arg(body)?;
+                cli_review::post_review(&repo, pr, github::ReviewAction::Approve, &body).await?;
+                println!("Approved {}#{}", repo, pr);
+                Ok(())
+            }
+            Commands::Diff { pr, json } => {
+                let repo = resolve_cli_repo(&args).await?;
+                run_diff_export(&repo, pr, json).await
+            }
+            Commands::Config {
+                command: ConfigCommand::Check,
+            } => run_config_check(&args),
+            Commands::Inbox => unreachable!("the inbox runs in the TUI"),
         };
     }
 
-    let repo = if args.local {
-        args.repo.clone().unwrap_or_else(|| "local".to_string())
+    let mut config = if let Some(ref dir) = args.working_dir {
+        config::Config::load_for_dir(Path::new(dir))?
     } else {
-        // Detect or use provided repo
-        match args.repo.clone() {
-            Some(r) => r,
-            None => match github::detect_repo().await {
-                Ok(r) => r,
-                Err(e) => {
-                    eprintln!("Error: {}", e);
-                    std::process::exit(1);
-                }
-            },
+        config::Config::load()?
+    };
+    let theme_errors = syntax::load_theme_files(&[&config.diff.theme, &config.diff.light_theme]);
+    for issue in config_check::check_themes(&config, &syntax::available_themes(), &theme_errors) {
+        eprintln!("Warning: {}", issue);
+    }
+    if !args.paths.is_empty() {
+        config.scope.paths = args.paths.clone();
+    }
+    if args.dry_run {
+        config.ai.dry_run = true;
+    }
+    // ローカルモードは GitHub を使わないため、トークンがなくても起動できるようにする
+    if let Err(e) = github::init_backend(&config.github).await {
+        if !args.local {
+            eprintln!("Error: {:#}", e);
+            std::process::exit(1);
+        }
+        tracing::debug!(error = %e, "GitHub backend unavailable in local mode");
+    }
+    // Revalidate refreshed PR data with ETags instead of downloading it again
+    github::enable_response_cache(&config.cache);
+    if args.local {
+        args.working_dir = resolve_local_dir(&args, &config);
+    }
+
+    let repos = if args.local {
+        vec![args
+            .repo
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "local".to_string())]
+    } else if !args.repo.is_empty() {
+        args.repo.clone()
+    } else {
+        // Detect the current repo and add the workspace repos after it. Outside
+        // a repository, fall back to the first workspace repo.
+        match github::detect_repo().await {
+            Ok(r) => config.workspace.repos_with(&r),
+            Err(_) if !config.workspace.repos.is_empty() => config.workspace.repos.clone(),
+            Err(e) => {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
         }
     };
+    let repo = repos[0].clone();
 
     // Pre-initialize syntax highlighting in background to avoid delay on first diff view
     std::thread::spawn(|| {
@@ -146,17 +347,28 @@ async fn main() -> Result<()> {
         let _ = syntax::theme_set();
     });
 
-    let config = if let Some(ref dir) = args.working_dir {
-        config::Config::load_for_dir(Path::new(dir))?
-    } else {
-        config::Config::load()?
-    };
+    // --export without --ai-rally writes a rally that already ran
+    if let Some(path) = args.export.as_ref().filter(|_| !args.ai_rally) {
+        return export_rally_markdown(&repo, &args, path);
+    }
 
     // Headless mode: --ai-rally with --pr or --local bypasses TUI entirely
-    if args.ai_rally && args.pr.is_some() {
-        let pr = args.pr.unwrap();
+    if let Some(pr) = args.pr.filter(|_| args.ai_rally) {
         let working_dir = resolve_working_dir(&args);
-        match headless::run_headless_rally(&repo, pr, &config, working_dir.as_deref()).await {
+        let result = headless::run_headless_rally(
+            &repo,
+            pr,
+            &config,
+            working_dir.as_deref(),
+            args.resume_rally,
+        )
+        .await;
+        if let Some(ref path) = args.export {
+            if let Err(e) = export_rally_markdown(&repo, &args, path) {
+                eprintln!("Error: {:#}", e);
+            }
+        }
+        match result {
             Ok(approved) => std::process::exit(if approved { 0 } else { 1 }),
             Err(e) => {
                 headless::write_error_json(&e.to_string());
@@ -167,7 +379,19 @@ async fn main() -> Result<()> {
     }
     if args.local && args.ai_rally {
         let working_dir = resolve_working_dir(&args);
-        match headless::run_headless_rally_local(&repo, &config, working_dir.as_deref()).await {
+        let result = headless::run_headless_rally_local(
+            &repo,
+            &config,
+            working_dir.as_deref(),
+            args.resume_rally,
+        )
+        .await;
+        if let Some(ref path) = args.export {
+            if let Err(e) = export_rally_markdown(&repo, &args, path) {
+                eprintln!("Error: {:#}", e);
+            }
+        }
+        match result {
             Ok(approved) => std::process::exit(if approved { 0 } else { 1 }),
             Err(e) => {
                 headless::write_error_json(&e.to_string());
@@ -177,12 +401,21 @@ async fn main() -> Result<()> {
         }
     }
 
+    // Pick light or dark themes to match the terminal. The query has to run
+    // before the TUI takes over the terminal.
+    let detected = if config.ui.background == config::BackgroundMode::Auto {
+        term_background::detect()
+    } else {
+        None
+    };
+    config.apply_background(detected);
+
     if args.local {
         run_with_local_diff(&repo, &config, &args).await
-    } else if let Some(pr) = args.pr {
+    } else if let Some(pr) = args.pr.filter(|_| !inbox) {
         run_with_pr(&repo, pr, &config, &args).await
     } else {
-        run_with_pr_list(&repo, config, &args).await
+        run_with_pr_list(&repos, config, &args, inbox).await
     }
 }
 
@@ -192,14 +425,24 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
     let working_dir = args.working_dir.clone();
     let refresh_pending = Arc::new(AtomicBool::new(false));
 
-    app.set_retry_sender(retry_tx.clone());
-    setup_local_watch(retry_tx, working_dir.clone(), refresh_pending.clone());
+    app.set_retry_sender(retry_tx);
     app.set_local_mode(true);
     app.set_local_auto_focus(args.auto_focus);
     setup_working_dir(&mut app, args);
+    app.start_local_watcher();
+    let mut local_repos = LocalRepoRegistry::load(&config.workspace.dirs);
+    if let Some(ref dir) = working_dir {
+        local_repos.record(dir);
+    }
+    app.set_local_repos(local_repos);
+    setup_ipc(&mut app, args)?;
+    setup_replay(&mut app, args)?;
+    setup_anonymizer(&mut app, repo, args);
+    app.enable_follow_events();
 
     if args.ai_rally {
         app.set_start_ai_rally_on_load(true);
+        app.set_resume_rally(args.resume_rally);
     }
 
     let cancel_token = CancellationToken::new();
@@ -209,12 +452,15 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
     loader::fetch_local_diff(repo.clone(), working_dir.clone(), tx.clone()).await;
 
     tokio::spawn(async move {
+        // W でディレクトリを切り替えたら、以降の読み込みもそちらに追従する
+        let mut working_dir = working_dir;
         tokio::select! {
             _ = token_clone.cancelled() => {}
             _ = async {
                 while let Some(request) = retry_rx.recv().await {
                     match request {
-                        RefreshRequest::LocalRefresh => {
+                        RefreshRequest::LocalRefresh { working_dir: dir } => {
+                            working_dir = dir;
                             refresh_pending.store(false, Ordering::Release);
 
                             loop {
@@ -240,6 +486,7 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
     });
 
     let result = app.run().await;
+    cleanup_ipc(args);
     cancel_token.cancel();
 
     if let Err(ref e) = result {
@@ -251,62 +498,6 @@ async fn run_with_local_diff(repo: &str, config: &config::Config, args: &Args) -
     std::process::exit(exit_code);
 }
 
-fn setup_local_watch(
-    refresh_tx: mpsc::Sender<RefreshRequest>,
-    working_dir: Option<String>,
-    refresh_pending: Arc<AtomicBool>,
-) {
-    let watch_dir = working_dir.unwrap_or_else(|| {
-        std::env::current_dir()
-            .map(|path| path.to_string_lossy().to_string())
-            .unwrap_or_else(|_| ".".to_string())
-    });
-
-    std::thread::spawn({
-        let refresh_tx = refresh_tx.clone();
-        move || {
-            let callback = move |result: notify::Result<notify::Event>| {
-                let Ok(event) = result else {
-                    return;
-                };
-
-                let should_refresh = should_refresh_local_change(&event.paths, &event.kind);
-
-                if should_refresh && !refresh_pending.swap(true, Ordering::AcqRel) {
-                    let _ = refresh_tx.try_send(RefreshRequest::LocalRefresh);
-                }
-            };
-
-            let Ok(mut watcher) = RecommendedWatcher::new(callback, Config::default()) else {
-                return;
-            };
-
-            let _ = watcher.watch(Path::new(&watch_dir), RecursiveMode::Recursive);
-
-            loop {
-                std::thread::sleep(Duration::from_secs(60));
-            }
-        }
-    });
-}
-
-fn should_refresh_local_change(paths: &[PathBuf], kind: &EventKind) -> bool {
-    !matches!(kind, EventKind::Access(_))
-        && paths
-            .iter()
-            .any(|path| !is_git_file(path) && !is_octorus_config_file(path))
-}
-
-fn is_git_file(path: &Path) -> bool {
-    path.components()
-        .any(|component| component.as_os_str() == ".git")
-}
-
-fn is_octorus_config_file(path: &Path) -> bool {
-    path.components()
-        .any(|component| component.as_os_str() == ".octorus")
-}
-
 /// Run the app with a specific PR number (existing flow)
 async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args) -> Result<()> {
     // リトライ用のチャンネル
@@ -318,11 +509,22 @@ async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args)
 
     app.set_retry_sender(retry_tx);
     setup_working_dir(&mut app, args);
+    app.set_local_repos(LocalRepoRegistry::load(&config.workspace.dirs));
+    setup_ipc(&mut app, args)?;
+    setup_replay(&mut app, args)?;
+    setup_anonymizer(&mut app, repo, args);
+    app.set_wait_for_review(args.wait_for_review);
+    app.enable_follow_events();
+    app.enable_progress_sync();
+    app.enable_outbox();
 
     // Set flag to start AI Rally mode when --ai-rally is passed
     if args.ai_rally {
         app.set_start_ai_rally_on_load(true);
+        app.set_resume_rally(args.resume_rally);
     }
+    // Show the PR saved by the previous run right away; the fetch below refreshes it
+    app.enable_disk_cache();
 
     // Cancellation token for graceful shutdown
     let cancel_token = CancellationToken::new();
@@ -331,7 +533,6 @@ async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args)
     // バックグラウンドでAPI取得
     let repo_clone = repo.to_string();
     let pr_number = pr;
-    let working_dir = args.working_dir.clone();
 
     tokio::spawn(async move {
         tokio::select! {
@@ -341,12 +542,12 @@ async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args)
 
                 while let Some(request) = retry_rx.recv().await {
                     match request {
-                        RefreshRequest::PrRefresh { pr_number } => {
+                        RefreshRequest::PrRefresh { repo, pr_number } => {
                             let tx_retry = tx.clone();
-                            loader::fetch_pr_data(repo_clone.clone(), pr_number, loader::FetchMode::Fresh, tx_retry)
+                            loader::fetch_pr_data(repo, pr_number, loader::FetchMode::Fresh, tx_retry)
                                 .await;
                         }
-                        RefreshRequest::LocalRefresh => {
+                        RefreshRequest::LocalRefresh { working_dir } => {
                             refresh_pending.store(false, Ordering::Release);
                             loop {
                                 let tx_retry = tx.clone();
@@ -364,6 +565,7 @@ async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args)
 
     // Run the app and ensure terminal is restored on error
     let result = app.run().await;
+    cleanup_ipc(args);
 
     // Signal background tasks to stop
     cancel_token.cancel();
@@ -378,23 +580,44 @@ async fn run_with_pr(repo: &str, pr: u32, config: &config::Config, args: &Args)
     // これにより Drop ベースのクリーンアップはスキップされるが、バックグラウンドタスクは
     // cancel_token.cancel() で明示的に停止済みであり、残るのは spawn_blocking の
     // tree-sitter パース処理のみ。OS がプロセス終了時にリソースを回収するため問題なし。
-    let exit_code = if result.is_ok() { 0 } else { 1 };
+    let exit_code = session_exit_code(&result, &app, args);
     std::process::exit(exit_code);
 }
 
-/// Run the app with PR list (new flow)
-async fn run_with_pr_list(repo: &str, config: config::Config, args: &Args) -> Result<()> {
+/// Run the app with PR list (new flow). With `inbox`, the notifications inbox is shown first.
+async fn run_with_pr_list(
+    repos: &[String],
+    config: config::Config,
+    args: &Args,
+    inbox: bool,
+) -> Result<()> {
+    let repo = repos[0].as_str();
     // リトライ用のチャンネル（PR リスト画面から Local モードへの切替に対応）
     let (retry_tx, mut retry_rx) = mpsc::channel::<RefreshRequest>(1);
     let refresh_pending = Arc::new(AtomicBool::new(false));
 
+    let local_repos = LocalRepoRegistry::load(&config.workspace.dirs);
     let mut app = app::App::new_pr_list(repo, config);
+    app.set_workspace_repos(repos.to_vec());
     app.set_retry_sender(retry_tx);
     setup_working_dir(&mut app, args);
+    app.set_local_repos(local_repos);
+    setup_ipc(&mut app, args)?;
+    setup_replay(&mut app, args)?;
+    setup_anonymizer(&mut app, repo, args);
+    app.set_wait_for_review(args.wait_for_review);
+    app.enable_follow_events();
+    app.enable_progress_sync();
+    app.enable_outbox();
+    app.enable_disk_cache();
+    if inbox {
+        app.open_inbox_on_start();
+    }
 
     // Set pending AI Rally flag if --ai-rally was passed
     if args.ai_rally {
         app.set_pending_ai_rally(true);
+        app.set_resume_rally(args.resume_rally);
     }
 
     // Start loading PR list
@@ -419,7 +642,6 @@ async fn run_with_pr_list(repo: &str, config: config::Config, args: &Args) -> Re
 
     // リトライループ（Local/PR リフレッシュ対応）
     let repo_for_retry = repo.to_string();
-    let working_dir = args.working_dir.clone();
 
     tokio::spawn(async move {
         tokio::select! {
@@ -427,12 +649,12 @@ async fn run_with_pr_list(repo: &str, config: config::Config, args: &Args) -> Re
             _ = async {
                 while let Some(request) = retry_rx.recv().await {
                     match request {
-                        RefreshRequest::PrRefresh { pr_number } => {
+                        RefreshRequest::PrRefresh { repo, pr_number } => {
                             let tx_retry = data_tx.clone();
-                            loader::fetch_pr_data(repo_for_retry.clone(), pr_number, loader::FetchMode::Fresh, tx_retry)
+                            loader::fetch_pr_data(repo, pr_number, loader::FetchMode::Fresh, tx_retry)
                                 .await;
                         }
-                        RefreshRequest::LocalRefresh => {
+                        RefreshRequest::LocalRefresh { working_dir } => {
                             refresh_pending.store(false, Ordering::Release);
                             loop {
                                 let tx_retry = data_tx.clone();
@@ -450,6 +672,7 @@ async fn run_with_pr_list(repo: &str, config: config::Config, args: &Args) -> Re
 
     // Run the app
     let result = app.run().await;
+    cleanup_ipc(args);
 
     // Signal background tasks to stop
     cancel_token.cancel();
@@ -462,10 +685,121 @@ async fn run_with_pr_list(repo: &str, config: config::Config, args: &Args) -> Re
     // run_with_pr と同様、spawn_blocking タスクの完了待ちによるハングを防止するため
     // 即座にプロセスを終了する。バックグラウンドタスクやサブプロセスの明示的な停止は
     // app.run() 内で完了済み。
-    let exit_code = if result.is_ok() { 0 } else { 1 };
+    let exit_code = session_exit_code(&result, &app, args);
     std::process::exit(exit_code);
 }
 
+/// PR セッションの終了コード。`--wait-for-review` ではレビュー結果を返す（[`exit_status`]）
+fn session_exit_code(result: &Result<()>, app: &app::App, args: &Args) -> i32 {
+    match result {
+        Err(_) => exit_status::ERROR,
+        Ok(()) if args.wait_for_review => exit_status::for_review(app.review_outcome()),
+        Ok(()) => 0,
+    }
+}
+
+/// Set up the GitHub backend for a subcommand and pick its repository
+/// (the first `--repo`, or the one detected from the current directory)
+async fn resolve_cli_repo(args: &Args) -> Result<String> {
+    github::init_backend(&config::Config::load()?.github).await?;
+    match args.repo.first() {
+        Some(repo) => Ok(repo.clone()),
+        None => Ok(github::detect_repo().await?),
+    }
+}
+
+/// Write review comments in quickfix format to a file or stdout
+async fn run_quickfix_export(
+    repo: &str,
+    pr: u32,
+    rally: bool,
+    output: Option<&Path>,
+) -> Result<()> {
+    let entries = quickfix::collect_pr_entries(repo, pr, rally).await?;
+    match output {
+        Some(path) => {
+            let mut file = std::fs::File::create(path)?;
+            quickfix::write_entries(&mut file, &entries)?;
+            eprintln!("Wrote {} entries to {}", entries.len(), path.display());
+        }
+        None => quickfix::write_entries(&mut io::stdout().lock(), &entries)?,
+    }
+    Ok(())
+}
+
+/// Print the PR diff as is, or parsed into JSON with `--json`
+async fn run_diff_export(repo: &str, pr: u32, json: bool) -> Result<()> {
+    if !json {
+        print!("{}", github::fetch_pr_diff(repo, pr).await?);
+        return Ok(());
+    }
+    let export = diff_json::fetch_pr_export(repo, pr).await?;
+    println!("{}", serde_json::to_string_pretty(&export)?);
+    Ok(())
+}
+
+/// Read a `--body` argument, taking it from stdin when it is "-"
+fn read_body_arg(body: String) -> Result<String> {
+    if body != "-" {
+        return Ok(body);
+    }
+    let mut body = String::new();
+    io::Read::read_to_string(&mut io::stdin(), &mut body)?;
+    Ok(body)
+}
+
+/// Print config problems and the effective merged configuration (`or config check`).
+/// Exits with status 1 if any problem is an error.
+fn run_config_check(args: &Args) -> Result<()> {
+    let (config, mut issues) =
+        config::Config::load_with_issues(args.working_dir.as_deref().map(Path::new))?;
+    let theme_errors = syntax::load_theme_files(&[&config.diff.theme, &config.diff.light_theme]);
+    issues.extend(config_check::check_themes(
+        &config,
+        &syntax::available_themes(),
+        &theme_errors,
+    ));
+
+    // 出力をそのまま設定ファイルとして使えるよう、読み込んだファイルはコメントにする
+    let describe = |path: PathBuf, loaded: bool| {
+        let state = if loaded { "" } else { " (not found)" };
+        format!("{}{}", path.display(), state)
+    };
+    println!(
+        "# Global config: {}",
+        describe(
+            config::Config::config_path(),
+            config.loaded_global_config.is_some()
+        )
+    );
+    println!(
+        "# Local config:  {}",
+        describe(
+            config.project_root.join(".octorus/config.toml"),
+            config.loaded_local_config.is_some()
+        )
+    );
+    if !config.local_overrides.is_empty() {
+        let mut overrides: Vec<&str> = config.local_overrides.iter().map(String::as_str).collect();
+        overrides.sort_unstable();
+        println!("#   overrides: {}", overrides.join(", "));
+    }
+    println!();
+    println!("{}", toml::to_string_pretty(&config)?);
+
+    for issue in &issues {
+        let label = if issue.is_error() { "Error" } else { "Warning" };
+        eprintln!("{}: {}", label, issue);
+    }
+    if issues.iter().any(config_check::ConfigIssue::is_error) {
+        std::process::exit(exit_status::ERROR);
+    }
+    if issues.is_empty() {
+        eprintln!("No problems found");
+    }
+    Ok(())
+}
+
 /// Resolve working directory for headless mode
 fn resolve_working_dir(args: &Args) -> Option<String> {
     if let Some(dir) = args.working_dir.clone() {
@@ -477,6 +811,99 @@ fn resolve_working_dir(args: &Args) -> Option<String> {
     }
 }
 
+/// Resolve the repository shown in local mode: `--dir`, the current directory, or the
+/// first `[workspace] dirs` entry when started outside a repository.
+/// Directories are resolved to their repository root.
+fn resolve_local_dir(args: &Args, config: &config::Config) -> Option<String> {
+    if let Some(ref dir) = args.working_dir {
+        return match local_repos::resolve_dir(dir) {
+            Ok(dir) => Some(dir),
+            Err(e) => {
+                eprintln!("Error: {:#}", e);
+                std::process::exit(1);
+            }
+        };
+    }
+    local_repos::resolve_dir(".").ok().or_else(|| {
+        config
+            .workspace
+            .dirs
+            .iter()
+            .find_map(|dir| local_repos::resolve_dir(dir).ok())
+    })
+}
+
+/// Start the IPC server when --socket is given
+fn setup_ipc(app: &mut app::App, args: &Args) -> Result<()> {
+    let Some(ref path) = args.socket else {
+        return Ok(());
+    };
+    #[cfg(unix)]
+    {
+        let listener = octorus::ipc::bind(path)?;
+        let (tx, rx) = mpsc::channel(16);
+        app.set_ipc_receiver(rx);
+        tokio::spawn(octorus::ipc::serve(listener, tx));
+        Ok(())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = app;
+        anyhow::bail!("--socket is only supported on Unix ({})", path.display())
+    }
+}
+
+/// Start replaying a rally transcript given with --replay
+fn setup_replay(app: &mut app::App, args: &Args) -> Result<()> {
+    if let Some(ref path) = args.replay {
+        let transcript = octorus::ai::transcript::Transcript::load(path)?;
+        app.start_rally_replay(transcript);
+    }
+    Ok(())
+}
+
+/// Write a rally as a Markdown transcript for --export: the --replay transcript if given,
+/// otherwise the saved event log of the PR (of the working directory in local mode)
+fn export_rally_markdown(repo: &str, args: &Args, path: &Path) -> Result<()> {
+    use octorus::ai::{session, transcript::Transcript};
+
+    let transcript = if let Some(ref replay) = args.replay {
+        Transcript::load(replay)?
+    } else {
+        let pr_number = match args.pr {
+            Some(pr) => pr,
+            None if args.local => 0,
+            None => anyhow::bail!("--export needs --pr, --local or --replay"),
+        };
+        let events = session::read_events(&session::events_path(repo, pr_number)?);
+        if events.is_empty() {
+            anyhow::bail!("No saved AI Rally found for {} #{}", repo, pr_number);
+        }
+        Transcript::new(repo, pr_number, events)
+    };
+    std::fs::write(path, transcript.to_markdown())
+        .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    eprintln!("Wrote the rally transcript to {}", path.display());
+    Ok(())
+}
+
+/// Anonymize names on screen when --anonymize is given
+fn setup_anonymizer(app: &mut app::App, repo: &str, args: &Args) {
+    if args.anonymize {
+        app.set_anonymizer(octorus::anonymize::Anonymizer::new(
+            repo,
+            args.anonymize_repo,
+        ));
+    }
+}
+
+/// Remove the IPC socket file on exit
+fn cleanup_ipc(args: &Args) {
+    if let Some(ref path) = args.socket {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
 /// Set up working directory for AI agents
 fn setup_working_dir(app: &mut app::App, args: &Args) {
     if let Some(dir) = args.working_dir.clone() {
@@ -506,61 +933,121 @@ fn setup_working_dir(app: &mut app::App, args: &Args) {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use notify::event::{AccessKind, AccessMode, CreateKind};
-    use std::path::PathBuf;
 
     #[test]
-    fn test_should_refresh_local_change_ignores_access_events() {
-        let paths = vec![PathBuf::from("src/main.rs")];
-        let kind = EventKind::Access(AccessKind::Close(AccessMode::Write));
+    fn test_paths_option_splits_on_commas() {
+        let args = Args::try_parse_from(["or", "--paths", "services/api,libs/shared"]).unwrap();
+        assert_eq!(args.paths, vec!["services/api", "libs/shared"]);
+
+        let args = Args::try_parse_from(["or"]).unwrap();
+        assert!(args.paths.is_empty());
+    }
+
+    #[test]
+    fn test_dry_run_option() {
+        let args = Args::try_parse_from(["or", "--ai-rally", "--dry-run"]).unwrap();
+        assert!(args.ai_rally);
+        assert!(args.dry_run);
+
+        let args = Args::try_parse_from(["or"]).unwrap();
+        assert!(!args.dry_run);
+    }
+
+    #[test]
+    fn test_comment_and_approve_subcommands() {
+        let args = Args::try_parse_from([
+            "or",
+            "comment",
+            "--pr",
+            "12",
+            "--file",
+            "src/x.rs",
+            "--start-line",
+            "40",
+            "--line",
+            "42",
+            "--body",
+            "-",
+        ])
+        .unwrap();
+        match args.command {
+            Some(Commands::Comment {
+                pr,
+                file,
+                line,
+                start_line,
+                body,
+            }) => {
+                assert_eq!((pr, file.as_str(), line), (12, "src/x.rs", 42));
+                assert_eq!(start_line, Some(40));
+                assert_eq!(body, "-");
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+        assert!(Args::try_parse_from(["or", "comment", "--pr", "12", "--line", "42"]).is_err());
 
-        assert!(!should_refresh_local_change(&paths, &kind));
+        let args = Args::try_parse_from(["or", "approve", "--pr", "12"]).unwrap();
+        match args.command {
+            Some(Commands::Approve { pr, body }) => assert_eq!((pr, body.as_str()), (12, "")),
+            other => panic!("unexpected command: {:?}", other),
+        }
     }
 
     #[test]
-    fn test_should_refresh_local_change_ignores_git_paths() {
-        let paths = vec![PathBuf::from(".git/HEAD"), PathBuf::from(".git/index.lock")];
-        let kind = EventKind::Create(CreateKind::File);
+    fn test_diff_subcommand() {
+        let args = Args::try_parse_from(["or", "diff", "--pr", "12", "--json"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Commands::Diff { pr: 12, json: true })
+        ));
+
+        let args = Args::try_parse_from(["or", "diff", "--pr", "12"]).unwrap();
+        assert!(matches!(
+            args.command,
+            Some(Commands::Diff {
+                pr: 12,
+                json: false
+            })
+        ));
+    }
 
-        assert!(!should_refresh_local_change(&paths, &kind));
+    #[test]
+    fn test_export_option() {
+        let args = Args::try_parse_from(["or", "--pr", "1", "--export", "rally.md"]).unwrap();
+        assert_eq!(args.export, Some(PathBuf::from("rally.md")));
+
+        let args = Args::try_parse_from(["or"]).unwrap();
+        assert!(args.export.is_none());
     }
 
     #[test]
-    fn test_should_refresh_local_change_refreshes_subdir_change() {
-        let paths = vec![
-            PathBuf::from(".git/HEAD"),
-            PathBuf::from("src/subdir/changed.rs"),
-        ];
-        let kind = EventKind::Create(CreateKind::File);
+    fn test_replay_option_conflicts_with_ai_rally() {
+        let args = Args::try_parse_from(["or", "--replay", "rally.json"]).unwrap();
+        assert_eq!(args.replay, Some(PathBuf::from("rally.json")));
 
-        assert!(should_refresh_local_change(&paths, &kind));
+        assert!(Args::try_parse_from(["or", "--replay", "rally.json", "--ai-rally"]).is_err());
     }
 
     #[test]
-    fn test_is_git_file_identifies_git_path() {
-        assert!(is_git_file(std::path::Path::new(".git/refs/heads/main")));
-        assert!(!is_git_file(std::path::Path::new("src/main.rs")));
+    fn test_resume_rally_requires_ai_rally() {
+        let args = Args::try_parse_from(["or", "--ai-rally", "--resume-rally"]).unwrap();
+        assert!(args.ai_rally && args.resume_rally);
+
+        assert!(Args::try_parse_from(["or", "--resume-rally"]).is_err());
     }
 
     #[test]
-    fn test_should_refresh_local_change_ignores_octorus_paths() {
-        let paths = vec![
-            PathBuf::from(".octorus/config.toml"),
-            PathBuf::from(".octorus/prompts/reviewer.md"),
-        ];
-        let kind = EventKind::Create(CreateKind::File);
+    fn test_anonymize_repo_requires_anonymize() {
+        let args = Args::try_parse_from(["or", "--anonymize", "--anonymize-repo"]).unwrap();
+        assert!(args.anonymize && args.anonymize_repo);
 
-        assert!(!should_refresh_local_change(&paths, &kind));
+        assert!(Args::try_parse_from(["or", "--anonymize-repo"]).is_err());
     }
 
     #[test]
-    fn test_is_octorus_config_file() {
-        assert!(is_octorus_config_file(std::path::Path::new(
-            ".octorus/config.toml"
-        )));
-        assert!(is_octorus_config_file(std::path::Path::new(
-            ".octorus/prompts/reviewer.md"
-        )));
-        assert!(!is_octorus_config_file(std::path::Path::new("src/main.rs")));
+    fn test_inbox_subcommand_accepts_repo() {
+        let args = Args::try_parse_from(["or", "--repo", "owner/repo", "inbox"]).unwrap();
+        assert!(matches!(args.command, Some(Commands::Inbox)));
+        assert_eq!(args.repo, vec!["owner/repo"]);
     }
 }
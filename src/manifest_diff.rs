@@ -0,0 +1,522 @@
+//! 依存マニフェスト・ロックファイルの差分の要約
+//!
+//! Cargo.toml / Cargo.lock・package.json / package-lock.json / yarn.lock・go.mod・
+//! requirements.txt の patch から、追加・削除・バージョン変更された依存を取り出す。
+//! patch の行だけを見るため、ハンクの外にある情報（セクション名など）は使わない。
+
+use std::cmp::Ordering;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::diff::{classify_line, LineType};
+
+/// 依存のエコシステム（OSV の `ecosystem` 名と対応）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Ecosystem {
+    Cargo,
+    Npm,
+    Go,
+    PyPI,
+}
+
+impl Ecosystem {
+    /// OSV の `affected[].package.ecosystem`
+    pub fn osv_name(self) -> &'static str {
+        match self {
+            Self::Cargo => "crates.io",
+            Self::Npm => "npm",
+            Self::Go => "Go",
+            Self::PyPI => "PyPI",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileFormat {
+    CargoToml,
+    CargoLock,
+    PackageJson,
+    PackageLock,
+    YarnLock,
+    GoMod,
+    Requirements,
+}
+
+impl FileFormat {
+    fn detect(filename: &str) -> Option<Self> {
+        let name = filename.rsplit('/').next().unwrap_or(filename);
+        match name {
+            "Cargo.toml" => Some(Self::CargoToml),
+            "Cargo.lock" => Some(Self::CargoLock),
+            "package.json" => Some(Self::PackageJson),
+            "package-lock.json" | "npm-shrinkwrap.json" => Some(Self::PackageLock),
+            "yarn.lock" => Some(Self::YarnLock),
+            "go.mod" => Some(Self::GoMod),
+            _ if name.ends_with(".txt") && name.starts_with("requirements") => {
+                Some(Self::Requirements)
+            }
+            _ => None,
+        }
+    }
+
+    fn ecosystem(self) -> Ecosystem {
+        match self {
+            Self::CargoToml | Self::CargoLock => Ecosystem::Cargo,
+            Self::PackageJson | Self::PackageLock | Self::YarnLock => Ecosystem::Npm,
+            Self::GoMod => Ecosystem::Go,
+            Self::Requirements => Ecosystem::PyPI,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Upgraded,
+    Downgraded,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DependencyChange {
+    pub name: String,
+    pub kind: ChangeKind,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ManifestSummary {
+    pub ecosystem: Ecosystem,
+    pub changes: Vec<DependencyChange>,
+}
+
+impl ManifestSummary {
+    pub fn count(&self, kind: ChangeKind) -> usize {
+        self.changes.iter().filter(|c| c.kind == kind).count()
+    }
+}
+
+/// 依存マニフェストかどうか（patch を見ずにファイル名だけで判定）
+pub fn is_manifest(filename: &str) -> bool {
+    FileFormat::detect(filename).is_some()
+}
+
+/// マニフェストの patch を要約する。マニフェストでない、または依存の変更がなければ None
+pub fn summarize(filename: &str, patch: &str) -> Option<ManifestSummary> {
+    let format = FileFormat::detect(filename)?;
+    let mut versions = Versions::default();
+    match format {
+        FileFormat::CargoLock | FileFormat::PackageLock | FileFormat::YarnLock => {
+            collect_lock_versions(format, patch, &mut versions)
+        }
+        _ => {
+            for line in patch.lines() {
+                let (line_type, content) = classify_line(line);
+                if let Some((name, version)) = parse_manifest_line(format, content) {
+                    versions.record(line_type, &name, &version);
+                }
+            }
+        }
+    }
+    let changes = versions.changes();
+    if changes.is_empty() {
+        return None;
+    }
+    Some(ManifestSummary {
+        ecosystem: format.ecosystem(),
+        changes,
+    })
+}
+
+/// 依存名 → 変更前/変更後のバージョン集合
+#[derive(Default)]
+struct Versions {
+    old: BTreeMap<String, BTreeSet<String>>,
+    new: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl Versions {
+    fn record(&mut self, line_type: LineType, name: &str, version: &str) {
+        if matches!(line_type, LineType::Removed | LineType::Context) {
+            self.old
+                .entry(name.to_string())
+                .or_default()
+                .insert(version.to_string());
+        }
+        if matches!(line_type, LineType::Added | LineType::Context) {
+            self.new
+                .entry(name.to_string())
+                .or_default()
+                .insert(version.to_string());
+        }
+    }
+
+    fn changes(&self) -> Vec<DependencyChange> {
+        let empty = BTreeSet::new();
+        let names: BTreeSet<&String> = self.old.keys().chain(self.new.keys()).collect();
+        let mut changes = Vec::new();
+        for name in names {
+            let old = self.old.get(name).unwrap_or(&empty);
+            let new = self.new.get(name).unwrap_or(&empty);
+            let removed: Vec<&String> = old.difference(new).collect();
+            let added: Vec<&String> = new.difference(old).collect();
+            if let ([old_version], [new_version]) = (removed.as_slice(), added.as_slice()) {
+                let kind = match compare_versions(old_version, new_version) {
+                    Ordering::Greater => ChangeKind::Downgraded,
+                    _ => ChangeKind::Upgraded,
+                };
+                changes.push(DependencyChange {
+                    name: name.clone(),
+                    kind,
+                    old_version: Some((*old_version).clone()),
+                    new_version: Some((*new_version).clone()),
+                });
+                continue;
+            }
+            for version in removed {
+                changes.push(DependencyChange {
+                    name: name.clone(),
+                    kind: ChangeKind::Removed,
+                    old_version: Some(version.clone()),
+                    new_version: None,
+                });
+            }
+            for version in added {
+                changes.push(DependencyChange {
+                    name: name.clone(),
+                    kind: ChangeKind::Added,
+                    old_version: None,
+                    new_version: Some(version.clone()),
+                });
+            }
+        }
+        changes
+    }
+}
+
+/// マニフェストの 1 行から依存名とバージョン（要求）を読む
+fn parse_manifest_line(format: FileFormat, content: &str) -> Option<(String, String)> {
+    let content = content.trim();
+    match format {
+        FileFormat::CargoToml => {
+            let (key, value) = content.split_once('=')?;
+            let key = key.trim();
+            if ["version", "rust-version", "edition", "resolver"].contains(&key) {
+                return None;
+            }
+            let value = value.trim();
+            let version = match value.strip_prefix('{') {
+                // name = { version = "1", features = [...] }
+                Some(inline) => inline.split(',').find_map(|field| {
+                    let (k, v) = field.split_once('=')?;
+                    (k.trim() == "version").then(|| unquote(v.trim().trim_end_matches('}')))
+                })?,
+                // 数値だけの値（`opt-level = 3` など）は依存ではない
+                None if value.starts_with('"') => unquote(value),
+                None => return None,
+            };
+            looks_like_version(&version).then(|| (key.to_string(), version))
+        }
+        FileFormat::PackageJson => {
+            let (key, value) = content.split_once(':')?;
+            let key = unquote(key);
+            if key == "version" {
+                return None;
+            }
+            let version = unquote(value.trim().trim_end_matches(','));
+            looks_like_version(&version).then_some((key, version))
+        }
+        FileFormat::GoMod => {
+            if content.contains("=>") {
+                return None;
+            }
+            let content = content.strip_prefix("require ").unwrap_or(content);
+            let mut fields = content.split_whitespace();
+            let (module, version) = (fields.next()?, fields.next()?);
+            (module.contains('.') && version.starts_with('v'))
+                .then(|| (module.to_string(), version.to_string()))
+        }
+        FileFormat::Requirements => {
+            if content.starts_with('#') || content.starts_with('-') {
+                return None;
+            }
+            let content = content.split(['#', ';']).next()?.trim();
+            let split = content.find(['=', '<', '>', '~', '!'])?;
+            let name = content[..split].trim();
+            let version = content[split..].trim_start_matches(['=', '<', '>', '~', '!']);
+            (!name.is_empty() && !version.is_empty())
+                .then(|| (name.to_lowercase(), version.trim().to_string()))
+        }
+        _ => None,
+    }
+}
+
+/// ロックファイルの「パッケージ名の行 → バージョンの行」の並びを読む。
+/// 名前の行がハンク内にない依存は読めないので、ハンクの区切りで状態を捨てる
+fn collect_lock_versions(format: FileFormat, patch: &str, versions: &mut Versions) {
+    // (変更前の名前, 変更後の名前)
+    let mut old_name: Option<String> = None;
+    let mut new_name: Option<String> = None;
+    for line in patch.lines() {
+        let (line_type, content) = classify_line(line);
+        if line_type == LineType::Header {
+            old_name = None;
+            new_name = None;
+            continue;
+        }
+        if let Some(name) = lock_package_name(format, content) {
+            if line_type != LineType::Added {
+                old_name = name.clone();
+            }
+            if line_type != LineType::Removed {
+                new_name = name;
+            }
+            continue;
+        }
+        let Some(version) = lock_version(format, content) else {
+            continue;
+        };
+        if line_type != LineType::Added {
+            if let Some(ref name) = old_name {
+                versions.record(LineType::Removed, name, &version);
+            }
+        }
+        if line_type != LineType::Removed {
+            if let Some(ref name) = new_name {
+                versions.record(LineType::Added, name, &version);
+            }
+        }
+    }
+}
+
+/// パッケージの区切りの行なら `Some(名前)`（名前を持たない区切りは `Some(None)`）
+fn lock_package_name(format: FileFormat, content: &str) -> Option<Option<String>> {
+    match format {
+        FileFormat::CargoLock => {
+            if content.trim() == "[[package]]" {
+                return Some(None);
+            }
+            let value = content.strip_prefix("name = ")?;
+            Some(Some(unquote(value)))
+        }
+        FileFormat::PackageLock => {
+            // "node_modules/foo": {
+            let key = content.trim().strip_suffix('{')?.trim().strip_suffix(':')?;
+            let key = unquote(key);
+            Some(
+                key.rfind("node_modules/")
+                    .map(|i| key[i + "node_modules/".len()..].to_string()),
+            )
+        }
+        FileFormat::YarnLock => {
+            // lodash@^4.17.21, "lodash@^4.17.20":
+            if content.starts_with(' ') || content.starts_with('#') {
+                return None;
+            }
+            let spec = content.strip_suffix(':')?.split(',').next()?;
+            let spec = unquote(spec);
+            // `__metadata:` など名前を持たないエントリ
+            let at = spec.get(1..).and_then(|rest| rest.find('@'));
+            Some(at.map(|at| spec[..at + 1].to_string()))
+        }
+        _ => None,
+    }
+}
+
+fn lock_version(format: FileFormat, content: &str) -> Option<String> {
+    let content = content.trim();
+    let value = match format {
+        FileFormat::CargoLock => content.strip_prefix("version = ")?,
+        FileFormat::PackageLock => content
+            .strip_prefix("\"version\":")?
+            .trim()
+            .trim_end_matches(','),
+        FileFormat::YarnLock => content
+            .strip_prefix("version:")
+            .or_else(|| content.strip_prefix("version "))?,
+        _ => return None,
+    };
+    Some(unquote(value))
+}
+
+fn unquote(value: &str) -> String {
+    value.trim().trim_matches(['"', '\'']).to_string()
+}
+
+fn looks_like_version(value: &str) -> bool {
+    value
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_digit() || "^~<>=*".contains(c))
+}
+
+/// バージョン要求（`^1.2`・`>=1.0` など）から比較に使うバージョンを取り出す
+pub fn base_version(requirement: &str) -> &str {
+    requirement
+        .trim_start_matches(['^', '~', '=', '<', '>', 'v', ' '])
+        .split([',', ' '])
+        .next()
+        .unwrap_or(requirement)
+}
+
+/// ドット区切りのバージョンを比較する（数値の部分は数値として比べる）
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    let a_parts: Vec<&str> = base_version(a).split(['.', '-', '+']).collect();
+    let b_parts: Vec<&str> = base_version(b).split(['.', '-', '+']).collect();
+    for (x, y) in a_parts.iter().zip(b_parts.iter()) {
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    a_parts.len().cmp(&b_parts.len())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(
+        name: &str,
+        kind: ChangeKind,
+        old: Option<&str>,
+        new: Option<&str>,
+    ) -> DependencyChange {
+        DependencyChange {
+            name: name.to_string(),
+            kind,
+            old_version: old.map(str::to_string),
+            new_version: new.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_cargo_toml_changes() {
+        let patch = "@@ -1,6 +1,7 @@\n [package]\n-version = \"0.5.3\"\n+version = \"0.5.4\"\n [dependencies]\n-serde = \"1.0.200\"\n+serde = \"1.0.228\"\n-tokio = { version = \"1.40\", features = [\"rt\"] }\n+tokio = { version = \"1.49\", features = [\"rt\", \"net\"] }\n+chrono = \"0.4\"\n-old = \"0.1\"\n";
+        let summary = summarize("Cargo.toml", patch).unwrap();
+        assert_eq!(summary.ecosystem, Ecosystem::Cargo);
+        assert_eq!(
+            summary.changes,
+            vec![
+                change("chrono", ChangeKind::Added, None, Some("0.4")),
+                change("old", ChangeKind::Removed, Some("0.1"), None),
+                change(
+                    "serde",
+                    ChangeKind::Upgraded,
+                    Some("1.0.200"),
+                    Some("1.0.228")
+                ),
+                change("tokio", ChangeKind::Upgraded, Some("1.40"), Some("1.49")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cargo_toml_feature_change_is_not_a_version_change() {
+        let patch = "@@ -1 +1 @@\n-tokio = { version = \"1\", features = [\"rt\"] }\n+tokio = { version = \"1\", features = [\"net\"] }\n";
+        assert_eq!(summarize("Cargo.toml", patch), None);
+    }
+
+    #[test]
+    fn test_cargo_lock_changes() {
+        let patch = "@@ -10,12 +10,17 @@\n [[package]]\n name = \"serde\"\n-version = \"1.0.200\"\n+version = \"1.0.228\"\n source = \"registry\"\n \n+[[package]]\n+name = \"smallvec\"\n+version = \"1.15.0\"\n+\n [[package]]\n name = \"syn\"\n-version = \"2.0.1\"\n+version = \"1.0.109\"\n";
+        let summary = summarize("Cargo.lock", patch).unwrap();
+        assert_eq!(
+            summary.changes,
+            vec![
+                change(
+                    "serde",
+                    ChangeKind::Upgraded,
+                    Some("1.0.200"),
+                    Some("1.0.228")
+                ),
+                change("smallvec", ChangeKind::Added, None, Some("1.15.0")),
+                change(
+                    "syn",
+                    ChangeKind::Downgraded,
+                    Some("2.0.1"),
+                    Some("1.0.109")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_package_json_and_lock_changes() {
+        let patch = "@@ -1,6 +1,6 @@\n {\n-  \"version\": \"1.0.0\",\n+  \"version\": \"1.1.0\",\n   \"dependencies\": {\n-    \"react\": \"^18.2.0\",\n+    \"react\": \"^19.0.0\",\n     \"name\": \"app\"\n";
+        let summary = summarize("web/package.json", patch).unwrap();
+        assert_eq!(summary.ecosystem, Ecosystem::Npm);
+        assert_eq!(
+            summary.changes,
+            vec![change(
+                "react",
+                ChangeKind::Upgraded,
+                Some("^18.2.0"),
+                Some("^19.0.0")
+            )]
+        );
+
+        let patch = "@@ -5,6 +5,6 @@\n     \"node_modules/@babel/core\": {\n-      \"version\": \"7.24.0\",\n+      \"version\": \"7.25.2\",\n       \"resolved\": \"...\"\n";
+        let summary = summarize("package-lock.json", patch).unwrap();
+        assert_eq!(
+            summary.changes,
+            vec![change(
+                "@babel/core",
+                ChangeKind::Upgraded,
+                Some("7.24.0"),
+                Some("7.25.2")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_yarn_lock_go_mod_and_requirements() {
+        let patch = "@@ -1,4 +1,4 @@\n \"@types/node@^20.0.0\":\n-  version \"20.1.0\"\n+  version \"20.2.5\"\n";
+        let summary = summarize("yarn.lock", patch).unwrap();
+        assert_eq!(
+            summary.changes,
+            vec![change(
+                "@types/node",
+                ChangeKind::Upgraded,
+                Some("20.1.0"),
+                Some("20.2.5")
+            )]
+        );
+
+        let patch = "@@ -3,5 +3,5 @@\n go 1.22\n require (\n-\tgithub.com/foo/bar v1.2.0\n+\tgithub.com/foo/bar v1.10.0 // indirect\n";
+        let summary = summarize("go.mod", patch).unwrap();
+        assert_eq!(
+            summary.changes,
+            vec![change(
+                "github.com/foo/bar",
+                ChangeKind::Upgraded,
+                Some("v1.2.0"),
+                Some("v1.10.0")
+            )]
+        );
+
+        let patch = "@@ -1,2 +1,2 @@\n # pinned\n-Django==4.2.1\n+Django==5.0.3 ; python_version >= \"3.10\"\n";
+        let summary = summarize("requirements-dev.txt", patch).unwrap();
+        assert_eq!(summary.ecosystem, Ecosystem::PyPI);
+        assert_eq!(
+            summary.changes,
+            vec![change(
+                "django",
+                ChangeKind::Upgraded,
+                Some("4.2.1"),
+                Some("5.0.3")
+            )]
+        );
+    }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2.10", "1.2.9"), Ordering::Greater);
+        assert_eq!(compare_versions("^1.2", "1.2.0"), Ordering::Less);
+        assert_eq!(compare_versions("v1.10.0", "v1.2.0"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0.0", "2.0.0"), Ordering::Equal);
+    }
+}
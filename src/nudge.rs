@@ -0,0 +1,105 @@
+//! 放置されている PR へのリマインドコメント
+//!
+//! PR 一覧で最終更新から設定日数以上経った PR を選び、テンプレートから
+//! 丁寧なリマインドコメントを作る。テンプレートの変数はプロンプトテンプレートと
+//! 同じ `{{name}}` 形式で置換する。
+
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+
+use crate::ai::prompt_loader::render_template;
+
+/// `[nudge] template` の既定値
+pub const DEFAULT_TEMPLATE: &str = "Hi {{reviewers}} 👋 Friendly reminder: this PR has been waiting for {{days}} days since its last update. Could you take a look when you have a moment? Thank you!";
+
+/// テンプレートに埋め込む PR の情報
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NudgeContext {
+    pub number: u32,
+    pub title: String,
+    pub author: String,
+    /// 最終更新からの経過日数
+    pub days: i64,
+    /// レビュー依頼中のユーザー・チーム（`@` なし）
+    pub reviewers: Vec<String>,
+}
+
+/// 最終更新（RFC 3339）からの経過日数。パースできなければ None
+pub fn days_stale(updated_at: &str, now: DateTime<Utc>) -> Option<i64> {
+    let updated = DateTime::parse_from_rfc3339(updated_at).ok()?;
+    Some((now - updated.with_timezone(&Utc)).num_days().max(0))
+}
+
+/// テンプレートの変数を置換する。
+///
+/// - `{{days}}`: 最終更新からの経過日数
+/// - `{{reviewers}}`: レビュー依頼中の相手へのメンション。誰もいなければ作成者へのメンション
+/// - `{{author}}`: 作成者へのメンション
+/// - `{{number}}` / `{{title}}`: PR の番号とタイトル
+pub fn render(template: &str, ctx: &NudgeContext) -> String {
+    let author = format!("@{}", ctx.author);
+    let reviewers = if ctx.reviewers.is_empty() {
+        author.clone()
+    } else {
+        ctx.reviewers
+            .iter()
+            .map(|r| format!("@{}", r))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let vars = HashMap::from([
+        ("days", ctx.days.to_string()),
+        ("reviewers", reviewers),
+        ("author", author),
+        ("number", ctx.number.to_string()),
+        ("title", ctx.title.clone()),
+    ]);
+    render_template(template, &vars)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn context(reviewers: &[&str]) -> NudgeContext {
+        NudgeContext {
+            number: 42,
+            title: "Add feature".to_string(),
+            author: "alice".to_string(),
+            days: 5,
+            reviewers: reviewers.iter().map(|r| r.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn test_days_stale() {
+        let now = DateTime::parse_from_rfc3339("2024-01-10T12:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(days_stale("2024-01-05T13:00:00Z", now), Some(4));
+        assert_eq!(days_stale("2024-01-05T12:00:00+00:00", now), Some(5));
+        // 時計のずれで未来の日時でも負にしない
+        assert_eq!(days_stale("2024-01-11T00:00:00Z", now), Some(0));
+        assert_eq!(days_stale("yesterday", now), None);
+    }
+
+    #[test]
+    fn test_render_mentions_requested_reviewers() {
+        let body = render(
+            "{{reviewers}}: #{{number}} {{title}} by {{author}}, {{days}} days",
+            &context(&["bob", "acme/backend"]),
+        );
+        assert_eq!(
+            body,
+            "@bob @acme/backend: #42 Add feature by @alice, 5 days"
+        );
+    }
+
+    #[test]
+    fn test_render_falls_back_to_author() {
+        let body = render(DEFAULT_TEMPLATE, &context(&[]));
+        assert!(body.starts_with("Hi @alice 👋"));
+        assert!(body.contains("waiting for 5 days"));
+    }
+}
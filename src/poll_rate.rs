@@ -0,0 +1,128 @@
+//! 活動状況に応じたポーリング間隔
+//!
+//! メインループのイベント待ち（通常 100ms）と PR 監視のポーリング間隔を、
+//! 読み込みや AI Rally の実行中は短く、何も実行しておらず入力もしばらくない間は
+//! 長くする。ノート PC で開きっぱなしにしたときの電池の消費を抑えるため。
+
+use std::time::{Duration, Instant};
+
+use crate::config::PollingConfig;
+
+/// 読み込み・Rally の実行中のイベント待ち
+const BUSY_EVENT_TIMEOUT: Duration = Duration::from_millis(50);
+/// 通常のイベント待ち
+const ACTIVE_EVENT_TIMEOUT: Duration = Duration::from_millis(100);
+/// アイドル中のイベント待ち（キー入力はすぐ届くので応答性は変わらない）
+const IDLE_EVENT_TIMEOUT: Duration = Duration::from_millis(1000);
+/// アイドル中は PR 監視の間隔をこの倍数に延ばす
+const IDLE_WATCH_FACTOR: u32 = 4;
+
+/// ポーリングの速さ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PollTier {
+    /// 読み込みや Rally が実行中
+    Busy,
+    Active,
+    /// 何も実行しておらず、入力もしばらくない
+    Idle,
+}
+
+impl PollTier {
+    /// メインループでイベントを待つ時間
+    pub fn event_timeout(self) -> Duration {
+        match self {
+            Self::Busy => BUSY_EVENT_TIMEOUT,
+            Self::Active => ACTIVE_EVENT_TIMEOUT,
+            Self::Idle => IDLE_EVENT_TIMEOUT,
+        }
+    }
+
+    /// PR 監視のポーリング間隔
+    pub fn watch_interval(self, base: Duration) -> Duration {
+        match self {
+            Self::Idle => base * IDLE_WATCH_FACTOR,
+            Self::Busy | Self::Active => base,
+        }
+    }
+}
+
+/// 最後の入力からの経過時間と実行中の処理からポーリングの速さを決める
+#[derive(Debug, Clone)]
+pub struct ActivityTracker {
+    adaptive: bool,
+    idle_after: Duration,
+    last_input: Instant,
+}
+
+impl ActivityTracker {
+    pub fn new(config: &PollingConfig, now: Instant) -> Self {
+        Self {
+            adaptive: config.adaptive,
+            idle_after: Duration::from_secs(config.idle_after_secs),
+            last_input: now,
+        }
+    }
+
+    /// キー入力・リサイズなどのイベントを受け取った
+    pub fn record_input(&mut self, now: Instant) {
+        self.last_input = now;
+    }
+
+    /// `busy` は読み込みや Rally などが実行中か
+    pub fn tier(&self, busy: bool, now: Instant) -> PollTier {
+        if !self.adaptive {
+            PollTier::Active
+        } else if busy {
+            PollTier::Busy
+        } else if now.saturating_duration_since(self.last_input) >= self.idle_after {
+            PollTier::Idle
+        } else {
+            PollTier::Active
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(adaptive: bool) -> PollingConfig {
+        PollingConfig {
+            adaptive,
+            idle_after_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_tier_follows_activity() {
+        let start = Instant::now();
+        let mut tracker = ActivityTracker::new(&config(true), start);
+        assert_eq!(tracker.tier(false, start), PollTier::Active);
+        assert_eq!(tracker.tier(true, start), PollTier::Busy);
+
+        let later = start + Duration::from_secs(61);
+        assert_eq!(tracker.tier(false, later), PollTier::Idle);
+        // 実行中の処理があればアイドルにしない
+        assert_eq!(tracker.tier(true, later), PollTier::Busy);
+
+        tracker.record_input(later);
+        assert_eq!(tracker.tier(false, later), PollTier::Active);
+    }
+
+    #[test]
+    fn test_disabled_keeps_fixed_rate() {
+        let start = Instant::now();
+        let tracker = ActivityTracker::new(&config(false), start);
+        let later = start + Duration::from_secs(3600);
+        assert_eq!(tracker.tier(true, later), PollTier::Active);
+        assert_eq!(tracker.tier(false, later), PollTier::Active);
+        assert_eq!(PollTier::Active.event_timeout(), Duration::from_millis(100));
+    }
+
+    #[test]
+    fn test_watch_interval_slows_down_when_idle() {
+        let base = Duration::from_secs(20);
+        assert_eq!(PollTier::Busy.watch_interval(base), base);
+        assert_eq!(PollTier::Idle.watch_interval(base), Duration::from_secs(80));
+    }
+}
@@ -0,0 +1,312 @@
+//! ローカルブランチからの PR 作成
+//!
+//! ローカルモードで見ているブランチを、リポジトリを指すリモートへ push してから PR を作る。
+//! タイトルと本文はベースブランチからのコミットで下書きし、エディタで仕上げてもらう。
+
+use anyhow::{bail, Context, Result};
+use std::process::Command;
+
+use crate::checkout::remote_for_repo;
+
+/// PR の作成元になるローカルブランチ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LocalBranch {
+    pub name: String,
+    /// push 先のリモート（リポジトリを指すもの、`origin` を優先）
+    pub remote: String,
+    /// ベースにできるリモートのブランチ。リモートの既定のブランチを先頭に置く
+    pub bases: Vec<String>,
+}
+
+/// PR のタイトルと本文
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrDraft {
+    pub title: String,
+    pub body: String,
+}
+
+impl PrDraft {
+    /// エディタに渡すテキスト。1 行目がタイトル、空行を挟んで本文
+    pub fn to_editor_text(&self) -> String {
+        if self.body.is_empty() {
+            self.title.clone()
+        } else {
+            format!("{}\n\n{}", self.title, self.body)
+        }
+    }
+}
+
+fn run_git(working_dir: Option<&str>, args: &[&str]) -> Result<String> {
+    let mut command = Command::new("git");
+    if let Some(dir) = working_dir {
+        command.current_dir(dir);
+    }
+    let output = command
+        .args(args)
+        .output()
+        .context("failed to spawn git command")?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        bail!("git {} failed: {}", args.join(" "), stderr.trim());
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// 現在のブランチと、`repo` を指すリモートのベースにできるブランチを調べる。
+/// detached HEAD やリモートの既定のブランチの上では PR を作れないのでエラーにする
+pub fn inspect(working_dir: Option<&str>, repo: &str) -> Result<LocalBranch> {
+    let name = run_git(working_dir, &["symbolic-ref", "--quiet", "--short", "HEAD"])
+        .context("HEAD is detached; switch to a branch first")?;
+    let remotes = run_git(working_dir, &["remote", "-v"])?;
+    let remote = remote_for_repo(&remotes, repo)
+        .with_context(|| format!("No git remote points to {}", repo))?;
+
+    let prefix = format!("{}/", remote);
+    let default = run_git(
+        working_dir,
+        &[
+            "symbolic-ref",
+            "--quiet",
+            "--short",
+            &format!("refs/remotes/{}/HEAD", remote),
+        ],
+    )
+    .ok()
+    .and_then(|head| head.strip_prefix(&prefix).map(str::to_string));
+    if default.as_deref() == Some(name.as_str()) {
+        bail!(
+            "{} is the default branch; switch to a feature branch first",
+            name
+        );
+    }
+
+    let refs = run_git(
+        working_dir,
+        &[
+            "for-each-ref",
+            "--format=%(refname:short)",
+            &format!("refs/remotes/{}/", remote),
+        ],
+    )?;
+    let bases = order_bases(
+        refs.lines()
+            .filter_map(|line| line.strip_prefix(&prefix))
+            .filter(|branch| *branch != "HEAD" && *branch != name),
+        default.as_deref(),
+    );
+    Ok(LocalBranch {
+        name,
+        remote,
+        bases,
+    })
+}
+
+/// 既定のブランチ、`main` / `master`、残りの順に並べる
+fn order_bases<'a>(branches: impl Iterator<Item = &'a str>, default: Option<&str>) -> Vec<String> {
+    let mut bases: Vec<String> = branches.map(str::to_string).collect();
+    bases.sort_by_key(|branch| {
+        if Some(branch.as_str()) == default {
+            0
+        } else if branch == "main" || branch == "master" {
+            1
+        } else {
+            2
+        }
+    });
+    bases
+}
+
+/// ベースブランチからのコミットで PR のタイトルと本文を下書きする
+pub fn draft(working_dir: Option<&str>, branch: &LocalBranch, base: &str) -> PrDraft {
+    let range = format!("{}/{}..HEAD", branch.remote, base);
+    let log = run_git(
+        working_dir,
+        &["log", "--reverse", "--format=%s%x1f%b%x1e", &range],
+    )
+    .unwrap_or_default();
+    let commits: Vec<(String, String)> = log
+        .split('\x1e')
+        .filter_map(|entry| {
+            let (subject, body) = entry.trim().split_once('\x1f')?;
+            Some((subject.trim().to_string(), body.trim().to_string()))
+        })
+        .collect();
+    draft_from_commits(&commits, &branch.name)
+}
+
+/// 1 コミットならその件名と本文を、複数ならブランチ名をタイトルにして件名を箇条書きにする
+fn draft_from_commits(commits: &[(String, String)], branch: &str) -> PrDraft {
+    match commits {
+        [(subject, body)] => PrDraft {
+            title: subject.clone(),
+            body: body.clone(),
+        },
+        _ => {
+            let title = branch
+                .rsplit('/')
+                .next()
+                .unwrap_or(branch)
+                .replace(['-', '_'], " ");
+            let mut chars = title.chars();
+            let title = match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => branch.to_string(),
+            };
+            let body = commits
+                .iter()
+                .map(|(subject, _)| format!("- {}", subject))
+                .collect::<Vec<_>>()
+                .join("\n");
+            PrDraft { title, body }
+        }
+    }
+}
+
+/// エディタで編集したテキストを読む。最初の空でない行がタイトル、残りが本文。
+/// タイトルがなければ None
+pub fn parse_draft(text: &str) -> Option<PrDraft> {
+    let text = text.trim_start();
+    let (title, body) = text.split_once('\n').unwrap_or((text, ""));
+    let title = title.trim();
+    if title.is_empty() {
+        return None;
+    }
+    Some(PrDraft {
+        title: title.to_string(),
+        body: body.trim().to_string(),
+    })
+}
+
+/// ブランチをリモートへ push し、upstream に設定する
+pub fn push(working_dir: Option<&str>, branch: &LocalBranch) -> Result<()> {
+    run_git(
+        working_dir,
+        &["push", "--quiet", "-u", &branch.remote, &branch.name],
+    )?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+    use tempfile::tempdir;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .env("GIT_AUTHOR_NAME", "octorus-test")
+            .env("GIT_AUTHOR_EMAIL", "octorus-test@example.com")
+            .env("GIT_COMMITTER_NAME", "octorus-test")
+            .env("GIT_COMMITTER_EMAIL", "octorus-test@example.com")
+            .status()
+            .expect("failed to run git");
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    fn commit(dir: &Path, file: &str, message: &str) {
+        std::fs::write(dir.join(file), file).unwrap();
+        git(dir, &["add", file]);
+        git(dir, &["commit", "-q", "-m", message]);
+    }
+
+    #[test]
+    fn test_draft_from_commits() {
+        let single = vec![("Fix the parser".to_string(), "Details.".to_string())];
+        assert_eq!(
+            draft_from_commits(&single, "fix-parser"),
+            PrDraft {
+                title: "Fix the parser".to_string(),
+                body: "Details.".to_string(),
+            }
+        );
+
+        let several = vec![
+            ("Add a flag".to_string(), String::new()),
+            ("Document the flag".to_string(), "More.".to_string()),
+        ];
+        assert_eq!(
+            draft_from_commits(&several, "feature/new_cli-flag"),
+            PrDraft {
+                title: "New cli flag".to_string(),
+                body: "- Add a flag\n- Document the flag".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn test_parse_draft() {
+        assert_eq!(
+            parse_draft("\n  Title here \n\nBody line 1\nBody line 2\n\n"),
+            Some(PrDraft {
+                title: "Title here".to_string(),
+                body: "Body line 1\nBody line 2".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_draft("Only a title"),
+            Some(PrDraft {
+                title: "Only a title".to_string(),
+                body: String::new(),
+            })
+        );
+        assert_eq!(parse_draft("  \n\n"), None);
+    }
+
+    #[test]
+    fn test_order_bases_puts_default_first() {
+        let branches = ["develop", "main", "release"];
+        assert_eq!(
+            order_bases(branches.into_iter(), Some("develop")),
+            vec!["develop", "main", "release"]
+        );
+        assert_eq!(
+            order_bases(branches.into_iter(), None),
+            vec!["main", "develop", "release"]
+        );
+    }
+
+    #[test]
+    fn test_inspect_drafts_and_pushes_branch() {
+        let tempdir = tempdir().unwrap();
+        let origin = tempdir.path().join("owner").join("repo");
+        std::fs::create_dir_all(&origin).unwrap();
+        git(&origin, &["init", "-q", "-b", "main"]);
+        commit(&origin, "a.txt", "Initial commit");
+        git(&origin, &["branch", "release"]);
+
+        let clone = tempdir.path().join("clone");
+        git(
+            tempdir.path(),
+            &["clone", "-q", origin.to_str().unwrap(), "clone"],
+        );
+        let wd = clone.to_str();
+
+        let err = inspect(wd, "owner/repo").unwrap_err();
+        assert!(err.to_string().contains("default branch"), "{}", err);
+
+        git(&clone, &["switch", "-q", "-c", "add-greeting"]);
+        commit(&clone, "b.txt", "Add a greeting");
+        let branch = inspect(wd, "owner/repo").unwrap();
+        assert_eq!(branch.name, "add-greeting");
+        assert_eq!(branch.remote, "origin");
+        assert_eq!(branch.bases, vec!["main", "release"]);
+
+        assert_eq!(draft(wd, &branch, "main").title, "Add a greeting");
+
+        push(wd, &branch).unwrap();
+        assert_eq!(
+            run_git(
+                Some(origin.to_str().unwrap()),
+                &["branch", "--list", "add-greeting"]
+            )
+            .unwrap(),
+            "add-greeting"
+        );
+        assert_eq!(
+            run_git(wd, &["rev-parse", "--abbrev-ref", "@{upstream}"]).unwrap(),
+            "origin/add-greeting"
+        );
+    }
+}
@@ -0,0 +1,268 @@
+//! PR 全体の AI による要約
+//!
+//! PR のタイトル・本文・変更ファイルの patch を `ai.reviewer` のエージェントに 1 回渡し、
+//! 概要・リスクのある箇所・ファイルを読む順番・破壊的変更を JSON で受け取る。
+//! 要約は head コミットごとにキャッシュディレクトリの
+//! `summaries/<owner>_<repo>-<PR 番号>.json` に保存し、head が変わるまで使い回す
+
+use std::path::PathBuf;
+
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::ai::oneshot::OneshotAgent;
+use crate::cache::{cache_dir, sanitize_repo_name};
+use crate::config::{CacheConfig, Config};
+use crate::github::ChangedFile;
+
+/// プロンプトに含める patch の合計の最大文字数
+const MAX_DIFF_CHARS: usize = 60_000;
+
+const SUMMARY_CACHE_DIR: &str = "summaries";
+
+/// PR の要約
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PrSummary {
+    /// 変更の概要（Markdown）
+    pub overview: String,
+    #[serde(default)]
+    pub risk_areas: Vec<SummaryItem>,
+    /// 読むとよい順に並べたファイル
+    #[serde(default)]
+    pub review_order: Vec<SummaryItem>,
+    #[serde(default)]
+    pub breaking_changes: Vec<String>,
+}
+
+/// ファイル（または箇所）とその理由
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SummaryItem {
+    #[serde(default)]
+    pub path: String,
+    #[serde(default)]
+    pub reason: String,
+}
+
+/// 要約を頼む PR
+#[derive(Debug, Clone)]
+pub struct SummaryRequest {
+    pub title: String,
+    pub body: String,
+    pub files: Vec<ChangedFile>,
+}
+
+/// 要約を頼むエージェント
+pub struct PrSummarizer {
+    agent: OneshotAgent,
+}
+
+impl PrSummarizer {
+    pub fn from_config(config: &Config, repo: &str) -> Result<Self> {
+        Ok(Self {
+            agent: OneshotAgent::from_config(config, repo, "PR summaries")?,
+        })
+    }
+
+    /// ステータス表示用の名前
+    pub fn name(&self) -> &'static str {
+        self.agent.name()
+    }
+
+    pub async fn summarize(&self, request: &SummaryRequest) -> Result<PrSummary> {
+        let output = self.agent.run(&build_prompt(request)).await?;
+        parse_summary(&output)
+    }
+}
+
+pub fn build_prompt(request: &SummaryRequest) -> String {
+    let file_list: Vec<String> = request
+        .files
+        .iter()
+        .map(|file| {
+            format!(
+                "- {} ({}, +{} -{})",
+                file.filename, file.status, file.additions, file.deletions
+            )
+        })
+        .collect();
+
+    let mut diff = String::new();
+    let mut omitted = 0;
+    for file in &request.files {
+        let Some(patch) = file.patch.as_deref() else {
+            continue;
+        };
+        if diff.len() + patch.len() > MAX_DIFF_CHARS {
+            omitted += 1;
+            continue;
+        }
+        diff.push_str(&format!("--- {}\n{}\n", file.filename, patch));
+    }
+    if omitted > 0 {
+        diff.push_str(&format!("... ({} more patches omitted)\n", omitted));
+    }
+
+    let body = match request.body.trim() {
+        "" => "(no description)",
+        body => body,
+    };
+    format!(
+        "Summarize the pull request \"{title}\" for a code reviewer. \
+Reply with a single JSON object and nothing else, using this shape:\n\
+{{\"overview\": \"what the PR does, in a few sentences of Markdown\", \
+\"risk_areas\": [{{\"path\": \"file path\", \"reason\": \"what could go wrong\"}}], \
+\"review_order\": [{{\"path\": \"file path\", \"reason\": \"why read it at this point\"}}], \
+\"breaking_changes\": [\"change that breaks existing users, callers or data\"]}}\n\
+List every changed file in review_order, starting with the files that explain the rest. \
+Leave risk_areas and breaking_changes empty when there are none. Do not use any tools.\n\n\
+Description:\n{body}\n\n\
+Changed files:\n{files}\n\n\
+```diff\n{diff}```",
+        title = request.title,
+        files = file_list.join("\n"),
+    )
+}
+
+/// エージェントの出力から要約の JSON を取り出す。コードブロックや前後の文章は無視する
+pub fn parse_summary(output: &str) -> Result<PrSummary> {
+    let start = output.find('{');
+    let end = output.rfind('}');
+    let json = match (start, end) {
+        (Some(start), Some(end)) if start < end => &output[start..=end],
+        _ => return Err(anyhow!("The agent did not return a JSON summary")),
+    };
+    serde_json::from_str(json).context("Failed to parse the PR summary")
+}
+
+/// 保存する要約。head が変わったら使わない
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedSummary {
+    head_sha: String,
+    summary: PrSummary,
+}
+
+/// 要約のディスクキャッシュ。PR ごとに最新の head の要約を 1 つだけ保存する
+#[derive(Debug, Clone)]
+pub struct SummaryCache {
+    dir: PathBuf,
+}
+
+impl SummaryCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    /// `[cache]` の設定から作る。無効なら None
+    pub fn from_config(config: &CacheConfig) -> Option<Self> {
+        config
+            .enabled
+            .then(|| Self::new(cache_dir().join(SUMMARY_CACHE_DIR)))
+    }
+
+    fn entry_path(&self, repo: &str, pr_number: u32) -> Option<PathBuf> {
+        let repo = sanitize_repo_name(repo).ok()?;
+        Some(self.dir.join(format!("{}-{}.json", repo, pr_number)))
+    }
+
+    /// `head_sha` の要約を読む。保存がないか head が違えば None
+    pub fn load(&self, repo: &str, pr_number: u32, head_sha: &str) -> Option<PrSummary> {
+        let content = std::fs::read_to_string(self.entry_path(repo, pr_number)?).ok()?;
+        let cached: CachedSummary = serde_json::from_str(&content).ok()?;
+        (cached.head_sha == head_sha).then_some(cached.summary)
+    }
+
+    pub fn save(
+        &self,
+        repo: &str,
+        pr_number: u32,
+        head_sha: &str,
+        summary: &PrSummary,
+    ) -> Result<()> {
+        let path = self
+            .entry_path(repo, pr_number)
+            .ok_or_else(|| anyhow!("Invalid repository name: {}", repo))?;
+        std::fs::create_dir_all(&self.dir)?;
+        let cached = CachedSummary {
+            head_sha: head_sha.to_string(),
+            summary: summary.clone(),
+        };
+        std::fs::write(path, serde_json::to_string(&cached)?)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(filename: &str, patch: Option<&str>) -> ChangedFile {
+        ChangedFile {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 1,
+            patch: patch.map(str::to_string),
+            viewed: false,
+        }
+    }
+
+    fn summary() -> PrSummary {
+        PrSummary {
+            overview: "Adds retries".to_string(),
+            risk_areas: vec![SummaryItem {
+                path: "src/net.rs".to_string(),
+                reason: "retries are unbounded".to_string(),
+            }],
+            review_order: Vec::new(),
+            breaking_changes: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_build_prompt_lists_files_and_limits_patches() {
+        let big = "+x\n".repeat(MAX_DIFF_CHARS / 3);
+        let request = SummaryRequest {
+            title: "Add retry".to_string(),
+            body: "  ".to_string(),
+            files: vec![
+                file("src/net.rs", Some("@@ -1 +1 @@\n-a\n+b")),
+                file("assets/logo.png", None),
+                file("src/big.rs", Some(&big)),
+            ],
+        };
+        let prompt = build_prompt(&request);
+        assert!(prompt.starts_with("Summarize the pull request \"Add retry\""));
+        assert!(prompt.contains("Description:\n(no description)"));
+        assert!(prompt.contains("- src/net.rs (modified, +1 -1)\n- assets/logo.png"));
+        assert!(prompt.contains("--- src/net.rs\n@@ -1 +1 @@\n-a\n+b\n"));
+        assert!(!prompt.contains("--- src/big.rs"));
+        assert!(prompt.contains("(1 more patches omitted)"));
+    }
+
+    #[test]
+    fn test_parse_summary() {
+        let output = "Here it is:\n```json\n{\"overview\": \"Adds retries\", \"risk_areas\": \
+[{\"path\": \"src/net.rs\", \"reason\": \"retries are unbounded\"}]}\n```";
+        assert_eq!(parse_summary(output).unwrap(), summary());
+
+        assert!(parse_summary("no summary").is_err());
+        assert!(parse_summary("{\"risk_areas\": []}").is_err());
+    }
+
+    #[test]
+    fn test_summary_cache_is_per_head() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = SummaryCache::new(dir.path().join(SUMMARY_CACHE_DIR));
+        assert!(cache.load("owner/repo", 1, "abc").is_none());
+
+        cache.save("owner/repo", 1, "abc", &summary()).unwrap();
+        assert_eq!(cache.load("owner/repo", 1, "abc"), Some(summary()));
+        assert!(cache.load("owner/repo", 1, "def").is_none());
+        assert!(cache.load("owner/repo", 2, "abc").is_none());
+
+        // 新しい head の要約で置き換わる
+        cache.save("owner/repo", 1, "def", &summary()).unwrap();
+        assert!(cache.load("owner/repo", 1, "abc").is_none());
+        assert!(cache.save("../evil", 1, "abc", &summary()).is_err());
+    }
+}
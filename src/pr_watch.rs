@@ -0,0 +1,166 @@
+//! 開いている PR の更新監視
+//!
+//! `gh api repos/{repo}/pulls/{n}` を一定間隔でポーリングし、head SHA とコメント数の
+//! 変化から「push された」「レビューコメントが増えた」「会話コメントが増えた」を
+//! 判別する。変化の種類ごとに必要なデータだけを再取得できるよう、差分を
+//! [`PrChange`] として通知する。アイドル中はポーリング間隔を延ばし、
+//! 操作が再開されたらすぐに問い合わせる。API のレート制限の残りが少ないときも
+//! 間隔を延ばし、使い切ったらリセットまで問い合わせない。
+
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use crate::github;
+use crate::poll_rate::PollTier;
+
+/// ポーリング間隔の下限（秒）
+pub const MIN_INTERVAL_SECS: u64 = 5;
+
+/// PR に起きた変化の種類
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PrChange {
+    /// 新しいコミット（ファイル一覧と diff を再取得）
+    Files,
+    /// レビュー / インラインコメント
+    ReviewComments,
+    /// 会話（issue）コメント
+    Discussion,
+}
+
+/// 変化検出に使う PR の要約
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrSnapshot {
+    pub head_sha: String,
+    pub updated_at: String,
+    pub comments: u64,
+    pub review_comments: u64,
+}
+
+impl PrSnapshot {
+    pub fn from_json(value: &serde_json::Value) -> Option<Self> {
+        Some(Self {
+            head_sha: value["head"]["sha"].as_str()?.to_string(),
+            updated_at: value["updated_at"].as_str()?.to_string(),
+            comments: value["comments"].as_u64().unwrap_or(0),
+            review_comments: value["review_comments"].as_u64().unwrap_or(0),
+        })
+    }
+
+    /// `newer` までに起きた変化。何も変わっていなければ空
+    pub fn changes_since(&self, newer: &PrSnapshot) -> Vec<PrChange> {
+        let mut changes = Vec::new();
+        if self.head_sha != newer.head_sha {
+            changes.push(PrChange::Files);
+        }
+        if self.review_comments != newer.review_comments {
+            changes.push(PrChange::ReviewComments);
+        }
+        if self.comments != newer.comments {
+            changes.push(PrChange::Discussion);
+        }
+        // レビュー本文のみの投稿や編集はカウントに現れないため、
+        // updated_at だけが進んだ場合はレビュー側を取り直す
+        if changes.is_empty() && self.updated_at != newer.updated_at {
+            changes.push(PrChange::ReviewComments);
+        }
+        changes
+    }
+}
+
+pub async fn fetch_snapshot(repo: &str, pr_number: u32) -> Result<PrSnapshot> {
+    let endpoint = format!("repos/{}/pulls/{}", repo, pr_number);
+    let value = github::gh_api(&endpoint).await?;
+    PrSnapshot::from_json(&value).context("Unexpected pull request response")
+}
+
+/// PR をポーリングし、変化があるたびに `tx` へ通知する。受信側が閉じたら終了。
+/// 間隔は `tier`（メインループの活動状況）に合わせて変える
+pub async fn watch(
+    repo: String,
+    pr_number: u32,
+    interval_secs: u64,
+    tx: mpsc::Sender<(u32, Vec<PrChange>)>,
+    mut tier: tokio::sync::watch::Receiver<PollTier>,
+) {
+    let interval = Duration::from_secs(interval_secs.max(MIN_INTERVAL_SECS));
+    let mut last: Option<PrSnapshot> = None;
+    loop {
+        match fetch_snapshot(&repo, pr_number).await {
+            Ok(snapshot) => {
+                if let Some(ref previous) = last {
+                    let changes = previous.changes_since(&snapshot);
+                    if !changes.is_empty() && tx.send((pr_number, changes)).await.is_err() {
+                        return;
+                    }
+                }
+                last = Some(snapshot);
+            }
+            // レート制限は次の間隔を延ばして待つだけなので警告しない
+            Err(e) if github::is_rate_limit_error(&format!("{:#}", e)) => {
+                debug!("Rate limited while polling PR #{}: {:#}", pr_number, e)
+            }
+            Err(e) => warn!("Failed to poll PR #{}: {:#}", pr_number, e),
+        }
+        if tx.is_closed() {
+            return;
+        }
+        let current = *tier.borrow_and_update();
+        let base = current.watch_interval(interval);
+        let wait = github::rate_limited_interval(base);
+        tokio::select! {
+            _ = tokio::time::sleep(wait) => {}
+            // アイドルから戻ったらすぐに問い合わせる（レート制限で待っている間は除く）
+            Ok(()) = tier.changed(), if current == PollTier::Idle && wait == base => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(sha: &str, updated: &str, comments: u64, review_comments: u64) -> PrSnapshot {
+        PrSnapshot {
+            head_sha: sha.to_string(),
+            updated_at: updated.to_string(),
+            comments,
+            review_comments,
+        }
+    }
+
+    #[test]
+    fn test_from_json() {
+        let value = serde_json::json!({
+            "head": {"sha": "abc"},
+            "updated_at": "2024-01-01T00:00:00Z",
+            "comments": 3,
+            "review_comments": 5
+        });
+        assert_eq!(
+            PrSnapshot::from_json(&value),
+            Some(snapshot("abc", "2024-01-01T00:00:00Z", 3, 5))
+        );
+        assert_eq!(PrSnapshot::from_json(&serde_json::json!({})), None);
+    }
+
+    #[test]
+    fn test_changes_since() {
+        let base = snapshot("a", "t1", 1, 1);
+        assert!(base.changes_since(&base.clone()).is_empty());
+        assert_eq!(
+            base.changes_since(&snapshot("b", "t2", 1, 1)),
+            vec![PrChange::Files]
+        );
+        assert_eq!(
+            base.changes_since(&snapshot("a", "t2", 2, 3)),
+            vec![PrChange::ReviewComments, PrChange::Discussion]
+        );
+        assert_eq!(
+            base.changes_since(&snapshot("a", "t2", 1, 1)),
+            vec![PrChange::ReviewComments]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+//! 件数がわかっているバックグラウンド処理の進捗
+//!
+//! バッチ diff 読み込み・ハイライトの事前構築・一括 viewed など、
+//! 対象の総数が最初にわかる処理で「N/M 件」と残り時間を表示するために使う。
+
+use std::time::{Duration, Instant};
+
+use crate::config::ProgressStyle;
+
+/// バーの幅（セル数）
+const BAR_WIDTH: usize = 12;
+
+#[derive(Debug, Clone)]
+pub struct Progress {
+    pub label: &'static str,
+    pub done: usize,
+    pub total: usize,
+    started: Instant,
+}
+
+impl Progress {
+    pub fn new(label: &'static str, total: usize) -> Self {
+        Self::started_at(label, total, Instant::now())
+    }
+
+    pub fn started_at(label: &'static str, total: usize, started: Instant) -> Self {
+        Self {
+            label,
+            done: 0,
+            total,
+            started,
+        }
+    }
+
+    /// 完了件数を進める（総数は超えない）
+    pub fn advance(&mut self, count: usize) {
+        self.done = (self.done + count).min(self.total);
+    }
+
+    pub fn percent(&self) -> u8 {
+        if self.total == 0 {
+            return 100;
+        }
+        (self.done * 100 / self.total) as u8
+    }
+
+    /// ここまでの平均速度から見積もった残り時間（1 件も終わっていなければ不明）
+    pub fn eta_at(&self, now: Instant) -> Option<Duration> {
+        if self.done == 0 || self.done >= self.total {
+            return None;
+        }
+        let elapsed = now.saturating_duration_since(self.started);
+        let per_item = elapsed / self.done as u32;
+        Some(per_item * (self.total - self.done) as u32)
+    }
+
+    /// `Loading diffs ████░░░░ 12/40 (30%) ETA 5s` 形式の表示
+    pub fn render(&self, style: ProgressStyle) -> String {
+        self.render_at(style, Instant::now())
+    }
+
+    pub fn render_at(&self, style: ProgressStyle, now: Instant) -> String {
+        let filled = (self.done * BAR_WIDTH)
+            .checked_div(self.total)
+            .unwrap_or(BAR_WIDTH);
+        let bar = match style {
+            ProgressStyle::Bar => {
+                format!("{}{} ", "█".repeat(filled), "░".repeat(BAR_WIDTH - filled))
+            }
+            ProgressStyle::Ascii => format!(
+                "[{}{}] ",
+                "#".repeat(filled),
+                "-".repeat(BAR_WIDTH - filled)
+            ),
+            ProgressStyle::Percent => String::new(),
+        };
+        let eta = self
+            .eta_at(now)
+            .map(|eta| format!(" ETA {}", format_eta(eta)))
+            .unwrap_or_default();
+        format!(
+            "{} {}{}/{} ({}%){}",
+            self.label,
+            bar,
+            self.done,
+            self.total,
+            self.percent(),
+            eta
+        )
+    }
+}
+
+/// `45s` / `2m05s` / `1h03m`
+fn format_eta(eta: Duration) -> String {
+    let secs = eta.as_secs().max(1);
+    match secs {
+        0..=59 => format!("{}s", secs),
+        60..=3599 => format!("{}m{:02}s", secs / 60, secs % 60),
+        _ => format!("{}h{:02}m", secs / 3600, secs % 3600 / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_styles_with_eta() {
+        let start = Instant::now();
+        let mut progress = Progress::started_at("Loading diffs", 40, start);
+        progress.advance(10);
+        let now = start + Duration::from_secs(5);
+
+        assert_eq!(
+            progress.render_at(ProgressStyle::Bar, now),
+            "Loading diffs ███░░░░░░░░░ 10/40 (25%) ETA 15s"
+        );
+        assert_eq!(
+            progress.render_at(ProgressStyle::Ascii, now),
+            "Loading diffs [###---------] 10/40 (25%) ETA 15s"
+        );
+        assert_eq!(
+            progress.render_at(ProgressStyle::Percent, now),
+            "Loading diffs 10/40 (25%) ETA 15s"
+        );
+    }
+
+    #[test]
+    fn test_eta_unknown_until_first_item() {
+        let start = Instant::now();
+        let mut progress = Progress::started_at("Marking viewed", 3, start);
+        assert_eq!(progress.eta_at(start + Duration::from_secs(2)), None);
+
+        progress.advance(5);
+        assert_eq!(progress.done, 3);
+        assert_eq!(progress.percent(), 100);
+        assert_eq!(progress.eta_at(start + Duration::from_secs(2)), None);
+    }
+
+    #[test]
+    fn test_format_eta() {
+        assert_eq!(format_eta(Duration::from_millis(200)), "1s");
+        assert_eq!(format_eta(Duration::from_secs(125)), "2m05s");
+        assert_eq!(format_eta(Duration::from_secs(3780)), "1h03m");
+    }
+}
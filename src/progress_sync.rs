@@ -0,0 +1,251 @@
+//! レビュー進捗のマシン間同期
+//!
+//! [`ReviewProgress`] を JSON のまま外部に置き、別のマシンでも同じ PR のレビューを続けられるようにする。
+//! 置き場所は `[sync] backend` で選ぶ。
+//!
+//! - [`GistSync`]: private gist に進捗ごとのファイルとして置く
+//! - [`GitRefSync`]: リポジトリの `refs/octorus/progress/<名前>` に 1 ファイルのコミットとして置き、リモートに push する
+//!
+//! 同期は取得 → [`ReviewProgress::merge`] → 変化があれば保存の順に行うため、
+//! 両方のマシンで変更していてもファイルごとに新しい方が残る。
+
+use std::process::Stdio;
+use std::sync::Arc;
+
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+use crate::config::{SyncBackendKind, SyncConfig};
+use crate::github::{self, FieldValue};
+use crate::review_progress::ReviewProgress;
+
+/// Git の参照に置くときのファイル名
+const REF_FILE: &str = "progress.json";
+const REF_PREFIX: &str = "refs/octorus/progress";
+
+/// 進捗の置き場所
+#[async_trait]
+pub trait ProgressSyncBackend: Send + Sync {
+    /// `key` に置かれた内容。まだなければ None
+    async fn pull(&self, key: &str) -> Result<Option<String>>;
+
+    /// `key` の内容を置き換える
+    async fn push(&self, key: &str, content: &str) -> Result<()>;
+}
+
+/// 設定に従って同期先を作る。同期しない設定なら None
+pub fn backend_from_config(
+    config: &SyncConfig,
+    working_dir: Option<&str>,
+) -> Option<Arc<dyn ProgressSyncBackend>> {
+    match config.backend {
+        SyncBackendKind::Off => None,
+        SyncBackendKind::Gist if config.gist_id.is_empty() => {
+            tracing::warn!("[sync] backend = \"gist\" needs gist_id; progress sync is disabled");
+            None
+        }
+        SyncBackendKind::Gist => Some(Arc::new(GistSync {
+            gist_id: config.gist_id.clone(),
+        })),
+        SyncBackendKind::Git => Some(Arc::new(GitRefSync {
+            working_dir: working_dir.map(str::to_string),
+            remote: config.remote.clone(),
+        })),
+    }
+}
+
+/// `local` を同期先の進捗とまとめ、まとめた結果が同期先と違えば置き換える。
+/// まとめた進捗を返す（保存先は持たない）
+pub async fn sync_progress(
+    backend: &dyn ProgressSyncBackend,
+    key: &str,
+    local: &ReviewProgress,
+) -> Result<ReviewProgress> {
+    let mut merged = ReviewProgress::default();
+    merged.merge(local);
+    let remote = match backend.pull(key).await? {
+        Some(text) => Some(
+            serde_json::from_str::<ReviewProgress>(&text)
+                .context("Failed to parse synced review progress")?,
+        ),
+        None => None,
+    };
+    match remote {
+        Some(ref remote) => {
+            merged.merge(remote);
+            if merged == *remote {
+                return Ok(merged);
+            }
+        }
+        None if merged.is_empty() => return Ok(merged),
+        None => {}
+    }
+    backend
+        .push(key, &serde_json::to_string_pretty(&merged)?)
+        .await?;
+    Ok(merged)
+}
+
+/// private gist のファイルとして置く
+pub struct GistSync {
+    gist_id: String,
+}
+
+#[async_trait]
+impl ProgressSyncBackend for GistSync {
+    async fn pull(&self, key: &str) -> Result<Option<String>> {
+        let gist = github::gh_api(&format!("gists/{}", self.gist_id)).await?;
+        Ok(gist["files"][key]["content"].as_str().map(str::to_string))
+    }
+
+    async fn push(&self, key: &str, content: &str) -> Result<()> {
+        let field = format!("files[{}][content]", key);
+        github::gh_api_patch(
+            &format!("gists/{}", self.gist_id),
+            &[(field.as_str(), FieldValue::String(content))],
+        )
+        .await?;
+        Ok(())
+    }
+}
+
+/// リポジトリの参照に 1 ファイルのコミットとして置く
+pub struct GitRefSync {
+    working_dir: Option<String>,
+    remote: String,
+}
+
+impl GitRefSync {
+    async fn git(&self, args: &[&str], stdin: Option<&str>) -> Result<String> {
+        let mut command = Command::new("git");
+        command
+            .args(args)
+            .stdin(if stdin.is_some() {
+                Stdio::piped()
+            } else {
+                Stdio::null()
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // 同期用のコミットなので、ユーザーの設定がなくても作れるようにする
+            .env("GIT_AUTHOR_NAME", "octorus")
+            .env("GIT_AUTHOR_EMAIL", "octorus@localhost")
+            .env("GIT_COMMITTER_NAME", "octorus")
+            .env("GIT_COMMITTER_EMAIL", "octorus@localhost");
+        if let Some(ref dir) = self.working_dir {
+            command.current_dir(dir);
+        }
+        let mut child = command.spawn().context("failed to spawn git command")?;
+        if let (Some(input), Some(mut pipe)) = (stdin, child.stdin.take()) {
+            pipe.write_all(input.as_bytes()).await?;
+        }
+        let output = child.wait_with_output().await?;
+        if !output.status.success() {
+            bail!(
+                "git {} failed: {}",
+                args.join(" "),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+#[async_trait]
+impl ProgressSyncBackend for GitRefSync {
+    async fn pull(&self, key: &str) -> Result<Option<String>> {
+        let reference = format!("{}/{}", REF_PREFIX, key);
+        let refspec = format!("+{0}:{0}", reference);
+        if let Err(e) = self
+            .git(&["fetch", "--quiet", &self.remote, &refspec], None)
+            .await
+        {
+            if e.to_string().contains("couldn't find remote ref") {
+                return Ok(None);
+            }
+            return Err(e);
+        }
+        let object = format!("{}:{}", reference, REF_FILE);
+        Ok(Some(self.git(&["cat-file", "-p", &object], None).await?))
+    }
+
+    async fn push(&self, key: &str, content: &str) -> Result<()> {
+        let reference = format!("{}/{}", REF_PREFIX, key);
+        let blob = self
+            .git(&["hash-object", "-w", "--stdin"], Some(content))
+            .await?;
+        let tree = self
+            .git(
+                &["mktree"],
+                Some(&format!("100644 blob {}\t{}\n", blob, REF_FILE)),
+            )
+            .await?;
+        let commit = self
+            .git(
+                &["commit-tree", &tree, "-m", "octorus review progress"],
+                None,
+            )
+            .await?;
+        self.git(&["update-ref", &reference, &commit], None).await?;
+        let refspec = format!("+{0}:{0}", reference);
+        self.git(&["push", "--quiet", &self.remote, &refspec], None)
+            .await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn git(dir: &Path, args: &[&str]) {
+        let status = std::process::Command::new("git")
+            .args(args)
+            .current_dir(dir)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?} failed", args);
+    }
+
+    #[tokio::test]
+    async fn test_git_ref_sync_merges_progress_between_clones() {
+        let root = tempfile::tempdir().unwrap();
+        let remote = root.path().join("remote.git");
+        git(root.path(), &["init", "-q", "--bare", "remote.git"]);
+        let clone = |name: &str| {
+            let dir = root.path().join(name);
+            git(
+                root.path(),
+                &["clone", "-q", remote.to_str().unwrap(), name],
+            );
+            GitRefSync {
+                working_dir: Some(dir.display().to_string()),
+                remote: "origin".to_string(),
+            }
+        };
+        let first = clone("first");
+        let second = clone("second");
+        let key = "owner_repo-7-abcdef.json";
+
+        // まだ何も置かれていない
+        assert!(first.pull(key).await.unwrap().is_none());
+
+        let mut progress = ReviewProgress::load_from(root.path().join("first.json"));
+        progress.toggle_reviewed("src/a.rs").unwrap();
+        sync_progress(&first, key, &progress).await.unwrap();
+
+        // 別のクローンではメモを足して同期すると、両方の変更がまとまる
+        let mut other = ReviewProgress::load_from(root.path().join("second.json"));
+        other.set_note("src/b.rs", "check later").unwrap();
+        let merged = sync_progress(&second, key, &other).await.unwrap();
+        assert!(merged.is_reviewed("src/a.rs"));
+        assert_eq!(merged.note("src/b.rs"), Some("check later"));
+
+        let pulled = sync_progress(&first, key, &progress).await.unwrap();
+        assert_eq!(pulled, merged);
+    }
+}
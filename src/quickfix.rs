@@ -0,0 +1,241 @@
+//! レビューコメントを quickfix 形式 (`file:line:col: message`) で書き出す
+//!
+//! Helix (`:open` + jumplist) や Vim (`:cfile`) から読み込めるよう、
+//! 1 行 1 エントリで出力する。
+
+use std::io::Write;
+
+use anyhow::{Context, Result};
+
+use crate::ai::adapter::{CommentSeverity, ReviewComment as ReviewerComment};
+use crate::ai::session::{read_history, HistoryEntryType};
+use crate::diff;
+use crate::github::comment::ReviewComment;
+use crate::github::{self, ChangedFile};
+
+/// quickfix の 1 エントリ
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuickfixEntry {
+    pub path: String,
+    pub line: u32,
+    pub col: u32,
+    pub message: String,
+}
+
+impl QuickfixEntry {
+    /// `file:line:col: message` 形式の 1 行に整形（改行はスペースに畳む）
+    pub fn format(&self) -> String {
+        let message = self
+            .message
+            .lines()
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        format!("{}:{}:{}: {}", self.path, self.line, self.col, message)
+    }
+}
+
+/// 新ファイル側の行番号と、その行の最初の非空白文字のカラム（1 始まり）を求める
+///
+/// 行が diff に含まれない場合（outdated なコメントなど）は 1 列目を指す。
+fn resolve_position(files: &[ChangedFile], path: &str, line: Option<u32>) -> (u32, u32) {
+    let Some(line) = line.filter(|&l| l > 0) else {
+        return (1, 1);
+    };
+    let col = files
+        .iter()
+        .find(|f| f.filename == path)
+        .and_then(|f| f.patch.as_deref())
+        .and_then(|patch| diff::new_line_content(patch, line))
+        .and_then(|content| content.chars().position(|c| !c.is_whitespace()))
+        .map_or(1, |idx| idx as u32 + 1);
+    (line, col)
+}
+
+fn sort_entries(entries: &mut [QuickfixEntry]) {
+    entries.sort_by(|a, b| {
+        a.path
+            .cmp(&b.path)
+            .then(a.line.cmp(&b.line))
+            .then(a.col.cmp(&b.col))
+    });
+}
+
+/// GitHub のレビューコメントを quickfix エントリに変換
+pub fn from_review_comments(
+    files: &[ChangedFile],
+    comments: &[ReviewComment],
+) -> Vec<QuickfixEntry> {
+    let mut entries: Vec<QuickfixEntry> = comments
+        .iter()
+        .map(|c| {
+            let (line, col) = resolve_position(files, &c.path, c.line);
+            QuickfixEntry {
+                path: c.path.clone(),
+                line,
+                col,
+                message: format!("@{}: {}", c.user.login, c.body),
+            }
+        })
+        .collect();
+    sort_entries(&mut entries);
+    entries
+}
+
+fn severity_label(severity: CommentSeverity) -> &'static str {
+    match severity {
+        CommentSeverity::Critical => "critical",
+        CommentSeverity::Major => "major",
+        CommentSeverity::Minor => "minor",
+        CommentSeverity::Suggestion => "suggestion",
+    }
+}
+
+/// AI Rally のレビュアー指摘を quickfix エントリに変換
+pub fn from_reviewer_comments(
+    files: &[ChangedFile],
+    comments: &[ReviewerComment],
+) -> Vec<QuickfixEntry> {
+    let mut entries: Vec<QuickfixEntry> = comments
+        .iter()
+        .map(|c| {
+            let (line, col) = resolve_position(files, &c.path, Some(c.line));
+            QuickfixEntry {
+                path: c.path.clone(),
+                line,
+                col,
+                message: format!("[{}] {}", severity_label(c.severity), c.body),
+            }
+        })
+        .collect();
+    sort_entries(&mut entries);
+    entries
+}
+
+/// PR のレビューコメント（`rally` 指定時は最新の AI Rally レビュー指摘）を取得して変換
+pub async fn collect_pr_entries(
+    repo: &str,
+    pr_number: u32,
+    rally: bool,
+) -> Result<Vec<QuickfixEntry>> {
+    let files = github::fetch_changed_files(repo, pr_number).await?;
+
+    if rally {
+        let history = read_history(repo, pr_number)?;
+        let Some(review) = history.iter().rev().find_map(|e| match &e.entry_type {
+            HistoryEntryType::Review(output) => Some(output),
+            HistoryEntryType::Fix(_) => None,
+        }) else {
+            anyhow::bail!("No AI Rally review found for PR #{}", pr_number);
+        };
+        return Ok(from_reviewer_comments(&files, &review.comments));
+    }
+
+    let comments = github::comment::fetch_review_comments(repo, pr_number).await?;
+    Ok(from_review_comments(&files, &comments))
+}
+
+/// エントリを書き出す
+pub fn write_entries<W: Write>(writer: &mut W, entries: &[QuickfixEntry]) -> Result<()> {
+    for entry in entries {
+        writeln!(writer, "{}", entry.format()).context("Failed to write quickfix entry")?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::User;
+
+    fn make_file(filename: &str, patch: &str) -> ChangedFile {
+        ChangedFile {
+            filename: filename.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: Some(patch.to_string()),
+            viewed: false,
+        }
+    }
+
+    fn make_comment(path: &str, line: Option<u32>, body: &str) -> ReviewComment {
+        ReviewComment {
+            id: 1,
+            path: path.to_string(),
+            line,
+            start_line: None,
+            body: body.to_string(),
+            user: User {
+                login: "alice".to_string(),
+            },
+            created_at: "2024-01-01T00:00:00Z".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_format_collapses_multiline_message() {
+        let entry = QuickfixEntry {
+            path: "src/main.rs".to_string(),
+            line: 10,
+            col: 5,
+            message: "first line\n\n  second line  \n".to_string(),
+        };
+        assert_eq!(entry.format(), "src/main.rs:10:5: first line second line");
+    }
+
+    #[test]
+    fn test_from_review_comments_resolves_column_and_sorts() {
+        let files = vec![make_file(
+            "src/lib.rs",
+            "@@ -1,2 +1,3 @@\n fn main() {\n+    run();\n }",
+        )];
+        let comments = vec![
+            make_comment("src/lib.rs", Some(2), "Handle the error"),
+            make_comment("src/app.rs", Some(7), "Not in the diff"),
+            make_comment("src/lib.rs", None, "Outdated"),
+        ];
+
+        let lines: Vec<String> = from_review_comments(&files, &comments)
+            .iter()
+            .map(QuickfixEntry::format)
+            .collect();
+        assert_eq!(
+            lines,
+            vec![
+                "src/app.rs:7:1: @alice: Not in the diff",
+                "src/lib.rs:1:1: @alice: Outdated",
+                "src/lib.rs:2:5: @alice: Handle the error",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_from_reviewer_comments_includes_severity() {
+        let files = vec![make_file("a.rs", "@@ -1,1 +1,1 @@\n-x\n+\ty")];
+        let comments = vec![ReviewerComment {
+            path: "a.rs".to_string(),
+            line: 1,
+            body: "Rename this".to_string(),
+            severity: CommentSeverity::Minor,
+            sources: Vec::new(),
+        }];
+
+        let entries = from_reviewer_comments(&files, &comments);
+        assert_eq!(entries[0].format(), "a.rs:1:2: [minor] Rename this");
+    }
+
+    #[test]
+    fn test_write_entries() {
+        let entries = vec![QuickfixEntry {
+            path: "a.rs".to_string(),
+            line: 3,
+            col: 1,
+            message: "msg".to_string(),
+        }];
+        let mut buf = Vec::new();
+        write_entries(&mut buf, &entries).unwrap();
+        assert_eq!(String::from_utf8(buf).unwrap(), "a.rs:3:1: msg\n");
+    }
+}
@@ -0,0 +1,275 @@
+//! PR ごとのローカルなレビュー進捗
+//!
+//! GitHub の viewed とは別に、自分が見終えたファイルとファイルごとのメモを
+//! キャッシュディレクトリの `review_progress/<owner>_<repo>-<PR 番号>-<head SHA>.json` に保存する。
+//! head SHA ごとに分けるため、新しいコミットが push されると進捗は最初からになる。
+//! ファイルごとに最後に変更した時刻を持ち、別のマシンの進捗と [`ReviewProgress::merge`] で
+//! まとめるときは新しい方を残す（同期は [`crate::progress_sync`]）。
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::cache;
+use crate::github::ChangedFile;
+
+const PROGRESS_DIR: &str = "review_progress";
+
+/// 1 つの PR の head に対するレビュー進捗
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ReviewProgress {
+    #[serde(default)]
+    reviewed: BTreeSet<String>,
+    #[serde(default)]
+    notes: BTreeMap<String, String>,
+    /// ファイルごとに印やメモを最後に変更した時刻（UNIX ミリ秒）
+    #[serde(default)]
+    updated_at: BTreeMap<String, i64>,
+    /// 保存先（読み込んだときのパス）
+    #[serde(skip)]
+    path: Option<PathBuf>,
+}
+
+impl ReviewProgress {
+    /// `repo` の PR の `head_sha` に対する進捗を読み込む。保存がなければ空の進捗になる
+    pub fn load(repo: &str, pr_number: u32, head_sha: &str) -> Result<Self> {
+        Ok(Self::load_from(progress_path(
+            &cache::cache_dir(),
+            repo,
+            pr_number,
+            head_sha,
+        )?))
+    }
+
+    pub fn load_from(path: PathBuf) -> Self {
+        let mut progress: Self = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        progress.path = Some(path);
+        progress
+    }
+
+    pub fn is_reviewed(&self, filename: &str) -> bool {
+        self.reviewed.contains(filename)
+    }
+
+    /// 見終えた印を切り替えて保存する。切り替え後に見終えた状態なら true
+    pub fn toggle_reviewed(&mut self, filename: &str) -> Result<bool> {
+        let reviewed = if self.reviewed.remove(filename) {
+            false
+        } else {
+            self.reviewed.insert(filename.to_string());
+            true
+        };
+        self.touch(filename);
+        self.save()?;
+        Ok(reviewed)
+    }
+
+    pub fn note(&self, filename: &str) -> Option<&str> {
+        self.notes.get(filename).map(String::as_str)
+    }
+
+    /// メモを保存する。空白だけのメモは削除する
+    pub fn set_note(&mut self, filename: &str, note: &str) -> Result<()> {
+        let note = note.trim_end();
+        if note.trim().is_empty() {
+            self.notes.remove(filename);
+        } else {
+            self.notes.insert(filename.to_string(), note.to_string());
+        }
+        self.touch(filename);
+        self.save()
+    }
+
+    fn touch(&mut self, filename: &str) {
+        self.updated_at
+            .insert(filename.to_string(), Utc::now().timestamp_millis());
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.reviewed.is_empty() && self.notes.is_empty() && self.updated_at.is_empty()
+    }
+
+    /// 同期に使う名前（保存先のファイル名）
+    pub fn sync_key(&self) -> Option<String> {
+        self.path
+            .as_ref()
+            .and_then(|path| path.file_name())
+            .map(|name| name.to_string_lossy().into_owned())
+    }
+
+    /// ファイルの最後の変更時刻。時刻のない古い進捗でも印かメモがあれば 0
+    fn file_updated_at(&self, filename: &str) -> Option<i64> {
+        self.updated_at.get(filename).copied().or_else(|| {
+            (self.reviewed.contains(filename) || self.notes.contains_key(filename)).then_some(0)
+        })
+    }
+
+    /// `other` の進捗を取り込む。ファイルごとに変更時刻の新しい方の印とメモを残す。
+    /// 変わったファイルがあれば true（保存はしない）
+    pub fn merge(&mut self, other: &ReviewProgress) -> bool {
+        let filenames: BTreeSet<&String> = other
+            .reviewed
+            .iter()
+            .chain(other.notes.keys())
+            .chain(other.updated_at.keys())
+            .collect();
+        let mut changed = false;
+        for filename in filenames {
+            if other.file_updated_at(filename) <= self.file_updated_at(filename) {
+                continue;
+            }
+            if other.reviewed.contains(filename) {
+                self.reviewed.insert(filename.clone());
+            } else {
+                self.reviewed.remove(filename);
+            }
+            match other.notes.get(filename) {
+                Some(note) => self.notes.insert(filename.clone(), note.clone()),
+                None => self.notes.remove(filename),
+            };
+            if let Some(&time) = other.updated_at.get(filename) {
+                self.updated_at.insert(filename.clone(), time);
+            }
+            changed = true;
+        }
+        changed
+    }
+
+    /// レビュー済みのファイル数。ローカルの印か GitHub の viewed のどちらかがあれば数える
+    pub fn reviewed_count(&self, files: &[ChangedFile]) -> usize {
+        files
+            .iter()
+            .filter(|file| file.viewed || self.is_reviewed(&file.filename))
+            .count()
+    }
+
+    pub fn save(&self) -> Result<()> {
+        let Some(ref path) = self.path else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
+
+fn progress_path(root: &Path, repo: &str, pr_number: u32, head_sha: &str) -> Result<PathBuf> {
+    let sha: String = head_sha
+        .chars()
+        .filter(char::is_ascii_alphanumeric)
+        .take(12)
+        .collect();
+    Ok(root.join(PROGRESS_DIR).join(format!(
+        "{}-{}-{}.json",
+        cache::sanitize_repo_name(repo)?,
+        pr_number,
+        sha
+    )))
+}
+
+/// `12/37 files reviewed` と幅 `width` の進捗バー
+pub fn progress_label(reviewed: usize, total: usize, width: usize) -> String {
+    let filled = if total == 0 {
+        0
+    } else {
+        (reviewed * width).div_ceil(total).min(width)
+    };
+    format!(
+        "{}{} {}/{} files reviewed",
+        "█".repeat(filled),
+        "░".repeat(width - filled),
+        reviewed,
+        total
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str, viewed: bool) -> ChangedFile {
+        ChangedFile {
+            filename: name.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+            viewed,
+        }
+    }
+
+    #[test]
+    fn test_progress_is_persisted_per_head() {
+        let root = tempfile::tempdir().unwrap();
+        let path = progress_path(root.path(), "owner/repo", 7, "abcdef1234567890").unwrap();
+        assert!(path.ends_with("review_progress/owner_repo-7-abcdef123456.json"));
+
+        let mut progress = ReviewProgress::load_from(path.clone());
+        assert!(progress.toggle_reviewed("src/a.rs").unwrap());
+        progress
+            .set_note("src/a.rs", "check the error path\n")
+            .unwrap();
+
+        let loaded = ReviewProgress::load_from(path.clone());
+        assert!(loaded.is_reviewed("src/a.rs"));
+        assert_eq!(loaded.note("src/a.rs"), Some("check the error path"));
+
+        // 新しい head では最初から
+        let other = progress_path(root.path(), "owner/repo", 7, "fedcba").unwrap();
+        assert!(!ReviewProgress::load_from(other).is_reviewed("src/a.rs"));
+
+        // 印を外し、空のメモを保存すると消える
+        let mut progress = loaded;
+        assert!(!progress.toggle_reviewed("src/a.rs").unwrap());
+        progress.set_note("src/a.rs", "  \n").unwrap();
+        let loaded = ReviewProgress::load_from(path);
+        assert!(!loaded.is_reviewed("src/a.rs"));
+        assert_eq!(loaded.note("src/a.rs"), None);
+    }
+
+    #[test]
+    fn test_reviewed_count_includes_github_viewed() {
+        let mut progress = ReviewProgress::default();
+        progress.toggle_reviewed("b.rs").unwrap();
+        let files = vec![file("a.rs", true), file("b.rs", false), file("c.rs", false)];
+        assert_eq!(progress.reviewed_count(&files), 2);
+    }
+
+    #[test]
+    fn test_merge_keeps_newer_change_per_file() {
+        let mut local = ReviewProgress::default();
+        local.toggle_reviewed("a.rs").unwrap();
+        local.set_note("b.rs", "local note").unwrap();
+
+        let mut remote = ReviewProgress::default();
+        remote.toggle_reviewed("c.rs").unwrap();
+        remote.set_note("a.rs", "remote note").unwrap();
+        // 別のマシンで a.rs の印を外した後のメモが一番新しい
+        remote.updated_at.insert("a.rs".to_string(), i64::MAX);
+        remote.updated_at.insert("b.rs".to_string(), 1);
+
+        assert!(local.merge(&remote));
+        assert!(!local.is_reviewed("a.rs"));
+        assert_eq!(local.note("a.rs"), Some("remote note"));
+        assert_eq!(local.note("b.rs"), Some("local note"));
+        assert!(local.is_reviewed("c.rs"));
+
+        // 取り込み済みなら何も変わらない
+        assert!(!local.merge(&remote));
+    }
+
+    #[test]
+    fn test_progress_label() {
+        assert_eq!(progress_label(1, 4, 8), "██░░░░░░ 1/4 files reviewed");
+        assert_eq!(progress_label(0, 0, 4), "░░░░ 0/0 files reviewed");
+        assert_eq!(progress_label(37, 37, 4), "████ 37/37 files reviewed");
+    }
+}
@@ -0,0 +1,155 @@
+//! レビュー本文のテンプレート（`[[review_templates]]`）
+//!
+//! PR のラベルや変更されたファイルのパスに一致するテンプレートを選び、
+//! レビューのエディタを開いたときに本文の初期値として入れる。
+//! 複数のテンプレートが一致した場合は、設定に書いた順に空行を挟んでつなげる。
+
+use crate::config::ReviewTemplate;
+
+/// PR に一致したテンプレート
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatchedTemplates<'a> {
+    /// 一致したテンプレートの名前（設定の順）
+    pub names: Vec<&'a str>,
+    /// つなげた本文
+    pub body: String,
+}
+
+impl ReviewTemplate {
+    /// ラベルか変更されたパスのどちらかが一致するか。条件がなければ常に一致する
+    pub fn matches(&self, labels: &[&str], paths: &[&str]) -> bool {
+        if self.labels.is_empty() && self.paths.is_empty() {
+            return true;
+        }
+        let label_match = self.labels.iter().any(|pattern| {
+            let pattern = pattern.to_lowercase();
+            labels
+                .iter()
+                .any(|label| wildcard_matches(&pattern, &label.to_lowercase()))
+        });
+        label_match
+            || self
+                .paths
+                .iter()
+                .any(|pattern| paths.iter().any(|path| path_matches(pattern, path)))
+    }
+}
+
+/// PR に一致するテンプレート。本文が空のテンプレートは使わない
+pub fn matching<'a>(
+    templates: &'a [ReviewTemplate],
+    labels: &[&str],
+    paths: &[&str],
+) -> Option<MatchedTemplates<'a>> {
+    let matched: Vec<&ReviewTemplate> = templates
+        .iter()
+        .filter(|template| !template.body.trim().is_empty() && template.matches(labels, paths))
+        .collect();
+    if matched.is_empty() {
+        return None;
+    }
+    Some(MatchedTemplates {
+        names: matched
+            .iter()
+            .map(|template| template.name.as_str())
+            .filter(|name| !name.is_empty())
+            .collect(),
+        body: matched
+            .iter()
+            .map(|template| template.body.trim_end())
+            .collect::<Vec<_>>()
+            .join("\n\n"),
+    })
+}
+
+/// `*` を含むパターンはワイルドカード（`/` にも一致）、含まなければ
+/// そのファイルかディレクトリ以下に一致する
+fn path_matches(pattern: &str, path: &str) -> bool {
+    let pattern = pattern.trim().trim_start_matches("./");
+    if pattern.contains('*') {
+        return wildcard_matches(pattern, path);
+    }
+    let prefix = pattern.trim_matches('/');
+    !prefix.is_empty()
+        && (path == prefix
+            || path
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.starts_with('/')))
+}
+
+/// `*` を任意の文字列として一致するか
+fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    let mut parts = pattern.split('*');
+    let first = parts.next().unwrap_or("");
+    let Some(mut rest) = text.strip_prefix(first) else {
+        return false;
+    };
+    let parts: Vec<&str> = parts.collect();
+    let Some((last, middle)) = parts.split_last() else {
+        return rest.is_empty();
+    };
+    for part in middle {
+        match rest.find(part) {
+            Some(pos) => rest = &rest[pos + part.len()..],
+            None => return false,
+        }
+    }
+    rest.ends_with(last)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(name: &str, labels: &[&str], paths: &[&str], body: &str) -> ReviewTemplate {
+        ReviewTemplate {
+            name: name.to_string(),
+            labels: labels.iter().map(|s| s.to_string()).collect(),
+            paths: paths.iter().map(|s| s.to_string()).collect(),
+            body: body.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_path_matches() {
+        assert!(path_matches("migrations/", "migrations/001_init.sql"));
+        assert!(path_matches("./migrations", "migrations/001_init.sql"));
+        assert!(!path_matches("migrations", "db/migrations/001.sql"));
+        assert!(!path_matches("migrations", "migrations_old/001.sql"));
+        assert!(path_matches("Cargo.toml", "Cargo.toml"));
+        assert!(path_matches("*.sql", "db/schema.sql"));
+        assert!(path_matches("*/migrations/*", "db/migrations/001.sql"));
+        assert!(!path_matches("*.sql", "db/schema.rs"));
+        assert!(!path_matches("/", "src/lib.rs"));
+    }
+
+    #[test]
+    fn test_matching_by_label_or_path() {
+        let templates = vec![
+            template(
+                "Database",
+                &[],
+                &["migrations/"],
+                "## DB\n- [ ] Reversible\n",
+            ),
+            template("Security", &["security", "auth-*"], &[], "- [ ] No secrets"),
+            template("Empty", &["security"], &[], "  "),
+        ];
+        assert_eq!(matching(&templates, &["bug"], &["src/lib.rs"]), None);
+
+        let matched = matching(&templates, &["Auth-Flow"], &["migrations/001.sql"]).unwrap();
+        assert_eq!(matched.names, vec!["Database", "Security"]);
+        assert_eq!(matched.body, "## DB\n- [ ] Reversible\n\n- [ ] No secrets");
+
+        let matched = matching(&templates, &["SECURITY"], &[]).unwrap();
+        assert_eq!(matched.names, vec!["Security"]);
+    }
+
+    #[test]
+    fn test_template_without_conditions_always_matches() {
+        let templates = vec![template("", &[], &[], "Thanks!")];
+        let matched = matching(&templates, &[], &[]).unwrap();
+        assert!(matched.names.is_empty());
+        assert_eq!(matched.body, "Thanks!");
+    }
+}
@@ -0,0 +1,163 @@
+//! モノレポ向けのパススコープ
+//!
+//! `--paths` または `[scope] paths` で指定したディレクトリ配下のファイルだけを
+//! セッション全体で扱い、レビュー本文にはスコープが対象とするパスを列挙する。
+
+use crate::github::ChangedFile;
+
+/// レビュー対象のパススコープ（空なら全ファイルが対象）
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PathScope {
+    prefixes: Vec<String>,
+}
+
+impl PathScope {
+    pub fn new<S: AsRef<str>>(paths: &[S]) -> Self {
+        let mut prefixes: Vec<String> = paths
+            .iter()
+            .map(|p| normalize(p.as_ref()))
+            .filter(|p| !p.is_empty())
+            .collect();
+        prefixes.sort();
+        prefixes.dedup();
+        Self { prefixes }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.prefixes.is_empty()
+    }
+
+    /// パスがスコープ内か（ディレクトリ境界で前方一致）
+    pub fn contains(&self, path: &str) -> bool {
+        self.is_empty() || self.prefixes.iter().any(|p| matches_prefix(p, path))
+    }
+
+    /// スコープ外の要素を取り除く
+    pub fn retain<T>(&self, items: &mut Vec<T>, path: impl Fn(&T) -> &str) {
+        if !self.is_empty() {
+            items.retain(|item| self.contains(path(item)));
+        }
+    }
+
+    /// 一覧のタイトルなどに表示する短い説明
+    pub fn label(&self) -> String {
+        self.prefixes.join(", ")
+    }
+
+    /// レビュー本文の末尾に、このレビューが対象とするパスとファイル数を追記する
+    pub fn review_body(&self, body: &str, files: &[ChangedFile]) -> String {
+        if self.is_empty() {
+            return body.to_string();
+        }
+        let mut coverage = String::from("This review covers changes under:\n");
+        for prefix in &self.prefixes {
+            let count = files
+                .iter()
+                .filter(|f| matches_prefix(prefix, &f.filename))
+                .count();
+            let unit = if count == 1 { "file" } else { "files" };
+            coverage.push_str(&format!(
+                "- `{}` ({} {})\n",
+                display_prefix(prefix, files),
+                count,
+                unit
+            ));
+        }
+        let coverage = coverage.trim_end();
+
+        let body = body.trim_end();
+        if body.is_empty() {
+            coverage.to_string()
+        } else {
+            format!("{}\n\n---\n{}", body, coverage)
+        }
+    }
+}
+
+fn normalize(path: &str) -> String {
+    let path = path.trim();
+    let path = path.strip_prefix("./").unwrap_or(path);
+    path.trim_matches('/').to_string()
+}
+
+fn matches_prefix(prefix: &str, path: &str) -> bool {
+    path == prefix
+        || path
+            .strip_prefix(prefix)
+            .is_some_and(|rest| rest.starts_with('/'))
+}
+
+/// ディレクトリなら末尾に `/` を付けて表示する
+fn display_prefix(prefix: &str, files: &[ChangedFile]) -> String {
+    if files.iter().any(|f| f.filename == prefix) {
+        prefix.to_string()
+    } else {
+        format!("{}/", prefix)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(name: &str) -> ChangedFile {
+        ChangedFile {
+            filename: name.to_string(),
+            status: "modified".to_string(),
+            additions: 1,
+            deletions: 0,
+            patch: None,
+            viewed: false,
+        }
+    }
+
+    #[test]
+    fn test_contains_matches_directory_boundaries() {
+        let scope = PathScope::new(&["services/api/", "./libs/shared", "README.md"]);
+        assert!(scope.contains("services/api/main.go"));
+        assert!(scope.contains("libs/shared/util/mod.rs"));
+        assert!(scope.contains("README.md"));
+        assert!(!scope.contains("services/api-gateway/main.go"));
+        assert!(!scope.contains("services/web/index.ts"));
+        assert!(!scope.contains("README.md.bak"));
+    }
+
+    #[test]
+    fn test_empty_scope_contains_everything() {
+        let scope = PathScope::new::<&str>(&[]);
+        assert!(scope.is_empty());
+        assert!(scope.contains("anything.rs"));
+        assert!(PathScope::new(&["", "/"]).is_empty());
+    }
+
+    #[test]
+    fn test_retain() {
+        let scope = PathScope::new(&["a"]);
+        let mut files = vec![file("a/x.rs"), file("b/y.rs"), file("a/z.rs")];
+        scope.retain(&mut files, |f| &f.filename);
+        let names: Vec<_> = files.iter().map(|f| f.filename.as_str()).collect();
+        assert_eq!(names, vec!["a/x.rs", "a/z.rs"]);
+    }
+
+    #[test]
+    fn test_review_body_lists_covered_paths() {
+        let scope = PathScope::new(&["services/api", "libs/shared", "Cargo.toml"]);
+        let files = vec![
+            file("services/api/a.rs"),
+            file("services/api/b.rs"),
+            file("Cargo.toml"),
+        ];
+
+        assert_eq!(
+            scope.review_body("LGTM", &files),
+            "LGTM\n\n---\nThis review covers changes under:\n\
+             - `Cargo.toml` (1 file)\n\
+             - `libs/shared/` (0 files)\n\
+             - `services/api/` (2 files)"
+        );
+        assert!(scope
+            .review_body("", &files)
+            .starts_with("This review covers changes under:"));
+        assert_eq!(PathScope::default().review_body("LGTM", &files), "LGTM");
+    }
+}
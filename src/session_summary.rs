@@ -0,0 +1,167 @@
+//! 終了時に表示するセッションのまとめ
+//!
+//! 監査ログのこのセッションのエントリから、コメントの投稿数やレビューの送信結果を数える。
+//! 開いたファイル数と未送信の下書き数は監査ログに残らないため、アプリ側から渡す。
+
+use crate::audit::{AuditAction, AuditEntry};
+
+/// このセッションで行ったことのまとめ
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SessionSummary {
+    /// diff を開いたファイル数
+    pub files_opened: usize,
+    /// viewed にしたファイル数
+    pub files_marked_viewed: usize,
+    /// 投稿したコメント数（suggestion を含む）
+    pub comments: usize,
+    pub replies: usize,
+    /// 送信したレビュー（PR 番号と種類、送信順）
+    pub reviews: Vec<(u32, AuditAction)>,
+    /// 失敗した操作の数
+    pub failures: usize,
+    /// 送信していない下書きコメント数
+    pub unsent_drafts: usize,
+}
+
+/// `3/4 file(s)` の形式の対象から、実際にマークできたファイル数を取り出す
+fn marked_count(target: Option<&str>) -> usize {
+    target
+        .and_then(|t| t.split_once('/'))
+        .and_then(|(count, _)| count.trim().parse().ok())
+        .unwrap_or(0)
+}
+
+impl SessionSummary {
+    pub fn from_entries(entries: &[AuditEntry], files_opened: usize, unsent_drafts: usize) -> Self {
+        let mut summary = Self {
+            files_opened,
+            unsent_drafts,
+            ..Self::default()
+        };
+        for entry in entries {
+            if !entry.ok {
+                summary.failures += 1;
+            }
+            match entry.action {
+                // 一部のファイルだけマークできた場合も数える
+                AuditAction::MarkViewed => {
+                    summary.files_marked_viewed += marked_count(entry.target.as_deref())
+                }
+                _ if !entry.ok => {}
+                AuditAction::Comment | AuditAction::Suggestion => summary.comments += 1,
+                AuditAction::Reply => summary.replies += 1,
+                AuditAction::Approve | AuditAction::RequestChanges | AuditAction::ReviewComment => {
+                    summary.reviews.push((entry.pr_number, entry.action))
+                }
+                _ => {}
+            }
+        }
+        summary
+    }
+
+    /// 何もしていないセッションか
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// 送信していない作業が残っているか
+    pub fn has_unsent_work(&self) -> bool {
+        self.unsent_drafts > 0
+    }
+
+    /// 表示用の行
+    pub fn lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            format!(
+                "Files opened: {}, marked viewed: {}",
+                self.files_opened, self.files_marked_viewed
+            ),
+            format!(
+                "Comments posted: {}, replies: {}",
+                self.comments, self.replies
+            ),
+        ];
+        if self.reviews.is_empty() {
+            lines.push("Review: not submitted".to_string());
+        }
+        for (pr_number, action) in &self.reviews {
+            lines.push(format!("Review: {} on PR #{}", action.label(), pr_number));
+        }
+        if self.unsent_drafts > 0 {
+            lines.push(format!("Unsent drafts: {} comment(s)", self.unsent_drafts));
+        }
+        if self.failures > 0 {
+            lines.push(format!(
+                "Failed actions: {} (see the session activity)",
+                self.failures
+            ));
+        }
+        lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pr_number: u32, action: AuditAction, target: Option<&str>, ok: bool) -> AuditEntry {
+        AuditEntry {
+            timestamp: "2024-01-01T00:00:00+00:00".to_string(),
+            session: "s".to_string(),
+            repo: "owner/repo".to_string(),
+            pr_number,
+            action,
+            target: target.map(str::to_string),
+            ok,
+            error: (!ok).then(|| "boom".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_summary_counts_session_entries() {
+        let entries = vec![
+            entry(1, AuditAction::Comment, Some("src/a.rs:3"), true),
+            entry(1, AuditAction::Suggestion, Some("src/a.rs:5"), true),
+            entry(1, AuditAction::Comment, Some("src/a.rs:9"), false),
+            entry(1, AuditAction::Reply, Some("#42"), true),
+            entry(1, AuditAction::MarkViewed, Some("3/4 file(s)"), false),
+            entry(1, AuditAction::MarkViewed, Some("2/2 file(s)"), true),
+            entry(1, AuditAction::Approve, None, true),
+            entry(2, AuditAction::RequestChanges, None, false),
+        ];
+        let summary = SessionSummary::from_entries(&entries, 4, 0);
+        assert_eq!(summary.files_opened, 4);
+        assert_eq!(summary.files_marked_viewed, 5);
+        assert_eq!(summary.comments, 2);
+        assert_eq!(summary.replies, 1);
+        assert_eq!(summary.reviews, vec![(1, AuditAction::Approve)]);
+        assert_eq!(summary.failures, 3);
+        assert!(!summary.has_unsent_work());
+        assert_eq!(
+            summary.lines(),
+            vec![
+                "Files opened: 4, marked viewed: 5",
+                "Comments posted: 2, replies: 1",
+                "Review: approve on PR #1",
+                "Failed actions: 3 (see the session activity)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_summary_reports_unsent_drafts_without_review() {
+        let summary = SessionSummary::from_entries(&[], 0, 2);
+        assert!(summary.has_unsent_work());
+        assert!(!summary.is_empty());
+        assert_eq!(
+            summary.lines(),
+            vec![
+                "Files opened: 0, marked viewed: 0",
+                "Comments posted: 0, replies: 0",
+                "Review: not submitted",
+                "Unsent drafts: 2 comment(s)",
+            ]
+        );
+        assert!(SessionSummary::from_entries(&[], 0, 0).is_empty());
+    }
+}
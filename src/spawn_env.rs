@@ -0,0 +1,161 @@
+//! サブプロセスに渡す環境変数
+//!
+//! `[env]` に設定した環境変数を AI エージェント（claude / codex / gemini）や翻訳コマンドの
+//! 起動時に追加する。`[env.repos."owner/name"]` はそのリポジトリでだけ使い、
+//! 共通の `[env.vars]` より優先する。
+//!
+//! 秘密情報を含む変数の値は、Rally のログやエラーメッセージに出る前に伏せる。
+
+use std::borrow::Cow;
+
+use crate::config::EnvConfig;
+
+/// 値を伏せたときの表示
+pub const REDACTED: &str = "[redacted]";
+
+/// 名前にこれらを含む変数は、`secrets` に書かなくても秘密として扱う
+const SECRET_NAME_PATTERNS: &[&str] =
+    &["TOKEN", "SECRET", "PASSWORD", "PASSWD", "KEY", "CREDENTIAL"];
+
+/// 短すぎる値はログ中の無関係な文字列まで伏せてしまうため対象外にする
+const MIN_SECRET_LEN: usize = 4;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SpawnEnv {
+    vars: Vec<(String, String)>,
+    /// 伏せる値（長いものから順に置換する）
+    secrets: Vec<String>,
+}
+
+impl SpawnEnv {
+    /// リポジトリに適用する環境変数を解決する
+    pub fn for_repo(config: &EnvConfig, repo: &str) -> Self {
+        let mut vars = config.vars.clone();
+        if let Some(repo_vars) = config.repos.get(repo) {
+            vars.extend(repo_vars.iter().map(|(k, v)| (k.clone(), v.clone())));
+        }
+
+        let mut secrets: Vec<String> = vars
+            .iter()
+            .filter(|(name, value)| {
+                value.len() >= MIN_SECRET_LEN
+                    && (config.secrets.iter().any(|s| s == *name) || is_secret_name(name))
+            })
+            .map(|(_, value)| value.clone())
+            .collect();
+        secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+        secrets.dedup();
+
+        Self {
+            vars: vars.into_iter().collect(),
+            secrets,
+        }
+    }
+
+    /// 変数を追加する。名前から秘密とみなせる値は伏せる対象にも加える
+    pub fn with_var(mut self, name: &str, value: &str) -> Self {
+        self.vars.retain(|(k, _)| k != name);
+        self.vars.push((name.to_string(), value.to_string()));
+        if value.len() >= MIN_SECRET_LEN && is_secret_name(name) {
+            self.secrets.push(value.to_string());
+            self.secrets.sort_by_key(|s| std::cmp::Reverse(s.len()));
+            self.secrets.dedup();
+        }
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.vars.is_empty()
+    }
+
+    /// `Command::envs` に渡す (名前, 値) の組
+    pub fn vars(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.vars.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// 秘密の値を [`REDACTED`] に置き換える
+    pub fn redact<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        let mut result = Cow::Borrowed(text);
+        for secret in &self.secrets {
+            if result.contains(secret.as_str()) {
+                result = Cow::Owned(result.replace(secret.as_str(), REDACTED));
+            }
+        }
+        result
+    }
+}
+
+fn is_secret_name(name: &str) -> bool {
+    let upper = name.to_ascii_uppercase();
+    SECRET_NAME_PATTERNS.iter().any(|p| upper.contains(p))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(toml_str: &str) -> EnvConfig {
+        let config: crate::config::Config = toml::from_str(toml_str).unwrap();
+        config.env
+    }
+
+    #[test]
+    fn test_repo_vars_override_common_vars() {
+        let config = config(
+            r#"
+            [env.vars]
+            RUSTUP_TOOLCHAIN = "stable"
+            CARGO_TERM_COLOR = "never"
+
+            [env.repos."owner/app"]
+            RUSTUP_TOOLCHAIN = "1.82"
+            "#,
+        );
+        let env = SpawnEnv::for_repo(&config, "owner/app");
+        let vars: Vec<_> = env.vars().collect();
+        assert_eq!(
+            vars,
+            vec![("CARGO_TERM_COLOR", "never"), ("RUSTUP_TOOLCHAIN", "1.82")]
+        );
+
+        let other = SpawnEnv::for_repo(&config, "owner/other");
+        assert!(other.vars().any(|v| v == ("RUSTUP_TOOLCHAIN", "stable")));
+        assert!(SpawnEnv::for_repo(&EnvConfig::default(), "owner/app").is_empty());
+    }
+
+    #[test]
+    fn test_redact_secret_values() {
+        let config = config(
+            r#"
+            [env]
+            secrets = ["DATABASE_URL"]
+
+            [env.vars]
+            NPM_TOKEN = "npm_abcdef"
+            DATABASE_URL = "postgres://user:pw@db/app"
+            RUSTUP_TOOLCHAIN = "1.82"
+            API_KEY = "abc"
+            "#,
+        );
+        let env = SpawnEnv::for_repo(&config, "owner/app");
+        assert_eq!(
+            env.redact("token=npm_abcdef url=postgres://user:pw@db/app toolchain 1.82 abc"),
+            "token=[redacted] url=[redacted] toolchain 1.82 abc"
+        );
+        assert!(matches!(env.redact("nothing here"), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_with_var_replaces_and_redacts() {
+        let config = config(
+            r#"
+            [env.vars]
+            GEMINI_API_KEY = "old-key"
+            "#,
+        );
+        let env = SpawnEnv::for_repo(&config, "owner/app").with_var("GEMINI_API_KEY", "new-key");
+        let vars: Vec<_> = env.vars().collect();
+        assert_eq!(vars, vec![("GEMINI_API_KEY", "new-key")]);
+        assert_eq!(env.redact("key=new-key"), "key=[redacted]");
+    }
+}
@@ -0,0 +1,292 @@
+//! suggestion の作業ツリーへの適用
+//!
+//! コメントの ```` ```suggestion ```` ブロックで、コメントが付いた行（複数行なら `start_line..=line`）を
+//! 置き換える。置き換える行の PR の head での内容は patch から求め、作業ツリーの同じ行と
+//! 一致するか確かめる。ローカルの変更で行がずれていても、一致する箇所が 1 つだけならそこに適用する。
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+use crate::diff::new_line_content;
+use crate::github::comment::ReviewComment;
+
+/// コメントの suggestion と、置き換える PR の head の行
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub path: String,
+    pub start_line: u32,
+    pub end_line: u32,
+    /// PR の head での `start_line..=end_line` の内容
+    pub original: Vec<String>,
+    /// 置き換え後の行（空なら行を削除する）
+    pub replacement: Vec<String>,
+}
+
+impl Suggestion {
+    /// コメントの suggestion を、ファイルの patch から置き換える行と対応付ける
+    pub fn from_comment(comment: &ReviewComment, patch: &str) -> Result<Self, String> {
+        let replacement =
+            extract(&comment.body).ok_or_else(|| "No suggestion in this comment".to_string())?;
+        let end_line = comment
+            .line
+            .ok_or_else(|| "The suggested lines are outdated".to_string())?;
+        let start_line = comment.start_line.unwrap_or(end_line).min(end_line);
+        let original = (start_line..=end_line)
+            .map(|line| new_line_content(patch, line).map(String::from))
+            .collect::<Option<Vec<_>>>()
+            .ok_or_else(|| "The suggested lines are not in the diff".to_string())?;
+        Ok(Self {
+            path: comment.path.clone(),
+            start_line,
+            end_line,
+            original,
+            replacement,
+        })
+    }
+
+    /// `path:start-end` 形式の表示
+    pub fn location(&self) -> String {
+        format_location(&self.path, self.start_line, self.end_line)
+    }
+}
+
+/// 適用した結果
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Applied {
+    /// 置き換えた内容。`start_line` は作業ツリーで置き換えた位置
+    Changed { content: String, start_line: u32 },
+    /// 作業ツリーに既に適用されている
+    AlreadyApplied,
+}
+
+/// 本文の最初の ```` ```suggestion ```` ブロックの行。閉じていないブロックは suggestion とみなさない
+pub fn extract(body: &str) -> Option<Vec<String>> {
+    let mut lines = body
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line));
+    lines.find(|line| line.trim() == "```suggestion")?;
+    let mut suggested = Vec::new();
+    for line in lines {
+        if line.trim_start().starts_with("```") {
+            return Some(suggested);
+        }
+        suggested.push(line.to_string());
+    }
+    None
+}
+
+/// 作業ツリーの内容に suggestion を適用する。
+/// 置き換える行が PR の head と一致しなければ（一致する箇所が 1 つに決まらなければ）競合として失敗する
+pub fn apply(content: &str, suggestion: &Suggestion) -> Result<Applied, String> {
+    let lines: Vec<&str> = content.split_inclusive('\n').collect();
+    let expected_start = suggestion.start_line as usize - 1;
+    let start = if block_matches(&lines, expected_start, &suggestion.original) {
+        expected_start
+    } else {
+        let found = find_block(&lines, &suggestion.original);
+        match found.as_slice() {
+            [start] => *start,
+            _ if block_matches(&lines, expected_start, &suggestion.replacement)
+                || find_block(&lines, &suggestion.replacement).len() == 1 =>
+            {
+                return Ok(Applied::AlreadyApplied);
+            }
+            [] => {
+                return Err(format!(
+                    "Conflict: {} differs from the PR head locally",
+                    suggestion.location()
+                ))
+            }
+            _ => {
+                return Err(format!(
+                    "Conflict: the lines of {} appear {} times locally",
+                    suggestion.location(),
+                    found.len()
+                ))
+            }
+        }
+    };
+
+    let end = start + suggestion.original.len();
+    let eol = if content.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let mut result: String = lines[..start].concat();
+    for line in &suggestion.replacement {
+        result.push_str(line);
+        result.push_str(eol);
+    }
+    // 末尾に改行のないファイルの最終行を置き換えたときは、改行なしのまま保つ
+    if end == lines.len() && !content.ends_with('\n') && !suggestion.replacement.is_empty() {
+        result.truncate(result.len() - eol.len());
+    }
+    result.push_str(&lines[end..].concat());
+    Ok(Applied::Changed {
+        content: result,
+        start_line: start as u32 + 1,
+    })
+}
+
+fn strip_eol(line: &str) -> &str {
+    let line = line.strip_suffix('\n').unwrap_or(line);
+    line.strip_suffix('\r').unwrap_or(line)
+}
+
+fn block_matches(lines: &[&str], start: usize, block: &[String]) -> bool {
+    !block.is_empty()
+        && lines.get(start..start + block.len()).is_some_and(|window| {
+            window
+                .iter()
+                .zip(block)
+                .all(|(line, expected)| strip_eol(line) == expected)
+        })
+}
+
+fn find_block(lines: &[&str], block: &[String]) -> Vec<usize> {
+    (0..lines.len())
+        .filter(|&start| block_matches(lines, start, block))
+        .collect()
+}
+
+fn format_location(path: &str, start_line: u32, end_line: u32) -> String {
+    if start_line == end_line {
+        format!("{}:{}", path, start_line)
+    } else {
+        format!("{}:{}-{}", path, start_line, end_line)
+    }
+}
+
+/// 作業ツリーのファイルのパスと内容（UTF-8 のテキストのみ）
+pub async fn read_local(working_dir: Option<&str>, path: &str) -> Result<(PathBuf, String)> {
+    let local_path = crate::compare::repo_root(working_dir).await?.join(path);
+    let bytes = std::fs::read(&local_path)
+        .with_context(|| format!("Failed to read {}", local_path.display()))?;
+    if bytes.contains(&0) {
+        bail!("{} is binary", path);
+    }
+    let content =
+        String::from_utf8(bytes).map_err(|_| anyhow::anyhow!("{} is not UTF-8 text", path))?;
+    Ok((local_path, content))
+}
+
+/// 確認の間にファイルが変わっていなければ書き込む
+pub fn write_local(local_path: &Path, before: &str, after: &str) -> Result<()> {
+    let current = std::fs::read(local_path)
+        .with_context(|| format!("Failed to read {}", local_path.display()))?;
+    if current != before.as_bytes() {
+        bail!("{} changed on disk, try again", local_path.display());
+    }
+    std::fs::write(local_path, after)
+        .with_context(|| format!("Failed to write {}", local_path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::github::User;
+
+    const PATCH: &str = "\
+@@ -1,4 +1,4 @@
+ fn main() {
+-    let x = 1;
++    let x = 2;
+     println!(\"{}\", x);
+ }";
+
+    fn comment(body: &str, start_line: Option<u32>, line: Option<u32>) -> ReviewComment {
+        ReviewComment {
+            id: 1,
+            path: "src/main.rs".to_string(),
+            line,
+            start_line,
+            body: body.to_string(),
+            user: User {
+                login: "alice".to_string(),
+            },
+            created_at: String::new(),
+        }
+    }
+
+    fn suggestion(start_line: Option<u32>, line: u32, suggested: &str) -> Suggestion {
+        let body = format!("nit\r\n```suggestion\r\n{}\r\n```", suggested);
+        Suggestion::from_comment(&comment(&body, start_line, Some(line)), PATCH).unwrap()
+    }
+
+    #[test]
+    fn test_extract_suggestion() {
+        assert_eq!(
+            extract("Use 3\n```suggestion\n    let x = 3;\n```\nthanks"),
+            Some(vec!["    let x = 3;".to_string()])
+        );
+        // 空の suggestion は行の削除
+        assert_eq!(extract("```suggestion\n```"), Some(vec![]));
+        assert_eq!(extract("```rust\nlet x = 3;\n```"), None);
+        assert_eq!(extract("```suggestion\nunclosed"), None);
+    }
+
+    #[test]
+    fn test_from_comment_maps_lines_through_patch() {
+        let s = suggestion(Some(2), 3, "    let y = 2;\n    println!(\"{}\", y);");
+        assert_eq!(s.location(), "src/main.rs:2-3");
+        assert_eq!(s.original, ["    let x = 2;", "    println!(\"{}\", x);"]);
+
+        let outdated = comment("```suggestion\nx\n```", None, None);
+        assert!(Suggestion::from_comment(&outdated, PATCH).is_err());
+        let outside = comment("```suggestion\nx\n```", None, Some(9));
+        assert!(Suggestion::from_comment(&outside, PATCH).is_err());
+        let plain = comment("looks good", None, Some(2));
+        assert!(Suggestion::from_comment(&plain, PATCH).is_err());
+    }
+
+    #[test]
+    fn test_apply_at_same_or_shifted_lines() {
+        let s = suggestion(None, 2, "    let x = 3;");
+        let head = "fn main() {\n    let x = 2;\n    println!(\"{}\", x);\n}\n";
+        assert_eq!(
+            apply(head, &s),
+            Ok(Applied::Changed {
+                content: "fn main() {\n    let x = 3;\n    println!(\"{}\", x);\n}\n".to_string(),
+                start_line: 2,
+            })
+        );
+
+        // ローカルで上に行が増えていても、一致する箇所が 1 つなら適用する
+        let shifted = "// header\r\nfn main() {\r\n    let x = 2;\r\n}";
+        assert_eq!(
+            apply(shifted, &s),
+            Ok(Applied::Changed {
+                content: "// header\r\nfn main() {\r\n    let x = 3;\r\n}".to_string(),
+                start_line: 3,
+            })
+        );
+
+        // 最終行を削除する
+        let last = suggestion(None, 4, "");
+        assert_eq!(
+            apply(
+                "fn main() {\n    let x = 2;\n    println!(\"{}\", x);\n}",
+                &last
+            ),
+            Ok(Applied::Changed {
+                content: "fn main() {\n    let x = 2;\n    println!(\"{}\", x);\n".to_string(),
+                start_line: 4,
+            })
+        );
+    }
+
+    #[test]
+    fn test_apply_detects_conflicts() {
+        let s = suggestion(None, 2, "    let x = 3;");
+        let applied = "fn main() {\n    let x = 3;\n}\n";
+        assert_eq!(apply(applied, &s), Ok(Applied::AlreadyApplied));
+
+        let edited = "fn main() {\n    let x = 5;\n}\n";
+        assert!(apply(edited, &s).unwrap_err().contains("differs"));
+
+        let twice = "    let x = 2;\n\n    let x = 2;\n";
+        assert!(apply(twice, &s).unwrap_err().contains("2 times"));
+    }
+}
@@ -261,6 +261,57 @@ pub fn is_definition_line(content: &str, symbol: &str) -> bool {
     false
 }
 
+/// Extract the symbol name declared on a definition line.
+///
+/// Returns e.g. `foo` for `pub fn foo(`, or `Bar` for `impl<T> Bar<T> {`.
+pub fn definition_name<'a>(content: &'a str, prefixes: &[&str]) -> Option<&'a str> {
+    let trimmed = content.trim_start();
+
+    for prefix in prefixes {
+        let Some(mut rest) = trimmed.strip_prefix(prefix) else {
+            continue;
+        };
+        if *prefix == "impl<" {
+            let pos = rest.find('>')?;
+            rest = rest[pos + 1..].trim_start();
+        }
+        if !rest.starts_with(is_ident_start) {
+            continue;
+        }
+        let end = rest.find(|c: char| !is_ident_char(c)).unwrap_or(rest.len());
+        return Some(&rest[..end]);
+    }
+
+    None
+}
+
+/// Collect symbol definitions from the added and context lines of a patch.
+///
+/// Only files in a supported (tree-sitter) language are indexed.
+/// Returns `(name, diff_line_index)` pairs in patch order.
+pub fn collect_patch_definitions(filename: &str, patch: &str) -> Vec<(String, usize)> {
+    let Some(lang) = Path::new(filename)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(SupportedLanguage::from_extension)
+    else {
+        return Vec::new();
+    };
+    let prefixes = lang.definition_prefixes();
+
+    patch
+        .lines()
+        .enumerate()
+        .filter_map(|(line_idx, line)| {
+            let (line_type, content) = classify_line(line);
+            if !matches!(line_type, LineType::Added | LineType::Context) {
+                return None;
+            }
+            definition_name(content, prefixes).map(|name| (name.to_string(), line_idx))
+        })
+        .collect()
+}
+
 /// Search for a symbol definition within the PR diff patches.
 ///
 /// Returns `(file_index, diff_line_index)` if found.
@@ -1002,4 +1053,31 @@ mod tests {
             assert!(result.is_ok());
         });
     }
+
+    #[test]
+    fn test_definition_name() {
+        let rust = SupportedLanguage::Rust.definition_prefixes();
+        assert_eq!(
+            definition_name("pub fn process(x: i32) {", rust),
+            Some("process")
+        );
+        assert_eq!(definition_name("    struct Config {", rust), Some("Config"));
+        assert_eq!(
+            definition_name("impl<T> Wrapper<T> {", rust),
+            Some("Wrapper")
+        );
+        assert_eq!(definition_name("let fn_ptr = 1;", rust), None);
+        assert_eq!(definition_name("fn (", rust), None);
+    }
+
+    #[test]
+    fn test_collect_patch_definitions() {
+        let patch =
+            "@@ -1,3 +1,4 @@\n fn kept() {}\n-fn removed() {}\n+pub struct Added;\n+let x = 1;";
+        assert_eq!(
+            collect_patch_definitions("src/lib.rs", patch),
+            vec![("kept".to_string(), 1), ("Added".to_string(), 3)]
+        );
+        assert!(collect_patch_definitions("notes.txt", patch).is_empty());
+    }
 }
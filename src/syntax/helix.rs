@@ -0,0 +1,312 @@
+//! Import of Helix `theme.toml` files as syntect themes.
+//!
+//! Helix highlight scopes are mostly TextMate scope names (`keyword.control`, `string`,
+//! `comment.line`, ...), so each key is used as a scope selector as-is. Keys whose TextMate
+//! counterpart is named differently (`function` → `entity.name.function`, `type` →
+//! `storage.type`, ...) also get the TextMate names. The result is an approximation: only
+//! foreground/background colors and the bold/italic/underline modifiers are imported,
+//! `inherits` is ignored, and of the `ui.*` keys only the ones with a syntect counterpart
+//! (background, text, selection, line numbers) are used.
+
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use anyhow::{Context, Result};
+use syntect::highlighting::{
+    Color, FontStyle, ScopeSelectors, StyleModifier, Theme, ThemeItem, ThemeSettings,
+};
+
+/// TextMate scopes added to the selector of a Helix scope.
+const SCOPE_ALIASES: &[(&str, &[&str])] = &[
+    ("keyword", &["storage"]),
+    ("keyword.storage", &["storage.modifier", "storage.type"]),
+    ("keyword.function", &["storage.type.function"]),
+    ("function", &["entity.name.function", "support.function"]),
+    ("function.method", &["entity.name.function.method"]),
+    ("function.macro", &["entity.name.function.macro"]),
+    ("function.builtin", &["support.function.builtin"]),
+    (
+        "type",
+        &["entity.name.type", "storage.type", "support.type"],
+    ),
+    (
+        "type.builtin",
+        &["support.type.builtin", "storage.type.builtin"],
+    ),
+    (
+        "constructor",
+        &["entity.name.function.constructor", "entity.name.class"],
+    ),
+    ("operator", &["keyword.operator"]),
+    (
+        "attribute",
+        &["entity.other.attribute-name", "meta.attribute"],
+    ),
+    (
+        "namespace",
+        &["entity.name.namespace", "entity.name.module"],
+    ),
+    ("tag", &["entity.name.tag"]),
+    ("label", &["entity.name.label"]),
+    ("variable.other.member", &["variable.other.property"]),
+    ("markup.link.url", &["markup.underline.link"]),
+    ("markup.raw.inline", &["markup.inline.raw"]),
+];
+
+/// Keys of a style table (`{ fg = "...", bg = "...", modifiers = [...] }`).
+const STYLE_KEYS: &[&str] = &["fg", "bg", "modifiers", "underline"];
+
+/// Approximate RGB values of the terminal color names Helix themes may use.
+const NAMED_COLORS: &[(&str, (u8, u8, u8))] = &[
+    ("black", (0x00, 0x00, 0x00)),
+    ("red", (0xcd, 0x00, 0x00)),
+    ("green", (0x00, 0xcd, 0x00)),
+    ("yellow", (0xcd, 0xcd, 0x00)),
+    ("blue", (0x00, 0x00, 0xee)),
+    ("magenta", (0xcd, 0x00, 0xcd)),
+    ("cyan", (0x00, 0xcd, 0xcd)),
+    ("gray", (0x7f, 0x7f, 0x7f)),
+    ("light-red", (0xff, 0x00, 0x00)),
+    ("light-green", (0x00, 0xff, 0x00)),
+    ("light-yellow", (0xff, 0xff, 0x00)),
+    ("light-blue", (0x5c, 0x5c, 0xff)),
+    ("light-magenta", (0xff, 0x00, 0xff)),
+    ("light-cyan", (0x00, 0xff, 0xff)),
+    ("light-gray", (0xe5, 0xe5, 0xe5)),
+    ("white", (0xff, 0xff, 0xff)),
+];
+
+/// Convert the contents of a Helix `theme.toml` into a syntect theme named `name`.
+///
+/// Fails only if the file is not valid TOML; colors that cannot be resolved are skipped.
+pub fn theme_from_helix(source: &str, name: &str) -> Result<Theme> {
+    let table: toml::Table = toml::from_str(source).context("invalid Helix theme")?;
+    let palette: HashMap<&str, &str> = table
+        .get("palette")
+        .and_then(toml::Value::as_table)
+        .map(|palette| {
+            palette
+                .iter()
+                .filter_map(|(key, value)| Some((key.as_str(), value.as_str()?)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut styles = Vec::new();
+    for (key, value) in &table {
+        if key != "palette" && key != "inherits" {
+            collect_styles(key, value, &mut styles);
+        }
+    }
+
+    let color = |name: &str| parse_color(palette.get(name).copied().unwrap_or(name));
+    let mut settings = ThemeSettings::default();
+    let mut scopes = Vec::new();
+    for (scope, style) in styles {
+        let style = StyleModifier {
+            foreground: style
+                .get("fg")
+                .and_then(|value| value.as_str())
+                .and_then(color),
+            background: style
+                .get("bg")
+                .and_then(|value| value.as_str())
+                .and_then(color),
+            font_style: font_style(&style),
+        };
+        match scope.as_str() {
+            "ui.background" => settings.background = style.background,
+            "ui.text" => settings.foreground = style.foreground,
+            "ui.selection" => settings.selection = style.background,
+            "ui.linenr" => settings.gutter_foreground = style.foreground,
+            "warning" | "error" | "info" | "hint" => {}
+            _ if scope.starts_with("ui.") || scope.starts_with("diagnostic") => {}
+            _ => {
+                if let Some(selector) = scope_selector(&scope) {
+                    scopes.push(ThemeItem {
+                        scope: selector,
+                        style,
+                    });
+                }
+            }
+        }
+    }
+
+    Ok(Theme {
+        name: Some(name.to_string()),
+        author: None,
+        settings,
+        scopes,
+    })
+}
+
+/// Flatten `key = value` into (scope, style table) pairs.
+///
+/// A plain string is a foreground color. Unquoted dotted keys (`keyword.control = "red"`)
+/// are nested tables in TOML, so tables that are not style tables are walked with the key
+/// as a prefix.
+fn collect_styles(key: &str, value: &toml::Value, styles: &mut Vec<(String, toml::Table)>) {
+    match value {
+        toml::Value::String(color) => {
+            let mut style = toml::Table::new();
+            style.insert("fg".to_string(), toml::Value::String(color.clone()));
+            styles.push((key.to_string(), style));
+        }
+        toml::Value::Table(table) if table.keys().all(|k| STYLE_KEYS.contains(&k.as_str())) => {
+            styles.push((key.to_string(), table.clone()));
+        }
+        toml::Value::Table(table) => {
+            for (child, value) in table {
+                collect_styles(&format!("{key}.{child}"), value, styles);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn font_style(style: &toml::Table) -> Option<FontStyle> {
+    let mut font_style = FontStyle::empty();
+    for modifier in style
+        .get("modifiers")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_str)
+    {
+        font_style |= match modifier {
+            "bold" => FontStyle::BOLD,
+            "italic" => FontStyle::ITALIC,
+            "underlined" => FontStyle::UNDERLINE,
+            _ => FontStyle::empty(),
+        };
+    }
+    if style.contains_key("underline") {
+        font_style |= FontStyle::UNDERLINE;
+    }
+    (!font_style.is_empty()).then_some(font_style)
+}
+
+/// The Helix scope plus its TextMate aliases, e.g. `function, entity.name.function, ...`.
+fn scope_selector(scope: &str) -> Option<ScopeSelectors> {
+    let aliases = SCOPE_ALIASES
+        .iter()
+        .find(|(helix, _)| *helix == scope)
+        .map_or(&[][..], |(_, aliases)| *aliases);
+    let selector = std::iter::once(scope)
+        .chain(aliases.iter().copied())
+        .collect::<Vec<_>>()
+        .join(", ");
+    ScopeSelectors::from_str(&selector).ok()
+}
+
+/// `#rrggbb` or a terminal color name.
+fn parse_color(value: &str) -> Option<Color> {
+    let (r, g, b) = match value.strip_prefix('#') {
+        Some(hex) if hex.len() == 6 => (
+            u8::from_str_radix(&hex[0..2], 16).ok()?,
+            u8::from_str_radix(&hex[2..4], 16).ok()?,
+            u8::from_str_radix(&hex[4..6], 16).ok()?,
+        ),
+        Some(_) => return None,
+        None => NAMED_COLORS
+            .iter()
+            .find(|(name, _)| *name == value)
+            .map(|(_, rgb)| *rgb)?,
+    };
+    Some(Color { r, g, b, a: 0xff })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::syntax::ThemeStyleCache;
+    use ratatui::style::{Color as TuiColor, Modifier};
+    use syntect::parsing::ScopeStack;
+
+    const HELIX_THEME: &str = r##"
+inherits = "onedark"
+"keyword" = { fg = "purple", modifiers = ["bold"] }
+"comment" = { fg = "gray", modifiers = ["italic"] }
+"string" = "green"
+"function" = "#61afef"
+type.builtin = "yellow"
+"ui.background" = { bg = "bg" }
+"ui.text" = "fg"
+"ui.cursor" = { bg = "purple" }
+
+[palette]
+purple = "#c678dd"
+green = "#98c379"
+bg = "#282c34"
+fg = "#abb2bf"
+"##;
+
+    fn rgb(r: u8, g: u8, b: u8) -> Option<Color> {
+        Some(Color { r, g, b, a: 0xff })
+    }
+
+    fn style_for(theme: &Theme, scope: &str) -> Option<StyleModifier> {
+        let stack = ScopeStack::from_str(scope).unwrap();
+        theme
+            .scopes
+            .iter()
+            .filter_map(|item| Some((item.scope.does_match(stack.as_slice())?, item.style)))
+            .max_by(|a, b| a.0.partial_cmp(&b.0).unwrap())
+            .map(|(_, style)| style)
+    }
+
+    #[test]
+    fn test_theme_from_helix_resolves_palette_and_modifiers() {
+        let theme = theme_from_helix(HELIX_THEME, "onedark").unwrap();
+        assert_eq!(theme.name.as_deref(), Some("onedark"));
+        assert_eq!(theme.settings.background, rgb(0x28, 0x2c, 0x34));
+        assert_eq!(theme.settings.foreground, rgb(0xab, 0xb2, 0xbf));
+
+        let keyword = style_for(&theme, "keyword.control").unwrap();
+        assert_eq!(keyword.foreground, rgb(0xc6, 0x78, 0xdd));
+        assert_eq!(keyword.font_style, Some(FontStyle::BOLD));
+        // Color names missing from the palette are approximate terminal colors
+        assert_eq!(
+            style_for(&theme, "support.type.builtin")
+                .unwrap()
+                .foreground,
+            rgb(0xcd, 0xcd, 0x00)
+        );
+        assert_eq!(
+            style_for(&theme, "comment.line").unwrap().font_style,
+            Some(FontStyle::ITALIC)
+        );
+        // UI scopes do not become syntax scopes
+        assert!(style_for(&theme, "ui.cursor").is_none());
+    }
+
+    #[test]
+    fn test_helix_scopes_map_to_textmate_scopes() {
+        let theme = theme_from_helix(HELIX_THEME, "onedark").unwrap();
+        assert_eq!(
+            style_for(&theme, "entity.name.function")
+                .unwrap()
+                .foreground,
+            rgb(0x61, 0xaf, 0xef)
+        );
+        assert_eq!(
+            style_for(&theme, "storage.modifier").unwrap().foreground,
+            rgb(0xc6, 0x78, 0xdd)
+        );
+
+        // Also used for tree-sitter highlighting
+        let cache = ThemeStyleCache::new(&theme);
+        assert_eq!(
+            cache.get("function").fg,
+            Some(TuiColor::Rgb(0x61, 0xaf, 0xef))
+        );
+        assert!(cache.get("keyword").add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_theme_from_helix_rejects_invalid_toml() {
+        assert!(theme_from_helix("keyword = ", "broken").is_err());
+        assert_eq!(parse_color("#12345"), None);
+        assert_eq!(parse_color("no-such-color"), None);
+    }
+}
@@ -24,12 +24,14 @@
 //!
 //! ## Theme Loading
 //!
-//! Themes are loaded from two sources:
+//! Themes are loaded from three sources:
 //! 1. **Bundled themes**: two-face extras + Dracula (compiled into binary)
-//! 2. **User themes**: `~/.config/octorus/themes/*.tmTheme` files
+//! 2. **User themes**: `~/.config/octorus/themes/*.tmTheme` and Helix `*.toml` files
+//! 3. **Theme files**: `diff.theme` / `diff.light_theme` set to a file path
 //!
 //! User themes override bundled themes if they have the same name.
 
+pub mod helix;
 pub mod highlighter;
 pub mod injection;
 pub mod parser_pool;
@@ -43,13 +45,15 @@ pub use parser_pool::ParserPool;
 pub use themes::ThemeStyleCache;
 
 use std::io::Cursor;
+use std::path::Path;
 use std::sync::OnceLock;
 
+use anyhow::Context;
 use lasso::Rodeo;
 use ratatui::style::{Color, Modifier, Style};
 use ratatui::text::Span;
 use syntect::easy::HighlightLines;
-use syntect::highlighting::ThemeSet;
+use syntect::highlighting::{Theme, ThemeSet};
 use syntect::parsing::SyntaxSet;
 use xdg::BaseDirectories;
 
@@ -75,6 +79,7 @@ pub fn syntax_set() -> &'static SyntaxSet {
 /// 1. Syntect default themes
 /// 2. Bundled themes (Dracula)
 /// 3. User themes from ~/.config/octorus/themes/
+/// 4. Theme files from the config (only if [`load_theme_files`] ran first)
 pub fn theme_set() -> &'static ThemeSet {
     THEME_SET.get_or_init(load_all_themes)
 }
@@ -98,8 +103,11 @@ fn load_all_themes() -> ThemeSet {
             if let Ok(entries) = std::fs::read_dir(&user_themes_dir) {
                 for entry in entries.flatten() {
                     let path = entry.path();
-                    if path.extension().is_some_and(|e| e == "tmTheme") {
-                        if let Ok(theme) = ThemeSet::get_theme(&path) {
+                    if path
+                        .extension()
+                        .is_some_and(|e| e == "tmTheme" || e == "toml")
+                    {
+                        if let Ok(theme) = load_theme_file(&path) {
                             // Use filename without extension as theme name
                             if let Some(name) = path.file_stem().and_then(|s| s.to_str()) {
                                 themes.themes.insert(name.to_string(), theme);
@@ -114,6 +122,46 @@ fn load_all_themes() -> ThemeSet {
     themes
 }
 
+/// Load the themes configured as file paths (see [`crate::config::theme_file_path`]) into
+/// the global ThemeSet, keyed by the configured value so that [`get_theme`] finds them.
+///
+/// Must be called before the ThemeSet is first used. Returns the configured value and the
+/// error for each theme file that could not be loaded.
+pub fn load_theme_files(themes: &[&str]) -> Vec<(String, String)> {
+    let mut theme_set = load_all_themes();
+    let mut errors = Vec::new();
+    for theme in themes {
+        let Some(path) = crate::config::theme_file_path(theme) else {
+            continue;
+        };
+        match load_theme_file(&path) {
+            Ok(loaded) => {
+                theme_set.themes.insert(theme.to_string(), loaded);
+            }
+            Err(e) => errors.push((theme.to_string(), format!("{:#}", e))),
+        }
+    }
+    if THEME_SET.set(theme_set).is_err() {
+        tracing::debug!("theme set already initialized, theme files not loaded");
+    }
+    errors
+}
+
+/// Load a `.tmTheme` file, or a Helix theme for `.toml` files.
+fn load_theme_file(path: &Path) -> anyhow::Result<Theme> {
+    if path
+        .extension()
+        .is_some_and(|e| e.eq_ignore_ascii_case("toml"))
+    {
+        let source = std::fs::read_to_string(path)
+            .with_context(|| format!("failed to read {}", path.display()))?;
+        let name = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        helix::theme_from_helix(&source, name)
+    } else {
+        ThemeSet::get_theme(path).with_context(|| format!("failed to load {}", path.display()))
+    }
+}
+
 /// List all available theme names.
 pub fn available_themes() -> Vec<&'static str> {
     theme_set().themes.keys().map(|s| s.as_str()).collect()
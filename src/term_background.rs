@@ -0,0 +1,164 @@
+//! 端末の背景色（ライト / ダーク）の判定
+//!
+//! 起動時に OSC 11 で端末に背景色を問い合わせ、応答がなければ環境変数 `COLORFGBG` から
+//! 推測する。結果に合わせて UI と diff のシンタックスハイライトのテーマを切り替える。
+
+use std::time::Duration;
+
+/// 問い合わせの応答を待つ時間
+const QUERY_TIMEOUT: Duration = Duration::from_millis(200);
+
+/// 端末の背景
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Background {
+    Dark,
+    Light,
+}
+
+impl Background {
+    /// RGB（各 0.0〜1.0）の輝度から判定する
+    pub fn from_rgb(r: f64, g: f64, b: f64) -> Self {
+        let luminance = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+        if luminance > 0.5 {
+            Self::Light
+        } else {
+            Self::Dark
+        }
+    }
+}
+
+/// OSC 11 の応答（`ESC ] 11 ; rgb:RRRR/GGGG/BBBB` の後に BEL または ST）を解釈する
+pub fn parse_osc11_response(response: &str) -> Option<Background> {
+    let start = response.find("]11;")?;
+    let rest = &response[start + 4..];
+    let end = rest.find(['\x07', '\x1b']).unwrap_or(rest.len());
+    let spec = &rest[..end];
+    let spec = spec
+        .strip_prefix("rgb:")
+        .or_else(|| spec.strip_prefix("rgba:"))?;
+    let mut channels = spec.split('/').map(parse_channel);
+    let r = channels.next()??;
+    let g = channels.next()??;
+    let b = channels.next()??;
+    Some(Background::from_rgb(r, g, b))
+}
+
+/// 1〜4 桁の 16 進数を 0.0〜1.0 にする
+fn parse_channel(hex: &str) -> Option<f64> {
+    if hex.is_empty() || hex.len() > 4 {
+        return None;
+    }
+    let value = u32::from_str_radix(hex, 16).ok()?;
+    let max = (1u32 << (hex.len() * 4)) - 1;
+    Some(f64::from(value) / f64::from(max))
+}
+
+/// `COLORFGBG`（`前景;背景` または `前景;default;背景`）の背景の色番号から推測する
+pub fn from_colorfgbg(value: &str) -> Option<Background> {
+    let bg: u8 = value.rsplit(';').next()?.trim().parse().ok()?;
+    Some(match bg {
+        7 | 9..=15 => Background::Light,
+        _ => Background::Dark,
+    })
+}
+
+/// 端末の背景を判定する。分からなければ None
+pub fn detect() -> Option<Background> {
+    query_terminal(QUERY_TIMEOUT).or_else(|| {
+        std::env::var("COLORFGBG")
+            .ok()
+            .and_then(|value| from_colorfgbg(&value))
+    })
+}
+
+/// DA1 の応答（`ESC [ ? ... c`）まで受け取ったか
+fn has_da1_response(response: &[u8]) -> bool {
+    response
+        .windows(3)
+        .position(|w| w == b"\x1b[?")
+        .is_some_and(|start| response[start..].contains(&b'c'))
+}
+
+#[cfg(unix)]
+fn query_terminal(timeout: Duration) -> Option<Background> {
+    use std::io::{ErrorKind, IsTerminal, Read, Write};
+    use std::os::unix::fs::OpenOptionsExt;
+    use std::time::Instant;
+
+    if !std::io::stdin().is_terminal() || !std::io::stdout().is_terminal() {
+        return None;
+    }
+    // 応答が来なかったときに後のキー入力を読んでしまわないよう、ノンブロッキングで開く
+    let mut tty = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_NONBLOCK)
+        .open("/dev/tty")
+        .ok()?;
+    crossterm::terminal::enable_raw_mode().ok()?;
+
+    // 背景色の問い合わせの後に DA1 も送る。OSC 11 に対応していない端末も DA1 には
+    // 応答するので、DA1 の応答が届いた時点で待つのをやめられる
+    let mut response = Vec::new();
+    if tty.write_all(b"\x1b]11;?\x1b\\\x1b[c").is_ok() && tty.flush().is_ok() {
+        let deadline = Instant::now() + timeout;
+        let mut buf = [0u8; 256];
+        while Instant::now() < deadline && !has_da1_response(&response) {
+            match tty.read(&mut buf) {
+                Ok(0) => break,
+                Ok(n) => response.extend_from_slice(&buf[..n]),
+                Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                    std::thread::sleep(Duration::from_millis(5));
+                }
+                Err(_) => break,
+            }
+        }
+    }
+    let _ = crossterm::terminal::disable_raw_mode();
+    parse_osc11_response(&String::from_utf8_lossy(&response))
+}
+
+#[cfg(not(unix))]
+fn query_terminal(_timeout: Duration) -> Option<Background> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_osc11_response() {
+        assert_eq!(
+            parse_osc11_response("\x1b]11;rgb:ffff/ffff/ffff\x1b\\"),
+            Some(Background::Light)
+        );
+        assert_eq!(
+            parse_osc11_response("\x1b]11;rgb:1e1e/1e1e/2e2e\x07\x1b[?62;22c"),
+            Some(Background::Dark)
+        );
+        // 2 桁の応答を返す端末もある
+        assert_eq!(
+            parse_osc11_response("\x1b]11;rgb:fd/f6/e3\x07"),
+            Some(Background::Light)
+        );
+        // OSC 11 に対応していない端末は DA1 だけを返す
+        assert_eq!(parse_osc11_response("\x1b[?1;2c"), None);
+        assert_eq!(parse_osc11_response("\x1b]11;rgb:zz/00/00\x07"), None);
+    }
+
+    #[test]
+    fn test_from_colorfgbg() {
+        assert_eq!(from_colorfgbg("15;0"), Some(Background::Dark));
+        assert_eq!(from_colorfgbg("0;15"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("0;default;7"), Some(Background::Light));
+        assert_eq!(from_colorfgbg("default"), None);
+    }
+
+    #[test]
+    fn test_has_da1_response() {
+        assert!(!has_da1_response(b"\x1b]11;rgb:0/0/0\x07"));
+        assert!(!has_da1_response(b"\x1b[?62;2"));
+        assert!(has_da1_response(b"\x1b]11;rgb:0/0/0\x07\x1b[?62;22c"));
+    }
+}
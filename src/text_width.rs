@@ -0,0 +1,154 @@
+//! 端末上の表示幅の計算
+//!
+//! CJK 文字や絵文字は 1 文字で 2 カラムを占めるため、文字数ではなく
+//! `unicode-width` による表示幅で折り返し・切り詰め・パディングを行う。
+//! コメントパネルのスクロール計算と描画が同じ折り返し結果を使うよう、
+//! 折り返しはすべてこのモジュールを通す。
+
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// 1 文字の表示幅（制御文字は 0）
+pub fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+}
+
+/// 文字列の表示幅
+pub fn display_width(s: &str) -> usize {
+    s.width()
+}
+
+/// 先頭 `chars` 文字の表示幅（カーソル位置の計算用）
+pub fn prefix_width(s: &str, chars: usize) -> usize {
+    s.chars().take(chars).map(char_width).sum()
+}
+
+/// 1 行のテキストを表示幅 `max_width` ごとに文字単位で折り返す。
+/// 収まらない全角文字は次の行に送る。空文字列は空行 1 つになる
+pub fn wrap(text: &str, max_width: usize) -> Vec<String> {
+    if max_width == 0 {
+        return vec![text.to_string()];
+    }
+    let mut lines = Vec::new();
+    let mut current = String::new();
+    let mut current_width = 0;
+    for c in text.chars() {
+        let w = char_width(c);
+        if current_width + w > max_width && !current.is_empty() {
+            lines.push(std::mem::take(&mut current));
+            current_width = 0;
+        }
+        current.push(c);
+        current_width += w;
+    }
+    if !current.is_empty() || lines.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
+/// [`wrap`] した場合の行数（文字列を確保せずに数える）
+pub fn wrapped_line_count(text: &str, max_width: usize) -> usize {
+    if max_width == 0 {
+        return 1;
+    }
+    let mut count = 1;
+    let mut current_width = 0;
+    for c in text.chars() {
+        let w = char_width(c);
+        if current_width + w > max_width && current_width > 0 {
+            count += 1;
+            current_width = 0;
+        }
+        current_width += w;
+    }
+    count
+}
+
+/// 表示幅 `max_width` に収まるよう切り詰め、切り詰めた場合は末尾に `...` を付ける
+pub fn truncate(s: &str, max_width: usize) -> String {
+    if display_width(s) <= max_width {
+        return s.to_string();
+    }
+    let budget = max_width.saturating_sub(3);
+    let mut truncated = String::new();
+    let mut width = 0;
+    for c in s.chars() {
+        let w = char_width(c);
+        if width + w > budget {
+            break;
+        }
+        truncated.push(c);
+        width += w;
+    }
+    truncated.push_str("...");
+    truncated
+}
+
+/// 表示幅が `width` になるよう末尾を空白で埋める（`format!("{:<w$}")` は文字数基準のため）
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let padding = width.saturating_sub(display_width(s));
+    format!("{}{}", s, " ".repeat(padding))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display_width_counts_cjk_and_emoji_as_double() {
+        assert_eq!(display_width("abc"), 3);
+        assert_eq!(display_width("日本語"), 6);
+        assert_eq!(display_width("OK👍"), 4);
+        assert_eq!(prefix_width("あいう", 2), 4);
+        assert_eq!(prefix_width("aあb", 3), 4);
+    }
+
+    #[test]
+    fn test_wrap_japanese_by_width() {
+        assert_eq!(
+            wrap("この変更は問題ありません", 10),
+            vec!["この変更は", "問題ありま", "せん"]
+        );
+        // 奇数幅では全角文字を次の行に送る
+        assert_eq!(wrap("あいう", 5), vec!["あい", "う"]);
+        assert_eq!(wrap("ab日本", 3), vec!["ab", "日", "本"]);
+        assert_eq!(wrap("", 10), vec![""]);
+        assert_eq!(wrap("abc", 0), vec!["abc"]);
+    }
+
+    #[test]
+    fn test_wrapped_line_count_matches_wrap() {
+        for (text, width) in [
+            ("この変更は問題ありません", 10),
+            ("あいう", 5),
+            ("ab日本", 3),
+            ("LGTM 👍👍👍", 4),
+            ("plain ascii text", 7),
+            ("", 4),
+        ] {
+            assert_eq!(
+                wrapped_line_count(text, width),
+                wrap(text, width).len(),
+                "{:?} at width {}",
+                text,
+                width
+            );
+        }
+        // 文字数（12）で割ると 2 行だが、表示幅（24）では 3 行
+        assert_eq!(wrapped_line_count("この変更は問題ありません", 10), 3);
+    }
+
+    #[test]
+    fn test_truncate_by_width() {
+        assert_eq!(truncate("short", 10), "short");
+        assert_eq!(truncate("日本語のタイトル", 10), "日本語...");
+        assert_eq!(truncate("abcdefghijkl", 8), "abcde...");
+        assert!(display_width(&truncate("絵文字👍を含むタイトル", 11)) <= 11);
+    }
+
+    #[test]
+    fn test_pad_to_width() {
+        assert_eq!(pad_to_width("日本", 6), "日本  ");
+        assert_eq!(pad_to_width("abc", 2), "abc");
+    }
+}
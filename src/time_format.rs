@@ -0,0 +1,249 @@
+//! PR・コメント・AI Rally ログの日時表示
+//!
+//! GitHub API の RFC 3339 タイムスタンプを、設定に応じて相対表示（"3h ago"）
+//! または指定タイムゾーンでの絶対表示に変換する。
+
+use std::fmt::Write;
+
+use chrono::{DateTime, FixedOffset, Local, TimeZone, Utc};
+
+use crate::config::{TimeConfig, TimeStyle};
+
+/// 表示に使うタイムゾーン
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TimeZoneSetting {
+    Local,
+    Utc,
+    Fixed(FixedOffset),
+}
+
+impl TimeZoneSetting {
+    /// "local" / "utc" / "+09:00" 形式をパース
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_lowercase().as_str() {
+            "" | "local" => Some(Self::Local),
+            "utc" | "z" => Some(Self::Utc),
+            offset => parse_offset(offset).map(Self::Fixed),
+        }
+    }
+}
+
+fn parse_offset(value: &str) -> Option<FixedOffset> {
+    let (sign, rest) = match value.as_bytes().first()? {
+        b'+' => (1, &value[1..]),
+        b'-' => (-1, &value[1..]),
+        _ => return None,
+    };
+    let (hours, minutes) = match rest.split_once(':') {
+        Some((h, m)) => (h.parse::<i32>().ok()?, m.parse::<i32>().ok()?),
+        None => (rest.parse::<i32>().ok()?, 0),
+    };
+    if minutes >= 60 {
+        return None;
+    }
+    FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+}
+
+/// 日時の整形（App が保持し、`t` で相対/絶対を切り替える）
+#[derive(Debug, Clone)]
+pub struct TimeFormatter {
+    pub style: TimeStyle,
+    format: String,
+    timezone: TimeZoneSetting,
+}
+
+impl Default for TimeFormatter {
+    fn default() -> Self {
+        Self::from_config(&TimeConfig::default())
+    }
+}
+
+impl TimeFormatter {
+    pub fn from_config(config: &TimeConfig) -> Self {
+        let timezone = TimeZoneSetting::parse(&config.timezone).unwrap_or_else(|| {
+            tracing::warn!(
+                "Invalid time.timezone {:?}, falling back to local time",
+                config.timezone
+            );
+            TimeZoneSetting::Local
+        });
+        Self {
+            style: config.style,
+            format: config.format.clone(),
+            timezone,
+        }
+    }
+
+    pub fn toggle_style(&mut self) {
+        self.style = match self.style {
+            TimeStyle::Relative => TimeStyle::Absolute,
+            TimeStyle::Absolute => TimeStyle::Relative,
+        };
+    }
+
+    /// RFC 3339 のタイムスタンプを整形（パースできなければそのまま返す）
+    pub fn format(&self, timestamp: &str) -> String {
+        self.format_at(timestamp, Utc::now())
+    }
+
+    pub fn format_at(&self, timestamp: &str, now: DateTime<Utc>) -> String {
+        let Ok(time) = DateTime::parse_from_rfc3339(timestamp) else {
+            return timestamp.to_string();
+        };
+        let time = time.with_timezone(&Utc);
+        match self.style {
+            TimeStyle::Relative => relative(time, now),
+            TimeStyle::Absolute => self.absolute(time),
+        }
+    }
+
+    /// 常に絶対表示で整形する（詳細表示用）
+    pub fn format_absolute(&self, timestamp: &str) -> String {
+        match DateTime::parse_from_rfc3339(timestamp) {
+            Ok(time) => self.absolute(time.with_timezone(&Utc)),
+            Err(_) => timestamp.to_string(),
+        }
+    }
+
+    fn absolute(&self, time: DateTime<Utc>) -> String {
+        match self.timezone {
+            TimeZoneSetting::Local => strftime(time.with_timezone(&Local), &self.format),
+            TimeZoneSetting::Utc => strftime(time, &self.format),
+            TimeZoneSetting::Fixed(offset) => strftime(time.with_timezone(&offset), &self.format),
+        }
+    }
+}
+
+/// 不正な strftime 形式でも panic せず RFC 3339 にフォールバックする
+fn strftime<Tz: TimeZone>(time: DateTime<Tz>, format: &str) -> String
+where
+    Tz::Offset: std::fmt::Display,
+{
+    let mut out = String::new();
+    match write!(out, "{}", time.format(format)) {
+        Ok(()) => out,
+        Err(_) => time.to_rfc3339(),
+    }
+}
+
+/// "3h ago" 形式の相対表示
+pub fn relative(time: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let seconds = (now - time).num_seconds();
+    let (amount, future) = if seconds < 0 {
+        (-seconds, true)
+    } else {
+        (seconds, false)
+    };
+    if amount < 45 {
+        return "just now".to_string();
+    }
+
+    let (value, unit) = match amount {
+        s if s < 3600 => ((s + 30) / 60, "m"),
+        s if s < 86_400 => (s / 3600, "h"),
+        s if s < 7 * 86_400 => (s / 86_400, "d"),
+        s if s < 30 * 86_400 => (s / (7 * 86_400), "w"),
+        s if s < 365 * 86_400 => (s / (30 * 86_400), "mo"),
+        s => (s / (365 * 86_400), "y"),
+    };
+    let value = value.max(1);
+    if future {
+        format!("in {}{}", value, unit)
+    } else {
+        format!("{}{} ago", value, unit)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn formatter(style: TimeStyle, timezone: &str) -> TimeFormatter {
+        TimeFormatter::from_config(&TimeConfig {
+            style,
+            format: "%Y-%m-%d %H:%M".to_string(),
+            timezone: timezone.to_string(),
+        })
+    }
+
+    #[test]
+    fn test_relative_units() {
+        let now = at("2024-06-15T12:00:00Z");
+        assert_eq!(relative(at("2024-06-15T11:59:30Z"), now), "just now");
+        assert_eq!(relative(at("2024-06-15T11:58:00Z"), now), "2m ago");
+        assert_eq!(relative(at("2024-06-15T09:00:00Z"), now), "3h ago");
+        assert_eq!(relative(at("2024-06-13T12:00:00Z"), now), "2d ago");
+        assert_eq!(relative(at("2024-06-01T12:00:00Z"), now), "2w ago");
+        assert_eq!(relative(at("2024-03-01T12:00:00Z"), now), "3mo ago");
+        assert_eq!(relative(at("2022-06-01T12:00:00Z"), now), "2y ago");
+        assert_eq!(relative(at("2024-06-15T12:10:00Z"), now), "in 10m");
+    }
+
+    #[test]
+    fn test_absolute_with_timezones() {
+        let now = at("2024-06-15T12:00:00Z");
+        let ts = "2024-06-15T03:04:05Z";
+        assert_eq!(
+            formatter(TimeStyle::Absolute, "utc").format_at(ts, now),
+            "2024-06-15 03:04"
+        );
+        assert_eq!(
+            formatter(TimeStyle::Absolute, "+09:00").format_at(ts, now),
+            "2024-06-15 12:04"
+        );
+        assert_eq!(
+            formatter(TimeStyle::Absolute, "-05:30").format_at(ts, now),
+            "2024-06-14 21:34"
+        );
+    }
+
+    #[test]
+    fn test_toggle_and_fallbacks() {
+        let now = at("2024-06-15T12:00:00Z");
+        let mut f = formatter(TimeStyle::Relative, "utc");
+        assert_eq!(f.format_at("2024-06-15T11:00:00Z", now), "1h ago");
+        f.toggle_style();
+        assert_eq!(f.format_at("2024-06-15T11:00:00Z", now), "2024-06-15 11:00");
+        assert_eq!(
+            f.format_absolute("2024-06-15T11:00:00+00:00"),
+            "2024-06-15 11:00"
+        );
+
+        // パースできない値はそのまま
+        assert_eq!(f.format_at("", now), "");
+        assert_eq!(f.format_at("yesterday", now), "yesterday");
+
+        // 不正な形式は RFC 3339 で表示
+        let broken = TimeFormatter::from_config(&TimeConfig {
+            style: TimeStyle::Absolute,
+            format: "%Q".to_string(),
+            timezone: "utc".to_string(),
+        });
+        assert_eq!(
+            broken.format_at("2024-06-15T11:00:00Z", now),
+            "2024-06-15T11:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_timezone_parse() {
+        assert_eq!(
+            TimeZoneSetting::parse("Local"),
+            Some(TimeZoneSetting::Local)
+        );
+        assert_eq!(TimeZoneSetting::parse("UTC"), Some(TimeZoneSetting::Utc));
+        assert_eq!(TimeZoneSetting::parse("+0900"), None);
+        assert_eq!(
+            TimeZoneSetting::parse("+9"),
+            Some(TimeZoneSetting::Fixed(
+                FixedOffset::east_opt(9 * 3600).unwrap()
+            ))
+        );
+        assert_eq!(TimeZoneSetting::parse("Asia/Tokyo"), None);
+        assert_eq!(TimeZoneSetting::parse("+09:75"), None);
+    }
+}
@@ -0,0 +1,218 @@
+//! コメント本文の翻訳
+//!
+//! 翻訳処理は [`TranslationBackend`] で抽象化している。
+//!
+//! - [`CommandBackend`]: `[translate] command` のテンプレートを `sh -c` で実行する。
+//!   `{lang}` は翻訳先の言語に置換され、本文は stdin で渡し、stdout を翻訳結果とする
+//! - [`AgentBackend`]: `ai.reviewer` に設定された AI エージェント（claude / codex / gemini）で翻訳する
+//!
+//! どちらも `[env]` の環境変数を追加して起動し、エラーに含まれる秘密の値は伏せる
+
+use anyhow::{anyhow, Result};
+use async_trait::async_trait;
+use tokio::process::Command;
+
+use crate::ai::adapter::SupportedAgent;
+use crate::ai::oneshot::{self, run_with_stdin};
+use crate::config::Config;
+use crate::spawn_env::SpawnEnv;
+
+/// 翻訳バックエンド
+#[async_trait]
+pub trait TranslationBackend: Send + Sync {
+    /// ステータス表示用の名前
+    fn name(&self) -> String;
+
+    /// `text` を `language` に翻訳する
+    async fn translate(&self, text: &str, language: &str) -> Result<String>;
+}
+
+/// 外部コマンドによる翻訳
+pub struct CommandBackend {
+    template: String,
+    env: SpawnEnv,
+}
+
+impl CommandBackend {
+    pub fn new(template: impl Into<String>) -> Self {
+        Self {
+            template: template.into(),
+            env: SpawnEnv::default(),
+        }
+    }
+
+    /// コマンドに追加する環境変数
+    pub fn with_env(mut self, env: SpawnEnv) -> Self {
+        self.env = env;
+        self
+    }
+
+    pub fn render(&self, language: &str) -> String {
+        self.template.replace("{lang}", language)
+    }
+}
+
+#[async_trait]
+impl TranslationBackend for CommandBackend {
+    fn name(&self) -> String {
+        self.template
+            .split_whitespace()
+            .next()
+            .unwrap_or("command")
+            .to_string()
+    }
+
+    async fn translate(&self, text: &str, language: &str) -> Result<String> {
+        let mut cmd = Command::new("sh");
+        cmd.arg("-c").arg(self.render(language));
+        run_with_stdin(cmd, text, &self.env).await
+    }
+}
+
+/// AI エージェントによる翻訳
+pub struct AgentBackend {
+    agent: SupportedAgent,
+    env: SpawnEnv,
+}
+
+impl AgentBackend {
+    pub fn new(agent: SupportedAgent) -> Self {
+        Self {
+            agent,
+            env: SpawnEnv::default(),
+        }
+    }
+
+    /// エージェントに追加する環境変数
+    pub fn with_env(mut self, env: SpawnEnv) -> Self {
+        self.env = env;
+        self
+    }
+}
+
+#[async_trait]
+impl TranslationBackend for AgentBackend {
+    fn name(&self) -> String {
+        self.agent.name().to_string()
+    }
+
+    async fn translate(&self, text: &str, language: &str) -> Result<String> {
+        oneshot::run_prompt(self.agent, &build_prompt(text, language), &self.env).await
+    }
+}
+
+/// 設定からバックエンドを選ぶ（`command` があればそちらを優先）。
+/// `repo` は `[env.repos]` の環境変数を選ぶために使う
+pub fn backend_from_config(config: &Config, repo: &str) -> Result<Box<dyn TranslationBackend>> {
+    let env = SpawnEnv::for_repo(&config.env, repo);
+    if let Some(ref template) = config.translate.command {
+        let backend = CommandBackend::new(template.clone()).with_env(env);
+        return Ok(Box::new(backend));
+    }
+    let agent = SupportedAgent::from_name(&config.ai.reviewer).ok_or_else(|| {
+        anyhow!(
+            "Unsupported agent for translation: {}. Set [translate] command instead",
+            config.ai.reviewer
+        )
+    })?;
+    Ok(Box::new(AgentBackend::new(agent).with_env(env)))
+}
+
+pub fn build_prompt(text: &str, language: &str) -> String {
+    format!(
+        "Translate the following GitHub code review comment into {language}. \
+Keep Markdown, code blocks, inline code, identifiers and URLs unchanged. \
+Output only the translated text without any preamble.\n\n---\n{text}"
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_command_backend_render() {
+        let backend = CommandBackend::new("trans -b :{lang}");
+        assert_eq!(backend.render("ja"), "trans -b :ja");
+        assert_eq!(backend.name(), "trans");
+    }
+
+    #[test]
+    fn test_backend_from_config() {
+        let mut config: Config = toml::from_str("").unwrap();
+        assert_eq!(
+            backend_from_config(&config, "owner/repo").unwrap().name(),
+            "claude"
+        );
+
+        config.ai.reviewer = "codex".to_string();
+        assert_eq!(
+            backend_from_config(&config, "owner/repo").unwrap().name(),
+            "codex"
+        );
+
+        config.ai.reviewer = "unknown".to_string();
+        assert!(backend_from_config(&config, "owner/repo").is_err());
+
+        config.translate.command = Some("deepl --to {lang}".to_string());
+        assert_eq!(
+            backend_from_config(&config, "owner/repo").unwrap().name(),
+            "deepl"
+        );
+    }
+
+    #[test]
+    fn test_build_prompt_contains_text_and_language() {
+        let prompt = build_prompt("typo here", "Japanese");
+        assert!(prompt.contains("into Japanese"));
+        assert!(prompt.ends_with("typo here"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_backend_pipes_body_through_stdin() {
+        let backend = CommandBackend::new("tr a-z A-Z; printf ' [{lang}]'");
+        let translated = backend.translate("looks good\n", "ja").await.unwrap();
+        assert_eq!(translated, "LOOKS GOOD\n [ja]");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_backend_reports_failure() {
+        let backend = CommandBackend::new("echo nope >&2; exit 3");
+        let err = backend.translate("x", "ja").await.unwrap_err();
+        assert!(err.to_string().contains("nope"));
+
+        let backend = CommandBackend::new("cat >/dev/null");
+        assert!(backend.translate("x", "ja").await.is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_command_backend_injects_env_and_redacts_secrets() {
+        let config: Config = toml::from_str(
+            r#"
+            [translate]
+            command = "printf '%s' \"$GLOSSARY\"; echo \"bad token $DEEPL_TOKEN\" >&2; exit $FAIL"
+
+            [env.vars]
+            GLOSSARY = "default"
+            DEEPL_TOKEN = "deepl-secret-123"
+            FAIL = "0"
+
+            [env.repos."owner/app"]
+            GLOSSARY = "app"
+            "#,
+        )
+        .unwrap();
+        let backend = backend_from_config(&config, "owner/app").unwrap();
+        assert_eq!(backend.translate("x", "ja").await.unwrap(), "app");
+
+        let mut config = config;
+        config.env.vars.insert("FAIL".to_string(), "1".to_string());
+        let backend = backend_from_config(&config, "owner/other").unwrap();
+        let err = backend.translate("x", "ja").await.unwrap_err().to_string();
+        assert!(err.contains("bad token [redacted]"), "{}", err);
+        assert!(!err.contains("deepl-secret-123"));
+    }
+}
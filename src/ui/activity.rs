@@ -0,0 +1,76 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem},
+    Frame,
+};
+
+use super::popup;
+use crate::app::SessionActivityState;
+use crate::audit::AuditEntry;
+use crate::time_format::TimeFormatter;
+
+fn build_entry_item(entry: &AuditEntry, time: &TimeFormatter) -> ListItem<'static> {
+    let (mark, color) = if entry.ok {
+        ("✓", Color::Green)
+    } else {
+        ("✗", Color::Red)
+    };
+    let mut spans = vec![
+        Span::styled(
+            format!("{} ", time.format(&entry.timestamp)),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(format!("{} ", mark), Style::default().fg(color)),
+        Span::raw(format!("PR #{} {}", entry.pr_number, entry.action.label())),
+    ];
+    if let Some(ref target) = entry.target {
+        spans.push(Span::styled(
+            format!("  {}", target),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+
+    let mut lines = vec![Line::from(spans)];
+    if let Some(ref error) = entry.error {
+        lines.push(Line::from(Span::styled(
+            format!("    {}", error),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    ListItem::new(lines)
+}
+
+/// セッション操作履歴パネルを描画
+pub fn render_panel(
+    frame: &mut Frame,
+    panel: &SessionActivityState,
+    time: &TimeFormatter,
+    focused: bool,
+) {
+    let panel_area = popup::area(frame, 110, 30);
+
+    let title = format!(
+        "Session activity ({}) - {} - j/k: scroll, Esc: close",
+        panel.entries.len(),
+        crate::audit::log_path().display()
+    );
+    let block = popup::block(title, focused);
+
+    if panel.entries.is_empty() {
+        let empty = List::new(vec![ListItem::new(Span::styled(
+            "No actions recorded in this session yet",
+            Style::default().fg(Color::DarkGray),
+        ))])
+        .block(block);
+        frame.render_widget(empty, panel_area);
+        return;
+    }
+
+    let items: Vec<ListItem> = panel
+        .entries
+        .iter()
+        .map(|entry| build_entry_item(entry, time))
+        .collect();
+    popup::render_list(frame, panel_area, items, panel.scroll, block);
+}
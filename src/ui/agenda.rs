@@ -0,0 +1,44 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::popup;
+use crate::app::{AgendaPanel, App};
+
+/// レビューアジェンダ（投稿・保存する Markdown をそのまま表示する）
+pub fn render_panel(frame: &mut Frame, app: &App, panel: &AgendaPanel, focused: bool) {
+    let panel_area = popup::area(frame, 100, 30);
+
+    let title = if app.is_local_mode() {
+        "Review agenda - w: save, Esc: close"
+    } else {
+        "Review agenda - c: post as comment, w: save, Esc: close"
+    };
+    let block = popup::block(title, focused);
+
+    let lines: Vec<Line> = panel
+        .markdown
+        .lines()
+        .map(|line| {
+            let anonymized = app.anonymizer.text(line).into_owned();
+            if line.starts_with('#') {
+                Line::from(Span::styled(
+                    anonymized,
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                ))
+            } else if line.contains('⚠') {
+                Line::from(Span::styled(anonymized, Style::default().fg(Color::Red)))
+            } else {
+                Line::from(anonymized)
+            }
+        })
+        .collect();
+    let scroll = panel.scroll.min(u16::MAX as usize) as u16;
+    let widget = Paragraph::new(lines).block(block).scroll((scroll, 0));
+    frame.render_widget(widget, panel_area);
+}
@@ -12,10 +12,20 @@ use ratatui::{
 use super::common::build_pr_info;
 use crate::ai::{RallyState, ReviewAction, RevieweeStatus};
 use crate::app::{AiRallyState, App, LogEntry, LogEventType};
+use crate::config::{KeybindingView, KeybindingsConfig};
+use crate::text_width;
+use crate::time_format::TimeFormatter;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     // Build PR info before borrowing ai_rally_state to avoid borrow conflict
     let pr_info = build_pr_info(app);
+    let replay = app
+        .rally_replay
+        .as_ref()
+        .map(|r| (r.position(), r.total(), r.playing));
+    let scope = app.rally_scope.clone();
+    let budget = (app.config.ai.budget_tokens, app.config.ai.budget_usd);
+    let kb = app.config.keybindings.for_view(KeybindingView::AiRally);
 
     let Some(rally_state) = &mut app.ai_rally_state else {
         return;
@@ -30,17 +40,33 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         ])
         .split(frame.area());
 
-    render_header(frame, chunks[0], rally_state, &pr_info);
-    render_main_content(frame, chunks[1], rally_state);
-    render_status_bar(frame, chunks[2], rally_state);
+    render_header(
+        frame,
+        chunks[0],
+        rally_state,
+        &pr_info,
+        replay,
+        &scope,
+        budget,
+    );
+    render_main_content(frame, chunks[1], rally_state, &app.time_format);
+    render_status_bar(frame, chunks[2], rally_state, replay.is_some(), &kb);
 
     // Render modal on top if showing log detail
     if rally_state.showing_log_detail {
-        render_log_detail_modal(frame, rally_state);
+        render_log_detail_modal(frame, rally_state, &app.time_format);
     }
 }
 
-fn render_header(frame: &mut Frame, area: Rect, state: &AiRallyState, pr_info: &str) {
+fn render_header(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AiRallyState,
+    pr_info: &str,
+    replay: Option<(usize, usize, bool)>,
+    scope: &[String],
+    budget: (u64, f64),
+) {
     let state_text = match state.state {
         RallyState::Initializing => "Initializing...",
         RallyState::ReviewerReviewing => "Reviewer reviewing...",
@@ -65,22 +91,41 @@ fn render_header(frame: &mut Frame, area: Rect, state: &AiRallyState, pr_info: &
         RallyState::Error => Color::Red,
     };
 
-    let title = format!(
+    let mut title = format!(
         " AI Rally - Iteration {}/{} ",
         state.iteration, state.max_iterations
     );
+    if let Some((position, total, playing)) = replay {
+        let mode = if position == total {
+            "done"
+        } else if playing {
+            "playing"
+        } else {
+            "paused"
+        };
+        title.push_str(&format!("[Replay {}/{} {}] ", position, total, mode));
+    }
+    // 範囲を絞った Rally の対象
+    match scope {
+        [] => {}
+        [file] => title.push_str(&format!("[{}] ", file)),
+        files => title.push_str(&format!("[{} files] ", files.len())),
+    }
+
+    let mut status_line = vec![
+        Span::styled("Status: ", Style::default().fg(Color::Gray)),
+        Span::styled(
+            state_text,
+            Style::default()
+                .fg(state_color)
+                .add_modifier(Modifier::BOLD),
+        ),
+    ];
+    status_line.extend(usage_spans(state, budget));
 
     let header = Paragraph::new(vec![
         Line::from(Span::styled(pr_info, Style::default().fg(Color::White))),
-        Line::from(vec![
-            Span::styled("Status: ", Style::default().fg(Color::Gray)),
-            Span::styled(
-                state_text,
-                Style::default()
-                    .fg(state_color)
-                    .add_modifier(Modifier::BOLD),
-            ),
-        ]),
+        Line::from(status_line),
     ])
     .block(
         Block::default()
@@ -92,7 +137,12 @@ fn render_header(frame: &mut Frame, area: Rect, state: &AiRallyState, pr_info: &
     frame.render_widget(header, area);
 }
 
-fn render_main_content(frame: &mut Frame, area: Rect, state: &mut AiRallyState) {
+fn render_main_content(
+    frame: &mut Frame,
+    area: Rect,
+    state: &mut AiRallyState,
+    time: &TimeFormatter,
+) {
     // Add waiting prompt area when in clarification/permission/post-confirmation state
     let is_waiting = matches!(
         state.state,
@@ -124,9 +174,9 @@ fn render_main_content(frame: &mut Frame, area: Rect, state: &mut AiRallyState)
 
     if is_waiting {
         render_waiting_prompt(frame, chunks[1], state);
-        render_logs(frame, chunks[2], state);
+        render_logs(frame, chunks[2], state, time);
     } else {
-        render_logs(frame, chunks[1], state);
+        render_logs(frame, chunks[1], state, time);
     }
 }
 
@@ -157,7 +207,7 @@ fn render_waiting_prompt(frame: &mut Frame, area: Rect, state: &AiRallyState) {
         }
         RallyState::WaitingForPostConfirmation => {
             if let Some(ref info) = state.pending_review_post {
-                let summary = truncate_string(&info.summary, 120);
+                let summary = text_width::truncate(&info.summary, 120);
                 (
                     " Review Post Confirmation ",
                     format!(
@@ -167,7 +217,7 @@ fn render_waiting_prompt(frame: &mut Frame, area: Rect, state: &AiRallyState) {
                     "Press 'y' to post to PR, 'n' to skip, 'q' to abort",
                 )
             } else if let Some(ref info) = state.pending_fix_post {
-                let summary = truncate_string(&info.summary, 120);
+                let summary = text_width::truncate(&info.summary, 120);
                 let files_display = if info.files_modified.len() <= 5 {
                     info.files_modified.join(", ")
                 } else {
@@ -214,6 +264,47 @@ fn render_waiting_prompt(frame: &mut Frame, area: Rect, state: &AiRallyState) {
     frame.render_widget(prompt, area);
 }
 
+/// Usage so far and the budget: yellow past 80% of the budget, red once it is exceeded
+fn usage_spans(
+    state: &AiRallyState,
+    (budget_tokens, budget_usd): (u64, f64),
+) -> Vec<Span<'static>> {
+    let total = state.usage.total;
+    if total.is_empty() && budget_tokens == 0 && budget_usd <= 0.0 {
+        return Vec::new();
+    }
+    let mut budgets = Vec::new();
+    let mut ratio: f64 = 0.0;
+    if budget_tokens > 0 {
+        budgets.push(format!("{} tokens", budget_tokens));
+        ratio = ratio.max(total.total_tokens() as f64 / budget_tokens as f64);
+    }
+    if budget_usd > 0.0 {
+        budgets.push(format!("${:.2}", budget_usd));
+        ratio = ratio.max(total.cost_usd.unwrap_or(0.0) / budget_usd);
+    }
+    let color = if ratio >= 1.0 {
+        Color::Red
+    } else if ratio >= 0.8 {
+        Color::Yellow
+    } else {
+        Color::White
+    };
+
+    let mut text = total.to_string();
+    let current = state.usage.iteration(state.iteration);
+    if !current.is_empty() && current != total {
+        text.push_str(&format!(", this iteration {}", current));
+    }
+    if !budgets.is_empty() {
+        text.push_str(&format!(" / budget {}", budgets.join(", ")));
+    }
+    vec![
+        Span::styled("  Usage: ", Style::default().fg(Color::Gray)),
+        Span::styled(text, Style::default().fg(color)),
+    ]
+}
+
 fn render_history(frame: &mut Frame, area: Rect, state: &AiRallyState) {
     let visible_height = area.height.saturating_sub(2) as usize;
 
@@ -240,7 +331,7 @@ fn render_history(frame: &mut Frame, area: Rect, state: &AiRallyState) {
                     };
                     (
                         format!("Review: {}", action_text),
-                        truncate_string(&review.summary, 60),
+                        text_width::truncate(&review.summary, 60),
                         color,
                     )
                 }
@@ -260,27 +351,27 @@ fn render_history(frame: &mut Frame, area: Rect, state: &AiRallyState) {
                     };
                     (
                         format!("Fix: {}", status_text),
-                        truncate_string(&fix.summary, 60),
+                        text_width::truncate(&fix.summary, 60),
                         color,
                     )
                 }
                 crate::ai::orchestrator::RallyEvent::ClarificationNeeded(q) => (
                     "Clarification".to_string(),
-                    truncate_string(q, 60),
+                    text_width::truncate(q, 60),
                     Color::Magenta,
                 ),
                 crate::ai::orchestrator::RallyEvent::PermissionNeeded(action, _) => (
                     "Permission".to_string(),
-                    truncate_string(action, 60),
+                    text_width::truncate(action, 60),
                     Color::Magenta,
                 ),
                 crate::ai::orchestrator::RallyEvent::Approved(summary) => (
                     "APPROVED".to_string(),
-                    truncate_string(summary, 60),
+                    text_width::truncate(summary, 60),
                     Color::Green,
                 ),
                 crate::ai::orchestrator::RallyEvent::Error(e) => {
-                    ("ERROR".to_string(), truncate_string(e, 60), Color::Red)
+                    ("ERROR".to_string(), text_width::truncate(e, 60), Color::Red)
                 }
                 _ => return None,
             };
@@ -310,7 +401,7 @@ fn render_history(frame: &mut Frame, area: Rect, state: &AiRallyState) {
     frame.render_widget(list, area);
 }
 
-fn render_logs(frame: &mut Frame, area: Rect, state: &mut AiRallyState) {
+fn render_logs(frame: &mut Frame, area: Rect, state: &mut AiRallyState, time: &TimeFormatter) {
     let visible_height = area.height.saturating_sub(2) as usize; // subtract borders
     state.last_visible_log_height = visible_height;
     let total_logs = state.logs.len();
@@ -331,7 +422,7 @@ fn render_logs(frame: &mut Frame, area: Rect, state: &mut AiRallyState) {
         .take(visible_height)
         .map(|(idx, entry)| {
             let is_selected = state.selected_log_index == Some(idx);
-            format_log_entry(entry, is_selected)
+            format_log_entry(entry, is_selected, time)
         })
         .collect();
 
@@ -370,7 +461,11 @@ fn render_logs(frame: &mut Frame, area: Rect, state: &mut AiRallyState) {
     }
 }
 
-fn format_log_entry(entry: &LogEntry, is_selected: bool) -> ListItem<'static> {
+fn format_log_entry(
+    entry: &LogEntry,
+    is_selected: bool,
+    time: &TimeFormatter,
+) -> ListItem<'static> {
     // Use ASCII characters for better terminal compatibility
     // Some terminals may not render emojis correctly
     let (icon, color) = match entry.event_type {
@@ -399,7 +494,7 @@ fn format_log_entry(entry: &LogEntry, is_selected: bool) -> ListItem<'static> {
     let selector = if is_selected { ">" } else { " " };
 
     // Truncate message for list display (full content available in detail modal)
-    let display_message = truncate_string(&entry.message, 80);
+    let display_message = text_width::truncate(&entry.message, 80);
 
     let mut item = ListItem::new(Line::from(vec![
         Span::styled(
@@ -409,7 +504,7 @@ fn format_log_entry(entry: &LogEntry, is_selected: bool) -> ListItem<'static> {
                 .add_modifier(Modifier::BOLD),
         ),
         Span::styled(
-            format!("[{}] ", entry.timestamp),
+            format!("[{}] ", time.format(&entry.timestamp)),
             Style::default().fg(Color::DarkGray),
         ),
         Span::styled(format!("{} ", icon), Style::default().fg(color)),
@@ -428,7 +523,7 @@ fn format_log_entry(entry: &LogEntry, is_selected: bool) -> ListItem<'static> {
     item
 }
 
-fn render_log_detail_modal(frame: &mut Frame, state: &AiRallyState) {
+fn render_log_detail_modal(frame: &mut Frame, state: &AiRallyState, time: &TimeFormatter) {
     let Some(selected_idx) = state.selected_log_index else {
         return;
     };
@@ -461,7 +556,11 @@ fn render_log_detail_modal(frame: &mut Frame, state: &AiRallyState) {
         LogEventType::Error => ("Error", Color::Red),
     };
 
-    let title = format!(" {} - {} ", type_label, entry.timestamp);
+    let title = format!(
+        " {} - {} ",
+        type_label,
+        time.format_absolute(&entry.timestamp)
+    );
 
     // Build content with word wrap
     let content = Paragraph::new(entry.message.clone())
@@ -478,26 +577,54 @@ fn render_log_detail_modal(frame: &mut Frame, state: &AiRallyState) {
     frame.render_widget(content, modal_area);
 }
 
-fn render_status_bar(frame: &mut Frame, area: Rect, state: &AiRallyState) {
+fn render_status_bar(
+    frame: &mut Frame,
+    area: Rect,
+    state: &AiRallyState,
+    replay: bool,
+    kb: &KeybindingsConfig,
+) {
+    let select = format!(
+        "{}/{}/↑↓: select | {}: detail",
+        kb.move_down.display(),
+        kb.move_up.display(),
+        kb.open_panel.display()
+    );
+    let quit = kb.quit.display();
+    let background = format!("{}: Background", kb.rally_background.display());
+    let approve = kb.rally_approve.display();
+    let deny = kb.rally_deny.display();
     let help_text = if state.showing_log_detail {
-        "Esc/Enter/q: Close detail"
+        format!("Esc/{}/{}: Close detail", kb.open_panel.display(), quit)
+    } else if replay {
+        format!(
+            "{}: Play/Pause | {}/→: Step | {} | {}/{}: Save JSON/Markdown | {}: Close",
+            kb.rally_replay_pause.display(),
+            kb.move_right.display(),
+            select,
+            kb.rally_export.display(),
+            kb.rally_export_markdown.display(),
+            quit
+        )
     } else {
         match state.state {
             RallyState::WaitingForClarification => {
-                "y: Open editor | n: Skip | j/k/↑↓: select | Enter: detail | q: Abort"
+                format!("{approve}: Open editor | {deny}: Skip | {select} | {quit}: Abort")
             }
             RallyState::WaitingForPermission => {
-                "y: Approve | n: Deny | j/k/↑↓: select | Enter: detail | q: Abort"
+                format!("{approve}: Approve | {deny}: Deny | {select} | {quit}: Abort")
             }
             RallyState::WaitingForPostConfirmation => {
-                "y: Post to PR | n: Skip | j/k/↑↓: select | Enter: detail | q: Abort"
+                format!("{approve}: Post to PR | {deny}: Skip | {select} | {quit}: Abort")
             }
-            RallyState::Completed => "j/k/↑↓: select | Enter: detail | b: Background | q: Close",
-            RallyState::Aborted => "j/k/↑↓: select | Enter: detail | b: Background | q: Close",
-            RallyState::Error => {
-                "r: Retry | j/k/↑↓: select | Enter: detail | b: Background | q: Close"
+            RallyState::Completed | RallyState::Aborted => {
+                format!("{select} | {background} | {quit}: Close")
             }
-            _ => "j/k/↑↓: select | Enter: detail | b: Background | q: Abort",
+            RallyState::Error => format!(
+                "{}: Retry | {select} | {background} | {quit}: Close",
+                kb.rally_retry.display()
+            ),
+            _ => format!("{select} | {background} | {quit}: Abort"),
         }
     };
 
@@ -513,13 +640,3 @@ fn render_status_bar(frame: &mut Frame, area: Rect, state: &AiRallyState) {
 
     frame.render_widget(status_bar, area);
 }
-
-fn truncate_string(s: &str, max_chars: usize) -> String {
-    let char_count = s.chars().count();
-    if char_count <= max_chars {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max_chars.saturating_sub(3)).collect();
-        format!("{}...", truncated)
-    }
-}
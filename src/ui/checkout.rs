@@ -0,0 +1,95 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use super::popup;
+use crate::app::{App, CheckoutPhase, CheckoutState};
+
+/// 警告に並べる未コミットのファイル数の上限
+const MAX_DIRTY_FILES: usize = 5;
+
+fn key_hint(key: &str, description: &str) -> Line<'static> {
+    let key_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    Line::from(vec![
+        Span::styled(format!("  {}", key), key_style),
+        Span::raw(format!(": {}", description)),
+    ])
+}
+
+/// PR のブランチのチェックアウトの確認・進み具合・結果を描画
+pub fn render_popup(frame: &mut Frame, app: &App, state: &CheckoutState, focused: bool) {
+    let popup_area = popup::area(frame, 70, 14);
+    let title = format!("Checkout PR #{}", state.target.pr_number);
+    let block = popup::block(title, focused);
+    let branch = state.target.local_branch();
+
+    let mut lines: Vec<Line> = Vec::new();
+    match state.phase {
+        CheckoutPhase::Confirm => {
+            lines.push(Line::from(format!(
+                "  Fetch the PR head and switch to branch {}",
+                branch
+            )));
+            lines.push(Line::from(""));
+            if state.dirty.is_empty() {
+                lines.push(key_hint("y / Enter", "check out"));
+            } else {
+                let warning = Style::default().fg(Color::Red);
+                lines.push(Line::from(Span::styled(
+                    format!("  {} file(s) have uncommitted changes:", state.dirty.len()),
+                    warning,
+                )));
+                for file in state.dirty.iter().take(MAX_DIRTY_FILES) {
+                    lines.push(Line::from(format!("    {}", file)));
+                }
+                if state.dirty.len() > MAX_DIRTY_FILES {
+                    lines.push(Line::from(format!(
+                        "    ... and {} more",
+                        state.dirty.len() - MAX_DIRTY_FILES
+                    )));
+                }
+                lines.push(Line::from(""));
+                lines.push(key_hint("s", "stash them and check out"));
+            }
+            lines.push(key_hint("Esc", "cancel"));
+        }
+        CheckoutPhase::Running => {
+            lines.push(Line::from(format!(
+                "  {} Checking out {}...",
+                app.spinner_char(),
+                branch
+            )));
+            lines.push(Line::from(""));
+            lines.push(key_hint("Esc", "close (the result is shown in the footer)"));
+        }
+        CheckoutPhase::Done(Ok(ref result)) => {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", result.summary()),
+                Style::default().fg(Color::Green),
+            )));
+            lines.push(Line::from(""));
+            if !app.is_local_mode() {
+                lines.push(key_hint("L", "switch to local mode on this branch"));
+            }
+            lines.push(key_hint("Esc", "close"));
+        }
+        CheckoutPhase::Done(Err(ref error)) => {
+            lines.push(Line::from(Span::styled(
+                format!("  {}", error),
+                Style::default().fg(Color::Red),
+            )));
+            lines.push(Line::from(""));
+            lines.push(key_hint("Esc", "close"));
+        }
+    }
+
+    let panel = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(panel, popup_area);
+}
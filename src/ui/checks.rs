@@ -0,0 +1,187 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::common::build_pr_info;
+use crate::app::{App, CheckLogState};
+use crate::ci_log::CiLogLine;
+use crate::github::CheckRun;
+use crate::text_width;
+
+const HELP_TEXT: &str = "j/k: move | Enter: open failed log | q/Esc: back";
+const LOG_HELP_TEXT: &str = "j/k: scroll | Ctrl-d/u: page | G: end | q/Esc: close log";
+
+/// head コミットの CI チェック一覧と失敗ジョブのログ
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // List
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(build_pr_info(app))
+        .block(Block::default().borders(Borders::ALL).title("octorus"));
+    frame.render_widget(header, chunks[0]);
+
+    let Some(ref checks) = app.checks else {
+        return;
+    };
+
+    match (&checks.runs, &checks.error) {
+        (_, Some(error)) => {
+            let message = Paragraph::new(Span::styled(
+                format!("Failed to load checks: {}", error),
+                Style::default().fg(Color::Red),
+            ))
+            .block(Block::default().borders(Borders::ALL).title("Checks"));
+            frame.render_widget(message, chunks[1]);
+        }
+        (None, None) => {
+            let loading = Paragraph::new(Span::styled(
+                format!("{} Loading checks...", app.spinner_char()),
+                Style::default().fg(Color::Yellow),
+            ))
+            .block(Block::default().borders(Borders::ALL).title("Checks"));
+            frame.render_widget(loading, chunks[1]);
+        }
+        (Some(runs), None) if runs.is_empty() => {
+            let message = Paragraph::new(Span::styled(
+                "No checks for the head commit",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .block(Block::default().borders(Borders::ALL).title("Checks"));
+            frame.render_widget(message, chunks[1]);
+        }
+        (Some(runs), None) => {
+            let failed = runs.iter().filter(|r| r.is_failing()).count();
+            let items: Vec<ListItem> = runs.iter().map(build_item).collect();
+            let mut list_state = ListState::default().with_selected(Some(checks.selected));
+            let widget = List::new(items)
+                .block(Block::default().borders(Borders::ALL).title(format!(
+                    "Checks ({}, {} failed)",
+                    runs.len(),
+                    failed
+                )))
+                .highlight_style(Style::default().bg(Color::DarkGray));
+            frame.render_stateful_widget(widget, chunks[1], &mut list_state);
+        }
+    }
+
+    if let Some(ref log) = checks.log {
+        render_log(frame, chunks[1], log, app.spinner_char());
+    }
+
+    let help = if checks.log.is_some() {
+        LOG_HELP_TEXT
+    } else {
+        HELP_TEXT
+    };
+    let footer_line = super::footer::build_footer_line(app, help);
+    let footer = Paragraph::new(footer_line).block(super::footer::build_footer_block(app));
+    frame.render_widget(footer, chunks[2]);
+}
+
+/// チェックの状態の印と色
+fn status_mark(run: &CheckRun) -> (&'static str, Color) {
+    if run.status != "completed" {
+        return ("●", Color::Yellow);
+    }
+    match run.conclusion.as_deref() {
+        Some("success") => ("✓", Color::Green),
+        Some("skipped" | "neutral") => ("-", Color::DarkGray),
+        _ if run.is_failing() => ("✗", Color::Red),
+        _ => ("?", Color::DarkGray),
+    }
+}
+
+fn build_item(run: &CheckRun) -> ListItem<'static> {
+    let (mark, color) = status_mark(run);
+    let state = if run.status == "completed" {
+        run.conclusion.clone().unwrap_or_default()
+    } else {
+        run.status.replace('_', " ")
+    };
+    let source = if run.actions_run_id().is_some() {
+        ""
+    } else {
+        "  (external)"
+    };
+    ListItem::new(Line::from(vec![
+        Span::styled(format!(" {} ", mark), Style::default().fg(color)),
+        Span::raw(text_width::pad_to_width(
+            &text_width::truncate(&run.name, 60),
+            60,
+        )),
+        Span::styled(format!("  {}", state), Style::default().fg(color)),
+        Span::styled(source, Style::default().fg(Color::DarkGray)),
+    ]))
+}
+
+/// 失敗ジョブのログ（一覧の上に重ねて表示する）
+fn render_log(frame: &mut Frame, area: Rect, log_state: &CheckLogState, spinner: &str) {
+    frame.render_widget(Clear, area);
+    let title = format!("Failed log: {}", log_state.name);
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Red))
+        .title(title);
+
+    let (lines, scroll) = match (&log_state.log, &log_state.error) {
+        (_, Some(error)) => (
+            vec![Line::from(Span::styled(
+                format!("Failed to load log: {}", error),
+                Style::default().fg(Color::Red),
+            ))],
+            0,
+        ),
+        (None, None) => (
+            vec![Line::from(Span::styled(
+                format!("{} Loading log...", spinner),
+                Style::default().fg(Color::Yellow),
+            ))],
+            0,
+        ),
+        (Some(log), None) => {
+            let visible = area.height.saturating_sub(2) as usize;
+            let mut lines: Vec<Line> = Vec::with_capacity(log.lines.len() + 1);
+            if log.omitted > 0 {
+                lines.push(Line::from(Span::styled(
+                    format!("... {} earlier lines omitted", log.omitted),
+                    Style::default().fg(Color::DarkGray),
+                )));
+            }
+            lines.extend(log.lines.iter().map(build_log_line));
+            let scroll = log_state
+                .scroll
+                .min(lines.len().saturating_sub(visible.max(1)));
+            (lines, scroll)
+        }
+    };
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .scroll((scroll.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(paragraph, area);
+}
+
+fn build_log_line(line: &CiLogLine) -> Line<'_> {
+    match line {
+        CiLogLine::Step(name) => Line::from(Span::styled(
+            format!("── {} ──", name),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+        CiLogLine::Error(text) => {
+            Line::from(Span::styled(text.as_str(), Style::default().fg(Color::Red)))
+        }
+        CiLogLine::Text(text) => Line::from(text.as_str()),
+    }
+}
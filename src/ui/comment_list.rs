@@ -4,54 +4,17 @@ use ratatui::{
     text::{Line, Span},
     widgets::{
         Block, Borders, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
-        ScrollbarState, Wrap,
+        ScrollbarState,
     },
     Frame,
 };
-use unicode_width::UnicodeWidthChar;
 
 use super::common::render_rally_status_bar;
+use super::markdown;
 use crate::app::{App, CommentTab};
-
-/// Wrap text to fit within the specified width, handling multibyte characters
-fn wrap_text(text: &str, max_width: usize) -> Vec<String> {
-    if max_width == 0 {
-        return vec![text.to_string()];
-    }
-
-    let mut lines = Vec::new();
-    let mut current_line = String::new();
-    let mut current_width = 0;
-
-    for ch in text.chars() {
-        // Skip newlines, treat as space
-        if ch == '\n' {
-            continue;
-        }
-
-        let char_width = ch.width().unwrap_or(1);
-
-        if current_width + char_width > max_width {
-            // Start new line
-            lines.push(current_line);
-            current_line = String::new();
-            current_width = 0;
-        }
-
-        current_line.push(ch);
-        current_width += char_width;
-    }
-
-    if !current_line.is_empty() {
-        lines.push(current_line);
-    }
-
-    if lines.is_empty() {
-        lines.push(String::new());
-    }
-
-    lines
-}
+use crate::config::KeybindingView;
+use crate::github::comment::DiscussionComment;
+use crate::text_width;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     // Handle detail mode separately
@@ -97,19 +60,61 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 
     // Footer
     let footer_chunk_idx = if has_rally { 3 } else { 2 };
-    let footer_text = match app.comment_tab {
-        CommentTab::Review => "j/k/↑↓: move | Enter: jump to file | [/]: switch tab | q: back",
-        CommentTab::Discussion => "j/k/↑↓: move | Enter: view detail | [/]: switch tab | q: back",
+    let apply_prompt = app.suggestion_apply_prompt();
+    let kb = app.config.keybindings.for_view(KeybindingView::CommentList);
+    let footer_text = match (app.comment_tab, apply_prompt) {
+        _ if app.is_pending_discussion_delete() => DELETE_CONFIRM_TEXT.to_string(),
+        (_, Some(prompt)) => prompt,
+        (CommentTab::Review, None) => format!(
+            "{}/{}/↑↓: move | {}: jump to file | {}: mark | {}: reply to marked | {}: apply suggestion | {}: author | {}: bots | {}: unresolved only | {}/{}: switch tab | {}: back",
+            kb.move_down.display(),
+            kb.move_up.display(),
+            kb.open_panel.display(),
+            kb.comments_mark.display(),
+            kb.comments_batch_reply.display(),
+            kb.comments_apply_suggestion.display(),
+            kb.comments_user_info.display(),
+            kb.comments_toggle_bots.display(),
+            kb.comments_unresolved_only.display(),
+            kb.comments_prev_tab.display(),
+            kb.comments_next_tab.display(),
+            kb.quit.display(),
+        ),
+        (CommentTab::Discussion, None) => format!(
+            "{}/{}/↑↓: move | {}: view detail | {}: edit | {}: delete | {}: author | {}: bots | {}/{}: switch tab | {}: back",
+            kb.move_down.display(),
+            kb.move_up.display(),
+            kb.open_panel.display(),
+            kb.comments_edit.display(),
+            kb.comments_delete.display(),
+            kb.comments_user_info.display(),
+            kb.comments_toggle_bots.display(),
+            kb.comments_prev_tab.display(),
+            kb.comments_next_tab.display(),
+            kb.quit.display(),
+        ),
     };
-    let footer = Paragraph::new(footer_text).block(Block::default().borders(Borders::ALL));
+    let footer = Paragraph::new(super::footer::build_footer_line(app, &footer_text))
+        .block(Block::default().borders(Borders::ALL));
     frame.render_widget(footer, chunks[footer_chunk_idx]);
 }
 
+const DELETE_CONFIRM_TEXT: &str = "Delete this comment? y: delete | any other key: cancel";
+
+/// 投稿後に編集されたコメントの印
+fn edited_span(comment: &DiscussionComment) -> Option<Span<'static>> {
+    comment
+        .is_edited()
+        .then(|| Span::styled("  (edited)", Style::default().fg(Color::DarkGray)))
+}
+
 /// Generic comment list renderer.
 ///
 /// Renders a list of comments with a loading/empty state, scrollbar, and stateful selection.
 ///
 /// - `comments`: The list of comments to render (if loaded).
+/// - `order`: Indices of the comments to show, in display order (bot comments last).
+/// - `bot_count` / `bots_expanded`: Bot comments folded into a "Bot activity" row.
 /// - `loading`: Whether comments are currently loading.
 /// - `selected_index`: The index of the selected comment.
 /// - `scroll_offset`: Mutable reference to the scroll offset (updated after render).
@@ -120,6 +125,9 @@ fn render_comment_list_generic<T, F>(
     frame: &mut Frame,
     area: ratatui::layout::Rect,
     comments: Option<&[T]>,
+    order: &[usize],
+    bot_count: usize,
+    bots_expanded: bool,
     loading: bool,
     selected_index: usize,
     scroll_offset: &mut usize,
@@ -158,22 +166,43 @@ fn render_comment_list_generic<T, F>(
     let available_width = area.width.saturating_sub(4) as usize;
     let body_width = available_width.saturating_sub(4);
 
-    let items: Vec<ListItem> = items_data
-        .iter()
-        .enumerate()
-        .map(|(i, item)| {
-            let is_selected = i == selected_index;
-            format_item(item, i, is_selected, body_width)
-        })
-        .collect();
+    // 折りたたんだ bot のコメントを選んでいたら先頭の行を選択表示にする（次のキー入力で追従する）
+    let selected_index = if order.contains(&selected_index) {
+        selected_index
+    } else {
+        order.first().copied().unwrap_or(selected_index)
+    };
+    let human_count = if bots_expanded {
+        order.len() - bot_count
+    } else {
+        order.len()
+    };
+    let mut items: Vec<ListItem> = Vec::with_capacity(order.len() + 1);
+    let mut selected_row = 0;
+    for (row, &i) in order.iter().enumerate() {
+        if row == human_count {
+            items.push(bot_activity_item(bot_count, bots_expanded));
+        }
+        let Some(item) = items_data.get(i) else {
+            continue;
+        };
+        let is_selected = i == selected_index;
+        if is_selected {
+            selected_row = items.len();
+        }
+        items.push(format_item(item, i, is_selected, body_width));
+    }
+    if bot_count > 0 && !bots_expanded {
+        items.push(bot_activity_item(bot_count, bots_expanded));
+    }
 
     // Use ListState for stateful rendering with automatic scroll management
     let mut list_state = ListState::default()
         .with_offset(*scroll_offset)
-        .with_selected(Some(selected_index));
+        .with_selected(Some(selected_row));
 
     let block = Block::default().borders(Borders::ALL);
-    let total_items = items_data.len();
+    let total_items = items.len();
 
     let list = List::new(items).block(block).highlight_style(
         Style::default()
@@ -192,7 +221,7 @@ fn render_comment_list_generic<T, F>(
             .end_symbol(Some("▼"));
 
         let mut scrollbar_state =
-            ScrollbarState::new(total_items.saturating_sub(1)).position(selected_index);
+            ScrollbarState::new(total_items.saturating_sub(1)).position(selected_row);
 
         frame.render_stateful_widget(
             scrollbar,
@@ -205,6 +234,22 @@ fn render_comment_list_generic<T, F>(
     }
 }
 
+/// bot のコメントをまとめた見出し行
+fn bot_activity_item(count: usize, expanded: bool) -> ListItem<'static> {
+    let (marker, hint) = if expanded {
+        ("▾", "b: collapse")
+    } else {
+        ("▸", "b: expand")
+    };
+    ListItem::new(vec![
+        Line::from(Span::styled(
+            format!("{} Bot activity: {} comment(s) ({})", marker, count, hint),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(""),
+    ])
+}
+
 fn render_tab_header(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let review_count = app.review_comments.as_ref().map(|c| c.len()).unwrap_or(0);
     let discussion_count = app
@@ -241,8 +286,22 @@ fn render_tab_header(frame: &mut Frame, app: &App, area: ratatui::layout::Rect)
         Span::raw(" "),
         Span::styled(
             format!(
-                "[Review Comments ({})]{}",
+                "[Review Comments ({}{}{}){}]{}",
                 review_count,
+                match app.unresolved_thread_count() {
+                    Some(count) if count > 0 => format!(", {} unresolved", count),
+                    _ => String::new(),
+                },
+                if app.marked_review_comments.is_empty() {
+                    String::new()
+                } else {
+                    format!(", {} marked", app.marked_review_comments.len())
+                },
+                if app.comment_list_unresolved_only {
+                    " unresolved only"
+                } else {
+                    ""
+                },
                 loading_indicator(app.comments_loading)
             ),
             review_style,
@@ -266,10 +325,18 @@ fn render_tab_header(frame: &mut Frame, app: &App, area: ratatui::layout::Rect)
 fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
     use crate::github::comment::ReviewComment;
 
+    let time = &app.time_format;
+    let marked = &app.marked_review_comments;
+    let errors = &app.batch_reply_errors;
+    let anonymizer = &app.anonymizer;
+    let (order, bot_count) = app.comment_list_order(CommentTab::Review);
     render_comment_list_generic(
         frame,
         area,
         app.review_comments.as_deref(),
+        &order,
+        bot_count,
+        app.comment_list_bots_expanded,
         app.comments_loading,
         app.selected_comment,
         &mut app.comment_list_scroll_offset,
@@ -277,10 +344,17 @@ fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layou
         |comment: &ReviewComment, _i: usize, is_selected: bool, body_width: usize| {
             let prefix = if is_selected { "> " } else { "  " };
             let line_info = comment.line.map(|l| format!(":{}", l)).unwrap_or_default();
+            // 一括返信の対象（マークがあるときだけ列を確保する）
+            let mark = match (marked.is_empty(), marked.contains(&comment.id)) {
+                (true, _) => "",
+                (false, true) => "● ",
+                (false, false) => "  ",
+            };
             let header_line = Line::from(vec![
                 Span::raw(prefix),
+                Span::styled(mark, Style::default().fg(Color::Yellow)),
                 Span::styled(
-                    format!("@{}", comment.user.login),
+                    format!("@{}", anonymizer.user(&comment.user.login)),
                     Style::default().fg(Color::Cyan),
                 ),
                 Span::raw(" on "),
@@ -288,15 +362,30 @@ fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layou
                     format!("{}{}", comment.path, line_info),
                     Style::default().fg(Color::Green),
                 ),
+                Span::raw("  "),
+                Span::styled(
+                    time.format(&comment.created_at),
+                    Style::default().fg(Color::DarkGray),
+                ),
             ]);
 
-            let body_text: String = comment.body.lines().collect::<Vec<_>>().join(" ");
-            let wrapped_lines = wrap_text(&body_text, body_width);
+            let body = anonymizer.text(&comment.body);
+            let body_text: String = body.lines().collect::<Vec<_>>().join(" ");
+            let wrapped_lines = text_width::wrap(&body_text, body_width);
 
             let mut lines = vec![header_line];
             for wrapped_line in wrapped_lines {
                 lines.push(Line::from(vec![Span::raw("    "), Span::raw(wrapped_line)]));
             }
+            if let Some(error) = errors.get(&comment.id) {
+                lines.push(Line::from(vec![
+                    Span::raw("    "),
+                    Span::styled(
+                        text_width::truncate(&format!("✗ Reply failed: {}", error), body_width),
+                        Style::default().fg(Color::Red),
+                    ),
+                ]));
+            }
             lines.push(Line::from(""));
 
             ListItem::new(lines)
@@ -305,12 +394,16 @@ fn render_review_comments(frame: &mut Frame, app: &mut App, area: ratatui::layou
 }
 
 fn render_discussion_comments(frame: &mut Frame, app: &mut App, area: ratatui::layout::Rect) {
-    use crate::github::comment::DiscussionComment;
-
+    let time = &app.time_format;
+    let anonymizer = &app.anonymizer;
+    let (order, bot_count) = app.comment_list_order(CommentTab::Discussion);
     render_comment_list_generic(
         frame,
         area,
         app.discussion_comments.as_deref(),
+        &order,
+        bot_count,
+        app.comment_list_bots_expanded,
         app.discussion_comments_loading,
         app.selected_discussion_comment,
         &mut app.discussion_comment_list_scroll_offset,
@@ -318,31 +411,25 @@ fn render_discussion_comments(frame: &mut Frame, app: &mut App, area: ratatui::l
         |comment: &DiscussionComment, _i: usize, is_selected: bool, body_width: usize| {
             let prefix = if is_selected { "> " } else { "  " };
 
-            // Format created_at to a shorter form (just the date part)
-            let date = comment
-                .created_at
-                .split('T')
-                .next()
-                .unwrap_or(&comment.created_at);
+            let date = time.format(&comment.created_at);
 
-            let header_line = Line::from(vec![
+            let mut header_spans = vec![
                 Span::raw(prefix),
                 Span::styled(
-                    format!("@{}", comment.user.login),
+                    format!("@{}", anonymizer.user(&comment.user.login)),
                     Style::default().fg(Color::Cyan),
                 ),
                 Span::raw("  "),
-                Span::styled(date.to_string(), Style::default().fg(Color::DarkGray)),
-            ]);
+                Span::styled(date, Style::default().fg(Color::DarkGray)),
+            ];
+            header_spans.extend(edited_span(comment));
+            let header_line = Line::from(header_spans);
 
-            // Truncate body for list view
-            let body_text: String = comment.body.lines().collect::<Vec<_>>().join(" ");
-            let truncated = if body_text.len() > body_width * 2 {
-                format!("{}...", &body_text[..body_width * 2])
-            } else {
-                body_text
-            };
-            let wrapped_lines = wrap_text(&truncated, body_width);
+            // Truncate body for list view (at most two wrapped lines)
+            let body = anonymizer.text(&comment.body);
+            let body_text: String = body.lines().collect::<Vec<_>>().join(" ");
+            let truncated = text_width::truncate(&body_text, body_width * 2);
+            let wrapped_lines = text_width::wrap(&truncated, body_width);
 
             let mut lines = vec![header_line];
             for wrapped_line in wrapped_lines {
@@ -385,20 +472,17 @@ fn render_discussion_detail(frame: &mut Frame, app: &App) {
         .split(frame.area());
 
     // Header
-    let date = comment
-        .created_at
-        .split('T')
-        .next()
-        .unwrap_or(&comment.created_at);
-    let header = Paragraph::new(Line::from(vec![
+    let date = app.time_format.format(&comment.created_at);
+    let mut header_spans = vec![
         Span::styled(
-            format!("@{}", comment.user.login),
+            format!("@{}", app.anonymizer.user(&comment.user.login)),
             Style::default().fg(Color::Cyan),
         ),
         Span::raw("  "),
-        Span::styled(date.to_string(), Style::default().fg(Color::DarkGray)),
-    ]))
-    .block(
+        Span::styled(date, Style::default().fg(Color::DarkGray)),
+    ];
+    header_spans.extend(edited_span(comment));
+    let header = Paragraph::new(Line::from(header_spans)).block(
         Block::default()
             .borders(Borders::ALL)
             .title("Comment Detail"),
@@ -407,15 +491,18 @@ fn render_discussion_detail(frame: &mut Frame, app: &App) {
 
     // Content with scroll
     let content_height = chunks[1].height.saturating_sub(2) as usize;
-    let body_lines: Vec<Line> = comment
-        .body
-        .lines()
+    let content_width = chunks[1].width.saturating_sub(2) as usize;
+    let task_count = app.discussion_task_count();
+    let selected_task = (task_count > 0).then_some(app.discussion_task_cursor);
+    let body = app.anonymizer.text(&comment.body);
+    let rendered = markdown::render_body(&body, content_width, selected_task);
+    let total_lines = rendered.len();
+    let body_lines: Vec<Line> = rendered
+        .into_iter()
         .skip(app.discussion_comment_detail_scroll)
         .take(content_height)
-        .map(|line| Line::from(line.to_string()))
         .collect();
 
-    let total_lines = comment.body.lines().count();
     let scroll_info = if total_lines > content_height {
         format!(
             " ({}/{})",
@@ -426,13 +513,11 @@ fn render_discussion_detail(frame: &mut Frame, app: &App) {
         String::new()
     };
 
-    let content = Paragraph::new(body_lines)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(format!("Content{}", scroll_info)),
-        )
-        .wrap(Wrap { trim: false });
+    let content = Paragraph::new(body_lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Content{}", scroll_info)),
+    );
     frame.render_widget(content, chunks[1]);
 
     // Rally status bar (if background rally exists)
@@ -442,7 +527,14 @@ fn render_discussion_detail(frame: &mut Frame, app: &App) {
 
     // Footer
     let footer_chunk_idx = if has_rally { 3 } else { 2 };
-    let footer = Paragraph::new("j/k/↑↓: scroll | Ctrl+d/u: page | Enter/Esc: back to list")
+    let help_text = if app.is_pending_discussion_delete() {
+        DELETE_CONFIRM_TEXT
+    } else if task_count > 0 {
+        "j/k/↑↓: scroll | Ctrl+d/u: page | Tab: next task | x: toggle task | e: edit | D: delete | u: author | Enter/Esc: back to list"
+    } else {
+        "j/k/↑↓: scroll | Ctrl+d/u: page | e: edit | D: delete | u: author | Enter/Esc: back to list"
+    };
+    let footer = Paragraph::new(super::footer::build_footer_line(app, help_text))
         .block(Block::default().borders(Borders::ALL));
     frame.render_widget(footer, chunks[footer_chunk_idx]);
 }
@@ -0,0 +1,138 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use super::common::build_pr_info;
+use crate::anonymize::Anonymizer;
+use crate::app::{App, CommitListState};
+use crate::github::PrCommit;
+use crate::text_width;
+use crate::time_format::TimeFormatter;
+
+const HELP_TEXT: &str = "j/k: move | Enter: view commit | q/Esc: back";
+
+/// PR のコミット一覧（先頭行は PR 全体の diff）
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // List
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(build_pr_info(app))
+        .block(Block::default().borders(Borders::ALL).title("octorus"));
+    frame.render_widget(header, chunks[0]);
+
+    let Some(ref list) = app.commit_list else {
+        return;
+    };
+
+    match (&list.commits, &list.error) {
+        (_, Some(error)) => {
+            let message = Paragraph::new(Span::styled(
+                format!("Failed to load commits: {}", error),
+                Style::default().fg(Color::Red),
+            ))
+            .block(Block::default().borders(Borders::ALL).title("Commits"));
+            frame.render_widget(message, chunks[1]);
+        }
+        (None, None) => {
+            let loading = Paragraph::new(Span::styled(
+                format!("{} Loading commits...", app.spinner_char()),
+                Style::default().fg(Color::Yellow),
+            ))
+            .block(Block::default().borders(Borders::ALL).title("Commits"));
+            frame.render_widget(loading, chunks[1]);
+        }
+        (Some(commits), None) => {
+            let current = app.commit_view.as_ref().map(|v| v.commit.sha.as_str());
+            let spinner = app.spinner_char();
+            let items: Vec<ListItem> = std::iter::once(all_changes_item(current.is_none()))
+                .chain(commits.iter().map(|commit| {
+                    build_item(
+                        commit,
+                        current,
+                        list,
+                        spinner,
+                        &app.time_format,
+                        &app.anonymizer,
+                    )
+                }))
+                .collect();
+            let mut list_state = ListState::default().with_selected(Some(list.selected));
+            let widget = List::new(items)
+                .block(
+                    Block::default()
+                        .borders(Borders::ALL)
+                        .title(format!("Commits ({})", commits.len())),
+                )
+                .highlight_style(Style::default().bg(Color::DarkGray));
+            frame.render_stateful_widget(widget, chunks[1], &mut list_state);
+        }
+    }
+
+    let footer_line = super::footer::build_footer_line(app, HELP_TEXT);
+    let footer = Paragraph::new(footer_line).block(super::footer::build_footer_block(app));
+    frame.render_widget(footer, chunks[2]);
+}
+
+/// 表示中のものに付ける印
+fn current_mark(is_current: bool) -> Span<'static> {
+    Span::styled(
+        if is_current { "▶ " } else { "  " },
+        Style::default().fg(Color::Yellow),
+    )
+}
+
+fn all_changes_item(is_current: bool) -> ListItem<'static> {
+    ListItem::new(Line::from(vec![
+        current_mark(is_current),
+        Span::styled("All changes", Style::default().add_modifier(Modifier::BOLD)),
+    ]))
+}
+
+fn build_item(
+    commit: &PrCommit,
+    current: Option<&str>,
+    list: &CommitListState,
+    spinner: &str,
+    time: &TimeFormatter,
+    anonymizer: &Anonymizer,
+) -> ListItem<'static> {
+    let loading = if list.loading_sha.as_deref() == Some(commit.sha.as_str()) {
+        format!("  {} loading...", spinner)
+    } else {
+        String::new()
+    };
+    ListItem::new(Line::from(vec![
+        current_mark(current == Some(commit.sha.as_str())),
+        Span::styled(
+            format!("{} ", commit.short_sha()),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(
+            if commit.is_merge { "[merge] " } else { "" },
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::raw(text_width::pad_to_width(
+            &text_width::truncate(&commit.headline, 60),
+            60,
+        )),
+        Span::styled(
+            format!("  @{}", anonymizer.user(&commit.author)),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(
+            format!("  {}", time.format(&commit.date)),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(loading, Style::default().fg(Color::Yellow)),
+    ]))
+}
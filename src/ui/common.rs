@@ -10,13 +10,96 @@ use crate::app::{App, DataState};
 
 /// Build PR info string for header display (shared between file_list and ai_rally)
 pub fn build_pr_info(app: &App) -> String {
+    format!(
+        "{}{}{}{}{}{}",
+        tabs_prefix(app),
+        queue_prefix(app),
+        build_pr_title(app),
+        threads_suffix(app),
+        commit_suffix(app),
+        scope_suffix(app)
+    )
+}
+
+/// 開いている PR のタブ。表示中のタブに `*`、表示していないタブで AI Rally が動いていれば `(AI)` を付ける
+pub fn pr_tabs_label(app: &App) -> String {
+    let active = app.pr_tabs.active();
+    let tabs: Vec<String> = app
+        .pr_tabs
+        .prs()
+        .iter()
+        .enumerate()
+        .map(|(i, &n)| {
+            let marker = if Some(n) == active { "*" } else { "" };
+            let rally = if app.pr_tabs.has_parked_rally(n) {
+                "(AI)"
+            } else {
+                ""
+            };
+            format!("{}{}:#{}{}", marker, i + 1, n, rally)
+        })
+        .collect();
+    format!("[tabs {}]", tabs.join(" "))
+}
+
+/// 2 つ以上の PR をタブで開いているときのタブ表示
+fn tabs_prefix(app: &App) -> String {
+    if app.pr_tabs.prs().len() < 2 {
+        return String::new();
+    }
+    format!("{} ", pr_tabs_label(app))
+}
+
+/// PR 一覧のキューから開いた PR の位置
+fn queue_prefix(app: &App) -> String {
+    match app.review_queue_position() {
+        Some((position, total)) => format!("[queue {}/{}] ", position, total),
+        None => String::new(),
+    }
+}
+
+/// 未解決のレビュースレッドが残っているときのヘッダー表示
+fn threads_suffix(app: &App) -> String {
+    match app.unresolved_thread_count() {
+        Some(count) if count > 0 => format!("  [{} unresolved]", count),
+        _ => String::new(),
+    }
+}
+
+/// 1 コミットの diff を表示しているときのヘッダー表示
+fn commit_suffix(app: &App) -> String {
+    match app.commit_view {
+        Some(ref view) => format!(
+            "  [commit {}: {}]",
+            view.commit.short_sha(),
+            view.commit.headline
+        ),
+        None => String::new(),
+    }
+}
+
+fn build_pr_title(app: &App) -> String {
     if app.is_local_mode() {
         let af = if app.is_local_auto_focus() { " AF" } else { "" };
         format!("[LOCAL{}] Local HEAD diff", af)
     } else {
         match &app.data_state {
             DataState::Loaded { pr, .. } => {
-                format!("PR #{}: {} by @{}", pr.number, pr.title, pr.user.login)
+                let labels = if pr.labels.is_empty() {
+                    String::new()
+                } else {
+                    let names: Vec<&str> = pr.labels.iter().map(|l| l.name.as_str()).collect();
+                    format!("  [{}]", names.join(", "))
+                };
+                let draft = if pr.draft { "[DRAFT] " } else { "" };
+                format!(
+                    "{}PR #{}: {} by @{}{}",
+                    draft,
+                    pr.number,
+                    pr.title,
+                    app.anonymizer.user(&pr.user.login),
+                    labels
+                )
             }
             _ => match app.pr_number {
                 Some(n) => format!("PR #{}", n),
@@ -26,6 +109,15 @@ pub fn build_pr_info(app: &App) -> String {
     }
 }
 
+/// `--paths` でスコープを絞っているときのヘッダー表示
+pub fn scope_suffix(app: &App) -> String {
+    if app.path_scope.is_empty() {
+        String::new()
+    } else {
+        format!("  [scope: {}]", app.path_scope.label())
+    }
+}
+
 /// Render rally status bar for background rally indication
 pub fn render_rally_status_bar(frame: &mut Frame, area: Rect, app: &App) {
     let Some(rally_state) = &app.ai_rally_state else {
@@ -59,7 +151,7 @@ pub fn render_rally_status_bar(frame: &mut Frame, area: Rect, app: &App) {
 mod tests {
     use super::*;
     use crate::app::App;
-    use crate::github::{Branch, PullRequest, User};
+    use crate::github::{Branch, Label, PullRequest, User};
 
     #[test]
     fn test_build_pr_info_loaded() {
@@ -83,10 +175,35 @@ mod tests {
                     login: "alice".to_string(),
                 },
                 updated_at: "2024-01-01T00:00:00Z".to_string(),
+                labels: Vec::new(),
+                draft: false,
             }),
             files: vec![],
         };
         assert_eq!(build_pr_info(&app), "PR #42: Add feature X by @alice");
+
+        if let DataState::Loaded { ref mut pr, .. } = app.data_state {
+            pr.labels = vec![
+                Label {
+                    name: "bug".to_string(),
+                },
+                Label {
+                    name: "ui".to_string(),
+                },
+            ];
+        }
+        assert_eq!(
+            build_pr_info(&app),
+            "PR #42: Add feature X by @alice  [bug, ui]"
+        );
+
+        if let DataState::Loaded { ref mut pr, .. } = app.data_state {
+            pr.draft = true;
+        }
+        assert_eq!(
+            build_pr_info(&app),
+            "[DRAFT] PR #42: Add feature X by @alice  [bug, ui]"
+        );
     }
 
     #[test]
@@ -0,0 +1,160 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, LocalCompareState};
+use crate::compare::{CompareRow, Comparison, RowKind};
+
+const HELP_TEXT: &str = "j/k: scroll | Ctrl-d/u: page | n/N: next/prev difference | q/Esc: back";
+
+/// PR の head 版（左）と作業ツリー版（右）を並べて表示する
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Body
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let Some(ref view) = app.local_compare else {
+        return;
+    };
+    let tab = " ".repeat(app.config.diff.tab_width as usize);
+
+    let header = Paragraph::new(header_line(view)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Compare with local"),
+    );
+    frame.render_widget(header, chunks[0]);
+
+    match (&view.comparison, &view.error) {
+        (_, Some(error)) => {
+            let message = Paragraph::new(Span::styled(
+                format!("Failed to compare: {}", error),
+                Style::default().fg(Color::Red),
+            ))
+            .block(Block::default().borders(Borders::ALL));
+            frame.render_widget(message, chunks[1]);
+        }
+        (None, None) => {
+            let loading = Paragraph::new(Span::styled(
+                format!("{} Loading PR head and local file...", app.spinner_char()),
+                Style::default().fg(Color::Yellow),
+            ))
+            .block(Block::default().borders(Borders::ALL));
+            frame.render_widget(loading, chunks[1]);
+        }
+        (Some(comparison), None) => {
+            render_columns(frame, chunks[1], comparison, view.scroll, &tab);
+        }
+    }
+
+    let footer_line = super::footer::build_footer_line(app, HELP_TEXT);
+    let footer = Paragraph::new(footer_line).block(super::footer::build_footer_block(app));
+    frame.render_widget(footer, chunks[2]);
+}
+
+fn header_line(view: &LocalCompareState) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        view.path.clone(),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    if let Some(ref comparison) = view.comparison {
+        let blocks = comparison.divergent_blocks().len();
+        let summary = if blocks == 0 {
+            "  local matches the PR head".to_string()
+        } else {
+            format!(
+                "  {} difference{} from the PR head",
+                blocks,
+                if blocks == 1 { "" } else { "s" }
+            )
+        };
+        let color = if blocks == 0 {
+            Color::Green
+        } else {
+            Color::Yellow
+        };
+        spans.push(Span::styled(summary, Style::default().fg(color)));
+    }
+    Line::from(spans)
+}
+
+fn render_columns(
+    frame: &mut Frame,
+    area: Rect,
+    comparison: &Comparison,
+    scroll: usize,
+    tab: &str,
+) {
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(area);
+
+    let height = area.height.saturating_sub(2) as usize;
+    let rows = comparison.rows.iter().skip(scroll).take(height);
+    let (left, right): (Vec<Line>, Vec<Line>) = rows
+        .map(|row| (cell(row, Side::Pr, tab), cell(row, Side::Local, tab)))
+        .unzip();
+
+    let short_sha: String = comparison.head_sha.chars().take(7).collect();
+    let pr_title = if comparison.pr_missing {
+        format!("PR head {} (file deleted)", short_sha)
+    } else {
+        format!("PR head {}", short_sha)
+    };
+    let local_title = if comparison.local_missing {
+        "Working tree (file missing)"
+    } else {
+        "Working tree"
+    };
+
+    frame.render_widget(
+        Paragraph::new(left).block(Block::default().borders(Borders::ALL).title(pr_title)),
+        columns[0],
+    );
+    frame.render_widget(
+        Paragraph::new(right).block(Block::default().borders(Borders::ALL).title(local_title)),
+        columns[1],
+    );
+}
+
+#[derive(Clone, Copy)]
+enum Side {
+    Pr,
+    Local,
+}
+
+fn cell(row: &CompareRow, side: Side, tab: &str) -> Line<'static> {
+    let (content, color) = match side {
+        Side::Pr => (&row.pr, Color::Red),
+        Side::Local => (&row.local, Color::Green),
+    };
+    let Some((line_no, text)) = content else {
+        // 反対側にだけある行の位置合わせ
+        return Line::from(Span::styled(
+            "      ~",
+            Style::default().fg(Color::DarkGray),
+        ));
+    };
+    let style = match row.kind {
+        RowKind::Same => Style::default(),
+        RowKind::Changed => Style::default().fg(Color::Yellow),
+        RowKind::PrOnly | RowKind::LocalOnly => Style::default().fg(color),
+    };
+    Line::from(vec![
+        Span::styled(
+            format!("{:>5} ", line_no),
+            Style::default().fg(Color::DarkGray),
+        ),
+        Span::styled(text.replace('\t', tab), style),
+    ])
+}
@@ -0,0 +1,163 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, ConflictResolveState};
+use crate::conflict::{ConflictBlock, Resolution};
+
+const HELP_TEXT: &str =
+    "n/N: next/prev conflict | o: take ours | t: take theirs | u: undo | j/k: scroll | w: write | q/Esc: back";
+
+/// コンフリクトごとに ours / base / theirs を並べて表示する
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Body
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let Some(ref view) = app.conflict_resolve else {
+        return;
+    };
+    let tab = " ".repeat(app.config.diff.tab_width as usize);
+
+    let header = Paragraph::new(header_line(view)).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Resolve conflicts"),
+    );
+    frame.render_widget(header, chunks[0]);
+
+    match (&view.file, &view.error) {
+        (_, Some(error)) => {
+            let message = Paragraph::new(Span::styled(
+                format!("Failed to load conflicts: {}", error),
+                Style::default().fg(Color::Red),
+            ))
+            .block(Block::default().borders(Borders::ALL));
+            frame.render_widget(message, chunks[1]);
+        }
+        (None, None) => {
+            let loading = Paragraph::new(Span::styled(
+                format!("{} Reading working tree file...", app.spinner_char()),
+                Style::default().fg(Color::Yellow),
+            ))
+            .block(Block::default().borders(Borders::ALL));
+            frame.render_widget(loading, chunks[1]);
+        }
+        (Some(file), None) => {
+            if let Some(block) = file.conflicts().nth(view.selected) {
+                render_columns(frame, chunks[1], block, view.scroll, &tab);
+            }
+        }
+    }
+
+    let footer_line = super::footer::build_footer_line(app, HELP_TEXT);
+    let footer = Paragraph::new(footer_line).block(super::footer::build_footer_block(app));
+    frame.render_widget(footer, chunks[2]);
+}
+
+fn header_line(view: &ConflictResolveState) -> Line<'static> {
+    let mut spans = vec![Span::styled(
+        view.path.clone(),
+        Style::default().add_modifier(Modifier::BOLD),
+    )];
+    let Some(ref file) = view.file else {
+        return Line::from(spans);
+    };
+    let total = file.conflict_count();
+    let resolved = file.resolved_count();
+    if let Some(block) = file.conflicts().nth(view.selected) {
+        spans.push(Span::raw(format!(
+            "  conflict {}/{} at line {}",
+            view.selected + 1,
+            total,
+            block.start_line
+        )));
+    }
+    let (summary, color) = if view.writing {
+        ("  writing...".to_string(), Color::Yellow)
+    } else if resolved == total {
+        ("  all resolved, press w to write".to_string(), Color::Green)
+    } else {
+        (
+            format!("  {} of {} resolved", resolved, total),
+            Color::Yellow,
+        )
+    };
+    spans.push(Span::styled(summary, Style::default().fg(color)));
+    Line::from(spans)
+}
+
+fn render_columns(frame: &mut Frame, area: Rect, block: &ConflictBlock, scroll: usize, tab: &str) {
+    let mut columns: Vec<(String, &[String], Option<Resolution>)> = vec![(
+        format!("Ours ({})", block.ours_label),
+        block.ours.as_slice(),
+        Some(Resolution::Ours),
+    )];
+    if let Some(ref base) = block.base {
+        columns.push(("Base".to_string(), base.as_slice(), None));
+    }
+    columns.push((
+        format!("Theirs ({})", block.theirs_label),
+        block.theirs.as_slice(),
+        Some(Resolution::Theirs),
+    ));
+
+    let areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![
+            Constraint::Ratio(1, columns.len() as u32);
+            columns.len()
+        ])
+        .split(area);
+    let height = area.height.saturating_sub(2) as usize;
+
+    for ((title, lines, side), column_area) in columns.into_iter().zip(areas.iter()) {
+        // 採用した側は緑、採用しなかった側は暗く表示する
+        let (border, text) = match (side, block.resolution) {
+            (Some(side), Some(chosen)) if side == chosen => (
+                Style::default().fg(Color::Green),
+                Style::default().fg(Color::Green),
+            ),
+            (_, Some(_)) => (
+                Style::default().fg(Color::DarkGray),
+                Style::default().fg(Color::DarkGray),
+            ),
+            (_, None) => (Style::default(), Style::default()),
+        };
+        let title = match (side, block.resolution) {
+            (Some(side), Some(chosen)) if side == chosen => format!("{} ✓", title),
+            _ => title,
+        };
+        let body: Vec<Line> = if lines.is_empty() {
+            vec![Line::from(Span::styled(
+                "(empty)",
+                Style::default().fg(Color::DarkGray),
+            ))]
+        } else {
+            lines
+                .iter()
+                .skip(scroll)
+                .take(height)
+                .map(|line| Line::from(Span::styled(line.replace('\t', tab), text)))
+                .collect()
+        };
+        frame.render_widget(
+            Paragraph::new(body).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(border)
+                    .title(title),
+            ),
+            *column_area,
+        );
+    }
+}
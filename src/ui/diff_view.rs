@@ -12,14 +12,23 @@ use ratatui::{
 use syntect::easy::HighlightLines;
 
 use super::common::render_rally_status_bar;
+use super::markdown;
+use super::palette::DiffPalette;
+use crate::anonymize::Anonymizer;
 use crate::app::{
-    hash_string, App, CachedDiffLine, DiffCache, InputMode, InternedSpan, LineInputContext,
+    hash_string, App, BatchReplyTarget, CachedDiffLine, CommentTranslation, DiffCache, InputMode,
+    InternedSpan, LineInputContext,
 };
-use crate::diff::{classify_line, LineType};
+use crate::diff::{
+    classify_patch_lines, patch_lines, patch_warnings, LineType, ParentOrigin, PatchLine,
+};
+use crate::diff_search::SearchQuery;
+use crate::encoding;
 use crate::syntax::{
     apply_line_highlights, collect_line_highlights, collect_line_highlights_with_injections,
     get_theme, highlight_code_line, syntax_for_file, Highlighter, ParserPool,
 };
+use crate::text_width;
 
 /// Expand tab characters to spaces with fixed width.
 ///
@@ -34,6 +43,13 @@ pub fn expand_tabs(s: &str, tab_width: u8) -> Cow<'_, str> {
     }
 }
 
+/// Number of lines highlighted at once around the viewport of a large plain cache.
+pub const LAZY_HIGHLIGHT_CHUNK: usize = 256;
+
+/// Plain caches with at least this many lines are highlighted chunk by chunk around the
+/// viewport while the background build of the full cache runs.
+pub const LAZY_HIGHLIGHT_MIN_LINES: usize = 2000;
+
 /// Build a plain DiffCache without syntax highlighting (diff coloring only).
 ///
 /// This is a fast path (~1ms) used to provide immediate visual feedback while
@@ -46,14 +62,17 @@ pub fn expand_tabs(s: &str, tab_width: u8) -> Cow<'_, str> {
 /// Returns a DiffCache with file_index set to 0 (caller should update).
 pub fn build_plain_diff_cache(patch: &str, tab_width: u8) -> DiffCache {
     let patch_hash = hash_string(patch);
+    let parse_warnings = patch_warnings(patch);
     let expanded = expand_tabs(patch, tab_width);
     let mut interner = Rodeo::default();
     let lines: Vec<CachedDiffLine> = expanded
         .lines()
-        .map(|line| {
-            let (line_type, content) = classify_line(line);
-
-            let spans = match line_type {
+        .zip(patch_lines(&expanded))
+        .map(|(line, patch_line)| {
+            let PatchLine {
+                line_type, content, ..
+            } = patch_line;
+            let mut spans = match line_type {
                 LineType::Header => vec![InternedSpan {
                     content: interner.get_or_intern(line),
                     style: Style::default().fg(Color::Cyan),
@@ -93,11 +112,18 @@ pub fn build_plain_diff_cache(patch: &str, tab_width: u8) -> DiffCache {
                     },
                 ],
             };
+            apply_parent_origins(&mut spans, &patch_line, &mut interner);
 
             CachedDiffLine { spans, line_type }
         })
         .collect();
 
+    let highlighted_chunks = if lines.len() >= LAZY_HIGHLIGHT_MIN_LINES {
+        vec![false; lines.len().div_ceil(LAZY_HIGHLIGHT_CHUNK)]
+    } else {
+        Vec::new()
+    };
+
     DiffCache {
         file_index: 0,
         patch_hash,
@@ -105,6 +131,63 @@ pub fn build_plain_diff_cache(patch: &str, tab_width: u8) -> DiffCache {
         interner,
         highlighted: false,
         markdown_rich: false,
+        parse_warnings,
+        highlighted_chunks,
+    }
+}
+
+/// Syntax-highlight one chunk of a large plain DiffCache in place.
+///
+/// Only the lines of the chunk are parsed, so highlighting that depends on earlier lines
+/// (e.g. an unterminated block comment) may differ until the full cache from the background
+/// build replaces this one. Lines classified differently on their own (the chunk starts in
+/// the middle of a hunk) keep their plain spans.
+///
+/// # Arguments
+/// * `cache` - The plain cache built from `patch`
+/// * `patch` - The diff patch content the cache was built from
+/// * `chunk` - Index of the chunk ([`LAZY_HIGHLIGHT_CHUNK`] lines each)
+///
+/// Does nothing if the chunk is out of range or already highlighted.
+pub fn highlight_cache_chunk(
+    cache: &mut DiffCache,
+    patch: &str,
+    chunk: usize,
+    filename: &str,
+    theme_name: &str,
+    parser_pool: &mut ParserPool,
+    tab_width: u8,
+) {
+    if cache.highlighted_chunks.get(chunk) != Some(&false) {
+        return;
+    }
+    cache.highlighted_chunks[chunk] = true;
+
+    let start = chunk * LAZY_HIGHLIGHT_CHUNK;
+    let source = patch
+        .lines()
+        .skip(start)
+        .take(LAZY_HIGHLIGHT_CHUNK)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let highlighted =
+        build_diff_cache(&source, filename, theme_name, parser_pool, false, tab_width);
+
+    let DiffCache {
+        lines, interner, ..
+    } = cache;
+    for (target, line) in lines.iter_mut().skip(start).zip(highlighted.lines) {
+        if target.line_type != line.line_type {
+            continue;
+        }
+        target.spans = line
+            .spans
+            .into_iter()
+            .map(|span| InternedSpan {
+                content: interner.get_or_intern(highlighted.interner.resolve(&span.content)),
+                style: span.style,
+            })
+            .collect();
     }
 }
 
@@ -130,6 +213,7 @@ pub fn build_diff_cache(
     tab_width: u8,
 ) -> DiffCache {
     let patch_hash = hash_string(patch);
+    let parse_warnings = patch_warnings(patch);
     let expanded = expand_tabs(patch, tab_width);
     let patch = expanded.as_ref();
     let mut interner = Rodeo::default();
@@ -225,6 +309,7 @@ pub fn build_diff_cache(
     let mut lines = lines;
     if markdown_rich && (ext == "md" || ext == "markdown") {
         apply_markdown_rich_transforms(&mut lines, &mut interner);
+        apply_markdown_gfm_transforms(&mut lines, &mut interner);
         apply_markdown_table_transforms(&mut lines, &mut interner);
     }
 
@@ -235,6 +320,8 @@ pub fn build_diff_cache(
         interner,
         highlighted: true,
         markdown_rich,
+        parse_warnings,
+        highlighted_chunks: Vec::new(),
     }
 }
 
@@ -502,6 +589,105 @@ fn remove_inline_emphasis_delimiters(line: &mut CachedDiffLine, interner: &mut R
     }
 }
 
+/// Transform GitHub-flavored task list items and alert markers.
+///
+/// Runs after `apply_markdown_rich_transforms`, so list markers are already `・`.
+///
+/// - Task items (`・ [ ]` / `・ [x]`) → `☐` / `☑`
+/// - Alert markers (`> [!NOTE]`, `> [!WARNING]`, ...) → colored bold label
+fn apply_markdown_gfm_transforms(lines: &mut [CachedDiffLine], interner: &mut Rodeo) {
+    for line in lines.iter_mut() {
+        if line.spans.len() <= 1 {
+            continue;
+        }
+        let first = interner.resolve(&line.spans[0].content);
+        if first != "+" && first != "-" && first != " " {
+            continue;
+        }
+
+        let full_content: String = line.spans[1..]
+            .iter()
+            .map(|s| interner.resolve(&s.content))
+            .collect();
+        let trimmed = full_content.trim_start();
+        let indent = full_content.chars().count() - trimmed.chars().count();
+
+        if let Some(rest) = trimmed.strip_prefix("・ ") {
+            let (mark, style) = match rest.get(..3) {
+                Some("[ ]") => ("☐", Style::default()),
+                Some("[x]") | Some("[X]") => ("☑", Style::default().fg(Color::Green)),
+                _ => continue,
+            };
+            if rest.len() == 3 || rest[3..].starts_with(' ') {
+                // `・ [ ]` (5 chars) → `☐`
+                replace_char_range(line, interner, indent, 5, mark, style);
+            }
+        } else if let Some(quoted) = trimmed.strip_prefix('>') {
+            let marker = quoted.trim_start();
+            if let Some(kind) = markdown::AlertKind::parse(marker.trim_end()) {
+                let start = indent + 1 + (quoted.chars().count() - marker.chars().count());
+                let style = Style::default()
+                    .fg(kind.color())
+                    .add_modifier(Modifier::BOLD);
+                let len = marker.trim_end().chars().count();
+                replace_char_range(line, interner, start, len, kind.label(), style);
+            }
+        }
+    }
+}
+
+/// Replace `len` content characters starting at `start` (after the diff marker)
+/// with a single styled span, splitting the spans at the range boundaries.
+fn replace_char_range(
+    line: &mut CachedDiffLine,
+    interner: &mut Rodeo,
+    start: usize,
+    len: usize,
+    replacement: &str,
+    style: Style,
+) {
+    let end = start + len;
+    let mut spans = Vec::with_capacity(line.spans.len() + 2);
+    spans.push(line.spans[0].clone());
+    let mut pos = 0;
+    let mut inserted = false;
+    for span in &line.spans[1..] {
+        let content = interner.resolve(&span.content).to_string();
+        let count = content.chars().count();
+        let (span_start, span_end) = (pos, pos + count);
+        pos = span_end;
+        if span_end <= start || span_start >= end {
+            spans.push(span.clone());
+            continue;
+        }
+        let before: String = content
+            .chars()
+            .take(start.saturating_sub(span_start))
+            .collect();
+        let after: String = content.chars().skip(end - span_start).collect();
+        if !before.is_empty() {
+            spans.push(InternedSpan {
+                content: interner.get_or_intern(&before),
+                style: span.style,
+            });
+        }
+        if !inserted {
+            spans.push(InternedSpan {
+                content: interner.get_or_intern(replacement),
+                style,
+            });
+            inserted = true;
+        }
+        if !after.is_empty() {
+            spans.push(InternedSpan {
+                content: interner.get_or_intern(&after),
+                style: span.style,
+            });
+        }
+    }
+    line.spans = spans;
+}
+
 /// Transform markdown table rows to use box-drawing characters.
 ///
 /// - Data/header rows: `|` → `│`
@@ -678,8 +864,7 @@ fn build_combined_source_for_highlight(patch: &str) -> (String, Vec<(usize, Line
     // Maps source line index -> (diff line index, line type)
     let mut line_mapping: Vec<(usize, LineType)> = Vec::new();
 
-    for (diff_line_idx, line) in patch.lines().enumerate() {
-        let (line_type, content) = classify_line(line);
+    for (diff_line_idx, (line_type, content)) in classify_patch_lines(patch).enumerate() {
         match line_type {
             // Only include added and context lines (post-change version)
             // This ensures the source is syntactically valid for tree-sitter
@@ -854,11 +1039,13 @@ fn build_lines_with_cst(
 
     patch
         .lines()
+        .zip(patch_lines(patch))
         .enumerate()
-        .map(|(i, line)| {
-            let (line_type, content) = classify_line(line);
-
-            let spans = match line_type {
+        .map(|(i, (line, patch_line))| {
+            let PatchLine {
+                line_type, content, ..
+            } = patch_line;
+            let mut spans = match line_type {
                 LineType::Header => {
                     vec![InternedSpan {
                         content: interner.get_or_intern(line),
@@ -913,6 +1100,7 @@ fn build_lines_with_cst(
                     std::iter::once(marker).chain(code_spans).collect()
                 }
             };
+            apply_parent_origins(&mut spans, &patch_line, interner);
 
             CachedDiffLine { spans, line_type }
         })
@@ -943,10 +1131,17 @@ fn build_lines_with_syntect(
 
     patch
         .lines()
-        .map(|line| {
-            let (line_type, content) = classify_line(line);
-
-            let spans = build_line_spans(line_type, line, content, &mut highlighter, interner);
+        .zip(patch_lines(patch))
+        .map(|(line, patch_line)| {
+            let line_type = patch_line.line_type;
+            let mut spans = build_line_spans(
+                line_type,
+                line,
+                patch_line.content,
+                &mut highlighter,
+                interner,
+            );
+            apply_parent_origins(&mut spans, &patch_line, interner);
 
             CachedDiffLine { spans, line_type }
         })
@@ -963,13 +1158,14 @@ fn build_lines_with_syntect(
 /// * `range` – the range of lines to render (may be a sub-range).
 /// * `selected_line` – absolute index of the currently selected line.
 /// * `comment_lines` – set of diff line indices that have comments (for `●` marker).
+/// * `palette` – 追加/削除行と複数行選択の背景色。
 /// * `multiline_range` – 複数行選択範囲 (start, end) の inclusive range。None なら通常選択。
 pub fn render_cached_lines<'a>(
     cache: &'a DiffCache,
     range: std::ops::Range<usize>,
     selected_line: usize,
     comment_lines: &HashSet<usize>,
-    bg_color: bool,
+    palette: &DiffPalette,
     multiline_range: Option<(usize, usize)>,
 ) -> Vec<Line<'a>> {
     // Clamp range to valid bounds to prevent out-of-bounds panic
@@ -1006,28 +1202,193 @@ pub fn render_cached_lines<'a>(
                 if is_selected {
                     line.style(
                         Style::default()
-                            .bg(Color::Rgb(0, 40, 80))
+                            .bg(palette.multiline)
                             .add_modifier(Modifier::REVERSED),
                     )
                 } else {
-                    line.style(Style::default().bg(Color::Rgb(0, 40, 80)))
+                    line.style(Style::default().bg(palette.multiline))
                 }
             } else if is_selected {
                 // 選択行: REVERSED のみ（背景色 + REVERSED は fg/bg 反転で視認性が低下するため省略）
                 line.style(Style::default().add_modifier(Modifier::REVERSED))
-            } else if bg_color {
-                match cached.line_type {
-                    LineType::Added => line.style(Style::default().bg(Color::Rgb(0, 60, 0))),
-                    LineType::Removed => line.style(Style::default().bg(Color::Rgb(60, 0, 0))),
-                    _ => line,
-                }
             } else {
-                line
+                let bg = match cached.line_type {
+                    LineType::Added => palette.added,
+                    LineType::Removed => palette.removed,
+                    _ => None,
+                };
+                match bg {
+                    Some(bg) => line.style(Style::default().bg(bg)),
+                    None => line,
+                }
             }
         })
         .collect()
 }
 
+/// UTF-8 として読めなかったバイトや BOM を `\xNN` / `<BOM>` などの目印に置き換えて強調する
+pub(crate) fn mark_undecodable(lines: Vec<Line<'_>>) -> Vec<Line<'_>> {
+    let marker_style = Style::default().fg(Color::White).bg(Color::Red);
+    lines
+        .into_iter()
+        .map(|mut line| {
+            let has_marker = line.spans.iter().any(|span| {
+                span.content
+                    .chars()
+                    .any(|c| encoding::indicator(c).is_some())
+            });
+            if !has_marker {
+                return line;
+            }
+            let mut spans = Vec::with_capacity(line.spans.len() + 2);
+            for span in std::mem::take(&mut line.spans) {
+                let mut plain = String::new();
+                for c in span.content.chars() {
+                    match encoding::indicator(c) {
+                        Some(marker) => {
+                            if !plain.is_empty() {
+                                spans.push(Span::styled(std::mem::take(&mut plain), span.style));
+                            }
+                            spans.push(Span::styled(marker, span.style.patch(marker_style)));
+                        }
+                        None => plain.push(c),
+                    }
+                }
+                if !plain.is_empty() {
+                    spans.push(Span::styled(plain, span.style));
+                }
+            }
+            line.spans = spans;
+            line
+        })
+        .collect()
+}
+
+/// 検索の一致箇所をハイライトする（行のスタイルや選択行の反転はそのまま）
+pub(crate) fn highlight_search_matches<'a>(
+    lines: Vec<Line<'a>>,
+    query: &SearchQuery,
+) -> Vec<Line<'a>> {
+    let match_style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    lines
+        .into_iter()
+        .map(|mut line| {
+            let text: String = line.spans.iter().map(|s| s.content.as_ref()).collect();
+            let ranges = query.ranges(&text);
+            if ranges.is_empty() {
+                return line;
+            }
+            let mut spans = Vec::with_capacity(line.spans.len() + ranges.len() * 2);
+            let mut offset = 0;
+            for span in std::mem::take(&mut line.spans) {
+                let len = span.content.len();
+                // span 内の一致の境界で分割する
+                let mut cuts = vec![0, len];
+                for &(start, end) in &ranges {
+                    for pos in [start, end] {
+                        if pos > offset && pos < offset + len {
+                            cuts.push(pos - offset);
+                        }
+                    }
+                }
+                cuts.sort_unstable();
+                cuts.dedup();
+                for window in cuts.windows(2) {
+                    let (from, to) = (window[0], window[1]);
+                    let matched = ranges
+                        .iter()
+                        .any(|&(start, end)| offset + from >= start && offset + from < end);
+                    let content: Cow<'a, str> = match span.content {
+                        Cow::Borrowed(s) => Cow::Borrowed(&s[from..to]),
+                        Cow::Owned(ref s) => Cow::Owned(s[from..to].to_string()),
+                    };
+                    let style = if matched {
+                        span.style.patch(match_style)
+                    } else {
+                        span.style
+                    };
+                    spans.push(Span::styled(content, style));
+                }
+                offset += len;
+            }
+            line.spans = spans;
+            line
+        })
+        .collect()
+}
+
+/// blame のガターの幅（author・経過時間と区切り）
+const BLAME_GUTTER_WIDTH: usize = 18;
+
+/// blame の表示中は、コンテキスト行の先頭にその行を最後に変更したコミットの author と
+/// 経過時間を薄く添える。追加・削除行とヘッダーは同じ幅の空白にして桁を揃える
+pub(crate) fn prepend_blame_gutter<'a>(
+    app: &App,
+    lines: Vec<Line<'a>>,
+    start: usize,
+) -> Vec<Line<'a>> {
+    let Some(blame) = app.current_file_blame() else {
+        return lines;
+    };
+    let Some(patch) = app
+        .files()
+        .get(app.selected_file)
+        .and_then(|f| f.patch.as_deref())
+    else {
+        return lines;
+    };
+    let now = chrono::Utc::now();
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut numbers = crate::diff::context_line_numbers(patch).skip(start);
+    lines
+        .into_iter()
+        .map(|mut line| {
+            let label = numbers
+                .next()
+                .flatten()
+                .and_then(|n| blame.get(n))
+                .map(|commit| {
+                    let author = text_width::truncate(&app.anonymizer.user(&commit.author), 12);
+                    format!(
+                        "{} {:>4} ",
+                        text_width::pad_to_width(&author, 12),
+                        commit.age(now)
+                    )
+                })
+                .unwrap_or_else(|| " ".repeat(BLAME_GUTTER_WIDTH));
+            line.spans.insert(0, Span::styled(label, dim));
+            line
+        })
+        .collect()
+}
+
+/// diff 内検索のフッター表示。検索していなければ None
+pub(crate) fn search_footer_text(app: &App) -> Option<String> {
+    let search = app.diff_search.as_ref()?;
+    let scope = if search.all_files {
+        "all files"
+    } else {
+        "this file"
+    };
+    if search.editing {
+        return Some(format!(
+            "/{}█  [{}]  Tab: toggle all files | Enter: search | Esc: cancel",
+            search.input, scope
+        ));
+    }
+    let query = search.query.as_ref()?;
+    let position = match search.position {
+        Some((index, total)) => format!("{}/{}", index + 1, total),
+        None => "no match".to_string(),
+    };
+    Some(format!(
+        "/{}  [{}, {}]  n/N: next/prev match | /: new search | Esc: clear",
+        query.text(),
+        scope,
+        position
+    ))
+}
+
 pub fn render(frame: &mut Frame, app: &App) {
     // If comment panel is open (focused), show split view with comment panel
     if app.comment_panel_open {
@@ -1111,19 +1472,59 @@ pub(crate) fn render_header(frame: &mut Frame, app: &App, area: ratatui::layout:
         .files()
         .get(app.selected_file)
         .map(|file| {
+            let preview = app
+                .preview_label(app.selected_file)
+                .map(|label| format!(" [{}]", label))
+                .unwrap_or_default();
             format!(
-                "{} (+{} -{})",
-                file.filename, file.additions, file.deletions
+                "{} (+{} -{}){}",
+                file.filename, file.additions, file.deletions, preview
             )
         })
         .unwrap_or_else(|| "No file selected".to_string());
 
-    let header =
-        Paragraph::new(header_text).block(Block::default().borders(Borders::ALL).title("Diff"));
+    let header = Paragraph::new(header_text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(diff_title(app)),
+    );
     frame.render_widget(header, area);
 }
 
+/// diff ヘッダーのタイトル。patch のパースに問題があれば件数と最初の警告を添える
+fn diff_title(app: &App) -> Line<'static> {
+    let warnings = app
+        .diff_cache
+        .as_ref()
+        .filter(|cache| cache.file_index == app.selected_file)
+        .map(|cache| cache.parse_warnings.as_slice())
+        .unwrap_or_default();
+    let Some(first) = warnings.first() else {
+        return Line::from("Diff");
+    };
+    let count = if warnings.len() == 1 {
+        "1 parse warning".to_string()
+    } else {
+        format!("{} parse warnings", warnings.len())
+    };
+    Line::from(vec![
+        Span::raw("Diff "),
+        Span::styled(
+            format!("⚠ {} ({})", count, first),
+            Style::default().fg(Color::Yellow),
+        ),
+    ])
+}
+
 pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
+    if let Some(lines) = collapsed_vendored_lines(app) {
+        let collapsed = Paragraph::new(lines).block(Block::default().borders(Borders::ALL));
+        frame.render_widget(collapsed, area);
+        return;
+    }
+    let area = super::explain::render_beside(frame, app, area);
+    let area = super::manifest::render_summary_above(frame, app, area);
+
     let visible_height = area.height.saturating_sub(2) as usize;
 
     // Try to use cached lines if available
@@ -1142,14 +1543,19 @@ pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::l
             .multiline_selection
             .as_ref()
             .map(|s| (s.start(), s.end()));
-        render_cached_lines(
+        let lines = mark_undecodable(render_cached_lines(
             cache,
             visible_start..visible_end,
             app.selected_line,
             &app.file_comment_lines,
-            app.config.diff.bg_color,
+            &DiffPalette::from_config(&app.config),
             multiline_range,
-        )
+        ));
+        let lines = match app.diff_search.as_ref().and_then(|s| s.highlight_query()) {
+            Some(query) => highlight_search_matches(lines, &query),
+            None => lines,
+        };
+        prepend_blame_gutter(app, lines, visible_start)
     } else {
         // Fallback: parse without cache (should rarely happen)
         let file = app.files().get(app.selected_file);
@@ -1158,7 +1564,7 @@ pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::l
         match file {
             Some(f) => match f.patch.as_ref() {
                 Some(patch) => parse_patch_to_lines(
-                    patch,
+                    &crate::encoding::display_patch(&crate::lfs::display_patch(patch)),
                     app.selected_line,
                     &f.filename,
                     theme_name,
@@ -1223,6 +1629,71 @@ pub(crate) fn render_diff_content(frame: &mut Frame, app: &App, area: ratatui::l
     }
 }
 
+/// 折りたたみ中の vendored ファイルの代わりに表示する、依存の要約
+pub(crate) fn collapsed_vendored_lines(app: &App) -> Option<Vec<Line<'static>>> {
+    if !app.is_vendored_collapsed(app.selected_file) {
+        return None;
+    }
+    let file = app.files().get(app.selected_file)?;
+    let root = crate::vendored::package_root(&file.filename)?;
+    let summary = crate::vendored::summarize(app.files());
+    let package = summary.packages.iter().find(|p| p.root == root);
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(
+                "  ▸ Vendored dependency ",
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                root.clone(),
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(format!(
+            "    This file: +{} -{}",
+            file.additions, file.deletions
+        )),
+    ];
+    if let Some(package) = package {
+        lines.push(Line::from(format!(
+            "    Dependency: {} file(s), +{} -{}",
+            package.files, package.additions, package.deletions
+        )));
+        if let Some(change) = package.version_change() {
+            lines.push(Line::from(vec![
+                Span::raw("    Version: "),
+                Span::styled(change, Style::default().fg(Color::Yellow)),
+            ]));
+        }
+    }
+    let licenses: Vec<&str> = summary
+        .license_changes
+        .iter()
+        .filter(|c| c.path.starts_with(&format!("{}/", root)))
+        .map(|c| c.path.as_str())
+        .collect();
+    for path in licenses {
+        lines.push(Line::from(Span::styled(
+            format!("    ⚠ Licence changed: {}", path),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )));
+    }
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "    {}: expand diff",
+            app.config.keybindings.toggle_vendored.display()
+        ),
+        Style::default().fg(Color::DarkGray),
+    )));
+    Some(lines)
+}
+
 /// Fallback function to render patch lines when cache is not available.
 ///
 /// This function is called from `render_diff_content` when `app.diff_cache` is None,
@@ -1336,6 +1807,33 @@ fn build_line_spans(
     }
 }
 
+/// Replace the single `+`/`-`/space marker of a combined diff line with one
+/// column per parent, colored by how the line relates to that parent.
+///
+/// Regular diff lines are left untouched.
+fn apply_parent_origins(spans: &mut Vec<InternedSpan>, line: &PatchLine<'_>, interner: &mut Rodeo) {
+    let Some(origins) = line.parent_origins() else {
+        return;
+    };
+    if spans.is_empty() {
+        return;
+    }
+    let columns: Vec<InternedSpan> = line
+        .origin
+        .char_indices()
+        .zip(origins)
+        .map(|((i, _), origin)| InternedSpan {
+            content: interner.get_or_intern(&line.origin[i..i + 1]),
+            style: match origin {
+                ParentOrigin::Added => Style::default().fg(Color::Green),
+                ParentOrigin::Removed => Style::default().fg(Color::Red),
+                ParentOrigin::Unchanged => Style::default(),
+            },
+        })
+        .collect();
+    spans.splice(0..1, columns);
+}
+
 fn highlight_or_fallback(
     content: &str,
     highlighter: &mut Option<HighlightLines<'_>>,
@@ -1366,7 +1864,9 @@ fn render_footer(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let help_text = if app.multiline_selection.is_some() {
         "j/k/↑↓: extend selection | c: comment | s: suggest | Esc: cancel".to_string()
     } else if app.comment_panel_open {
-        "j/k/↑↓: scroll | n/N: jump | Tab: switch | r: reply | c: comment | s: suggest | ←/h: back | Esc/q: close".to_string()
+        "j/k/↑↓: scroll | n/N: jump | Tab: switch | r: reply | T: translate | c: comment | s: suggest | ←/h: back | Esc/q: close".to_string()
+    } else if let Some(text) = search_footer_text(app) {
+        text
     } else if app.is_local_mode() {
         "j/k/↑↓: move | M: markdown rich | Ctrl-d/u: page | ←/h/q: back".to_string()
     } else {
@@ -1382,6 +1882,8 @@ fn render_footer(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
 /// Render inline comments panel for current line
 fn render_inline_comments(frame: &mut Frame, app: &App, area: ratatui::layout::Rect) {
     let indices = app.get_comment_indices_at_current_line();
+    // スクロール計算（App::comment_panel_content_lines）と同じ幅で折り返す
+    let inner_width = area.width.saturating_sub(2) as usize;
 
     let mut lines: Vec<Line> = vec![];
 
@@ -1422,24 +1924,28 @@ fn render_inline_comments(frame: &mut Frame, app: &App, area: ratatui::layout::R
             lines.push(Line::from(vec![
                 indicator,
                 Span::styled(
-                    format!("@{}", comment.user.login),
+                    format!("@{}", app.anonymizer.user(&comment.user.login)),
                     Style::default().fg(Color::Cyan),
                 ),
                 Span::styled(
-                    format!(" (line {})", comment.line.unwrap_or(0)),
+                    format!(
+                        " (line {}) {}",
+                        comment.line.unwrap_or(0),
+                        app.time_format.format(&comment.created_at)
+                    ),
                     Style::default().fg(Color::DarkGray),
                 ),
             ]));
 
             // Body
-            for line in comment.body.lines() {
-                lines.push(Line::from(line.to_string()));
-            }
+            let body = app.anonymizer.text(&comment.body);
+            lines.extend(markdown::render_body(&body, inner_width, None));
+            push_translation_lines(&mut lines, app, comment.id, inner_width);
             lines.push(Line::from("")); // Spacing after comment body
         }
     }
 
-    let title = "Comments (j/k/↑↓: scroll, c: comment, s: suggest, r: reply)";
+    let title = "Comments (j/k/↑↓: scroll, c: comment, s: suggest, r: reply, T: translate)";
     let total_lines = lines.len();
 
     let block = Block::default()
@@ -1449,7 +1955,6 @@ fn render_inline_comments(frame: &mut Frame, app: &App, area: ratatui::layout::R
 
     let paragraph = Paragraph::new(lines)
         .block(block)
-        .wrap(Wrap { trim: true })
         .scroll((app.comment_panel_scroll, 0));
 
     frame.render_widget(paragraph, area);
@@ -1475,6 +1980,43 @@ fn render_inline_comments(frame: &mut Frame, app: &App, area: ratatui::layout::R
     }
 }
 
+/// Render the translation block shown below a comment body
+/// (line count must match `App::comment_translation_lines`)
+fn push_translation_lines(lines: &mut Vec<Line<'_>>, app: &App, comment_id: u64, width: usize) {
+    let Some(translation) = app.comment_translations.get(&comment_id) else {
+        return;
+    };
+    let dim = Style::default().fg(Color::DarkGray);
+    match translation {
+        CommentTranslation::Pending => {
+            lines.push(Line::from(Span::styled("Translating...", dim)));
+        }
+        CommentTranslation::Failed(e) => {
+            let message = format!("Translation failed: {}", e);
+            for line in text_width::wrap(&message, width) {
+                lines.push(Line::from(Span::styled(
+                    line,
+                    Style::default().fg(Color::Red),
+                )));
+            }
+        }
+        CommentTranslation::Done(text) => {
+            let language = app.config.translate.language.as_deref().unwrap_or("");
+            lines.push(Line::from(Span::styled(
+                format!("── Translation ({}) ──", language),
+                dim,
+            )));
+            let text = app.anonymizer.text(text);
+            for line in text.lines().flat_map(|l| text_width::wrap(l, width)) {
+                lines.push(Line::from(Span::styled(
+                    line,
+                    Style::default().fg(Color::Green),
+                )));
+            }
+        }
+    }
+}
+
 /// Render unified text input view (comment/suggestion/reply)
 pub fn render_text_input(frame: &mut Frame, app: &App) {
     let chunks = Layout::default()
@@ -1511,9 +2053,58 @@ pub fn render_text_input(frame: &mut Frame, app: &App) {
             reply_to_body,
             ..
         }) => {
-            render_reply_context(frame, chunks[1], reply_to_user, reply_to_body);
+            render_reply_context(
+                frame,
+                chunks[1],
+                &app.anonymizer.user(reply_to_user),
+                &app.anonymizer.text(reply_to_body),
+            );
             render_text_input_area(frame, app, chunks[2], "Reply", "Type your reply here...");
         }
+        Some(InputMode::BatchReply { targets }) => {
+            render_batch_reply_context(frame, chunks[1], targets, &app.anonymizer);
+            render_text_input_area(
+                frame,
+                app,
+                chunks[2],
+                &format!("Reply to {} threads", targets.len()),
+                "Type the reply sent to every thread (e.g. Fixed in abc123)...",
+            );
+        }
+        Some(InputMode::Nudge {
+            pr_number,
+            title,
+            days,
+        }) => {
+            render_nudge_context(frame, chunks[1], *pr_number, title, *days);
+            render_text_input_area(
+                frame,
+                app,
+                chunks[2],
+                "Reminder",
+                "Type the reminder posted on the PR...",
+            );
+        }
+        Some(InputMode::ReviewAgenda { pr_number }) => {
+            render_agenda_context(frame, chunks[1], *pr_number);
+            render_text_input_area(
+                frame,
+                app,
+                chunks[2],
+                "Review agenda",
+                "Edit the agenda posted on the PR...",
+            );
+        }
+        Some(InputMode::FileNote { filename }) => {
+            render_note_context(frame, chunks[1], filename);
+            render_text_input_area(
+                frame,
+                app,
+                chunks[2],
+                "Note",
+                "Type a note for this file (save empty to remove)...",
+            );
+        }
         None => {}
     }
 }
@@ -1655,6 +2246,96 @@ fn render_reply_context(
     frame.render_widget(paragraph, area);
 }
 
+/// Render the list of threads a batch reply is sent to
+fn render_batch_reply_context(
+    frame: &mut Frame,
+    area: ratatui::layout::Rect,
+    targets: &[BatchReplyTarget],
+    anonymizer: &Anonymizer,
+) {
+    let lines: Vec<Line> = targets
+        .iter()
+        .map(|target| {
+            Line::from(vec![
+                Span::styled(
+                    format!("@{}", anonymizer.user(&target.user)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(" on "),
+                Span::styled(target.location.clone(), Style::default().fg(Color::Green)),
+            ])
+        })
+        .collect();
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(format!("Threads ({})", targets.len())),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+/// Render context info for a reminder on a stale PR
+fn render_nudge_context(
+    frame: &mut Frame,
+    area: ratatui::layout::Rect,
+    pr_number: u32,
+    title: &str,
+    days: i64,
+) {
+    let lines = vec![
+        Line::from(vec![
+            Span::styled(
+                format!("PR #{} ", pr_number),
+                Style::default().fg(Color::Yellow),
+            ),
+            Span::raw(title.to_string()),
+        ]),
+        Line::from(Span::styled(
+            format!("No update for {} days", days),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Posted as a PR conversation comment"),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_agenda_context(frame: &mut Frame, area: ratatui::layout::Rect, pr_number: u32) {
+    let lines = vec![Line::from(vec![
+        Span::styled(
+            format!("PR #{} ", pr_number),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw("Review agenda for a pair-review session"),
+    ])];
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Posted as a PR conversation comment"),
+    );
+    frame.render_widget(paragraph, area);
+}
+
+fn render_note_context(frame: &mut Frame, area: ratatui::layout::Rect, filename: &str) {
+    let lines = vec![Line::from(vec![
+        Span::styled("File: ", Style::default().fg(Color::DarkGray)),
+        Span::styled(filename, Style::default().fg(Color::Cyan)),
+    ])];
+
+    let paragraph = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Saved locally, not posted to GitHub"),
+    );
+    frame.render_widget(paragraph, area);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -2058,6 +2739,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_highlight_cache_chunk_highlights_only_that_chunk() {
+        let body: Vec<String> = (0..LAZY_HIGHLIGHT_MIN_LINES)
+            .map(|i| format!("+let x{i} = {i};"))
+            .collect();
+        let patch = format!(
+            "@@ -0,0 +1,{} @@\n{}",
+            LAZY_HIGHLIGHT_MIN_LINES,
+            body.join("\n")
+        );
+        let mut cache = build_plain_diff_cache(&patch, 4);
+        assert_eq!(
+            cache.highlighted_chunks.len(),
+            (LAZY_HIGHLIGHT_MIN_LINES + 1).div_ceil(LAZY_HIGHLIGHT_CHUNK)
+        );
+        // 小さな patch は対象外
+        assert!(build_plain_diff_cache("@@ -1 +1 @@\n+x", 4)
+            .highlighted_chunks
+            .is_empty());
+
+        let mut parser_pool = ParserPool::new();
+        highlight_cache_chunk(
+            &mut cache,
+            &patch,
+            1,
+            "foo.rs",
+            "base16-ocean.dark",
+            &mut parser_pool,
+            4,
+        );
+
+        assert_eq!(cache.highlighted_chunks[..3], [false, true, false]);
+        assert!(!cache.highlighted);
+        let line = LAZY_HIGHLIGHT_CHUNK + 1;
+        assert!(cache.lines[line].spans.len() > 2);
+        assert_eq!(cache.lines[1].spans.len(), 2);
+        let text: String = cache.lines[line]
+            .spans
+            .iter()
+            .map(|span| cache.resolve(span.content))
+            .collect();
+        assert!(text.ends_with(&format!("let x{0} = {0};", LAZY_HIGHLIGHT_CHUNK)));
+    }
+
     #[test]
     fn render_cached_lines_inserts_comment_markers() {
         let patch = "diff --git a/foo.rs b/foo.rs\n--- a/foo.rs\n+++ b/foo.rs\n@@ -1,3 +1,3 @@\n fn main() {\n-    println!(\"hello\");\n+    println!(\"world\");\n }";
@@ -2087,14 +2812,20 @@ mod tests {
         );
 
         // render_cached_lines でコメントマーカーが挿入されること
-        let plain_rendered =
-            render_cached_lines(&plain, 0..plain.lines.len(), 0, &comment_lines, false, None);
+        let plain_rendered = render_cached_lines(
+            &plain,
+            0..plain.lines.len(),
+            0,
+            &comment_lines,
+            &DiffPalette::default(),
+            None,
+        );
         let hl_rendered = render_cached_lines(
             &highlighted,
             0..highlighted.lines.len(),
             0,
             &comment_lines,
-            false,
+            &DiffPalette::default(),
             None,
         );
 
@@ -2179,6 +2910,27 @@ mod tests {
         assert_eq!(removed.line_type, LineType::Removed);
     }
 
+    #[test]
+    fn test_build_plain_diff_cache_combined_diff_origins() {
+        let patch = "diff --cc foo.rs\n@@@ -1,1 -1,1 +1,2 @@@\n  same\n+ ours\n- gone";
+        let cache = build_plain_diff_cache(patch, 4);
+
+        // 親ごとに 1 カラムずつマーカーを持つ
+        let ours = &cache.lines[3];
+        assert_eq!(ours.line_type, LineType::Added);
+        assert_eq!(ours.spans.len(), 3);
+        assert_eq!(cache.resolve(ours.spans[0].content), "+");
+        assert_eq!(ours.spans[0].style.fg, Some(Color::Green));
+        assert_eq!(cache.resolve(ours.spans[1].content), " ");
+        assert_eq!(ours.spans[1].style.fg, None);
+        assert_eq!(cache.resolve(ours.spans[2].content), "ours");
+
+        let gone = &cache.lines[4];
+        assert_eq!(gone.line_type, LineType::Removed);
+        assert_eq!(gone.spans[0].style.fg, Some(Color::Red));
+        assert_eq!(cache.resolve(gone.spans[2].content), "gone");
+    }
+
     #[test]
     fn test_parse_patch_to_lines_basic() {
         let patch = r#"@@ -1,3 +1,3 @@
@@ -2270,7 +3022,14 @@ mod tests {
         assert_eq!(cache.lines.len(), 4);
 
         // range が完全に範囲外 → 空の Vec
-        let result = render_cached_lines(&cache, 100..200, 0, &HashSet::new(), false, None);
+        let result = render_cached_lines(
+            &cache,
+            100..200,
+            0,
+            &HashSet::new(),
+            &DiffPalette::default(),
+            None,
+        );
         assert!(
             result.is_empty(),
             "Out-of-bounds range should return empty Vec"
@@ -2282,7 +3041,14 @@ mod tests {
         let cache = build_plain_diff_cache("", 4);
         assert!(cache.lines.is_empty());
 
-        let result = render_cached_lines(&cache, 0..10, 0, &HashSet::new(), false, None);
+        let result = render_cached_lines(
+            &cache,
+            0..10,
+            0,
+            &HashSet::new(),
+            &DiffPalette::default(),
+            None,
+        );
         assert!(result.is_empty(), "Empty cache should return empty Vec");
     }
 }
@@ -2922,6 +3688,35 @@ mod priming_diff_tests {
         "#);
     }
 
+    #[test]
+    fn test_markdown_task_list_and_alert_transforms() {
+        use insta::assert_snapshot;
+
+        let patch = r#"@@ -1,4 +1,4 @@
++- [ ] todo
++- [x] done
++> [!WARNING]
+ plain text"#;
+
+        let mut parser_pool = ParserPool::new();
+        let cache = build_diff_cache(
+            patch,
+            "test.md",
+            "base16-ocean.dark",
+            &mut parser_pool,
+            true,
+            4,
+        );
+
+        assert_snapshot!(format_diff_cache_spans(&cache), @r#"
+        L0: "@@ -1,4 +1,4 @@" [fg:Cyan]
+        L1: "+" [fg:Green] | "☐" [default] | " todo" [default]
+        L2: "+" [fg:Green] | "☑" [fg:Green] | " done" [default]
+        L3: "+" [fg:Green] | "> " [fg:DarkGray] | "Warning" [fg:Yellow,BOLD]
+        L4: " " [default] | "plain text" [default]
+        "#);
+    }
+
     #[test]
     fn test_expand_tabs() {
         // No tabs → Cow::Borrowed (zero allocation)
@@ -0,0 +1,50 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use super::markdown;
+use crate::app::{App, FileExplanation};
+
+/// 変更の説明のパネルを開いていれば diff の右に描画し、残りの diff 領域を返す
+pub(crate) fn render_beside(frame: &mut Frame, app: &App, area: Rect) -> Rect {
+    if !app.explain_panel_open {
+        return area;
+    }
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Min(0), Constraint::Percentage(40)])
+        .split(area);
+
+    let width = chunks[1].width.saturating_sub(2) as usize;
+    let lines = match app.current_file_explanation() {
+        Some(FileExplanation::Done(text)) => markdown::render_body(text, width, None),
+        Some(FileExplanation::Pending) => {
+            vec![Line::from(format!("{} Explaining...", app.spinner_char()))]
+        }
+        Some(FileExplanation::Failed(error)) => vec![Line::from(Span::styled(
+            error.clone(),
+            Style::default().fg(Color::Red),
+        ))],
+        None => vec![Line::from(Span::styled(
+            format!(
+                "Press {} to explain this file",
+                app.config.keybindings.explain_file.display()
+            ),
+            Style::default().fg(Color::DarkGray),
+        ))],
+    };
+    let panel = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title("Explanation")
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
+        .wrap(Wrap { trim: false });
+    frame.render_widget(panel, chunks[1]);
+    chunks[0]
+}
@@ -11,7 +11,9 @@ use ratatui::{
 
 use super::common::{build_pr_info, render_rally_status_bar};
 use crate::app::App;
+use crate::file_sort::{self, FileSort};
 use crate::github::ChangedFile;
+use crate::review_progress;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     let has_rally = app.has_background_rally();
@@ -40,13 +42,17 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     // Header
     let pr_info = build_pr_info(app);
 
-    let header =
-        Paragraph::new(pr_info).block(Block::default().borders(Borders::ALL).title("octorus"));
+    let mut header_block = Block::default().borders(Borders::ALL).title("octorus");
+    if let Some(progress) = review_progress_title(app) {
+        header_block = header_block.title_top(progress);
+    }
+    let header = Paragraph::new(pr_info).block(header_block);
     frame.render_widget(header, chunks[0]);
 
     // File list
-    let files = app.files();
-    let total_files = files.len();
+    let total_files = app.files().len();
+    let vendored_note = vendored_title_note(app);
+    let sort_note = format!("{}{}", sort_title_note(app), rally_scope_title_note(app));
 
     // フィルタ適用中はフィルタ済みサブセットを表示
     if let Some(ref filter) = app.file_list_filter {
@@ -58,28 +64,28 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
-                        .title(format!("Changed Files (0/{})", total_files)),
+                        .title(format!("Changed Files (0/{}){}", total_files, sort_note)),
                 );
             frame.render_widget(empty, chunks[1]);
         } else {
-            let filtered: Vec<&ChangedFile> =
-                filter.matched_indices.iter().map(|&i| &files[i]).collect();
             let display_selected = filter.selected.unwrap_or(0);
-            let display_count = filtered.len();
-
-            let items = build_file_list_items_ref(&filtered, display_selected);
+            let display_count = filter.matched_indices.len();
+            let (items, selected_row) = build_sorted_file_list_items(
+                app,
+                &filter.matched_indices,
+                filter.matched_indices.get(display_selected).copied(),
+            );
 
             let list = List::new(items)
-                .block(
-                    Block::default()
-                        .borders(Borders::ALL)
-                        .title(format!("Changed Files ({}/{})", display_count, total_files)),
-                )
+                .block(Block::default().borders(Borders::ALL).title(format!(
+                    "Changed Files ({}/{}){}{}",
+                    display_count, total_files, vendored_note, sort_note
+                )))
                 .highlight_style(Style::default().bg(Color::DarkGray));
 
             let mut list_state = ListState::default()
                 .with_offset(app.file_list_scroll_offset)
-                .with_selected(Some(display_selected));
+                .with_selected(selected_row);
 
             frame.render_stateful_widget(list, chunks[1], &mut list_state);
 
@@ -104,28 +110,24 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             }
         }
     } else {
-        let items = build_file_list_items(files, app.selected_file);
+        let (items, selected_row) =
+            build_sorted_file_list_items(app, app.file_order(), Some(app.selected_file));
+        let display_selected = app.file_display_position(app.selected_file);
 
         let list = List::new(items)
-            .block(
-                Block::default()
-                    .borders(Borders::ALL)
-                    .title(format!("Changed Files ({})", total_files)),
-            )
+            .block(Block::default().borders(Borders::ALL).title(format!(
+                "Changed Files ({}){}{}",
+                total_files, vendored_note, sort_note
+            )))
             .highlight_style(Style::default().bg(Color::DarkGray));
 
         let mut list_state = ListState::default()
             .with_offset(app.file_list_scroll_offset)
-            .with_selected(Some(app.selected_file));
+            .with_selected(selected_row);
 
         frame.render_stateful_widget(list, chunks[1], &mut list_state);
 
-        // Persist both offset and clamped selected index from ListState
-        // (render_stateful_widget may clamp selected if list shrank)
         app.file_list_scroll_offset = list_state.offset();
-        if let Some(sel) = list_state.selected() {
-            app.selected_file = sel;
-        }
 
         // Render scrollbar if there are more files than visible
         if total_files > 1 {
@@ -134,7 +136,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 .end_symbol(Some("▼"));
 
             let mut scrollbar_state =
-                ScrollbarState::new(total_files.saturating_sub(1)).position(app.selected_file);
+                ScrollbarState::new(total_files.saturating_sub(1)).position(display_selected);
 
             frame.render_stateful_widget(
                 scrollbar,
@@ -173,16 +175,16 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     let filter_hint = if app.file_list_filter.is_some() {
         "Esc: clear filter"
     } else {
-        "Space /: filter"
+        "Space /: filter | Space s: sort | Ctrl-f: find"
     };
     let help_text = if app.is_local_mode() {
         format!(
-            "j/k/↑↓: move | Enter/→/l: split view | {} | {} | R: refresh | q: quit | ?: help",
+            "j/k/↑↓: move | Enter/→/l: split view | {} | {} | X: fixup | R: refresh | q: quit | ?: help",
             filter_hint, ai_rally_text
         )
     } else {
         format!(
-            "j/k/↑↓: move | Enter/→/l: split view | {} | v: viewed | V: viewed dir | O: browser | a: approve | r: request changes | c: comment | C: comments | {} | R: refresh | q: quit | ?: help",
+            "j/k/↑↓: move | Enter/→/l: split view | {} | v: viewed | V: viewed dir | O: browser | a: approve | r: request changes | c: comment | C: comments | H: activity | {} | R: refresh | q: quit | ?: help",
             filter_hint, ai_rally_text
         )
     };
@@ -210,6 +212,29 @@ fn render_filter_bar(
     frame.render_widget(filter_bar, area);
 }
 
+/// vendored 依存・ライセンス変更があればタイトルに添える件数
+fn vendored_title_note(app: &App) -> String {
+    let summary = crate::vendored::summarize(app.files());
+    if summary.is_empty() {
+        return String::new();
+    }
+    let mut parts = Vec::new();
+    if !summary.packages.is_empty() {
+        parts.push(format!("{} vendored", summary.packages.len()));
+    }
+    if !summary.license_changes.is_empty() {
+        parts.push(format!(
+            "{} licence change(s)",
+            summary.license_changes.len()
+        ));
+    }
+    format!(
+        " · {} ({})",
+        parts.join(", "),
+        app.config.keybindings.toggle_vendored.display()
+    )
+}
+
 /// Loading状態の表示
 pub fn render_loading(frame: &mut Frame, app: &App) {
     let chunks = Layout::default()
@@ -292,32 +317,116 @@ pub fn render_error(frame: &mut Frame, app: &App, error_msg: &str) {
     frame.render_widget(footer, chunks[2]);
 }
 
-/// ファイル一覧のリストアイテムを構築する（side_by_side でも再利用）
-pub(crate) fn build_file_list_items<'a>(
-    files: &'a [ChangedFile],
-    selected_file: usize,
-) -> Vec<ListItem<'a>> {
-    files
-        .iter()
-        .enumerate()
-        .map(|(i, file)| build_file_list_item(file, i == selected_file))
-        .collect()
+/// 表示順（`order`）に並べたファイル一覧のリストアイテムと、選択中のファイルの行を構築する
+/// （split view でも再利用）。パス順ではトップレベルのディレクトリごとに見出しの行を入れる
+pub(crate) fn build_sorted_file_list_items<'a>(
+    app: &'a App,
+    order: &[usize],
+    selected_file: Option<usize>,
+) -> (Vec<ListItem<'a>>, Option<usize>) {
+    let files = app.files();
+    // すべてルート直下のファイルなら見出しは付けない
+    let group = app.file_sort().groups_by_directory()
+        && files
+            .iter()
+            .any(|file| file_sort::top_level_dir(&file.filename).is_some());
+    let mut items = Vec::with_capacity(order.len());
+    let mut selected_row = None;
+    let mut current_dir = None;
+    let progress = app.review_progress();
+    for &index in order {
+        let Some(file) = files.get(index) else {
+            continue;
+        };
+        if group {
+            let dir = file_sort::top_level_dir(&file.filename);
+            if current_dir != Some(dir) {
+                current_dir = Some(dir);
+                items.push(directory_header(dir));
+            }
+        }
+        let is_selected = selected_file == Some(index);
+        if is_selected {
+            selected_row = Some(items.len());
+        }
+        let reviewed = progress.is_some_and(|p| p.is_reviewed(&file.filename));
+        let note = progress.and_then(|p| p.note(&file.filename));
+        let rally_marked = app.rally_scope_marks.contains(&file.filename);
+        items.push(build_file_list_item(
+            file,
+            is_selected,
+            reviewed,
+            note,
+            rally_marked,
+        ));
+    }
+    (items, selected_row)
+}
+
+/// ディレクトリの見出しの行
+fn directory_header(dir: Option<&str>) -> ListItem<'static> {
+    let label = match dir {
+        Some(dir) => format!("{}/", dir),
+        None => "./".to_string(),
+    };
+    ListItem::new(Line::from(Span::styled(
+        label,
+        Style::default()
+            .fg(Color::Blue)
+            .add_modifier(Modifier::BOLD),
+    )))
+}
+
+/// ヘッダーの右上に出すレビュー進捗（`12/37 files reviewed`）
+fn review_progress_title(app: &App) -> Option<Line<'static>> {
+    let (reviewed, total) = app.review_progress_counts()?;
+    let color = if total > 0 && reviewed == total {
+        Color::Green
+    } else {
+        Color::Cyan
+    };
+    Some(
+        Line::from(format!(
+            " {} ",
+            review_progress::progress_label(reviewed, total, 10)
+        ))
+        .style(Style::default().fg(color))
+        .right_aligned(),
+    )
+}
+
+/// パス順以外で並べているときのタイトルの注記
+pub(crate) fn sort_title_note(app: &App) -> String {
+    match app.file_sort() {
+        FileSort::Path => String::new(),
+        sort => format!(" - sorted by {}", sort.label()),
+    }
 }
 
-/// フィルタ済みファイル一覧のリストアイテムを構築する
-fn build_file_list_items_ref<'a>(files: &[&'a ChangedFile], selected: usize) -> Vec<ListItem<'a>> {
-    files
-        .iter()
-        .enumerate()
-        .map(|(i, file)| build_file_list_item(file, i == selected))
-        .collect()
+/// AI Rally の対象にマークしたファイルがあるときのタイトルの注記
+fn rally_scope_title_note(app: &App) -> String {
+    match app.rally_scope_marks.len() {
+        0 => String::new(),
+        count => format!(" - {} marked for AI Rally", count),
+    }
 }
 
-fn build_file_list_item<'a>(file: &'a ChangedFile, is_selected: bool) -> ListItem<'a> {
+/// `reviewed` はローカルで見終えた印、`note` はファイルのメモ、
+/// `rally_marked` は AI Rally の対象にマークしたファイル
+fn build_file_list_item<'a>(
+    file: &'a ChangedFile,
+    is_selected: bool,
+    reviewed: bool,
+    note: Option<&'a str>,
+    rally_marked: bool,
+) -> ListItem<'a> {
     let style = if is_selected {
         Style::default()
             .fg(Color::Yellow)
             .add_modifier(Modifier::BOLD)
+    } else if crate::vendored::is_vendored(&file.filename) {
+        // vendored 依存は折りたたみ対象なので目立たせない
+        Style::default().fg(Color::DarkGray)
     } else {
         Style::default()
     };
@@ -339,19 +448,53 @@ fn build_file_list_item<'a>(file: &'a ChangedFile, is_selected: bool) -> ListIte
         _ => '?',
     };
 
-    let line = Line::from(vec![
+    let mut spans = vec![
         Span::styled(
             format!("[{}] ", status_char),
             Style::default().fg(status_color),
         ),
         if file.viewed {
             Span::styled("✓ ", Style::default().fg(Color::Green))
+        } else if reviewed {
+            Span::styled("✓ ", Style::default().fg(Color::Cyan))
         } else {
             Span::raw("  ")
         },
         Span::styled(&file.filename, style),
         Span::raw(format!(" +{} -{}", file.additions, file.deletions)),
-    ]);
+    ];
+    if rally_marked {
+        spans.push(Span::styled(
+            "  ◆ rally",
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+    if crate::vendored::is_license_file(&file.filename) {
+        spans.push(Span::styled(
+            "  ⚠ licence",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if file
+        .patch
+        .as_deref()
+        .is_some_and(crate::conflict::patch_has_conflict_markers)
+    {
+        spans.push(Span::styled(
+            "  ⚠ conflict",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+    if let Some(note) = note {
+        spans.push(Span::styled(
+            format!("  ✎ {}", note.lines().next().unwrap_or_default()),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::ITALIC),
+        ));
+    }
 
-    ListItem::new(line)
+    ListItem::new(Line::from(spans))
 }
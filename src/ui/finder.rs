@@ -0,0 +1,76 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{ListItem, Paragraph},
+    Frame,
+};
+
+use super::picker::highlighted_label;
+use super::popup;
+use crate::app::{App, FileFinderState, FinderItem};
+
+fn build_item(app: &App, item: &FinderItem, positions: &[usize]) -> ListItem<'static> {
+    let mut spans = Vec::new();
+    match item.diff_line {
+        None => {
+            spans.push(Span::styled("  ", Style::default()));
+            spans.extend(highlighted_label(&item.label, positions, Style::default()));
+        }
+        Some(_) => {
+            spans.push(Span::styled("ƒ ", Style::default().fg(Color::Magenta)));
+            spans.extend(highlighted_label(
+                &item.label,
+                positions,
+                Style::default().fg(Color::Cyan),
+            ));
+            if let Some(file) = app.files().get(item.file_index) {
+                spans.push(Span::styled(
+                    format!("  {}", file.filename),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+        }
+    }
+    ListItem::new(Line::from(spans))
+}
+
+/// ファジーファインダーを描画
+pub fn render_popup(frame: &mut Frame, app: &App, finder: &FileFinderState, focused: bool) {
+    let popup_area = popup::area(frame, 100, 24);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(popup_area);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(finder.query.clone()),
+        Span::styled("█", Style::default().fg(Color::DarkGray)),
+    ]))
+    .block(popup::block(
+        "Find file / symbol - Enter: open, ↑↓/Ctrl-n/p: move, Esc: close",
+        focused,
+    ));
+    frame.render_widget(input, chunks[0]);
+
+    let items: Vec<ListItem> = finder
+        .matches
+        .iter()
+        .filter_map(|(i, positions)| {
+            finder
+                .items
+                .get(*i)
+                .map(|item| build_item(app, item, positions))
+        })
+        .collect();
+    let title = format!("{}/{}", finder.matches.len(), finder.items.len());
+    popup::render_list(
+        frame,
+        chunks[1],
+        items,
+        finder.selected,
+        popup::block(title, focused),
+    );
+}
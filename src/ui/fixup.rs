@@ -0,0 +1,71 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::ListItem,
+    Frame,
+};
+
+use super::popup;
+use crate::anonymize::Anonymizer;
+use crate::app::FixupPopupState;
+
+/// fixup コミット支援ポップアップを描画
+pub fn render_popup(
+    frame: &mut Frame,
+    state: &FixupPopupState,
+    anonymizer: &Anonymizer,
+    focused: bool,
+) {
+    let popup_area = popup::area(frame, 100, state.groups.len() as u16 * 2 + 4);
+
+    let items: Vec<ListItem> = state
+        .groups
+        .iter()
+        .enumerate()
+        .map(|(i, group)| {
+            let checked = state.checked.get(i).copied().unwrap_or(false);
+            let mark = if checked { "[x] " } else { "[ ] " };
+            let location = match group.comment_line {
+                Some(line) => format!("{}:{}", group.comment_path, line),
+                None => group.comment_path.clone(),
+            };
+            let header_style = if i == state.selected {
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            ListItem::new(vec![
+                Line::from(vec![
+                    Span::styled(mark, Style::default().fg(Color::Green)),
+                    Span::styled(location, header_style),
+                    Span::styled(
+                        format!(
+                            "  @{}  {} hunk(s)",
+                            anonymizer.user(&group.comment_author),
+                            group.hunks.len()
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]),
+                Line::from(Span::raw(format!(
+                    "    {}",
+                    anonymizer.text(&group.excerpt())
+                ))),
+            ])
+        })
+        .collect();
+
+    let title = format!(
+        "Fixup commits onto {} ({} unassigned hunk(s)) - Space: toggle, Enter: commit, r: rebase --autosquash, Esc: close",
+        state.base_branch, state.unassigned
+    );
+    popup::render_list(
+        frame,
+        popup_area,
+        items,
+        state.selected,
+        popup::block(title, focused),
+    );
+}
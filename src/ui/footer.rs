@@ -5,18 +5,26 @@ use ratatui::{
 };
 
 use crate::app::App;
+use crate::github::RateLimit;
 
 /// Build footer line content based on app state.
 ///
 /// During submission or result display, the footer shows only the status
 /// (full-width override). Otherwise, it shows the normal help text with
-/// optional comments loading indicator appended.
+/// optional comments loading indicator and the remaining API quota appended.
 pub fn build_footer_line<'a>(app: &'a App, help_text: &'a str) -> Line<'a> {
     if app.is_pending_approve_confirmation() {
         Line::from(Span::styled(
             app.approve_confirmation_footer_text(),
             Style::default().fg(Color::Yellow),
         ))
+    } else if let Some(prompt) = app.draft_toggle_prompt() {
+        Line::from(Span::styled(prompt, Style::default().fg(Color::Yellow)))
+    } else if let Some(progress) = app.mark_viewed_progress() {
+        Line::from(Span::styled(
+            progress.render(app.config.ui.progress),
+            Style::default().fg(Color::Yellow),
+        ))
     } else if app.is_submitting_comment() {
         Line::from(Span::styled(
             format!("{} Submitting...", app.spinner_char()),
@@ -33,7 +41,15 @@ pub fn build_footer_line<'a>(app: &'a App, help_text: &'a str) -> Line<'a> {
             Style::default().fg(color),
         ))
     } else {
-        let mut spans = vec![Span::raw(help_text)];
+        let mut spans = Vec::new();
+        let drafts = app.review_drafts().len();
+        if app.review_draft_mode || drafts > 0 {
+            spans.push(Span::styled(
+                format!("[Draft: {}] ", drafts),
+                Style::default().fg(Color::Magenta),
+            ));
+        }
+        spans.push(Span::raw(help_text));
         if app.comments_loading {
             spans.push(Span::raw("  "));
             spans.push(Span::styled(
@@ -41,16 +57,60 @@ pub fn build_footer_line<'a>(app: &'a App, help_text: &'a str) -> Line<'a> {
                 Style::default().fg(Color::Yellow),
             ));
         }
+        for progress in app.background_progress() {
+            spans.push(Span::raw("  "));
+            spans.push(Span::styled(
+                progress.render(app.config.ui.progress),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        if let Some(limit) = &app.rate_limit {
+            spans.push(Span::raw("  "));
+            spans.push(rate_limit_span(limit, chrono::Utc::now().timestamp()));
+        }
         Line::from(spans)
     }
 }
 
+/// Remaining API quota: dim while plenty is left, yellow when low, red once exhausted.
+fn rate_limit_span(limit: &RateLimit, now: i64) -> Span<'static> {
+    let resets_in = format_wait(limit.until_reset(u64::try_from(now).unwrap_or(0)));
+    if limit.is_exhausted() {
+        Span::styled(
+            format!("API rate limit reached (resets in {})", resets_in),
+            Style::default().fg(Color::Red),
+        )
+    } else if limit.is_low() {
+        Span::styled(
+            format!(
+                "API {}/{} (resets in {})",
+                limit.remaining, limit.limit, resets_in
+            ),
+            Style::default().fg(Color::Yellow),
+        )
+    } else {
+        Span::styled(
+            format!("API {}/{}", limit.remaining, limit.limit),
+            Style::default().fg(Color::DarkGray),
+        )
+    }
+}
+
+fn format_wait(wait: std::time::Duration) -> String {
+    let secs = wait.as_secs();
+    if secs < 60 {
+        format!("{}s", secs)
+    } else {
+        format!("{}m", secs.div_ceil(60))
+    }
+}
+
 pub fn build_footer_block(app: &App) -> Block<'static> {
     build_footer_block_with_border(app, Style::default())
 }
 
 pub fn build_footer_block_with_border(app: &App, base_style: Style) -> Block<'static> {
-    let style = if app.is_pending_approve_confirmation() {
+    let style = if app.is_pending_approve_confirmation() || app.is_pending_draft_toggle() {
         Style::default().fg(Color::Yellow)
     } else {
         base_style
@@ -89,6 +149,65 @@ mod tests {
         assert_eq!(line.spans.len(), 3); // help + "  " + loading
     }
 
+    #[test]
+    fn test_background_progress_appended_after_help() {
+        let mut app = App::new_for_test();
+        app.config.ui.progress = crate::config::ProgressStyle::Percent;
+        let mut progress = crate::progress::Progress::new("Highlighting", 4);
+        progress.advance(1);
+        app.set_prefetch_progress_for_test(progress);
+
+        let line = build_footer_line(&app, HELP);
+        let text = line_to_string(&line);
+        assert!(text.starts_with(HELP));
+        assert!(text.contains("Highlighting 1/4 (25%)"));
+        assert_eq!(line.spans.len(), 3);
+    }
+
+    #[test]
+    fn test_rate_limit_appended_and_colored_by_remaining() {
+        let mut app = App::new_for_test();
+        let reset = chrono::Utc::now().timestamp() as u64 + 600;
+        let mut limit = RateLimit {
+            resource: "core".to_string(),
+            limit: 5000,
+            remaining: 4321,
+            reset,
+        };
+        app.rate_limit = Some(limit.clone());
+        let line = build_footer_line(&app, HELP);
+        assert_eq!(line_to_string(&line), format!("{}  API 4321/5000", HELP));
+        assert_eq!(line.spans[2].style.fg, Some(Color::DarkGray));
+
+        limit.remaining = 100;
+        app.rate_limit = Some(limit.clone());
+        let line = build_footer_line(&app, HELP);
+        assert!(line_to_string(&line).ends_with("API 100/5000 (resets in 10m)"));
+        assert_eq!(line.spans[2].style.fg, Some(Color::Yellow));
+
+        limit.remaining = 0;
+        app.rate_limit = Some(limit);
+        let line = build_footer_line(&app, HELP);
+        assert!(line_to_string(&line).ends_with("API rate limit reached (resets in 10m)"));
+        assert_eq!(line.spans[2].style.fg, Some(Color::Red));
+    }
+
+    #[test]
+    fn test_format_wait() {
+        assert_eq!(format_wait(std::time::Duration::from_secs(30)), "30s");
+        assert_eq!(format_wait(std::time::Duration::from_secs(61)), "2m");
+    }
+
+    #[test]
+    fn test_review_draft_mode_prefixes_pending_count() {
+        let mut app = App::new_for_test();
+        app.review_draft_mode = true;
+        let line = build_footer_line(&app, HELP);
+        let text = line_to_string(&line);
+        assert_eq!(text, format!("[Draft: 0] {}", HELP));
+        assert_eq!(line.spans.len(), 2);
+    }
+
     #[test]
     fn test_submitting_shows_status_only() {
         let mut app = App::new_for_test();
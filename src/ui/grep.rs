@@ -0,0 +1,109 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{ListItem, Paragraph},
+    Frame,
+};
+
+use super::popup;
+use crate::app::{App, GrepState};
+use crate::diff::LineType;
+use crate::diff_search::GrepMatch;
+
+fn build_item(app: &App, result: &GrepMatch) -> ListItem<'static> {
+    let filename = app
+        .files()
+        .get(result.hit.file_index)
+        .map(|f| f.filename.as_str())
+        .unwrap_or_default();
+    let location = match result.line_number {
+        Some(n) => format!("{}:{}", filename, n),
+        None => filename.to_string(),
+    };
+    let (marker, marker_color) = match result.line_type {
+        LineType::Added => ("+", Color::Green),
+        LineType::Removed => ("-", Color::Red),
+        _ => (" ", Color::DarkGray),
+    };
+    let mut spans = vec![
+        Span::styled(location, Style::default().fg(Color::Cyan)),
+        Span::raw(" "),
+        Span::styled(marker, Style::default().fg(marker_color)),
+        Span::raw(" "),
+    ];
+    let match_style = Style::default().fg(Color::Black).bg(Color::Yellow);
+    let mut offset = 0;
+    for &(start, end) in &result.ranges {
+        if start > offset {
+            spans.push(Span::raw(result.snippet[offset..start].to_string()));
+        }
+        spans.push(Span::styled(
+            result.snippet[start..end].to_string(),
+            match_style,
+        ));
+        offset = end;
+    }
+    spans.push(Span::raw(result.snippet[offset..].to_string()));
+    ListItem::new(Line::from(spans))
+}
+
+/// 全ファイルの grep を描画
+pub fn render_popup(frame: &mut Frame, app: &App, grep: &GrepState, focused: bool) {
+    let popup_area = popup::area(frame, 120, 30);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(popup_area);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(grep.input.clone()),
+        Span::styled("█", Style::default().fg(Color::DarkGray)),
+    ]))
+    .block(popup::block(
+        "Search all files - Enter: open, ↑↓/Ctrl-n/p: move, Esc: close",
+        focused,
+    ));
+    frame.render_widget(input, chunks[0]);
+
+    let items: Vec<ListItem> = grep
+        .results
+        .iter()
+        .map(|result| build_item(app, result))
+        .collect();
+    let mut title = if grep.input.is_empty() {
+        "Type to search the diffs of all changed files".to_string()
+    } else if grep.is_searching() {
+        format!(
+            "{} Searching {}/{} files - {} matches",
+            app.spinner_char(),
+            grep.scanned,
+            grep.total,
+            grep.results.len()
+        )
+    } else if grep.truncated {
+        format!(
+            "{}+ matches in {} files (refine the query)",
+            grep.results.len(),
+            grep.file_count()
+        )
+    } else {
+        format!(
+            "{} matches in {} files",
+            grep.results.len(),
+            grep.file_count()
+        )
+    };
+    if grep.skipped > 0 {
+        title.push_str(&format!(", {} files not loaded yet", grep.skipped));
+    }
+    popup::render_list(
+        frame,
+        chunks[1],
+        items,
+        grep.selected,
+        popup::block(title, focused),
+    );
+}
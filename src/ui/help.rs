@@ -8,7 +8,8 @@ use ratatui::{
 
 use crate::ai::{PromptLoader, PromptSource};
 use crate::app::{App, HelpTab};
-use crate::config::{Config, KeybindingsConfig};
+use crate::cheatsheet;
+use crate::config::{BackgroundMode, Config, KeybindingsConfig, TimeStyle};
 use crate::syntax::available_themes;
 
 /// Format a key display with padding for alignment
@@ -88,8 +89,11 @@ fn render_tab_header(frame: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_keybindings_tab(frame: &mut Frame, app: &mut App, area: Rect) {
-    let kb = &app.config.keybindings;
-    let help_lines = build_help_lines(kb);
+    let query = app
+        .help_filter
+        .as_ref()
+        .map_or("", |filter| filter.query.as_str());
+    let help_lines = build_help_lines(&app.config.keybindings, query);
     let total_lines = help_lines.len();
     let content_height = area.height.saturating_sub(2) as usize;
 
@@ -104,11 +108,17 @@ fn render_keybindings_tab(frame: &mut Frame, app: &mut App, area: Rect) {
         String::new()
     };
 
+    let search_info = match &app.help_filter {
+        Some(filter) if filter.input_active => format!(" /{}│", filter.query),
+        Some(filter) => format!(" /{}", filter.query),
+        None => String::new(),
+    };
+
     let help = Paragraph::new(help_lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(format!("Keybindings{}", scroll_info)),
+                .title(format!("Keybindings{}{}", search_info, scroll_info)),
         )
         .scroll((app.help_scroll_offset as u16, 0));
     frame.render_widget(help, area);
@@ -174,11 +184,26 @@ fn render_config_tab(frame: &mut Frame, app: &mut App, area: Rect) {
 
 fn render_help_footer(frame: &mut Frame, app: &App, area: Rect) {
     let kb = &app.config.keybindings;
-    let footer_text = format!(
-        " {}/{}: close | [/]: switch tab | j/k: scroll | g/G: top/bottom",
-        kb.quit.display(),
-        kb.help.display()
-    );
+    let footer_text = match &app.help_filter {
+        Some(filter) if filter.input_active => {
+            " Type to search keys and actions | Enter: apply | Esc: clear".to_string()
+        }
+        Some(_) if app.help_tab == HelpTab::Keybindings => format!(
+            " Esc: clear search | {}: edit search | j/k: scroll | g/G: top/bottom",
+            kb.search.display()
+        ),
+        _ if app.help_tab == HelpTab::Keybindings => format!(
+            " {}/{}: close | {}: search | [/]: switch tab | j/k: scroll | g/G: top/bottom",
+            kb.quit.display(),
+            kb.help.display(),
+            kb.search.display()
+        ),
+        _ => format!(
+            " {}/{}: close | [/]: switch tab | j/k: scroll | g/G: top/bottom",
+            kb.quit.display(),
+            kb.help.display()
+        ),
+    };
     let footer = Paragraph::new(Line::from(Span::styled(
         footer_text,
         Style::default().fg(Color::DarkGray),
@@ -234,6 +259,10 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
         .unwrap_or("(default)")
         .to_string();
 
+    let guidelines_display = PromptLoader::guidelines_path(&config.ai, &config.project_root)
+        .map(|path| path.display().to_string())
+        .unwrap_or_else(|| "(none)".to_string());
+
     // Resolve prompt sources
     let prompt_loader = PromptLoader::new(&config.ai, &config.project_root);
     let prompt_sources = prompt_loader.resolve_all_sources();
@@ -269,8 +298,20 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
                 .add_modifier(Modifier::BOLD),
         )]),
         config_value_line("Theme", &config.diff.theme, "diff.theme", overrides),
+        config_value_line("Light theme", &config.diff.light_theme, "diff.light_theme", overrides),
+        config_value_line(
+            "Background",
+            match config.ui.background {
+                BackgroundMode::Auto => "auto",
+                BackgroundMode::Dark => "dark",
+                BackgroundMode::Light => "light",
+            },
+            "ui.background",
+            overrides,
+        ),
         config_value_line("Tab width", &config.diff.tab_width.to_string(), "diff.tab_width", overrides),
         config_value_line("Background color", &config.diff.bg_color.to_string(), "diff.bg_color", overrides),
+        config_value_line("Remember position", &config.diff.remember_position.to_string(), "diff.remember_position", overrides),
         Line::from(""),
         Line::from(vec![Span::styled(
             "Editor",
@@ -279,6 +320,110 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
                 .add_modifier(Modifier::BOLD),
         )]),
         config_value_line("Editor", &editor_display, "editor", overrides),
+        config_value_line(
+            "Follow path",
+            config.follow.path.as_deref().unwrap_or("(disabled)"),
+            "follow.path",
+            overrides,
+        ),
+        config_value_line(
+            "Translate to",
+            config.translate.language.as_deref().unwrap_or("(disabled)"),
+            "translate.language",
+            overrides,
+        ),
+        config_value_line(
+            "Timestamps",
+            &format!(
+                "{} ({}, {})",
+                match config.time.style {
+                    TimeStyle::Relative => "relative",
+                    TimeStyle::Absolute => "absolute",
+                },
+                config.time.format,
+                config.time.timezone
+            ),
+            "time.style",
+            overrides,
+        ),
+        config_value_line(
+            "Path scope",
+            &if config.scope.paths.is_empty() {
+                "(all files)".to_string()
+            } else {
+                config.scope.paths.join(", ")
+            },
+            "scope.paths",
+            overrides,
+        ),
+        config_value_line(
+            "Workspace repos",
+            &if config.workspace.repos.is_empty() {
+                "(none)".to_string()
+            } else {
+                config.workspace.repos.join(", ")
+            },
+            "workspace.repos",
+            overrides,
+        ),
+        config_value_line(
+            "Workspace dirs",
+            &if config.workspace.dirs.is_empty() {
+                "(none)".to_string()
+            } else {
+                config.workspace.dirs.join(", ")
+            },
+            "workspace.dirs",
+            overrides,
+        ),
+        config_value_line(
+            "Review templates",
+            &if config.review_templates.is_empty() {
+                "(none)".to_string()
+            } else {
+                format!("{} rule(s)", config.review_templates.len())
+            },
+            "review_templates",
+            overrides,
+        ),
+        config_value_line(
+            "Watch PR",
+            &if config.watch.enabled {
+                format!("every {}s", config.watch.interval_secs)
+            } else {
+                "(disabled)".to_string()
+            },
+            "watch.enabled",
+            overrides,
+        ),
+        config_value_line(
+            "Learning mode",
+            if config.learning.enabled {
+                "enabled"
+            } else {
+                "(disabled)"
+            },
+            "learning.enabled",
+            overrides,
+        ),
+        config_value_line(
+            "Nudge",
+            &format!("after {} days", config.nudge.stale_days),
+            "nudge.stale_days",
+            overrides,
+        ),
+        config_value_line(
+            "GitHub token",
+            &match config.github.credential {
+                crate::config::CredentialSource::Env => "environment".to_string(),
+                crate::config::CredentialSource::Keychain => format!(
+                    "keychain ({} / {})",
+                    config.github.keychain_service, config.github.keychain_account
+                ),
+            },
+            "github.credential",
+            overrides,
+        ),
         Line::from(""),
         Line::from(vec![Span::styled(
             "AI Rally Settings",
@@ -288,10 +433,16 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
         )]),
         config_value_line("Reviewer", &config.ai.reviewer, "ai.reviewer", overrides),
         config_value_line("Reviewee", &config.ai.reviewee, "ai.reviewee", overrides),
+        config_value_line("Co-reviewer", config.ai.co_reviewer.as_deref().unwrap_or("(none)"), "ai.co_reviewer", overrides),
         config_value_line("Max iterations", &config.ai.max_iterations.to_string(), "ai.max_iterations", overrides),
         config_value_line("Timeout (secs)", &config.ai.timeout_secs.to_string(), "ai.timeout_secs", overrides),
         config_value_line("Auto post", &config.ai.auto_post.to_string(), "ai.auto_post", overrides),
+        config_value_line("Dry run", &config.ai.dry_run.to_string(), "ai.dry_run", overrides),
+        config_value_line("Context budget", &config.ai.context_budget_tokens.to_string(), "ai.context_budget_tokens", overrides),
+        config_value_line("Budget tokens", &config.ai.budget_tokens.to_string(), "ai.budget_tokens", overrides),
+        config_value_line("Budget USD", &config.ai.budget_usd.to_string(), "ai.budget_usd", overrides),
         config_value_line("Prompt dir", &prompt_dir_display, "ai.prompt_dir", overrides),
+        config_value_line("Guidelines", &guidelines_display, "ai.guidelines_file", overrides),
     ];
 
     // Reviewer additional tools (always show so local overrides to empty are visible)
@@ -347,372 +498,60 @@ pub fn build_config_lines(config: &Config) -> Vec<Line<'static>> {
     lines
 }
 
-fn build_help_lines(kb: &KeybindingsConfig) -> Vec<Line<'static>> {
+/// Build the Keybindings tab from the live keybinding config (user remaps and
+/// per-view overrides included), keeping only the rows that match `query`.
+fn build_help_lines(kb: &KeybindingsConfig, query: &str) -> Vec<Line<'static>> {
     let key_width = 14; // Width for key column
+    let heading_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let dim_style = Style::default().fg(Color::DarkGray);
+
+    let groups = cheatsheet::filter(cheatsheet::build(kb), query);
+    let mut lines = Vec::new();
+    let mut current_title = None;
+    for group in &groups {
+        if current_title != Some(group.title) {
+            current_title = Some(group.title);
+            lines.push(Line::from(""));
+            lines.push(Line::from(Span::styled(group.title, heading_style)));
+        }
+        if let Some(state) = group.state {
+            lines.push(Line::from(Span::styled(format!("  {}:", state), dim_style)));
+        }
+        for entry in &group.entries {
+            lines.push(Line::from(format!(
+                "{}  {}",
+                fmt_key(&entry.keys, key_width),
+                entry.description
+            )));
+        }
+    }
 
-    vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "File List View",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(format!(
-            "{}  Move selection",
-            fmt_key(
-                &format!(
-                    "{}/{}, Down/Up",
-                    kb.move_down.display(),
-                    kb.move_up.display()
-                ),
-                key_width
-            )
-        )),
-        Line::from(format!(
-            "{}  Open split view",
-            fmt_key(&kb.open_panel.display(), key_width)
-        )),
-        Line::from("  v               Mark selected file as viewed"),
-        Line::from("  V               Mark selected directory as viewed"),
-        Line::from(format!(
-            "{}  Approve PR",
-            fmt_key(&kb.approve.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Request changes",
-            fmt_key(&kb.request_changes.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Comment only",
-            fmt_key(&kb.comment.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  View review comments",
-            fmt_key(&kb.comment_list.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Start AI Rally",
-            fmt_key(&kb.ai_rally.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Open PR in browser",
-            fmt_key(&kb.open_in_browser.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Refresh (clear cache and reload)",
-            fmt_key(&kb.refresh.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Toggle help",
-            fmt_key(&kb.help.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Toggle local diff mode",
-            fmt_key(&kb.toggle_local_mode.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Toggle auto-focus (local mode)",
-            fmt_key(&kb.toggle_auto_focus.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Filter list",
-            fmt_key(&kb.filter.display(), key_width)
-        )),
-        Line::from(format!("{}  Quit", fmt_key(&kb.quit.display(), key_width))),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Split View",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![Span::styled(
-            "  File List Focus:",
-            Style::default().fg(Color::DarkGray),
-        )]),
-        Line::from(format!(
-            "{}  Move file selection (diff follows)",
-            fmt_key(
-                &format!(
-                    "{}/{}, Down/Up",
-                    kb.move_down.display(),
-                    kb.move_up.display()
-                ),
-                key_width
-            )
-        )),
-        Line::from(format!(
-            "{}  Filter list",
-            fmt_key(&kb.filter.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}, Right, {}     Focus diff pane",
-            fmt_key(&kb.open_panel.display(), 5),
-            kb.move_right.display()
-        )),
-        Line::from(format!(
-            "  Left, {}, {}    Back to file list",
-            kb.move_left.display(),
-            kb.quit.display()
-        )),
-        Line::from(vec![Span::styled(
-            "  Diff Focus:",
-            Style::default().fg(Color::DarkGray),
-        )]),
-        Line::from(format!(
-            "{}  Scroll diff",
-            fmt_key(
-                &format!(
-                    "{}/{}, Down/Up",
-                    kb.move_down.display(),
-                    kb.move_up.display()
-                ),
-                key_width
-            )
-        )),
-        Line::from(format!(
-            "{}  Page scroll (also J/K)",
-            fmt_key(
-                &format!("{}/{}", kb.page_down.display(), kb.page_up.display()),
-                key_width
-            )
-        )),
-        Line::from(format!(
-            "{}  Go to definition",
-            fmt_key(&kb.go_to_definition.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Open file in $EDITOR",
-            fmt_key(&kb.go_to_file.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}/{}  Jump to first/last line",
-            fmt_key(&kb.jump_to_first.display(), 10),
-            kb.jump_to_last.display()
-        )),
-        Line::from(format!(
-            "{}  Jump back",
-            fmt_key(&kb.jump_back.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}/{}  Next/prev comment",
-            fmt_key(&kb.next_comment.display(), 10),
-            kb.prev_comment.display()
-        )),
-        Line::from(format!(
-            "{}  Open comment panel",
-            fmt_key(&kb.open_panel.display(), key_width)
-        )),
-        Line::from(format!(
-            "  Right, {}       Open fullscreen diff",
-            kb.move_right.display()
-        )),
-        Line::from(format!(
-            "  Left, {}        Back to file focus",
-            kb.move_left.display()
-        )),
-        Line::from(format!(
-            "{}  Back to file list",
-            fmt_key(&kb.quit.display(), key_width)
-        )),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Diff View",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(format!(
-            "{}  Move line selection",
-            fmt_key(
-                &format!(
-                    "{}/{}, Down/Up",
-                    kb.move_down.display(),
-                    kb.move_up.display()
-                ),
-                key_width
-            )
-        )),
-        Line::from(format!(
-            "{}  Go to definition",
-            fmt_key(&kb.go_to_definition.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Open file in $EDITOR",
-            fmt_key(&kb.go_to_file.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}/{}  Jump to first/last line",
-            fmt_key(&kb.jump_to_first.display(), 10),
-            kb.jump_to_last.display()
-        )),
-        Line::from(format!(
-            "{}  Jump back",
-            fmt_key(&kb.jump_back.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Jump to next comment",
-            fmt_key(&kb.next_comment.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Jump to previous comment",
-            fmt_key(&kb.prev_comment.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Open comment panel",
-            fmt_key(&kb.open_panel.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Page down (also J)",
-            fmt_key(&kb.page_down.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Page up (also K)",
-            fmt_key(&kb.page_up.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Add comment at line",
-            fmt_key(&kb.comment.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Add suggestion at line",
-            fmt_key(&kb.suggestion.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Multiline select mode",
-            fmt_key(
-                &format!("{}/Shift+Enter", kb.multiline_select.display()),
-                key_width,
-            )
-        )),
-        Line::from(vec![Span::styled(
-            "  Multiline Select Mode:",
-            Style::default().fg(Color::DarkGray),
-        )]),
-        Line::from(format!(
-            "{}/{}  Extend selection",
-            fmt_key(&kb.move_down.display(), 10),
-            kb.move_up.display()
-        )),
-        Line::from(format!(
-            "{}  Comment on selection",
-            fmt_key(&kb.comment.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Suggest on selection",
-            fmt_key(&kb.suggestion.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Cancel selection",
-            fmt_key("Esc", key_width)
-        )),
-        Line::from(format!(
-            "{}  Toggle markdown rich display",
-            fmt_key(&kb.toggle_markdown_rich.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Back to file list",
-            fmt_key(&format!("{}, Esc", kb.quit.display()), key_width)
-        )),
-        Line::from(vec![Span::styled(
-            "  Comment Panel (focused):",
-            Style::default().fg(Color::DarkGray),
-        )]),
-        Line::from(format!(
-            "{}/{}  Scroll panel",
-            fmt_key(&kb.move_down.display(), 10),
-            kb.move_up.display()
-        )),
-        Line::from(format!(
-            "{}  Add comment",
-            fmt_key(&kb.comment.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Add suggestion",
-            fmt_key(&kb.suggestion.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Reply to comment",
-            fmt_key(&kb.reply.display(), key_width)
-        )),
-        Line::from("  Tab/Shift-Tab   Select reply target (multiple)"),
-        Line::from(format!(
-            "{}/{}  Jump to next/prev comment",
-            fmt_key(&kb.next_comment.display(), 10),
-            kb.prev_comment.display()
-        )),
-        Line::from(format!("  Esc/{}        Close panel", kb.quit.display())),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Comment List View",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from("  [, ]            Switch tab (Review/Discussion)"),
-        Line::from(format!(
-            "{}  Move selection",
-            fmt_key(
-                &format!(
-                    "{}/{}, Down/Up",
-                    kb.move_down.display(),
-                    kb.move_up.display()
-                ),
-                key_width
-            )
-        )),
-        Line::from(format!(
-            "{}  Review: Jump to file | Discussion: View detail",
-            fmt_key(&kb.open_panel.display(), key_width)
-        )),
-        Line::from(format!(
-            "{}  Back to file list",
-            fmt_key(&format!("{}, Esc", kb.quit.display()), key_width)
-        )),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Input Mode (Comment/Suggestion/Reply)",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(format!(
-            "{}  Submit",
-            fmt_key(&kb.submit.display(), key_width)
-        )),
-        Line::from("  Esc             Cancel input"),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "AI Rally View",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![Span::styled(
-            "  (When AI requests permission or clarification)",
-            Style::default().fg(Color::DarkGray),
-        )]),
-        Line::from("  y               Grant permission / Answer yes"),
-        Line::from("  n               Deny permission / Skip"),
-        Line::from(format!(
-            "{}  Abort rally",
-            fmt_key(&kb.quit.display(), key_width)
-        )),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Available Themes",
-            Style::default()
-                .fg(Color::Yellow)
-                .add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(format!("  {}", available_themes().join(", "))),
-        Line::from(vec![Span::styled(
+    if groups.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  No keybindings match \"{}\"", query),
+            dim_style,
+        )));
+    }
+
+    if query.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled("Available Themes", heading_style)));
+        lines.push(Line::from(format!("  {}", available_themes().join(", "))));
+        lines.push(Line::from(Span::styled(
             "  Set in ~/.config/octorus/config.toml: [diff] theme = \"Dracula\"",
-            Style::default().fg(Color::DarkGray),
-        )]),
-        Line::from(""),
-    ]
+            dim_style,
+        )));
+        lines.push(Line::from(Span::styled(
+            "  or a .tmTheme / Helix .toml file: [diff] theme = \"~/themes/nord.tmTheme\"",
+            dim_style,
+        )));
+    }
+
+    lines.push(Line::from(""));
+    lines
 }
 
 #[cfg(test)]
@@ -755,4 +594,40 @@ mod tests {
             "Should contain prompt filename"
         );
     }
+
+    fn help_text(kb: &KeybindingsConfig, query: &str) -> String {
+        build_help_lines(kb, query)
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn test_build_help_lines_reflects_remaps() {
+        let kb = KeybindingsConfig {
+            edit_labels: crate::keybinding::KeySequence::double(
+                crate::keybinding::KeyBinding::char(' '),
+                crate::keybinding::KeyBinding::char('l'),
+            ),
+            ..Default::default()
+        };
+        let joined = help_text(&kb, "");
+        assert!(joined.contains("File List View"));
+        assert!(joined.contains("Available Themes"));
+        assert!(joined.contains("  Spacel          Edit labels"));
+        assert!(!joined.contains("  gt "));
+    }
+
+    #[test]
+    fn test_build_help_lines_filters_by_query() {
+        let kb = KeybindingsConfig::default();
+        let joined = help_text(&kb, "label");
+        assert!(joined.contains("Edit labels"));
+        assert!(!joined.contains("Approve PR"));
+        assert!(!joined.contains("Available Themes"));
+
+        let joined = help_text(&kb, "no such binding");
+        assert!(joined.contains("No keybindings match \"no such binding\""));
+    }
 }
@@ -0,0 +1,146 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::anonymize::Anonymizer;
+use crate::app::App;
+use crate::github::Notification;
+use crate::text_width;
+use crate::time_format::TimeFormatter;
+
+const HELP_TEXT: &str =
+    "j/k: move | Enter: review | m: mark read | Tab: all PR notifications | r: refresh | O: browser | q/Esc: back";
+
+/// 自分宛ての PR の通知の受信箱
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // List
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let Some(ref inbox) = app.inbox else {
+        return;
+    };
+
+    let scope = if inbox.show_all {
+        "PR notifications"
+    } else {
+        "Review requests"
+    };
+    let header = Paragraph::new(format!("Inbox: {}", scope))
+        .block(Block::default().borders(Borders::ALL).title("octorus"));
+    frame.render_widget(header, chunks[0]);
+
+    let visible = inbox.visible();
+    let unread = visible.iter().filter(|n| n.unread).count();
+    let mut title = format!("Notifications ({}, {} unread)", visible.len(), unread);
+    if inbox.error.is_some() {
+        title.push_str(" - refresh failed");
+    }
+    match (&inbox.notifications, &inbox.error) {
+        (None, Some(error)) => {
+            let message = Paragraph::new(Span::styled(
+                format!("Failed to load notifications: {}", error),
+                Style::default().fg(Color::Red),
+            ))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Notifications"),
+            );
+            frame.render_widget(message, chunks[1]);
+        }
+        (None, None) => {
+            let loading = Paragraph::new(Span::styled(
+                format!("{} Loading notifications...", app.spinner_char()),
+                Style::default().fg(Color::Yellow),
+            ))
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title("Notifications"),
+            );
+            frame.render_widget(loading, chunks[1]);
+        }
+        (Some(_), _) if visible.is_empty() => {
+            let empty = if inbox.show_all {
+                "No unread PR notifications"
+            } else {
+                "No review requests. Press Tab to show all PR notifications"
+            };
+            let message = Paragraph::new(Span::styled(empty, Style::default().fg(Color::DarkGray)))
+                .block(Block::default().borders(Borders::ALL).title(title));
+            frame.render_widget(message, chunks[1]);
+        }
+        (Some(_), _) => {
+            let items: Vec<ListItem> = visible
+                .iter()
+                .map(|n| build_item(n, &app.time_format, &app.anonymizer))
+                .collect();
+            let mut list_state = ListState::default().with_selected(Some(inbox.selected));
+            let widget = List::new(items)
+                .block(Block::default().borders(Borders::ALL).title(title))
+                .highlight_style(Style::default().bg(Color::DarkGray));
+            frame.render_stateful_widget(widget, chunks[1], &mut list_state);
+        }
+    }
+
+    let footer_line = super::footer::build_footer_line(app, HELP_TEXT);
+    let footer = Paragraph::new(footer_line).block(super::footer::build_footer_block(app));
+    frame.render_widget(footer, chunks[2]);
+}
+
+/// 通知の理由を読みやすくする（`review_requested` → `review requested`）
+fn reason_label(reason: &str) -> String {
+    reason.replace('_', " ")
+}
+
+fn build_item(
+    notification: &Notification,
+    time: &TimeFormatter,
+    anonymizer: &Anonymizer,
+) -> ListItem<'static> {
+    let (mark, title_style) = if notification.unread {
+        ("●", Style::default().add_modifier(Modifier::BOLD))
+    } else {
+        (" ", Style::default().fg(Color::DarkGray))
+    };
+    let reason_color = if notification.is_review_request() {
+        Color::Yellow
+    } else {
+        Color::Cyan
+    };
+    let location = format!(
+        "{}#{}",
+        anonymizer.repo(&notification.repo),
+        notification.pr_number
+    );
+    ListItem::new(Line::from(vec![
+        Span::styled(format!(" {} ", mark), Style::default().fg(Color::Blue)),
+        Span::styled(
+            text_width::pad_to_width(&text_width::truncate(&location, 32), 32),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::raw("  "),
+        Span::styled(
+            text_width::pad_to_width(&text_width::truncate(&notification.title, 60), 60),
+            title_style,
+        ),
+        Span::styled(
+            format!("  {:<16}", reason_label(&notification.reason)),
+            Style::default().fg(reason_color),
+        ),
+        Span::styled(
+            time.format(&notification.updated_at),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]))
+}
@@ -0,0 +1,22 @@
+use ratatui::Frame;
+
+use super::picker;
+use crate::app::{App, LabelPickerState};
+
+/// ラベル編集のポップアップ
+pub fn render_picker(frame: &mut Frame, app: &App, state: &LabelPickerState, focused: bool) {
+    let title = if state.applying {
+        format!("{} Applying label changes...", app.spinner_char())
+    } else if let Some(ref error) = state.error {
+        // 付いているラベルは外せるので、一覧の読み込みに失敗しても閉じない
+        format!("Labels - failed to load repository labels: {}", error)
+    } else {
+        "Labels - Tab: add/remove, Enter: apply, Esc: cancel".to_string()
+    };
+    let placeholder = match (&state.labels, &state.error) {
+        (_, Some(_)) => "No labels".to_string(),
+        (None, None) => format!("{} Loading labels...", app.spinner_char()),
+        (Some(_), None) => "This repository has no labels".to_string(),
+    };
+    picker::render_popup(frame, &state.picker, &title, &placeholder, focused);
+}
@@ -0,0 +1,80 @@
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    Frame,
+};
+use unicode_width::UnicodeWidthStr;
+
+use super::popup;
+use crate::app::App;
+
+/// 学習モードのキーバインド表示（画面右下に一時的に表示）
+pub fn render_hint(frame: &mut Frame, text: &str) {
+    let area = frame.area();
+    let width = (text.width() as u16 + 4).min(area.width);
+    let height = 3;
+    // フッターに重ならないよう 1 行上げる
+    let hint_area = Rect::new(
+        area.x + area.width.saturating_sub(width + 1),
+        area.y + area.height.saturating_sub(height + 4),
+        width,
+        height.min(area.height),
+    );
+
+    frame.render_widget(Clear, hint_area);
+    let hint = Paragraph::new(Line::from(Span::styled(
+        text.to_string(),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    )))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Yellow)),
+    );
+    frame.render_widget(hint, hint_area);
+}
+
+/// キー使用統計パネルを描画
+pub fn render_stats(frame: &mut Frame, app: &App, focused: bool) {
+    let panel_area = popup::area(frame, 60, 24);
+    let block = popup::block(
+        format!("Key usage ({} presses) - Esc: close", app.key_usage.total()),
+        focused,
+    );
+
+    let ranked = app.key_usage.ranked();
+    if ranked.is_empty() {
+        let empty = List::new(vec![ListItem::new(Span::styled(
+            "No keys recorded yet",
+            Style::default().fg(Color::DarkGray),
+        ))])
+        .block(block);
+        frame.render_widget(empty, panel_area);
+        return;
+    }
+
+    let items: Vec<ListItem> = ranked
+        .into_iter()
+        .map(|(key, count, home_row)| {
+            let mut spans = vec![
+                Span::styled(
+                    format!("{:>6}  ", count),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::raw(key.to_string()),
+            ];
+            if let Some(binding) = home_row {
+                spans.push(Span::styled(
+                    format!("  try {}", binding),
+                    Style::default().fg(Color::Yellow),
+                ));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
+    frame.render_widget(List::new(items).block(block), panel_area);
+}
@@ -0,0 +1,102 @@
+use ratatui::{
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{ListItem, Paragraph, Wrap},
+    Frame,
+};
+
+use super::popup;
+use crate::app::{App, LineHistoryState};
+use crate::line_history::LineChange;
+use crate::text_width;
+
+/// 選択した行を変更したコミットの一覧
+pub fn render_popup(frame: &mut Frame, app: &App, state: &LineHistoryState, focused: bool) {
+    let popup_area = popup::area(frame, 110, 24);
+    let range = if state.start == state.end {
+        format!("L{}", state.start)
+    } else {
+        format!("L{}-{}", state.start, state.end)
+    };
+    let scope = match state.history {
+        Some(ref history) if !history.line_level => " (file history)",
+        _ => "",
+    };
+    let title = format!(
+        "History of {}:{}{} - Enter: view commit, Esc: close",
+        state.filename, range, scope
+    );
+    let block = popup::block(title, focused);
+
+    let Some(ref history) = state.history else {
+        let message = match state.error {
+            Some(ref error) => Span::styled(error.clone(), Style::default().fg(Color::Red)),
+            None => Span::raw(format!("{} Loading...", app.spinner_char())),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(message))
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            popup_area,
+        );
+        return;
+    };
+    if history.changes.is_empty() {
+        frame.render_widget(
+            Paragraph::new(Span::styled(
+                "No commits found",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .block(block),
+            popup_area,
+        );
+        return;
+    }
+
+    let items: Vec<ListItem> = history
+        .changes
+        .iter()
+        .map(|change| build_item(app, state, change))
+        .collect();
+    popup::render_list(frame, popup_area, items, state.selected, block);
+}
+
+fn build_item<'a>(app: &App, state: &LineHistoryState, change: &'a LineChange) -> ListItem<'a> {
+    let commit = &change.commit;
+    let mut spans = vec![
+        Span::styled(
+            format!("{} ", commit.short_sha()),
+            Style::default().fg(Color::Yellow),
+        ),
+        Span::styled(
+            if commit.is_merge { "[merge] " } else { "" },
+            Style::default().fg(Color::Magenta),
+        ),
+        Span::raw(text_width::pad_to_width(
+            &text_width::truncate(&commit.headline, 50),
+            50,
+        )),
+        Span::styled(
+            format!("  {}", app.anonymizer.user(&commit.author)),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(
+            format!("  {}", app.time_format.format(&commit.date)),
+            Style::default().fg(Color::DarkGray),
+        ),
+    ];
+    // リネームを辿った先のパス
+    if change.path != state.filename {
+        spans.push(Span::styled(
+            format!("  {}", change.path),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    if state.loading_sha.as_deref() == Some(commit.sha.as_str()) {
+        spans.push(Span::styled(
+            format!("  {} loading...", app.spinner_char()),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+    ListItem::new(Line::from(spans))
+}
@@ -0,0 +1,129 @@
+use chrono::{DateTime, Utc};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+use crate::app::{App, LocalDataState};
+use crate::lfs::format_size;
+use crate::local_store::Artifact;
+use crate::text_width;
+use crate::time_format::TimeFormatter;
+
+const HELP_TEXT: &str =
+    "j/k: move | Space: mark | d: archive | u: restore | x: delete archived | p: prune closed PRs | q: back";
+
+/// ローカルに保存したデータの一覧（種類・名前・PR の状態・経過時間・容量）
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // List
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let Some(ref view) = app.local_data else {
+        return;
+    };
+
+    let (active, archived): (Vec<&Artifact>, Vec<&Artifact>) =
+        view.artifacts.iter().partition(|a| !a.archived);
+    let total = |items: &[&Artifact]| items.iter().map(|a| a.size).sum::<u64>();
+    let header = Paragraph::new(format!(
+        "{} ({} items, {}) | Archive ({} items, {})",
+        view.root.display(),
+        active.len(),
+        format_size(total(&active)),
+        archived.len(),
+        format_size(total(&archived)),
+    ))
+    .block(Block::default().borders(Borders::ALL).title("Local Data"));
+    frame.render_widget(header, chunks[0]);
+
+    let title = if view.states_loading {
+        format!("Items {} checking PR states...", app.spinner_char())
+    } else {
+        "Items".to_string()
+    };
+    if view.artifacts.is_empty() {
+        let empty = Paragraph::new("No local data")
+            .style(Style::default().fg(Color::DarkGray))
+            .block(Block::default().borders(Borders::ALL).title(title));
+        frame.render_widget(empty, chunks[1]);
+    } else {
+        let items: Vec<ListItem> = view
+            .artifacts
+            .iter()
+            .enumerate()
+            .map(|(i, artifact)| build_item(view, i, artifact, &app.time_format))
+            .collect();
+        let mut list_state = ListState::default().with_selected(Some(view.selected));
+        let list = List::new(items)
+            .block(Block::default().borders(Borders::ALL).title(title))
+            .highlight_style(Style::default().bg(Color::DarkGray));
+        frame.render_stateful_widget(list, chunks[1], &mut list_state);
+    }
+
+    let footer_line = super::footer::build_footer_line(app, HELP_TEXT);
+    let footer = Paragraph::new(footer_line).block(super::footer::build_footer_block(app));
+    frame.render_widget(footer, chunks[2]);
+}
+
+fn build_item(
+    view: &LocalDataState,
+    index: usize,
+    artifact: &Artifact,
+    time: &TimeFormatter,
+) -> ListItem<'static> {
+    let mark = if view.marked.contains(&index) {
+        "● "
+    } else {
+        "  "
+    };
+    let (state, state_color) = match view.is_closed(artifact) {
+        Some(true) => ("closed", Color::Magenta),
+        Some(false) => ("open", Color::Green),
+        None if artifact.pr.is_some() => ("?", Color::DarkGray),
+        None => ("", Color::DarkGray),
+    };
+    let age = artifact
+        .modified
+        .map(|modified| time.format(&DateTime::<Utc>::from(modified).to_rfc3339()))
+        .unwrap_or_default();
+    let name_style = if artifact.archived {
+        Style::default().fg(Color::DarkGray)
+    } else {
+        Style::default().add_modifier(Modifier::BOLD)
+    };
+
+    ListItem::new(Line::from(vec![
+        Span::styled(mark, Style::default().fg(Color::Yellow)),
+        Span::styled(
+            format!("{:<14}", artifact.kind.label()),
+            Style::default().fg(Color::Cyan),
+        ),
+        Span::styled(
+            text_width::pad_to_width(&text_width::truncate(&artifact.name, 40), 40),
+            name_style,
+        ),
+        Span::styled(format!(" {:<7}", state), Style::default().fg(state_color)),
+        Span::styled(
+            format!("{:>10}", format_size(artifact.size)),
+            Style::default(),
+        ),
+        Span::styled(format!("  {}", age), Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            if artifact.archived {
+                "  [archived]"
+            } else {
+                ""
+            },
+            Style::default().fg(Color::DarkGray),
+        ),
+    ]))
+}
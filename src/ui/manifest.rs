@@ -0,0 +1,145 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use crate::advisory::Advisory;
+use crate::app::App;
+use crate::manifest_diff::{ChangeKind, DependencyChange, ManifestSummary};
+
+/// 依存マニフェストなら diff の上に要約を描画し、残りの diff 領域を返す
+pub(crate) fn render_summary_above(frame: &mut Frame, app: &App, area: Rect) -> Rect {
+    let rows = app.manifest_summary_rows(area.height);
+    let Some(summary) = app.manifest_summary().filter(|_| rows > 0) else {
+        return area;
+    };
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(rows), Constraint::Min(0)])
+        .split(area);
+
+    let lines = build_summary_lines(summary, |change| app.dependency_advisories(change));
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Dependencies")
+            .border_style(Style::default().fg(Color::DarkGray)),
+    );
+    frame.render_widget(panel, chunks[0]);
+    chunks[1]
+}
+
+fn build_summary_lines<'a>(
+    summary: &ManifestSummary,
+    advisories: impl Fn(&DependencyChange) -> Vec<&'a Advisory>,
+) -> Vec<Line<'static>> {
+    let mut header = format!(
+        "{} added, {} removed, {} upgraded",
+        summary.count(ChangeKind::Added),
+        summary.count(ChangeKind::Removed),
+        summary.count(ChangeKind::Upgraded)
+    );
+    let downgraded = summary.count(ChangeKind::Downgraded);
+    if downgraded > 0 {
+        header.push_str(&format!(", {} downgraded", downgraded));
+    }
+    let mut lines = vec![Line::from(Span::styled(
+        header,
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ))];
+
+    for change in &summary.changes {
+        let old = change.old_version.as_deref().unwrap_or("?");
+        let new = change.new_version.as_deref().unwrap_or("?");
+        let (marker, color, versions) = match change.kind {
+            ChangeKind::Added => ("+", Color::Green, new.to_string()),
+            ChangeKind::Removed => ("-", Color::Red, old.to_string()),
+            ChangeKind::Upgraded => ("↑", Color::Cyan, format!("{} → {}", old, new)),
+            ChangeKind::Downgraded => ("↓", Color::Magenta, format!("{} → {}", old, new)),
+        };
+        let mut spans = vec![
+            Span::styled(format!(" {} ", marker), Style::default().fg(color)),
+            Span::raw(change.name.clone()),
+            Span::styled(
+                format!(" {}", versions),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        for advisory in advisories(change) {
+            spans.push(Span::styled(
+                format!("  ⚠ {} {}", advisory.id, advisory.summary),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::advisory::AdvisoryDb;
+    use crate::manifest_diff::Ecosystem;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    fn change(
+        name: &str,
+        kind: ChangeKind,
+        old: Option<&str>,
+        new: Option<&str>,
+    ) -> DependencyChange {
+        DependencyChange {
+            name: name.to_string(),
+            kind,
+            old_version: old.map(str::to_string),
+            new_version: new.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_summary_lines_flag_advisories() {
+        let mut db = AdvisoryDb::default();
+        db.add_json(
+            r#"{"id": "RUSTSEC-2024-0001", "summary": "Use-after-free",
+                "affected": [{"package": {"ecosystem": "crates.io", "name": "foo"},
+                              "versions": ["1.0.0"]}]}"#,
+        );
+        let summary = ManifestSummary {
+            ecosystem: Ecosystem::Cargo,
+            changes: vec![
+                change("foo", ChangeKind::Added, None, Some("1.0.0")),
+                change("bar", ChangeKind::Removed, Some("0.3.1"), None),
+                change("baz", ChangeKind::Upgraded, Some("1.0.0"), Some("1.1.0")),
+                change("qux", ChangeKind::Downgraded, Some("2.0.0"), Some("1.9.0")),
+            ],
+        };
+        let text: Vec<String> = build_summary_lines(&summary, |c| {
+            c.new_version
+                .as_deref()
+                .map(|v| db.lookup(summary.ecosystem, &c.name, v))
+                .unwrap_or_default()
+        })
+        .iter()
+        .map(line_text)
+        .collect();
+        assert_eq!(
+            text,
+            vec![
+                "1 added, 1 removed, 1 upgraded, 1 downgraded",
+                " + foo 1.0.0  ⚠ RUSTSEC-2024-0001 Use-after-free",
+                " - bar 0.3.1",
+                " ↑ baz 1.0.0 → 1.1.0",
+                " ↓ qux 2.0.0 → 1.9.0",
+            ]
+        );
+    }
+}
@@ -0,0 +1,444 @@
+//! コメント本文の Markdown 表示
+//!
+//! PR の会話やレビューコメントでよく使われる GitHub 拡張のうち、
+//! 生のままだと読みにくいものだけを整形する。
+//!
+//! - アラート（`> [!NOTE]` など）→ 色付きの見出しと縦線
+//! - タスクリスト（`- [ ]` / `- [x]`）→ `☐` / `☑`
+//! - テーブル → 列幅を揃えた罫線表
+//!
+//! コードブロック内はそのまま表示する。折り返し後の行数は
+//! コメントパネルのスクロール計算にも使うため、描画と計算は必ず
+//! [`render_body`] を通す。
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+
+use crate::text_width;
+
+/// GitHub のアラート種別
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AlertKind {
+    Note,
+    Tip,
+    Important,
+    Warning,
+    Caution,
+}
+
+impl AlertKind {
+    /// `[!NOTE]` 形式のマーカーを解釈する（大文字小文字は区別しない）
+    pub fn parse(marker: &str) -> Option<Self> {
+        let name = marker.strip_prefix("[!")?.strip_suffix(']')?;
+        match name.to_ascii_uppercase().as_str() {
+            "NOTE" => Some(Self::Note),
+            "TIP" => Some(Self::Tip),
+            "IMPORTANT" => Some(Self::Important),
+            "WARNING" => Some(Self::Warning),
+            "CAUTION" => Some(Self::Caution),
+            _ => None,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Note => "Note",
+            Self::Tip => "Tip",
+            Self::Important => "Important",
+            Self::Warning => "Warning",
+            Self::Caution => "Caution",
+        }
+    }
+
+    pub fn color(self) -> Color {
+        match self {
+            Self::Note => Color::Blue,
+            Self::Tip => Color::Green,
+            Self::Important => Color::Magenta,
+            Self::Warning => Color::Yellow,
+            Self::Caution => Color::Red,
+        }
+    }
+}
+
+/// タスクリストの 1 項目
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct TaskLine<'a> {
+    indent: &'a str,
+    checked: bool,
+    text: &'a str,
+    /// `[ ]` の中の文字のバイト位置（行頭から）
+    mark_offset: usize,
+}
+
+/// `- [ ] text` 形式の行を解釈する
+fn parse_task(line: &str) -> Option<TaskLine<'_>> {
+    let trimmed = line.trim_start();
+    let indent = &line[..line.len() - trimmed.len()];
+    let rest = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("* "))
+        .or_else(|| trimmed.strip_prefix("+ "))?;
+    let checked = match rest.get(..3)? {
+        "[ ]" => false,
+        "[x]" | "[X]" => true,
+        _ => return None,
+    };
+    let text = &rest[3..];
+    if !(text.is_empty() || text.starts_with(' ')) {
+        return None;
+    }
+    Some(TaskLine {
+        indent,
+        checked,
+        text: text.trim_start(),
+        mark_offset: indent.len() + 2 + 1,
+    })
+}
+
+/// コードフェンス（``` / ~~~）の開始・終了行か
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// 本文中のタスク項目の数（コードブロック内は除く）
+pub fn task_count(body: &str) -> usize {
+    let mut in_fence = false;
+    body.lines()
+        .filter(|line| {
+            if is_fence(line) {
+                in_fence = !in_fence;
+                return false;
+            }
+            !in_fence && parse_task(line).is_some()
+        })
+        .count()
+}
+
+/// `index` 番目のタスク項目のチェックを反転した本文を返す。
+/// 改行コード（`\r\n` を含む）は元のまま保つ
+pub fn toggle_task(body: &str, index: usize) -> Option<(String, bool)> {
+    let mut in_fence = false;
+    let mut seen = 0;
+    let mut offset = 0;
+    for raw in body.split_inclusive('\n') {
+        let line = raw.trim_end_matches(['\n', '\r']);
+        if is_fence(line) {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            if let Some(task) = parse_task(line) {
+                if seen == index {
+                    let at = offset + task.mark_offset;
+                    let mark = if task.checked { " " } else { "x" };
+                    let mut toggled = body.to_string();
+                    toggled.replace_range(at..at + 1, mark);
+                    return Some((toggled, !task.checked));
+                }
+                seen += 1;
+            }
+        }
+        offset += raw.len();
+    }
+    None
+}
+
+/// 折り返し前の 1 行分（先頭の装飾 + 本文）
+struct Block {
+    prefix: Vec<Span<'static>>,
+    /// 折り返した 2 行目以降の先頭
+    continuation: Vec<Span<'static>>,
+    text: String,
+    style: Style,
+}
+
+impl Block {
+    fn plain(text: &str, style: Style) -> Self {
+        Self {
+            prefix: vec![],
+            continuation: vec![],
+            text: text.to_string(),
+            style,
+        }
+    }
+}
+
+/// コメント本文を表示幅 `width` で折り返した行に変換する。
+/// `selected_task` を指定するとそのタスク項目のチェックボックスを強調する
+pub fn render_body(body: &str, width: usize, selected_task: Option<usize>) -> Vec<Line<'static>> {
+    let source: Vec<&str> = body.lines().collect();
+    let mut blocks: Vec<Block> = Vec::with_capacity(source.len());
+    let mut in_fence = false;
+    let mut alert: Option<AlertKind> = None;
+    let mut task_index = 0;
+    let mut i = 0;
+
+    while i < source.len() {
+        let line = source[i];
+
+        if is_fence(line) {
+            in_fence = !in_fence;
+            alert = None;
+            blocks.push(Block::plain(line, Style::default().fg(Color::DarkGray)));
+            i += 1;
+            continue;
+        }
+        if in_fence {
+            blocks.push(Block::plain(line, Style::default()));
+            i += 1;
+            continue;
+        }
+
+        // アラート：`> [!NOTE]` から始まる引用ブロック
+        if let Some(quoted) = line.trim_start().strip_prefix('>') {
+            let quoted = quoted.strip_prefix(' ').unwrap_or(quoted);
+            if let Some(kind) = AlertKind::parse(quoted.trim()) {
+                alert = Some(kind);
+                let bar = Span::styled("▎ ", Style::default().fg(kind.color()));
+                blocks.push(Block {
+                    prefix: vec![bar.clone()],
+                    continuation: vec![bar],
+                    text: kind.label().to_string(),
+                    style: Style::default()
+                        .fg(kind.color())
+                        .add_modifier(Modifier::BOLD),
+                });
+                i += 1;
+                continue;
+            }
+            if let Some(kind) = alert {
+                let bar = Span::styled("▎ ", Style::default().fg(kind.color()));
+                blocks.push(Block {
+                    prefix: vec![bar.clone()],
+                    continuation: vec![bar],
+                    text: quoted.to_string(),
+                    style: Style::default(),
+                });
+                i += 1;
+                continue;
+            }
+        } else {
+            alert = None;
+        }
+
+        if let Some(task) = parse_task(line) {
+            let selected = selected_task == Some(task_index);
+            task_index += 1;
+            let (mark, color) = if task.checked {
+                ("☑", Color::Green)
+            } else {
+                ("☐", Color::DarkGray)
+            };
+            let mut mark_style = Style::default().fg(color);
+            if selected {
+                mark_style = mark_style
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::REVERSED);
+            }
+            let indent = " ".repeat(text_width::display_width(task.indent) + 2);
+            blocks.push(Block {
+                prefix: vec![
+                    Span::raw(task.indent.to_string()),
+                    Span::styled(mark, mark_style),
+                    Span::raw(" "),
+                ],
+                continuation: vec![Span::raw(indent)],
+                text: task.text.to_string(),
+                style: if task.checked {
+                    Style::default().fg(Color::DarkGray)
+                } else {
+                    Style::default()
+                },
+            });
+            i += 1;
+            continue;
+        }
+
+        // テーブル：2 行目が区切り行の `|` 行の連続
+        if line.trim_start().starts_with('|') {
+            let end = source[i..]
+                .iter()
+                .position(|l| !l.trim_start().starts_with('|'))
+                .map_or(source.len(), |n| i + n);
+            if end - i >= 2 && is_table_separator(source[i + 1]) {
+                blocks.extend(table_blocks(&source[i..end]));
+                i = end;
+                continue;
+            }
+        }
+
+        blocks.push(Block::plain(line, Style::default()));
+        i += 1;
+    }
+
+    let mut lines = Vec::new();
+    for block in blocks {
+        let prefix_width: usize = block
+            .prefix
+            .iter()
+            .map(|s| text_width::display_width(&s.content))
+            .sum();
+        // 幅 0（未レイアウト）のときは折り返さない
+        let wrap_width = if width == 0 {
+            0
+        } else {
+            width.saturating_sub(prefix_width).max(1)
+        };
+        let wrapped = text_width::wrap(&block.text, wrap_width);
+        for (n, chunk) in wrapped.into_iter().enumerate() {
+            let mut spans = if n == 0 {
+                block.prefix.clone()
+            } else {
+                block.continuation.clone()
+            };
+            spans.push(Span::styled(chunk, block.style));
+            lines.push(Line::from(spans));
+        }
+    }
+    if lines.is_empty() {
+        lines.push(Line::from(""));
+    }
+    lines
+}
+
+fn is_table_separator(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.contains('-')
+        && trimmed
+            .chars()
+            .all(|c| c == '|' || c == '-' || c == ':' || c == ' ')
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    let trimmed = line.trim();
+    let inner = trimmed.strip_prefix('|').unwrap_or(trimmed);
+    let inner = inner.strip_suffix('|').unwrap_or(inner);
+    inner.split('|').map(|c| c.trim().to_string()).collect()
+}
+
+/// テーブルを列幅を揃えた罫線表にする（1 行目は見出し、2 行目は区切り行）
+fn table_blocks(rows: &[&str]) -> Vec<Block> {
+    let cells: Vec<Vec<String>> = rows.iter().map(|row| table_cells(row)).collect();
+    let columns = cells.iter().map(Vec::len).max().unwrap_or(0);
+    let widths: Vec<usize> = (0..columns)
+        .map(|col| {
+            cells
+                .iter()
+                .enumerate()
+                .filter(|(n, _)| *n != 1)
+                .filter_map(|(_, row)| row.get(col))
+                .map(|cell| text_width::display_width(cell))
+                .max()
+                .unwrap_or(0)
+        })
+        .collect();
+
+    let border = Style::default().fg(Color::DarkGray);
+    cells
+        .iter()
+        .enumerate()
+        .map(|(n, row)| {
+            if n == 1 {
+                let parts: Vec<String> = widths.iter().map(|w| "─".repeat(w + 2)).collect();
+                return Block::plain(&format!("├{}┤", parts.join("┼")), border);
+            }
+            let parts: Vec<String> = widths
+                .iter()
+                .enumerate()
+                .map(|(col, w)| {
+                    let cell = row.get(col).map(String::as_str).unwrap_or("");
+                    format!(" {} ", text_width::pad_to_width(cell, *w))
+                })
+                .collect();
+            let style = if n == 0 {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else {
+                Style::default()
+            };
+            Block::plain(&format!("│{}│", parts.join("│")), style)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(lines: &[Line]) -> Vec<String> {
+        lines
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect()
+    }
+
+    #[test]
+    fn test_alert_block_rendered_with_label_and_bar() {
+        let body = "> [!WARNING]\n> Breaks the public API\n\nafter";
+        let lines = render_body(body, 40, None);
+        assert_eq!(
+            texts(&lines),
+            vec!["▎ Warning", "▎ Breaks the public API", "", "after"]
+        );
+        assert_eq!(lines[0].spans[1].style.fg, Some(Color::Yellow));
+        // ただの引用はそのまま
+        assert_eq!(texts(&render_body("> quoted", 40, None)), vec!["> quoted"]);
+        assert_eq!(AlertKind::parse("[!tip]"), Some(AlertKind::Tip));
+        assert_eq!(AlertKind::parse("[!OTHER]"), None);
+    }
+
+    #[test]
+    fn test_task_list_rendered_as_checkboxes() {
+        let body = "- [ ] write docs\n  - [x] add tests\n- [link](url)";
+        let lines = render_body(body, 40, Some(1));
+        assert_eq!(
+            texts(&lines),
+            vec!["☐ write docs", "  ☑ add tests", "- [link](url)"]
+        );
+        assert!(lines[1].spans[1]
+            .style
+            .add_modifier
+            .contains(Modifier::REVERSED));
+    }
+
+    #[test]
+    fn test_table_columns_aligned() {
+        let body = "| Name | Value |\n| --- | --- |\n| foo | 12345 |";
+        assert_eq!(
+            texts(&render_body(body, 80, None)),
+            vec!["│ Name │ Value │", "├──────┼───────┤", "│ foo  │ 12345 │"]
+        );
+    }
+
+    #[test]
+    fn test_code_fence_left_untouched() {
+        let body = "```\n- [ ] not a task\n```\n- [ ] task";
+        assert_eq!(task_count(body), 1);
+        assert_eq!(
+            texts(&render_body(body, 40, None)),
+            vec!["```", "- [ ] not a task", "```", "☐ task"]
+        );
+    }
+
+    #[test]
+    fn test_wrapped_task_keeps_indent() {
+        let lines = render_body("- [ ] abcdefgh", 6, None);
+        assert_eq!(texts(&lines), vec!["☐ abcd", "  efgh"]);
+    }
+
+    #[test]
+    fn test_toggle_task_preserves_other_lines() {
+        let body = "intro\r\n- [ ] one\r\n```\n- [ ] code\n```\n* [X] two\n";
+        let (toggled, checked) = toggle_task(body, 0).unwrap();
+        assert!(checked);
+        assert_eq!(
+            toggled,
+            "intro\r\n- [x] one\r\n```\n- [ ] code\n```\n* [X] two\n"
+        );
+        let (toggled, checked) = toggle_task(body, 1).unwrap();
+        assert!(!checked);
+        assert!(toggled.ends_with("* [ ] two\n"));
+        assert!(toggle_task(body, 2).is_none());
+    }
+}
@@ -1,13 +1,43 @@
+mod activity;
+mod agenda;
 mod ai_rally;
+mod checks;
+mod checkout;
 mod comment_list;
+mod commit_list;
 mod common;
+mod compare;
+mod conflict;
 pub mod diff_view;
+mod explain;
 mod file_list;
+mod finder;
+mod fixup;
 mod footer;
+mod grep;
 mod help;
+mod inbox;
+mod labels;
+mod learning;
+mod line_history;
+mod local_data;
+mod manifest;
+pub mod markdown;
+mod outbox;
+pub mod palette;
+pub mod picker;
+pub mod popup;
 mod pr_list;
+mod pr_overview;
+mod pr_summary;
+mod quit;
+mod user_info;
+mod review_drafts;
+mod reviewers;
 mod split_view;
 pub mod text_area;
+mod vendored;
+mod wrap_up;
 
 use anyhow::Result;
 use crossterm::{
@@ -17,16 +47,14 @@ use crossterm::{
 };
 use ratatui::{
     backend::CrosstermBackend,
-    layout::Rect,
-    style::{Color, Modifier, Style},
-    text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem},
+    layout::{Rect, Size},
+    widgets::ListItem,
     Frame, Terminal,
 };
 use std::io::{self, Stdout};
 use std::sync::atomic::{AtomicBool, Ordering};
 
-use crate::app::{App, AppState, DataState};
+use crate::app::{App, AppState, DataState, PopupId};
 
 static KITTY_ENABLED: AtomicBool = AtomicBool::new(false);
 
@@ -60,6 +88,48 @@ pub fn restore_terminal(terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Re
     Ok(())
 }
 
+/// 入力処理から使う端末。外部エディタなどを開くあいだは TUI を一時停止する。
+/// テストでは描画先を `TestBackend` にした端末を渡す
+pub trait TuiTerminal {
+    fn size(&self) -> Result<Size>;
+
+    /// 端末を外部のプロセスに明け渡す
+    fn suspend(&mut self) -> Result<()>;
+
+    /// 一時停止から TUI に戻る
+    fn resume(&mut self) -> Result<()>;
+}
+
+impl TuiTerminal for Terminal<CrosstermBackend<Stdout>> {
+    fn size(&self) -> Result<Size> {
+        Ok(Terminal::size(self)?)
+    }
+
+    fn suspend(&mut self) -> Result<()> {
+        restore_terminal(self)
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        *self = setup_terminal()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+impl TuiTerminal for Terminal<ratatui::backend::TestBackend> {
+    fn size(&self) -> Result<Size> {
+        Ok(Terminal::size(self)?)
+    }
+
+    fn suspend(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
 /// Pop Kitty keyboard enhancement flags if previously pushed.
 /// Uses CAS to prevent double-pop. Safe to call multiple times.
 pub fn cleanup_keyboard_enhancement() {
@@ -72,15 +142,20 @@ pub fn cleanup_keyboard_enhancement() {
 }
 
 pub fn render(frame: &mut Frame, app: &mut App) {
-    // PR一覧画面・ヘルプ画面はデータ状態に依存しないためスキップ
-    if app.state != AppState::PullRequestList && app.state != AppState::Help {
+    // PR一覧画面・ヘルプ画面・受信箱はデータ状態に依存しないためスキップ
+    if !matches!(
+        app.state,
+        AppState::PullRequestList | AppState::Help | AppState::Inbox
+    ) {
         // Loading状態の場合は専用画面を表示
         if matches!(app.data_state, DataState::Loading) {
             file_list::render_loading(frame, app);
+            render_popups(frame, app);
             return;
         }
         if let DataState::Error(ref msg) = app.data_state {
             file_list::render_error(frame, app, msg);
+            render_popups(frame, app);
             return;
         }
     }
@@ -94,11 +169,131 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         AppState::Help => help::render(frame, app),
         AppState::AiRally => ai_rally::render(frame, app),
         AppState::SplitViewFileList | AppState::SplitViewDiff => split_view::render(frame, app),
+        AppState::LocalCompare => compare::render(frame, app),
+        AppState::LocalData => local_data::render(frame, app),
+        AppState::CommitList => commit_list::render(frame, app),
+        AppState::Checks => checks::render(frame, app),
+        AppState::ConflictResolve => conflict::render(frame, app),
+        AppState::PrOverview => pr_overview::render(frame, app),
+        AppState::PrSummary => pr_summary::render(frame, app),
+        AppState::Inbox => inbox::render(frame, app),
+    }
+
+    render_popups(frame, app);
+
+    // 学習モード: フォールバックキーに対応するキーバインド表示
+    if !app.key_stats_open {
+        if let Some(hint) = app.key_hint_text() {
+            learning::render_hint(frame, hint);
+        }
+    }
+}
+
+/// ポップアップを開いた順に重ね、キー入力を受け取る最前面のものだけ枠を強調する
+fn render_popups(frame: &mut Frame, app: &App) {
+    let popups = app.open_popups();
+    for (i, &id) in popups.iter().enumerate() {
+        render_popup(frame, app, id, i + 1 == popups.len());
     }
+}
 
-    // シンボル選択ポップアップ（最前面に描画）
-    if let Some(ref popup) = app.symbol_popup {
-        render_symbol_popup(frame, popup);
+/// ポップアップを 1 つ描画する
+fn render_popup(frame: &mut Frame, app: &App, id: PopupId, focused: bool) {
+    match id {
+        PopupId::Symbol => {
+            if let Some(ref popup) = app.symbol_popup {
+                render_symbol_popup(frame, popup, focused);
+            }
+        }
+        PopupId::Fixup => {
+            if let Some(ref state) = app.fixup_popup {
+                fixup::render_popup(frame, state, &app.anonymizer, focused);
+            }
+        }
+        PopupId::SessionActivity => {
+            if let Some(ref panel) = app.session_activity {
+                activity::render_panel(frame, panel, &app.time_format, focused);
+            }
+        }
+        PopupId::Finder => {
+            if let Some(ref finder) = app.file_finder {
+                finder::render_popup(frame, app, finder, focused);
+            }
+        }
+        PopupId::VendoredSummary => vendored::render_summary(frame, app, focused),
+        PopupId::ReviewDrafts => review_drafts::render_list(frame, app, focused),
+        PopupId::ReviewAgenda => {
+            if let Some(ref panel) = app.review_agenda {
+                agenda::render_panel(frame, app, panel, focused);
+            }
+        }
+        PopupId::People => {
+            if let Some(ref state) = app.people_picker {
+                reviewers::render_picker(frame, app, state, focused);
+            }
+        }
+        PopupId::Labels => {
+            if let Some(ref state) = app.label_picker {
+                labels::render_picker(frame, app, state, focused);
+            }
+        }
+        PopupId::KeyStats => learning::render_stats(frame, app, focused),
+        PopupId::Repos => {
+            if let Some(ref switcher) = app.repo_switcher {
+                picker::render_popup(
+                    frame,
+                    switcher,
+                    "Switch repository - Enter: open PR list, Esc: cancel",
+                    "No repositories",
+                    focused,
+                );
+            }
+        }
+        PopupId::Grep => {
+            if let Some(ref state) = app.grep {
+                grep::render_popup(frame, app, state, focused);
+            }
+        }
+        PopupId::QuitSummary => {
+            if let Some(ref summary) = app.quit_summary {
+                quit::render_popup(frame, app, summary, focused);
+            }
+        }
+        PopupId::UserInfo => {
+            if let Some(ref state) = app.user_info {
+                user_info::render_popup(frame, app, state, focused);
+            }
+        }
+        PopupId::Checkout => {
+            if let Some(ref state) = app.checkout {
+                checkout::render_popup(frame, app, state, focused);
+            }
+        }
+        PopupId::ReviewSummary => {
+            if let Some(ref summary) = app.review_summary {
+                wrap_up::render_popup(frame, summary, &app.anonymizer, focused);
+            }
+        }
+        PopupId::Outbox => outbox::render_list(frame, app, focused),
+        PopupId::CreatePr => {
+            if let Some(ref state) = app.pr_create {
+                picker::render_popup(
+                    frame,
+                    &state.picker,
+                    &format!(
+                        "Create a PR from {} - Enter: choose base and write, Esc: cancel",
+                        state.branch.name
+                    ),
+                    "No branches",
+                    focused,
+                );
+            }
+        }
+        PopupId::LineHistory => {
+            if let Some(ref state) = app.line_history {
+                line_history::render_popup(frame, app, state, focused);
+            }
+        }
     }
 }
 
@@ -110,9 +305,7 @@ fn centered_rect(width: u16, height: u16, area: Rect) -> Rect {
 }
 
 /// シンボル選択ポップアップを描画
-fn render_symbol_popup(frame: &mut Frame, popup: &crate::app::SymbolPopupState) {
-    let area = frame.area();
-
+fn render_symbol_popup(frame: &mut Frame, popup: &crate::app::SymbolPopupState, focused: bool) {
     // ポップアップサイズ計算
     let max_width = popup
         .symbols
@@ -121,38 +314,17 @@ fn render_symbol_popup(frame: &mut Frame, popup: &crate::app::SymbolPopupState)
         .max()
         .unwrap_or(10) as u16
         + 6; // padding + borders
-    let height = (popup.symbols.len() as u16 + 2).min(area.height.saturating_sub(4)); // +2 for borders
-    let width = max_width.max(20).min(area.width.saturating_sub(4));
-
-    let popup_area = centered_rect(width, height, area);
-
-    // 背景クリア
-    frame.render_widget(Clear, popup_area);
+    let height = popup.symbols.len() as u16 + 2; // +2 for borders
+    let area = popup::area(frame, max_width.max(20), height);
 
-    // リストアイテム作成
     let items: Vec<ListItem> = popup
         .symbols
         .iter()
-        .enumerate()
-        .map(|(i, (name, _, _))| {
-            let style = if i == popup.selected {
-                Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
-            ListItem::new(Line::from(Span::styled(format!("  {}  ", name), style)))
-        })
+        .map(|(name, _, _)| ListItem::new(format!("  {}  ", name)))
         .collect();
-
-    let list = List::new(items).block(
-        Block::default()
-            .borders(Borders::ALL)
-            .title("Select symbol (j/k/↑↓: move, Enter: jump, Esc: cancel)")
-            .border_style(Style::default().fg(Color::Cyan)),
+    let block = popup::block(
+        "Select symbol (j/k/↑↓: move, Enter: jump, Esc: cancel)",
+        focused,
     );
-
-    frame.render_widget(list, popup_area);
+    popup::render_list(frame, area, items, popup.selected, block);
 }
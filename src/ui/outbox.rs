@@ -0,0 +1,162 @@
+use chrono::Utc;
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::popup;
+use crate::app::App;
+use crate::cache::OutboxEntry;
+
+/// 送信に失敗したコメント・レビューの送信待ち一覧
+pub fn render_list(frame: &mut Frame, app: &App, focused: bool) {
+    let panel_area = popup::area(frame, 100, 24);
+
+    let entries = app.outbox.entries();
+    let title = format!(
+        "Outbox ({}) - r: retry all now, d: discard, Esc: close",
+        entries.len()
+    );
+    let block = popup::accent_block(title, Color::Red, focused);
+
+    let lines = build_list_lines(
+        entries,
+        app.selected_outbox_entry,
+        Utc::now().timestamp_millis(),
+    );
+    // 選択行が見えるようにスクロールする（各送信待ちはヘッダー行 + 本文 + 状態の 3 行）
+    let inner_height = panel_area.height.saturating_sub(2) as usize;
+    let selected_bottom = (app.selected_outbox_entry + 1) * 3;
+    let scroll = selected_bottom.saturating_sub(inner_height) as u16;
+    let panel = Paragraph::new(lines).block(block).scroll((scroll, 0));
+    frame.render_widget(panel, panel_area);
+}
+
+fn build_list_lines(entries: &[OutboxEntry], selected: usize, now_ms: i64) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let is_selected = i == selected;
+        let mut header = format!(
+            "{} {}#{}",
+            entry.payload.kind_label(),
+            entry.repo,
+            entry.pr_number
+        );
+        if let Some(target) = entry.payload.target() {
+            header.push(' ');
+            header.push_str(&target);
+        }
+        let header_style = if is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        lines.push(Line::from(vec![
+            Span::raw(if is_selected { "> " } else { "  " }),
+            Span::styled(header, header_style),
+        ]));
+
+        // 本文は 1 行目だけを表示し、続きがあれば省略を示す
+        let mut body_lines = entry.payload.body().lines();
+        let first = body_lines.next().unwrap_or_default().to_string();
+        let more = if body_lines.next().is_some() {
+            " …"
+        } else {
+            ""
+        };
+        lines.push(Line::from(Span::styled(
+            format!("    {}{}", first, more),
+            Style::default().fg(Color::Gray),
+        )));
+
+        lines.push(Line::from(Span::styled(
+            format!(
+                "    {} attempt(s), {}: {}",
+                entry.attempts,
+                next_attempt_label(entry, now_ms),
+                entry.last_error
+            ),
+            Style::default().fg(Color::Red),
+        )));
+    }
+    lines
+}
+
+/// `retry in 45s` / `retrying` / `held until retried`
+fn next_attempt_label(entry: &OutboxEntry, now_ms: i64) -> String {
+    if entry.is_held() {
+        return "held until retried".to_string();
+    }
+    let secs = (entry.next_attempt_at - now_ms).max(0) / 1000;
+    match secs {
+        0 => "retrying".to_string(),
+        1..=59 => format!("retry in {}s", secs),
+        _ => format!("retry in {}m", (secs + 59) / 60),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::OutboxPayload;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_list_lines_show_target_body_and_retry_state() {
+        let entries = vec![
+            OutboxEntry {
+                id: 1,
+                repo: "owner/repo".to_string(),
+                pr_number: 7,
+                payload: OutboxPayload::Comment {
+                    commit_id: "abc".to_string(),
+                    path: "src/a.rs".to_string(),
+                    position: 2,
+                    start_line: None,
+                    line: 10,
+                    body: "nit: rename\nmore".to_string(),
+                    suggestion: false,
+                },
+                attempts: 2,
+                last_error: "error connecting to api.github.com".to_string(),
+                queued_at: "2024-01-01T00:00:00Z".to_string(),
+                next_attempt_at: 90_000,
+            },
+            OutboxEntry {
+                id: 2,
+                repo: "owner/repo".to_string(),
+                pr_number: 8,
+                payload: OutboxPayload::Reply {
+                    comment_id: 42,
+                    body: "thanks".to_string(),
+                },
+                attempts: 1,
+                last_error: "API rate limit exceeded".to_string(),
+                queued_at: "2024-01-01T00:00:00Z".to_string(),
+                next_attempt_at: 0,
+            },
+        ];
+        let text: Vec<String> = build_list_lines(&entries, 1, 30_000)
+            .iter()
+            .map(line_text)
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "  comment owner/repo#7 src/a.rs:10",
+                "    nit: rename …",
+                "    2 attempt(s), retry in 1m: error connecting to api.github.com",
+                "> reply owner/repo#8 #42",
+                "    thanks",
+                "    1 attempt(s), retrying: API rate limit exceeded",
+            ]
+        );
+    }
+}
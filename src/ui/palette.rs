@@ -0,0 +1,68 @@
+use ratatui::style::Color;
+
+use crate::config::{BackgroundMode, Config};
+
+/// diff の行の背景色。端末の背景（ライト / ダーク）に合わせて選ぶ
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DiffPalette {
+    /// 追加行の背景（None なら付けない）
+    pub added: Option<Color>,
+    /// 削除行の背景（None なら付けない）
+    pub removed: Option<Color>,
+    /// 複数行選択の範囲の背景
+    pub multiline: Color,
+}
+
+impl Default for DiffPalette {
+    /// ダーク背景で、追加/削除行に背景色を付けない
+    fn default() -> Self {
+        Self::new(BackgroundMode::Dark, false)
+    }
+}
+
+impl DiffPalette {
+    /// `bg_color` が false なら追加/削除行に背景色を付けない
+    pub fn new(background: BackgroundMode, bg_color: bool) -> Self {
+        let (added, removed, multiline) = match background {
+            BackgroundMode::Light => (
+                Color::Rgb(220, 255, 220),
+                Color::Rgb(255, 220, 220),
+                Color::Rgb(210, 230, 255),
+            ),
+            // auto は起動時に確定するので、ここに来るのは判定前だけ
+            BackgroundMode::Dark | BackgroundMode::Auto => (
+                Color::Rgb(0, 60, 0),
+                Color::Rgb(60, 0, 0),
+                Color::Rgb(0, 40, 80),
+            ),
+        };
+        Self {
+            added: bg_color.then_some(added),
+            removed: bg_color.then_some(removed),
+            multiline,
+        }
+    }
+
+    pub fn from_config(config: &Config) -> Self {
+        Self::new(config.ui.background, config.diff.bg_color)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_palette_follows_background() {
+        let dark = DiffPalette::new(BackgroundMode::Dark, true);
+        assert_eq!(dark.added, Some(Color::Rgb(0, 60, 0)));
+        let light = DiffPalette::new(BackgroundMode::Light, true);
+        assert_eq!(light.added, Some(Color::Rgb(220, 255, 220)));
+        assert_ne!(light.multiline, dark.multiline);
+
+        let plain = DiffPalette::new(BackgroundMode::Light, false);
+        assert_eq!(plain.added, None);
+        assert_eq!(plain.removed, None);
+        assert_eq!(plain.multiline, light.multiline);
+    }
+}
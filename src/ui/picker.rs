@@ -0,0 +1,280 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{ListItem, Paragraph},
+    Frame,
+};
+
+use super::popup;
+use crate::fuzzy::fuzzy_match;
+
+/// ピッカーの候補
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PickerItem {
+    pub label: String,
+    /// label の後ろに薄く表示する補足
+    pub detail: Option<String>,
+    /// 選択済みの印を付けるか
+    pub checked: bool,
+}
+
+/// ピッカーのキー入力結果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerAction {
+    /// クエリの編集・カーソル移動（継続）
+    Continue,
+    /// Enter: `items` のインデックスの候補を選んだ
+    Select(usize),
+    /// Esc / Ctrl-c: 閉じる
+    Close,
+}
+
+/// 候補をクエリでファジーに絞り込んで選ぶポップアップ
+#[derive(Debug, Clone, Default)]
+pub struct Picker {
+    pub query: String,
+    items: Vec<PickerItem>,
+    /// 一致した候補（`items` のインデックスと一致位置）。スコア順
+    matches: Vec<(usize, Vec<usize>)>,
+    selected: usize,
+}
+
+impl Picker {
+    pub fn new(items: Vec<PickerItem>) -> Self {
+        let mut picker = Self {
+            items,
+            ..Self::default()
+        };
+        picker.refilter();
+        picker
+    }
+
+    pub fn items(&self) -> &[PickerItem] {
+        &self.items
+    }
+
+    /// 候補を差し替える。クエリと選択中の候補は可能な限り保つ
+    pub fn set_items(&mut self, items: Vec<PickerItem>) {
+        let selected_label = self.selected_item().map(|item| item.label.clone());
+        self.items = items;
+        self.refilter();
+        if let Some(label) = selected_label {
+            if let Some(pos) = self
+                .matches
+                .iter()
+                .position(|(i, _)| self.items[*i].label == label)
+            {
+                self.selected = pos;
+            }
+        }
+    }
+
+    /// 選択中の候補の印を切り替える（複数選択で使う）
+    pub fn toggle_checked(&mut self) {
+        if let Some(item) = self
+            .matches
+            .get(self.selected)
+            .and_then(|(i, _)| self.items.get_mut(*i))
+        {
+            item.checked = !item.checked;
+        }
+    }
+
+    pub fn selected_item(&self) -> Option<&PickerItem> {
+        self.matches
+            .get(self.selected)
+            .and_then(|(i, _)| self.items.get(*i))
+    }
+
+    /// クエリで絞り込む。クエリが空なら元の順、そうでなければスコア順（同点は元の順）
+    fn refilter(&mut self) {
+        let mut scored: Vec<(i64, usize, Vec<usize>)> = self
+            .items
+            .iter()
+            .enumerate()
+            .filter_map(|(i, item)| {
+                let m = fuzzy_match(&self.query, &item.label)?;
+                Some((m.score, i, m.positions))
+            })
+            .collect();
+        scored.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+        self.matches = scored.into_iter().map(|(_, i, pos)| (i, pos)).collect();
+        self.selected = 0;
+    }
+
+    pub fn handle_key(&mut self, key: &KeyEvent) -> PickerAction {
+        let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+        let last = self.matches.len().saturating_sub(1);
+        match key.code {
+            KeyCode::Esc => return PickerAction::Close,
+            KeyCode::Char('c') if ctrl => return PickerAction::Close,
+            KeyCode::Enter => {
+                return match self.matches.get(self.selected) {
+                    Some((i, _)) => PickerAction::Select(*i),
+                    None => PickerAction::Continue,
+                };
+            }
+            KeyCode::Down => self.selected = (self.selected + 1).min(last),
+            KeyCode::Char('n' | 'j') if ctrl => self.selected = (self.selected + 1).min(last),
+            KeyCode::Up => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('p' | 'k') if ctrl => self.selected = self.selected.saturating_sub(1),
+            KeyCode::Char('u') if ctrl => {
+                self.query.clear();
+                self.refilter();
+            }
+            KeyCode::Backspace if !self.query.is_empty() => {
+                self.query.pop();
+                self.refilter();
+            }
+            KeyCode::Char(c) if !ctrl => {
+                self.query.push(c);
+                self.refilter();
+            }
+            _ => {}
+        }
+        PickerAction::Continue
+    }
+}
+
+/// 一致位置をハイライトした label のスパン列
+pub(super) fn highlighted_label(
+    label: &str,
+    positions: &[usize],
+    base: Style,
+) -> Vec<Span<'static>> {
+    let matched = base.fg(Color::Yellow).add_modifier(Modifier::BOLD);
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+
+    for (i, c) in label.chars().enumerate() {
+        let is_match = positions.binary_search(&i).is_ok();
+        if is_match != current_matched && !current.is_empty() {
+            let style = if current_matched { matched } else { base };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_matched = is_match;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        let style = if current_matched { matched } else { base };
+        spans.push(Span::styled(current, style));
+    }
+    spans
+}
+
+/// ピッカーを描画。`title` は入力欄の枠に、`placeholder` は候補がないときに表示する
+pub fn render_popup(
+    frame: &mut Frame,
+    picker: &Picker,
+    title: &str,
+    placeholder: &str,
+    focused: bool,
+) {
+    let popup_area = popup::area(frame, 70, 20);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(3), Constraint::Min(0)])
+        .split(popup_area);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(Color::Cyan)),
+        Span::raw(picker.query.clone()),
+        Span::styled("█", Style::default().fg(Color::DarkGray)),
+    ]))
+    .block(popup::block(title.to_string(), focused));
+    frame.render_widget(input, chunks[0]);
+
+    let list_block = popup::block(
+        format!("{}/{}", picker.matches.len(), picker.items.len()),
+        focused,
+    );
+
+    if picker.items.is_empty() {
+        let message = Paragraph::new(Span::styled(
+            placeholder.to_string(),
+            Style::default().fg(Color::DarkGray),
+        ))
+        .block(list_block);
+        frame.render_widget(message, chunks[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = picker
+        .matches
+        .iter()
+        .filter_map(|(i, positions)| {
+            let item = picker.items.get(*i)?;
+            let mut spans = vec![if item.checked {
+                Span::styled("✓ ", Style::default().fg(Color::Green))
+            } else {
+                Span::raw("  ")
+            }];
+            spans.extend(highlighted_label(&item.label, positions, Style::default()));
+            if let Some(ref detail) = item.detail {
+                spans.push(Span::styled(
+                    format!("  {}", detail),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Some(ListItem::new(Line::from(spans)))
+        })
+        .collect();
+    popup::render_list(frame, chunks[1], items, picker.selected, list_block);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(label: &str) -> PickerItem {
+        PickerItem {
+            label: label.to_string(),
+            detail: None,
+            checked: false,
+        }
+    }
+
+    fn press(picker: &mut Picker, code: KeyCode) -> PickerAction {
+        picker.handle_key(&KeyEvent::new(code, KeyModifiers::NONE))
+    }
+
+    #[test]
+    fn test_highlighted_label_splits_matches() {
+        let spans = highlighted_label("src/main.rs", &[4, 5], Style::default());
+        let texts: Vec<&str> = spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(texts, vec!["src/", "ma", "in.rs"]);
+        assert!(spans[1].style.add_modifier.contains(Modifier::BOLD));
+    }
+
+    #[test]
+    fn test_query_filters_and_select_returns_item_index() {
+        let mut picker = Picker::new(vec![item("alice"), item("bob"), item("carol")]);
+        press(&mut picker, KeyCode::Char('c'));
+        press(&mut picker, KeyCode::Char('r'));
+        assert_eq!(picker.selected_item().unwrap().label, "carol");
+        assert_eq!(press(&mut picker, KeyCode::Enter), PickerAction::Select(2));
+
+        press(&mut picker, KeyCode::Backspace);
+        press(&mut picker, KeyCode::Backspace);
+        press(&mut picker, KeyCode::Down);
+        assert_eq!(picker.selected_item().unwrap().label, "bob");
+        assert_eq!(press(&mut picker, KeyCode::Esc), PickerAction::Close);
+    }
+
+    #[test]
+    fn test_set_items_keeps_selection() {
+        let mut picker = Picker::new(vec![item("alice"), item("bob")]);
+        press(&mut picker, KeyCode::Down);
+        let mut bob = item("bob");
+        bob.checked = true;
+        picker.set_items(vec![item("alice"), bob]);
+        assert!(picker.selected_item().unwrap().checked);
+
+        picker.toggle_checked();
+        assert!(!picker.items()[1].checked);
+    }
+}
@@ -0,0 +1,160 @@
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Style},
+    text::Line,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState},
+    Frame,
+};
+
+use super::centered_rect;
+use crate::config::KeybindingsConfig;
+use crate::keybinding::KeySequence;
+
+/// ページ送りで動かす行数（ポップアップの高さは描画時に決まるため固定）
+const PAGE: usize = 10;
+
+/// ポップアップ共通のキー操作。どのポップアップでも同じキーが同じ意味になるようにする
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PopupKey {
+    /// Esc / quit: 閉じる
+    Close,
+    /// Enter: 選択中の項目を決定する
+    Confirm,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    /// Home
+    Top,
+    /// End / jump_to_last
+    Bottom,
+    /// ポップアップ固有のキー
+    Other,
+}
+
+impl PopupKey {
+    pub fn from_key(key: &KeyEvent, kb: &KeybindingsConfig) -> Self {
+        match key.code {
+            KeyCode::Esc => return Self::Close,
+            KeyCode::Enter => return Self::Confirm,
+            KeyCode::Up => return Self::Up,
+            KeyCode::Down => return Self::Down,
+            KeyCode::PageUp => return Self::PageUp,
+            KeyCode::PageDown => return Self::PageDown,
+            KeyCode::Home => return Self::Top,
+            KeyCode::End => return Self::Bottom,
+            _ => {}
+        }
+        let pressed =
+            |seq: &KeySequence| seq.is_single() && seq.first().is_some_and(|b| b.matches(key));
+        if pressed(&kb.quit) {
+            Self::Close
+        } else if pressed(&kb.move_down) {
+            Self::Down
+        } else if pressed(&kb.move_up) {
+            Self::Up
+        } else if pressed(&kb.page_down) {
+            Self::PageDown
+        } else if pressed(&kb.page_up) {
+            Self::PageUp
+        } else if pressed(&kb.jump_to_last) {
+            Self::Bottom
+        } else {
+            Self::Other
+        }
+    }
+
+    /// 移動キーなら、`len` 件の中で `current` から動かした位置を返す
+    pub fn navigate(self, current: usize, len: usize) -> Option<usize> {
+        let next = match self {
+            Self::Up => current.saturating_sub(1),
+            Self::Down => current + 1,
+            Self::PageUp => current.saturating_sub(PAGE),
+            Self::PageDown => current + PAGE,
+            Self::Top => 0,
+            Self::Bottom => usize::MAX,
+            _ => return None,
+        };
+        Some(next.min(len.saturating_sub(1)))
+    }
+}
+
+/// 画面中央に最大 `max_width` x `max_height` の領域を確保し、下に描画されたものを消す
+pub fn area(frame: &mut Frame, max_width: u16, max_height: u16) -> Rect {
+    let screen = frame.area();
+    let width = screen.width.saturating_sub(8).min(max_width);
+    let height = screen.height.saturating_sub(6).min(max_height);
+    let popup_area = centered_rect(width, height, screen);
+    frame.render_widget(Clear, popup_area);
+    popup_area
+}
+
+/// ポップアップの枠。キー入力を受け取る最前面のものだけ枠に色を付ける
+pub fn block<'a>(title: impl Into<Line<'a>>, focused: bool) -> Block<'a> {
+    accent_block(title, Color::Cyan, focused)
+}
+
+/// `block` の枠の色を変えたもの
+pub fn accent_block<'a>(title: impl Into<Line<'a>>, accent: Color, focused: bool) -> Block<'a> {
+    let color = if focused { accent } else { Color::DarkGray };
+    Block::default()
+        .borders(Borders::ALL)
+        .title(title)
+        .border_style(Style::default().fg(color))
+}
+
+/// 一覧を描画する。選択中の項目が見えるようにスクロールする
+pub fn render_list(
+    frame: &mut Frame,
+    area: Rect,
+    items: Vec<ListItem>,
+    selected: usize,
+    block: Block,
+) {
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().bg(Color::DarkGray));
+    let mut state = ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> PopupKey {
+        PopupKey::from_key(
+            &KeyEvent::new(code, KeyModifiers::NONE),
+            &KeybindingsConfig::default(),
+        )
+    }
+
+    #[test]
+    fn test_popup_key_follows_keybindings() {
+        assert_eq!(key(KeyCode::Esc), PopupKey::Close);
+        assert_eq!(key(KeyCode::Char('q')), PopupKey::Close);
+        assert_eq!(key(KeyCode::Enter), PopupKey::Confirm);
+        assert_eq!(key(KeyCode::Char('j')), PopupKey::Down);
+        assert_eq!(key(KeyCode::Up), PopupKey::Up);
+        assert_eq!(key(KeyCode::Char('x')), PopupKey::Other);
+        let ctrl_d = KeyEvent::new(KeyCode::Char('d'), KeyModifiers::CONTROL);
+        assert_eq!(
+            PopupKey::from_key(&ctrl_d, &KeybindingsConfig::default()),
+            PopupKey::PageDown
+        );
+    }
+
+    #[test]
+    fn test_navigate_clamps_to_items() {
+        assert_eq!(PopupKey::Down.navigate(2, 3), Some(2));
+        assert_eq!(PopupKey::Up.navigate(0, 3), Some(0));
+        assert_eq!(PopupKey::PageDown.navigate(1, 30), Some(11));
+        assert_eq!(PopupKey::PageUp.navigate(5, 30), Some(0));
+        assert_eq!(PopupKey::Bottom.navigate(0, 30), Some(29));
+        assert_eq!(PopupKey::Top.navigate(7, 30), Some(0));
+        assert_eq!(PopupKey::Down.navigate(0, 0), Some(0));
+        assert_eq!(PopupKey::Confirm.navigate(0, 3), None);
+    }
+}
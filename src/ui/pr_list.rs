@@ -9,8 +9,14 @@ use ratatui::{
     Frame,
 };
 
+use std::collections::HashMap;
+
+use crate::anonymize::Anonymizer;
 use crate::app::App;
-use crate::github::PullRequestSummary;
+use crate::config::KeybindingView;
+use crate::github::{PullRequestSummary, ReviewCoverage};
+use crate::text_width;
+use crate::time_format::TimeFormatter;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     let has_filter_bar = app.pr_list_filter.as_ref().is_some_and(|f| f.input_active);
@@ -31,7 +37,26 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 
     // Header
     let filter_str = app.pr_list_state_filter.display_name();
-    let header_text = format!("PR List: {} ({})", app.repo, filter_str);
+    let mut header_text = format!(
+        "PR List: {} ({})",
+        app.anonymizer.repo(&app.repo),
+        filter_str
+    );
+    if let Some(index) = app
+        .workspace_repos
+        .iter()
+        .position(|repo| *repo == app.repo)
+        .filter(|_| app.workspace_repos.len() > 1)
+    {
+        header_text.push_str(&format!(
+            " [repo {}/{}]",
+            index + 1,
+            app.workspace_repos.len()
+        ));
+    }
+    if !app.pr_tabs.prs().is_empty() {
+        header_text.push_str(&format!(" {}", super::common::pr_tabs_label(app)));
+    }
     let header =
         Paragraph::new(header_text).block(Block::default().borders(Borders::ALL).title("octorus"));
     frame.render_widget(header, chunks[0]);
@@ -106,7 +131,14 @@ pub fn render(frame: &mut Frame, app: &mut App) {
                 format!("Pull Requests ({})", total_prs)
             };
 
-            let items = build_pr_list_items_ref(&display_prs, display_selected);
+            let items = build_pr_list_items_ref(
+                &display_prs,
+                display_selected,
+                &app.time_format,
+                &app.pr_review_coverage,
+                app.config.nudge.stale_days,
+                &app.anonymizer,
+            );
 
             // Use ListState for stateful rendering with automatic scroll management
             let mut list_state = ListState::default()
@@ -182,20 +214,50 @@ fn render_filter_bar(
 }
 
 fn render_footer(frame: &mut Frame, area: ratatui::layout::Rect, app: &App) {
+    let kb = app.config.keybindings.for_view(KeybindingView::PrList);
     let filter_hint = if app.pr_list_filter.is_some() {
-        "Esc: clear filter | "
+        "Esc: clear filter | ".to_string()
+    } else {
+        format!("{}: filter | ", kb.filter.display())
+    };
+    let repo_hint = if app.workspace_repos.len() > 1 {
+        format!("{}: repos | ", kb.switch_repo.display())
     } else {
-        "Space /: filter | "
+        String::new()
     };
     let footer_text = format!(
-        "j/k/↑↓: move | Enter: select | {}gg/G: top/bottom | O: browser | o: open | c: closed | a: all | r: refresh | q: quit | ?: help",
-        filter_hint
+        "{}/{}/↑↓: move | {}: select | {}: new tab | {}{}/{}: top/bottom | {}: browser | {}: open | {}: closed | {}: all | {}: refresh | {}: nudge | {}: inbox | {}{}: quit | {}: help",
+        kb.move_down.display(),
+        kb.move_up.display(),
+        kb.open_panel.display(),
+        kb.open_pr_tab.display(),
+        filter_hint,
+        kb.jump_to_first.display(),
+        kb.jump_to_last.display(),
+        kb.open_in_browser.display(),
+        kb.pr_state_open.display(),
+        kb.pr_state_closed.display(),
+        kb.pr_state_all.display(),
+        kb.refresh.display(),
+        kb.nudge.display(),
+        kb.inbox.display(),
+        repo_hint,
+        kb.quit.display(),
+        kb.help.display(),
     );
     let footer = Paragraph::new(footer_text).block(Block::default().borders(Borders::ALL));
     frame.render_widget(footer, area);
 }
 
-fn build_pr_list_items_ref(prs: &[&PullRequestSummary], selected: usize) -> Vec<ListItem<'static>> {
+fn build_pr_list_items_ref(
+    prs: &[&PullRequestSummary],
+    selected: usize,
+    time: &TimeFormatter,
+    coverage: &HashMap<u32, ReviewCoverage>,
+    stale_days: u32,
+    anonymizer: &Anonymizer,
+) -> Vec<ListItem<'static>> {
+    let now = chrono::Utc::now();
     prs.iter()
         .enumerate()
         .map(|(i, pr)| {
@@ -213,11 +275,12 @@ fn build_pr_list_items_ref(prs: &[&PullRequestSummary], selected: usize) -> Vec<
                 Style::default().fg(Color::Yellow)
             };
             let number_span = Span::styled(format!("#{:<5}", pr.number), number_style);
+            let coverage_span = coverage_badge(coverage.get(&pr.number));
 
-            // Draft + Title (truncate if too long, respecting char boundaries)
+            // Draft + Title (truncate and pad by display width)
             let title_width = 50;
             let full_title = format!("{}{}", draft_marker, pr.title);
-            let title = truncate_string(&full_title, title_width);
+            let title = text_width::truncate(&full_title, title_width);
             let title_style = if is_selected {
                 Style::default()
                     .fg(Color::Yellow)
@@ -227,14 +290,12 @@ fn build_pr_list_items_ref(prs: &[&PullRequestSummary], selected: usize) -> Vec<
             } else {
                 Style::default()
             };
-            let title_span = Span::styled(
-                format!("{:<width$}", title, width = title_width),
-                title_style,
-            );
+            let title_span =
+                Span::styled(text_width::pad_to_width(&title, title_width), title_style);
 
             // Author
             let author_span = Span::styled(
-                format!("by @{}", pr.author.login),
+                format!("by @{}", anonymizer.user(&pr.author.login)),
                 Style::default().fg(Color::Cyan),
             );
 
@@ -251,13 +312,27 @@ fn build_pr_list_items_ref(prs: &[&PullRequestSummary], selected: usize) -> Vec<
                 String::new()
             };
             let labels_span = Span::styled(labels_str, Style::default().fg(Color::Blue));
+            // リマインド（nudge）の対象になる open な PR は更新日時を目立たせる
+            let is_stale = pr.state.eq_ignore_ascii_case("open")
+                && crate::nudge::days_stale(&pr.updated_at, now)
+                    .is_some_and(|days| days >= i64::from(stale_days));
+            let updated_span = Span::styled(
+                format!("  {}", time.format(&pr.updated_at)),
+                Style::default().fg(if is_stale {
+                    Color::Yellow
+                } else {
+                    Color::DarkGray
+                }),
+            );
 
             let line = Line::from(vec![
                 number_span,
+                coverage_span,
                 Span::raw("  "),
                 title_span,
                 Span::raw("  "),
                 author_span,
+                updated_span,
                 labels_span,
             ]);
 
@@ -266,12 +341,15 @@ fn build_pr_list_items_ref(prs: &[&PullRequestSummary], selected: usize) -> Vec<
         .collect()
 }
 
-/// Truncate a string to fit within a given width, respecting char boundaries
-fn truncate_string(s: &str, max_width: usize) -> String {
-    if s.chars().count() <= max_width {
-        s.to_string()
-    } else {
-        let truncated: String = s.chars().take(max_width.saturating_sub(3)).collect();
-        format!("{}...", truncated)
-    }
+/// レビュー済みファイルの割合のバッジ（未取得の間は同じ幅の空白）
+fn coverage_badge(coverage: Option<&ReviewCoverage>) -> Span<'static> {
+    let Some(percent) = coverage.and_then(ReviewCoverage::percent) else {
+        return Span::raw("     ");
+    };
+    let color = match percent {
+        100 => Color::Green,
+        0 => Color::DarkGray,
+        _ => Color::Magenta,
+    };
+    Span::styled(format!("{:>4}%", percent), Style::default().fg(color))
 }
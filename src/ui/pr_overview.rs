@@ -0,0 +1,201 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::common::build_pr_info;
+use super::markdown;
+use crate::anonymize::Anonymizer;
+use crate::app::{App, PrOverviewState};
+use crate::github::{PullRequest, ReviewerStatus};
+
+const HELP_TEXT: &str =
+    "j/k: scroll | Ctrl-d/u: page | g/G: top/end | O: open in browser | q/Esc: back";
+
+/// PR のタイトル・本文・ラベル・レビュアー・リンクされた Issue
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(build_pr_info(app))
+        .block(Block::default().borders(Borders::ALL).title("octorus"));
+    frame.render_widget(header, chunks[0]);
+
+    let content_width = chunks[1].width.saturating_sub(2) as usize;
+    let content_height = chunks[1].height.saturating_sub(2) as usize;
+    let lines = match (app.pr(), app.pr_overview.as_ref()) {
+        (Some(pr), Some(overview)) => build_lines(
+            pr,
+            overview,
+            &app.anonymizer,
+            content_width,
+            app.spinner_char(),
+        ),
+        _ => Vec::new(),
+    };
+
+    let max_scroll = lines.len().saturating_sub(content_height);
+    let Some(ref mut overview) = app.pr_overview else {
+        return;
+    };
+    overview.scroll = overview.scroll.min(max_scroll);
+    let scroll_info = if max_scroll > 0 {
+        format!(" ({}/{})", overview.scroll + 1, max_scroll + 1)
+    } else {
+        String::new()
+    };
+    let content = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("Overview{}", scroll_info)),
+        )
+        .scroll((overview.scroll.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(content, chunks[1]);
+
+    let footer_line = super::footer::build_footer_line(app, HELP_TEXT);
+    let footer = Paragraph::new(footer_line).block(super::footer::build_footer_block(app));
+    frame.render_widget(footer, chunks[2]);
+}
+
+/// 概要の全行。本文はコメントと同じ Markdown 表示で幅 `width` に折り返す
+fn build_lines(
+    pr: &PullRequest,
+    overview: &PrOverviewState,
+    anonymizer: &Anonymizer,
+    width: usize,
+    spinner: &str,
+) -> Vec<Line<'static>> {
+    let label_style = Style::default().fg(Color::DarkGray);
+    let field = |name: &str, value: Vec<Span<'static>>| {
+        let mut spans = vec![Span::styled(format!("{:<14}", name), label_style)];
+        spans.extend(value);
+        Line::from(spans)
+    };
+
+    let mut lines = vec![
+        Line::from(Span::styled(
+            format!("#{} {}", pr.number, pr.title),
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        field(
+            "Author",
+            vec![Span::styled(
+                format!("@{}", anonymizer.user(&pr.user.login)),
+                Style::default().fg(Color::Cyan),
+            )],
+        ),
+        field(
+            "Branch",
+            vec![Span::raw(format!(
+                "{} → {}",
+                pr.head.ref_name, pr.base.ref_name
+            ))],
+        ),
+        field("State", vec![Span::raw(pr.state.clone())]),
+        field(
+            "Labels",
+            if pr.labels.is_empty() {
+                vec![Span::styled("none", label_style)]
+            } else {
+                let names: Vec<&str> = pr.labels.iter().map(|l| l.name.as_str()).collect();
+                vec![Span::styled(
+                    names.join(", "),
+                    Style::default().fg(Color::Magenta),
+                )]
+            },
+        ),
+    ];
+
+    match (&overview.details, &overview.error) {
+        (_, Some(error)) => lines.push(field(
+            "Reviewers",
+            vec![Span::styled(
+                format!("failed to load: {}", error),
+                Style::default().fg(Color::Red),
+            )],
+        )),
+        (None, None) => lines.push(field(
+            "Reviewers",
+            vec![Span::styled(
+                format!("{} loading...", spinner),
+                Style::default().fg(Color::Yellow),
+            )],
+        )),
+        (Some(details), None) => {
+            lines.push(field(
+                "Reviewers",
+                if details.reviewers.is_empty() {
+                    vec![Span::styled("none", label_style)]
+                } else {
+                    reviewer_spans(&details.reviewers, anonymizer)
+                },
+            ));
+            if details.linked_issues.is_empty() {
+                lines.push(field(
+                    "Linked issues",
+                    vec![Span::styled("none", label_style)],
+                ));
+            }
+            for (i, issue) in details.linked_issues.iter().enumerate() {
+                let color = if issue.state == "OPEN" {
+                    Color::Green
+                } else {
+                    Color::Magenta
+                };
+                lines.push(field(
+                    if i == 0 { "Linked issues" } else { "" },
+                    vec![
+                        Span::styled(format!("#{} ", issue.number), Style::default().fg(color)),
+                        Span::raw(issue.title.clone()),
+                        Span::styled(format!(" ({})", issue.state.to_lowercase()), label_style),
+                    ],
+                ));
+            }
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled("─".repeat(width), label_style)));
+    match pr.body.as_deref().map(str::trim).filter(|b| !b.is_empty()) {
+        Some(body) => lines.extend(markdown::render_body(&anonymizer.text(body), width, None)),
+        None => lines.push(Line::from(Span::styled(
+            "No description provided.",
+            label_style,
+        ))),
+    }
+    lines
+}
+
+/// `bob requested, alice ✓ approved` のようにレビュアーごとの状態を色付きで並べる
+fn reviewer_spans(reviewers: &[ReviewerStatus], anonymizer: &Anonymizer) -> Vec<Span<'static>> {
+    let mut spans = Vec::new();
+    for (i, reviewer) in reviewers.iter().enumerate() {
+        if i > 0 {
+            spans.push(Span::raw(", "));
+        }
+        let (mark, state, color) = match reviewer.state.as_str() {
+            "APPROVED" => ("✓ ", "approved", Color::Green),
+            "CHANGES_REQUESTED" => ("✗ ", "changes requested", Color::Red),
+            "COMMENTED" => ("", "commented", Color::DarkGray),
+            "DISMISSED" => ("", "dismissed", Color::DarkGray),
+            _ => ("", "requested", Color::Yellow),
+        };
+        spans.push(Span::raw(anonymizer.user(&reviewer.name).into_owned()));
+        spans.push(Span::styled(
+            format!(" {}{}", mark, state),
+            Style::default().fg(color),
+        ));
+    }
+    spans
+}
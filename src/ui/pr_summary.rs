@@ -0,0 +1,142 @@
+use ratatui::{
+    layout::{Constraint, Direction, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph},
+    Frame,
+};
+
+use super::common::build_pr_info;
+use super::markdown;
+use crate::app::{App, PrSummaryState};
+use crate::pr_summary::SummaryItem;
+use crate::text_width;
+
+const HELP_TEXT: &str = "j/k: scroll | Ctrl-d/u: page | g/G: top/end | R: regenerate | q/Esc: back";
+
+/// AI による PR の要約（概要・リスクのある箇所・読む順番・破壊的変更）
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Min(0),    // Content
+            Constraint::Length(3), // Footer
+        ])
+        .split(frame.area());
+
+    let header = Paragraph::new(build_pr_info(app))
+        .block(Block::default().borders(Borders::ALL).title("octorus"));
+    frame.render_widget(header, chunks[0]);
+
+    let content_width = chunks[1].width.saturating_sub(2) as usize;
+    let content_height = chunks[1].height.saturating_sub(2) as usize;
+    let lines = match app.pr_summary.as_ref() {
+        Some(state) => build_lines(state, content_width, app.spinner_char()),
+        None => Vec::new(),
+    };
+
+    let max_scroll = lines.len().saturating_sub(content_height);
+    let Some(ref mut state) = app.pr_summary else {
+        return;
+    };
+    state.scroll = state.scroll.min(max_scroll);
+    let scroll_info = if max_scroll > 0 {
+        format!(" ({}/{})", state.scroll + 1, max_scroll + 1)
+    } else {
+        String::new()
+    };
+    let short_sha: String = state.head_sha.chars().take(7).collect();
+    let content = Paragraph::new(lines)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(format!("AI Summary @ {}{}", short_sha, scroll_info)),
+        )
+        .scroll((state.scroll.min(u16::MAX as usize) as u16, 0));
+    frame.render_widget(content, chunks[1]);
+
+    let footer_line = super::footer::build_footer_line(app, HELP_TEXT);
+    let footer = Paragraph::new(footer_line).block(super::footer::build_footer_block(app));
+    frame.render_widget(footer, chunks[2]);
+}
+
+/// 要約の全行。概要は Markdown として、項目の理由は幅 `width` に折り返す
+fn build_lines(state: &PrSummaryState, width: usize, spinner: &str) -> Vec<Line<'static>> {
+    if let Some(ref error) = state.error {
+        return vec![Line::from(Span::styled(
+            format!("Failed to summarize the PR: {}", error),
+            Style::default().fg(Color::Red),
+        ))];
+    }
+    let Some(ref summary) = state.summary else {
+        return vec![Line::from(Span::styled(
+            format!("{} Summarizing the PR with {}...", spinner, state.agent),
+            Style::default().fg(Color::Yellow),
+        ))];
+    };
+
+    let mut lines = vec![heading("Overview", Color::Cyan)];
+    lines.extend(markdown::render_body(summary.overview.trim(), width, None));
+
+    lines.push(Line::from(""));
+    lines.push(heading("Risk areas", Color::Red));
+    push_items(&mut lines, &summary.risk_areas, false, width);
+
+    lines.push(Line::from(""));
+    lines.push(heading("Suggested review order", Color::Green));
+    push_items(&mut lines, &summary.review_order, true, width);
+
+    lines.push(Line::from(""));
+    lines.push(heading("Breaking changes", Color::Magenta));
+    if summary.breaking_changes.is_empty() {
+        lines.push(none_line());
+    }
+    for change in &summary.breaking_changes {
+        push_wrapped(&mut lines, " • ", change, width);
+    }
+    lines
+}
+
+fn heading(title: &str, color: Color) -> Line<'static> {
+    Line::from(Span::styled(
+        title.to_string(),
+        Style::default().fg(color).add_modifier(Modifier::BOLD),
+    ))
+}
+
+fn none_line() -> Line<'static> {
+    Line::from(Span::styled("none", Style::default().fg(Color::DarkGray)))
+}
+
+/// `1. path` の行の下に理由を字下げして並べる
+fn push_items(lines: &mut Vec<Line<'static>>, items: &[SummaryItem], numbered: bool, width: usize) {
+    if items.is_empty() {
+        lines.push(none_line());
+    }
+    for (i, item) in items.iter().enumerate() {
+        let marker = if numbered {
+            format!("{:>2}. ", i + 1)
+        } else {
+            " • ".to_string()
+        };
+        let indent = " ".repeat(text_width::display_width(&marker));
+        lines.push(Line::from(vec![
+            Span::raw(marker),
+            Span::styled(item.path.clone(), Style::default().fg(Color::Cyan)),
+        ]));
+        if !item.reason.trim().is_empty() {
+            push_wrapped(lines, &indent, item.reason.trim(), width);
+        }
+    }
+}
+
+/// 先頭に `prefix`、折り返した 2 行目以降に同じ幅の空白を付ける
+fn push_wrapped(lines: &mut Vec<Line<'static>>, prefix: &str, text: &str, width: usize) {
+    let indent = " ".repeat(text_width::display_width(prefix));
+    let wrap_width = width.saturating_sub(indent.len()).max(1);
+    for (i, line) in text_width::wrap(text, wrap_width).into_iter().enumerate() {
+        let lead = if i == 0 { prefix } else { indent.as_str() };
+        lines.push(Line::from(format!("{}{}", lead, line)));
+    }
+}
@@ -0,0 +1,50 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use super::popup;
+use crate::app::App;
+use crate::session_summary::SessionSummary;
+
+/// 終了前の確認。セッションのまとめと未送信の下書きの扱いを選ばせる
+pub fn render_popup(frame: &mut Frame, app: &App, summary: &SessionSummary, focused: bool) {
+    let popup_area = popup::area(frame, 70, 14);
+    let block = popup::accent_block("Quit octorus?", Color::Yellow, focused);
+
+    let mut lines: Vec<Line> = summary
+        .lines()
+        .into_iter()
+        .map(|line| Line::from(format!("  {}", line)))
+        .collect();
+    lines.push(Line::from(""));
+
+    let key_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let submittable = app.quit_submittable_drafts();
+    if submittable > 0 {
+        lines.push(Line::from(vec![
+            Span::styled("  s", key_style),
+            Span::raw(format!(
+                ": submit {} draft(s) as a comment review and quit",
+                submittable
+            )),
+        ]));
+    }
+    lines.push(Line::from(vec![
+        Span::styled("  q", key_style),
+        Span::raw(": quit and discard unsent drafts"),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Esc", key_style),
+        Span::raw(": keep reviewing"),
+    ]));
+
+    let panel = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(panel, popup_area);
+}
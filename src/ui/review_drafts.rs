@@ -0,0 +1,108 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::Paragraph,
+    Frame,
+};
+
+use super::popup;
+use crate::app::App;
+use crate::github::DraftReviewComment;
+
+/// 送信前のレビュー下書き一覧
+pub fn render_list(frame: &mut Frame, app: &App, focused: bool) {
+    let panel_area = popup::area(frame, 100, 24);
+
+    let drafts = app.review_drafts();
+    let title = format!(
+        "Pending review ({}) - Enter: jump, d: discard, Esc: close | submit with {}/{}/{}",
+        drafts.len(),
+        app.config.keybindings.approve.display(),
+        app.config.keybindings.request_changes.display(),
+        app.config.keybindings.comment.display()
+    );
+    let block = popup::accent_block(title, Color::Magenta, focused);
+
+    let lines = build_list_lines(drafts, app.selected_review_draft);
+    // 選択行が見えるようにスクロールする（各下書きはヘッダー行 + 本文 1 行）
+    let inner_height = panel_area.height.saturating_sub(2) as usize;
+    let selected_bottom = (app.selected_review_draft + 1) * 2;
+    let scroll = selected_bottom.saturating_sub(inner_height) as u16;
+    let panel = Paragraph::new(lines).block(block).scroll((scroll, 0));
+    frame.render_widget(panel, panel_area);
+}
+
+fn build_list_lines(drafts: &[DraftReviewComment], selected: usize) -> Vec<Line<'static>> {
+    let mut lines = Vec::new();
+    for (i, draft) in drafts.iter().enumerate() {
+        let is_selected = i == selected;
+        let location = match draft.start_line {
+            Some(start) => format!("{}:{}-{}", draft.path, start, draft.line),
+            None => format!("{}:{}", draft.path, draft.line),
+        };
+        let location_style = if is_selected {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD | Modifier::REVERSED)
+        } else {
+            Style::default().fg(Color::Cyan)
+        };
+        lines.push(Line::from(vec![
+            Span::raw(if is_selected { "> " } else { "  " }),
+            Span::styled(location, location_style),
+        ]));
+
+        // 本文は 1 行目だけを表示し、続きがあれば省略を示す
+        let mut body_lines = draft.body.lines();
+        let first = body_lines.next().unwrap_or_default().to_string();
+        let more = if body_lines.next().is_some() {
+            " …"
+        } else {
+            ""
+        };
+        lines.push(Line::from(Span::styled(
+            format!("    {}{}", first, more),
+            Style::default().fg(Color::Gray),
+        )));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_list_lines_show_location_and_first_body_line() {
+        let drafts = vec![
+            DraftReviewComment {
+                path: "src/a.rs".to_string(),
+                body: "nit: rename".to_string(),
+                position: 2,
+                start_line: None,
+                line: 10,
+            },
+            DraftReviewComment {
+                path: "src/b.rs".to_string(),
+                body: "```suggestion\nlet x = 1;\n```".to_string(),
+                position: 5,
+                start_line: Some(3),
+                line: 4,
+            },
+        ];
+        let text: Vec<String> = build_list_lines(&drafts, 1).iter().map(line_text).collect();
+        assert_eq!(
+            text,
+            vec![
+                "  src/a.rs:10",
+                "    nit: rename",
+                "> src/b.rs:3-4",
+                "    ```suggestion …",
+            ]
+        );
+    }
+}
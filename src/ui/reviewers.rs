@@ -0,0 +1,19 @@
+use ratatui::Frame;
+
+use super::picker;
+use crate::app::{App, PeoplePickerState};
+
+/// レビュー依頼・アサインのピッカー
+pub fn render_picker(frame: &mut Frame, app: &App, state: &PeoplePickerState, focused: bool) {
+    let title = format!(
+        "{} - Enter: toggle, Tab: switch to {}, Esc: close",
+        state.target.title(),
+        state.target.other().title().to_lowercase()
+    );
+    let placeholder = match (&state.people, &state.error) {
+        (_, Some(error)) => format!("Failed to load users: {}", error),
+        (None, None) => format!("{} Loading users...", app.spinner_char()),
+        (Some(_), None) => "No users found".to_string(),
+    };
+    picker::render_popup(frame, &state.picker, &title, &placeholder, focused);
+}
@@ -3,17 +3,18 @@ use ratatui::{
     style::{Color, Style},
     text::{Line, Span},
     widgets::{
-        Block, Borders, List, ListItem, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
+        Block, Borders, List, ListState, Paragraph, Scrollbar, ScrollbarOrientation,
         ScrollbarState, Wrap,
     },
     Frame,
 };
 
-use super::common::render_rally_status_bar;
+use super::common::{render_rally_status_bar, scope_suffix};
 use super::diff_view;
-use super::file_list::build_file_list_items;
+use super::file_list::{build_sorted_file_list_items, sort_title_note};
+use super::markdown;
+use super::palette::DiffPalette;
 use crate::app::{App, AppState, DataState};
-use crate::github::ChangedFile;
 
 pub fn render(frame: &mut Frame, app: &mut App) {
     let has_rally = app.has_background_rally();
@@ -92,6 +93,7 @@ fn render_file_list_pane(
             None => "PR".to_string(),
         },
     };
+    let pr_info = format!("{}{}", pr_info, scope_suffix(app));
 
     let header = Paragraph::new(pr_info).block(
         Block::default()
@@ -102,8 +104,8 @@ fn render_file_list_pane(
     frame.render_widget(header, chunks[0]);
 
     // File list
-    let files = app.files();
-    let total_files = files.len();
+    let total_files = app.files().len();
+    let sort_note = sort_title_note(app);
 
     if let Some(ref filter) = app.file_list_filter {
         if filter.matched_indices.is_empty() {
@@ -114,29 +116,33 @@ fn render_file_list_pane(
                     Block::default()
                         .borders(Borders::ALL)
                         .border_style(Style::default().fg(border_color))
-                        .title(format!("Files (0/{})", total_files)),
+                        .title(format!("Files (0/{}){}", total_files, sort_note)),
                 );
             frame.render_widget(empty, chunks[1]);
         } else {
-            let filtered: Vec<&ChangedFile> =
-                filter.matched_indices.iter().map(|&i| &files[i]).collect();
             let display_selected = filter.selected.unwrap_or(0);
-            let display_count = filtered.len();
-
-            let items = build_file_list_items_ref(&filtered, display_selected);
+            let display_count = filter.matched_indices.len();
+            let (items, selected_row) = build_sorted_file_list_items(
+                app,
+                &filter.matched_indices,
+                filter.matched_indices.get(display_selected).copied(),
+            );
 
             let list = List::new(items)
                 .block(
                     Block::default()
                         .borders(Borders::ALL)
                         .border_style(Style::default().fg(border_color))
-                        .title(format!("Files ({}/{})", display_count, total_files)),
+                        .title(format!(
+                            "Files ({}/{}){}",
+                            display_count, total_files, sort_note
+                        )),
                 )
                 .highlight_style(Style::default().bg(Color::DarkGray));
 
             let mut list_state = ListState::default()
                 .with_offset(app.file_list_scroll_offset)
-                .with_selected(Some(display_selected));
+                .with_selected(selected_row);
 
             frame.render_stateful_widget(list, chunks[1], &mut list_state);
 
@@ -161,27 +167,26 @@ fn render_file_list_pane(
             }
         }
     } else {
-        let items = build_file_list_items(files, app.selected_file);
+        let (items, selected_row) =
+            build_sorted_file_list_items(app, app.file_order(), Some(app.selected_file));
+        let display_selected = app.file_display_position(app.selected_file);
 
         let list = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .border_style(Style::default().fg(border_color))
-                    .title(format!("Files ({})", total_files)),
+                    .title(format!("Files ({}){}", total_files, sort_note)),
             )
             .highlight_style(Style::default().bg(Color::DarkGray));
 
         let mut list_state = ListState::default()
             .with_offset(app.file_list_scroll_offset)
-            .with_selected(Some(app.selected_file));
+            .with_selected(selected_row);
 
         frame.render_stateful_widget(list, chunks[1], &mut list_state);
 
         app.file_list_scroll_offset = list_state.offset();
-        if let Some(sel) = list_state.selected() {
-            app.selected_file = sel;
-        }
 
         if total_files > 1 {
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
@@ -189,7 +194,7 @@ fn render_file_list_pane(
                 .end_symbol(Some("▼"));
 
             let mut scrollbar_state =
-                ScrollbarState::new(total_files.saturating_sub(1)).position(app.selected_file);
+                ScrollbarState::new(total_files.saturating_sub(1)).position(display_selected);
 
             frame.render_stateful_widget(
                 scrollbar,
@@ -229,7 +234,7 @@ fn render_file_list_pane(
         if app.file_list_filter.is_some() {
             "j/k/↑↓: move | Esc: clear filter | Enter/→/l: diff | ←/h/q: back"
         } else {
-            "j/k/↑↓: move | Space /: filter | Enter/→/l: diff | O: browser | ←/h/q: back"
+            "j/k/↑↓: move | Space /: filter | Ctrl-f: find | Enter/→/l: diff | O: browser | ←/h/q: back"
         }
     } else {
         "←/h: focus files"
@@ -242,58 +247,6 @@ fn render_file_list_pane(
     frame.render_widget(footer, chunks[next_chunk]);
 }
 
-fn build_file_list_items_ref<'a>(files: &[&'a ChangedFile], selected: usize) -> Vec<ListItem<'a>> {
-    use ratatui::style::Modifier;
-
-    files
-        .iter()
-        .enumerate()
-        .map(|(i, file)| {
-            let is_selected = i == selected;
-            let style = if is_selected {
-                Style::default()
-                    .fg(Color::Yellow)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
-            };
-
-            let status_color = match file.status.as_str() {
-                "added" => Color::Green,
-                "removed" => Color::Red,
-                "modified" => Color::Yellow,
-                "copied" => Color::Cyan,
-                _ => Color::White,
-            };
-
-            let status_char = match file.status.as_str() {
-                "added" => 'A',
-                "removed" => 'D',
-                "modified" => 'M',
-                "renamed" => 'R',
-                "copied" => 'C',
-                _ => '?',
-            };
-
-            let line = Line::from(vec![
-                Span::styled(
-                    format!("[{}] ", status_char),
-                    Style::default().fg(status_color),
-                ),
-                if file.viewed {
-                    Span::styled("✓ ", Style::default().fg(Color::Green))
-                } else {
-                    Span::raw("  ")
-                },
-                Span::styled(&file.filename, style),
-                Span::raw(format!(" +{} -{}", file.additions, file.deletions)),
-            ]);
-
-            ListItem::new(line)
-        })
-        .collect()
-}
-
 fn render_diff_pane(frame: &mut Frame, app: &App, area: ratatui::layout::Rect, is_focused: bool) {
     let border_color = if is_focused {
         Color::Yellow
@@ -331,7 +284,10 @@ fn render_diff_pane_normal(
     render_diff_body(frame, app, chunks[1], border_color);
 
     // Footer
-    let footer_text = if is_focused {
+    let search_text = diff_view::search_footer_text(app).filter(|_| is_focused);
+    let footer_text = if let Some(ref text) = search_text {
+        text.as_str()
+    } else if is_focused {
         if app.is_local_mode() {
             "j/k/↑↓: scroll | M: markdown rich | →/l: fullscreen | ←/h: files | q: back"
         } else {
@@ -381,6 +337,8 @@ fn render_diff_pane_with_comments(
     // Inline comments
     let indices = app.get_comment_indices_at_current_line();
     let mut lines: Vec<Line> = vec![];
+    // スクロール計算（App::comment_panel_content_lines）と同じ幅で折り返す
+    let inner_width = chunks[2].width.saturating_sub(2) as usize;
 
     if indices.is_empty() {
         lines.push(Line::from(Span::styled(
@@ -402,7 +360,7 @@ fn render_diff_pane_with_comments(
 
             lines.push(Line::from(vec![
                 Span::styled(
-                    format!("@{}", comment.user.login),
+                    format!("@{}", app.anonymizer.user(&comment.user.login)),
                     Style::default().fg(Color::Cyan),
                 ),
                 Span::styled(
@@ -411,9 +369,8 @@ fn render_diff_pane_with_comments(
                 ),
             ]));
 
-            for line in comment.body.lines() {
-                lines.push(Line::from(line.to_string()));
-            }
+            let body = app.anonymizer.text(&comment.body);
+            lines.extend(markdown::render_body(&body, inner_width, None));
             lines.push(Line::from(""));
         }
     }
@@ -428,7 +385,6 @@ fn render_diff_pane_with_comments(
                 .border_style(Style::default().fg(Color::Yellow))
                 .title(title),
         )
-        .wrap(Wrap { trim: true })
         .scroll((app.comment_panel_scroll, 0));
     frame.render_widget(paragraph, chunks[2]);
 
@@ -467,9 +423,13 @@ fn render_diff_header(
         .files()
         .get(app.selected_file)
         .map(|file| {
+            let preview = app
+                .preview_label(app.selected_file)
+                .map(|label| format!(" [{}]", label))
+                .unwrap_or_default();
             format!(
-                "{} (+{} -{})",
-                file.filename, file.additions, file.deletions
+                "{} (+{} -{}){}",
+                file.filename, file.additions, file.deletions, preview
             )
         })
         .unwrap_or_else(|| "No file selected".to_string());
@@ -489,6 +449,18 @@ fn render_diff_body(
     area: ratatui::layout::Rect,
     border_color: Color,
 ) {
+    if let Some(lines) = diff_view::collapsed_vendored_lines(app) {
+        let collapsed = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(border_color)),
+        );
+        frame.render_widget(collapsed, area);
+        return;
+    }
+    let area = super::explain::render_beside(frame, app, area);
+    let area = super::manifest::render_summary_above(frame, app, area);
+
     let lines: Vec<Line> = if let Some(ref cache) = app.diff_cache {
         let visible_height = area.height.saturating_sub(2) as usize;
         let line_count = cache.lines.len();
@@ -499,14 +471,19 @@ fn render_diff_body(
             .multiline_selection
             .as_ref()
             .map(|s| (s.start(), s.end()));
-        diff_view::render_cached_lines(
+        let lines = diff_view::mark_undecodable(diff_view::render_cached_lines(
             cache,
             visible_start..visible_end,
             app.selected_line,
             &app.file_comment_lines,
-            app.config.diff.bg_color,
+            &DiffPalette::from_config(&app.config),
             multiline_range,
-        )
+        ));
+        let lines = match app.diff_search.as_ref().and_then(|s| s.highlight_query()) {
+            Some(query) => diff_view::highlight_search_matches(lines, &query),
+            None => lines,
+        };
+        diff_view::prepend_blame_gutter(app, lines, visible_start)
     } else {
         let file = app.files().get(app.selected_file);
         match file {
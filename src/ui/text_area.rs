@@ -8,9 +8,9 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use unicode_width::UnicodeWidthChar;
 
 use crate::keybinding::{event_to_keybinding, KeySequence, SequenceMatch, SequenceState};
+use crate::text_width;
 
 /// テキストエリアのキー入力結果
 pub enum TextAreaAction {
@@ -258,7 +258,9 @@ impl TextArea {
         frame.render_widget(paragraph, area);
 
         // カーソル表示（CJK文字の表示幅を考慮）
-        let cursor_x = area.x + 1 + self.cursor_display_width() as u16;
+        // 枠の内側に収める（長い行ではカーソルを右端に留める）
+        let max_x = area.x + area.width.saturating_sub(2);
+        let cursor_x = (area.x + 1 + self.cursor_display_width() as u16).min(max_x);
         let cursor_y = area.y + 1 + (self.cursor_row.saturating_sub(self.scroll_offset)) as u16;
         if cursor_y < area.y + area.height.saturating_sub(1) {
             frame.set_cursor_position((cursor_x, cursor_y));
@@ -377,11 +379,7 @@ impl TextArea {
 
     /// カーソル位置までの表示幅を計算する（CJK文字は2カラム幅）
     fn cursor_display_width(&self) -> usize {
-        let line = &self.lines[self.cursor_row];
-        line.chars()
-            .take(self.cursor_col)
-            .map(|c| UnicodeWidthChar::width(c).unwrap_or(0))
-            .sum()
+        text_width::prefix_width(&self.lines[self.cursor_row], self.cursor_col)
     }
 
     fn adjust_scroll(&mut self) {
@@ -0,0 +1,123 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use super::popup;
+use crate::app::{App, UserInfoState};
+
+/// 表示する CODEOWNER のファイル数の上限
+const MAX_OWNED_FILES: usize = 8;
+
+fn pr_state_color(state: &str) -> Color {
+    match state {
+        "OPEN" => Color::Green,
+        "MERGED" => Color::Magenta,
+        _ => Color::Red,
+    }
+}
+
+/// コメントの作成者の情報を描画
+pub fn render_popup(frame: &mut Frame, app: &App, state: &UserInfoState, focused: bool) {
+    let popup_area = popup::area(frame, 80, 20);
+    let anonymizer = &app.anonymizer;
+    let title = format!("@{} - Esc: close", anonymizer.user(&state.login));
+    let block = popup::block(title, focused);
+    let label = Style::default().fg(Color::DarkGray);
+    let heading = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+
+    let mut lines: Vec<Line> = Vec::new();
+    let Some(ref info) = state.info else {
+        let message = match state.error {
+            Some(ref error) => Span::styled(error.clone(), Style::default().fg(Color::Red)),
+            None => Span::raw(format!("{} Loading...", app.spinner_char())),
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(message))
+                .block(block)
+                .wrap(Wrap { trim: false }),
+            popup_area,
+        );
+        return;
+    };
+
+    // 匿名化中は仮名に置き換えられない実名や所属を伏せる
+    if !anonymizer.is_enabled() {
+        if let Some(ref name) = info.name {
+            lines.push(Line::from(vec![
+                Span::styled("Name     ", label),
+                Span::raw(name.clone()),
+            ]));
+        }
+        if let Some(ref company) = info.company {
+            lines.push(Line::from(vec![
+                Span::styled("Company  ", label),
+                Span::raw(company.clone()),
+            ]));
+        }
+    }
+    let role = match info.permission.as_deref() {
+        Some(permission) if info.is_maintainer() => Span::styled(
+            format!("{} (maintainer)", permission.to_lowercase()),
+            Style::default().fg(Color::Green),
+        ),
+        Some(permission) => Span::raw(permission.to_lowercase()),
+        None => Span::styled("unknown (needs push access to see)", label),
+    };
+    lines.push(Line::from(vec![Span::styled("Role     ", label), role]));
+
+    let owners_line = match state.owned_files {
+        None => Span::styled("no CODEOWNERS file", label),
+        Some(ref files) if files.is_empty() => Span::raw("none of the changed files"),
+        Some(ref files) => Span::styled(
+            format!("{} changed file(s)", files.len()),
+            Style::default().fg(Color::Green),
+        ),
+    };
+    lines.push(Line::from(vec![
+        Span::styled("Owns     ", label),
+        owners_line,
+    ]));
+    if let Some(ref files) = state.owned_files {
+        for path in files.iter().take(MAX_OWNED_FILES) {
+            lines.push(Line::from(Span::styled(
+                format!("         {}", path),
+                Style::default().fg(Color::Cyan),
+            )));
+        }
+        if files.len() > MAX_OWNED_FILES {
+            lines.push(Line::from(Span::styled(
+                format!("         ... and {} more", files.len() - MAX_OWNED_FILES),
+                label,
+            )));
+        }
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!("PRs in this repository ({})", info.pr_count),
+        heading,
+    )));
+    if info.recent_prs.is_empty() {
+        lines.push(Line::from(Span::styled("  None", label)));
+    }
+    for pr in &info.recent_prs {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  {:<7}", pr.state.to_lowercase()),
+                Style::default().fg(pr_state_color(&pr.state)),
+            ),
+            Span::styled(format!("#{} ", pr.number), Style::default().fg(Color::Cyan)),
+            Span::raw(anonymizer.text(&pr.title).into_owned()),
+        ]));
+    }
+
+    let panel = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(panel, popup_area);
+}
@@ -0,0 +1,127 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use super::popup;
+use crate::app::App;
+use crate::vendored::VendoredSummary;
+
+/// vendored 依存のバージョン変更とライセンスファイルの変更をまとめたパネル
+pub fn render_summary(frame: &mut Frame, app: &App, focused: bool) {
+    let panel_area = popup::area(frame, 90, 30);
+    let block = popup::block("Vendored dependencies - Esc: close", focused);
+    let summary = crate::vendored::summarize(app.files());
+    let panel = Paragraph::new(build_summary_lines(&summary))
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(panel, panel_area);
+}
+
+fn build_summary_lines(summary: &VendoredSummary) -> Vec<Line<'static>> {
+    let heading = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::from(Span::styled(
+        format!("Licence changes ({})", summary.license_changes.len()),
+        heading,
+    ))];
+    if summary.license_changes.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No licence files changed",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for change in &summary.license_changes {
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("  ⚠ {:<9}", change.status),
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            Span::raw(change.path.clone()),
+        ]));
+    }
+
+    lines.push(Line::from(""));
+    lines.push(Line::from(Span::styled(
+        format!(
+            "Vendored dependencies ({}, {} version change(s))",
+            summary.packages.len(),
+            summary.version_bumps()
+        ),
+        heading,
+    )));
+    if summary.packages.is_empty() {
+        lines.push(Line::from(Span::styled(
+            "  No vendored files changed",
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+    for package in &summary.packages {
+        let mut spans = vec![
+            Span::styled(
+                format!("  {}", package.root),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::styled(
+                format!(
+                    "  {} file(s) +{} -{}",
+                    package.files, package.additions, package.deletions
+                ),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ];
+        if let Some(change) = package.version_change() {
+            spans.push(Span::styled(
+                format!("  {}", change),
+                Style::default().fg(Color::Yellow),
+            ));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::vendored::{LicenseChange, VendoredPackage};
+
+    fn line_text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_summary_lines_list_licences_before_packages() {
+        let summary = VendoredSummary {
+            packages: vec![VendoredPackage {
+                root: "node_modules/left-pad".to_string(),
+                files: 2,
+                additions: 10,
+                deletions: 3,
+                old_version: Some("1.2.0".to_string()),
+                new_version: Some("1.3.0".to_string()),
+            }],
+            license_changes: vec![LicenseChange {
+                path: "node_modules/left-pad/LICENSE".to_string(),
+                status: "modified".to_string(),
+            }],
+        };
+        let text: Vec<String> = build_summary_lines(&summary)
+            .iter()
+            .map(line_text)
+            .collect();
+        assert_eq!(
+            text,
+            vec![
+                "Licence changes (1)",
+                "  ⚠ modified node_modules/left-pad/LICENSE",
+                "",
+                "Vendored dependencies (1, 1 version change(s))",
+                "  node_modules/left-pad  2 file(s) +10 -3  1.2.0 → 1.3.0",
+            ]
+        );
+    }
+}
@@ -0,0 +1,150 @@
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Paragraph, Wrap},
+    Frame,
+};
+
+use super::popup;
+use crate::anonymize::Anonymizer;
+use crate::app::ReviewSummary;
+use crate::github::ReviewAction;
+
+/// 項目ごとに表示する件数の上限
+const MAX_ITEMS: usize = 5;
+
+/// 承認・変更要求の前のまとめ。見直す項目を一覧して、そのまま送信するか選ばせる
+pub fn render_popup(
+    frame: &mut Frame,
+    summary: &ReviewSummary,
+    anonymizer: &Anonymizer,
+    focused: bool,
+) {
+    let popup_area = popup::area(frame, 90, 30);
+    let title = match summary.action {
+        ReviewAction::Approve => "Approve this PR?",
+        ReviewAction::RequestChanges => "Request changes on this PR?",
+        ReviewAction::Comment => "Submit this review?",
+    };
+    let block = popup::accent_block(title, Color::Yellow, focused);
+
+    let mut lines = vec![Line::from(format!("  {}", summary.summary()))];
+    lines.extend(build_item_lines(summary, anonymizer));
+    lines.push(Line::from(""));
+
+    let key_style = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let (confirm_keys, confirm_label) = match summary.action {
+        ReviewAction::Approve => ("  a/y", ": approve anyway"),
+        _ => ("  y", ": submit anyway"),
+    };
+    let review_target = if summary.unresolved_threads > 0 {
+        "open the comment list"
+    } else if !summary.drafts.is_empty() {
+        "open the drafts"
+    } else {
+        "open the first unviewed file"
+    };
+    lines.push(Line::from(vec![
+        Span::styled(confirm_keys, key_style),
+        Span::raw(confirm_label),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  r", key_style),
+        Span::raw(format!(": review them ({})", review_target)),
+    ]));
+    lines.push(Line::from(vec![
+        Span::styled("  Esc", key_style),
+        Span::raw(": cancel"),
+    ]));
+
+    let panel = Paragraph::new(lines)
+        .block(block)
+        .wrap(Wrap { trim: false });
+    frame.render_widget(panel, popup_area);
+}
+
+/// 下書き・未確認のファイル・未解決のスレッドの一覧（それぞれ先頭の数件）
+fn build_item_lines(summary: &ReviewSummary, anonymizer: &Anonymizer) -> Vec<Line<'static>> {
+    let heading = Style::default()
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD);
+    let location = Style::default().fg(Color::Cyan);
+    let dim = Style::default().fg(Color::DarkGray);
+    let mut lines = Vec::new();
+
+    if !summary.drafts.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  Pending comments ({}) - sent with the review",
+                summary.drafts.len()
+            ),
+            heading,
+        )));
+        for draft in summary.drafts.iter().take(MAX_ITEMS) {
+            lines.push(Line::from(vec![
+                Span::styled(format!("    {}:{}  ", draft.path, draft.line), location),
+                Span::raw(first_line(&draft.body)),
+            ]));
+        }
+        push_more(&mut lines, summary.drafts.len(), MAX_ITEMS, dim);
+    }
+
+    if !summary.unviewed_files.is_empty() {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  Unviewed files ({})", summary.unviewed_files.len()),
+            heading,
+        )));
+        for path in summary.unviewed_files.iter().take(MAX_ITEMS) {
+            lines.push(Line::from(Span::styled(format!("    {}", path), location)));
+        }
+        push_more(&mut lines, summary.unviewed_files.len(), MAX_ITEMS, dim);
+    }
+
+    if summary.unresolved_threads > 0 {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  Unresolved threads ({})", summary.unresolved_threads),
+            heading,
+        )));
+        for comment in summary.thread_heads.iter().take(MAX_ITEMS) {
+            let position = match comment.line {
+                Some(line) => format!("    {}:{}  ", comment.path, line),
+                None => format!("    {}  ", comment.path),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(position, location),
+                Span::styled(format!("@{} ", anonymizer.user(&comment.user.login)), dim),
+                Span::raw(first_line(&anonymizer.text(&comment.body))),
+            ]));
+        }
+        // 読み込んでいないスレッドも含めた残りの件数
+        let shown = summary.thread_heads.len().min(MAX_ITEMS);
+        push_more(&mut lines, summary.unresolved_threads, shown, dim);
+    }
+    lines
+}
+
+/// 本文の 1 行目。続きがあれば省略を示す
+fn first_line(body: &str) -> String {
+    let mut body_lines = body.lines();
+    let first = body_lines.next().unwrap_or_default();
+    if body_lines.next().is_some() {
+        format!("{} …", first)
+    } else {
+        first.to_string()
+    }
+}
+
+/// `total` 件のうち先頭の `shown` 件だけ表示したときの残りの件数
+fn push_more(lines: &mut Vec<Line<'static>>, total: usize, shown: usize, style: Style) {
+    if total > shown {
+        lines.push(Line::from(Span::styled(
+            format!("    ... and {} more", total - shown),
+            style,
+        )));
+    }
+}
@@ -0,0 +1,347 @@
+//! vendored 依存（`vendor/`・`third_party/`・コミットされた `node_modules/`）の検出と要約
+//!
+//! vendored 依存の diff は量が多いだけで読む価値が低いので既定で折りたたみ、
+//! 代わりに依存ごとのバージョン変更と、見落としてはいけないライセンスファイルの変更をまとめる。
+
+use crate::diff::{classify_line, LineType};
+use crate::github::ChangedFile;
+
+/// vendored 依存を置くディレクトリ名
+const VENDOR_DIRS: &[&str] = &[
+    "vendor",
+    "vendored",
+    "third_party",
+    "third-party",
+    "node_modules",
+];
+
+/// ライセンスファイルとみなすファイル名の接頭辞（大文字小文字は区別しない）
+const LICENSE_PREFIXES: &[&str] = &["LICENSE", "LICENCE", "COPYING", "NOTICE", "UNLICENSE"];
+
+/// Go の `vendor/modules.txt`（依存モジュールとバージョンの一覧）
+const GO_MODULES_TXT: &str = "modules.txt";
+
+/// vendored 依存 1 つ分の変更
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VendoredPackage {
+    /// 依存のディレクトリ（例: `vendor/github.com/foo/bar`, `node_modules/@scope/pkg`）
+    pub root: String,
+    pub files: usize,
+    pub additions: u32,
+    pub deletions: u32,
+    pub old_version: Option<String>,
+    pub new_version: Option<String>,
+}
+
+impl VendoredPackage {
+    fn new(root: &str) -> Self {
+        Self {
+            root: root.to_string(),
+            ..Self::default()
+        }
+    }
+
+    /// `1.2.0 → 1.3.0` 形式のバージョン変更（変更がなければ None）
+    pub fn version_change(&self) -> Option<String> {
+        match (&self.old_version, &self.new_version) {
+            (Some(old), Some(new)) if old != new => Some(format!("{} → {}", old, new)),
+            (None, Some(new)) => Some(format!("added {}", new)),
+            (Some(old), None) => Some(format!("removed {}", old)),
+            _ => None,
+        }
+    }
+}
+
+/// ライセンスファイルの変更
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LicenseChange {
+    pub path: String,
+    /// GitHub のファイルステータス（`added` / `modified` / `removed` など）
+    pub status: String,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VendoredSummary {
+    pub packages: Vec<VendoredPackage>,
+    /// vendored 依存かどうかにかかわらず、すべてのライセンスファイルの変更
+    pub license_changes: Vec<LicenseChange>,
+}
+
+impl VendoredSummary {
+    pub fn is_empty(&self) -> bool {
+        self.packages.is_empty() && self.license_changes.is_empty()
+    }
+
+    pub fn version_bumps(&self) -> usize {
+        self.packages
+            .iter()
+            .filter(|p| p.version_change().is_some())
+            .count()
+    }
+}
+
+/// パスが属する vendored 依存のディレクトリ。vendored でなければ None
+///
+/// 入れ子の `node_modules` は最も内側の依存を返す。
+/// vendor ディレクトリ直下のファイル（`vendor/modules.txt` など）は vendor ディレクトリ自体を返す。
+pub fn package_root(path: &str) -> Option<String> {
+    let parts: Vec<&str> = path.split('/').collect();
+    let vendor_index = parts[..parts.len() - 1]
+        .iter()
+        .rposition(|part| VENDOR_DIRS.contains(part))?;
+    let rest = &parts[vendor_index + 1..parts.len() - 1];
+    let depth = match rest.first() {
+        None => 0,
+        // npm のスコープ付きパッケージ
+        Some(first) if first.starts_with('@') => 2,
+        // Go のモジュールパス（github.com/owner/repo）
+        Some(first) if first.contains('.') && rest.len() >= 3 => 3,
+        Some(_) => 1,
+    };
+    Some(parts[..=vendor_index + depth.min(rest.len())].join("/"))
+}
+
+pub fn is_vendored(path: &str) -> bool {
+    package_root(path).is_some()
+}
+
+pub fn is_license_file(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path).to_ascii_uppercase();
+    LICENSE_PREFIXES
+        .iter()
+        .any(|prefix| name.starts_with(prefix))
+}
+
+/// 変更ファイルから vendored 依存ごとの要約とライセンスファイルの変更を集める
+pub fn summarize(files: &[ChangedFile]) -> VendoredSummary {
+    let mut summary = VendoredSummary::default();
+    for file in files {
+        if is_license_file(&file.filename) {
+            summary.license_changes.push(LicenseChange {
+                path: file.filename.clone(),
+                status: file.status.clone(),
+            });
+        }
+        let Some(root) = package_root(&file.filename) else {
+            continue;
+        };
+        let package = package_entry(&mut summary.packages, &root);
+        package.files += 1;
+        package.additions += file.additions;
+        package.deletions += file.deletions;
+
+        let Some(ref patch) = file.patch else {
+            continue;
+        };
+        let name = &file.filename[root.len() + 1..];
+        if name == GO_MODULES_TXT {
+            for (module, old, new) in go_module_versions(patch) {
+                let package = package_entry(&mut summary.packages, &format!("{}/{}", root, module));
+                package.old_version = package.old_version.take().or(old);
+                package.new_version = package.new_version.take().or(new);
+            }
+        } else if let Some((old, new)) = manifest_versions(name, patch) {
+            package.old_version = old;
+            package.new_version = new;
+        }
+    }
+    // modules.txt だけが変わった vendor ディレクトリ自体は依存として数えない
+    summary
+        .packages
+        .retain(|p| (p.files > 0 && !is_vendor_dir(&p.root)) || p.version_change().is_some());
+    summary
+}
+
+fn package_entry<'a>(
+    packages: &'a mut Vec<VendoredPackage>,
+    root: &str,
+) -> &'a mut VendoredPackage {
+    let index = match packages.iter().position(|p| p.root == root) {
+        Some(index) => index,
+        None => {
+            packages.push(VendoredPackage::new(root));
+            packages.len() - 1
+        }
+    };
+    &mut packages[index]
+}
+
+fn is_vendor_dir(root: &str) -> bool {
+    VENDOR_DIRS.contains(&root.rsplit('/').next().unwrap_or(root))
+}
+
+/// 依存直下のマニフェスト（package.json / Cargo.toml / pyproject.toml / VERSION）から
+/// 削除行・追加行のバージョンを読む
+fn manifest_versions(name: &str, patch: &str) -> Option<(Option<String>, Option<String>)> {
+    let extract: fn(&str) -> Option<String> = match name {
+        "package.json" => json_version,
+        "Cargo.toml" | "pyproject.toml" => toml_version,
+        "VERSION" => |line: &str| Some(line.trim().to_string()).filter(|v| !v.is_empty()),
+        _ => return None,
+    };
+    let mut old = None;
+    let mut new = None;
+    for line in patch.lines() {
+        match classify_line(line) {
+            (LineType::Removed, content) if old.is_none() => old = extract(content),
+            (LineType::Added, content) if new.is_none() => new = extract(content),
+            _ => {}
+        }
+    }
+    Some((old, new)).filter(|(old, new)| old.is_some() || new.is_some())
+}
+
+/// `"version": "1.2.3",`
+fn json_version(line: &str) -> Option<String> {
+    let rest = line.trim().strip_prefix("\"version\"")?;
+    let value = rest.trim_start().strip_prefix(':')?.trim();
+    Some(value.trim_end_matches(',').trim_matches('"').to_string())
+}
+
+/// `version = "1.2.3"`
+fn toml_version(line: &str) -> Option<String> {
+    let (key, value) = line.split_once('=')?;
+    if key.trim() != "version" {
+        return None;
+    }
+    Some(value.trim().trim_matches('"').to_string())
+}
+
+/// `vendor/modules.txt` の `# github.com/foo/bar v1.2.3` 行からモジュールごとの新旧バージョンを読む
+fn go_module_versions(patch: &str) -> Vec<(String, Option<String>, Option<String>)> {
+    let mut modules: Vec<(String, Option<String>, Option<String>)> = Vec::new();
+    for line in patch.lines() {
+        let (line_type, content) = classify_line(line);
+        if !matches!(line_type, LineType::Removed | LineType::Added) {
+            continue;
+        }
+        let Some(rest) = content.strip_prefix("# ") else {
+            continue;
+        };
+        let mut fields = rest.split_whitespace();
+        let (Some(module), Some(version)) = (fields.next(), fields.next()) else {
+            continue;
+        };
+        let index = match modules.iter().position(|(m, _, _)| m == module) {
+            Some(index) => index,
+            None => {
+                modules.push((module.to_string(), None, None));
+                modules.len() - 1
+            }
+        };
+        let slot = if line_type == LineType::Removed {
+            &mut modules[index].1
+        } else {
+            &mut modules[index].2
+        };
+        *slot = Some(version.to_string());
+    }
+    modules
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(filename: &str, status: &str, patch: Option<&str>) -> ChangedFile {
+        ChangedFile {
+            filename: filename.to_string(),
+            status: status.to_string(),
+            additions: 1,
+            deletions: 1,
+            patch: patch.map(str::to_string),
+            viewed: false,
+        }
+    }
+
+    #[test]
+    fn test_package_root() {
+        assert_eq!(
+            package_root("vendor/github.com/foo/bar/baz/x.go").as_deref(),
+            Some("vendor/github.com/foo/bar")
+        );
+        assert_eq!(
+            package_root("web/node_modules/@scope/pkg/index.js").as_deref(),
+            Some("web/node_modules/@scope/pkg")
+        );
+        assert_eq!(
+            package_root("node_modules/a/node_modules/b/lib/b.js").as_deref(),
+            Some("node_modules/a/node_modules/b")
+        );
+        assert_eq!(
+            package_root("third_party/zlib/inflate.c").as_deref(),
+            Some("third_party/zlib")
+        );
+        assert_eq!(
+            package_root("vendor/modules.txt").as_deref(),
+            Some("vendor")
+        );
+        assert_eq!(package_root("src/vendor.rs"), None);
+        assert_eq!(package_root("vendor"), None);
+    }
+
+    #[test]
+    fn test_is_license_file() {
+        assert!(is_license_file("LICENSE"));
+        assert!(is_license_file("vendor/foo/license.md"));
+        assert!(is_license_file("third_party/zlib/COPYING"));
+        assert!(is_license_file("node_modules/x/LICENCE-MIT"));
+        assert!(!is_license_file("src/licensing.rs"));
+    }
+
+    #[test]
+    fn test_summarize_version_bumps_and_licenses() {
+        let files = vec![
+            file(
+                "node_modules/left-pad/package.json",
+                "modified",
+                Some("@@ -1,3 +1,3 @@\n {\n-  \"version\": \"1.2.0\",\n+  \"version\": \"1.3.0\",\n"),
+            ),
+            file("node_modules/left-pad/index.js", "modified", Some("@@ -1 +1 @@\n-a\n+b\n")),
+            file("node_modules/left-pad/LICENSE", "modified", Some("@@ -1 +1 @@\n-MIT\n+GPL\n")),
+            file(
+                "vendor/modules.txt",
+                "modified",
+                Some("@@ -1,2 +1,2 @@\n-# github.com/foo/bar v1.0.0\n+# github.com/foo/bar v1.1.0\n ## explicit\n"),
+            ),
+            file("vendor/github.com/foo/bar/bar.go", "modified", None),
+            file("LICENSE", "modified", None),
+            file("src/main.rs", "modified", None),
+        ];
+        let summary = summarize(&files);
+
+        assert_eq!(summary.packages.len(), 2);
+        let left_pad = &summary.packages[0];
+        assert_eq!(left_pad.root, "node_modules/left-pad");
+        assert_eq!(left_pad.files, 3);
+        assert_eq!(left_pad.version_change().as_deref(), Some("1.2.0 → 1.3.0"));
+        let bar = &summary.packages[1];
+        assert_eq!(bar.root, "vendor/github.com/foo/bar");
+        assert_eq!(bar.files, 1);
+        assert_eq!(bar.version_change().as_deref(), Some("v1.0.0 → v1.1.0"));
+        assert_eq!(summary.version_bumps(), 2);
+
+        let licenses: Vec<&str> = summary
+            .license_changes
+            .iter()
+            .map(|c| c.path.as_str())
+            .collect();
+        assert_eq!(licenses, vec!["node_modules/left-pad/LICENSE", "LICENSE"]);
+    }
+
+    #[test]
+    fn test_manifest_versions() {
+        assert_eq!(
+            manifest_versions(
+                "Cargo.toml",
+                "@@ -1 +1 @@\n-version = \"0.1.0\"\n+version = \"0.2.0\"\n"
+            ),
+            Some((Some("0.1.0".to_string()), Some("0.2.0".to_string())))
+        );
+        assert_eq!(
+            manifest_versions("Cargo.toml", "@@ -1 +1 @@\n-serde = { version = \"1\" }\n"),
+            None
+        );
+        assert_eq!(manifest_versions("index.js", "@@ -1 +1 @@\n-a\n+b\n"), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Integration tests for the library API.
+//!
+//! These only use the crate-root re-exports and the non-TUI modules, and must
+//! pass with `cargo test --no-default-features`.
+
+use std::path::Path;
+use std::process::Command;
+
+use octorus::{
+    fetch_local_diff, fetch_local_diffs_batched, get_line_info, line_number_to_position,
+    parse_unified_diff, DataLoadResult, LineType, PrCacheKey, PrData, SessionCache,
+};
+use tokio::sync::mpsc;
+
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "test")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "test")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .status()
+        .expect("git should be installed");
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// A repository with one modified file and one untracked file
+fn working_tree() -> tempfile::TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    git(dir.path(), &["init", "-q"]);
+    std::fs::write(dir.path().join("lib.rs"), "fn a() {}\nfn b() {}\n").unwrap();
+    git(dir.path(), &["add", "."]);
+    git(dir.path(), &["commit", "-q", "-m", "init"]);
+    std::fs::write(dir.path().join("lib.rs"), "fn a() {}\nfn c() {}\n").unwrap();
+    std::fs::write(dir.path().join("new.rs"), "fn d() {}\n").unwrap();
+    dir
+}
+
+#[tokio::test]
+async fn test_load_local_diff_and_cache_it() {
+    let dir = working_tree();
+    let working_dir = Some(dir.path().to_string_lossy().into_owned());
+
+    // Phase 1: the file list arrives without patches
+    let (tx, mut rx) = mpsc::channel(1);
+    fetch_local_diff("local".to_string(), working_dir.clone(), tx).await;
+    let (pr, mut files) = match rx.recv().await.unwrap() {
+        DataLoadResult::Success { pr, files } => (pr, files),
+        DataLoadResult::Comments { .. } => panic!("local diffs have no comments"),
+        DataLoadResult::FilePage(_) => panic!("local diffs are sent at once"),
+        DataLoadResult::Error(e) => panic!("load failed: {}", e),
+    };
+    files.sort_by(|a, b| a.filename.cmp(&b.filename));
+    let names: Vec<&str> = files.iter().map(|f| f.filename.as_str()).collect();
+    assert_eq!(names, ["lib.rs", "new.rs"]);
+    assert!(files.iter().all(|f| f.patch.is_none()));
+
+    // Phase 2: patches are loaded in batches
+    let (tx, mut rx) = mpsc::channel(4);
+    fetch_local_diffs_batched(
+        working_dir,
+        vec!["lib.rs".to_string()],
+        vec!["new.rs".to_string()],
+        10,
+        tx,
+    )
+    .await;
+    while let Some(results) = rx.recv().await {
+        for result in results {
+            let file = files
+                .iter_mut()
+                .find(|f| f.filename == result.filename)
+                .unwrap();
+            file.patch = result.patch;
+        }
+    }
+
+    let patch = files[0].patch.as_deref().unwrap();
+    let index = patch.lines().position(|l| l == "+fn c() {}").unwrap();
+    let info = get_line_info(patch, index).unwrap();
+    assert_eq!(info.line_type, LineType::Added);
+    assert_eq!(info.new_line_number, Some(2));
+    // Review comment positions count from the first hunk header, not the file headers
+    let hunk = patch.lines().position(|l| l.starts_with("@@")).unwrap();
+    assert_eq!(
+        line_number_to_position(patch, 2),
+        Some((index - hunk) as u32)
+    );
+    assert!(files[1].patch.as_deref().unwrap().contains("+fn d() {}"));
+
+    let mut cache = SessionCache::new();
+    let key = PrCacheKey {
+        repo: "local".to_string(),
+        pr_number: 0,
+    };
+    let pr_updated_at = pr.updated_at.clone();
+    cache.put_pr_data(
+        key.clone(),
+        PrData {
+            pr,
+            files,
+            pr_updated_at,
+        },
+    );
+    let cached = cache.get_pr_data(&key).unwrap();
+    assert_eq!(cached.files.len(), 2);
+    assert_eq!(cached.pr.head.ref_name, "HEAD");
+}
+
+#[test]
+fn test_parse_multi_file_diff() {
+    let diff = "\
+diff --git a/a.rs b/a.rs
+--- a/a.rs
++++ b/a.rs
+@@ -1 +1 @@
+-old
++new
+diff --git a/b.rs b/b.rs
+new file mode 100644
+--- /dev/null
++++ b/b.rs
+@@ -0,0 +1 @@
++added
+";
+    let patches = parse_unified_diff(diff);
+    assert_eq!(patches.len(), 2);
+    assert!(patches["a.rs"].contains("+new"));
+    assert!(patches["b.rs"].ends_with("+added"));
+}